@@ -117,10 +117,102 @@ client_request_definitions! {
         params: v2::ThreadListParams,
         response: v2::ThreadListResponse,
     },
+    ThreadTurnsList => "thread/turns/list" {
+        params: v2::ThreadTurnsListParams,
+        response: v2::ThreadTurnsListResponse,
+    },
+    ThreadRecover => "thread/recover" {
+        params: v2::ThreadRecoverParams,
+        response: v2::ThreadRecoverResponse,
+    },
+    ThreadDuelStart => "thread/duel/start" {
+        params: v2::ThreadDuelStartParams,
+        response: v2::ThreadDuelStartResponse,
+    },
+    ThreadDuelPick => "thread/duel/pick" {
+        params: v2::ThreadDuelPickParams,
+        response: v2::ThreadDuelPickResponse,
+    },
+    ThreadFork => "thread/fork" {
+        params: v2::ThreadForkParams,
+        response: v2::ThreadForkResponse,
+    },
+    ThreadMerge => "thread/merge" {
+        params: v2::ThreadMergeParams,
+        response: v2::ThreadMergeResponse,
+    },
+    ThreadRegenerate => "thread/regenerate" {
+        params: v2::ThreadRegenerateParams,
+        response: v2::ThreadRegenerateResponse,
+    },
+    ThreadEditAndResend => "thread/editAndResend" {
+        params: v2::ThreadEditAndResendParams,
+        response: v2::ThreadEditAndResendResponse,
+    },
+    ItemVariantsList => "item/variants/list" {
+        params: v2::ItemVariantsListParams,
+        response: v2::ItemVariantsListResponse,
+    },
+    ThreadSetEnv => "thread/setEnv" {
+        params: v2::ThreadSetEnvParams,
+        response: v2::ThreadSetEnvResponse,
+    },
+    ThreadSetWebhook => "thread/setWebhook" {
+        params: v2::ThreadSetWebhookParams,
+        response: v2::ThreadSetWebhookResponse,
+    },
+    StorageReport => "storage/report" {
+        params: v2::StorageReportParams,
+        response: v2::StorageReportResponse,
+    },
+    StorageCleanup => "storage/cleanup" {
+        params: v2::StorageCleanupParams,
+        response: v2::StorageCleanupResponse,
+    },
+    RolloutList => "rollout/list" {
+        params: v2::RolloutListParams,
+        response: v2::RolloutListResponse,
+    },
+    RolloutDelete => "rollout/delete" {
+        params: v2::RolloutDeleteParams,
+        response: v2::RolloutDeleteResponse,
+    },
+    RolloutArchive => "rollout/archive" {
+        params: v2::RolloutArchiveParams,
+        response: v2::RolloutArchiveResponse,
+    },
+    ThreadSuggestionAccept => "thread/suggestion/accept" {
+        params: v2::ThreadSuggestionAcceptParams,
+        response: v2::ThreadSuggestionAcceptResponse,
+    },
     ThreadCompact => "thread/compact" {
         params: v2::ThreadCompactParams,
         response: v2::ThreadCompactResponse,
     },
+    ThreadPlanRead => "thread/plan/read" {
+        params: v2::ThreadPlanReadParams,
+        response: v2::ThreadPlanReadResponse,
+    },
+    ThreadPlanSetStepStatus => "thread/plan/setStepStatus" {
+        params: v2::ThreadPlanSetStepStatusParams,
+        response: v2::ThreadPlanSetStepStatusResponse,
+    },
+    ThreadPlanSetMode => "thread/plan/setMode" {
+        params: v2::ThreadPlanSetModeParams,
+        response: v2::ThreadPlanSetModeResponse,
+    },
+    ThreadPlanApprove => "thread/plan/approve" {
+        params: v2::ThreadPlanApproveParams,
+        response: v2::ThreadPlanApproveResponse,
+    },
+    ThreadPlanSendFeedback => "thread/plan/sendFeedback" {
+        params: v2::ThreadPlanSendFeedbackParams,
+        response: v2::ThreadPlanSendFeedbackResponse,
+    },
+    ThreadEventsReplay => "thread/events/replay" {
+        params: v2::ThreadEventsReplayParams,
+        response: v2::ThreadEventsReplayResponse,
+    },
     TurnStart => "turn/start" {
         params: v2::TurnStartParams,
         response: v2::TurnStartResponse,
@@ -129,6 +221,78 @@ client_request_definitions! {
         params: v2::TurnInterruptParams,
         response: v2::TurnInterruptResponse,
     },
+    ItemCancel => "item/cancel" {
+        params: v2::ItemCancelParams,
+        response: v2::ItemCancelResponse,
+    },
+    SubagentCancel => "subagent/cancel" {
+        params: v2::SubagentCancelParams,
+        response: v2::SubagentCancelResponse,
+    },
+    ThreadSetFocus => "thread/setFocus" {
+        params: v2::ThreadSetFocusParams,
+        response: v2::ThreadSetFocusResponse,
+    },
+    McpServerRestart => "mcpServer/restart" {
+        params: v2::McpServerRestartParams,
+        response: v2::McpServerRestartResponse,
+    },
+    TurnRevert => "turn/revert" {
+        params: v2::TurnRevertParams,
+        response: v2::TurnRevertResponse,
+    },
+    TurnRevertFile => "turn/revertFile" {
+        params: v2::TurnRevertFileParams,
+        response: v2::TurnRevertResponse,
+    },
+    FileTree => "file/tree" {
+        params: v2::FileTreeParams,
+        response: v2::FileTreeResponse,
+    },
+    FileRead => "file/read" {
+        params: v2::FileReadParams,
+        response: v2::FileReadResponse,
+    },
+    FileWatch => "file/watch" {
+        params: v2::FileWatchParams,
+        response: v2::FileWatchResponse,
+    },
+    FileUnwatch => "file/unwatch" {
+        params: v2::FileUnwatchParams,
+        response: v2::FileUnwatchResponse,
+    },
+    WorktreeList => "worktree/list" {
+        params: v2::WorktreeListParams,
+        response: v2::WorktreeListResponse,
+    },
+    WorktreeRemove => "worktree/remove" {
+        params: v2::WorktreeRemoveParams,
+        response: v2::WorktreeRemoveResponse,
+    },
+    WorktreeMerge => "worktree/merge" {
+        params: v2::WorktreeMergeParams,
+        response: v2::WorktreeMergeResponse,
+    },
+    GitStatus => "git/status" {
+        params: v2::GitStatusParams,
+        response: v2::GitStatusResponse,
+    },
+    GitStage => "git/stage" {
+        params: v2::GitStageParams,
+        response: v2::GitStageResponse,
+    },
+    GitCommit => "git/commit" {
+        params: v2::GitCommitParams,
+        response: v2::GitCommitResponse,
+    },
+    GitBranch => "git/branch" {
+        params: v2::GitBranchParams,
+        response: v2::GitBranchResponse,
+    },
+    RepositoryOnboard => "repository/onboard" {
+        params: v2::RepositoryOnboardParams,
+        response: v2::RepositoryOnboardResponse,
+    },
     ReviewStart => "review/start" {
         params: v2::ReviewStartParams,
         response: v2::TurnStartResponse,
@@ -159,6 +323,31 @@ client_request_definitions! {
         response: v2::GetAccountRateLimitsResponse,
     },
 
+    DiagnosticsRead => "diagnostics/read" {
+        params: #[ts(type = "undefined")] #[serde(skip_serializing_if = "Option::is_none")] Option<()>,
+        response: v2::DiagnosticsReadResponse,
+    },
+
+    DoctorRead => "doctor/read" {
+        params: #[ts(type = "undefined")] #[serde(skip_serializing_if = "Option::is_none")] Option<()>,
+        response: v2::DoctorReadResponse,
+    },
+
+    CrashReportList => "crashReport/list" {
+        params: #[ts(type = "undefined")] #[serde(skip_serializing_if = "Option::is_none")] Option<()>,
+        response: v2::CrashReportListResponse,
+    },
+
+    CrashReportExport => "crashReport/export" {
+        params: v2::CrashReportExportParams,
+        response: v2::CrashReportExportResponse,
+    },
+
+    AudioTranscribe => "audio/transcribe" {
+        params: v2::AudioTranscribeParams,
+        response: v2::AudioTranscribeResponse,
+    },
+
     FeedbackUpload => "feedback/upload" {
         params: v2::FeedbackUploadParams,
         response: v2::FeedbackUploadResponse,
@@ -176,6 +365,18 @@ client_request_definitions! {
         params: v2::ConfigBatchWriteParams,
         response: v2::ConfigWriteResponse,
     },
+    ConfigReload => "config/reload" {
+        params: v2::ConfigReloadParams,
+        response: v2::ConfigReloadResponse,
+    },
+    ProjectDefaultsRead => "project/defaults/read" {
+        params: v2::ProjectDefaultsReadParams,
+        response: v2::ProjectDefaultsReadResponse,
+    },
+    ProjectDefaultsWrite => "project/defaults/write" {
+        params: v2::ProjectDefaultsWriteParams,
+        response: v2::ProjectDefaultsWriteResponse,
+    },
 
     GetAccount => "account/read" {
         params: v2::GetAccountParams,
@@ -458,6 +659,13 @@ server_request_definitions! {
         response: v2::FileChangeRequestApprovalResponse,
     },
 
+    /// Sent when a turn crosses the `max_session_cost_usd` budget. The turn
+    /// waits for the response and stops unless it is accepted.
+    CostLimitRequestApproval => "turn/costLimit/requestApproval" {
+        params: v2::CostLimitRequestApprovalParams,
+        response: v2::CostLimitRequestApprovalResponse,
+    },
+
     /// DEPRECATED APIs below
     /// Request to approve a patch.
     /// This request is used for Turns started via the legacy APIs (i.e. SendUserTurn, SendUserMessage).
@@ -503,9 +711,13 @@ server_notification_definitions! {
     Error => "error" (v2::ErrorNotification),
     ThreadStarted => "thread/started" (v2::ThreadStartedNotification),
     ThreadTokenUsageUpdated => "thread/tokenUsage/updated" (v2::ThreadTokenUsageUpdatedNotification),
+    ThreadContextWindowState => "thread/contextWindowState" (v2::ThreadContextWindowStateNotification),
     TurnStarted => "turn/started" (v2::TurnStartedNotification),
     TurnCompleted => "turn/completed" (v2::TurnCompletedNotification),
     TurnDiffUpdated => "turn/diff/updated" (v2::TurnDiffUpdatedNotification),
+    SubagentReport => "turn/subagentReport" (v2::SubagentReportNotification),
+    TurnRetryScheduled => "turn/retryScheduled" (v2::TurnRetryScheduledNotification),
+    TurnStalled => "turn/stalled" (v2::TurnStalledNotification),
     ItemStarted => "item/started" (v2::ItemStartedNotification),
     ItemCompleted => "item/completed" (v2::ItemCompletedNotification),
     AgentMessageDelta => "item/agentMessage/delta" (v2::AgentMessageDeltaNotification),
@@ -513,10 +725,35 @@ server_notification_definitions! {
     McpToolCallProgress => "item/mcpToolCall/progress" (v2::McpToolCallProgressNotification),
     AccountUpdated => "account/updated" (v2::AccountUpdatedNotification),
     AccountRateLimitsUpdated => "account/rateLimits/updated" (v2::AccountRateLimitsUpdatedNotification),
+    AccountRateLimitsWarning => "account/rateLimits/warning" (v2::AccountRateLimitsWarningNotification),
     ReasoningSummaryTextDelta => "item/reasoning/summaryTextDelta" (v2::ReasoningSummaryTextDeltaNotification),
     ReasoningSummaryPartAdded => "item/reasoning/summaryPartAdded" (v2::ReasoningSummaryPartAddedNotification),
     ReasoningTextDelta => "item/reasoning/textDelta" (v2::ReasoningTextDeltaNotification),
     ContextCompacted => "thread/compacted" (v2::ContextCompactedNotification),
+    ThreadForked => "thread/forked" (v2::ThreadForkedNotification),
+    ThreadMergeProgress => "thread/merge/progress" (v2::ThreadMergeProgressNotification),
+    ThreadMerged => "thread/merged" (v2::ThreadMergedNotification),
+    ThreadPlanUpdated => "thread/plan/updated" (v2::ThreadPlanUpdatedNotification),
+    ThreadPlanReady => "thread/plan/ready" (v2::ThreadPlanReadyNotification),
+    ThreadResponseRegenerated => "thread/responseRegenerated" (v2::ThreadResponseRegeneratedNotification),
+    ThreadUserMessageEdited => "thread/userMessageEdited" (v2::ThreadUserMessageEditedNotification),
+    ItemVariants => "item/variants" (v2::ItemVariantsNotification),
+    ThreadEnvUpdated => "thread/envUpdated" (v2::ThreadEnvUpdatedNotification),
+    StorageReportReady => "storage/reportReady" (v2::StorageReportReadyNotification),
+    StorageCleanupProgress => "storage/cleanupProgress" (v2::StorageCleanupProgressNotification),
+    StorageCleanupCompleted => "storage/cleanupCompleted" (v2::StorageCleanupCompletedNotification),
+    FollowUpSuggestions => "turn/followUpSuggestions" (v2::FollowUpSuggestionsNotification),
+    MemoryContextInjected => "turn/memoryContextInjected" (v2::MemoryContextInjectedNotification),
+    FileChanged => "file/changed" (v2::FileChangedNotification),
+    WorktreeMerged => "worktree/merged" (v2::WorktreeMergedNotification),
+    WorktreeRemoved => "worktree/removed" (v2::WorktreeRemovedNotification),
+    RepositoryOnboardingProgress => "repository/onboardingProgress" (v2::RepositoryOnboardingProgressNotification),
+    McpServerDown => "mcpServer/down" (v2::McpServerDownNotification),
+    McpServerRestarted => "mcpServer/restarted" (v2::McpServerRestartedNotification),
+    SubsystemStartup => "thread/subsystemStartup" (v2::SubsystemStartupNotification),
+    ThreadDesktopNotification => "thread/desktopNotification" (v2::ThreadDesktopNotification),
+    ProjectDocsLoaded => "thread/projectDocsLoaded" (v2::ProjectDocsLoadedNotification),
+    ConfigChanged => "config/changed" (v2::ConfigChangedNotification),
 
     /// Notifies the user of world-writable directories on Windows, which cannot be protected by the sandbox.
     WindowsWorldWritableWarning => "windows/worldWritableWarning" (v2::WindowsWorldWritableWarningNotification),
@@ -21,6 +21,31 @@ pub fn build_turns_from_event_msgs(events: &[EventMsg]) -> Vec<Turn> {
     builder.finish()
 }
 
+/// Select a page of `turns`, walking backwards from the newest turn.
+///
+/// Returns up to `limit` turns that come right before the turn whose id is
+/// `before` (or the newest turns when `before` is `None`), oldest first, along
+/// with the id to pass as `before` to fetch the page preceding this one.
+/// Returns `None` when no turn has the id `before`.
+pub fn page_turns(
+    mut turns: Vec<Turn>,
+    before: Option<&str>,
+    limit: usize,
+) -> Option<(Vec<Turn>, Option<String>)> {
+    let end = match before {
+        Some(id) => turns.iter().position(|turn| turn.id == id)?,
+        None => turns.len(),
+    };
+    let start = end.saturating_sub(limit.max(1));
+    turns.truncate(end);
+    let page = turns.split_off(start);
+    let next_before = match page.first() {
+        Some(first) if start > 0 => Some(first.id.clone()),
+        _ => None,
+    };
+    Some((page, next_before))
+}
+
 struct ThreadHistoryBuilder {
     turns: Vec<Turn>,
     current_turn: Option<PendingTurn>,
@@ -58,6 +83,12 @@ impl ThreadHistoryBuilder {
             EventMsg::ExitedReviewMode(_) => {}
             EventMsg::UndoCompleted(_) => {}
             EventMsg::TurnAborted(payload) => self.handle_turn_aborted(payload),
+            EventMsg::ConversationMerged(payload) => {
+                let id = self.next_item_id();
+                self.ensure_turn()
+                    .items
+                    .push(ThreadItem::merged_context(id, payload.clone()));
+            }
             _ => {}
         }
     }
@@ -209,6 +240,8 @@ mod tests {
     use codex_protocol::protocol::AgentMessageEvent;
     use codex_protocol::protocol::AgentReasoningEvent;
     use codex_protocol::protocol::AgentReasoningRawContentEvent;
+    use codex_protocol::protocol::ConversationMergedEvent;
+    use codex_protocol::protocol::SessionSummaryEvent;
     use codex_protocol::protocol::TurnAbortReason;
     use codex_protocol::protocol::TurnAbortedEvent;
     use codex_protocol::protocol::UserMessageEvent;
@@ -297,6 +330,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pages_turns_backwards_from_the_newest() {
+        let events: Vec<EventMsg> = (1..=5)
+            .map(|index| {
+                EventMsg::UserMessage(UserMessageEvent {
+                    message: format!("Message {index}"),
+                    images: None,
+                })
+            })
+            .collect();
+        let turns = build_turns_from_event_msgs(&events);
+        let ids = |page: &[Turn]| page.iter().map(|turn| turn.id.clone()).collect::<Vec<_>>();
+
+        let (page, next_before) = page_turns(turns.clone(), None, 2).expect("page");
+        assert_eq!(ids(&page), vec!["turn-4", "turn-5"]);
+        assert_eq!(next_before.as_deref(), Some("turn-4"));
+
+        let (page, next_before) = page_turns(turns.clone(), Some("turn-2"), 2).expect("page");
+        assert_eq!(ids(&page), vec!["turn-1"]);
+        assert_eq!(next_before, None);
+
+        assert_eq!(page_turns(turns, Some("turn-9"), 2), None);
+    }
+
     #[test]
     fn splits_reasoning_when_interleaved() {
         let events = vec![
@@ -406,4 +463,39 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn records_merged_context_marker() {
+        let source = codex_protocol::ConversationId::new();
+        let events = vec![
+            EventMsg::UserMessage(UserMessageEvent {
+                message: "Fix the build".into(),
+                images: None,
+            }),
+            EventMsg::ConversationMerged(ConversationMergedEvent {
+                source_conversation_id: source,
+                summary: SessionSummaryEvent {
+                    goals: vec!["Upgrade the parser".into()],
+                    decisions: Vec::new(),
+                    files_changed: vec!["src/parser.rs".into()],
+                    open_questions: Vec::new(),
+                    saved_path: None,
+                },
+            }),
+        ];
+
+        let turns = build_turns_from_event_msgs(&events);
+        assert_eq!(turns.len(), 1);
+        assert_eq!(
+            turns[0].items[1],
+            ThreadItem::MergedContext {
+                id: "item-2".into(),
+                source_thread_id: source.to_string(),
+                goals: vec!["Upgrade the parser".into()],
+                decisions: Vec::new(),
+                files_changed: vec!["src/parser.rs".into()],
+                open_questions: Vec::new(),
+            }
+        );
+    }
 }
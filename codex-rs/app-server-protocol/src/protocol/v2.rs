@@ -5,6 +5,7 @@ use crate::protocol::common::AuthMode;
 use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
 use codex_protocol::approvals::SandboxCommandAssessment as CoreSandboxCommandAssessment;
+use codex_protocol::config_types::PlanDetailPreference;
 use codex_protocol::config_types::ReasoningEffort;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::items::AgentMessageContent as CoreAgentMessageContent;
@@ -12,10 +13,18 @@ use codex_protocol::items::TurnItem as CoreTurnItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::parse_command::ParsedCommand as CoreParsedCommand;
 use codex_protocol::protocol::CodexErrorInfo as CoreCodexErrorInfo;
+use codex_protocol::protocol::ConversationMergedEvent as CoreConversationMergedEvent;
 use codex_protocol::protocol::CreditsSnapshot as CoreCreditsSnapshot;
+use codex_protocol::protocol::InjectedMemoryEntry as CoreInjectedMemoryEntry;
+use codex_protocol::protocol::MessageVariant as CoreMessageVariant;
+use codex_protocol::protocol::ProjectDocEntry as CoreProjectDocEntry;
 use codex_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use codex_protocol::protocol::RateLimitWindow as CoreRateLimitWindow;
+use codex_protocol::protocol::RepoStorageUsage as CoreRepoStorageUsage;
 use codex_protocol::protocol::SessionSource as CoreSessionSource;
+use codex_protocol::protocol::StorageUsageEntry as CoreStorageUsageEntry;
+use codex_protocol::protocol::SubagentAgentReport as CoreSubagentAgentReport;
+use codex_protocol::protocol::SubagentConflict as CoreSubagentConflict;
 use codex_protocol::protocol::TokenUsage as CoreTokenUsage;
 use codex_protocol::protocol::TokenUsageInfo as CoreTokenUsageInfo;
 use codex_protocol::user_input::UserInput as CoreUserInput;
@@ -89,6 +98,8 @@ pub enum CodexErrorInfo {
         #[ts(rename = "httpStatusCode")]
         http_status_code: Option<u16>,
     },
+    /// The stall watchdog interrupted a turn that stopped making progress.
+    TurnStalled,
     Other,
 }
 
@@ -113,6 +124,7 @@ impl From<CoreCodexErrorInfo> for CodexErrorInfo {
             CoreCodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code } => {
                 CodexErrorInfo::ResponseTooManyFailedAttempts { http_status_code }
             }
+            CoreCodexErrorInfo::TurnStalled => CodexErrorInfo::TurnStalled,
             CoreCodexErrorInfo::Other => CodexErrorInfo::Other,
         }
     }
@@ -251,6 +263,25 @@ pub struct ConfigEdit {
     pub merge_strategy: MergeStrategy,
 }
 
+/// Re-reads `config.toml` for a running thread instead of waiting for the
+/// file watcher to notice the change.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConfigReloadParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConfigReloadResponse {
+    /// Config keys whose new values are already in effect for the thread.
+    pub applied: Vec<String>,
+    /// Config keys that changed but only apply once a new thread starts.
+    pub requires_new_session: Vec<String>,
+}
+
 v2_enum_from_core!(
     pub enum CommandRiskLevel from codex_protocol::approvals::SandboxRiskLevel {
         Low,
@@ -286,6 +317,12 @@ pub enum SandboxPolicy {
         exclude_tmpdir_env_var: bool,
         #[serde(default)]
         exclude_slash_tmp: bool,
+        #[serde(default)]
+        allow_write: Vec<String>,
+        #[serde(default)]
+        deny_write: Vec<String>,
+        #[serde(default)]
+        network_allowlist: Vec<String>,
     },
 }
 
@@ -301,11 +338,17 @@ impl SandboxPolicy {
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                allow_write,
+                deny_write,
+                network_allowlist,
             } => codex_protocol::protocol::SandboxPolicy::WorkspaceWrite {
                 writable_roots: writable_roots.clone(),
                 network_access: *network_access,
                 exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                 exclude_slash_tmp: *exclude_slash_tmp,
+                allow_write: allow_write.clone(),
+                deny_write: deny_write.clone(),
+                network_allowlist: network_allowlist.clone(),
             },
         }
     }
@@ -323,11 +366,17 @@ impl From<codex_protocol::protocol::SandboxPolicy> for SandboxPolicy {
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                allow_write,
+                deny_write,
+                network_allowlist,
             } => SandboxPolicy::WorkspaceWrite {
                 writable_roots,
                 network_access,
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
+                allow_write,
+                deny_write,
+                network_allowlist,
             },
         }
     }
@@ -549,6 +598,153 @@ pub struct GetAccountRateLimitsResponse {
     pub rate_limits: RateLimitSnapshot,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct DiagnosticsReadResponse {
+    /// Thread creations that reused the cached `config.toml` layers.
+    pub config_cache_hits: i64,
+    /// Thread creations that had to read `config.toml` from disk.
+    pub config_cache_misses: i64,
+    /// Timings of the most recent `thread/start` and `thread/resume` calls,
+    /// oldest first.
+    pub thread_opens: Vec<ThreadOpenTiming>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadOpenTiming {
+    pub thread_id: String,
+    pub kind: ThreadOpenKind,
+    /// Time spent deriving the thread's config.
+    pub config_ms: i64,
+    /// Time spent reading the stored rollout; `null` for new threads.
+    pub history_ms: Option<i64>,
+    /// Time spent starting the session, up to `SessionConfigured`.
+    pub session_ms: i64,
+    pub total_ms: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum ThreadOpenKind {
+    Start,
+    Resume,
+}
+
+/// Problems found by the startup self-check, errors first. The TUI shows the
+/// same findings in its startup banner.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct DoctorReadResponse {
+    pub findings: Vec<DoctorFinding>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct DoctorFinding {
+    pub check: DoctorCheck,
+    pub severity: DoctorSeverity,
+    pub summary: String,
+    /// Command that resolves the problem, when there is one.
+    pub fix: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum DoctorCheck {
+    Auth,
+    Config,
+    Sgrep,
+    LockFiles,
+    Clock,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum DoctorSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CrashReportListResponse {
+    /// Stored crash reports, newest first.
+    pub data: Vec<CrashReportSummary>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CrashReportSummary {
+    pub id: String,
+    /// Unix timestamp in seconds.
+    pub created_at: i64,
+    pub kind: CrashReportKind,
+    /// Background task that panicked or failed, if known.
+    pub task: Option<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum CrashReportKind {
+    Panic,
+    TaskFailure,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CrashReportExportParams {
+    pub id: String,
+}
+
+/// A crash report ready to attach to a bug report.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CrashReportExportResponse {
+    pub path: PathBuf,
+    /// The report as pretty-printed JSON: message, backtrace, recent logs,
+    /// and `config.toml` with secrets redacted.
+    pub contents: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AudioTranscribeParams {
+    /// Base64-encoded WAV recording.
+    pub audio: String,
+}
+
+/// Transcript of a voice recording, for the client to place in its composer.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AudioTranscribeResponse {
+    pub text: String,
+    pub engine: TranscriptionEngine,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum TranscriptionEngine {
+    WhisperCpp,
+    Api,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -739,563 +935,2106 @@ pub struct ThreadListResponse {
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ThreadCompactParams {
+pub struct ThreadTurnsListParams {
     pub thread_id: String,
+    /// Return the turns that come before the turn with this id. When omitted,
+    /// the newest turns are returned.
+    pub before: Option<String>,
+    /// Optional page size; defaults to a reasonable server-side value.
+    pub limit: Option<u32>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ThreadCompactResponse {}
+pub struct ThreadTurnsListResponse {
+    /// Turns with their items, oldest first.
+    pub data: Vec<Turn>,
+    /// Value to pass as `before` to load the preceding page.
+    /// If None, this page starts at the first turn of the thread.
+    pub next_before: Option<String>,
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct Thread {
-    pub id: String,
-    /// Usually the first user message in the thread, if available.
-    pub preview: String,
-    /// Model provider used for this thread (for example, 'openai').
-    pub model_provider: String,
-    /// Unix timestamp (in seconds) when the thread was created.
-    pub created_at: i64,
-    /// [UNSTABLE] Path to the thread on disk.
-    pub path: PathBuf,
-    /// Working directory captured for the thread.
-    pub cwd: PathBuf,
-    /// Version of the CLI that created the thread.
-    pub cli_version: String,
-    /// Origin of the thread (CLI, VSCode, codex exec, codex app-server, etc.).
-    pub source: SessionSource,
-    /// Optional Git metadata captured when the thread was created.
-    pub git_info: Option<GitInfo>,
-    /// Only populated on a `thread/resume` response.
-    /// For all other responses and notifications returning a Thread,
-    /// the turns field will be an empty list.
-    pub turns: Vec<Turn>,
+pub struct ThreadRecoverParams {
+    pub thread_id: String,
+    pub strategy: RecoveryStrategy,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+/// How `thread/recover` gets a stuck thread moving again.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct AccountUpdatedNotification {
-    pub auth_mode: Option<AuthMode>,
+pub enum RecoveryStrategy {
+    /// Interrupt the running turn and send the last user message again.
+    RetryTurn,
+    /// Interrupt the running turn.
+    Interrupt,
+    /// Shut the thread down and resume it from its rollout, dropping any
+    /// in-memory state such as running tools and MCP connections.
+    Restart,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ThreadTokenUsageUpdatedNotification {
-    pub thread_id: String,
-    pub turn_id: String,
-    pub token_usage: ThreadTokenUsage,
-}
+pub struct ThreadRecoverResponse {}
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ThreadTokenUsage {
-    pub total: TokenUsageBreakdown,
-    pub last: TokenUsageBreakdown,
-    #[ts(type = "number | null")]
-    pub model_context_window: Option<i64>,
-}
-
-impl From<CoreTokenUsageInfo> for ThreadTokenUsage {
-    fn from(value: CoreTokenUsageInfo) -> Self {
-        Self {
-            total: value.total_token_usage.into(),
-            last: value.last_token_usage.into(),
-            model_context_window: value.model_context_window,
-        }
-    }
+pub struct ThreadDuelStartParams {
+    pub thread_id: String,
+    /// Sent to both branches as the first turn.
+    pub input: Vec<UserInput>,
+    pub model_a: String,
+    pub model_b: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct TokenUsageBreakdown {
-    #[ts(type = "number")]
-    pub total_tokens: i64,
-    #[ts(type = "number")]
-    pub input_tokens: i64,
-    #[ts(type = "number")]
-    pub cached_input_tokens: i64,
-    #[ts(type = "number")]
-    pub output_tokens: i64,
-    #[ts(type = "number")]
-    pub reasoning_output_tokens: i64,
-}
-
-impl From<CoreTokenUsage> for TokenUsageBreakdown {
-    fn from(value: CoreTokenUsage) -> Self {
-        Self {
-            total_tokens: value.total_tokens,
-            input_tokens: value.input_tokens,
-            cached_input_tokens: value.cached_input_tokens,
-            output_tokens: value.output_tokens,
-            reasoning_output_tokens: value.reasoning_output_tokens,
-        }
-    }
+pub struct ThreadDuelStartResponse {
+    pub duel_id: String,
+    /// The two ephemeral threads, in `model_a`, `model_b` order. Their turns
+    /// stream as regular notifications under each branch's thread id.
+    pub branches: Vec<DuelBranch>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct Turn {
-    pub id: String,
-    /// Only populated on a `thread/resume` response.
-    /// For all other responses and notifications returning a Turn,
-    /// the items field will be an empty list.
-    pub items: Vec<ThreadItem>,
-    #[serde(flatten)]
-    pub status: TurnStatus,
+pub struct DuelBranch {
+    pub thread_id: String,
+    pub model: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS, Error)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-#[error("{message}")]
-pub struct TurnError {
-    pub message: String,
-    pub codex_error_info: Option<CodexErrorInfo>,
+pub struct ThreadDuelPickParams {
+    pub duel_id: String,
+    /// Thread id of the winning branch. Its turn is appended to the original
+    /// thread and both branches are discarded.
+    pub winner_thread_id: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ErrorNotification {
-    pub error: TurnError,
+pub struct ThreadDuelPickResponse {
+    /// The original thread the winning turn was merged into.
     pub thread_id: String,
-    pub turn_id: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
-#[serde(tag = "status", rename_all = "camelCase")]
-#[ts(tag = "status", export_to = "v2/")]
-pub enum TurnStatus {
-    Completed,
-    Interrupted,
-    Failed { error: TurnError },
-    InProgress,
-}
-
-// Turn APIs
-#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct TurnStartParams {
+pub struct ThreadForkParams {
     pub thread_id: String,
-    pub input: Vec<UserInput>,
-    /// Override the working directory for this turn and subsequent turns.
-    pub cwd: Option<PathBuf>,
-    /// Override the approval policy for this turn and subsequent turns.
-    pub approval_policy: Option<AskForApproval>,
-    /// Override the sandbox policy for this turn and subsequent turns.
-    pub sandbox_policy: Option<SandboxPolicy>,
-    /// Override the model for this turn and subsequent turns.
-    pub model: Option<String>,
-    /// Override the reasoning effort for this turn and subsequent turns.
-    pub effort: Option<ReasoningEffort>,
-    /// Override the reasoning summary for this turn and subsequent turns.
-    pub summary: Option<ReasoningSummary>,
+    /// 0-based index of the assistant message to branch after; the new thread
+    /// keeps everything up to and including it.
+    pub at_item: usize,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ReviewStartParams {
-    pub thread_id: String,
-    pub target: ReviewTarget,
-
-    /// When true, also append the final review message to the original thread.
-    #[serde(default)]
-    pub append_to_original_thread: bool,
-}
+pub struct ThreadForkResponse {}
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
-#[serde(tag = "type", rename_all = "camelCase")]
-#[ts(tag = "type", export_to = "v2/")]
-pub enum ReviewTarget {
-    /// Review the working tree: staged, unstaged, and untracked files.
-    UncommittedChanges,
-
-    /// Review changes between the current branch and the given base branch.
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    BaseBranch { branch: String },
-
-    /// Review the changes introduced by a specific commit.
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    Commit {
-        sha: String,
-        /// Optional human-readable label (e.g., commit subject) for UIs.
-        title: Option<String>,
-    },
-
-    /// Arbitrary instructions, equivalent to the old free-form prompt.
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    Custom { instructions: String },
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadMergeParams {
+    /// The thread that receives the merged context.
+    pub thread_id: String,
+    /// The thread to summarize; it is left unchanged.
+    pub source_thread_id: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct TurnStartResponse {
-    pub turn: Turn,
-}
+pub struct ThreadMergeResponse {}
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct TurnInterruptParams {
+pub struct ThreadRegenerateParams {
     pub thread_id: String,
-    pub turn_id: String,
+    /// Model for the regenerated turn only; defaults to the thread's model.
+    pub model: Option<String>,
+    /// Reasoning effort for the regenerated turn only.
+    pub effort: Option<ReasoningEffort>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct TurnInterruptResponse {}
+pub struct ThreadRegenerateResponse {}
 
-// User input types
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
-#[serde(tag = "type", rename_all = "camelCase")]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadEditAndResendParams {
+    pub thread_id: String,
+    /// 0-based index of the user message to replace; it and everything
+    /// after it are dropped from the thread.
+    pub at_item: usize,
+    /// The edited message, sent as a new turn.
+    pub input: Vec<UserInput>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadEditAndResendResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ItemVariantsListParams {
+    pub thread_id: String,
+    /// An `agentMessage` item id: the current answer or a previous attempt.
+    pub item_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ItemVariantsListResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MessageVariant {
+    /// Item id of the attempt's final `agentMessage`, when known.
+    pub item_id: Option<String>,
+    pub text: String,
+    pub model: String,
+    pub effort: Option<ReasoningEffort>,
+}
+
+impl From<CoreMessageVariant> for MessageVariant {
+    fn from(value: CoreMessageVariant) -> Self {
+        Self {
+            item_id: value.message_id,
+            text: value.text,
+            model: value.model,
+            effort: value.effort,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSetEnvParams {
+    pub thread_id: String,
+    /// Replaces the variables previously set on the thread; an empty map
+    /// clears them.
+    pub env: HashMap<String, String>,
+    /// Also save `env` as the env profile of the thread's working directory,
+    /// applied to new threads started there.
+    #[serde(default)]
+    pub persist: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSetEnvResponse {}
+
+v2_enum_from_core!(
+    pub enum WebhookEvent from codex_protocol::config_types::WebhookEvent {
+        TurnComplete, ApprovalNeeded, Error
+    }
+);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSetWebhookParams {
+    pub thread_id: String,
+    /// `http` or `https` URL receiving the thread's events. Replaces an
+    /// earlier registration of the same URL.
+    pub url: String,
+    /// Events to deliver; all of them when omitted. An empty list removes
+    /// the webhook.
+    #[serde(default)]
+    pub events: Option<Vec<WebhookEvent>>,
+    /// Secret used to sign payloads with HMAC-SHA256; unsigned when omitted.
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSetWebhookResponse {}
+
+/// Session defaults for a project, applied to threads started with a `cwd`
+/// inside it. A field left unset falls back to `config.toml`; values passed
+/// to `thread/start` still win.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ProjectDefaults {
+    pub model: Option<String>,
+    pub effort: Option<ReasoningEffort>,
+    pub approval_policy: Option<AskForApproval>,
+    pub sandbox: Option<SandboxMode>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ProjectDefaultsReadParams {
+    pub cwd: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ProjectDefaultsReadResponse {
+    /// Project the defaults belong to: the repository root for a `cwd`
+    /// inside a git repository without its own entry.
+    pub project: PathBuf,
+    pub defaults: ProjectDefaults,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ProjectDefaultsWriteParams {
+    pub cwd: PathBuf,
+    /// Replaces the project's defaults; unset fields are removed.
+    pub defaults: ProjectDefaults,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ProjectDefaultsWriteResponse {
+    pub project: PathBuf,
+}
+
+/// Measures disk usage of the thread's Codex home and project. The report
+/// arrives as a `storage/reportReady` notification.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct StorageReportParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct StorageReportResponse {}
+
+/// Frees disk space. Nothing is removed unless asked; progress and the
+/// outcome arrive as `storage/cleanupProgress` and `storage/cleanupCompleted`
+/// notifications.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct StorageCleanupParams {
+    pub thread_id: String,
+    /// Delete active and archived rollouts not modified for this many days.
+    pub delete_rollouts_older_than_days: Option<i64>,
+    /// Prune checkpoints and other unreferenced git objects older than this
+    /// many days from the thread's repository.
+    pub prune_checkpoints_older_than_days: Option<i64>,
+    /// Rebuild the project's memory database to reclaim free pages.
+    #[serde(default)]
+    pub vacuum_memory: bool,
+    /// Delete attachments of threads whose rollout no longer exists.
+    #[serde(default)]
+    pub gc_attachments: bool,
+    /// Report what would be removed without changing anything.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct StorageCleanupResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RolloutListParams {
+    /// Only list archived (`true`) or active (`false`) rollouts. Usage totals
+    /// always cover both.
+    pub archived: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RolloutListResponse {
+    /// Rollout files, most recently modified first.
+    pub data: Vec<RolloutFile>,
+    pub usage: RolloutUsage,
+}
+
+/// A rollout file in `sessions/` or, compressed, in `archived_sessions/`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RolloutFile {
+    pub path: PathBuf,
+    pub size_bytes: i64,
+    /// Unix timestamp in seconds of the last modification.
+    pub modified_at: Option<i64>,
+    pub archived: bool,
+}
+
+/// Disk usage of rollout files.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RolloutUsage {
+    pub active_files: i64,
+    pub active_bytes: i64,
+    pub archived_files: i64,
+    pub archived_bytes: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RolloutDeleteParams {
+    pub path: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RolloutDeleteResponse {
+    /// Usage after the deletion.
+    pub usage: RolloutUsage,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RolloutArchiveParams {
+    /// An active rollout under `sessions/`.
+    pub path: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RolloutArchiveResponse {
+    /// The compressed `.jsonl.gz` archive that replaced the rollout.
+    pub archive_path: PathBuf,
+    /// Usage after archiving.
+    pub usage: RolloutUsage,
+}
+
+/// Sends one of the thread's current follow-up suggestions as the next user
+/// message.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSuggestionAcceptParams {
+    pub thread_id: String,
+    /// 0-based position in the last `turn/followUpSuggestions` list.
+    pub index: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadSuggestionAcceptResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCompactParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadCompactResponse {}
+
+v2_enum_from_core!(
+    pub enum ThreadPlanStepStatus from codex_protocol::plan_tool::StepStatus {
+        Pending, InProgress, Completed
+    }
+);
+
+/// The latest `update_plan` steps of a thread, with any statuses the user
+/// set by hand. Stored by the server so the plan survives restarts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlan {
+    pub explanation: Option<String>,
+    pub steps: Vec<ThreadPlanStep>,
+    /// Unix timestamp (in seconds) of the last change.
+    pub updated_at: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanStep {
+    pub step: String,
+    pub status: ThreadPlanStepStatus,
+    /// Whether `status` was set with `thread/plan/setStepStatus` rather than
+    /// by the agent.
+    pub overridden: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanReadParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanReadResponse {
+    /// Null until the agent has published a plan in this thread.
+    pub plan: Option<ThreadPlan>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadEventsReplayParams {
+    pub thread_id: String,
+    /// `seq` of the last event the client applied. Omit to get every event
+    /// of the current turn.
+    pub since_seq: Option<i64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadEventsReplayResponse {
+    pub events: Vec<ThreadJournalEvent>,
+    /// True when events after `sinceSeq` belonged to an earlier turn and are
+    /// no longer journaled. Reload the thread with `thread/resume` before
+    /// applying `events`.
+    pub gap: bool,
+}
+
+/// A `codex/event/*` notification as it was sent.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadJournalEvent {
+    pub seq: i64,
+    pub method: String,
+    pub params: JsonValue,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanSetStepStatusParams {
+    pub thread_id: String,
+    /// 0-based index into the plan's steps.
+    pub index: u32,
+    pub status: ThreadPlanStepStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanSetStepStatusResponse {
+    pub plan: ThreadPlan,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanSetModeParams {
+    pub thread_id: String,
+    /// While enabled, `turn/start` drafts a plan for review instead of
+    /// changing the workspace. Disabling abandons any plan awaiting review.
+    pub enabled: bool,
+    /// Defaults to the configured `plan_detail`.
+    pub detail: Option<PlanDetailPreference>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanSetModeResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanApproveParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanApproveResponse {
+    /// The turn implementing the approved plan.
+    pub turn: Turn,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanSendFeedbackParams {
+    pub thread_id: String,
+    pub feedback: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadPlanSendFeedbackResponse {
+    /// The turn revising the plan.
+    pub turn: Turn,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct Thread {
+    pub id: String,
+    /// Usually the first user message in the thread, if available.
+    pub preview: String,
+    /// Model provider used for this thread (for example, 'openai').
+    pub model_provider: String,
+    /// Unix timestamp (in seconds) when the thread was created.
+    pub created_at: i64,
+    /// [UNSTABLE] Path to the thread on disk.
+    pub path: PathBuf,
+    /// Working directory captured for the thread.
+    pub cwd: PathBuf,
+    /// Version of the CLI that created the thread.
+    pub cli_version: String,
+    /// Origin of the thread (CLI, VSCode, codex exec, codex app-server, etc.).
+    pub source: SessionSource,
+    /// Optional Git metadata captured when the thread was created.
+    pub git_info: Option<GitInfo>,
+    /// Only populated on `thread/resume` and `thread/turns/list` responses.
+    /// For all other responses and notifications returning a Thread,
+    /// the turns field will be an empty list.
+    pub turns: Vec<Turn>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AccountUpdatedNotification {
+    pub auth_mode: Option<AuthMode>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadTokenUsageUpdatedNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub token_usage: ThreadTokenUsage,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadTokenUsage {
+    pub total: TokenUsageBreakdown,
+    pub last: TokenUsageBreakdown,
+    #[ts(type = "number | null")]
+    pub model_context_window: Option<i64>,
+}
+
+impl From<CoreTokenUsageInfo> for ThreadTokenUsage {
+    fn from(value: CoreTokenUsageInfo) -> Self {
+        Self {
+            total: value.total_token_usage.into(),
+            last: value.last_token_usage.into(),
+            model_context_window: value.model_context_window,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadContextWindowStateNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub state: ThreadContextWindowState,
+}
+
+/// How full the thread's context window is, for a gauge with a "compact now"
+/// action (`thread/compact`).
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadContextWindowState {
+    #[ts(type = "number | null")]
+    pub model_context_window: Option<i64>,
+    #[ts(type = "number")]
+    pub tokens_in_context_window: i64,
+    /// Share of the window still available, 0-100; null when the model's
+    /// window size is unknown.
+    #[ts(type = "number | null")]
+    pub percent_remaining: Option<i64>,
+    /// Compactions run on this thread since it was loaded.
+    pub compaction_count: u32,
+}
+
+impl ThreadContextWindowState {
+    pub fn update(&mut self, info: &CoreTokenUsageInfo) {
+        self.model_context_window = info.model_context_window;
+        self.tokens_in_context_window = info.last_token_usage.tokens_in_context_window();
+        self.percent_remaining = info.model_context_window.map(|window| {
+            info.last_token_usage
+                .percent_of_context_window_remaining(window)
+        });
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TokenUsageBreakdown {
+    #[ts(type = "number")]
+    pub total_tokens: i64,
+    #[ts(type = "number")]
+    pub input_tokens: i64,
+    #[ts(type = "number")]
+    pub cached_input_tokens: i64,
+    #[ts(type = "number")]
+    pub output_tokens: i64,
+    #[ts(type = "number")]
+    pub reasoning_output_tokens: i64,
+}
+
+impl From<CoreTokenUsage> for TokenUsageBreakdown {
+    fn from(value: CoreTokenUsage) -> Self {
+        Self {
+            total_tokens: value.total_tokens,
+            input_tokens: value.input_tokens,
+            cached_input_tokens: value.cached_input_tokens,
+            output_tokens: value.output_tokens,
+            reasoning_output_tokens: value.reasoning_output_tokens,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct Turn {
+    pub id: String,
+    /// Only populated on a `thread/resume` response.
+    /// For all other responses and notifications returning a Turn,
+    /// the items field will be an empty list.
+    pub items: Vec<ThreadItem>,
+    #[serde(flatten)]
+    pub status: TurnStatus,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS, Error)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+#[error("{message}")]
+pub struct TurnError {
+    pub message: String,
+    pub codex_error_info: Option<CodexErrorInfo>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ErrorNotification {
+    pub error: TurnError,
+    pub thread_id: String,
+    pub turn_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(tag = "status", rename_all = "camelCase")]
+#[ts(tag = "status", export_to = "v2/")]
+pub enum TurnStatus {
+    Completed,
+    Interrupted,
+    Failed { error: TurnError },
+    InProgress,
+}
+
+// Turn APIs
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnStartParams {
+    pub thread_id: String,
+    pub input: Vec<UserInput>,
+    /// Override the working directory for this turn and subsequent turns.
+    pub cwd: Option<PathBuf>,
+    /// Override the approval policy for this turn and subsequent turns.
+    pub approval_policy: Option<AskForApproval>,
+    /// Override the sandbox policy for this turn and subsequent turns.
+    pub sandbox_policy: Option<SandboxPolicy>,
+    /// Override the model for this turn and subsequent turns.
+    pub model: Option<String>,
+    /// Override the reasoning effort for this turn and subsequent turns.
+    pub effort: Option<ReasoningEffort>,
+    /// Override the reasoning summary for this turn and subsequent turns.
+    pub summary: Option<ReasoningSummary>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ReviewStartParams {
+    pub thread_id: String,
+    pub target: ReviewTarget,
+
+    /// When true, also append the final review message to the original thread.
+    #[serde(default)]
+    pub append_to_original_thread: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(tag = "type", export_to = "v2/")]
+pub enum ReviewTarget {
+    /// Review the working tree: staged, unstaged, and untracked files.
+    UncommittedChanges,
+
+    /// Review changes between the current branch and the given base branch.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    BaseBranch { branch: String },
+
+    /// Review the changes introduced by a specific commit.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Commit {
+        sha: String,
+        /// Optional human-readable label (e.g., commit subject) for UIs.
+        title: Option<String>,
+    },
+
+    /// Arbitrary instructions, equivalent to the old free-form prompt.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Custom { instructions: String },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnStartResponse {
+    pub turn: Turn,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnInterruptParams {
+    pub thread_id: String,
+    pub turn_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnInterruptResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ItemCancelParams {
+    pub thread_id: String,
+    /// Id of a running `commandExecution` or `mcpToolCall` item.
+    pub item_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ItemCancelResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct SubagentCancelParams {
+    pub thread_id: String,
+    /// Id of the `subagent_run` tool call that launched the task.
+    pub call_id: String,
+    /// 0-based index of the task within that call, as in
+    /// `subagent_task_update` events.
+    pub agent_index: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct SubagentCancelResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerRestartParams {
+    pub thread_id: String,
+    /// Server name as configured under `mcp_servers`.
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpServerRestartResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnRevertParams {
+    pub thread_id: String,
+    /// Turn whose file changes should be restored to their state before it ran.
+    pub turn_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnRevertFileParams {
+    pub thread_id: String,
+    /// File to restore, absolute or relative to the thread's working directory.
+    pub path: PathBuf,
+    /// Turn to revert the file to the start of; defaults to the most recent turn.
+    pub turn_id: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnRevertResponse {}
+
+// Workspace file APIs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileTreeParams {
+    /// Directory to list. Entries excluded by `.gitignore` are omitted.
+    pub path: PathBuf,
+    /// Maximum depth to descend below `path`; unlimited when omitted.
+    pub max_depth: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileTreeResponse {
+    pub root: FileTreeNode,
+    /// True when the listing stopped early because the tree is too large.
+    pub truncated: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileTreeNode {
+    pub name: String,
+    pub path: PathBuf,
+    pub kind: FileTreeNodeKind,
+    /// Directories first, then files, each sorted by name.
+    pub children: Vec<FileTreeNode>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum FileTreeNodeKind {
+    File,
+    Directory,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileReadParams {
+    pub path: PathBuf,
+    /// First line to return (1-based, inclusive); defaults to the first line.
+    pub start_line: Option<u32>,
+    /// Last line to return (1-based, inclusive); defaults to the last line.
+    pub end_line: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileReadResponse {
+    pub content: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    pub total_lines: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileWatchParams {
+    /// Directory to watch recursively.
+    pub path: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileWatchResponse {
+    /// Identifies this watch in `file/changed` notifications and `file/unwatch`.
+    pub watch_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileUnwatchParams {
+    pub watch_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileUnwatchResponse {}
+
+// Worktree APIs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WorktreeListParams {
+    /// Any path inside the repository.
+    pub cwd: PathBuf,
+    /// Days without activity before a clean, unused worktree is reported as
+    /// stale; defaults to 7.
+    pub stale_after_days: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WorktreeListResponse {
+    pub data: Vec<Worktree>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct Worktree {
+    pub path: PathBuf,
+    pub branch: Option<String>,
+    pub head: Option<String>,
+    pub is_main: bool,
+    pub locked: bool,
+    /// No loaded thread uses it, it has no uncommitted changes, and it has
+    /// been idle for `staleAfterDays`; a candidate for merge and clean up.
+    pub stale: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WorktreeRemoveParams {
+    pub cwd: PathBuf,
+    pub path: PathBuf,
+    /// Remove even when the worktree has uncommitted changes.
+    #[serde(default)]
+    pub force: bool,
+    /// Also delete the worktree's branch.
+    #[serde(default)]
+    pub delete_branch: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WorktreeRemoveResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WorktreeMergeParams {
+    pub cwd: PathBuf,
+    /// Branch to merge into the branch checked out in the main worktree.
+    pub branch: String,
+    /// Record the branch's changes as a single commit.
+    #[serde(default)]
+    pub squash: bool,
+    /// After a successful merge, remove the branch's worktree and the branch.
+    #[serde(default)]
+    pub clean_up: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WorktreeMergeResponse {
+    /// `HEAD` of the main worktree after the merge.
+    pub commit: String,
+    /// Worktree removed by `cleanUp`, if any.
+    pub removed_path: Option<PathBuf>,
+}
+
+// Git commit APIs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitStatusParams {
+    /// Any path inside the repository.
+    pub cwd: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitStatusResponse {
+    /// Checked-out branch; null for a detached `HEAD`.
+    pub branch: Option<String>,
+    pub entries: Vec<GitStatusEntry>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitStatusEntry {
+    /// Path relative to the repository root.
+    pub path: PathBuf,
+    /// Previous path for renames and copies.
+    pub original_path: Option<PathBuf>,
+    /// Porcelain status code for the index (e.g. `M`, `A`, `?`, or space).
+    pub index: String,
+    /// Porcelain status code for the working tree.
+    pub worktree: String,
+    /// Whether the path has changes staged for the next commit.
+    pub staged: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitStageParams {
+    pub cwd: PathBuf,
+    /// Paths relative to the repository root; deletions are staged too.
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitStageResponse {}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitCommitParams {
+    pub cwd: PathBuf,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitCommitResponse {
+    /// Id of the new commit.
+    pub commit: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitBranchParams {
+    pub cwd: PathBuf,
+    /// Branch to create at `HEAD` and check out; local changes are kept.
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct GitBranchResponse {}
+
+// Repository onboarding APIs
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RepositoryOnboardParams {
+    pub cwd: PathBuf,
+    /// Mark the repository as trusted in `config.toml`.
+    #[serde(default)]
+    pub trust_repository: bool,
+    /// Return the prompt that generates `AGENTS.md` when it is missing.
+    #[serde(default)]
+    pub generate_agents_md: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RepositoryOnboardResponse {
+    pub repo_root: PathBuf,
+    /// Submit this as the first turn to generate `AGENTS.md`.
+    pub agents_md_prompt: Option<String>,
+    /// Steps that failed; onboarding continues past individual failures.
+    pub failed_steps: Vec<RepositoryOnboardingStep>,
+}
+
+/// Steps of `repository/onboard`, in the order they run.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum RepositoryOnboardingStep {
+    DetectGit,
+    RegisterRepository,
+    AgentsMd,
+    SemanticIndex,
+    Memory,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(tag = "status", rename_all = "camelCase")]
+#[ts(tag = "status", export_to = "v2/")]
+pub enum RepositoryOnboardingStepStatus {
+    Started,
+    Completed {
+        detail: Option<String>,
+    },
+    Skipped {
+        reason: String,
+    },
+    /// Call `repository/onboard` again with the matching opt-in to perform
+    /// the step.
+    NeedsConfirmation {
+        message: String,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct RepositoryOnboardingProgressNotification {
+    /// The `cwd` of the `repository/onboard` request.
+    pub cwd: PathBuf,
+    pub step: RepositoryOnboardingStep,
+    #[serde(flatten)]
+    pub status: RepositoryOnboardingStepStatus,
+}
+
+// User input types
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(tag = "type")]
+#[ts(export_to = "v2/")]
+pub enum UserInput {
+    Text {
+        text: String,
+    },
+    Image {
+        url: String,
+    },
+    LocalImage {
+        path: PathBuf,
+    },
+    /// Anchors a `> ` quote in the accompanying text to the assistant
+    /// message at this position (counting from 0).
+    Quote {
+        item: usize,
+    },
+}
+
+impl UserInput {
+    pub fn into_core(self) -> CoreUserInput {
+        match self {
+            UserInput::Text { text } => CoreUserInput::Text { text },
+            UserInput::Image { url } => CoreUserInput::Image { image_url: url },
+            UserInput::LocalImage { path } => CoreUserInput::LocalImage { path },
+            UserInput::Quote { item } => CoreUserInput::Quote { item },
+        }
+    }
+}
+
+impl From<CoreUserInput> for UserInput {
+    fn from(value: CoreUserInput) -> Self {
+        match value {
+            CoreUserInput::Text { text } => UserInput::Text { text },
+            CoreUserInput::Image { image_url } => UserInput::Image { url: image_url },
+            CoreUserInput::LocalImage { path } => UserInput::LocalImage { path },
+            CoreUserInput::Quote { item } => UserInput::Quote { item },
+            _ => unreachable!("unsupported user input variant"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
+#[ts(tag = "type")]
+#[ts(export_to = "v2/")]
+pub enum ThreadItem {
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    UserMessage { id: String, content: Vec<UserInput> },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    AgentMessage { id: String, text: String },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    Reasoning {
+        id: String,
+        #[serde(default)]
+        summary: Vec<String>,
+        #[serde(default)]
+        content: Vec<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CommandExecution {
+        id: String,
+        /// The command to be executed.
+        command: String,
+        /// The command's working directory.
+        cwd: PathBuf,
+        /// Identifier for the underlying PTY process (when available).
+        process_id: Option<String>,
+        status: CommandExecutionStatus,
+        /// A best-effort parsing of the command to understand the action(s) it will perform.
+        /// This returns a list of CommandAction objects because a single shell command may
+        /// be composed of many commands piped together.
+        command_actions: Vec<CommandAction>,
+        /// The command's output, aggregated from stdout and stderr.
+        aggregated_output: Option<String>,
+        /// The command's exit code.
+        exit_code: Option<i32>,
+        /// The duration of the command execution in milliseconds.
+        duration_ms: Option<i64>,
+    },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    FileChange {
+        id: String,
+        changes: Vec<FileUpdateChange>,
+        status: PatchApplyStatus,
+    },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    McpToolCall {
+        id: String,
+        server: String,
+        tool: String,
+        status: McpToolCallStatus,
+        arguments: JsonValue,
+        result: Option<McpToolCallResult>,
+        error: Option<McpToolCallError>,
+    },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    WebSearch { id: String, query: String },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    TodoList { id: String, items: Vec<TodoItem> },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    ImageView { id: String, path: String },
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    CodeReview { id: String, review: String },
+    /// Marks where the summary of another thread was merged into this one.
+    #[serde(rename_all = "camelCase")]
+    #[ts(rename_all = "camelCase")]
+    MergedContext {
+        id: String,
+        source_thread_id: String,
+        goals: Vec<String>,
+        decisions: Vec<String>,
+        files_changed: Vec<String>,
+        open_questions: Vec<String>,
+    },
+}
+
+impl ThreadItem {
+    pub fn merged_context(id: String, event: CoreConversationMergedEvent) -> Self {
+        let summary = event.summary;
+        ThreadItem::MergedContext {
+            id,
+            source_thread_id: event.source_conversation_id.to_string(),
+            goals: summary.goals,
+            decisions: summary.decisions,
+            files_changed: summary.files_changed,
+            open_questions: summary.open_questions,
+        }
+    }
+}
+
+impl From<CoreTurnItem> for ThreadItem {
+    fn from(value: CoreTurnItem) -> Self {
+        match value {
+            CoreTurnItem::UserMessage(user) => ThreadItem::UserMessage {
+                id: user.id,
+                content: user.content.into_iter().map(UserInput::from).collect(),
+            },
+            CoreTurnItem::AgentMessage(agent) => {
+                let text = agent
+                    .content
+                    .into_iter()
+                    .map(|entry| match entry {
+                        CoreAgentMessageContent::Text { text } => text,
+                    })
+                    .collect::<String>();
+                ThreadItem::AgentMessage { id: agent.id, text }
+            }
+            CoreTurnItem::Reasoning(reasoning) => ThreadItem::Reasoning {
+                id: reasoning.id,
+                summary: reasoning.summary_text,
+                content: reasoning.raw_content,
+            },
+            CoreTurnItem::WebSearch(search) => ThreadItem::WebSearch {
+                id: search.id,
+                query: search.query,
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum CommandExecutionStatus {
+    InProgress,
+    Completed,
+    Failed,
+    Declined,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileUpdateChange {
+    pub path: String,
+    pub kind: PatchChangeKind,
+    pub diff: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "camelCase")]
 #[ts(tag = "type")]
 #[ts(export_to = "v2/")]
-pub enum UserInput {
-    Text { text: String },
-    Image { url: String },
-    LocalImage { path: PathBuf },
+pub enum PatchChangeKind {
+    Add,
+    Delete,
+    Update { move_path: Option<PathBuf> },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum PatchApplyStatus {
+    InProgress,
+    Completed,
+    Failed,
+    Declined,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum McpToolCallStatus {
+    InProgress,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpToolCallResult {
+    pub content: Vec<McpContentBlock>,
+    pub structured_content: Option<JsonValue>,
+    /// Image blocks from `content`, saved to disk for display by path.
+    #[serde(default)]
+    pub images: Vec<McpToolCallImage>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpToolCallImage {
+    pub path: PathBuf,
+    pub mime_type: String,
 }
 
-impl UserInput {
-    pub fn into_core(self) -> CoreUserInput {
-        match self {
-            UserInput::Text { text } => CoreUserInput::Text { text },
-            UserInput::Image { url } => CoreUserInput::Image { image_url: url },
-            UserInput::LocalImage { path } => CoreUserInput::LocalImage { path },
+impl From<codex_protocol::protocol::McpImageAttachment> for McpToolCallImage {
+    fn from(value: codex_protocol::protocol::McpImageAttachment) -> Self {
+        Self {
+            path: value.path,
+            mime_type: value.mime_type,
         }
     }
 }
 
-impl From<CoreUserInput> for UserInput {
-    fn from(value: CoreUserInput) -> Self {
-        match value {
-            CoreUserInput::Text { text } => UserInput::Text { text },
-            CoreUserInput::Image { image_url } => UserInput::Image { url: image_url },
-            CoreUserInput::LocalImage { path } => UserInput::LocalImage { path },
-            _ => unreachable!("unsupported user input variant"),
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpToolCallError {
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TodoItem {
+    pub id: String,
+    pub text: String,
+    pub completed: bool,
+}
+
+// === Server Notifications ===
+// Thread/Turn lifecycle notifications and item progress events
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadStartedNotification {
+    pub thread: Thread,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnStartedNotification {
+    pub thread_id: String,
+    pub turn: Turn,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct Usage {
+    pub input_tokens: i32,
+    pub cached_input_tokens: i32,
+    pub output_tokens: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnCompletedNotification {
+    pub thread_id: String,
+    pub turn: Turn,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+/// Notification that the turn-level unified diff has changed.
+/// Contains the latest aggregated diff across all file changes in the turn.
+pub struct TurnDiffUpdatedNotification {
+    pub turn_id: String,
+    pub diff: String,
+}
+
+v2_enum_from_core!(
+    pub enum SubagentTaskStatus from codex_protocol::protocol::SubagentTaskStatus {
+        Running, Done, Timeout, Failed, Cancelled
+    }
+);
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct SubagentAgentReport {
+    pub task: String,
+    /// Index (0-based) of the task within the `subagent_run` call.
+    pub agent_index: i64,
+    pub status: SubagentTaskStatus,
+    /// Files the task edited, sorted.
+    pub files: Vec<PathBuf>,
+    /// Unified diff of the task's edits, if it made any.
+    pub diff: Option<String>,
+}
+
+impl From<CoreSubagentAgentReport> for SubagentAgentReport {
+    fn from(value: CoreSubagentAgentReport) -> Self {
+        Self {
+            task: value.task,
+            agent_index: value.agent_index,
+            status: value.status.into(),
+            files: value.files,
+            diff: value.unified_diff,
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
-#[serde(tag = "type", rename_all = "camelCase")]
-#[ts(tag = "type")]
+#[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub enum ThreadItem {
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    UserMessage { id: String, content: Vec<UserInput> },
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    AgentMessage { id: String, text: String },
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    Reasoning {
-        id: String,
-        #[serde(default)]
-        summary: Vec<String>,
-        #[serde(default)]
-        content: Vec<String>,
-    },
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    CommandExecution {
-        id: String,
-        /// The command to be executed.
-        command: String,
-        /// The command's working directory.
-        cwd: PathBuf,
-        /// Identifier for the underlying PTY process (when available).
-        process_id: Option<String>,
-        status: CommandExecutionStatus,
-        /// A best-effort parsing of the command to understand the action(s) it will perform.
-        /// This returns a list of CommandAction objects because a single shell command may
-        /// be composed of many commands piped together.
-        command_actions: Vec<CommandAction>,
-        /// The command's output, aggregated from stdout and stderr.
-        aggregated_output: Option<String>,
-        /// The command's exit code.
-        exit_code: Option<i32>,
-        /// The duration of the command execution in milliseconds.
-        duration_ms: Option<i64>,
-    },
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    FileChange {
-        id: String,
-        changes: Vec<FileUpdateChange>,
-        status: PatchApplyStatus,
-    },
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    McpToolCall {
-        id: String,
-        server: String,
-        tool: String,
-        status: McpToolCallStatus,
-        arguments: JsonValue,
-        result: Option<McpToolCallResult>,
-        error: Option<McpToolCallError>,
-    },
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    WebSearch { id: String, query: String },
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    TodoList { id: String, items: Vec<TodoItem> },
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    ImageView { id: String, path: String },
-    #[serde(rename_all = "camelCase")]
-    #[ts(rename_all = "camelCase")]
-    CodeReview { id: String, review: String },
+pub struct SubagentConflict {
+    pub path: PathBuf,
+    /// Indices of the tasks that edited the file.
+    pub agent_indices: Vec<i64>,
+}
+
+impl From<CoreSubagentConflict> for SubagentConflict {
+    fn from(value: CoreSubagentConflict) -> Self {
+        Self {
+            path: value.path,
+            agent_indices: value.agent_indices,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+/// Notification that every subagent task of a `subagent_run` call finished
+/// and at least one of them edited files.
+pub struct SubagentReportNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    /// Tool call id that launched the subagents.
+    pub call_id: String,
+    pub agents: Vec<SubagentAgentReport>,
+    /// Files edited by more than one task.
+    pub conflicts: Vec<SubagentConflict>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ItemStartedNotification {
+    pub item: ThreadItem,
+    pub thread_id: String,
+    pub turn_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ItemCompletedNotification {
+    pub item: ThreadItem,
+    pub thread_id: String,
+    pub turn_id: String,
+}
+
+// Item-specific progress notifications
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AgentMessageDeltaNotification {
+    pub item_id: String,
+    pub delta: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ReasoningSummaryTextDeltaNotification {
+    pub item_id: String,
+    pub delta: String,
+    pub summary_index: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ReasoningSummaryPartAddedNotification {
+    pub item_id: String,
+    pub summary_index: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ReasoningTextDeltaNotification {
+    pub item_id: String,
+    pub delta: String,
+    pub content_index: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CommandExecutionOutputDeltaNotification {
+    pub item_id: String,
+    pub delta: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct McpToolCallProgressNotification {
+    pub item_id: String,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WindowsWorldWritableWarningNotification {
+    pub sample_paths: Vec<String>,
+    pub extra_count: usize,
+    pub failed_scan: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ContextCompactedNotification {
+    pub thread_id: String,
+    pub turn_id: String,
 }
 
-impl From<CoreTurnItem> for ThreadItem {
-    fn from(value: CoreTurnItem) -> Self {
-        match value {
-            CoreTurnItem::UserMessage(user) => ThreadItem::UserMessage {
-                id: user.id,
-                content: user.content.into_iter().map(UserInput::from).collect(),
-            },
-            CoreTurnItem::AgentMessage(agent) => {
-                let text = agent
-                    .content
-                    .into_iter()
-                    .map(|entry| match entry {
-                        CoreAgentMessageContent::Text { text } => text,
-                    })
-                    .collect::<String>();
-                ThreadItem::AgentMessage { id: agent.id, text }
-            }
-            CoreTurnItem::Reasoning(reasoning) => ThreadItem::Reasoning {
-                id: reasoning.id,
-                summary: reasoning.summary_text,
-                content: reasoning.raw_content,
-            },
-            CoreTurnItem::WebSearch(search) => ThreadItem::WebSearch {
-                id: search.id,
-                query: search.query,
-            },
-        }
-    }
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ThreadResponseRegeneratedNotification {
+    pub thread_id: String,
+    /// The discarded answer; the regenerated turn follows as a regular turn.
+    pub previous_attempt: MessageVariant,
+    /// Number of `agentMessage` items dropped from the thread.
+    pub removed_messages: usize,
+    pub model: String,
+    pub effort: Option<ReasoningEffort>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub enum CommandExecutionStatus {
-    InProgress,
-    Completed,
-    Failed,
-    Declined,
+pub struct ThreadUserMessageEditedNotification {
+    pub thread_id: String,
+    /// The edited message's index; the turn for the new text follows as a
+    /// regular turn.
+    pub at_item: usize,
+    /// Number of `userMessage` items dropped from the thread, the edited
+    /// one included.
+    pub removed_user_messages: usize,
+    /// Number of `agentMessage` items dropped from the thread.
+    pub removed_messages: usize,
+    /// Files pinned after the edited message was sent, now unpinned.
+    pub unpinned_files: Vec<PathBuf>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct FileUpdateChange {
-    pub path: String,
-    pub kind: PatchChangeKind,
-    pub diff: String,
+pub struct ItemVariantsNotification {
+    pub thread_id: String,
+    pub item_id: String,
+    /// Previous attempts, oldest first.
+    pub variants: Vec<MessageVariant>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
-#[serde(tag = "type", rename_all = "camelCase")]
-#[ts(tag = "type")]
+#[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub enum PatchChangeKind {
-    Add,
-    Delete,
-    Update { move_path: Option<PathBuf> },
+pub struct ThreadEnvUpdatedNotification {
+    pub thread_id: String,
+    /// Names of the variables now set on the thread, sorted. Values are not
+    /// echoed back.
+    pub names: Vec<String>,
+    /// Project the env profile was saved for, when it was persisted.
+    pub persisted_project: Option<PathBuf>,
+    /// Why saving the env profile failed; the variables still apply to the
+    /// thread.
+    pub error: Option<String>,
 }
 
+v2_enum_from_core!(
+    pub enum StorageCategory from codex_protocol::protocol::StorageCategory {
+        Rollouts, ArchivedRollouts, Attachments, Memory, Checkpoints
+    }
+);
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub enum PatchApplyStatus {
-    InProgress,
-    Completed,
-    Failed,
-    Declined,
+pub struct StorageUsage {
+    pub category: StorageCategory,
+    pub files: i64,
+    pub bytes: i64,
+}
+
+impl From<CoreStorageUsageEntry> for StorageUsage {
+    fn from(value: CoreStorageUsageEntry) -> Self {
+        Self {
+            category: value.category.into(),
+            files: value.files,
+            bytes: value.bytes,
+        }
+    }
 }
 
+/// Rollouts recorded in one working directory.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub enum McpToolCallStatus {
-    InProgress,
-    Completed,
-    Failed,
+pub struct RepoStorageUsage {
+    pub cwd: PathBuf,
+    pub sessions: i64,
+    pub bytes: i64,
+}
+
+impl From<CoreRepoStorageUsage> for RepoStorageUsage {
+    fn from(value: CoreRepoStorageUsage) -> Self {
+        Self {
+            cwd: value.cwd,
+            sessions: value.sessions,
+            bytes: value.bytes,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct McpToolCallResult {
-    pub content: Vec<McpContentBlock>,
-    pub structured_content: Option<JsonValue>,
+pub struct StorageReportReadyNotification {
+    pub thread_id: String,
+    pub usage: Vec<StorageUsage>,
+    /// Rollout usage per working directory, largest first.
+    pub repos: Vec<RepoStorageUsage>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct McpToolCallError {
+pub struct StorageCleanupProgressNotification {
+    pub thread_id: String,
+    pub category: StorageCategory,
     pub message: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct TodoItem {
-    pub id: String,
-    pub text: String,
-    pub completed: bool,
+pub struct StorageCleanupCompletedNotification {
+    pub thread_id: String,
+    pub dry_run: bool,
+    /// Files and bytes removed, or that would be removed on a dry run, per
+    /// category that was cleaned.
+    pub freed: Vec<StorageUsage>,
+    /// Steps that failed; the other steps still ran.
+    pub errors: Vec<String>,
 }
 
-// === Server Notifications ===
-// Thread/Turn lifecycle notifications and item progress events
+/// Memories recalled into the model's context for a turn, in ranking order.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ThreadStartedNotification {
-    pub thread: Thread,
+pub struct MemoryContextInjectedNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub memories: Vec<InjectedMemory>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct TurnStartedNotification {
+pub struct InjectedMemory {
+    pub id: String,
+    /// `fact`, `pattern`, `decision`, `lesson`, `mistake`, `preference`, or
+    /// `location`.
+    pub memory_type: String,
+    pub content: String,
+    /// Unix timestamp in seconds when the memory was learned.
+    pub created_at: i64,
+    /// Combined retrieval score.
+    pub score: f64,
+    /// Human-readable reasons it was recalled.
+    pub reasons: Vec<String>,
+    /// Memory pack the entry came from (`name@version`), if any.
+    pub source_pack: Option<String>,
+}
+
+impl From<CoreInjectedMemoryEntry> for InjectedMemory {
+    fn from(value: CoreInjectedMemoryEntry) -> Self {
+        Self {
+            id: value.id,
+            memory_type: value.memory_type,
+            content: value.content,
+            created_at: value.created_at,
+            score: value.score,
+            reasons: value.reasons,
+            source_pack: value.source_pack,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FollowUpSuggestionsNotification {
     pub thread_id: String,
-    pub turn: Turn,
+    /// The completed turn the suggestions follow.
+    pub turn_id: String,
+    pub suggestions: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct Usage {
-    pub input_tokens: i32,
-    pub cached_input_tokens: i32,
-    pub output_tokens: i32,
+pub struct ThreadForkedNotification {
+    /// The thread that was forked; it is left unchanged.
+    pub thread_id: String,
+    /// The new thread, ready for `thread/resume`.
+    pub forked_thread_id: String,
+    pub rollout_path: PathBuf,
+    pub at_item: usize,
 }
 
+v2_enum_from_core!(
+    pub enum ThreadMergeStage from codex_protocol::protocol::ConversationMergeStage {
+        LoadingSource, Summarizing, Recording
+    }
+);
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct TurnCompletedNotification {
+pub struct ThreadMergeProgressNotification {
     pub thread_id: String,
-    pub turn: Turn,
+    pub source_thread_id: String,
+    pub stage: ThreadMergeStage,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-/// Notification that the turn-level unified diff has changed.
-/// Contains the latest aggregated diff across all file changes in the turn.
-pub struct TurnDiffUpdatedNotification {
+pub struct ThreadMergedNotification {
+    pub thread_id: String,
     pub turn_id: String,
-    pub diff: String,
+    /// The `mergedContext` marker now in the thread's transcript.
+    pub item: ThreadItem,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ItemStartedNotification {
-    pub item: ThreadItem,
+pub struct ThreadPlanUpdatedNotification {
     pub thread_id: String,
-    pub turn_id: String,
+    pub plan: ThreadPlan,
 }
 
+/// A plan drafted in plan mode is waiting for `thread/plan/approve` or
+/// `thread/plan/sendFeedback`.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ItemCompletedNotification {
-    pub item: ThreadItem,
+pub struct ThreadPlanReadyNotification {
     pub thread_id: String,
-    pub turn_id: String,
+    /// The request the plan was drafted for.
+    pub request: String,
+    /// Null when the agent did not publish steps with `update_plan`.
+    pub plan: Option<ThreadPlan>,
+    /// The agent's closing message for the planning turn.
+    pub summary: Option<String>,
+    /// Feedback applied so far, oldest first.
+    pub feedback: Vec<String>,
 }
 
-// Item-specific progress notifications
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct AgentMessageDeltaNotification {
-    pub item_id: String,
-    pub delta: String,
+pub struct McpServerDownNotification {
+    pub thread_id: String,
+    pub server: String,
+    pub reason: String,
+    /// Whether the server will keep being restarted automatically.
+    pub retrying: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ReasoningSummaryTextDeltaNotification {
-    pub item_id: String,
-    pub delta: String,
-    pub summary_index: i64,
+pub struct McpServerRestartedNotification {
+    pub thread_id: String,
+    pub server: String,
 }
 
+v2_enum_from_core!(
+    pub enum ThreadSubsystem from codex_protocol::protocol::SessionSubsystem {
+        Memory, SemanticSearch
+    }
+);
+
+/// Sent once per subsystem after a thread starts. Memory and semantic search
+/// are unavailable until their notification arrives with `ready: true`.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ReasoningSummaryPartAddedNotification {
-    pub item_id: String,
-    pub summary_index: i64,
+pub struct SubsystemStartupNotification {
+    pub thread_id: String,
+    pub subsystem: ThreadSubsystem,
+    pub ready: bool,
+    /// Why the subsystem is unavailable when `ready` is `false`.
+    pub reason: Option<String>,
 }
 
+// Desktop notifications
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ReasoningTextDeltaNotification {
-    pub item_id: String,
-    pub delta: String,
-    pub content_index: i64,
+pub struct ThreadSetFocusParams {
+    pub thread_id: String,
+    /// Whether the thread is in front of the user, e.g. its window is focused.
+    pub focused: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct CommandExecutionOutputDeltaNotification {
-    pub item_id: String,
-    pub delta: String,
+pub struct ThreadSetFocusResponse {}
+
+/// Filtered by `gui.notifications` in `config.toml`, which takes the same
+/// type names as `tui.notifications`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum DesktopNotificationKind {
+    /// `agent-turn-complete`
+    TurnComplete,
+    /// `approval-requested`
+    ApprovalRequested,
+    /// `error`
+    Error,
 }
 
+/// Something in a thread the client has not reported as focused needs the
+/// user's attention; show it as an OS notification.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct McpToolCallProgressNotification {
-    pub item_id: String,
+pub struct ThreadDesktopNotification {
+    pub thread_id: String,
+    pub kind: DesktopNotificationKind,
     pub message: String,
 }
 
+/// A project doc (`AGENTS.md` or a configured fallback) included in a
+/// thread's instructions.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct WindowsWorldWritableWarningNotification {
-    pub sample_paths: Vec<String>,
-    pub extra_count: usize,
-    pub failed_scan: bool,
+pub struct ProjectDoc {
+    pub path: PathBuf,
+    /// Bytes of the file included in the instructions.
+    pub bytes: i64,
+    /// The file did not fit in `project_doc_max_bytes` and was cut short, or
+    /// left out entirely when `bytes` is 0.
+    pub truncated: bool,
 }
 
+impl From<CoreProjectDocEntry> for ProjectDoc {
+    fn from(value: CoreProjectDocEntry) -> Self {
+        Self {
+            path: value.path,
+            bytes: value.bytes,
+            truncated: value.truncated,
+        }
+    }
+}
+
+/// Sent once after a thread starts when it found project docs.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
-pub struct ContextCompactedNotification {
+pub struct ProjectDocsLoadedNotification {
+    pub thread_id: String,
+    /// Ordered from the repository root to the working directory; later docs
+    /// take precedence over earlier ones.
+    pub docs: Vec<ProjectDoc>,
+}
+
+/// Sent when a thread picks up an edit to `config.toml` on its own.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ConfigChangedNotification {
+    pub thread_id: String,
+    pub applied: Vec<String>,
+    pub requires_new_session: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnRetryScheduledNotification {
+    pub thread_id: String,
+    pub turn_id: String,
+    /// 1-based number of the upcoming retry.
+    pub attempt: i64,
+    pub max_attempts: i64,
+    /// Milliseconds until the retry starts.
+    pub delay_ms: i64,
+    /// The error that triggered the retry.
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnStalledNotification {
     pub thread_id: String,
     pub turn_id: String,
+    /// Seconds since the turn last showed any activity.
+    pub idle_seconds: i64,
+    /// Type of the last event the turn emitted, e.g. `exec_command_begin`.
+    pub last_event: String,
+    pub running_tool_calls: i64,
+    /// Whether the turn was interrupted; if so a `turn/completed` with status
+    /// `interrupted` follows.
+    pub interrupted: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileChangedNotification {
+    pub watch_id: String,
+    pub path: PathBuf,
+    pub kind: FileSystemChangeKind,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum FileSystemChangeKind {
+    Created,
+    Modified,
+    Removed,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WorktreeMergedNotification {
+    pub branch: String,
+    pub commit: String,
+    pub squash: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WorktreeRemovedNotification {
+    pub path: PathBuf,
+    pub branch: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1309,6 +3048,9 @@ pub struct CommandExecutionRequestApprovalParams {
     pub reason: Option<String>,
     /// Optional model-provided risk assessment describing the blocked command.
     pub risk: Option<SandboxCommandAssessment>,
+    /// `host:port` destinations the sandbox's network allowlist refused.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub network_destinations: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1351,6 +3093,38 @@ pub struct FileChangeRequestApprovalResponse {
     pub decision: ApprovalDecision,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CostLimitRequestApprovalParams {
+    pub thread_id: String,
+    pub turn_id: String,
+    /// Cost of the thread so far in USD.
+    pub session_cost_usd: f64,
+    /// Configured `max_session_cost_usd` budget.
+    pub max_session_cost_usd: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CostLimitRequestAcceptSettings {
+    /// If true, stop asking for the rest of the thread instead of asking
+    /// again after another `max_session_cost_usd` is spent.
+    #[serde(default)]
+    pub for_session: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct CostLimitRequestApprovalResponse {
+    pub decision: ApprovalDecision,
+    /// Optional approval settings for when the decision is `accept`.
+    #[serde(default)]
+    pub accept_settings: Option<CostLimitRequestAcceptSettings>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1358,6 +3132,28 @@ pub struct AccountRateLimitsUpdatedNotification {
     pub rate_limits: RateLimitSnapshot,
 }
 
+/// Sent once each time usage of a window crosses a warning threshold
+/// (75%, 90% and 95%), mirroring the warnings shown by the TUI.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct AccountRateLimitsWarningNotification {
+    pub window: RateLimitWindowKind,
+    pub threshold_percent: i32,
+    /// Human-readable warning, e.g. "Heads up, you have less than 25% of your
+    /// 5h limit left."
+    pub message: String,
+    pub rate_limits: RateLimitSnapshot,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum RateLimitWindowKind {
+    Primary,
+    Secondary,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -752,6 +752,7 @@ impl CodexClient {
             item_id,
             reason,
             risk,
+            network_destinations,
         } = params;
 
         println!(
@@ -763,6 +764,9 @@ impl CodexClient {
         if let Some(risk) = risk.as_ref() {
             println!("< risk assessment: {risk:?}");
         }
+        if !network_destinations.is_empty() {
+            println!("< blocked network: {}", network_destinations.join(", "));
+        }
 
         let response = CommandExecutionRequestApprovalResponse {
             decision: ApprovalDecision::Accept,
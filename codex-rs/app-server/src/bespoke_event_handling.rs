@@ -1,8 +1,12 @@
 use crate::codex_message_processor::ApiVersion;
+use crate::codex_message_processor::ContextWindowStore;
+use crate::codex_message_processor::PendingConfigReloads;
 use crate::codex_message_processor::PendingInterrupts;
 use crate::codex_message_processor::TurnSummary;
 use crate::codex_message_processor::TurnSummaryStore;
+use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
+use crate::plan_board::PlanBoard;
 use codex_app_server_protocol::AccountRateLimitsUpdatedNotification;
 use codex_app_server_protocol::AgentMessageDeltaNotification;
 use codex_app_server_protocol::ApplyPatchApprovalParams;
@@ -14,35 +18,64 @@ use codex_app_server_protocol::CommandExecutionOutputDeltaNotification;
 use codex_app_server_protocol::CommandExecutionRequestApprovalParams;
 use codex_app_server_protocol::CommandExecutionRequestApprovalResponse;
 use codex_app_server_protocol::CommandExecutionStatus;
+use codex_app_server_protocol::ConfigChangedNotification;
+use codex_app_server_protocol::ConfigReloadResponse;
 use codex_app_server_protocol::ContextCompactedNotification;
+use codex_app_server_protocol::CostLimitRequestApprovalParams;
+use codex_app_server_protocol::CostLimitRequestApprovalResponse;
 use codex_app_server_protocol::ErrorNotification;
 use codex_app_server_protocol::ExecCommandApprovalParams;
 use codex_app_server_protocol::ExecCommandApprovalResponse;
 use codex_app_server_protocol::FileChangeRequestApprovalParams;
 use codex_app_server_protocol::FileChangeRequestApprovalResponse;
 use codex_app_server_protocol::FileUpdateChange;
+use codex_app_server_protocol::FollowUpSuggestionsNotification;
 use codex_app_server_protocol::InterruptConversationResponse;
 use codex_app_server_protocol::ItemCompletedNotification;
 use codex_app_server_protocol::ItemStartedNotification;
+use codex_app_server_protocol::ItemVariantsNotification;
+use codex_app_server_protocol::JSONRPCErrorError;
+use codex_app_server_protocol::McpServerDownNotification;
+use codex_app_server_protocol::McpServerRestartedNotification;
 use codex_app_server_protocol::McpToolCallError;
+use codex_app_server_protocol::McpToolCallImage;
 use codex_app_server_protocol::McpToolCallResult;
 use codex_app_server_protocol::McpToolCallStatus;
+use codex_app_server_protocol::MemoryContextInjectedNotification;
 use codex_app_server_protocol::PatchApplyStatus;
 use codex_app_server_protocol::PatchChangeKind as V2PatchChangeKind;
+use codex_app_server_protocol::ProjectDocsLoadedNotification;
 use codex_app_server_protocol::ReasoningSummaryPartAddedNotification;
 use codex_app_server_protocol::ReasoningSummaryTextDeltaNotification;
 use codex_app_server_protocol::ReasoningTextDeltaNotification;
 use codex_app_server_protocol::SandboxCommandAssessment as V2SandboxCommandAssessment;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequestPayload;
+use codex_app_server_protocol::StorageCleanupCompletedNotification;
+use codex_app_server_protocol::StorageCleanupProgressNotification;
+use codex_app_server_protocol::StorageReportReadyNotification;
+use codex_app_server_protocol::SubagentReportNotification;
+use codex_app_server_protocol::SubsystemStartupNotification;
+use codex_app_server_protocol::ThreadContextWindowState;
+use codex_app_server_protocol::ThreadContextWindowStateNotification;
+use codex_app_server_protocol::ThreadEnvUpdatedNotification;
+use codex_app_server_protocol::ThreadForkedNotification;
 use codex_app_server_protocol::ThreadItem;
+use codex_app_server_protocol::ThreadMergeProgressNotification;
+use codex_app_server_protocol::ThreadMergedNotification;
+use codex_app_server_protocol::ThreadPlanReadyNotification;
+use codex_app_server_protocol::ThreadPlanUpdatedNotification;
+use codex_app_server_protocol::ThreadResponseRegeneratedNotification;
 use codex_app_server_protocol::ThreadTokenUsage;
 use codex_app_server_protocol::ThreadTokenUsageUpdatedNotification;
+use codex_app_server_protocol::ThreadUserMessageEditedNotification;
 use codex_app_server_protocol::Turn;
 use codex_app_server_protocol::TurnCompletedNotification;
 use codex_app_server_protocol::TurnDiffUpdatedNotification;
 use codex_app_server_protocol::TurnError;
 use codex_app_server_protocol::TurnInterruptResponse;
+use codex_app_server_protocol::TurnRetryScheduledNotification;
+use codex_app_server_protocol::TurnStalledNotification;
 use codex_app_server_protocol::TurnStatus;
 use codex_core::CodexConversation;
 use codex_core::parse_command::shlex_join;
@@ -56,6 +89,7 @@ use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::SubsystemStartupStatus;
 use codex_core::protocol::TokenCountEvent;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::review_format::format_review_findings_block;
@@ -76,7 +110,10 @@ pub(crate) async fn apply_bespoke_event_handling(
     conversation: Arc<CodexConversation>,
     outgoing: Arc<OutgoingMessageSender>,
     pending_interrupts: PendingInterrupts,
+    pending_config_reloads: PendingConfigReloads,
     turn_summary_store: TurnSummaryStore,
+    context_window_store: ContextWindowStore,
+    plan_board: Arc<PlanBoard>,
     api_version: ApiVersion,
 ) {
     let Event {
@@ -174,6 +211,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             cwd,
             reason,
             risk,
+            network_destinations,
             parsed_cmd,
         }) => match api_version {
             ApiVersion::V1 => {
@@ -210,6 +248,7 @@ pub(crate) async fn apply_bespoke_event_handling(
                     item_id: item_id.clone(),
                     reason,
                     risk: risk.map(V2SandboxCommandAssessment::from),
+                    network_destinations,
                 };
                 let rx = outgoing
                     .send_request(ServerRequestPayload::CommandExecutionRequestApproval(
@@ -240,11 +279,13 @@ pub(crate) async fn apply_bespoke_event_handling(
                 event_turn_id.clone(),
             )
             .await;
+            track_in_progress_item(conversation_id, &notification.item, &turn_summary_store).await;
             outgoing
                 .send_server_notification(ServerNotification::ItemStarted(notification))
                 .await;
         }
         EventMsg::McpToolCallEnd(end_event) => {
+            take_in_progress_item(conversation_id, &end_event.call_id, &turn_summary_store).await;
             let notification = construct_mcp_tool_call_end_notification(
                 end_event,
                 conversation_id.to_string(),
@@ -264,6 +305,95 @@ pub(crate) async fn apply_bespoke_event_handling(
                 .send_server_notification(ServerNotification::AgentMessageDelta(notification))
                 .await;
         }
+        EventMsg::McpServerDown(event) => {
+            let notification = McpServerDownNotification {
+                thread_id: conversation_id.to_string(),
+                server: event.server,
+                reason: event.reason,
+                retrying: event.retrying,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::McpServerDown(notification))
+                .await;
+        }
+        EventMsg::SubsystemStartup(event) => {
+            let (ready, reason) = match event.status {
+                SubsystemStartupStatus::Ready => (true, None),
+                SubsystemStartupStatus::Unavailable { reason } => (false, Some(reason)),
+            };
+            let notification = SubsystemStartupNotification {
+                thread_id: conversation_id.to_string(),
+                subsystem: event.subsystem.into(),
+                ready,
+                reason,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::SubsystemStartup(notification))
+                .await;
+        }
+        EventMsg::ProjectDocsLoaded(event) => {
+            let notification = ProjectDocsLoadedNotification {
+                thread_id: conversation_id.to_string(),
+                docs: event.docs.into_iter().map(Into::into).collect(),
+            };
+            outgoing
+                .send_server_notification(ServerNotification::ProjectDocsLoaded(notification))
+                .await;
+        }
+        EventMsg::McpServerRestarted(event) => {
+            let notification = McpServerRestartedNotification {
+                thread_id: conversation_id.to_string(),
+                server: event.server,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::McpServerRestarted(notification))
+                .await;
+        }
+        EventMsg::ConfigChanged(event) => {
+            let pending = pending_config_reloads.lock().await.remove(&event_turn_id);
+            if let Some(request_id) = pending {
+                let response = ConfigReloadResponse {
+                    applied: event.applied,
+                    requires_new_session: event.requires_new_session,
+                };
+                outgoing.send_response(request_id, response).await;
+            } else {
+                let notification = ConfigChangedNotification {
+                    thread_id: conversation_id.to_string(),
+                    applied: event.applied,
+                    requires_new_session: event.requires_new_session,
+                };
+                outgoing
+                    .send_server_notification(ServerNotification::ConfigChanged(notification))
+                    .await;
+            }
+        }
+        EventMsg::TurnRetryScheduled(event) => {
+            let notification = TurnRetryScheduledNotification {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_turn_id.clone(),
+                attempt: event.attempt,
+                max_attempts: event.max_attempts,
+                delay_ms: event.delay_ms,
+                reason: event.reason,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::TurnRetryScheduled(notification))
+                .await;
+        }
+        EventMsg::StallDetected(event) => {
+            let notification = TurnStalledNotification {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_turn_id.clone(),
+                idle_seconds: event.idle_seconds,
+                last_event: event.last_event,
+                running_tool_calls: event.running_tool_calls,
+                interrupted: event.interrupted,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::TurnStalled(notification))
+                .await;
+        }
         EventMsg::ContextCompacted(..) => {
             let notification = ContextCompactedNotification {
                 thread_id: conversation_id.to_string(),
@@ -272,6 +402,182 @@ pub(crate) async fn apply_bespoke_event_handling(
             outgoing
                 .send_server_notification(ServerNotification::ContextCompacted(notification))
                 .await;
+            update_context_window_state(
+                conversation_id,
+                event_turn_id.clone(),
+                &context_window_store,
+                &outgoing,
+                |state| state.compaction_count += 1,
+            )
+            .await;
+        }
+        EventMsg::ConversationForked(event) => {
+            let notification = ThreadForkedNotification {
+                thread_id: conversation_id.to_string(),
+                forked_thread_id: event.conversation_id.to_string(),
+                rollout_path: event.rollout_path,
+                at_item: event.at_item,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::ThreadForked(notification))
+                .await;
+        }
+        EventMsg::ConversationMergeProgress(event) => {
+            let notification = ThreadMergeProgressNotification {
+                thread_id: conversation_id.to_string(),
+                source_thread_id: event.source_conversation_id.to_string(),
+                stage: event.stage.into(),
+            };
+            outgoing
+                .send_server_notification(ServerNotification::ThreadMergeProgress(notification))
+                .await;
+        }
+        EventMsg::ConversationMerged(event) => {
+            let notification = ThreadMergedNotification {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_turn_id.clone(),
+                item: ThreadItem::merged_context(event_turn_id.clone(), event),
+            };
+            outgoing
+                .send_server_notification(ServerNotification::ThreadMerged(notification))
+                .await;
+        }
+        EventMsg::ResponseRegenerated(event) => {
+            let notification = ThreadResponseRegeneratedNotification {
+                thread_id: conversation_id.to_string(),
+                previous_attempt: event.previous_attempt.into(),
+                removed_messages: event.removed_messages,
+                model: event.model,
+                effort: event.effort,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::ThreadResponseRegenerated(
+                    notification,
+                ))
+                .await;
+        }
+        EventMsg::UserMessageEdited(event) => {
+            let notification = ThreadUserMessageEditedNotification {
+                thread_id: conversation_id.to_string(),
+                at_item: event.at_item,
+                removed_user_messages: event.removed_user_messages,
+                removed_messages: event.removed_messages,
+                unpinned_files: event.unpinned_files,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::ThreadUserMessageEdited(notification))
+                .await;
+        }
+        EventMsg::MessageVariants(event) => {
+            let notification = ItemVariantsNotification {
+                thread_id: conversation_id.to_string(),
+                item_id: event.message_id,
+                variants: event.variants.into_iter().map(Into::into).collect(),
+            };
+            outgoing
+                .send_server_notification(ServerNotification::ItemVariants(notification))
+                .await;
+        }
+        EventMsg::PlanUpdate(args) => {
+            match plan_board.record_update(conversation_id, &args).await {
+                Ok(plan) => {
+                    let notification = ThreadPlanUpdatedNotification {
+                        thread_id: conversation_id.to_string(),
+                        plan,
+                    };
+                    outgoing
+                        .send_server_notification(ServerNotification::ThreadPlanUpdated(
+                            notification,
+                        ))
+                        .await;
+                }
+                Err(err) => error!("failed to save plan for {conversation_id}: {err}"),
+            }
+        }
+        EventMsg::PlanReady(event) => {
+            // The board holds the same steps, with any statuses set by hand.
+            let plan = match event.plan {
+                Some(_) => plan_board
+                    .read(conversation_id)
+                    .await
+                    .unwrap_or_else(|err| {
+                        error!("failed to read plan for {conversation_id}: {err}");
+                        None
+                    }),
+                None => None,
+            };
+            let notification = ThreadPlanReadyNotification {
+                thread_id: conversation_id.to_string(),
+                request: event.request,
+                plan,
+                summary: event.summary,
+                feedback: event.feedback,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::ThreadPlanReady(notification))
+                .await;
+        }
+        EventMsg::SessionEnvUpdated(event) => {
+            let notification = ThreadEnvUpdatedNotification {
+                thread_id: conversation_id.to_string(),
+                names: event.names,
+                persisted_project: event.persisted_project,
+                error: event.error,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::ThreadEnvUpdated(notification))
+                .await;
+        }
+        EventMsg::StorageReport(event) => {
+            let notification = StorageReportReadyNotification {
+                thread_id: conversation_id.to_string(),
+                usage: event.usage.into_iter().map(Into::into).collect(),
+                repos: event.repos.into_iter().map(Into::into).collect(),
+            };
+            outgoing
+                .send_server_notification(ServerNotification::StorageReportReady(notification))
+                .await;
+        }
+        EventMsg::StorageCleanupProgress(event) => {
+            let notification = StorageCleanupProgressNotification {
+                thread_id: conversation_id.to_string(),
+                category: event.category.into(),
+                message: event.message,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::StorageCleanupProgress(notification))
+                .await;
+        }
+        EventMsg::StorageCleanupCompleted(event) => {
+            let notification = StorageCleanupCompletedNotification {
+                thread_id: conversation_id.to_string(),
+                dry_run: event.dry_run,
+                freed: event.freed.into_iter().map(Into::into).collect(),
+                errors: event.errors,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::StorageCleanupCompleted(notification))
+                .await;
+        }
+        EventMsg::MemoryContextInjected(event) => {
+            let notification = MemoryContextInjectedNotification {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_turn_id,
+                memories: event.memories.into_iter().map(Into::into).collect(),
+            };
+            outgoing
+                .send_server_notification(ServerNotification::MemoryContextInjected(notification))
+                .await;
+        }
+        EventMsg::FollowUpSuggestions(event) => {
+            let notification = FollowUpSuggestionsNotification {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_turn_id,
+                suggestions: event.suggestions,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::FollowUpSuggestions(notification))
+                .await;
         }
         EventMsg::ReasoningContentDelta(event) => {
             let notification = ReasoningSummaryTextDeltaNotification {
@@ -307,10 +613,31 @@ pub(crate) async fn apply_bespoke_event_handling(
                 .await;
         }
         EventMsg::TokenCount(token_count_event) => {
+            if let Some(info) = token_count_event.info.clone() {
+                update_context_window_state(
+                    conversation_id,
+                    event_turn_id.clone(),
+                    &context_window_store,
+                    &outgoing,
+                    |state| state.update(&info),
+                )
+                .await;
+            }
             handle_token_count_event(conversation_id, event_turn_id, token_count_event, &outgoing)
                 .await;
         }
         EventMsg::Error(ev) => {
+            // A failed `config/reload` is not a turn error; answer the request.
+            let pending = pending_config_reloads.lock().await.remove(&event_turn_id);
+            if let Some(request_id) = pending {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: ev.message,
+                    data: None,
+                };
+                outgoing.send_error(request_id, error).await;
+                return;
+            }
             let turn_error = TurnError {
                 message: ev.message,
                 codex_error_info: ev.codex_error_info.map(V2CodexErrorInfo::from),
@@ -462,6 +789,7 @@ pub(crate) async fn apply_bespoke_event_handling(
                 exit_code: None,
                 duration_ms: None,
             };
+            track_in_progress_item(conversation_id, &item, &turn_summary_store).await;
             let notification = ItemStartedNotification {
                 thread_id: conversation_id.to_string(),
                 turn_id: event_turn_id.clone(),
@@ -494,6 +822,7 @@ pub(crate) async fn apply_bespoke_event_handling(
                 duration,
                 ..
             } = exec_command_end_event;
+            take_in_progress_item(conversation_id, &call_id, &turn_summary_store).await;
 
             let status = if exit_code == 0 {
                 CommandExecutionStatus::Completed
@@ -534,6 +863,63 @@ pub(crate) async fn apply_bespoke_event_handling(
                 .send_server_notification(ServerNotification::ItemCompleted(notification))
                 .await;
         }
+        EventMsg::ToolCallCancelled(cancelled_event) => {
+            let Some(item) = take_in_progress_item(
+                conversation_id,
+                &cancelled_event.call_id,
+                &turn_summary_store,
+            )
+            .await
+            else {
+                return;
+            };
+            let duration_ms =
+                i64::try_from(cancelled_event.duration.as_millis()).unwrap_or(i64::MAX);
+            let item = match item {
+                ThreadItem::CommandExecution {
+                    id,
+                    command,
+                    cwd,
+                    process_id,
+                    command_actions,
+                    ..
+                } => ThreadItem::CommandExecution {
+                    id,
+                    command,
+                    cwd,
+                    process_id,
+                    status: CommandExecutionStatus::Cancelled,
+                    command_actions,
+                    aggregated_output: None,
+                    exit_code: None,
+                    duration_ms: Some(duration_ms),
+                },
+                ThreadItem::McpToolCall {
+                    id,
+                    server,
+                    tool,
+                    arguments,
+                    ..
+                } => ThreadItem::McpToolCall {
+                    id,
+                    server,
+                    tool,
+                    status: McpToolCallStatus::Cancelled,
+                    arguments,
+                    result: None,
+                    error: None,
+                },
+                other => other,
+            };
+            let notification = ItemCompletedNotification {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_turn_id.clone(),
+                item,
+            };
+            outgoing
+                .send_server_notification(ServerNotification::ItemCompleted(notification))
+                .await;
+        }
         // If this is a TurnAborted, reply to any pending interrupt requests.
         EventMsg::TurnAborted(turn_aborted_event) => {
             let pending = {
@@ -565,6 +951,46 @@ pub(crate) async fn apply_bespoke_event_handling(
             )
             .await;
         }
+        EventMsg::CostLimitApprovalRequest(event) => match api_version {
+            ApiVersion::V1 => {
+                // The legacy API has no budget prompt; stop the turn.
+                if let Err(err) = conversation
+                    .submit(Op::CostLimitApproval {
+                        id: event_turn_id,
+                        decision: ReviewDecision::Denied,
+                    })
+                    .await
+                {
+                    error!("failed to answer cost limit request: {err}");
+                }
+            }
+            ApiVersion::V2 => {
+                let params = CostLimitRequestApprovalParams {
+                    thread_id: conversation_id.to_string(),
+                    turn_id: event_turn_id.clone(),
+                    session_cost_usd: event.session_cost_usd,
+                    max_session_cost_usd: event.max_session_cost_usd,
+                };
+                let rx = outgoing
+                    .send_request(ServerRequestPayload::CostLimitRequestApproval(params))
+                    .await;
+                tokio::spawn(async move {
+                    on_cost_limit_request_approval_response(event_turn_id, rx, conversation).await;
+                });
+            }
+        },
+        EventMsg::SubagentReport(event) => {
+            let notification = SubagentReportNotification {
+                thread_id: conversation_id.to_string(),
+                turn_id: event_turn_id,
+                call_id: event.call_id,
+                agents: event.agents.into_iter().map(Into::into).collect(),
+                conflicts: event.conflicts.into_iter().map(Into::into).collect(),
+            };
+            outgoing
+                .send_server_notification(ServerNotification::SubagentReport(notification))
+                .await;
+        }
         EventMsg::TurnDiff(turn_diff_event) => {
             handle_turn_diff(
                 &event_turn_id,
@@ -679,6 +1105,32 @@ async fn complete_command_execution_item(
         .await;
 }
 
+/// Remembers a started command or tool call so that a later cancellation can
+/// complete it with the same details.
+async fn track_in_progress_item(
+    conversation_id: ConversationId,
+    item: &ThreadItem,
+    turn_summary_store: &TurnSummaryStore,
+) {
+    let item_id = match item {
+        ThreadItem::CommandExecution { id, .. } | ThreadItem::McpToolCall { id, .. } => id.clone(),
+        _ => return,
+    };
+    let mut map = turn_summary_store.lock().await;
+    let summary = map.entry(conversation_id).or_default();
+    summary.in_progress_items.insert(item_id, item.clone());
+}
+
+async fn take_in_progress_item(
+    conversation_id: ConversationId,
+    item_id: &str,
+    turn_summary_store: &TurnSummaryStore,
+) -> Option<ThreadItem> {
+    let mut map = turn_summary_store.lock().await;
+    map.get_mut(&conversation_id)
+        .and_then(|summary| summary.in_progress_items.remove(item_id))
+}
+
 async fn find_and_remove_turn_summary(
     conversation_id: ConversationId,
     turn_summary_store: &TurnSummaryStore,
@@ -721,6 +1173,32 @@ async fn handle_turn_interrupted(
     .await;
 }
 
+/// Apply `update` to the thread's context window state and send the result as
+/// `thread/contextWindowState`.
+async fn update_context_window_state(
+    conversation_id: ConversationId,
+    turn_id: String,
+    context_window_store: &ContextWindowStore,
+    outgoing: &OutgoingMessageSender,
+    update: impl FnOnce(&mut ThreadContextWindowState),
+) {
+    let state = {
+        let mut store = context_window_store.lock().await;
+        let state = store.entry(conversation_id).or_default();
+        update(state);
+        state.clone()
+    };
+    outgoing
+        .send_server_notification(ServerNotification::ThreadContextWindowState(
+            ThreadContextWindowStateNotification {
+                thread_id: conversation_id.to_string(),
+                turn_id,
+                state,
+            },
+        ))
+        .await;
+}
+
 async fn handle_token_count_event(
     conversation_id: ConversationId,
     turn_id: String,
@@ -963,6 +1441,47 @@ async fn on_file_change_request_approval_response(
     }
 }
 
+async fn on_cost_limit_request_approval_response(
+    event_turn_id: String,
+    receiver: oneshot::Receiver<JsonValue>,
+    conversation: Arc<CodexConversation>,
+) {
+    let decision = match receiver.await {
+        Ok(value) => {
+            let response = serde_json::from_value::<CostLimitRequestApprovalResponse>(value)
+                .unwrap_or_else(|err| {
+                    error!("failed to deserialize CostLimitRequestApprovalResponse: {err}");
+                    CostLimitRequestApprovalResponse {
+                        decision: ApprovalDecision::Decline,
+                        accept_settings: None,
+                    }
+                });
+            match (response.decision, response.accept_settings) {
+                (ApprovalDecision::Accept, Some(settings)) if settings.for_session => {
+                    ReviewDecision::ApprovedForSession
+                }
+                (ApprovalDecision::Accept, _) => ReviewDecision::Approved,
+                (ApprovalDecision::Decline, _) => ReviewDecision::Denied,
+                (ApprovalDecision::Cancel, _) => ReviewDecision::Abort,
+            }
+        }
+        Err(err) => {
+            error!("request failed: {err:?}");
+            ReviewDecision::Denied
+        }
+    };
+
+    if let Err(err) = conversation
+        .submit(Op::CostLimitApproval {
+            id: event_turn_id,
+            decision,
+        })
+        .await
+    {
+        error!("failed to submit CostLimitApproval: {err}");
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn on_command_execution_request_approval_response(
     event_turn_id: String,
@@ -1079,6 +1598,12 @@ async fn construct_mcp_tool_call_end_notification(
             Some(McpToolCallResult {
                 content: value.content.clone(),
                 structured_content: value.structured_content.clone(),
+                images: end_event
+                    .images
+                    .iter()
+                    .cloned()
+                    .map(McpToolCallImage::from)
+                    .collect(),
             }),
             None,
         ),
@@ -1364,6 +1889,66 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_context_window_state_keeps_usage_across_compactions() -> Result<()> {
+        let conversation_id = ConversationId::new();
+        let (tx, mut rx) = mpsc::channel(CHANNEL_CAPACITY);
+        let outgoing = Arc::new(OutgoingMessageSender::new(tx));
+        let store: ContextWindowStore = Arc::new(Mutex::new(HashMap::new()));
+
+        let info = TokenUsageInfo {
+            total_token_usage: TokenUsage::default(),
+            last_token_usage: TokenUsage {
+                total_tokens: 60_000,
+                ..Default::default()
+            },
+            model_context_window: Some(112_000),
+        };
+        update_context_window_state(
+            conversation_id,
+            "turn-1".to_string(),
+            &store,
+            &outgoing,
+            |state| state.update(&info),
+        )
+        .await;
+        update_context_window_state(
+            conversation_id,
+            "turn-2".to_string(),
+            &store,
+            &outgoing,
+            |state| state.compaction_count += 1,
+        )
+        .await;
+
+        let mut states = Vec::new();
+        while let Ok(message) = rx.try_recv() {
+            match message {
+                OutgoingMessage::AppServerNotification(
+                    ServerNotification::ThreadContextWindowState(payload),
+                ) => states.push(payload.state),
+                other => bail!("unexpected notification: {other:?}"),
+            }
+        }
+        let usage = ThreadContextWindowState {
+            model_context_window: Some(112_000),
+            tokens_in_context_window: 60_000,
+            percent_remaining: Some(52),
+            compaction_count: 0,
+        };
+        assert_eq!(
+            states,
+            vec![
+                usage.clone(),
+                ThreadContextWindowState {
+                    compaction_count: 1,
+                    ..usage
+                },
+            ]
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_handle_token_count_event_without_usage_info() -> Result<()> {
         let conversation_id = ConversationId::new();
@@ -1602,6 +2187,7 @@ mod tests {
             },
             duration: Duration::from_nanos(92708),
             result: Ok(result),
+            images: Vec::new(),
         };
 
         let thread_id = ConversationId::new().to_string();
@@ -1625,6 +2211,7 @@ mod tests {
                 result: Some(McpToolCallResult {
                     content,
                     structured_content: None,
+                    images: Vec::new(),
                 }),
                 error: None,
             },
@@ -1644,6 +2231,7 @@ mod tests {
             },
             duration: Duration::from_millis(1),
             result: Err("boom".to_string()),
+            images: Vec::new(),
         };
 
         let thread_id = ConversationId::new().to_string();
@@ -1,10 +1,25 @@
 use crate::bespoke_event_handling::apply_bespoke_event_handling;
+use crate::config_cache::ConfigCache;
+use crate::crash_reports;
+use crate::desktop_notifications::DesktopNotifications;
 use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
+use crate::event_journal;
+use crate::event_journal::EventJournal;
 use crate::fuzzy_file_search::run_fuzzy_file_search;
 use crate::models::supported_models;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotification;
+use crate::plan_board;
+use crate::plan_board::PlanBoard;
+use crate::rate_limits::RateLimits;
+use crate::repository_onboarding::run_repository_onboarding;
+use crate::workspace_files::build_file_tree;
+use crate::workspace_files::read_line_range;
+use crate::workspace_files::watch_directory;
+use crate::worktrees;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use chrono::DateTime;
 use chrono::Utc;
 use codex_app_server_protocol::Account;
@@ -15,18 +30,36 @@ use codex_app_server_protocol::AddConversationSubscriptionResponse;
 use codex_app_server_protocol::ArchiveConversationParams;
 use codex_app_server_protocol::ArchiveConversationResponse;
 use codex_app_server_protocol::AskForApproval;
+use codex_app_server_protocol::AudioTranscribeParams;
+use codex_app_server_protocol::AudioTranscribeResponse;
 use codex_app_server_protocol::AuthMode;
 use codex_app_server_protocol::AuthStatusChangeNotification;
 use codex_app_server_protocol::CancelLoginAccountParams;
 use codex_app_server_protocol::CancelLoginAccountResponse;
 use codex_app_server_protocol::CancelLoginChatGptResponse;
 use codex_app_server_protocol::ClientRequest;
+use codex_app_server_protocol::ConfigReloadParams;
 use codex_app_server_protocol::ConversationGitInfo;
 use codex_app_server_protocol::ConversationSummary;
+use codex_app_server_protocol::CrashReportExportParams;
+use codex_app_server_protocol::CrashReportExportResponse;
+use codex_app_server_protocol::CrashReportListResponse;
+use codex_app_server_protocol::DiagnosticsReadResponse;
+use codex_app_server_protocol::DoctorCheck;
+use codex_app_server_protocol::DoctorFinding;
+use codex_app_server_protocol::DoctorReadResponse;
+use codex_app_server_protocol::DoctorSeverity;
+use codex_app_server_protocol::DuelBranch;
 use codex_app_server_protocol::ExecOneOffCommandParams;
 use codex_app_server_protocol::ExecOneOffCommandResponse;
 use codex_app_server_protocol::FeedbackUploadParams;
 use codex_app_server_protocol::FeedbackUploadResponse;
+use codex_app_server_protocol::FileReadParams;
+use codex_app_server_protocol::FileTreeParams;
+use codex_app_server_protocol::FileUnwatchParams;
+use codex_app_server_protocol::FileUnwatchResponse;
+use codex_app_server_protocol::FileWatchParams;
+use codex_app_server_protocol::FileWatchResponse;
 use codex_app_server_protocol::FuzzyFileSearchParams;
 use codex_app_server_protocol::FuzzyFileSearchResponse;
 use codex_app_server_protocol::GetAccountParams;
@@ -38,10 +71,21 @@ use codex_app_server_protocol::GetConversationSummaryParams;
 use codex_app_server_protocol::GetConversationSummaryResponse;
 use codex_app_server_protocol::GetUserAgentResponse;
 use codex_app_server_protocol::GetUserSavedConfigResponse;
+use codex_app_server_protocol::GitBranchParams;
+use codex_app_server_protocol::GitBranchResponse;
+use codex_app_server_protocol::GitCommitParams;
+use codex_app_server_protocol::GitCommitResponse;
 use codex_app_server_protocol::GitDiffToRemoteResponse;
 use codex_app_server_protocol::GitInfo as ApiGitInfo;
+use codex_app_server_protocol::GitStageParams;
+use codex_app_server_protocol::GitStageResponse;
+use codex_app_server_protocol::GitStatusParams;
 use codex_app_server_protocol::InputItem as WireInputItem;
 use codex_app_server_protocol::InterruptConversationParams;
+use codex_app_server_protocol::ItemCancelParams;
+use codex_app_server_protocol::ItemCancelResponse;
+use codex_app_server_protocol::ItemVariantsListParams;
+use codex_app_server_protocol::ItemVariantsListResponse;
 use codex_app_server_protocol::JSONRPCErrorError;
 use codex_app_server_protocol::ListConversationsParams;
 use codex_app_server_protocol::ListConversationsResponse;
@@ -52,17 +96,34 @@ use codex_app_server_protocol::LoginChatGptCompleteNotification;
 use codex_app_server_protocol::LoginChatGptResponse;
 use codex_app_server_protocol::LogoutAccountResponse;
 use codex_app_server_protocol::LogoutChatGptResponse;
+use codex_app_server_protocol::McpServerRestartParams;
+use codex_app_server_protocol::McpServerRestartResponse;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::ModelListResponse;
 use codex_app_server_protocol::NewConversationParams;
 use codex_app_server_protocol::NewConversationResponse;
+use codex_app_server_protocol::ProjectDefaults;
+use codex_app_server_protocol::ProjectDefaultsReadParams;
+use codex_app_server_protocol::ProjectDefaultsReadResponse;
+use codex_app_server_protocol::ProjectDefaultsWriteParams;
+use codex_app_server_protocol::ProjectDefaultsWriteResponse;
+use codex_app_server_protocol::RecoveryStrategy;
 use codex_app_server_protocol::RemoveConversationListenerParams;
 use codex_app_server_protocol::RemoveConversationSubscriptionResponse;
+use codex_app_server_protocol::RepositoryOnboardParams;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ResumeConversationParams;
 use codex_app_server_protocol::ResumeConversationResponse;
 use codex_app_server_protocol::ReviewStartParams;
 use codex_app_server_protocol::ReviewTarget;
+use codex_app_server_protocol::RolloutArchiveParams;
+use codex_app_server_protocol::RolloutArchiveResponse;
+use codex_app_server_protocol::RolloutDeleteParams;
+use codex_app_server_protocol::RolloutDeleteResponse;
+use codex_app_server_protocol::RolloutFile;
+use codex_app_server_protocol::RolloutListParams;
+use codex_app_server_protocol::RolloutListResponse;
+use codex_app_server_protocol::RolloutUsage;
 use codex_app_server_protocol::SandboxMode;
 use codex_app_server_protocol::SendUserMessageParams;
 use codex_app_server_protocol::SendUserMessageResponse;
@@ -72,20 +133,71 @@ use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::SessionConfiguredNotification;
 use codex_app_server_protocol::SetDefaultModelParams;
 use codex_app_server_protocol::SetDefaultModelResponse;
+use codex_app_server_protocol::StorageCleanupParams;
+use codex_app_server_protocol::StorageCleanupResponse;
+use codex_app_server_protocol::StorageReportResponse;
+use codex_app_server_protocol::SubagentCancelParams;
+use codex_app_server_protocol::SubagentCancelResponse;
 use codex_app_server_protocol::Thread;
 use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadArchiveResponse;
+use codex_app_server_protocol::ThreadCompactParams;
+use codex_app_server_protocol::ThreadCompactResponse;
+use codex_app_server_protocol::ThreadContextWindowState;
+use codex_app_server_protocol::ThreadDuelPickParams;
+use codex_app_server_protocol::ThreadDuelPickResponse;
+use codex_app_server_protocol::ThreadDuelStartParams;
+use codex_app_server_protocol::ThreadDuelStartResponse;
+use codex_app_server_protocol::ThreadEditAndResendParams;
+use codex_app_server_protocol::ThreadEditAndResendResponse;
+use codex_app_server_protocol::ThreadEventsReplayParams;
+use codex_app_server_protocol::ThreadEventsReplayResponse;
+use codex_app_server_protocol::ThreadForkParams;
+use codex_app_server_protocol::ThreadForkResponse;
 use codex_app_server_protocol::ThreadItem;
 use codex_app_server_protocol::ThreadListParams;
 use codex_app_server_protocol::ThreadListResponse;
+use codex_app_server_protocol::ThreadMergeParams;
+use codex_app_server_protocol::ThreadMergeResponse;
+use codex_app_server_protocol::ThreadOpenKind;
+use codex_app_server_protocol::ThreadOpenTiming;
+use codex_app_server_protocol::ThreadPlanApproveParams;
+use codex_app_server_protocol::ThreadPlanApproveResponse;
+use codex_app_server_protocol::ThreadPlanReadParams;
+use codex_app_server_protocol::ThreadPlanReadResponse;
+use codex_app_server_protocol::ThreadPlanSendFeedbackParams;
+use codex_app_server_protocol::ThreadPlanSendFeedbackResponse;
+use codex_app_server_protocol::ThreadPlanSetModeParams;
+use codex_app_server_protocol::ThreadPlanSetModeResponse;
+use codex_app_server_protocol::ThreadPlanSetStepStatusParams;
+use codex_app_server_protocol::ThreadPlanSetStepStatusResponse;
+use codex_app_server_protocol::ThreadPlanUpdatedNotification;
+use codex_app_server_protocol::ThreadRecoverParams;
+use codex_app_server_protocol::ThreadRecoverResponse;
+use codex_app_server_protocol::ThreadRegenerateParams;
+use codex_app_server_protocol::ThreadRegenerateResponse;
 use codex_app_server_protocol::ThreadResumeParams;
 use codex_app_server_protocol::ThreadResumeResponse;
+use codex_app_server_protocol::ThreadSetEnvParams;
+use codex_app_server_protocol::ThreadSetEnvResponse;
+use codex_app_server_protocol::ThreadSetFocusParams;
+use codex_app_server_protocol::ThreadSetFocusResponse;
+use codex_app_server_protocol::ThreadSetWebhookParams;
+use codex_app_server_protocol::ThreadSetWebhookResponse;
 use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::ThreadStartResponse;
 use codex_app_server_protocol::ThreadStartedNotification;
+use codex_app_server_protocol::ThreadSuggestionAcceptParams;
+use codex_app_server_protocol::ThreadSuggestionAcceptResponse;
+use codex_app_server_protocol::ThreadTurnsListParams;
+use codex_app_server_protocol::ThreadTurnsListResponse;
+use codex_app_server_protocol::TranscriptionEngine;
 use codex_app_server_protocol::Turn;
 use codex_app_server_protocol::TurnError;
 use codex_app_server_protocol::TurnInterruptParams;
+use codex_app_server_protocol::TurnRevertFileParams;
+use codex_app_server_protocol::TurnRevertParams;
+use codex_app_server_protocol::TurnRevertResponse;
 use codex_app_server_protocol::TurnStartParams;
 use codex_app_server_protocol::TurnStartResponse;
 use codex_app_server_protocol::TurnStartedNotification;
@@ -93,8 +205,14 @@ use codex_app_server_protocol::TurnStatus;
 use codex_app_server_protocol::UserInfoResponse;
 use codex_app_server_protocol::UserInput as V2UserInput;
 use codex_app_server_protocol::UserSavedConfig;
+use codex_app_server_protocol::WorktreeListParams;
+use codex_app_server_protocol::WorktreeMergeParams;
+use codex_app_server_protocol::WorktreeMergedNotification;
+use codex_app_server_protocol::WorktreeRemoveParams;
+use codex_app_server_protocol::WorktreeRemoveResponse;
+use codex_app_server_protocol::WorktreeRemovedNotification;
 use codex_app_server_protocol::build_turns_from_event_msgs;
-use codex_backend_client::Client as BackendClient;
+use codex_app_server_protocol::page_turns;
 use codex_core::AuthManager;
 use codex_core::CodexConversation;
 use codex_core::ConversationManager;
@@ -112,34 +230,47 @@ use codex_core::config::ConfigToml;
 use codex_core::config::edit::ConfigEditsBuilder;
 use codex_core::config_loader::load_config_as_toml;
 use codex_core::default_client::get_codex_user_agent;
+use codex_core::doctor::DoctorCheck as CoreDoctorCheck;
+use codex_core::doctor::DoctorSeverity as CoreDoctorSeverity;
 use codex_core::exec::ExecParams;
 use codex_core::exec_env::create_env;
 use codex_core::features::Feature;
 use codex_core::find_conversation_path_by_id_str;
 use codex_core::git_info::git_diff_to_remote;
+use codex_core::onboarding::DefaultOnboardingBackend;
 use codex_core::parse_cursor;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::StorageCleanupOptions;
+use codex_core::protocol::Submission;
 use codex_core::read_head_for_summary;
+use codex_core::sessions;
+use codex_core::transcription::TranscriptionBackend;
+use codex_core::transcription::TranscriptionError;
+use codex_core::transcription::transcribe;
 use codex_feedback::CodexFeedback;
 use codex_login::ServerOptions as LoginServerOptions;
 use codex_login::ShutdownHandle;
 use codex_login::run_login_server;
 use codex_protocol::ConversationId;
 use codex_protocol::config_types::ForcedLoginMethod;
+use codex_protocol::config_types::PlanDetailPreference;
+use codex_protocol::config_types::WebhookEvent;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::GitInfo as CoreGitInfo;
-use codex_protocol::protocol::RateLimitSnapshot as CoreRateLimitSnapshot;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::USER_MESSAGE_BEGIN;
 use codex_protocol::user_input::UserInput as CoreInputItem;
 use codex_utils_json_to_toml::json_to_toml;
+use notify::RecommendedWatcher;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::ffi::OsStr;
 use std::io::Error as IoError;
 use std::path::Path;
@@ -148,6 +279,7 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
+use std::time::Instant;
 use tokio::select;
 use tokio::sync::Mutex;
 use tokio::sync::oneshot;
@@ -158,16 +290,42 @@ use uuid::Uuid;
 
 type PendingInterruptQueue = Vec<(RequestId, ApiVersion)>;
 pub(crate) type PendingInterrupts = Arc<Mutex<HashMap<ConversationId, PendingInterruptQueue>>>;
+/// `config/reload` requests keyed by the submission id of their
+/// `Op::ReloadConfig`; answered when the matching event arrives.
+pub(crate) type PendingConfigReloads = Arc<Mutex<HashMap<String, RequestId>>>;
 
 /// Per-conversation accumulation of the latest states e.g. error message while a turn runs.
 #[derive(Default, Clone)]
 pub(crate) struct TurnSummary {
     pub(crate) file_change_started: HashSet<String>,
+    /// Commands and MCP tool calls that have started but not finished, by item id.
+    pub(crate) in_progress_items: HashMap<String, ThreadItem>,
     pub(crate) last_error: Option<TurnError>,
 }
 
 pub(crate) type TurnSummaryStore = Arc<Mutex<HashMap<ConversationId, TurnSummary>>>;
 
+/// Latest context window usage per conversation, kept across turns so
+/// `thread/contextWindowState` can also be sent after a compaction.
+pub(crate) type ContextWindowStore = Arc<Mutex<HashMap<ConversationId, ThreadContextWindowState>>>;
+
+/// A `thread/duel/start` waiting for `thread/duel/pick`.
+struct Duel {
+    thread_id: ConversationId,
+    branches: Vec<DuelBranchThread>,
+}
+
+/// One ephemeral fork of a duel, deleted once a winner is picked.
+struct DuelBranchThread {
+    conversation_id: ConversationId,
+    conversation: Arc<CodexConversation>,
+    subscription_id: Uuid,
+    rollout_path: PathBuf,
+}
+
+// Number of thread open timings kept for `diagnostics/read`.
+const MAX_THREAD_OPEN_TIMINGS: usize = 20;
+
 // Duration before a ChatGPT login attempt is abandoned.
 const LOGIN_CHATGPT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
 struct ActiveLogin {
@@ -192,8 +350,24 @@ pub(crate) struct CodexMessageProcessor {
     active_login: Arc<Mutex<Option<ActiveLogin>>>,
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: PendingInterrupts,
+    pending_config_reloads: PendingConfigReloads,
     turn_summary_store: TurnSummaryStore,
+    context_window_store: ContextWindowStore,
+    plan_board: Arc<PlanBoard>,
+    // Threads in plan mode, with the plan detail set by `thread/plan/setMode`.
+    plan_modes: HashMap<ConversationId, Option<PlanDetailPreference>>,
+    event_journal: Arc<EventJournal>,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+    // Active `file/watch` subscriptions; dropping a watcher stops its notifications.
+    file_watchers: HashMap<String, RecommendedWatcher>,
+    config_cache: Arc<ConfigCache>,
+    // Most recent `thread/start` and `thread/resume` timings for `diagnostics/read`.
+    thread_open_timings: VecDeque<ThreadOpenTiming>,
+    // Duels started by `thread/duel/start`, keyed by duel id.
+    duels: HashMap<String, Duel>,
+    rate_limits: Arc<RateLimits>,
+    // Threads reported focused by `thread/setFocus`, and the notification types to send.
+    desktop_notifications: Arc<DesktopNotifications>,
     feedback: CodexFeedback,
 }
 
@@ -234,8 +408,15 @@ impl CodexMessageProcessor {
         outgoing: Arc<OutgoingMessageSender>,
         codex_linux_sandbox_exe: Option<PathBuf>,
         config: Arc<Config>,
+        config_cache: Arc<ConfigCache>,
         feedback: CodexFeedback,
     ) -> Self {
+        let rate_limits = Arc::new(RateLimits::new(
+            Arc::clone(&auth_manager),
+            config.chatgpt_base_url.clone(),
+        ));
+        let desktop_notifications =
+            Arc::new(DesktopNotifications::new(config.gui_notifications.clone()));
         Self {
             auth_manager,
             conversation_manager,
@@ -245,12 +426,29 @@ impl CodexMessageProcessor {
             conversation_listeners: HashMap::new(),
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
+            pending_config_reloads: Arc::new(Mutex::new(HashMap::new())),
             turn_summary_store: Arc::new(Mutex::new(HashMap::new())),
+            context_window_store: Arc::new(Mutex::new(HashMap::new())),
+            plan_board: Arc::new(PlanBoard::new(&config.codex_home)),
+            plan_modes: HashMap::new(),
+            event_journal: Arc::new(EventJournal::new(&config.codex_home)),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
+            file_watchers: HashMap::new(),
+            config_cache,
+            thread_open_timings: VecDeque::new(),
+            duels: HashMap::new(),
+            rate_limits,
+            desktop_notifications,
             feedback,
         }
     }
 
+    /// Starts pushing `account/rateLimits/updated` (and threshold warnings)
+    /// to the client. Called once the client has initialized.
+    pub(crate) fn start_rate_limit_polling(&self) {
+        self.rate_limits.spawn_poller(Arc::clone(&self.outgoing));
+    }
+
     fn review_request_from_target(
         target: ReviewTarget,
         append_to_original_thread: bool,
@@ -354,19 +552,286 @@ impl CodexMessageProcessor {
             ClientRequest::ThreadList { request_id, params } => {
                 self.thread_list(request_id, params).await;
             }
-            ClientRequest::ThreadCompact {
-                request_id,
-                params: _,
-            } => {
-                self.send_unimplemented_error(request_id, "thread/compact")
+            ClientRequest::ThreadTurnsList { request_id, params } => {
+                self.thread_turns_list(request_id, params).await;
+            }
+            ClientRequest::ThreadRecover { request_id, params } => {
+                self.thread_recover(request_id, params).await;
+            }
+            ClientRequest::ThreadDuelStart { request_id, params } => {
+                self.thread_duel_start(request_id, params).await;
+            }
+            ClientRequest::ThreadDuelPick { request_id, params } => {
+                self.thread_duel_pick(request_id, params).await;
+            }
+            ClientRequest::ThreadFork { request_id, params } => {
+                let ThreadForkParams { thread_id, at_item } = params;
+                self.submit_thread_op(
+                    request_id,
+                    thread_id,
+                    Op::ForkConversation { at_item },
+                    ThreadForkResponse {},
+                )
+                .await;
+            }
+            ClientRequest::ThreadMerge { request_id, params } => {
+                self.thread_merge(request_id, params).await;
+            }
+            ClientRequest::ThreadRegenerate { request_id, params } => {
+                let ThreadRegenerateParams {
+                    thread_id,
+                    model,
+                    effort,
+                } = params;
+                self.submit_thread_op(
+                    request_id,
+                    thread_id,
+                    Op::RegenerateLast { model, effort },
+                    ThreadRegenerateResponse {},
+                )
+                .await;
+            }
+            ClientRequest::ThreadEditAndResend { request_id, params } => {
+                let ThreadEditAndResendParams {
+                    thread_id,
+                    at_item,
+                    input,
+                } = params;
+                self.submit_thread_op(
+                    request_id,
+                    thread_id,
+                    Op::EditAndResend {
+                        at_item,
+                        items: input.into_iter().map(V2UserInput::into_core).collect(),
+                    },
+                    ThreadEditAndResendResponse {},
+                )
+                .await;
+            }
+            ClientRequest::ItemVariantsList { request_id, params } => {
+                let ItemVariantsListParams { thread_id, item_id } = params;
+                self.submit_thread_op(
+                    request_id,
+                    thread_id,
+                    Op::GetMessageVariants {
+                        message_id: item_id,
+                    },
+                    ItemVariantsListResponse {},
+                )
+                .await;
+            }
+            ClientRequest::ThreadSetEnv { request_id, params } => {
+                let ThreadSetEnvParams {
+                    thread_id,
+                    env,
+                    persist,
+                } = params;
+                self.submit_thread_op(
+                    request_id,
+                    thread_id,
+                    Op::SetSessionEnv { vars: env, persist },
+                    ThreadSetEnvResponse {},
+                )
+                .await;
+            }
+            ClientRequest::ThreadSetWebhook { request_id, params } => {
+                self.thread_set_webhook(request_id, params).await;
+            }
+            ClientRequest::StorageReport { request_id, params } => {
+                self.submit_thread_op(
+                    request_id,
+                    params.thread_id,
+                    Op::GetStorageReport,
+                    StorageReportResponse {},
+                )
+                .await;
+            }
+            ClientRequest::StorageCleanup { request_id, params } => {
+                let StorageCleanupParams {
+                    thread_id,
+                    delete_rollouts_older_than_days,
+                    prune_checkpoints_older_than_days,
+                    vacuum_memory,
+                    gc_attachments,
+                    dry_run,
+                } = params;
+                self.submit_thread_op(
+                    request_id,
+                    thread_id,
+                    Op::CleanupStorage {
+                        options: StorageCleanupOptions {
+                            delete_rollouts_older_than_days,
+                            prune_checkpoints_older_than_days,
+                            vacuum_memory,
+                            gc_attachments,
+                            dry_run,
+                        },
+                    },
+                    StorageCleanupResponse {},
+                )
+                .await;
+            }
+            ClientRequest::RolloutList { request_id, params } => {
+                self.rollout_list(request_id, params).await;
+            }
+            ClientRequest::RolloutDelete { request_id, params } => {
+                self.rollout_delete(request_id, params).await;
+            }
+            ClientRequest::RolloutArchive { request_id, params } => {
+                self.rollout_archive(request_id, params).await;
+            }
+            ClientRequest::ThreadSuggestionAccept { request_id, params } => {
+                let ThreadSuggestionAcceptParams { thread_id, index } = params;
+                self.submit_thread_op(
+                    request_id,
+                    thread_id,
+                    Op::AcceptSuggestion { index },
+                    ThreadSuggestionAcceptResponse {},
+                )
+                .await;
+            }
+            ClientRequest::ThreadCompact { request_id, params } => {
+                let ThreadCompactParams { thread_id } = params;
+                self.submit_thread_op(request_id, thread_id, Op::Compact, ThreadCompactResponse {})
                     .await;
             }
+            ClientRequest::ThreadPlanRead { request_id, params } => {
+                self.thread_plan_read(request_id, params).await;
+            }
+            ClientRequest::ThreadPlanSetStepStatus { request_id, params } => {
+                self.thread_plan_set_step_status(request_id, params).await;
+            }
+            ClientRequest::ThreadPlanSetMode { request_id, params } => {
+                self.thread_plan_set_mode(request_id, params).await;
+            }
+            ClientRequest::ThreadPlanApprove { request_id, params } => {
+                let ThreadPlanApproveParams { thread_id } = params;
+                if let Some(turn) = self
+                    .start_plan_turn(request_id.clone(), &thread_id, Op::ApprovePlan)
+                    .await
+                {
+                    self.outgoing
+                        .send_response(request_id, ThreadPlanApproveResponse { turn: turn.clone() })
+                        .await;
+                    self.send_turn_started(thread_id, turn).await;
+                }
+            }
+            ClientRequest::ThreadPlanSendFeedback { request_id, params } => {
+                let ThreadPlanSendFeedbackParams {
+                    thread_id,
+                    feedback,
+                } = params;
+                if let Some(turn) = self
+                    .start_plan_turn(request_id.clone(), &thread_id, Op::RevisePlan { feedback })
+                    .await
+                {
+                    self.outgoing
+                        .send_response(
+                            request_id,
+                            ThreadPlanSendFeedbackResponse { turn: turn.clone() },
+                        )
+                        .await;
+                    self.send_turn_started(thread_id, turn).await;
+                }
+            }
+            ClientRequest::ThreadEventsReplay { request_id, params } => {
+                self.thread_events_replay(request_id, params).await;
+            }
             ClientRequest::TurnStart { request_id, params } => {
                 self.turn_start(request_id, params).await;
             }
             ClientRequest::TurnInterrupt { request_id, params } => {
                 self.turn_interrupt(request_id, params).await;
             }
+            ClientRequest::ItemCancel { request_id, params } => {
+                self.item_cancel(request_id, params).await;
+            }
+            ClientRequest::SubagentCancel { request_id, params } => {
+                let SubagentCancelParams {
+                    thread_id,
+                    call_id,
+                    agent_index,
+                } = params;
+                self.submit_thread_op(
+                    request_id,
+                    thread_id,
+                    Op::CancelSubagentTask {
+                        call_id,
+                        agent_index,
+                    },
+                    SubagentCancelResponse {},
+                )
+                .await;
+            }
+            ClientRequest::ThreadSetFocus { request_id, params } => {
+                self.thread_set_focus(request_id, params).await;
+            }
+            ClientRequest::McpServerRestart { request_id, params } => {
+                self.mcp_server_restart(request_id, params).await;
+            }
+            ClientRequest::ConfigReload { request_id, params } => {
+                self.config_reload(request_id, params).await;
+            }
+            ClientRequest::ProjectDefaultsRead { request_id, params } => {
+                self.project_defaults_read(request_id, params).await;
+            }
+            ClientRequest::ProjectDefaultsWrite { request_id, params } => {
+                self.project_defaults_write(request_id, params).await;
+            }
+            ClientRequest::TurnRevert { request_id, params } => {
+                let TurnRevertParams { thread_id, turn_id } = params;
+                self.submit_revert(request_id, thread_id, Op::RevertTurn { turn_id })
+                    .await;
+            }
+            ClientRequest::TurnRevertFile { request_id, params } => {
+                let TurnRevertFileParams {
+                    thread_id,
+                    path,
+                    turn_id,
+                } = params;
+                self.submit_revert(
+                    request_id,
+                    thread_id,
+                    Op::RevertFileChange { path, turn_id },
+                )
+                .await;
+            }
+            ClientRequest::FileTree { request_id, params } => {
+                self.file_tree(request_id, params).await;
+            }
+            ClientRequest::FileRead { request_id, params } => {
+                self.file_read(request_id, params).await;
+            }
+            ClientRequest::FileWatch { request_id, params } => {
+                self.file_watch(request_id, params).await;
+            }
+            ClientRequest::FileUnwatch { request_id, params } => {
+                self.file_unwatch(request_id, params).await;
+            }
+            ClientRequest::WorktreeList { request_id, params } => {
+                self.worktree_list(request_id, params).await;
+            }
+            ClientRequest::WorktreeRemove { request_id, params } => {
+                self.worktree_remove(request_id, params).await;
+            }
+            ClientRequest::WorktreeMerge { request_id, params } => {
+                self.worktree_merge(request_id, params).await;
+            }
+            ClientRequest::GitStatus { request_id, params } => {
+                self.git_status(request_id, params).await;
+            }
+            ClientRequest::GitStage { request_id, params } => {
+                self.git_stage(request_id, params).await;
+            }
+            ClientRequest::GitCommit { request_id, params } => {
+                self.git_commit(request_id, params).await;
+            }
+            ClientRequest::GitBranch { request_id, params } => {
+                self.git_branch(request_id, params).await;
+            }
+            ClientRequest::RepositoryOnboard { request_id, params } => {
+                self.repository_onboard(request_id, params);
+            }
             ClientRequest::ReviewStart { request_id, params } => {
                 self.review_start(request_id, params).await;
             }
@@ -483,21 +948,36 @@ impl CodexMessageProcessor {
             } => {
                 self.get_account_rate_limits(request_id).await;
             }
+            ClientRequest::DiagnosticsRead {
+                request_id,
+                params: _,
+            } => {
+                self.diagnostics_read(request_id).await;
+            }
+            ClientRequest::DoctorRead {
+                request_id,
+                params: _,
+            } => {
+                self.doctor_read(request_id).await;
+            }
+            ClientRequest::CrashReportList {
+                request_id,
+                params: _,
+            } => {
+                self.crash_report_list(request_id).await;
+            }
+            ClientRequest::CrashReportExport { request_id, params } => {
+                self.crash_report_export(request_id, params).await;
+            }
+            ClientRequest::AudioTranscribe { request_id, params } => {
+                self.audio_transcribe(request_id, params);
+            }
             ClientRequest::FeedbackUpload { request_id, params } => {
                 self.upload_feedback(request_id, params).await;
             }
         }
     }
 
-    async fn send_unimplemented_error(&self, request_id: RequestId, method: &str) {
-        let error = JSONRPCErrorError {
-            code: INTERNAL_ERROR_CODE,
-            message: format!("{method} is not implemented yet"),
-            data: None,
-        };
-        self.outgoing.send_error(request_id, error).await;
-    }
-
     async fn login_v2(&mut self, request_id: RequestId, params: LoginAccountParams) {
         match params {
             LoginAccountParams::ApiKey { api_key } => {
@@ -1038,7 +1518,7 @@ impl CodexMessageProcessor {
     }
 
     async fn get_account_rate_limits(&self, request_id: RequestId) {
-        match self.fetch_account_rate_limits().await {
+        match self.rate_limits.fetch().await {
             Ok(rate_limits) => {
                 let response = GetAccountRateLimitsResponse {
                     rate_limits: rate_limits.into(),
@@ -1051,41 +1531,6 @@ impl CodexMessageProcessor {
         }
     }
 
-    async fn fetch_account_rate_limits(&self) -> Result<CoreRateLimitSnapshot, JSONRPCErrorError> {
-        let Some(auth) = self.auth_manager.auth() else {
-            return Err(JSONRPCErrorError {
-                code: INVALID_REQUEST_ERROR_CODE,
-                message: "codex account authentication required to read rate limits".to_string(),
-                data: None,
-            });
-        };
-
-        if auth.mode != AuthMode::ChatGPT {
-            return Err(JSONRPCErrorError {
-                code: INVALID_REQUEST_ERROR_CODE,
-                message: "chatgpt authentication required to read rate limits".to_string(),
-                data: None,
-            });
-        }
-
-        let client = BackendClient::from_auth(self.config.chatgpt_base_url.clone(), &auth)
-            .await
-            .map_err(|err| JSONRPCErrorError {
-                code: INTERNAL_ERROR_CODE,
-                message: format!("failed to construct backend client: {err}"),
-                data: None,
-            })?;
-
-        client
-            .get_rate_limits()
-            .await
-            .map_err(|err| JSONRPCErrorError {
-                code: INTERNAL_ERROR_CODE,
-                message: format!("failed to fetch codex rate limits: {err}"),
-                data: None,
-            })
-    }
-
     async fn get_user_saved_config(&self, request_id: RequestId) {
         let toml_value = match load_config_as_toml(&self.config.codex_home).await {
             Ok(val) => val,
@@ -1261,18 +1706,21 @@ impl CodexMessageProcessor {
             );
         }
 
-        let config = match derive_config_from_params(overrides, Some(cli_overrides)).await {
-            Ok(config) => config,
-            Err(err) => {
-                let error = JSONRPCErrorError {
-                    code: INVALID_REQUEST_ERROR_CODE,
-                    message: format!("error deriving config: {err}"),
-                    data: None,
-                };
-                self.outgoing.send_error(request_id, error).await;
-                return;
-            }
-        };
+        let config =
+            match derive_config_from_params(&self.config_cache, overrides, Some(cli_overrides))
+                .await
+            {
+                Ok(config) => config,
+                Err(err) => {
+                    let error = JSONRPCErrorError {
+                        code: INVALID_REQUEST_ERROR_CODE,
+                        message: format!("error deriving config: {err}"),
+                        data: None,
+                    };
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            };
 
         match self.conversation_manager.new_conversation(config).await {
             Ok(conversation_id) => {
@@ -1301,6 +1749,7 @@ impl CodexMessageProcessor {
     }
 
     async fn thread_start(&mut self, request_id: RequestId, params: ThreadStartParams) {
+        let started_at = Instant::now();
         let overrides = self.build_thread_config_overrides(
             params.model,
             params.model_provider,
@@ -1311,21 +1760,25 @@ impl CodexMessageProcessor {
             params.developer_instructions,
         );
 
-        let config = match derive_config_from_params(overrides, params.config).await {
-            Ok(config) => config,
-            Err(err) => {
-                let error = JSONRPCErrorError {
-                    code: INVALID_REQUEST_ERROR_CODE,
-                    message: format!("error deriving config: {err}"),
-                    data: None,
-                };
-                self.outgoing.send_error(request_id, error).await;
-                return;
-            }
-        };
+        let config =
+            match derive_config_from_params(&self.config_cache, overrides, params.config).await {
+                Ok(config) => config,
+                Err(err) => {
+                    let error = JSONRPCErrorError {
+                        code: INVALID_REQUEST_ERROR_CODE,
+                        message: format!("error deriving config: {err}"),
+                        data: None,
+                    };
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            };
+        let config_ms = elapsed_ms(started_at);
 
+        let session_started_at = Instant::now();
         match self.conversation_manager.new_conversation(config).await {
             Ok(new_conv) => {
+                let session_ms = elapsed_ms(session_started_at);
                 let NewConversation {
                     conversation_id,
                     session_configured,
@@ -1387,6 +1840,14 @@ impl CodexMessageProcessor {
                     );
                 }
 
+                self.record_thread_open(ThreadOpenTiming {
+                    thread_id: conversation_id.to_string(),
+                    kind: ThreadOpenKind::Start,
+                    config_ms,
+                    history_ms: None,
+                    session_ms,
+                    total_ms: elapsed_ms(started_at),
+                });
                 self.outgoing.send_response(request_id, response).await;
 
                 let notif = ThreadStartedNotification { thread };
@@ -1511,134 +1972,682 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
-    async fn thread_resume(&mut self, request_id: RequestId, params: ThreadResumeParams) {
-        let ThreadResumeParams {
+    async fn thread_turns_list(&self, request_id: RequestId, params: ThreadTurnsListParams) {
+        let ThreadTurnsListParams {
             thread_id,
-            history,
-            path,
-            model,
-            model_provider,
-            cwd,
-            approval_policy,
-            sandbox,
-            config: cli_overrides,
-            base_instructions,
-            developer_instructions,
+            before,
+            limit,
         } = params;
 
-        let overrides_requested = model.is_some()
-            || model_provider.is_some()
-            || cwd.is_some()
-            || approval_policy.is_some()
-            || sandbox.is_some()
-            || cli_overrides.is_some()
-            || base_instructions.is_some()
-            || developer_instructions.is_some();
-
-        let config = if overrides_requested {
-            let overrides = self.build_thread_config_overrides(
-                model,
-                model_provider,
-                cwd,
-                approval_policy,
-                sandbox,
-                base_instructions,
-                developer_instructions,
-            );
-            match derive_config_from_params(overrides, cli_overrides).await {
-                Ok(config) => config,
-                Err(err) => {
-                    let error = JSONRPCErrorError {
-                        code: INVALID_REQUEST_ERROR_CODE,
-                        message: format!("error deriving config: {err}"),
-                        data: None,
-                    };
-                    self.outgoing.send_error(request_id, error).await;
-                    return;
-                }
+        let conversation_id = match ConversationId::from_string(&thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
             }
-        } else {
-            self.config.as_ref().clone()
         };
-
-        let conversation_history = if let Some(history) = history {
-            if history.is_empty() {
+        let path = match find_conversation_path_by_id_str(
+            &self.config.codex_home,
+            &conversation_id.to_string(),
+        )
+        .await
+        {
+            Ok(Some(path)) => path,
+            Ok(None) => {
                 self.send_invalid_request_error(
                     request_id,
-                    "history must not be empty".to_string(),
+                    format!("no rollout found for conversation id {conversation_id}"),
                 )
                 .await;
                 return;
             }
-            InitialHistory::Forked(history.into_iter().map(RolloutItem::ResponseItem).collect())
-        } else if let Some(path) = path {
-            match RolloutRecorder::get_rollout_history(&path).await {
-                Ok(initial_history) => initial_history,
-                Err(err) => {
-                    self.send_invalid_request_error(
-                        request_id,
-                        format!("failed to load rollout `{}`: {err}", path.display()),
+            Err(err) => {
+                self.send_internal_error(
+                    request_id,
+                    format!("failed to locate conversation id {conversation_id}: {err}"),
+                )
+                .await;
+                return;
+            }
+        };
+        let history = match RolloutRecorder::get_rollout_history(&path).await {
+            Ok(history) => history,
+            Err(err) => {
+                self.send_internal_error(
+                    request_id,
+                    format!("failed to load rollout `{}`: {err}", path.display()),
+                )
+                .await;
+                return;
+            }
+        };
+
+        let turns = history
+            .get_event_msgs()
+            .as_deref()
+            .map_or_else(Vec::new, build_turns_from_event_msgs);
+        let page_size = limit.unwrap_or(25).max(1) as usize;
+        let Some((data, next_before)) = page_turns(turns, before.as_deref(), page_size) else {
+            self.send_invalid_request_error(
+                request_id,
+                format!(
+                    "thread {conversation_id} has no turn {}",
+                    before.unwrap_or_default()
+                ),
+            )
+            .await;
+            return;
+        };
+
+        let response = ThreadTurnsListResponse { data, next_before };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    /// Gets a stuck thread moving again, typically after `turn/stalled`.
+    async fn thread_recover(&mut self, request_id: RequestId, params: ThreadRecoverParams) {
+        let ThreadRecoverParams {
+            thread_id,
+            strategy,
+        } = params;
+
+        let (conversation_id, conversation) =
+            match self.conversation_from_thread_id(&thread_id).await {
+                Ok(v) => v,
+                Err(error) => {
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            };
+
+        let result = match strategy {
+            RecoveryStrategy::Interrupt => conversation
+                .submit(Op::Interrupt)
+                .await
+                .map(|_| ())
+                .map_err(|err| format!("failed to interrupt thread: {err}")),
+            RecoveryStrategy::RetryTurn => {
+                let events = match RolloutRecorder::get_rollout_history(
+                    &conversation.rollout_path(),
+                )
+                .await
+                {
+                    Ok(history) => history.get_event_msgs().unwrap_or_default(),
+                    Err(err) => {
+                        self.send_internal_error(
+                            request_id,
+                            format!("failed to load rollout for thread {conversation_id}: {err}"),
+                        )
+                        .await;
+                        return;
+                    }
+                };
+                let Some(items) = last_user_input(&events) else {
+                    self.send_invalid_request_error(
+                        request_id,
+                        format!("thread {conversation_id} has no user message to retry"),
                     )
                     .await;
                     return;
+                };
+                // Ops are handled in order, so the retry starts a new turn
+                // once the interrupted one has been aborted.
+                match conversation.submit(Op::Interrupt).await {
+                    Ok(_) => conversation
+                        .submit(Op::UserInput { items })
+                        .await
+                        .map(|_| ())
+                        .map_err(|err| format!("failed to retry turn: {err}")),
+                    Err(err) => Err(format!("failed to interrupt thread: {err}")),
                 }
             }
-        } else {
-            let existing_conversation_id = match ConversationId::from_string(&thread_id) {
-                Ok(id) => id,
-                Err(err) => {
-                    let error = JSONRPCErrorError {
-                        code: INVALID_REQUEST_ERROR_CODE,
-                        message: format!("invalid thread id: {err}"),
-                        data: None,
-                    };
+            RecoveryStrategy::Restart => self.restart_conversation(conversation_id).await,
+        };
+
+        match result {
+            Ok(()) => {
+                self.outgoing
+                    .send_response(request_id, ThreadRecoverResponse {})
+                    .await;
+            }
+            Err(message) => self.send_internal_error(request_id, message).await,
+        }
+    }
+
+    /// Shuts down the conversation and resumes it from its rollout under the
+    /// same id, re-attaching a v2 listener.
+    async fn restart_conversation(
+        &mut self,
+        conversation_id: ConversationId,
+    ) -> Result<(), String> {
+        let Some(conversation) = self
+            .conversation_manager
+            .remove_conversation(&conversation_id)
+            .await
+        else {
+            return Err(format!("conversation not found: {conversation_id}"));
+        };
+        let rollout_path = conversation.rollout_path();
+        if let Err(err) = conversation.submit(Op::Shutdown).await {
+            warn!("failed to submit Shutdown to conversation {conversation_id}: {err}");
+        }
+
+        self.conversation_manager
+            .resume_conversation_from_rollout(
+                self.config.as_ref().clone(),
+                rollout_path,
+                self.auth_manager.clone(),
+            )
+            .await
+            .map_err(|err| format!("failed to restart thread {conversation_id}: {err}"))?;
+        self.attach_conversation_listener(conversation_id, false, ApiVersion::V2)
+            .await
+            .map(|_| ())
+            .map_err(|err| err.message)
+    }
+
+    async fn thread_duel_start(&mut self, request_id: RequestId, params: ThreadDuelStartParams) {
+        let ThreadDuelStartParams {
+            thread_id,
+            input,
+            model_a,
+            model_b,
+        } = params;
+
+        let (conversation_id, conversation) =
+            match self.conversation_from_thread_id(&thread_id).await {
+                Ok(v) => v,
+                Err(error) => {
                     self.outgoing.send_error(request_id, error).await;
                     return;
                 }
             };
-
-            let path = match find_conversation_path_by_id_str(
-                &self.config.codex_home,
-                &existing_conversation_id.to_string(),
-            )
-            .await
+        let rollout_path = conversation.rollout_path();
+        let cwd =
+            match read_summary_from_rollout(&rollout_path, self.config.model_provider_id.as_str())
+                .await
             {
-                Ok(Some(p)) => p,
-                Ok(None) => {
-                    self.send_invalid_request_error(
+                Ok(summary) => summary.cwd,
+                Err(err) => {
+                    self.send_internal_error(
                         request_id,
-                        format!("no rollout found for conversation id {existing_conversation_id}"),
+                        format!("failed to load rollout for thread {conversation_id}: {err}"),
                     )
                     .await;
                     return;
                 }
-                Err(err) => {
-                    self.send_invalid_request_error(
+            };
+
+        // Fork both branches before starting either turn so they race fairly.
+        let mut branches = Vec::new();
+        let mut response_branches = Vec::new();
+        for model in [model_a, model_b] {
+            match self
+                .fork_duel_branch(&rollout_path, cwd.clone(), model.clone())
+                .await
+            {
+                Ok(branch) => {
+                    response_branches.push(DuelBranch {
+                        thread_id: branch.conversation_id.to_string(),
+                        model,
+                    });
+                    branches.push(branch);
+                }
+                Err(message) => {
+                    for branch in branches {
+                        self.discard_duel_branch(branch).await;
+                    }
+                    self.send_internal_error(request_id, message).await;
+                    return;
+                }
+            }
+        }
+
+        let items: Vec<CoreInputItem> = input.into_iter().map(V2UserInput::into_core).collect();
+        for branch in &branches {
+            if let Err(err) = branch
+                .conversation
+                .submit(Op::UserInput {
+                    items: items.clone(),
+                })
+                .await
+            {
+                warn!(
+                    "failed to start duel turn on thread {}: {err}",
+                    branch.conversation_id
+                );
+            }
+        }
+
+        let duel_id = Uuid::new_v4().to_string();
+        self.duels.insert(
+            duel_id.clone(),
+            Duel {
+                thread_id: conversation_id,
+                branches,
+            },
+        );
+        self.outgoing
+            .send_response(
+                request_id,
+                ThreadDuelStartResponse {
+                    duel_id,
+                    branches: response_branches,
+                },
+            )
+            .await;
+    }
+
+    /// Forks the thread at `rollout_path` into an ephemeral thread running
+    /// `model`, with a v2 listener so its turns stream under its own id.
+    async fn fork_duel_branch(
+        &mut self,
+        rollout_path: &Path,
+        cwd: PathBuf,
+        model: String,
+    ) -> Result<DuelBranchThread, String> {
+        let overrides = ConfigOverrides {
+            model: Some(model),
+            cwd: Some(cwd),
+            codex_linux_sandbox_exe: self.codex_linux_sandbox_exe.clone(),
+            ..Default::default()
+        };
+        let config = derive_config_from_params(&self.config_cache, overrides, None)
+            .await
+            .map_err(|err| format!("error deriving config: {err}"))?;
+        let NewConversation {
+            conversation_id,
+            conversation,
+            session_configured,
+        } = self
+            .conversation_manager
+            .fork_conversation_at_end(config, rollout_path.to_path_buf())
+            .await
+            .map_err(|err| format!("error forking thread: {err}"))?;
+        let subscription_id = self
+            .attach_conversation_listener(conversation_id, false, ApiVersion::V2)
+            .await
+            .map_err(|err| err.message)?;
+        Ok(DuelBranchThread {
+            conversation_id,
+            conversation,
+            subscription_id,
+            rollout_path: session_configured.rollout_path,
+        })
+    }
+
+    async fn thread_merge(&mut self, request_id: RequestId, params: ThreadMergeParams) {
+        let ThreadMergeParams {
+            thread_id,
+            source_thread_id,
+        } = params;
+        let source_conversation_id = match ConversationId::from_string(&source_thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(
+                    request_id,
+                    format!("invalid source thread id: {err}"),
+                )
+                .await;
+                return;
+            }
+        };
+        self.submit_thread_op(
+            request_id,
+            thread_id,
+            Op::MergeConversation {
+                source_conversation_id,
+            },
+            ThreadMergeResponse {},
+        )
+        .await;
+    }
+
+    async fn thread_plan_read(&self, request_id: RequestId, params: ThreadPlanReadParams) {
+        let conversation_id = match ConversationId::from_string(&params.thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+        match self.plan_board.read(conversation_id).await {
+            Ok(plan) => {
+                self.outgoing
+                    .send_response(request_id, ThreadPlanReadResponse { plan })
+                    .await;
+            }
+            Err(err) => {
+                self.outgoing
+                    .send_error(request_id, plan_board::internal_error(err))
+                    .await;
+            }
+        }
+    }
+
+    async fn thread_events_replay(&self, request_id: RequestId, params: ThreadEventsReplayParams) {
+        let conversation_id = match ConversationId::from_string(&params.thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+        match self
+            .event_journal
+            .replay_since(conversation_id, params.since_seq)
+            .await
+        {
+            Ok(replay) => {
+                self.outgoing
+                    .send_response(
                         request_id,
-                        format!(
-                            "failed to locate conversation id {existing_conversation_id}: {err}"
-                        ),
+                        ThreadEventsReplayResponse {
+                            events: replay.events,
+                            gap: replay.gap,
+                        },
                     )
                     .await;
+            }
+            Err(err) => {
+                self.outgoing
+                    .send_error(request_id, event_journal::internal_error(err))
+                    .await;
+            }
+        }
+    }
+
+    async fn thread_plan_set_step_status(
+        &self,
+        request_id: RequestId,
+        params: ThreadPlanSetStepStatusParams,
+    ) {
+        let ThreadPlanSetStepStatusParams {
+            thread_id,
+            index,
+            status,
+        } = params;
+        let conversation_id = match ConversationId::from_string(&thread_id) {
+            Ok(id) => id,
+            Err(err) => {
+                self.send_invalid_request_error(request_id, format!("invalid thread id: {err}"))
+                    .await;
+                return;
+            }
+        };
+        match self
+            .plan_board
+            .set_step_status(conversation_id, index as usize, status)
+            .await
+        {
+            Ok(plan) => {
+                self.outgoing
+                    .send_response(
+                        request_id,
+                        ThreadPlanSetStepStatusResponse { plan: plan.clone() },
+                    )
+                    .await;
+                self.outgoing
+                    .send_server_notification(ServerNotification::ThreadPlanUpdated(
+                        ThreadPlanUpdatedNotification { thread_id, plan },
+                    ))
+                    .await;
+            }
+            Err(error) => self.outgoing.send_error(request_id, error).await,
+        }
+    }
+
+    async fn thread_plan_set_mode(
+        &mut self,
+        request_id: RequestId,
+        params: ThreadPlanSetModeParams,
+    ) {
+        let ThreadPlanSetModeParams {
+            thread_id,
+            enabled,
+            detail,
+        } = params;
+        let (conversation_id, conversation) =
+            match self.conversation_from_thread_id(&thread_id).await {
+                Ok(v) => v,
+                Err(error) => {
+                    self.outgoing.send_error(request_id, error).await;
                     return;
                 }
             };
 
-            match RolloutRecorder::get_rollout_history(&path).await {
-                Ok(initial_history) => initial_history,
-                Err(err) => {
-                    self.send_invalid_request_error(
+        if enabled {
+            self.plan_modes.insert(conversation_id, detail);
+        } else if self.plan_modes.remove(&conversation_id).is_some()
+            && let Err(err) = conversation.submit(Op::CancelPlan).await
+        {
+            let error = JSONRPCErrorError {
+                code: INTERNAL_ERROR_CODE,
+                message: format!("failed to cancel plan: {err}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+        self.outgoing
+            .send_response(request_id, ThreadPlanSetModeResponse {})
+            .await;
+    }
+
+    /// Submits a plan review op and returns the turn it starts, or replies
+    /// with the error. Core reports a missing plan as a warning.
+    async fn start_plan_turn(
+        &self,
+        request_id: RequestId,
+        thread_id: &str,
+        op: Op,
+    ) -> Option<Turn> {
+        let (_, conversation) = match self.conversation_from_thread_id(thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return None;
+            }
+        };
+        match conversation.submit(op).await {
+            Ok(turn_id) => Some(Turn {
+                id: turn_id,
+                items: vec![],
+                status: TurnStatus::InProgress,
+            }),
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to start turn: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                None
+            }
+        }
+    }
+
+    async fn send_turn_started(&self, thread_id: String, turn: Turn) {
+        self.outgoing
+            .send_server_notification(ServerNotification::TurnStarted(TurnStartedNotification {
+                thread_id,
+                turn,
+            }))
+            .await;
+    }
+
+    async fn thread_duel_pick(&mut self, request_id: RequestId, params: ThreadDuelPickParams) {
+        let ThreadDuelPickParams {
+            duel_id,
+            winner_thread_id,
+        } = params;
+
+        let Some(duel) = self.duels.get(&duel_id) else {
+            self.send_invalid_request_error(request_id, format!("duel not found: {duel_id}"))
+                .await;
+            return;
+        };
+        let thread_id = duel.thread_id;
+        let Some(winner_path) = duel
+            .branches
+            .iter()
+            .find(|branch| branch.conversation_id.to_string() == winner_thread_id)
+            .map(|branch| branch.rollout_path.clone())
+        else {
+            self.send_invalid_request_error(
+                request_id,
+                format!("thread {winner_thread_id} is not a branch of duel {duel_id}"),
+            )
+            .await;
+            return;
+        };
+
+        let items = match RolloutRecorder::get_rollout_history(&winner_path).await {
+            Ok(history) => duel_turn_items(history.get_rollout_items()),
+            Err(err) => {
+                self.send_internal_error(
+                    request_id,
+                    format!("failed to load rollout for thread {winner_thread_id}: {err}"),
+                )
+                .await;
+                return;
+            }
+        };
+
+        let result = match self.conversation_manager.get_conversation(thread_id).await {
+            Ok(conversation) => conversation
+                .submit(Op::AppendHistory { items })
+                .await
+                .map(|_| ())
+                .map_err(|err| format!("failed to merge duel into thread {thread_id}: {err}")),
+            Err(_) => Err(format!("conversation not found: {thread_id}")),
+        };
+        if let Some(duel) = self.duels.remove(&duel_id) {
+            for branch in duel.branches {
+                self.discard_duel_branch(branch).await;
+            }
+        }
+
+        match result {
+            Ok(()) => {
+                self.outgoing
+                    .send_response(
                         request_id,
-                        format!("failed to load rollout `{}`: {err}", path.display()),
+                        ThreadDuelPickResponse {
+                            thread_id: thread_id.to_string(),
+                        },
                     )
                     .await;
-                    return;
+            }
+            Err(message) => self.send_internal_error(request_id, message).await,
+        }
+    }
+
+    /// Stops a duel branch and deletes its rollout.
+    async fn discard_duel_branch(&mut self, branch: DuelBranchThread) {
+        let DuelBranchThread {
+            conversation_id,
+            conversation,
+            subscription_id,
+            rollout_path,
+        } = branch;
+        if let Some(cancel) = self.conversation_listeners.remove(&subscription_id) {
+            let _ = cancel.send(());
+        }
+        self.conversation_manager
+            .remove_conversation(&conversation_id)
+            .await;
+        if let Err(err) = conversation.submit(Op::Shutdown).await {
+            warn!("failed to submit Shutdown to conversation {conversation_id}: {err}");
+        }
+        if let Err(err) = tokio::fs::remove_file(&rollout_path).await {
+            warn!(
+                "failed to delete duel rollout {}: {err}",
+                rollout_path.display()
+            );
+        }
+    }
+
+    async fn thread_resume(&mut self, request_id: RequestId, params: ThreadResumeParams) {
+        let ThreadResumeParams {
+            thread_id,
+            history,
+            path,
+            model,
+            model_provider,
+            cwd,
+            approval_policy,
+            sandbox,
+            config: cli_overrides,
+            base_instructions,
+            developer_instructions,
+        } = params;
+
+        let overrides_requested = model.is_some()
+            || model_provider.is_some()
+            || cwd.is_some()
+            || approval_policy.is_some()
+            || sandbox.is_some()
+            || cli_overrides.is_some()
+            || base_instructions.is_some()
+            || developer_instructions.is_some();
+
+        let started_at = Instant::now();
+        let overrides = overrides_requested.then(|| {
+            self.build_thread_config_overrides(
+                model,
+                model_provider,
+                cwd,
+                approval_policy,
+                sandbox,
+                base_instructions,
+                developer_instructions,
+            )
+        });
+        // Deriving the config and reading the rollout are independent, so
+        // overlap them.
+        let config_future = async {
+            let started_at = Instant::now();
+            let config = match overrides {
+                Some(overrides) => {
+                    derive_config_from_params(&self.config_cache, overrides, cli_overrides).await
                 }
+                None => Ok(self.config.as_ref().clone()),
+            };
+            (config, elapsed_ms(started_at))
+        };
+        let history_future = async {
+            let started_at = Instant::now();
+            let history = self.load_thread_history(&thread_id, history, path).await;
+            (history, elapsed_ms(started_at))
+        };
+        let ((config, config_ms), (conversation_history, history_ms)) =
+            tokio::join!(config_future, history_future);
+        let config = match config {
+            Ok(config) => config,
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("error deriving config: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        let conversation_history = match conversation_history {
+            Ok(conversation_history) => conversation_history,
+            Err(message) => {
+                self.send_invalid_request_error(request_id, message).await;
+                return;
             }
         };
 
         let fallback_model_provider = config.model_provider_id.clone();
 
+        let session_started_at = Instant::now();
         match self
             .conversation_manager
             .resume_conversation_with_history(
@@ -1653,6 +2662,7 @@ impl CodexMessageProcessor {
                 session_configured,
                 ..
             }) => {
+                let session_ms = elapsed_ms(session_started_at);
                 let SessionConfiguredEvent {
                     rollout_path,
                     initial_messages,
@@ -1693,29 +2703,321 @@ impl CodexMessageProcessor {
                     .as_deref()
                     .map_or_else(Vec::new, build_turns_from_event_msgs);
 
-                let response = ThreadResumeResponse {
-                    thread,
-                    model: session_configured.model,
-                    model_provider: session_configured.model_provider_id,
-                    cwd: session_configured.cwd,
-                    approval_policy: session_configured.approval_policy.into(),
-                    sandbox: session_configured.sandbox_policy.into(),
-                    reasoning_effort: session_configured.reasoning_effort,
+                let response = ThreadResumeResponse {
+                    thread,
+                    model: session_configured.model,
+                    model_provider: session_configured.model_provider_id,
+                    cwd: session_configured.cwd,
+                    approval_policy: session_configured.approval_policy.into(),
+                    sandbox: session_configured.sandbox_policy.into(),
+                    reasoning_effort: session_configured.reasoning_effort,
+                };
+
+                self.record_thread_open(ThreadOpenTiming {
+                    thread_id: conversation_id.to_string(),
+                    kind: ThreadOpenKind::Resume,
+                    config_ms,
+                    history_ms: Some(history_ms),
+                    session_ms,
+                    total_ms: elapsed_ms(started_at),
+                });
+                self.outgoing.send_response(request_id, response).await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("error resuming thread: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    /// Reads the history a thread resumes from: inline `history`, the
+    /// rollout at `path`, or the stored rollout for `thread_id`. Errors are
+    /// messages for an invalid-request reply.
+    async fn load_thread_history(
+        &self,
+        thread_id: &str,
+        history: Option<Vec<ResponseItem>>,
+        path: Option<PathBuf>,
+    ) -> Result<InitialHistory, String> {
+        if let Some(history) = history {
+            if history.is_empty() {
+                return Err("history must not be empty".to_string());
+            }
+            return Ok(InitialHistory::Forked(
+                history.into_iter().map(RolloutItem::ResponseItem).collect(),
+            ));
+        }
+        let path = match path {
+            Some(path) => path,
+            None => {
+                let conversation_id = ConversationId::from_string(thread_id)
+                    .map_err(|err| format!("invalid thread id: {err}"))?;
+                match find_conversation_path_by_id_str(
+                    &self.config.codex_home,
+                    &conversation_id.to_string(),
+                )
+                .await
+                {
+                    Ok(Some(path)) => path,
+                    Ok(None) => {
+                        return Err(format!(
+                            "no rollout found for conversation id {conversation_id}"
+                        ));
+                    }
+                    Err(err) => {
+                        return Err(format!(
+                            "failed to locate conversation id {conversation_id}: {err}"
+                        ));
+                    }
+                }
+            }
+        };
+        RolloutRecorder::get_rollout_history(&path)
+            .await
+            .map_err(|err| format!("failed to load rollout `{}`: {err}", path.display()))
+    }
+
+    fn record_thread_open(&mut self, timing: ThreadOpenTiming) {
+        tracing::debug!(?timing, "thread opened");
+        if self.thread_open_timings.len() == MAX_THREAD_OPEN_TIMINGS {
+            self.thread_open_timings.pop_front();
+        }
+        self.thread_open_timings.push_back(timing);
+    }
+
+    async fn diagnostics_read(&self, request_id: RequestId) {
+        let (config_cache_hits, config_cache_misses) = self.config_cache.stats();
+        let response = DiagnosticsReadResponse {
+            config_cache_hits,
+            config_cache_misses,
+            thread_opens: self.thread_open_timings.iter().cloned().collect(),
+        };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    async fn doctor_read(&self, request_id: RequestId) {
+        let findings = codex_core::doctor::run_checks(&self.config)
+            .await
+            .into_iter()
+            .map(|finding| DoctorFinding {
+                check: match finding.check {
+                    CoreDoctorCheck::Auth => DoctorCheck::Auth,
+                    CoreDoctorCheck::Config => DoctorCheck::Config,
+                    CoreDoctorCheck::Sgrep => DoctorCheck::Sgrep,
+                    CoreDoctorCheck::LockFiles => DoctorCheck::LockFiles,
+                    CoreDoctorCheck::Clock => DoctorCheck::Clock,
+                },
+                severity: match finding.severity {
+                    CoreDoctorSeverity::Warning => DoctorSeverity::Warning,
+                    CoreDoctorSeverity::Error => DoctorSeverity::Error,
+                },
+                summary: finding.summary,
+                fix: finding.fix,
+            })
+            .collect();
+        self.outgoing
+            .send_response(request_id, DoctorReadResponse { findings })
+            .await;
+    }
+
+    async fn crash_report_list(&self, request_id: RequestId) {
+        match crash_reports::list(&self.config.codex_home) {
+            Ok(data) => {
+                self.outgoing
+                    .send_response(request_id, CrashReportListResponse { data })
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to list crash reports: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    async fn crash_report_export(&self, request_id: RequestId, params: CrashReportExportParams) {
+        match crash_reports::export(&self.config.codex_home, &params.id) {
+            Ok((path, contents)) => {
+                self.outgoing
+                    .send_response(request_id, CrashReportExportResponse { path, contents })
+                    .await;
+            }
+            Err(err) => {
+                let code = match err.kind() {
+                    std::io::ErrorKind::InvalidInput | std::io::ErrorKind::NotFound => {
+                        INVALID_REQUEST_ERROR_CODE
+                    }
+                    _ => INTERNAL_ERROR_CODE,
+                };
+                let error = JSONRPCErrorError {
+                    code,
+                    message: format!("failed to export crash report `{}`: {err}", params.id),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    async fn rollout_list(&self, request_id: RequestId, params: RolloutListParams) {
+        let files = match sessions::list_rollouts(&self.config.codex_home) {
+            Ok(files) => files,
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to list rollouts: {err}"))
+                    .await;
+                return;
+            }
+        };
+        let usage = rollout_usage(&files);
+        let data = files
+            .into_iter()
+            .filter(|file| {
+                params
+                    .archived
+                    .is_none_or(|archived| archived == file.archived)
+            })
+            .map(rollout_file_to_v2)
+            .collect();
+        self.outgoing
+            .send_response(request_id, RolloutListResponse { data, usage })
+            .await;
+    }
+
+    async fn rollout_delete(&self, request_id: RequestId, params: RolloutDeleteParams) {
+        if let Err(err) = sessions::delete_rollout(&self.config.codex_home, &params.path) {
+            self.send_rollout_error(request_id, "delete", &params.path, err)
+                .await;
+            return;
+        }
+        if let Some(conversation_id) = sessions::rollout_conversation_id(&params.path) {
+            self.remove_event_journal(conversation_id).await;
+        }
+        match sessions::list_rollouts(&self.config.codex_home) {
+            Ok(files) => {
+                let usage = rollout_usage(&files);
+                self.outgoing
+                    .send_response(request_id, RolloutDeleteResponse { usage })
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(request_id, format!("failed to list rollouts: {err}"))
+                    .await;
+            }
+        }
+    }
+
+    async fn rollout_archive(&self, request_id: RequestId, params: RolloutArchiveParams) {
+        // Compressing a long rollout takes a while; keep it off the runtime.
+        let codex_home = self.config.codex_home.clone();
+        let path = params.path.clone();
+        let archived = tokio::task::spawn_blocking(move || {
+            let archive_path = sessions::archive_rollout(&codex_home, &path)?;
+            let files = sessions::list_rollouts(&codex_home)?;
+            Ok::<_, std::io::Error>((archive_path, files))
+        })
+        .await
+        .unwrap_or_else(|err| Err(std::io::Error::other(err)));
+        match archived {
+            Ok((archive_path, files)) => {
+                if let Some(conversation_id) = sessions::rollout_conversation_id(&params.path) {
+                    self.remove_event_journal(conversation_id).await;
+                }
+                let response = RolloutArchiveResponse {
+                    archive_path,
+                    usage: rollout_usage(&files),
                 };
-
                 self.outgoing.send_response(request_id, response).await;
             }
             Err(err) => {
-                let error = JSONRPCErrorError {
-                    code: INTERNAL_ERROR_CODE,
-                    message: format!("error resuming thread: {err}"),
-                    data: None,
-                };
-                self.outgoing.send_error(request_id, error).await;
+                self.send_rollout_error(request_id, "archive", &params.path, err)
+                    .await;
             }
         }
     }
 
+    /// Drops the event journal of a thread whose rollout was archived or
+    /// deleted.
+    async fn remove_event_journal(&self, conversation_id: ConversationId) {
+        if let Err(err) = self.event_journal.remove(conversation_id).await {
+            warn!("failed to remove event journal of {conversation_id}: {err}");
+        }
+    }
+
+    async fn send_rollout_error(
+        &self,
+        request_id: RequestId,
+        action: &str,
+        path: &Path,
+        err: std::io::Error,
+    ) {
+        let message = format!("failed to {action} rollout `{}`: {err}", path.display());
+        match err.kind() {
+            std::io::ErrorKind::InvalidInput
+            | std::io::ErrorKind::NotFound
+            | std::io::ErrorKind::ResourceBusy => {
+                self.send_invalid_request_error(request_id, message).await;
+            }
+            _ => self.send_internal_error(request_id, message).await,
+        }
+    }
+
+    fn audio_transcribe(&self, request_id: RequestId, params: AudioTranscribeParams) {
+        // Transcription can take seconds, so don't hold up other requests.
+        let outgoing = self.outgoing.clone();
+        let config = self.config.clone();
+        let auth = self.auth_manager.auth();
+        tokio::spawn(async move {
+            let audio = match BASE64_STANDARD.decode(params.audio.as_bytes()) {
+                Ok(audio) => audio,
+                Err(err) => {
+                    let error = JSONRPCErrorError {
+                        code: INVALID_REQUEST_ERROR_CODE,
+                        message: format!("audio is not valid base64: {err}"),
+                        data: None,
+                    };
+                    outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            };
+            match transcribe(&config, auth, &audio).await {
+                Ok(transcript) => {
+                    let engine = match transcript.backend {
+                        TranscriptionBackend::WhisperCpp => TranscriptionEngine::WhisperCpp,
+                        TranscriptionBackend::Api => TranscriptionEngine::Api,
+                    };
+                    let response = AudioTranscribeResponse {
+                        text: transcript.text,
+                        engine,
+                    };
+                    outgoing.send_response(request_id, response).await;
+                }
+                Err(err) => {
+                    let code = match err {
+                        TranscriptionError::NotWav | TranscriptionError::NoEngine => {
+                            INVALID_REQUEST_ERROR_CODE
+                        }
+                        TranscriptionError::WhisperCpp(_) | TranscriptionError::Api(_) => {
+                            INTERNAL_ERROR_CODE
+                        }
+                    };
+                    let error = JSONRPCErrorError {
+                        code,
+                        message: err.to_string(),
+                        data: None,
+                    };
+                    outgoing.send_error(request_id, error).await;
+                }
+            }
+        });
+    }
+
     async fn get_conversation_summary(
         &self,
         request_id: RequestId,
@@ -1977,7 +3279,7 @@ impl CodexMessageProcessor {
                     ..Default::default()
                 };
 
-                derive_config_from_params(overrides, Some(cli_overrides)).await
+                derive_config_from_params(&self.config_cache, overrides, Some(cli_overrides)).await
             }
             None => Ok(self.config.as_ref().clone()),
         };
@@ -2288,6 +3590,9 @@ impl CodexMessageProcessor {
             Ok(())
         }
         .await;
+        if result.is_ok() {
+            self.remove_event_journal(conversation_id).await;
+        }
 
         result.map_err(|err| JSONRPCErrorError {
             code: INTERNAL_ERROR_CODE,
@@ -2423,13 +3728,14 @@ impl CodexMessageProcessor {
     }
 
     async fn turn_start(&self, request_id: RequestId, params: TurnStartParams) {
-        let (_, conversation) = match self.conversation_from_thread_id(&params.thread_id).await {
-            Ok(v) => v,
-            Err(error) => {
-                self.outgoing.send_error(request_id, error).await;
-                return;
-            }
-        };
+        let (conversation_id, conversation) =
+            match self.conversation_from_thread_id(&params.thread_id).await {
+                Ok(v) => v,
+                Err(error) => {
+                    self.outgoing.send_error(request_id, error).await;
+                    return;
+                }
+            };
 
         // Map v2 input items to core input items.
         let mapped_items: Vec<CoreInputItem> = params
@@ -2459,12 +3765,22 @@ impl CodexMessageProcessor {
                 .await;
         }
 
-        // Start the turn by submitting the user input. Return its submission id as turn_id.
-        let turn_id = conversation
-            .submit(Op::UserInput {
+        // In plan mode, text-only input starts a plan for review instead.
+        let op = match (
+            self.plan_modes.get(&conversation_id),
+            plan_request_text(&mapped_items),
+        ) {
+            (Some(detail), Some(request)) => Op::StartPlan {
+                request,
+                detail: *detail,
+            },
+            _ => Op::UserInput {
                 items: mapped_items,
-            })
-            .await;
+            },
+        };
+
+        // Start the turn by submitting the user input. Return its submission id as turn_id.
+        let turn_id = conversation.submit(op).await;
 
         match turn_id {
             Ok(turn_id) => {
@@ -2567,16 +3883,305 @@ impl CodexMessageProcessor {
                 }
             };
 
-        // Record the pending interrupt so we can reply when TurnAborted arrives.
-        {
-            let mut map = self.pending_interrupts.lock().await;
-            map.entry(conversation_id)
-                .or_default()
-                .push((request_id, ApiVersion::V2));
+        // Record the pending interrupt so we can reply when TurnAborted arrives.
+        {
+            let mut map = self.pending_interrupts.lock().await;
+            map.entry(conversation_id)
+                .or_default()
+                .push((request_id, ApiVersion::V2));
+        }
+
+        // Submit the interrupt; we'll respond upon TurnAborted.
+        let _ = conversation.submit(Op::Interrupt).await;
+    }
+
+    async fn item_cancel(&mut self, request_id: RequestId, params: ItemCancelParams) {
+        let ItemCancelParams { thread_id, item_id } = params;
+
+        let (_, conversation) = match self.conversation_from_thread_id(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        // The item completes with status `cancelled` once the tool call stops.
+        match conversation
+            .submit(Op::CancelToolCall { call_id: item_id })
+            .await
+        {
+            Ok(_) => {
+                self.outgoing
+                    .send_response(request_id, ItemCancelResponse {})
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to cancel item: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    /// Submits the restart; the outcome arrives as an `mcpServer/restarted` or
+    /// `mcpServer/down` notification.
+    async fn mcp_server_restart(&mut self, request_id: RequestId, params: McpServerRestartParams) {
+        let McpServerRestartParams { thread_id, name } = params;
+
+        let (_, conversation) = match self.conversation_from_thread_id(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        match conversation.submit(Op::RestartMcpServer { name }).await {
+            Ok(_) => {
+                self.outgoing
+                    .send_response(request_id, McpServerRestartResponse {})
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to restart MCP server: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    /// Asks the thread to re-read `config.toml`; the reply is sent once the
+    /// thread reports what changed.
+    async fn config_reload(&mut self, request_id: RequestId, params: ConfigReloadParams) {
+        let ConfigReloadParams { thread_id } = params;
+        // The caller just changed `config.toml`; new threads should see it too.
+        self.config_cache.invalidate().await;
+
+        let (_, conversation) = match self.conversation_from_thread_id(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        let sub_id = format!("config-reload-{}", Uuid::new_v4());
+        self.pending_config_reloads
+            .lock()
+            .await
+            .insert(sub_id.clone(), request_id.clone());
+        let submission = Submission {
+            id: sub_id.clone(),
+            op: Op::ReloadConfig,
+        };
+        if let Err(err) = conversation.submit_with_id(submission).await {
+            self.pending_config_reloads.lock().await.remove(&sub_id);
+            let error = JSONRPCErrorError {
+                code: INTERNAL_ERROR_CODE,
+                message: format!("failed to reload config: {err}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+        }
+    }
+
+    async fn project_defaults_read(
+        &self,
+        request_id: RequestId,
+        params: ProjectDefaultsReadParams,
+    ) {
+        let ProjectDefaultsReadParams { cwd } = params;
+        let cfg = match self.load_config_toml_for_project(&cwd).await {
+            Ok(cfg) => cfg,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        let defaults = cfg
+            .get_active_project(&cwd)
+            .map(|project| project.defaults)
+            .unwrap_or_default();
+        let response = ProjectDefaultsReadResponse {
+            project: cfg.project_path_for(&cwd),
+            defaults: ProjectDefaults {
+                model: defaults.model,
+                effort: defaults.model_reasoning_effort,
+                approval_policy: defaults.approval_policy.map(AskForApproval::from),
+                sandbox: defaults.sandbox_mode.map(SandboxMode::from),
+            },
+        };
+        self.outgoing.send_response(request_id, response).await;
+    }
+
+    async fn project_defaults_write(
+        &self,
+        request_id: RequestId,
+        params: ProjectDefaultsWriteParams,
+    ) {
+        let ProjectDefaultsWriteParams { cwd, defaults } = params;
+        let cfg = match self.load_config_toml_for_project(&cwd).await {
+            Ok(cfg) => cfg,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        let project = cfg.project_path_for(&cwd);
+        let defaults = codex_core::config::ProjectDefaults {
+            model: defaults.model,
+            model_reasoning_effort: defaults.effort,
+            approval_policy: defaults.approval_policy.map(AskForApproval::to_core),
+            sandbox_mode: defaults.sandbox.map(SandboxMode::to_core),
+        };
+        let result = ConfigEditsBuilder::new(&self.config.codex_home)
+            .set_project_defaults(project.clone(), &defaults)
+            .apply()
+            .await;
+        // Threads started from here on should pick up the new defaults.
+        self.config_cache.invalidate().await;
+        match result {
+            Ok(()) => {
+                self.outgoing
+                    .send_response(request_id, ProjectDefaultsWriteResponse { project })
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to save project defaults: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    /// `config.toml` as saved, for reading or updating the project of `cwd`.
+    async fn load_config_toml_for_project(
+        &self,
+        cwd: &Path,
+    ) -> Result<ConfigToml, JSONRPCErrorError> {
+        if !cwd.is_absolute() {
+            return Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("cwd must be an absolute path: {}", cwd.display()),
+                data: None,
+            });
+        }
+        let toml_value = load_config_as_toml(&self.config.codex_home)
+            .await
+            .map_err(|err| JSONRPCErrorError {
+                code: INTERNAL_ERROR_CODE,
+                message: format!("failed to load config.toml: {err}"),
+                data: None,
+            })?;
+        toml_value.try_into().map_err(|err| JSONRPCErrorError {
+            code: INTERNAL_ERROR_CODE,
+            message: format!("failed to parse config.toml: {err}"),
+            data: None,
+        })
+    }
+
+    async fn thread_set_webhook(&mut self, request_id: RequestId, params: ThreadSetWebhookParams) {
+        let ThreadSetWebhookParams {
+            thread_id,
+            url,
+            events,
+            secret,
+        } = params;
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            self.send_invalid_request_error(
+                request_id,
+                format!("webhook url must be an http or https URL: {url}"),
+            )
+            .await;
+            return;
+        }
+        let events = match events {
+            Some(events) => events.into_iter().map(|event| event.to_core()).collect(),
+            None => vec![
+                WebhookEvent::TurnComplete,
+                WebhookEvent::ApprovalNeeded,
+                WebhookEvent::Error,
+            ],
+        };
+        self.submit_thread_op(
+            request_id,
+            thread_id,
+            Op::SetWebhook {
+                url,
+                events,
+                secret,
+            },
+            ThreadSetWebhookResponse {},
+        )
+        .await;
+    }
+
+    /// Submits an op whose result arrives later as a notification, and
+    /// acknowledges the request with `response` once it is queued.
+    async fn submit_thread_op<T: Serialize>(
+        &mut self,
+        request_id: RequestId,
+        thread_id: String,
+        op: Op,
+        response: T,
+    ) {
+        let (_, conversation) = match self.conversation_from_thread_id(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        match conversation.submit(op).await {
+            Ok(_) => self.outgoing.send_response(request_id, response).await,
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to submit to thread {thread_id}: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    /// Submits a revert op; the restored files are reported as a `fileChange`
+    /// item once the revert finishes.
+    async fn submit_revert(&mut self, request_id: RequestId, thread_id: String, op: Op) {
+        let (_, conversation) = match self.conversation_from_thread_id(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+
+        match conversation.submit(op).await {
+            Ok(_) => {
+                self.outgoing
+                    .send_response(request_id, TurnRevertResponse {})
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to revert: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
         }
-
-        // Submit the interrupt; we'll respond upon TurnAborted.
-        let _ = conversation.submit(Op::Interrupt).await;
     }
 
     async fn add_conversation_listener(
@@ -2652,9 +4257,17 @@ impl CodexMessageProcessor {
         self.conversation_listeners
             .insert(subscription_id, cancel_tx);
 
-        let outgoing_for_task = self.outgoing.clone();
+        // Everything sent for this thread is journaled for `thread/events/replay`.
+        let outgoing_for_task = Arc::new(
+            self.outgoing
+                .for_thread(self.event_journal.clone(), conversation_id),
+        );
         let pending_interrupts = self.pending_interrupts.clone();
+        let pending_config_reloads = self.pending_config_reloads.clone();
         let turn_summary_store = self.turn_summary_store.clone();
+        let context_window_store = self.context_window_store.clone();
+        let plan_board = self.plan_board.clone();
+        let desktop_notifications = self.desktop_notifications.clone();
         let api_version_for_task = api_version;
         tokio::spawn(async move {
             loop {
@@ -2697,7 +4310,6 @@ impl CodexMessageProcessor {
                             "conversationId".to_string(),
                             conversation_id.to_string().into(),
                         );
-
                         outgoing_for_task
                             .send_notification(OutgoingNotification {
                                 method,
@@ -2705,13 +4317,22 @@ impl CodexMessageProcessor {
                             })
                             .await;
 
+                        if let ApiVersion::V2 = api_version_for_task {
+                            desktop_notifications
+                                .on_event(conversation_id, &event.msg, &outgoing_for_task)
+                                .await;
+                        }
+
                         apply_bespoke_event_handling(
                             event.clone(),
                             conversation_id,
                             conversation.clone(),
                             outgoing_for_task.clone(),
                             pending_interrupts.clone(),
+                            pending_config_reloads.clone(),
                             turn_summary_store.clone(),
+                            context_window_store.clone(),
+                            plan_board.clone(),
                             api_version_for_task,
                         )
                         .await;
@@ -2722,6 +4343,23 @@ impl CodexMessageProcessor {
         Ok(subscription_id)
     }
 
+    async fn thread_set_focus(&self, request_id: RequestId, params: ThreadSetFocusParams) {
+        let ThreadSetFocusParams { thread_id, focused } = params;
+        let conversation_id = match self.conversation_from_thread_id(&thread_id).await {
+            Ok((conversation_id, _)) => conversation_id,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        self.desktop_notifications
+            .set_focused(conversation_id, focused)
+            .await;
+        self.outgoing
+            .send_response(request_id, ThreadSetFocusResponse {})
+            .await;
+    }
+
     async fn git_diff_to_origin(&self, request_id: RequestId, cwd: PathBuf) {
         let diff = git_diff_to_remote(&cwd).await;
         match diff {
@@ -2783,6 +4421,325 @@ impl CodexMessageProcessor {
         self.outgoing.send_response(request_id, response).await;
     }
 
+    async fn file_tree(&self, request_id: RequestId, params: FileTreeParams) {
+        let FileTreeParams { path, max_depth } = params;
+        let max_depth = max_depth.map(|depth| depth as usize);
+        let result = tokio::task::spawn_blocking(move || build_file_tree(&path, max_depth)).await;
+        match result {
+            Ok(Ok(response)) => self.outgoing.send_response(request_id, response).await,
+            Ok(Err(err)) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("failed to list file tree: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to list file tree: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    async fn file_read(&self, request_id: RequestId, params: FileReadParams) {
+        let FileReadParams {
+            path,
+            start_line,
+            end_line,
+        } = params;
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => {
+                let response = read_line_range(&contents, start_line, end_line);
+                self.outgoing.send_response(request_id, response).await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("failed to read {}: {err}", path.display()),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    async fn file_watch(&mut self, request_id: RequestId, params: FileWatchParams) {
+        let FileWatchParams { path } = params;
+        let watch_id = Uuid::new_v4().to_string();
+        match watch_directory(&path, watch_id.clone(), self.outgoing.clone()) {
+            Ok(watcher) => {
+                self.file_watchers.insert(watch_id.clone(), watcher);
+                self.outgoing
+                    .send_response(request_id, FileWatchResponse { watch_id })
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INVALID_REQUEST_ERROR_CODE,
+                    message: format!("failed to watch {}: {err}", path.display()),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
+    async fn file_unwatch(&mut self, request_id: RequestId, params: FileUnwatchParams) {
+        let FileUnwatchParams { watch_id } = params;
+        if self.file_watchers.remove(&watch_id).is_some() {
+            self.outgoing
+                .send_response(request_id, FileUnwatchResponse {})
+                .await;
+        } else {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("watch not found: {watch_id}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+        }
+    }
+
+    async fn worktree_list(&self, request_id: RequestId, params: WorktreeListParams) {
+        let WorktreeListParams {
+            cwd,
+            stale_after_days,
+        } = params;
+        let stale_after_days = stale_after_days.unwrap_or(worktrees::DEFAULT_STALE_AFTER_DAYS);
+        let active_paths = self.conversation_manager.active_cwds().await;
+        let result = tokio::task::spawn_blocking(move || {
+            worktrees::list_worktrees(&cwd, stale_after_days, &active_paths)
+        })
+        .await;
+        match result {
+            Ok(Ok(response)) => self.outgoing.send_response(request_id, response).await,
+            Ok(Err(err)) => {
+                self.send_worktree_error(request_id, INVALID_REQUEST_ERROR_CODE, "list", err)
+                    .await;
+            }
+            Err(err) => {
+                self.send_worktree_error(request_id, INTERNAL_ERROR_CODE, "list", err)
+                    .await;
+            }
+        }
+    }
+
+    async fn worktree_remove(&self, request_id: RequestId, params: WorktreeRemoveParams) {
+        let WorktreeRemoveParams {
+            cwd,
+            path,
+            force,
+            delete_branch,
+        } = params;
+        let worktree_path = path.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            worktrees::remove_worktree(&cwd, &worktree_path, force, delete_branch)
+        })
+        .await;
+        match result {
+            Ok(Ok(branch)) => {
+                self.outgoing
+                    .send_response(request_id, WorktreeRemoveResponse {})
+                    .await;
+                self.outgoing
+                    .send_server_notification(ServerNotification::WorktreeRemoved(
+                        WorktreeRemovedNotification { path, branch },
+                    ))
+                    .await;
+            }
+            Ok(Err(err)) => {
+                self.send_worktree_error(request_id, INVALID_REQUEST_ERROR_CODE, "remove", err)
+                    .await;
+            }
+            Err(err) => {
+                self.send_worktree_error(request_id, INTERNAL_ERROR_CODE, "remove", err)
+                    .await;
+            }
+        }
+    }
+
+    async fn worktree_merge(&self, request_id: RequestId, params: WorktreeMergeParams) {
+        let WorktreeMergeParams {
+            cwd,
+            branch,
+            squash,
+            clean_up,
+        } = params;
+        let merge_branch = branch.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            worktrees::merge_worktree(&cwd, &merge_branch, squash, clean_up)
+        })
+        .await;
+        match result {
+            Ok(Ok(response)) => {
+                let merged = WorktreeMergedNotification {
+                    branch: branch.clone(),
+                    commit: response.commit.clone(),
+                    squash,
+                };
+                let removed_path = response.removed_path.clone();
+                self.outgoing.send_response(request_id, response).await;
+                self.outgoing
+                    .send_server_notification(ServerNotification::WorktreeMerged(merged))
+                    .await;
+                if let Some(path) = removed_path {
+                    self.outgoing
+                        .send_server_notification(ServerNotification::WorktreeRemoved(
+                            WorktreeRemovedNotification {
+                                path,
+                                branch: Some(branch),
+                            },
+                        ))
+                        .await;
+                }
+            }
+            Ok(Err(err)) => {
+                self.send_worktree_error(request_id, INVALID_REQUEST_ERROR_CODE, "merge", err)
+                    .await;
+            }
+            Err(err) => {
+                self.send_worktree_error(request_id, INTERNAL_ERROR_CODE, "merge", err)
+                    .await;
+            }
+        }
+    }
+
+    async fn send_worktree_error(
+        &self,
+        request_id: RequestId,
+        code: i64,
+        action: &str,
+        err: impl std::fmt::Display,
+    ) {
+        let error = JSONRPCErrorError {
+            code,
+            message: format!("failed to {action} worktree: {err}"),
+            data: None,
+        };
+        self.outgoing.send_error(request_id, error).await;
+    }
+
+    async fn git_status(&self, request_id: RequestId, params: GitStatusParams) {
+        let GitStatusParams { cwd } = params;
+        let result = tokio::task::spawn_blocking(move || worktrees::git_status(&cwd)).await;
+        match result {
+            Ok(Ok(response)) => self.outgoing.send_response(request_id, response).await,
+            Ok(Err(err)) => {
+                self.send_git_error(
+                    request_id,
+                    INVALID_REQUEST_ERROR_CODE,
+                    "read git status",
+                    err,
+                )
+                .await;
+            }
+            Err(err) => {
+                self.send_git_error(request_id, INTERNAL_ERROR_CODE, "read git status", err)
+                    .await;
+            }
+        }
+    }
+
+    async fn git_stage(&self, request_id: RequestId, params: GitStageParams) {
+        let GitStageParams { cwd, paths } = params;
+        let result =
+            tokio::task::spawn_blocking(move || codex_git::stage_files(&cwd, &paths)).await;
+        match result {
+            Ok(Ok(())) => {
+                self.outgoing
+                    .send_response(request_id, GitStageResponse {})
+                    .await;
+            }
+            Ok(Err(err)) => {
+                self.send_git_error(request_id, INVALID_REQUEST_ERROR_CODE, "stage files", err)
+                    .await;
+            }
+            Err(err) => {
+                self.send_git_error(request_id, INTERNAL_ERROR_CODE, "stage files", err)
+                    .await;
+            }
+        }
+    }
+
+    async fn git_commit(&self, request_id: RequestId, params: GitCommitParams) {
+        let GitCommitParams { cwd, message } = params;
+        if message.trim().is_empty() {
+            self.send_invalid_request_error(request_id, "commit message is empty".to_string())
+                .await;
+            return;
+        }
+        let result =
+            tokio::task::spawn_blocking(move || codex_git::commit_staged(&cwd, &message)).await;
+        match result {
+            Ok(Ok(commit)) => {
+                self.outgoing
+                    .send_response(request_id, GitCommitResponse { commit })
+                    .await;
+            }
+            Ok(Err(err)) => {
+                self.send_git_error(request_id, INVALID_REQUEST_ERROR_CODE, "commit", err)
+                    .await;
+            }
+            Err(err) => {
+                self.send_git_error(request_id, INTERNAL_ERROR_CODE, "commit", err)
+                    .await;
+            }
+        }
+    }
+
+    async fn git_branch(&self, request_id: RequestId, params: GitBranchParams) {
+        let GitBranchParams { cwd, name } = params;
+        let result =
+            tokio::task::spawn_blocking(move || codex_git::create_branch(&cwd, &name)).await;
+        match result {
+            Ok(Ok(())) => {
+                self.outgoing
+                    .send_response(request_id, GitBranchResponse {})
+                    .await;
+            }
+            Ok(Err(err)) => {
+                self.send_git_error(request_id, INVALID_REQUEST_ERROR_CODE, "create branch", err)
+                    .await;
+            }
+            Err(err) => {
+                self.send_git_error(request_id, INTERNAL_ERROR_CODE, "create branch", err)
+                    .await;
+            }
+        }
+    }
+
+    fn repository_onboard(&self, request_id: RequestId, params: RepositoryOnboardParams) {
+        // Indexing can take minutes, so don't hold up other requests.
+        let backend = Arc::new(DefaultOnboardingBackend::new(self.config.memory.clone()));
+        tokio::spawn(run_repository_onboarding(
+            self.outgoing.clone(),
+            request_id,
+            self.config.codex_home.clone(),
+            backend,
+            params,
+        ));
+    }
+
+    async fn send_git_error(
+        &self,
+        request_id: RequestId,
+        code: i64,
+        action: &str,
+        err: impl std::fmt::Display,
+    ) {
+        let error = JSONRPCErrorError {
+            code,
+            message: format!("failed to {action}: {err}"),
+            data: None,
+        };
+        self.outgoing.send_error(request_id, error).await;
+    }
+
     async fn upload_feedback(&self, request_id: RequestId, params: FeedbackUploadParams) {
         let FeedbackUploadParams {
             classification,
@@ -2858,6 +4815,7 @@ impl CodexMessageProcessor {
 }
 
 async fn derive_config_from_params(
+    config_cache: &ConfigCache,
     overrides: ConfigOverrides,
     cli_overrides: Option<std::collections::HashMap<String, serde_json::Value>>,
 ) -> std::io::Result<Config> {
@@ -2867,7 +4825,11 @@ async fn derive_config_from_params(
         .map(|(k, v)| (k, json_to_toml(v)))
         .collect();
 
-    Config::load_with_cli_overrides(cli_overrides, overrides).await
+    config_cache.load(cli_overrides, overrides).await
+}
+
+fn elapsed_ms(started_at: Instant) -> i64 {
+    i64::try_from(started_at.elapsed().as_millis()).unwrap_or(i64::MAX)
 }
 
 async fn read_summary_from_rollout(
@@ -2990,6 +4952,91 @@ fn parse_datetime(timestamp: Option<&str>) -> Option<DateTime<Utc>> {
     })
 }
 
+/// The response items of a duel branch's turn: everything from its last user
+/// message on, since the branch starts with the forked thread's history.
+fn duel_turn_items(rollout_items: Vec<RolloutItem>) -> Vec<ResponseItem> {
+    let mut items: Vec<ResponseItem> = rollout_items
+        .into_iter()
+        .filter_map(|item| match item {
+            RolloutItem::ResponseItem(item) => Some(item),
+            _ => None,
+        })
+        .collect();
+    let start = items
+        .iter()
+        .rposition(|item| {
+            matches!(
+                codex_core::parse_turn_item(item),
+                Some(TurnItem::UserMessage(_))
+            )
+        })
+        .unwrap_or(items.len());
+    items.split_off(start)
+}
+
+/// The input of the most recent user message, for retrying its turn.
+fn last_user_input(events: &[EventMsg]) -> Option<Vec<CoreInputItem>> {
+    events.iter().rev().find_map(|event| {
+        let EventMsg::UserMessage(message) = event else {
+            return None;
+        };
+        let mut items = Vec::new();
+        if !message.message.trim().is_empty() {
+            items.push(CoreInputItem::Text {
+                text: message.message.clone(),
+            });
+        }
+        for image_url in message.images.iter().flatten() {
+            items.push(CoreInputItem::Image {
+                image_url: image_url.clone(),
+            });
+        }
+        (!items.is_empty()).then_some(items)
+    })
+}
+
+/// The request plan mode drafts a plan for, or `None` when the input carries
+/// anything but text, which the TUI also sends as an ordinary turn.
+fn plan_request_text(items: &[CoreInputItem]) -> Option<String> {
+    let mut texts = Vec::with_capacity(items.len());
+    for item in items {
+        let CoreInputItem::Text { text } = item else {
+            return None;
+        };
+        texts.push(text.as_str());
+    }
+    let request = texts.join("\n");
+    (!request.trim().is_empty()).then_some(request)
+}
+
+fn rollout_usage(files: &[sessions::RolloutFile]) -> RolloutUsage {
+    let sessions::StorageUsage {
+        active_files,
+        active_bytes,
+        archived_files,
+        archived_bytes,
+    } = sessions::StorageUsage::from_files(files);
+    RolloutUsage {
+        active_files,
+        active_bytes,
+        archived_files,
+        archived_bytes,
+    }
+}
+
+fn rollout_file_to_v2(file: sessions::RolloutFile) -> RolloutFile {
+    let modified_at = file
+        .modified
+        .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+        .and_then(|since_epoch| i64::try_from(since_epoch.as_secs()).ok());
+    RolloutFile {
+        path: file.path,
+        size_bytes: file.size_bytes,
+        modified_at,
+        archived: file.archived,
+    }
+}
+
 fn summary_to_thread(summary: ConversationSummary) -> Thread {
     let ConversationSummary {
         conversation_id,
@@ -3033,6 +5080,76 @@ mod tests {
     use serde_json::json;
     use tempfile::TempDir;
 
+    #[test]
+    fn last_user_input_returns_the_latest_user_message() {
+        use codex_protocol::protocol::AgentMessageEvent;
+        use codex_protocol::protocol::UserMessageEvent;
+
+        let events = vec![
+            EventMsg::UserMessage(UserMessageEvent {
+                message: "first".to_string(),
+                images: None,
+            }),
+            EventMsg::UserMessage(UserMessageEvent {
+                message: "second".to_string(),
+                images: Some(vec!["data:image/png;base64,AAA".to_string()]),
+            }),
+            EventMsg::AgentMessage(AgentMessageEvent {
+                message: "working on it".to_string(),
+            }),
+        ];
+
+        assert_eq!(
+            last_user_input(&events),
+            Some(vec![
+                CoreInputItem::Text {
+                    text: "second".to_string(),
+                },
+                CoreInputItem::Image {
+                    image_url: "data:image/png;base64,AAA".to_string(),
+                },
+            ])
+        );
+        assert_eq!(last_user_input(&events[2..]), None);
+    }
+
+    #[test]
+    fn duel_turn_items_keeps_the_last_turn() {
+        use codex_protocol::models::ContentItem;
+
+        let message = |role: &str, text: &str| {
+            let content = if role == "user" {
+                ContentItem::InputText {
+                    text: text.to_string(),
+                }
+            } else {
+                ContentItem::OutputText {
+                    text: text.to_string(),
+                }
+            };
+            ResponseItem::Message {
+                id: None,
+                role: role.to_string(),
+                content: vec![content],
+            }
+        };
+        let rollout_items = vec![
+            RolloutItem::ResponseItem(message("user", "earlier question")),
+            RolloutItem::ResponseItem(message("assistant", "earlier answer")),
+            RolloutItem::ResponseItem(message("user", "duel prompt")),
+            RolloutItem::ResponseItem(message("assistant", "branch answer")),
+        ];
+
+        assert_eq!(
+            duel_turn_items(rollout_items),
+            vec![
+                message("user", "duel prompt"),
+                message("assistant", "branch answer"),
+            ]
+        );
+        assert_eq!(duel_turn_items(Vec::new()), Vec::new());
+    }
+
     #[test]
     fn extract_conversation_summary_prefers_plain_user_messages() -> Result<()> {
         let conversation_id = ConversationId::from_string("3f941c35-29b3-493b-b0a4-e25800d9aeb0")?;
@@ -0,0 +1,131 @@
+//! Reuses the config layers read from `$CODEX_HOME` across thread creation.
+//!
+//! Every `thread/start` and `thread/resume` with overrides derives a fresh
+//! `Config` from `config.toml`. Reading and parsing the files is the slow part,
+//! so the parsed layers are cached until `config.toml` changes on disk or the
+//! server writes it through `config/value/write` or `config/batchWrite`.
+//! Per-request overrides are still applied on every call.
+
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+use std::time::SystemTime;
+
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::Config;
+use codex_core::config::ConfigOverrides;
+use codex_core::config_loader::LoadedConfigLayers;
+use codex_core::config_loader::load_config_layers;
+use tokio::sync::Mutex;
+use toml::Value as TomlValue;
+
+pub(crate) struct ConfigCache {
+    codex_home: PathBuf,
+    cached: Mutex<Option<CachedLayers>>,
+    hits: AtomicI64,
+    misses: AtomicI64,
+}
+
+struct CachedLayers {
+    layers: LoadedConfigLayers,
+    /// Modification time of `config.toml` when the layers were read.
+    modified: Option<SystemTime>,
+}
+
+impl ConfigCache {
+    pub(crate) fn new(codex_home: PathBuf) -> Self {
+        Self {
+            codex_home,
+            cached: Mutex::new(None),
+            hits: AtomicI64::new(0),
+            misses: AtomicI64::new(0),
+        }
+    }
+
+    /// Derives a `Config` from the cached layers, reading them again first
+    /// when `config.toml` changed since the last read.
+    pub(crate) async fn load(
+        &self,
+        cli_overrides: Vec<(String, TomlValue)>,
+        overrides: ConfigOverrides,
+    ) -> io::Result<Config> {
+        let modified = tokio::fs::metadata(self.codex_home.join(CONFIG_TOML_FILE))
+            .await
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        let layers = {
+            let mut cached = self.cached.lock().await;
+            match cached.as_ref() {
+                Some(entry) if entry.modified == modified => {
+                    self.hits.fetch_add(1, Ordering::Relaxed);
+                    entry.layers.clone()
+                }
+                _ => {
+                    self.misses.fetch_add(1, Ordering::Relaxed);
+                    let layers = load_config_layers(&self.codex_home).await?;
+                    *cached = Some(CachedLayers {
+                        layers: layers.clone(),
+                        modified,
+                    });
+                    layers
+                }
+            }
+        };
+        Config::load_from_layers(layers, cli_overrides, overrides, self.codex_home.clone())
+    }
+
+    /// Drops the cached layers. Called after the server writes `config.toml`,
+    /// since a write within the file system's timestamp resolution may not
+    /// change the modification time.
+    pub(crate) async fn invalidate(&self) {
+        *self.cached.lock().await = None;
+    }
+
+    /// Cache hits and misses since the server started.
+    pub(crate) fn stats(&self) -> (i64, i64) {
+        (
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn reuses_layers_until_invalidated() {
+        let codex_home = TempDir::new().expect("tempdir");
+        std::fs::write(
+            codex_home.path().join(CONFIG_TOML_FILE),
+            "model = \"gpt-5\"\n",
+        )
+        .expect("write config");
+        let cache = ConfigCache::new(codex_home.path().to_path_buf());
+
+        let config = cache
+            .load(Vec::new(), ConfigOverrides::default())
+            .await
+            .expect("load config");
+        assert_eq!(config.model, "gpt-5");
+        cache
+            .load(Vec::new(), ConfigOverrides::default())
+            .await
+            .expect("load config");
+        assert_eq!(cache.stats(), (1, 1));
+
+        std::fs::write(codex_home.path().join(CONFIG_TOML_FILE), "model = \"o3\"\n")
+            .expect("write config");
+        cache.invalidate().await;
+        let config = cache
+            .load(Vec::new(), ConfigOverrides::default())
+            .await
+            .expect("load config");
+        assert_eq!(config.model, "o3");
+        assert_eq!(cache.stats(), (1, 2));
+    }
+}
@@ -0,0 +1,387 @@
+//! Crash reports for panics and failed background tasks.
+//!
+//! [`install`] registers a panic hook that writes a JSON report to
+//! `$CODEX_HOME/crash-reports/` before the default hook runs. A report holds
+//! the panic message and location, a backtrace, the tail of the in-memory log
+//! buffer, and `config.toml` with secrets redacted. Tasks started with
+//! [`spawn_supervised`] are named in the reports of panics they cause, and
+//! [`record_task_failure`] covers tasks that stop with an error instead.
+//! `crashReport/list` and `crashReport/export` read the reports back so
+//! clients can attach them to bug reports.
+
+use std::backtrace::Backtrace;
+use std::future::Future;
+use std::io;
+use std::panic::PanicHookInfo;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
+
+use chrono::Utc;
+use codex_app_server_protocol::CrashReportKind;
+use codex_app_server_protocol::CrashReportSummary;
+use codex_core::config::Config;
+use codex_feedback::CodexFeedback;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::task::JoinHandle;
+use toml::Value as TomlValue;
+use tracing::error;
+
+const CRASH_REPORTS_DIR: &str = "crash-reports";
+
+/// Oldest reports are deleted once there are more than this many.
+const MAX_REPORTS: usize = 50;
+
+/// Log lines copied from the in-memory buffer into each report.
+const MAX_LOG_LINES: usize = 200;
+
+const REDACTED: &str = "[redacted]";
+
+/// Key segments (split on `_` and `-`) whose values are redacted wherever
+/// they appear, e.g. `api_key` or `bearer-token`.
+const SECRET_KEY_SEGMENTS: &[&str] = &[
+    "key",
+    "token",
+    "secret",
+    "password",
+    "passwd",
+    "auth",
+    "authorization",
+    "cookie",
+    "credentials",
+];
+
+/// Tables whose every value is redacted: environment variables and HTTP
+/// headers routinely carry credentials under arbitrary names.
+const SECRET_TABLES: &[&str] = &["env", "set", "http_headers"];
+
+static REPORTER: OnceLock<CrashReporter> = OnceLock::new();
+
+tokio::task_local! {
+    static SUPERVISED_TASK: &'static str;
+}
+
+struct CrashReporter {
+    dir: PathBuf,
+    feedback: CodexFeedback,
+    config_snapshot: String,
+    next_seq: AtomicU32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CrashReport {
+    id: String,
+    /// Unix timestamp in seconds.
+    created_at: i64,
+    kind: CrashReportKind,
+    version: String,
+    /// Supervised task that panicked or failed, if known.
+    task: Option<String>,
+    thread: Option<String>,
+    message: String,
+    location: Option<String>,
+    backtrace: Option<String>,
+    recent_logs: Vec<String>,
+    /// `config.toml` with secrets replaced by `[redacted]`.
+    config: String,
+}
+
+/// Start writing crash reports for this process. Later calls are ignored.
+pub(crate) fn install(config: &Config, feedback: CodexFeedback) {
+    let reporter = CrashReporter {
+        dir: config.codex_home.join(CRASH_REPORTS_DIR),
+        feedback,
+        config_snapshot: redacted_config(&config.codex_home),
+        next_seq: AtomicU32::new(0),
+    };
+    if REPORTER.set(reporter).is_err() {
+        return;
+    }
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if let Some(reporter) = REPORTER.get() {
+            reporter.record_panic(info);
+        }
+        previous(info);
+    }));
+}
+
+/// Spawn `future` so that a panic inside it is reported under `name`, and
+/// log when it dies.
+pub(crate) fn spawn_supervised<F>(name: &'static str, future: F) -> JoinHandle<()>
+where
+    F: Future<Output = ()> + Send + 'static,
+{
+    let task = tokio::spawn(SUPERVISED_TASK.scope(name, future));
+    tokio::spawn(async move {
+        if let Err(err) = task.await
+            && err.is_panic()
+        {
+            error!("task `{name}` panicked; a crash report was written");
+        }
+    })
+}
+
+/// Report a supervised task that stopped with an error rather than a panic.
+pub(crate) fn record_task_failure(name: &str, message: &str) {
+    if let Some(reporter) = REPORTER.get() {
+        reporter.write(
+            CrashReportKind::TaskFailure,
+            Some(name.to_string()),
+            message,
+            None,
+        );
+    }
+}
+
+impl CrashReporter {
+    fn record_panic(&self, info: &PanicHookInfo<'_>) {
+        let message = if let Some(message) = info.payload().downcast_ref::<&str>() {
+            (*message).to_string()
+        } else if let Some(message) = info.payload().downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "panic with a non-string payload".to_string()
+        };
+        let task = SUPERVISED_TASK.try_with(|name| (*name).to_string()).ok();
+        let location = info
+            .location()
+            .map(|location| format!("{}:{}", location.file(), location.line()));
+        self.write(CrashReportKind::Panic, task, &message, location);
+    }
+
+    fn write(
+        &self,
+        kind: CrashReportKind,
+        task: Option<String>,
+        message: &str,
+        location: Option<String>,
+    ) {
+        let now = Utc::now();
+        let seq = self.next_seq.fetch_add(1, Ordering::Relaxed);
+        let report = CrashReport {
+            id: format!(
+                "{}-{}-{seq}",
+                now.format("%Y%m%dT%H%M%SZ"),
+                std::process::id()
+            ),
+            created_at: now.timestamp(),
+            kind,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            task,
+            thread: std::thread::current().name().map(str::to_string),
+            message: message.to_string(),
+            location,
+            backtrace: match kind {
+                CrashReportKind::Panic => Some(Backtrace::force_capture().to_string()),
+                CrashReportKind::TaskFailure => None,
+            },
+            recent_logs: self.feedback.recent_lines(MAX_LOG_LINES),
+            config: self.config_snapshot.clone(),
+        };
+        // Nothing sensible can be done if this fails while already crashing.
+        let _ = write_report(&self.dir, &report);
+    }
+}
+
+fn write_report(dir: &Path, report: &CrashReport) -> io::Result<()> {
+    std::fs::create_dir_all(dir)?;
+    let json = serde_json::to_string_pretty(report).map_err(io::Error::other)?;
+    std::fs::write(dir.join(format!("{}.json", report.id)), json)?;
+    prune(dir)
+}
+
+/// Delete the oldest reports beyond [`MAX_REPORTS`].
+fn prune(dir: &Path) -> io::Result<()> {
+    let mut paths = report_paths(dir)?;
+    if paths.len() <= MAX_REPORTS {
+        return Ok(());
+    }
+    // Ids start with a sortable UTC timestamp.
+    paths.sort();
+    for path in &paths[..paths.len() - MAX_REPORTS] {
+        std::fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+fn report_paths(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut paths = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "json") {
+            paths.push(path);
+        }
+    }
+    Ok(paths)
+}
+
+/// Stored reports, newest first. Files that do not parse are skipped.
+pub(crate) fn list(codex_home: &Path) -> io::Result<Vec<CrashReportSummary>> {
+    let mut summaries = Vec::new();
+    for path in report_paths(&codex_home.join(CRASH_REPORTS_DIR))? {
+        let Ok(text) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(report) = serde_json::from_str::<CrashReport>(&text) else {
+            continue;
+        };
+        summaries.push(CrashReportSummary {
+            id: report.id,
+            created_at: report.created_at,
+            kind: report.kind,
+            task: report.task,
+            message: report.message,
+        });
+    }
+    summaries.sort_by(|a, b| b.id.cmp(&a.id));
+    Ok(summaries)
+}
+
+/// Path and contents of the report with `id`, ready to attach to a bug.
+pub(crate) fn export(codex_home: &Path, id: &str) -> io::Result<(PathBuf, String)> {
+    let valid = !id.is_empty()
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid crash report id `{id}`"),
+        ));
+    }
+    let path = codex_home
+        .join(CRASH_REPORTS_DIR)
+        .join(format!("{id}.json"));
+    let contents = std::fs::read_to_string(&path)?;
+    Ok((path, contents))
+}
+
+fn redacted_config(codex_home: &Path) -> String {
+    let Ok(text) = std::fs::read_to_string(codex_home.join("config.toml")) else {
+        return String::new();
+    };
+    match toml::from_str::<TomlValue>(&text) {
+        Ok(mut value) => {
+            redact(&mut value);
+            toml::to_string(&value).unwrap_or_default()
+        }
+        Err(_) => "# config.toml did not parse; omitted".to_string(),
+    }
+}
+
+fn redact(value: &mut TomlValue) {
+    match value {
+        TomlValue::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                if is_secret_key(key) {
+                    *value = TomlValue::String(REDACTED.to_string());
+                } else if SECRET_TABLES.contains(&key.as_str()) {
+                    redact_all(value);
+                } else {
+                    redact(value);
+                }
+            }
+        }
+        TomlValue::Array(items) => items.iter_mut().for_each(redact),
+        _ => {}
+    }
+}
+
+fn redact_all(value: &mut TomlValue) {
+    match value {
+        TomlValue::Table(table) => table.values_mut().for_each(redact_all),
+        TomlValue::Array(items) => items.iter_mut().for_each(redact_all),
+        other => *other = TomlValue::String(REDACTED.to_string()),
+    }
+}
+
+fn is_secret_key(key: &str) -> bool {
+    key.to_ascii_lowercase()
+        .split(['_', '-'])
+        .any(|segment| SECRET_KEY_SEGMENTS.contains(&segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn redacts_secrets_in_config() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            dir.path().join("config.toml"),
+            r#"
+model = "gpt-5"
+max_output_tokens = 100
+
+[model_providers.azure]
+env_key = "AZURE_API_KEY"
+http_headers = { "X-Team" = "search" }
+
+[mcp_servers.docs]
+command = "docs-mcp"
+env = { DOCS_TOKEN = "abc" }
+"#,
+        )
+        .expect("write config");
+
+        let value: TomlValue = toml::from_str(&redacted_config(dir.path())).expect("toml");
+        assert_eq!(value["model"].as_str(), Some("gpt-5"));
+        assert_eq!(value["max_output_tokens"].as_integer(), Some(100));
+        let azure = &value["model_providers"]["azure"];
+        assert_eq!(azure["env_key"].as_str(), Some(REDACTED));
+        assert_eq!(azure["http_headers"]["X-Team"].as_str(), Some(REDACTED));
+        let docs = &value["mcp_servers"]["docs"];
+        assert_eq!(docs["command"].as_str(), Some("docs-mcp"));
+        assert_eq!(docs["env"]["DOCS_TOKEN"].as_str(), Some(REDACTED));
+    }
+
+    #[test]
+    fn lists_newest_first_and_exports_by_id() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let dir = codex_home.path().join(CRASH_REPORTS_DIR);
+        for (id, kind) in [
+            ("20260101T000000Z-1-0", CrashReportKind::Panic),
+            ("20260102T000000Z-1-0", CrashReportKind::TaskFailure),
+        ] {
+            let report = CrashReport {
+                id: id.to_string(),
+                created_at: 0,
+                kind,
+                version: "0.0.0".to_string(),
+                task: Some("processor".to_string()),
+                thread: None,
+                message: "boom".to_string(),
+                location: None,
+                backtrace: None,
+                recent_logs: Vec::new(),
+                config: String::new(),
+            };
+            write_report(&dir, &report).expect("write report");
+        }
+
+        let ids: Vec<String> = list(codex_home.path())
+            .expect("list")
+            .into_iter()
+            .map(|summary| summary.id)
+            .collect();
+        assert_eq!(ids, vec!["20260102T000000Z-1-0", "20260101T000000Z-1-0"]);
+
+        let (path, contents) = export(codex_home.path(), "20260101T000000Z-1-0").expect("export");
+        assert_eq!(path, dir.join("20260101T000000Z-1-0.json"));
+        assert!(contents.contains("\"message\": \"boom\""));
+        assert!(export(codex_home.path(), "../config").is_err());
+    }
+}
@@ -0,0 +1,178 @@
+//! `thread/desktopNotification` for GUI clients.
+//!
+//! Clients report which threads are in front of the user with
+//! `thread/setFocus`. When a turn completes, an approval is requested, or a
+//! turn fails in any other thread, the server asks the client to show an OS
+//! notification, filtered by `gui.notifications` in `config.toml`.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use codex_app_server_protocol::DesktopNotificationKind;
+use codex_app_server_protocol::ServerNotification;
+use codex_app_server_protocol::ThreadDesktopNotification;
+use codex_core::config::types::Notifications;
+use codex_core::protocol::EventMsg;
+use codex_protocol::ConversationId;
+use tokio::sync::Mutex;
+
+use crate::outgoing_message::OutgoingMessageSender;
+
+const PREVIEW_MAX_CHARS: usize = 80;
+
+pub(crate) struct DesktopNotifications {
+    settings: Notifications,
+    focused: Mutex<HashSet<ConversationId>>,
+}
+
+impl DesktopNotifications {
+    pub(crate) fn new(settings: Notifications) -> Self {
+        Self {
+            settings,
+            focused: Mutex::new(HashSet::new()),
+        }
+    }
+
+    pub(crate) async fn set_focused(&self, conversation_id: ConversationId, focused: bool) {
+        let mut threads = self.focused.lock().await;
+        if focused {
+            threads.insert(conversation_id);
+        } else {
+            threads.remove(&conversation_id);
+        }
+    }
+
+    /// Sends a notification for `msg` unless the thread is focused or its
+    /// type is turned off.
+    pub(crate) async fn on_event(
+        &self,
+        conversation_id: ConversationId,
+        msg: &EventMsg,
+        outgoing: &Arc<OutgoingMessageSender>,
+    ) {
+        let Some((kind, message)) = notification_for(msg) else {
+            return;
+        };
+        if !self.settings.allows(config_name(kind))
+            || self.focused.lock().await.contains(&conversation_id)
+        {
+            return;
+        }
+        outgoing
+            .send_server_notification(ServerNotification::ThreadDesktopNotification(
+                ThreadDesktopNotification {
+                    thread_id: conversation_id.to_string(),
+                    kind,
+                    message,
+                },
+            ))
+            .await;
+    }
+}
+
+/// The type name used by `gui.notifications`, shared with `tui.notifications`.
+fn config_name(kind: DesktopNotificationKind) -> &'static str {
+    match kind {
+        DesktopNotificationKind::TurnComplete => "agent-turn-complete",
+        DesktopNotificationKind::ApprovalRequested => "approval-requested",
+        DesktopNotificationKind::Error => "error",
+    }
+}
+
+fn notification_for(msg: &EventMsg) -> Option<(DesktopNotificationKind, String)> {
+    match msg {
+        EventMsg::TaskComplete(event) => {
+            let message = event
+                .last_agent_message
+                .as_deref()
+                .and_then(preview)
+                .unwrap_or_else(|| "Agent turn complete".to_string());
+            Some((DesktopNotificationKind::TurnComplete, message))
+        }
+        EventMsg::ExecApprovalRequest(event) => {
+            let command = preview(&event.command.join(" ")).unwrap_or_default();
+            Some((
+                DesktopNotificationKind::ApprovalRequested,
+                format!("Approval requested: {command}"),
+            ))
+        }
+        EventMsg::ApplyPatchApprovalRequest(event) => {
+            let target = match event.changes.keys().next() {
+                Some(path) if event.changes.len() == 1 => path.display().to_string(),
+                _ => format!("{} files", event.changes.len()),
+            };
+            Some((
+                DesktopNotificationKind::ApprovalRequested,
+                format!("Codex wants to edit {target}"),
+            ))
+        }
+        EventMsg::ElicitationRequest(event) => Some((
+            DesktopNotificationKind::ApprovalRequested,
+            format!("Approval requested by {}", event.server_name),
+        )),
+        EventMsg::Error(event) => {
+            let message = preview(&event.message).unwrap_or_default();
+            Some((DesktopNotificationKind::Error, format!("Error: {message}")))
+        }
+        _ => None,
+    }
+}
+
+/// `text` on one line, cut to [`PREVIEW_MAX_CHARS`]; `None` when blank.
+fn preview(text: &str) -> Option<String> {
+    let line = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if line.is_empty() {
+        return None;
+    }
+    if line.chars().count() <= PREVIEW_MAX_CHARS {
+        return Some(line);
+    }
+    let cut: String = line.chars().take(PREVIEW_MAX_CHARS - 1).collect();
+    Some(format!("{}…", cut.trim_end()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ErrorEvent;
+    use codex_core::protocol::TaskCompleteEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn turn_completion_previews_the_last_message() {
+        let msg = EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: Some("Done.\n\nAll tests pass.".to_string()),
+        });
+        assert_eq!(
+            notification_for(&msg),
+            Some((
+                DesktopNotificationKind::TurnComplete,
+                "Done. All tests pass.".to_string()
+            ))
+        );
+
+        let msg = EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: None,
+        });
+        assert_eq!(
+            notification_for(&msg),
+            Some((
+                DesktopNotificationKind::TurnComplete,
+                "Agent turn complete".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn long_errors_are_cut_short() {
+        let msg = EventMsg::Error(ErrorEvent {
+            message: "x".repeat(200),
+            codex_error_info: None,
+        });
+        let Some((kind, message)) = notification_for(&msg) else {
+            panic!("errors are notified");
+        };
+        assert_eq!(kind, DesktopNotificationKind::Error);
+        assert_eq!(message, format!("Error: {}…", "x".repeat(79)));
+    }
+}
@@ -0,0 +1,517 @@
+//! Per-thread journal of the notifications behind `thread/events/replay`.
+//!
+//! Every notification sent for a thread, `codex/event/*` and v2 alike, is
+//! appended to `$CODEX_HOME/event_journal/<thread id>.jsonl` under a sequence
+//! number that the notification also carries as `seq`. A client that reloads
+//! mid-turn asks for the events after the last `seq` it applied and rebuilds
+//! the live view from them. Only the current turn is kept: the journal
+//! restarts at each `task_started`, since finished turns can be read from the
+//! rollout.
+//!
+//! Sequence numbers are assigned in memory; a writer task per thread owns the
+//! file so streaming never waits on the disk. Consecutive deltas of the same
+//! stream are coalesced into one entry that remembers where each delta ended,
+//! so a replay from the middle of the run still starts at the right place.
+//! The journal is removed with its thread.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_app_server_protocol::JSONRPCErrorError;
+use codex_app_server_protocol::ThreadJournalEvent;
+use codex_protocol::ConversationId;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Map;
+use serde_json::Value as JsonValue;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufWriter;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc;
+use tokio::sync::oneshot;
+use tokio::time::Instant;
+use tracing::warn;
+
+use crate::error_code::INTERNAL_ERROR_CODE;
+
+const JOURNAL_DIR: &str = "event_journal";
+
+/// How long a delta entry stays open for the deltas that follow it before it
+/// is written. Bounds what a crash can lose from a streaming turn.
+const COALESCE_WINDOW: Duration = Duration::from_millis(250);
+
+/// The notification that starts a turn, and with it a new journal.
+const TURN_STARTED_METHOD: &str = "codex/event/task_started";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct JournalEntry {
+    seq: i64,
+    /// For coalesced deltas, the `seq` of the first one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    first_seq: Option<i64>,
+    /// For coalesced deltas, the byte offset in the delta text where each
+    /// delta from `first_seq` up to (not including) `seq` ends.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    splits: Vec<usize>,
+    method: String,
+    params: JsonValue,
+}
+
+impl JournalEntry {
+    fn first_seq(&self) -> i64 {
+        self.first_seq.unwrap_or(self.seq)
+    }
+
+    /// Appends `next` if it continues the same delta stream.
+    fn coalesce(&mut self, next: &JournalEntry) -> bool {
+        let Some(pointer) = delta_pointer(&self.method) else {
+            return false;
+        };
+        if next.method != self.method
+            || next.seq != self.seq + 1
+            || !same_stream(&self.params, &next.params, pointer)
+        {
+            return false;
+        }
+        let Some(JsonValue::String(next_delta)) = next.params.pointer(pointer) else {
+            return false;
+        };
+        let Some(JsonValue::String(delta)) = self.params.pointer_mut(pointer) else {
+            return false;
+        };
+        self.splits.push(delta.len());
+        delta.push_str(next_delta);
+        self.first_seq.get_or_insert(self.seq);
+        self.seq = next.seq;
+        true
+    }
+
+    /// The event as replayed to a client that already applied `since_seq`.
+    fn into_event(mut self, since_seq: Option<i64>) -> ThreadJournalEvent {
+        if let Some(since) = since_seq
+            && since >= self.first_seq()
+            && let Some(pointer) = delta_pointer(&self.method)
+            && let Some(split) = usize::try_from(since - self.first_seq())
+                .ok()
+                .and_then(|index| self.splits.get(index))
+            && let Some(JsonValue::String(delta)) = self.params.pointer_mut(pointer)
+        {
+            delta.drain(..*split);
+        }
+        ThreadJournalEvent {
+            seq: self.seq,
+            method: self.method,
+            params: self.params,
+        }
+    }
+}
+
+/// Where the text of a streamed delta lives in the params of `method`.
+fn delta_pointer(method: &str) -> Option<&'static str> {
+    match method {
+        "codex/event/agent_message_delta"
+        | "codex/event/agent_reasoning_delta"
+        | "codex/event/agent_reasoning_raw_content_delta" => Some("/msg/delta"),
+        "item/agentMessage/delta"
+        | "item/commandExecution/outputDelta"
+        | "item/reasoning/summaryTextDelta"
+        | "item/reasoning/textDelta" => Some("/delta"),
+        _ => None,
+    }
+}
+
+/// Whether two deltas belong to the same stream: everything but the delta
+/// text matches.
+fn same_stream(a: &JsonValue, b: &JsonValue, pointer: &str) -> bool {
+    let mut a = a.clone();
+    let mut b = b.clone();
+    for params in [&mut a, &mut b] {
+        if let Some(delta) = params.pointer_mut(pointer) {
+            *delta = JsonValue::Null;
+        }
+    }
+    a == b
+}
+
+/// Events after `since_seq`, and whether some of them are no longer
+/// journaled.
+pub(crate) struct Replay {
+    pub(crate) events: Vec<ThreadJournalEvent>,
+    pub(crate) gap: bool,
+}
+
+enum WriterCommand {
+    Append(JournalEntry),
+    /// Write everything received so far, then acknowledge.
+    Flush(oneshot::Sender<()>),
+    /// Delete the journal and stop.
+    Remove(oneshot::Sender<io::Result<()>>),
+}
+
+struct ThreadJournal {
+    next_seq: i64,
+    writer: mpsc::UnboundedSender<WriterCommand>,
+}
+
+pub(crate) struct EventJournal {
+    dir: PathBuf,
+    threads: Mutex<HashMap<ConversationId, ThreadJournal>>,
+}
+
+impl EventJournal {
+    pub(crate) fn new(codex_home: &Path) -> Self {
+        Self {
+            dir: codex_home.join(JOURNAL_DIR),
+            threads: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Journal a notification and return its sequence number. The write
+    /// happens in the background.
+    pub(crate) async fn append(
+        &self,
+        conversation_id: ConversationId,
+        method: &str,
+        params: &Map<String, JsonValue>,
+    ) -> io::Result<i64> {
+        let mut threads = self.threads.lock().await;
+        let thread = match threads.entry(conversation_id) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                // Continue the numbering of a journal left by an earlier process.
+                let path = self.path(conversation_id);
+                let next_seq = next_seq(&read_entries(&path).await?);
+                let (writer, commands) = mpsc::unbounded_channel();
+                tokio::spawn(run_writer(path, commands));
+                entry.insert(ThreadJournal { next_seq, writer })
+            }
+        };
+        let seq = thread.next_seq;
+        let entry = JournalEntry {
+            seq,
+            first_seq: None,
+            splits: Vec::new(),
+            method: method.to_string(),
+            params: JsonValue::Object(params.clone()),
+        };
+        thread
+            .writer
+            .send(WriterCommand::Append(entry))
+            .map_err(|_| io::Error::other("event journal writer stopped"))?;
+        thread.next_seq += 1;
+        Ok(seq)
+    }
+
+    /// Journaled events with a `seq` above `since_seq`, or all of them.
+    pub(crate) async fn replay_since(
+        &self,
+        conversation_id: ConversationId,
+        since_seq: Option<i64>,
+    ) -> io::Result<Replay> {
+        let flushed = {
+            let threads = self.threads.lock().await;
+            threads.get(&conversation_id).and_then(|thread| {
+                let (tx, rx) = oneshot::channel();
+                thread.writer.send(WriterCommand::Flush(tx)).ok()?;
+                Some(rx)
+            })
+        };
+        if let Some(flushed) = flushed {
+            let _ = flushed.await;
+        }
+
+        let entries = read_entries(&self.path(conversation_id)).await?;
+        // Events before the first entry belong to earlier turns. A client
+        // ahead of the journal saw events that a restart lost.
+        let gap = match (since_seq, entries.first()) {
+            (Some(since), Some(first)) => {
+                since < first.first_seq() - 1 || since >= next_seq(&entries)
+            }
+            _ => false,
+        };
+        let events = entries
+            .into_iter()
+            .filter(|entry| since_seq.is_none_or(|since| entry.seq > since))
+            .map(|entry| entry.into_event(since_seq))
+            .collect();
+        Ok(Replay { events, gap })
+    }
+
+    /// Delete the journal of a thread that was archived or deleted.
+    pub(crate) async fn remove(&self, conversation_id: ConversationId) -> io::Result<()> {
+        let removed = self.threads.lock().await.remove(&conversation_id);
+        if let Some(thread) = removed {
+            let (tx, rx) = oneshot::channel();
+            if thread.writer.send(WriterCommand::Remove(tx)).is_ok()
+                && let Ok(result) = rx.await
+            {
+                return result;
+            }
+        }
+        remove_file(&self.path(conversation_id)).await
+    }
+
+    fn path(&self, conversation_id: ConversationId) -> PathBuf {
+        self.dir.join(format!("{conversation_id}.jsonl"))
+    }
+}
+
+/// Owns a thread's journal file. Entries are buffered and written once no
+/// more are queued; a delta is held back for `COALESCE_WINDOW` so the deltas
+/// after it can be merged into it.
+async fn run_writer(path: PathBuf, mut commands: mpsc::UnboundedReceiver<WriterCommand>) {
+    let mut file: Option<BufWriter<tokio::fs::File>> = None;
+    let mut open_delta: Option<(JournalEntry, Instant)> = None;
+    loop {
+        let deadline = open_delta.as_ref().map(|(_, deadline)| *deadline);
+        let command = tokio::select! {
+            command = commands.recv() => command,
+            () = sleep_until(deadline) => {
+                flush_all(&path, &mut file, &mut open_delta).await;
+                continue;
+            }
+        };
+
+        match command {
+            Some(WriterCommand::Append(entry)) => {
+                if let Some((delta, _)) = open_delta.as_mut()
+                    && delta.coalesce(&entry)
+                {
+                    continue;
+                }
+                let starts_turn = entry.method == TURN_STARTED_METHOD;
+                match open_delta.take() {
+                    // The previous turn's journal is about to be replaced.
+                    Some(_) if starts_turn => {}
+                    Some((delta, _)) => write_entry(&path, &mut file, &delta, false).await,
+                    None => {}
+                }
+                if delta_pointer(&entry.method).is_some() {
+                    open_delta = Some((entry, Instant::now() + COALESCE_WINDOW));
+                } else {
+                    write_entry(&path, &mut file, &entry, starts_turn).await;
+                }
+            }
+            Some(WriterCommand::Flush(done)) => {
+                flush_all(&path, &mut file, &mut open_delta).await;
+                let _ = done.send(());
+                continue;
+            }
+            Some(WriterCommand::Remove(done)) => {
+                drop(file);
+                let _ = done.send(remove_file(&path).await);
+                return;
+            }
+            None => {
+                flush_all(&path, &mut file, &mut open_delta).await;
+                return;
+            }
+        }
+
+        if commands.is_empty() {
+            flush(&path, &mut file).await;
+        }
+    }
+}
+
+/// Writes the open delta, if any, and everything buffered.
+async fn flush_all(
+    path: &Path,
+    file: &mut Option<BufWriter<tokio::fs::File>>,
+    open_delta: &mut Option<(JournalEntry, Instant)>,
+) {
+    if let Some((entry, _)) = open_delta.take() {
+        write_entry(path, file, &entry, false).await;
+    }
+    flush(path, file).await;
+}
+
+async fn sleep_until(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline).await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Buffers `entry`, starting a new file first if it opens a turn.
+async fn write_entry(
+    path: &Path,
+    file: &mut Option<BufWriter<tokio::fs::File>>,
+    entry: &JournalEntry,
+    starts_turn: bool,
+) {
+    let result = async {
+        if starts_turn || file.is_none() {
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let opened = tokio::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(!starts_turn)
+                .truncate(starts_turn)
+                .open(path)
+                .await?;
+            *file = Some(BufWriter::new(opened));
+        }
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        if let Some(file) = file.as_mut() {
+            file.write_all(&line).await?;
+        }
+        Ok::<_, io::Error>(())
+    }
+    .await;
+    if let Err(err) = result {
+        warn!("failed to journal {}: {err}", entry.method);
+        *file = None;
+    }
+}
+
+async fn flush(path: &Path, file: &mut Option<BufWriter<tokio::fs::File>>) {
+    if let Some(writer) = file.as_mut()
+        && let Err(err) = writer.flush().await
+    {
+        warn!("failed to write event journal {}: {err}", path.display());
+        *file = None;
+    }
+}
+
+async fn remove_file(path: &Path) -> io::Result<()> {
+    match tokio::fs::remove_file(path).await {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+/// Entries on disk. A line cut short by a crash is skipped.
+async fn read_entries(path: &Path) -> io::Result<Vec<JournalEntry>> {
+    let contents = match tokio::fs::read_to_string(path).await {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn next_seq(entries: &[JournalEntry]) -> i64 {
+    entries.last().map_or(0, |entry| entry.seq + 1)
+}
+
+pub(crate) fn internal_error(err: io::Error) -> JSONRPCErrorError {
+    JSONRPCErrorError {
+        code: INTERNAL_ERROR_CODE,
+        message: format!("failed to access event journal: {err}"),
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn seqs(replay: &Replay) -> Vec<i64> {
+        replay.events.iter().map(|event| event.seq).collect()
+    }
+
+    fn params(value: JsonValue) -> Map<String, JsonValue> {
+        match value {
+            JsonValue::Object(map) => map,
+            _ => Map::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_the_current_turn_across_restarts() {
+        let home = TempDir::new().expect("tempdir");
+        let thread = ConversationId::new();
+        let journal = EventJournal::new(home.path());
+        let event = params(json!({"msg": {"type": "token_count"}}));
+
+        for method in [
+            TURN_STARTED_METHOD,
+            "codex/event/x",
+            TURN_STARTED_METHOD,
+            "codex/event/x",
+        ] {
+            journal
+                .append(thread, method, &event)
+                .await
+                .expect("append");
+        }
+        journal.replay_since(thread, None).await.expect("flush");
+
+        // A new process continues the numbering of the journal on disk.
+        let journal = EventJournal::new(home.path());
+        let seq = journal
+            .append(thread, "codex/event/x", &event)
+            .await
+            .expect("append");
+        assert_eq!(seq, 4);
+
+        let replay = journal.replay_since(thread, Some(2)).await.expect("replay");
+        assert_eq!((seqs(&replay), replay.gap), (vec![3, 4], false));
+        assert_eq!(replay.events[0].method, "codex/event/x");
+
+        // Event 1 belonged to the previous turn.
+        let replay = journal.replay_since(thread, Some(0)).await.expect("replay");
+        assert_eq!((seqs(&replay), replay.gap), (vec![2, 3, 4], true));
+
+        let replay = journal.replay_since(thread, None).await.expect("replay");
+        assert_eq!((seqs(&replay), replay.gap), (vec![2, 3, 4], false));
+
+        journal.remove(thread).await.expect("remove");
+        let replay = journal.replay_since(thread, None).await.expect("replay");
+        assert_eq!(seqs(&replay), Vec::<i64>::new());
+    }
+
+    #[tokio::test]
+    async fn deltas_are_coalesced_and_replayed_from_the_middle() {
+        let home = TempDir::new().expect("tempdir");
+        let thread = ConversationId::new();
+        let journal = EventJournal::new(home.path());
+        let delta = |item: &str, text: &str| params(json!({"itemId": item, "delta": text}));
+
+        let notifications = [
+            ("item/agentMessage/delta", delta("a", "Hel")),
+            ("item/agentMessage/delta", delta("a", "lo, ")),
+            ("item/agentMessage/delta", delta("a", "world")),
+            ("item/agentMessage/delta", delta("b", "!")),
+            ("item/completed", params(json!({"itemId": "a"}))),
+        ];
+        for (method, params) in &notifications {
+            journal
+                .append(thread, method, params)
+                .await
+                .expect("append");
+        }
+
+        let replay = journal.replay_since(thread, None).await.expect("replay");
+        assert_eq!(seqs(&replay), vec![2, 3, 4]);
+        assert_eq!(
+            replay.events[0].params,
+            json!({"itemId": "a", "delta": "Hello, world"})
+        );
+
+        // The client applied the first delta before reloading.
+        let replay = journal.replay_since(thread, Some(0)).await.expect("replay");
+        assert_eq!((seqs(&replay), replay.gap), (vec![2, 3, 4], false));
+        assert_eq!(
+            replay.events[0].params,
+            json!({"itemId": "a", "delta": "lo, world"})
+        );
+
+        let replay = journal.replay_since(thread, Some(2)).await.expect("replay");
+        assert_eq!(seqs(&replay), vec![3, 4]);
+    }
+}
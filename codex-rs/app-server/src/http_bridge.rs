@@ -0,0 +1,169 @@
+//! Opt-in HTTP transport so browser frontends and editor plugins can drive
+//! the app server without owning its stdio.
+//!
+//! `codex app-server --listen 127.0.0.1:<port>` serves the same JSON-RPC
+//! surface as stdio:
+//!
+//! - `POST /rpc` takes one JSON-RPC message (a request, a notification, or
+//!   the response to a server request) and answers `202 Accepted`.
+//! - `GET /events` is a server-sent event stream carrying every message the
+//!   server sends: responses, notifications and server requests.
+//!
+//! Every request must carry the token from [`TOKEN_ENV_VAR`], either as
+//! `Authorization: Bearer <token>` or, for `EventSource` clients that cannot
+//! set headers, as a `token` query parameter. All clients share one logical
+//! connection, so `initialize` is sent once.
+
+use std::convert::Infallible;
+use std::io::Result as IoResult;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::HeaderMap;
+use axum::http::StatusCode;
+use axum::http::Uri;
+use axum::http::header::AUTHORIZATION;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::response::sse::Event;
+use axum::response::sse::KeepAlive;
+use axum::response::sse::Sse;
+use axum::routing::get;
+use axum::routing::post;
+use codex_app_server_protocol::JSONRPCMessage;
+use tokio::net::TcpListener;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tracing::info;
+use tracing::warn;
+
+/// Environment variable holding the token HTTP clients must present.
+pub(crate) const TOKEN_ENV_VAR: &str = "CODEX_APP_SERVER_TOKEN";
+
+/// Outgoing messages buffered per `/events` subscriber before it lags.
+pub(crate) const EVENTS_BUFFER: usize = 1024;
+
+#[derive(Clone)]
+struct BridgeState {
+    token: Arc<str>,
+    incoming_tx: mpsc::Sender<JSONRPCMessage>,
+    events_tx: broadcast::Sender<Arc<str>>,
+}
+
+/// Serve `/rpc` and `/events` on `listen` until the server stops.
+/// Messages posted to `/rpc` go to `incoming_tx`; every serialized message
+/// sent on `events_tx` is streamed to `/events` subscribers.
+pub(crate) async fn serve(
+    listen: SocketAddr,
+    token: String,
+    incoming_tx: mpsc::Sender<JSONRPCMessage>,
+    events_tx: broadcast::Sender<Arc<str>>,
+) -> IoResult<()> {
+    let state = BridgeState {
+        token: token.into(),
+        incoming_tx,
+        events_tx,
+    };
+    let app = Router::new()
+        .route("/rpc", post(post_rpc))
+        .route("/events", get(get_events))
+        .with_state(state);
+    let listener = TcpListener::bind(listen).await?;
+    info!("app server listening on http://{}", listener.local_addr()?);
+    axum::serve(listener, app).await
+}
+
+async fn post_rpc(
+    State(state): State<BridgeState>,
+    headers: HeaderMap,
+    uri: Uri,
+    body: String,
+) -> StatusCode {
+    if !is_authorized(&headers, &uri, &state.token) {
+        return StatusCode::UNAUTHORIZED;
+    }
+    let message = match serde_json::from_str::<JSONRPCMessage>(&body) {
+        Ok(message) => message,
+        Err(err) => {
+            warn!("rejected malformed JSON-RPC message over HTTP: {err}");
+            return StatusCode::BAD_REQUEST;
+        }
+    };
+    match state.incoming_tx.send(message).await {
+        Ok(()) => StatusCode::ACCEPTED,
+        Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+    }
+}
+
+async fn get_events(State(state): State<BridgeState>, headers: HeaderMap, uri: Uri) -> Response {
+    if !is_authorized(&headers, &uri, &state.token) {
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+    let events = futures::stream::unfold(state.events_tx.subscribe(), |mut rx| async move {
+        let event = match rx.recv().await {
+            Ok(json) => Event::default().data(json.as_ref()),
+            // Tell the client it missed messages rather than dropping them
+            // silently; it can resynchronize with `thread/turns/list`.
+            Err(RecvError::Lagged(skipped)) => {
+                Event::default().event("lagged").data(skipped.to_string())
+            }
+            Err(RecvError::Closed) => return None,
+        };
+        Some((Ok::<_, Infallible>(event), rx))
+    });
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+fn is_authorized(headers: &HeaderMap, uri: &Uri, token: &str) -> bool {
+    let bearer = headers
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let query = uri.query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("token="))
+    });
+    bearer
+        .into_iter()
+        .chain(query)
+        .any(|given| tokens_match(given.trim(), token))
+}
+
+/// Compares without short-circuiting so response timing does not reveal how
+/// much of the token was right.
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn accepts_bearer_header_or_query_token() {
+        let mut headers = HeaderMap::new();
+        let bare: Uri = "/events".parse().expect("uri");
+        assert!(!is_authorized(&headers, &bare, "s3cret"));
+
+        headers.insert(AUTHORIZATION, HeaderValue::from_static("Bearer s3cret"));
+        assert!(is_authorized(&headers, &bare, "s3cret"));
+
+        let headers = HeaderMap::new();
+        let with_query: Uri = "/events?since=0&token=s3cret".parse().expect("uri");
+        assert!(is_authorized(&headers, &with_query, "s3cret"));
+        let wrong: Uri = "/events?token=s3cre".parse().expect("uri");
+        assert!(!is_authorized(&headers, &wrong, "s3cret"));
+    }
+}
@@ -6,7 +6,9 @@ use codex_core::config::ConfigOverrides;
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use std::io::ErrorKind;
 use std::io::Result as IoResult;
+use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::Arc;
 
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingMessage;
@@ -17,6 +19,7 @@ use tokio::io::AsyncBufReadExt;
 use tokio::io::AsyncWriteExt;
 use tokio::io::BufReader;
 use tokio::io::{self};
+use tokio::sync::broadcast;
 use tokio::sync::mpsc;
 use toml::Value as TomlValue;
 use tracing::Level;
@@ -32,47 +35,80 @@ use tracing_subscriber::util::SubscriberInitExt;
 mod bespoke_event_handling;
 mod codex_message_processor;
 mod config_api;
+mod config_cache;
+mod crash_reports;
+mod desktop_notifications;
 mod error_code;
+mod event_journal;
 mod fuzzy_file_search;
+mod http_bridge;
 mod message_processor;
 mod models;
 mod outgoing_message;
+mod plan_board;
+mod rate_limits;
+mod repository_onboarding;
+mod workspace_files;
+mod worktrees;
 
 /// Size of the bounded channels used to communicate between tasks. The value
 /// is a balance between throughput and memory usage – 128 messages should be
 /// plenty for an interactive CLI.
 const CHANNEL_CAPACITY: usize = 128;
 
+/// Where the app server reads requests from and writes messages to.
+#[derive(Debug, Clone, Default)]
+pub enum AppServerTransport {
+    /// Newline-delimited JSON-RPC over stdin/stdout.
+    #[default]
+    Stdio,
+    /// `POST /rpc` and a server-sent `GET /events` stream on a loopback
+    /// address, authenticated with the token in `CODEX_APP_SERVER_TOKEN`.
+    Http { listen: SocketAddr },
+}
+
 pub async fn run_main(
     codex_linux_sandbox_exe: Option<PathBuf>,
     cli_config_overrides: CliConfigOverrides,
+    transport: AppServerTransport,
 ) -> IoResult<()> {
     // Set up channels.
     let (incoming_tx, mut incoming_rx) = mpsc::channel::<JSONRPCMessage>(CHANNEL_CAPACITY);
     let (outgoing_tx, mut outgoing_rx) = mpsc::channel::<OutgoingMessage>(CHANNEL_CAPACITY);
 
-    // Task: read from stdin, push to `incoming_tx`.
-    let stdin_reader_handle = tokio::spawn({
-        async move {
-            let stdin = io::stdin();
-            let reader = BufReader::new(stdin);
-            let mut lines = reader.lines();
-
-            while let Some(line) = lines.next_line().await.unwrap_or_default() {
-                match serde_json::from_str::<JSONRPCMessage>(&line) {
-                    Ok(msg) => {
-                        if incoming_tx.send(msg).await.is_err() {
-                            // Receiver gone – nothing left to do.
-                            break;
-                        }
-                    }
-                    Err(e) => error!("Failed to deserialize JSONRPCMessage: {e}"),
-                }
+    // Task: read from the transport, push to `incoming_tx`.
+    let (reader_handle, events_tx) = match transport {
+        AppServerTransport::Stdio => (
+            crash_reports::spawn_supervised("stdin reader", read_stdin(incoming_tx)),
+            None,
+        ),
+        AppServerTransport::Http { listen } => {
+            if !listen.ip().is_loopback() {
+                return Err(std::io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("--listen must be a loopback address, got {listen}"),
+                ));
             }
-
-            debug!("stdin reader finished (EOF)");
+            let token = std::env::var(http_bridge::TOKEN_ENV_VAR)
+                .ok()
+                .filter(|token| !token.trim().is_empty())
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("set {} to serve over HTTP", http_bridge::TOKEN_ENV_VAR),
+                    )
+                })?;
+            let (events_tx, _) = broadcast::channel::<Arc<str>>(http_bridge::EVENTS_BUFFER);
+            let server = http_bridge::serve(listen, token, incoming_tx, events_tx.clone());
+            let handle = crash_reports::spawn_supervised("http transport", async move {
+                if let Err(err) = server.await {
+                    error!("HTTP transport stopped: {err}");
+                    crash_reports::record_task_failure("http transport", &err.to_string());
+                }
+            });
+            (handle, Some(events_tx))
         }
-    });
+    };
 
     // Parse CLI overrides once and derive the base Config eagerly so later
     // components do not need to work with raw TOML values.
@@ -121,8 +157,10 @@ pub async fn run_main(
         }))
         .try_init();
 
+    crash_reports::install(&config, feedback.clone());
+
     // Task: process incoming messages.
-    let processor_handle = tokio::spawn({
+    let processor_handle = crash_reports::spawn_supervised("processor", {
         let outgoing_message_sender = OutgoingMessageSender::new(outgoing_tx);
         let cli_overrides: Vec<(String, TomlValue)> = cli_kv_overrides.clone();
         let mut processor = MessageProcessor::new(
@@ -146,8 +184,8 @@ pub async fn run_main(
         }
     });
 
-    // Task: write outgoing messages to stdout.
-    let stdout_writer_handle = tokio::spawn(async move {
+    // Task: write outgoing messages to stdout or the HTTP event stream.
+    let writer_handle = crash_reports::spawn_supervised("outgoing writer", async move {
         let mut stdout = io::stdout();
         while let Some(outgoing_message) = outgoing_rx.recv().await {
             let Ok(value) = serde_json::to_value(outgoing_message) else {
@@ -155,24 +193,52 @@ pub async fn run_main(
                 continue;
             };
             match serde_json::to_string(&value) {
-                Ok(mut json) => {
-                    json.push('\n');
-                    if let Err(e) = stdout.write_all(json.as_bytes()).await {
-                        error!("Failed to write to stdout: {e}");
-                        break;
+                Ok(json) => match &events_tx {
+                    // No subscribers just means no client is listening yet.
+                    Some(events_tx) => {
+                        let _ = events_tx.send(json.into());
                     }
-                }
+                    None => {
+                        let mut json = json;
+                        json.push('\n');
+                        if let Err(e) = stdout.write_all(json.as_bytes()).await {
+                            error!("Failed to write to stdout: {e}");
+                            break;
+                        }
+                    }
+                },
                 Err(e) => error!("Failed to serialize JSONRPCMessage: {e}"),
             }
         }
 
-        info!("stdout writer exited (channel closed)");
+        info!("outgoing writer exited (channel closed)");
     });
 
-    // Wait for all tasks to finish.  The typical exit path is the stdin reader
-    // hitting EOF which, once it drops `incoming_tx`, propagates shutdown to
-    // the processor and then to the stdout task.
-    let _ = tokio::join!(stdin_reader_handle, processor_handle, stdout_writer_handle);
+    // Wait for all tasks to finish.  Over stdio, the typical exit path is the
+    // stdin reader hitting EOF which, once it drops `incoming_tx`, propagates
+    // shutdown to the processor and then to the writer task. The HTTP
+    // transport serves until the process is stopped.
+    let _ = tokio::join!(reader_handle, processor_handle, writer_handle);
 
     Ok(())
 }
+
+async fn read_stdin(incoming_tx: mpsc::Sender<JSONRPCMessage>) {
+    let stdin = io::stdin();
+    let reader = BufReader::new(stdin);
+    let mut lines = reader.lines();
+
+    while let Some(line) = lines.next_line().await.unwrap_or_default() {
+        match serde_json::from_str::<JSONRPCMessage>(&line) {
+            Ok(msg) => {
+                if incoming_tx.send(msg).await.is_err() {
+                    // Receiver gone – nothing left to do.
+                    break;
+                }
+            }
+            Err(e) => error!("Failed to deserialize JSONRPCMessage: {e}"),
+        }
+    }
+
+    debug!("stdin reader finished (EOF)");
+}
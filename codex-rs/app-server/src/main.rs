@@ -1,10 +1,16 @@
+use codex_app_server::AppServerTransport;
 use codex_app_server::run_main;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_common::CliConfigOverrides;
 
 fn main() -> anyhow::Result<()> {
     arg0_dispatch_or_else(|codex_linux_sandbox_exe| async move {
-        run_main(codex_linux_sandbox_exe, CliConfigOverrides::default()).await?;
+        run_main(
+            codex_linux_sandbox_exe,
+            CliConfigOverrides::default(),
+            AppServerTransport::Stdio,
+        )
+        .await?;
         Ok(())
     })
 }
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use crate::codex_message_processor::CodexMessageProcessor;
 use crate::config_api::ConfigApi;
+use crate::config_cache::ConfigCache;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
 use codex_app_server_protocol::ClientInfo;
@@ -30,6 +31,7 @@ pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
     codex_message_processor: CodexMessageProcessor,
     config_api: ConfigApi,
+    config_cache: Arc<ConfigCache>,
     initialized: bool,
 }
 
@@ -53,12 +55,14 @@ impl MessageProcessor {
             auth_manager.clone(),
             SessionSource::VSCode,
         ));
+        let config_cache = Arc::new(ConfigCache::new(config.codex_home.clone()));
         let codex_message_processor = CodexMessageProcessor::new(
             auth_manager,
             conversation_manager,
             outgoing.clone(),
             codex_linux_sandbox_exe,
             Arc::clone(&config),
+            Arc::clone(&config_cache),
             feedback,
         );
         let config_api = ConfigApi::new(config.codex_home.clone(), cli_overrides);
@@ -67,6 +71,7 @@ impl MessageProcessor {
             outgoing,
             codex_message_processor,
             config_api,
+            config_cache,
             initialized: false,
         }
     }
@@ -127,6 +132,7 @@ impl MessageProcessor {
                     self.outgoing.send_response(request_id, response).await;
 
                     self.initialized = true;
+                    self.codex_message_processor.start_rate_limit_polling();
 
                     return;
                 }
@@ -190,7 +196,9 @@ impl MessageProcessor {
         request_id: RequestId,
         params: ConfigValueWriteParams,
     ) {
-        match self.config_api.write_value(params).await {
+        let result = self.config_api.write_value(params).await;
+        self.config_cache.invalidate().await;
+        match result {
             Ok(response) => self.outgoing.send_response(request_id, response).await,
             Err(error) => self.outgoing.send_error(request_id, error).await,
         }
@@ -201,7 +209,9 @@ impl MessageProcessor {
         request_id: RequestId,
         params: ConfigBatchWriteParams,
     ) {
-        match self.config_api.batch_write(params).await {
+        let result = self.config_api.batch_write(params).await;
+        self.config_cache.invalidate().await;
+        match result {
             Ok(response) => self.outgoing.send_response(request_id, response).await,
             Err(error) => self.outgoing.send_error(request_id, error).await,
         }
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::sync::atomic::AtomicI64;
 use std::sync::atomic::Ordering;
 
@@ -8,27 +9,48 @@ use codex_app_server_protocol::Result;
 use codex_app_server_protocol::ServerNotification;
 use codex_app_server_protocol::ServerRequest;
 use codex_app_server_protocol::ServerRequestPayload;
+use codex_protocol::ConversationId;
 use serde::Serialize;
+use serde_json::Value as JsonValue;
 use tokio::sync::Mutex;
 use tokio::sync::mpsc;
 use tokio::sync::oneshot;
 use tracing::warn;
 
 use crate::error_code::INTERNAL_ERROR_CODE;
+use crate::event_journal::EventJournal;
 
 /// Sends messages to the client and manages request callbacks.
 pub(crate) struct OutgoingMessageSender {
-    next_request_id: AtomicI64,
+    next_request_id: Arc<AtomicI64>,
     sender: mpsc::Sender<OutgoingMessage>,
-    request_id_to_callback: Mutex<HashMap<RequestId, oneshot::Sender<Result>>>,
+    request_id_to_callback: Arc<Mutex<HashMap<RequestId, oneshot::Sender<Result>>>>,
+    /// Set on senders created with [`Self::for_thread`].
+    journal: Option<(Arc<EventJournal>, ConversationId)>,
 }
 
 impl OutgoingMessageSender {
     pub(crate) fn new(sender: mpsc::Sender<OutgoingMessage>) -> Self {
         Self {
-            next_request_id: AtomicI64::new(0),
+            next_request_id: Arc::new(AtomicI64::new(0)),
             sender,
-            request_id_to_callback: Mutex::new(HashMap::new()),
+            request_id_to_callback: Arc::new(Mutex::new(HashMap::new())),
+            journal: None,
+        }
+    }
+
+    /// A sender for one thread's events: every notification sent through it
+    /// is journaled for `thread/events/replay` and carries its `seq`.
+    pub(crate) fn for_thread(
+        &self,
+        journal: Arc<EventJournal>,
+        conversation_id: ConversationId,
+    ) -> Self {
+        Self {
+            next_request_id: self.next_request_id.clone(),
+            sender: self.sender.clone(),
+            request_id_to_callback: self.request_id_to_callback.clone(),
+            journal: Some((journal, conversation_id)),
         }
     }
 
@@ -95,6 +117,20 @@ impl OutgoingMessageSender {
     }
 
     pub(crate) async fn send_server_notification(&self, notification: ServerNotification) {
+        if self.journal.is_some() {
+            match serde_json::to_value(&notification) {
+                Ok(JsonValue::Object(mut value)) => {
+                    if let Some(JsonValue::String(method)) = value.remove("method") {
+                        let params = value.remove("params");
+                        self.send_notification(OutgoingNotification { method, params })
+                            .await;
+                        return;
+                    }
+                }
+                Ok(_) => {}
+                Err(err) => warn!("failed to serialize server notification: {err}"),
+            }
+        }
         if let Err(err) = self
             .sender
             .send(OutgoingMessage::AppServerNotification(notification))
@@ -106,7 +142,20 @@ impl OutgoingMessageSender {
 
     /// All notifications should be migrated to [`ServerNotification`] and
     /// [`OutgoingMessage::Notification`] should be removed.
-    pub(crate) async fn send_notification(&self, notification: OutgoingNotification) {
+    pub(crate) async fn send_notification(&self, mut notification: OutgoingNotification) {
+        if let Some((journal, conversation_id)) = &self.journal
+            && let Some(JsonValue::Object(params)) = notification.params.as_mut()
+        {
+            match journal
+                .append(*conversation_id, &notification.method, params)
+                .await
+            {
+                Ok(seq) => {
+                    params.insert("seq".to_string(), seq.into());
+                }
+                Err(err) => warn!("failed to journal {}: {err}", notification.method),
+            }
+        }
         let outgoing_message = OutgoingMessage::Notification(notification);
         if let Err(err) = self.sender.send(outgoing_message).await {
             warn!("failed to send notification to client: {err:?}");
@@ -158,12 +207,14 @@ mod tests {
     use codex_app_server_protocol::AccountLoginCompletedNotification;
     use codex_app_server_protocol::AccountRateLimitsUpdatedNotification;
     use codex_app_server_protocol::AccountUpdatedNotification;
+    use codex_app_server_protocol::AgentMessageDeltaNotification;
     use codex_app_server_protocol::AuthMode;
     use codex_app_server_protocol::LoginChatGptCompleteNotification;
     use codex_app_server_protocol::RateLimitSnapshot;
     use codex_app_server_protocol::RateLimitWindow;
     use pretty_assertions::assert_eq;
     use serde_json::json;
+    use tempfile::TempDir;
     use uuid::Uuid;
 
     use super::*;
@@ -274,4 +325,40 @@ mod tests {
             "ensure the notification serializes correctly"
         );
     }
+
+    #[tokio::test]
+    async fn thread_senders_journal_notifications_with_their_seq() {
+        let home = TempDir::new().expect("tempdir");
+        let journal = Arc::new(EventJournal::new(home.path()));
+        let thread = ConversationId::new();
+        let (tx, mut rx) = mpsc::channel(4);
+        let outgoing = OutgoingMessageSender::new(tx).for_thread(journal.clone(), thread);
+
+        outgoing
+            .send_server_notification(ServerNotification::AgentMessageDelta(
+                AgentMessageDeltaNotification {
+                    item_id: "item-1".to_string(),
+                    delta: "Hi".to_string(),
+                },
+            ))
+            .await;
+
+        let message = rx.recv().await.expect("notification");
+        assert_eq!(
+            serde_json::to_value(message).expect("serialize"),
+            json!({
+                "method": "item/agentMessage/delta",
+                "params": {"itemId": "item-1", "delta": "Hi", "seq": 0},
+            })
+        );
+        let replay = journal.replay_since(thread, None).await.expect("replay");
+        assert_eq!(
+            replay
+                .events
+                .iter()
+                .map(|event| (event.seq, event.method.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(0, "item/agentMessage/delta")]
+        );
+    }
 }
@@ -0,0 +1,291 @@
+//! Per-thread plan storage behind `thread/plan/read` and
+//! `thread/plan/setStepStatus`.
+//!
+//! Every `update_plan` call is saved to `$CODEX_HOME/plans/<thread id>.json`
+//! so clients can render a progress board that survives restarts and
+//! resumes. Users can set a step's status by hand; the override sticks until
+//! the agent reports a different status for that step than it did when the
+//! override was made.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::Utc;
+use codex_app_server_protocol::JSONRPCErrorError;
+use codex_app_server_protocol::ThreadPlan;
+use codex_app_server_protocol::ThreadPlanStep;
+use codex_app_server_protocol::ThreadPlanStepStatus;
+use codex_protocol::ConversationId;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+use crate::error_code::INTERNAL_ERROR_CODE;
+use crate::error_code::INVALID_REQUEST_ERROR_CODE;
+
+const PLANS_DIR: &str = "plans";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredPlan {
+    explanation: Option<String>,
+    steps: Vec<StoredStep>,
+    updated_at: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredStep {
+    step: String,
+    /// Status last reported by the agent.
+    status: ThreadPlanStepStatus,
+    /// Status set by the user, if any.
+    #[serde(default)]
+    override_status: Option<ThreadPlanStepStatus>,
+}
+
+impl StoredPlan {
+    /// Apply an `update_plan` call. Overrides carry over to steps with the
+    /// same text whose agent status has not changed.
+    fn updated(previous: Option<StoredPlan>, args: &UpdatePlanArgs, now: i64) -> Self {
+        let previous = previous.map(|plan| plan.steps).unwrap_or_default();
+        let steps = args
+            .plan
+            .iter()
+            .map(|item| {
+                let status = ThreadPlanStepStatus::from(item.status.clone());
+                let override_status = previous
+                    .iter()
+                    .find(|old| old.step == item.step && old.status == status)
+                    .and_then(|old| old.override_status);
+                StoredStep {
+                    step: item.step.clone(),
+                    status,
+                    override_status,
+                }
+            })
+            .collect();
+        Self {
+            explanation: args.explanation.clone(),
+            steps,
+            updated_at: now,
+        }
+    }
+
+    fn to_thread_plan(&self) -> ThreadPlan {
+        ThreadPlan {
+            explanation: self.explanation.clone(),
+            steps: self
+                .steps
+                .iter()
+                .map(|step| ThreadPlanStep {
+                    step: step.step.clone(),
+                    status: step.override_status.unwrap_or(step.status),
+                    overridden: step.override_status.is_some(),
+                })
+                .collect(),
+            updated_at: self.updated_at,
+        }
+    }
+}
+
+pub(crate) struct PlanBoard {
+    dir: PathBuf,
+    /// Serializes read-modify-write cycles on the plan files.
+    lock: Mutex<()>,
+}
+
+impl PlanBoard {
+    pub(crate) fn new(codex_home: &Path) -> Self {
+        Self {
+            dir: codex_home.join(PLANS_DIR),
+            lock: Mutex::new(()),
+        }
+    }
+
+    pub(crate) async fn read(
+        &self,
+        conversation_id: ConversationId,
+    ) -> io::Result<Option<ThreadPlan>> {
+        let _guard = self.lock.lock().await;
+        Ok(self
+            .load(conversation_id)
+            .await?
+            .map(|plan| plan.to_thread_plan()))
+    }
+
+    /// Save a plan the agent published.
+    pub(crate) async fn record_update(
+        &self,
+        conversation_id: ConversationId,
+        args: &UpdatePlanArgs,
+    ) -> io::Result<ThreadPlan> {
+        let _guard = self.lock.lock().await;
+        let previous = self.load(conversation_id).await?;
+        let plan = StoredPlan::updated(previous, args, Utc::now().timestamp());
+        self.store(conversation_id, &plan).await?;
+        Ok(plan.to_thread_plan())
+    }
+
+    /// Override the status of step `index`. Setting the status the agent
+    /// reported clears the override.
+    pub(crate) async fn set_step_status(
+        &self,
+        conversation_id: ConversationId,
+        index: usize,
+        status: ThreadPlanStepStatus,
+    ) -> Result<ThreadPlan, JSONRPCErrorError> {
+        let _guard = self.lock.lock().await;
+        let Some(mut plan) = self.load(conversation_id).await.map_err(internal_error)? else {
+            return Err(invalid_request("thread has no plan yet".to_string()));
+        };
+        let len = plan.steps.len();
+        let Some(step) = plan.steps.get_mut(index) else {
+            return Err(invalid_request(format!(
+                "plan has no step {index}; it has {len}"
+            )));
+        };
+        step.override_status = (step.status != status).then_some(status);
+        plan.updated_at = Utc::now().timestamp();
+        self.store(conversation_id, &plan)
+            .await
+            .map_err(internal_error)?;
+        Ok(plan.to_thread_plan())
+    }
+
+    fn path(&self, conversation_id: ConversationId) -> PathBuf {
+        self.dir.join(format!("{conversation_id}.json"))
+    }
+
+    async fn load(&self, conversation_id: ConversationId) -> io::Result<Option<StoredPlan>> {
+        match tokio::fs::read(self.path(conversation_id)).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn store(&self, conversation_id: ConversationId, plan: &StoredPlan) -> io::Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let path = self.path(conversation_id);
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec_pretty(plan)?).await?;
+        tokio::fs::rename(&tmp, &path).await
+    }
+}
+
+pub(crate) fn internal_error(err: io::Error) -> JSONRPCErrorError {
+    JSONRPCErrorError {
+        code: INTERNAL_ERROR_CODE,
+        message: format!("failed to access plan: {err}"),
+        data: None,
+    }
+}
+
+fn invalid_request(message: String) -> JSONRPCErrorError {
+    JSONRPCErrorError {
+        code: INVALID_REQUEST_ERROR_CODE,
+        message,
+        data: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::plan_tool::PlanItemArg;
+    use codex_protocol::plan_tool::StepStatus;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    fn update(steps: &[(&str, StepStatus)]) -> UpdatePlanArgs {
+        UpdatePlanArgs {
+            explanation: None,
+            plan: steps
+                .iter()
+                .map(|(step, status)| PlanItemArg {
+                    step: step.to_string(),
+                    status: status.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    fn statuses(plan: &ThreadPlan) -> Vec<(ThreadPlanStepStatus, bool)> {
+        plan.steps
+            .iter()
+            .map(|step| (step.status, step.overridden))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn override_lasts_until_the_agent_moves_the_step() {
+        let home = TempDir::new().expect("tempdir");
+        let board = PlanBoard::new(home.path());
+        let thread = ConversationId::new();
+
+        board
+            .record_update(
+                thread,
+                &update(&[
+                    ("Write", StepStatus::Pending),
+                    ("Test", StepStatus::Pending),
+                ]),
+            )
+            .await
+            .expect("record plan");
+        board
+            .set_step_status(thread, 1, ThreadPlanStepStatus::Completed)
+            .await
+            .expect("override step");
+
+        let plan = board
+            .record_update(
+                thread,
+                &update(&[
+                    ("Write", StepStatus::InProgress),
+                    ("Test", StepStatus::Pending),
+                ]),
+            )
+            .await
+            .expect("record plan");
+        assert_eq!(
+            statuses(&plan),
+            vec![
+                (ThreadPlanStepStatus::InProgress, false),
+                (ThreadPlanStepStatus::Completed, true),
+            ]
+        );
+
+        let plan = board
+            .record_update(
+                thread,
+                &update(&[
+                    ("Write", StepStatus::Completed),
+                    ("Test", StepStatus::InProgress),
+                ]),
+            )
+            .await
+            .expect("record plan");
+        assert_eq!(
+            statuses(&plan),
+            vec![
+                (ThreadPlanStepStatus::Completed, false),
+                (ThreadPlanStepStatus::InProgress, false),
+            ]
+        );
+
+        let reloaded = PlanBoard::new(home.path())
+            .read(thread)
+            .await
+            .expect("read plan");
+        assert_eq!(reloaded, Some(plan));
+        let err = board
+            .set_step_status(thread, 2, ThreadPlanStepStatus::Completed)
+            .await
+            .expect_err("step 2 does not exist");
+        assert_eq!(err.code, INVALID_REQUEST_ERROR_CODE);
+    }
+}
@@ -0,0 +1,294 @@
+//! ChatGPT rate limit fetching shared by `account/rateLimits/read` and a
+//! background poller.
+//!
+//! One `BackendClient` is kept for as long as the access token stays the
+//! same. While the user is signed in with ChatGPT the poller fetches the
+//! limits every [`POLL_INTERVAL`] plus up to [`POLL_JITTER`], sends
+//! `account/rateLimits/updated` when the snapshot changes, and sends
+//! `account/rateLimits/warning` when a window crosses one of the TUI's
+//! warning thresholds.
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use codex_app_server_protocol::AccountRateLimitsUpdatedNotification;
+use codex_app_server_protocol::AccountRateLimitsWarningNotification;
+use codex_app_server_protocol::AuthMode;
+use codex_app_server_protocol::JSONRPCErrorError;
+use codex_app_server_protocol::RateLimitWindowKind;
+use codex_app_server_protocol::ServerNotification;
+use codex_backend_client::Client as BackendClient;
+use codex_common::rate_limits::RATE_LIMIT_WARNING_THRESHOLDS;
+use codex_common::rate_limits::get_limits_duration;
+use codex_core::AuthManager;
+use codex_core::CodexAuth;
+use codex_protocol::protocol::RateLimitSnapshot;
+use codex_protocol::protocol::RateLimitWindow;
+use tokio::sync::Mutex;
+use tracing::debug;
+
+use crate::error_code::INTERNAL_ERROR_CODE;
+use crate::error_code::INVALID_REQUEST_ERROR_CODE;
+use crate::outgoing_message::OutgoingMessageSender;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+const POLL_JITTER: Duration = Duration::from_secs(15);
+
+pub(crate) struct RateLimits {
+    auth_manager: Arc<AuthManager>,
+    chatgpt_base_url: String,
+    /// Client built for the access token it was created with.
+    client: Mutex<Option<(String, BackendClient)>>,
+}
+
+impl RateLimits {
+    pub(crate) fn new(auth_manager: Arc<AuthManager>, chatgpt_base_url: String) -> Self {
+        Self {
+            auth_manager,
+            chatgpt_base_url,
+            client: Mutex::new(None),
+        }
+    }
+
+    pub(crate) async fn fetch(&self) -> Result<RateLimitSnapshot, JSONRPCErrorError> {
+        let auth = self.chatgpt_auth()?;
+        let client = self.client_for(&auth).await?;
+        client
+            .get_rate_limits()
+            .await
+            .map_err(|err| JSONRPCErrorError {
+                code: INTERNAL_ERROR_CODE,
+                message: format!("failed to fetch codex rate limits: {err}"),
+                data: None,
+            })
+    }
+
+    /// Starts polling in the background for the rest of the process.
+    pub(crate) fn spawn_poller(self: &Arc<Self>, outgoing: Arc<OutgoingMessageSender>) {
+        let rate_limits = Arc::clone(self);
+        tokio::spawn(async move {
+            let mut last_snapshot = None;
+            let mut warnings = RateLimitWarnings::default();
+            loop {
+                if rate_limits.chatgpt_auth().is_err() {
+                    last_snapshot = None;
+                    warnings = RateLimitWarnings::default();
+                } else {
+                    match rate_limits.fetch().await {
+                        Ok(snapshot) if last_snapshot.as_ref() != Some(&snapshot) => {
+                            for warning in warnings.take_warnings(&snapshot) {
+                                outgoing
+                                    .send_server_notification(
+                                        ServerNotification::AccountRateLimitsWarning(warning),
+                                    )
+                                    .await;
+                            }
+                            outgoing
+                                .send_server_notification(
+                                    ServerNotification::AccountRateLimitsUpdated(
+                                        AccountRateLimitsUpdatedNotification {
+                                            rate_limits: snapshot.clone().into(),
+                                        },
+                                    ),
+                                )
+                                .await;
+                            last_snapshot = Some(snapshot);
+                        }
+                        Ok(_) => {}
+                        Err(err) => debug!("rate limit poll failed: {}", err.message),
+                    }
+                }
+                tokio::time::sleep(next_poll_delay()).await;
+            }
+        });
+    }
+
+    fn chatgpt_auth(&self) -> Result<CodexAuth, JSONRPCErrorError> {
+        let Some(auth) = self.auth_manager.auth() else {
+            return Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "codex account authentication required to read rate limits".to_string(),
+                data: None,
+            });
+        };
+
+        if auth.mode != AuthMode::ChatGPT {
+            return Err(JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: "chatgpt authentication required to read rate limits".to_string(),
+                data: None,
+            });
+        }
+
+        Ok(auth)
+    }
+
+    /// The cached client, rebuilt when the access token has changed.
+    async fn client_for(&self, auth: &CodexAuth) -> Result<BackendClient, JSONRPCErrorError> {
+        let construct_error = |err: String| JSONRPCErrorError {
+            code: INTERNAL_ERROR_CODE,
+            message: format!("failed to construct backend client: {err}"),
+            data: None,
+        };
+        let token = auth
+            .get_token()
+            .await
+            .map_err(|err| construct_error(err.to_string()))?;
+
+        let mut cached = self.client.lock().await;
+        if let Some((cached_token, client)) = cached.as_ref()
+            && *cached_token == token
+        {
+            return Ok(client.clone());
+        }
+        let client = BackendClient::from_auth(self.chatgpt_base_url.clone(), auth)
+            .await
+            .map_err(|err| construct_error(err.to_string()))?;
+        *cached = Some((token, client.clone()));
+        Ok(client)
+    }
+}
+
+fn next_poll_delay() -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or_default();
+    POLL_INTERVAL + POLL_JITTER.mul_f64(f64::from(nanos) / 1_000_000_000.0)
+}
+
+/// Number of warning thresholds already reported per window. Unlike the
+/// TUI, which lives for one session, the count drops again when usage falls
+/// (the window reset) so the warnings fire again on the next climb.
+#[derive(Default)]
+struct RateLimitWarnings {
+    primary_reached: usize,
+    secondary_reached: usize,
+}
+
+impl RateLimitWarnings {
+    fn take_warnings(
+        &mut self,
+        snapshot: &RateLimitSnapshot,
+    ) -> Vec<AccountRateLimitsWarningNotification> {
+        let at_cap = |window: &Option<RateLimitWindow>| {
+            window
+                .as_ref()
+                .is_some_and(|window| window.used_percent >= 100.0)
+        };
+        if at_cap(&snapshot.primary) || at_cap(&snapshot.secondary) {
+            return Vec::new();
+        }
+
+        let mut warnings = Vec::new();
+        for (kind, window, reached, default_label) in [
+            (
+                RateLimitWindowKind::Secondary,
+                &snapshot.secondary,
+                &mut self.secondary_reached,
+                "weekly",
+            ),
+            (
+                RateLimitWindowKind::Primary,
+                &snapshot.primary,
+                &mut self.primary_reached,
+                "5h",
+            ),
+        ] {
+            let Some(window) = window else {
+                continue;
+            };
+            let now_reached = RATE_LIMIT_WARNING_THRESHOLDS
+                .iter()
+                .filter(|threshold| window.used_percent >= **threshold)
+                .count();
+            if now_reached > *reached {
+                let threshold = RATE_LIMIT_WARNING_THRESHOLDS[now_reached - 1];
+                let limit_label = window
+                    .window_minutes
+                    .map(get_limits_duration)
+                    .unwrap_or_else(|| default_label.to_string());
+                let remaining_percent = 100.0 - threshold;
+                warnings.push(AccountRateLimitsWarningNotification {
+                    window: kind,
+                    threshold_percent: threshold as i32,
+                    message: format!(
+                        "Heads up, you have less than {remaining_percent:.0}% of your {limit_label} limit left."
+                    ),
+                    rate_limits: snapshot.clone().into(),
+                });
+            }
+            *reached = now_reached;
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn snapshot(primary_used: f64, secondary_used: f64) -> RateLimitSnapshot {
+        RateLimitSnapshot {
+            primary: Some(RateLimitWindow {
+                used_percent: primary_used,
+                window_minutes: Some(300),
+                resets_at: None,
+            }),
+            secondary: Some(RateLimitWindow {
+                used_percent: secondary_used,
+                window_minutes: Some(10_080),
+                resets_at: None,
+            }),
+            credits: None,
+        }
+    }
+
+    fn messages(warnings: Vec<AccountRateLimitsWarningNotification>) -> Vec<String> {
+        warnings
+            .into_iter()
+            .map(|warning| warning.message)
+            .collect()
+    }
+
+    #[test]
+    fn warns_once_per_threshold_and_rearms_after_reset() {
+        let mut warnings = RateLimitWarnings::default();
+
+        assert_eq!(
+            messages(warnings.take_warnings(&snapshot(50.0, 10.0))),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            messages(warnings.take_warnings(&snapshot(92.0, 76.0))),
+            vec![
+                "Heads up, you have less than 25% of your weekly limit left.".to_string(),
+                "Heads up, you have less than 10% of your 5h limit left.".to_string(),
+            ]
+        );
+        assert_eq!(
+            messages(warnings.take_warnings(&snapshot(93.0, 80.0))),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            messages(warnings.take_warnings(&snapshot(5.0, 80.0))),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            messages(warnings.take_warnings(&snapshot(76.0, 80.0))),
+            vec!["Heads up, you have less than 25% of your 5h limit left.".to_string()]
+        );
+    }
+
+    #[test]
+    fn no_warnings_once_a_limit_is_reached() {
+        let mut warnings = RateLimitWarnings::default();
+        assert_eq!(
+            messages(warnings.take_warnings(&snapshot(100.0, 96.0))),
+            Vec::<String>::new()
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! `repository/onboard`: runs the core onboarding chain and forwards each
+//! step to the client as `repository/onboardingProgress`.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_app_server_protocol::RepositoryOnboardParams;
+use codex_app_server_protocol::RepositoryOnboardResponse;
+use codex_app_server_protocol::RepositoryOnboardingProgressNotification;
+use codex_app_server_protocol::RepositoryOnboardingStep;
+use codex_app_server_protocol::RepositoryOnboardingStepStatus;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ServerNotification;
+use codex_core::onboarding::OnboardingBackend;
+use codex_core::onboarding::OnboardingOptions;
+use codex_core::onboarding::OnboardingProgress;
+use codex_core::onboarding::OnboardingStep;
+use codex_core::onboarding::OnboardingStepStatus;
+use codex_core::onboarding::onboard_repository;
+use tokio::sync::mpsc;
+
+use crate::outgoing_message::OutgoingMessageSender;
+
+/// Onboards `params.cwd`, sending progress notifications before the response.
+pub(crate) async fn run_repository_onboarding(
+    outgoing: Arc<OutgoingMessageSender>,
+    request_id: RequestId,
+    codex_home: PathBuf,
+    backend: Arc<dyn OnboardingBackend>,
+    params: RepositoryOnboardParams,
+) {
+    let RepositoryOnboardParams {
+        cwd,
+        trust_repository,
+        generate_agents_md,
+    } = params;
+    let options = OnboardingOptions {
+        trust_repository,
+        generate_agents_md,
+    };
+
+    // Progress arrives through a sync callback; forward it on its own task so
+    // notifications go out while the slow steps run.
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let forward_outgoing = outgoing.clone();
+    let forward_cwd = cwd.clone();
+    let forwarder = tokio::spawn(async move {
+        while let Some(OnboardingProgress { step, status }) = rx.recv().await {
+            let notification = RepositoryOnboardingProgressNotification {
+                cwd: forward_cwd.clone(),
+                step: to_v2_step(step),
+                status: to_v2_status(status),
+            };
+            forward_outgoing
+                .send_server_notification(ServerNotification::RepositoryOnboardingProgress(
+                    notification,
+                ))
+                .await;
+        }
+    });
+
+    let report = onboard_repository(&codex_home, &cwd, backend.as_ref(), options, |progress| {
+        let _ = tx.send(progress);
+    })
+    .await;
+    drop(tx);
+    let _ = forwarder.await;
+
+    let response = RepositoryOnboardResponse {
+        repo_root: report.repo_root,
+        agents_md_prompt: report.agents_md_prompt,
+        failed_steps: report.failed_steps.into_iter().map(to_v2_step).collect(),
+    };
+    outgoing.send_response(request_id, response).await;
+}
+
+fn to_v2_step(step: OnboardingStep) -> RepositoryOnboardingStep {
+    match step {
+        OnboardingStep::DetectGit => RepositoryOnboardingStep::DetectGit,
+        OnboardingStep::RegisterRepository => RepositoryOnboardingStep::RegisterRepository,
+        OnboardingStep::AgentsMd => RepositoryOnboardingStep::AgentsMd,
+        OnboardingStep::SemanticIndex => RepositoryOnboardingStep::SemanticIndex,
+        OnboardingStep::Memory => RepositoryOnboardingStep::Memory,
+    }
+}
+
+fn to_v2_status(status: OnboardingStepStatus) -> RepositoryOnboardingStepStatus {
+    match status {
+        OnboardingStepStatus::Started => RepositoryOnboardingStepStatus::Started,
+        OnboardingStepStatus::Completed { detail } => {
+            RepositoryOnboardingStepStatus::Completed { detail }
+        }
+        OnboardingStepStatus::Skipped { reason } => {
+            RepositoryOnboardingStepStatus::Skipped { reason }
+        }
+        OnboardingStepStatus::NeedsConfirmation { message } => {
+            RepositoryOnboardingStepStatus::NeedsConfirmation { message }
+        }
+        OnboardingStepStatus::Failed { error } => RepositoryOnboardingStepStatus::Failed { error },
+    }
+}
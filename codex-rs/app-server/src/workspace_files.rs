@@ -0,0 +1,279 @@
+//! Workspace browsing for clients that show the project next to a thread:
+//! gitignore-aware tree listings, ranged file reads, and recursive watches
+//! that report changes as `file/changed` notifications.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_app_server_protocol::FileChangedNotification;
+use codex_app_server_protocol::FileReadResponse;
+use codex_app_server_protocol::FileSystemChangeKind;
+use codex_app_server_protocol::FileTreeNode;
+use codex_app_server_protocol::FileTreeNodeKind;
+use codex_app_server_protocol::FileTreeResponse;
+use codex_app_server_protocol::ServerNotification;
+use ignore::WalkBuilder;
+use ignore::gitignore::Gitignore;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use notify::event::ModifyKind;
+use notify::event::RenameMode;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::outgoing_message::OutgoingMessageSender;
+
+/// Upper bound on entries returned by a single tree listing.
+const MAX_TREE_ENTRIES: usize = 10_000;
+
+/// Lists `root` as a nested tree, skipping `.git` and anything excluded by
+/// ignore files. Blocking; run it on the blocking pool.
+pub(crate) fn build_file_tree(
+    root: &Path,
+    max_depth: Option<usize>,
+) -> io::Result<FileTreeResponse> {
+    if !root.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not a directory", root.display()),
+        ));
+    }
+
+    let mut walk_builder = WalkBuilder::new(root);
+    walk_builder
+        .hidden(false)
+        .require_git(false)
+        .max_depth(max_depth)
+        .filter_entry(|entry| entry.file_name() != ".git");
+
+    // Collect entries first, then attach each one to its parent deepest-first
+    // so every directory is complete by the time it is moved into its parent.
+    let mut entries: Vec<(usize, PathBuf, bool)> = Vec::new();
+    let mut truncated = false;
+    for entry in walk_builder.build().flatten() {
+        if entry.depth() == 0 {
+            continue;
+        }
+        if entries.len() >= MAX_TREE_ENTRIES {
+            truncated = true;
+            break;
+        }
+        let is_dir = entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir());
+        entries.push((entry.depth(), entry.into_path(), is_dir));
+    }
+    entries.sort_by(|(a, ..), (b, ..)| b.cmp(a));
+
+    let mut children: HashMap<PathBuf, Vec<FileTreeNode>> = HashMap::new();
+    for (_, path, is_dir) in entries {
+        let node = if is_dir {
+            let mut nodes = children.remove(&path).unwrap_or_default();
+            sort_nodes(&mut nodes);
+            tree_node(path.clone(), FileTreeNodeKind::Directory, nodes)
+        } else {
+            tree_node(path.clone(), FileTreeNodeKind::File, Vec::new())
+        };
+        if let Some(parent) = path.parent() {
+            children.entry(parent.to_path_buf()).or_default().push(node);
+        }
+    }
+
+    let mut nodes = children.remove(root).unwrap_or_default();
+    sort_nodes(&mut nodes);
+    Ok(FileTreeResponse {
+        root: tree_node(root.to_path_buf(), FileTreeNodeKind::Directory, nodes),
+        truncated,
+    })
+}
+
+fn tree_node(path: PathBuf, kind: FileTreeNodeKind, children: Vec<FileTreeNode>) -> FileTreeNode {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+    FileTreeNode {
+        name,
+        path,
+        kind,
+        children,
+    }
+}
+
+/// Directories first, then files, each group sorted by name.
+fn sort_nodes(nodes: &mut [FileTreeNode]) {
+    nodes.sort_by(|a, b| {
+        let a_is_file = a.kind == FileTreeNodeKind::File;
+        let b_is_file = b.kind == FileTreeNodeKind::File;
+        a_is_file.cmp(&b_is_file).then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+/// Returns the 1-based inclusive line range of `contents`, clamped to the
+/// lines that exist.
+pub(crate) fn read_line_range(
+    contents: &str,
+    start_line: Option<u32>,
+    end_line: Option<u32>,
+) -> FileReadResponse {
+    let lines: Vec<&str> = contents.split_inclusive('\n').collect();
+    let total_lines = u32::try_from(lines.len()).unwrap_or(u32::MAX);
+    let start_line = start_line.unwrap_or(1).max(1);
+    let end_line = end_line.unwrap_or(total_lines).min(total_lines);
+
+    let content = if start_line <= end_line {
+        lines[(start_line - 1) as usize..end_line as usize].concat()
+    } else {
+        String::new()
+    };
+
+    FileReadResponse {
+        content,
+        start_line,
+        end_line,
+        total_lines,
+    }
+}
+
+/// Starts watching `root` recursively. Changes to paths outside `.git` and
+/// not ignored by the root `.gitignore` are sent as `file/changed`
+/// notifications until the returned watcher is dropped.
+pub(crate) fn watch_directory(
+    root: &Path,
+    watch_id: String,
+    outgoing: Arc<OutgoingMessageSender>,
+) -> notify::Result<RecommendedWatcher> {
+    let root = root.canonicalize()?;
+    let (gitignore, error) = Gitignore::new(root.join(".gitignore"));
+    if let Some(error) = error {
+        warn!("failed to parse .gitignore in {}: {error}", root.display());
+    }
+
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let filter_root = root.clone();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let event = match res {
+            Ok(event) => event,
+            Err(err) => {
+                warn!("file watcher error: {err}");
+                return;
+            }
+        };
+        let Some(kind) = change_kind(event.kind) else {
+            return;
+        };
+        for path in event.paths {
+            if is_reported(&filter_root, &gitignore, &path) {
+                let _ = tx.send((path, kind));
+            }
+        }
+    })?;
+    watcher.watch(&root, RecursiveMode::Recursive)?;
+
+    // The callback owns the sender, so this loop ends once the watcher is dropped.
+    tokio::spawn(async move {
+        while let Some((path, kind)) = rx.recv().await {
+            outgoing
+                .send_server_notification(ServerNotification::FileChanged(
+                    FileChangedNotification {
+                        watch_id: watch_id.clone(),
+                        path,
+                        kind,
+                    },
+                ))
+                .await;
+        }
+    });
+
+    Ok(watcher)
+}
+
+fn change_kind(kind: EventKind) -> Option<FileSystemChangeKind> {
+    match kind {
+        EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+            Some(FileSystemChangeKind::Created)
+        }
+        EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+            Some(FileSystemChangeKind::Removed)
+        }
+        EventKind::Modify(_) => Some(FileSystemChangeKind::Modified),
+        EventKind::Access(_) | EventKind::Any | EventKind::Other => None,
+    }
+}
+
+fn is_reported(root: &Path, gitignore: &Gitignore, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(root) else {
+        return false;
+    };
+    if relative
+        .components()
+        .any(|component| component.as_os_str() == ".git")
+    {
+        return false;
+    }
+    !gitignore
+        .matched_path_or_any_parents(relative, path.is_dir())
+        .is_ignore()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn names(node: &FileTreeNode) -> Vec<&str> {
+        node.children
+            .iter()
+            .map(|child| child.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn file_tree_skips_ignored_entries_and_lists_directories_first() -> io::Result<()> {
+        let temp = tempfile::tempdir()?;
+        let root = temp.path();
+        std::fs::create_dir_all(root.join(".git"))?;
+        std::fs::create_dir_all(root.join("src"))?;
+        std::fs::create_dir_all(root.join("target/debug"))?;
+        std::fs::write(root.join(".gitignore"), "target/\n")?;
+        std::fs::write(root.join("README.md"), "readme\n")?;
+        std::fs::write(root.join("src/lib.rs"), "")?;
+        std::fs::write(root.join("target/debug/app"), "")?;
+
+        let tree = build_file_tree(root, None)?;
+
+        assert!(!tree.truncated);
+        assert_eq!(names(&tree.root), vec!["src", ".gitignore", "README.md"]);
+        assert_eq!(names(&tree.root.children[0]), vec!["lib.rs"]);
+        assert_eq!(
+            tree.root.children[0].children[0].path,
+            root.join("src/lib.rs")
+        );
+
+        let shallow = build_file_tree(root, Some(1))?;
+        assert!(shallow.root.children[0].children.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn read_line_range_clamps_to_existing_lines() {
+        let contents = "one\ntwo\nthree\n";
+
+        assert_eq!(
+            read_line_range(contents, Some(2), Some(10)),
+            FileReadResponse {
+                content: "two\nthree\n".to_string(),
+                start_line: 2,
+                end_line: 3,
+                total_lines: 3,
+            }
+        );
+        assert_eq!(read_line_range(contents, None, None).content, contents);
+        assert_eq!(read_line_range(contents, Some(5), None).content, "");
+    }
+}
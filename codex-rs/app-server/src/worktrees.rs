@@ -0,0 +1,143 @@
+//! Git worktree housekeeping for clients that run threads in separate
+//! checkouts: listing with stale detection, merge-back, and removal, plus
+//! the status/stage/commit/branch steps of a "Commit changes" flow.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_app_server_protocol::GitStatusEntry;
+use codex_app_server_protocol::GitStatusResponse;
+use codex_app_server_protocol::Worktree;
+use codex_app_server_protocol::WorktreeListResponse;
+use codex_app_server_protocol::WorktreeMergeResponse;
+use codex_git::GitToolingError;
+use codex_git::StatusEntry;
+use codex_git::WorktreeInfo;
+
+/// Idle time after which a clean, unused worktree is reported as stale.
+pub(crate) const DEFAULT_STALE_AFTER_DAYS: u32 = 7;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Lists the worktrees of the repository at `cwd`, flagging stale ones.
+/// Worktrees containing any of `active_paths` are never stale. Blocking.
+pub(crate) fn list_worktrees(
+    cwd: &Path,
+    stale_after_days: u32,
+    active_paths: &[PathBuf],
+) -> Result<WorktreeListResponse, GitToolingError> {
+    let max_age = Duration::from_secs(u64::from(stale_after_days) * SECONDS_PER_DAY);
+    let stale = codex_git::find_stale_worktrees(cwd, max_age, active_paths)?;
+    let data = codex_git::list_worktrees(cwd)?
+        .into_iter()
+        .map(|info| {
+            let is_stale = stale.iter().any(|candidate| candidate.path == info.path);
+            to_worktree(info, is_stale)
+        })
+        .collect();
+    Ok(WorktreeListResponse { data })
+}
+
+/// Removes the worktree at `path` and, when `delete_branch` is set, its
+/// branch. Returns the branch the worktree had checked out. Blocking.
+pub(crate) fn remove_worktree(
+    cwd: &Path,
+    path: &Path,
+    force: bool,
+    delete_branch: bool,
+) -> Result<Option<String>, GitToolingError> {
+    let branch =
+        find_worktree(cwd, |info| same_path(&info.path, path))?.and_then(|info| info.branch);
+    codex_git::remove_worktree(cwd, path, force)?;
+    if delete_branch && let Some(branch) = &branch {
+        codex_git::delete_branch(cwd, branch, force)?;
+    }
+    Ok(branch)
+}
+
+/// Merges `branch` into the main worktree. With `clean_up`, also removes the
+/// worktree that has `branch` checked out and deletes the branch. Blocking.
+pub(crate) fn merge_worktree(
+    cwd: &Path,
+    branch: &str,
+    squash: bool,
+    clean_up: bool,
+) -> Result<WorktreeMergeResponse, GitToolingError> {
+    let commit = codex_git::merge_worktree_into_base(cwd, branch, squash)?;
+    let mut removed_path = None;
+    if clean_up {
+        let worktree = find_worktree(cwd, |info| {
+            !info.is_main && info.branch.as_deref() == Some(branch)
+        })?;
+        if let Some(worktree) = worktree {
+            codex_git::remove_worktree(cwd, &worktree.path, false)?;
+            removed_path = Some(worktree.path);
+        }
+        // A squashed branch never looks merged to git, so `-d` would refuse it.
+        codex_git::delete_branch(cwd, branch, squash)?;
+    }
+    Ok(WorktreeMergeResponse {
+        commit,
+        removed_path,
+    })
+}
+
+/// Branch and changed paths of the repository at `cwd`. Blocking.
+pub(crate) fn git_status(cwd: &Path) -> Result<GitStatusResponse, GitToolingError> {
+    let status = codex_git::working_tree_status(cwd)?;
+    Ok(GitStatusResponse {
+        branch: status.branch,
+        entries: status
+            .entries
+            .into_iter()
+            .map(to_git_status_entry)
+            .collect(),
+    })
+}
+
+fn to_git_status_entry(entry: StatusEntry) -> GitStatusEntry {
+    let staged = entry.is_staged();
+    let StatusEntry {
+        path,
+        original_path,
+        index,
+        worktree,
+    } = entry;
+    GitStatusEntry {
+        path,
+        original_path,
+        index,
+        worktree,
+        staged,
+    }
+}
+
+fn find_worktree(
+    cwd: &Path,
+    predicate: impl Fn(&WorktreeInfo) -> bool,
+) -> Result<Option<WorktreeInfo>, GitToolingError> {
+    Ok(codex_git::list_worktrees(cwd)?.into_iter().find(predicate))
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    a == b || matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b)
+}
+
+fn to_worktree(info: WorktreeInfo, stale: bool) -> Worktree {
+    let WorktreeInfo {
+        path,
+        head,
+        branch,
+        is_main,
+        locked,
+    } = info;
+    Worktree {
+        path,
+        branch,
+        head,
+        is_main,
+        locked,
+        stale,
+    }
+}
@@ -20,12 +20,18 @@ use codex_app_server_protocol::ClientInfo;
 use codex_app_server_protocol::ClientNotification;
 use codex_app_server_protocol::ConfigBatchWriteParams;
 use codex_app_server_protocol::ConfigReadParams;
+use codex_app_server_protocol::ConfigReloadParams;
 use codex_app_server_protocol::ConfigValueWriteParams;
 use codex_app_server_protocol::FeedbackUploadParams;
 use codex_app_server_protocol::GetAccountParams;
 use codex_app_server_protocol::GetAuthStatusParams;
+use codex_app_server_protocol::GitBranchParams;
+use codex_app_server_protocol::GitCommitParams;
+use codex_app_server_protocol::GitStageParams;
+use codex_app_server_protocol::GitStatusParams;
 use codex_app_server_protocol::InitializeParams;
 use codex_app_server_protocol::InterruptConversationParams;
+use codex_app_server_protocol::ItemCancelParams;
 use codex_app_server_protocol::JSONRPCError;
 use codex_app_server_protocol::JSONRPCMessage;
 use codex_app_server_protocol::JSONRPCNotification;
@@ -36,16 +42,26 @@ use codex_app_server_protocol::LoginApiKeyParams;
 use codex_app_server_protocol::ModelListParams;
 use codex_app_server_protocol::NewConversationParams;
 use codex_app_server_protocol::RemoveConversationListenerParams;
+use codex_app_server_protocol::RepositoryOnboardParams;
 use codex_app_server_protocol::RequestId;
 use codex_app_server_protocol::ResumeConversationParams;
 use codex_app_server_protocol::ReviewStartParams;
+use codex_app_server_protocol::RolloutArchiveParams;
+use codex_app_server_protocol::RolloutDeleteParams;
+use codex_app_server_protocol::RolloutListParams;
 use codex_app_server_protocol::SendUserMessageParams;
 use codex_app_server_protocol::SendUserTurnParams;
 use codex_app_server_protocol::ServerRequest;
 use codex_app_server_protocol::SetDefaultModelParams;
+use codex_app_server_protocol::SubagentCancelParams;
 use codex_app_server_protocol::ThreadArchiveParams;
 use codex_app_server_protocol::ThreadListParams;
+use codex_app_server_protocol::ThreadPlanApproveParams;
+use codex_app_server_protocol::ThreadPlanSendFeedbackParams;
+use codex_app_server_protocol::ThreadPlanSetModeParams;
 use codex_app_server_protocol::ThreadResumeParams;
+use codex_app_server_protocol::ThreadSetFocusParams;
+use codex_app_server_protocol::ThreadSetWebhookParams;
 use codex_app_server_protocol::ThreadStartParams;
 use codex_app_server_protocol::TurnInterruptParams;
 use codex_app_server_protocol::TurnStartParams;
@@ -322,6 +338,33 @@ impl McpProcess {
         self.send_request("thread/archive", params).await
     }
 
+    /// Send a `rollout/list` JSON-RPC request.
+    pub async fn send_rollout_list_request(
+        &mut self,
+        params: RolloutListParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("rollout/list", params).await
+    }
+
+    /// Send a `rollout/delete` JSON-RPC request.
+    pub async fn send_rollout_delete_request(
+        &mut self,
+        params: RolloutDeleteParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("rollout/delete", params).await
+    }
+
+    /// Send a `rollout/archive` JSON-RPC request.
+    pub async fn send_rollout_archive_request(
+        &mut self,
+        params: RolloutArchiveParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("rollout/archive", params).await
+    }
+
     /// Send a `thread/list` JSON-RPC request.
     pub async fn send_thread_list_request(
         &mut self,
@@ -381,6 +424,111 @@ impl McpProcess {
         self.send_request("turn/interrupt", params).await
     }
 
+    /// Send an `item/cancel` JSON-RPC request (v2).
+    pub async fn send_item_cancel_request(
+        &mut self,
+        params: ItemCancelParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("item/cancel", params).await
+    }
+
+    /// Send a `subagent/cancel` JSON-RPC request (v2).
+    pub async fn send_subagent_cancel_request(
+        &mut self,
+        params: SubagentCancelParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("subagent/cancel", params).await
+    }
+
+    /// Send a `git/status` JSON-RPC request (v2).
+    pub async fn send_git_status_request(
+        &mut self,
+        params: GitStatusParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("git/status", params).await
+    }
+
+    /// Send a `git/stage` JSON-RPC request (v2).
+    pub async fn send_git_stage_request(&mut self, params: GitStageParams) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("git/stage", params).await
+    }
+
+    /// Send a `git/commit` JSON-RPC request (v2).
+    pub async fn send_git_commit_request(
+        &mut self,
+        params: GitCommitParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("git/commit", params).await
+    }
+
+    /// Send a `git/branch` JSON-RPC request (v2).
+    pub async fn send_git_branch_request(
+        &mut self,
+        params: GitBranchParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("git/branch", params).await
+    }
+
+    /// Send a `repository/onboard` JSON-RPC request (v2).
+    pub async fn send_repository_onboard_request(
+        &mut self,
+        params: RepositoryOnboardParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("repository/onboard", params).await
+    }
+
+    /// Send a `thread/setFocus` JSON-RPC request (v2).
+    pub async fn send_thread_set_focus_request(
+        &mut self,
+        params: ThreadSetFocusParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/setFocus", params).await
+    }
+
+    /// Send a `thread/setWebhook` JSON-RPC request (v2).
+    pub async fn send_thread_set_webhook_request(
+        &mut self,
+        params: ThreadSetWebhookParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/setWebhook", params).await
+    }
+
+    /// Send a `thread/plan/setMode` JSON-RPC request (v2).
+    pub async fn send_thread_plan_set_mode_request(
+        &mut self,
+        params: ThreadPlanSetModeParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/plan/setMode", params).await
+    }
+
+    /// Send a `thread/plan/approve` JSON-RPC request (v2).
+    pub async fn send_thread_plan_approve_request(
+        &mut self,
+        params: ThreadPlanApproveParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/plan/approve", params).await
+    }
+
+    /// Send a `thread/plan/sendFeedback` JSON-RPC request (v2).
+    pub async fn send_thread_plan_send_feedback_request(
+        &mut self,
+        params: ThreadPlanSendFeedbackParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("thread/plan/sendFeedback", params).await
+    }
+
     /// Send a `review/start` JSON-RPC request (v2).
     pub async fn send_review_start_request(
         &mut self,
@@ -428,6 +576,15 @@ impl McpProcess {
         self.send_request("config/batchWrite", params).await
     }
 
+    /// Send a `config/reload` JSON-RPC request.
+    pub async fn send_config_reload_request(
+        &mut self,
+        params: ConfigReloadParams,
+    ) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("config/reload", params).await
+    }
+
     /// Send an `account/logout` JSON-RPC request.
     pub async fn send_logout_account_request(&mut self) -> anyhow::Result<i64> {
         self.send_request("account/logout", None).await
@@ -617,6 +774,32 @@ impl McpProcess {
         }
     }
 
+    /// Reads until the response or the error for `request_id` arrives.
+    pub async fn read_stream_until_request_result(
+        &mut self,
+        request_id: RequestId,
+    ) -> anyhow::Result<JSONRPCMessage> {
+        loop {
+            let message = self.read_jsonrpc_message().await?;
+            match message {
+                JSONRPCMessage::Notification(notification) => {
+                    eprintln!("notification: {notification:?}");
+                    self.enqueue_user_message(notification);
+                }
+                JSONRPCMessage::Request(_) => {
+                    anyhow::bail!("unexpected JSONRPCMessage::Request: {message:?}");
+                }
+                JSONRPCMessage::Response(ref response) if response.id == request_id => {
+                    return Ok(message);
+                }
+                JSONRPCMessage::Error(ref err) if err.id == request_id => {
+                    return Ok(message);
+                }
+                JSONRPCMessage::Response(_) | JSONRPCMessage::Error(_) => {}
+            }
+        }
+    }
+
     pub async fn read_stream_until_notification_message(
         &mut self,
         method: &str,
@@ -649,6 +832,32 @@ impl McpProcess {
         }
     }
 
+    /// Reads notifications until one with `method` arrives and returns all of
+    /// them, that one last, so tests can check what was (not) sent before it.
+    pub async fn read_notifications_until(
+        &mut self,
+        method: &str,
+    ) -> anyhow::Result<Vec<JSONRPCNotification>> {
+        let mut notifications = Vec::new();
+        loop {
+            let message = self.read_jsonrpc_message().await?;
+            match message {
+                JSONRPCMessage::Notification(notification) => {
+                    let done = notification.method == method;
+                    notifications.push(notification);
+                    if done {
+                        return Ok(notifications);
+                    }
+                }
+                JSONRPCMessage::Request(_)
+                | JSONRPCMessage::Error(_)
+                | JSONRPCMessage::Response(_) => {
+                    anyhow::bail!("unexpected message while reading notifications: {message:?}");
+                }
+            }
+        }
+    }
+
     fn take_pending_notification_by_method(&mut self, method: &str) -> Option<JSONRPCNotification> {
         if let Some(pos) = self
             .pending_user_messages
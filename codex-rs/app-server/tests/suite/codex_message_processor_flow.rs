@@ -415,6 +415,9 @@ async fn test_send_user_turn_updates_sandbox_and_cwd_between_turns() -> Result<(
                 network_access: false,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                allow_write: vec![],
+                deny_write: vec![],
+                network_allowlist: vec![],
             },
             model: model.clone(),
             effort: Some(ReasoningEffort::Medium),
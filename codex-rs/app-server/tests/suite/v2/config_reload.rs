@@ -0,0 +1,256 @@
+use std::path::Path;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_mock_chat_completions_server;
+use app_test_support::to_response;
+use codex_app_server_protocol::ConfigChangedNotification;
+use codex_app_server_protocol::ConfigReloadParams;
+use codex_app_server_protocol::ConfigReloadResponse;
+use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::JSONRPCNotification;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn config_reload_reports_applied_and_session_keys() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "")?;
+    let (mut mcp, thread_id) = start_thread(codex_home.path()).await?;
+
+    write_config_toml(
+        codex_home.path(),
+        &server.uri(),
+        "hide_agent_reasoning = true\n",
+        "never",
+    )?;
+    let response: ConfigReloadResponse = to_response(reload(&mut mcp, &thread_id).await?)?;
+    assert_eq!(
+        response,
+        ConfigReloadResponse {
+            applied: vec!["hide_agent_reasoning".to_string()],
+            requires_new_session: Vec::new(),
+        }
+    );
+
+    write_config_toml(
+        codex_home.path(),
+        &server.uri(),
+        "hide_agent_reasoning = true\n",
+        "on-request",
+    )?;
+    let response: ConfigReloadResponse = to_response(reload(&mut mcp, &thread_id).await?)?;
+    assert_eq!(
+        response,
+        ConfigReloadResponse {
+            applied: Vec::new(),
+            requires_new_session: vec!["approval_policy".to_string()],
+        }
+    );
+
+    // Nothing changed since the last reload.
+    let response: ConfigReloadResponse = to_response(reload(&mut mcp, &thread_id).await?)?;
+    assert_eq!(
+        response,
+        ConfigReloadResponse {
+            applied: Vec::new(),
+            requires_new_session: Vec::new(),
+        }
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn config_reload_rejects_unparsable_config() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "")?;
+    let (mut mcp, thread_id) = start_thread(codex_home.path()).await?;
+
+    std::fs::write(codex_home.path().join("config.toml"), "model = [")?;
+    let request_id = mcp
+        .send_config_reload_request(ConfigReloadParams {
+            thread_id: thread_id.clone(),
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert!(
+        error
+            .error
+            .message
+            .starts_with("failed to reload config.toml"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    // The thread kept its settings, so the next reload compares against them.
+    create_config_toml(
+        codex_home.path(),
+        &server.uri(),
+        "hide_agent_reasoning = true\n",
+    )?;
+    let response: ConfigReloadResponse = to_response(reload(&mut mcp, &thread_id).await?)?;
+    assert_eq!(response.applied, vec!["hide_agent_reasoning".to_string()]);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn config_reload_rejects_unknown_threads() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_config_reload_request(ConfigReloadParams {
+            thread_id: "not-a-thread".to_string(),
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert!(
+        error.error.message.starts_with("invalid thread id"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn config_edits_are_announced_with_config_changed() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "")?;
+    let (mut mcp, thread_id) = start_thread(codex_home.path()).await?;
+
+    // No `config/reload`: the thread's watcher notices the edit on its own.
+    write_config_toml(
+        codex_home.path(),
+        &server.uri(),
+        "show_raw_agent_reasoning = true\n",
+        "untrusted",
+    )?;
+    let notification: JSONRPCNotification = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("config/changed"),
+    )
+    .await??;
+    let changed: ConfigChangedNotification =
+        serde_json::from_value(notification.params.expect("params must be present"))?;
+    assert_eq!(
+        changed,
+        ConfigChangedNotification {
+            thread_id,
+            applied: vec!["show_raw_agent_reasoning".to_string()],
+            requires_new_session: vec!["approval_policy".to_string()],
+        }
+    );
+
+    Ok(())
+}
+
+/// Starts a thread and waits until it watches `config.toml`.
+async fn start_thread(codex_home: &Path) -> Result<(McpProcess, String)> {
+    let mut mcp = McpProcess::new(codex_home).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let thread_req = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let thread_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(thread_req)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(thread_resp)?;
+
+    // The watcher loads its baseline in the background after `thread/start`;
+    // until then reloads are refused.
+    timeout(DEFAULT_READ_TIMEOUT, async {
+        loop {
+            let request_id = mcp
+                .send_config_reload_request(ConfigReloadParams {
+                    thread_id: thread.id.clone(),
+                })
+                .await?;
+            let message = mcp
+                .read_stream_until_request_result(RequestId::Integer(request_id))
+                .await?;
+            if matches!(message, JSONRPCMessage::Response(_)) {
+                return anyhow::Ok(());
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+    })
+    .await??;
+
+    Ok((mcp, thread.id))
+}
+
+async fn reload(mcp: &mut McpProcess, thread_id: &str) -> Result<JSONRPCResponse> {
+    let request_id = mcp
+        .send_config_reload_request(ConfigReloadParams {
+            thread_id: thread_id.to_string(),
+        })
+        .await?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await?
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str, extra: &str) -> std::io::Result<()> {
+    write_config_toml(codex_home, server_uri, extra, "never")
+}
+
+fn write_config_toml(
+    codex_home: &Path,
+    server_uri: &str,
+    extra: &str,
+    approval_policy: &str,
+) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "{approval_policy}"
+sandbox_mode = "read-only"
+{extra}
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
@@ -0,0 +1,198 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_final_assistant_message_sse_response;
+use app_test_support::create_mock_chat_completions_server;
+use app_test_support::to_response;
+use codex_app_server_protocol::DesktopNotificationKind;
+use codex_app_server_protocol::JSONRPCNotification;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadDesktopNotification;
+use codex_app_server_protocol::ThreadSetFocusParams;
+use codex_app_server_protocol::ThreadSetFocusResponse;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::TurnStartResponse;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn turn_completion_is_notified_only_while_unfocused() -> Result<()> {
+    let responses = vec![
+        create_final_assistant_message_sse_response("First reply")?,
+        create_final_assistant_message_sse_response("Second reply")?,
+    ];
+    let server = create_mock_chat_completions_server(responses).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    let thread_id = start_thread(&mut mcp).await?;
+
+    let notifications = run_turn(&mut mcp, &thread_id, "Hello").await?;
+    assert_eq!(
+        desktop_notifications(&notifications)?,
+        vec![ThreadDesktopNotification {
+            thread_id: thread_id.clone(),
+            kind: DesktopNotificationKind::TurnComplete,
+            message: "First reply".to_string(),
+        }]
+    );
+
+    let focus_req = mcp
+        .send_thread_set_focus_request(ThreadSetFocusParams {
+            thread_id: thread_id.clone(),
+            focused: true,
+        })
+        .await?;
+    let focus_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(focus_req)),
+    )
+    .await??;
+    let _: ThreadSetFocusResponse = to_response(focus_resp)?;
+
+    let notifications = run_turn(&mut mcp, &thread_id, "Again").await?;
+    assert_eq!(desktop_notifications(&notifications)?, Vec::new());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn disabled_notification_types_are_not_sent() -> Result<()> {
+    let responses = vec![create_final_assistant_message_sse_response("Done")?];
+    let server = create_mock_chat_completions_server(responses).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(
+        codex_home.path(),
+        &server.uri(),
+        "[gui]\nnotifications = [\"approval-requested\", \"error\"]\n",
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    let thread_id = start_thread(&mut mcp).await?;
+
+    let notifications = run_turn(&mut mcp, &thread_id, "Hello").await?;
+    assert_eq!(desktop_notifications(&notifications)?, Vec::new());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn set_focus_rejects_unknown_threads() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri(), "")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let focus_req = mcp
+        .send_thread_set_focus_request(ThreadSetFocusParams {
+            thread_id: "not-a-thread".to_string(),
+            focused: true,
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(focus_req)),
+    )
+    .await??;
+    assert!(
+        error.error.message.starts_with("invalid thread id"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    Ok(())
+}
+
+async fn start_thread(mcp: &mut McpProcess) -> Result<String> {
+    let thread_req = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let thread_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(thread_req)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(thread_resp)?;
+    Ok(thread.id)
+}
+
+/// Runs a turn and returns the notifications sent until it completed.
+async fn run_turn(
+    mcp: &mut McpProcess,
+    thread_id: &str,
+    text: &str,
+) -> Result<Vec<JSONRPCNotification>> {
+    let turn_req = mcp
+        .send_turn_start_request(TurnStartParams {
+            thread_id: thread_id.to_string(),
+            input: vec![V2UserInput::Text {
+                text: text.to_string(),
+            }],
+            ..Default::default()
+        })
+        .await?;
+    let turn_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(turn_req)),
+    )
+    .await??;
+    let _: TurnStartResponse = to_response(turn_resp)?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_notifications_until("turn/completed"),
+    )
+    .await?
+}
+
+fn desktop_notifications(
+    notifications: &[JSONRPCNotification],
+) -> Result<Vec<ThreadDesktopNotification>> {
+    notifications
+        .iter()
+        .filter(|notification| notification.method == "thread/desktopNotification")
+        .map(|notification| {
+            Ok(serde_json::from_value(
+                notification.params.clone().expect("params must be present"),
+            )?)
+        })
+        .collect()
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str, extra: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+
+{extra}"#
+        ),
+    )
+}
@@ -0,0 +1,231 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_mock_chat_completions_server;
+use app_test_support::to_response;
+use codex_app_server_protocol::GitBranchParams;
+use codex_app_server_protocol::GitBranchResponse;
+use codex_app_server_protocol::GitCommitParams;
+use codex_app_server_protocol::GitCommitResponse;
+use codex_app_server_protocol::GitStageParams;
+use codex_app_server_protocol::GitStageResponse;
+use codex_app_server_protocol::GitStatusEntry;
+use codex_app_server_protocol::GitStatusParams;
+use codex_app_server_protocol::GitStatusResponse;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn git_endpoints_stage_commit_and_branch() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let repo = TempDir::new()?;
+    init_repo(repo.path())?;
+    std::fs::write(repo.path().join("notes.md"), "hello\n")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let status = git_status(&mut mcp, repo.path()).await?;
+    assert_eq!(
+        status,
+        GitStatusResponse {
+            branch: Some("main".to_string()),
+            entries: vec![GitStatusEntry {
+                path: PathBuf::from("notes.md"),
+                original_path: None,
+                index: "?".to_string(),
+                worktree: "?".to_string(),
+                staged: false,
+            }],
+        }
+    );
+
+    let branch_req = mcp
+        .send_git_branch_request(GitBranchParams {
+            cwd: repo.path().to_path_buf(),
+            name: "notes".to_string(),
+        })
+        .await?;
+    let branch_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(branch_req)),
+    )
+    .await??;
+    let _: GitBranchResponse = to_response(branch_resp)?;
+
+    let stage_req = mcp
+        .send_git_stage_request(GitStageParams {
+            cwd: repo.path().to_path_buf(),
+            paths: vec![PathBuf::from("notes.md")],
+        })
+        .await?;
+    let stage_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(stage_req)),
+    )
+    .await??;
+    let _: GitStageResponse = to_response(stage_resp)?;
+
+    let status = git_status(&mut mcp, repo.path()).await?;
+    assert_eq!(status.branch, Some("notes".to_string()));
+    assert_eq!(
+        status.entries,
+        vec![GitStatusEntry {
+            path: PathBuf::from("notes.md"),
+            original_path: None,
+            index: "A".to_string(),
+            worktree: " ".to_string(),
+            staged: true,
+        }]
+    );
+
+    let commit_req = mcp
+        .send_git_commit_request(GitCommitParams {
+            cwd: repo.path().to_path_buf(),
+            message: "Add notes".to_string(),
+        })
+        .await?;
+    let commit_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(commit_req)),
+    )
+    .await??;
+    let GitCommitResponse { commit } = to_response(commit_resp)?;
+    assert_eq!(commit, git(repo.path(), &["rev-parse", "HEAD"])?);
+    assert_eq!(
+        git(repo.path(), &["log", "-1", "--format=%s"])?,
+        "Add notes"
+    );
+
+    let status = git_status(&mut mcp, repo.path()).await?;
+    assert_eq!(status.entries, Vec::new());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn git_endpoints_reject_bad_requests() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let not_a_repo = TempDir::new()?;
+    let repo = TempDir::new()?;
+    init_repo(repo.path())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let status_req = mcp
+        .send_git_status_request(GitStatusParams {
+            cwd: not_a_repo.path().to_path_buf(),
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(status_req)),
+    )
+    .await??;
+    assert!(
+        error.error.message.starts_with("failed to read git status"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    let commit_req = mcp
+        .send_git_commit_request(GitCommitParams {
+            cwd: repo.path().to_path_buf(),
+            message: "  ".to_string(),
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(commit_req)),
+    )
+    .await??;
+    assert_eq!(error.error.message, "commit message is empty");
+
+    // Nothing is staged, so git refuses the commit.
+    let commit_req = mcp
+        .send_git_commit_request(GitCommitParams {
+            cwd: repo.path().to_path_buf(),
+            message: "Empty".to_string(),
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(commit_req)),
+    )
+    .await??;
+    assert!(
+        error.error.message.starts_with("failed to commit"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    Ok(())
+}
+
+async fn git_status(mcp: &mut McpProcess, cwd: &Path) -> Result<GitStatusResponse> {
+    let request_id = mcp
+        .send_git_status_request(GitStatusParams {
+            cwd: cwd.to_path_buf(),
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    to_response(response)
+}
+
+/// Repository on `main` with one commit, so `HEAD` resolves.
+fn init_repo(path: &Path) -> Result<()> {
+    git(path, &["init", "--initial-branch=main"])?;
+    git(path, &["config", "user.name", "Test"])?;
+    git(path, &["config", "user.email", "test@example.com"])?;
+    git(path, &["commit", "--allow-empty", "--message", "init"])?;
+    Ok(())
+}
+
+fn git(path: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git").args(args).current_dir(path).output()?;
+    anyhow::ensure!(
+        output.status.success(),
+        "git {args:?} failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(String::from_utf8(output.stdout)?.trim().to_string())
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
@@ -0,0 +1,208 @@
+#![cfg(unix)]
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_final_assistant_message_sse_response;
+use app_test_support::create_mock_chat_completions_server;
+use app_test_support::create_shell_command_sse_response;
+use app_test_support::to_response;
+use codex_app_server_protocol::CommandExecutionStatus;
+use codex_app_server_protocol::ItemCancelParams;
+use codex_app_server_protocol::ItemCancelResponse;
+use codex_app_server_protocol::ItemCompletedNotification;
+use codex_app_server_protocol::ItemStartedNotification;
+use codex_app_server_protocol::JSONRPCNotification;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadItem;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnCompletedNotification;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::TurnStartResponse;
+use codex_app_server_protocol::TurnStatus;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn item_cancel_stops_a_running_command_and_the_turn_continues() -> Result<()> {
+    let tmp = TempDir::new()?;
+    let codex_home = tmp.path().join("codex_home");
+    std::fs::create_dir(&codex_home)?;
+    let working_directory = tmp.path().join("workdir");
+    std::fs::create_dir(&working_directory)?;
+
+    // A long-running command, then the reply once the model learns it was cancelled.
+    let server = create_mock_chat_completions_server(vec![
+        create_shell_command_sse_response(
+            vec!["sleep".to_string(), "30".to_string()],
+            Some(&working_directory),
+            Some(30_000),
+            "call_sleep",
+        )?,
+        create_final_assistant_message_sse_response("Stopped the command")?,
+    ])
+    .await;
+    create_config_toml(&codex_home, &server.uri())?;
+
+    let mut mcp = McpProcess::new(&codex_home).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let thread_req = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let thread_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(thread_req)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(thread_resp)?;
+
+    let turn_req = mcp
+        .send_turn_start_request(TurnStartParams {
+            thread_id: thread.id.clone(),
+            input: vec![V2UserInput::Text {
+                text: "run sleep".to_string(),
+            }],
+            cwd: Some(working_directory.clone()),
+            ..Default::default()
+        })
+        .await?;
+    let turn_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(turn_req)),
+    )
+    .await??;
+    let TurnStartResponse { turn } = to_response::<TurnStartResponse>(turn_resp)?;
+
+    // Wait until the command is running before cancelling it.
+    loop {
+        let started: ItemStartedNotification = read_notification(&mut mcp, "item/started").await?;
+        if let ThreadItem::CommandExecution { id, .. } = started.item {
+            assert_eq!(id, "call_sleep");
+            break;
+        }
+    }
+
+    let cancel_req = mcp
+        .send_item_cancel_request(ItemCancelParams {
+            thread_id: thread.id.clone(),
+            item_id: "call_sleep".to_string(),
+        })
+        .await?;
+    let cancel_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(cancel_req)),
+    )
+    .await??;
+    let _: ItemCancelResponse = to_response(cancel_resp)?;
+
+    let completed = loop {
+        let completed: ItemCompletedNotification =
+            read_notification(&mut mcp, "item/completed").await?;
+        if let ThreadItem::CommandExecution { .. } = completed.item {
+            break completed;
+        }
+    };
+    assert_eq!(completed.turn_id, turn.id);
+    let ThreadItem::CommandExecution {
+        id,
+        status,
+        aggregated_output,
+        exit_code,
+        duration_ms,
+        ..
+    } = completed.item
+    else {
+        unreachable!("matched above");
+    };
+    assert_eq!(id, "call_sleep");
+    assert_eq!(status, CommandExecutionStatus::Cancelled);
+    assert_eq!(aggregated_output, None);
+    assert_eq!(exit_code, None);
+    assert!(
+        duration_ms.is_some_and(|ms| ms < 30_000),
+        "unexpected duration: {duration_ms:?}"
+    );
+
+    // Only the tool call stops; the model is told and the turn finishes normally.
+    let turn_completed: TurnCompletedNotification =
+        read_notification(&mut mcp, "turn/completed").await?;
+    assert_eq!(turn_completed.thread_id, thread.id);
+    assert_eq!(turn_completed.turn.status, TurnStatus::Completed);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn item_cancel_rejects_unknown_threads() -> Result<()> {
+    let tmp = TempDir::new()?;
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    create_config_toml(tmp.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(tmp.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let cancel_req = mcp
+        .send_item_cancel_request(ItemCancelParams {
+            thread_id: "not-a-thread".to_string(),
+            item_id: "call_sleep".to_string(),
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(cancel_req)),
+    )
+    .await??;
+    assert!(
+        error.error.message.starts_with("invalid thread id"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    Ok(())
+}
+
+async fn read_notification<T: serde::de::DeserializeOwned>(
+    mcp: &mut McpProcess,
+    method: &str,
+) -> Result<T> {
+    let notification: JSONRPCNotification = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message(method),
+    )
+    .await??;
+    Ok(serde_json::from_value(
+        notification.params.expect("params must be present"),
+    )?)
+}
+
+fn create_config_toml(codex_home: &std::path::Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "workspace-write"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
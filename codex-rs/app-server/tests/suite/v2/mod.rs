@@ -1,11 +1,21 @@
 mod account;
+mod config_reload;
 mod config_rpc;
+mod cost_limit;
+mod desktop_notifications;
+mod git;
+mod item_cancel;
 mod model_list;
+mod plan_mode;
 mod rate_limits;
+mod repository_onboard;
 mod review;
+mod rollout_rpc;
+mod subagent_cancel;
 mod thread_archive;
 mod thread_list;
 mod thread_resume;
+mod thread_set_webhook;
 mod thread_start;
 mod turn_interrupt;
 mod turn_start;
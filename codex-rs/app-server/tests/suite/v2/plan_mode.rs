@@ -0,0 +1,299 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_final_assistant_message_sse_response;
+use app_test_support::create_mock_chat_completions_server;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCNotification;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadPlan;
+use codex_app_server_protocol::ThreadPlanApproveParams;
+use codex_app_server_protocol::ThreadPlanApproveResponse;
+use codex_app_server_protocol::ThreadPlanReadyNotification;
+use codex_app_server_protocol::ThreadPlanSendFeedbackParams;
+use codex_app_server_protocol::ThreadPlanSendFeedbackResponse;
+use codex_app_server_protocol::ThreadPlanSetModeParams;
+use codex_app_server_protocol::ThreadPlanSetModeResponse;
+use codex_app_server_protocol::ThreadPlanStep;
+use codex_app_server_protocol::ThreadPlanStepStatus;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::TurnStartResponse;
+use codex_app_server_protocol::TurnStartedNotification;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use codex_core::protocol_config_types::PlanDetailPreference;
+use pretty_assertions::assert_eq;
+use serde_json::json;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+use wiremock::MockServer;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn plan_mode_drafts_revises_and_approves_a_plan() -> Result<()> {
+    let responses = vec![
+        create_update_plan_sse_response("call-plan")?,
+        create_final_assistant_message_sse_response("Plan drafted")?,
+        create_final_assistant_message_sse_response("Plan revised")?,
+        create_final_assistant_message_sse_response("Implemented")?,
+    ];
+    let server = create_mock_chat_completions_server(responses).await;
+
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let thread_req = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let thread_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(thread_req)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(thread_resp)?;
+
+    let mode_req = mcp
+        .send_thread_plan_set_mode_request(ThreadPlanSetModeParams {
+            thread_id: thread.id.clone(),
+            enabled: true,
+            detail: Some(PlanDetailPreference::Coarse),
+        })
+        .await?;
+    let mode_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(mode_req)),
+    )
+    .await??;
+    let _: ThreadPlanSetModeResponse = to_response(mode_resp)?;
+
+    // In plan mode, turn/start drafts a plan instead of running the request.
+    let turn_req = mcp
+        .send_turn_start_request(TurnStartParams {
+            thread_id: thread.id.clone(),
+            input: vec![V2UserInput::Text {
+                text: "Add telemetry".to_string(),
+            }],
+            ..Default::default()
+        })
+        .await?;
+    let turn_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(turn_req)),
+    )
+    .await??;
+    let _: TurnStartResponse = to_response(turn_resp)?;
+
+    let ready = read_plan_ready(&mut mcp).await?;
+    let plan = ready.plan.clone().expect("plan published with update_plan");
+    assert_eq!(
+        ready,
+        ThreadPlanReadyNotification {
+            thread_id: thread.id.clone(),
+            request: "Add telemetry".to_string(),
+            plan: Some(ThreadPlan {
+                explanation: None,
+                steps: vec![
+                    ThreadPlanStep {
+                        step: "Add counters".to_string(),
+                        status: ThreadPlanStepStatus::Pending,
+                        overridden: false,
+                    },
+                    ThreadPlanStep {
+                        step: "Wire exporter".to_string(),
+                        status: ThreadPlanStepStatus::Pending,
+                        overridden: false,
+                    },
+                ],
+                updated_at: plan.updated_at,
+            }),
+            summary: Some("Plan drafted".to_string()),
+            feedback: Vec::new(),
+        }
+    );
+    let prompt = last_request_body(&server).await;
+    assert!(
+        prompt.contains("<plan_mode>"),
+        "missing plan prompt: {prompt}"
+    );
+    assert!(
+        prompt.contains("keep the plan short"),
+        "missing detail: {prompt}"
+    );
+
+    let feedback_req = mcp
+        .send_thread_plan_send_feedback_request(ThreadPlanSendFeedbackParams {
+            thread_id: thread.id.clone(),
+            feedback: "Add tests too".to_string(),
+        })
+        .await?;
+    let feedback_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(feedback_req)),
+    )
+    .await??;
+    let ThreadPlanSendFeedbackResponse { turn } = to_response(feedback_resp)?;
+    let started = read_turn_started(&mut mcp).await?;
+    assert_eq!(started.turn, turn);
+
+    let ready = read_plan_ready(&mut mcp).await?;
+    assert_eq!(ready.summary, Some("Plan revised".to_string()));
+    assert_eq!(ready.feedback, vec!["Add tests too".to_string()]);
+    let prompt = last_request_body(&server).await;
+    assert!(
+        prompt.contains("Add tests too"),
+        "missing feedback: {prompt}"
+    );
+
+    let approve_req = mcp
+        .send_thread_plan_approve_request(ThreadPlanApproveParams {
+            thread_id: thread.id.clone(),
+        })
+        .await?;
+    let approve_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(approve_req)),
+    )
+    .await??;
+    let ThreadPlanApproveResponse { turn } = to_response(approve_resp)?;
+    let started = read_turn_started(&mut mcp).await?;
+    assert_eq!(started.turn, turn);
+
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("turn/completed"),
+    )
+    .await??;
+    let prompt = last_request_body(&server).await;
+    assert!(
+        prompt.contains("Plan approved."),
+        "missing approval: {prompt}"
+    );
+    assert!(
+        prompt.contains("1. [ ] Add counters"),
+        "missing steps: {prompt}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn plan_mode_rejects_unknown_threads() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let approve_req = mcp
+        .send_thread_plan_approve_request(ThreadPlanApproveParams {
+            thread_id: "not-a-thread".to_string(),
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(approve_req)),
+    )
+    .await??;
+    assert!(
+        error.error.message.starts_with("invalid thread id"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    Ok(())
+}
+
+async fn read_plan_ready(mcp: &mut McpProcess) -> Result<ThreadPlanReadyNotification> {
+    let notification: JSONRPCNotification = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("thread/plan/ready"),
+    )
+    .await??;
+    Ok(serde_json::from_value(
+        notification.params.expect("params must be present"),
+    )?)
+}
+
+async fn read_turn_started(mcp: &mut McpProcess) -> Result<TurnStartedNotification> {
+    let notification: JSONRPCNotification = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("turn/started"),
+    )
+    .await??;
+    Ok(serde_json::from_value(
+        notification.params.expect("params must be present"),
+    )?)
+}
+
+/// Body of the most recent chat completions request, as text.
+async fn last_request_body(server: &MockServer) -> String {
+    let requests = server.received_requests().await.unwrap_or_default();
+    let request = requests
+        .last()
+        .expect("mock server should have received a request");
+    String::from_utf8_lossy(&request.body).into_owned()
+}
+
+fn create_update_plan_sse_response(call_id: &str) -> Result<String> {
+    let arguments = serde_json::to_string(&json!({
+        "plan": [
+            { "step": "Add counters", "status": "pending" },
+            { "step": "Wire exporter", "status": "pending" },
+        ]
+    }))?;
+    let tool_call = json!({
+        "choices": [
+            {
+                "delta": {
+                    "tool_calls": [
+                        {
+                            "id": call_id,
+                            "function": {
+                                "name": "update_plan",
+                                "arguments": arguments
+                            }
+                        }
+                    ]
+                },
+                "finish_reason": "tool_calls"
+            }
+        ]
+    });
+    Ok(format!(
+        "data: {}\n\ndata: DONE\n\n",
+        serde_json::to_string(&tool_call)?
+    ))
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
@@ -3,12 +3,15 @@ use app_test_support::ChatGptAuthFixture;
 use app_test_support::McpProcess;
 use app_test_support::to_response;
 use app_test_support::write_chatgpt_auth;
+use codex_app_server_protocol::AccountRateLimitsUpdatedNotification;
+use codex_app_server_protocol::AccountRateLimitsWarningNotification;
 use codex_app_server_protocol::GetAccountRateLimitsResponse;
 use codex_app_server_protocol::JSONRPCError;
 use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::LoginApiKeyParams;
 use codex_app_server_protocol::RateLimitSnapshot;
 use codex_app_server_protocol::RateLimitWindow;
+use codex_app_server_protocol::RateLimitWindowKind;
 use codex_app_server_protocol::RequestId;
 use codex_core::auth::AuthCredentialsStoreMode;
 use pretty_assertions::assert_eq;
@@ -160,6 +163,88 @@ async fn get_account_rate_limits_returns_snapshot() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn rate_limits_are_pushed_after_initialize() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    write_chatgpt_auth(
+        codex_home.path(),
+        ChatGptAuthFixture::new("chatgpt-token").account_id("account-123"),
+        AuthCredentialsStoreMode::File,
+    )?;
+
+    let server = MockServer::start().await;
+    write_chatgpt_base_url(codex_home.path(), &server.uri())?;
+    Mock::given(method("GET"))
+        .and(path("/api/codex/usage"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "plan_type": "pro",
+            "rate_limit": {
+                "allowed": true,
+                "limit_reached": false,
+                "primary_window": {
+                    "used_percent": 91,
+                    "limit_window_seconds": 18000,
+                    "reset_after_seconds": 120,
+                    "reset_at": 1735689720,
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let mut mcp = McpProcess::new_with_env(codex_home.path(), &[("OPENAI_API_KEY", None)]).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let expected_rate_limits = RateLimitSnapshot {
+        primary: Some(RateLimitWindow {
+            used_percent: 91,
+            window_duration_mins: Some(300),
+            resets_at: Some(1735689720),
+        }),
+        secondary: None,
+        credits: None,
+    };
+
+    let warning = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("account/rateLimits/warning"),
+    )
+    .await??;
+    let warning: AccountRateLimitsWarningNotification = serde_json::from_value(
+        warning
+            .params
+            .expect("account/rateLimits/warning params must be present"),
+    )?;
+    assert_eq!(
+        warning,
+        AccountRateLimitsWarningNotification {
+            window: RateLimitWindowKind::Primary,
+            threshold_percent: 90,
+            message: "Heads up, you have less than 10% of your 5h limit left.".to_string(),
+            rate_limits: expected_rate_limits.clone(),
+        }
+    );
+
+    let updated = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("account/rateLimits/updated"),
+    )
+    .await??;
+    let updated: AccountRateLimitsUpdatedNotification = serde_json::from_value(
+        updated
+            .params
+            .expect("account/rateLimits/updated params must be present"),
+    )?;
+    assert_eq!(
+        updated,
+        AccountRateLimitsUpdatedNotification {
+            rate_limits: expected_rate_limits,
+        }
+    );
+
+    Ok(())
+}
+
 async fn login_with_api_key(mcp: &mut McpProcess, api_key: &str) -> Result<()> {
     let request_id = mcp
         .send_login_api_key_request(LoginApiKeyParams {
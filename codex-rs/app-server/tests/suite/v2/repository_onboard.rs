@@ -0,0 +1,173 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCNotification;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RepositoryOnboardParams;
+use codex_app_server_protocol::RepositoryOnboardResponse;
+use codex_app_server_protocol::RepositoryOnboardingProgressNotification;
+use codex_app_server_protocol::RepositoryOnboardingStep;
+use codex_app_server_protocol::RepositoryOnboardingStepStatus;
+use codex_app_server_protocol::RequestId;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Every step reports `started` and then its outcome.
+const PROGRESS_NOTIFICATIONS: usize = 10;
+
+#[tokio::test]
+async fn repository_onboard_reports_progress_then_responds() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let repo = TempDir::new()?;
+    let mut mcp = spawn_without_sgrep(codex_home.path()).await?;
+
+    let request_id = mcp
+        .send_repository_onboard_request(RepositoryOnboardParams {
+            cwd: repo.path().to_path_buf(),
+            trust_repository: false,
+            generate_agents_md: false,
+        })
+        .await?;
+
+    let mut progress = Vec::new();
+    for _ in 0..PROGRESS_NOTIFICATIONS {
+        progress.push(read_progress(&mut mcp).await?);
+    }
+    assert!(
+        progress
+            .iter()
+            .all(|update| update.cwd.as_path() == repo.path())
+    );
+    assert_eq!(
+        progress
+            .iter()
+            .step_by(2)
+            .map(|update| (update.step, update.status.clone()))
+            .collect::<Vec<_>>(),
+        [
+            RepositoryOnboardingStep::DetectGit,
+            RepositoryOnboardingStep::RegisterRepository,
+            RepositoryOnboardingStep::AgentsMd,
+            RepositoryOnboardingStep::SemanticIndex,
+            RepositoryOnboardingStep::Memory,
+        ]
+        .map(|step| (step, RepositoryOnboardingStepStatus::Started))
+        .to_vec()
+    );
+    let outcomes: Vec<RepositoryOnboardingStepStatus> = progress
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .map(|update| update.status.clone())
+        .collect();
+    assert_eq!(
+        outcomes[..4],
+        [
+            RepositoryOnboardingStepStatus::Skipped {
+                reason: "not a git repository".to_string(),
+            },
+            RepositoryOnboardingStepStatus::NeedsConfirmation {
+                message: format!("Trust {} to let Codex work here?", repo.path().display()),
+            },
+            RepositoryOnboardingStepStatus::NeedsConfirmation {
+                message: "Generate AGENTS.md for this repository?".to_string(),
+            },
+            RepositoryOnboardingStepStatus::Skipped {
+                reason: "sgrep is not installed".to_string(),
+            },
+        ]
+    );
+    assert!(
+        matches!(
+            outcomes[4],
+            RepositoryOnboardingStepStatus::Completed { .. }
+        ),
+        "unexpected memory outcome: {:?}",
+        outcomes[4]
+    );
+
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let response: RepositoryOnboardResponse = to_response(response)?;
+    assert_eq!(
+        response,
+        RepositoryOnboardResponse {
+            repo_root: repo.path().to_path_buf(),
+            agents_md_prompt: None,
+            failed_steps: Vec::new(),
+        }
+    );
+    assert!(!codex_home.path().join("config.toml").exists());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn repository_onboard_performs_confirmed_steps() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    let repo = TempDir::new()?;
+    let mut mcp = spawn_without_sgrep(codex_home.path()).await?;
+
+    let request_id = mcp
+        .send_repository_onboard_request(RepositoryOnboardParams {
+            cwd: repo.path().to_path_buf(),
+            trust_repository: true,
+            generate_agents_md: true,
+        })
+        .await?;
+    for _ in 0..PROGRESS_NOTIFICATIONS {
+        read_progress(&mut mcp).await?;
+    }
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let response: RepositoryOnboardResponse = to_response(response)?;
+
+    assert!(
+        response
+            .agents_md_prompt
+            .is_some_and(|prompt| prompt.contains("AGENTS.md")),
+        "missing AGENTS.md prompt"
+    );
+    let config = std::fs::read_to_string(codex_home.path().join("config.toml"))?;
+    assert!(
+        config.contains(&repo.path().display().to_string()),
+        "project not trusted: {config}"
+    );
+
+    Ok(())
+}
+
+/// App server that cannot find `sgrep`, so indexing is skipped.
+async fn spawn_without_sgrep(codex_home: &std::path::Path) -> Result<McpProcess> {
+    let home = codex_home.to_string_lossy().into_owned();
+    let empty_path = codex_home.join("bin");
+    std::fs::create_dir_all(&empty_path)?;
+    let empty_path = empty_path.to_string_lossy().into_owned();
+    let mut mcp = McpProcess::new_with_env(
+        codex_home,
+        &[("HOME", Some(&home)), ("PATH", Some(&empty_path))],
+    )
+    .await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    Ok(mcp)
+}
+
+async fn read_progress(mcp: &mut McpProcess) -> Result<RepositoryOnboardingProgressNotification> {
+    let notification: JSONRPCNotification = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("repository/onboardingProgress"),
+    )
+    .await??;
+    Ok(serde_json::from_value(
+        notification.params.expect("params must be present"),
+    )?)
+}
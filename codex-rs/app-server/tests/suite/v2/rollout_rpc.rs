@@ -0,0 +1,216 @@
+use std::path::Path;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_fake_rollout;
+use app_test_support::create_mock_chat_completions_server;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::RolloutArchiveParams;
+use codex_app_server_protocol::RolloutArchiveResponse;
+use codex_app_server_protocol::RolloutDeleteParams;
+use codex_app_server_protocol::RolloutDeleteResponse;
+use codex_app_server_protocol::RolloutListParams;
+use codex_app_server_protocol::RolloutListResponse;
+use codex_app_server_protocol::ThreadResumeParams;
+use codex_app_server_protocol::ThreadResumeResponse;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn rollouts_can_be_listed_archived_resumed_and_deleted() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let archived_id = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-05T12-00-00",
+        "2025-01-05T12:00:00Z",
+        "Archive me",
+        Some("mock_provider"),
+        None,
+    )?;
+    let deleted_id = create_fake_rollout(
+        codex_home.path(),
+        "2025-01-06T12-00-00",
+        "2025-01-06T12:00:00Z",
+        "Delete me",
+        Some("mock_provider"),
+        None,
+    )?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let listed = list(&mut mcp, None).await?;
+    assert_eq!(listed.data.len(), 2);
+    assert_eq!(
+        (listed.usage.active_files, listed.usage.archived_files),
+        (2, 0)
+    );
+    let rollout_for = |id: &str| {
+        listed
+            .data
+            .iter()
+            .find(|file| file.path.to_string_lossy().contains(id))
+            .map(|file| file.path.clone())
+            .expect("rollout should be listed")
+    };
+    let archived_rollout = rollout_for(&archived_id);
+    let deleted_rollout = rollout_for(&deleted_id);
+
+    let request_id = mcp
+        .send_rollout_archive_request(RolloutArchiveParams {
+            path: archived_rollout.clone(),
+        })
+        .await?;
+    let archived: RolloutArchiveResponse = to_response(
+        timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+        )
+        .await??,
+    )?;
+    assert!(!archived_rollout.exists());
+    assert!(
+        archived
+            .archive_path
+            .to_string_lossy()
+            .ends_with(".jsonl.gz")
+    );
+    assert_eq!(
+        (archived.usage.active_files, archived.usage.archived_files),
+        (1, 1)
+    );
+
+    let listed = list(&mut mcp, Some(true)).await?;
+    let archived_paths: Vec<_> = listed.data.iter().map(|file| file.path.clone()).collect();
+    assert_eq!(archived_paths, vec![archived.archive_path.clone()]);
+
+    // Resuming an archived thread moves its rollout back into `sessions/`.
+    let request_id = mcp
+        .send_thread_resume_request(ThreadResumeParams {
+            thread_id: archived_id.clone(),
+            ..Default::default()
+        })
+        .await?;
+    let resumed: ThreadResumeResponse = to_response(
+        timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+        )
+        .await??,
+    )?;
+    assert_eq!(resumed.thread.id, archived_id);
+    assert!(archived_rollout.exists());
+    assert!(!archived.archive_path.exists());
+
+    // The resumed thread is writing its rollout, so it cannot be removed.
+    let request_id = mcp
+        .send_rollout_delete_request(RolloutDeleteParams {
+            path: archived_rollout.clone(),
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert!(
+        error.error.message.contains("in use by a running session"),
+        "unexpected error: {}",
+        error.error.message
+    );
+
+    let request_id = mcp
+        .send_rollout_delete_request(RolloutDeleteParams {
+            path: deleted_rollout.clone(),
+        })
+        .await?;
+    let deleted: RolloutDeleteResponse = to_response(
+        timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+        )
+        .await??,
+    )?;
+    assert!(!deleted_rollout.exists());
+    assert_eq!(
+        (deleted.usage.active_files, deleted.usage.archived_files),
+        (1, 0)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn rollout_delete_rejects_files_outside_sessions() -> Result<()> {
+    let server = create_mock_chat_completions_server(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let outside = codex_home
+        .path()
+        .join("rollout-2025-01-05T12-00-00-00000000-0000-0000-0000-000000000001.jsonl");
+    std::fs::write(&outside, "{}\n")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let request_id = mcp
+        .send_rollout_delete_request(RolloutDeleteParams {
+            path: outside.clone(),
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert!(
+        error.error.message.starts_with("failed to delete rollout"),
+        "unexpected error: {}",
+        error.error.message
+    );
+    assert!(outside.exists());
+
+    Ok(())
+}
+
+async fn list(mcp: &mut McpProcess, archived: Option<bool>) -> Result<RolloutListResponse> {
+    let request_id = mcp
+        .send_rollout_list_request(RolloutListParams { archived })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    to_response(response)
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
@@ -0,0 +1,181 @@
+use std::path::Path;
+
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::create_final_assistant_message_sse_response;
+use app_test_support::create_mock_chat_completions_server_unchecked;
+use app_test_support::to_response;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ThreadSetWebhookParams;
+use codex_app_server_protocol::ThreadSetWebhookResponse;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use codex_app_server_protocol::WebhookEvent;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use tempfile::TempDir;
+use tokio::time::timeout;
+use wiremock::Mock;
+use wiremock::MockServer;
+use wiremock::ResponseTemplate;
+use wiremock::matchers::method;
+use wiremock::matchers::path;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn webhook_receives_redacted_signed_turn_complete() -> Result<()> {
+    let responses = vec![create_final_assistant_message_sse_response(
+        "Deployed with sk-abcdefghijklmnopqrstuvwx",
+    )?];
+    let server = create_mock_chat_completions_server_unchecked(responses).await;
+    let hook = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/hook"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&hook)
+        .await;
+
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let (mut mcp, thread_id) = start_thread(codex_home.path()).await?;
+
+    let request_id = mcp
+        .send_thread_set_webhook_request(ThreadSetWebhookParams {
+            thread_id: thread_id.clone(),
+            url: format!("{}/hook", hook.uri()),
+            events: Some(vec![WebhookEvent::TurnComplete]),
+            secret: Some("s3cret".to_string()),
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let _: ThreadSetWebhookResponse = to_response(response)?;
+
+    mcp.send_turn_start_request(TurnStartParams {
+        thread_id,
+        input: vec![V2UserInput::Text {
+            text: "Deploy".to_string(),
+        }],
+        ..Default::default()
+    })
+    .await?;
+    timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_notification_message("turn/completed"),
+    )
+    .await??;
+
+    // Delivery happens in the background after the turn completes.
+    let request = timeout(DEFAULT_READ_TIMEOUT, async {
+        loop {
+            if let Some(request) = hook
+                .received_requests()
+                .await
+                .and_then(|r| r.first().cloned())
+            {
+                return request;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+    })
+    .await?;
+
+    assert_eq!(
+        request
+            .headers
+            .get("X-Codex-Event")
+            .and_then(|value| value.to_str().ok()),
+        Some("turn-complete")
+    );
+    let signature = request
+        .headers
+        .get("X-Codex-Signature-256")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    assert!(signature.starts_with("sha256="), "signature: {signature}");
+    let payload: Value = serde_json::from_slice(&request.body)?;
+    let text = payload["text"].as_str().unwrap_or_default();
+    assert!(
+        text.ends_with("Turn complete: Deployed with [REDACTED]"),
+        "text: {text}"
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn set_webhook_rejects_non_http_urls() -> Result<()> {
+    let server = create_mock_chat_completions_server_unchecked(Vec::new()).await;
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path(), &server.uri())?;
+    let (mut mcp, thread_id) = start_thread(codex_home.path()).await?;
+
+    let request_id = mcp
+        .send_thread_set_webhook_request(ThreadSetWebhookParams {
+            thread_id,
+            url: "file:///tmp/hook".to_string(),
+            events: None,
+            secret: None,
+        })
+        .await?;
+    let error = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(
+        error.error.message,
+        "webhook url must be an http or https URL: file:///tmp/hook"
+    );
+
+    Ok(())
+}
+
+async fn start_thread(codex_home: &Path) -> Result<(McpProcess, String)> {
+    let mut mcp = McpProcess::new(codex_home).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+
+    let thread_req = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            ..Default::default()
+        })
+        .await?;
+    let thread_resp: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(thread_req)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(thread_resp)?;
+    Ok((mcp, thread.id))
+}
+
+fn create_config_toml(codex_home: &Path, server_uri: &str) -> std::io::Result<()> {
+    let config_toml = codex_home.join("config.toml");
+    std::fs::write(
+        config_toml,
+        format!(
+            r#"
+model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+
+model_provider = "mock_provider"
+
+[model_providers.mock_provider]
+name = "Mock provider for test"
+base_url = "{server_uri}/v1"
+wire_api = "chat"
+request_max_retries = 0
+stream_max_retries = 0
+"#
+        ),
+    )
+}
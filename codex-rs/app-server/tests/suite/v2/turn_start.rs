@@ -536,6 +536,9 @@ async fn turn_start_updates_sandbox_and_cwd_between_turns_v2() -> Result<()> {
                 network_access: false,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                allow_write: vec![],
+                deny_write: vec![],
+                network_allowlist: vec![],
             }),
             model: Some("mock-model".to_string()),
             effort: Some(ReasoningEffort::Medium),
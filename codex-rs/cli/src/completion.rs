@@ -0,0 +1,219 @@
+//! `codex-kaioken completion <shell>`.
+//!
+//! The printed script registers `codex-kaioken` itself as the completer: on
+//! every <Tab> the shell runs the binary with `COMPLETE=<shell>` set, and
+//! clap_complete answers from the full command tree, so every subcommand and
+//! flag is covered. `--profile`, `--model`, and the `resume` session id
+//! complete to values looked up at that moment: profiles from `config.toml`,
+//! the built-in model presets, and the most recent sessions.
+
+use std::ffi::OsStr;
+use std::future::Future;
+use std::path::Path;
+
+use clap::Command;
+use clap_complete::CompleteEnv;
+use clap_complete::Shell;
+use clap_complete::engine::ArgValueCompleter;
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::Shells;
+use codex_common::model_presets::builtin_model_presets;
+use codex_core::INTERACTIVE_SESSION_SOURCES;
+use codex_core::RolloutRecorder;
+use codex_core::config::find_codex_home;
+use codex_core::config::load_config_as_toml_with_cli_overrides;
+
+const BIN_NAME: &str = "codex-kaioken";
+
+/// Environment variable the registered script sets when asking for candidates.
+const COMPLETE_ENV_VAR: &str = "COMPLETE";
+
+/// How many recent sessions `resume` completes to.
+const RECENT_SESSION_LIMIT: usize = 50;
+
+/// Answers the shell and exits when it is asking for completions; returns
+/// otherwise. Call before anything else parses the arguments.
+pub(crate) fn complete_from_env(command: fn() -> Command) {
+    CompleteEnv::with_factory(move || with_completers(command()))
+        .var(COMPLETE_ENV_VAR)
+        .bin(BIN_NAME)
+        .complete();
+}
+
+/// Writes the script that registers dynamic completion for `shell` to stdout.
+pub(crate) fn print_completion(shell: Shell) -> anyhow::Result<()> {
+    let name = shell.to_string();
+    let Some(completer) = Shells::builtins().completer(&name) else {
+        anyhow::bail!("dynamic completion is not supported for {name}");
+    };
+    completer.write_registration(
+        COMPLETE_ENV_VAR,
+        BIN_NAME,
+        BIN_NAME,
+        BIN_NAME,
+        &mut std::io::stdout(),
+    )?;
+    Ok(())
+}
+
+/// Attaches value completers to the matching arguments of `cmd` and its
+/// subcommands.
+pub(crate) fn with_completers(mut cmd: Command) -> Command {
+    cmd = add_completer(cmd, "config_profile", complete_profiles);
+    cmd = add_completer(cmd, "model", complete_models);
+    if cmd.get_name() == "resume" {
+        cmd = add_completer(cmd, "session_id", complete_sessions);
+    }
+
+    let subcommands: Vec<String> = cmd
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        cmd = cmd.mut_subcommand(name, with_completers);
+    }
+    cmd
+}
+
+fn add_completer(
+    cmd: Command,
+    arg_id: &str,
+    complete: fn(&OsStr) -> Vec<CompletionCandidate>,
+) -> Command {
+    if !cmd.get_arguments().any(|arg| arg.get_id() == arg_id) {
+        return cmd;
+    }
+    cmd.mut_arg(arg_id, |arg| arg.add(ArgValueCompleter::new(complete)))
+}
+
+fn complete_profiles(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Ok(codex_home) = find_codex_home() else {
+        return Vec::new();
+    };
+    let mut profiles: Vec<String> = block_on(load_config_as_toml_with_cli_overrides(
+        &codex_home,
+        Vec::new(),
+    ))
+    .and_then(Result::ok)
+    .map(|config| config.profiles.into_keys().collect())
+    .unwrap_or_default();
+    profiles.sort();
+    candidates(current, profiles.into_iter().map(|profile| (profile, None)))
+}
+
+fn complete_models(current: &OsStr) -> Vec<CompletionCandidate> {
+    let models = builtin_model_presets(None)
+        .into_iter()
+        .map(|preset| (preset.model.to_string(), None));
+    candidates(current, models)
+}
+
+fn complete_sessions(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Ok(codex_home) = find_codex_home() else {
+        return Vec::new();
+    };
+    let sessions = block_on(recent_sessions(&codex_home)).unwrap_or_default();
+    candidates(current, sessions)
+}
+
+/// Session ids with when they were last updated, most recent first.
+async fn recent_sessions(codex_home: &Path) -> Vec<(String, Option<String>)> {
+    let Ok(page) = RolloutRecorder::list_conversations(
+        codex_home,
+        RECENT_SESSION_LIMIT,
+        None,
+        INTERACTIVE_SESSION_SOURCES,
+        None,
+        "",
+    )
+    .await
+    else {
+        return Vec::new();
+    };
+    page.items
+        .into_iter()
+        .filter_map(|item| {
+            let id = item.head.first()?.get("id")?.as_str()?.to_string();
+            Some((id, item.updated_at.or(item.created_at)))
+        })
+        .collect()
+}
+
+/// The `values` starting with `current`, each with optional help text.
+fn candidates(
+    current: &OsStr,
+    values: impl IntoIterator<Item = (String, Option<String>)>,
+) -> Vec<CompletionCandidate> {
+    let current = current.to_string_lossy();
+    values
+        .into_iter()
+        .filter(|(value, _)| value.starts_with(current.as_ref()))
+        .map(|(value, help)| CompletionCandidate::new(value).help(help.map(Into::into)))
+        .collect()
+}
+
+/// Completion runs before the async runtime starts, so lookups get their own.
+fn block_on<F: Future>(future: F) -> Option<F::Output> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .ok()?;
+    Some(runtime.block_on(future))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Arg;
+    use pretty_assertions::assert_eq;
+
+    fn has_completer(cmd: &Command, arg_id: &str) -> bool {
+        cmd.get_arguments()
+            .find(|arg| arg.get_id() == arg_id)
+            .expect("argument exists")
+            .get::<ArgValueCompleter>()
+            .is_some()
+    }
+
+    #[test]
+    fn attaches_completers_to_nested_arguments() {
+        let cmd = Command::new(BIN_NAME)
+            .arg(Arg::new("config_profile").long("profile"))
+            .subcommand(
+                Command::new("resume")
+                    .arg(Arg::new("session_id"))
+                    .arg(Arg::new("model").long("model")),
+            )
+            .subcommand(Command::new("exec").arg(Arg::new("session_id")));
+
+        let mut cmd = with_completers(cmd);
+        assert!(has_completer(&cmd, "config_profile"));
+        let resume = cmd.find_subcommand_mut("resume").expect("resume");
+        assert!(has_completer(resume, "session_id"));
+        assert!(has_completer(resume, "model"));
+        let exec = cmd.find_subcommand_mut("exec").expect("exec");
+        assert!(!has_completer(exec, "session_id"));
+    }
+
+    #[test]
+    fn candidates_match_the_typed_prefix() {
+        let values = vec![
+            ("019a-old".to_string(), Some("2025-01-01".to_string())),
+            ("019b-new".to_string(), None),
+        ];
+
+        let matches: Vec<(String, Option<String>)> = candidates(OsStr::new("019a"), values)
+            .into_iter()
+            .map(|candidate| {
+                (
+                    candidate.get_value().to_string_lossy().into_owned(),
+                    candidate.get_help().map(ToString::to_string),
+                )
+            })
+            .collect();
+        assert_eq!(
+            matches,
+            vec![("019a-old".to_string(), Some("2025-01-01".to_string()))]
+        );
+    }
+}
@@ -2,7 +2,7 @@ use clap::Args;
 use clap::CommandFactory;
 use clap::Parser;
 use clap_complete::Shell;
-use clap_complete::generate;
+use codex_app_server::AppServerTransport;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_chatgpt::apply_command::ApplyCommand;
 use codex_chatgpt::apply_command::run_apply_command;
@@ -25,13 +25,17 @@ use codex_tui::Cli as TuiCli;
 use codex_tui::update_action::UpdateAction;
 use owo_colors::OwoColorize;
 use std::env;
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod completion;
 mod mcp_cmd;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::completion::complete_from_env;
+use crate::completion::print_completion;
 use crate::mcp_cmd::McpCli;
 
 use codex_core::config::Config;
@@ -89,12 +93,15 @@ enum Subcommand {
     AppServer(AppServerCommand),
 
     /// Generate shell completion scripts.
+    #[clap(visible_alias = "completions")]
     Completion(CompletionCommand),
 
     /// Run commands within a Codex-provided sandbox.
-    #[clap(visible_alias = "debug")]
     Sandbox(SandboxArgs),
 
+    /// Debugging tools: sandboxed commands and replay of captured model streams.
+    Debug(DebugArgs),
+
     /// Execpolicy tooling.
     #[clap(hide = true)]
     Execpolicy(ExecpolicyCommand),
@@ -106,6 +113,9 @@ enum Subcommand {
     /// Resume a previous interactive session (picker by default; use --last to continue the most recent).
     Resume(ResumeCommand),
 
+    /// Play back a recorded session rollout in the TUI without calling the model.
+    Replay(ReplayRolloutCommand),
+
     /// [EXPERIMENTAL] Browse tasks from Codex Cloud and apply changes locally.
     #[clap(name = "cloud", alias = "cloud-tasks")]
     Cloud(CloudTasksCli),
@@ -148,6 +158,20 @@ struct ResumeCommand {
     config_overrides: TuiCli,
 }
 
+#[derive(Debug, Parser)]
+struct ReplayRolloutCommand {
+    /// Rollout file to play back, e.g. one under `~/.codex/sessions/`.
+    #[arg(value_name = "ROLLOUT", value_hint = clap::ValueHint::FilePath)]
+    rollout: PathBuf,
+
+    /// Playback speed relative to the recording; 0 plays without pauses.
+    #[arg(long = "speed", value_name = "FACTOR", default_value_t = 1.0)]
+    speed: f64,
+
+    #[clap(flatten)]
+    config_overrides: TuiCli,
+}
+
 #[derive(Debug, Parser)]
 struct SandboxArgs {
     #[command(subcommand)]
@@ -168,6 +192,32 @@ enum SandboxCommand {
     Windows(WindowsCommand),
 }
 
+#[derive(Debug, Parser)]
+struct DebugArgs {
+    #[command(subcommand)]
+    cmd: DebugCommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum DebugCommand {
+    /// Re-run response handling against a stream recorded by the `debug_capture` feature.
+    Replay(ReplayCommand),
+
+    #[command(flatten)]
+    Sandbox(SandboxCommand),
+}
+
+#[derive(Debug, Parser)]
+struct ReplayCommand {
+    /// Capture file, usually under `~/.codex/captures/`.
+    #[arg(value_name = "CAPTURE")]
+    capture: PathBuf,
+
+    /// Replay only the request with this id.
+    #[arg(long = "request", value_name = "REQUEST_ID")]
+    request_id: Option<String>,
+}
+
 #[derive(Debug, Parser)]
 struct ExecpolicyCommand {
     #[command(subcommand)]
@@ -233,6 +283,12 @@ struct AppServerCommand {
     /// Omit to run the app server; specify a subcommand for tooling.
     #[command(subcommand)]
     subcommand: Option<AppServerSubcommand>,
+
+    /// Serve JSON-RPC over HTTP (`POST /rpc`, server-sent `GET /events`) on
+    /// this loopback address instead of stdio. Clients must present the
+    /// token from `CODEX_APP_SERVER_TOKEN`.
+    #[arg(long = "listen", value_name = "ADDR")]
+    listen: Option<SocketAddr>,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -467,6 +523,7 @@ fn pre_main_hardening() {
 }
 
 pub fn real_main() -> anyhow::Result<()> {
+    complete_from_env(MultitoolCli::command);
     arg0_dispatch_or_else(|codex_linux_sandbox_exe| async move {
         cli_main(codex_linux_sandbox_exe).await?;
         Ok(())
@@ -517,7 +574,16 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
         }
         Some(Subcommand::AppServer(app_server_cli)) => match app_server_cli.subcommand {
             None => {
-                codex_app_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
+                let transport = match app_server_cli.listen {
+                    Some(listen) => AppServerTransport::Http { listen },
+                    None => AppServerTransport::Stdio,
+                };
+                codex_app_server::run_main(
+                    codex_linux_sandbox_exe,
+                    root_config_overrides,
+                    transport,
+                )
+                .await?;
             }
             Some(AppServerSubcommand::GenerateTs(gen_cli)) => {
                 codex_app_server_protocol::generate_ts(
@@ -546,6 +612,21 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             let exit_info = codex_tui::run_main(interactive, codex_linux_sandbox_exe).await?;
             handle_app_exit(exit_info)?;
         }
+        Some(Subcommand::Replay(ReplayRolloutCommand {
+            rollout,
+            speed,
+            config_overrides,
+        })) => {
+            interactive.replay_rollout = Some(rollout);
+            interactive.replay_speed = speed;
+            merge_resume_cli_flags(&mut interactive, config_overrides);
+            prepend_config_flags(
+                &mut interactive.config_overrides,
+                root_config_overrides.clone(),
+            );
+            let exit_info = codex_tui::run_main(interactive, codex_linux_sandbox_exe).await?;
+            handle_app_exit(exit_info)?;
+        }
         Some(Subcommand::Login(mut login_cli)) => {
             prepend_config_flags(
                 &mut login_cli.config_overrides,
@@ -585,7 +666,7 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             run_logout(logout_cli.config_overrides).await;
         }
         Some(Subcommand::Completion(completion_cli)) => {
-            print_completion(completion_cli);
+            print_completion(completion_cli.shell)?;
         }
         Some(Subcommand::Cloud(mut cloud_cli)) => {
             prepend_config_flags(
@@ -594,36 +675,20 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             codex_cloud_tasks::run_main(cloud_cli, codex_linux_sandbox_exe).await?;
         }
-        Some(Subcommand::Sandbox(sandbox_args)) => match sandbox_args.cmd {
-            SandboxCommand::Macos(mut seatbelt_cli) => {
-                prepend_config_flags(
-                    &mut seatbelt_cli.config_overrides,
-                    root_config_overrides.clone(),
-                );
-                codex_cli::debug_sandbox::run_command_under_seatbelt(
-                    seatbelt_cli,
-                    codex_linux_sandbox_exe,
-                )
-                .await?;
-            }
-            SandboxCommand::Linux(mut landlock_cli) => {
-                prepend_config_flags(
-                    &mut landlock_cli.config_overrides,
-                    root_config_overrides.clone(),
-                );
-                codex_cli::debug_sandbox::run_command_under_landlock(
-                    landlock_cli,
-                    codex_linux_sandbox_exe,
-                )
-                .await?;
-            }
-            SandboxCommand::Windows(mut windows_cli) => {
-                prepend_config_flags(
-                    &mut windows_cli.config_overrides,
+        Some(Subcommand::Sandbox(sandbox_args)) => {
+            run_sandbox_command(
+                sandbox_args.cmd,
+                root_config_overrides.clone(),
+                codex_linux_sandbox_exe,
+            )
+            .await?;
+        }
+        Some(Subcommand::Debug(DebugArgs { cmd })) => match cmd {
+            DebugCommand::Replay(replay_cli) => run_debug_replay(replay_cli).await?,
+            DebugCommand::Sandbox(sandbox_cmd) => {
+                run_sandbox_command(
+                    sandbox_cmd,
                     root_config_overrides.clone(),
-                );
-                codex_cli::debug_sandbox::run_command_under_windows(
-                    windows_cli,
                     codex_linux_sandbox_exe,
                 )
                 .await?;
@@ -683,6 +748,62 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
     Ok(())
 }
 
+async fn run_sandbox_command(
+    cmd: SandboxCommand,
+    root_config_overrides: CliConfigOverrides,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    match cmd {
+        SandboxCommand::Macos(mut seatbelt_cli) => {
+            prepend_config_flags(&mut seatbelt_cli.config_overrides, root_config_overrides);
+            codex_cli::debug_sandbox::run_command_under_seatbelt(
+                seatbelt_cli,
+                codex_linux_sandbox_exe,
+            )
+            .await
+        }
+        SandboxCommand::Linux(mut landlock_cli) => {
+            prepend_config_flags(&mut landlock_cli.config_overrides, root_config_overrides);
+            codex_cli::debug_sandbox::run_command_under_landlock(
+                landlock_cli,
+                codex_linux_sandbox_exe,
+            )
+            .await
+        }
+        SandboxCommand::Windows(mut windows_cli) => {
+            prepend_config_flags(&mut windows_cli.config_overrides, root_config_overrides);
+            codex_cli::debug_sandbox::run_command_under_windows(
+                windows_cli,
+                codex_linux_sandbox_exe,
+            )
+            .await
+        }
+    }
+}
+
+/// Prints the response events core derives from each captured request.
+async fn run_debug_replay(cmd: ReplayCommand) -> anyhow::Result<()> {
+    let ReplayCommand {
+        capture,
+        request_id,
+    } = cmd;
+    let replayed =
+        codex_core::debug_capture::replay_capture(&capture, request_id.as_deref()).await?;
+    for request in replayed {
+        println!("request {} ({})", request.request_id, request.model);
+        for event in &request.events {
+            match event {
+                Ok(event) => println!("  {event:?}"),
+                Err(err) => println!("  error: {err}"),
+            }
+        }
+        if let Some(err) = &request.captured_error {
+            println!("  captured stream error: {err}");
+        }
+    }
+    Ok(())
+}
+
 /// Prepend root-level overrides so they have lower precedence than
 /// CLI-specific ones specified after the subcommand (if any).
 fn prepend_config_flags(
@@ -767,12 +888,6 @@ fn merge_resume_cli_flags(interactive: &mut TuiCli, resume_cli: TuiCli) {
         .extend(resume_cli.config_overrides.raw_overrides);
 }
 
-fn print_completion(cmd: CompletionCommand) {
-    let mut app = MultitoolCli::command();
-    let name = "codex";
-    generate(cmd.shell, &mut app, name, &mut std::io::stdout());
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1002,4 +1117,60 @@ mod tests {
             .expect_err("feature should be rejected");
         assert_eq!(err.to_string(), "Unknown feature flag: does_not_exist");
     }
+
+    #[test]
+    fn debug_replay_parses_capture_and_request() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "debug",
+            "replay",
+            "capture.jsonl",
+            "--request",
+            "req-1",
+        ])
+        .expect("parse");
+        let Some(Subcommand::Debug(DebugArgs {
+            cmd: DebugCommand::Replay(replay),
+        })) = cli.subcommand
+        else {
+            panic!("expected debug replay");
+        };
+        assert_eq!(replay.capture, PathBuf::from("capture.jsonl"));
+        assert_eq!(replay.request_id.as_deref(), Some("req-1"));
+    }
+
+    #[test]
+    fn replay_parses_rollout_and_speed() {
+        let cli = MultitoolCli::try_parse_from([
+            "codex",
+            "replay",
+            "rollout.jsonl",
+            "--speed",
+            "4",
+            "-m",
+            "gpt-5.1-test",
+        ])
+        .expect("parse");
+        let Some(Subcommand::Replay(replay)) = cli.subcommand else {
+            panic!("expected replay");
+        };
+        assert_eq!(replay.rollout, PathBuf::from("rollout.jsonl"));
+        assert_eq!(replay.speed, 4.0);
+        assert_eq!(
+            replay.config_overrides.model.as_deref(),
+            Some("gpt-5.1-test")
+        );
+    }
+
+    #[test]
+    fn debug_keeps_legacy_sandbox_aliases() {
+        let cli =
+            MultitoolCli::try_parse_from(["codex", "debug", "landlock", "ls"]).expect("parse");
+        assert_matches!(
+            cli.subcommand,
+            Some(Subcommand::Debug(DebugArgs {
+                cmd: DebugCommand::Sandbox(SandboxCommand::Linux(_)),
+            }))
+        );
+    }
 }
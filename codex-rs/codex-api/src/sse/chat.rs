@@ -6,9 +6,11 @@ use codex_client::StreamResponse;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ReasoningItemContent;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::TokenUsage;
 use eventsource_stream::Eventsource;
 use futures::Stream;
 use futures::StreamExt;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::time::Duration;
 use tokio::sync::mpsc;
@@ -49,7 +51,9 @@ pub async fn process_chat_sse<S>(
     let mut tool_call_order: Vec<String> = Vec::new();
     let mut assistant_item: Option<ResponseItem> = None;
     let mut reasoning_item: Option<ResponseItem> = None;
-    let mut completed_sent = false;
+    // Reported in a final chunk, often after the `stop` chunk, by servers
+    // that account tokens for streamed completions.
+    let mut token_usage: Option<TokenUsage> = None;
 
     loop {
         let start = Instant::now();
@@ -63,28 +67,7 @@ pub async fn process_chat_sse<S>(
                 let _ = tx_event.send(Err(ApiError::Stream(e.to_string()))).await;
                 return;
             }
-            Ok(None) => {
-                if let Some(reasoning) = reasoning_item {
-                    let _ = tx_event
-                        .send(Ok(ResponseEvent::OutputItemDone(reasoning)))
-                        .await;
-                }
-
-                if let Some(assistant) = assistant_item {
-                    let _ = tx_event
-                        .send(Ok(ResponseEvent::OutputItemDone(assistant)))
-                        .await;
-                }
-                if !completed_sent {
-                    let _ = tx_event
-                        .send(Ok(ResponseEvent::Completed {
-                            response_id: String::new(),
-                            token_usage: None,
-                        }))
-                        .await;
-                }
-                return;
-            }
+            Ok(None) => break,
             Err(_) => {
                 let _ = tx_event
                     .send(Err(ApiError::Stream("idle timeout waiting for SSE".into())))
@@ -98,6 +81,9 @@ pub async fn process_chat_sse<S>(
         if sse.data.trim().is_empty() {
             continue;
         }
+        if sse.data.trim() == "[DONE]" {
+            break;
+        }
 
         let value: serde_json::Value = match serde_json::from_str(&sse.data) {
             Ok(val) => val,
@@ -110,6 +96,14 @@ pub async fn process_chat_sse<S>(
             }
         };
 
+        if let Some(usage) = value
+            .get("usage")
+            .filter(|usage| !usage.is_null())
+            .and_then(|usage| serde_json::from_value::<ChatCompletionUsage>(usage.clone()).ok())
+        {
+            token_usage = Some(usage.into());
+        }
+
         let Some(choices) = value.get("choices").and_then(|c| c.as_array()) else {
             continue;
         };
@@ -198,15 +192,6 @@ pub async fn process_chat_sse<S>(
                         .send(Ok(ResponseEvent::OutputItemDone(assistant)))
                         .await;
                 }
-                if !completed_sent {
-                    let _ = tx_event
-                        .send(Ok(ResponseEvent::Completed {
-                            response_id: String::new(),
-                            token_usage: None,
-                        }))
-                        .await;
-                    completed_sent = true;
-                }
                 continue;
             }
 
@@ -235,6 +220,62 @@ pub async fn process_chat_sse<S>(
             }
         }
     }
+
+    if let Some(reasoning) = reasoning_item {
+        let _ = tx_event
+            .send(Ok(ResponseEvent::OutputItemDone(reasoning)))
+            .await;
+    }
+    if let Some(assistant) = assistant_item {
+        let _ = tx_event
+            .send(Ok(ResponseEvent::OutputItemDone(assistant)))
+            .await;
+    }
+    let _ = tx_event
+        .send(Ok(ResponseEvent::Completed {
+            response_id: String::new(),
+            token_usage,
+        }))
+        .await;
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionUsage {
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    total_tokens: Option<i64>,
+    prompt_tokens_details: Option<ChatPromptTokensDetails>,
+    completion_tokens_details: Option<ChatCompletionTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatPromptTokensDetails {
+    cached_tokens: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionTokensDetails {
+    reasoning_tokens: Option<i64>,
+}
+
+impl From<ChatCompletionUsage> for TokenUsage {
+    fn from(val: ChatCompletionUsage) -> Self {
+        TokenUsage {
+            input_tokens: val.prompt_tokens,
+            cached_input_tokens: val
+                .prompt_tokens_details
+                .and_then(|d| d.cached_tokens)
+                .unwrap_or(0),
+            output_tokens: val.completion_tokens,
+            reasoning_output_tokens: val
+                .completion_tokens_details
+                .and_then(|d| d.reasoning_tokens)
+                .unwrap_or(0),
+            total_tokens: val
+                .total_tokens
+                .unwrap_or(val.prompt_tokens + val.completion_tokens),
+        }
+    }
 }
 
 async fn append_assistant_text(
@@ -501,4 +542,43 @@ mod tests {
         }));
         assert_matches!(events.last(), Some(ResponseEvent::Completed { .. }));
     }
+
+    #[tokio::test]
+    async fn reports_usage_sent_after_the_stop_chunk() {
+        let delta = json!({"choices": [{"delta": {"content": "hi"}}]});
+        let finish_stop = json!({"choices": [{"finish_reason": "stop"}]});
+        let usage = json!({
+            "choices": [],
+            "usage": {
+                "prompt_tokens": 120,
+                "completion_tokens": 8,
+                "prompt_tokens_details": {"cached_tokens": 100}
+            }
+        });
+
+        let mut body = build_body(&[delta, finish_stop, usage]);
+        body.push_str("event: message\ndata: [DONE]\n\n");
+        let events = collect_events(&body).await;
+
+        assert_matches!(
+            events.last(),
+            Some(ResponseEvent::Completed {
+                token_usage: Some(TokenUsage {
+                    input_tokens: 120,
+                    cached_input_tokens: 100,
+                    output_tokens: 8,
+                    reasoning_output_tokens: 0,
+                    total_tokens: 128,
+                }),
+                ..
+            })
+        );
+        assert_eq!(
+            events
+                .iter()
+                .filter(|ev| matches!(ev, ResponseEvent::Completed { .. }))
+                .count(),
+            1
+        );
+    }
 }
@@ -39,3 +39,5 @@ pub mod model_presets;
 pub mod approval_presets;
 // Shared OSS provider utilities used by TUI and exec
 pub mod oss;
+// Rate limit warning thresholds and window labels shared by the TUI and app server
+pub mod rate_limits;
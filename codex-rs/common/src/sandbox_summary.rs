@@ -9,6 +9,9 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             network_access,
             exclude_tmpdir_env_var,
             exclude_slash_tmp,
+            allow_write,
+            deny_write,
+            network_allowlist,
         } => {
             let mut summary = "workspace-write".to_string();
 
@@ -27,8 +30,16 @@ pub fn summarize_sandbox_policy(sandbox_policy: &SandboxPolicy) -> String {
             );
 
             summary.push_str(&format!(" [{}]", writable_entries.join(", ")));
+            if !allow_write.is_empty() {
+                summary.push_str(&format!(" (allow write: {})", allow_write.join(", ")));
+            }
+            if !deny_write.is_empty() {
+                summary.push_str(&format!(" (deny write: {})", deny_write.join(", ")));
+            }
             if *network_access {
                 summary.push_str(" (network access enabled)");
+            } else if !network_allowlist.is_empty() {
+                summary.push_str(&format!(" (network: {})", network_allowlist.join(", ")));
             }
             summary
         }
@@ -1,6 +1,7 @@
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
+use crate::patch_hunks;
 use crate::protocol::FileChange;
 use crate::protocol::ReviewDecision;
 use crate::safety::SafetyCheck;
@@ -31,6 +32,19 @@ pub(crate) enum InternalApplyPatchInvocation {
 pub(crate) struct ApplyPatchExec {
     pub(crate) action: ApplyPatchAction,
     pub(crate) user_explicitly_approved_this_action: bool,
+    /// Set when the user approved only part of the proposed patch; tells the
+    /// model what was left out.
+    pub(crate) partial_note: Option<String>,
+}
+
+impl ApplyPatchExec {
+    /// The tool output to return to the model for this patch.
+    pub(crate) fn annotate_output(&self, content: String) -> String {
+        match &self.partial_note {
+            Some(note) => format!("{content}\n\n{note}"),
+            None => content,
+        }
+    }
 }
 
 pub(crate) async fn apply_patch(
@@ -51,6 +65,7 @@ pub(crate) async fn apply_patch(
         } => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
             action,
             user_explicitly_approved_this_action: user_explicitly_approved,
+            partial_note: None,
         }),
         SafetyCheck::AskUser => {
             // Compute a readable summary of path changes to include in the
@@ -60,11 +75,12 @@ pub(crate) async fn apply_patch(
             // give the user the option to expand the set of writable roots so
             // that similar patches can be auto-approved in the future during
             // this session.
+            let proposed = convert_apply_patch_to_protocol(&action);
             let rx_approve = sess
                 .request_patch_approval(
                     turn_context,
                     call_id.to_owned(),
-                    convert_apply_patch_to_protocol(&action),
+                    proposed.clone(),
                     None,
                     None,
                 )
@@ -74,8 +90,12 @@ pub(crate) async fn apply_patch(
                     InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
                         action,
                         user_explicitly_approved_this_action: true,
+                        partial_note: None,
                     })
                 }
+                ReviewDecision::ApprovedPartially { changes } => {
+                    partial_apply_patch(&action, &proposed, &changes)
+                }
                 ReviewDecision::Denied | ReviewDecision::Abort => {
                     InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
                         "patch rejected by user".to_string(),
@@ -89,6 +109,40 @@ pub(crate) async fn apply_patch(
     }
 }
 
+/// Apply only the `accepted` part of `action`. Answers that add to or edit
+/// the proposal are treated as a rejection.
+fn partial_apply_patch(
+    action: &ApplyPatchAction,
+    proposed: &HashMap<PathBuf, FileChange>,
+    accepted: &HashMap<PathBuf, FileChange>,
+) -> InternalApplyPatchInvocation {
+    if accepted.is_empty() || !patch_hunks::is_subset(accepted, proposed) {
+        return InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+            "patch rejected by user".to_string(),
+        )));
+    }
+
+    let command = vec![
+        "apply_patch".to_string(),
+        patch_hunks::patch_from_changes(accepted),
+    ];
+    match codex_apply_patch::maybe_parse_apply_patch_verified(&command, &action.cwd) {
+        codex_apply_patch::MaybeApplyPatchVerified::Body(partial) => {
+            InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                action: partial,
+                user_explicitly_approved_this_action: true,
+                partial_note: Some(patch_hunks::rejection_note(proposed, accepted)),
+            })
+        }
+        other => {
+            tracing::warn!("failed to rebuild partially approved patch: {other:?}");
+            InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+                "the approved part of the patch could not be applied on its own".to_string(),
+            )))
+        }
+    }
+}
+
 pub(crate) fn convert_apply_patch_to_protocol(
     action: &ApplyPatchAction,
 ) -> HashMap<PathBuf, FileChange> {
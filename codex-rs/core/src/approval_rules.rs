@@ -0,0 +1,260 @@
+//! User-defined command rules from the `[approvals]` config table.
+//!
+//! `always_deny` rules reject a command outright and `auto_approve` rules let
+//! it run without an approval prompt. A rule wrapped in slashes (`/^git (status|diff)$/`)
+//! is a regex; anything else is a glob where `*` matches any text, so
+//! `cargo test*` approves every `cargo test` invocation.
+
+use std::fmt;
+
+use regex::Regex;
+use wildmatch::WildMatchPattern;
+
+use crate::bash::extract_bash_command;
+use crate::bash::parse_shell_lc_plain_commands;
+
+type CommandGlob = WildMatchPattern<'*', '?'>;
+
+/// Compiled `approvals.auto_approve` and `approvals.always_deny` rules.
+#[derive(Clone, Default)]
+pub struct ApprovalRules {
+    auto_approve: Vec<CommandRule>,
+    always_deny: Vec<CommandRule>,
+}
+
+/// The first rule that decided a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalRuleMatch {
+    /// The command runs without asking; holds the matching rule.
+    AutoApprove(String),
+    /// The command is rejected; holds the matching rule.
+    Deny(String),
+}
+
+/// A single glob or `/regex/` command rule.
+#[derive(Clone)]
+pub(crate) struct CommandRule {
+    source: String,
+    matcher: RuleMatcher,
+}
+
+#[derive(Clone)]
+enum RuleMatcher {
+    Glob(CommandGlob),
+    Regex(Regex),
+}
+
+impl ApprovalRules {
+    /// Compiles the rules, failing on the first invalid regex.
+    pub fn new(auto_approve: &[String], always_deny: &[String]) -> Result<Self, regex::Error> {
+        Ok(Self {
+            auto_approve: compile_rules(auto_approve)?,
+            always_deny: compile_rules(always_deny)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.auto_approve.is_empty() && self.always_deny.is_empty()
+    }
+
+    /// Checks `command` against the rules. Plain `bash -lc` scripts are split
+    /// into their commands: one denied command denies the whole script, and
+    /// every command must be approved for the script to be auto-approved.
+    /// Other scripts (redirections, substitutions, ...) are never
+    /// auto-approved; deny rules are matched against the whole script and
+    /// every piece of it between shell operators.
+    pub fn evaluate(&self, command: &[String]) -> Option<ApprovalRuleMatch> {
+        if self.is_empty() {
+            return None;
+        }
+        let commands: Vec<String> = match parse_shell_lc_plain_commands(command) {
+            Some(commands) => commands.iter().map(|words| words.join(" ")).collect(),
+            None => {
+                if let Some((_, script)) = extract_bash_command(command) {
+                    return self.deny_match(script_segments(script));
+                }
+                vec![command.join(" ")]
+            }
+        };
+
+        if let Some(denied) = self.deny_match(commands.iter().map(String::as_str)) {
+            return Some(denied);
+        }
+
+        let mut approved_by = None;
+        for command in &commands {
+            approved_by = Some(first_match(&self.auto_approve, command)?);
+        }
+        approved_by.map(|rule| ApprovalRuleMatch::AutoApprove(rule.to_string()))
+    }
+
+    fn deny_match<'a>(
+        &self,
+        mut commands: impl Iterator<Item = &'a str>,
+    ) -> Option<ApprovalRuleMatch> {
+        commands.find_map(|command| {
+            first_match(&self.always_deny, command)
+                .map(|rule| ApprovalRuleMatch::Deny(rule.to_string()))
+        })
+    }
+
+    fn sources(rules: &[CommandRule]) -> impl Iterator<Item = &str> {
+        rules.iter().map(|rule| rule.source.as_str())
+    }
+}
+
+impl fmt::Debug for ApprovalRules {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApprovalRules")
+            .field(
+                "auto_approve",
+                &Self::sources(&self.auto_approve).collect::<Vec<_>>(),
+            )
+            .field(
+                "always_deny",
+                &Self::sources(&self.always_deny).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl PartialEq for ApprovalRules {
+    fn eq(&self, other: &Self) -> bool {
+        Self::sources(&self.auto_approve).eq(Self::sources(&other.auto_approve))
+            && Self::sources(&self.always_deny).eq(Self::sources(&other.always_deny))
+    }
+}
+
+impl CommandRule {
+    pub(crate) fn new(source: &str) -> Result<Self, regex::Error> {
+        let trimmed = source.trim();
+        let matcher = match trimmed
+            .strip_prefix('/')
+            .and_then(|rest| rest.strip_suffix('/'))
+        {
+            Some(pattern) if !pattern.is_empty() => RuleMatcher::Regex(Regex::new(pattern)?),
+            _ => RuleMatcher::Glob(CommandGlob::new(trimmed)),
+        };
+        Ok(Self {
+            source: source.to_string(),
+            matcher,
+        })
+    }
+
+    pub(crate) fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether the rule matches `command`, given as its words joined by spaces.
+    pub(crate) fn matches(&self, command: &str) -> bool {
+        match &self.matcher {
+            RuleMatcher::Glob(glob) => glob.matches(command),
+            RuleMatcher::Regex(regex) => regex.is_match(command),
+        }
+    }
+}
+
+fn compile_rules(rules: &[String]) -> Result<Vec<CommandRule>, regex::Error> {
+    rules
+        .iter()
+        .map(String::as_str)
+        .map(CommandRule::new)
+        .collect()
+}
+
+/// The whole `script` followed by the pieces between its operators, pipes,
+/// subshells and substitutions, e.g. `rm -rf x 2>/dev/null` for
+/// `cd /tmp; rm -rf x 2>/dev/null`.
+fn script_segments(script: &str) -> impl Iterator<Item = &str> {
+    let pieces = script
+        .split([';', '&', '|', '\n', '(', ')', '`', '{', '}'])
+        .map(str::trim)
+        .filter(|piece| !piece.is_empty());
+    std::iter::once(script.trim()).chain(pieces)
+}
+
+fn first_match<'a>(rules: &'a [CommandRule], command: &str) -> Option<&'a str> {
+    rules
+        .iter()
+        .find(|rule| rule.matches(command))
+        .map(CommandRule::source)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn rules(auto_approve: &[&str], always_deny: &[&str]) -> ApprovalRules {
+        let to_owned = |rules: &[&str]| rules.iter().map(ToString::to_string).collect::<Vec<_>>();
+        ApprovalRules::new(&to_owned(auto_approve), &to_owned(always_deny)).expect("valid rules")
+    }
+
+    fn command(words: &[&str]) -> Vec<String> {
+        words.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn globs_and_regexes_match_commands() {
+        let rules = rules(&["cargo test*", "/^git (status|diff)$/"], &["rm -rf*"]);
+        assert_eq!(
+            rules.evaluate(&command(&["cargo", "test", "-p", "codex-core"])),
+            Some(ApprovalRuleMatch::AutoApprove("cargo test*".to_string()))
+        );
+        assert_eq!(
+            rules.evaluate(&command(&["git", "diff"])),
+            Some(ApprovalRuleMatch::AutoApprove(
+                "/^git (status|diff)$/".to_string()
+            ))
+        );
+        assert_eq!(
+            rules.evaluate(&command(&["rm", "-rf", "target"])),
+            Some(ApprovalRuleMatch::Deny("rm -rf*".to_string()))
+        );
+        assert_eq!(rules.evaluate(&command(&["git", "push"])), None);
+    }
+
+    #[test]
+    fn scripts_need_every_command_approved_and_any_denied() {
+        let rules = rules(&["cargo test*", "git status"], &["rm -rf*"]);
+        assert_eq!(
+            rules.evaluate(&command(&["bash", "-lc", "git status && cargo test"])),
+            Some(ApprovalRuleMatch::AutoApprove("cargo test*".to_string()))
+        );
+        assert_eq!(
+            rules.evaluate(&command(&["bash", "-lc", "cargo test && git push"])),
+            None
+        );
+        assert_eq!(
+            rules.evaluate(&command(&["bash", "-lc", "cargo test && rm -rf /"])),
+            Some(ApprovalRuleMatch::Deny("rm -rf*".to_string()))
+        );
+    }
+
+    #[test]
+    fn deny_rules_apply_to_scripts_that_are_not_plain_commands() {
+        let rules = rules(&["cargo test*", "bash*"], &["rm -rf*"]);
+        assert_eq!(
+            rules.evaluate(&command(&["bash", "-lc", "rm -rf x 2>/dev/null"])),
+            Some(ApprovalRuleMatch::Deny("rm -rf*".to_string()))
+        );
+        assert_eq!(
+            rules.evaluate(&command(&["bash", "-lc", "cd /tmp && (rm -rf x) > log"])),
+            Some(ApprovalRuleMatch::Deny("rm -rf*".to_string()))
+        );
+        assert_eq!(
+            rules.evaluate(&command(&["bash", "-lc", "echo $(rm -rf ~)"])),
+            Some(ApprovalRuleMatch::Deny("rm -rf*".to_string()))
+        );
+        // Without a deny match the command falls back to the approval policy.
+        assert_eq!(
+            rules.evaluate(&command(&["bash", "-lc", "cargo test 2>&1 | tee log"])),
+            None
+        );
+    }
+
+    #[test]
+    fn invalid_regex_is_rejected() {
+        assert!(ApprovalRules::new(&["/(unclosed/".to_string()], &[]).is_err());
+    }
+}
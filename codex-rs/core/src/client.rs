@@ -42,14 +42,16 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::client_common::ResponseStream;
 use crate::config::Config;
+use crate::debug_capture::RequestCapture;
 use crate::default_client::build_reqwest_client;
 use crate::error::CodexErr;
 use crate::error::Result;
+use crate::features::Feature;
 use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_family::ModelFamily;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
-use crate::openai_model_info::get_model_info;
+use crate::openai_model_info::get_provider_model_info;
 use crate::tools::spec::create_tools_json_for_chat_completions_api;
 use crate::tools::spec::create_tools_json_for_responses_api;
 
@@ -93,13 +95,17 @@ impl ModelClient {
         let pct = self.config.model_family.effective_context_window_percent;
         self.config
             .model_context_window
-            .or_else(|| get_model_info(&self.config.model_family).map(|info| info.context_window))
+            .or_else(|| {
+                get_provider_model_info(&self.config.model_provider_id, &self.config.model_family)
+                    .map(|info| info.context_window)
+            })
             .map(|w| w.saturating_mul(pct) / 100)
     }
 
     pub fn get_auto_compact_token_limit(&self) -> Option<i64> {
         self.config.model_auto_compact_token_limit.or_else(|| {
-            get_model_info(&self.config.model_family).and_then(|info| info.auto_compact_token_limit)
+            get_provider_model_info(&self.config.model_provider_id, &self.config.model_family)
+                .and_then(|info| info.auto_compact_token_limit)
         })
     }
 
@@ -156,6 +162,7 @@ impl ModelClient {
         let api_prompt = build_api_prompt(prompt, instructions, tools_json);
         let conversation_id = self.conversation_id.to_string();
         let session_source = self.session_source.clone();
+        let capture = self.start_capture(WireApi::Chat, || capture_body(&api_prompt));
 
         let mut refreshed = false;
         loop {
@@ -165,7 +172,8 @@ impl ModelClient {
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
             let transport = ReqwestTransport::new(build_reqwest_client());
-            let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
+            let (request_telemetry, sse_telemetry) =
+                self.build_streaming_telemetry(capture.clone());
             let client = ApiChatClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
 
@@ -244,6 +252,13 @@ impl ModelClient {
         let api_prompt = build_api_prompt(prompt, instructions.clone(), tools_json);
         let conversation_id = self.conversation_id.to_string();
         let session_source = self.session_source.clone();
+        let capture = self.start_capture(WireApi::Responses, || {
+            let mut body = capture_body(&api_prompt);
+            body["reasoning"] = serde_json::to_value(&reasoning).unwrap_or_default();
+            body["include"] = serde_json::to_value(&include).unwrap_or_default();
+            body["text"] = serde_json::to_value(&text).unwrap_or_default();
+            body
+        });
 
         let mut refreshed = false;
         loop {
@@ -253,7 +268,8 @@ impl ModelClient {
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
             let transport = ReqwestTransport::new(build_reqwest_client());
-            let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
+            let (request_telemetry, sse_telemetry) =
+                self.build_streaming_telemetry(capture.clone());
             let client = ApiResponsesClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
 
@@ -373,9 +389,38 @@ impl ModelClient {
 }
 
 impl ModelClient {
+    /// Starts a debug capture for one model request when the `debug_capture`
+    /// feature is enabled. `body` is only built when capturing.
+    fn start_capture(
+        &self,
+        wire_api: WireApi,
+        body: impl FnOnce() -> Value,
+    ) -> Option<Arc<RequestCapture>> {
+        if !self.config.features.enabled(Feature::DebugCapture) {
+            return None;
+        }
+        match RequestCapture::start(
+            &self.config.codex_home,
+            &self.conversation_id,
+            wire_api,
+            &self.config.model,
+            body(),
+        ) {
+            Ok(capture) => Some(Arc::new(capture)),
+            Err(err) => {
+                warn!("failed to start debug capture: {err}");
+                None
+            }
+        }
+    }
+
     /// Builds request and SSE telemetry for streaming API calls (Chat/Responses).
-    fn build_streaming_telemetry(&self) -> (Arc<dyn RequestTelemetry>, Arc<dyn SseTelemetry>) {
-        let telemetry = Arc::new(ApiTelemetry::new(self.otel_event_manager.clone()));
+    fn build_streaming_telemetry(
+        &self,
+        capture: Option<Arc<RequestCapture>>,
+    ) -> (Arc<dyn RequestTelemetry>, Arc<dyn SseTelemetry>) {
+        let telemetry =
+            Arc::new(ApiTelemetry::new(self.otel_event_manager.clone()).with_capture(capture));
         let request_telemetry: Arc<dyn RequestTelemetry> = telemetry.clone();
         let sse_telemetry: Arc<dyn SseTelemetry> = telemetry;
         (request_telemetry, sse_telemetry)
@@ -389,6 +434,17 @@ impl ModelClient {
     }
 }
 
+/// The request fields shared by both wire APIs, as recorded in debug captures.
+fn capture_body(api_prompt: &ApiPrompt) -> Value {
+    serde_json::json!({
+        "instructions": api_prompt.instructions,
+        "input": api_prompt.input,
+        "tools": api_prompt.tools,
+        "parallel_tool_calls": api_prompt.parallel_tool_calls,
+        "output_schema": api_prompt.output_schema,
+    })
+}
+
 /// Adapts the core `Prompt` type into the `codex-api` payload shape.
 fn build_api_prompt(prompt: &Prompt, instructions: String, tools_json: Vec<Value>) -> ApiPrompt {
     ApiPrompt {
@@ -502,11 +558,20 @@ fn map_unauthorized_status(status: StatusCode) -> CodexErr {
 
 struct ApiTelemetry {
     otel_event_manager: OtelEventManager,
+    capture: Option<Arc<RequestCapture>>,
 }
 
 impl ApiTelemetry {
     fn new(otel_event_manager: OtelEventManager) -> Self {
-        Self { otel_event_manager }
+        Self {
+            otel_event_manager,
+            capture: None,
+        }
+    }
+
+    fn with_capture(mut self, capture: Option<Arc<RequestCapture>>) -> Self {
+        self.capture = capture;
+        self
     }
 }
 
@@ -538,5 +603,13 @@ impl SseTelemetry for ApiTelemetry {
         duration: Duration,
     ) {
         self.otel_event_manager.log_sse_event(result, duration);
+        if let Some(capture) = &self.capture {
+            match result {
+                Ok(Some(Ok(event))) => capture.record_event(event),
+                Ok(Some(Err(err))) => capture.record_error(&err.to_string()),
+                Ok(None) => {}
+                Err(_) => capture.record_error("idle timeout waiting for SSE"),
+            }
+        }
     }
 }
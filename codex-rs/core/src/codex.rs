@@ -1,7 +1,11 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::OnceLock;
+use std::sync::PoisonError;
+use std::sync::Weak;
 use std::sync::atomic::AtomicU64;
 
 use crate::AuthManager;
@@ -10,12 +14,18 @@ use crate::client_common::REVIEW_PROMPT;
 use crate::compact;
 use crate::compact::run_inline_auto_compact_task;
 use crate::compact::should_use_remote_compact_task;
+use crate::compact_local::run_local_auto_compact;
 use crate::compact_remote::run_inline_remote_auto_compact_task;
 use crate::features::Feature;
 use crate::function_tool::FunctionCallError;
 use crate::parse_command::parse_command;
 use crate::parse_turn_item;
 use crate::response_processing::process_items;
+use crate::retry_policy::ScheduledRetry;
+use crate::retry_policy::TurnRetryPolicy;
+use crate::session_summary::AUTOMATIC_NOTES_TIMEOUT;
+use crate::session_summary::NotesKind;
+use crate::stall_watchdog::ActivityTracker;
 use crate::terminal;
 use crate::truncate::TruncationPolicy;
 use crate::user_notification::UserNotifier;
@@ -52,6 +62,7 @@ use serde_json::Value;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 use tokio::sync::oneshot;
+use tokio::sync::watch;
 use tokio_util::sync::CancellationToken;
 use tracing::debug;
 use tracing::error;
@@ -64,18 +75,38 @@ use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::compact::collect_user_messages;
 use crate::config::Config;
+use crate::config::reload::ReloadableSettings;
+use crate::config::types::CompactionPolicy;
+use crate::config::types::PlanDetailPreference;
 use crate::config::types::ShellEnvironmentPolicy;
+use crate::config::types::WebhookConfig;
 use crate::context_manager::ContextManager;
+use crate::cost::pricing_for_model;
+use crate::env_drift::EnvironmentDrift;
+use crate::env_drift::WorkspaceFingerprint;
 use crate::environment_context::EnvironmentContext;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 #[cfg(test)]
 use crate::exec::StreamOutput;
+use crate::local_provider::detect_context_window;
 use crate::mcp::auth::compute_auth_statuses;
+use crate::mcp::secrets::SecretResolver;
 use crate::mcp_connection_manager::McpConnectionManager;
+use crate::model_family::ModelFamily;
 use crate::model_family::find_family_for_model;
-use crate::openai_model_info::get_model_info;
+use crate::model_family::local_model_family;
+use crate::model_provider_info::LOCAL_PROVIDER_ID;
+use crate::openai_model_info::ModelInfo;
+use crate::openai_model_info::get_provider_model_info;
+use crate::pinned_files::read_pinned_files;
+use crate::plan_mode::PLAN_MODE_INSTRUCTIONS;
+use crate::plan_mode::PlanPhase;
+use crate::plan_mode::PlanWorkflow;
+use crate::plan_mode::PlanWorkflowError;
+use crate::plan_mode::plan_request_prompt;
 use crate::project_doc::get_user_instructions;
+use crate::prompt_adaptation::PromptAdaptation;
 use crate::protocol::AgentMessageContentDeltaEvent;
 use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
@@ -83,15 +114,23 @@ use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
 use crate::protocol::CheckpointAction;
 use crate::protocol::CheckpointCreatedEvent;
+use crate::protocol::CheckpointDiffEvent;
 use crate::protocol::CheckpointEntry;
 use crate::protocol::CheckpointErrorEvent;
 use crate::protocol::CheckpointListEvent;
 use crate::protocol::CheckpointRestoredEvent;
+use crate::protocol::CostLimitApprovalRequestEvent;
 use crate::protocol::DeprecationNoticeEvent;
+use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
+use crate::protocol::FollowUpSuggestionsEvent;
 use crate::protocol::Op;
+use crate::protocol::PatchApplyBeginEvent;
+use crate::protocol::PatchApplyEndEvent;
+use crate::protocol::PlanReadyEvent;
+use crate::protocol::ProjectDocsLoadedEvent;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
 use crate::protocol::ReasoningRawContentDeltaEvent;
@@ -99,17 +138,23 @@ use crate::protocol::ReviewDecision;
 use crate::protocol::SandboxCommandAssessment;
 use crate::protocol::SandboxPolicy;
 use crate::protocol::SessionConfiguredEvent;
+use crate::protocol::SessionLockLostEvent;
 use crate::protocol::StreamErrorEvent;
 use crate::protocol::Submission;
 use crate::protocol::TokenCountEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::protocol::TurnDiffEvent;
+use crate::protocol::TurnRetryScheduledEvent;
 use crate::protocol::WarningEvent;
+use crate::revert::RevertScope;
+use crate::revert::plan_revert;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::shell;
 use crate::state::ActiveTurn;
+use crate::state::FileStamp;
+use crate::state::ReadCacheKey;
 use crate::state::SessionServices;
 use crate::state::SessionState;
 use crate::tasks::GhostSnapshotTask;
@@ -127,7 +172,7 @@ use crate::unified_exec::UnifiedExecSessionManager;
 use crate::user_instructions::DeveloperInstructions;
 use crate::user_instructions::UserInstructions;
 use crate::user_notification::UserNotification;
-use crate::util::backoff;
+use crate::webhooks;
 use chrono::SecondsFormat;
 use chrono::Utc;
 use codex_async_utils::OrCancelExt;
@@ -135,7 +180,9 @@ use codex_execpolicy::Policy as ExecPolicy;
 use codex_git::CreateGhostCommitOptions;
 use codex_git::GhostCommit;
 use codex_git::create_ghost_commit;
+use codex_git::diff_ghost_commits;
 use codex_git::restore_ghost_commit;
+use codex_git::restore_ghost_commit_paths;
 use codex_otel::otel_event_manager::OtelEventManager;
 use codex_protocol::config_types::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
@@ -144,6 +191,7 @@ use codex_protocol::models::ContentItem;
 use codex_protocol::models::FunctionCallOutputPayload;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::user_input::UserInput;
@@ -172,7 +220,7 @@ pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
     pub async fn spawn(
-        config: Config,
+        mut config: Config,
         auth_manager: Arc<AuthManager>,
         conversation_history: InitialHistory,
         session_source: SessionSource,
@@ -180,12 +228,22 @@ impl Codex {
         let (tx_sub, rx_sub) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
         let (tx_event, rx_event) = async_channel::unbounded();
 
-        let user_instructions = get_user_instructions(&config).await;
+        let (user_instructions, project_docs) = get_user_instructions(&config).await;
 
         let exec_policy = crate::exec_policy::exec_policy_for(&config.features, &config.codex_home)
             .await
             .map_err(|err| CodexErr::Fatal(format!("failed to load execpolicy: {err}")))?;
 
+        if config.model_provider_id == LOCAL_PROVIDER_ID
+            && config.model_context_window.is_none()
+            && let Some(window) = detect_context_window(&config.model_provider, &config.model).await
+        {
+            config.model_context_window = Some(window);
+            config.model_auto_compact_token_limit = config
+                .model_auto_compact_token_limit
+                .or(ModelInfo::new(window).auto_compact_token_limit);
+        }
+
         let config = Arc::new(config);
 
         let session_configuration = SessionConfiguration {
@@ -204,6 +262,8 @@ impl Codex {
             features: config.features.clone(),
             exec_policy,
             session_source,
+            session_env: HashMap::new(),
+            session_webhooks: Vec::new(),
         };
 
         // Generate a unique ID for the lifetime of this Codex session.
@@ -222,6 +282,24 @@ impl Codex {
             CodexErr::InternalAgentDied
         })?;
         let conversation_id = session.conversation_id;
+        if !project_docs.is_empty() {
+            session
+                .send_event_raw(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg: EventMsg::ProjectDocsLoaded(ProjectDocsLoadedEvent { docs: project_docs }),
+                })
+                .await;
+        }
+
+        let codex_home = config.codex_home.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Err(err) = crate::mcp::attachments::prune_attachments(
+                &codex_home,
+                std::time::SystemTime::now(),
+            ) {
+                warn!("failed to prune MCP image attachments: {err}");
+            }
+        });
 
         // This task will run until Op::Shutdown is received.
         tokio::spawn(submission_loop(session, config, rx_sub));
@@ -305,6 +383,16 @@ pub(crate) struct TurnContext {
 }
 
 impl TurnContext {
+    /// Announces the start of a task along with the settings used for it.
+    pub(crate) fn task_started_event(&self) -> TaskStartedEvent {
+        TaskStartedEvent {
+            model_context_window: self.client.get_model_context_window(),
+            model: self.client.get_model(),
+            reasoning_effort: self.client.get_reasoning_effort(),
+            sandbox_policy: Some(self.sandbox_policy.clone()),
+        }
+    }
+
     pub(crate) fn resolve_path(&self, path: Option<String>) -> PathBuf {
         path.as_ref()
             .map(PathBuf::from)
@@ -364,6 +452,14 @@ pub(crate) struct SessionConfiguration {
     original_config_do_not_use: Arc<Config>,
     /// Source of the session (cli, vscode, exec, mcp, ...)
     session_source: SessionSource,
+
+    /// Variables set with `Op::SetSessionEnv`, layered over the configured
+    /// shell environment for every command of this session.
+    session_env: HashMap<String, String>,
+
+    /// Webhooks registered with `Op::SetWebhook`, delivered alongside the
+    /// configured `[[webhooks]]`.
+    session_webhooks: Vec<WebhookConfig>,
 }
 
 impl SessionConfiguration {
@@ -412,14 +508,22 @@ impl Session {
         sub_id: String,
     ) -> TurnContext {
         let config = session_configuration.original_config_do_not_use.clone();
-        let model_family = find_family_for_model(&session_configuration.model)
+        let model_family = find_provider_model_family(&config, &session_configuration.model)
             .unwrap_or_else(|| config.model_family.clone());
         let mut per_turn_config = (*config).clone();
         per_turn_config.model = session_configuration.model.clone();
         per_turn_config.model_family = model_family.clone();
         per_turn_config.model_reasoning_effort = session_configuration.model_reasoning_effort;
         per_turn_config.model_reasoning_summary = session_configuration.model_reasoning_summary;
-        if let Some(model_info) = get_model_info(&model_family) {
+        if let Some(verbosity) = config
+            .model_preferences
+            .get(&session_configuration.model)
+            .and_then(|preference| preference.verbosity)
+        {
+            per_turn_config.model_verbosity = Some(verbosity);
+        }
+        if let Some(model_info) = get_provider_model_info(&config.model_provider_id, &model_family)
+        {
             per_turn_config.model_context_window = Some(model_info.context_window);
         }
 
@@ -439,10 +543,19 @@ impl Session {
             session_configuration.session_source.clone(),
         );
 
-        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
             model_family: &model_family,
             features: &config.features,
         });
+        tools_config.subagent_scratchpad = config.subagent_scratchpad.is_some();
+        if config.subagent_scratchpad.is_none() && config.features.enabled(Feature::Subagents) {
+            tools_config.subagent_roles = Some(config.subagent_roles.clone());
+        }
+
+        let mut shell_environment_policy = config.shell_environment_policy.clone();
+        shell_environment_policy
+            .r#set
+            .extend(session_configuration.session_env.clone());
 
         TurnContext {
             sub_id,
@@ -454,7 +567,7 @@ impl Session {
             user_instructions: session_configuration.user_instructions.clone(),
             approval_policy: session_configuration.approval_policy,
             sandbox_policy: session_configuration.sandbox_policy.clone(),
-            shell_environment_policy: config.shell_environment_policy.clone(),
+            shell_environment_policy,
             tools_config,
             final_output_json_schema: None,
             codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
@@ -524,6 +637,7 @@ impl Session {
             anyhow::anyhow!("failed to initialize rollout recorder: {e:#}")
         })?;
         let rollout_path = rollout_recorder.rollout_path.clone();
+        let rollout_lock_held = rollout_recorder.lock_held();
 
         let mut post_session_configured_events = Vec::<Event>::new();
 
@@ -542,6 +656,12 @@ impl Session {
                 msg: EventMsg::DeprecationNotice(DeprecationNoticeEvent { summary, details }),
             });
         }
+        if let Some(message) = config.resource_limits.unsupported_platform_warning() {
+            post_session_configured_events.push(Event {
+                id: INITIAL_SUBMIT_ID.to_owned(),
+                msg: EventMsg::Warning(WarningEvent { message }),
+            });
+        }
 
         let otel_event_manager = OtelEventManager::new(
             conversation_id,
@@ -552,7 +672,8 @@ impl Session {
             auth_manager.auth().map(|a| a.mode),
             config.otel.log_user_prompt,
             terminal::user_agent(),
-        );
+        )
+        .with_turn_spans(config.otel.trace_spans);
 
         otel_event_manager.conversation_starts(
             config.model_provider.name.as_str(),
@@ -569,35 +690,25 @@ impl Session {
         // Create the mutable state for the Session.
         let state = SessionState::new(session_configuration.clone());
 
-        // Initialize memory manager for persistent learning
-        let memory_manager = match crate::memory::MemoryManager::init(
-            &session_configuration.cwd,
-            crate::memory::MemoryConfig::default(),
-        )
-        .await
-        {
-            Ok(mm) => {
-                tracing::info!("Memory system initialized for session");
-                Some(Arc::new(mm))
-            }
-            Err(e) => {
-                tracing::warn!("Failed to initialize memory system: {}", e);
-                None
-            }
-        };
+        let recalled_session_notes =
+            crate::session_summary::load_recent_notes(&session_configuration.cwd).await;
 
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
-            notifier: UserNotifier::new(config.notify.clone()),
             rollout: Mutex::new(Some(rollout_recorder)),
             user_shell: default_shell,
-            show_raw_agent_reasoning: config.show_raw_agent_reasoning,
+            reloadable: std::sync::RwLock::new(ReloadableSettings::from_config(&config)),
+            config_reload: Mutex::new(None),
             auth_manager: Arc::clone(&auth_manager),
             otel_event_manager,
             tool_approvals: Mutex::new(ApprovalStore::default()),
-            memory_manager,
+            memory_manager: OnceLock::new(),
+            recalled_session_notes,
+            subagent_cancellations: Mutex::new(HashMap::new()),
+            tool_call_cancellations: Mutex::new(HashMap::new()),
+            activity_tracker: ActivityTracker::default(),
         };
 
         let sess = Arc::new(Session {
@@ -626,7 +737,7 @@ impl Session {
                 history_log_id,
                 history_entry_count,
                 initial_messages,
-                rollout_path,
+                rollout_path: rollout_path.clone(),
             }),
         })
         .chain(post_session_configured_events.into_iter());
@@ -640,11 +751,28 @@ impl Session {
             .initialize(
                 config.mcp_servers.clone(),
                 config.mcp_oauth_credentials_store_mode,
+                Arc::new(SecretResolver::from_config(&config.secrets)),
                 auth_statuses.clone(),
                 tx_event.clone(),
                 sess.services.mcp_startup_cancellation_token.clone(),
             )
             .await;
+        crate::mcp_connection_manager::spawn_supervisor(
+            Arc::downgrade(&sess.services.mcp_connection_manager),
+            tx_event.clone(),
+        );
+        if !matches!(
+            session_configuration.session_source,
+            SessionSource::SubAgent(_)
+        ) {
+            crate::config::reload::spawn_config_watcher(Arc::downgrade(&sess), (*config).clone());
+        }
+        spawn_rollout_lock_watch(Arc::downgrade(&sess), rollout_lock_held, rollout_path);
+        crate::subsystem_startup::spawn_subsystem_startup(
+            Arc::downgrade(&sess),
+            session_configuration.cwd.clone(),
+            config.memory.clone(),
+        );
 
         let sandbox_state = SandboxState {
             sandbox_policy: session_configuration.sandbox_policy.clone(),
@@ -672,6 +800,10 @@ impl Session {
         self.tx_event.clone()
     }
 
+    pub(crate) fn conversation_id(&self) -> ConversationId {
+        self.conversation_id
+    }
+
     /// Ensure all rollout writes are durably flushed.
     pub(crate) async fn flush_rollout(&self) {
         let recorder = {
@@ -757,6 +889,40 @@ impl Session {
         }
     }
 
+    /// The session's working directory and the config it was started with.
+    pub(crate) async fn cwd_and_config(&self) -> (PathBuf, Arc<Config>) {
+        let state = self.state.lock().await;
+        (
+            state.session_configuration.cwd.clone(),
+            Arc::clone(&state.session_configuration.original_config_do_not_use),
+        )
+    }
+
+    /// Replaces the variables layered over the shell environment for later
+    /// turns. Returns the session's working directory and config.
+    pub(crate) async fn set_session_env(
+        &self,
+        vars: HashMap<String, String>,
+    ) -> (PathBuf, Arc<Config>) {
+        let mut state = self.state.lock().await;
+        state.session_configuration.session_env = vars;
+        (
+            state.session_configuration.cwd.clone(),
+            Arc::clone(&state.session_configuration.original_config_do_not_use),
+        )
+    }
+
+    /// Registers `webhook` for this session, replacing any registration of
+    /// the same URL; empty `events` only removes it.
+    pub(crate) async fn set_webhook(&self, webhook: WebhookConfig) {
+        let mut state = self.state.lock().await;
+        let webhooks = &mut state.session_configuration.session_webhooks;
+        webhooks.retain(|existing| existing.url != webhook.url);
+        if !webhook.events.is_empty() {
+            webhooks.push(webhook);
+        }
+    }
+
     pub(crate) async fn update_settings(&self, updates: SessionSettingsUpdate) {
         let mut state = self.state.lock().await;
 
@@ -794,6 +960,26 @@ impl Session {
         Arc::new(turn_context)
     }
 
+    /// Like `new_turn_with_sub_id`, but `updates` only apply to this turn.
+    pub(crate) async fn new_one_off_turn(
+        &self,
+        sub_id: String,
+        updates: SessionSettingsUpdate,
+    ) -> Arc<TurnContext> {
+        let session_configuration = {
+            let state = self.state.lock().await;
+            state.session_configuration.clone().apply(&updates)
+        };
+        Arc::new(Self::make_turn_context(
+            Some(Arc::clone(&self.services.auth_manager)),
+            &self.services.otel_event_manager,
+            session_configuration.provider.clone(),
+            &session_configuration,
+            self.conversation_id,
+            sub_id,
+        ))
+    }
+
     fn build_environment_update_item(
         &self,
         previous: Option<&Arc<TurnContext>>,
@@ -832,6 +1018,27 @@ impl Session {
     }
 
     pub(crate) async fn send_event_raw(&self, event: Event) {
+        self.services.activity_tracker.record_event(&event.msg);
+        self.services
+            .otel_event_manager
+            .record_turn_event(&event.id, &event.msg);
+        if let Some((kind, summary)) = webhooks::classify(&event.msg) {
+            let mut targets = self.reloadable_settings().webhooks;
+            let state = self.state.lock().await;
+            let configuration = &state.session_configuration;
+            targets.extend(configuration.session_webhooks.iter().cloned());
+            if targets.iter().any(|webhook| webhook.events.contains(&kind)) {
+                webhooks::deliver(
+                    &targets,
+                    &configuration.original_config_do_not_use.redactor,
+                    kind,
+                    self.conversation_id.to_string(),
+                    &event.id,
+                    &configuration.cwd,
+                    &summary,
+                );
+            }
+        }
         // Persist the event into rollout (recorder filters as needed)
         let rollout_items = vec![RolloutItem::EventMsg(event.msg.clone())];
         self.persist_rollout_items(&rollout_items).await;
@@ -891,6 +1098,26 @@ impl Session {
         .await
     }
 
+    /// Summarize long shell output for the model with a side request.
+    pub(crate) async fn summarize_exec_output(
+        &self,
+        turn_context: &TurnContext,
+        command: &str,
+        output: &str,
+    ) -> anyhow::Result<String> {
+        crate::exec_output::summarize_output(
+            turn_context.client.config(),
+            turn_context.client.provider().clone(),
+            Arc::clone(&self.services.auth_manager),
+            &self.services.otel_event_manager,
+            self.conversation_id,
+            turn_context.client.get_session_source(),
+            command,
+            output,
+        )
+        .await
+    }
+
     /// Emit an exec approval request event and await the user's decision.
     ///
     /// The request is keyed by `sub_id`/`call_id` so matching responses are delivered
@@ -904,6 +1131,7 @@ impl Session {
         cwd: PathBuf,
         reason: Option<String>,
         risk: Option<SandboxCommandAssessment>,
+        network_destinations: Vec<String>,
     ) -> ReviewDecision {
         let sub_id = turn_context.sub_id.clone();
         // Add the tx_approve callback to the map before sending the request.
@@ -931,6 +1159,7 @@ impl Session {
             cwd,
             reason,
             risk,
+            network_destinations,
             parsed_cmd,
         });
         self.send_event(turn_context, event).await;
@@ -974,6 +1203,15 @@ impl Session {
         rx_approve
     }
 
+    /// Whether the active turn is waiting on the user to answer an approval.
+    pub(crate) async fn has_pending_approvals(&self) -> bool {
+        let active = self.active_turn.lock().await;
+        match active.as_ref() {
+            Some(at) => at.turn_state.lock().await.has_pending_approvals(),
+            None => false,
+        }
+    }
+
     pub async fn notify_approval(&self, sub_id: &str, decision: ReviewDecision) {
         let entry = {
             let mut active = self.active_turn.lock().await;
@@ -1112,6 +1350,15 @@ impl Session {
                 .into(),
             );
         }
+        if let Some(notes) = self.services.recalled_session_notes.as_deref() {
+            items.push(ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: notes.to_string(),
+                }],
+            });
+        }
         items.push(ResponseItem::from(EnvironmentContext::new(
             Some(turn_context.cwd.clone()),
             Some(turn_context.approval_policy),
@@ -1121,9 +1368,211 @@ impl Session {
         items
     }
 
-    /// Get the memory manager if available.
+    /// Get the memory manager if available. Returns `None` while memory is
+    /// still starting; see [`Session::memory_starting`].
     pub(crate) fn memory_manager(&self) -> Option<&std::sync::Arc<crate::memory::MemoryManager>> {
-        self.services.memory_manager.as_ref()
+        self.services.memory_manager.get().and_then(Option::as_ref)
+    }
+
+    /// Whether the memory system is still starting in the background.
+    pub(crate) fn memory_starting(&self) -> bool {
+        self.services.memory_manager.get().is_none()
+    }
+
+    /// Merge near-duplicate memories with the session's provider and auth.
+    pub(crate) async fn consolidate_memories(
+        &self,
+        memory: &crate::memory::MemoryManager,
+    ) -> anyhow::Result<crate::memory::ConsolidationResult> {
+        let config = self.clone_original_config().await?;
+        memory
+            .consolidate(
+                config,
+                self.get_provider().await,
+                Arc::clone(&self.services.auth_manager),
+                &self.services.otel_event_manager,
+                self.conversation_id,
+                self.get_session_source().await,
+            )
+            .await
+    }
+
+    /// Track a running subagent task so it can be cancelled individually.
+    pub(crate) async fn register_subagent_task(
+        &self,
+        call_id: &str,
+        agent_index: i64,
+    ) -> CancellationToken {
+        let token = CancellationToken::new();
+        self.services
+            .subagent_cancellations
+            .lock()
+            .await
+            .insert((call_id.to_string(), agent_index), token.clone());
+        token
+    }
+
+    pub(crate) async fn unregister_subagent_task(&self, call_id: &str, agent_index: i64) {
+        self.services
+            .subagent_cancellations
+            .lock()
+            .await
+            .remove(&(call_id.to_string(), agent_index));
+    }
+
+    /// Track a running tool call so it can be cancelled individually. The
+    /// returned token is a child of the turn's token, so interrupting the
+    /// turn still cancels the call.
+    pub(crate) async fn register_tool_call(
+        &self,
+        call_id: &str,
+        turn_token: &CancellationToken,
+    ) -> CancellationToken {
+        let token = turn_token.child_token();
+        self.services
+            .tool_call_cancellations
+            .lock()
+            .await
+            .insert(call_id.to_string(), token.clone());
+        token
+    }
+
+    pub(crate) async fn unregister_tool_call(&self, call_id: &str) {
+        self.services
+            .tool_call_cancellations
+            .lock()
+            .await
+            .remove(call_id);
+    }
+
+    /// Start a plan-first workflow for `request`, replacing any plan that is
+    /// awaiting review, and return the prompt for the planning turn. `detail`
+    /// overrides the configured `plan_detail`.
+    async fn begin_plan_workflow(
+        &self,
+        request: String,
+        detail: Option<PlanDetailPreference>,
+        turn_context: Option<&TurnContext>,
+    ) -> String {
+        let (instructions, configured_detail) = match turn_context {
+            Some(turn_context) => {
+                let config = turn_context.client.config();
+                let adaptation = PromptAdaptation::for_model(
+                    &turn_context.client.get_model_family(),
+                    &config.prompt_adaptations,
+                );
+                (
+                    adaptation.plan_mode_instructions().to_string(),
+                    config.plan_detail,
+                )
+            }
+            None => (
+                PLAN_MODE_INSTRUCTIONS.to_string(),
+                PlanDetailPreference::default(),
+            ),
+        };
+        let detail = detail.unwrap_or(configured_detail);
+        let prompt = plan_request_prompt(&instructions, detail, &request);
+        self.state.lock().await.plan_workflow = Some(PlanWorkflow::new(request));
+        prompt
+    }
+
+    async fn revise_plan(&self, feedback: String) -> Result<String, PlanWorkflowError> {
+        let mut state = self.state.lock().await;
+        let workflow = state
+            .plan_workflow
+            .as_mut()
+            .ok_or(PlanWorkflowError::NoWorkflow)?;
+        workflow.revise(feedback)
+    }
+
+    async fn approve_plan(&self) -> Result<String, PlanWorkflowError> {
+        let mut state = self.state.lock().await;
+        let workflow = state
+            .plan_workflow
+            .take()
+            .ok_or(PlanWorkflowError::NoWorkflow)?;
+        if workflow.phase() != PlanPhase::AwaitingReview {
+            state.plan_workflow = Some(workflow);
+            return Err(PlanWorkflowError::NotAwaitingReview);
+        }
+        workflow.approve()
+    }
+
+    async fn cancel_plan(&self) {
+        self.state.lock().await.plan_workflow = None;
+    }
+
+    /// Remember a plan published via `update_plan` during a planning turn.
+    pub(crate) async fn record_plan_update(&self, plan: &UpdatePlanArgs) {
+        if let Some(workflow) = self.state.lock().await.plan_workflow.as_mut() {
+            workflow.record_plan(plan);
+        }
+    }
+
+    /// Close out a planning turn, returning the plan to report for review.
+    pub(crate) async fn finish_plan_turn(&self, summary: Option<String>) -> Option<PlanReadyEvent> {
+        self.state
+            .lock()
+            .await
+            .plan_workflow
+            .as_mut()?
+            .finish_drafting(summary)
+    }
+
+    async fn send_plan_workflow_error(&self, sub_id: String, err: PlanWorkflowError) {
+        self.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::Warning(WarningEvent {
+                message: format!("Plan mode: {err}"),
+            }),
+        })
+        .await;
+    }
+
+    async fn cancel_subagent_task(&self, sub_id: String, call_id: String, agent_index: i64) {
+        let token = self
+            .services
+            .subagent_cancellations
+            .lock()
+            .await
+            .remove(&(call_id.clone(), agent_index));
+        match token {
+            Some(token) => token.cancel(),
+            None => {
+                self.send_event_raw(Event {
+                    id: sub_id,
+                    msg: EventMsg::Warning(WarningEvent {
+                        message: format!(
+                            "No running subagent task {} for call {call_id}",
+                            agent_index + 1
+                        ),
+                    }),
+                })
+                .await;
+            }
+        }
+    }
+
+    async fn cancel_tool_call(&self, sub_id: String, call_id: String) {
+        let token = self
+            .services
+            .tool_call_cancellations
+            .lock()
+            .await
+            .remove(&call_id);
+        match token {
+            Some(token) => token.cancel(),
+            None => {
+                self.send_event_raw(Event {
+                    id: sub_id,
+                    msg: EventMsg::Warning(WarningEvent {
+                        message: format!("No running tool call {call_id}"),
+                    }),
+                })
+                .await;
+            }
+        }
     }
 
     pub(crate) async fn persist_rollout_items(&self, items: &[RolloutItem]) {
@@ -1148,16 +1597,83 @@ impl Session {
         turn_context: &TurnContext,
         token_usage: Option<&TokenUsage>,
     ) {
-        {
+        let pricing = pricing_for_model(&turn_context.client.get_model());
+        let cost_update = {
             let mut state = self.state.lock().await;
-            if let Some(token_usage) = token_usage {
-                state.update_token_info_from_usage(
-                    token_usage,
-                    turn_context.client.get_model_context_window(),
-                );
+            match token_usage {
+                Some(token_usage) => {
+                    state.update_token_info_from_usage(
+                        token_usage,
+                        turn_context.client.get_model_context_window(),
+                    );
+                    pricing.map(|pricing| {
+                        state.cost.record(&pricing, token_usage);
+                        state
+                            .cost
+                            .update_event(self.reloadable_settings().max_session_cost_usd)
+                    })
+                }
+                None => None,
             }
-        }
+        };
         self.send_token_count_event(turn_context).await;
+        if let Some(cost_update) = cost_update {
+            self.send_event(turn_context, EventMsg::CostUpdate(cost_update))
+                .await;
+        }
+    }
+
+    /// Pause before the next model request when the session has exceeded
+    /// `max_session_cost_usd`, and ask the user whether to keep going.
+    /// Returns whether the turn may continue.
+    async fn confirm_cost_budget(&self, turn_context: &TurnContext) -> bool {
+        let Some(max_session_cost_usd) = self.reloadable_settings().max_session_cost_usd else {
+            return true;
+        };
+        let session_cost_usd = {
+            let state = self.state.lock().await;
+            if !state.cost.needs_confirmation(Some(max_session_cost_usd)) {
+                return true;
+            }
+            state.cost.session_cost_usd()
+        };
+
+        let sub_id = turn_context.sub_id.clone();
+        let (tx_approve, rx_approve) = oneshot::channel();
+        let prev_entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.insert_pending_approval(sub_id.clone(), tx_approve)
+                }
+                None => None,
+            }
+        };
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending approval for sub_id: {sub_id}");
+        }
+
+        let event = EventMsg::CostLimitApprovalRequest(CostLimitApprovalRequestEvent {
+            session_cost_usd,
+            max_session_cost_usd,
+        });
+        self.send_event(turn_context, event).await;
+        let decision = rx_approve.await.unwrap_or_default();
+        let proceed = self.state.lock().await.cost.apply_decision(decision);
+        if !proceed {
+            self.send_event(
+                turn_context,
+                EventMsg::Warning(WarningEvent {
+                    message: format!(
+                        "Stopped: session cost ${session_cost_usd:.2} exceeds the \
+                         ${max_session_cost_usd:.2} budget"
+                    ),
+                }),
+            )
+            .await;
+        }
+        proceed
     }
 
     pub(crate) async fn recompute_token_usage(&self, turn_context: &TurnContext) {
@@ -1366,21 +1882,7 @@ impl Session {
             return;
         }
 
-        let mut history = self.clone_history().await;
-        let maybe_checkpoint =
-            history
-                .get_history()
-                .into_iter()
-                .rev()
-                .find_map(|item| match item {
-                    ResponseItem::GhostSnapshot {
-                        ghost_commit,
-                        checkpoint: Some(metadata),
-                    } if metadata.name == trimmed => Some((ghost_commit, metadata)),
-                    _ => None,
-                });
-
-        let Some((ghost_commit, metadata)) = maybe_checkpoint else {
+        let Some((ghost_commit, metadata)) = self.find_checkpoint(trimmed).await else {
             self.send_event(
                 turn_context.as_ref(),
                 EventMsg::CheckpointError(CheckpointErrorEvent {
@@ -1450,41 +1952,309 @@ impl Session {
         });
     }
 
-    pub(crate) async fn list_checkpoints(self: &Arc<Self>, sub_id: String) {
+    pub(crate) async fn diff_checkpoints(
+        self: &Arc<Self>,
+        sub_id: String,
+        from: String,
+        to: String,
+    ) {
+        let from = from.trim().to_string();
+        let to = to.trim().to_string();
         let turn_context = self
             .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
             .await;
-        let mut history = self.clone_history().await;
-        let mut checkpoints: Vec<CheckpointEntry> = history
-            .get_history()
-            .into_iter()
-            .filter_map(|item| checkpoint_entry_from_item(&item))
-            .collect();
-        checkpoints.reverse();
 
-        self.send_event(
-            turn_context.as_ref(),
-            EventMsg::CheckpointList(CheckpointListEvent { checkpoints }),
-        )
-        .await;
-    }
+        if from.is_empty() || to.is_empty() {
+            self.send_event(
+                turn_context.as_ref(),
+                EventMsg::CheckpointError(CheckpointErrorEvent {
+                    action: CheckpointAction::Diff,
+                    name: None,
+                    message: "Two checkpoint names are required to compute a diff.".to_string(),
+                }),
+            )
+            .await;
+            return;
+        }
 
-    pub(crate) async fn notify_stream_error(
-        &self,
-        turn_context: &TurnContext,
-        message: impl Into<String>,
-        codex_error: CodexErr,
-    ) {
-        let codex_error_info = CodexErrorInfo::ResponseStreamDisconnected {
-            http_status_code: codex_error.http_status_code_value(),
-        };
-        let event = EventMsg::StreamError(StreamErrorEvent {
-            message: message.into(),
+        let (from_checkpoint, to_checkpoint) = match (
+            self.find_checkpoint(&from).await,
+            self.find_checkpoint(&to).await,
+        ) {
+            (Some(from_checkpoint), Some(to_checkpoint)) => (from_checkpoint, to_checkpoint),
+            (from_checkpoint, _) => {
+                let missing = if from_checkpoint.is_none() { from } else { to };
+                self.send_event(
+                    turn_context.as_ref(),
+                    EventMsg::CheckpointError(CheckpointErrorEvent {
+                        action: CheckpointAction::Diff,
+                        message: format!("Checkpoint `{missing}` not found."),
+                        name: Some(missing),
+                    }),
+                )
+                .await;
+                return;
+            }
+        };
+        let (from_commit, from_metadata) = from_checkpoint;
+        let (to_commit, to_metadata) = to_checkpoint;
+
+        let session = Arc::clone(self);
+        let context = Arc::clone(&turn_context);
+        let repo_path = context.cwd.clone();
+        tokio::spawn(async move {
+            let from_entry =
+                checkpoint_entry(&from_metadata.name, &from_commit, from_metadata.created_at);
+            let to_entry = checkpoint_entry(&to_metadata.name, &to_commit, to_metadata.created_at);
+            let diff_result = tokio::task::spawn_blocking(move || {
+                diff_ghost_commits(&repo_path, &from_commit, &to_commit)
+            })
+            .await;
+
+            let msg = match diff_result {
+                Ok(Ok(unified_diff)) => EventMsg::CheckpointDiff(CheckpointDiffEvent {
+                    from: from_entry,
+                    to: to_entry,
+                    unified_diff,
+                }),
+                Ok(Err(err)) => EventMsg::CheckpointError(CheckpointErrorEvent {
+                    action: CheckpointAction::Diff,
+                    name: None,
+                    message: format!("Failed to diff checkpoints `{from}` and `{to}`: {err}"),
+                }),
+                Err(err) => EventMsg::CheckpointError(CheckpointErrorEvent {
+                    action: CheckpointAction::Diff,
+                    name: None,
+                    message: format!("Failed to diff checkpoints `{from}` and `{to}`: {err}"),
+                }),
+            };
+            session.send_event(context.as_ref(), msg).await;
+        });
+    }
+
+    pub(crate) async fn record_turn_snapshot(&self, turn_id: String, ghost_commit: GhostCommit) {
+        let mut state = self.state.lock().await;
+        state.record_turn_snapshot(turn_id, ghost_commit);
+    }
+
+    pub(crate) async fn revert_file_change(
+        self: &Arc<Self>,
+        sub_id: String,
+        path: PathBuf,
+        turn_id: Option<String>,
+    ) {
+        let turn_context = self
+            .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
+            .await;
+        let snapshot = {
+            let state = self.state.lock().await;
+            match turn_id.as_deref() {
+                Some(turn_id) => state.turn_snapshot(turn_id).map(|(commit, _)| commit),
+                None => state.latest_turn_snapshot(),
+            }
+        };
+        let Some(snapshot) = snapshot else {
+            let message = match turn_id {
+                Some(turn_id) => format!("No snapshot was captured for turn `{turn_id}`."),
+                None => "No snapshot is available to revert from.".to_string(),
+            };
+            self.send_revert_error(&turn_context, message).await;
+            return;
+        };
+
+        let path = path
+            .strip_prefix(&turn_context.cwd)
+            .map(Path::to_path_buf)
+            .unwrap_or(path);
+        self.spawn_revert(turn_context, snapshot, RevertScope::Paths(vec![path]));
+    }
+
+    pub(crate) async fn revert_turn(self: &Arc<Self>, sub_id: String, turn_id: String) {
+        let turn_context = self
+            .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
+            .await;
+        let snapshot = self.state.lock().await.turn_snapshot(&turn_id);
+        let Some((snapshot, next)) = snapshot else {
+            self.send_revert_error(
+                &turn_context,
+                format!("No snapshot was captured for turn `{turn_id}`."),
+            )
+            .await;
+            return;
+        };
+
+        self.spawn_revert(turn_context, snapshot, RevertScope::ChangedUntil(next));
+    }
+
+    /// Restores the files selected by `scope` from `snapshot`, reporting the
+    /// edits as a patch so the transcript shows what was reverted.
+    fn spawn_revert(
+        self: &Arc<Self>,
+        turn_context: Arc<TurnContext>,
+        snapshot: GhostCommit,
+        scope: RevertScope,
+    ) {
+        let session = Arc::clone(self);
+        tokio::spawn(async move {
+            let repo_path = turn_context.cwd.clone();
+            let plan_snapshot = snapshot.clone();
+            let plan = match tokio::task::spawn_blocking(move || {
+                plan_revert(&repo_path, &plan_snapshot, scope)
+            })
+            .await
+            {
+                Ok(Ok(plan)) => plan,
+                Ok(Err(err)) => {
+                    session
+                        .send_revert_error(&turn_context, format!("Failed to revert: {err}"))
+                        .await;
+                    return;
+                }
+                Err(err) => {
+                    session
+                        .send_revert_error(&turn_context, format!("Failed to revert: {err}"))
+                        .await;
+                    return;
+                }
+            };
+            if plan.paths.is_empty() {
+                session
+                    .notify_background_event(&turn_context, "Nothing to revert.")
+                    .await;
+                return;
+            }
+
+            let call_id = format!("revert-{}", turn_context.sub_id);
+            session
+                .send_event(
+                    &turn_context,
+                    EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
+                        call_id: call_id.clone(),
+                        turn_id: turn_context.sub_id.clone(),
+                        auto_approved: true,
+                        changes: plan.changes.clone(),
+                    }),
+                )
+                .await;
+
+            let repo_path = turn_context.cwd.clone();
+            let reverted = plan.paths.len();
+            let restore_result = tokio::task::spawn_blocking(move || {
+                restore_ghost_commit_paths(&repo_path, &snapshot, &plan.paths)
+            })
+            .await;
+            let (success, stdout, stderr) = match restore_result {
+                Ok(Ok(())) => (true, format!("Reverted {reverted} file(s)."), String::new()),
+                Ok(Err(err)) => (false, String::new(), format!("Failed to revert: {err}")),
+                Err(err) => (false, String::new(), format!("Failed to revert: {err}")),
+            };
+            session
+                .send_event(
+                    &turn_context,
+                    EventMsg::PatchApplyEnd(PatchApplyEndEvent {
+                        call_id,
+                        turn_id: turn_context.sub_id.clone(),
+                        stdout,
+                        stderr,
+                        success,
+                        changes: plan.changes,
+                    }),
+                )
+                .await;
+        });
+    }
+
+    async fn send_revert_error(&self, turn_context: &TurnContext, message: String) {
+        self.send_event(
+            turn_context,
+            EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(CodexErrorInfo::Other),
+            }),
+        )
+        .await;
+    }
+
+    /// Returns the most recent checkpoint recorded under `name`, if any.
+    async fn find_checkpoint(&self, name: &str) -> Option<(GhostCommit, CheckpointMetadata)> {
+        let mut history = self.clone_history().await;
+        history
+            .get_history()
+            .into_iter()
+            .rev()
+            .find_map(|item| match item {
+                ResponseItem::GhostSnapshot {
+                    ghost_commit,
+                    checkpoint: Some(metadata),
+                } if metadata.name == name => Some((ghost_commit, metadata)),
+                _ => None,
+            })
+    }
+
+    pub(crate) async fn list_checkpoints(self: &Arc<Self>, sub_id: String) {
+        let turn_context = self
+            .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
+            .await;
+        let mut history = self.clone_history().await;
+        let mut checkpoints: Vec<CheckpointEntry> = history
+            .get_history()
+            .into_iter()
+            .filter_map(|item| checkpoint_entry_from_item(&item))
+            .collect();
+        checkpoints.reverse();
+
+        self.send_event(
+            turn_context.as_ref(),
+            EventMsg::CheckpointList(CheckpointListEvent { checkpoints }),
+        )
+        .await;
+    }
+
+    pub(crate) async fn notify_stream_error(
+        &self,
+        turn_context: &TurnContext,
+        message: impl Into<String>,
+        codex_error: CodexErr,
+    ) {
+        let codex_error_info = CodexErrorInfo::ResponseStreamDisconnected {
+            http_status_code: codex_error.http_status_code_value(),
+        };
+        let event = EventMsg::StreamError(StreamErrorEvent {
+            message: message.into(),
             codex_error_info: Some(codex_error_info),
         });
         self.send_event(turn_context, event).await;
     }
 
+    /// Surface a scheduled retry to any UI/front‑end so the user understands
+    /// what is happening instead of staring at a seemingly frozen screen.
+    pub(crate) async fn notify_retry_scheduled(
+        &self,
+        turn_context: &TurnContext,
+        retry: ScheduledRetry,
+        codex_error: CodexErr,
+    ) {
+        let ScheduledRetry {
+            attempt,
+            max_attempts,
+            delay,
+        } = retry;
+        let reason = codex_error.to_string();
+        self.notify_stream_error(
+            turn_context,
+            format!("Reconnecting... {attempt}/{max_attempts}"),
+            codex_error,
+        )
+        .await;
+        let event = EventMsg::TurnRetryScheduled(TurnRetryScheduledEvent {
+            attempt: i64::try_from(attempt).unwrap_or(i64::MAX),
+            max_attempts: i64::try_from(max_attempts).unwrap_or(i64::MAX),
+            delay_ms: i64::try_from(delay.as_millis()).unwrap_or(i64::MAX),
+            reason,
+        });
+        self.send_event(turn_context, event).await;
+    }
+
     async fn maybe_start_ghost_snapshot(
         self: &Arc<Self>,
         turn_context: Arc<TurnContext>,
@@ -1537,6 +2307,46 @@ impl Session {
         }
     }
 
+    /// Content of an earlier `read_file` in this turn, if the file still has
+    /// the same stamp.
+    pub(crate) async fn cached_file_read(
+        &self,
+        key: &ReadCacheKey,
+        stamp: FileStamp,
+    ) -> Option<String> {
+        let mut active = self.active_turn.lock().await;
+        let at = active.as_mut()?;
+        let mut ts = at.turn_state.lock().await;
+        ts.read_cache().get(key, stamp)
+    }
+
+    pub(crate) async fn cache_file_read(
+        &self,
+        key: ReadCacheKey,
+        stamp: FileStamp,
+        content: String,
+    ) {
+        let mut active = self.active_turn.lock().await;
+        if let Some(at) = active.as_mut() {
+            let mut ts = at.turn_state.lock().await;
+            ts.read_cache().insert(key, stamp, content);
+        }
+    }
+
+    /// Drops cached reads of files a patch touched.
+    pub(crate) async fn invalidate_file_reads<'a>(
+        &self,
+        paths: impl IntoIterator<Item = &'a Path>,
+    ) {
+        let mut active = self.active_turn.lock().await;
+        if let Some(at) = active.as_mut() {
+            let mut ts = at.turn_state.lock().await;
+            for path in paths {
+                ts.read_cache().invalidate(path);
+            }
+        }
+    }
+
     pub async fn list_resources(
         &self,
         server: &str,
@@ -1609,8 +2419,24 @@ impl Session {
         }
     }
 
-    pub(crate) fn notifier(&self) -> &UserNotifier {
-        &self.services.notifier
+    pub(crate) fn notifier(&self) -> UserNotifier {
+        UserNotifier::new(self.reloadable_settings().notify)
+    }
+
+    fn reloadable_settings(&self) -> ReloadableSettings {
+        self.services
+            .reloadable
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    pub(crate) fn set_reloadable_settings(&self, settings: ReloadableSettings) {
+        *self
+            .services
+            .reloadable
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = settings;
     }
 
     pub(crate) fn user_shell(&self) -> &shell::Shell {
@@ -1618,7 +2444,23 @@ impl Session {
     }
 
     fn show_raw_agent_reasoning(&self) -> bool {
-        self.services.show_raw_agent_reasoning
+        self.reloadable_settings().show_raw_agent_reasoning
+    }
+
+    pub(crate) fn compaction_policies(&self) -> Vec<CompactionPolicy> {
+        self.reloadable_settings().compaction_policies
+    }
+
+    /// Pinned messages to carry over a compaction; none unless the
+    /// `keep-pinned-messages` policy is on.
+    pub(crate) async fn pinned_items_for_compaction(&self) -> Vec<ResponseItem> {
+        if !self
+            .compaction_policies()
+            .contains(&CompactionPolicy::KeepPinnedMessages)
+        {
+            return Vec::new();
+        }
+        self.state.lock().await.pinned_items()
     }
 
     async fn cancel_mcp_startup(&self) {
@@ -1674,6 +2516,185 @@ impl Session {
         let state = self.state.lock().await;
         state.session_configuration.session_source.clone()
     }
+
+    /// Summarize the session into `.kaioken/notes/` so the next session in the
+    /// same directory can recall it.
+    async fn write_automatic_session_notes(&self) {
+        let history = self.clone_history().await.get_history();
+        if !crate::session_summary::has_user_input(&history) {
+            return;
+        }
+        let (config, cwd) = {
+            let state = self.state.lock().await;
+            (
+                Arc::clone(&state.session_configuration.original_config_do_not_use),
+                state.session_configuration.cwd.clone(),
+            )
+        };
+        let result = tokio::time::timeout(AUTOMATIC_NOTES_TIMEOUT, async {
+            let summary = crate::session_summary::summarize_session(
+                config,
+                self.get_provider().await,
+                Arc::clone(&self.services.auth_manager),
+                &self.services.otel_event_manager,
+                self.conversation_id,
+                self.get_session_source().await,
+                &history,
+            )
+            .await?;
+            crate::session_summary::write_session_notes(
+                &cwd,
+                NotesKind::Automatic,
+                self.conversation_id,
+                &summary,
+            )
+            .await?;
+            anyhow::Ok(())
+        })
+        .await;
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => warn!("failed to write session notes: {err}"),
+            Err(_) => warn!("timed out writing session notes"),
+        }
+    }
+
+    /// Remember the workspace state at the end of a turn so the next user
+    /// turn can tell what changed in between.
+    pub(crate) async fn record_workspace_fingerprint(&self, turn_context: &TurnContext) {
+        if !self.enabled(Feature::EnvironmentDrift).await {
+            return;
+        }
+        let fingerprint = WorkspaceFingerprint::capture(&turn_context.cwd).await;
+        self.state
+            .lock()
+            .await
+            .set_workspace_fingerprint(fingerprint);
+    }
+
+    /// Describe workspace changes made since the last turn ended, telling
+    /// the client as well as the model.
+    async fn build_environment_drift_item(
+        &self,
+        turn_context: &TurnContext,
+    ) -> Option<ResponseItem> {
+        let previous = self.state.lock().await.take_workspace_fingerprint()?;
+        let current = WorkspaceFingerprint::capture(&turn_context.cwd).await?;
+        let drift = EnvironmentDrift::detect(&previous, &current).await?;
+        self.send_event(
+            turn_context,
+            EventMsg::BackgroundEvent(BackgroundEventEvent {
+                message: format!("Workspace changed since the last turn: {}", drift.summary()),
+            }),
+        )
+        .await;
+        Some(drift.to_response_item())
+    }
+
+    /// The latest contents of pinned files that changed since the model last
+    /// saw them, telling the client which ones are sent.
+    async fn build_pinned_files_item(&self, turn_context: &TurnContext) -> Option<ResponseItem> {
+        let paths = self.state.lock().await.pinned_files.paths();
+        if paths.is_empty() {
+            return None;
+        }
+        let contents = read_pinned_files(paths).await;
+        let update = self.state.lock().await.pinned_files.update(contents)?;
+        self.send_event(
+            turn_context,
+            EventMsg::BackgroundEvent(BackgroundEventEvent {
+                message: format!("Sending pinned files: {}", update.summary()),
+            }),
+        )
+        .await;
+        Some(update.to_response_item())
+    }
+
+    /// Recall memories relevant to the user's message, telling the client
+    /// which ones the turn uses.
+    async fn build_memory_context_item(
+        &self,
+        turn_context: &TurnContext,
+        items: &[UserInput],
+    ) -> Option<ResponseItem> {
+        let memory = self.memory_manager()?;
+        let query = items
+            .iter()
+            .filter_map(|item| match item {
+                UserInput::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if query.trim().is_empty() {
+            return None;
+        }
+        let injection = memory.build_context(&query, &[]).await?;
+        if injection.report.entries.is_empty() {
+            return None;
+        }
+        self.send_event(
+            turn_context,
+            EventMsg::MemoryContextInjected(injection.report.to_event()),
+        )
+        .await;
+        Some(ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: injection.text,
+            }],
+        })
+    }
+
+    /// Ask a small model what the user might send after the turn that just
+    /// finished, and offer the suggestions unless another turn has started
+    /// in the meantime.
+    pub(crate) async fn suggest_follow_ups(self: &Arc<Self>, turn_context: Arc<TurnContext>) {
+        if !self.enabled(Feature::FollowUpSuggestions).await {
+            return;
+        }
+        let history = self.clone_history().await.get_history();
+        let sess = Arc::clone(self);
+
+        // Keep the finished turn's task free; the side request can be slow.
+        tokio::spawn(async move {
+            let result = async {
+                let config = sess.clone_original_config().await?;
+                crate::follow_up::suggest_follow_ups(
+                    config,
+                    sess.get_provider().await,
+                    Arc::clone(&sess.services.auth_manager),
+                    &sess.services.otel_event_manager,
+                    sess.conversation_id,
+                    sess.get_session_source().await,
+                    &history,
+                )
+                .await
+            }
+            .await;
+            let suggestions = match result {
+                Ok(suggestions) if !suggestions.is_empty() => suggestions,
+                Ok(_) => return,
+                Err(err) => {
+                    warn!("failed to suggest follow-ups: {err}");
+                    return;
+                }
+            };
+            if sess.active_turn.lock().await.is_some() {
+                return;
+            }
+            sess.state
+                .lock()
+                .await
+                .set_follow_up_suggestions(suggestions.clone());
+            sess.send_event(
+                turn_context.as_ref(),
+                EventMsg::FollowUpSuggestions(FollowUpSuggestionsEvent { suggestions }),
+            )
+            .await;
+        });
+    }
 }
 
 async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiver<Submission>) {
@@ -1717,6 +2738,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ExecApproval { id, decision } => {
                 handlers::exec_approval(&sess, id, decision).await;
             }
+            Op::CostLimitApproval { id, decision } => {
+                handlers::cost_limit_approval(&sess, id, decision).await;
+            }
             Op::PatchApproval { id, decision } => {
                 handlers::patch_approval(&sess, id, decision).await;
             }
@@ -1730,8 +2754,20 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ListMcpTools => {
                 handlers::list_mcp_tools(&sess, &config, sub.id.clone()).await;
             }
-            Op::ListCustomPrompts => {
-                handlers::list_custom_prompts(&sess, sub.id.clone()).await;
+            Op::ListMcpUnresolvedSecrets => {
+                handlers::list_mcp_unresolved_secrets(&sess, &config, sub.id.clone());
+            }
+            Op::RestartMcpServer { name } => {
+                handlers::restart_mcp_server(&sess, sub.id.clone(), name);
+            }
+            Op::ReloadConfig => {
+                handlers::reload_config(&sess, sub.id.clone()).await;
+            }
+            Op::ReclaimSessionLock => {
+                handlers::reclaim_session_lock(&sess, sub.id.clone()).await;
+            }
+            Op::ListCustomPrompts => {
+                handlers::list_custom_prompts(&sess, sub.id.clone()).await;
             }
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
@@ -1745,6 +2781,43 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ListCheckpoints => {
                 handlers::list_checkpoints(&sess, sub.id.clone()).await;
             }
+            Op::DiffCheckpoints { from, to } => {
+                handlers::diff_checkpoints(&sess, sub.id.clone(), from, to).await;
+            }
+            Op::RevertFileChange { path, turn_id } => {
+                handlers::revert_file_change(&sess, sub.id.clone(), path, turn_id).await;
+            }
+            Op::RevertTurn { turn_id } => {
+                handlers::revert_turn(&sess, sub.id.clone(), turn_id).await;
+            }
+            Op::StartPlan { request, detail } => {
+                handlers::start_plan(
+                    &sess,
+                    sub.id.clone(),
+                    request,
+                    detail,
+                    &mut previous_context,
+                )
+                .await;
+            }
+            Op::RevisePlan { feedback } => {
+                handlers::revise_plan(&sess, sub.id.clone(), feedback, &mut previous_context).await;
+            }
+            Op::ApprovePlan => {
+                handlers::approve_plan(&sess, sub.id.clone(), &mut previous_context).await;
+            }
+            Op::CancelPlan => {
+                handlers::cancel_plan(&sess).await;
+            }
+            Op::CancelSubagentTask {
+                call_id,
+                agent_index,
+            } => {
+                handlers::cancel_subagent_task(&sess, sub.id.clone(), call_id, agent_index).await;
+            }
+            Op::CancelToolCall { call_id } => {
+                handlers::cancel_tool_call(&sess, sub.id.clone(), call_id).await;
+            }
             Op::Compact => {
                 handlers::compact(&sess, sub.id.clone()).await;
             }
@@ -1769,6 +2842,9 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                     break;
                 }
             }
+            Op::AppendHistory { items } => {
+                handlers::append_history(&sess, sub.id.clone(), items).await;
+            }
             Op::Review { review_request } => {
                 handlers::review(&sess, &config, sub.id.clone(), review_request).await;
             }
@@ -1778,6 +2854,69 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::ListMemories => {
                 handlers::list_memories(&sess, sub.id.clone()).await;
             }
+            Op::ConsolidateMemories => {
+                handlers::consolidate_memories(&sess, sub.id.clone()).await;
+            }
+            Op::SummarizeSession { save } => {
+                handlers::summarize_session(&sess, sub.id.clone(), save).await;
+            }
+            Op::ForkConversation { at_item } => {
+                handlers::fork_conversation(&sess, sub.id.clone(), at_item).await;
+            }
+            Op::MergeConversation {
+                source_conversation_id,
+            } => {
+                handlers::merge_conversation(&sess, sub.id.clone(), source_conversation_id).await;
+            }
+            Op::RegenerateLast { model, effort } => {
+                handlers::regenerate_last(
+                    &sess,
+                    sub.id.clone(),
+                    model,
+                    effort,
+                    &mut previous_context,
+                )
+                .await;
+            }
+            Op::EditAndResend { at_item, items } => {
+                handlers::edit_and_resend(
+                    &sess,
+                    sub.id.clone(),
+                    at_item,
+                    items,
+                    &mut previous_context,
+                )
+                .await;
+            }
+            Op::GetMessageVariants { message_id } => {
+                handlers::get_message_variants(&sess, sub.id.clone(), message_id).await;
+            }
+            Op::PinHistoryItem { at_item, pinned } => {
+                handlers::pin_history_item(&sess, sub.id.clone(), at_item, pinned).await;
+            }
+            Op::PinFile { path, pinned } => {
+                handlers::pin_file(&sess, sub.id.clone(), path, pinned).await;
+            }
+            Op::SetSessionEnv { vars, persist } => {
+                handlers::set_session_env(&sess, sub.id.clone(), vars, persist).await;
+            }
+            Op::SetWebhook {
+                url,
+                events,
+                secret,
+            } => {
+                handlers::set_webhook(&sess, url, events, secret).await;
+            }
+            Op::GetStorageReport => {
+                handlers::storage_report(&sess, sub.id.clone()).await;
+            }
+            Op::CleanupStorage { options } => {
+                handlers::cleanup_storage(&sess, sub.id.clone(), options).await;
+            }
+            Op::AcceptSuggestion { index } => {
+                handlers::accept_suggestion(&sess, sub.id.clone(), index, &mut previous_context)
+                    .await;
+            }
             _ => {} // Ignore unknown ops; enum is non_exhaustive to allow extensions.
         }
     }
@@ -1785,6 +2924,33 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
 }
 
 /// Operation handlers
+/// Reports `EventMsg::SessionLockLost` each time the rollout writer notices
+/// that another process took the session over. Ends with the writer.
+fn spawn_rollout_lock_watch(
+    session: Weak<Session>,
+    mut lock_held: watch::Receiver<bool>,
+    rollout_path: PathBuf,
+) {
+    tokio::spawn(async move {
+        while lock_held.changed().await.is_ok() {
+            if *lock_held.borrow_and_update() {
+                continue;
+            }
+            let Some(session) = session.upgrade() else {
+                return;
+            };
+            session
+                .send_event_raw(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg: EventMsg::SessionLockLost(SessionLockLostEvent {
+                        rollout_path: rollout_path.clone(),
+                    }),
+                })
+                .await;
+        }
+    });
+}
+
 mod handlers {
     use crate::codex::Session;
     use crate::codex::SessionSettingsUpdate;
@@ -1792,26 +2958,59 @@ mod handlers {
 
     use crate::codex::spawn_review_thread;
     use crate::config::Config;
+    use crate::config::types::WebhookConfig;
+    use crate::conversation_manager::truncate_after_nth_agent_message;
     use crate::mcp::auth::compute_auth_statuses;
+    use crate::mcp::secrets::SecretResolver;
+    use crate::mcp_connection_manager::restart_server;
+    use crate::parse_turn_item;
+    use crate::pinned_files::MAX_PINNED_FILE_BYTES;
+    use crate::rollout::RolloutRecorder;
+    use crate::rollout::RolloutRecorderParams;
     use crate::tasks::CompactTask;
     use crate::tasks::RegularTask;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandTask;
+    use codex_protocol::ConversationId;
+    use codex_protocol::config_types::PlanDetailPreference;
+    use codex_protocol::config_types::ReasoningEffort as ReasoningEffortConfig;
+    use codex_protocol::config_types::WebhookEvent;
     use codex_protocol::custom_prompts::CustomPrompt;
+    use codex_protocol::items::AgentMessageContent;
+    use codex_protocol::items::TurnItem;
+    use codex_protocol::models::ResponseItem;
+    use codex_protocol::protocol::BackgroundEventEvent;
     use codex_protocol::protocol::CodexErrorInfo;
+    use codex_protocol::protocol::CompactedItem;
+    use codex_protocol::protocol::ConversationForkedEvent;
+    use codex_protocol::protocol::ConversationMergeProgressEvent;
+    use codex_protocol::protocol::ConversationMergeStage;
+    use codex_protocol::protocol::ConversationMergedEvent;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
+    use codex_protocol::protocol::McpServerDownEvent;
+    use codex_protocol::protocol::McpServerRestartedEvent;
+    use codex_protocol::protocol::McpUnresolvedSecretsResponseEvent;
+    use codex_protocol::protocol::MessageVariant;
+    use codex_protocol::protocol::MessageVariantsEvent;
     use codex_protocol::protocol::Op;
+    use codex_protocol::protocol::ResponseRegeneratedEvent;
     use codex_protocol::protocol::ReviewDecision;
     use codex_protocol::protocol::ReviewRequest;
+    use codex_protocol::protocol::RolloutItem;
+    use codex_protocol::protocol::StorageCategory;
     use codex_protocol::protocol::TurnAbortReason;
+    use codex_protocol::protocol::UserMessageEditedEvent;
+    use codex_protocol::protocol::WarningEvent;
 
     use codex_protocol::user_input::UserInput;
     use codex_rmcp_client::ElicitationAction;
     use codex_rmcp_client::ElicitationResponse;
     use mcp_types::RequestId;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
     use std::sync::Arc;
     use tracing::info;
     use tracing::warn;
@@ -1870,6 +3069,27 @@ mod handlers {
                 sess.record_conversation_items(&current_context, std::slice::from_ref(&env_item))
                     .await;
             }
+            if let Some(drift_item) = sess.build_environment_drift_item(&current_context).await {
+                sess.record_conversation_items(&current_context, std::slice::from_ref(&drift_item))
+                    .await;
+            }
+            if let Some(memory_item) = sess
+                .build_memory_context_item(&current_context, &items)
+                .await
+            {
+                sess.record_conversation_items(
+                    &current_context,
+                    std::slice::from_ref(&memory_item),
+                )
+                .await;
+            }
+            if let Some(pinned_item) = sess.build_pinned_files_item(&current_context).await {
+                sess.record_conversation_items(
+                    &current_context,
+                    std::slice::from_ref(&pinned_item),
+                )
+                .await;
+            }
 
             sess.spawn_task(Arc::clone(&current_context), items, RegularTask)
                 .await;
@@ -1877,6 +3097,82 @@ mod handlers {
         }
     }
 
+    /// Send the `index`-th follow-up suggestion as a user message.
+    pub async fn accept_suggestion(
+        sess: &Arc<Session>,
+        sub_id: String,
+        index: usize,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        let suggestion = sess.state.lock().await.take_follow_up_suggestion(index);
+        let Some(text) = suggestion else {
+            let event = Event {
+                id: sub_id,
+                msg: EventMsg::Warning(WarningEvent {
+                    message: format!("There is no follow-up suggestion #{}.", index + 1),
+                }),
+            };
+            sess.send_event_raw(event).await;
+            return;
+        };
+        let op = Op::UserInput {
+            items: vec![UserInput::Text { text }],
+        };
+        user_input_or_turn(sess, sub_id, op, previous_context).await;
+    }
+
+    pub async fn start_plan(
+        sess: &Arc<Session>,
+        sub_id: String,
+        request: String,
+        detail: Option<PlanDetailPreference>,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        let prompt = sess
+            .begin_plan_workflow(request, detail, previous_context.as_deref())
+            .await;
+        submit_plan_prompt(sess, sub_id, prompt, previous_context).await;
+    }
+
+    pub async fn revise_plan(
+        sess: &Arc<Session>,
+        sub_id: String,
+        feedback: String,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        match sess.revise_plan(feedback).await {
+            Ok(prompt) => submit_plan_prompt(sess, sub_id, prompt, previous_context).await,
+            Err(err) => sess.send_plan_workflow_error(sub_id, err).await,
+        }
+    }
+
+    pub async fn approve_plan(
+        sess: &Arc<Session>,
+        sub_id: String,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        match sess.approve_plan().await {
+            Ok(prompt) => submit_plan_prompt(sess, sub_id, prompt, previous_context).await,
+            Err(err) => sess.send_plan_workflow_error(sub_id, err).await,
+        }
+    }
+
+    pub async fn cancel_plan(sess: &Arc<Session>) {
+        sess.cancel_plan().await;
+    }
+
+    async fn submit_plan_prompt(
+        sess: &Arc<Session>,
+        sub_id: String,
+        prompt: String,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        let op = Op::UserInput {
+            items: vec![UserInput::Text { text: prompt }],
+        };
+        user_input_or_turn(sess, sub_id, op, previous_context).await;
+    }
+
     pub async fn run_user_shell_command(
         sess: &Arc<Session>,
         sub_id: String,
@@ -1930,6 +3226,10 @@ mod handlers {
         }
     }
 
+    pub async fn cost_limit_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
+        sess.notify_approval(&id, decision).await;
+    }
+
     pub async fn patch_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
         match decision {
             ReviewDecision::Abort => {
@@ -2016,6 +3316,80 @@ mod handlers {
         sess.send_event_raw(event).await;
     }
 
+    /// Looks the secrets up in the background since providers such as the
+    /// 1Password CLI may wait for the user to unlock them.
+    pub fn list_mcp_unresolved_secrets(sess: &Arc<Session>, config: &Arc<Config>, sub_id: String) {
+        let sess = Arc::clone(sess);
+        let config = Arc::clone(config);
+        tokio::spawn(async move {
+            let servers = SecretResolver::from_config(&config.secrets)
+                .unresolved_secrets(&config.mcp_servers)
+                .await;
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::McpUnresolvedSecretsResponse(McpUnresolvedSecretsResponseEvent {
+                    servers,
+                }),
+            })
+            .await;
+        });
+    }
+
+    /// Restarts the server in the background so the submission loop keeps
+    /// handling ops while it starts.
+    pub fn restart_mcp_server(sess: &Arc<Session>, sub_id: String, name: String) {
+        let sess = Arc::clone(sess);
+        tokio::spawn(async move {
+            let msg = match restart_server(&sess.services.mcp_connection_manager, &name).await {
+                Ok(()) => EventMsg::McpServerRestarted(McpServerRestartedEvent { server: name }),
+                Err(err) => EventMsg::McpServerDown(McpServerDownEvent {
+                    server: name,
+                    reason: format!("{err:#}"),
+                    retrying: false,
+                }),
+            };
+            sess.send_event_raw(Event { id: sub_id, msg }).await;
+        });
+    }
+
+    pub async fn reclaim_session_lock(sess: &Session, sub_id: String) {
+        let recorder = {
+            let guard = sess.services.rollout.lock().await;
+            guard.clone()
+        };
+        let msg = match recorder {
+            Some(recorder) => match recorder.reclaim_lock().await {
+                Ok(()) => EventMsg::BackgroundEvent(BackgroundEventEvent {
+                    message: "Took this session back; recording resumed.".to_string(),
+                }),
+                Err(err) => EventMsg::Error(ErrorEvent {
+                    message: format!("failed to take this session back: {err}"),
+                    codex_error_info: Some(CodexErrorInfo::Other),
+                }),
+            },
+            None => EventMsg::Error(ErrorEvent {
+                message: "this session is not being recorded".to_string(),
+                codex_error_info: Some(CodexErrorInfo::Other),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn reload_config(sess: &Session, sub_id: String) {
+        let msg = match crate::config::reload::reload_session_config(sess).await {
+            Ok(Some(event)) => EventMsg::ConfigChanged(event),
+            Ok(None) => EventMsg::Error(ErrorEvent {
+                message: "config reload is not available for this session".to_string(),
+                codex_error_info: Some(CodexErrorInfo::Other),
+            }),
+            Err(err) => EventMsg::Error(ErrorEvent {
+                message: format!("failed to reload config.toml: {err}"),
+                codex_error_info: Some(CodexErrorInfo::Other),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
     pub async fn list_custom_prompts(sess: &Session, sub_id: String) {
         let custom_prompts: Vec<CustomPrompt> =
             if let Some(dir) = crate::custom_prompts::default_prompts_dir() {
@@ -2053,137 +3427,913 @@ mod handlers {
         sess.list_checkpoints(sub_id).await;
     }
 
-    pub async fn compact(sess: &Arc<Session>, sub_id: String) {
-        let turn_context = sess
-            .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
-            .await;
+    pub async fn diff_checkpoints(sess: &Arc<Session>, sub_id: String, from: String, to: String) {
+        sess.diff_checkpoints(sub_id, from, to).await;
+    }
+
+    pub async fn revert_file_change(
+        sess: &Arc<Session>,
+        sub_id: String,
+        path: PathBuf,
+        turn_id: Option<String>,
+    ) {
+        sess.revert_file_change(sub_id, path, turn_id).await;
+    }
+
+    pub async fn revert_turn(sess: &Arc<Session>, sub_id: String, turn_id: String) {
+        sess.revert_turn(sub_id, turn_id).await;
+    }
+
+    pub async fn cancel_subagent_task(
+        sess: &Arc<Session>,
+        sub_id: String,
+        call_id: String,
+        agent_index: i64,
+    ) {
+        sess.cancel_subagent_task(sub_id, call_id, agent_index)
+            .await;
+    }
+
+    pub async fn cancel_tool_call(sess: &Arc<Session>, sub_id: String, call_id: String) {
+        sess.cancel_tool_call(sub_id, call_id).await;
+    }
+
+    pub async fn compact(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess
+            .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
+            .await;
+
+        sess.spawn_task(
+            Arc::clone(&turn_context),
+            vec![UserInput::Text {
+                text: turn_context.compact_prompt().to_string(),
+            }],
+            CompactTask,
+        )
+        .await;
+    }
+
+    pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
+        sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
+        sess.services
+            .unified_exec_manager
+            .terminate_all_sessions()
+            .await;
+        info!("Shutting down Codex instance");
+
+        if sess.enabled(Feature::SessionNotes).await {
+            sess.write_automatic_session_notes().await;
+        }
+
+        // Gracefully flush and shutdown rollout recorder on session end so tests
+        // that inspect the rollout file do not race with the background writer.
+        let recorder_opt = {
+            let mut guard = sess.services.rollout.lock().await;
+            guard.take()
+        };
+        if let Some(rec) = recorder_opt
+            && let Err(e) = rec.shutdown().await
+        {
+            warn!("failed to shutdown rollout recorder: {e}");
+            let event = Event {
+                id: sub_id.clone(),
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "Failed to shutdown rollout recorder".to_string(),
+                    codex_error_info: Some(CodexErrorInfo::Other),
+                }),
+            };
+            sess.send_event_raw(event).await;
+        }
+
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::ShutdownComplete,
+        };
+        sess.send_event_raw(event).await;
+        true
+    }
+
+    pub async fn review(
+        sess: &Arc<Session>,
+        config: &Arc<Config>,
+        sub_id: String,
+        review_request: ReviewRequest,
+    ) {
+        let turn_context = sess
+            .new_turn_with_sub_id(sub_id.clone(), SessionSettingsUpdate::default())
+            .await;
+        spawn_review_thread(
+            Arc::clone(sess),
+            Arc::clone(config),
+            turn_context.clone(),
+            sub_id,
+            review_request,
+        )
+        .await;
+    }
+
+    pub async fn append_history(sess: &Arc<Session>, sub_id: String, items: Vec<ResponseItem>) {
+        let turn_context = sess
+            .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
+            .await;
+        sess.record_conversation_items(&turn_context, &items).await;
+        for item in &items {
+            if let Some(turn_item) = parse_turn_item(item) {
+                sess.emit_turn_item_completed(&turn_context, turn_item)
+                    .await;
+            }
+        }
+    }
+
+    pub async fn remember(sess: &Arc<Session>, sub_id: String, text: String) {
+        let (success, memory_id, error) = if let Some(mm) = sess.memory_manager() {
+            match mm.remember(&text).await {
+                Ok(memory) => (true, Some(memory.id), None),
+                Err(e) => (false, None, Some(e.to_string())),
+            }
+        } else if sess.memory_starting() {
+            (
+                false,
+                None,
+                Some("Memory system is still starting; try again shortly".to_string()),
+            )
+        } else {
+            (false, None, Some("Memory system not available".to_string()))
+        };
+
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::MemoryRememberResponse(
+                crate::protocol::MemoryRememberResponseEvent {
+                    success,
+                    memory_id,
+                    error,
+                },
+            ),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn list_memories(sess: &Arc<Session>, sub_id: String) {
+        let (total_count, counts_by_type, recent_memories, storage_path) =
+            if let Some(mm) = sess.memory_manager() {
+                let stats = mm.stats().await.unwrap_or_default();
+                let summary = mm.summary().await;
+
+                let recent: Vec<crate::protocol::MemoryEntry> = summary
+                    .recent
+                    .into_iter()
+                    .map(|m| crate::protocol::MemoryEntry {
+                        id: m.id,
+                        memory_type: format!("{:?}", m.memory_type),
+                        content: m.content,
+                        importance: m.importance,
+                        use_count: m.use_count,
+                    })
+                    .collect();
+
+                (
+                    stats.total_count,
+                    stats.counts_by_type,
+                    recent,
+                    stats.storage_path,
+                )
+            } else {
+                (0, std::collections::HashMap::new(), Vec::new(), None)
+            };
+
+        let event = Event {
+            id: sub_id,
+            msg: EventMsg::MemoryListResponse(crate::protocol::MemoryListResponseEvent {
+                total_count,
+                counts_by_type,
+                recent_memories,
+                storage_path,
+            }),
+        };
+        sess.send_event_raw(event).await;
+    }
+
+    pub async fn set_webhook(
+        sess: &Session,
+        url: String,
+        events: Vec<WebhookEvent>,
+        secret: Option<String>,
+    ) {
+        sess.set_webhook(WebhookConfig {
+            url,
+            events,
+            secret_env_var: None,
+            secret,
+        })
+        .await;
+    }
+
+    pub async fn set_session_env(
+        sess: &Session,
+        sub_id: String,
+        vars: HashMap<String, String>,
+        persist: bool,
+    ) {
+        let mut names: Vec<String> = vars.keys().cloned().collect();
+        names.sort();
+        let (cwd, config) = sess.set_session_env(vars.clone()).await;
+
+        let (persisted_project, error) = if persist {
+            match crate::config::edit::ConfigEditsBuilder::new(&config.codex_home)
+                .set_project_env(cwd.clone(), &vars)
+                .apply()
+                .await
+            {
+                Ok(()) => (Some(cwd), None),
+                Err(err) => (None, Some(format!("failed to save env profile: {err}"))),
+            }
+        } else {
+            (None, None)
+        };
+
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::SessionEnvUpdated(crate::protocol::SessionEnvUpdatedEvent {
+                names,
+                persisted_project,
+                error,
+            }),
+        })
+        .await;
+    }
+
+    pub async fn storage_report(sess: &Arc<Session>, sub_id: String) {
+        let (cwd, config) = sess.cwd_and_config().await;
+        let project_root = sess
+            .memory_manager()
+            .map_or(cwd, |memory| memory.project_root().to_path_buf());
+        let sess = Arc::clone(sess);
+
+        // Walking the sessions directory can take a while; keep the
+        // submission loop free.
+        tokio::spawn(async move {
+            let codex_home = config.codex_home.clone();
+            let report = tokio::task::spawn_blocking(move || {
+                crate::storage::storage_report(&codex_home, &project_root)
+            })
+            .await
+            .map_err(std::io::Error::other)
+            .and_then(|report| report);
+            let msg = match report {
+                Ok(report) => EventMsg::StorageReport(report),
+                Err(err) => EventMsg::Error(ErrorEvent {
+                    message: format!("Failed to measure storage: {err}"),
+                    codex_error_info: Some(CodexErrorInfo::Other),
+                }),
+            };
+            sess.send_event_raw(Event { id: sub_id, msg }).await;
+        });
+    }
+
+    pub async fn cleanup_storage(
+        sess: &Arc<Session>,
+        sub_id: String,
+        options: crate::protocol::StorageCleanupOptions,
+    ) {
+        let (cwd, config) = sess.cwd_and_config().await;
+        let memory = sess.memory_manager().cloned();
+        let sess = Arc::clone(sess);
+
+        tokio::spawn(async move {
+            let progress = |category, message: &str| {
+                let event = Event {
+                    id: sub_id.clone(),
+                    msg: EventMsg::StorageCleanupProgress(
+                        crate::protocol::StorageCleanupProgressEvent {
+                            category,
+                            message: message.to_string(),
+                        },
+                    ),
+                };
+                let sess = Arc::clone(&sess);
+                async move { sess.send_event_raw(event).await }
+            };
+
+            let mut freed = Vec::new();
+            let mut errors = Vec::new();
+            let steps = crate::storage::cleanup_steps(
+                &config.codex_home,
+                &cwd,
+                sess.conversation_id,
+                &options,
+            );
+            for step in steps {
+                progress(step.category, &step.description).await;
+                let description = step.description.clone();
+                match tokio::task::spawn_blocking(move || step.run()).await {
+                    Ok(Ok(entry)) => freed.push(entry),
+                    Ok(Err(err)) => errors.push(format!("{description} failed: {err}")),
+                    Err(err) => errors.push(format!("{description} failed: {err}")),
+                }
+            }
+            if options.vacuum_memory {
+                let description = "Compacting the memory database";
+                progress(StorageCategory::Memory, description).await;
+                match crate::storage::vacuum_memory(memory.as_deref(), options.dry_run).await {
+                    Ok(entry) => freed.push(entry),
+                    Err(err) => errors.push(format!("{description} failed: {err}")),
+                }
+            }
+
+            sess.send_event_raw(Event {
+                id: sub_id.clone(),
+                msg: EventMsg::StorageCleanupCompleted(
+                    crate::protocol::StorageCleanupCompletedEvent {
+                        dry_run: options.dry_run,
+                        freed,
+                        errors,
+                    },
+                ),
+            })
+            .await;
+        });
+    }
+
+    pub async fn consolidate_memories(sess: &Arc<Session>, sub_id: String) {
+        let Some(memory) = sess.memory_manager().cloned() else {
+            let error = if sess.memory_starting() {
+                "Memory system is still starting; try again shortly"
+            } else {
+                "Memory system not available"
+            };
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::MemoriesConsolidated(crate::protocol::MemoriesConsolidatedEvent {
+                    consolidated: Vec::new(),
+                    error: Some(error.to_string()),
+                }),
+            })
+            .await;
+            return;
+        };
+        let sess = Arc::clone(sess);
+
+        // Each cluster is a model call; keep the submission loop free.
+        tokio::spawn(async move {
+            let (consolidated, error) = match sess.consolidate_memories(&memory).await {
+                Ok(result) => (
+                    result
+                        .consolidated
+                        .into_iter()
+                        .map(|c| crate::protocol::ConsolidatedMemoryEntry {
+                            id: c.memory.id,
+                            memory_type: c.memory.memory_type.as_str().to_string(),
+                            content: c.memory.content,
+                            merged_from: c.merged_from,
+                        })
+                        .collect(),
+                    None,
+                ),
+                Err(e) => (Vec::new(), Some(e.to_string())),
+            };
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::MemoriesConsolidated(crate::protocol::MemoriesConsolidatedEvent {
+                    consolidated,
+                    error,
+                }),
+            })
+            .await;
+        });
+    }
+
+    pub async fn summarize_session(sess: &Arc<Session>, sub_id: String, save: bool) {
+        let turn_context = sess
+            .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
+            .await;
+        let history = sess.clone_history().await.get_history();
+        let sess = Arc::clone(sess);
+
+        // The side request can take a while; keep the submission loop free.
+        tokio::spawn(async move {
+            let result = async {
+                let config = sess.clone_original_config().await?;
+                let mut summary = crate::session_summary::summarize_session(
+                    config,
+                    sess.get_provider().await,
+                    Arc::clone(&sess.services.auth_manager),
+                    &sess.services.otel_event_manager,
+                    sess.conversation_id,
+                    sess.get_session_source().await,
+                    &history,
+                )
+                .await?;
+                if save {
+                    summary.saved_path = Some(
+                        crate::session_summary::write_session_notes(
+                            &turn_context.cwd,
+                            NotesKind::Manual,
+                            sess.conversation_id,
+                            &summary,
+                        )
+                        .await?,
+                    );
+                }
+                anyhow::Ok(summary)
+            }
+            .await;
+
+            let msg = match result {
+                Ok(summary) => EventMsg::SessionSummary(summary),
+                Err(err) => EventMsg::Warning(WarningEvent {
+                    message: format!("Failed to summarize the session: {err}"),
+                }),
+            };
+            sess.send_event(turn_context.as_ref(), msg).await;
+        });
+    }
+
+    /// Write the history up to the `at_item`-th assistant message into a new
+    /// rollout file, leaving this conversation as it is.
+    pub async fn fork_conversation(sess: &Arc<Session>, sub_id: String, at_item: usize) {
+        sess.flush_rollout().await;
+        let rollout_path = {
+            let guard = sess.services.rollout.lock().await;
+            guard.as_ref().map(|rec| rec.rollout_path.clone())
+        };
+
+        let result = async {
+            let Some(path) = rollout_path else {
+                anyhow::bail!("this session is not being recorded");
+            };
+            let config = sess.clone_original_config().await?;
+            let items = RolloutRecorder::get_rollout_history(&path)
+                .await?
+                .get_rollout_items();
+            let instructions = items.iter().find_map(|item| match item {
+                RolloutItem::SessionMeta(meta_line) => Some(meta_line.meta.instructions.clone()),
+                _ => None,
+            });
+            let mut items = truncate_after_nth_agent_message(items, at_item)
+                .ok_or_else(|| anyhow::anyhow!("no assistant message #{}", at_item + 1))?;
+            // The new recorder writes its own session metadata.
+            items.retain(|item| !matches!(item, RolloutItem::SessionMeta(_)));
+
+            let conversation_id = ConversationId::new();
+            let recorder = RolloutRecorder::new(
+                &config,
+                RolloutRecorderParams::new(
+                    conversation_id,
+                    instructions.flatten(),
+                    sess.get_session_source().await,
+                ),
+            )
+            .await?;
+            recorder.record_items(&items).await?;
+            recorder.shutdown().await?;
+            anyhow::Ok(ConversationForkedEvent {
+                conversation_id,
+                rollout_path: recorder.rollout_path.clone(),
+                at_item,
+            })
+        }
+        .await;
+
+        let msg = match result {
+            Ok(event) => EventMsg::ConversationForked(event),
+            Err(err) => EventMsg::Warning(WarningEvent {
+                message: format!("Failed to fork the conversation: {err}"),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    /// Summarize the conversation `source` and record the summary in this
+    /// conversation's history as merged context. `source` is left unchanged.
+    pub async fn merge_conversation(sess: &Arc<Session>, sub_id: String, source: ConversationId) {
+        let turn_context = sess
+            .new_turn_with_sub_id(sub_id, SessionSettingsUpdate::default())
+            .await;
+        let sess = Arc::clone(sess);
+
+        // The summary is a model call; keep the submission loop free.
+        tokio::spawn(async move {
+            let progress = |stage| {
+                let msg = EventMsg::ConversationMergeProgress(ConversationMergeProgressEvent {
+                    source_conversation_id: source,
+                    stage,
+                });
+                let sess = Arc::clone(&sess);
+                let turn_context = Arc::clone(&turn_context);
+                async move { sess.send_event(turn_context.as_ref(), msg).await }
+            };
+
+            let result = async {
+                if source == sess.conversation_id {
+                    anyhow::bail!("a conversation cannot be merged into itself");
+                }
+                progress(ConversationMergeStage::LoadingSource).await;
+                let config = sess.clone_original_config().await?;
+                let path = crate::find_conversation_path_by_id_str(
+                    &config.codex_home,
+                    &source.to_string(),
+                )
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("no rollout found for conversation {source}"))?;
+                let history: Vec<ResponseItem> = RolloutRecorder::get_rollout_history(&path)
+                    .await?
+                    .get_rollout_items()
+                    .into_iter()
+                    .filter_map(|item| match item {
+                        RolloutItem::ResponseItem(item) => Some(item),
+                        _ => None,
+                    })
+                    .collect();
+
+                progress(ConversationMergeStage::Summarizing).await;
+                let summary = crate::session_summary::summarize_session(
+                    config,
+                    sess.get_provider().await,
+                    Arc::clone(&sess.services.auth_manager),
+                    &sess.services.otel_event_manager,
+                    sess.conversation_id,
+                    sess.get_session_source().await,
+                    &history,
+                )
+                .await?;
+
+                progress(ConversationMergeStage::Recording).await;
+                let item = crate::session_summary::merged_context_item(source, &summary);
+                sess.record_conversation_items(&turn_context, &[item]).await;
+                anyhow::Ok(summary)
+            }
+            .await;
+
+            let msg = match result {
+                Ok(summary) => EventMsg::ConversationMerged(ConversationMergedEvent {
+                    source_conversation_id: source,
+                    summary,
+                }),
+                Err(err) => EventMsg::Warning(WarningEvent {
+                    message: format!("Failed to merge conversation {source}: {err}"),
+                }),
+            };
+            sess.send_event(turn_context.as_ref(), msg).await;
+        });
+    }
+
+    pub async fn regenerate_last(
+        sess: &Arc<Session>,
+        sub_id: String,
+        model: Option<String>,
+        effort: Option<ReasoningEffortConfig>,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        let send_warning = |message: &str| {
+            let event = Event {
+                id: sub_id.clone(),
+                msg: EventMsg::Warning(WarningEvent {
+                    message: message.to_string(),
+                }),
+            };
+            async move { sess.send_event_raw(event).await }
+        };
+        if sess.active_turn.lock().await.is_some() {
+            send_warning("Wait for the current turn to finish before regenerating.").await;
+            return;
+        }
+
+        let history = sess.clone_history().await.get_history();
+        let last_user_message =
+            history
+                .iter()
+                .enumerate()
+                .rev()
+                .find_map(|(idx, item)| match parse_turn_item(item) {
+                    Some(TurnItem::UserMessage(message)) => Some((idx, message.content)),
+                    _ => None,
+                });
+        let Some((idx, items)) = last_user_message else {
+            send_warning("There is no message to regenerate yet.").await;
+            return;
+        };
+        let answers: Vec<(Option<String>, String)> = history[idx + 1..]
+            .iter()
+            .filter_map(|item| match item {
+                ResponseItem::Message { role, id, .. } if role == "assistant" => {
+                    match parse_turn_item(item) {
+                        Some(TurnItem::AgentMessage(message)) => Some((
+                            id.clone(),
+                            message
+                                .content
+                                .into_iter()
+                                .map(|AgentMessageContent::Text { text }| text)
+                                .collect::<String>(),
+                        )),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            })
+            .collect();
+        let Some(message_id) = answers.last().map(|(id, _)| id.clone()) else {
+            send_warning("The last message has no answer to regenerate.").await;
+            return;
+        };
+
+        let turn_context = sess
+            .new_one_off_turn(
+                sub_id.clone(),
+                SessionSettingsUpdate {
+                    model,
+                    reasoning_effort: effort.map(Some),
+                    ..Default::default()
+                },
+            )
+            .await;
+        let (previous_model, previous_effort) = match previous_context.as_ref() {
+            Some(previous) => (
+                previous.client.get_model(),
+                previous.client.get_reasoning_effort(),
+            ),
+            None => (
+                turn_context.client.get_model(),
+                turn_context.client.get_reasoning_effort(),
+            ),
+        };
+        let removed_messages = answers.len();
+        let previous_attempt = MessageVariant {
+            message_id,
+            text: answers
+                .into_iter()
+                .map(|(_, text)| text)
+                .collect::<Vec<_>>()
+                .join("\n\n"),
+            model: previous_model,
+            effort: previous_effort,
+        };
+
+        // Rewind to just before the user message; the new turn records it again.
+        let rewound = history[..idx].to_vec();
+        let user_message = rewound
+            .iter()
+            .filter(|item| matches!(parse_turn_item(item), Some(TurnItem::UserMessage(_))))
+            .count();
+        rewind_history(sess, rewound).await;
+        {
+            let mut state = sess.state.lock().await;
+            state.push_previous_attempt(user_message, previous_attempt.clone());
+        }
 
-        sess.spawn_task(
-            Arc::clone(&turn_context),
-            vec![UserInput::Text {
-                text: turn_context.compact_prompt().to_string(),
-            }],
-            CompactTask,
+        sess.send_event(
+            turn_context.as_ref(),
+            EventMsg::ResponseRegenerated(ResponseRegeneratedEvent {
+                previous_attempt,
+                removed_messages,
+                model: turn_context.client.get_model(),
+                effort: turn_context.client.get_reasoning_effort(),
+            }),
         )
         .await;
-    }
-
-    pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
-        sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
-        sess.services
-            .unified_exec_manager
-            .terminate_all_sessions()
+        sess.spawn_task(Arc::clone(&turn_context), items, RegularTask)
             .await;
-        info!("Shutting down Codex instance");
+        *previous_context = Some(turn_context);
+    }
 
-        // Gracefully flush and shutdown rollout recorder on session end so tests
-        // that inspect the rollout file do not race with the background writer.
-        let recorder_opt = {
-            let mut guard = sess.services.rollout.lock().await;
-            guard.take()
-        };
-        if let Some(rec) = recorder_opt
-            && let Err(e) = rec.shutdown().await
-        {
-            warn!("failed to shutdown rollout recorder: {e}");
+    /// Discard the `at_item`-th user message and everything after it, then
+    /// send `items` in its place.
+    pub async fn edit_and_resend(
+        sess: &Arc<Session>,
+        sub_id: String,
+        at_item: usize,
+        items: Vec<UserInput>,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        let send_warning = |message: String| {
             let event = Event {
                 id: sub_id.clone(),
-                msg: EventMsg::Error(ErrorEvent {
-                    message: "Failed to shutdown rollout recorder".to_string(),
-                    codex_error_info: Some(CodexErrorInfo::Other),
-                }),
+                msg: EventMsg::Warning(WarningEvent { message }),
             };
-            sess.send_event_raw(event).await;
+            async move { sess.send_event_raw(event).await }
+        };
+        if sess.active_turn.lock().await.is_some() {
+            send_warning("Wait for the current turn to finish before editing a message.".into())
+                .await;
+            return;
+        }
+        if items.is_empty() {
+            send_warning("The edited message is empty.".into()).await;
+            return;
         }
 
-        let event = Event {
-            id: sub_id,
-            msg: EventMsg::ShutdownComplete,
+        let history = sess.clone_history().await.get_history();
+        let user_messages: Vec<usize> = history
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| matches!(parse_turn_item(item), Some(TurnItem::UserMessage(_))))
+            .map(|(idx, _)| idx)
+            .collect();
+        let Some(&idx) = user_messages.get(at_item) else {
+            send_warning(format!("There is no user message #{}.", at_item + 1)).await;
+            return;
         };
-        sess.send_event_raw(event).await;
-        true
-    }
+        let removed_messages = history[idx + 1..]
+            .iter()
+            .filter(|item| matches!(parse_turn_item(item), Some(TurnItem::AgentMessage(_))))
+            .count();
 
-    pub async fn review(
-        sess: &Arc<Session>,
-        config: &Arc<Config>,
-        sub_id: String,
-        review_request: ReviewRequest,
-    ) {
         let turn_context = sess
             .new_turn_with_sub_id(sub_id.clone(), SessionSettingsUpdate::default())
             .await;
-        spawn_review_thread(
-            Arc::clone(sess),
-            Arc::clone(config),
-            turn_context.clone(),
-            sub_id,
-            review_request,
+        rewind_history(sess, history[..idx].to_vec()).await;
+        let unpinned_files = {
+            let mut state = sess.state.lock().await;
+            state.discard_previous_attempts_from(at_item);
+            state.pinned_files.unpin_pinned_after(at_item)
+        };
+
+        sess.send_event(
+            turn_context.as_ref(),
+            EventMsg::UserMessageEdited(UserMessageEditedEvent {
+                at_item,
+                removed_user_messages: user_messages.len() - at_item,
+                removed_messages,
+                unpinned_files,
+            }),
         )
         .await;
+        sess.spawn_task(Arc::clone(&turn_context), items, RegularTask)
+            .await;
+        *previous_context = Some(turn_context);
     }
 
-    pub async fn remember(sess: &Arc<Session>, sub_id: String, text: String) {
-        let (success, memory_id, error) = if let Some(mm) = sess.memory_manager() {
-            match mm.remember(&text).await {
-                Ok(memory) => (true, Some(memory.id), None),
-                Err(e) => (false, None, Some(e.to_string())),
+    /// Replace history with `items`, an earlier prefix of it, and record the
+    /// rewind so a resumed session sees the same history. Pins on assistant
+    /// messages that are no longer in history are dropped.
+    async fn rewind_history(sess: &Session, items: Vec<ResponseItem>) {
+        let kept_answers = items
+            .iter()
+            .filter(|item| matches!(parse_turn_item(item), Some(TurnItem::AgentMessage(_))))
+            .count();
+        sess.state.lock().await.unpin_items_from(kept_answers);
+        sess.replace_history(items.clone()).await;
+        sess.persist_rollout_items(&[RolloutItem::Compacted(CompactedItem {
+            message: String::new(),
+            replacement_history: Some(items),
+        })])
+        .await;
+    }
+
+    /// Pin or unpin the `at_item`-th assistant message so compaction keeps it.
+    pub async fn pin_history_item(
+        sess: &Arc<Session>,
+        sub_id: String,
+        at_item: usize,
+        pinned: bool,
+    ) {
+        let result = async {
+            if !pinned {
+                return anyhow::Ok(None);
             }
-        } else {
-            (false, None, Some("Memory system not available".to_string()))
-        };
+            sess.flush_rollout().await;
+            let rollout_path = {
+                let guard = sess.services.rollout.lock().await;
+                guard.as_ref().map(|rec| rec.rollout_path.clone())
+            };
+            let Some(path) = rollout_path else {
+                anyhow::bail!("this session is not being recorded");
+            };
+            let items = RolloutRecorder::get_rollout_history(&path)
+                .await?
+                .get_rollout_items();
+            let message = truncate_after_nth_agent_message(items, at_item)
+                .and_then(|items| {
+                    items.into_iter().rev().find_map(|item| match item {
+                        RolloutItem::ResponseItem(item @ ResponseItem::Message { .. }) => {
+                            Some(item)
+                        }
+                        _ => None,
+                    })
+                })
+                .ok_or_else(|| anyhow::anyhow!("no assistant message #{}", at_item + 1))?;
+            anyhow::Ok(Some(message))
+        }
+        .await;
 
-        let event = Event {
-            id: sub_id,
-            msg: EventMsg::MemoryRememberResponse(
-                crate::protocol::MemoryRememberResponseEvent {
-                    success,
-                    memory_id,
-                    error,
-                },
-            ),
+        let warning = match result {
+            Ok(item) => {
+                sess.state.lock().await.set_pinned_item(at_item, item);
+                let kept = sess
+                    .compaction_policies()
+                    .contains(&CompactionPolicy::KeepPinnedMessages);
+                (pinned && !kept).then(|| {
+                    format!(
+                        "Pinned assistant message #{}, but compaction.policies does not include keep-pinned-messages, so compaction will not keep it.",
+                        at_item + 1
+                    )
+                })
+            }
+            Err(err) => Some(format!(
+                "Failed to pin assistant message #{}: {err}",
+                at_item + 1
+            )),
         };
-        sess.send_event_raw(event).await;
+        if let Some(message) = warning {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Warning(WarningEvent { message }),
+            })
+            .await;
+        }
     }
 
-    pub async fn list_memories(sess: &Arc<Session>, sub_id: String) {
-        let (total_count, counts_by_type, recent_memories, storage_path) =
-            if let Some(mm) = sess.memory_manager() {
-                let stats = mm.stats().await.unwrap_or_default();
-                let summary = mm.summary().await;
+    /// Pin or unpin a file whose contents are recorded ahead of user turns.
+    pub async fn pin_file(sess: &Arc<Session>, sub_id: String, path: PathBuf, pinned: bool) {
+        let (cwd, _) = sess.cwd_and_config().await;
+        let path = cwd.join(path);
+        let result = async {
+            if !pinned {
+                if !sess.state.lock().await.pinned_files.unpin(&path) {
+                    anyhow::bail!("it is not pinned");
+                }
+                return anyhow::Ok(());
+            }
+            let metadata = tokio::fs::metadata(&path).await?;
+            if !metadata.is_file() {
+                anyhow::bail!("not a file");
+            }
+            if metadata.len() > MAX_PINNED_FILE_BYTES {
+                anyhow::bail!("larger than {} KiB", MAX_PINNED_FILE_BYTES / 1024);
+            }
+            let user_messages = sess
+                .clone_history()
+                .await
+                .get_history()
+                .iter()
+                .filter(|item| matches!(parse_turn_item(item), Some(TurnItem::UserMessage(_))))
+                .count();
+            sess.state
+                .lock()
+                .await
+                .pinned_files
+                .pin(path.clone(), user_messages);
+            anyhow::Ok(())
+        }
+        .await;
 
-                let recent: Vec<crate::protocol::MemoryEntry> = summary
-                    .recent
-                    .into_iter()
-                    .map(|m| crate::protocol::MemoryEntry {
-                        id: m.id,
-                        memory_type: format!("{:?}", m.memory_type),
-                        content: m.content,
-                        importance: m.importance,
-                        use_count: m.use_count,
-                    })
-                    .collect();
+        if let Err(err) = result {
+            let action = if pinned { "pin" } else { "unpin" };
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Warning(WarningEvent {
+                    message: format!("Failed to {action} {}: {err}", path.display()),
+                }),
+            })
+            .await;
+        }
+    }
 
-                (
-                    stats.total_count,
-                    stats.counts_by_type,
-                    recent,
-                    stats.storage_path,
-                )
-            } else {
-                (0, std::collections::HashMap::new(), Vec::new(), None)
-            };
+    pub async fn get_message_variants(sess: &Arc<Session>, sub_id: String, message_id: String) {
+        let history = sess.clone_history().await.get_history();
+        let mut user_messages = 0;
+        let mut answered = None;
+        for item in &history {
+            match item {
+                ResponseItem::Message { role, id, .. }
+                    if role == "assistant" && id.as_deref() == Some(message_id.as_str()) =>
+                {
+                    answered = user_messages.checked_sub(1);
+                    break;
+                }
+                _ => {
+                    if matches!(parse_turn_item(item), Some(TurnItem::UserMessage(_))) {
+                        user_messages += 1;
+                    }
+                }
+            }
+        }
 
-        let event = Event {
+        let variants = {
+            let state = sess.state.lock().await;
+            answered
+                .or_else(|| state.user_message_for_attempt(&message_id))
+                .map(|user_message| state.previous_attempts(user_message))
+                .unwrap_or_default()
+        };
+        sess.send_event_raw(Event {
             id: sub_id,
-            msg: EventMsg::MemoryListResponse(crate::protocol::MemoryListResponseEvent {
-                total_count,
-                counts_by_type,
-                recent_memories,
-                storage_path,
+            msg: EventMsg::MessageVariants(MessageVariantsEvent {
+                message_id,
+                variants,
             }),
-        };
-        sess.send_event_raw(event).await;
+        })
+        .await;
+    }
+}
+
+/// The family of `model`, limited to what the configured provider supports.
+fn find_provider_model_family(config: &Config, model: &str) -> Option<ModelFamily> {
+    let family = find_family_for_model(model)?;
+    if config.model_provider_id == LOCAL_PROVIDER_ID {
+        Some(local_model_family(family))
+    } else {
+        Some(family)
     }
 }
 
@@ -2196,7 +4346,7 @@ async fn spawn_review_thread(
     review_request: ReviewRequest,
 ) {
     let model = config.review_model.clone();
-    let review_model_family = find_family_for_model(&model)
+    let review_model_family = find_provider_model_family(&config, &model)
         .unwrap_or_else(|| parent_turn_context.client.get_model_family());
     // For reviews, disable web_search and view_image regardless of global settings.
     let mut review_features = config.features.clone();
@@ -2220,7 +4370,7 @@ async fn spawn_review_thread(
     per_turn_config.model_family = model_family.clone();
     per_turn_config.model_reasoning_effort = Some(ReasoningEffortConfig::Low);
     per_turn_config.model_reasoning_summary = ReasoningSummaryConfig::Detailed;
-    if let Some(model_info) = get_model_info(&model_family) {
+    if let Some(model_info) = get_provider_model_info(&config.model_provider_id, &model_family) {
         per_turn_config.model_context_window = Some(model_info.context_window);
     }
 
@@ -2303,11 +4453,11 @@ pub(crate) async fn run_task(
     if input.is_empty() {
         return None;
     }
-    let event = EventMsg::TaskStarted(TaskStartedEvent {
-        model_context_window: turn_context.client.get_model_context_window(),
-    });
+    let event = EventMsg::TaskStarted(turn_context.task_started_event());
     sess.send_event(&turn_context, event).await;
 
+    sess.state.lock().await.cost.start_turn();
+
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
     sess.record_input_and_rollout_usermsg(turn_context.as_ref(), &initial_input_for_turn)
         .await;
@@ -2320,6 +4470,10 @@ pub(crate) async fn run_task(
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
 
     loop {
+        if !sess.confirm_cost_budget(&turn_context).await {
+            break;
+        }
+
         // Note that pending_input would be something like a message the user
         // submitted through the UI while the model was running. Though the UI
         // may support this, the model might not.
@@ -2371,6 +4525,9 @@ pub(crate) async fn run_task(
 
                 // as long as compaction works well in getting us way below the token limit, we shouldn't worry about being in an infinite loop.
                 if token_limit_reached {
+                    if run_local_auto_compact(&sess, &turn_context).await {
+                        continue;
+                    }
                     if should_use_remote_compact_task(&sess).await {
                         run_inline_remote_auto_compact_task(sess.clone(), turn_context.clone())
                             .await;
@@ -2500,15 +4657,29 @@ async fn run_turn(
             base_instructions = Some(new_instructions);
         }
     }
-    let prompt = Prompt {
+    let model_family = turn_context.client.get_model_family();
+    let adaptation = PromptAdaptation::for_model(
+        &model_family,
+        &turn_context.client.config().prompt_adaptations,
+    );
+    let mut tools = router.specs();
+    adaptation.apply_to_tools(&mut tools);
+    let mut prompt = Prompt {
         input,
-        tools: router.specs(),
+        tools,
         parallel_tool_calls,
         base_instructions_override: base_instructions,
         output_schema: turn_context.final_output_json_schema.clone(),
     };
+    let adapted_instructions =
+        adaptation.apply_to_instructions(&prompt.get_full_instructions(&model_family));
+    if adapted_instructions.is_some() {
+        prompt.base_instructions_override = adapted_instructions;
+    }
 
-    let mut retries = 0;
+    // Use the configured provider-specific stream retry budget.
+    let mut retry_policy =
+        TurnRetryPolicy::new(turn_context.client.get_provider().stream_max_retries());
     loop {
         match try_run_turn(
             Arc::clone(&router),
@@ -2546,31 +4717,25 @@ async fn run_turn(
             Err(e @ CodexErr::QuotaExceeded) => return Err(e),
             Err(e @ CodexErr::RefreshTokenFailed(_)) => return Err(e),
             Err(e) => {
-                // Use the configured provider-specific stream retry budget.
-                let max_retries = turn_context.client.get_provider().stream_max_retries();
-                if retries < max_retries {
-                    retries += 1;
-                    let delay = match e {
-                        CodexErr::Stream(_, Some(delay)) => delay,
-                        _ => backoff(retries),
-                    };
-                    warn!(
-                        "stream disconnected - retrying turn ({retries}/{max_retries} in {delay:?})...",
-                    );
-
-                    // Surface retry information to any UI/front‑end so the
-                    // user understands what is happening instead of staring
-                    // at a seemingly frozen screen.
-                    sess.notify_stream_error(
-                        &turn_context,
-                        format!("Reconnecting... {retries}/{max_retries}"),
-                        e,
-                    )
-                    .await;
-
-                    tokio::time::sleep(delay).await;
-                } else {
+                let Some(retry) = retry_policy.schedule(&e) else {
                     return Err(e);
+                };
+                let delay = retry.delay;
+                warn!(
+                    "stream disconnected - retrying turn ({}/{} in {delay:?})...",
+                    retry.attempt, retry.max_attempts
+                );
+                sess.notify_retry_scheduled(&turn_context, retry, e).await;
+
+                // Interrupting the turn cancels the pending retry.
+                if tokio::time::sleep(delay)
+                    .or_cancel(&cancellation_token)
+                    .await
+                    .is_err()
+                {
+                    return Err(CodexErr::TurnAborted {
+                        dangling_artifacts: Vec::new(),
+                    });
                 }
             }
         }
@@ -2647,6 +4812,7 @@ async fn try_run_turn(
                 ));
             }
         };
+        sess.services.activity_tracker.record_provider_traffic();
 
         let add_completed = &mut |response_item: ProcessedResponseItem| {
             output.push_back(future::ready(Ok(response_item)).boxed());
@@ -2880,6 +5046,7 @@ mod tests {
 
     use crate::protocol::CompactedItem;
     use crate::protocol::InitialHistory;
+    use crate::protocol::MessageVariant;
     use crate::protocol::ResumedHistory;
     use crate::state::TaskKind;
     use crate::tasks::SessionTask;
@@ -2995,6 +5162,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn task_started_event_reports_turn_settings() {
+        let (_, turn_context) = make_session_and_context();
+
+        let event = turn_context.task_started_event();
+
+        assert_eq!(
+            event.model_context_window,
+            turn_context.client.get_model_context_window()
+        );
+        assert_eq!(event.model, turn_context.client.get_model());
+        assert_eq!(
+            event.reasoning_effort,
+            turn_context.client.get_reasoning_effort()
+        );
+        assert_eq!(
+            event.sandbox_policy,
+            Some(turn_context.sandbox_policy.clone())
+        );
+    }
+
     #[test]
     fn falls_back_to_content_when_structured_is_null() {
         let ctr = CallToolResult {
@@ -3105,6 +5293,8 @@ mod tests {
             features: Features::default(),
             exec_policy: Arc::new(ExecPolicy::empty()),
             session_source: SessionSource::Exec,
+            session_env: HashMap::new(),
+            session_webhooks: Vec::new(),
         };
 
         let state = SessionState::new(session_configuration.clone());
@@ -3113,14 +5303,21 @@ mod tests {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
-            notifier: UserNotifier::new(None),
             rollout: Mutex::new(None),
             user_shell: default_user_shell(),
-            show_raw_agent_reasoning: config.show_raw_agent_reasoning,
+            reloadable: std::sync::RwLock::new(ReloadableSettings {
+                notify: None,
+                ..ReloadableSettings::from_config(&config)
+            }),
+            config_reload: Mutex::new(None),
             auth_manager: Arc::clone(&auth_manager),
             otel_event_manager: otel_event_manager.clone(),
             tool_approvals: Mutex::new(ApprovalStore::default()),
-            memory_manager: None, // No memory for exec mode
+            memory_manager: OnceLock::from(None), // No memory for exec mode
+            recalled_session_notes: None,
+            subagent_cancellations: Mutex::new(HashMap::new()),
+            tool_call_cancellations: Mutex::new(HashMap::new()),
+            activity_tracker: ActivityTracker::default(),
         };
 
         let turn_context = Session::make_turn_context(
@@ -3184,6 +5381,8 @@ mod tests {
             features: Features::default(),
             exec_policy: Arc::new(ExecPolicy::empty()),
             session_source: SessionSource::Exec,
+            session_env: HashMap::new(),
+            session_webhooks: Vec::new(),
         };
 
         let state = SessionState::new(session_configuration.clone());
@@ -3192,14 +5391,21 @@ mod tests {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
-            notifier: UserNotifier::new(None),
             rollout: Mutex::new(None),
             user_shell: default_user_shell(),
-            show_raw_agent_reasoning: config.show_raw_agent_reasoning,
+            reloadable: std::sync::RwLock::new(ReloadableSettings {
+                notify: None,
+                ..ReloadableSettings::from_config(&config)
+            }),
+            config_reload: Mutex::new(None),
             auth_manager: Arc::clone(&auth_manager),
             otel_event_manager: otel_event_manager.clone(),
             tool_approvals: Mutex::new(ApprovalStore::default()),
-            memory_manager: None, // No memory for test
+            memory_manager: OnceLock::from(None), // No memory for test
+            recalled_session_notes: None,
+            subagent_cancellations: Mutex::new(HashMap::new()),
+            tool_call_cancellations: Mutex::new(HashMap::new()),
+            activity_tracker: ActivityTracker::default(),
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
@@ -3308,6 +5514,59 @@ mod tests {
         assert!(rx.try_recv().is_err());
     }
 
+    #[tokio::test]
+    async fn message_variants_are_found_from_any_attempt() {
+        let (sess, tc, rx) = make_session_and_context_with_rx();
+        let message = |role: &str, id: Option<&str>, text: &str| ResponseItem::Message {
+            id: id.map(str::to_string),
+            role: role.to_string(),
+            content: vec![if role == "user" {
+                ContentItem::InputText {
+                    text: text.to_string(),
+                }
+            } else {
+                ContentItem::OutputText {
+                    text: text.to_string(),
+                }
+            }],
+        };
+        sess.record_into_history(
+            &[
+                message("user", None, "first question"),
+                message("assistant", Some("msg_a"), "first answer"),
+                message("user", None, "second question"),
+                message("assistant", Some("msg_c"), "regenerated answer"),
+            ],
+            tc.as_ref(),
+        )
+        .await;
+        let attempt = MessageVariant {
+            message_id: Some("msg_b".to_string()),
+            text: "original answer".to_string(),
+            model: "gpt-5".to_string(),
+            effort: None,
+        };
+        sess.state
+            .lock()
+            .await
+            .push_previous_attempt(1, attempt.clone());
+
+        for (message_id, expected) in [
+            ("msg_c", vec![attempt.clone()]),
+            ("msg_b", vec![attempt.clone()]),
+            ("msg_a", Vec::new()),
+        ] {
+            handlers::get_message_variants(&sess, "sub".to_string(), message_id.to_string()).await;
+            match rx.recv().await.expect("event").msg {
+                EventMsg::MessageVariants(event) => {
+                    assert_eq!(event.message_id, message_id);
+                    assert_eq!(event.variants, expected);
+                }
+                other => panic!("unexpected event: {other:?}"),
+            }
+        }
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn abort_review_task_emits_exited_then_aborted_and_records_history() {
         let (sess, tc, rx) = make_session_and_context_with_rx();
@@ -3370,6 +5629,63 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn cancel_tool_call_cancels_only_that_call() {
+        let (sess, _tc, rx) = make_session_and_context_with_rx();
+        let turn_token = CancellationToken::new();
+        let first = sess.register_tool_call("call-1", &turn_token).await;
+        let second = sess.register_tool_call("call-2", &turn_token).await;
+
+        sess.cancel_tool_call("sub".to_string(), "call-1".to_string())
+            .await;
+        assert!(first.is_cancelled());
+        assert!(!second.is_cancelled());
+        assert!(!turn_token.is_cancelled());
+
+        sess.cancel_tool_call("sub".to_string(), "call-1".to_string())
+            .await;
+        let evt = tokio::time::timeout(std::time::Duration::from_secs(1), rx.recv())
+            .await
+            .expect("timeout waiting for event")
+            .expect("event");
+        assert!(matches!(evt.msg, EventMsg::Warning(_)));
+
+        turn_token.cancel();
+        assert!(second.is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn cancel_subagent_task_cancels_only_that_task() {
+        let (sess, _tc, rx) = make_session_and_context_with_rx();
+        let first = sess.register_subagent_task("call-1", 0).await;
+        let second = sess.register_subagent_task("call-1", 1).await;
+
+        sess.cancel_subagent_task("sub".to_string(), "call-1".to_string(), 1)
+            .await;
+        assert!(!first.is_cancelled());
+        assert!(second.is_cancelled());
+
+        // A task that already stopped is reported rather than ignored.
+        sess.cancel_subagent_task("sub".to_string(), "call-1".to_string(), 1)
+            .await;
+        let evt = tokio::time::timeout(std::time::Duration::from_secs(1), rx.recv())
+            .await
+            .expect("timeout waiting for event")
+            .expect("event");
+        let EventMsg::Warning(warning) = evt.msg else {
+            panic!("expected a warning, got {:?}", evt.msg);
+        };
+        assert_eq!(
+            warning.message,
+            "No running subagent task 2 for call call-1"
+        );
+
+        sess.unregister_subagent_task("call-1", 0).await;
+        sess.cancel_subagent_task("sub".to_string(), "call-1".to_string(), 0)
+            .await;
+        assert!(!first.is_cancelled());
+    }
+
     #[tokio::test]
     async fn fatal_tool_error_stops_turn_and_reports_error() {
         let (session, turn_context, _rx) = make_session_and_context_with_rx();
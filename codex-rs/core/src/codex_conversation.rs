@@ -8,15 +8,17 @@ use std::path::PathBuf;
 pub struct CodexConversation {
     codex: Codex,
     rollout_path: PathBuf,
+    cwd: PathBuf,
 }
 
 /// Conduit for the bidirectional stream of messages that compose a conversation
 /// in Codex.
 impl CodexConversation {
-    pub(crate) fn new(codex: Codex, rollout_path: PathBuf) -> Self {
+    pub(crate) fn new(codex: Codex, rollout_path: PathBuf, cwd: PathBuf) -> Self {
         Self {
             codex,
             rollout_path,
+            cwd,
         }
     }
 
@@ -36,4 +38,9 @@ impl CodexConversation {
     pub fn rollout_path(&self) -> PathBuf {
         self.rollout_path.clone()
     }
+
+    /// Working directory the session was configured with.
+    pub fn cwd(&self) -> PathBuf {
+        self.cwd.clone()
+    }
 }
@@ -275,6 +275,7 @@ async fn handle_exec_approval(
         event.cwd,
         event.reason,
         event.risk,
+        event.network_destinations,
     );
     let decision = await_approval_with_cancel(
         approval_fut,
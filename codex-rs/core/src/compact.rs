@@ -11,13 +11,12 @@ use crate::features::Feature;
 use crate::protocol::CompactedItem;
 use crate::protocol::ContextCompactedEvent;
 use crate::protocol::EventMsg;
-use crate::protocol::TaskStartedEvent;
 use crate::protocol::TurnContextItem;
 use crate::protocol::WarningEvent;
+use crate::retry_policy::TurnRetryPolicy;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::approx_token_count;
 use crate::truncate::truncate_text;
-use crate::util::backoff;
 use codex_app_server_protocol::AuthMode;
 use codex_protocol::items::TurnItem;
 use codex_protocol::models::ContentItem;
@@ -56,9 +55,7 @@ pub(crate) async fn run_compact_task(
     turn_context: Arc<TurnContext>,
     input: Vec<UserInput>,
 ) {
-    let start_event = EventMsg::TaskStarted(TaskStartedEvent {
-        model_context_window: turn_context.client.get_model_context_window(),
-    });
+    let start_event = EventMsg::TaskStarted(turn_context.task_started_event());
     sess.send_event(&turn_context, start_event).await;
     run_compact_task_inner(sess.clone(), turn_context, input).await;
 }
@@ -79,7 +76,7 @@ async fn run_compact_task_inner(
     let mut truncated_count = 0usize;
 
     let max_retries = turn_context.client.get_provider().stream_max_retries();
-    let mut retries = 0;
+    let mut retry_policy = TurnRetryPolicy::new(max_retries);
 
     let rollout_item = RolloutItem::TurnContext(TurnContextItem {
         cwd: turn_context.cwd.clone(),
@@ -123,7 +120,7 @@ async fn run_compact_task_inner(
                     );
                     history.remove_first_item();
                     truncated_count += 1;
-                    retries = 0;
+                    retry_policy = TurnRetryPolicy::new(max_retries);
                     continue;
                 }
                 sess.set_total_tokens_full(turn_context.as_ref()).await;
@@ -132,16 +129,10 @@ async fn run_compact_task_inner(
                 return;
             }
             Err(e) => {
-                if retries < max_retries {
-                    retries += 1;
-                    let delay = backoff(retries);
-                    sess.notify_stream_error(
-                        turn_context.as_ref(),
-                        format!("Reconnecting... {retries}/{max_retries}"),
-                        e,
-                    )
-                    .await;
-                    tokio::time::sleep(delay).await;
+                if let Some(retry) = retry_policy.schedule(&e) {
+                    sess.notify_retry_scheduled(turn_context.as_ref(), retry, e)
+                        .await;
+                    tokio::time::sleep(retry.delay).await;
                     continue;
                 } else {
                     let event = EventMsg::Error(e.to_error_event(None));
@@ -158,7 +149,10 @@ async fn run_compact_task_inner(
     let summary_text = format!("{SUMMARY_PREFIX}\n{summary_suffix}");
     let user_messages = collect_user_messages(&history_snapshot);
 
-    let initial_context = sess.build_initial_context(turn_context.as_ref());
+    let mut initial_context = sess.build_initial_context(turn_context.as_ref());
+    let pinned = sess.pinned_items_for_compaction().await;
+    let keeps_pinned = !pinned.is_empty();
+    initial_context.extend(pinned);
     let mut new_history = build_compacted_history(initial_context, &user_messages, &summary_text);
     let ghost_snapshots: Vec<ResponseItem> = history_snapshot
         .iter()
@@ -166,12 +160,13 @@ async fn run_compact_task_inner(
         .cloned()
         .collect();
     new_history.extend(ghost_snapshots);
-    sess.replace_history(new_history).await;
+    sess.replace_history(new_history.clone()).await;
     sess.recompute_token_usage(&turn_context).await;
 
+    // Pinned messages cannot be rebuilt from the summary on resume.
     let rollout_item = RolloutItem::Compacted(CompactedItem {
         message: summary_text.clone(),
-        replacement_history: None,
+        replacement_history: keeps_pinned.then_some(new_history),
     });
     sess.persist_rollout_items(&[rollout_item]).await;
 
@@ -0,0 +1,285 @@
+//! Automatic compaction without a summary request.
+//!
+//! The `drop-oldest-tools-first` and `summarize-reasoning` policies shrink
+//! earlier turns in place. When that brings the estimated history under half
+//! the auto-compact limit, the summary request is skipped; otherwise the
+//! history is left alone and compaction summarizes as usual. The turn in
+//! progress is never touched.
+
+use std::sync::Arc;
+
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ReasoningItemReasoningSummary;
+use codex_protocol::models::ResponseItem;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::CompactionPolicy;
+use crate::context_manager::ContextManager;
+use crate::event_mapping::parse_turn_item;
+use crate::protocol::CompactedItem;
+use crate::protocol::ContextCompactedEvent;
+use crate::protocol::EventMsg;
+use crate::protocol::RolloutItem;
+use crate::truncate::approx_token_count;
+
+/// Applies the local compaction policies. Returns `false`, leaving the
+/// history untouched, when they are off or do not free enough room.
+pub(crate) async fn run_local_auto_compact(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+) -> bool {
+    let policies = sess.compaction_policies();
+    let summarize_reasoning = policies.contains(&CompactionPolicy::SummarizeReasoning);
+    let drop_tools = policies.contains(&CompactionPolicy::DropOldestToolsFirst);
+    let Some(limit) = turn_context.client.get_auto_compact_token_limit() else {
+        return false;
+    };
+    if !summarize_reasoning && !drop_tools {
+        return false;
+    }
+    let target = limit / 2;
+
+    let mut items = sess.clone_history().await.get_history();
+    let mut summarized = 0;
+    if summarize_reasoning {
+        summarized = condense_reasoning(&mut items);
+    }
+    let mut dropped = 0;
+    if drop_tools {
+        let excess = estimate_tokens(&items, turn_context).saturating_sub(target);
+        dropped = drop_oldest_tool_calls(&mut items, excess);
+    }
+    if (summarized == 0 && dropped == 0) || estimate_tokens(&items, turn_context) > target {
+        return false;
+    }
+
+    sess.replace_history(items.clone()).await;
+    sess.recompute_token_usage(turn_context).await;
+    sess.persist_rollout_items(&[RolloutItem::Compacted(CompactedItem {
+        message: String::new(),
+        replacement_history: Some(items),
+    })])
+    .await;
+    sess.send_event(
+        turn_context,
+        EventMsg::ContextCompacted(ContextCompactedEvent {}),
+    )
+    .await;
+    sess.notify_background_event(
+        turn_context.as_ref(),
+        format!(
+            "Compacted without a summary: dropped {dropped} old tool call(s) and condensed {summarized} reasoning item(s)."
+        ),
+    )
+    .await;
+    true
+}
+
+fn estimate_tokens(items: &[ResponseItem], turn_context: &TurnContext) -> i64 {
+    let mut history = ContextManager::new();
+    history.replace(items.to_vec());
+    history
+        .estimate_token_count(turn_context)
+        .unwrap_or(i64::MAX)
+}
+
+/// Index of the last user message; items from there on belong to the turn in
+/// progress.
+fn current_turn_start(items: &[ResponseItem]) -> usize {
+    items
+        .iter()
+        .rposition(|item| matches!(parse_turn_item(item), Some(TurnItem::UserMessage(_))))
+        .unwrap_or(items.len())
+}
+
+/// Replaces reasoning items of earlier turns with an assistant message holding
+/// their summary text, dropping those without one. Returns how many were
+/// replaced.
+fn condense_reasoning(items: &mut Vec<ResponseItem>) -> usize {
+    let boundary = current_turn_start(items);
+    let mut condensed = 0;
+    let mut kept = Vec::with_capacity(items.len());
+    for (idx, item) in items.drain(..).enumerate() {
+        match item {
+            ResponseItem::Reasoning { summary, .. } if idx < boundary => {
+                condensed += 1;
+                let text = summary
+                    .into_iter()
+                    .map(|ReasoningItemReasoningSummary::SummaryText { text }| text)
+                    .filter(|text| !text.trim().is_empty())
+                    .collect::<Vec<_>>()
+                    .join("\n\n");
+                if !text.is_empty() {
+                    kept.push(ResponseItem::Message {
+                        id: None,
+                        role: "assistant".to_string(),
+                        content: vec![ContentItem::OutputText { text }],
+                    });
+                }
+            }
+            item => kept.push(item),
+        }
+    }
+    *items = kept;
+    condensed
+}
+
+/// Call id shared by a tool call and its output: `Some(None)` for tool calls
+/// without one, `None` for items that are not part of a tool call.
+fn tool_call_id(item: &ResponseItem) -> Option<Option<&str>> {
+    match item {
+        ResponseItem::FunctionCall { call_id, .. }
+        | ResponseItem::FunctionCallOutput { call_id, .. }
+        | ResponseItem::CustomToolCall { call_id, .. }
+        | ResponseItem::CustomToolCallOutput { call_id, .. } => Some(Some(call_id)),
+        ResponseItem::LocalShellCall { call_id, .. } => Some(call_id.as_deref()),
+        ResponseItem::WebSearchCall { .. } => Some(None),
+        _ => None,
+    }
+}
+
+/// Removes tool calls of earlier turns, oldest first and each together with
+/// its output, until about `excess` tokens are freed. Returns how many calls
+/// were removed.
+fn drop_oldest_tool_calls(items: &mut Vec<ResponseItem>, excess: i64) -> usize {
+    let mut freed = 0i64;
+    let mut dropped = 0;
+    while freed < excess {
+        let boundary = current_turn_start(items);
+        let Some(idx) = items[..boundary]
+            .iter()
+            .position(|item| tool_call_id(item).is_some())
+        else {
+            break;
+        };
+        let call_id = tool_call_id(&items[idx]).flatten().map(str::to_string);
+        let mut removed = vec![items.remove(idx)];
+        if let Some(call_id) = call_id {
+            let (matching, rest): (Vec<_>, Vec<_>) = std::mem::take(items)
+                .into_iter()
+                .partition(|item| tool_call_id(item).flatten() == Some(call_id.as_str()));
+            *items = rest;
+            removed.extend(matching);
+        }
+        freed += removed
+            .iter()
+            .map(|item| {
+                let serialized = serde_json::to_string(item).unwrap_or_default();
+                i64::try_from(approx_token_count(&serialized)).unwrap_or(i64::MAX)
+            })
+            .sum::<i64>();
+        dropped += 1;
+    }
+    dropped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
+    use pretty_assertions::assert_eq;
+
+    fn user(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    fn call(call_id: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: "{}".to_string(),
+            call_id: call_id.to_string(),
+        }
+    }
+
+    fn output(call_id: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload {
+                content: "x".repeat(400),
+                ..Default::default()
+            },
+        }
+    }
+
+    fn reasoning(summary: &str) -> ResponseItem {
+        ResponseItem::Reasoning {
+            id: String::new(),
+            summary: vec![ReasoningItemReasoningSummary::SummaryText {
+                text: summary.to_string(),
+            }],
+            content: None,
+            encrypted_content: Some("e".repeat(4000)),
+        }
+    }
+
+    #[test]
+    fn oldest_tool_calls_are_dropped_with_their_output() {
+        let mut items = vec![
+            user("first"),
+            call("a"),
+            output("a"),
+            call("b"),
+            output("b"),
+            user("second"),
+            call("c"),
+            output("c"),
+        ];
+
+        assert_eq!(drop_oldest_tool_calls(&mut items, 1), 1);
+        assert_eq!(
+            items,
+            vec![
+                user("first"),
+                call("b"),
+                output("b"),
+                user("second"),
+                call("c"),
+                output("c"),
+            ]
+        );
+
+        // The turn in progress is kept even when more room is needed.
+        assert_eq!(drop_oldest_tool_calls(&mut items, i64::MAX), 1);
+        assert_eq!(
+            items,
+            vec![user("first"), user("second"), call("c"), output("c")]
+        );
+    }
+
+    #[test]
+    fn earlier_reasoning_is_replaced_by_its_summary() {
+        let mut items = vec![
+            user("first"),
+            reasoning("Looked at the failing test."),
+            reasoning(""),
+            user("second"),
+            reasoning("Still thinking."),
+        ];
+
+        assert_eq!(condense_reasoning(&mut items), 2);
+        assert_eq!(
+            items,
+            vec![
+                user("first"),
+                ResponseItem::Message {
+                    id: None,
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "Looked at the failing test.".to_string(),
+                    }],
+                },
+                user("second"),
+                reasoning("Still thinking."),
+            ]
+        );
+    }
+}
@@ -1,4 +1,6 @@
 use crate::config::CONFIG_TOML_FILE;
+use crate::config::ProjectDefaults;
+use crate::config::types::CompactionPolicy;
 use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
 use crate::config::types::PlanDetailPreference;
@@ -8,6 +10,7 @@ use anyhow::Context;
 use codex_protocol::config_types::ReasoningEffort;
 use codex_protocol::config_types::TrustLevel;
 use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use tempfile::NamedTempFile;
@@ -25,6 +28,11 @@ pub enum ConfigEdit {
         model: Option<String>,
         effort: Option<ReasoningEffort>,
     },
+    /// Remember the reasoning effort for a model under `[model_preferences]`.
+    SetModelPreference {
+        model: String,
+        effort: Option<ReasoningEffort>,
+    },
     /// Toggle the acknowledgement flag under `[notice]`.
     SetNoticeHideFullAccessWarning(bool),
     /// Toggle the Windows world-writable directories warning acknowledgement flag.
@@ -244,6 +252,15 @@ impl ConfigDocument {
                 );
                 mutated
             }),
+            ConfigEdit::SetModelPreference { model, effort } => {
+                let segments = ["model_preferences", model.as_str(), "reasoning_effort"];
+                Ok(match effort {
+                    Some(effort) => {
+                        self.write_value(Scope::Global, &segments, value(effort.to_string()))
+                    }
+                    None => self.clear(Scope::Global, &segments),
+                })
+            }
             ConfigEdit::SetNoticeHideFullAccessWarning(acknowledged) => Ok(self.write_value(
                 Scope::Global,
                 &[Notice::TABLE_KEY, "hide_full_access_warning"],
@@ -498,6 +515,14 @@ impl ConfigEditsBuilder {
         self
     }
 
+    pub fn set_model_preference(mut self, model: &str, effort: Option<ReasoningEffort>) -> Self {
+        self.edits.push(ConfigEdit::SetModelPreference {
+            model: model.to_string(),
+            effort,
+        });
+        self
+    }
+
     pub fn set_hide_full_access_warning(mut self, acknowledged: bool) -> Self {
         self.edits
             .push(ConfigEdit::SetNoticeHideFullAccessWarning(acknowledged));
@@ -549,6 +574,72 @@ impl ConfigEditsBuilder {
         self
     }
 
+    /// Replace the env profile under `[projects."<path>".env]`; an empty map
+    /// removes it.
+    pub fn set_project_env<P: Into<PathBuf>>(
+        mut self,
+        project_path: P,
+        env: &HashMap<String, String>,
+    ) -> Self {
+        let segments = vec![
+            "projects".to_string(),
+            project_path.into().to_string_lossy().to_string(),
+            "env".to_string(),
+        ];
+        if env.is_empty() {
+            self.edits.push(ConfigEdit::ClearPath { segments });
+        } else {
+            let mut table = TomlTable::new();
+            for (name, val) in env.iter().collect::<BTreeMap<_, _>>() {
+                table.insert(name, value(val.as_str()));
+            }
+            self.edits.push(ConfigEdit::SetPath {
+                segments,
+                value: TomlItem::Table(table),
+            });
+        }
+        self
+    }
+
+    /// Replace the session defaults under `[projects."<path>"]`; unset fields
+    /// are removed.
+    pub fn set_project_defaults<P: Into<PathBuf>>(
+        mut self,
+        project_path: P,
+        defaults: &ProjectDefaults,
+    ) -> Self {
+        let project = project_path.into().to_string_lossy().to_string();
+        let ProjectDefaults {
+            model,
+            model_reasoning_effort,
+            approval_policy,
+            sandbox_mode,
+        } = defaults;
+        let entries = [
+            ("model", model.clone()),
+            (
+                "model_reasoning_effort",
+                model_reasoning_effort.map(|effort| effort.to_string()),
+            ),
+            (
+                "approval_policy",
+                approval_policy.map(|policy| policy.to_string()),
+            ),
+            ("sandbox_mode", sandbox_mode.map(|mode| mode.to_string())),
+        ];
+        for (key, setting) in entries {
+            let segments = vec!["projects".to_string(), project.clone(), key.to_string()];
+            self.edits.push(match setting {
+                Some(setting) => ConfigEdit::SetPath {
+                    segments,
+                    value: value(setting),
+                },
+                None => ConfigEdit::ClearPath { segments },
+            });
+        }
+        self
+    }
+
     /// Enable or disable a feature flag by key under the `[features]` table.
     pub fn set_feature_enabled(mut self, key: &str, enabled: bool) -> Self {
         self.edits.push(ConfigEdit::SetPath {
@@ -579,6 +670,23 @@ impl ConfigEditsBuilder {
         self
     }
 
+    pub fn set_tui_theme(mut self, theme: &str) -> Self {
+        self.edits.push(ConfigEdit::SetPath {
+            segments: vec!["tui".to_string(), "theme".to_string()],
+            value: value(theme),
+        });
+        self
+    }
+
+    pub fn set_compaction_policies(mut self, policies: &[CompactionPolicy]) -> Self {
+        let policies: toml_edit::Array = policies.iter().map(|policy| policy.as_str()).collect();
+        self.edits.push(ConfigEdit::SetPath {
+            segments: vec!["compaction".to_string(), "policies".to_string()],
+            value: value(policies),
+        });
+        self
+    }
+
     pub fn set_tui_subagent_max_tasks(mut self, limit: i64) -> Self {
         let clamped = limit.clamp(SUBAGENT_LIMIT_MIN, SUBAGENT_LIMIT_HARD_CAP);
         self.edits.push(ConfigEdit::SetPath {
@@ -607,12 +715,85 @@ impl ConfigEditsBuilder {
 mod tests {
     use super::*;
     use crate::config::types::McpServerTransportConfig;
+    use crate::protocol::AskForApproval;
     use codex_protocol::config_types::ReasoningEffort;
     use pretty_assertions::assert_eq;
     use tempfile::tempdir;
     use tokio::runtime::Builder;
     use toml::Value as TomlValue;
 
+    #[test]
+    fn project_env_is_written_and_cleared() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        let env = HashMap::from([(
+            "DATABASE_URL".to_string(),
+            "postgres://localhost/dev".to_string(),
+        )]);
+
+        ConfigEditsBuilder::new(codex_home)
+            .set_project_env("/work/app", &env)
+            .apply_blocking()
+            .expect("persist");
+        let raw = std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let value: TomlValue = toml::from_str(&raw).expect("parse config");
+        assert_eq!(
+            value
+                .get("projects")
+                .and_then(|projects| projects.get("/work/app"))
+                .and_then(|project| project.get("env"))
+                .and_then(|env| env.get("DATABASE_URL"))
+                .and_then(TomlValue::as_str),
+            Some("postgres://localhost/dev")
+        );
+
+        ConfigEditsBuilder::new(codex_home)
+            .set_project_env("/work/app", &HashMap::new())
+            .apply_blocking()
+            .expect("persist");
+        let raw = std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let value: TomlValue = toml::from_str(&raw).expect("parse config");
+        assert_eq!(
+            value
+                .get("projects")
+                .and_then(|projects| projects.get("/work/app"))
+                .and_then(|project| project.get("env")),
+            None
+        );
+    }
+
+    #[test]
+    fn project_defaults_are_written_and_cleared() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        let defaults = ProjectDefaults {
+            model: Some("gpt-5-codex".to_string()),
+            model_reasoning_effort: Some(ReasoningEffort::High),
+            approval_policy: Some(AskForApproval::OnRequest),
+            sandbox_mode: None,
+        };
+        let seed = r#"[projects."/work/app"]
+trust_level = "trusted"
+sandbox_mode = "read-only"
+"#;
+        std::fs::write(codex_home.join(CONFIG_TOML_FILE), seed).expect("seed config");
+
+        ConfigEditsBuilder::new(codex_home)
+            .set_project_defaults("/work/app", &defaults)
+            .apply_blocking()
+            .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"[projects."/work/app"]
+trust_level = "trusted"
+model = "gpt-5-codex"
+model_reasoning_effort = "high"
+approval_policy = "on-request"
+"#;
+        assert_eq!(contents, expected);
+    }
+
     #[test]
     fn blocking_set_model_top_level() {
         let tmp = tempdir().expect("tmpdir");
@@ -636,6 +817,46 @@ model_reasoning_effort = "high"
         assert_eq!(contents, expected);
     }
 
+    #[test]
+    fn blocking_set_model_preference_keys_by_model_slug() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            r#"model = "gpt-5.1-codex"
+"#,
+        )
+        .expect("seed");
+
+        apply_blocking(
+            codex_home,
+            Some("team"),
+            &[
+                ConfigEdit::SetModelPreference {
+                    model: "gpt-5.2-codex".to_string(),
+                    effort: Some(ReasoningEffort::High),
+                },
+                ConfigEdit::SetModelPreference {
+                    model: "gpt-5.1-codex".to_string(),
+                    effort: Some(ReasoningEffort::Low),
+                },
+            ],
+        )
+        .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"model = "gpt-5.1-codex"
+
+[model_preferences."gpt-5.2-codex"]
+reasoning_effort = "high"
+
+[model_preferences."gpt-5.1-codex"]
+reasoning_effort = "low"
+"#;
+        assert_eq!(contents, expected);
+    }
+
     #[test]
     fn blocking_set_model_preserves_inline_table_contents() {
         let tmp = tempdir().expect("tmpdir");
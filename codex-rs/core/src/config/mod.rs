@@ -1,42 +1,66 @@
+use crate::approval_rules::ApprovalRules;
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::ApprovalsToml;
+use crate::config::types::CompactionToml;
+use crate::config::types::DEFAULT_MAX_TRANSCRIPT_WIDTH;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
+use crate::config::types::ExecOutputToml;
+use crate::config::types::Gui;
 use crate::config::types::History;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ModelPreference;
 use crate::config::types::Notice;
+use crate::config::types::NotificationMethod;
 use crate::config::types::Notifications;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
 use crate::config::types::PlanDetailPreference;
+use crate::config::types::PromptAdaptationToml;
 use crate::config::types::ReasoningSummaryFormat;
+use crate::config::types::RedactionToml;
+use crate::config::types::ResourceLimitsToml;
 use crate::config::types::SUBAGENT_LIMIT_HARD_CAP;
 use crate::config::types::SUBAGENT_LIMIT_MIN;
 use crate::config::types::SandboxWorkspaceWrite;
+use crate::config::types::Secrets;
+use crate::config::types::SessionRetention;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
+use crate::config::types::StallWatchdog;
+use crate::config::types::SubagentRole;
+use crate::config::types::SubagentsToml;
+use crate::config::types::TranscriptionToml;
 use crate::config::types::Tui;
 use crate::config::types::UriBasedFileOpener;
+use crate::config::types::UserShellContext;
+use crate::config::types::WebhookConfig;
 use crate::config_loader::LoadedConfigLayers;
 use crate::config_loader::load_config_as_toml;
 use crate::config_loader::load_config_layers_with_overrides;
 use crate::config_loader::merge_toml_values;
+use crate::exec_output::ExecOutputPipeline;
 use crate::features::Feature;
 use crate::features::FeatureOverrides;
 use crate::features::Features;
 use crate::features::FeaturesToml;
 use crate::git_info::resolve_root_git_project_for_trust;
+use crate::memory::MemoryConfig;
 use crate::model_family::ModelFamily;
 use crate::model_family::derive_default_model_family;
 use crate::model_family::find_family_for_model;
+use crate::model_family::local_model_family;
 use crate::model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
+use crate::model_provider_info::LOCAL_PROVIDER_ID;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::OLLAMA_OSS_PROVIDER_ID;
 use crate::model_provider_info::built_in_model_providers;
-use crate::openai_model_info::get_model_info;
+use crate::openai_model_info::get_provider_model_info;
 use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
+use crate::redaction::Redactor;
 use codex_app_server_protocol::Tools;
 use codex_app_server_protocol::UserSavedConfig;
 use codex_protocol::config_types::ForcedLoginMethod;
@@ -62,6 +86,7 @@ use toml_edit::DocumentMut;
 
 pub mod edit;
 pub mod profile;
+pub mod reload;
 pub mod types;
 
 pub const OPENAI_DEFAULT_MODEL: &str = "gpt-5.2-codex";
@@ -159,6 +184,12 @@ pub struct Config {
     /// TUI notifications preference. When set, the TUI will send OSC 9 notifications on approvals
     /// and turn completions when not focused.
     pub tui_notifications: Notifications,
+    /// How TUI notifications are delivered, unless overridden per type in
+    /// `tui_notification_methods`.
+    pub tui_notification_method: NotificationMethod,
+    pub tui_notification_methods: HashMap<String, NotificationMethod>,
+    /// Desktop notification types the app server sends to GUI clients.
+    pub gui_notifications: Notifications,
     pub show_rate_limits_in_footer: bool,
     pub plan_detail: PlanDetailPreference,
     pub subagent_max_tasks: i64,
@@ -172,6 +203,16 @@ pub struct Config {
     /// Enable half-page scrolling with Page Up/Down.
     pub scroll_half_page: bool,
 
+    /// Maximum width of the TUI transcript and composer, centered on wider
+    /// terminals. `None` uses the full terminal width.
+    pub max_transcript_width: Option<u16>,
+
+    /// Name of the TUI color theme; `None` uses the built-in dark theme.
+    pub tui_theme: Option<String>,
+
+    /// User key bindings from `[tui.keys]`, action name to key chord.
+    pub tui_keys: BTreeMap<String, String>,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
@@ -195,6 +236,9 @@ pub struct Config {
     /// auto (default): keyring if available, otherwise file.
     pub mcp_oauth_credentials_store_mode: OAuthCredentialsStoreMode,
 
+    /// Where `secret://name` values in `mcp_servers` are looked up.
+    pub secrets: Secrets,
+
     /// Combined provider map (defaults merged with user-defined overrides).
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
@@ -214,6 +258,47 @@ pub struct Config {
     /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
     pub history: History,
 
+    /// When old rollout files are archived or deleted.
+    pub session_retention: SessionRetention,
+
+    /// Session budget in USD. Once exceeded, the turn pauses and asks for
+    /// confirmation before making further model requests.
+    pub max_session_cost_usd: Option<f64>,
+
+    /// Commands auto-approved or always denied before the approval prompt.
+    pub approval_rules: ApprovalRules,
+
+    /// When a turn without progress is reported or interrupted.
+    pub stall_watchdog: StallWatchdog,
+
+    /// Limits on the processes spawned for the model's shell commands.
+    pub resource_limits: ResourceLimitsToml,
+
+    /// Secret masking applied to rollouts and tool output.
+    pub redactor: Redactor,
+
+    /// Cleanup, truncation and summarization of shell output for the model.
+    pub exec_output: ExecOutputPipeline,
+
+    /// What the model sees of `!` commands run by the user.
+    pub user_shell_context: UserShellContext,
+
+    /// Engine and settings for voice input transcription.
+    pub transcription: TranscriptionToml,
+
+    /// Policies applied when the conversation is compacted.
+    pub compaction: CompactionToml,
+
+    /// Limits and retrieval settings for project memory.
+    pub memory: MemoryConfig,
+
+    /// HTTP endpoints notified of finished turns, approval requests and
+    /// errors.
+    pub webhooks: Vec<WebhookConfig>,
+
+    /// Reasoning effort and verbosity remembered per model slug.
+    pub model_preferences: HashMap<String, ModelPreference>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: UriBasedFileOpener,
@@ -291,6 +376,17 @@ pub struct Config {
 
     /// OTEL configuration (exporter type, endpoint, headers, etc.).
     pub otel: crate::config::types::OtelConfig,
+
+    /// Per-model-family prompt overrides, keyed by family or slug prefix.
+    pub prompt_adaptations: HashMap<String, PromptAdaptationToml>,
+
+    /// Role presets for subagent tasks, keyed by role name.
+    pub subagent_roles: BTreeMap<String, SubagentRole>,
+
+    /// Set by the parent on the config of a subagent it spawns, so the
+    /// subagent's scratchpad tools write to the parent call's scratchpad.
+    /// Never loaded from `config.toml`.
+    pub subagent_scratchpad: Option<crate::subagent_scratchpad::ScratchpadWriter>,
 }
 
 impl Config {
@@ -314,6 +410,24 @@ impl Config {
 
         Self::load_from_base_config_with_overrides(cfg, overrides, codex_home)
     }
+
+    /// Like [`Config::load_with_cli_overrides`], but starts from config
+    /// layers the caller already read, so callers that create many sessions
+    /// can read the files once.
+    pub fn load_from_layers(
+        layers: LoadedConfigLayers,
+        cli_overrides: Vec<(String, TomlValue)>,
+        overrides: ConfigOverrides,
+        codex_home: PathBuf,
+    ) -> std::io::Result<Self> {
+        let cfg: ConfigToml = apply_overlays(layers, cli_overrides)
+            .try_into()
+            .map_err(|e| {
+                tracing::error!("Failed to deserialize overridden config: {e}");
+                std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+            })?;
+        Self::load_from_base_config_with_overrides(cfg, overrides, codex_home)
+    }
 }
 
 pub async fn load_config_as_toml_with_cli_overrides(
@@ -640,6 +754,10 @@ pub struct ConfigToml {
     #[serde(default)]
     pub mcp_oauth_credentials_store: Option<OAuthCredentialsStoreMode>,
 
+    /// Providers for `secret://name` values in `mcp_servers`.
+    #[serde(default)]
+    pub secrets: Option<Secrets>,
+
     /// User-defined provider entries that extend/override the built-in list.
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderInfo>,
@@ -664,6 +782,46 @@ pub struct ConfigToml {
     #[serde(default)]
     pub history: Option<History>,
 
+    /// Retention policy for rollout files under `~/.codex/sessions`.
+    pub session_retention: Option<SessionRetention>,
+
+    /// Session budget in USD; exceeding it pauses the turn for confirmation.
+    pub max_session_cost_usd: Option<f64>,
+
+    /// Command rules consulted before asking for approval.
+    pub approvals: Option<ApprovalsToml>,
+
+    /// Reporting and interruption of turns that stop making progress.
+    pub stall_watchdog: Option<StallWatchdog>,
+
+    /// CPU time, memory, open file and file size limits for shell commands.
+    pub resource_limits: Option<ResourceLimitsToml>,
+
+    /// Masking of secrets in rollouts and tool output.
+    pub redaction: Option<RedactionToml>,
+
+    /// Post-processing of shell command output before the model sees it.
+    pub exec_output: Option<ExecOutputToml>,
+
+    /// What the model sees of `!` commands run by the user.
+    pub user_shell_context: Option<UserShellContext>,
+
+    /// Speech-to-text engine for voice input.
+    pub transcription: Option<TranscriptionToml>,
+
+    /// Policies applied when the conversation is compacted.
+    pub compaction: Option<CompactionToml>,
+
+    /// Limits and retrieval settings for project memory.
+    pub memory: Option<MemoryConfig>,
+
+    /// HTTP endpoints notified of session events.
+    pub webhooks: Option<Vec<WebhookConfig>>,
+
+    /// Reasoning effort and verbosity to use for each model, keyed by slug.
+    /// The TUI records the effort picked for a model here.
+    pub model_preferences: Option<HashMap<String, ModelPreference>>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: Option<UriBasedFileOpener>,
@@ -671,6 +829,9 @@ pub struct ConfigToml {
     /// Collection of settings that are specific to the TUI.
     pub tui: Option<Tui>,
 
+    /// Settings for GUI clients of the app server.
+    pub gui: Option<Gui>,
+
     /// When set to `true`, `AgentReasoning` events will be hidden from the
     /// UI/output. Defaults to `false`.
     pub hide_agent_reasoning: Option<bool>,
@@ -715,6 +876,13 @@ pub struct ConfigToml {
     /// OTEL configuration.
     pub otel: Option<crate::config::types::OtelConfigToml>,
 
+    /// Per-model-family prompt overrides, keyed by family or slug prefix.
+    #[serde(default)]
+    pub prompt_adaptations: HashMap<String, PromptAdaptationToml>,
+
+    /// Subagent settings, including role presets under `[subagents.roles]`.
+    pub subagents: Option<SubagentsToml>,
+
     /// Tracks whether the Windows onboarding screen has been acknowledged.
     pub windows_wsl_setup_acknowledged: Option<bool>,
 
@@ -758,9 +926,28 @@ impl From<ConfigToml> for UserSavedConfig {
     }
 }
 
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
 pub struct ProjectConfig {
     pub trust_level: Option<TrustLevel>,
+
+    /// Variables set for commands run in this project, on top of
+    /// `[shell_environment_policy]`.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
+    #[serde(flatten)]
+    pub defaults: ProjectDefaults,
+}
+
+/// Settings for sessions started in a project. Each one wins over the
+/// top-level setting and the active profile, but not over a value the
+/// session was started with.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ProjectDefaults {
+    pub model: Option<String>,
+    pub model_reasoning_effort: Option<ReasoningEffort>,
+    pub approval_policy: Option<AskForApproval>,
+    pub sandbox_mode: Option<SandboxMode>,
 }
 
 impl ProjectConfig {
@@ -828,11 +1015,17 @@ impl ConfigToml {
                     network_access,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    allow_write,
+                    deny_write,
+                    network_allowlist,
                 }) => SandboxPolicy::WorkspaceWrite {
                     writable_roots: writable_roots.clone(),
                     network_access: *network_access,
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
+                    allow_write: allow_write.clone(),
+                    deny_write: deny_write.clone(),
+                    network_allowlist: allowlist_for_platform(network_allowlist),
                 },
                 None => SandboxPolicy::new_workspace_write_policy(),
             },
@@ -875,6 +1068,21 @@ impl ConfigToml {
         None
     }
 
+    /// Key under `[projects]` whose settings apply to `resolved_cwd`: the
+    /// cwd itself when it has an entry, otherwise the root of its git
+    /// repository, or the cwd outside of one.
+    pub fn project_path_for(&self, resolved_cwd: &Path) -> PathBuf {
+        let has_entry = self
+            .projects
+            .as_ref()
+            .is_some_and(|projects| projects.contains_key(&*resolved_cwd.to_string_lossy()));
+        if has_entry {
+            return resolved_cwd.to_path_buf();
+        }
+        resolve_root_git_project_for_trust(resolved_cwd)
+            .unwrap_or_else(|| resolved_cwd.to_path_buf())
+    }
+
     pub fn get_config_profile(
         &self,
         override_profile: Option<String>,
@@ -1039,14 +1247,16 @@ impl Config {
                 }
             })
             .collect();
-        let active_project = cfg
-            .get_active_project(&resolved_cwd)
-            .unwrap_or(ProjectConfig { trust_level: None });
+        let active_project = cfg.get_active_project(&resolved_cwd).unwrap_or_default();
 
         let SandboxPolicyResolution {
             policy: mut sandbox_policy,
             forced_auto_mode_downgraded_on_windows,
-        } = cfg.derive_sandbox_policy(sandbox_mode, config_profile.sandbox_mode, &resolved_cwd);
+        } = cfg.derive_sandbox_policy(
+            sandbox_mode.or(active_project.defaults.sandbox_mode),
+            config_profile.sandbox_mode,
+            &resolved_cwd,
+        );
         if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut sandbox_policy {
             for path in additional_writable_roots {
                 if !writable_roots.iter().any(|existing| existing == &path) {
@@ -1055,6 +1265,7 @@ impl Config {
             }
         }
         let approval_policy = approval_policy_override
+            .or(active_project.defaults.approval_policy)
             .or(config_profile.approval_policy)
             .or(cfg.approval_policy)
             .unwrap_or_else(|| {
@@ -1070,9 +1281,11 @@ impl Config {
             });
         let did_user_set_custom_approval_policy_or_sandbox_mode = approval_policy_override
             .is_some()
+            || active_project.defaults.approval_policy.is_some()
             || config_profile.approval_policy.is_some()
             || cfg.approval_policy.is_some()
             || sandbox_mode.is_some()
+            || active_project.defaults.sandbox_mode.is_some()
             || config_profile.sandbox_mode.is_some()
             || cfg.sandbox_mode.is_some();
 
@@ -1096,10 +1309,39 @@ impl Config {
             })?
             .clone();
 
-        let shell_environment_policy = cfg.shell_environment_policy.into();
+        let mut shell_environment_policy: ShellEnvironmentPolicy =
+            cfg.shell_environment_policy.into();
+        shell_environment_policy
+            .r#set
+            .extend(active_project.env.clone());
 
         let history = cfg.history.unwrap_or_default();
 
+        let approvals = cfg.approvals.unwrap_or_default();
+        let approval_rules = ApprovalRules::new(&approvals.auto_approve, &approvals.always_deny)
+            .map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid rule in [approvals]: {err}"),
+                )
+            })?;
+
+        let redaction = cfg.redaction.unwrap_or_default();
+        let redactor = Redactor::new(&redaction).map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid pattern in [redaction]: {err}"),
+            )
+        })?;
+
+        let exec_output = cfg.exec_output.unwrap_or_default();
+        let exec_output = ExecOutputPipeline::new(&exec_output).map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("invalid rule in [exec_output]: {err}"),
+            )
+        })?;
+
         let include_apply_patch_tool_flag = features.enabled(Feature::ApplyPatchFreeform);
         let tools_web_search_request = features.enabled(Feature::WebSearchRequest);
         let use_experimental_unified_exec_tool = features.enabled(Feature::UnifiedExec);
@@ -1120,12 +1362,16 @@ impl Config {
         let forced_login_method = cfg.forced_login_method;
 
         let model = model
+            .or(active_project.defaults.model.clone())
             .or(config_profile.model)
             .or(cfg.model)
             .unwrap_or_else(default_model);
 
         let mut model_family =
             find_family_for_model(&model).unwrap_or_else(|| derive_default_model_family(&model));
+        if model_provider_id == LOCAL_PROVIDER_ID {
+            model_family = local_model_family(model_family);
+        }
 
         if let Some(supports_reasoning_summaries) = cfg.model_supports_reasoning_summaries {
             model_family.supports_reasoning_summaries = supports_reasoning_summaries;
@@ -1134,7 +1380,7 @@ impl Config {
             model_family.reasoning_summary_format = model_reasoning_summary_format;
         }
 
-        let openai_model_info = get_model_info(&model_family);
+        let openai_model_info = get_provider_model_info(&model_provider_id, &model_family);
         let model_context_window = cfg
             .model_context_window
             .or_else(|| openai_model_info.as_ref().map(|info| info.context_window));
@@ -1212,6 +1458,7 @@ impl Config {
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             mcp_oauth_credentials_store_mode: cfg.mcp_oauth_credentials_store.unwrap_or_default(),
+            secrets: cfg.secrets.unwrap_or_default(),
             model_providers,
             project_doc_max_bytes: cfg.project_doc_max_bytes.unwrap_or(PROJECT_DOC_MAX_BYTES),
             project_doc_fallback_filenames: cfg
@@ -1230,6 +1477,19 @@ impl Config {
             tool_output_token_limit: cfg.tool_output_token_limit,
             codex_home,
             history,
+            session_retention: cfg.session_retention.unwrap_or_default(),
+            max_session_cost_usd: cfg.max_session_cost_usd,
+            approval_rules,
+            stall_watchdog: cfg.stall_watchdog.unwrap_or_default(),
+            resource_limits: cfg.resource_limits.unwrap_or_default(),
+            redactor,
+            exec_output,
+            user_shell_context: cfg.user_shell_context.unwrap_or_default(),
+            transcription: cfg.transcription.unwrap_or_default(),
+            compaction: cfg.compaction.unwrap_or_default(),
+            memory: cfg.memory.unwrap_or_default(),
+            webhooks: cfg.webhooks.unwrap_or_default(),
+            model_preferences: cfg.model_preferences.unwrap_or_default(),
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
             codex_linux_sandbox_exe,
 
@@ -1238,8 +1498,10 @@ impl Config {
                 .show_raw_agent_reasoning
                 .or(show_raw_agent_reasoning)
                 .unwrap_or(false),
-            model_reasoning_effort: config_profile
+            model_reasoning_effort: active_project
+                .defaults
                 .model_reasoning_effort
+                .or(config_profile.model_reasoning_effort)
                 .or(cfg.model_reasoning_effort),
             model_reasoning_summary: config_profile
                 .model_reasoning_summary
@@ -1269,6 +1531,21 @@ impl Config {
                 .as_ref()
                 .map(|t| t.notifications.clone())
                 .unwrap_or_default(),
+            tui_notification_method: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.notification_method)
+                .unwrap_or_default(),
+            tui_notification_methods: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.notification_methods.clone())
+                .unwrap_or_default(),
+            gui_notifications: cfg
+                .gui
+                .as_ref()
+                .map(|g| g.notifications.clone())
+                .unwrap_or_default(),
             show_rate_limits_in_footer: cfg
                 .tui
                 .as_ref()
@@ -1284,6 +1561,18 @@ impl Config {
             animations: cfg.tui.as_ref().map(|t| t.animations).unwrap_or(true),
             scroll_lines: cfg.tui.as_ref().map(|t| t.scroll_lines).unwrap_or(3),
             scroll_half_page: cfg.tui.as_ref().map(|t| t.scroll_half_page).unwrap_or(true),
+            max_transcript_width: Some(
+                cfg.tui
+                    .as_ref()
+                    .map_or(DEFAULT_MAX_TRANSCRIPT_WIDTH, |t| t.max_transcript_width),
+            )
+            .filter(|width| *width > 0),
+            tui_theme: cfg.tui.as_ref().and_then(|t| t.theme.clone()),
+            tui_keys: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.keys.clone())
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1291,12 +1580,17 @@ impl Config {
                     .environment
                     .unwrap_or(DEFAULT_OTEL_ENVIRONMENT.to_string());
                 let exporter = t.exporter.unwrap_or(OtelExporterKind::None);
+                let trace_spans = t.trace_spans.unwrap_or(true);
                 OtelConfig {
                     log_user_prompt,
                     environment,
                     exporter,
+                    trace_spans,
                 }
             },
+            prompt_adaptations: cfg.prompt_adaptations,
+            subagent_roles: cfg.subagents.map(|s| s.roles).unwrap_or_default(),
+            subagent_scratchpad: None,
         };
         Ok(config)
     }
@@ -1360,6 +1654,19 @@ impl Config {
     }
 }
 
+/// The configured `network_allowlist`, or none outside macOS: only Seatbelt
+/// can confine a command to the local proxy that enforces it, so elsewhere
+/// sandboxed network stays fully off.
+fn allowlist_for_platform(allowlist: &[String]) -> Vec<String> {
+    if cfg!(target_os = "macos") || allowlist.is_empty() {
+        return allowlist.to_vec();
+    }
+    tracing::warn!(
+        "sandbox_workspace_write.network_allowlist is only supported on macOS; keeping network disabled"
+    );
+    Vec::new()
+}
+
 fn default_model() -> String {
     OPENAI_DEFAULT_MODEL.to_string()
 }
@@ -1464,6 +1771,98 @@ persistence = "none"
         assert_eq!(tui.notifications, Notifications::Enabled(true));
     }
 
+    #[test]
+    fn gui_notifications_can_be_filtered_by_type() {
+        let cfg = r#"
+[gui]
+notifications = ["approval-requested", "error"]
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("gui config should parse");
+        let notifications = parsed.gui.expect("config should include gui section").notifications;
+
+        assert!(notifications.allows("approval-requested"));
+        assert!(notifications.allows("error"));
+        assert!(!notifications.allows("agent-turn-complete"));
+    }
+
+    #[test]
+    fn subagent_roles_are_loaded_from_config() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[subagents.roles.researcher]
+description = "Read-only investigation"
+model = "gpt-5.1-codex-mini"
+sandbox_mode = "read-only"
+
+[subagents.roles.builder]
+sandbox_mode = "workspace-write"
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.subagent_roles,
+            BTreeMap::from([
+                (
+                    "builder".to_string(),
+                    SubagentRole {
+                        description: None,
+                        model: None,
+                        sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+                    },
+                ),
+                (
+                    "researcher".to_string(),
+                    SubagentRole {
+                        description: Some("Read-only investigation".to_string()),
+                        model: Some("gpt-5.1-codex-mini".to_string()),
+                        sandbox_mode: Some(SandboxMode::ReadOnly),
+                    },
+                ),
+            ])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn memory_limits_are_loaded_from_config() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[memory]
+max_memories = 500
+ttl_days = 90
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.memory,
+            MemoryConfig {
+                max_memories: 500,
+                ttl_days: Some(90),
+                ..MemoryConfig::default()
+            }
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_sandbox_config_parsing() {
         let sandbox_full_access = r#"
@@ -1547,6 +1946,9 @@ exclude_slash_tmp = true
                         network_access: false,
                         exclude_tmpdir_env_var: true,
                         exclude_slash_tmp: true,
+                        allow_write: vec![],
+                        deny_write: vec![],
+                        network_allowlist: vec![],
                     },
                     forced_auto_mode_downgraded_on_windows: false,
                 }
@@ -1592,6 +1994,9 @@ trust_level = "trusted"
                         network_access: false,
                         exclude_tmpdir_env_var: true,
                         exclude_slash_tmp: true,
+                        allow_write: vec![],
+                        deny_write: vec![],
+                        network_allowlist: vec![],
                     },
                     forced_auto_mode_downgraded_on_windows: false,
                 }
@@ -3002,12 +3407,26 @@ model_verbosity = "high"
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: HashMap::new(),
                 mcp_oauth_credentials_store_mode: Default::default(),
+                secrets: Secrets::default(),
                 model_providers: fixture.model_provider_map.clone(),
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
                 codex_home: fixture.codex_home(),
                 history: History::default(),
+                session_retention: SessionRetention::default(),
+                max_session_cost_usd: None,
+                approval_rules: ApprovalRules::default(),
+                stall_watchdog: StallWatchdog::default(),
+                resource_limits: ResourceLimitsToml::default(),
+                redactor: Redactor::default(),
+                exec_output: ExecOutputPipeline::default(),
+                user_shell_context: UserShellContext::default(),
+                transcription: TranscriptionToml::default(),
+                compaction: CompactionToml::default(),
+                memory: MemoryConfig::default(),
+                webhooks: Vec::new(),
+                model_preferences: HashMap::new(),
                 file_opener: UriBasedFileOpener::VsCode,
                 codex_linux_sandbox_exe: None,
                 hide_agent_reasoning: false,
@@ -3028,19 +3447,28 @@ model_verbosity = "high"
                 use_experimental_use_rmcp_client: false,
                 features: Features::with_defaults(),
                 active_profile: Some("o3".to_string()),
-                active_project: ProjectConfig { trust_level: None },
+                active_project: ProjectConfig::default(),
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
                 check_for_update_on_startup: true,
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
+                tui_notification_method: Default::default(),
+                tui_notification_methods: HashMap::new(),
+                gui_notifications: Default::default(),
                 show_rate_limits_in_footer: true,
                 plan_detail: PlanDetailPreference::default(),
                 subagent_max_tasks: 4,
                 animations: true,
                 scroll_lines: 3,
                 scroll_half_page: true,
+                max_transcript_width: Some(120),
+                tui_theme: None,
+                tui_keys: BTreeMap::new(),
                 otel: OtelConfig::default(),
+                prompt_adaptations: HashMap::new(),
+                subagent_roles: BTreeMap::new(),
+                subagent_scratchpad: None,
             },
             o3_profile_config
         );
@@ -3080,12 +3508,26 @@ model_verbosity = "high"
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
+            secrets: Secrets::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             codex_home: fixture.codex_home(),
             history: History::default(),
+            session_retention: SessionRetention::default(),
+            max_session_cost_usd: None,
+            approval_rules: ApprovalRules::default(),
+            stall_watchdog: StallWatchdog::default(),
+            resource_limits: ResourceLimitsToml::default(),
+            redactor: Redactor::default(),
+            exec_output: ExecOutputPipeline::default(),
+            user_shell_context: UserShellContext::default(),
+            transcription: TranscriptionToml::default(),
+            compaction: CompactionToml::default(),
+            memory: MemoryConfig::default(),
+            webhooks: Vec::new(),
+            model_preferences: HashMap::new(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3106,19 +3548,28 @@ model_verbosity = "high"
             use_experimental_use_rmcp_client: false,
             features: Features::with_defaults(),
             active_profile: Some("gpt3".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig::default(),
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_notification_method: Default::default(),
+            tui_notification_methods: HashMap::new(),
+            gui_notifications: Default::default(),
             show_rate_limits_in_footer: true,
             plan_detail: PlanDetailPreference::default(),
             subagent_max_tasks: 4,
             animations: true,
             scroll_lines: 3,
             scroll_half_page: true,
+            max_transcript_width: Some(120),
+            tui_theme: None,
+            tui_keys: BTreeMap::new(),
             otel: OtelConfig::default(),
+            prompt_adaptations: HashMap::new(),
+            subagent_roles: BTreeMap::new(),
+            subagent_scratchpad: None,
         };
 
         assert_eq!(expected_gpt3_profile_config, gpt3_profile_config);
@@ -3173,12 +3624,26 @@ model_verbosity = "high"
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
+            secrets: Secrets::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             codex_home: fixture.codex_home(),
             history: History::default(),
+            session_retention: SessionRetention::default(),
+            max_session_cost_usd: None,
+            approval_rules: ApprovalRules::default(),
+            stall_watchdog: StallWatchdog::default(),
+            resource_limits: ResourceLimitsToml::default(),
+            redactor: Redactor::default(),
+            exec_output: ExecOutputPipeline::default(),
+            user_shell_context: UserShellContext::default(),
+            transcription: TranscriptionToml::default(),
+            compaction: CompactionToml::default(),
+            memory: MemoryConfig::default(),
+            webhooks: Vec::new(),
+            model_preferences: HashMap::new(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3199,19 +3664,28 @@ model_verbosity = "high"
             use_experimental_use_rmcp_client: false,
             features: Features::with_defaults(),
             active_profile: Some("zdr".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig::default(),
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_notification_method: Default::default(),
+            tui_notification_methods: HashMap::new(),
+            gui_notifications: Default::default(),
             show_rate_limits_in_footer: true,
             plan_detail: PlanDetailPreference::default(),
             subagent_max_tasks: 4,
             animations: true,
             scroll_lines: 3,
             scroll_half_page: true,
+            max_transcript_width: Some(120),
+            tui_theme: None,
+            tui_keys: BTreeMap::new(),
             otel: OtelConfig::default(),
+            prompt_adaptations: HashMap::new(),
+            subagent_roles: BTreeMap::new(),
+            subagent_scratchpad: None,
         };
 
         assert_eq!(expected_zdr_profile_config, zdr_profile_config);
@@ -3252,12 +3726,26 @@ model_verbosity = "high"
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
+            secrets: Secrets::default(),
             model_providers: fixture.model_provider_map.clone(),
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
             codex_home: fixture.codex_home(),
             history: History::default(),
+            session_retention: SessionRetention::default(),
+            max_session_cost_usd: None,
+            approval_rules: ApprovalRules::default(),
+            stall_watchdog: StallWatchdog::default(),
+            resource_limits: ResourceLimitsToml::default(),
+            redactor: Redactor::default(),
+            exec_output: ExecOutputPipeline::default(),
+            user_shell_context: UserShellContext::default(),
+            transcription: TranscriptionToml::default(),
+            compaction: CompactionToml::default(),
+            memory: MemoryConfig::default(),
+            webhooks: Vec::new(),
+            model_preferences: HashMap::new(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3278,19 +3766,28 @@ model_verbosity = "high"
             use_experimental_use_rmcp_client: false,
             features: Features::with_defaults(),
             active_profile: Some("gpt5".to_string()),
-            active_project: ProjectConfig { trust_level: None },
+            active_project: ProjectConfig::default(),
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
+            tui_notification_method: Default::default(),
+            tui_notification_methods: HashMap::new(),
+            gui_notifications: Default::default(),
             show_rate_limits_in_footer: true,
             plan_detail: PlanDetailPreference::default(),
             subagent_max_tasks: 4,
             animations: true,
             scroll_lines: 3,
             scroll_half_page: true,
+            max_transcript_width: Some(120),
+            tui_theme: None,
+            tui_keys: BTreeMap::new(),
             otel: OtelConfig::default(),
+            prompt_adaptations: HashMap::new(),
+            subagent_roles: BTreeMap::new(),
+            subagent_scratchpad: None,
         };
 
         assert_eq!(expected_gpt5_profile_config, gpt5_profile_config);
@@ -3567,6 +4064,7 @@ trust_level = "untrusted"
             test_path.to_string_lossy().to_string(),
             ProjectConfig {
                 trust_level: Some(TrustLevel::Untrusted),
+                ..Default::default()
             },
         );
 
@@ -3606,6 +4104,56 @@ trust_level = "untrusted"
 
         Ok(())
     }
+
+    #[test]
+    fn project_defaults_apply_unless_the_session_overrides_them() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let test_project_dir = TempDir::new()?;
+        let test_path = test_project_dir.path();
+        let cfg: ConfigToml = toml::from_str(&format!(
+            r#"
+model = "gpt-5"
+approval_policy = "on-request"
+
+[projects.'{}']
+model = "gpt-5-codex"
+model_reasoning_effort = "high"
+approval_policy = "never"
+sandbox_mode = "read-only"
+"#,
+            test_path.display()
+        ))
+        .expect("parse config");
+        assert_eq!(cfg.project_path_for(test_path), test_path.to_path_buf());
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg.clone(),
+            ConfigOverrides {
+                cwd: Some(test_path.to_path_buf()),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(config.model, "gpt-5-codex");
+        assert_eq!(config.model_reasoning_effort, Some(ReasoningEffort::High));
+        assert_eq!(config.approval_policy, AskForApproval::Never);
+        assert_eq!(config.sandbox_policy, SandboxPolicy::new_read_only_policy());
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides {
+                cwd: Some(test_path.to_path_buf()),
+                model: Some("o3".to_string()),
+                approval_policy: Some(AskForApproval::OnFailure),
+                ..Default::default()
+            },
+            codex_home.path().to_path_buf(),
+        )?;
+        assert_eq!(config.model, "o3");
+        assert_eq!(config.approval_policy, AskForApproval::OnFailure);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
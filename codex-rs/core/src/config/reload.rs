@@ -0,0 +1,364 @@
+//! Reloads `config.toml` while sessions are running.
+//!
+//! A watcher on `CODEX_HOME` reloads the file whenever it changes and compares
+//! it with the previous load. Settings that are safe to change mid-session are
+//! applied in place; settings that shape the session itself (model provider,
+//! sandbox, MCP servers, ...) are only reported, since they take effect in the
+//! next session. Either way an [`EventMsg::ConfigChanged`] lists the keys.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Weak;
+use std::time::Duration;
+
+use codex_protocol::protocol::ConfigChangedEvent;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::WarningEvent;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::codex::INITIAL_SUBMIT_ID;
+use crate::codex::Session;
+use crate::config::CONFIG_TOML_FILE;
+use crate::config::Config;
+use crate::config::ConfigOverrides;
+use crate::config::load_config_as_toml_with_cli_overrides;
+use crate::config::types::CompactionPolicy;
+use crate::config::types::WebhookConfig;
+
+/// Editors often write a file in several steps; wait for them to settle.
+const RELOAD_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// Loads `config.toml` the way a new session in `cwd` would, without the CLI
+/// or harness overrides of any running session.
+pub async fn load_from_disk(
+    codex_home: &Path,
+    cwd: &Path,
+    active_profile: Option<String>,
+) -> std::io::Result<Config> {
+    let cfg = load_config_as_toml_with_cli_overrides(codex_home, Vec::new()).await?;
+    Config::load_from_base_config_with_overrides(
+        cfg,
+        ConfigOverrides {
+            cwd: Some(cwd.to_path_buf()),
+            config_profile: active_profile,
+            ..Default::default()
+        },
+        codex_home.to_path_buf(),
+    )
+}
+
+/// Compares two loads of `config.toml`. Returns `None` when nothing this
+/// module tracks changed.
+pub fn diff_configs(old: &Config, new: &Config) -> Option<ConfigChangedEvent> {
+    let applied = [
+        ("notify", old.notify != new.notify),
+        (
+            "show_raw_agent_reasoning",
+            old.show_raw_agent_reasoning != new.show_raw_agent_reasoning,
+        ),
+        (
+            "hide_agent_reasoning",
+            old.hide_agent_reasoning != new.hide_agent_reasoning,
+        ),
+        (
+            "max_session_cost_usd",
+            old.max_session_cost_usd != new.max_session_cost_usd,
+        ),
+        ("file_opener", old.file_opener != new.file_opener),
+        (
+            "disable_paste_burst",
+            old.disable_paste_burst != new.disable_paste_burst,
+        ),
+        (
+            "tui.notifications",
+            old.tui_notifications != new.tui_notifications,
+        ),
+        (
+            "tui.notification_method",
+            old.tui_notification_method != new.tui_notification_method
+                || old.tui_notification_methods != new.tui_notification_methods,
+        ),
+        ("tui.animations", old.animations != new.animations),
+        (
+            "tui.show_rate_limits_in_footer",
+            old.show_rate_limits_in_footer != new.show_rate_limits_in_footer,
+        ),
+        ("tui.plan_detail", old.plan_detail != new.plan_detail),
+        ("tui.scroll_lines", old.scroll_lines != new.scroll_lines),
+        (
+            "tui.scroll_half_page",
+            old.scroll_half_page != new.scroll_half_page,
+        ),
+        (
+            "tui.max_transcript_width",
+            old.max_transcript_width != new.max_transcript_width,
+        ),
+        ("tui.theme", old.tui_theme != new.tui_theme),
+        ("tui.keys", old.tui_keys != new.tui_keys),
+        ("compaction", old.compaction != new.compaction),
+        ("webhooks", old.webhooks != new.webhooks),
+    ];
+    let requires_new_session = [
+        ("model", old.model != new.model),
+        (
+            "model_provider",
+            old.model_provider_id != new.model_provider_id
+                || old.model_provider != new.model_provider,
+        ),
+        (
+            "approval_policy",
+            old.approval_policy != new.approval_policy,
+        ),
+        ("sandbox_mode", old.sandbox_policy != new.sandbox_policy),
+        ("mcp_servers", old.mcp_servers != new.mcp_servers),
+        ("features", old.features != new.features),
+    ];
+
+    let changed_keys = |keys: &[(&str, bool)]| -> Vec<String> {
+        keys.iter()
+            .filter(|(_, changed)| *changed)
+            .map(|(key, _)| (*key).to_string())
+            .collect()
+    };
+    let event = ConfigChangedEvent {
+        applied: changed_keys(&applied),
+        requires_new_session: changed_keys(&requires_new_session),
+    };
+    (!event.applied.is_empty() || !event.requires_new_session.is_empty()).then_some(event)
+}
+
+impl Config {
+    /// Copies the settings named in `keys` (as reported in
+    /// [`ConfigChangedEvent::applied`]) from a freshly loaded config.
+    pub fn apply_reloaded(&mut self, keys: &[String], reloaded: &Config) {
+        for key in keys {
+            match key.as_str() {
+                "notify" => self.notify = reloaded.notify.clone(),
+                "show_raw_agent_reasoning" => {
+                    self.show_raw_agent_reasoning = reloaded.show_raw_agent_reasoning;
+                }
+                "hide_agent_reasoning" => {
+                    self.hide_agent_reasoning = reloaded.hide_agent_reasoning;
+                }
+                "max_session_cost_usd" => self.max_session_cost_usd = reloaded.max_session_cost_usd,
+                "file_opener" => self.file_opener = reloaded.file_opener,
+                "disable_paste_burst" => self.disable_paste_burst = reloaded.disable_paste_burst,
+                "tui.notifications" => self.tui_notifications = reloaded.tui_notifications.clone(),
+                "tui.notification_method" => {
+                    self.tui_notification_method = reloaded.tui_notification_method;
+                    self.tui_notification_methods = reloaded.tui_notification_methods.clone();
+                }
+                "tui.animations" => self.animations = reloaded.animations,
+                "tui.show_rate_limits_in_footer" => {
+                    self.show_rate_limits_in_footer = reloaded.show_rate_limits_in_footer;
+                }
+                "tui.plan_detail" => self.plan_detail = reloaded.plan_detail,
+                "tui.scroll_lines" => self.scroll_lines = reloaded.scroll_lines,
+                "tui.scroll_half_page" => self.scroll_half_page = reloaded.scroll_half_page,
+                "tui.max_transcript_width" => {
+                    self.max_transcript_width = reloaded.max_transcript_width;
+                }
+                "tui.theme" => self.tui_theme = reloaded.tui_theme.clone(),
+                "tui.keys" => self.tui_keys = reloaded.tui_keys.clone(),
+                "compaction" => self.compaction = reloaded.compaction.clone(),
+                "webhooks" => self.webhooks = reloaded.webhooks.clone(),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Settings a running session reads on every use so reloads take effect.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ReloadableSettings {
+    pub(crate) notify: Option<Vec<String>>,
+    pub(crate) show_raw_agent_reasoning: bool,
+    pub(crate) max_session_cost_usd: Option<f64>,
+    pub(crate) compaction_policies: Vec<CompactionPolicy>,
+    pub(crate) webhooks: Vec<WebhookConfig>,
+}
+
+impl ReloadableSettings {
+    pub(crate) fn from_config(config: &Config) -> Self {
+        Self {
+            notify: config.notify.clone(),
+            show_raw_agent_reasoning: config.show_raw_agent_reasoning,
+            max_session_cost_usd: config.max_session_cost_usd,
+            compaction_policies: config.compaction.policies.clone(),
+            webhooks: config.webhooks.clone(),
+        }
+    }
+}
+
+/// The last load of `config.toml` and the session's effective config, kept
+/// so each reload can tell what changed.
+pub(crate) struct ConfigReloadState {
+    baseline: Config,
+    live: Config,
+}
+
+impl ConfigReloadState {
+    /// `live` is the session's effective config, overrides included.
+    pub(crate) async fn new(live: Config) -> std::io::Result<Self> {
+        let baseline =
+            load_from_disk(&live.codex_home, &live.cwd, live.active_profile.clone()).await?;
+        Ok(Self { baseline, live })
+    }
+}
+
+/// Re-reads `config.toml` and applies the reloadable keys that changed since
+/// the last load. Returns `Ok(None)` when `session` does not track its config
+/// file (e.g. subagents), and an empty event when nothing changed.
+pub(crate) async fn reload_session_config(
+    session: &Session,
+) -> std::io::Result<Option<ConfigChangedEvent>> {
+    let mut guard = session.services.config_reload.lock().await;
+    let Some(state) = guard.as_mut() else {
+        return Ok(None);
+    };
+    let reloaded = load_from_disk(
+        &state.live.codex_home,
+        &state.live.cwd,
+        state.live.active_profile.clone(),
+    )
+    .await?;
+    let event = diff_configs(&state.baseline, &reloaded).unwrap_or_default();
+    state.live.apply_reloaded(&event.applied, &reloaded);
+    session.set_reloadable_settings(ReloadableSettings::from_config(&state.live));
+    state.baseline = reloaded;
+    Ok(Some(event))
+}
+
+/// Watches `config.toml` for the lifetime of `session`, applying reloadable
+/// settings and emitting `ConfigChanged` events. `config` is the session's
+/// effective config, overrides included.
+pub(crate) fn spawn_config_watcher(session: Weak<Session>, config: Config) {
+    tokio::spawn(async move {
+        let codex_home = config.codex_home.clone();
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let _watcher = match watch_config_file(&codex_home, tx) {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                warn!("failed to watch {}: {err}", codex_home.display());
+                return;
+            }
+        };
+        let state = match ConfigReloadState::new(config).await {
+            Ok(state) => state,
+            Err(err) => {
+                warn!("failed to load config for reload baseline: {err}");
+                return;
+            }
+        };
+        match session.upgrade() {
+            Some(session) => *session.services.config_reload.lock().await = Some(state),
+            None => return,
+        }
+
+        while rx.recv().await.is_some() {
+            tokio::time::sleep(RELOAD_DEBOUNCE).await;
+            while rx.try_recv().is_ok() {}
+
+            let Some(session) = session.upgrade() else {
+                return;
+            };
+            let msg = match reload_session_config(&session).await {
+                Ok(Some(event)) if event != ConfigChangedEvent::default() => {
+                    EventMsg::ConfigChanged(event)
+                }
+                Ok(_) => continue,
+                Err(err) => EventMsg::Warning(WarningEvent {
+                    message: format!("Ignoring config.toml change: {err}"),
+                }),
+            };
+            session
+                .send_event_raw(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg,
+                })
+                .await;
+        }
+    });
+}
+
+fn watch_config_file(
+    codex_home: &Path,
+    tx: mpsc::UnboundedSender<()>,
+) -> notify::Result<RecommendedWatcher> {
+    let config_path: PathBuf = codex_home.join(CONFIG_TOML_FILE);
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        if let Ok(event) = res
+            && event.paths.iter().any(|path| path == &config_path)
+        {
+            let _ = tx.send(());
+        }
+    })?;
+    watcher.watch(codex_home, RecursiveMode::NonRecursive)?;
+    Ok(watcher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigToml;
+    use crate::config::types::Notifications;
+    use pretty_assertions::assert_eq;
+
+    fn base_config(codex_home: &Path) -> Config {
+        Config::load_from_base_config_with_overrides(
+            ConfigToml::default(),
+            ConfigOverrides {
+                cwd: Some(codex_home.to_path_buf()),
+                ..Default::default()
+            },
+            codex_home.to_path_buf(),
+        )
+        .expect("load default config")
+    }
+
+    #[test]
+    fn diff_separates_live_and_session_settings() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let old = base_config(codex_home.path());
+        assert_eq!(diff_configs(&old, &old), None);
+
+        let mut new = old.clone();
+        new.tui_notifications = Notifications::Enabled(false);
+        new.max_session_cost_usd = Some(5.0);
+        new.model_provider_id = "oss".to_string();
+
+        assert_eq!(
+            diff_configs(&old, &new),
+            Some(ConfigChangedEvent {
+                applied: vec![
+                    "max_session_cost_usd".to_string(),
+                    "tui.notifications".to_string(),
+                ],
+                requires_new_session: vec!["model_provider".to_string()],
+            })
+        );
+    }
+
+    #[test]
+    fn apply_reloaded_copies_only_named_keys() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let mut live = base_config(codex_home.path());
+        live.show_raw_agent_reasoning = true;
+
+        let mut reloaded = live.clone();
+        reloaded.show_raw_agent_reasoning = false;
+        reloaded.notify = Some(vec!["notify-send".to_string()]);
+        reloaded.model = "other-model".to_string();
+
+        live.apply_reloaded(&["notify".to_string()], &reloaded);
+
+        assert_eq!(live.notify, Some(vec!["notify-send".to_string()]));
+        assert!(live.show_raw_agent_reasoning);
+        assert_ne!(live.model, "other-model");
+    }
+}
@@ -3,7 +3,14 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use crate::resource_limits::ResourceLimits;
+pub use codex_protocol::config_types::PlanDetailPreference;
+use codex_protocol::config_types::ReasoningEffort;
+use codex_protocol::config_types::SandboxMode;
+use codex_protocol::config_types::Verbosity;
+pub use codex_protocol::config_types::WebhookEvent;
 use serde::Deserializer;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
@@ -271,6 +278,291 @@ pub enum HistoryPersistence {
     None,
 }
 
+/// How much of a `!` command run by the user is added to the model's context.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum UserShellContext {
+    /// The command, exit code, duration, and (truncated) output.
+    #[default]
+    Output,
+    /// The command, exit code, and duration only.
+    Summary,
+    /// Nothing; the command is only shown in the transcript.
+    None,
+}
+
+/// Settings remembered for one model, from `[model_preferences."<model>"]`.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModelPreference {
+    /// Reasoning effort used when switching to the model.
+    pub reasoning_effort: Option<ReasoningEffort>,
+    /// `text.verbosity` sent with requests to the model.
+    pub verbosity: Option<Verbosity>,
+}
+
+/// An HTTP endpoint that receives session events, from `[[webhooks]]`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct WebhookConfig {
+    pub url: String,
+
+    /// Events to deliver; all of them when omitted.
+    #[serde(default = "all_webhook_events")]
+    pub events: Vec<WebhookEvent>,
+
+    /// Environment variable holding the secret used to sign payloads with
+    /// HMAC-SHA256. Payloads are unsigned when unset.
+    pub secret_env_var: Option<String>,
+
+    /// Signing secret of a webhook registered with `Op::SetWebhook`; takes
+    /// precedence over `secret_env_var`.
+    #[serde(skip)]
+    pub secret: Option<String>,
+}
+
+pub(crate) fn all_webhook_events() -> Vec<WebhookEvent> {
+    vec![
+        WebhookEvent::TurnComplete,
+        WebhookEvent::ApprovalNeeded,
+        WebhookEvent::Error,
+    ]
+}
+
+/// Step of context compaction, from `compaction.policies`.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CompactionPolicy {
+    /// Before summarizing, drop the oldest tool calls and their output.
+    DropOldestToolsFirst,
+    /// Before summarizing, replace reasoning items with their summary text.
+    SummarizeReasoning,
+    /// Carry messages pinned with `/pin` over every compaction verbatim.
+    KeepPinnedMessages,
+}
+
+impl CompactionPolicy {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            CompactionPolicy::DropOldestToolsFirst => "drop-oldest-tools-first",
+            CompactionPolicy::SummarizeReasoning => "summarize-reasoning",
+            CompactionPolicy::KeepPinnedMessages => "keep-pinned-messages",
+        }
+    }
+}
+
+/// How the conversation is shrunk when it nears the context window, from
+/// `[compaction]`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct CompactionToml {
+    /// Applied together. When the local policies free enough room, automatic
+    /// compaction skips the summary request.
+    pub policies: Vec<CompactionPolicy>,
+}
+
+impl Default for CompactionToml {
+    fn default() -> Self {
+        Self {
+            policies: vec![CompactionPolicy::KeepPinnedMessages],
+        }
+    }
+}
+
+/// Speech-to-text engine used for voice input.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TranscriptionEngine {
+    /// whisper.cpp when a model is configured, falling back to the API.
+    #[default]
+    Auto,
+    /// Only the local whisper.cpp binary.
+    WhisperCpp,
+    /// Only the OpenAI transcription API.
+    Api,
+}
+
+/// Voice input settings, from `[transcription]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct TranscriptionToml {
+    pub engine: TranscriptionEngine,
+
+    /// whisper.cpp executable; defaults to `whisper-cli` on `PATH`.
+    pub whisper_cpp_binary: Option<PathBuf>,
+
+    /// ggml model file for whisper.cpp. Local transcription is disabled
+    /// without one.
+    pub whisper_cpp_model: Option<PathBuf>,
+
+    /// Base URL of an OpenAI-compatible transcription API.
+    pub api_base_url: Option<String>,
+
+    /// Model for the transcription API; defaults to `whisper-1`.
+    pub api_model: Option<String>,
+
+    /// ISO-639-1 language of the recordings; detected when unset.
+    pub language: Option<String>,
+}
+
+/// Retention policy for rollout files under `~/.codex/sessions`.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Default)]
+pub struct SessionRetention {
+    /// Compress sessions not modified for this many days into
+    /// `~/.codex/archived_sessions`.
+    pub archive_after_days: Option<i64>,
+
+    /// Delete sessions, active or archived, not modified for this many days.
+    pub delete_after_days: Option<i64>,
+}
+
+/// Where `secret://name` values in `mcp_servers` are looked up, from
+/// `[secrets]`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Secrets {
+    /// Providers to ask, in order, until one has the secret.
+    pub providers: Vec<SecretProviderKind>,
+
+    /// 1Password vault for secret names that do not name one.
+    pub onepassword_vault: String,
+}
+
+impl Default for Secrets {
+    fn default() -> Self {
+        Self {
+            providers: vec![SecretProviderKind::Env, SecretProviderKind::Keychain],
+            onepassword_vault: "Private".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SecretProviderKind {
+    /// `CODEX_SECRET_<NAME>` environment variables.
+    Env,
+    /// The OS keychain, under the `Codex Secrets` service.
+    Keychain,
+    /// 1Password, through the `op` CLI.
+    #[serde(rename = "1password")]
+    OnePassword,
+}
+
+/// Detection of turns that stop making progress, from `[stall_watchdog]`.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq)]
+#[serde(default)]
+pub struct StallWatchdog {
+    /// Seconds without output, tool activity, or provider traffic before a
+    /// turn counts as stalled. `0` disables the watchdog.
+    pub timeout_secs: i64,
+
+    /// Interrupt a stalled turn with a retriable error instead of only
+    /// reporting it.
+    pub auto_interrupt: bool,
+}
+
+impl Default for StallWatchdog {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 300,
+            auto_interrupt: false,
+        }
+    }
+}
+
+/// Limits on the processes spawned for shell commands, from
+/// `[resource_limits]`, with per-sandbox-mode overrides such as
+/// `[resource_limits.workspace-write]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct ResourceLimitsToml {
+    #[serde(flatten)]
+    pub defaults: ResourceLimits,
+    pub read_only: Option<ResourceLimits>,
+    pub workspace_write: Option<ResourceLimits>,
+    pub danger_full_access: Option<ResourceLimits>,
+}
+
+/// Command rules consulted before asking for approval, from `[approvals]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ApprovalsToml {
+    /// Commands that run without an approval prompt, e.g. `"cargo test*"`.
+    #[serde(default)]
+    pub auto_approve: Vec<String>,
+
+    /// Commands that are always rejected, e.g. `"rm -rf*"`.
+    #[serde(default)]
+    pub always_deny: Vec<String>,
+}
+
+/// Masking of secrets before they are persisted or sent back to the model,
+/// from `[redaction]`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct RedactionToml {
+    /// Apply the built-in credential patterns and `patterns`.
+    pub enabled: bool,
+
+    /// Extra regexes whose matches are replaced with `[REDACTED]`.
+    pub patterns: Vec<String>,
+}
+
+impl Default for RedactionToml {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+/// Post-processing of shell command output before the model sees it, from
+/// `[exec_output]`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ExecOutputToml {
+    /// Remove ANSI escape sequences such as colors and cursor movement.
+    pub strip_ansi: bool,
+
+    /// Per-command settings; the first rule matching a command applies.
+    pub rules: Vec<ExecOutputRuleToml>,
+
+    /// With `streaming_exec`, the longest a command runs before its output so
+    /// far is handed to the model.
+    pub stream_interval_ms: u64,
+
+    /// With `streaming_exec`, the most output tokens one command sends to the
+    /// model over all its chunks.
+    pub stream_budget_tokens: usize,
+}
+
+impl Default for ExecOutputToml {
+    fn default() -> Self {
+        Self {
+            strip_ansi: true,
+            rules: Vec::new(),
+            stream_interval_ms: 2_000,
+            stream_budget_tokens: 20_000,
+        }
+    }
+}
+
+/// One `[[exec_output.rules]]` entry.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ExecOutputRuleToml {
+    /// Commands the rule applies to, as globs (`"cargo build*"`) or
+    /// `/regex/` patterns like `[approvals]` rules.
+    pub commands: Vec<String>,
+
+    /// Lines kept from the start of long output.
+    pub head_lines: Option<usize>,
+
+    /// Lines kept from the end of long output.
+    pub tail_lines: Option<usize>,
+
+    /// Summarize output longer than this many lines with a small model.
+    pub summarize_over_lines: Option<usize>,
+}
+
 // ===== OTEL configuration =====
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -323,6 +615,9 @@ pub struct OtelConfigToml {
 
     /// Exporter to use. Defaults to `otlp-file`.
     pub exporter: Option<OtelExporterKind>,
+
+    /// Export turns and tool calls as nested spans. Defaults to true.
+    pub trace_spans: Option<bool>,
 }
 
 /// Effective OTEL settings after defaults are applied.
@@ -331,6 +626,7 @@ pub struct OtelConfig {
     pub log_user_prompt: bool,
     pub environment: String,
     pub exporter: OtelExporterKind,
+    pub trace_spans: bool,
 }
 
 impl Default for OtelConfig {
@@ -339,6 +635,7 @@ impl Default for OtelConfig {
             log_user_prompt: false,
             environment: DEFAULT_OTEL_ENVIRONMENT.to_owned(),
             exporter: OtelExporterKind::None,
+            trace_spans: true,
         }
     }
 }
@@ -356,18 +653,55 @@ impl Default for Notifications {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+impl Notifications {
+    /// Whether notifications of `kind` (e.g. `"approval-requested"`) are on.
+    pub fn allows(&self, kind: &str) -> bool {
+        match self {
+            Self::Enabled(enabled) => *enabled,
+            Self::Custom(allowed) => allowed.iter().any(|allowed| allowed == kind),
+        }
+    }
+}
+
+/// Transport used for TUI notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
 #[serde(rename_all = "snake_case")]
-pub enum PlanDetailPreference {
+pub enum NotificationMethod {
+    /// Pick a transport from the terminal and multiplexer in use.
+    #[default]
     Auto,
-    Coarse,
-    Detailed,
+    /// OSC 9 desktop notification (iTerm2, WezTerm, Ghostty, kitty).
+    Osc9,
+    /// OSC 777 desktop notification (VTE-based terminals, foot, urxvt).
+    Osc777,
+    /// Terminal bell.
+    Bell,
+    /// `tmux display-message` in the tmux status line.
+    Tmux,
 }
 
-impl Default for PlanDetailPreference {
-    fn default() -> Self {
-        PlanDetailPreference::Auto
-    }
+/// Prompt adjustments applied when the active model belongs to a given
+/// family. Keyed by model family or slug prefix under `[prompt_adaptations]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct PromptAdaptationToml {
+    /// Extra guidance appended to the base instructions.
+    pub instructions: Option<String>,
+
+    /// Replacement descriptions for tools, keyed by tool name.
+    #[serde(default)]
+    pub tool_descriptions: HashMap<String, String>,
+
+    /// Replacement instructions used when planning.
+    pub plan_mode_instructions: Option<String>,
+}
+
+/// Settings for GUI clients of the app server.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Gui {
+    /// Which `thread/desktopNotification` types are sent for threads the
+    /// client has not reported as focused. Defaults to `true`.
+    #[serde(default)]
+    pub notifications: Notifications,
 }
 
 /// Collection of settings that are specific to the TUI.
@@ -378,6 +712,16 @@ pub struct Tui {
     #[serde(default)]
     pub notifications: Notifications,
 
+    /// How notifications are delivered: `auto`, `osc9`, `osc777`, `bell`,
+    /// or `tmux`. Defaults to `auto`, which picks one from the terminal.
+    #[serde(default)]
+    pub notification_method: NotificationMethod,
+
+    /// Per-type overrides of `notification_method`, keyed by notification
+    /// type (e.g. `error = "bell"`).
+    #[serde(default)]
+    pub notification_methods: HashMap<String, NotificationMethod>,
+
     /// Enable animations (welcome screen, shimmer effects, spinners).
     /// Defaults to `true`.
     #[serde(default = "default_true")]
@@ -406,12 +750,35 @@ pub struct Tui {
     /// Defaults to `true`.
     #[serde(default = "default_true")]
     pub scroll_half_page: bool,
+
+    /// Maximum width, in columns, of the transcript and composer. Wider
+    /// terminals center the layout. `0` uses the full terminal width.
+    /// Defaults to 120.
+    #[serde(default = "default_max_transcript_width")]
+    pub max_transcript_width: u16,
+
+    /// Color theme: `dark` (the default), `light`, `high-contrast`, or the
+    /// name of a palette in `~/.codex/themes/` without the `.toml` extension.
+    #[serde(default)]
+    pub theme: Option<String>,
+
+    /// Key chords for TUI actions, keyed by action name
+    /// (e.g. `command_palette = "ctrl+k"`). `"none"` unbinds an action.
+    #[serde(default)]
+    pub keys: BTreeMap<String, String>,
 }
 
 const fn default_true() -> bool {
     true
 }
 
+/// Default for `tui.max_transcript_width`.
+pub const DEFAULT_MAX_TRANSCRIPT_WIDTH: u16 = 120;
+
+const fn default_max_transcript_width() -> u16 {
+    DEFAULT_MAX_TRANSCRIPT_WIDTH
+}
+
 const fn default_scroll_lines() -> u16 {
     3
 }
@@ -425,6 +792,28 @@ const fn default_subagent_limit() -> i64 {
     4
 }
 
+/// Settings for subagent tasks spawned by the `subagent_run` tool.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SubagentsToml {
+    /// Named role presets the orchestrator can assign to spawned tasks.
+    #[serde(default)]
+    pub roles: BTreeMap<String, SubagentRole>,
+}
+
+/// Preset applied to a subagent task spawned with a given role.
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct SubagentRole {
+    /// Short description shown to the orchestrating model.
+    pub description: Option<String>,
+
+    /// Model used by tasks in this role. Defaults to the parent's model.
+    pub model: Option<String>,
+
+    /// Sandbox used by tasks in this role. Defaults to the parent's sandbox
+    /// and can never grant more access than the parent has.
+    pub sandbox_mode: Option<SandboxMode>,
+}
+
 /// Settings for notices we display to users via the tui and app-server clients
 /// (primarily the Codex IDE extension). NOTE: these are different from
 /// notifications - notices are warnings, NUX screens, acknowledgements, etc.
@@ -458,6 +847,18 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    /// Glob patterns whose matching paths are writable in addition to the
+    /// workspace and `writable_roots`.
+    #[serde(default)]
+    pub allow_write: Vec<String>,
+    /// Glob patterns whose matching paths stay read-only even under a
+    /// writable root.
+    #[serde(default)]
+    pub deny_write: Vec<String>,
+    /// Hosts (`host`, `host:port` or `*.domain`) reachable through the
+    /// sandbox's network proxy while `network_access` is off.
+    #[serde(default)]
+    pub network_allowlist: Vec<String>,
 }
 
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
@@ -11,6 +11,8 @@ use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::SessionConfiguredEvent;
 use crate::rollout::RolloutRecorder;
+use crate::rollout::lock::release_session_lock;
+use crate::rollout::lock::take_over_session_lock;
 use crate::skills::SkillsManager;
 use codex_protocol::ConversationId;
 use codex_protocol::items::TurnItem;
@@ -117,6 +119,7 @@ impl ConversationManager {
         let conversation = Arc::new(CodexConversation::new(
             codex,
             session_configured.rollout_path.clone(),
+            session_configured.cwd.clone(),
         ));
         self.conversations
             .write()
@@ -152,6 +155,24 @@ impl ConversationManager {
             .await
     }
 
+    /// Resume `rollout_path` even though another process is writing it. The
+    /// other process stops recording and reports `EventMsg::SessionLockLost`.
+    pub async fn take_over_conversation_from_rollout(
+        &self,
+        config: Config,
+        rollout_path: PathBuf,
+        auth_manager: Arc<AuthManager>,
+    ) -> CodexResult<NewConversation> {
+        take_over_session_lock(&rollout_path).await?;
+        let resumed = self
+            .resume_conversation_from_rollout(config, rollout_path.clone(), auth_manager)
+            .await;
+        if resumed.is_err() {
+            release_session_lock(&rollout_path);
+        }
+        resumed
+    }
+
     pub async fn resume_conversation_with_history(
         &self,
         config: Config,
@@ -171,6 +192,16 @@ impl ConversationManager {
         self.finalize_spawn(codex, conversation_id).await
     }
 
+    /// Working directories of the conversations currently loaded.
+    pub async fn active_cwds(&self) -> Vec<PathBuf> {
+        self.conversations
+            .read()
+            .await
+            .values()
+            .map(|conversation| conversation.cwd())
+            .collect()
+    }
+
     /// Removes the conversation from the manager's internal map, though the
     /// conversation is stored as `Arc<CodexConversation>`, it is possible that
     /// other references to it exist elsewhere. Returns the conversation if the
@@ -205,6 +236,31 @@ impl ConversationManager {
 
         self.finalize_spawn(codex, conversation_id).await
     }
+
+    /// Fork an existing conversation keeping its entire history, so the next
+    /// turn can run under a different `config` without touching the original.
+    pub async fn fork_conversation_at_end(
+        &self,
+        config: Config,
+        path: PathBuf,
+    ) -> CodexResult<NewConversation> {
+        let items = RolloutRecorder::get_rollout_history(&path)
+            .await?
+            .get_rollout_items();
+        let history = if items.is_empty() {
+            InitialHistory::New
+        } else {
+            InitialHistory::Forked(items)
+        };
+
+        let auth_manager = self.auth_manager.clone();
+        let CodexSpawnOk {
+            codex,
+            conversation_id,
+        } = Codex::spawn(config, auth_manager, history, self.session_source.clone()).await?;
+
+        self.finalize_spawn(codex, conversation_id).await
+    }
 }
 
 /// Return a prefix of `items` obtained by cutting strictly before the nth user message
@@ -242,10 +298,42 @@ fn truncate_before_nth_user_message(history: InitialHistory, n: usize) -> Initia
     }
 }
 
+/// Return the prefix of `items` ending with the nth (0-based) assistant
+/// message, plus the events recorded right after it (such as the
+/// `AgentMessage` event the transcript is replayed from). `None` when there
+/// are not that many assistant messages.
+pub(crate) fn truncate_after_nth_agent_message(
+    items: Vec<RolloutItem>,
+    n: usize,
+) -> Option<Vec<RolloutItem>> {
+    let agent_idx = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            matches!(
+                item,
+                RolloutItem::ResponseItem(item @ ResponseItem::Message { .. })
+                    if matches!(
+                        crate::event_mapping::parse_turn_item(item),
+                        Some(TurnItem::AgentMessage(_))
+                    )
+            )
+        })
+        .map(|(idx, _)| idx)
+        .nth(n)?;
+
+    let mut cut_idx = agent_idx + 1;
+    while matches!(items.get(cut_idx), Some(RolloutItem::EventMsg(_))) {
+        cut_idx += 1;
+    }
+    Some(items.into_iter().take(cut_idx).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::codex::make_session_and_context;
+    use crate::protocol::AgentMessageEvent;
     use assert_matches::assert_matches;
     use codex_protocol::models::ContentItem;
     use codex_protocol::models::ReasoningItemReasoningSummary;
@@ -323,6 +411,31 @@ mod tests {
         assert_matches!(truncated2, InitialHistory::New);
     }
 
+    #[test]
+    fn keeps_events_after_the_forked_assistant_message() {
+        let agent_event = RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+            message: "a1".to_string(),
+        }));
+        let items = vec![
+            RolloutItem::ResponseItem(user_msg("u1")),
+            RolloutItem::ResponseItem(assistant_msg("a1")),
+            agent_event.clone(),
+            RolloutItem::ResponseItem(user_msg("u2")),
+            RolloutItem::ResponseItem(assistant_msg("a2")),
+        ];
+
+        let truncated = truncate_after_nth_agent_message(items.clone(), 0);
+        assert_eq!(
+            serde_json::to_value(&truncated).unwrap(),
+            serde_json::to_value(Some(&items[..3])).unwrap()
+        );
+        assert_eq!(
+            truncate_after_nth_agent_message(items.clone(), 1).map(|items| items.len()),
+            Some(5)
+        );
+        assert!(truncate_after_nth_agent_message(items, 2).is_none());
+    }
+
     #[test]
     fn ignores_session_prefix_messages_when_truncating() {
         let (session, turn_context) = make_session_and_context();
@@ -0,0 +1,179 @@
+//! Dollar cost tracking for model usage.
+//!
+//! Token usage reported by the model is priced with [`pricing_for_model`] and
+//! accumulated per turn and per session in [`CostTracker`]. When
+//! `max_session_cost_usd` is configured, the session pauses before the next
+//! model request once the budget is exceeded and asks the user whether to
+//! continue.
+
+use crate::protocol::CostUpdateEvent;
+use crate::protocol::ReviewDecision;
+use crate::protocol::TokenUsage;
+
+const TOKENS_PER_MILLION: f64 = 1_000_000.0;
+
+/// USD prices per million tokens for a model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelPricing {
+    pub input_per_million: f64,
+    pub cached_input_per_million: f64,
+    pub output_per_million: f64,
+}
+
+impl ModelPricing {
+    const fn new(input: f64, cached_input: f64, output: f64) -> Self {
+        Self {
+            input_per_million: input,
+            cached_input_per_million: cached_input,
+            output_per_million: output,
+        }
+    }
+
+    /// Cost of `usage` in USD. Reasoning tokens are billed as output tokens and
+    /// are already included in `output_tokens`.
+    pub fn cost_usd(&self, usage: &TokenUsage) -> f64 {
+        let non_cached = usage.non_cached_input() as f64;
+        let cached = usage.cached_input() as f64;
+        let output = usage.output_tokens.max(0) as f64;
+        (non_cached * self.input_per_million
+            + cached * self.cached_input_per_million
+            + output * self.output_per_million)
+            / TOKENS_PER_MILLION
+    }
+}
+
+/// List prices for known OpenAI models, or `None` when the model is not priced
+/// (e.g. local OSS models).
+pub fn pricing_for_model(slug: &str) -> Option<ModelPricing> {
+    match slug {
+        // https://platform.openai.com/docs/pricing
+        "o3" => Some(ModelPricing::new(2.0, 0.5, 8.0)),
+        "o4-mini" => Some(ModelPricing::new(1.1, 0.275, 4.4)),
+        "codex-mini-latest" => Some(ModelPricing::new(1.5, 0.375, 6.0)),
+        _ if slug.starts_with("gpt-4.1-nano") => Some(ModelPricing::new(0.1, 0.025, 0.4)),
+        _ if slug.starts_with("gpt-4.1-mini") => Some(ModelPricing::new(0.4, 0.1, 1.6)),
+        _ if slug.starts_with("gpt-4.1") => Some(ModelPricing::new(2.0, 0.5, 8.0)),
+        _ if slug.starts_with("gpt-4o-mini") => Some(ModelPricing::new(0.15, 0.075, 0.6)),
+        _ if slug.starts_with("gpt-4o") => Some(ModelPricing::new(2.5, 1.25, 10.0)),
+        _ if slug.starts_with("gpt-5-nano") => Some(ModelPricing::new(0.05, 0.005, 0.4)),
+        _ if slug.starts_with("gpt-5-mini") || slug.starts_with("gpt-5.1-codex-mini") => {
+            Some(ModelPricing::new(0.25, 0.025, 2.0))
+        }
+        _ if slug.starts_with("gpt-5") || slug.starts_with("codex-") => {
+            Some(ModelPricing::new(1.25, 0.125, 10.0))
+        }
+        _ => None,
+    }
+}
+
+/// Accumulated cost of a session and its current turn.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CostTracker {
+    session_cost_usd: f64,
+    turn_cost_usd: f64,
+    /// Session cost at which the budget was last approved; the user is asked
+    /// again once another full budget has been spent on top of it.
+    approved_at_usd: Option<f64>,
+    /// The user chose to keep going without further budget prompts.
+    budget_waived: bool,
+}
+
+impl CostTracker {
+    pub(crate) fn start_turn(&mut self) {
+        self.turn_cost_usd = 0.0;
+    }
+
+    pub(crate) fn record(&mut self, pricing: &ModelPricing, usage: &TokenUsage) {
+        let cost = pricing.cost_usd(usage);
+        self.turn_cost_usd += cost;
+        self.session_cost_usd += cost;
+    }
+
+    pub(crate) fn session_cost_usd(&self) -> f64 {
+        self.session_cost_usd
+    }
+
+    pub(crate) fn update_event(&self, max_session_cost_usd: Option<f64>) -> CostUpdateEvent {
+        CostUpdateEvent {
+            turn_cost_usd: self.turn_cost_usd,
+            session_cost_usd: self.session_cost_usd,
+            max_session_cost_usd,
+        }
+    }
+
+    /// Whether the session must pause for confirmation before spending more.
+    pub(crate) fn needs_confirmation(&self, max_session_cost_usd: Option<f64>) -> bool {
+        let Some(max) = max_session_cost_usd else {
+            return false;
+        };
+        if self.budget_waived {
+            return false;
+        }
+        let limit = self.approved_at_usd.map_or(max, |approved| approved + max);
+        self.session_cost_usd >= limit
+    }
+
+    /// Apply the user's answer to a budget prompt. Returns whether the turn
+    /// may continue.
+    pub(crate) fn apply_decision(&mut self, decision: ReviewDecision) -> bool {
+        match decision {
+            ReviewDecision::Approved | ReviewDecision::ApprovedPartially { .. } => {
+                self.approved_at_usd = Some(self.session_cost_usd);
+                true
+            }
+            ReviewDecision::ApprovedForSession => {
+                self.budget_waived = true;
+                true
+            }
+            ReviewDecision::Denied | ReviewDecision::Abort => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn usage(input: i64, cached: i64, output: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens: input,
+            cached_input_tokens: cached,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            total_tokens: input + output,
+        }
+    }
+
+    #[test]
+    fn prices_cached_input_separately() {
+        let pricing = pricing_for_model("gpt-5.1-codex").expect("priced");
+        let cost = pricing.cost_usd(&usage(1_000_000, 400_000, 100_000));
+        // 600k fresh input + 400k cached input + 100k output.
+        assert!((cost - (0.75 + 0.05 + 1.0)).abs() < 1e-9);
+        assert_eq!(pricing_for_model("gpt-oss-20b"), None);
+    }
+
+    #[test]
+    fn budget_prompts_again_after_another_budget_is_spent() {
+        let pricing = ModelPricing::new(1.0, 1.0, 1.0);
+        let mut tracker = CostTracker::default();
+        let max = Some(1.0);
+
+        tracker.record(&pricing, &usage(600_000, 0, 0));
+        assert!(!tracker.needs_confirmation(max));
+        tracker.record(&pricing, &usage(600_000, 0, 0));
+        assert!(tracker.needs_confirmation(max));
+
+        assert!(tracker.apply_decision(ReviewDecision::Approved));
+        assert!(!tracker.needs_confirmation(max));
+        tracker.record(&pricing, &usage(1_000_000, 0, 0));
+        assert!(tracker.needs_confirmation(max));
+
+        assert!(!tracker.apply_decision(ReviewDecision::Denied));
+        assert!(tracker.apply_decision(ReviewDecision::ApprovedForSession));
+        tracker.record(&pricing, &usage(5_000_000, 0, 0));
+        assert!(!tracker.needs_confirmation(max));
+        assert!(!CostTracker::default().needs_confirmation(None));
+    }
+}
@@ -0,0 +1,315 @@
+//! Fast self-checks for common misconfigurations.
+//!
+//! [`run_checks`] looks at saved credentials, unknown keys in `config.toml`,
+//! the optional `sgrep` binary, git lock files left behind by crashed
+//! processes, and the system clock. Each problem becomes a [`DoctorFinding`]
+//! with the command that fixes it, so every front end reports the same
+//! findings in the same words.
+
+use std::path::Path;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Serialize;
+use serde::de::Visitor;
+use tokio::time::timeout;
+
+use crate::auth::CodexAuth;
+use crate::auth::read_codex_api_key_from_env;
+use crate::auth::read_openai_api_key_from_env;
+use crate::config::CONFIG_TOML_FILE;
+use crate::config::Config;
+use crate::config::ConfigToml;
+use crate::default_client::build_reqwest_client;
+use crate::git_info::get_git_repo_root;
+use crate::onboarding::find_sgrep_binary;
+
+const CLOCK_CHECK_TIMEOUT: Duration = Duration::from_secs(3);
+const MAX_CLOCK_SKEW_SECS: i64 = 5 * 60;
+const STALE_LOCK_AGE: Duration = Duration::from_secs(10 * 60);
+const GIT_LOCK_FILES: [&str; 3] = ["index.lock", "HEAD.lock", "config.lock"];
+const DEFAULT_CLOCK_CHECK_URL: &str = "https://api.openai.com/v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoctorCheck {
+    Auth,
+    Config,
+    Sgrep,
+    LockFiles,
+    Clock,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DoctorSeverity {
+    /// Codex works, but a feature is degraded or a setting is ignored.
+    Warning,
+    /// Requests are likely to fail until the problem is fixed.
+    Error,
+}
+
+/// One problem found by [`run_checks`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DoctorFinding {
+    pub check: DoctorCheck,
+    pub severity: DoctorSeverity,
+    pub summary: String,
+    /// Command that resolves the problem, when there is one.
+    pub fix: Option<String>,
+}
+
+/// Run every check and return the problems found, errors first. Checks that
+/// cannot complete quickly (the clock check needs the network) are skipped
+/// rather than reported.
+pub async fn run_checks(config: &Config) -> Vec<DoctorFinding> {
+    let mut findings: Vec<DoctorFinding> = check_auth(config).into_iter().collect();
+    findings.extend(check_config_keys(&config.codex_home));
+    findings.extend(check_sgrep());
+    if let Some(repo_root) = get_git_repo_root(&config.cwd) {
+        findings.extend(check_git_locks(&repo_root, STALE_LOCK_AGE));
+    }
+    findings.extend(check_clock(config).await);
+    findings.sort_by_key(|finding| finding.severity != DoctorSeverity::Error);
+    findings
+}
+
+fn check_auth(config: &Config) -> Option<DoctorFinding> {
+    if !config.model_provider.requires_openai_auth
+        || read_openai_api_key_from_env().is_some()
+        || read_codex_api_key_from_env().is_some()
+    {
+        return None;
+    }
+    let summary = match CodexAuth::from_auth_storage(
+        &config.codex_home,
+        config.cli_auth_credentials_store_mode,
+    ) {
+        Ok(Some(_)) => return None,
+        Ok(None) => "Not logged in and OPENAI_API_KEY is not set".to_string(),
+        Err(err) => format!("Saved credentials could not be read: {err}"),
+    };
+    Some(DoctorFinding {
+        check: DoctorCheck::Auth,
+        severity: DoctorSeverity::Error,
+        summary,
+        fix: Some("codex-kaioken login".to_string()),
+    })
+}
+
+/// Report top-level keys of `config.toml` that Codex does not recognize and
+/// therefore silently ignores, which is usually a typo.
+fn check_config_keys(codex_home: &Path) -> Vec<DoctorFinding> {
+    let path = codex_home.join(CONFIG_TOML_FILE);
+    let Ok(contents) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+    let Ok(toml::Value::Table(table)) = contents.parse::<toml::Value>() else {
+        return Vec::new();
+    };
+    let known = config_toml_keys();
+    table
+        .keys()
+        .filter(|key| !known.contains(&key.as_str()))
+        .map(|key| DoctorFinding {
+            check: DoctorCheck::Config,
+            severity: DoctorSeverity::Warning,
+            summary: format!("Unknown key `{key}` in {} is ignored", path.display()),
+            fix: None,
+        })
+        .collect()
+}
+
+/// Top-level keys `ConfigToml` accepts, read from its `Deserialize` impl so
+/// the list cannot drift from the struct.
+fn config_toml_keys() -> &'static [&'static str] {
+    struct FieldNames(&'static [&'static str]);
+
+    impl<'de> serde::Deserializer<'de> for &mut FieldNames {
+        type Error = serde::de::value::Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+            Err(serde::de::Error::custom("only struct fields are read"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            _name: &'static str,
+            fields: &'static [&'static str],
+            _visitor: V,
+        ) -> Result<V::Value, Self::Error> {
+            self.0 = fields;
+            Err(serde::de::Error::custom("only struct fields are read"))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    let mut names = FieldNames(&[]);
+    let _ = <ConfigToml as serde::Deserialize>::deserialize(&mut names);
+    names.0
+}
+
+fn check_sgrep() -> Option<DoctorFinding> {
+    find_sgrep_binary().is_none().then(|| DoctorFinding {
+        check: DoctorCheck::Sgrep,
+        severity: DoctorSeverity::Warning,
+        summary: "sgrep is not installed, so semantic search is unavailable \
+                  (https://github.com/Rika-Labs/sgrep)"
+            .to_string(),
+        fix: None,
+    })
+}
+
+/// Lock files git leaves behind when a git process is killed; while they
+/// exist, commits, checkpoints and undo fail in this repository.
+fn check_git_locks(repo_root: &Path, older_than: Duration) -> Vec<DoctorFinding> {
+    let git_dir = repo_root.join(".git");
+    if !git_dir.is_dir() {
+        return Vec::new();
+    }
+    let now = SystemTime::now();
+    GIT_LOCK_FILES
+        .iter()
+        .map(|name| git_dir.join(name))
+        .filter(|path| {
+            std::fs::metadata(path)
+                .and_then(|metadata| metadata.modified())
+                .is_ok_and(|modified| {
+                    now.duration_since(modified).unwrap_or_default() >= older_than
+                })
+        })
+        .map(|path| DoctorFinding {
+            check: DoctorCheck::LockFiles,
+            severity: DoctorSeverity::Error,
+            summary: format!(
+                "Stale git lock file {} blocks commits and checkpoints",
+                path.display()
+            ),
+            fix: Some(format!("rm {}", path.display())),
+        })
+        .collect()
+}
+
+async fn check_clock(config: &Config) -> Option<DoctorFinding> {
+    let url = config
+        .model_provider
+        .base_url
+        .clone()
+        .unwrap_or_else(|| DEFAULT_CLOCK_CHECK_URL.to_string());
+    let response = timeout(CLOCK_CHECK_TIMEOUT, build_reqwest_client().head(url).send())
+        .await
+        .ok()?
+        .ok()?;
+    let date = response
+        .headers()
+        .get(reqwest::header::DATE)?
+        .to_str()
+        .ok()?;
+    let server_time = DateTime::parse_from_rfc2822(date).ok()?.with_timezone(&Utc);
+    clock_skew_finding(server_time, Utc::now())
+}
+
+fn clock_skew_finding(
+    server_time: DateTime<Utc>,
+    local_time: DateTime<Utc>,
+) -> Option<DoctorFinding> {
+    let skew = (local_time - server_time).num_seconds();
+    if skew.abs() < MAX_CLOCK_SKEW_SECS {
+        return None;
+    }
+    let direction = if skew > 0 { "ahead of" } else { "behind" };
+    let minutes = skew.abs() / 60;
+    Some(DoctorFinding {
+        check: DoctorCheck::Clock,
+        severity: DoctorSeverity::Error,
+        summary: format!(
+            "System clock is {minutes} minutes {direction} the server; sign-in and requests may fail"
+        ),
+        fix: Some(clock_sync_command().to_string()),
+    })
+}
+
+fn clock_sync_command() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "sudo sntp -sS time.apple.com"
+    } else if cfg!(windows) {
+        "w32tm /resync"
+    } else {
+        "sudo timedatectl set-ntp true"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn unknown_config_keys_are_reported() {
+        let codex_home = TempDir::new().unwrap();
+        std::fs::write(
+            codex_home.path().join(CONFIG_TOML_FILE),
+            "model = \"gpt-5.1-codex\"\nmodle_provider = \"openai\"\n\n[history]\npersistence = \"none\"\n",
+        )
+        .unwrap();
+
+        let summaries: Vec<String> = check_config_keys(codex_home.path())
+            .into_iter()
+            .map(|finding| finding.summary)
+            .collect();
+
+        assert_eq!(
+            summaries,
+            vec![format!(
+                "Unknown key `modle_provider` in {} is ignored",
+                codex_home.path().join(CONFIG_TOML_FILE).display()
+            )]
+        );
+    }
+
+    #[test]
+    fn lock_files_are_stale_only_after_the_threshold() {
+        let repo = TempDir::new().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        let lock = repo.path().join(".git").join("index.lock");
+        std::fs::write(&lock, "").unwrap();
+
+        assert_eq!(check_git_locks(repo.path(), STALE_LOCK_AGE), Vec::new());
+        assert_eq!(
+            check_git_locks(repo.path(), Duration::ZERO),
+            vec![DoctorFinding {
+                check: DoctorCheck::LockFiles,
+                severity: DoctorSeverity::Error,
+                summary: format!(
+                    "Stale git lock file {} blocks commits and checkpoints",
+                    lock.display()
+                ),
+                fix: Some(format!("rm {}", lock.display())),
+            }]
+        );
+    }
+
+    #[test]
+    fn small_clock_differences_are_ignored() {
+        let server_time = Utc::now();
+
+        assert_eq!(
+            clock_skew_finding(server_time, server_time + chrono::Duration::seconds(90)),
+            None
+        );
+        let finding =
+            clock_skew_finding(server_time, server_time - chrono::Duration::minutes(12)).unwrap();
+        assert_eq!(
+            finding.summary,
+            "System clock is 12 minutes behind the server; sign-in and requests may fail"
+        );
+    }
+}
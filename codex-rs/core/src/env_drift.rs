@@ -0,0 +1,307 @@
+//! Detects workspace changes made outside the session between turns.
+//!
+//! With the `environment_drift` feature enabled, the session fingerprints the
+//! git workspace (branch, `HEAD`, and the size and mtime of every file with
+//! uncommitted changes) when a turn ends. When the next user turn starts, a
+//! fresh fingerprint is compared against it and, if the user pulled, switched
+//! branches, or a formatter rewrote files in the meantime, a short
+//! `<environment_drift>` summary is recorded ahead of the user's message.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+
+use crate::git_info::run_git_command_with_timeout;
+
+pub(crate) const ENVIRONMENT_DRIFT_OPEN_TAG: &str = "<environment_drift>";
+const ENVIRONMENT_DRIFT_CLOSE_TAG: &str = "</environment_drift>";
+
+/// Changed paths listed by name; the rest are only counted.
+const MAX_LISTED_FILES: usize = 8;
+
+/// Length of the abbreviated commit ids in the summary.
+const SHORT_SHA_LEN: usize = 7;
+
+/// Git workspace state at the end of a turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WorkspaceFingerprint {
+    root: PathBuf,
+    branch: Option<String>,
+    head: Option<String>,
+    /// Files with uncommitted changes, relative to the repository root.
+    dirty: BTreeMap<String, DirtyFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DirtyFile {
+    /// `git status --porcelain` code, e.g. `M` or `??`.
+    status: String,
+    len: Option<u64>,
+    modified: Option<SystemTime>,
+}
+
+impl WorkspaceFingerprint {
+    /// Fingerprint the repository containing `cwd`; `None` outside git or
+    /// when git does not answer in time.
+    pub(crate) async fn capture(cwd: &Path) -> Option<Self> {
+        let root = run_git_command_with_timeout(&["rev-parse", "--show-toplevel"], cwd).await?;
+        if !root.status.success() {
+            return None;
+        }
+        let root = PathBuf::from(String::from_utf8(root.stdout).ok()?.trim());
+
+        let (head, branch, status) = tokio::join!(
+            run_git_command_with_timeout(&["rev-parse", "HEAD"], &root),
+            run_git_command_with_timeout(&["branch", "--show-current"], &root),
+            run_git_command_with_timeout(&["status", "--porcelain=v1", "-z"], &root),
+        );
+        let status = status.filter(|out| out.status.success())?;
+
+        let mut dirty = BTreeMap::new();
+        for (code, path) in parse_porcelain_z(&status.stdout) {
+            let metadata = tokio::fs::metadata(root.join(&path)).await.ok();
+            dirty.insert(
+                path,
+                DirtyFile {
+                    status: code,
+                    len: metadata.as_ref().map(std::fs::Metadata::len),
+                    modified: metadata.and_then(|m| m.modified().ok()),
+                },
+            );
+        }
+
+        Some(Self {
+            root,
+            branch: branch.and_then(|out| stdout_line(&out)),
+            head: head.and_then(|out| stdout_line(&out)),
+            dirty,
+        })
+    }
+}
+
+/// What changed between two fingerprints of the same workspace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct EnvironmentDrift {
+    pub(crate) changed_files: Vec<String>,
+    pub(crate) branch_switch: Option<(Option<String>, Option<String>)>,
+    pub(crate) head_move: Option<(String, String)>,
+}
+
+impl EnvironmentDrift {
+    /// Compare the fingerprint from the end of the last turn with the
+    /// current one. Files changed by commits that `HEAD` moved over (a pull
+    /// or checkout) count as changed too. Fingerprints of different
+    /// repositories (the session's cwd moved) are not compared.
+    pub(crate) async fn detect(
+        previous: &WorkspaceFingerprint,
+        current: &WorkspaceFingerprint,
+    ) -> Option<Self> {
+        if previous == current || previous.root != current.root {
+            return None;
+        }
+
+        let mut changed: BTreeSet<String> = previous
+            .dirty
+            .iter()
+            .filter(|(path, state)| current.dirty.get(*path) != Some(state))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            current
+                .dirty
+                .keys()
+                .filter(|path| !previous.dirty.contains_key(*path))
+                .cloned(),
+        );
+
+        let head_move = match (&previous.head, &current.head) {
+            (Some(before), Some(after)) if before != after => {
+                changed.extend(committed_changes(&current.root, before, after).await);
+                Some((before.clone(), after.clone()))
+            }
+            _ => None,
+        };
+        let branch_switch = (previous.branch != current.branch)
+            .then(|| (previous.branch.clone(), current.branch.clone()));
+
+        let drift = Self {
+            changed_files: changed.into_iter().collect(),
+            branch_switch,
+            head_move,
+        };
+        (!drift.is_empty()).then_some(drift)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.changed_files.is_empty() && self.branch_switch.is_none() && self.head_move.is_none()
+    }
+
+    /// One-paragraph summary, e.g. "12 files changed outside the session
+    /// (...). Branch switched from feature to main."
+    pub(crate) fn summary(&self) -> String {
+        let mut sentences = Vec::new();
+        if !self.changed_files.is_empty() {
+            let count = self.changed_files.len();
+            let noun = if count == 1 { "file" } else { "files" };
+            let mut listed = self
+                .changed_files
+                .iter()
+                .take(MAX_LISTED_FILES)
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ");
+            if count > MAX_LISTED_FILES {
+                listed.push_str(&format!(", and {} more", count - MAX_LISTED_FILES));
+            }
+            sentences.push(format!(
+                "{count} {noun} changed outside the session ({listed})."
+            ));
+        }
+        if let Some((before, after)) = &self.branch_switch {
+            let name = |branch: &Option<String>| {
+                branch
+                    .clone()
+                    .unwrap_or_else(|| "a detached HEAD".to_string())
+            };
+            sentences.push(format!(
+                "Branch switched from {} to {}.",
+                name(before),
+                name(after)
+            ));
+        }
+        if let Some((before, after)) = &self.head_move {
+            sentences.push(format!(
+                "HEAD moved from {} to {}.",
+                short_sha(before),
+                short_sha(after)
+            ));
+        }
+        sentences.join(" ")
+    }
+
+    /// The message recorded ahead of the next user turn.
+    pub(crate) fn to_response_item(&self) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!(
+                    "{ENVIRONMENT_DRIFT_OPEN_TAG}\nThe workspace changed since your last turn. {}\nRe-read affected files before relying on what you saw earlier.\n{ENVIRONMENT_DRIFT_CLOSE_TAG}",
+                    self.summary()
+                ),
+            }],
+        }
+    }
+}
+
+/// Paths touched by the commits between `before` and `after`.
+async fn committed_changes(root: &Path, before: &str, after: &str) -> Vec<String> {
+    let range = format!("{before}..{after}");
+    let Some(out) = run_git_command_with_timeout(&["diff", "--name-only", &range], root).await
+    else {
+        return Vec::new();
+    };
+    if !out.status.success() {
+        return Vec::new();
+    }
+    String::from_utf8_lossy(&out.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parse `git status --porcelain=v1 -z` into `(code, path)` pairs. Renames
+/// and copies carry their source path as an extra NUL-separated field.
+fn parse_porcelain_z(stdout: &[u8]) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    let mut fields = stdout
+        .split(|byte| *byte == 0)
+        .filter(|field| !field.is_empty());
+    while let Some(field) = fields.next() {
+        let field = String::from_utf8_lossy(field);
+        let Some((code, path)) = field.split_at_checked(3) else {
+            continue;
+        };
+        let code = code.trim().to_string();
+        if code.starts_with('R') || code.starts_with('C') {
+            fields.next();
+        }
+        entries.push((code, path.to_string()));
+    }
+    entries
+}
+
+fn stdout_line(out: &std::process::Output) -> Option<String> {
+    if !out.status.success() {
+        return None;
+    }
+    let line = String::from_utf8_lossy(&out.stdout).trim().to_string();
+    (!line.is_empty()).then_some(line)
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..SHORT_SHA_LEN).unwrap_or(sha)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn fingerprint(branch: &str, head: &str, dirty: &[(&str, u64)]) -> WorkspaceFingerprint {
+        WorkspaceFingerprint {
+            root: PathBuf::from("/repo"),
+            branch: Some(branch.to_string()),
+            head: Some(head.to_string()),
+            dirty: dirty
+                .iter()
+                .map(|(path, len)| {
+                    (
+                        path.to_string(),
+                        DirtyFile {
+                            status: " M".to_string(),
+                            len: Some(*len),
+                            modified: None,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parses_porcelain_with_renames() {
+        let stdout = b" M src/lib.rs\0R  new.rs\0old.rs\0?? notes.md\0";
+        assert_eq!(
+            parse_porcelain_z(stdout),
+            vec![
+                ("M".to_string(), "src/lib.rs".to_string()),
+                ("R".to_string(), "new.rs".to_string()),
+                ("??".to_string(), "notes.md".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_changed_files_and_branch_switch() {
+        let previous = fingerprint("feature", "abc", &[("a.rs", 1), ("b.rs", 2)]);
+        let current = fingerprint("main", "abc", &[("a.rs", 1), ("b.rs", 3), ("c.rs", 4)]);
+
+        let drift = EnvironmentDrift::detect(&previous, &current)
+            .await
+            .expect("drift");
+        assert_eq!(drift.changed_files, vec!["b.rs", "c.rs"]);
+        assert_eq!(
+            drift.summary(),
+            "2 files changed outside the session (b.rs, c.rs). Branch switched from feature to main."
+        );
+
+        assert_eq!(EnvironmentDrift::detect(&current, &current).await, None);
+    }
+}
@@ -28,6 +28,7 @@ pub(crate) struct EnvironmentContext {
     pub approval_policy: Option<AskForApproval>,
     pub sandbox_mode: Option<SandboxMode>,
     pub network_access: Option<NetworkAccess>,
+    pub network_allowlist: Option<Vec<String>>,
     pub writable_roots: Option<Vec<PathBuf>>,
     pub shell: Shell,
 }
@@ -60,6 +61,10 @@ impl EnvironmentContext {
                 }
                 None => None,
             },
+            network_allowlist: sandbox_policy
+                .as_ref()
+                .map(|policy| policy.network_allowlist().to_vec())
+                .filter(|allowlist| !allowlist.is_empty()),
             writable_roots: match sandbox_policy {
                 Some(SandboxPolicy::WorkspaceWrite { writable_roots, .. }) => {
                     if writable_roots.is_empty() {
@@ -83,6 +88,7 @@ impl EnvironmentContext {
             approval_policy,
             sandbox_mode,
             network_access,
+            network_allowlist,
             writable_roots,
             // should compare all fields except shell
             shell: _,
@@ -92,6 +98,7 @@ impl EnvironmentContext {
             && self.approval_policy == *approval_policy
             && self.sandbox_mode == *sandbox_mode
             && self.network_access == *network_access
+            && self.network_allowlist == *network_allowlist
             && self.writable_roots == *writable_roots
     }
 
@@ -139,6 +146,7 @@ impl EnvironmentContext {
     ///   <sandbox_mode>...</sandbox_mode>
     ///   <writable_roots>...</writable_roots>
     ///   <network_access>...</network_access>
+    ///   <network_allowlist>...</network_allowlist>
     ///   <shell>...</shell>
     /// </environment_context>
     /// ```
@@ -160,6 +168,13 @@ impl EnvironmentContext {
                 "  <network_access>{network_access}</network_access>"
             ));
         }
+        if let Some(network_allowlist) = self.network_allowlist {
+            lines.push("  <network_allowlist>".to_string());
+            for host in network_allowlist {
+                lines.push(format!("    <host>{host}</host>"));
+            }
+            lines.push("  </network_allowlist>".to_string());
+        }
         if let Some(writable_roots) = self.writable_roots {
             lines.push("  <writable_roots>".to_string());
             for writable_root in writable_roots {
@@ -210,6 +225,9 @@ mod tests {
             network_access,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec![],
         }
     }
 
@@ -237,6 +255,30 @@ mod tests {
         assert_eq!(context.serialize_to_xml(), expected);
     }
 
+    #[test]
+    fn serialize_network_allowlist_environment_context() {
+        let mut policy = workspace_write_policy(vec![], false);
+        if let SandboxPolicy::WorkspaceWrite {
+            network_allowlist, ..
+        } = &mut policy
+        {
+            *network_allowlist = vec!["crates.io".to_string(), "*.github.com:443".to_string()];
+        }
+        let context = EnvironmentContext::new(None, None, Some(policy), fake_shell());
+
+        let expected = r#"<environment_context>
+  <sandbox_mode>workspace-write</sandbox_mode>
+  <network_access>restricted</network_access>
+  <network_allowlist>
+    <host>crates.io</host>
+    <host>*.github.com:443</host>
+  </network_allowlist>
+  <shell>bash</shell>
+</environment_context>"#;
+
+        assert_eq!(context.serialize_to_xml(), expected);
+    }
+
     #[test]
     fn serialize_read_only_environment_context() {
         let context = EnvironmentContext::new(
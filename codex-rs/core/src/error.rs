@@ -1,5 +1,6 @@
 use crate::codex::ProcessedResponseItem;
 use crate::exec::ExecToolCallOutput;
+use crate::resource_limits::ResourceLimitViolation;
 use crate::token_data::KnownPlan;
 use crate::token_data::PlanType;
 use crate::truncate::TruncationPolicy;
@@ -32,7 +33,11 @@ pub enum SandboxErr {
         "sandbox denied exec error, exit code: {}, stdout: {}, stderr: {}",
         .output.exit_code, .output.stdout.text, .output.stderr.text
     )]
-    Denied { output: Box<ExecToolCallOutput> },
+    Denied {
+        output: Box<ExecToolCallOutput>,
+        /// Destinations the network allowlist refused during the command.
+        network_destinations: Vec<String>,
+    },
 
     /// Error from linux seccomp filter setup
     #[cfg(target_os = "linux")]
@@ -52,6 +57,13 @@ pub enum SandboxErr {
     #[error("command was killed by a signal")]
     Signal(i32),
 
+    /// Command ran into one of the configured `[resource_limits]`
+    #[error("command exceeded its {violation}")]
+    ResourceLimit {
+        violation: ResourceLimitViolation,
+        output: Box<ExecToolCallOutput>,
+    },
+
     /// Error from linux landlock
     #[error("Landlock was not able to fully enforce all sandbox rules")]
     LandlockRestrict,
@@ -487,7 +499,7 @@ impl CodexErr {
 
 pub fn get_error_message_ui(e: &CodexErr) -> String {
     let message = match e {
-        CodexErr::Sandbox(SandboxErr::Denied { output }) => {
+        CodexErr::Sandbox(SandboxErr::Denied { output, .. }) => {
             let aggregated = output.aggregated_output.text.trim();
             if !aggregated.is_empty() {
                 output.aggregated_output.text.clone()
@@ -505,6 +517,9 @@ pub fn get_error_message_ui(e: &CodexErr) -> String {
                 }
             }
         }
+        CodexErr::Sandbox(SandboxErr::ResourceLimit { violation, .. }) => {
+            format!("error: command exceeded its {violation} and was stopped")
+        }
         // Timeouts are not sandbox errors from a UX perspective; present them plainly
         CodexErr::Sandbox(SandboxErr::Timeout { output }) => {
             format!(
@@ -594,6 +609,7 @@ mod tests {
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
+            network_destinations: Vec::new(),
         });
         assert_eq!(get_error_message_ui(&err), "aggregate detail");
     }
@@ -610,6 +626,7 @@ mod tests {
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
+            network_destinations: Vec::new(),
         });
         assert_eq!(get_error_message_ui(&err), "stderr detail\nstdout detail");
     }
@@ -626,6 +643,7 @@ mod tests {
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
+            network_destinations: Vec::new(),
         });
         assert_eq!(get_error_message_ui(&err), "stdout only");
     }
@@ -669,6 +687,7 @@ mod tests {
         };
         let err = CodexErr::Sandbox(SandboxErr::Denied {
             output: Box::new(output),
+            network_destinations: Vec::new(),
         });
         assert_eq!(
             get_error_message_ui(&err),
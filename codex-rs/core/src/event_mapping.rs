@@ -10,9 +10,15 @@ use codex_protocol::models::ReasoningItemReasoningSummary;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::models::WebSearchAction;
 use codex_protocol::user_input::UserInput;
+use codex_protocol::user_input::parse_quote_context;
 use tracing::warn;
 use uuid::Uuid;
 
+use crate::env_drift::ENVIRONMENT_DRIFT_OPEN_TAG;
+use crate::memory::injector::PROJECT_MEMORY_OPEN_TAG;
+use crate::pinned_files::PINNED_FILES_OPEN_TAG;
+use crate::session_summary::MERGED_CONTEXT_OPEN_TAG;
+use crate::session_summary::SESSION_NOTES_OPEN_TAG;
 use crate::user_instructions::UserInstructions;
 use crate::user_shell_command::is_user_shell_command_text;
 
@@ -20,6 +26,11 @@ fn is_session_prefix(text: &str) -> bool {
     let trimmed = text.trim_start();
     let lowered = trimmed.to_ascii_lowercase();
     lowered.starts_with("<environment_context>")
+        || lowered.starts_with(SESSION_NOTES_OPEN_TAG)
+        || lowered.starts_with(MERGED_CONTEXT_OPEN_TAG)
+        || lowered.starts_with(ENVIRONMENT_DRIFT_OPEN_TAG)
+        || lowered.starts_with(PROJECT_MEMORY_OPEN_TAG)
+        || lowered.starts_with(PINNED_FILES_OPEN_TAG)
 }
 
 fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
@@ -35,7 +46,10 @@ fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
                 if is_session_prefix(text) || is_user_shell_command_text(text) {
                     return None;
                 }
-                content.push(UserInput::Text { text: text.clone() });
+                match parse_quote_context(text) {
+                    Some(item) => content.push(UserInput::Quote { item }),
+                    None => content.push(UserInput::Text { text: text.clone() }),
+                }
             }
             ContentItem::InputImage { image_url } => {
                 content.push(UserInput::Image {
@@ -174,6 +188,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_quote_anchor_in_user_message() {
+        let item = ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![
+                ContentItem::InputText {
+                    text: "> [#2] use pnpm\nwhy?".to_string(),
+                },
+                ContentItem::InputText {
+                    text: codex_protocol::user_input::quote_context_text(1),
+                },
+            ],
+        };
+
+        match parse_turn_item(&item) {
+            Some(TurnItem::UserMessage(user)) => {
+                assert_eq!(
+                    user.content,
+                    vec![
+                        UserInput::Text {
+                            text: "> [#2] use pnpm\nwhy?".to_string(),
+                        },
+                        UserInput::Quote { item: 1 },
+                    ]
+                );
+                assert_eq!(user.message(), "> [#2] use pnpm\nwhy?");
+            }
+            other => panic!("expected TurnItem::UserMessage, got {other:?}"),
+        }
+    }
+
     #[test]
     fn skips_user_instructions_and_env() {
         let items = vec![
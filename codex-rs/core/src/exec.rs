@@ -25,9 +25,11 @@ use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandOutputDeltaEvent;
 use crate::protocol::ExecOutputStream;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimits;
 use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxManager;
+use crate::sandboxing::network_proxy::NetworkProxy;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use crate::text_encoding::bytes_to_string_smart;
@@ -190,14 +192,22 @@ pub(crate) async fn execute_exec_env(
     let ExecEnv {
         command,
         cwd,
-        env,
+        mut env,
         expiration,
         sandbox,
         with_escalated_permissions,
         justification,
         arg0,
+        resource_limits,
     } = env;
 
+    // The platform sandbox confines the command to localhost; allowlisted
+    // hosts are reached through a proxy that lives as long as the command.
+    let network_proxy = NetworkProxy::start_for(sandbox_policy, sandbox).await?;
+    if let Some(proxy) = &network_proxy {
+        proxy.apply_env(&mut env);
+    }
+
     let params = ExecParams {
         command,
         cwd,
@@ -209,9 +219,23 @@ pub(crate) async fn execute_exec_env(
     };
 
     let start = Instant::now();
-    let raw_output_result = exec(params, sandbox, sandbox_policy, stdout_stream).await;
+    let raw_output_result = exec(
+        params,
+        sandbox,
+        sandbox_policy,
+        stdout_stream,
+        resource_limits,
+    )
+    .await;
     let duration = start.elapsed();
-    finalize_exec_result(raw_output_result, sandbox, duration)
+    let network_destinations = network_proxy.map(NetworkProxy::finish).unwrap_or_default();
+    finalize_exec_result(
+        raw_output_result,
+        sandbox,
+        duration,
+        resource_limits,
+        network_destinations,
+    )
 }
 
 #[cfg(target_os = "windows")]
@@ -302,24 +326,29 @@ fn finalize_exec_result(
     raw_output_result: std::result::Result<RawExecToolCallOutput, CodexErr>,
     sandbox_type: SandboxType,
     duration: Duration,
+    resource_limits: ResourceLimits,
+    network_destinations: Vec<String>,
 ) -> Result<ExecToolCallOutput> {
     match raw_output_result {
         Ok(raw_output) => {
             #[allow(unused_mut)]
             let mut timed_out = raw_output.timed_out;
+            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
 
             #[cfg(target_family = "unix")]
             {
                 if let Some(signal) = raw_output.exit_status.signal() {
                     if signal == TIMEOUT_CODE {
                         timed_out = true;
+                    } else if resource_limits.violation_for_signal(signal).is_some() {
+                        // Reported as a resource limit violation below.
+                        exit_code = EXIT_CODE_SIGNAL_BASE + signal;
                     } else {
                         return Err(CodexErr::Sandbox(SandboxErr::Signal(signal)));
                     }
                 }
             }
 
-            let mut exit_code = raw_output.exit_status.code().unwrap_or(-1);
             if timed_out {
                 exit_code = EXEC_TIMEOUT_EXIT_CODE;
             }
@@ -342,9 +371,20 @@ fn finalize_exec_result(
                 }));
             }
 
-            if is_likely_sandbox_denied(sandbox_type, &exec_output) {
+            if let Some(violation) = resource_limits.violation(&exec_output) {
+                return Err(CodexErr::Sandbox(SandboxErr::ResourceLimit {
+                    violation,
+                    output: Box::new(exec_output),
+                }));
+            }
+
+            // A refused connection explains a failure even when the output
+            // does not mention the sandbox.
+            let network_blocked = !network_destinations.is_empty() && exec_output.exit_code != 0;
+            if network_blocked || is_likely_sandbox_denied(sandbox_type, &exec_output) {
                 return Err(CodexErr::Sandbox(SandboxErr::Denied {
                     output: Box::new(exec_output),
+                    network_destinations,
                 }));
             }
 
@@ -492,6 +532,7 @@ async fn exec(
     sandbox: SandboxType,
     sandbox_policy: &SandboxPolicy,
     stdout_stream: Option<StdoutStream>,
+    resource_limits: ResourceLimits,
 ) -> Result<RawExecToolCallOutput> {
     #[cfg(target_os = "windows")]
     if sandbox == SandboxType::WindowsRestrictedToken
@@ -523,6 +564,7 @@ async fn exec(
         sandbox_policy,
         StdioPolicy::RedirectForShellTool,
         env,
+        resource_limits,
     )
     .await?;
     consume_truncated_output(child, expiration, stdout_stream).await
@@ -0,0 +1,457 @@
+//! Post-processing of shell command output before it is added to the
+//! conversation.
+//!
+//! Terminal control sequences are removed first: ANSI escapes are stripped and
+//! lines redrawn with carriage returns (progress bars) keep only their final
+//! state. The first `[[exec_output.rules]]` entry matching the command then
+//! decides how much of a long output the model sees. The first `head_lines`
+//! and last `tail_lines` lines are kept around an omission marker, and output
+//! longer than `summarize_over_lines` is condensed by a small model with the
+//! excerpt attached. The turn's truncation policy still applies afterwards.
+//! What the user sees in the transcript is never changed.
+
+use std::fmt;
+use std::sync::Arc;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::ConversationId;
+use codex_protocol::config_types::ReasoningEffort;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::SessionSource;
+use codex_utils_string::take_bytes_at_char_boundary;
+use codex_utils_string::take_last_bytes_at_char_boundary;
+use futures::StreamExt;
+use regex_lite::Regex;
+use tokio::time::timeout;
+
+use crate::AuthManager;
+use crate::ModelProviderInfo;
+use crate::approval_rules::CommandRule;
+use crate::bash::extract_bash_command;
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::client::ModelClient;
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::config::Config;
+use crate::config::types::ExecOutputToml;
+
+/// Model used for the output summary side request.
+const EXEC_OUTPUT_SUMMARY_MODEL: &str = "gpt-5.1-codex-mini";
+
+const EXEC_OUTPUT_SUMMARY_REASONING: ReasoningEffort = ReasoningEffort::Low;
+
+/// Kept short since the turn waits for the summary.
+const EXEC_OUTPUT_SUMMARY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Upper bound on the output sent to the summary model; the middle is dropped
+/// first.
+const MAX_SUMMARY_INPUT_BYTES: usize = 60_000;
+
+const SUMMARY_INSTRUCTIONS: &str = r#"You condense the output of a shell command for a coding agent that ran it.
+
+Keep what the agent needs to decide its next step: whether the command succeeded, every error and warning with the file paths and line numbers it mentions, names of failing tests, and final counts or results. Drop progress lines, repeated messages, and routine noise. Quote error messages verbatim. Reply with plain text only, at most 30 lines."#;
+
+/// CSI, OSC, and two-character escape sequences.
+static ANSI_ESCAPE: LazyLock<Option<Regex>> = LazyLock::new(|| {
+    Regex::new(r"\x1b(?:\[[0-?]*[ -/]*[@-~]|\][^\x07\x1b]*(?:\x07|\x1b\\)|[@-Z\\-_])").ok()
+});
+
+/// Compiled `[exec_output]` settings.
+#[derive(Clone)]
+pub struct ExecOutputPipeline {
+    strip_ansi: bool,
+    rules: Vec<ExecOutputRule>,
+    stream_interval_ms: u64,
+    stream_budget_tokens: usize,
+}
+
+#[derive(Clone)]
+struct ExecOutputRule {
+    commands: Vec<CommandRule>,
+    head_lines: Option<usize>,
+    tail_lines: Option<usize>,
+    summarize_over_lines: Option<usize>,
+}
+
+/// Command output after the synchronous steps of the pipeline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ProcessedOutput {
+    /// The whole output with terminal control sequences removed.
+    pub full: String,
+    /// What the model sees without a summary: `full`, or its head and tail.
+    pub excerpt: String,
+    /// Whether the matching rule asks for `full` to be summarized.
+    pub summarize: bool,
+}
+
+impl Default for ExecOutputPipeline {
+    fn default() -> Self {
+        let toml = ExecOutputToml::default();
+        Self {
+            strip_ansi: toml.strip_ansi,
+            rules: Vec::new(),
+            stream_interval_ms: toml.stream_interval_ms,
+            stream_budget_tokens: toml.stream_budget_tokens,
+        }
+    }
+}
+
+impl ExecOutputPipeline {
+    /// Compiles the rules, failing on the first invalid regex.
+    pub fn new(toml: &ExecOutputToml) -> Result<Self, regex::Error> {
+        let rules = toml
+            .rules
+            .iter()
+            .map(|rule| -> Result<ExecOutputRule, regex::Error> {
+                Ok(ExecOutputRule {
+                    commands: rule
+                        .commands
+                        .iter()
+                        .map(String::as_str)
+                        .map(CommandRule::new)
+                        .collect::<Result<_, _>>()?,
+                    head_lines: rule.head_lines,
+                    tail_lines: rule.tail_lines,
+                    summarize_over_lines: rule.summarize_over_lines,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            strip_ansi: toml.strip_ansi,
+            rules,
+            stream_interval_ms: toml.stream_interval_ms,
+            stream_budget_tokens: toml.stream_budget_tokens,
+        })
+    }
+
+    /// Longest wait before a `streaming_exec` command yields its output.
+    pub(crate) fn stream_interval_ms(&self) -> u64 {
+        self.stream_interval_ms
+    }
+
+    /// Output tokens a `streaming_exec` command may send to the model.
+    pub(crate) fn stream_budget_tokens(&self) -> usize {
+        self.stream_budget_tokens
+    }
+
+    /// Removes terminal control sequences from `text` when enabled.
+    pub(crate) fn clean(&self, text: &str) -> String {
+        if self.strip_ansi {
+            strip_terminal_control(text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub(crate) fn process(&self, command: &[String], text: &str) -> ProcessedOutput {
+        let full = self.clean(text);
+        let Some(rule) = self.rule_for(command) else {
+            return ProcessedOutput {
+                excerpt: full.clone(),
+                full,
+                summarize: false,
+            };
+        };
+
+        let total_lines = full.lines().count();
+        let summarize = rule
+            .summarize_over_lines
+            .is_some_and(|limit| total_lines > limit);
+        let excerpt = if rule.head_lines.is_some() || rule.tail_lines.is_some() {
+            head_and_tail(
+                &full,
+                rule.head_lines.unwrap_or(0),
+                rule.tail_lines.unwrap_or(0),
+            )
+        } else {
+            full.clone()
+        };
+        ProcessedOutput {
+            full,
+            excerpt,
+            summarize,
+        }
+    }
+
+    fn rule_for(&self, command: &[String]) -> Option<&ExecOutputRule> {
+        if self.rules.is_empty() {
+            return None;
+        }
+        let mut candidates = vec![match extract_bash_command(command) {
+            Some((_, script)) => script.to_string(),
+            None => command.join(" "),
+        }];
+        if let Some(commands) = parse_shell_lc_plain_commands(command) {
+            candidates.extend(commands.iter().map(|words| words.join(" ")));
+        }
+        self.rules.iter().find(|rule| {
+            rule.commands
+                .iter()
+                .any(|pattern| candidates.iter().any(|command| pattern.matches(command)))
+        })
+    }
+
+    fn sources(&self) -> Vec<Vec<&str>> {
+        self.rules
+            .iter()
+            .map(|rule| rule.commands.iter().map(CommandRule::source).collect())
+            .collect()
+    }
+}
+
+impl fmt::Debug for ExecOutputPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecOutputPipeline")
+            .field("strip_ansi", &self.strip_ansi)
+            .field("rules", &self.sources())
+            .field("stream_interval_ms", &self.stream_interval_ms)
+            .field("stream_budget_tokens", &self.stream_budget_tokens)
+            .finish()
+    }
+}
+
+impl PartialEq for ExecOutputPipeline {
+    fn eq(&self, other: &Self) -> bool {
+        self.strip_ansi == other.strip_ansi
+            && self.stream_interval_ms == other.stream_interval_ms
+            && self.stream_budget_tokens == other.stream_budget_tokens
+            && self.sources() == other.sources()
+            && self.rules.iter().zip(&other.rules).all(|(a, b)| {
+                (a.head_lines, a.tail_lines, a.summarize_over_lines)
+                    == (b.head_lines, b.tail_lines, b.summarize_over_lines)
+            })
+    }
+}
+
+/// Removes ANSI escape sequences and, on every line, whatever a carriage
+/// return redrew.
+pub(crate) fn strip_terminal_control(text: &str) -> String {
+    let without_escapes = match ANSI_ESCAPE.as_ref() {
+        Some(regex) => regex.replace_all(text, ""),
+        None => text.into(),
+    };
+    if !without_escapes.contains('\r') {
+        return without_escapes.into_owned();
+    }
+    without_escapes
+        .split('\n')
+        .map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            line.rsplit('\r').next().unwrap_or(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Keeps the first `head` and last `tail` lines of `text` around a marker
+/// saying how many were left out.
+fn head_and_tail(text: &str, head: usize, tail: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    if lines.len() <= head.saturating_add(tail) {
+        return text.to_string();
+    }
+    let omitted = lines.len() - head - tail;
+    let mut kept: Vec<&str> = lines[..head].to_vec();
+    let marker = format!("[... {omitted} lines omitted ...]");
+    kept.push(&marker);
+    kept.extend_from_slice(&lines[lines.len() - tail..]);
+    kept.join("\n")
+}
+
+/// The text returned to the model for summarized output.
+pub(crate) fn with_summary(summary: &str, excerpt: &str) -> String {
+    format!("Summary of the output:\n{summary}\n\nExcerpt of the output:\n{excerpt}")
+}
+
+/// Summarize the output of `command` with a side request to
+/// [`EXEC_OUTPUT_SUMMARY_MODEL`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn summarize_output(
+    config: Arc<Config>,
+    provider: ModelProviderInfo,
+    auth_manager: Arc<AuthManager>,
+    otel: &OtelEventManager,
+    conversation_id: ConversationId,
+    session_source: SessionSource,
+    command: &str,
+    output: &str,
+) -> anyhow::Result<String> {
+    let output = if output.len() > MAX_SUMMARY_INPUT_BYTES {
+        let half = MAX_SUMMARY_INPUT_BYTES / 2;
+        format!(
+            "{}\n[... output truncated ...]\n{}",
+            take_bytes_at_char_boundary(output, half),
+            take_last_bytes_at_char_boundary(output, half)
+        )
+    } else {
+        output.to_string()
+    };
+
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!("Command: {command}\n\nOutput:\n{output}"),
+            }],
+        }],
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions_override: Some(SUMMARY_INSTRUCTIONS.to_string()),
+        output_schema: None,
+    };
+
+    let mut summary_config = (*config).clone();
+    summary_config.model = EXEC_OUTPUT_SUMMARY_MODEL.to_string();
+    let child_otel = otel.with_model(EXEC_OUTPUT_SUMMARY_MODEL, &summary_config.model_family.slug);
+    let client = ModelClient::new(
+        Arc::new(summary_config),
+        Some(auth_manager),
+        child_otel,
+        provider,
+        Some(EXEC_OUTPUT_SUMMARY_REASONING),
+        config.model_reasoning_summary,
+        conversation_id,
+        session_source,
+    );
+
+    let summary = timeout(EXEC_OUTPUT_SUMMARY_TIMEOUT, async move {
+        let mut stream = client.stream(&prompt).await?;
+        let mut last_text = None;
+        while let Some(event) = stream.next().await {
+            match event? {
+                ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. }) => {
+                    last_text = content.into_iter().find_map(|item| match item {
+                        ContentItem::OutputText { text } => Some(text),
+                        _ => None,
+                    });
+                }
+                ResponseEvent::Completed { .. } => break,
+                _ => {}
+            }
+        }
+        Ok::<_, crate::error::CodexErr>(last_text)
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("output summary request timed out"))??;
+
+    match summary {
+        Some(summary) if !summary.trim().is_empty() => Ok(summary.trim().to_string()),
+        _ => anyhow::bail!("the model returned no summary"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::types::ExecOutputRuleToml;
+    use pretty_assertions::assert_eq;
+
+    fn command(words: &[&str]) -> Vec<String> {
+        words.iter().map(ToString::to_string).collect()
+    }
+
+    fn numbered_lines(count: usize) -> String {
+        (1..=count)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn pipeline(rules: Vec<ExecOutputRuleToml>) -> ExecOutputPipeline {
+        ExecOutputPipeline::new(&ExecOutputToml {
+            rules,
+            ..Default::default()
+        })
+        .expect("valid rules")
+    }
+
+    #[test]
+    fn strips_escapes_and_carriage_return_redraws() {
+        assert_eq!(
+            strip_terminal_control(
+                "\x1b[1m\x1b[32mCompiling\x1b[0m foo\r\n 10%\r 50%\r100%\n\x1b]0;title\x07done"
+            ),
+            "Compiling foo\n100%\ndone"
+        );
+    }
+
+    #[test]
+    fn first_matching_rule_keeps_head_and_tail() {
+        let pipeline = pipeline(vec![
+            ExecOutputRuleToml {
+                commands: vec!["cargo build*".to_string()],
+                head_lines: Some(2),
+                tail_lines: Some(1),
+                summarize_over_lines: Some(4),
+            },
+            ExecOutputRuleToml {
+                commands: vec!["/^cargo/".to_string()],
+                tail_lines: Some(1),
+                ..Default::default()
+            },
+        ]);
+        let output = numbered_lines(6);
+
+        assert_eq!(
+            pipeline.process(
+                &command(&["bash", "-lc", "cd app && cargo build --release"]),
+                &output
+            ),
+            ProcessedOutput {
+                full: output.clone(),
+                excerpt: "line 1\nline 2\n[... 3 lines omitted ...]\nline 6".to_string(),
+                summarize: true,
+            }
+        );
+        assert_eq!(
+            pipeline
+                .process(&command(&["cargo", "test"]), &output)
+                .excerpt,
+            "[... 5 lines omitted ...]\nline 6"
+        );
+        assert_eq!(
+            pipeline.process(&command(&["ls"]), &output),
+            ProcessedOutput {
+                full: output.clone(),
+                excerpt: output,
+                summarize: false,
+            }
+        );
+    }
+
+    #[test]
+    fn short_output_is_kept_whole() {
+        let pipeline = pipeline(vec![ExecOutputRuleToml {
+            commands: vec!["make*".to_string()],
+            head_lines: Some(5),
+            tail_lines: Some(5),
+            summarize_over_lines: Some(100),
+        }]);
+        let output = numbered_lines(10);
+        assert_eq!(
+            pipeline.process(&command(&["make"]), &output),
+            ProcessedOutput {
+                full: output.clone(),
+                excerpt: output,
+                summarize: false,
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_regex_is_rejected() {
+        let toml = ExecOutputToml {
+            strip_ansi: true,
+            rules: vec![ExecOutputRuleToml {
+                commands: vec!["/(unclosed/".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        assert!(ExecOutputPipeline::new(&toml).is_err());
+    }
+}
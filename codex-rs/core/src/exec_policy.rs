@@ -3,6 +3,8 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::approval_rules::ApprovalRuleMatch;
+use crate::approval_rules::ApprovalRules;
 use crate::command_safety::is_dangerous_command::requires_initial_appoval;
 use codex_execpolicy::Decision;
 use codex_execpolicy::Evaluation;
@@ -115,14 +117,42 @@ fn evaluate_with_policy(
     }
 }
 
+/// `approvals.always_deny` rules are checked first, then execpolicy
+/// `forbidden` and `prompt` rules. `approvals.auto_approve` only skips the
+/// prompt for commands that stay in the sandbox.
 pub(crate) fn create_approval_requirement_for_command(
     policy: &Policy,
+    approval_rules: &ApprovalRules,
     command: &[String],
     approval_policy: AskForApproval,
     sandbox_policy: &SandboxPolicy,
     sandbox_permissions: SandboxPermissions,
 ) -> ApprovalRequirement {
-    if let Some(requirement) = evaluate_with_policy(policy, command, approval_policy) {
+    let rule_match = approval_rules.evaluate(command);
+    if let Some(ApprovalRuleMatch::Deny(rule)) = &rule_match {
+        return ApprovalRequirement::Forbidden {
+            reason: format!("command matches approvals.always_deny rule `{rule}`"),
+        };
+    }
+
+    let policy_requirement = evaluate_with_policy(policy, command, approval_policy);
+    if let Some(
+        requirement @ (ApprovalRequirement::Forbidden { .. }
+        | ApprovalRequirement::NeedsApproval { .. }),
+    ) = policy_requirement
+    {
+        return requirement;
+    }
+
+    if matches!(rule_match, Some(ApprovalRuleMatch::AutoApprove(_)))
+        && matches!(sandbox_permissions, SandboxPermissions::UseDefault)
+    {
+        return ApprovalRequirement::Skip {
+            bypass_sandbox: false,
+        };
+    }
+
+    if let Some(requirement) = policy_requirement {
         return requirement;
     }
 
@@ -308,6 +338,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
 
         let requirement = create_approval_requirement_for_command(
             &policy,
+            &ApprovalRules::default(),
             &command,
             AskForApproval::OnRequest,
             &SandboxPolicy::DangerFullAccess,
@@ -334,6 +365,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
 
         let requirement = create_approval_requirement_for_command(
             &policy,
+            &ApprovalRules::default(),
             &command,
             AskForApproval::Never,
             &SandboxPolicy::DangerFullAccess,
@@ -355,6 +387,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
         let empty_policy = Policy::empty();
         let requirement = create_approval_requirement_for_command(
             &empty_policy,
+            &ApprovalRules::default(),
             &command,
             AskForApproval::UnlessTrusted,
             &SandboxPolicy::ReadOnly,
@@ -366,4 +399,50 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             ApprovalRequirement::NeedsApproval { reason: None }
         );
     }
+
+    #[test]
+    fn approval_rules_yield_to_execpolicy_and_escalation() {
+        let policy_src = r#"prefix_rule(pattern=["cargo", "build"], decision="prompt")"#;
+        let mut parser = PolicyParser::new();
+        parser
+            .parse("test.codexpolicy", policy_src)
+            .expect("parse policy");
+        let policy = parser.build();
+        let rules = ApprovalRules::new(&["cargo *".to_string()], &["cargo publish*".to_string()])
+            .expect("valid rules");
+        let requirement = |command: &[&str], sandbox_permissions| {
+            let command: Vec<String> = command.iter().map(ToString::to_string).collect();
+            create_approval_requirement_for_command(
+                &policy,
+                &rules,
+                &command,
+                AskForApproval::OnRequest,
+                &SandboxPolicy::ReadOnly,
+                sandbox_permissions,
+            )
+        };
+
+        assert_eq!(
+            requirement(&["cargo", "test"], SandboxPermissions::UseDefault),
+            ApprovalRequirement::Skip {
+                bypass_sandbox: false
+            }
+        );
+        assert_eq!(
+            requirement(&["cargo", "build"], SandboxPermissions::UseDefault),
+            ApprovalRequirement::NeedsApproval {
+                reason: Some(PROMPT_REASON.to_string())
+            }
+        );
+        assert_eq!(
+            requirement(&["cargo", "test"], SandboxPermissions::RequireEscalated),
+            ApprovalRequirement::NeedsApproval { reason: None }
+        );
+        assert_eq!(
+            requirement(&["cargo", "publish"], SandboxPermissions::UseDefault),
+            ApprovalRequirement::Forbidden {
+                reason: "command matches approvals.always_deny rule `cargo publish*`".to_string()
+            }
+        );
+    }
 }
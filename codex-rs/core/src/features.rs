@@ -31,6 +31,8 @@ pub enum Feature {
     GhostCommit,
     /// Use the single unified PTY-backed exec tool.
     UnifiedExec,
+    /// Hand long-running command output to the model in budgeted chunks.
+    StreamingExec,
     /// Enable experimental RMCP features such as OAuth login.
     RmcpClient,
     /// Include the freeform apply_patch tool.
@@ -51,6 +53,16 @@ pub enum Feature {
     ShellTool,
     /// Allow model to call multiple tools in parallel (only for models supporting it).
     ParallelToolCalls,
+    /// Write session notes on shutdown for the next session to recall.
+    SessionNotes,
+    /// Record raw model requests and responses for offline replay.
+    DebugCapture,
+    /// Suggest follow-up prompts with a side request after each turn.
+    FollowUpSuggestions,
+    /// Tell the model about workspace changes made between turns.
+    EnvironmentDrift,
+    /// Let the model run tasks in parallel subagents with `subagent_run`.
+    Subagents,
 }
 
 impl Feature {
@@ -277,6 +289,12 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Experimental,
         default_enabled: false,
     },
+    FeatureSpec {
+        id: Feature::StreamingExec,
+        key: "streaming_exec",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::RmcpClient,
         key: "rmcp_client",
@@ -331,4 +349,34 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Stable,
         default_enabled: true,
     },
+    FeatureSpec {
+        id: Feature::SessionNotes,
+        key: "session_notes",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::DebugCapture,
+        key: "debug_capture",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::FollowUpSuggestions,
+        key: "follow_up_suggestions",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::EnvironmentDrift,
+        key: "environment_drift",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::Subagents,
+        key: "subagents",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
 ];
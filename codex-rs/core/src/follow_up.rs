@@ -0,0 +1,176 @@
+//! Follow-up suggestions offered after a turn completes.
+//!
+//! With the `follow_up_suggestions` feature enabled, the end of the
+//! conversation is sent to a small model in a side request that does not
+//! touch the session history. The model proposes up to
+//! [`MAX_SUGGESTIONS`] short prompts the user might send next; clients show
+//! them as chips and submit one with `Op::AcceptSuggestion`.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::ConversationId;
+use codex_protocol::config_types::ReasoningEffort;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::SessionSource;
+use codex_utils_string::take_bytes_at_char_boundary;
+use codex_utils_string::take_last_bytes_at_char_boundary;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::time::timeout;
+
+use crate::AuthManager;
+use crate::ModelProviderInfo;
+use crate::client::ModelClient;
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::config::Config;
+use crate::session_summary::build_transcript;
+
+/// Model used for the suggestion side request.
+const FOLLOW_UP_MODEL: &str = "gpt-5.1-codex-mini";
+
+const FOLLOW_UP_REASONING: ReasoningEffort = ReasoningEffort::Low;
+
+const FOLLOW_UP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Only the end of the conversation is relevant to what comes next.
+const MAX_TRANSCRIPT_BYTES: usize = 16_000;
+
+pub(crate) const MAX_SUGGESTIONS: usize = 3;
+
+/// Upper bound on a single suggestion.
+const MAX_SUGGESTION_BYTES: usize = 200;
+
+const FOLLOW_UP_INSTRUCTIONS: &str = r#"You propose what a user might ask a coding agent next, given the end of their conversation.
+
+Return a JSON object with `suggestions`: at most three follow-up requests, written as the user would type them. Each must be one short imperative sentence that builds directly on the agent's last answer. Do not repeat requests the user already made. Return an empty array when nothing useful comes to mind."#;
+
+#[derive(Debug, Default, Deserialize)]
+struct SuggestionsResponse {
+    #[serde(default)]
+    suggestions: Vec<String>,
+}
+
+/// Ask [`FOLLOW_UP_MODEL`] for follow-up prompts to `history`.
+pub(crate) async fn suggest_follow_ups(
+    config: Arc<Config>,
+    provider: ModelProviderInfo,
+    auth_manager: Arc<AuthManager>,
+    otel: &OtelEventManager,
+    conversation_id: ConversationId,
+    session_source: SessionSource,
+    history: &[ResponseItem],
+) -> anyhow::Result<Vec<String>> {
+    let transcript = build_transcript(history);
+    if transcript.is_empty() {
+        return Ok(Vec::new());
+    }
+    let transcript = take_last_bytes_at_char_boundary(&transcript, MAX_TRANSCRIPT_BYTES);
+
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!("Suggest follow-ups for this conversation:\n\n{transcript}"),
+            }],
+        }],
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions_override: Some(FOLLOW_UP_INSTRUCTIONS.to_string()),
+        output_schema: Some(suggestions_schema()),
+    };
+
+    let mut follow_up_config = (*config).clone();
+    follow_up_config.model = FOLLOW_UP_MODEL.to_string();
+    let child_otel = otel.with_model(FOLLOW_UP_MODEL, &follow_up_config.model_family.slug);
+    let client = ModelClient::new(
+        Arc::new(follow_up_config),
+        Some(auth_manager),
+        child_otel,
+        provider,
+        Some(FOLLOW_UP_REASONING),
+        config.model_reasoning_summary,
+        conversation_id,
+        session_source,
+    );
+
+    let output = timeout(FOLLOW_UP_TIMEOUT, async move {
+        let mut stream = client.stream(&prompt).await?;
+        let mut last_text = None;
+        while let Some(event) = stream.next().await {
+            match event? {
+                ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. }) => {
+                    last_text = content.into_iter().find_map(|item| match item {
+                        ContentItem::OutputText { text } => Some(text),
+                        _ => None,
+                    });
+                }
+                ResponseEvent::Completed { .. } => break,
+                _ => {}
+            }
+        }
+        Ok::<_, crate::error::CodexErr>(last_text)
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("follow-up request timed out"))??;
+
+    match output {
+        Some(output) => parse_suggestions(&output),
+        None => Ok(Vec::new()),
+    }
+}
+
+fn parse_suggestions(output: &str) -> anyhow::Result<Vec<String>> {
+    let response: SuggestionsResponse = serde_json::from_str(output.trim())?;
+    let mut suggestions: Vec<String> = Vec::new();
+    for suggestion in response.suggestions {
+        let suggestion = take_bytes_at_char_boundary(suggestion.trim(), MAX_SUGGESTION_BYTES);
+        if suggestion.is_empty() || suggestions.iter().any(|s| s == suggestion) {
+            continue;
+        }
+        suggestions.push(suggestion.to_string());
+        if suggestions.len() == MAX_SUGGESTIONS {
+            break;
+        }
+    }
+    Ok(suggestions)
+}
+
+fn suggestions_schema() -> serde_json::Value {
+    json!({
+        "name": "follow_up_suggestions",
+        "strict": true,
+        "schema": {
+            "type": "object",
+            "properties": {
+                "suggestions": { "type": "array", "items": { "type": "string" } },
+            },
+            "required": ["suggestions"],
+            "additionalProperties": false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_suggestions_trims_dedupes_and_caps() {
+        let output = r#"{"suggestions": [" Add tests ", "", "Add tests", "Update the docs", "Run clippy", "Open a PR"]}"#;
+        assert_eq!(
+            parse_suggestions(output).expect("parse"),
+            vec![
+                "Add tests".to_string(),
+                "Update the docs".to_string(),
+                "Run clippy".to_string(),
+            ]
+        );
+    }
+}
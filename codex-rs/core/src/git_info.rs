@@ -179,7 +179,10 @@ pub async fn git_diff_to_remote(cwd: &Path) -> Option<GitDiffToRemote> {
 }
 
 /// Run a git command with a timeout to prevent blocking on large repositories
-async fn run_git_command_with_timeout(args: &[&str], cwd: &Path) -> Option<std::process::Output> {
+pub(crate) async fn run_git_command_with_timeout(
+    args: &[&str],
+    cwd: &Path,
+) -> Option<std::process::Output> {
     let result = timeout(
         GIT_COMMAND_TIMEOUT,
         Command::new("git").args(args).current_dir(cwd).output(),
@@ -1,4 +1,5 @@
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimits;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use std::collections::HashMap;
@@ -35,6 +36,7 @@ where
         sandbox_policy,
         stdio_policy,
         env,
+        ResourceLimits::default(),
     )
     .await
 }
@@ -7,12 +7,14 @@
 
 pub mod api_bridge;
 mod apply_patch;
+pub mod approval_rules;
 pub mod auth;
 pub mod bash;
 mod client;
 mod client_common;
 pub mod codex;
 mod codex_conversation;
+mod compact_local;
 mod compact_remote;
 pub use codex_conversation::CodexConversation;
 mod codex_delegate;
@@ -20,16 +22,23 @@ mod command_safety;
 pub mod config;
 pub mod config_loader;
 mod context_manager;
+pub mod cost;
 pub mod custom_prompts;
+pub mod debug_capture;
+pub mod doctor;
+mod env_drift;
 mod environment_context;
 pub mod error;
 pub mod exec;
 pub mod exec_env;
+pub mod exec_output;
 mod exec_policy;
 pub mod features;
 mod flags;
+mod follow_up;
 pub mod git_info;
 pub mod landlock;
+mod local_provider;
 pub mod mcp;
 mod mcp_connection_manager;
 pub mod memory;
@@ -40,8 +49,13 @@ mod mcp_tool_call;
 mod message_history;
 mod model_provider_info;
 pub mod parse_command;
+pub mod patch_hunks;
+mod pinned_files;
+pub mod plan_mode;
 pub mod powershell;
 mod response_processing;
+mod retry_policy;
+mod revert;
 pub mod sandboxing;
 mod text_encoding;
 pub mod token_data;
@@ -51,6 +65,7 @@ mod user_instructions;
 pub use model_provider_info::DEFAULT_LMSTUDIO_PORT;
 pub use model_provider_info::DEFAULT_OLLAMA_PORT;
 pub use model_provider_info::LMSTUDIO_OSS_PROVIDER_ID;
+pub use model_provider_info::LOCAL_PROVIDER_ID;
 pub use model_provider_info::ModelProviderInfo;
 pub use model_provider_info::OLLAMA_OSS_PROVIDER_ID;
 pub use model_provider_info::WireApi;
@@ -68,14 +83,24 @@ pub use auth::CodexAuth;
 pub mod default_client;
 pub mod model_family;
 mod openai_model_info;
+pub mod onboarding;
 pub mod project_doc;
+pub mod prompt_adaptation;
+pub mod redaction;
+pub mod resource_limits;
 mod rollout;
+mod session_summary;
+mod stall_watchdog;
+pub mod subagent_scratchpad;
+mod subsystem_startup;
 pub(crate) mod safety;
 pub mod seatbelt;
 pub mod shell;
 pub mod skills;
 pub mod spawn;
+pub mod storage;
 pub mod terminal;
+pub mod transcription;
 mod tools;
 pub mod turn_diff_tracker;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
@@ -89,12 +114,16 @@ pub use rollout::list::ConversationsPage;
 pub use rollout::list::Cursor;
 pub use rollout::list::parse_cursor;
 pub use rollout::list::read_head_for_summary;
+pub use rollout::lock;
+pub use rollout::replay;
+pub use rollout::sessions;
 mod function_tool;
 mod state;
 mod tasks;
 mod user_notification;
 mod user_shell_command;
 pub mod util;
+mod webhooks;
 
 pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
 pub use command_safety::is_safe_command;
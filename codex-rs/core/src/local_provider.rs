@@ -0,0 +1,94 @@
+//! Capability detection for the built-in `local` provider.
+//!
+//! llama.cpp and vLLM serve whatever weights they were started with, so the
+//! context window cannot come from the model name. Both report it over HTTP:
+//! vLLM as `max_model_len` on each `/v1/models` entry, llama.cpp as `n_ctx`
+//! in `/props`. Detection is best effort; an unreachable server or an
+//! unfamiliar response leaves the window unknown, which only turns off the
+//! context meter and auto-compaction.
+
+use std::time::Duration;
+
+use serde_json::Value as JsonValue;
+
+use crate::default_client::build_reqwest_client;
+use crate::model_provider_info::ModelProviderInfo;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Context window of `model` as reported by the server behind `provider`.
+pub(crate) async fn detect_context_window(
+    provider: &ModelProviderInfo,
+    model: &str,
+) -> Option<i64> {
+    let base_url = provider.base_url.as_deref()?.trim_end_matches('/');
+    if let Some(models) = get_json(&format!("{base_url}/models")).await
+        && let Some(window) = context_window_from_models(&models, model)
+    {
+        return Some(window);
+    }
+    let root = base_url.strip_suffix("/v1").unwrap_or(base_url);
+    get_json(&format!("{root}/props"))
+        .await
+        .and_then(|props| context_window_from_props(&props))
+}
+
+async fn get_json(url: &str) -> Option<JsonValue> {
+    let response = build_reqwest_client()
+        .get(url)
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+        .inspect_err(|err| tracing::debug!("failed to probe {url}: {err}"))
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    response.json().await.ok()
+}
+
+/// vLLM's `max_model_len` for `model`, or for the only model served.
+fn context_window_from_models(models: &JsonValue, model: &str) -> Option<i64> {
+    let entries = models.get("data")?.as_array()?;
+    let entry = entries
+        .iter()
+        .find(|entry| entry.get("id").and_then(JsonValue::as_str) == Some(model))
+        .or_else(|| (entries.len() == 1).then(|| &entries[0]))?;
+    entry.get("max_model_len")?.as_i64()
+}
+
+/// llama.cpp's configured `n_ctx`, which may be smaller than the context the
+/// model was trained with.
+fn context_window_from_props(props: &JsonValue) -> Option<i64> {
+    props
+        .get("default_generation_settings")?
+        .get("n_ctx")?
+        .as_i64()
+        .filter(|n_ctx| *n_ctx > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn reads_vllm_and_llama_cpp_windows() {
+        let vllm = json!({
+            "object": "list",
+            "data": [
+                {"id": "qwen-coder", "max_model_len": 32768},
+                {"id": "llama", "max_model_len": 8192}
+            ]
+        });
+        assert_eq!(context_window_from_models(&vllm, "llama"), Some(8192));
+        assert_eq!(context_window_from_models(&vllm, "unknown"), None);
+
+        let llama_cpp_models =
+            json!({"data": [{"id": "model.gguf", "meta": {"n_ctx_train": 131072}}]});
+        assert_eq!(context_window_from_models(&llama_cpp_models, "x"), None);
+        let props = json!({"default_generation_settings": {"n_ctx": 16384}});
+        assert_eq!(context_window_from_props(&props), Some(16384));
+    }
+}
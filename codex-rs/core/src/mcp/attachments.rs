@@ -0,0 +1,210 @@
+//! On-disk store for images returned by MCP tools.
+//!
+//! Image content blocks arrive as inline base64. Saving them under
+//! `~/.codex/attachments/<conversation-id>/` lets clients show charts and
+//! screenshots by path. Oversized images are skipped, and conversation
+//! directories that have not been written to for [`ATTACHMENT_MAX_AGE`] are
+//! pruned when a session starts.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use base64::Engine;
+use codex_protocol::ConversationId;
+use codex_protocol::protocol::McpImageAttachment;
+use mcp_types::CallToolResult;
+use mcp_types::ContentBlock;
+use tracing::warn;
+
+pub const ATTACHMENTS_SUBDIR: &str = "attachments";
+
+/// Largest decoded image written to the store.
+const MAX_ATTACHMENT_BYTES: usize = 10 * 1024 * 1024;
+
+/// Conversation directories untouched for longer than this are pruned.
+const ATTACHMENT_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Writes every image block in `result` to the conversation's attachment
+/// directory and returns where each one was saved. Images that cannot be
+/// decoded, exceed [`MAX_ATTACHMENT_BYTES`], or fail to write are skipped.
+pub(crate) async fn save_image_attachments(
+    codex_home: &Path,
+    conversation_id: ConversationId,
+    call_id: &str,
+    result: &CallToolResult,
+) -> Vec<McpImageAttachment> {
+    let dir = codex_home
+        .join(ATTACHMENTS_SUBDIR)
+        .join(conversation_id.to_string());
+    let mut attachments = Vec::new();
+    for (index, block) in result.content.iter().enumerate() {
+        let ContentBlock::ImageContent(image) = block else {
+            continue;
+        };
+        // Base64 encodes 3 bytes in 4 characters; reject before decoding.
+        if image.data.len() / 4 * 3 > MAX_ATTACHMENT_BYTES {
+            warn!("skipping oversized image from MCP tool call {call_id}");
+            continue;
+        }
+        let bytes = match base64::engine::general_purpose::STANDARD.decode(&image.data) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                warn!("skipping undecodable image from MCP tool call {call_id}: {err}");
+                continue;
+            }
+        };
+
+        let file_name = format!(
+            "{}-{index}.{}",
+            sanitize_file_stem(call_id),
+            extension_for_mime(&image.mime_type)
+        );
+        let path = dir.join(file_name);
+        let write = async {
+            tokio::fs::create_dir_all(&dir).await?;
+            tokio::fs::write(&path, &bytes).await
+        };
+        if let Err(err) = write.await {
+            warn!("failed to save MCP image to {}: {err}", path.display());
+            continue;
+        }
+        attachments.push(McpImageAttachment {
+            path,
+            mime_type: image.mime_type.clone(),
+        });
+    }
+    attachments
+}
+
+/// Removes conversation attachment directories last modified before
+/// `now - ATTACHMENT_MAX_AGE`. Returns the directories that were removed.
+pub fn prune_attachments(codex_home: &Path, now: SystemTime) -> io::Result<Vec<PathBuf>> {
+    let root = codex_home.join(ATTACHMENTS_SUBDIR);
+    let entries = match std::fs::read_dir(&root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let Some(cutoff) = now.checked_sub(ATTACHMENT_MAX_AGE) else {
+        return Ok(Vec::new());
+    };
+
+    let mut removed = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_dir() && metadata.modified()? < cutoff {
+            std::fs::remove_dir_all(entry.path())?;
+            removed.push(entry.path());
+        }
+    }
+    Ok(removed)
+}
+
+fn sanitize_file_stem(call_id: &str) -> String {
+    call_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn extension_for_mime(mime_type: &str) -> &'static str {
+    match mime_type {
+        "image/png" => "png",
+        "image/jpeg" | "image/jpg" => "jpg",
+        "image/gif" => "gif",
+        "image/webp" => "webp",
+        "image/svg+xml" => "svg",
+        "image/bmp" => "bmp",
+        _ => "bin",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mcp_types::ImageContent;
+    use mcp_types::TextContent;
+    use pretty_assertions::assert_eq;
+
+    fn image_block(data: &[u8], mime_type: &str) -> ContentBlock {
+        ContentBlock::ImageContent(ImageContent {
+            annotations: None,
+            data: base64::engine::general_purpose::STANDARD.encode(data),
+            mime_type: mime_type.to_string(),
+            r#type: "image".to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn saves_image_blocks_and_skips_other_content() -> anyhow::Result<()> {
+        let codex_home = tempfile::tempdir()?;
+        let conversation_id = ConversationId::new();
+        let result = CallToolResult {
+            content: vec![
+                ContentBlock::TextContent(TextContent {
+                    annotations: None,
+                    text: "chart".to_string(),
+                    r#type: "text".to_string(),
+                }),
+                image_block(b"png bytes", "image/png"),
+                ContentBlock::ImageContent(ImageContent {
+                    annotations: None,
+                    data: "not base64!".to_string(),
+                    mime_type: "image/jpeg".to_string(),
+                    r#type: "image".to_string(),
+                }),
+            ],
+            is_error: None,
+            structured_content: None,
+        };
+
+        let attachments =
+            save_image_attachments(codex_home.path(), conversation_id, "call/1", &result).await;
+
+        let expected_path = codex_home
+            .path()
+            .join(ATTACHMENTS_SUBDIR)
+            .join(conversation_id.to_string())
+            .join("call_1-1.png");
+        assert_eq!(
+            attachments,
+            vec![McpImageAttachment {
+                path: expected_path.clone(),
+                mime_type: "image/png".to_string(),
+            }]
+        );
+        assert_eq!(std::fs::read(expected_path)?, b"png bytes");
+        Ok(())
+    }
+
+    #[test]
+    fn prune_removes_only_stale_conversation_directories() -> anyhow::Result<()> {
+        let codex_home = tempfile::tempdir()?;
+        let stale = codex_home.path().join(ATTACHMENTS_SUBDIR).join("stale");
+        std::fs::create_dir_all(&stale)?;
+
+        let now = SystemTime::now();
+        assert_eq!(
+            prune_attachments(codex_home.path(), now)?,
+            Vec::<PathBuf>::new()
+        );
+
+        let later = now + ATTACHMENT_MAX_AGE + Duration::from_secs(60);
+        assert_eq!(
+            prune_attachments(codex_home.path(), later)?,
+            vec![stale.clone()]
+        );
+        assert!(!stale.exists());
+        Ok(())
+    }
+}
@@ -1 +1,3 @@
+pub mod attachments;
 pub mod auth;
+pub mod secrets;
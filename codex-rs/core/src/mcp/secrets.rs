@@ -0,0 +1,287 @@
+//! `secret://name` references in MCP server configs.
+//!
+//! Instead of a token, the `env` values of a stdio server and the
+//! `http_headers` values of an HTTP server may hold `secret://name`. The name
+//! is looked up with the providers listed under `[secrets]` each time the
+//! server starts, so `config.toml` never holds the token itself. A server
+//! with a secret that no provider has is not started.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context;
+use anyhow::Result;
+use async_trait::async_trait;
+use codex_keyring_store::DefaultKeyringStore;
+use codex_keyring_store::KeyringStore;
+use codex_protocol::protocol::McpUnresolvedSecrets;
+use tokio::process::Command;
+use tracing::debug;
+use tracing::warn;
+
+use crate::config::types::McpServerConfig;
+use crate::config::types::McpServerTransportConfig;
+use crate::config::types::SecretProviderKind;
+use crate::config::types::Secrets;
+
+pub const SECRET_REF_PREFIX: &str = "secret://";
+
+const ENV_PREFIX: &str = "CODEX_SECRET_";
+const KEYRING_SERVICE: &str = "Codex Secrets";
+
+/// A place secrets can be read from.
+#[async_trait]
+pub trait CredentialProvider: Send + Sync {
+    /// Name used when a lookup fails.
+    fn name(&self) -> &'static str;
+
+    /// The secret called `name`, or `None` when this provider does not have
+    /// it.
+    async fn get(&self, name: &str) -> Result<Option<String>>;
+}
+
+/// Reads `secret://github-token` from `CODEX_SECRET_GITHUB_TOKEN`.
+struct EnvProvider;
+
+#[async_trait]
+impl CredentialProvider for EnvProvider {
+    fn name(&self) -> &'static str {
+        "env"
+    }
+
+    async fn get(&self, name: &str) -> Result<Option<String>> {
+        Ok(std::env::var(env_var_name(name))
+            .ok()
+            .filter(|value| !value.is_empty()))
+    }
+}
+
+fn env_var_name(name: &str) -> String {
+    let suffix: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{ENV_PREFIX}{suffix}")
+}
+
+/// Reads the secret stored under the `Codex Secrets` service, with the
+/// secret name as the account.
+struct KeychainProvider {
+    store: Arc<dyn KeyringStore>,
+}
+
+#[async_trait]
+impl CredentialProvider for KeychainProvider {
+    fn name(&self) -> &'static str {
+        "keychain"
+    }
+
+    async fn get(&self, name: &str) -> Result<Option<String>> {
+        let store = Arc::clone(&self.store);
+        let name = name.to_string();
+        // Keychain access can block on an OS unlock prompt.
+        let value = tokio::task::spawn_blocking(move || store.load(KEYRING_SERVICE, &name))
+            .await
+            .context("keychain lookup panicked")??;
+        Ok(value)
+    }
+}
+
+/// Reads secrets with `op read`. A name such as `Work/github/token` is a
+/// full `op://` reference; a bare name is the `credential` field of that
+/// item in the configured vault.
+struct OnePasswordProvider {
+    vault: String,
+}
+
+#[async_trait]
+impl CredentialProvider for OnePasswordProvider {
+    fn name(&self) -> &'static str {
+        "1password"
+    }
+
+    async fn get(&self, name: &str) -> Result<Option<String>> {
+        let reference = if name.contains('/') {
+            format!("op://{name}")
+        } else {
+            format!("op://{}/{name}/credential", self.vault)
+        };
+        let output = Command::new("op")
+            .args(["read", "--no-newline", &reference])
+            .kill_on_drop(true)
+            .output()
+            .await
+            .context("failed to run the 1Password CLI (`op`)")?;
+        if !output.status.success() {
+            debug!(
+                "op read {reference} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return Ok(None);
+        }
+        let value = String::from_utf8(output.stdout).context("secret is not UTF-8")?;
+        Ok(Some(value).filter(|value| !value.is_empty()))
+    }
+}
+
+/// Looks secrets up with each configured provider in turn.
+pub struct SecretResolver {
+    providers: Vec<Box<dyn CredentialProvider>>,
+}
+
+impl SecretResolver {
+    pub fn new(providers: Vec<Box<dyn CredentialProvider>>) -> Self {
+        Self { providers }
+    }
+
+    pub fn from_config(secrets: &Secrets) -> Self {
+        let providers = secrets
+            .providers
+            .iter()
+            .map(|kind| -> Box<dyn CredentialProvider> {
+                match kind {
+                    SecretProviderKind::Env => Box::new(EnvProvider),
+                    SecretProviderKind::Keychain => Box::new(KeychainProvider {
+                        store: Arc::new(DefaultKeyringStore),
+                    }),
+                    SecretProviderKind::OnePassword => Box::new(OnePasswordProvider {
+                        vault: secrets.onepassword_vault.clone(),
+                    }),
+                }
+            })
+            .collect();
+        Self::new(providers)
+    }
+
+    /// The secret from the first provider that has it. Provider errors are
+    /// logged and treated as the secret being absent.
+    pub async fn resolve(&self, name: &str) -> Option<String> {
+        for provider in &self.providers {
+            match provider.get(name).await {
+                Ok(Some(value)) => return Some(value),
+                Ok(None) => {}
+                Err(error) => warn!(
+                    "failed to read secret `{name}` from {}: {error:#}",
+                    provider.name()
+                ),
+            }
+        }
+        None
+    }
+
+    /// `transport` with its secret references replaced by their values,
+    /// and the names of the secrets that could not be found.
+    pub async fn resolve_transport(
+        &self,
+        mut transport: McpServerTransportConfig,
+    ) -> (McpServerTransportConfig, Vec<String>) {
+        let mut unresolved = Vec::new();
+        let values = match &mut transport {
+            McpServerTransportConfig::Stdio { env, .. } => env.as_mut(),
+            McpServerTransportConfig::StreamableHttp { http_headers, .. } => http_headers.as_mut(),
+        };
+        if let Some(values) = values {
+            for value in values.values_mut() {
+                let Some(name) = value.strip_prefix(SECRET_REF_PREFIX) else {
+                    continue;
+                };
+                let name = name.to_string();
+                match self.resolve(&name).await {
+                    Some(secret) => *value = secret,
+                    None if !unresolved.contains(&name) => unresolved.push(name),
+                    None => {}
+                }
+            }
+        }
+        unresolved.sort();
+        (transport, unresolved)
+    }
+
+    /// Enabled servers that reference secrets no provider has, by name.
+    pub async fn unresolved_secrets(
+        &self,
+        servers: &HashMap<String, McpServerConfig>,
+    ) -> Vec<McpUnresolvedSecrets> {
+        let mut result = Vec::new();
+        for (server, config) in servers.iter().filter(|(_, config)| config.enabled) {
+            let (_, secrets) = self.resolve_transport(config.transport.clone()).await;
+            if !secrets.is_empty() {
+                result.push(McpUnresolvedSecrets {
+                    server: server.clone(),
+                    secrets,
+                });
+            }
+        }
+        result.sort_by(|a, b| a.server.cmp(&b.server));
+        result
+    }
+}
+
+/// Startup error for a server whose secrets could not all be found.
+pub fn unresolved_secrets_error(secrets: &[String]) -> String {
+    let names = secrets
+        .iter()
+        .map(|name| format!("`{SECRET_REF_PREFIX}{name}`"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("no secret provider has {names}; see `[secrets]` in config.toml")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    struct FixedProvider(HashMap<String, String>);
+
+    #[async_trait]
+    impl CredentialProvider for FixedProvider {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        async fn get(&self, name: &str) -> Result<Option<String>> {
+            Ok(self.0.get(name).cloned())
+        }
+    }
+
+    #[tokio::test]
+    async fn replaces_found_secrets_and_reports_missing_ones() {
+        let resolver = SecretResolver::new(vec![Box::new(FixedProvider(HashMap::from([(
+            "github".to_string(),
+            "ghp_123".to_string(),
+        )])))]);
+        let transport = McpServerTransportConfig::Stdio {
+            command: "server".to_string(),
+            args: Vec::new(),
+            env: Some(HashMap::from([
+                ("GITHUB_TOKEN".to_string(), "secret://github".to_string()),
+                ("LINEAR_TOKEN".to_string(), "secret://linear".to_string()),
+                ("MODE".to_string(), "fast".to_string()),
+            ])),
+            env_vars: Vec::new(),
+            cwd: None,
+        };
+
+        let (transport, unresolved) = resolver.resolve_transport(transport).await;
+
+        assert_eq!(unresolved, vec!["linear".to_string()]);
+        let McpServerTransportConfig::Stdio { env: Some(env), .. } = transport else {
+            panic!("expected stdio transport");
+        };
+        assert_eq!(env["GITHUB_TOKEN"], "ghp_123");
+        assert_eq!(env["MODE"], "fast");
+    }
+
+    #[test]
+    fn env_var_names_are_uppercased_and_sanitized() {
+        assert_eq!(env_var_name("github-token"), "CODEX_SECRET_GITHUB_TOKEN");
+        assert_eq!(env_var_name("team.api_key"), "CODEX_SECRET_TEAM_API_KEY");
+    }
+}
@@ -13,9 +13,12 @@ use std::ffi::OsString;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::Weak;
 use std::time::Duration;
 
 use crate::mcp::auth::McpAuthStatusEntry;
+use crate::mcp::secrets::SecretResolver;
+use crate::mcp::secrets::unresolved_secrets_error;
 use anyhow::Context;
 use anyhow::Result;
 use anyhow::anyhow;
@@ -25,6 +28,8 @@ use codex_async_utils::OrCancelExt;
 use codex_protocol::approvals::ElicitationRequestEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::McpServerDownEvent;
+use codex_protocol::protocol::McpServerRestartedEvent;
 use codex_protocol::protocol::McpStartupCompleteEvent;
 use codex_protocol::protocol::McpStartupFailure;
 use codex_protocol::protocol::McpStartupStatus;
@@ -55,6 +60,7 @@ use serde::Serialize;
 use serde_json::json;
 use sha1::Digest;
 use sha1::Sha1;
+use tokio::sync::RwLock;
 use tokio::sync::oneshot;
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
@@ -78,6 +84,15 @@ pub const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
 /// Default timeout for individual MCP tool calls (10 minutes).
 const DEFAULT_TOOL_TIMEOUT: Duration = Duration::from_secs(600);
 
+/// How often the supervisor checks running servers for closed connections.
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Delay before the first automatic restart; doubled after each failure.
+const INITIAL_RESTART_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Automatic restarts attempted before a server is left down.
+const MAX_RESTART_ATTEMPTS: i32 = 5;
+
 fn qualify_tools<I>(tools: I) -> HashMap<String, ToolInfo>
 where
     I: IntoIterator<Item = ToolInfo>,
@@ -191,6 +206,7 @@ impl AsyncManagedClient {
         server_name: String,
         config: McpServerConfig,
         store_mode: OAuthCredentialsStoreMode,
+        secrets: Arc<SecretResolver>,
         cancel_token: CancellationToken,
         tx_event: Sender<Event>,
         elicitation_requests: ElicitationRequestManager,
@@ -201,8 +217,13 @@ impl AsyncManagedClient {
                 return Err(error.into());
             }
 
-            let client =
-                Arc::new(make_rmcp_client(&server_name, config.transport, store_mode).await?);
+            let (transport, unresolved) = secrets.resolve_transport(config.transport).await;
+            if !unresolved.is_empty() {
+                return Err(StartupOutcomeError::Failed {
+                    error: unresolved_secrets_error(&unresolved),
+                });
+            }
+            let client = Arc::new(make_rmcp_client(&server_name, transport, store_mode).await?);
             match start_server_task(
                 server_name,
                 client,
@@ -228,6 +249,15 @@ impl AsyncManagedClient {
         self.client.clone().await
     }
 
+    /// True when the server started successfully but its connection has
+    /// since closed. Servers that are still starting are never closed.
+    async fn is_closed(&self) -> bool {
+        match self.client.peek() {
+            Some(Ok(managed)) => managed.client.is_closed().await,
+            _ => false,
+        }
+    }
+
     async fn notify_sandbox_state_change(&self, sandbox_state: &SandboxState) -> Result<()> {
         let managed = self.client().await?;
         if !managed.server_supports_sandbox_state_capability {
@@ -263,6 +293,15 @@ pub struct SandboxState {
 pub(crate) struct McpConnectionManager {
     clients: HashMap<String, AsyncManagedClient>,
     elicitation_requests: ElicitationRequestManager,
+    restart_context: Option<RestartContext>,
+}
+
+/// What `initialize` was given, kept so servers can be started again.
+struct RestartContext {
+    configs: HashMap<String, McpServerConfig>,
+    store_mode: OAuthCredentialsStoreMode,
+    secrets: Arc<SecretResolver>,
+    tx_event: Sender<Event>,
 }
 
 impl McpConnectionManager {
@@ -270,6 +309,7 @@ impl McpConnectionManager {
         &mut self,
         mcp_servers: HashMap<String, McpServerConfig>,
         store_mode: OAuthCredentialsStoreMode,
+        secrets: Arc<SecretResolver>,
         auth_entries: HashMap<String, McpAuthStatusEntry>,
         tx_event: Sender<Event>,
         cancel_token: CancellationToken,
@@ -280,7 +320,17 @@ impl McpConnectionManager {
         let mut clients = HashMap::new();
         let mut join_set = JoinSet::new();
         let elicitation_requests = ElicitationRequestManager::default();
-        for (server_name, cfg) in mcp_servers.into_iter().filter(|(_, cfg)| cfg.enabled) {
+        let configs: HashMap<String, McpServerConfig> = mcp_servers
+            .into_iter()
+            .filter(|(_, cfg)| cfg.enabled)
+            .collect();
+        self.restart_context = Some(RestartContext {
+            configs: configs.clone(),
+            store_mode,
+            secrets: Arc::clone(&secrets),
+            tx_event: tx_event.clone(),
+        });
+        for (server_name, cfg) in configs {
             let cancel_token = cancel_token.child_token();
             let _ = emit_update(
                 &tx_event,
@@ -294,6 +344,7 @@ impl McpConnectionManager {
                 server_name.clone(),
                 cfg,
                 store_mode,
+                Arc::clone(&secrets),
                 cancel_token.clone(),
                 tx_event.clone(),
                 elicitation_requests.clone(),
@@ -356,6 +407,43 @@ impl McpConnectionManager {
         });
     }
 
+    /// Names of servers whose established connection has closed.
+    async fn closed_servers(&self) -> Vec<String> {
+        let mut closed = Vec::new();
+        for (server_name, client) in &self.clients {
+            if client.is_closed().await {
+                closed.push(server_name.clone());
+            }
+        }
+        closed
+    }
+
+    /// Replaces `server`'s client with a freshly started one. The returned
+    /// client is still starting so callers can await it without holding the
+    /// manager lock.
+    fn replace_client(&mut self, server: &str) -> Result<AsyncManagedClient> {
+        let context = self
+            .restart_context
+            .as_ref()
+            .ok_or_else(|| anyhow!("MCP servers have not been initialized"))?;
+        let config = context
+            .configs
+            .get(server)
+            .cloned()
+            .ok_or_else(|| anyhow!("unknown MCP server '{server}'"))?;
+        let client = AsyncManagedClient::new(
+            server.to_string(),
+            config,
+            context.store_mode,
+            Arc::clone(&context.secrets),
+            CancellationToken::new(),
+            context.tx_event.clone(),
+            self.elicitation_requests.clone(),
+        );
+        self.clients.insert(server.to_string(), client.clone());
+        Ok(client)
+    }
+
     async fn client_by_name(&self, name: &str) -> Result<ManagedClient> {
         self.clients
             .get(name)
@@ -631,6 +719,84 @@ impl McpConnectionManager {
     }
 }
 
+/// Stops `server` and starts it again from its configuration, waiting until
+/// the new connection is ready.
+pub(crate) async fn restart_server(
+    manager: &RwLock<McpConnectionManager>,
+    server: &str,
+) -> Result<()> {
+    let client = manager.write().await.replace_client(server)?;
+    match client.client().await {
+        Ok(_) => Ok(()),
+        Err(error) => Err(anyhow!(mcp_init_error_display(server, None, &error))),
+    }
+}
+
+/// Watches running MCP servers and restarts any whose connection closes,
+/// retrying with exponential backoff. Exits once the manager is dropped.
+pub(crate) fn spawn_supervisor(
+    manager: Weak<RwLock<McpConnectionManager>>,
+    tx_event: Sender<Event>,
+) {
+    tokio::spawn(async move {
+        loop {
+            tokio::time::sleep(HEALTH_CHECK_INTERVAL).await;
+            let Some(manager) = manager.upgrade() else {
+                return;
+            };
+            let closed = manager.read().await.closed_servers().await;
+            for server in closed {
+                emit_server_down(&tx_event, &server, "connection closed".to_string(), true).await;
+                restart_with_backoff(&manager, &tx_event, &server).await;
+            }
+        }
+    });
+}
+
+async fn restart_with_backoff(
+    manager: &RwLock<McpConnectionManager>,
+    tx_event: &Sender<Event>,
+    server: &str,
+) {
+    let mut backoff = INITIAL_RESTART_BACKOFF;
+    let mut last_error = String::new();
+    for attempt in 1..=MAX_RESTART_ATTEMPTS {
+        tokio::time::sleep(backoff).await;
+        match restart_server(manager, server).await {
+            Ok(()) => {
+                let _ = tx_event
+                    .send(Event {
+                        id: INITIAL_SUBMIT_ID.to_owned(),
+                        msg: EventMsg::McpServerRestarted(McpServerRestartedEvent {
+                            server: server.to_string(),
+                        }),
+                    })
+                    .await;
+                return;
+            }
+            Err(err) => {
+                warn!("restart attempt {attempt} for MCP server '{server}' failed: {err:#}");
+                last_error = format!("{err:#}");
+            }
+        }
+        backoff = backoff.saturating_mul(2);
+    }
+    emit_server_down(tx_event, server, last_error, false).await;
+}
+
+async fn emit_server_down(tx_event: &Sender<Event>, server: &str, reason: String, retrying: bool) {
+    let _ = tx_event
+        .send(Event {
+            id: INITIAL_SUBMIT_ID.to_owned(),
+            msg: EventMsg::McpServerDown(McpServerDownEvent {
+                server: server.to_string(),
+                reason,
+                retrying,
+            }),
+        })
+        .await;
+}
+
 async fn emit_update(
     tx_event: &Sender<Event>,
     update: McpStartupUpdateEvent,
@@ -1160,4 +1326,32 @@ mod tests {
             display
         );
     }
+
+    #[tokio::test]
+    async fn restart_server_rejects_unknown_servers() {
+        let manager = RwLock::new(McpConnectionManager::default());
+        let err = restart_server(&manager, "docs")
+            .await
+            .expect_err("manager is not initialized");
+        assert_eq!(err.to_string(), "MCP servers have not been initialized");
+
+        let (tx_event, _rx_event) = async_channel::unbounded();
+        manager
+            .write()
+            .await
+            .initialize(
+                HashMap::new(),
+                OAuthCredentialsStoreMode::default(),
+                Arc::new(SecretResolver::new(Vec::new())),
+                HashMap::new(),
+                tx_event,
+                CancellationToken::new(),
+            )
+            .await;
+        let err = restart_server(&manager, "docs")
+            .await
+            .expect_err("server is not configured");
+        assert_eq!(err.to_string(), "unknown MCP server 'docs'");
+        assert!(manager.read().await.closed_servers().await.is_empty());
+    }
 }
@@ -4,6 +4,7 @@ use tracing::error;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::mcp::attachments::save_image_attachments;
 use crate::protocol::EventMsg;
 use crate::protocol::McpInvocation;
 use crate::protocol::McpToolCallBeginEvent;
@@ -63,11 +64,25 @@ pub(crate) async fn handle_mcp_tool_call(
     if let Err(e) = &result {
         tracing::warn!("MCP tool call error: {e:?}");
     }
+    let duration = start.elapsed();
+    let images = match &result {
+        Ok(result) => {
+            save_image_attachments(
+                &turn_context.client.config().codex_home,
+                sess.conversation_id(),
+                &call_id,
+                result,
+            )
+            .await
+        }
+        Err(_) => Vec::new(),
+    };
     let tool_call_end_event = EventMsg::McpToolCallEnd(McpToolCallEndEvent {
         call_id: call_id.clone(),
         invocation,
-        duration: start.elapsed(),
+        duration,
         result: result.clone(),
+        images,
     });
 
     notify_mcp_tool_call_event(sess, turn_context, tool_call_end_event.clone()).await;
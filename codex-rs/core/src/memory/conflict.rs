@@ -0,0 +1,183 @@
+//! Contradiction detection between memories.
+//!
+//! When the user says "actually we switched from npm to pnpm", the earlier
+//! "project uses npm" memory should stop being injected. These heuristics
+//! decide whether a new memory replaces an existing one of the same type.
+
+use std::collections::HashSet;
+
+/// Embedding similarity above which a substituted term counts as a conflict.
+const SEMANTIC_CONFLICT_THRESHOLD: f32 = 0.85;
+/// Embedding similarity needed when the new memory announces a change.
+const SEMANTIC_CUE_THRESHOLD: f32 = 0.7;
+/// Term overlap (Jaccard) used instead of embeddings when they are unavailable.
+const LEXICAL_CONFLICT_THRESHOLD: f32 = 0.5;
+/// Term overlap needed when the new memory announces a change.
+const LEXICAL_CUE_THRESHOLD: f32 = 0.25;
+
+/// Phrases signalling that a statement replaces earlier knowledge.
+const CHANGE_CUES: &[&str] = &[
+    "actually",
+    "switched",
+    "instead of",
+    "no longer",
+    "not anymore",
+    "migrated",
+    "moved to",
+    "moved from",
+    "replaced",
+    "changed to",
+    "now use",
+    "deprecated",
+];
+
+/// Words ignored when comparing terms, including the change cues themselves.
+const STOPWORDS: &[&str] = &[
+    "a",
+    "actually",
+    "an",
+    "and",
+    "anymore",
+    "are",
+    "be",
+    "changed",
+    "deprecated",
+    "from",
+    "for",
+    "in",
+    "instead",
+    "is",
+    "it",
+    "longer",
+    "migrated",
+    "moved",
+    "no",
+    "not",
+    "now",
+    "of",
+    "on",
+    "or",
+    "our",
+    "replaced",
+    "switched",
+    "that",
+    "the",
+    "this",
+    "to",
+    "use",
+    "used",
+    "uses",
+    "using",
+    "was",
+    "we",
+    "were",
+    "with",
+];
+
+/// Returns true when `new` contradicts `old` and should supersede it.
+///
+/// `semantic_similarity` is the embedding similarity between the two, when the
+/// embedding service is available; otherwise term overlap is used instead.
+pub fn contradicts(new: &str, old: &str, semantic_similarity: Option<f32>) -> bool {
+    let new_terms = terms(new);
+    let old_terms = terms(old);
+    if new_terms.is_empty() || new_terms == old_terms {
+        return false;
+    }
+
+    let (similarity, threshold, cue_threshold) = match semantic_similarity {
+        Some(similarity) => (
+            similarity,
+            SEMANTIC_CONFLICT_THRESHOLD,
+            SEMANTIC_CUE_THRESHOLD,
+        ),
+        None => (
+            term_overlap(&new_terms, &old_terms),
+            LEXICAL_CONFLICT_THRESHOLD,
+            LEXICAL_CUE_THRESHOLD,
+        ),
+    };
+
+    if announces_change(new) {
+        return similarity >= cue_threshold && !new_terms.is_disjoint(&old_terms);
+    }
+
+    // A substitution ("npm" -> "pnpm") leaves terms unique to each side.
+    similarity >= threshold
+        && new_terms.difference(&old_terms).next().is_some()
+        && old_terms.difference(&new_terms).next().is_some()
+}
+
+fn announces_change(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    CHANGE_CUES.iter().any(|cue| lower.contains(cue))
+}
+
+fn terms(text: &str) -> HashSet<String> {
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .map(|term| term.trim_matches('.'))
+        .filter(|term| !term.is_empty() && !STOPWORDS.contains(term))
+        .map(str::to_string)
+        .collect()
+}
+
+fn term_overlap(a: &HashSet<String>, b: &HashSet<String>) -> f32 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f32 / union as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn change_announcement_supersedes_related_memory() {
+        assert!(contradicts(
+            "Actually we switched from npm to pnpm",
+            "The project uses npm",
+            None,
+        ));
+        assert!(!contradicts(
+            "Actually we switched from npm to pnpm",
+            "Tests live in the tests/ directory",
+            None,
+        ));
+    }
+
+    #[test]
+    fn substituted_term_supersedes_similar_memory() {
+        assert!(contradicts(
+            "Project uses pnpm for package management",
+            "Project uses npm for package management",
+            None,
+        ));
+        assert!(contradicts(
+            "Project uses pnpm for package management",
+            "Project uses npm for package management",
+            Some(0.93),
+        ));
+        assert!(!contradicts(
+            "Project uses pnpm for package management",
+            "Project uses npm for package management",
+            Some(0.4),
+        ));
+    }
+
+    #[test]
+    fn restatements_and_additions_do_not_conflict() {
+        assert!(!contradicts(
+            "The project uses npm.",
+            "the project uses npm",
+            None,
+        ));
+        assert!(!contradicts(
+            "Project uses npm workspaces for package management",
+            "Project uses npm for package management",
+            None,
+        ));
+    }
+}
@@ -1,15 +1,31 @@
 //! Memory decay and reinforcement management.
 //!
-//! This module handles the decay of memory importance over time
-//! and reinforcement when memories are used.
+//! This module handles the decay of memory importance over time,
+//! reinforcement when memories are used, and consolidation of
+//! near-duplicate memories.
 
+use std::future::Future;
 use std::sync::Arc;
 
 use tracing::info;
+use tracing::warn;
 
+use super::embedding::EmbeddingService;
+use super::embedding::EmbeddingVector;
+use super::llm_extractor::MergedMemory;
 use super::store::MemoryStore;
+use super::types::Memory;
 use super::types::MemoryConfig;
 
+/// Upper bound on evict-and-compact rounds when shrinking an oversized database.
+const MAX_SIZE_EVICTION_ROUNDS: usize = 5;
+
+/// Upper bound on clusters merged per consolidation, each costing one LLM call.
+const MAX_CONSOLIDATION_CLUSTERS: usize = 20;
+
+/// Metadata key holding the Unix timestamp of the last consolidation.
+const LAST_CONSOLIDATED_KEY: &str = "last_consolidated_at";
+
 /// Manages memory decay and reinforcement.
 pub struct DecayManager {
     store: Arc<MemoryStore>,
@@ -27,19 +43,70 @@ impl DecayManager {
     pub async fn apply_decay(&self) -> anyhow::Result<DecayResult> {
         let decayed = self.store.apply_decay().await?;
         let pruned = self.store.prune_low_importance().await?;
+        let evicted = self.enforce_limits().await?;
 
-        let result = DecayResult { decayed, pruned };
+        let result = DecayResult {
+            decayed,
+            pruned,
+            evicted,
+        };
 
-        if result.decayed > 0 || result.pruned > 0 {
+        if result.decayed > 0 || result.pruned > 0 || result.evicted > 0 {
             info!(
-                "Memory decay applied: {} memories decayed, {} pruned",
-                result.decayed, result.pruned
+                "Memory decay applied: {} memories decayed, {} pruned, {} evicted",
+                result.decayed, result.pruned, result.evicted
             );
         }
 
         Ok(result)
     }
 
+    /// Enforce the TTL, `max_memories` and `max_db_size_mb` limits, evicting
+    /// the lowest-scoring, least-recently-used memories first. Compacts the
+    /// database when anything was removed. Returns the number of evictions.
+    pub async fn enforce_limits(&self) -> anyhow::Result<u32> {
+        let mut evicted = 0;
+
+        if let Some(ttl_days) = self.config.ttl_days {
+            let cutoff = chrono::Utc::now().timestamp() - ttl_days * 86400;
+            evicted += self.store.prune_unused_since(cutoff).await?;
+        }
+
+        if self.config.max_memories > 0 {
+            let count = self.store.count().await?;
+            evicted += self
+                .store
+                .evict_lowest(count - self.config.max_memories)
+                .await?;
+        }
+
+        if evicted > 0 {
+            self.store.compact().await?;
+        }
+
+        if self.config.max_db_size_mb > 0 {
+            let max_bytes = self.config.max_db_size_mb * 1024 * 1024;
+            // Each round evicts in proportion to the overshoot, then compacts so
+            // the next size reading reflects the freed pages.
+            for _ in 0..MAX_SIZE_EVICTION_ROUNDS {
+                let size = self.store.db_size_bytes().await?;
+                let count = self.store.count().await?;
+                if size <= max_bytes || count == 0 {
+                    break;
+                }
+                let keep = count * max_bytes / size;
+                let removed = self.store.evict_lowest((count - keep).max(1)).await?;
+                self.store.compact().await?;
+                evicted += removed;
+                if removed == 0 {
+                    break;
+                }
+            }
+        }
+
+        Ok(evicted)
+    }
+
     /// Reinforce a memory when it's used.
     pub async fn reinforce(&self, memory_id: &str) -> anyhow::Result<()> {
         // Small boost for each use
@@ -54,6 +121,80 @@ impl DecayManager {
         true
     }
 
+    /// Whether the automatic consolidation is due, i.e. it is enabled and
+    /// has not run within `consolidation_interval_hours`.
+    pub async fn needs_consolidation(&self) -> bool {
+        if self.config.consolidation_interval_hours <= 0 {
+            return false;
+        }
+        let last = match self.store.get_metadata(LAST_CONSOLIDATED_KEY).await {
+            Ok(last) => last.and_then(|value| value.parse::<i64>().ok()),
+            Err(e) => {
+                warn!("Failed to read last consolidation time: {}", e);
+                return false;
+            }
+        };
+        let now = chrono::Utc::now().timestamp();
+        last.is_none_or(|last| now - last >= self.config.consolidation_interval_hours * 3600)
+    }
+
+    /// Merge clusters of near-duplicate memories into one canonical memory
+    /// each. Memories of the same type whose embeddings are at least
+    /// `consolidation_threshold` similar form a cluster; `merge` writes the
+    /// canonical text, falling back to the most important member's wording
+    /// when it fails. The merged memories are retired with `merged_from`
+    /// links to the canonical one.
+    pub async fn consolidate<F, Fut>(&self, mut merge: F) -> anyhow::Result<ConsolidationResult>
+    where
+        F: FnMut(Vec<Memory>) -> Fut,
+        Fut: Future<Output = anyhow::Result<MergedMemory>>,
+    {
+        let candidates = self.store.active_with_embeddings().await?;
+        let (memories, embeddings): (Vec<Memory>, Vec<EmbeddingVector>) =
+            candidates.into_iter().unzip();
+
+        let clusters =
+            cluster_by_similarity(&memories, &embeddings, self.config.consolidation_threshold);
+        let mut result = ConsolidationResult::default();
+        for cluster in clusters.into_iter().take(MAX_CONSOLIDATION_CLUSTERS) {
+            let members: Vec<Memory> = cluster.iter().map(|&i| memories[i].clone()).collect();
+            let merged = match merge(members.clone()).await {
+                Ok(merged) => merged,
+                Err(e) => {
+                    warn!("Memory merge failed, keeping the leader's wording: {}", e);
+                    MergedMemory {
+                        content: members[0].content.clone(),
+                        context: members[0].context.clone(),
+                    }
+                }
+            };
+
+            let canonical = canonical_memory(&members, merged);
+            let merged_from: Vec<String> = members.into_iter().map(|m| m.id).collect();
+            self.store.insert(&canonical).await?;
+            self.store.merge_into(&canonical.id, &merged_from).await?;
+            result.consolidated.push(ConsolidatedMemory {
+                memory: canonical,
+                merged_from,
+            });
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        self.store
+            .set_metadata(LAST_CONSOLIDATED_KEY, &now.to_string())
+            .await?;
+
+        if !result.consolidated.is_empty() {
+            info!(
+                "Memory consolidation merged {} memories into {}",
+                result.merged_count(),
+                result.consolidated.len()
+            );
+        }
+
+        Ok(result)
+    }
+
     /// Get the current decay configuration.
     pub fn config(&self) -> &MemoryConfig {
         &self.config
@@ -67,6 +208,83 @@ pub struct DecayResult {
     pub decayed: u32,
     /// Number of memories that were pruned (deleted).
     pub pruned: u32,
+    /// Number of memories evicted by the TTL or size limits.
+    pub evicted: u32,
+}
+
+/// Result of a consolidation.
+#[derive(Debug, Clone, Default)]
+pub struct ConsolidationResult {
+    /// One entry per merged cluster.
+    pub consolidated: Vec<ConsolidatedMemory>,
+}
+
+impl ConsolidationResult {
+    /// Number of memories retired by the consolidation.
+    pub fn merged_count(&self) -> usize {
+        self.consolidated.iter().map(|c| c.merged_from.len()).sum()
+    }
+}
+
+/// A canonical memory and the IDs of the memories it replaced.
+#[derive(Debug, Clone)]
+pub struct ConsolidatedMemory {
+    pub memory: Memory,
+    pub merged_from: Vec<String>,
+}
+
+/// Groups memories of the same type whose embeddings are at least
+/// `threshold` similar. Each memory joins the first cluster whose leader
+/// (its first member) it is similar to, so clusters cannot drift through
+/// chains of loosely related memories. `memories` is expected most
+/// important first, which makes every leader its cluster's most important
+/// member. Only clusters with two or more members are returned.
+fn cluster_by_similarity(
+    memories: &[Memory],
+    embeddings: &[EmbeddingVector],
+    threshold: f32,
+) -> Vec<Vec<usize>> {
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    for (i, memory) in memories.iter().enumerate() {
+        let cluster = clusters.iter_mut().find(|cluster| {
+            let leader = cluster[0];
+            memories[leader].memory_type == memory.memory_type
+                && EmbeddingService::cosine_similarity(&embeddings[leader], &embeddings[i])
+                    >= threshold
+        });
+        match cluster {
+            Some(cluster) => cluster.push(i),
+            None => clusters.push(vec![i]),
+        }
+    }
+    clusters.retain(|cluster| cluster.len() > 1);
+    clusters
+}
+
+/// Builds the canonical memory for a cluster: the merged text, the highest
+/// importance, the combined use count, and the cluster's full time span.
+fn canonical_memory(members: &[Memory], merged: MergedMemory) -> Memory {
+    let leader = &members[0];
+    let mut memory = Memory::new(leader.memory_type, merged.content);
+    memory.context = merged.context;
+    memory.source_file = leader.source_file.clone();
+    memory.branch = leader.branch.clone();
+    memory.importance = members
+        .iter()
+        .map(|m| m.importance)
+        .fold(leader.importance, f64::max);
+    memory.use_count = members.iter().map(|m| m.use_count).sum();
+    memory.created_at = members
+        .iter()
+        .map(|m| m.created_at)
+        .min()
+        .unwrap_or(memory.created_at);
+    memory.last_used = members
+        .iter()
+        .map(|m| m.last_used)
+        .max()
+        .unwrap_or(memory.last_used);
+    memory
 }
 
 #[cfg(test)]
@@ -77,8 +295,13 @@ mod tests {
     use tempfile::TempDir;
 
     async fn create_test_decay_manager() -> (DecayManager, Arc<MemoryStore>, TempDir) {
+        create_test_decay_manager_with_config(MemoryConfig::default()).await
+    }
+
+    async fn create_test_decay_manager_with_config(
+        config: MemoryConfig,
+    ) -> (DecayManager, Arc<MemoryStore>, TempDir) {
         let temp_dir = TempDir::new().unwrap();
-        let config = MemoryConfig::default();
         let store = Arc::new(
             MemoryStore::init(temp_dir.path(), config.clone())
                 .await
@@ -123,4 +346,123 @@ mod tests {
         let retrieved = store.get(&id).await.unwrap().unwrap();
         assert!((retrieved.importance - 0.9).abs() < 0.01);
     }
+
+    #[tokio::test]
+    async fn test_max_memories_evicts_lowest_scoring() {
+        let config = MemoryConfig {
+            max_memories: 2,
+            ..MemoryConfig::default()
+        };
+        let (manager, store, _dir) = create_test_decay_manager_with_config(config).await;
+
+        let keep = Memory::new(MemoryType::Lesson, "keep me".to_string()).with_importance(0.9);
+        let also_keep =
+            Memory::new(MemoryType::Fact, "recent fact".to_string()).with_importance(0.6);
+        let mut stale =
+            Memory::new(MemoryType::Fact, "stale fact".to_string()).with_importance(0.6);
+        stale.last_used -= 10 * 86400;
+        for memory in [&keep, &also_keep, &stale] {
+            store.insert(memory).await.unwrap();
+        }
+
+        let evicted = manager.enforce_limits().await.unwrap();
+
+        assert_eq!(evicted, 1);
+        assert_eq!(store.count().await.unwrap(), 2);
+        assert!(store.get(&stale.id).await.unwrap().is_none());
+        assert!(store.get(&keep.id).await.unwrap().is_some());
+    }
+
+    #[test]
+    fn test_cluster_by_similarity_groups_same_type_near_duplicates() {
+        let memories = vec![
+            Memory::new(MemoryType::Fact, "uses pnpm".to_string()),
+            Memory::new(MemoryType::Fact, "the project uses pnpm".to_string()),
+            Memory::new(MemoryType::Lesson, "uses pnpm, not npm".to_string()),
+            Memory::new(MemoryType::Fact, "tests live in tests/".to_string()),
+            Memory::new(MemoryType::Fact, "project uses pnpm workspaces".to_string()),
+        ];
+        let embeddings = vec![
+            vec![1.0, 0.0],
+            vec![0.99, 0.1],
+            vec![1.0, 0.0],
+            vec![0.0, 1.0],
+            vec![0.95, 0.3],
+        ];
+
+        let clusters = cluster_by_similarity(&memories, &embeddings, 0.98);
+
+        assert_eq!(clusters, vec![vec![0, 1]]);
+    }
+
+    #[tokio::test]
+    async fn test_consolidate_merges_cluster_and_records_sources() {
+        let (manager, store, _dir) = create_test_decay_manager().await;
+        if store.embedding_service().is_none() {
+            // Clustering needs embeddings.
+            return;
+        }
+
+        let first =
+            Memory::new(MemoryType::Fact, "The project uses pnpm".to_string()).with_importance(0.7);
+        let second = Memory::new(MemoryType::Fact, "The project uses pnpm.".to_string())
+            .with_importance(0.4);
+        let other = Memory::new(MemoryType::Fact, "CI runs on GitHub Actions".to_string());
+        for memory in [&first, &second, &other] {
+            store.insert(memory).await.unwrap();
+        }
+
+        let result = manager
+            .consolidate(|cluster| async move {
+                Ok(MergedMemory {
+                    content: format!("merged {} memories", cluster.len()),
+                    context: None,
+                })
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(result.consolidated.len(), 1);
+        let canonical = &result.consolidated[0];
+        assert_eq!(canonical.memory.content, "merged 2 memories");
+        assert!((canonical.memory.importance - 0.7).abs() < 0.001);
+        assert_eq!(
+            canonical.merged_from,
+            vec![first.id.clone(), second.id.clone()]
+        );
+
+        let mut recorded = store.merged_from(&canonical.memory.id).await.unwrap();
+        recorded.sort();
+        let mut expected = vec![first.id.clone(), second.id.clone()];
+        expected.sort();
+        assert_eq!(recorded, expected);
+        let retired = store.get(&second.id).await.unwrap().unwrap();
+        assert_eq!(
+            retired.superseded_by.as_deref(),
+            Some(canonical.memory.id.as_str())
+        );
+        assert!(!manager.needs_consolidation().await);
+    }
+
+    #[tokio::test]
+    async fn test_ttl_expires_unused_memories() {
+        let config = MemoryConfig {
+            ttl_days: Some(30),
+            ..MemoryConfig::default()
+        };
+        let (manager, store, _dir) = create_test_decay_manager_with_config(config).await;
+
+        let mut old_fact = Memory::new(MemoryType::Fact, "old fact".to_string());
+        old_fact.last_used -= 60 * 86400;
+        let mut old_lesson = Memory::new(MemoryType::Lesson, "old lesson".to_string());
+        old_lesson.last_used -= 60 * 86400;
+        store.insert(&old_fact).await.unwrap();
+        store.insert(&old_lesson).await.unwrap();
+
+        let evicted = manager.enforce_limits().await.unwrap();
+
+        assert_eq!(evicted, 1);
+        assert!(store.get(&old_fact.id).await.unwrap().is_none());
+        assert!(store.get(&old_lesson.id).await.unwrap().is_some());
+    }
 }
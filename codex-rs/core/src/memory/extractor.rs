@@ -4,6 +4,7 @@
 //! to store as persistent memories.
 
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -13,11 +14,18 @@ use tracing::{debug, info, warn};
 use super::store::MemoryStore;
 use super::types::{Memory, MemoryType};
 
+/// Prefix of the context line that stores a mistake's error signature.
+const SIGNATURE_PREFIX: &str = "Signature: ";
+
 /// Tracks recent command failures to detect when they get fixed.
 #[derive(Debug)]
 struct FailedAttempt {
     command: String,
     error: String,
+    /// Normalized error line used to recognize the same failure later.
+    signature: Option<String>,
+    /// Files edited since the failure, in edit order.
+    edits: Vec<PathBuf>,
     timestamp: Instant,
 }
 
@@ -117,6 +125,9 @@ impl MemoryExtractor {
     ) -> Vec<Memory> {
         let mut memories = Vec::new();
 
+        // Edits made after a failure are candidate fixes for it
+        self.record_edit(path).await;
+
         // Extract location if this is a significant file
         let location = Memory::new(
             MemoryType::Location,
@@ -146,9 +157,12 @@ impl MemoryExtractor {
             .with_context("explicitly remembered by user")
             .with_importance(0.9); // User-explicit memories are important
 
-        self.store.insert(&memory).await?;
+        let superseded = self.store.insert_superseding(&memory).await?;
 
         info!("Stored user memory: {}", memory.id);
+        if !superseded.is_empty() {
+            info!("User memory {} superseded {} older memories", memory.id, superseded.len());
+        }
         Ok(memory)
     }
 
@@ -164,7 +178,7 @@ impl MemoryExtractor {
             .with_context(format!("Original: {}", original))
             .with_importance(0.85);
 
-        if let Err(e) = self.store.insert(&memory).await {
+        if let Err(e) = self.store.insert_superseding(&memory).await {
             warn!("Failed to store correction memory: {}", e);
             return None;
         }
@@ -188,6 +202,8 @@ impl MemoryExtractor {
             FailedAttempt {
                 command: command.to_string(),
                 error: error.to_string(),
+                signature: error_signature(error),
+                edits: Vec::new(),
                 timestamp: now,
             },
         );
@@ -195,32 +211,94 @@ impl MemoryExtractor {
         debug!("Recorded failure: {}", truncate(command, 50));
     }
 
+    /// Attach a file edit to every pending failure as part of its fix.
+    async fn record_edit(&self, path: &std::path::Path) {
+        let mut failures = self.recent_failures.lock().await;
+        for failed in failures.values_mut() {
+            if !failed.edits.iter().any(|edited| edited == path) {
+                failed.edits.push(path.to_path_buf());
+            }
+        }
+    }
+
     /// Check if a successful command fixes a recent failure.
+    ///
+    /// The fix is the files edited since the failure, or the successful
+    /// command itself when it differs from the failed one. A rerun of the same
+    /// command with nothing changed in between is treated as flakiness and
+    /// produces no mistake.
     async fn check_for_fix(&self, command: &str, _output: &str) -> Option<Memory> {
-        let mut failures = self.recent_failures.lock().await;
-        let key = self.failure_key(command);
+        let failed = {
+            let mut failures = self.recent_failures.lock().await;
+            failures.remove(&self.failure_key(command))?
+        };
+        let signature = failed.signature.as_deref()?;
+
+        let fix = if !failed.edits.is_empty() {
+            let files: Vec<String> = failed
+                .edits
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            format!(
+                "editing {} and rerunning `{}`",
+                files.join(", "),
+                truncate(command, 60)
+            )
+        } else if failed.command != command {
+            format!("running `{}` instead", truncate(command, 60))
+        } else {
+            return None;
+        };
 
-        if let Some(failed) = failures.remove(&key) {
-            // This command succeeded where a similar one failed
-            let lesson_content = format!(
-                "When '{}' fails with '{}', try '{}'",
-                truncate(&failed.command, 50),
-                truncate(&failed.error, 100),
-                truncate(command, 50)
-            );
+        // The same mistake seen again: reinforce it rather than store a duplicate
+        if let Some(existing) = self
+            .matching_mistakes(&failed.error)
+            .await
+            .into_iter()
+            .next()
+        {
+            if let Err(e) = self.store.reinforce(&existing.id, 0.05).await {
+                warn!("Failed to reinforce mistake: {}", e);
+            }
+            return None;
+        }
 
-            let memory = Memory::new(MemoryType::Lesson, lesson_content)
-                .with_context(format!(
-                    "Failed command: {}\nError: {}\nFix: {}",
-                    failed.command, failed.error, command
-                ))
-                .with_importance(0.95); // Lessons from fixes are very important
+        let content = format!(
+            "`{}` failed with \"{}\"; fixed by {}",
+            truncate(&failed.command, 60),
+            signature,
+            fix
+        );
+        let memory = Memory::new(MemoryType::Mistake, content).with_context(format!(
+            "{SIGNATURE_PREFIX}{signature}\nFailed command: {}\nError: {}\nFix: {fix}",
+            failed.command,
+            truncate(&failed.error, 500)
+        ));
+
+        info!(
+            "Learned fix: {} -> {}",
+            truncate(&failed.command, 30),
+            truncate(command, 30)
+        );
+        Some(memory)
+    }
 
-            info!("Learned fix: {} -> {}", truncate(&failed.command, 30), truncate(command, 30));
-            return Some(memory);
+    /// Find stored mistakes whose error signature matches `error_output`.
+    pub async fn matching_mistakes(&self, error_output: &str) -> Vec<Memory> {
+        let Some(signature) = error_signature(error_output) else {
+            return Vec::new();
+        };
+        match self.store.get_by_type(MemoryType::Mistake).await {
+            Ok(mistakes) => mistakes
+                .into_iter()
+                .filter(|mistake| mistake_signature(mistake) == Some(signature.as_str()))
+                .collect(),
+            Err(e) => {
+                warn!("Failed to load mistakes: {}", e);
+                Vec::new()
+            }
         }
-
-        None
     }
 
     /// Generate a key for matching similar commands.
@@ -426,6 +504,49 @@ impl MemoryExtractor {
     }
 }
 
+/// Normalize error output into a signature that recognizes the same failure
+/// across runs: the first line mentioning an error (or the first non-empty
+/// line), with digit runs masked as `#` and whitespace collapsed.
+fn error_signature(output: &str) -> Option<String> {
+    let mut lines = output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    let first = lines.clone().next()?;
+    let line = lines
+        .find(|line| line.to_ascii_lowercase().contains("error"))
+        .unwrap_or(first);
+
+    let mut signature = String::new();
+    for word in line.split_whitespace() {
+        if !signature.is_empty() {
+            signature.push(' ');
+        }
+        let mut in_digits = false;
+        for ch in word.chars() {
+            if ch.is_ascii_digit() {
+                if !in_digits {
+                    signature.push('#');
+                }
+                in_digits = true;
+            } else {
+                signature.push(ch);
+                in_digits = false;
+            }
+        }
+    }
+    Some(signature.chars().take(160).collect())
+}
+
+/// The error signature stored in a mistake's context.
+fn mistake_signature(memory: &Memory) -> Option<&str> {
+    memory
+        .context
+        .as_deref()?
+        .lines()
+        .find_map(|line| line.strip_prefix(SIGNATURE_PREFIX))
+}
+
 /// Truncate a string to a maximum length.
 fn truncate(s: &str, max_len: usize) -> &str {
     if s.len() <= max_len {
@@ -438,6 +559,7 @@ fn truncate(s: &str, max_len: usize) -> &str {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
     use tempfile::TempDir;
     use crate::memory::types::MemoryConfig;
 
@@ -478,4 +600,63 @@ mod tests {
         let failures = extractor.recent_failures.lock().await;
         assert!(!failures.is_empty());
     }
+
+    #[tokio::test]
+    async fn test_mistake_links_failure_to_fix() {
+        let (extractor, _dir) = create_test_extractor().await;
+        let cwd = std::path::Path::new("/");
+        let stderr = "Compiling app v0.1.0\nerror[E0425]: cannot find value `x` in this scope\n  --> src/main.rs:4:5";
+
+        extractor
+            .on_exec_complete("cargo build", 101, "", stderr, cwd)
+            .await;
+        extractor
+            .on_file_edit(std::path::Path::new("src/main.rs"), "")
+            .await;
+        let memories = extractor
+            .on_exec_complete("cargo build", 0, "", "", cwd)
+            .await;
+
+        let mistake = memories
+            .iter()
+            .find(|m| m.memory_type == MemoryType::Mistake)
+            .expect("mistake recorded");
+        assert_eq!(
+            mistake.content,
+            "`cargo build` failed with \"error[E#]: cannot find value `x` in this scope\"; fixed by editing src/main.rs and rerunning `cargo build`"
+        );
+
+        // The same error on another line of another run matches the mistake
+        let later = "error[E0425]: cannot find value `x` in this scope\n  --> src/main.rs:9:1";
+        let matches = extractor.matching_mistakes(later).await;
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].id, mistake.id);
+
+        // Unrelated errors do not
+        assert!(
+            extractor
+                .matching_mistakes("error: linker `cc` not found")
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rerun_without_changes_is_not_a_mistake() {
+        let (extractor, _dir) = create_test_extractor().await;
+        let cwd = std::path::Path::new("/");
+
+        extractor
+            .on_exec_complete("cargo test", 101, "", "error: test failed", cwd)
+            .await;
+        let memories = extractor
+            .on_exec_complete("cargo test", 0, "", "", cwd)
+            .await;
+
+        assert!(
+            memories
+                .iter()
+                .all(|m| m.memory_type != MemoryType::Mistake)
+        );
+    }
 }
@@ -6,10 +6,113 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use codex_protocol::protocol::InjectedMemoryEntry;
+use codex_protocol::protocol::MemoryContextInjectedEvent;
+use serde::Serialize;
 use tracing::debug;
 
 use super::retriever::{MemoryRetriever, RetrievalContext};
-use super::types::{MemoryConfig, MemoryType, ScoredMemory};
+use super::types::{MemoryConfig, MemoryType, ScoreBreakdown, ScoredMemory};
+
+pub(crate) const PROJECT_MEMORY_OPEN_TAG: &str = "<project_memory>";
+
+/// Maximum memories listed under each type heading.
+const MAX_PER_SECTION: usize = 5;
+
+/// Characters of the memory id shown in each annotation, enough to tell
+/// memories apart and to find them again with the memory tools.
+const SHORT_ID_LEN: usize = 8;
+
+const SECS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// Memory text to inject into the prompt, with the reasons behind it.
+#[derive(Debug, Clone)]
+pub struct MemoryInjection {
+    /// Formatted `<project_memory>` block.
+    pub text: String,
+    /// Why each memory in `text` was chosen.
+    pub report: MemoryInjectionReport,
+}
+
+/// Explains an injection so UIs can show why a memory was included and
+/// retrieval weights can be tuned.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MemoryInjectionReport {
+    /// Injected memories in ranking order.
+    pub entries: Vec<InjectedMemory>,
+}
+
+/// One injected memory and its score breakdown.
+#[derive(Debug, Clone, Serialize)]
+pub struct InjectedMemory {
+    pub id: String,
+    pub memory_type: MemoryType,
+    pub content: String,
+    /// Unix timestamp when the memory was learned.
+    pub created_at: i64,
+    /// Combined retrieval score.
+    pub score: f64,
+    pub breakdown: ScoreBreakdown,
+    /// Human-readable reasons, e.g. "matches files you're editing".
+    pub reasons: Vec<String>,
+    /// Memory pack the entry came from, if any.
+    pub source_pack: Option<String>,
+}
+
+impl MemoryInjectionReport {
+    fn from_memories(memories: &[ScoredMemory]) -> Self {
+        let mut per_type: HashMap<MemoryType, usize> = HashMap::new();
+        let entries = memories
+            .iter()
+            .filter(|sm| {
+                let count = per_type.entry(sm.memory.memory_type).or_insert(0);
+                *count += 1;
+                *count <= MAX_PER_SECTION
+            })
+            .map(|sm| InjectedMemory {
+                id: sm.memory.id.clone(),
+                memory_type: sm.memory.memory_type,
+                content: sm.memory.content.clone(),
+                created_at: sm.memory.created_at,
+                score: sm.combined_score,
+                breakdown: sm.breakdown,
+                reasons: sm
+                    .breakdown
+                    .reasons()
+                    .into_iter()
+                    .map(str::to_string)
+                    .collect(),
+                source_pack: sm.source_pack.clone(),
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// The event that tells clients which memories the turn uses.
+    pub fn to_event(&self) -> MemoryContextInjectedEvent {
+        MemoryContextInjectedEvent {
+            memories: self
+                .entries
+                .iter()
+                .map(|entry| InjectedMemoryEntry {
+                    id: entry.id.clone(),
+                    memory_type: entry.memory_type.as_str().to_string(),
+                    content: entry.content.clone(),
+                    created_at: entry.created_at,
+                    score: entry.score,
+                    reasons: entry.reasons.clone(),
+                    source_pack: entry.source_pack.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Drop entries whose annotation did not survive truncation of `text`.
+    fn retain_present_in(&mut self, text: &str) {
+        self.entries
+            .retain(|entry| text.contains(&format!("id {}", short_id(&entry.id))));
+    }
+}
 
 /// Injects relevant memories into the agent context.
 pub struct MemoryInjector {
@@ -24,13 +127,15 @@ impl MemoryInjector {
     }
 
     /// Build memory context for the current turn.
-    /// Returns formatted memory text to inject into the prompt, or None if no relevant memories.
+    /// Returns formatted memory text to inject into the prompt with a report
+    /// of why each memory was chosen, or None if no relevant memories.
     pub async fn build_memory_context(
         &self,
         user_message: &str,
         active_files: &[std::path::PathBuf],
         recent_commands: &[String],
-    ) -> Option<String> {
+        branch: Option<String>,
+    ) -> Option<MemoryInjection> {
         if !self.config.enabled {
             return None;
         }
@@ -40,6 +145,7 @@ impl MemoryInjector {
             active_files: active_files.to_vec(),
             recent_commands: recent_commands.to_vec(),
             type_filter: None,
+            branch,
         };
 
         let memories = self.retriever.retrieve(&context).await;
@@ -54,14 +160,19 @@ impl MemoryInjector {
 
         // Truncate if too long
         let truncated = self.truncate_to_token_limit(&formatted);
+        let mut report = MemoryInjectionReport::from_memories(&memories);
+        report.retain_present_in(&truncated);
 
         debug!(
             "Injecting {} memories ({} chars) into context",
-            memories.len(),
+            report.entries.len(),
             truncated.len()
         );
 
-        Some(truncated)
+        Some(MemoryInjection {
+            text: truncated,
+            report,
+        })
     }
 
     /// Format memories into a structured prompt section.
@@ -76,10 +187,12 @@ impl MemoryInjector {
         }
 
         let mut sections = Vec::new();
+        let now = chrono::Utc::now().timestamp();
 
-        // Order: Lessons first (most important), then Decisions, Preferences, Patterns, Locations, Facts
+        // Order: Lessons first (most important), then Mistakes, Decisions, Preferences, Patterns, Locations, Facts
         let type_order = [
             (MemoryType::Lesson, "Lessons Learned"),
+            (MemoryType::Mistake, "Known Mistakes"),
             (MemoryType::Decision, "Decisions Made"),
             (MemoryType::Preference, "User Preferences"),
             (MemoryType::Pattern, "Codebase Patterns"),
@@ -94,7 +207,7 @@ impl MemoryInjector {
                 }
 
                 let mut section = format!("### {}\n", header);
-                for sm in mems.iter().take(5) {
+                for sm in mems.iter().take(MAX_PER_SECTION) {
                     // Format based on type
                     let bullet = match mem_type {
                         MemoryType::Lesson => format!(
@@ -106,6 +219,10 @@ impl MemoryInjector {
                                 String::new()
                             }
                         ),
+                        MemoryType::Mistake => format!(
+                            "- **[MISTAKE]** {}",
+                            sm.memory.content
+                        ),
                         MemoryType::Decision => format!(
                             "- **[DECISION]** {}",
                             sm.memory.content
@@ -117,6 +234,10 @@ impl MemoryInjector {
                         _ => format!("- {}", sm.memory.content),
                     };
                     section.push_str(&bullet);
+                    if let Some(ref pack) = sm.source_pack {
+                        section.push_str(&format!(" _[team pack: {pack}]_"));
+                    }
+                    section.push_str(&format!(" {}", annotation(sm, now)));
                     section.push('\n');
                 }
                 sections.push(section);
@@ -128,7 +249,7 @@ impl MemoryInjector {
         }
 
         format!(
-            "<project_memory>\n## Project Memory\n\n{}</project_memory>",
+            "{PROJECT_MEMORY_OPEN_TAG}\n## Project Memory\n\n{}</project_memory>",
             sections.join("\n")
         )
     }
@@ -179,6 +300,31 @@ impl MemoryInjector {
     }
 }
 
+/// Why a memory is in the context, e.g. `_[lesson, 3d old, score 0.82, id
+/// 1a2b3c4d]_`, so the agent can weigh stale or weak matches.
+fn annotation(sm: &ScoredMemory, now: i64) -> String {
+    format!(
+        "_[{}, {}, score {:.2}, id {}]_",
+        sm.memory.memory_type.as_str(),
+        age_label(now - sm.memory.created_at),
+        sm.combined_score,
+        short_id(&sm.memory.id)
+    )
+}
+
+fn age_label(age_secs: i64) -> String {
+    let days = age_secs.max(0) / SECS_PER_DAY;
+    match days {
+        0 => "learned today".to_string(),
+        1..=59 => format!("{days}d old"),
+        _ => format!("{}mo old", days / 30),
+    }
+}
+
+fn short_id(id: &str) -> &str {
+    id.get(..SHORT_ID_LEN).unwrap_or(id)
+}
+
 /// Truncate context to a reasonable length for display.
 fn truncate_context(ctx: &str) -> &str {
     let max_len = 60;
@@ -242,11 +388,15 @@ mod tests {
                 memory: Memory::new(MemoryType::Lesson, "always mock Redis".to_string()),
                 semantic_score: 0.9,
                 combined_score: 0.9,
+                breakdown: ScoreBreakdown::default(),
+                source_pack: None,
             },
             ScoredMemory {
                 memory: Memory::new(MemoryType::Pattern, "tests in __tests__".to_string()),
                 semantic_score: 0.8,
                 combined_score: 0.8,
+                breakdown: ScoreBreakdown::default(),
+                source_pack: None,
             },
         ];
 
@@ -257,6 +407,58 @@ mod tests {
         assert!(formatted.contains("tests in __tests__"));
     }
 
+    #[tokio::test]
+    async fn test_format_shows_pack_provenance() {
+        let (injector, _dir) = create_test_injector().await;
+
+        let memories = vec![ScoredMemory {
+            memory: Memory::new(MemoryType::Decision, "use Axum".to_string()),
+            semantic_score: 0.5,
+            combined_score: 0.5,
+            breakdown: ScoreBreakdown::default(),
+            source_pack: Some("acme-backend@1.2.0".to_string()),
+        }];
+
+        let formatted = injector.format_memories(&memories);
+        assert!(
+            formatted.contains("- **[DECISION]** use Axum _[team pack: acme-backend@1.2.0]_")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_format_annotates_type_age_and_score() {
+        let (injector, _dir) = create_test_injector().await;
+
+        let mut memory = Memory::new(MemoryType::Lesson, "always mock Redis".to_string());
+        memory.id = "1a2b3c4d-0000-0000-0000-000000000000".to_string();
+        memory.created_at -= 3 * SECS_PER_DAY;
+        let memories = vec![ScoredMemory {
+            memory,
+            semantic_score: 0.9,
+            combined_score: 0.8234,
+            breakdown: ScoreBreakdown::default(),
+            source_pack: None,
+        }];
+
+        let formatted = injector.format_memories(&memories);
+        assert!(formatted.contains(
+            "- **[LESSON]** always mock Redis _[lesson, 3d old, score 0.82, id 1a2b3c4d]_"
+        ));
+
+        let mut report = MemoryInjectionReport::from_memories(&memories);
+        report.retain_present_in(&formatted);
+        assert_eq!(report.entries.len(), 1);
+        report.retain_present_in("<project_memory>\n... (truncated)");
+        assert!(report.entries.is_empty());
+    }
+
+    #[test]
+    fn test_age_label() {
+        assert_eq!(age_label(60), "learned today");
+        assert_eq!(age_label(2 * SECS_PER_DAY), "2d old");
+        assert_eq!(age_label(90 * SECS_PER_DAY), "3mo old");
+    }
+
     #[tokio::test]
     async fn test_truncate_to_token_limit() {
         let (injector, _dir) = create_test_injector().await;
@@ -8,6 +8,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use futures::StreamExt;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use tokio::time::timeout;
@@ -60,6 +61,14 @@ pub struct ExtractionResponse {
     pub memories: Vec<ExtractedMemory>,
 }
 
+/// Canonical memory written by the LLM from a cluster of near-duplicates.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedMemory {
+    pub content: String,
+    #[serde(default)]
+    pub context: Option<String>,
+}
+
 impl LlmMemoryExtractor {
     /// Create a new LLM memory extractor.
     pub fn new(store: Arc<MemoryStore>) -> Self {
@@ -83,15 +92,19 @@ impl LlmMemoryExtractor {
         session_source: SessionSource,
         turn_summary: &str,
     ) -> Vec<Memory> {
+        let user_prompt =
+            format!("Extract memories from this conversation turn:\n\n{turn_summary}");
         let extracted = match self
-            .call_extraction_model(
+            .call_extraction_model::<ExtractionResponse>(
                 config,
                 provider,
                 auth_manager,
                 otel,
                 conversation_id,
                 session_source,
-                turn_summary,
+                EXTRACTION_SYSTEM_PROMPT,
+                user_prompt,
+                extraction_schema(),
             )
             .await
         {
@@ -115,22 +128,25 @@ impl LlmMemoryExtractor {
                 _ => MemoryType::Fact,
             };
 
-            // Check for semantic duplicates before adding
-            let is_duplicate = self
-                .store
-                .exists_semantically_similar(&em.content, memory_type, 0.85)
-                .await
-                .unwrap_or(false);
+            let mut memory = Memory::new(memory_type, em.content.clone());
+            if let Some(ctx) = em.context {
+                memory = memory.with_context(&ctx);
+            }
+            if let Some(imp) = em.importance {
+                memory = memory.with_importance(imp);
+            }
 
-            if !is_duplicate {
-                let mut memory = Memory::new(memory_type, em.content.clone());
-                if let Some(ctx) = em.context {
-                    memory = memory.with_context(&ctx);
-                }
-                if let Some(imp) = em.importance {
-                    memory = memory.with_importance(imp);
-                }
+            // A contradiction replaces the old memory even when the two are
+            // similar enough to look like duplicates.
+            let conflicts = self.store.find_conflicts(&memory).await.unwrap_or_default();
+            let is_duplicate = conflicts.is_empty()
+                && self
+                    .store
+                    .exists_semantically_similar(&em.content, memory_type, 0.85)
+                    .await
+                    .unwrap_or(false);
 
+            if !is_duplicate {
                 // Store the memory
                 if let Err(e) = self.store.insert(&memory).await {
                     warn!("Failed to store extracted memory: {}", e);
@@ -140,6 +156,11 @@ impl LlmMemoryExtractor {
                         truncate(&em.content, 50),
                         memory_type.as_str()
                     );
+                    for old in &conflicts {
+                        if let Err(e) = self.store.supersede(&old.id, &memory.id).await {
+                            warn!("Failed to supersede memory {}: {}", old.id, e);
+                        }
+                    }
                     memories.push(memory);
                 }
             } else {
@@ -153,8 +174,9 @@ impl LlmMemoryExtractor {
         memories
     }
 
-    /// Call the LLM to extract memories from turn content.
-    async fn call_extraction_model(
+    /// Merge near-duplicate memories of one type into a single canonical
+    /// memory that keeps every distinct detail.
+    pub async fn merge_memories(
         &self,
         config: Arc<Config>,
         provider: ModelProviderInfo,
@@ -162,14 +184,53 @@ impl LlmMemoryExtractor {
         otel: &OtelEventManager,
         conversation_id: ConversationId,
         session_source: SessionSource,
-        turn_summary: &str,
-    ) -> anyhow::Result<ExtractionResponse> {
-        let system_prompt = EXTRACTION_SYSTEM_PROMPT.to_string();
-        let user_prompt = format!(
-            "Extract memories from this conversation turn:\n\n{}",
-            turn_summary
-        );
+        memories: &[Memory],
+    ) -> anyhow::Result<MergedMemory> {
+        let mut user_prompt = String::from("Merge these memories into one:\n");
+        for memory in memories {
+            user_prompt.push_str(&format!(
+                "- [{}] {}",
+                memory.memory_type.as_str(),
+                memory.content
+            ));
+            if let Some(context) = &memory.context {
+                user_prompt.push_str(&format!(" (context: {context})"));
+            }
+            user_prompt.push('\n');
+        }
+
+        let merged: MergedMemory = self
+            .call_extraction_model(
+                config,
+                provider,
+                auth_manager,
+                otel,
+                conversation_id,
+                session_source,
+                MERGE_SYSTEM_PROMPT,
+                user_prompt,
+                merge_schema(),
+            )
+            .await?;
+        if merged.content.trim().is_empty() {
+            anyhow::bail!("Memory merge returned empty content");
+        }
+        Ok(merged)
+    }
 
+    /// Call the extraction model with a structured output schema.
+    async fn call_extraction_model<T: DeserializeOwned>(
+        &self,
+        config: Arc<Config>,
+        provider: ModelProviderInfo,
+        auth_manager: Arc<AuthManager>,
+        otel: &OtelEventManager,
+        conversation_id: ConversationId,
+        session_source: SessionSource,
+        system_prompt: &str,
+        user_prompt: String,
+        output_schema: serde_json::Value,
+    ) -> anyhow::Result<T> {
         let prompt = Prompt {
             input: vec![ResponseItem::Message {
                 id: None,
@@ -178,8 +239,8 @@ impl LlmMemoryExtractor {
             }],
             tools: Vec::new(),
             parallel_tool_calls: false,
-            base_instructions_override: Some(system_prompt),
-            output_schema: Some(extraction_schema()),
+            base_instructions_override: Some(system_prompt.to_string()),
+            output_schema: Some(output_schema),
         };
 
         // Create a modified config with our extraction model
@@ -225,7 +286,7 @@ impl LlmMemoryExtractor {
 
         match result {
             Ok(Ok(Some(json_str))) => {
-                let response: ExtractionResponse = serde_json::from_str(json_str.trim())?;
+                let response: T = serde_json::from_str(json_str.trim())?;
                 Ok(response)
             }
             Ok(Ok(None)) => {
@@ -302,6 +363,43 @@ Return a JSON object with a "memories" array. Each memory has:
 - importance: 0.0-1.0 based on how valuable this is (lessons/decisions should be high)
 "#;
 
+/// System prompt for merging near-duplicate memories.
+const MERGE_SYSTEM_PROMPT: &str = r#"You are a memory consolidation assistant. You are given several memories that say nearly the same thing about a software project. Write one memory that replaces all of them.
+
+Guidelines:
+- Keep every distinct, still-useful detail (paths, commands, versions, conditions)
+- Drop repetition and filler
+- Do not invent anything that is not in the memories
+- Be concise: 1-2 sentences
+
+Return a JSON object with:
+- content: The merged memory
+- context: Optional context about when this applies, or null
+"#;
+
+/// JSON schema for merge response.
+fn merge_schema() -> serde_json::Value {
+    json!({
+        "name": "memory_merge",
+        "strict": true,
+        "schema": {
+            "type": "object",
+            "properties": {
+                "content": {
+                    "type": "string",
+                    "description": "The merged memory (1-2 sentences)"
+                },
+                "context": {
+                    "type": ["string", "null"],
+                    "description": "Optional context about when this applies"
+                }
+            },
+            "required": ["content", "context"],
+            "additionalProperties": false
+        }
+    })
+}
+
 /// JSON schema for extraction response.
 fn extraction_schema() -> serde_json::Value {
     json!({
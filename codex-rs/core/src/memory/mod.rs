@@ -47,16 +47,19 @@
 //! memory.on_file_read(path, content).await;
 //!
 //! // Get context for injection (called before each turn)
-//! if let Some(context) = memory.build_context(user_message, active_files).await {
-//!     // Inject into prompt
+//! if let Some(injection) = memory.build_context(user_message, active_files).await {
+//!     // Inject injection.text into the prompt; injection.report explains
+//!     // why each memory was chosen.
 //! }
 //! ```
 
+pub mod conflict;
 pub mod decay;
 pub mod embedding;
 pub mod extractor;
 pub mod injector;
 pub mod llm_extractor;
+pub mod packs;
 pub mod retriever;
 pub mod store;
 pub mod types;
@@ -74,12 +77,20 @@ use codex_otel::otel_event_manager::OtelEventManager;
 use codex_protocol::ConversationId;
 use codex_protocol::protocol::SessionSource;
 
+pub use decay::ConsolidatedMemory;
+pub use decay::ConsolidationResult;
 pub use decay::DecayManager;
 pub use decay::DecayResult;
 pub use extractor::MemoryExtractor;
+pub use injector::InjectedMemory;
+pub use injector::MemoryInjection;
+pub use injector::MemoryInjectionReport;
 pub use injector::MemoryInjector;
 pub use injector::MemorySummary;
 pub use llm_extractor::LlmMemoryExtractor;
+pub use llm_extractor::MergedMemory;
+pub use packs::MemoryPack;
+pub use packs::MemoryPacks;
 pub use retriever::MemoryRetriever;
 pub use retriever::RetrievalContext;
 pub use store::MemoryStore;
@@ -88,6 +99,7 @@ pub use types::Memory;
 pub use types::MemoryConfig;
 pub use types::MemorySource;
 pub use types::MemoryType;
+pub use types::ScoreBreakdown;
 pub use types::ScoredMemory;
 
 /// Main facade for the memory system.
@@ -101,6 +113,7 @@ pub struct MemoryManager {
     retriever: Arc<MemoryRetriever>,
     injector: Arc<MemoryInjector>,
     decay: Arc<DecayManager>,
+    packs: Arc<MemoryPacks>,
     config: MemoryConfig,
     project_root: PathBuf,
 }
@@ -109,7 +122,8 @@ impl MemoryManager {
     /// Initialize the memory system for a project.
     ///
     /// Creates the `.kaioken/memory/` directory structure and initializes
-    /// all components.
+    /// all components. Decay is not applied here; call
+    /// [`MemoryManager::apply_decay`] once the manager is in use.
     pub async fn init(project_root: &Path, config: MemoryConfig) -> anyhow::Result<Self> {
         if !config.enabled {
             info!("Memory system disabled by configuration");
@@ -118,17 +132,13 @@ impl MemoryManager {
         let store = Arc::new(MemoryStore::init(project_root, config.clone()).await?);
         let extractor = Arc::new(MemoryExtractor::new(store.clone()));
         let llm_extractor = Arc::new(LlmMemoryExtractor::new(store.clone()));
-        let retriever = Arc::new(MemoryRetriever::new(store.clone(), config.clone()));
+        let packs = Arc::new(MemoryPacks::load(project_root, &config));
+        let retriever = Arc::new(
+            MemoryRetriever::new(store.clone(), config.clone()).with_packs(packs.clone()),
+        );
         let injector = Arc::new(MemoryInjector::new(retriever.clone(), config.clone()));
         let decay = Arc::new(DecayManager::new(store.clone(), config.clone()));
 
-        // Apply decay on startup
-        if config.enabled {
-            if let Err(e) = decay.apply_decay().await {
-                warn!("Failed to apply memory decay on startup: {}", e);
-            }
-        }
-
         info!("Memory system initialized for {}", project_root.display());
 
         Ok(Self {
@@ -138,6 +148,7 @@ impl MemoryManager {
             retriever,
             injector,
             decay,
+            packs,
             config,
             project_root: project_root.to_path_buf(),
         })
@@ -201,6 +212,28 @@ impl MemoryManager {
         }
     }
 
+    /// Notes on stored mistakes whose error matches a failed command's output,
+    /// for appending to that output so the agent does not repeat the mistake.
+    pub async fn mistake_notes(&self, error_output: &str) -> Option<String> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let mistakes = self.extractor.matching_mistakes(error_output).await;
+        if mistakes.is_empty() {
+            return None;
+        }
+
+        let mut notes = String::from("Memory: this error has been seen before.");
+        for mistake in mistakes.iter().take(3) {
+            if let Err(e) = self.store.mark_used(&mistake.id).await {
+                warn!("Failed to mark mistake as used: {}", e);
+            }
+            notes.push_str(&format!("\n- {}", mistake.content));
+        }
+        Some(notes)
+    }
+
     /// Called when the user explicitly requests to remember something.
     pub async fn remember(&self, input: &str) -> anyhow::Result<Memory> {
         self.extractor.on_user_remember(input).await
@@ -272,18 +305,20 @@ impl MemoryManager {
     // Retrieval and injection methods
     // ─────────────────────────────────────────────────────────────────────────
 
-    /// Build memory context for injection into the prompt.
+    /// Build memory context for injection into the prompt, with a report of
+    /// why each memory was chosen.
     pub async fn build_context(
         &self,
         user_message: &str,
         active_files: &[PathBuf],
-    ) -> Option<String> {
+    ) -> Option<MemoryInjection> {
         if !self.config.enabled {
             return None;
         }
 
+        let branch = crate::git_info::current_branch_name(&self.project_root).await;
         self.injector
-            .build_memory_context(user_message, active_files, &[])
+            .build_memory_context(user_message, active_files, &[], branch)
             .await
     }
 
@@ -330,7 +365,7 @@ impl MemoryManager {
         memory.context = context_str.map(|s| s.to_string());
         memory.source_file = source_file.map(|p| p.to_path_buf());
 
-        self.store.insert(&memory).await?;
+        self.store.insert_superseding(&memory).await?;
         debug!("Saved explicit memory: {} - {}", memory.id, memory.content);
         Ok(memory)
     }
@@ -364,7 +399,8 @@ impl MemoryManager {
         let matches = self.retriever.retrieve(&context).await;
         let mut deleted = 0;
 
-        for sm in matches {
+        // Pack memories are read-only and cannot be forgotten locally.
+        for sm in matches.into_iter().filter(|sm| sm.source_pack.is_none()) {
             if self.store.delete(&sm.memory.id).await? {
                 deleted += 1;
             }
@@ -384,11 +420,52 @@ impl MemoryManager {
 
     // Note: Reindexing is no longer needed - embeddings are generated on insert.
 
-    /// Apply decay manually (normally done on startup).
+    /// Apply decay to stored memories. Sessions run this in the background
+    /// once memory is ready.
     pub async fn apply_decay(&self) -> anyhow::Result<DecayResult> {
         self.decay.apply_decay().await
     }
 
+    /// Whether the automatic consolidation is due at session start.
+    pub async fn needs_consolidation(&self) -> bool {
+        self.config.enabled && self.decay.needs_consolidation().await
+    }
+
+    /// Merge near-duplicate memories, using the extraction model to write
+    /// each canonical memory.
+    pub async fn consolidate(
+        &self,
+        config: Arc<Config>,
+        provider: ModelProviderInfo,
+        auth_manager: Arc<AuthManager>,
+        otel: &OtelEventManager,
+        conversation_id: ConversationId,
+        session_source: SessionSource,
+    ) -> anyhow::Result<ConsolidationResult> {
+        self.decay
+            .consolidate(|cluster| {
+                let llm_extractor = self.llm_extractor.clone();
+                let config = config.clone();
+                let provider = provider.clone();
+                let auth_manager = auth_manager.clone();
+                let session_source = session_source.clone();
+                async move {
+                    llm_extractor
+                        .merge_memories(
+                            config,
+                            provider,
+                            auth_manager,
+                            otel,
+                            conversation_id,
+                            session_source,
+                            &cluster,
+                        )
+                        .await
+                }
+            })
+            .await
+    }
+
     /// Get the project root this memory manager is for.
     pub fn project_root(&self) -> &Path {
         &self.project_root
@@ -399,6 +476,11 @@ impl MemoryManager {
         &self.config
     }
 
+    /// Get the read-only memory packs mounted for this project.
+    pub fn packs(&self) -> &[MemoryPack] {
+        self.packs.packs()
+    }
+
     /// Get access to the store for advanced operations.
     pub fn store(&self) -> &Arc<MemoryStore> {
         &self.store
@@ -0,0 +1,276 @@
+//! Read-only team memory packs.
+//!
+//! A memory pack is a curated, versioned bundle of memories (conventions,
+//! architecture facts, past decisions) that a team publishes alongside a
+//! repository. Packs are loaded from `.kaioken/memory/packs/*.toml` and any
+//! extra paths in [`MemoryConfig::pack_paths`], kept in memory next to the
+//! local store, and never decayed, evicted, or edited.
+//!
+//! ```toml
+//! name = "acme-backend"
+//! version = "1.2.0"
+//! description = "Backend conventions"
+//!
+//! [[memories]]
+//! type = "decision"
+//! content = "Use Axum for all new HTTP services"
+//! context = "ADR-7"
+//! importance = 0.9
+//! ```
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use tracing::{debug, warn};
+
+use super::types::{Memory, MemoryConfig, MemoryType, ScoreBreakdown, ScoredMemory};
+
+/// On-disk format of a memory pack.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MemoryPack {
+    /// Pack name, shown as provenance when its memories are injected.
+    pub name: String,
+    /// Pack version, shown next to the name.
+    pub version: String,
+    /// Optional human-readable description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Memories published by the pack.
+    #[serde(default)]
+    pub memories: Vec<PackMemory>,
+}
+
+/// A single memory entry inside a pack.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackMemory {
+    /// The type of memory.
+    #[serde(rename = "type")]
+    pub memory_type: MemoryType,
+    /// The memory content.
+    pub content: String,
+    /// Optional context about where/why this applies.
+    #[serde(default)]
+    pub context: Option<String>,
+    /// Importance override; defaults to the type's default importance.
+    #[serde(default)]
+    pub importance: Option<f64>,
+}
+
+impl MemoryPack {
+    /// Parse a pack from a TOML file.
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        let pack: MemoryPack = toml::from_str(&contents)?;
+        Ok(pack)
+    }
+
+    /// Provenance label, e.g. `acme-backend@1.2.0`.
+    pub fn label(&self) -> String {
+        format!("{}@{}", self.name, self.version)
+    }
+}
+
+/// A pack memory converted into a [`Memory`] along with its provenance.
+#[derive(Debug, Clone)]
+struct PackEntry {
+    memory: Memory,
+    pack: String,
+}
+
+/// All memory packs mounted for a project.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryPacks {
+    packs: Vec<MemoryPack>,
+    entries: Vec<PackEntry>,
+}
+
+impl MemoryPacks {
+    /// Load packs from the project's pack directory and any configured paths.
+    ///
+    /// Packs that fail to parse are skipped with a warning so that one broken
+    /// file does not hide the rest.
+    pub fn load(project_root: &Path, config: &MemoryConfig) -> Self {
+        let mut files = pack_files(&project_root.join(".kaioken").join("memory").join("packs"));
+        for path in &config.pack_paths {
+            let path = project_root.join(path);
+            if path.is_dir() {
+                files.extend(pack_files(&path));
+            } else {
+                files.push(path);
+            }
+        }
+
+        let mut packs = Vec::new();
+        for file in files {
+            match MemoryPack::load(&file) {
+                Ok(pack) => packs.push(pack),
+                Err(e) => warn!("Failed to load memory pack {}: {}", file.display(), e),
+            }
+        }
+        Self::from_packs(packs)
+    }
+
+    /// Mount an explicit list of packs.
+    pub fn from_packs(packs: Vec<MemoryPack>) -> Self {
+        let mut entries = Vec::new();
+        for pack in &packs {
+            let label = pack.label();
+            for (idx, item) in pack.memories.iter().enumerate() {
+                let mut memory = Memory::new(item.memory_type, item.content.clone());
+                memory.id = format!("pack:{label}:{idx}");
+                memory.context = item.context.clone();
+                if let Some(importance) = item.importance {
+                    memory = memory.with_importance(importance);
+                }
+                entries.push(PackEntry {
+                    memory,
+                    pack: label.clone(),
+                });
+            }
+        }
+        if !packs.is_empty() {
+            debug!(
+                "Mounted {} memory packs with {} memories",
+                packs.len(),
+                entries.len()
+            );
+        }
+        Self { packs, entries }
+    }
+
+    /// The mounted packs.
+    pub fn packs(&self) -> &[MemoryPack] {
+        &self.packs
+    }
+
+    /// Whether no pack memories are available.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Pack memories matching any of `keywords`, plus important lessons and
+    /// decisions which are always included, mirroring the local store.
+    pub fn matching(&self, keywords: &[String]) -> Vec<ScoredMemory> {
+        self.entries
+            .iter()
+            .filter_map(|entry| {
+                let content = entry.memory.content.to_lowercase();
+                let hits = keywords
+                    .iter()
+                    .filter(|keyword| content.contains(keyword.as_str()))
+                    .count();
+                let always = matches!(
+                    entry.memory.memory_type,
+                    MemoryType::Lesson | MemoryType::Decision
+                ) && entry.memory.importance >= 0.7;
+                if hits == 0 && !always {
+                    return None;
+                }
+                let semantic_score = if keywords.is_empty() {
+                    0.0
+                } else {
+                    hits as f64 / keywords.len() as f64
+                };
+                Some(ScoredMemory {
+                    memory: entry.memory.clone(),
+                    semantic_score,
+                    combined_score: 0.0,
+                    breakdown: ScoreBreakdown::default(),
+                    source_pack: Some(entry.pack.clone()),
+                })
+            })
+            .collect()
+    }
+}
+
+/// List `*.toml` files in `dir`, sorted for a stable load order.
+fn pack_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const PACK: &str = r#"
+name = "acme-backend"
+version = "1.2.0"
+
+[[memories]]
+type = "decision"
+content = "Use Axum for all new HTTP services"
+importance = 0.9
+
+[[memories]]
+type = "fact"
+content = "Postgres migrations live in db/migrations"
+"#;
+
+    #[test]
+    fn test_load_pack_directory() {
+        let temp_dir = TempDir::new().unwrap();
+        let packs_dir = temp_dir
+            .path()
+            .join(".kaioken")
+            .join("memory")
+            .join("packs");
+        std::fs::create_dir_all(&packs_dir).unwrap();
+        std::fs::write(packs_dir.join("acme.toml"), PACK).unwrap();
+        std::fs::write(packs_dir.join("broken.toml"), "name = ").unwrap();
+
+        let packs = MemoryPacks::load(temp_dir.path(), &MemoryConfig::default());
+        assert_eq!(packs.packs().len(), 1);
+        assert_eq!(packs.packs()[0].label(), "acme-backend@1.2.0");
+    }
+
+    #[test]
+    fn test_load_configured_pack_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let shared_dir = temp_dir.path().join("shared-packs");
+        std::fs::create_dir_all(&shared_dir).unwrap();
+        std::fs::write(shared_dir.join("acme.toml"), PACK).unwrap();
+        std::fs::write(
+            temp_dir.path().join("infra.toml"),
+            PACK.replace("acme-backend", "acme-infra"),
+        )
+        .unwrap();
+
+        let config = MemoryConfig {
+            pack_paths: vec![PathBuf::from("shared-packs"), PathBuf::from("infra.toml")],
+            ..MemoryConfig::default()
+        };
+        let packs = MemoryPacks::load(temp_dir.path(), &config);
+        let labels: Vec<String> = packs.packs().iter().map(MemoryPack::label).collect();
+        assert_eq!(labels, vec!["acme-backend@1.2.0", "acme-infra@1.2.0"]);
+    }
+
+    #[test]
+    fn test_matching_keeps_provenance() {
+        let pack: MemoryPack = toml::from_str(PACK).unwrap();
+        let packs = MemoryPacks::from_packs(vec![pack]);
+
+        let results = packs.matching(&["migrations".to_string()]);
+        assert_eq!(results.len(), 2);
+        let fact = results
+            .iter()
+            .find(|sm| sm.memory.memory_type == MemoryType::Fact)
+            .unwrap();
+        assert_eq!(fact.source_pack.as_deref(), Some("acme-backend@1.2.0"));
+        assert!(fact.semantic_score > 0.0);
+
+        // Important decisions are always included, unrelated facts are not.
+        let results = packs.matching(&["redis".to_string()]);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].memory.memory_type, MemoryType::Decision);
+    }
+}
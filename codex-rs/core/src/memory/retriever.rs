@@ -8,14 +8,16 @@ use std::sync::Arc;
 
 use tracing::debug;
 
+use super::packs::MemoryPacks;
 use super::store::MemoryStore;
-use super::types::{Memory, MemoryConfig, MemoryType, ScoredMemory};
+use super::types::{Memory, MemoryConfig, MemoryType, ScoreBreakdown, ScoredMemory};
 
 // PathBuf is still used in RetrievalContext
 
 /// Retrieves relevant memories using semantic search.
 pub struct MemoryRetriever {
     store: Arc<MemoryStore>,
+    packs: Arc<MemoryPacks>,
     config: MemoryConfig,
 }
 
@@ -30,12 +32,24 @@ pub struct RetrievalContext {
     pub recent_commands: Vec<String>,
     /// Filter to specific memory types.
     pub type_filter: Option<Vec<MemoryType>>,
+    /// Git branch currently checked out, if known.
+    pub branch: Option<String>,
 }
 
 impl MemoryRetriever {
     /// Create a new memory retriever.
     pub fn new(store: Arc<MemoryStore>, config: MemoryConfig) -> Self {
-        Self { store, config }
+        Self {
+            store,
+            packs: Arc::new(MemoryPacks::default()),
+            config,
+        }
+    }
+
+    /// Also retrieve from the given read-only memory packs.
+    pub fn with_packs(mut self, packs: Arc<MemoryPacks>) -> Self {
+        self.packs = packs;
+        self
     }
 
     /// Retrieve memories relevant to the given context.
@@ -60,6 +74,8 @@ impl MemoryRetriever {
                             memory,
                             semantic_score: 0.0,
                             combined_score: 0.0,
+                            breakdown: ScoreBreakdown::default(),
+                            source_pack: None,
                         });
                     }
                 }
@@ -74,13 +90,22 @@ impl MemoryRetriever {
                     memory,
                     semantic_score: 0.0,
                     combined_score: 0.0,
+                    breakdown: ScoreBreakdown::default(),
+                    source_pack: None,
                 });
             }
         }
 
+        // Add matching memories from read-only team packs
+        if !self.packs.is_empty() {
+            let keywords = self.extract_keywords(&context.query);
+            scored.extend(self.packs.matching(&keywords));
+        }
+
         // Score all memories
         for sm in &mut scored {
-            sm.combined_score = self.compute_combined_score(sm, context);
+            sm.breakdown = self.score_breakdown(sm, context);
+            sm.combined_score = combined_score(&sm.breakdown);
         }
 
         // Sort by combined score
@@ -98,8 +123,8 @@ impl MemoryRetriever {
         // Take top results with diversity
         let result = self.diverse_top_k(scored, limit);
 
-        // Mark retrieved memories as used
-        for sm in &result {
+        // Mark retrieved memories as used (packs are read-only)
+        for sm in result.iter().filter(|sm| sm.source_pack.is_none()) {
             let _ = self.store.mark_used(&sm.memory.id).await;
         }
 
@@ -116,6 +141,8 @@ impl MemoryRetriever {
                     memory,
                     semantic_score: similarity as f64,
                     combined_score: 0.0,
+                    breakdown: ScoreBreakdown::default(),
+                    source_pack: None,
                 })
                 .collect(),
             Err(e) => {
@@ -149,8 +176,8 @@ impl MemoryRetriever {
         result
     }
 
-    /// Compute combined score for a memory.
-    fn compute_combined_score(&self, sm: &ScoredMemory, context: &RetrievalContext) -> f64 {
+    /// Compute the per-factor contributions to a memory's score.
+    fn score_breakdown(&self, sm: &ScoredMemory, context: &RetrievalContext) -> ScoreBreakdown {
         let memory = &sm.memory;
 
         // Base score from semantic search
@@ -169,7 +196,7 @@ impl MemoryRetriever {
 
         // Type boost for lessons and decisions
         let type_boost = match memory.memory_type {
-            MemoryType::Lesson => 1.5,
+            MemoryType::Lesson | MemoryType::Mistake => 1.5,
             MemoryType::Decision => 1.3,
             MemoryType::Preference => 1.2,
             _ => 1.0,
@@ -186,14 +213,22 @@ impl MemoryRetriever {
             1.0
         };
 
-        // Combine scores with weights
-        let combined = semantic * 0.35
-            + importance * 0.25
-            + recency * 0.15
-            + (frequency - 1.0) * 0.1
-            + 0.15; // base score
+        // Branch boost for memories learned on the checked-out branch
+        let branch_boost = match (&memory.branch, &context.branch) {
+            (Some(learned_on), Some(current)) if learned_on == current => 1.15,
+            _ => 1.0,
+        };
 
-        combined * type_boost * file_boost
+        // Weight the additive factors
+        ScoreBreakdown {
+            semantic: semantic * 0.35,
+            importance: importance * 0.25,
+            recency: recency * 0.15,
+            frequency: (frequency - 1.0) * 0.1,
+            type_boost,
+            file_overlap: file_boost,
+            branch_match: branch_boost,
+        }
     }
 
     /// Select top-K results with diversity across types.
@@ -255,6 +290,14 @@ impl MemoryRetriever {
 
 }
 
+/// Combine a score breakdown into a single ranking score.
+fn combined_score(breakdown: &ScoreBreakdown) -> f64 {
+    let base = 0.15;
+    let additive =
+        breakdown.semantic + breakdown.importance + breakdown.recency + breakdown.frequency + base;
+    additive * breakdown.type_boost * breakdown.file_overlap * breakdown.branch_match
+}
+
 /// Truncate a string for logging.
 fn truncate(s: &str, max_len: usize) -> &str {
     if s.len() <= max_len {
@@ -268,6 +311,7 @@ fn truncate(s: &str, max_len: usize) -> &str {
 mod tests {
     use super::*;
     use crate::memory::types::MemoryConfig;
+    use pretty_assertions::assert_eq;
     use tempfile::TempDir;
 
     async fn create_test_retriever() -> (MemoryRetriever, TempDir) {
@@ -302,6 +346,8 @@ mod tests {
                 ),
                 semantic_score: 1.0 - (i as f64 * 0.1),
                 combined_score: 1.0 - (i as f64 * 0.1),
+                breakdown: ScoreBreakdown::default(),
+                source_pack: None,
             })
             .collect();
 
@@ -316,4 +362,42 @@ mod tests {
         assert!(facts > 0);
         assert!(patterns > 0);
     }
+
+    #[tokio::test]
+    async fn test_score_breakdown_explains_matches() {
+        let (retriever, _dir) = create_test_retriever().await;
+
+        let mut memory = Memory::new(MemoryType::Location, "auth code in src/auth/".to_string())
+            .with_source_file(PathBuf::from("src/auth/login.rs"));
+        memory.branch = Some("feature/login".to_string());
+        let sm = ScoredMemory {
+            memory,
+            semantic_score: 0.0,
+            combined_score: 0.0,
+            breakdown: ScoreBreakdown::default(),
+            source_pack: None,
+        };
+        let mut context = RetrievalContext {
+            active_files: vec![PathBuf::from("src/auth/session.rs")],
+            branch: Some("feature/login".to_string()),
+            ..Default::default()
+        };
+
+        let breakdown = retriever.score_breakdown(&sm, &context);
+        assert_eq!(breakdown.file_overlap, 1.3);
+        assert_eq!(breakdown.branch_match, 1.15);
+        assert_eq!(
+            breakdown.reasons(),
+            vec![
+                "matches files you're editing",
+                "learned on the current branch",
+                "recently used",
+            ]
+        );
+
+        context.branch = Some("main".to_string());
+        let other_branch = retriever.score_breakdown(&sm, &context);
+        assert_eq!(other_branch.branch_match, 1.0);
+        assert!(combined_score(&breakdown) > combined_score(&other_branch));
+    }
 }
@@ -11,9 +11,15 @@ use rusqlite::{params, Connection, OptionalExtension};
 use tokio::sync::Mutex;
 use tracing::{debug, info, warn};
 
+use super::conflict::contradicts;
 use super::embedding::{bytes_to_embedding, embedding_to_bytes, EmbeddingService, EmbeddingVector};
 use super::types::{Memory, MemoryConfig, MemoryType};
 
+/// Directory holding a project's memory database and docs.
+pub fn memory_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".kaioken").join("memory")
+}
+
 /// SQLite-based memory store with embedding support.
 pub struct MemoryStore {
     /// Database connection (wrapped in mutex for async safety).
@@ -22,6 +28,8 @@ pub struct MemoryStore {
     db_path: PathBuf,
     /// Path to the memory docs directory.
     docs_path: PathBuf,
+    /// Project root, used to record the current git branch on new memories.
+    project_root: PathBuf,
     /// Configuration.
     config: MemoryConfig,
     /// Embedding service for semantic search.
@@ -32,7 +40,7 @@ impl MemoryStore {
     /// Initialize the memory store at the given project path.
     /// Creates `.kaioken/memory/` directory structure if needed.
     pub async fn init(project_root: &Path, config: MemoryConfig) -> anyhow::Result<Self> {
-        let memory_dir = project_root.join(".kaioken").join("memory");
+        let memory_dir = memory_dir(project_root);
         let docs_dir = memory_dir.join("docs");
         let db_path = memory_dir.join("memories.db");
 
@@ -63,6 +71,7 @@ impl MemoryStore {
             conn: Arc::new(Mutex::new(conn)),
             db_path,
             docs_path: docs_dir,
+            project_root: project_root.to_path_buf(),
             config,
             embedding_service,
         })
@@ -83,7 +92,9 @@ impl MemoryStore {
                 created_at INTEGER NOT NULL,
                 last_used INTEGER NOT NULL,
                 embedding_id TEXT,
-                embedding BLOB
+                embedding BLOB,
+                superseded_by TEXT,
+                branch TEXT
             );
 
             CREATE INDEX IF NOT EXISTS idx_memories_type ON memories(type);
@@ -109,10 +120,14 @@ impl MemoryStore {
 
         // Migrate: add embedding column if missing (for existing databases)
         let _ = conn.execute("ALTER TABLE memories ADD COLUMN embedding BLOB", []);
+        // Migrate: add supersede chain column if missing
+        let _ = conn.execute("ALTER TABLE memories ADD COLUMN superseded_by TEXT", []);
+        // Migrate: add branch column if missing
+        let _ = conn.execute("ALTER TABLE memories ADD COLUMN branch TEXT", []);
 
         // Store schema version
         conn.execute(
-            "INSERT OR REPLACE INTO metadata (key, value) VALUES ('schema_version', '2')",
+            "INSERT OR REPLACE INTO metadata (key, value) VALUES ('schema_version', '4')",
             [],
         )?;
 
@@ -134,14 +149,21 @@ impl MemoryStore {
             None
         };
 
+        // Record the branch the memory was learned on so retrieval can favor it
+        let branch = match &memory.branch {
+            Some(branch) => Some(branch.clone()),
+            None => crate::git_info::current_branch_name(&self.project_root).await,
+        };
+
         let conn = self.conn.lock().await;
 
         conn.execute(
             r#"
             INSERT INTO memories (
                 id, type, content, context, source_file,
-                importance, use_count, created_at, last_used, embedding_id, embedding
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+                importance, use_count, created_at, last_used, embedding_id, embedding,
+                superseded_by, branch
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
             "#,
             params![
                 memory.id,
@@ -155,6 +177,8 @@ impl MemoryStore {
                 memory.last_used,
                 memory.embedding_id,
                 embedding_bytes,
+                memory.superseded_by,
+                branch,
             ],
         )?;
 
@@ -166,6 +190,72 @@ impl MemoryStore {
         Ok(())
     }
 
+    /// Insert a memory and supersede any existing memories it contradicts.
+    /// Returns the memories that were superseded.
+    pub async fn insert_superseding(&self, memory: &Memory) -> anyhow::Result<Vec<Memory>> {
+        let conflicts = self.find_conflicts(memory).await?;
+        self.insert(memory).await?;
+        for old in &conflicts {
+            self.supersede(&old.id, &memory.id).await?;
+        }
+        Ok(conflicts)
+    }
+
+    /// Find active memories of the same type that `memory` contradicts, such
+    /// as "uses npm" when the new memory says "switched to pnpm".
+    pub async fn find_conflicts(&self, memory: &Memory) -> anyhow::Result<Vec<Memory>> {
+        let candidates: Vec<(Memory, Option<EmbeddingVector>)> = {
+            let conn = self.conn.lock().await;
+            let mut stmt = conn.prepare(
+                "SELECT * FROM memories WHERE type = ?1 AND id != ?2 AND superseded_by IS NULL",
+            )?;
+
+            stmt.query_map(params![memory.memory_type.as_str(), memory.id], |row| {
+                let old = Self::row_to_memory(row)?;
+                let embedding_bytes: Option<Vec<u8>> = row.get("embedding")?;
+                Ok((old, embedding_bytes))
+            })?
+            .filter_map(|r| r.ok())
+            .map(|(old, bytes)| (old, bytes.and_then(|b| bytes_to_embedding(&b))))
+            .collect()
+        };
+
+        let new_embedding = self
+            .embedding_service
+            .as_ref()
+            .and_then(|svc| svc.embed(&memory.content).ok());
+
+        Ok(candidates
+            .into_iter()
+            .filter(|(old, old_embedding)| {
+                let similarity = match (&new_embedding, old_embedding) {
+                    (Some(new), Some(existing)) => {
+                        Some(EmbeddingService::cosine_similarity(new, existing))
+                    }
+                    _ => None,
+                };
+                contradicts(&memory.content, &old.content, similarity)
+            })
+            .map(|(old, _)| old)
+            .collect())
+    }
+
+    /// Mark `old_id` as superseded by `new_id`. The old memory is kept for
+    /// audit and linked with a `supersedes` relationship.
+    pub async fn supersede(&self, old_id: &str, new_id: &str) -> anyhow::Result<()> {
+        {
+            let conn = self.conn.lock().await;
+            conn.execute(
+                "UPDATE memories SET superseded_by = ?1 WHERE id = ?2",
+                params![new_id, old_id],
+            )?;
+        }
+        self.add_relationship(new_id, old_id, "supersedes").await?;
+
+        info!("Memory {} superseded by {}", old_id, new_id);
+        Ok(())
+    }
+
     /// Write memory content to a markdown file for sgrep indexing.
     async fn write_memory_doc(&self, memory: &Memory) -> anyhow::Result<()> {
         let doc_path = self.docs_path.join(format!("{}.md", memory.id));
@@ -200,7 +290,7 @@ impl MemoryStore {
         let conn = self.conn.lock().await;
 
         let mut stmt = conn.prepare(
-            "SELECT * FROM memories WHERE type = ?1 ORDER BY importance DESC, last_used DESC",
+            "SELECT * FROM memories WHERE type = ?1 AND superseded_by IS NULL ORDER BY importance DESC, last_used DESC",
         )?;
 
         let memories = stmt
@@ -219,7 +309,7 @@ impl MemoryStore {
 
         // Get all memories and sort by effective importance in Rust
         // (SQLite can't compute the complex effective_importance formula)
-        let mut stmt = conn.prepare("SELECT * FROM memories")?;
+        let mut stmt = conn.prepare("SELECT * FROM memories WHERE superseded_by IS NULL")?;
 
         let mut memories: Vec<Memory> = stmt
             .query_map([], |row| Self::row_to_memory(row))?
@@ -253,7 +343,7 @@ impl MemoryStore {
             .map(|_| "content LIKE ?".to_string())
             .collect();
         let query = format!(
-            "SELECT * FROM memories WHERE {} ORDER BY importance DESC",
+            "SELECT * FROM memories WHERE superseded_by IS NULL AND ({}) ORDER BY importance DESC",
             conditions.join(" OR ")
         );
 
@@ -292,7 +382,9 @@ impl MemoryStore {
         // Get all memories with embeddings (collect while holding lock, then release)
         let memories_with_embeddings: Vec<(Memory, EmbeddingVector)> = {
             let conn = self.conn.lock().await;
-            let mut stmt = conn.prepare("SELECT * FROM memories WHERE embedding IS NOT NULL")?;
+            let mut stmt = conn.prepare(
+                "SELECT * FROM memories WHERE embedding IS NOT NULL AND superseded_by IS NULL",
+            )?;
 
             stmt.query_map([], |row| {
                 let memory = Self::row_to_memory(row)?;
@@ -343,7 +435,7 @@ impl MemoryStore {
         let embeddings: Vec<EmbeddingVector> = {
             let conn = self.conn.lock().await;
             let mut stmt = conn.prepare(
-                "SELECT embedding FROM memories WHERE type = ?1 AND embedding IS NOT NULL",
+                "SELECT embedding FROM memories WHERE type = ?1 AND embedding IS NOT NULL AND superseded_by IS NULL",
             )?;
 
             stmt.query_map(params![memory_type.as_str()], |row| {
@@ -465,6 +557,98 @@ impl MemoryStore {
         Ok(deleted as u32)
     }
 
+    /// Delete decaying memories that have not been used since `cutoff`
+    /// (a Unix timestamp). Lessons and decisions never expire.
+    pub async fn prune_unused_since(&self, cutoff: i64) -> anyhow::Result<u32> {
+        let conn = self.conn.lock().await;
+
+        let deleted = conn.execute(
+            r#"
+            DELETE FROM memories
+            WHERE last_used < ?1
+            AND type NOT IN ('lesson', 'decision')
+            "#,
+            params![cutoff],
+        )?;
+        drop(conn);
+
+        if deleted > 0 {
+            info!("Expired {deleted} unused memories");
+            self.cleanup_orphaned_docs().await?;
+        }
+
+        Ok(deleted as u32)
+    }
+
+    /// Evict the `count` lowest-ranked memories: lowest effective importance
+    /// first, least recently used among equals.
+    pub async fn evict_lowest(&self, count: i64) -> anyhow::Result<u32> {
+        if count <= 0 {
+            return Ok(0);
+        }
+
+        let conn = self.conn.lock().await;
+
+        let mut memories: Vec<Memory> = {
+            let mut stmt = conn.prepare("SELECT * FROM memories")?;
+            let memories = stmt
+                .query_map([], |row| Self::row_to_memory(row))?
+                .filter_map(|r| r.ok())
+                .collect();
+            memories
+        };
+        memories.sort_by(|a, b| {
+            a.effective_importance()
+                .partial_cmp(&b.effective_importance())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(a.last_used.cmp(&b.last_used))
+        });
+
+        let mut evicted = 0;
+        for memory in memories.iter().take(count as usize) {
+            evicted += conn.execute("DELETE FROM memories WHERE id = ?1", params![memory.id])?;
+        }
+        drop(conn);
+
+        if evicted > 0 {
+            info!("Evicted {evicted} memories to stay within limits");
+            self.cleanup_orphaned_docs().await?;
+        }
+
+        Ok(evicted as u32)
+    }
+
+    /// Number of memories in the store.
+    pub async fn count(&self) -> anyhow::Result<i64> {
+        let conn = self.conn.lock().await;
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM memories", [], |row| row.get(0))?;
+        Ok(count)
+    }
+
+    /// Current size of the database in bytes.
+    pub async fn db_size_bytes(&self) -> anyhow::Result<i64> {
+        let conn = self.conn.lock().await;
+        let page_count: i64 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        let page_size: i64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+        Ok(page_count * page_size)
+    }
+
+    /// Bytes held by free pages that [`Self::compact`] would give back.
+    pub async fn reclaimable_bytes(&self) -> anyhow::Result<i64> {
+        let conn = self.conn.lock().await;
+        let free_pages: i64 = conn.query_row("PRAGMA freelist_count", [], |row| row.get(0))?;
+        let page_size: i64 = conn.query_row("PRAGMA page_size", [], |row| row.get(0))?;
+        Ok(free_pages * page_size)
+    }
+
+    /// Rebuild the database file to reclaim space freed by deletions.
+    pub async fn compact(&self) -> anyhow::Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute_batch("VACUUM")?;
+        debug!("Compacted memory database");
+        Ok(())
+    }
+
     /// Clean up doc files for deleted memories.
     async fn cleanup_orphaned_docs(&self) -> anyhow::Result<()> {
         // Get all valid memory IDs (complete DB work before async operations)
@@ -499,7 +683,7 @@ impl MemoryStore {
 
         // Simple similarity check: exact content match with same type
         let exists: bool = conn.query_row(
-            "SELECT EXISTS(SELECT 1 FROM memories WHERE content = ?1 AND type = ?2)",
+            "SELECT EXISTS(SELECT 1 FROM memories WHERE content = ?1 AND type = ?2 AND superseded_by IS NULL)",
             params![content, memory_type.as_str()],
             |row| row.get(0),
         )?;
@@ -597,6 +781,91 @@ impl MemoryStore {
         Ok(results)
     }
 
+    /// Active memories that have an embedding, most important first.
+    pub async fn active_with_embeddings(&self) -> anyhow::Result<Vec<(Memory, EmbeddingVector)>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT * FROM memories WHERE embedding IS NOT NULL AND superseded_by IS NULL ORDER BY importance DESC, last_used DESC",
+        )?;
+
+        let memories = stmt
+            .query_map([], |row| {
+                let memory = Self::row_to_memory(row)?;
+                let embedding_bytes: Option<Vec<u8>> = row.get("embedding")?;
+                Ok((memory, embedding_bytes))
+            })?
+            .filter_map(|r| r.ok())
+            .filter_map(|(m, bytes)| {
+                bytes
+                    .and_then(|b| bytes_to_embedding(&b))
+                    .map(|emb| (m, emb))
+            })
+            .collect();
+
+        Ok(memories)
+    }
+
+    /// Retire `merged_ids` in favor of `canonical_id`. Like superseded
+    /// memories they are kept for audit, linked with `merged_from`.
+    pub async fn merge_into(
+        &self,
+        canonical_id: &str,
+        merged_ids: &[String],
+    ) -> anyhow::Result<()> {
+        for merged_id in merged_ids {
+            {
+                let conn = self.conn.lock().await;
+                conn.execute(
+                    "UPDATE memories SET superseded_by = ?1 WHERE id = ?2",
+                    params![canonical_id, merged_id],
+                )?;
+            }
+            self.add_relationship(canonical_id, merged_id, "merged_from")
+                .await?;
+        }
+
+        info!("Merged {} memories into {}", merged_ids.len(), canonical_id);
+        Ok(())
+    }
+
+    /// IDs of the memories that were merged into `id`.
+    pub async fn merged_from(&self, id: &str) -> anyhow::Result<Vec<String>> {
+        let conn = self.conn.lock().await;
+        let mut stmt = conn.prepare(
+            "SELECT to_id FROM relationships WHERE from_id = ?1 AND relation = 'merged_from' ORDER BY to_id",
+        )?;
+
+        let ids = stmt
+            .query_map(params![id], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        Ok(ids)
+    }
+
+    /// Read a value from the metadata table.
+    pub async fn get_metadata(&self, key: &str) -> anyhow::Result<Option<String>> {
+        let conn = self.conn.lock().await;
+        let value = conn
+            .query_row(
+                "SELECT value FROM metadata WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value)
+    }
+
+    /// Write a value to the metadata table.
+    pub async fn set_metadata(&self, key: &str, value: &str) -> anyhow::Result<()> {
+        let conn = self.conn.lock().await;
+        conn.execute(
+            "INSERT OR REPLACE INTO metadata (key, value) VALUES (?1, ?2)",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
     /// Convert a database row to a Memory struct.
     fn row_to_memory(row: &rusqlite::Row) -> rusqlite::Result<Memory> {
         let type_str: String = row.get("type")?;
@@ -613,6 +882,8 @@ impl MemoryStore {
             created_at: row.get("created_at")?,
             last_used: row.get("last_used")?,
             embedding_id: row.get("embedding_id")?,
+            superseded_by: row.get("superseded_by")?,
+            branch: row.get("branch")?,
         })
     }
 
@@ -719,4 +990,46 @@ mod tests {
             .await
             .unwrap());
     }
+
+    #[tokio::test]
+    async fn test_contradicting_memory_supersedes_old_one() {
+        let (store, _dir) = create_test_store().await;
+
+        let old = Memory::new(
+            MemoryType::Fact,
+            "The project uses npm for package management".to_string(),
+        );
+        store.insert(&old).await.unwrap();
+        let unrelated = Memory::new(MemoryType::Fact, "Tests live in tests/".to_string());
+        store.insert(&unrelated).await.unwrap();
+
+        let new = Memory::new(
+            MemoryType::Fact,
+            "Actually the project switched from npm to pnpm for package management".to_string(),
+        );
+        let superseded = store.insert_superseding(&new).await.unwrap();
+        assert_eq!(
+            superseded.into_iter().map(|m| m.id).collect::<Vec<_>>(),
+            vec![old.id.clone()]
+        );
+
+        // Kept for audit, but excluded from retrieval.
+        let audited = store.get(&old.id).await.unwrap().unwrap();
+        assert_eq!(audited.superseded_by, Some(new.id.clone()));
+        let active: Vec<String> = store
+            .get_by_type(MemoryType::Fact)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+        assert!(!active.contains(&old.id));
+        assert!(active.contains(&new.id));
+        assert!(store
+            .search_by_keywords(&["npm"])
+            .await
+            .unwrap()
+            .iter()
+            .all(|m| m.id != old.id));
+    }
 }
@@ -23,14 +23,17 @@ pub enum MemoryType {
     Preference,
     /// Where things are in the codebase (e.g., "auth code in src/auth/")
     Location,
+    /// A failing command linked to the change that fixed it
+    /// (e.g., "`cargo test` failed with \"error[E#]: ...\"; fixed by editing src/lib.rs")
+    Mistake,
 }
 
 impl MemoryType {
     /// Returns whether this memory type should decay over time.
-    /// Lessons and Decisions never decay - they're too important.
+    /// Lessons, Decisions, and Mistakes never decay - they're too important.
     pub fn decays(&self) -> bool {
         match self {
-            MemoryType::Lesson | MemoryType::Decision => false,
+            MemoryType::Lesson | MemoryType::Decision | MemoryType::Mistake => false,
             _ => true,
         }
     }
@@ -39,6 +42,7 @@ impl MemoryType {
     pub fn default_importance(&self) -> f64 {
         match self {
             MemoryType::Lesson => 0.9,
+            MemoryType::Mistake => 0.9,
             MemoryType::Decision => 0.85,
             MemoryType::Preference => 0.8,
             MemoryType::Pattern => 0.7,
@@ -56,6 +60,7 @@ impl MemoryType {
             MemoryType::Lesson => "lesson",
             MemoryType::Preference => "preference",
             MemoryType::Location => "location",
+            MemoryType::Mistake => "mistake",
         }
     }
 
@@ -68,6 +73,7 @@ impl MemoryType {
             "lesson" => Some(MemoryType::Lesson),
             "preference" => Some(MemoryType::Preference),
             "location" => Some(MemoryType::Location),
+            "mistake" => Some(MemoryType::Mistake),
             _ => None,
         }
     }
@@ -96,6 +102,13 @@ pub struct Memory {
     pub last_used: i64,
     /// Reference to the sgrep embedding, if indexed.
     pub embedding_id: Option<String>,
+    /// ID of the newer memory that contradicted this one. Superseded memories
+    /// are kept for audit but never retrieved.
+    #[serde(default)]
+    pub superseded_by: Option<String>,
+    /// Git branch checked out when this memory was learned, if known.
+    #[serde(default)]
+    pub branch: Option<String>,
 }
 
 impl Memory {
@@ -113,6 +126,8 @@ impl Memory {
             created_at: now,
             last_used: now,
             embedding_id: None,
+            superseded_by: None,
+            branch: None,
         }
     }
 
@@ -216,10 +231,78 @@ pub struct ScoredMemory {
     pub semantic_score: f64,
     /// Combined score considering all factors.
     pub combined_score: f64,
+    /// How each factor contributed to `combined_score`.
+    pub breakdown: ScoreBreakdown,
+    /// Label of the read-only memory pack this came from (`name@version`),
+    /// or `None` for memories from the local store.
+    pub source_pack: Option<String>,
 }
 
-/// Configuration for the memory system.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Per-factor contributions to a memory's combined retrieval score.
+///
+/// The additive factors are already weighted; the combined score is their
+/// sum plus a base score, multiplied by the boosts.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct ScoreBreakdown {
+    /// Weighted semantic similarity to the query.
+    pub semantic: f64,
+    /// Weighted effective importance.
+    pub importance: f64,
+    /// Weighted recency of last use.
+    pub recency: f64,
+    /// Weighted bonus for frequent use.
+    pub frequency: f64,
+    /// Multiplier for the memory type (lessons, mistakes, and decisions rank higher).
+    pub type_boost: f64,
+    /// Multiplier applied when the memory's source file overlaps the active files.
+    pub file_overlap: f64,
+    /// Multiplier applied when the memory was learned on the current branch.
+    pub branch_match: f64,
+}
+
+impl Default for ScoreBreakdown {
+    fn default() -> Self {
+        Self {
+            semantic: 0.0,
+            importance: 0.0,
+            recency: 0.0,
+            frequency: 0.0,
+            type_boost: 1.0,
+            file_overlap: 1.0,
+            branch_match: 1.0,
+        }
+    }
+}
+
+impl ScoreBreakdown {
+    /// Short human-readable reasons the memory was chosen, strongest first.
+    pub fn reasons(&self) -> Vec<&'static str> {
+        let mut reasons = Vec::new();
+        if self.file_overlap > 1.0 {
+            reasons.push("matches files you're editing");
+        }
+        if self.semantic >= 0.2 {
+            reasons.push("similar to your request");
+        }
+        if self.branch_match > 1.0 {
+            reasons.push("learned on the current branch");
+        }
+        if self.importance >= 0.2 {
+            reasons.push("high importance");
+        }
+        if self.recency >= 0.12 {
+            reasons.push("recently used");
+        }
+        if reasons.is_empty() {
+            reasons.push("general project knowledge");
+        }
+        reasons
+    }
+}
+
+/// Configuration for the memory system, from `[memory]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MemoryConfig {
     /// Whether the memory system is enabled.
     pub enabled: bool,
@@ -233,6 +316,22 @@ pub struct MemoryConfig {
     pub min_importance_threshold: f64,
     /// Maximum memories to retrieve per turn.
     pub max_retrieval_count: usize,
+    /// Maximum number of memories kept in the store; the lowest-scoring,
+    /// least-recently-used entries are evicted beyond this. 0 disables the limit.
+    pub max_memories: i64,
+    /// Maximum size of the SQLite database in megabytes. 0 disables the limit.
+    pub max_db_size_mb: i64,
+    /// Days a decaying memory may go unused before it expires; unset keeps
+    /// memories until the other limits evict them.
+    pub ttl_days: Option<i64>,
+    /// Extra memory pack files or directories, relative to the project root.
+    pub pack_paths: Vec<PathBuf>,
+    /// Embedding similarity above which memories of the same type are merged
+    /// during consolidation.
+    pub consolidation_threshold: f32,
+    /// Hours between automatic consolidations at session start. 0 disables
+    /// the automatic run; `Op::ConsolidateMemories` still works.
+    pub consolidation_interval_hours: i64,
 }
 
 impl Default for MemoryConfig {
@@ -244,6 +343,12 @@ impl Default for MemoryConfig {
             decay_rate: 0.95,
             min_importance_threshold: 0.1,
             max_retrieval_count: 15,
+            max_memories: 2000,
+            max_db_size_mb: 50,
+            ttl_days: None,
+            pack_paths: Vec::new(),
+            consolidation_threshold: 0.85,
+            consolidation_interval_hours: 24,
         }
     }
 }
@@ -318,6 +318,21 @@ pub fn find_family_for_model(slug: &str) -> Option<ModelFamily> {
     }
 }
 
+/// `family` as served by the `local` provider. OpenAI-compatible servers such
+/// as llama.cpp and vLLM take no reasoning or verbosity settings and run tool
+/// calls one at a time.
+pub fn local_model_family(family: ModelFamily) -> ModelFamily {
+    ModelFamily {
+        supports_reasoning_summaries: false,
+        reasoning_summary_format: ReasoningSummaryFormat::None,
+        default_reasoning_effort: None,
+        support_verbosity: false,
+        default_verbosity: None,
+        supports_parallel_tool_calls: false,
+        ..family
+    }
+}
+
 pub fn derive_default_model_family(model: &str) -> ModelFamily {
     ModelFamily {
         slug: model.to_string(),
@@ -216,6 +216,11 @@ pub const DEFAULT_OLLAMA_PORT: u16 = 11434;
 pub const LMSTUDIO_OSS_PROVIDER_ID: &str = "lmstudio";
 pub const OLLAMA_OSS_PROVIDER_ID: &str = "ollama";
 
+/// Any OpenAI-compatible Chat Completions server on this machine or network,
+/// such as llama.cpp's `llama-server` or vLLM.
+pub const LOCAL_PROVIDER_ID: &str = "local";
+pub const DEFAULT_LOCAL_BASE_URL: &str = "http://localhost:8080/v1";
+
 /// Built-in default provider list.
 pub fn built_in_model_providers() -> HashMap<String, ModelProviderInfo> {
     use ModelProviderInfo as P;
@@ -273,6 +278,7 @@ pub fn built_in_model_providers() -> HashMap<String, ModelProviderInfo> {
             LMSTUDIO_OSS_PROVIDER_ID,
             create_oss_provider(DEFAULT_LMSTUDIO_PORT, WireApi::Responses),
         ),
+        (LOCAL_PROVIDER_ID, create_local_provider()),
     ]
     .into_iter()
     .map(|(k, v)| (k.to_string(), v))
@@ -317,6 +323,19 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
     }
 }
 
+/// The `local` provider, served from `CODEX_LOCAL_BASE_URL` when set and
+/// [`DEFAULT_LOCAL_BASE_URL`] otherwise.
+pub fn create_local_provider() -> ModelProviderInfo {
+    let base_url = std::env::var("CODEX_LOCAL_BASE_URL")
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .unwrap_or_else(|| DEFAULT_LOCAL_BASE_URL.to_string());
+    ModelProviderInfo {
+        name: "Local".into(),
+        ..create_oss_provider_with_base_url(&base_url, WireApi::Chat)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
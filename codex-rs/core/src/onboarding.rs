@@ -0,0 +1,438 @@
+//! First-open repository onboarding.
+//!
+//! [`onboard_repository`] runs the bootstrap chain for a repository in order —
+//! detect git info, register the project, check for `AGENTS.md`, build the
+//! semantic index, and initialize project memory — reporting each step through
+//! a progress callback so a front end can render a wizard. Steps that change
+//! user-visible state (trusting the project, generating `AGENTS.md`) only run
+//! when the caller opts in via [`OnboardingOptions`]. The slow steps go
+//! through an [`OnboardingBackend`].
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use codex_protocol::config_types::TrustLevel;
+use codex_protocol::protocol::GitInfo;
+use serde::Serialize;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::config::set_project_trust_level;
+use crate::git_info::collect_git_info;
+use crate::git_info::get_git_repo_root;
+use crate::memory::MemoryConfig;
+use crate::memory::MemoryManager;
+use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
+
+/// Prompt that asks the model to write an `AGENTS.md` contributor guide.
+pub const AGENTS_MD_INIT_PROMPT: &str = include_str!("../templates/onboarding/init_prompt.md");
+
+const SEMANTIC_INDEX_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// Steps of the onboarding chain, in the order they run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    DetectGit,
+    RegisterRepository,
+    AgentsMd,
+    SemanticIndex,
+    Memory,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum OnboardingStepStatus {
+    Started,
+    Completed {
+        detail: Option<String>,
+    },
+    Skipped {
+        reason: String,
+    },
+    /// The step needs the user's confirmation; rerun with the matching
+    /// [`OnboardingOptions`] flag set to perform it.
+    NeedsConfirmation {
+        message: String,
+    },
+    Failed {
+        error: String,
+    },
+}
+
+/// Progress update emitted while onboarding runs.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OnboardingProgress {
+    pub step: OnboardingStep,
+    #[serde(flatten)]
+    pub status: OnboardingStepStatus,
+}
+
+/// Opt-ins for onboarding steps that need the user's confirmation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OnboardingOptions {
+    /// Mark the repository as trusted in `config.toml`.
+    pub trust_repository: bool,
+    /// Return [`AGENTS_MD_INIT_PROMPT`] when `AGENTS.md` is missing so the
+    /// caller can submit it as the first turn.
+    pub generate_agents_md: bool,
+}
+
+/// Performs the slow onboarding steps; injected so tests run neither
+/// `sgrep index` nor a real memory store.
+#[async_trait]
+pub trait OnboardingBackend: Send + Sync {
+    /// Builds the semantic index for `repo_root`, or returns `None` when
+    /// semantic search is unavailable.
+    async fn build_semantic_index(&self, repo_root: &Path) -> Option<Result<(), String>>;
+
+    /// Opens project memory for `repo_root` and summarizes what it holds.
+    async fn init_memory(&self, repo_root: &Path) -> Result<String, String>;
+}
+
+/// Indexes with the installed `sgrep` and opens the project's
+/// [`MemoryManager`].
+#[derive(Debug, Clone)]
+pub struct DefaultOnboardingBackend {
+    memory_config: MemoryConfig,
+}
+
+impl DefaultOnboardingBackend {
+    pub fn new(memory_config: MemoryConfig) -> Self {
+        Self { memory_config }
+    }
+}
+
+#[async_trait]
+impl OnboardingBackend for DefaultOnboardingBackend {
+    async fn build_semantic_index(&self, repo_root: &Path) -> Option<Result<(), String>> {
+        let sgrep = find_sgrep_binary()?;
+        Some(run_sgrep_index(&sgrep, repo_root).await)
+    }
+
+    async fn init_memory(&self, repo_root: &Path) -> Result<String, String> {
+        let memory = MemoryManager::init(repo_root, self.memory_config.clone())
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(memory.summary().await.to_string())
+    }
+}
+
+/// Outcome of [`onboard_repository`].
+#[derive(Debug, Clone, Default)]
+pub struct OnboardingReport {
+    pub repo_root: PathBuf,
+    pub git_info: Option<GitInfo>,
+    /// Prompt to submit to generate `AGENTS.md`, if requested and missing.
+    pub agents_md_prompt: Option<String>,
+    /// Steps that failed; onboarding continues past individual failures.
+    pub failed_steps: Vec<OnboardingStep>,
+}
+
+/// Run the onboarding chain for the repository containing `path`.
+pub async fn onboard_repository(
+    codex_home: &Path,
+    path: &Path,
+    backend: &dyn OnboardingBackend,
+    options: OnboardingOptions,
+    mut on_progress: impl FnMut(OnboardingProgress),
+) -> OnboardingReport {
+    let repo_root = get_git_repo_root(path).unwrap_or_else(|| path.to_path_buf());
+    let mut failed_steps = Vec::new();
+    let mut emit = |step: OnboardingStep, status: OnboardingStepStatus| {
+        if matches!(status, OnboardingStepStatus::Failed { .. }) {
+            failed_steps.push(step);
+        }
+        on_progress(OnboardingProgress { step, status });
+    };
+
+    emit(OnboardingStep::DetectGit, OnboardingStepStatus::Started);
+    let git_info = collect_git_info(&repo_root).await;
+    let status = match &git_info {
+        Some(info) => OnboardingStepStatus::Completed {
+            detail: info.branch.clone(),
+        },
+        None => OnboardingStepStatus::Skipped {
+            reason: "not a git repository".to_string(),
+        },
+    };
+    emit(OnboardingStep::DetectGit, status);
+
+    emit(
+        OnboardingStep::RegisterRepository,
+        OnboardingStepStatus::Started,
+    );
+    let status = if options.trust_repository {
+        match set_project_trust_level(codex_home, &repo_root, TrustLevel::Trusted) {
+            Ok(()) => OnboardingStepStatus::Completed { detail: None },
+            Err(err) => OnboardingStepStatus::Failed {
+                error: err.to_string(),
+            },
+        }
+    } else {
+        OnboardingStepStatus::NeedsConfirmation {
+            message: format!("Trust {} to let Codex work here?", repo_root.display()),
+        }
+    };
+    emit(OnboardingStep::RegisterRepository, status);
+
+    emit(OnboardingStep::AgentsMd, OnboardingStepStatus::Started);
+    let agents_md_prompt = if repo_root.join(DEFAULT_PROJECT_DOC_FILENAME).exists() {
+        emit(
+            OnboardingStep::AgentsMd,
+            OnboardingStepStatus::Skipped {
+                reason: format!("{DEFAULT_PROJECT_DOC_FILENAME} already exists"),
+            },
+        );
+        None
+    } else if options.generate_agents_md {
+        emit(
+            OnboardingStep::AgentsMd,
+            OnboardingStepStatus::Completed {
+                detail: Some(format!("{DEFAULT_PROJECT_DOC_FILENAME} will be generated")),
+            },
+        );
+        Some(AGENTS_MD_INIT_PROMPT.to_string())
+    } else {
+        emit(
+            OnboardingStep::AgentsMd,
+            OnboardingStepStatus::NeedsConfirmation {
+                message: format!("Generate {DEFAULT_PROJECT_DOC_FILENAME} for this repository?"),
+            },
+        );
+        None
+    };
+
+    emit(OnboardingStep::SemanticIndex, OnboardingStepStatus::Started);
+    let status = match backend.build_semantic_index(&repo_root).await {
+        Some(Ok(())) => OnboardingStepStatus::Completed { detail: None },
+        Some(Err(error)) => OnboardingStepStatus::Failed { error },
+        None => OnboardingStepStatus::Skipped {
+            reason: "sgrep is not installed".to_string(),
+        },
+    };
+    emit(OnboardingStep::SemanticIndex, status);
+
+    emit(OnboardingStep::Memory, OnboardingStepStatus::Started);
+    let status = match backend.init_memory(&repo_root).await {
+        Ok(summary) => OnboardingStepStatus::Completed {
+            detail: Some(summary),
+        },
+        Err(error) => OnboardingStepStatus::Failed { error },
+    };
+    emit(OnboardingStep::Memory, status);
+
+    OnboardingReport {
+        repo_root,
+        git_info,
+        agents_md_prompt,
+        failed_steps,
+    }
+}
+
+async fn run_sgrep_index(sgrep: &Path, repo_root: &Path) -> Result<(), String> {
+    let mut command = Command::new(sgrep);
+    command
+        .current_dir(repo_root)
+        .arg("index")
+        .arg("--path")
+        .arg(repo_root);
+    let output = timeout(SEMANTIC_INDEX_TIMEOUT, command.output())
+        .await
+        .map_err(|_| "sgrep index timed out".to_string())?
+        .map_err(|err| format!("failed to launch sgrep index: {err}"))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "sgrep index failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
+
+pub(crate) fn find_sgrep_binary() -> Option<PathBuf> {
+    dirs::home_dir()
+        .map(|home| home.join(".codex-kaioken/bin/sgrep"))
+        .filter(|path| path.is_file())
+        .or_else(|| which::which("sgrep").ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// Records the repository roots it was asked to index and open memory for.
+    struct FakeBackend {
+        index: Option<Result<(), String>>,
+        memory: Result<String, String>,
+        calls: Mutex<Vec<(OnboardingStep, PathBuf)>>,
+    }
+
+    impl FakeBackend {
+        fn new(index: Option<Result<(), String>>, memory: Result<String, String>) -> Self {
+            Self {
+                index,
+                memory,
+                calls: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl OnboardingBackend for FakeBackend {
+        async fn build_semantic_index(&self, repo_root: &Path) -> Option<Result<(), String>> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((OnboardingStep::SemanticIndex, repo_root.to_path_buf()));
+            self.index.clone()
+        }
+
+        async fn init_memory(&self, repo_root: &Path) -> Result<String, String> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push((OnboardingStep::Memory, repo_root.to_path_buf()));
+            self.memory.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn asks_for_confirmation_before_changing_state() {
+        let codex_home = TempDir::new().unwrap();
+        let repo = TempDir::new().unwrap();
+        let backend = FakeBackend::new(None, Ok("3 memories".to_string()));
+        let mut progress = Vec::new();
+
+        let report = onboard_repository(
+            codex_home.path(),
+            repo.path(),
+            &backend,
+            OnboardingOptions::default(),
+            |update| progress.push(update),
+        )
+        .await;
+
+        let finished: Vec<OnboardingProgress> = progress
+            .into_iter()
+            .filter(|update| update.status != OnboardingStepStatus::Started)
+            .collect();
+        assert_eq!(
+            finished,
+            vec![
+                OnboardingProgress {
+                    step: OnboardingStep::DetectGit,
+                    status: OnboardingStepStatus::Skipped {
+                        reason: "not a git repository".to_string(),
+                    },
+                },
+                OnboardingProgress {
+                    step: OnboardingStep::RegisterRepository,
+                    status: OnboardingStepStatus::NeedsConfirmation {
+                        message: format!("Trust {} to let Codex work here?", repo.path().display()),
+                    },
+                },
+                OnboardingProgress {
+                    step: OnboardingStep::AgentsMd,
+                    status: OnboardingStepStatus::NeedsConfirmation {
+                        message: "Generate AGENTS.md for this repository?".to_string(),
+                    },
+                },
+                OnboardingProgress {
+                    step: OnboardingStep::SemanticIndex,
+                    status: OnboardingStepStatus::Skipped {
+                        reason: "sgrep is not installed".to_string(),
+                    },
+                },
+                OnboardingProgress {
+                    step: OnboardingStep::Memory,
+                    status: OnboardingStepStatus::Completed {
+                        detail: Some("3 memories".to_string()),
+                    },
+                },
+            ]
+        );
+        assert_eq!(report.agents_md_prompt, None);
+        assert_eq!(report.failed_steps, Vec::new());
+        assert!(!codex_home.path().join("config.toml").exists());
+    }
+
+    #[tokio::test]
+    async fn returns_init_prompt_when_agents_md_is_missing() {
+        let codex_home = TempDir::new().unwrap();
+        let repo = TempDir::new().unwrap();
+        let backend = FakeBackend::new(Some(Ok(())), Ok(String::new()));
+
+        let report = onboard_repository(
+            codex_home.path(),
+            repo.path(),
+            &backend,
+            OnboardingOptions {
+                trust_repository: true,
+                generate_agents_md: true,
+            },
+            |_| {},
+        )
+        .await;
+
+        assert_eq!(
+            report.agents_md_prompt.as_deref(),
+            Some(AGENTS_MD_INIT_PROMPT)
+        );
+        assert!(codex_home.path().join("config.toml").exists());
+    }
+
+    #[tokio::test]
+    async fn continues_past_failed_steps() {
+        let codex_home = TempDir::new().unwrap();
+        let repo = TempDir::new().unwrap();
+        std::fs::write(repo.path().join(DEFAULT_PROJECT_DOC_FILENAME), "# Guide").unwrap();
+        let backend = FakeBackend::new(
+            Some(Err("sgrep index failed: disk full".to_string())),
+            Err("memory store is locked".to_string()),
+        );
+        let mut progress = Vec::new();
+
+        let report = onboard_repository(
+            codex_home.path(),
+            repo.path(),
+            &backend,
+            OnboardingOptions::default(),
+            |update| progress.push(update),
+        )
+        .await;
+
+        assert_eq!(
+            report.failed_steps,
+            vec![OnboardingStep::SemanticIndex, OnboardingStep::Memory]
+        );
+        assert_eq!(
+            *backend.calls.lock().unwrap(),
+            vec![
+                (OnboardingStep::SemanticIndex, repo.path().to_path_buf()),
+                (OnboardingStep::Memory, repo.path().to_path_buf()),
+            ]
+        );
+        assert!(progress.contains(&OnboardingProgress {
+            step: OnboardingStep::AgentsMd,
+            status: OnboardingStepStatus::Skipped {
+                reason: "AGENTS.md already exists".to_string(),
+            },
+        }));
+        assert_eq!(
+            progress.last(),
+            Some(&OnboardingProgress {
+                step: OnboardingStep::Memory,
+                status: OnboardingStepStatus::Failed {
+                    error: "memory store is locked".to_string(),
+                },
+            })
+        );
+    }
+}
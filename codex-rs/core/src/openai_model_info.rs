@@ -1,4 +1,5 @@
 use crate::model_family::ModelFamily;
+use crate::model_provider_info::LOCAL_PROVIDER_ID;
 
 // Shared constants for commonly used window/token sizes.
 pub(crate) const CONTEXT_WINDOW_272K: i64 = 272_000;
@@ -19,7 +20,7 @@ pub(crate) struct ModelInfo {
 }
 
 impl ModelInfo {
-    const fn new(context_window: i64) -> Self {
+    pub(crate) const fn new(context_window: i64) -> Self {
         Self {
             context_window,
             auto_compact_token_limit: Some(Self::default_auto_compact_limit(context_window)),
@@ -31,6 +32,19 @@ impl ModelInfo {
     }
 }
 
+/// [`get_model_info`] for a model served by `model_provider_id`. Nothing is
+/// assumed about what the `local` provider serves, whatever its name.
+pub(crate) fn get_provider_model_info(
+    model_provider_id: &str,
+    model_family: &ModelFamily,
+) -> Option<ModelInfo> {
+    if model_provider_id == LOCAL_PROVIDER_ID {
+        None
+    } else {
+        get_model_info(model_family)
+    }
+}
+
 pub(crate) fn get_model_info(model_family: &ModelFamily) -> Option<ModelInfo> {
     let slug = model_family.slug.as_str();
     match slug {
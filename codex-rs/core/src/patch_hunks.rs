@@ -0,0 +1,211 @@
+//! Splitting proposed patches into hunks for partial approval.
+//!
+//! A patch approval request lists each file's change, with updates given as
+//! unified diffs. Frontends split those diffs into `@@` hunks with
+//! [`split_unified_diff`], let the user drop files or hunks, and answer with
+//! `ReviewDecision::ApprovedPartially` carrying the remaining changes (built
+//! with [`retain_hunks`]). Core checks that the answer only leaves parts of
+//! the proposal out and applies an `apply_patch` body rebuilt from it.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::FileChange;
+
+/// One `@@` hunk of a unified diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffHunk {
+    /// The `@@ -a,b +c,d @@` line.
+    pub header: String,
+    /// Context, removed, and added lines with their ` `, `-`, or `+` prefix.
+    pub lines: Vec<String>,
+}
+
+/// Split `unified_diff` into its hunks. File headers before the first `@@`
+/// are dropped.
+pub fn split_unified_diff(unified_diff: &str) -> Vec<DiffHunk> {
+    let mut hunks: Vec<DiffHunk> = Vec::new();
+    for line in unified_diff.lines() {
+        if line.starts_with("@@") {
+            hunks.push(DiffHunk {
+                header: line.to_string(),
+                lines: Vec::new(),
+            });
+        } else if let Some(hunk) = hunks.last_mut() {
+            hunk.lines.push(line.to_string());
+        }
+    }
+    hunks
+}
+
+/// `unified_diff` reduced to the hunks at the indices in `keep`.
+pub fn retain_hunks(unified_diff: &str, keep: &[usize]) -> String {
+    let mut out = String::new();
+    for (idx, hunk) in split_unified_diff(unified_diff).into_iter().enumerate() {
+        if !keep.contains(&idx) {
+            continue;
+        }
+        out.push_str(&hunk.header);
+        out.push('\n');
+        for line in hunk.lines {
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Whether `accepted` only leaves out files or hunks of `proposed`.
+pub(crate) fn is_subset(
+    accepted: &HashMap<PathBuf, FileChange>,
+    proposed: &HashMap<PathBuf, FileChange>,
+) -> bool {
+    accepted
+        .iter()
+        .all(|(path, change)| match (change, proposed.get(path)) {
+            (
+                FileChange::Update {
+                    unified_diff,
+                    move_path,
+                },
+                Some(FileChange::Update {
+                    unified_diff: proposed_diff,
+                    move_path: proposed_move_path,
+                }),
+            ) => {
+                let proposed_hunks = split_unified_diff(proposed_diff);
+                let mut remaining = proposed_hunks.iter();
+                move_path == proposed_move_path
+                    && split_unified_diff(unified_diff)
+                        .iter()
+                        .all(|hunk| remaining.any(|candidate| candidate == hunk))
+            }
+            (change, Some(proposed)) => change == proposed,
+            (_, None) => false,
+        })
+}
+
+/// An `apply_patch` body making `changes`. Update hunks are matched by their
+/// context and removed lines, the same way model-written patches are.
+pub(crate) fn patch_from_changes(changes: &HashMap<PathBuf, FileChange>) -> String {
+    let mut entries: Vec<_> = changes.iter().collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut patch = String::from("*** Begin Patch\n");
+    for (path, change) in entries {
+        match change {
+            FileChange::Add { content } => {
+                patch.push_str(&format!("*** Add File: {}\n", path.display()));
+                for line in content.lines() {
+                    patch.push('+');
+                    patch.push_str(line);
+                    patch.push('\n');
+                }
+            }
+            FileChange::Delete { .. } => {
+                patch.push_str(&format!("*** Delete File: {}\n", path.display()));
+            }
+            FileChange::Update {
+                unified_diff,
+                move_path,
+            } => {
+                patch.push_str(&format!("*** Update File: {}\n", path.display()));
+                if let Some(dest) = move_path {
+                    patch.push_str(&format!("*** Move to: {}\n", dest.display()));
+                }
+                for hunk in split_unified_diff(unified_diff) {
+                    patch.push_str("@@\n");
+                    // `\ No newline at end of file` has no apply_patch form.
+                    for line in hunk.lines.iter().filter(|line| !line.starts_with('\\')) {
+                        patch.push_str(line);
+                        patch.push('\n');
+                    }
+                }
+            }
+        }
+    }
+    patch.push_str("*** End Patch");
+    patch
+}
+
+/// Tell the model which proposed changes the user left out.
+pub(crate) fn rejection_note(
+    proposed: &HashMap<PathBuf, FileChange>,
+    accepted: &HashMap<PathBuf, FileChange>,
+) -> String {
+    let mut rejected = Vec::new();
+    for (path, change) in proposed {
+        match (change, accepted.get(path)) {
+            (_, None) => rejected.push(format!("- {}: all changes", path.display())),
+            (
+                FileChange::Update { unified_diff, .. },
+                Some(FileChange::Update {
+                    unified_diff: kept, ..
+                }),
+            ) => {
+                let total = split_unified_diff(unified_diff).len();
+                let dropped = total.saturating_sub(split_unified_diff(kept).len());
+                if dropped > 0 {
+                    rejected.push(format!("- {}: {dropped} of {total} hunks", path.display()));
+                }
+            }
+            _ => {}
+        }
+    }
+    rejected.sort();
+    format!(
+        "The user rejected part of this patch. These changes were not applied:\n{}",
+        rejected.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const DIFF: &str = "@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n@@ -8,3 +8,3 @@\n h\n-i\n+I\n j\n";
+
+    fn update(unified_diff: &str) -> FileChange {
+        FileChange::Update {
+            unified_diff: unified_diff.to_string(),
+            move_path: None,
+        }
+    }
+
+    #[test]
+    fn retained_hunks_are_a_subset_of_the_proposal() {
+        let path = PathBuf::from("/repo/lib.txt");
+        let proposed = HashMap::from([(path.clone(), update(DIFF))]);
+
+        let second_only = retain_hunks(DIFF, &[1]);
+        assert_eq!(second_only, "@@ -8,3 +8,3 @@\n h\n-i\n+I\n j\n");
+        let accepted = HashMap::from([(path.clone(), update(&second_only))]);
+        assert!(is_subset(&accepted, &proposed));
+        assert_eq!(
+            rejection_note(&proposed, &accepted),
+            "The user rejected part of this patch. These changes were not applied:\n- /repo/lib.txt: 1 of 2 hunks"
+        );
+
+        let edited = HashMap::from([(path, update("@@ -8,3 +8,3 @@\n h\n-i\n+X\n j\n"))]);
+        assert!(!is_subset(&edited, &proposed));
+    }
+
+    #[test]
+    fn patch_applies_only_the_kept_hunks() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("lib.txt");
+        std::fs::write(&path, "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\n").expect("write file");
+        let changes = HashMap::from([(path.clone(), update(&retain_hunks(DIFF, &[1])))]);
+
+        let patch = patch_from_changes(&changes);
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        codex_apply_patch::apply_patch(&patch, &mut stdout, &mut stderr).expect("apply patch");
+
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read file"),
+            "a\nb\nc\nd\ne\nf\ng\nh\nI\nj\n"
+        );
+    }
+}
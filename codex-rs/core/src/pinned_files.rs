@@ -0,0 +1,321 @@
+//! Files pinned with `Op::PinFile`.
+//!
+//! The latest contents of every pinned file are recorded ahead of the next
+//! user turn in a `<pinned_files>` message, so specs or TODO lists stay in
+//! front of the model for the whole session. A file is only sent again once
+//! its contents change, or once history is rewritten (compaction, undo,
+//! rollback) and may no longer hold the earlier copy. Files sent together
+//! share a token budget; the file that crosses it is truncated and any after
+//! it wait for the next turn.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use sha2::Digest;
+use sha2::Sha256;
+
+use crate::truncate::TruncationPolicy;
+use crate::truncate::approx_token_count;
+use crate::truncate::truncate_text;
+
+pub(crate) const PINNED_FILES_OPEN_TAG: &str = "<pinned_files>";
+const PINNED_FILES_CLOSE_TAG: &str = "</pinned_files>";
+
+/// Tokens of file contents recorded ahead of a single turn.
+pub(crate) const PINNED_FILES_TOKEN_BUDGET: usize = 8_000;
+
+/// Files larger than this are refused when pinned.
+pub(crate) const MAX_PINNED_FILE_BYTES: u64 = 1024 * 1024;
+
+/// The session's pinned files, in the order they were pinned.
+#[derive(Debug, Default)]
+pub(crate) struct PinnedFiles {
+    files: Vec<PinnedFile>,
+}
+
+#[derive(Debug)]
+struct PinnedFile {
+    path: PathBuf,
+    /// User messages in history when the file was pinned.
+    pinned_after: usize,
+    /// What the model last saw; `None` until the file is next sent.
+    recorded: Option<Recorded>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Recorded {
+    Contents([u8; 32]),
+    Missing,
+}
+
+/// What one file contributes to a `<pinned_files>` message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PinnedFileBody {
+    Contents { text: String, truncated: bool },
+    Missing,
+}
+
+/// Pinned files that changed since the model last saw them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PinnedFilesUpdate {
+    files: Vec<(PathBuf, PinnedFileBody)>,
+    /// Changed files left out because the budget ran out.
+    deferred: Vec<PathBuf>,
+}
+
+impl PinnedFiles {
+    /// Adds `path`, pinned once history held `user_messages` user messages;
+    /// returns false when it was already pinned.
+    pub(crate) fn pin(&mut self, path: PathBuf, user_messages: usize) -> bool {
+        if self.files.iter().any(|file| file.path == path) {
+            return false;
+        }
+        self.files.push(PinnedFile {
+            path,
+            pinned_after: user_messages,
+            recorded: None,
+        });
+        true
+    }
+
+    /// Removes the files pinned after the `user_message`-th (0-based) user
+    /// message was sent, once that message is removed from history, and
+    /// returns their paths.
+    pub(crate) fn unpin_pinned_after(&mut self, user_message: usize) -> Vec<PathBuf> {
+        let (removed, kept) = std::mem::take(&mut self.files)
+            .into_iter()
+            .partition(|file| file.pinned_after > user_message);
+        self.files = kept;
+        removed.into_iter().map(|file| file.path).collect()
+    }
+
+    /// Removes `path`; returns false when it was not pinned.
+    pub(crate) fn unpin(&mut self, path: &Path) -> bool {
+        let len = self.files.len();
+        self.files.retain(|file| file.path != path);
+        self.files.len() != len
+    }
+
+    pub(crate) fn paths(&self) -> Vec<PathBuf> {
+        self.files.iter().map(|file| file.path.clone()).collect()
+    }
+
+    /// Sends every file again on the next turn; called when history is
+    /// replaced and may have lost the earlier copies.
+    pub(crate) fn forget_recorded(&mut self) {
+        for file in &mut self.files {
+            file.recorded = None;
+        }
+    }
+
+    /// Compares freshly read contents (`None` for files that could not be
+    /// read) with what the model last saw and marks the files included in
+    /// the returned update as seen.
+    pub(crate) fn update(
+        &mut self,
+        contents: Vec<(PathBuf, Option<String>)>,
+    ) -> Option<PinnedFilesUpdate> {
+        let mut remaining = PINNED_FILES_TOKEN_BUDGET;
+        let mut files = Vec::new();
+        let mut deferred = Vec::new();
+        for (path, text) in contents {
+            let Some(file) = self.files.iter_mut().find(|file| file.path == path) else {
+                continue;
+            };
+            let recorded = match &text {
+                Some(text) => Recorded::Contents(Sha256::digest(text.as_bytes()).into()),
+                None => Recorded::Missing,
+            };
+            if file.recorded.as_ref() == Some(&recorded) {
+                continue;
+            }
+            let body = match text {
+                None => PinnedFileBody::Missing,
+                Some(_) if remaining == 0 => {
+                    deferred.push(path);
+                    continue;
+                }
+                Some(text) => {
+                    let tokens = approx_token_count(&text);
+                    if tokens <= remaining {
+                        remaining -= tokens;
+                        PinnedFileBody::Contents {
+                            text,
+                            truncated: false,
+                        }
+                    } else {
+                        let text = truncate_text(&text, TruncationPolicy::Tokens(remaining));
+                        remaining = 0;
+                        PinnedFileBody::Contents {
+                            text,
+                            truncated: true,
+                        }
+                    }
+                }
+            };
+            file.recorded = Some(recorded);
+            files.push((path, body));
+        }
+        (!files.is_empty()).then_some(PinnedFilesUpdate { files, deferred })
+    }
+}
+
+/// Reads the pinned files at `paths`, lossily decoding non-UTF-8 contents.
+pub(crate) async fn read_pinned_files(paths: Vec<PathBuf>) -> Vec<(PathBuf, Option<String>)> {
+    let mut contents = Vec::with_capacity(paths.len());
+    for path in paths {
+        let text = tokio::fs::read(&path)
+            .await
+            .ok()
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned());
+        contents.push((path, text));
+    }
+    contents
+}
+
+impl PinnedFilesUpdate {
+    /// One-line summary for the client, e.g. "spec.md, TODO.md (truncated)".
+    pub(crate) fn summary(&self) -> String {
+        let mut parts: Vec<String> = self
+            .files
+            .iter()
+            .map(|(path, body)| {
+                let name = path.display();
+                match body {
+                    PinnedFileBody::Contents {
+                        truncated: false, ..
+                    } => name.to_string(),
+                    PinnedFileBody::Contents {
+                        truncated: true, ..
+                    } => format!("{name} (truncated)"),
+                    PinnedFileBody::Missing => format!("{name} (missing)"),
+                }
+            })
+            .collect();
+        parts.extend(
+            self.deferred
+                .iter()
+                .map(|path| format!("{} (deferred: over budget)", path.display())),
+        );
+        parts.join(", ")
+    }
+
+    /// The message recorded ahead of the next user turn.
+    pub(crate) fn to_response_item(&self) -> ResponseItem {
+        let mut text = format!(
+            "{PINNED_FILES_OPEN_TAG}\nThe user pinned these files so their latest contents stay in context. Pinned files not shown here are unchanged since you last saw them.\n"
+        );
+        for (path, body) in &self.files {
+            let path = path.display();
+            match body {
+                PinnedFileBody::Contents { text: contents, .. } => {
+                    text.push_str(&format!("<file path=\"{path}\">\n{contents}"));
+                    if !contents.ends_with('\n') {
+                        text.push('\n');
+                    }
+                    text.push_str("</file>\n");
+                }
+                PinnedFileBody::Missing => {
+                    text.push_str(&format!("<file path=\"{path}\" missing=\"true\"></file>\n"));
+                }
+            }
+        }
+        text.push_str(PINNED_FILES_CLOSE_TAG);
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText { text }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn contents(files: &[(&str, Option<&str>)]) -> Vec<(PathBuf, Option<String>)> {
+        files
+            .iter()
+            .map(|(path, text)| (PathBuf::from(path), text.map(str::to_string)))
+            .collect()
+    }
+
+    #[test]
+    fn sends_files_only_when_they_change() {
+        let mut pinned = PinnedFiles::default();
+        assert!(pinned.pin(PathBuf::from("/repo/spec.md"), 0));
+        assert!(pinned.pin(PathBuf::from("/repo/TODO.md"), 0));
+        assert!(!pinned.pin(PathBuf::from("/repo/spec.md"), 0));
+
+        let update = pinned
+            .update(contents(&[
+                ("/repo/spec.md", Some("v1")),
+                ("/repo/TODO.md", None),
+            ]))
+            .expect("first update");
+        assert_eq!(update.summary(), "/repo/spec.md, /repo/TODO.md (missing)");
+
+        assert_eq!(
+            pinned.update(contents(&[
+                ("/repo/spec.md", Some("v1")),
+                ("/repo/TODO.md", None),
+            ])),
+            None
+        );
+
+        let update = pinned
+            .update(contents(&[
+                ("/repo/spec.md", Some("v1")),
+                ("/repo/TODO.md", Some("- [ ] ship")),
+            ]))
+            .expect("changed update");
+        assert_eq!(update.summary(), "/repo/TODO.md");
+
+        pinned.forget_recorded();
+        assert!(pinned.unpin(Path::new("/repo/TODO.md")));
+        let update = pinned
+            .update(contents(&[("/repo/spec.md", Some("v1"))]))
+            .expect("resend after forget");
+        assert_eq!(update.summary(), "/repo/spec.md");
+    }
+
+    #[test]
+    fn truncates_and_defers_files_past_the_budget() {
+        let mut pinned = PinnedFiles::default();
+        pinned.pin(PathBuf::from("big.md"), 0);
+        pinned.pin(PathBuf::from("next.md"), 0);
+        let big = "word ".repeat(PINNED_FILES_TOKEN_BUDGET * 2);
+
+        let update = pinned
+            .update(contents(&[("big.md", Some(&big)), ("next.md", Some("hi"))]))
+            .expect("update");
+        assert_eq!(
+            update.summary(),
+            "big.md (truncated), next.md (deferred: over budget)"
+        );
+
+        // The deferred file goes out on the next turn.
+        let update = pinned
+            .update(contents(&[("big.md", Some(&big)), ("next.md", Some("hi"))]))
+            .expect("deferred update");
+        assert_eq!(update.summary(), "next.md");
+    }
+
+    #[test]
+    fn editing_a_message_unpins_files_pinned_after_it() {
+        let mut pinned = PinnedFiles::default();
+        pinned.pin(PathBuf::from("spec.md"), 0);
+        pinned.pin(PathBuf::from("notes.md"), 2);
+        pinned.pin(PathBuf::from("todo.md"), 3);
+
+        // Editing the third message (index 2) keeps pins made before it was sent.
+        assert_eq!(pinned.unpin_pinned_after(2), vec![PathBuf::from("todo.md")]);
+        assert_eq!(
+            pinned.paths(),
+            vec![PathBuf::from("spec.md"), PathBuf::from("notes.md")]
+        );
+    }
+}
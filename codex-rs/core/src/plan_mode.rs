@@ -0,0 +1,253 @@
+//! Plan-first workflow: prompt construction and per-session state.
+//!
+//! Clients drive the workflow through `Op::StartPlan`, `Op::RevisePlan`,
+//! `Op::ApprovePlan` and `Op::CancelPlan`; the session tracks progress in a
+//! [`PlanWorkflow`] and reports drafted plans via `EventMsg::PlanReady`, so the
+//! model sees the same instructions regardless of which front end started the
+//! plan.
+
+use codex_protocol::plan_tool::StepStatus;
+use codex_protocol::plan_tool::UpdatePlanArgs;
+use codex_protocol::protocol::PlanReadyEvent;
+
+use crate::config::types::PlanDetailPreference;
+
+/// Default instructions injected ahead of a plan-mode request. Individual
+/// model families can replace them via `[prompt_adaptations]`.
+pub const PLAN_MODE_INSTRUCTIONS: &str = include_str!("../templates/plan_mode/instructions.md");
+
+const PLAN_REQUEST_TMPL: &str = include_str!("../templates/plan_mode/request.md");
+const PLAN_FEEDBACK_TMPL: &str = include_str!("../templates/plan_mode/feedback.md");
+const PLAN_EXECUTE_TMPL: &str = include_str!("../templates/plan_mode/execute.md");
+
+/// Wrap the user's request with plan-mode instructions.
+pub fn plan_request_prompt(
+    instructions: &str,
+    detail: PlanDetailPreference,
+    request: &str,
+) -> String {
+    PLAN_REQUEST_TMPL
+        .replace("{instructions}", instructions.trim())
+        .replace("{detail}", detail_guidance(detail))
+        .replace("{request}", request.trim())
+}
+
+/// Ask the model to revise its plan using the user's feedback.
+pub fn plan_feedback_prompt(feedback: &str) -> String {
+    PLAN_FEEDBACK_TMPL.replace("{feedback}", feedback.trim())
+}
+
+/// Tell the model the plan was approved and it should start implementing.
+pub fn plan_execution_prompt(request: &str, plan: Option<&UpdatePlanArgs>) -> String {
+    let plan = plan
+        .map(format_plan_steps)
+        .filter(|steps| !steps.is_empty())
+        .unwrap_or_else(|| "(see the plan above)".to_string());
+    PLAN_EXECUTE_TMPL
+        .replace("{plan}", &plan)
+        .replace("{request}", request.trim())
+}
+
+/// Render plan steps as a numbered checklist.
+pub fn format_plan_steps(plan: &UpdatePlanArgs) -> String {
+    let mut lines = Vec::new();
+    if let Some(explanation) = plan
+        .explanation
+        .as_deref()
+        .map(str::trim)
+        .filter(|explanation| !explanation.is_empty())
+    {
+        lines.push(explanation.to_string());
+    }
+    for (idx, item) in plan.plan.iter().enumerate() {
+        let marker = match item.status {
+            StepStatus::Pending => " ",
+            StepStatus::InProgress => "~",
+            StepStatus::Completed => "x",
+        };
+        lines.push(format!("{}. [{marker}] {}", idx + 1, item.step));
+    }
+    lines.join("\n")
+}
+
+/// Where the plan-first workflow currently stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PlanPhase {
+    /// A planning turn is running.
+    Drafting,
+    /// The plan was reported to the client and waits for approval or feedback.
+    AwaitingReview,
+}
+
+/// Plan-first workflow state kept by the session between turns.
+#[derive(Debug, Clone)]
+pub(crate) struct PlanWorkflow {
+    request: String,
+    phase: PlanPhase,
+    latest_plan: Option<UpdatePlanArgs>,
+    feedback: Vec<String>,
+}
+
+impl PlanWorkflow {
+    pub(crate) fn new(request: String) -> Self {
+        Self {
+            request,
+            phase: PlanPhase::Drafting,
+            latest_plan: None,
+            feedback: Vec::new(),
+        }
+    }
+
+    pub(crate) fn phase(&self) -> PlanPhase {
+        self.phase
+    }
+
+    /// Remember the latest plan the model published while drafting.
+    pub(crate) fn record_plan(&mut self, plan: &UpdatePlanArgs) {
+        if self.phase == PlanPhase::Drafting {
+            self.latest_plan = Some(plan.clone());
+        }
+    }
+
+    /// Close out a drafting turn, returning the event to report to clients.
+    pub(crate) fn finish_drafting(&mut self, summary: Option<String>) -> Option<PlanReadyEvent> {
+        if self.phase != PlanPhase::Drafting {
+            return None;
+        }
+        self.phase = PlanPhase::AwaitingReview;
+        Some(PlanReadyEvent {
+            request: self.request.clone(),
+            plan: self.latest_plan.clone(),
+            summary,
+            feedback: self.feedback.clone(),
+        })
+    }
+
+    /// Start another drafting round, returning the prompt to send.
+    pub(crate) fn revise(&mut self, feedback: String) -> Result<String, PlanWorkflowError> {
+        if self.phase != PlanPhase::AwaitingReview {
+            return Err(PlanWorkflowError::NotAwaitingReview);
+        }
+        let prompt = plan_feedback_prompt(&feedback);
+        self.feedback.push(feedback);
+        self.phase = PlanPhase::Drafting;
+        Ok(prompt)
+    }
+
+    /// Finish the workflow, returning the prompt that starts implementation.
+    pub(crate) fn approve(self) -> Result<String, PlanWorkflowError> {
+        if self.phase != PlanPhase::AwaitingReview {
+            return Err(PlanWorkflowError::NotAwaitingReview);
+        }
+        Ok(plan_execution_prompt(
+            &self.request,
+            self.latest_plan.as_ref(),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub(crate) enum PlanWorkflowError {
+    #[error("no plan is being drafted or reviewed")]
+    NoWorkflow,
+    #[error("the plan is still being drafted")]
+    NotAwaitingReview,
+}
+
+fn detail_guidance(detail: PlanDetailPreference) -> &'static str {
+    match detail {
+        PlanDetailPreference::Auto => "match the level of detail to the size of the request.",
+        PlanDetailPreference::Coarse => {
+            "keep the plan short: a handful of high-level steps, no file-by-file breakdown."
+        }
+        PlanDetailPreference::Detailed => {
+            "be thorough: break the work into small steps that name the files and functions involved."
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::plan_tool::PlanItemArg;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn request_prompt_wraps_user_request() {
+        let prompt = plan_request_prompt(
+            PLAN_MODE_INSTRUCTIONS,
+            PlanDetailPreference::Detailed,
+            "map the codebase\n",
+        );
+
+        assert!(prompt.contains("plan-first workflow"));
+        assert!(prompt.contains("Plan detail: be thorough"));
+        assert!(prompt.ends_with("<user_request>\nmap the codebase\n</user_request>\n"));
+    }
+
+    #[test]
+    fn execution_prompt_includes_numbered_steps() {
+        let plan = UpdatePlanArgs {
+            explanation: Some("Add telemetry".to_string()),
+            plan: vec![
+                PlanItemArg {
+                    step: "Add counters".to_string(),
+                    status: StepStatus::Completed,
+                },
+                PlanItemArg {
+                    step: "Wire exporter".to_string(),
+                    status: StepStatus::Pending,
+                },
+            ],
+        };
+
+        assert_eq!(
+            format_plan_steps(&plan),
+            "Add telemetry\n1. [x] Add counters\n2. [ ] Wire exporter"
+        );
+        let prompt = plan_execution_prompt("ship it", Some(&plan));
+        assert!(prompt.starts_with("Plan approved."));
+        assert!(prompt.contains("<original_request>\nship it\n</original_request>"));
+        assert!(prompt.contains("2. [ ] Wire exporter"));
+    }
+
+    #[test]
+    fn workflow_moves_between_drafting_and_review() {
+        let plan = UpdatePlanArgs {
+            explanation: None,
+            plan: vec![PlanItemArg {
+                step: "Add counters".to_string(),
+                status: StepStatus::Pending,
+            }],
+        };
+        let mut workflow = PlanWorkflow::new("add telemetry".to_string());
+        assert_eq!(
+            workflow.revise("too vague".to_string()),
+            Err(PlanWorkflowError::NotAwaitingReview)
+        );
+
+        workflow.record_plan(&plan);
+        let ready = workflow
+            .finish_drafting(Some("summary".to_string()))
+            .expect("drafting turn should report a plan");
+        assert_eq!(ready.request, "add telemetry");
+        assert_eq!(ready.summary.as_deref(), Some("summary"));
+        assert_eq!(workflow.phase(), PlanPhase::AwaitingReview);
+        assert!(workflow.finish_drafting(None).is_none());
+
+        let prompt = workflow
+            .revise("split the first step".to_string())
+            .expect("revision should be accepted");
+        assert!(prompt.contains("split the first step"));
+        assert_eq!(workflow.phase(), PlanPhase::Drafting);
+
+        let ready = workflow
+            .finish_drafting(None)
+            .expect("revision turn should report a plan");
+        assert_eq!(ready.feedback, vec!["split the first step".to_string()]);
+
+        let prompt = workflow.approve().expect("plan should be approvable");
+        assert!(prompt.starts_with("Plan approved."));
+        assert!(prompt.contains("1. [ ] Add counters"));
+    }
+}
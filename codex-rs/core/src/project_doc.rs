@@ -10,10 +10,17 @@
 //!     root is found, only the current working directory is considered.
 //! 2.  Collect every `AGENTS.md` found from the repository root down to the
 //!     current working directory (inclusive) and concatenate their contents in
-//!     that order.
+//!     that order, so the doc closest to the working directory comes last and
+//!     takes precedence over more general ones (for example a package doc in a
+//!     monorepo over the root doc).
 //! 3.  We do **not** walk past the Git root.
+//!
+//! The combined docs are capped at `project_doc_max_bytes`. The budget is spent
+//! starting from the working directory, so a large root doc is truncated (or
+//! left out) before it can crowd out the more specific docs below it.
 
 use crate::config::Config;
+use codex_protocol::protocol::ProjectDocEntry;
 use dunce::canonicalize as normalize_path;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
@@ -28,22 +35,47 @@ pub const LOCAL_PROJECT_DOC_FILENAME: &str = "AGENTS.override.md";
 /// be concatenated with the following separator.
 const PROJECT_DOC_SEPARATOR: &str = "\n\n--- project-doc ---\n\n";
 
+/// A project doc and the part of it that fits in `project_doc_max_bytes`.
+struct ProjectDoc {
+    path: PathBuf,
+    contents: String,
+    truncated: bool,
+}
+
+impl ProjectDoc {
+    fn entry(&self) -> ProjectDocEntry {
+        ProjectDocEntry {
+            path: self.path.clone(),
+            bytes: self.contents.len() as i64,
+            truncated: self.truncated,
+        }
+    }
+}
+
 /// Combines `Config::instructions` and `AGENTS.md` (if present) into a single
-/// string of instructions.
-pub(crate) async fn get_user_instructions(config: &Config) -> Option<String> {
-    match read_project_docs(config).await {
-        Ok(Some(project_doc)) => match &config.user_instructions {
+/// string of instructions. Also returns the docs that were considered, in
+/// precedence order, so the session can report what the model sees.
+pub(crate) async fn get_user_instructions(
+    config: &Config,
+) -> (Option<String>, Vec<ProjectDocEntry>) {
+    let docs = match load_project_docs(config).await {
+        Ok(docs) => docs,
+        Err(e) => {
+            error!("error trying to find project doc: {e:#}");
+            Vec::new()
+        }
+    };
+    let entries = docs.iter().map(ProjectDoc::entry).collect();
+    let instructions = match join_project_docs(&docs) {
+        Some(project_doc) => match &config.user_instructions {
             Some(original_instructions) => Some(format!(
                 "{original_instructions}{PROJECT_DOC_SEPARATOR}{project_doc}"
             )),
             None => Some(project_doc),
         },
-        Ok(None) => config.user_instructions.clone(),
-        Err(e) => {
-            error!("error trying to find project doc: {e:#}");
-            config.user_instructions.clone()
-        }
-    }
+        None => config.user_instructions.clone(),
+    };
+    (instructions, entries)
 }
 
 /// Attempt to locate and load the project documentation.
@@ -53,25 +85,26 @@ pub(crate) async fn get_user_instructions(config: &Config) -> Option<String> {
 /// function returns `Ok(None)`. Unexpected I/O failures bubble up as `Err` so
 /// callers can decide how to handle them.
 pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String>> {
+    Ok(join_project_docs(&load_project_docs(config).await?))
+}
+
+/// Read the discovered docs, ordered from the repository root to the working
+/// directory. Docs that are empty are skipped; docs that no longer fit in the
+/// budget are kept with empty contents so they can be reported as truncated.
+async fn load_project_docs(config: &Config) -> std::io::Result<Vec<ProjectDoc>> {
     let max_total = config.project_doc_max_bytes;
 
     if max_total == 0 {
-        return Ok(None);
+        return Ok(Vec::new());
     }
 
     let paths = discover_project_doc_paths(config)?;
-    if paths.is_empty() {
-        return Ok(None);
-    }
-
     let mut remaining: u64 = max_total as u64;
-    let mut parts: Vec<String> = Vec::new();
-
-    for p in paths {
-        if remaining == 0 {
-            break;
-        }
+    let mut docs: Vec<ProjectDoc> = Vec::new();
 
+    // Spend the budget from the working directory upwards so the most
+    // specific doc always makes it in.
+    for p in paths.into_iter().rev() {
         let file = match tokio::fs::File::open(&p).await {
             Ok(f) => f,
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
@@ -83,7 +116,8 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
         let mut data: Vec<u8> = Vec::new();
         reader.read_to_end(&mut data).await?;
 
-        if size > remaining {
+        let truncated = size > remaining;
+        if truncated {
             tracing::warn!(
                 "Project doc `{}` exceeds remaining budget ({} bytes) - truncating.",
                 p.display(),
@@ -91,17 +125,36 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
             );
         }
 
-        let text = String::from_utf8_lossy(&data).to_string();
-        if !text.trim().is_empty() {
-            parts.push(text);
+        let mut contents = String::from_utf8_lossy(&data).to_string();
+        if contents.trim().is_empty() {
+            if !truncated {
+                continue;
+            }
+            contents.clear();
+        } else {
             remaining = remaining.saturating_sub(data.len() as u64);
         }
+        docs.push(ProjectDoc {
+            path: p,
+            contents,
+            truncated,
+        });
     }
 
+    docs.reverse();
+    Ok(docs)
+}
+
+fn join_project_docs(docs: &[ProjectDoc]) -> Option<String> {
+    let parts: Vec<&str> = docs
+        .iter()
+        .map(|doc| doc.contents.as_str())
+        .filter(|contents| !contents.is_empty())
+        .collect();
     if parts.is_empty() {
-        Ok(None)
+        None
     } else {
-        Ok(Some(parts.join("\n\n")))
+        Some(parts.join("\n\n"))
     }
 }
 
@@ -200,6 +253,7 @@ mod tests {
     use super::*;
     use crate::config::ConfigOverrides;
     use crate::config::ConfigToml;
+    use pretty_assertions::assert_eq;
     use std::fs;
     use tempfile::TempDir;
 
@@ -243,7 +297,9 @@ mod tests {
     async fn no_doc_file_returns_none() {
         let tmp = tempfile::tempdir().expect("tempdir");
 
-        let res = get_user_instructions(&make_config(&tmp, 4096, None)).await;
+        let res = get_user_instructions(&make_config(&tmp, 4096, None))
+            .await
+            .0;
         assert!(
             res.is_none(),
             "Expected None when AGENTS.md is absent and no system instructions provided"
@@ -259,6 +315,7 @@ mod tests {
 
         let res = get_user_instructions(&make_config(&tmp, 4096, None))
             .await
+            .0
             .expect("doc expected");
 
         assert_eq!(
@@ -278,6 +335,7 @@ mod tests {
 
         let res = get_user_instructions(&make_config(&tmp, LIMIT, None))
             .await
+            .0
             .expect("doc expected");
 
         assert_eq!(res.len(), LIMIT, "doc should be truncated to LIMIT bytes");
@@ -308,7 +366,7 @@ mod tests {
         let mut cfg = make_config(&repo, 4096, None);
         cfg.cwd = nested;
 
-        let res = get_user_instructions(&cfg).await.expect("doc expected");
+        let res = get_user_instructions(&cfg).await.0.expect("doc expected");
         assert_eq!(res, "root level doc");
     }
 
@@ -318,7 +376,7 @@ mod tests {
         let tmp = tempfile::tempdir().expect("tempdir");
         fs::write(tmp.path().join("AGENTS.md"), "something").unwrap();
 
-        let res = get_user_instructions(&make_config(&tmp, 0, None)).await;
+        let res = get_user_instructions(&make_config(&tmp, 0, None)).await.0;
         assert!(
             res.is_none(),
             "With limit 0 the function should return None"
@@ -336,6 +394,7 @@ mod tests {
 
         let res = get_user_instructions(&make_config(&tmp, 4096, Some(INSTRUCTIONS)))
             .await
+            .0
             .expect("should produce a combined instruction string");
 
         let expected = format!("{INSTRUCTIONS}{PROJECT_DOC_SEPARATOR}{}", "proj doc");
@@ -351,7 +410,9 @@ mod tests {
 
         const INSTRUCTIONS: &str = "some instructions";
 
-        let res = get_user_instructions(&make_config(&tmp, 4096, Some(INSTRUCTIONS))).await;
+        let res = get_user_instructions(&make_config(&tmp, 4096, Some(INSTRUCTIONS)))
+            .await
+            .0;
 
         assert_eq!(res, Some(INSTRUCTIONS.to_string()));
     }
@@ -380,10 +441,51 @@ mod tests {
         let mut cfg = make_config(&repo, 4096, None);
         cfg.cwd = nested;
 
-        let res = get_user_instructions(&cfg).await.expect("doc expected");
+        let res = get_user_instructions(&cfg).await.0.expect("doc expected");
         assert_eq!(res, "root doc\n\ncrate doc");
     }
 
+    /// When the docs exceed the budget, the doc closest to the working
+    /// directory is kept whole and the root doc is truncated; every doc is
+    /// reported with how much of it was included.
+    #[tokio::test]
+    async fn nested_docs_are_kept_before_root_docs() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            repo.path().join(".git"),
+            "gitdir: /path/to/actual/git/dir\n",
+        )
+        .unwrap();
+        fs::write(repo.path().join("AGENTS.md"), "R".repeat(100)).unwrap();
+        let package = repo.path().join("packages/web");
+        std::fs::create_dir_all(&package).unwrap();
+        fs::write(package.join("AGENTS.md"), "package doc").unwrap();
+
+        let mut cfg = make_config(&repo, 20, None);
+        cfg.cwd = package.clone();
+
+        let (res, entries) = get_user_instructions(&cfg).await;
+        assert_eq!(res, Some(format!("{}\n\npackage doc", "R".repeat(9))));
+
+        let root = normalize_path(repo.path()).unwrap();
+        let package = normalize_path(&package).unwrap();
+        assert_eq!(
+            entries,
+            vec![
+                ProjectDocEntry {
+                    path: root.join("AGENTS.md"),
+                    bytes: 9,
+                    truncated: true,
+                },
+                ProjectDocEntry {
+                    path: package.join("AGENTS.md"),
+                    bytes: 11,
+                    truncated: false,
+                },
+            ]
+        );
+    }
+
     /// AGENTS.override.md is preferred over AGENTS.md when both are present.
     #[tokio::test]
     async fn agents_local_md_preferred() {
@@ -395,6 +497,7 @@ mod tests {
 
         let res = get_user_instructions(&cfg)
             .await
+            .0
             .expect("local doc expected");
 
         assert_eq!(res, "local");
@@ -417,6 +520,7 @@ mod tests {
 
         let res = get_user_instructions(&cfg)
             .await
+            .0
             .expect("fallback doc expected");
 
         assert_eq!(res, "example instructions");
@@ -433,6 +537,7 @@ mod tests {
 
         let res = get_user_instructions(&cfg)
             .await
+            .0
             .expect("AGENTS.md should win");
 
         assert_eq!(res, "primary");
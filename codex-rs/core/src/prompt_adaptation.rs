@@ -0,0 +1,234 @@
+//! Per-model-family prompt adjustments.
+//!
+//! Different model families respond better to slightly different phrasings of
+//! the same guidance (tool call formats, reasoning hints, planning rules). A
+//! [`PromptAdaptation`] collects those tweaks for the active model so that the
+//! turn loop can apply them to the system prompt and tool descriptions every
+//! turn, which keeps behavior consistent when the model changes mid-session.
+//!
+//! Built-in adaptations can be extended or replaced from `config.toml`:
+//!
+//! ```toml
+//! [prompt_adaptations."gpt-oss"]
+//! instructions = "Call tools with a single JSON object."
+//! tool_descriptions = { shell = "Run a shell command." }
+//! ```
+
+use std::collections::HashMap;
+
+use crate::client_common::tools::ToolSpec;
+use crate::config::types::PromptAdaptationToml;
+use crate::model_family::ModelFamily;
+use crate::plan_mode::PLAN_MODE_INSTRUCTIONS;
+
+const GPT_OSS_INSTRUCTIONS: &str = include_str!("../templates/prompt_adaptation/gpt_oss.md");
+const NON_REASONING_INSTRUCTIONS: &str =
+    include_str!("../templates/prompt_adaptation/non_reasoning.md");
+
+/// Built-in adaptations keyed by model family or slug prefix.
+const BUILTIN_INSTRUCTIONS: &[(&str, &str)] = &[
+    ("gpt-oss", GPT_OSS_INSTRUCTIONS),
+    ("openai/gpt-oss", GPT_OSS_INSTRUCTIONS),
+    ("gpt-4.1", NON_REASONING_INSTRUCTIONS),
+    ("gpt-4o", NON_REASONING_INSTRUCTIONS),
+    ("gpt-3.5", NON_REASONING_INSTRUCTIONS),
+];
+
+/// Prompt adjustments resolved for a single model.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PromptAdaptation {
+    /// Extra guidance appended to the base instructions.
+    pub instructions: Option<String>,
+    /// Replacement descriptions for tools, keyed by tool name.
+    pub tool_descriptions: HashMap<String, String>,
+    /// Replacement instructions used when planning.
+    pub plan_mode_instructions: Option<String>,
+}
+
+impl PromptAdaptation {
+    /// Resolve the adaptation for `family`, layering the most specific matching
+    /// entry from `overrides` on top of the built-in defaults.
+    pub fn for_model(
+        family: &ModelFamily,
+        overrides: &HashMap<String, PromptAdaptationToml>,
+    ) -> Self {
+        let builtin = longest_match(
+            family,
+            BUILTIN_INSTRUCTIONS
+                .iter()
+                .map(|(key, value)| (*key, value)),
+        );
+        let mut adaptation = Self {
+            instructions: builtin.map(|instructions| (*instructions).to_string()),
+            ..Default::default()
+        };
+
+        if let Some(custom) = longest_match(
+            family,
+            overrides.iter().map(|(key, value)| (key.as_str(), value)),
+        ) {
+            if let Some(instructions) = &custom.instructions {
+                adaptation.instructions = Some(instructions.clone());
+            }
+            adaptation
+                .tool_descriptions
+                .extend(custom.tool_descriptions.clone());
+            adaptation.plan_mode_instructions = custom.plan_mode_instructions.clone();
+        }
+
+        adaptation
+    }
+
+    /// Instructions to use when the session is planning rather than executing.
+    pub fn plan_mode_instructions(&self) -> &str {
+        self.plan_mode_instructions
+            .as_deref()
+            .unwrap_or(PLAN_MODE_INSTRUCTIONS)
+    }
+
+    /// Append the adapted guidance, if any, to `base`.
+    pub(crate) fn apply_to_instructions(&self, base: &str) -> Option<String> {
+        let extra = self.instructions.as_deref()?.trim();
+        if extra.is_empty() {
+            return None;
+        }
+        Some(format!("{}\n\n{extra}\n", base.trim_end()))
+    }
+
+    /// Rewrite tool descriptions in place using the configured replacements.
+    pub(crate) fn apply_to_tools(&self, tools: &mut [ToolSpec]) {
+        if self.tool_descriptions.is_empty() {
+            return;
+        }
+        for tool in tools.iter_mut() {
+            match tool {
+                ToolSpec::Function(tool) => {
+                    if let Some(description) = self.tool_descriptions.get(&tool.name) {
+                        tool.description = description.clone();
+                    }
+                }
+                ToolSpec::Freeform(tool) => {
+                    if let Some(description) = self.tool_descriptions.get(&tool.name) {
+                        tool.description = description.clone();
+                    }
+                }
+                ToolSpec::LocalShell {} | ToolSpec::WebSearch {} => {}
+            }
+        }
+    }
+}
+
+/// Pick the entry whose key is the longest prefix of the model slug or family.
+fn longest_match<'a, T>(
+    family: &ModelFamily,
+    entries: impl Iterator<Item = (&'a str, T)>,
+) -> Option<T> {
+    entries
+        .filter(|(key, _)| family.slug.starts_with(key) || family.family.starts_with(key))
+        .max_by_key(|(key, _)| key.len())
+        .map(|(_, value)| value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client_common::tools::ResponsesApiTool;
+    use crate::model_family::find_family_for_model;
+    use crate::tools::spec::JsonSchema;
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+
+    fn family(slug: &str) -> ModelFamily {
+        find_family_for_model(slug).expect("known model family")
+    }
+
+    #[test]
+    fn builtin_adaptation_matches_family_prefix() {
+        let adaptation = PromptAdaptation::for_model(&family("gpt-oss-120b"), &HashMap::new());
+        assert_eq!(
+            adaptation,
+            PromptAdaptation {
+                instructions: Some(GPT_OSS_INSTRUCTIONS.to_string()),
+                ..Default::default()
+            }
+        );
+
+        let adaptation = PromptAdaptation::for_model(&family("gpt-5-codex"), &HashMap::new());
+        assert_eq!(adaptation, PromptAdaptation::default());
+        assert_eq!(adaptation.plan_mode_instructions(), PLAN_MODE_INSTRUCTIONS);
+    }
+
+    #[test]
+    fn config_overrides_prefer_longest_prefix() {
+        let overrides = HashMap::from([
+            (
+                "gpt-5".to_string(),
+                PromptAdaptationToml {
+                    instructions: Some("generic".to_string()),
+                    ..Default::default()
+                },
+            ),
+            (
+                "gpt-5-codex".to_string(),
+                PromptAdaptationToml {
+                    instructions: Some("codex".to_string()),
+                    tool_descriptions: HashMap::from([(
+                        "shell".to_string(),
+                        "Run a command.".to_string(),
+                    )]),
+                    plan_mode_instructions: Some("Plan briefly.".to_string()),
+                },
+            ),
+        ]);
+
+        let adaptation = PromptAdaptation::for_model(&family("gpt-5-codex"), &overrides);
+        assert_eq!(
+            adaptation,
+            PromptAdaptation {
+                instructions: Some("codex".to_string()),
+                tool_descriptions: HashMap::from([(
+                    "shell".to_string(),
+                    "Run a command.".to_string(),
+                )]),
+                plan_mode_instructions: Some("Plan briefly.".to_string()),
+            }
+        );
+        assert_eq!(adaptation.plan_mode_instructions(), "Plan briefly.");
+        assert_eq!(
+            adaptation.apply_to_instructions("base\n"),
+            Some("base\n\ncodex\n".to_string())
+        );
+    }
+
+    #[test]
+    fn apply_to_tools_rewrites_matching_descriptions() {
+        let adaptation = PromptAdaptation {
+            tool_descriptions: HashMap::from([("shell".to_string(), "adapted".to_string())]),
+            ..Default::default()
+        };
+        let tool = |name: &str| {
+            ToolSpec::Function(ResponsesApiTool {
+                name: name.to_string(),
+                description: "original".to_string(),
+                strict: false,
+                parameters: JsonSchema::Object {
+                    properties: BTreeMap::new(),
+                    required: None,
+                    additional_properties: None,
+                },
+            })
+        };
+        let mut tools = vec![tool("shell"), tool("read_file"), ToolSpec::WebSearch {}];
+
+        adaptation.apply_to_tools(&mut tools);
+
+        let mut expected_shell = tool("shell");
+        if let ToolSpec::Function(tool) = &mut expected_shell {
+            tool.description = "adapted".to_string();
+        }
+        assert_eq!(
+            tools,
+            vec![expected_shell, tool("read_file"), ToolSpec::WebSearch {}]
+        );
+    }
+}
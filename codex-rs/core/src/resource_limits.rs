@@ -0,0 +1,429 @@
+//! Per-process resource limits for shell commands run on the model's behalf.
+//!
+//! Limits are installed with `setrlimit(2)` in the child right before it
+//! execs, so they are inherited by everything the command spawns and each
+//! process is held to them individually. `exec_command` sessions run in a
+//! PTY that offers no such hook, so their command is started through
+//! `/bin/sh` with the equivalent `ulimit` calls instead. The kernel enforces
+//! the limits: a process that exceeds its CPU time or file size limit is
+//! killed with `SIGXCPU` or `SIGXFSZ`, while allocations and `open(2)` calls
+//! past the address space and file descriptor limits fail, which usually
+//! makes the command exit with an error. [`ResourceLimits::violation`] maps
+//! those outcomes back to the limit that was hit.
+//!
+//! rlimits bound virtual address space rather than resident memory, and the
+//! size of each file written rather than the total written. Limits are not
+//! enforced on Windows yet; sessions warn when they are configured there.
+
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::config::types::ResourceLimitsToml;
+use crate::exec::ExecToolCallOutput;
+use crate::protocol::SandboxPolicy;
+
+#[cfg(unix)]
+const MIB: u64 = 1024 * 1024;
+
+/// Seconds between the `SIGXCPU` sent at the CPU time limit and the `SIGKILL`
+/// sent to a process that ignores it.
+#[cfg(unix)]
+const CPU_TIME_KILL_GRACE_SECS: u64 = 5;
+
+/// Conventional shell exit code base for a child killed by a signal.
+const EXIT_CODE_SIGNAL_BASE: i32 = 128;
+
+const MEMORY_EXHAUSTED_KEYWORDS: [&str; 4] = [
+    "cannot allocate memory",
+    "out of memory",
+    "memory allocation of",
+    "std::bad_alloc",
+];
+
+const OPEN_FILES_EXHAUSTED_KEYWORDS: [&str; 1] = ["too many open files"];
+
+/// Limits applied to every process a shell command spawns. Unset limits are
+/// not enforced.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct ResourceLimits {
+    /// CPU seconds each process may use.
+    pub cpu_time_secs: Option<u64>,
+
+    /// Virtual address space each process may map, in MiB. This is not a
+    /// resident memory (RSS) limit: reserved but untouched memory counts too.
+    pub address_space_mb: Option<u64>,
+
+    /// File descriptors each process may hold open.
+    pub max_open_files: Option<u64>,
+
+    /// Largest single file a process may write, in MiB. The total volume
+    /// written across files is not limited.
+    pub max_file_size_mb: Option<u64>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ResourceLimitKind {
+    CpuTime,
+    AddressSpace,
+    OpenFiles,
+    FileSize,
+}
+
+/// A limit a command ran into, with the configured value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResourceLimitViolation {
+    pub kind: ResourceLimitKind,
+    pub limit: u64,
+}
+
+impl fmt::Display for ResourceLimitViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let limit = self.limit;
+        match self.kind {
+            ResourceLimitKind::CpuTime => write!(f, "CPU time limit of {limit} s"),
+            ResourceLimitKind::AddressSpace => {
+                write!(f, "address space limit of {limit} MiB")
+            }
+            ResourceLimitKind::OpenFiles => write!(f, "open file limit of {limit}"),
+            ResourceLimitKind::FileSize => write!(f, "file size limit of {limit} MiB"),
+        }
+    }
+}
+
+impl ResourceLimits {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `self`, with limits it leaves unset taken from `fallback`.
+    pub fn or(self, fallback: ResourceLimits) -> Self {
+        Self {
+            cpu_time_secs: self.cpu_time_secs.or(fallback.cpu_time_secs),
+            address_space_mb: self.address_space_mb.or(fallback.address_space_mb),
+            max_open_files: self.max_open_files.or(fallback.max_open_files),
+            max_file_size_mb: self.max_file_size_mb.or(fallback.max_file_size_mb),
+        }
+    }
+
+    /// Installs the limits on the current process. Only called between
+    /// `fork` and `exec`, so it must stay async-signal-safe.
+    #[cfg(unix)]
+    pub(crate) fn apply(&self) -> std::io::Result<()> {
+        if let Some(secs) = self.cpu_time_secs {
+            set_rlimit(
+                libc::RLIMIT_CPU,
+                secs,
+                secs.saturating_add(CPU_TIME_KILL_GRACE_SECS),
+            )?;
+        }
+        if let Some(mb) = self.address_space_mb {
+            let bytes = mb.saturating_mul(MIB);
+            set_rlimit(libc::RLIMIT_AS, bytes, bytes)?;
+        }
+        if let Some(count) = self.max_open_files {
+            set_rlimit(libc::RLIMIT_NOFILE, count, count)?;
+        }
+        if let Some(mb) = self.max_file_size_mb {
+            let bytes = mb.saturating_mul(MIB);
+            set_rlimit(libc::RLIMIT_FSIZE, bytes, bytes)?;
+        }
+        Ok(())
+    }
+
+    /// `command`, started through `/bin/sh` so that `ulimit` installs the
+    /// limits first, for spawners that offer no `pre_exec` hook. Without `-H`
+    /// or `-S`, `ulimit` sets both limits. Like [`ResourceLimits::apply`], a
+    /// stricter inherited limit is kept: `ulimit` fails to raise it and the
+    /// failure is ignored.
+    #[cfg(unix)]
+    pub(crate) fn wrap_command(&self, command: Vec<String>) -> Vec<String> {
+        if self.is_empty() {
+            return command;
+        }
+        let mut script = String::new();
+        let mut limit = |flags: &str, value: u64| {
+            script.push_str(&format!("ulimit {flags} {value} 2>/dev/null; "));
+        };
+        if let Some(secs) = self.cpu_time_secs {
+            limit("-H -t", secs.saturating_add(CPU_TIME_KILL_GRACE_SECS));
+            limit("-S -t", secs);
+        }
+        if let Some(mb) = self.address_space_mb {
+            // KiB.
+            limit("-v", mb.saturating_mul(1024));
+        }
+        if let Some(count) = self.max_open_files {
+            limit("-n", count);
+        }
+        if let Some(mb) = self.max_file_size_mb {
+            // 512-byte blocks, the unit `sh` uses for `-f`.
+            limit("-f", mb.saturating_mul(MIB / 512));
+        }
+        script.push_str("exec \"$@\"");
+
+        let mut wrapped = vec![
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            script,
+            "sh".to_string(),
+        ];
+        wrapped.extend(command);
+        wrapped
+    }
+
+    /// The limit that most likely made `output` fail, if any. Signals are
+    /// read from the conventional `128 + signal` exit code, which is also what
+    /// a wrapping shell reports when one of its children is killed.
+    pub(crate) fn violation(&self, output: &ExecToolCallOutput) -> Option<ResourceLimitViolation> {
+        if self.is_empty() || output.exit_code == 0 {
+            return None;
+        }
+
+        let signal = output.exit_code - EXIT_CODE_SIGNAL_BASE;
+        if let Some(violation) = self.violation_for_signal(signal) {
+            return Some(violation);
+        }
+
+        let text = output.aggregated_output.text.to_lowercase();
+        let mentions = |keywords: &[&str]| keywords.iter().any(|needle| text.contains(needle));
+        if let Some(limit) = self.address_space_mb
+            && mentions(&MEMORY_EXHAUSTED_KEYWORDS)
+        {
+            return Some(ResourceLimitViolation {
+                kind: ResourceLimitKind::AddressSpace,
+                limit,
+            });
+        }
+        if let Some(limit) = self.max_open_files
+            && mentions(&OPEN_FILES_EXHAUSTED_KEYWORDS)
+        {
+            return Some(ResourceLimitViolation {
+                kind: ResourceLimitKind::OpenFiles,
+                limit,
+            });
+        }
+        None
+    }
+
+    /// The limit enforced by delivering `signal`, if it is configured.
+    pub(crate) fn violation_for_signal(&self, signal: i32) -> Option<ResourceLimitViolation> {
+        #[cfg(unix)]
+        {
+            if signal == libc::SIGXCPU
+                && let Some(limit) = self.cpu_time_secs
+            {
+                return Some(ResourceLimitViolation {
+                    kind: ResourceLimitKind::CpuTime,
+                    limit,
+                });
+            }
+            if signal == libc::SIGXFSZ
+                && let Some(limit) = self.max_file_size_mb
+            {
+                return Some(ResourceLimitViolation {
+                    kind: ResourceLimitKind::FileSize,
+                    limit,
+                });
+            }
+        }
+        #[cfg(not(unix))]
+        let _ = signal;
+        None
+    }
+}
+
+impl ResourceLimitsToml {
+    /// Limits for commands run under `policy`: the override for its sandbox
+    /// mode, falling back to the defaults for anything it leaves unset.
+    pub fn for_policy(&self, policy: &SandboxPolicy) -> ResourceLimits {
+        let overrides = match policy {
+            SandboxPolicy::ReadOnly => self.read_only,
+            SandboxPolicy::WorkspaceWrite { .. } => self.workspace_write,
+            SandboxPolicy::DangerFullAccess => self.danger_full_access,
+        };
+        overrides.unwrap_or_default().or(self.defaults)
+    }
+
+    /// A warning for platforms where configured limits cannot be enforced.
+    pub fn unsupported_platform_warning(&self) -> Option<String> {
+        let configured = !self.defaults.is_empty()
+            || [
+                self.read_only,
+                self.workspace_write,
+                self.danger_full_access,
+            ]
+            .iter()
+            .flatten()
+            .any(|limits| !limits.is_empty());
+        (cfg!(windows) && configured).then(|| {
+            "[resource_limits] are not enforced on Windows; commands run without them.".to_string()
+        })
+    }
+}
+
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type RlimitResource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type RlimitResource = libc::c_int;
+
+/// Lowers both limits of `resource`, never above the current hard limit so an
+/// already stricter limit inherited from the parent is kept.
+#[cfg(unix)]
+fn set_rlimit(resource: RlimitResource, soft: u64, hard: u64) -> std::io::Result<()> {
+    let mut current = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: `current` is a valid, writable `rlimit`.
+    if unsafe { libc::getrlimit(resource, &mut current) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let hard = (hard as libc::rlim_t).min(current.rlim_max);
+    let limit = libc::rlimit {
+        rlim_cur: (soft as libc::rlim_t).min(hard),
+        rlim_max: hard,
+    };
+    // SAFETY: `limit` is a valid `rlimit`.
+    if unsafe { libc::setrlimit(resource, &limit) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::StreamOutput;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn output(exit_code: i32, text: &str) -> ExecToolCallOutput {
+        ExecToolCallOutput {
+            exit_code,
+            stdout: StreamOutput::new(String::new()),
+            stderr: StreamOutput::new(text.to_string()),
+            aggregated_output: StreamOutput::new(text.to_string()),
+            duration: Duration::from_secs(1),
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn overrides_fall_back_to_defaults() {
+        let defaults = ResourceLimits {
+            cpu_time_secs: Some(60),
+            max_open_files: Some(256),
+            ..Default::default()
+        };
+        let overrides = ResourceLimits {
+            cpu_time_secs: Some(600),
+            ..Default::default()
+        };
+        assert_eq!(
+            overrides.or(defaults),
+            ResourceLimits {
+                cpu_time_secs: Some(600),
+                max_open_files: Some(256),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn sandbox_mode_overrides_apply_to_matching_policies() {
+        let toml: ResourceLimitsToml = toml::from_str(
+            r#"
+cpu_time_secs = 600
+max_open_files = 1024
+
+[read-only]
+cpu_time_secs = 60
+"#,
+        )
+        .expect("valid [resource_limits]");
+
+        assert_eq!(
+            toml.for_policy(&SandboxPolicy::ReadOnly),
+            ResourceLimits {
+                cpu_time_secs: Some(60),
+                max_open_files: Some(1024),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            toml.for_policy(&SandboxPolicy::DangerFullAccess),
+            ResourceLimits {
+                cpu_time_secs: Some(600),
+                max_open_files: Some(1024),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn violations_are_detected_only_for_configured_limits() {
+        let limits = ResourceLimits {
+            cpu_time_secs: Some(30),
+            address_space_mb: Some(512),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            limits.violation(&output(EXIT_CODE_SIGNAL_BASE + libc::SIGXCPU, "")),
+            Some(ResourceLimitViolation {
+                kind: ResourceLimitKind::CpuTime,
+                limit: 30,
+            })
+        );
+        assert_eq!(
+            limits.violation(&output(1, "fatal: Out of memory, malloc failed")),
+            Some(ResourceLimitViolation {
+                kind: ResourceLimitKind::AddressSpace,
+                limit: 512,
+            })
+        );
+        assert_eq!(
+            limits.violation(&output(EXIT_CODE_SIGNAL_BASE + libc::SIGXFSZ, "")),
+            None
+        );
+        assert_eq!(limits.violation(&output(1, "Too many open files")), None);
+        assert_eq!(
+            ResourceLimits::default().violation(&output(1, "out of memory")),
+            None
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn wrapped_commands_set_limits_before_exec() {
+        let command = vec!["bash".to_string(), "-lc".to_string(), "make".to_string()];
+        assert_eq!(
+            ResourceLimits::default().wrap_command(command.clone()),
+            command
+        );
+
+        let limits = ResourceLimits {
+            cpu_time_secs: Some(30),
+            max_file_size_mb: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            limits.wrap_command(command),
+            vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                format!(
+                    "ulimit -H -t {} 2>/dev/null; ulimit -S -t 30 2>/dev/null; \
+                     ulimit -f 2048 2>/dev/null; exec \"$@\"",
+                    30 + CPU_TIME_KILL_GRACE_SECS
+                ),
+                "sh".to_string(),
+                "bash".to_string(),
+                "-lc".to_string(),
+                "make".to_string(),
+            ]
+        );
+    }
+}
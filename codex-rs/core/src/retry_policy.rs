@@ -0,0 +1,101 @@
+//! Decides whether a failed model request is retried and how long to wait.
+//!
+//! Transient provider failures (dropped streams, 5xx responses, timeouts) are
+//! retried with exponential backoff up to the provider's
+//! `stream_max_retries`. Errors that another attempt cannot fix, such as an
+//! exhausted quota or an interrupted turn, fail the turn immediately.
+
+use std::time::Duration;
+
+use crate::error::CodexErr;
+use crate::util::backoff;
+
+/// Retry budget for a single model request within a turn.
+#[derive(Debug)]
+pub(crate) struct TurnRetryPolicy {
+    max_retries: u64,
+    retries: u64,
+}
+
+/// A retry the policy scheduled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ScheduledRetry {
+    /// 1-based number of this retry.
+    pub(crate) attempt: u64,
+    pub(crate) max_attempts: u64,
+    pub(crate) delay: Duration,
+}
+
+impl TurnRetryPolicy {
+    pub(crate) fn new(max_retries: u64) -> Self {
+        Self {
+            max_retries,
+            retries: 0,
+        }
+    }
+
+    /// Schedules another attempt after `err`, or returns `None` when the
+    /// error is not transient or the retry budget is spent. A delay requested
+    /// by the provider takes precedence over the backoff.
+    pub(crate) fn schedule(&mut self, err: &CodexErr) -> Option<ScheduledRetry> {
+        if !is_transient(err) || self.retries >= self.max_retries {
+            return None;
+        }
+        self.retries += 1;
+        let delay = match err {
+            CodexErr::Stream(_, Some(delay)) => *delay,
+            _ => backoff(self.retries),
+        };
+        Some(ScheduledRetry {
+            attempt: self.retries,
+            max_attempts: self.max_retries,
+            delay,
+        })
+    }
+}
+
+/// Whether another attempt of the same request may succeed.
+pub(crate) fn is_transient(err: &CodexErr) -> bool {
+    !matches!(
+        err,
+        CodexErr::TurnAborted { .. }
+            | CodexErr::Interrupted
+            | CodexErr::EnvVar(_)
+            | CodexErr::Fatal(_)
+            | CodexErr::ContextWindowExceeded
+            | CodexErr::UsageLimitReached(_)
+            | CodexErr::UsageNotIncluded
+            | CodexErr::QuotaExceeded
+            | CodexErr::RefreshTokenFailed(_)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn retries_transient_errors_until_budget_is_spent() {
+        let mut policy = TurnRetryPolicy::new(2);
+        let err = CodexErr::Stream("disconnected".to_string(), Some(Duration::from_secs(3)));
+
+        assert_eq!(
+            policy.schedule(&err),
+            Some(ScheduledRetry {
+                attempt: 1,
+                max_attempts: 2,
+                delay: Duration::from_secs(3),
+            })
+        );
+        assert_eq!(policy.schedule(&err).map(|retry| retry.attempt), Some(2));
+        assert_eq!(policy.schedule(&err), None);
+    }
+
+    #[test]
+    fn does_not_retry_permanent_errors() {
+        let mut policy = TurnRetryPolicy::new(5);
+        assert_eq!(policy.schedule(&CodexErr::QuotaExceeded), None);
+        assert_eq!(policy.schedule(&CodexErr::Interrupted), None);
+    }
+}
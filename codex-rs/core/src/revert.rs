@@ -0,0 +1,106 @@
+//! Restores individual files from the ghost snapshots captured at the start
+//! of each turn, without rolling back the whole working tree.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_git::CreateGhostCommitOptions;
+use codex_git::GhostCommit;
+use codex_git::GitToolingError;
+use codex_git::changed_paths_between_ghost_commits;
+use codex_git::create_ghost_commit;
+use codex_git::read_ghost_commit_file;
+
+use crate::protocol::FileChange;
+
+/// Which files a revert should restore from the snapshot.
+pub(crate) enum RevertScope {
+    /// Exactly these paths, relative to the session `cwd`.
+    Paths(Vec<PathBuf>),
+    /// Every file that changed between the snapshot and `next`, or the current
+    /// working tree when the snapshot belongs to the most recent turn.
+    ChangedUntil(Option<GhostCommit>),
+}
+
+/// Files a revert will touch, with the edits it makes keyed by absolute path.
+pub(crate) struct RevertPlan {
+    pub(crate) paths: Vec<PathBuf>,
+    pub(crate) changes: HashMap<PathBuf, FileChange>,
+}
+
+/// Works out which files to restore and how each one will change. Blocking;
+/// run it on the blocking pool.
+pub(crate) fn plan_revert(
+    repo_path: &Path,
+    snapshot: &GhostCommit,
+    scope: RevertScope,
+) -> Result<RevertPlan, GitToolingError> {
+    let candidates = match scope {
+        RevertScope::Paths(paths) => paths,
+        RevertScope::ChangedUntil(next) => {
+            let to = match next {
+                Some(next) => next,
+                None => create_ghost_commit(&CreateGhostCommitOptions::new(repo_path))?,
+            };
+            changed_paths_between_ghost_commits(repo_path, snapshot, &to)?
+        }
+    };
+
+    let mut paths = Vec::with_capacity(candidates.len());
+    let mut changes = HashMap::with_capacity(candidates.len());
+    for path in candidates {
+        let absolute = repo_path.join(&path);
+        let current = std::fs::read_to_string(&absolute).ok();
+        let original = read_ghost_commit_file(repo_path, snapshot, &path)?;
+        if let Some(change) = revert_change(current, original) {
+            changes.insert(absolute, change);
+            paths.push(path);
+        }
+    }
+
+    Ok(RevertPlan { paths, changes })
+}
+
+/// Describes the edit that turns `current` back into `original`, or `None`
+/// when the file already matches the snapshot.
+fn revert_change(current: Option<String>, original: Option<String>) -> Option<FileChange> {
+    match (current, original) {
+        (Some(content), None) => Some(FileChange::Delete { content }),
+        (None, Some(content)) => Some(FileChange::Add { content }),
+        (Some(current), Some(original)) if current != original => Some(FileChange::Update {
+            unified_diff: similar::TextDiff::from_lines(&current, &original)
+                .unified_diff()
+                .context_radius(3)
+                .to_string(),
+            move_path: None,
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn revert_change_restores_the_snapshot_contents() {
+        assert!(matches!(
+            revert_change(Some("new\n".to_string()), None),
+            Some(FileChange::Delete { content }) if content == "new\n"
+        ));
+        assert!(matches!(
+            revert_change(None, Some("old\n".to_string())),
+            Some(FileChange::Add { content }) if content == "old\n"
+        ));
+        assert!(revert_change(Some("same\n".to_string()), Some("same\n".to_string())).is_none());
+
+        let Some(FileChange::Update { unified_diff, .. }) =
+            revert_change(Some("after\n".to_string()), Some("before\n".to_string()))
+        else {
+            panic!("expected an update");
+        };
+        assert_eq!(unified_diff, "@@ -1 +1 @@\n-after\n+before\n");
+    }
+}
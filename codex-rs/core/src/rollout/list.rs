@@ -12,7 +12,10 @@ use time::format_description::FormatItem;
 use time::macros::format_description;
 use uuid::Uuid;
 
+use super::ARCHIVED_SESSIONS_SUBDIR;
 use super::SESSIONS_SUBDIR;
+use super::sessions::is_compressed_rollout;
+use super::sessions::read_rollout_to_string;
 use crate::protocol::EventMsg;
 use codex_file_search as file_search;
 use codex_protocol::protocol::RolloutItem;
@@ -45,12 +48,15 @@ pub struct ConversationItem {
     pub created_at: Option<String>,
     /// RFC3339 timestamp string for the most recent response in the tail, if available.
     pub updated_at: Option<String>,
+    /// Total tokens used by the session, taken from the latest token count in the tail.
+    pub total_tokens: Option<i64>,
 }
 
 #[derive(Default)]
 struct HeadTailSummary {
     head: Vec<serde_json::Value>,
     tail: Vec<serde_json::Value>,
+    total_tokens: Option<i64>,
     saw_session_meta: bool,
     saw_user_event: bool,
     source: Option<SessionSource>,
@@ -150,8 +156,8 @@ pub(crate) async fn get_conversation(path: &Path) -> io::Result<String> {
 
 /// Load conversation file paths from disk using directory traversal.
 ///
-/// Directory layout: `~/.codex/sessions/YYYY/MM/DD/rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl`
-/// Returned newest (latest) first.
+/// Directory layout: `~/.codex/sessions/YYYY/MM/DD/rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl`,
+/// optionally compressed as `.jsonl.gz`. Returned newest (latest) first.
 async fn traverse_directories_for_paths(
     root: PathBuf,
     page_size: usize,
@@ -185,10 +191,6 @@ async fn traverse_directories_for_paths(
                     break 'outer;
                 }
                 let mut day_files = collect_files(day_path, |name_str, path| {
-                    if !name_str.starts_with("rollout-") || !name_str.ends_with(".jsonl") {
-                        return None;
-                    }
-
                     parse_timestamp_uuid_from_filename(name_str)
                         .map(|(ts, id)| (ts, id, name_str.to_string(), path.to_path_buf()))
                 })
@@ -236,6 +238,7 @@ async fn traverse_directories_for_paths(
                             tail,
                             created_at,
                             mut updated_at,
+                            total_tokens,
                             ..
                         } = summary;
                         updated_at = updated_at.or_else(|| created_at.clone());
@@ -245,6 +248,7 @@ async fn traverse_directories_for_paths(
                             tail,
                             created_at,
                             updated_at,
+                            total_tokens,
                         });
                     }
                 }
@@ -341,9 +345,12 @@ where
     Ok(collected)
 }
 
-fn parse_timestamp_uuid_from_filename(name: &str) -> Option<(OffsetDateTime, Uuid)> {
-    // Expected: rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl
-    let core = name.strip_prefix("rollout-")?.strip_suffix(".jsonl")?;
+pub(crate) fn parse_timestamp_uuid_from_filename(name: &str) -> Option<(OffsetDateTime, Uuid)> {
+    // Expected: rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl, or .jsonl.gz
+    let name = name.strip_prefix("rollout-")?;
+    let core = name
+        .strip_suffix(".jsonl")
+        .or_else(|| name.strip_suffix(".jsonl.gz"))?;
 
     // Scan from the right for a '-' such that the suffix parses as a UUID.
     let (sep_idx, uuid) = core
@@ -391,66 +398,91 @@ async fn read_head_and_tail(
 ) -> io::Result<HeadTailSummary> {
     use tokio::io::AsyncBufReadExt;
 
+    let mut summary = HeadTailSummary::default();
+
+    // Archives cannot be read from the end, so they are decompressed whole.
+    if is_compressed_rollout(path) {
+        let text = read_rollout_to_string(path).await?;
+        for line in text.lines() {
+            if summary.head.len() >= head_limit {
+                break;
+            }
+            add_head_line(&mut summary, line);
+        }
+        if tail_limit != 0 {
+            let (tail, updated_at, total_tokens) =
+                collect_last_response_values(text.as_bytes(), tail_limit);
+            summary.tail = tail;
+            summary.updated_at = updated_at;
+            summary.total_tokens = total_tokens;
+        }
+        return Ok(summary);
+    }
+
     let file = tokio::fs::File::open(path).await?;
     let reader = tokio::io::BufReader::new(file);
     let mut lines = reader.lines();
-    let mut summary = HeadTailSummary::default();
 
     while summary.head.len() < head_limit {
         let line_opt = lines.next_line().await?;
         let Some(line) = line_opt else { break };
-        let trimmed = line.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
-
-        let parsed: Result<RolloutLine, _> = serde_json::from_str(trimmed);
-        let Ok(rollout_line) = parsed else { continue };
-
-        match rollout_line.item {
-            RolloutItem::SessionMeta(session_meta_line) => {
-                summary.source = Some(session_meta_line.meta.source.clone());
-                summary.model_provider = session_meta_line.meta.model_provider.clone();
-                summary.created_at = summary
-                    .created_at
-                    .clone()
-                    .or_else(|| Some(rollout_line.timestamp.clone()));
-                if let Ok(val) = serde_json::to_value(session_meta_line) {
-                    summary.head.push(val);
-                    summary.saw_session_meta = true;
-                }
-            }
-            RolloutItem::ResponseItem(item) => {
-                summary.created_at = summary
-                    .created_at
-                    .clone()
-                    .or_else(|| Some(rollout_line.timestamp.clone()));
-                if let Ok(val) = serde_json::to_value(item) {
-                    summary.head.push(val);
-                }
-            }
-            RolloutItem::TurnContext(_) => {
-                // Not included in `head`; skip.
-            }
-            RolloutItem::Compacted(_) => {
-                // Not included in `head`; skip.
-            }
-            RolloutItem::EventMsg(ev) => {
-                if matches!(ev, EventMsg::UserMessage(_)) {
-                    summary.saw_user_event = true;
-                }
-            }
-        }
+        add_head_line(&mut summary, &line);
     }
 
     if tail_limit != 0 {
-        let (tail, updated_at) = read_tail_records(path, tail_limit).await?;
+        let (tail, updated_at, total_tokens) = read_tail_records(path, tail_limit).await?;
         summary.tail = tail;
         summary.updated_at = updated_at;
+        summary.total_tokens = total_tokens;
     }
     Ok(summary)
 }
 
+fn add_head_line(summary: &mut HeadTailSummary, line: &str) {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return;
+    }
+
+    let parsed: Result<RolloutLine, _> = serde_json::from_str(trimmed);
+    let Ok(rollout_line) = parsed else { return };
+
+    match rollout_line.item {
+        RolloutItem::SessionMeta(session_meta_line) => {
+            summary.source = Some(session_meta_line.meta.source.clone());
+            summary.model_provider = session_meta_line.meta.model_provider.clone();
+            summary.created_at = summary
+                .created_at
+                .clone()
+                .or_else(|| Some(rollout_line.timestamp.clone()));
+            if let Ok(val) = serde_json::to_value(session_meta_line) {
+                summary.head.push(val);
+                summary.saw_session_meta = true;
+            }
+        }
+        RolloutItem::ResponseItem(item) => {
+            summary.created_at = summary
+                .created_at
+                .clone()
+                .or_else(|| Some(rollout_line.timestamp.clone()));
+            if let Ok(val) = serde_json::to_value(item) {
+                summary.head.push(val);
+            }
+        }
+        RolloutItem::TurnContext(_) => {
+            // Not included in `head`; skip.
+        }
+        RolloutItem::Compacted(_) => {
+            // Not included in `head`; skip.
+        }
+        RolloutItem::EventMsg(ev) => {
+            if matches!(ev, EventMsg::UserMessage(_)) {
+                summary.saw_user_event = true;
+            }
+        }
+    }
+}
+
 /// Read up to `HEAD_RECORD_LIMIT` records from the start of the rollout file at `path`.
 /// This should be enough to produce a summary including the session meta line.
 pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Value>> {
@@ -458,16 +490,17 @@ pub async fn read_head_for_summary(path: &Path) -> io::Result<Vec<serde_json::Va
     Ok(summary.head)
 }
 
-async fn read_tail_records(
-    path: &Path,
-    max_records: usize,
-) -> io::Result<(Vec<serde_json::Value>, Option<String>)> {
+/// Tail response records, the newest response timestamp, and the latest
+/// total token count seen among those records.
+type TailRecords = (Vec<serde_json::Value>, Option<String>, Option<i64>);
+
+async fn read_tail_records(path: &Path, max_records: usize) -> io::Result<TailRecords> {
     use std::io::SeekFrom;
     use tokio::io::AsyncReadExt;
     use tokio::io::AsyncSeekExt;
 
     if max_records == 0 {
-        return Ok((Vec::new(), None));
+        return Ok((Vec::new(), None, None));
     }
 
     const CHUNK_SIZE: usize = 8192;
@@ -475,7 +508,7 @@ async fn read_tail_records(
     let mut file = tokio::fs::File::open(path).await?;
     let mut pos = file.seek(SeekFrom::End(0)).await?;
     if pos == 0 {
-        return Ok((Vec::new(), None));
+        return Ok((Vec::new(), None, None));
     }
 
     let mut buffer: Vec<u8> = Vec::new();
@@ -486,17 +519,18 @@ async fn read_tail_records(
             (true, Some(idx)) => idx + 1,
             _ => 0,
         };
-        let (tail, newest_ts) = collect_last_response_values(&buffer[slice_start..], max_records);
+        let (tail, newest_ts, total_tokens) =
+            collect_last_response_values(&buffer[slice_start..], max_records);
         if latest_timestamp.is_none() {
             latest_timestamp = newest_ts.clone();
         }
         if tail.len() >= max_records || pos == 0 {
-            return Ok((tail, latest_timestamp.or(newest_ts)));
+            return Ok((tail, latest_timestamp.or(newest_ts), total_tokens));
         }
 
         let read_size = CHUNK_SIZE.min(pos as usize);
         if read_size == 0 {
-            return Ok((tail, latest_timestamp.or(newest_ts)));
+            return Ok((tail, latest_timestamp.or(newest_ts), total_tokens));
         }
         pos -= read_size as u64;
         file.seek(SeekFrom::Start(pos)).await?;
@@ -507,19 +541,17 @@ async fn read_tail_records(
     }
 }
 
-fn collect_last_response_values(
-    buffer: &[u8],
-    max_records: usize,
-) -> (Vec<serde_json::Value>, Option<String>) {
+fn collect_last_response_values(buffer: &[u8], max_records: usize) -> TailRecords {
     use std::borrow::Cow;
 
     if buffer.is_empty() || max_records == 0 {
-        return (Vec::new(), None);
+        return (Vec::new(), None, None);
     }
 
     let text: Cow<'_, str> = String::from_utf8_lossy(buffer);
     let mut collected_rev: Vec<serde_json::Value> = Vec::new();
     let mut latest_timestamp: Option<String> = None;
+    let mut total_tokens: Option<i64> = None;
     for line in text.lines().rev() {
         let trimmed = line.trim();
         if trimmed.is_empty() {
@@ -528,6 +560,14 @@ fn collect_last_response_values(
         let parsed: serde_json::Result<RolloutLine> = serde_json::from_str(trimmed);
         let Ok(rollout_line) = parsed else { continue };
         let RolloutLine { timestamp, item } = rollout_line;
+        if let RolloutItem::EventMsg(EventMsg::TokenCount(event)) = &item
+            && total_tokens.is_none()
+        {
+            total_tokens = event
+                .info
+                .as_ref()
+                .map(|info| info.total_token_usage.total_tokens);
+        }
         if let RolloutItem::ResponseItem(item) = item
             && let Ok(val) = serde_json::to_value(&item)
         {
@@ -541,29 +581,38 @@ fn collect_last_response_values(
         }
     }
     collected_rev.reverse();
-    (collected_rev, latest_timestamp)
+    (collected_rev, latest_timestamp, total_tokens)
 }
 
 /// Locate a recorded conversation rollout file by its UUID string using the existing
-/// paginated listing implementation. Returns `Ok(Some(path))` if found, `Ok(None)` if not present
-/// or the id is invalid.
+/// paginated listing implementation. Active sessions are searched first, then archived
+/// ones, which may be compressed as `.jsonl.gz`. Returns `Ok(Some(path))` if found,
+/// `Ok(None)` if not present or the id is invalid.
 pub async fn find_conversation_path_by_id_str(
     codex_home: &Path,
     id_str: &str,
 ) -> io::Result<Option<PathBuf>> {
     // Validate UUID format early.
-    if Uuid::parse_str(id_str).is_err() {
+    let Ok(id) = Uuid::parse_str(id_str) else {
         return Ok(None);
+    };
+
+    for subdir in [SESSIONS_SUBDIR, ARCHIVED_SESSIONS_SUBDIR] {
+        if let Some(path) = find_rollout_in(&codex_home.join(subdir), id_str, id)? {
+            return Ok(Some(path));
+        }
     }
+    Ok(None)
+}
 
-    let mut root = codex_home.to_path_buf();
-    root.push(SESSIONS_SUBDIR);
+fn find_rollout_in(root: &Path, id_str: &str, id: Uuid) -> io::Result<Option<PathBuf>> {
     if !root.exists() {
         return Ok(None);
     }
+    // Room for the rollout's `.lock` file, which also matches the id.
     // This is safe because we know the values are valid.
     #[allow(clippy::unwrap_used)]
-    let limit = NonZero::new(1).unwrap();
+    let limit = NonZero::new(4).unwrap();
     // This is safe because we know the values are valid.
     #[allow(clippy::unwrap_used)]
     let threads = NonZero::new(2).unwrap();
@@ -574,7 +623,7 @@ pub async fn find_conversation_path_by_id_str(
     let results = file_search::run(
         id_str,
         limit,
-        &root,
+        root,
         exclude,
         threads,
         cancel,
@@ -586,6 +635,11 @@ pub async fn find_conversation_path_by_id_str(
     Ok(results
         .matches
         .into_iter()
-        .next()
-        .map(|m| root.join(m.path)))
+        .map(|m| root.join(m.path))
+        .find(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .and_then(parse_timestamp_uuid_from_filename)
+                .is_some_and(|(_, found)| found == id)
+        }))
 }
@@ -0,0 +1,322 @@
+//! Cross-process locks on rollout files.
+//!
+//! A session appending to a rollout holds `<rollout>.lock`, a small JSON file
+//! naming the owning process. Resuming a rollout that a live process on this
+//! machine is still writing fails with [`io::ErrorKind::ResourceBusy`], so two
+//! sessions never interleave writes. Frontends can show the owner from
+//! [`session_lock_owner`] and either open the rollout read-only (a replay) or
+//! take the session over with
+//! [`ConversationManager::take_over_conversation_from_rollout`]. The previous
+//! owner stops persisting as soon as it notices the lock changed hands,
+//! reports `EventMsg::SessionLockLost`, and can take the session back with
+//! `Op::ReclaimSessionLock`.
+//!
+//! [`ConversationManager::take_over_conversation_from_rollout`]: crate::ConversationManager::take_over_conversation_from_rollout
+
+use std::fs::OpenOptions;
+use std::io;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde::Serialize;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+
+const LOCK_SUFFIX: &str = ".lock";
+
+/// How long a lock file may stay empty or unreadable before it is treated as
+/// left behind by a crash. A younger one belongs to a process that created
+/// it and has not written its owner yet.
+const UNWRITTEN_LOCK_GRACE: Duration = Duration::from_secs(10);
+
+/// The process holding a rollout lock.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionLockOwner {
+    pub hostname: String,
+    pub pid: u32,
+    /// RFC 3339 time the lock was taken.
+    pub started_at: String,
+}
+
+impl SessionLockOwner {
+    fn current() -> Self {
+        Self {
+            hostname: hostname(),
+            pid: std::process::id(),
+            started_at: OffsetDateTime::now_utc()
+                .format(&Rfc3339)
+                .unwrap_or_default(),
+        }
+    }
+
+    fn is_same_process(&self, other: &SessionLockOwner) -> bool {
+        self.hostname == other.hostname && self.pid == other.pid
+    }
+
+    /// Whether the owner may still be running. Owners on other hosts (a
+    /// shared `CODEX_HOME`) cannot be checked and are assumed alive.
+    fn is_alive(&self) -> bool {
+        self.hostname != hostname() || process_exists(self.pid)
+    }
+}
+
+/// A held rollout lock; released on drop unless another process took the
+/// session over in the meantime.
+#[derive(Debug)]
+pub(crate) struct SessionLock {
+    path: PathBuf,
+    owner: SessionLockOwner,
+}
+
+impl SessionLock {
+    /// Lock `rollout_path` for this process. Locks left by processes that
+    /// are gone are replaced.
+    pub(crate) fn acquire(rollout_path: &Path) -> io::Result<Self> {
+        let path = lock_path(rollout_path);
+        let owner = SessionLockOwner::current();
+        let contents = serde_json::to_vec(&owner)?;
+        let mut replaced_stale = false;
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    file.write_all(&contents)?;
+                    return Ok(Self { path, owner });
+                }
+                Err(err) if err.kind() == ErrorKind::AlreadyExists && !replaced_stale => {
+                    match read_owner(&path) {
+                        Some(existing)
+                            if existing.is_alive() && !existing.is_same_process(&owner) =>
+                        {
+                            return Err(locked_error(&existing));
+                        }
+                        Some(_) => {}
+                        None if !is_abandoned(&path) => {
+                            return Err(io::Error::new(
+                                ErrorKind::ResourceBusy,
+                                "session is being locked by another process",
+                            ));
+                        }
+                        None => {}
+                    }
+                    remove_lock_file(&path)?;
+                    replaced_stale = true;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// False once another process has taken the session over. Reads the
+    /// lock file without blocking the runtime; the rollout writer calls this
+    /// before every batch it appends.
+    pub(crate) async fn is_held(&self) -> bool {
+        let owner = tokio::fs::read(&self.path)
+            .await
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<SessionLockOwner>(&bytes).ok());
+        owner.is_none_or(|owner| owner.is_same_process(&self.owner))
+    }
+
+    /// Point the lock back at this process after another one took the
+    /// session over.
+    pub(crate) async fn reclaim(&self) -> io::Result<()> {
+        replace_lock_file(&self.path, &self.owner).await
+    }
+}
+
+impl Drop for SessionLock {
+    fn drop(&mut self) {
+        if read_owner(&self.path).is_some_and(|owner| owner.is_same_process(&self.owner)) {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// The live process, other than this one, currently writing `rollout_path`.
+pub fn session_lock_owner(rollout_path: &Path) -> Option<SessionLockOwner> {
+    let current = SessionLockOwner::current();
+    read_owner(&lock_path(rollout_path))
+        .filter(|owner| owner.is_alive() && !owner.is_same_process(&current))
+}
+
+/// Point the lock on `rollout_path` at this process, even though another
+/// process holds it, so the next resume here can acquire it.
+pub(crate) async fn take_over_session_lock(rollout_path: &Path) -> io::Result<()> {
+    replace_lock_file(&lock_path(rollout_path), &SessionLockOwner::current()).await
+}
+
+/// Drop a lock taken over with [`take_over_session_lock`] when the resume
+/// that needed it failed.
+pub(crate) fn release_session_lock(rollout_path: &Path) {
+    let path = lock_path(rollout_path);
+    if read_owner(&path).is_some_and(|owner| owner.is_same_process(&SessionLockOwner::current())) {
+        let _ = std::fs::remove_file(&path);
+    }
+}
+
+/// Whether a live process, this one included, is writing `rollout_path`.
+pub(crate) fn is_session_locked(rollout_path: &Path) -> bool {
+    read_owner(&lock_path(rollout_path)).is_some_and(|owner| owner.is_alive())
+}
+
+fn lock_path(rollout_path: &Path) -> PathBuf {
+    let mut path = rollout_path.as_os_str().to_owned();
+    path.push(LOCK_SUFFIX);
+    PathBuf::from(path)
+}
+
+fn read_owner(path: &Path) -> Option<SessionLockOwner> {
+    let bytes = std::fs::read(path).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Whether an unreadable lock file has been around too long to be one that
+/// another process is still writing. A file that is already gone counts as
+/// abandoned so the caller retries.
+fn is_abandoned(path: &Path) -> bool {
+    match std::fs::metadata(path).and_then(|metadata| metadata.modified()) {
+        Ok(modified) => modified
+            .elapsed()
+            .is_ok_and(|age| age >= UNWRITTEN_LOCK_GRACE),
+        Err(_) => true,
+    }
+}
+
+/// Replace the lock file in one step, so readers never see it empty.
+async fn replace_lock_file(path: &Path, owner: &SessionLockOwner) -> io::Result<()> {
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", owner.pid));
+    let tmp = PathBuf::from(tmp);
+    tokio::fs::write(&tmp, serde_json::to_vec(owner)?).await?;
+    tokio::fs::rename(&tmp, path).await
+}
+
+fn remove_lock_file(path: &Path) -> io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn locked_error(owner: &SessionLockOwner) -> io::Error {
+    io::Error::new(
+        ErrorKind::ResourceBusy,
+        format!(
+            "session is in use by process {} on {} since {}",
+            owner.pid, owner.hostname, owner.started_at
+        ),
+    )
+}
+
+#[cfg(unix)]
+fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    // SAFETY: the buffer is valid for `buf.len()` bytes.
+    let rc = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+    if rc != 0 {
+        return String::new();
+    }
+    let len = buf.iter().position(|byte| *byte == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+#[cfg(not(unix))]
+fn hostname() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_default()
+}
+
+#[cfg(unix)]
+fn process_exists(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // SAFETY: signal 0 only checks whether the process can be signalled.
+    let rc = unsafe { libc::kill(pid, 0) };
+    rc == 0 || io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn process_exists(_pid: u32) -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn live_foreign_owner_blocks_until_taken_over() {
+        let dir = TempDir::new().expect("tempdir");
+        let rollout = dir.path().join("rollout.jsonl");
+        // pid 1 is always running.
+        let foreign = SessionLockOwner {
+            hostname: hostname(),
+            pid: 1,
+            started_at: "2025-01-01T00:00:00Z".to_string(),
+        };
+        std::fs::write(
+            lock_path(&rollout),
+            serde_json::to_vec(&foreign).expect("serialize owner"),
+        )
+        .expect("write lock");
+
+        assert_eq!(session_lock_owner(&rollout), Some(foreign));
+        let err = SessionLock::acquire(&rollout).expect_err("lock is held");
+        assert_eq!(err.kind(), ErrorKind::ResourceBusy);
+
+        take_over_session_lock(&rollout)
+            .await
+            .expect("take over lock");
+        let lock = SessionLock::acquire(&rollout).expect("acquire after takeover");
+        assert!(lock.is_held().await);
+        assert_eq!(session_lock_owner(&rollout), None);
+        drop(lock);
+        assert!(!lock_path(&rollout).exists());
+    }
+
+    #[tokio::test]
+    async fn lock_lost_to_takeover_is_left_alone() {
+        let dir = TempDir::new().expect("tempdir");
+        let rollout = dir.path().join("rollout.jsonl");
+        let lock = SessionLock::acquire(&rollout).expect("acquire");
+
+        let other = SessionLockOwner {
+            hostname: "elsewhere".to_string(),
+            pid: 4242,
+            started_at: "2025-01-01T00:00:00Z".to_string(),
+        };
+        let contents = serde_json::to_vec(&other).expect("serialize owner");
+        std::fs::write(lock_path(&rollout), &contents).expect("write lock");
+
+        assert!(!lock.is_held().await);
+        lock.reclaim().await.expect("reclaim");
+        assert!(lock.is_held().await);
+        std::fs::write(lock_path(&rollout), &contents).expect("write lock");
+        drop(lock);
+        assert_eq!(
+            std::fs::read(lock_path(&rollout)).expect("lock kept"),
+            contents
+        );
+    }
+
+    #[test]
+    fn unwritten_lock_is_treated_as_held() {
+        let dir = TempDir::new().expect("tempdir");
+        let rollout = dir.path().join("rollout.jsonl");
+        // Another process created the lock and has not written its owner yet.
+        std::fs::write(lock_path(&rollout), b"").expect("create lock");
+
+        let err = SessionLock::acquire(&rollout).expect_err("lock is being taken");
+        assert_eq!(err.kind(), ErrorKind::ResourceBusy);
+        assert_eq!(
+            std::fs::read(lock_path(&rollout)).expect("lock kept"),
+            Vec::<u8>::new()
+        );
+    }
+}
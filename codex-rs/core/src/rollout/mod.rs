@@ -8,8 +8,11 @@ pub const INTERACTIVE_SESSION_SOURCES: &[SessionSource] =
     &[SessionSource::Cli, SessionSource::VSCode];
 
 pub mod list;
+pub mod lock;
 pub(crate) mod policy;
 pub mod recorder;
+pub mod replay;
+pub mod sessions;
 
 pub use codex_protocol::protocol::SessionMeta;
 pub use list::find_conversation_path_by_id_str;
@@ -1,4 +1,5 @@
 use crate::protocol::EventMsg;
+use crate::protocol::ExecCommandSource;
 use crate::protocol::RolloutItem;
 use codex_protocol::models::ResponseItem;
 
@@ -47,9 +48,16 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::ExitedReviewMode(_)
         | EventMsg::UndoCompleted(_)
         | EventMsg::SubagentTaskUpdate(_)
+        | EventMsg::ConversationMerged(_)
         | EventMsg::TurnAborted(_) => true,
+        // `!` commands the user ran, so resumed transcripts can show them.
+        EventMsg::ExecCommandEnd(ev) => ev.source == ExecCommandSource::UserShell,
         EventMsg::Error(_)
         | EventMsg::Warning(_)
+        | EventMsg::ConfigChanged(_)
+        | EventMsg::SessionLockLost(_)
+        | EventMsg::StallDetected(_)
+        | EventMsg::TurnRetryScheduled(_)
         | EventMsg::TaskStarted(_)
         | EventMsg::TaskComplete(_)
         | EventMsg::AgentMessageDelta(_)
@@ -62,11 +70,13 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::McpToolCallEnd(_)
         | EventMsg::SubagentTaskLog(_)
         | EventMsg::SubagentHistoryItem(_)
+        | EventMsg::SubagentReport(_)
+        | EventMsg::SubagentScratchpad(_)
         | EventMsg::WebSearchBegin(_)
         | EventMsg::WebSearchEnd(_)
         | EventMsg::ExecCommandBegin(_)
         | EventMsg::ExecCommandOutputDelta(_)
-        | EventMsg::ExecCommandEnd(_)
+        | EventMsg::ToolCallCancelled(_)
         | EventMsg::ExecApprovalRequest(_)
         | EventMsg::ElicitationRequest(_)
         | EventMsg::ApplyPatchApprovalRequest(_)
@@ -78,12 +88,19 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::GetHistoryEntryResponse(_)
         | EventMsg::UndoStarted(_)
         | EventMsg::McpListToolsResponse(_)
+        | EventMsg::McpUnresolvedSecretsResponse(_)
         | EventMsg::McpStartupUpdate(_)
         | EventMsg::McpStartupComplete(_)
+        | EventMsg::McpServerDown(_)
+        | EventMsg::McpServerRestarted(_)
+        | EventMsg::SubsystemStartup(_)
+        | EventMsg::ProjectDocsLoaded(_)
         | EventMsg::ListCustomPromptsResponse(_)
         | EventMsg::PlanUpdate(_)
+        | EventMsg::PlanReady(_)
         | EventMsg::ShutdownComplete
         | EventMsg::ViewImageToolCall(_)
+        | EventMsg::FileRead(_)
         | EventMsg::DeprecationNotice(_)
         | EventMsg::ItemStarted(_)
         | EventMsg::ItemCompleted(_)
@@ -93,8 +110,24 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::CheckpointCreated(_)
         | EventMsg::CheckpointRestored(_)
         | EventMsg::CheckpointList(_)
+        | EventMsg::CheckpointDiff(_)
         | EventMsg::CheckpointError(_)
         | EventMsg::MemoryRememberResponse(_)
-        | EventMsg::MemoryListResponse(_) => false,
+        | EventMsg::MemoryListResponse(_)
+        | EventMsg::MemoriesConsolidated(_)
+        | EventMsg::MemoryContextInjected(_)
+        | EventMsg::SessionSummary(_)
+        | EventMsg::ResponseRegenerated(_)
+        | EventMsg::UserMessageEdited(_)
+        | EventMsg::MessageVariants(_)
+        | EventMsg::SessionEnvUpdated(_)
+        | EventMsg::StorageReport(_)
+        | EventMsg::StorageCleanupProgress(_)
+        | EventMsg::StorageCleanupCompleted(_)
+        | EventMsg::FollowUpSuggestions(_)
+        | EventMsg::ConversationForked(_)
+        | EventMsg::ConversationMergeProgress(_)
+        | EventMsg::CostUpdate(_)
+        | EventMsg::CostLimitApprovalRequest(_) => false,
     }
 }
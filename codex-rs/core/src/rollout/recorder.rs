@@ -15,6 +15,7 @@ use tokio::io::AsyncWriteExt;
 use tokio::sync::mpsc::Sender;
 use tokio::sync::mpsc::{self};
 use tokio::sync::oneshot;
+use tokio::sync::watch;
 use tracing::info;
 use tracing::warn;
 
@@ -22,10 +23,15 @@ use super::SESSIONS_SUBDIR;
 use super::list::ConversationsPage;
 use super::list::Cursor;
 use super::list::get_conversations;
+use super::lock::SessionLock;
 use super::policy::is_persisted_response_item;
+use super::sessions::is_compressed_rollout;
+use super::sessions::read_rollout_to_string;
+use super::sessions::restore_rollout;
 use crate::config::Config;
 use crate::default_client::originator;
 use crate::git_info::collect_git_info;
+use crate::redaction::Redactor;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::ResumedHistory;
 use codex_protocol::protocol::RolloutItem;
@@ -47,6 +53,8 @@ use codex_protocol::protocol::SessionSource;
 pub struct RolloutRecorder {
     tx: Sender<RolloutCmd>,
     pub(crate) rollout_path: PathBuf,
+    /// False while another process has taken the rollout over.
+    lock_held: watch::Receiver<bool>,
 }
 
 #[derive(Clone)]
@@ -70,6 +78,10 @@ enum RolloutCmd {
     Shutdown {
         ack: oneshot::Sender<()>,
     },
+    /// Take the lock back after a takeover and resume writing.
+    ReclaimLock {
+        ack: oneshot::Sender<std::io::Result<()>>,
+    },
 }
 
 impl RolloutRecorderParams {
@@ -151,30 +163,56 @@ impl RolloutRecorder {
                     }),
                 )
             }
-            RolloutRecorderParams::Resume { path } => (
-                tokio::fs::OpenOptions::new()
-                    .append(true)
-                    .open(&path)
-                    .await?,
-                path,
-                None,
-            ),
+            RolloutRecorderParams::Resume { path } => {
+                // Archived rollouts are compressed; move them back to append.
+                let path = if is_compressed_rollout(&path) {
+                    let codex_home = config.codex_home.clone();
+                    tokio::task::spawn_blocking(move || restore_rollout(&codex_home, &path))
+                        .await
+                        .map_err(IoError::other)??
+                } else {
+                    path
+                };
+                (
+                    tokio::fs::OpenOptions::new()
+                        .append(true)
+                        .open(&path)
+                        .await?,
+                    path,
+                    None,
+                )
+            }
         };
+        let lock = SessionLock::acquire(&rollout_path)?;
 
         // Clone the cwd for the spawned task to collect git info asynchronously
         let cwd = config.cwd.clone();
+        let redactor = config.redactor.clone();
 
         // A reasonably-sized bounded channel. If the buffer fills up the send
         // future will yield, which is fine – we only need to ensure we do not
         // perform *blocking* I/O on the caller's thread.
         let (tx, rx) = mpsc::channel::<RolloutCmd>(256);
+        let (lock_held_tx, lock_held) = watch::channel(true);
 
         // Spawn a Tokio task that owns the file handle and performs async
         // writes. Using `tokio::fs::File` keeps everything on the async I/O
         // driver instead of blocking the runtime.
-        tokio::task::spawn(rollout_writer(file, rx, meta, cwd));
-
-        Ok(Self { tx, rollout_path })
+        tokio::task::spawn(rollout_writer(
+            file,
+            rx,
+            meta,
+            cwd,
+            redactor,
+            lock,
+            lock_held_tx,
+        ));
+
+        Ok(Self {
+            tx,
+            rollout_path,
+            lock_held,
+        })
     }
 
     pub(crate) async fn record_items(&self, items: &[RolloutItem]) -> std::io::Result<()> {
@@ -207,9 +245,26 @@ impl RolloutRecorder {
             .map_err(|e| IoError::other(format!("failed waiting for rollout flush: {e}")))
     }
 
+    /// Changes to false when the writer notices another process took the
+    /// rollout over, and back to true after [`Self::reclaim_lock`].
+    pub(crate) fn lock_held(&self) -> watch::Receiver<bool> {
+        self.lock_held.clone()
+    }
+
+    /// Take the rollout back from the process that took it over.
+    pub(crate) async fn reclaim_lock(&self) -> std::io::Result<()> {
+        let (tx, rx) = oneshot::channel();
+        self.tx
+            .send(RolloutCmd::ReclaimLock { ack: tx })
+            .await
+            .map_err(|e| IoError::other(format!("failed to queue rollout lock reclaim: {e}")))?;
+        rx.await
+            .map_err(|e| IoError::other(format!("failed waiting for rollout lock reclaim: {e}")))?
+    }
+
     pub async fn get_rollout_history(path: &Path) -> std::io::Result<InitialHistory> {
         info!("Resuming rollout from {path:?}");
-        let text = tokio::fs::read_to_string(path).await?;
+        let text = read_rollout_to_string(path).await?;
         if text.trim().is_empty() {
             return Err(IoError::other("empty session file"));
         }
@@ -351,8 +406,12 @@ async fn rollout_writer(
     mut rx: mpsc::Receiver<RolloutCmd>,
     mut meta: Option<SessionMeta>,
     cwd: std::path::PathBuf,
+    redactor: Redactor,
+    lock: SessionLock,
+    lock_held: watch::Sender<bool>,
 ) -> std::io::Result<()> {
-    let mut writer = JsonlWriter { file };
+    let mut writer = JsonlWriter { file, redactor };
+    let mut taken_over = false;
 
     // If we have a meta, collect git info asynchronously and write meta first
     if let Some(session_meta) = meta.take() {
@@ -372,6 +431,16 @@ async fn rollout_writer(
     while let Some(cmd) = rx.recv().await {
         match cmd {
             RolloutCmd::AddItems(items) => {
+                // Another process resumed this rollout with a forced takeover;
+                // stop writing so the two sessions do not interleave.
+                if taken_over || !lock.is_held().await {
+                    if !taken_over {
+                        warn!("rollout was taken over by another process; no longer recording");
+                        taken_over = true;
+                        lock_held.send_replace(false);
+                    }
+                    continue;
+                }
                 for item in items {
                     if is_persisted_response_item(&item) {
                         writer.write_rollout_item(item).await?;
@@ -389,6 +458,14 @@ async fn rollout_writer(
             RolloutCmd::Shutdown { ack } => {
                 let _ = ack.send(());
             }
+            RolloutCmd::ReclaimLock { ack } => {
+                let result = lock.reclaim().await;
+                if result.is_ok() {
+                    taken_over = false;
+                    lock_held.send_replace(true);
+                }
+                let _ = ack.send(result);
+            }
         }
     }
 
@@ -397,10 +474,12 @@ async fn rollout_writer(
 
 struct JsonlWriter {
     file: tokio::fs::File,
+    /// Masks secrets in message and tool output text before it reaches disk.
+    redactor: Redactor,
 }
 
 impl JsonlWriter {
-    async fn write_rollout_item(&mut self, rollout_item: RolloutItem) -> std::io::Result<()> {
+    async fn write_rollout_item(&mut self, mut rollout_item: RolloutItem) -> std::io::Result<()> {
         let timestamp_format: &[FormatItem] = format_description!(
             "[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond digits:3]Z"
         );
@@ -408,6 +487,7 @@ impl JsonlWriter {
             .format(timestamp_format)
             .map_err(|e| IoError::other(format!("failed to format timestamp: {e}")))?;
 
+        self.redactor.redact_rollout_item(&mut rollout_item);
         let line = RolloutLine {
             timestamp,
             item: rollout_item,
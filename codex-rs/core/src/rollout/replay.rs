@@ -0,0 +1,226 @@
+//! Deterministic playback of rollout files.
+//!
+//! A replay re-emits the events recorded in a rollout, in order and with the
+//! original pacing, without talking to a model. Frontends consume them as if
+//! they came from a live session, which makes UI regressions and reported
+//! bugs reproducible from the user's rollout alone.
+//!
+//! Only events the recorder persists are available (see
+//! `rollout::policy`), so streaming deltas and tool-call begin events are
+//! not part of a replay.
+
+use std::io;
+use std::io::Error as IoError;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_protocol::protocol::SessionConfiguredEvent;
+use time::OffsetDateTime;
+use time::format_description::well_known::Rfc3339;
+use tracing::warn;
+
+/// Longest pause kept between two replayed events; idle stretches in the
+/// original session (lunch breaks, long approvals) are shortened to this.
+const MAX_REPLAY_GAP: Duration = Duration::from_secs(5);
+
+/// One recorded event and how long to wait before emitting it.
+#[derive(Debug, Clone)]
+pub struct ReplayStep {
+    pub delay: Duration,
+    pub event: Event,
+}
+
+/// A rollout file loaded for playback.
+#[derive(Debug, Clone)]
+pub struct RolloutReplay {
+    /// Synthesized from the rollout's session meta and first turn context so
+    /// frontends can render the session header.
+    pub session_configured: SessionConfiguredEvent,
+    pub steps: Vec<ReplayStep>,
+}
+
+impl RolloutReplay {
+    pub async fn load(path: &Path) -> io::Result<Self> {
+        let text = tokio::fs::read_to_string(path).await?;
+        Self::parse(&text, path.to_path_buf())
+    }
+
+    /// Parses rollout JSONL. Lines that do not parse are skipped, matching
+    /// how resume treats damaged rollouts.
+    pub fn parse(text: &str, rollout_path: PathBuf) -> io::Result<Self> {
+        let mut meta = None;
+        let mut turn_context = None;
+        let mut steps = Vec::new();
+        let mut previous_at: Option<OffsetDateTime> = None;
+        let mut turn = 0usize;
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let rollout_line = match serde_json::from_str::<RolloutLine>(line) {
+                Ok(rollout_line) => rollout_line,
+                Err(e) => {
+                    warn!("skipping unparseable rollout line during replay: {e}");
+                    continue;
+                }
+            };
+            let at = OffsetDateTime::parse(&rollout_line.timestamp, &Rfc3339).ok();
+            match rollout_line.item {
+                RolloutItem::SessionMeta(line) => {
+                    meta.get_or_insert(line.meta);
+                }
+                RolloutItem::TurnContext(item) => {
+                    turn_context.get_or_insert(item);
+                }
+                RolloutItem::EventMsg(msg) => {
+                    if matches!(msg, EventMsg::UserMessage(_)) {
+                        turn += 1;
+                    }
+                    let delay = match (previous_at, at) {
+                        (Some(previous), Some(at)) => (at - previous)
+                            .try_into()
+                            .unwrap_or(Duration::ZERO)
+                            .min(MAX_REPLAY_GAP),
+                        _ => Duration::ZERO,
+                    };
+                    steps.push(ReplayStep {
+                        delay,
+                        event: Event {
+                            id: format!("replay-{turn}"),
+                            msg,
+                        },
+                    });
+                }
+                RolloutItem::ResponseItem(_) | RolloutItem::Compacted(_) => {}
+            }
+            if at.is_some() {
+                previous_at = at;
+            }
+        }
+
+        let meta =
+            meta.ok_or_else(|| IoError::other("rollout file has no session meta to replay"))?;
+        let session_configured = SessionConfiguredEvent {
+            session_id: meta.id,
+            model: turn_context
+                .as_ref()
+                .map(|ctx| ctx.model.clone())
+                .unwrap_or_default(),
+            model_provider_id: meta.model_provider.unwrap_or_default(),
+            approval_policy: turn_context
+                .as_ref()
+                .map(|ctx| ctx.approval_policy)
+                .unwrap_or_default(),
+            sandbox_policy: turn_context
+                .as_ref()
+                .map(|ctx| ctx.sandbox_policy.clone())
+                .unwrap_or_else(SandboxPolicy::new_read_only_policy),
+            cwd: turn_context
+                .as_ref()
+                .map(|ctx| ctx.cwd.clone())
+                .unwrap_or(meta.cwd),
+            reasoning_effort: turn_context.and_then(|ctx| ctx.effort),
+            history_log_id: 0,
+            history_entry_count: 0,
+            initial_messages: None,
+            rollout_path,
+        };
+        Ok(Self {
+            session_configured,
+            steps,
+        })
+    }
+
+    /// Emits every recorded event in order. `speed` scales the recorded
+    /// pacing (2.0 plays twice as fast); zero or less emits without waiting.
+    /// Returns early once `emit` reports the consumer is gone.
+    pub async fn play(self, speed: f64, mut emit: impl FnMut(Event) -> bool) {
+        for step in self.steps {
+            if speed > 0.0 && !step.delay.is_zero() {
+                tokio::time::sleep(step.delay.div_f64(speed)).await;
+            }
+            if !emit(step.event) {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::ConversationId;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::SessionMeta;
+    use codex_protocol::protocol::SessionMetaLine;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    fn line(timestamp: &str, item: RolloutItem) -> String {
+        serde_json::to_string(&RolloutLine {
+            timestamp: timestamp.to_string(),
+            item,
+        })
+        .expect("serialize rollout line")
+    }
+
+    #[tokio::test]
+    async fn replays_events_with_recorded_pacing() {
+        let id = ConversationId::new();
+        let text = [
+            line(
+                "2025-01-01T00:00:00.000Z",
+                RolloutItem::SessionMeta(SessionMetaLine {
+                    meta: SessionMeta {
+                        id,
+                        model_provider: Some("openai".to_string()),
+                        ..Default::default()
+                    },
+                    git: None,
+                }),
+            ),
+            line(
+                "2025-01-01T00:00:01.000Z",
+                RolloutItem::EventMsg(EventMsg::UserMessage(UserMessageEvent {
+                    message: "hi".to_string(),
+                    images: None,
+                })),
+            ),
+            "not json".to_string(),
+            line(
+                "2025-01-01T00:10:00.000Z",
+                RolloutItem::EventMsg(EventMsg::AgentMessage(AgentMessageEvent {
+                    message: "hello".to_string(),
+                })),
+            ),
+        ]
+        .join("\n");
+
+        let replay =
+            RolloutReplay::parse(&text, PathBuf::from("rollout.jsonl")).expect("parse replay");
+        assert_eq!(replay.session_configured.session_id, id);
+        assert_eq!(replay.session_configured.model_provider_id, "openai");
+        let delays: Vec<Duration> = replay.steps.iter().map(|step| step.delay).collect();
+        assert_eq!(delays, vec![Duration::from_secs(1), MAX_REPLAY_GAP]);
+
+        let mut ids = Vec::new();
+        replay
+            .play(0.0, |event| {
+                ids.push(event.id);
+                true
+            })
+            .await;
+        assert_eq!(ids, vec!["replay-1".to_string(), "replay-1".to_string()]);
+    }
+
+    #[test]
+    fn rejects_rollouts_without_session_meta() {
+        let err = RolloutReplay::parse("", PathBuf::from("rollout.jsonl"))
+            .expect_err("empty rollout has no session meta");
+        assert!(err.to_string().contains("session meta"));
+    }
+}
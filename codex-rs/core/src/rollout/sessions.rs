@@ -0,0 +1,413 @@
+//! Storage management for rollout files: listing, archiving, deletion and
+//! the `[session_retention]` policy.
+//!
+//! Active rollouts live under `~/.codex/sessions/YYYY/MM/DD/`. Archiving
+//! compresses a rollout into `~/.codex/archived_sessions/<name>.jsonl.gz` and
+//! removes the original, so it no longer shows up in resume listings. Resuming
+//! an archived rollout restores it into `sessions/` first.
+
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use codex_protocol::ConversationId;
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use super::ARCHIVED_SESSIONS_SUBDIR;
+use super::SESSIONS_SUBDIR;
+use super::list::parse_timestamp_uuid_from_filename;
+use super::lock::is_session_locked;
+use crate::config::types::SessionRetention;
+
+const ROLLOUT_PREFIX: &str = "rollout-";
+pub(crate) const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A rollout file on disk.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RolloutFile {
+    pub path: PathBuf,
+    pub size_bytes: i64,
+    pub modified: Option<SystemTime>,
+    /// Whether the file lives under `archived_sessions/`.
+    pub archived: bool,
+}
+
+/// Disk usage of rollout files, split into active and archived sessions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StorageUsage {
+    pub active_files: i64,
+    pub active_bytes: i64,
+    pub archived_files: i64,
+    pub archived_bytes: i64,
+}
+
+impl StorageUsage {
+    pub fn from_files(files: &[RolloutFile]) -> Self {
+        let mut usage = Self::default();
+        for file in files {
+            if file.archived {
+                usage.archived_files += 1;
+                usage.archived_bytes += file.size_bytes;
+            } else {
+                usage.active_files += 1;
+                usage.active_bytes += file.size_bytes;
+            }
+        }
+        usage
+    }
+
+    pub fn total_bytes(&self) -> i64 {
+        self.active_bytes + self.archived_bytes
+    }
+}
+
+/// Files touched by [`apply_retention`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetentionReport {
+    /// `(original, archive)` pairs for rollouts that were compressed.
+    pub archived: Vec<(PathBuf, PathBuf)>,
+    pub deleted: Vec<PathBuf>,
+}
+
+/// List every active and archived rollout under `codex_home`, most recently
+/// modified first.
+pub fn list_rollouts(codex_home: &Path) -> io::Result<Vec<RolloutFile>> {
+    let mut files = Vec::new();
+    collect_rollouts(&codex_home.join(SESSIONS_SUBDIR), false, &mut files)?;
+    collect_rollouts(&codex_home.join(ARCHIVED_SESSIONS_SUBDIR), true, &mut files)?;
+    files.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    Ok(files)
+}
+
+/// Delete a rollout file. Only files inside the sessions or archive
+/// directories of `codex_home` may be removed.
+pub fn delete_rollout(codex_home: &Path, path: &Path) -> io::Result<()> {
+    let path = managed_rollout_path(codex_home, path)?;
+    ensure_not_in_use(&path)?;
+    std::fs::remove_file(path)
+}
+
+/// Compress an active rollout into `archived_sessions/` and remove the
+/// original. Returns the path of the archive.
+pub fn archive_rollout(codex_home: &Path, path: &Path) -> io::Result<PathBuf> {
+    let path = managed_rollout_path(codex_home, path)?;
+    let sessions_root = dunce::canonicalize(codex_home.join(SESSIONS_SUBDIR))?;
+    if !path.starts_with(&sessions_root) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is already archived", path.display()),
+        ));
+    }
+    ensure_not_in_use(&path)?;
+    let Some(file_name) = path.file_name() else {
+        return Err(invalid_rollout_path(&path));
+    };
+
+    let archive_dir = codex_home.join(ARCHIVED_SESSIONS_SUBDIR);
+    std::fs::create_dir_all(&archive_dir)?;
+    let mut archive_name = file_name.to_os_string();
+    archive_name.push(".gz");
+    let archive_path = archive_dir.join(archive_name);
+
+    let mut reader = BufReader::new(File::open(&path)?);
+    let mut encoder = GzEncoder::new(
+        BufWriter::new(File::create(&archive_path)?),
+        Compression::default(),
+    );
+    io::copy(&mut reader, &mut encoder)?;
+    encoder.finish()?;
+    std::fs::remove_file(&path)?;
+    Ok(archive_path)
+}
+
+/// Decompress an archived `.jsonl.gz` rollout back into
+/// `sessions/YYYY/MM/DD/` so a resumed session can append to it, and remove
+/// the archive. Returns the restored path.
+pub fn restore_rollout(codex_home: &Path, path: &Path) -> io::Result<PathBuf> {
+    let path = managed_rollout_path(codex_home, path)?;
+    let Some(name) = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_suffix(".gz"))
+    else {
+        return Err(invalid_rollout_path(&path));
+    };
+    // `rollout-YYYY-MM-DDThh-mm-ss-<uuid>.jsonl`
+    let date: Vec<&str> = name
+        .strip_prefix(ROLLOUT_PREFIX)
+        .and_then(|rest| rest.get(..10))
+        .map(|date| date.split('-').collect())
+        .unwrap_or_default();
+    if date.len() != 3
+        || !date
+            .iter()
+            .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+    {
+        return Err(invalid_rollout_path(&path));
+    }
+
+    let mut dir = codex_home.join(SESSIONS_SUBDIR);
+    dir.extend(&date);
+    std::fs::create_dir_all(&dir)?;
+    let restored = dir.join(name);
+    let mut decoder = GzDecoder::new(BufReader::new(File::open(&path)?));
+    let mut writer = BufWriter::new(File::create(&restored)?);
+    io::copy(&mut decoder, &mut writer)?;
+    writer.flush()?;
+    std::fs::remove_file(&path)?;
+    Ok(restored)
+}
+
+/// Whether `path` names a gzip-compressed rollout.
+pub fn is_compressed_rollout(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == "gz")
+}
+
+/// The conversation a rollout file records, from its file name.
+pub fn rollout_conversation_id(path: &Path) -> Option<ConversationId> {
+    let (_, uuid) = parse_timestamp_uuid_from_filename(path.file_name()?.to_str()?)?;
+    ConversationId::from_string(&uuid.to_string()).ok()
+}
+
+/// Read a whole rollout, decompressing `.jsonl.gz` archives.
+pub async fn read_rollout_to_string(path: &Path) -> io::Result<String> {
+    if !is_compressed_rollout(path) {
+        return tokio::fs::read_to_string(path).await;
+    }
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut text = String::new();
+        GzDecoder::new(BufReader::new(File::open(path)?)).read_to_string(&mut text)?;
+        Ok(text)
+    })
+    .await
+    .map_err(io::Error::other)?
+}
+
+/// Archive and delete rollouts that have not been modified within the
+/// windows configured by `retention`.
+pub fn apply_retention(
+    codex_home: &Path,
+    retention: &SessionRetention,
+    now: SystemTime,
+) -> io::Result<RetentionReport> {
+    let mut report = RetentionReport::default();
+    if retention.archive_after_days.is_none() && retention.delete_after_days.is_none() {
+        return Ok(report);
+    }
+
+    let delete_cutoff = retention
+        .delete_after_days
+        .and_then(|days| cutoff(now, days));
+    let archive_cutoff = retention
+        .archive_after_days
+        .and_then(|days| cutoff(now, days));
+    for file in list_rollouts(codex_home)? {
+        let Some(modified) = file.modified else {
+            continue;
+        };
+        if is_session_locked(&file.path) {
+            continue;
+        }
+        if delete_cutoff.is_some_and(|cutoff| modified < cutoff) {
+            delete_rollout(codex_home, &file.path)?;
+            report.deleted.push(file.path);
+        } else if !file.archived && archive_cutoff.is_some_and(|cutoff| modified < cutoff) {
+            let archive = archive_rollout(codex_home, &file.path)?;
+            report.archived.push((file.path, archive));
+        }
+    }
+    Ok(report)
+}
+
+/// `days` before `now`; `None` when `days` is negative.
+pub(crate) fn cutoff(now: SystemTime, days: i64) -> Option<SystemTime> {
+    let days = u64::try_from(days).ok()?;
+    now.checked_sub(Duration::from_secs(days.saturating_mul(SECONDS_PER_DAY)))
+}
+
+fn collect_rollouts(dir: &Path, archived: bool, out: &mut Vec<RolloutFile>) -> io::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_rollouts(&path, archived, out)?;
+        } else if file_type.is_file() && is_rollout_file_name(&path) {
+            let metadata = entry.metadata()?;
+            out.push(RolloutFile {
+                path,
+                size_bytes: i64::try_from(metadata.len()).unwrap_or(i64::MAX),
+                modified: metadata.modified().ok(),
+                archived,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn is_rollout_file_name(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| {
+            name.starts_with(ROLLOUT_PREFIX)
+                && (name.ends_with(".jsonl") || name.ends_with(".jsonl.gz"))
+        })
+}
+
+/// Resolve `path` and make sure it names a rollout inside `codex_home`.
+fn managed_rollout_path(codex_home: &Path, path: &Path) -> io::Result<PathBuf> {
+    let resolved = dunce::canonicalize(path)?;
+    if !is_rollout_file_name(&resolved) {
+        return Err(invalid_rollout_path(path));
+    }
+    for subdir in [SESSIONS_SUBDIR, ARCHIVED_SESSIONS_SUBDIR] {
+        if let Ok(root) = dunce::canonicalize(codex_home.join(subdir))
+            && resolved.starts_with(&root)
+        {
+            return Ok(resolved);
+        }
+    }
+    Err(invalid_rollout_path(path))
+}
+
+/// Refuse to remove a rollout that a running session is appending to.
+fn ensure_not_in_use(path: &Path) -> io::Result<()> {
+    if is_session_locked(path) {
+        return Err(io::Error::new(
+            io::ErrorKind::ResourceBusy,
+            format!("{} is in use by a running session", path.display()),
+        ));
+    }
+    Ok(())
+}
+
+fn invalid_rollout_path(path: &Path) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!(
+            "{} is not a rollout file in the sessions directory",
+            path.display()
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    const ROLLOUT_NAME: &str =
+        "rollout-2025-01-01T12-00-00-00000000-0000-0000-0000-000000000001.jsonl";
+
+    fn write_rollout(home: &Path, contents: &str) -> PathBuf {
+        let dir = home
+            .join(SESSIONS_SUBDIR)
+            .join("2025")
+            .join("01")
+            .join("01");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(ROLLOUT_NAME);
+        std::fs::write(&path, contents).unwrap();
+        dunce::canonicalize(path).unwrap()
+    }
+
+    #[test]
+    fn archive_compresses_and_updates_usage() {
+        let home = TempDir::new().unwrap();
+        let path = write_rollout(home.path(), "{\"type\":\"session_meta\"}\n");
+
+        let files = list_rollouts(home.path()).unwrap();
+        assert_eq!(StorageUsage::from_files(&files).active_files, 1);
+
+        let archive = archive_rollout(home.path(), &path).unwrap();
+        assert!(!path.exists());
+        let mut decoded = String::new();
+        GzDecoder::new(File::open(&archive).unwrap())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "{\"type\":\"session_meta\"}\n");
+
+        let usage = StorageUsage::from_files(&list_rollouts(home.path()).unwrap());
+        assert_eq!((usage.active_files, usage.archived_files), (0, 1));
+        assert!(archive_rollout(home.path(), &archive).is_err());
+    }
+
+    #[tokio::test]
+    async fn archived_rollouts_can_be_read_and_restored() {
+        let home = TempDir::new().unwrap();
+        let path = write_rollout(home.path(), "{\"type\":\"session_meta\"}\n");
+        let archive = archive_rollout(home.path(), &path).unwrap();
+
+        assert!(is_compressed_rollout(&archive));
+        assert_eq!(
+            read_rollout_to_string(&archive).await.unwrap(),
+            "{\"type\":\"session_meta\"}\n"
+        );
+
+        let restored = restore_rollout(home.path(), &archive).unwrap();
+        assert_eq!(dunce::canonicalize(&restored).unwrap(), path);
+        assert!(!archive.exists());
+        assert_eq!(
+            std::fs::read_to_string(&restored).unwrap(),
+            "{\"type\":\"session_meta\"}\n"
+        );
+    }
+
+    #[test]
+    fn delete_rejects_paths_outside_sessions() {
+        let home = TempDir::new().unwrap();
+        let outside = home.path().join(ROLLOUT_NAME);
+        std::fs::write(&outside, "{}\n").unwrap();
+
+        assert!(delete_rollout(home.path(), &outside).is_err());
+        assert!(outside.exists());
+
+        let path = write_rollout(home.path(), "{}\n");
+        delete_rollout(home.path(), &path).unwrap();
+        assert!(list_rollouts(home.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn retention_archives_then_deletes_old_rollouts() {
+        let home = TempDir::new().unwrap();
+        let path = write_rollout(home.path(), "{}\n");
+        let day = Duration::from_secs(SECONDS_PER_DAY);
+        let retention = SessionRetention {
+            archive_after_days: Some(7),
+            delete_after_days: Some(30),
+        };
+
+        let report = apply_retention(home.path(), &retention, SystemTime::now()).unwrap();
+        assert_eq!(report, RetentionReport::default());
+
+        let report =
+            apply_retention(home.path(), &retention, SystemTime::now() + day * 10).unwrap();
+        assert_eq!(report.archived.len(), 1);
+        assert_eq!(report.archived[0].0, path);
+
+        let report =
+            apply_retention(home.path(), &retention, SystemTime::now() + day * 40).unwrap();
+        assert_eq!(report.deleted.len(), 1);
+        assert!(list_rollouts(home.path()).unwrap().is_empty());
+    }
+}
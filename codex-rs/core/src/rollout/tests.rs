@@ -13,11 +13,13 @@ use time::macros::format_description;
 use uuid::Uuid;
 
 use crate::rollout::INTERACTIVE_SESSION_SOURCES;
+use crate::rollout::find_conversation_path_by_id_str;
 use crate::rollout::list::ConversationItem;
 use crate::rollout::list::ConversationsPage;
 use crate::rollout::list::Cursor;
 use crate::rollout::list::get_conversation;
 use crate::rollout::list::get_conversations;
+use crate::rollout::sessions::archive_rollout;
 use anyhow::Result;
 use codex_protocol::ConversationId;
 use codex_protocol::models::ContentItem;
@@ -29,6 +31,9 @@ use codex_protocol::protocol::RolloutLine;
 use codex_protocol::protocol::SessionMeta;
 use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::TokenCountEvent;
+use codex_protocol::protocol::TokenUsage;
+use codex_protocol::protocol::TokenUsageInfo;
 use codex_protocol::protocol::UserMessageEvent;
 
 const NO_SOURCE_FILTER: &[SessionSource] = &[];
@@ -234,6 +239,7 @@ async fn test_list_conversations_latest_first() {
                 tail: Vec::new(),
                 created_at: Some("2025-01-03T12-00-00".into()),
                 updated_at: Some("2025-01-03T12-00-00".into()),
+                total_tokens: None,
             },
             ConversationItem {
                 path: p2,
@@ -241,6 +247,7 @@ async fn test_list_conversations_latest_first() {
                 tail: Vec::new(),
                 created_at: Some("2025-01-02T12-00-00".into()),
                 updated_at: Some("2025-01-02T12-00-00".into()),
+                total_tokens: None,
             },
             ConversationItem {
                 path: p3,
@@ -248,6 +255,7 @@ async fn test_list_conversations_latest_first() {
                 tail: Vec::new(),
                 created_at: Some("2025-01-01T12-00-00".into()),
                 updated_at: Some("2025-01-01T12-00-00".into()),
+                total_tokens: None,
             },
         ],
         next_cursor: None,
@@ -365,6 +373,7 @@ async fn test_pagination_cursor() {
                 tail: Vec::new(),
                 created_at: Some("2025-03-05T09-00-00".into()),
                 updated_at: Some("2025-03-05T09-00-00".into()),
+                total_tokens: None,
             },
             ConversationItem {
                 path: p4,
@@ -372,6 +381,7 @@ async fn test_pagination_cursor() {
                 tail: Vec::new(),
                 created_at: Some("2025-03-04T09-00-00".into()),
                 updated_at: Some("2025-03-04T09-00-00".into()),
+                total_tokens: None,
             },
         ],
         next_cursor: Some(expected_cursor1.clone()),
@@ -432,6 +442,7 @@ async fn test_pagination_cursor() {
                 tail: Vec::new(),
                 created_at: Some("2025-03-03T09-00-00".into()),
                 updated_at: Some("2025-03-03T09-00-00".into()),
+                total_tokens: None,
             },
             ConversationItem {
                 path: p2,
@@ -439,6 +450,7 @@ async fn test_pagination_cursor() {
                 tail: Vec::new(),
                 created_at: Some("2025-03-02T09-00-00".into()),
                 updated_at: Some("2025-03-02T09-00-00".into()),
+                total_tokens: None,
             },
         ],
         next_cursor: Some(expected_cursor2.clone()),
@@ -480,6 +492,7 @@ async fn test_pagination_cursor() {
             tail: Vec::new(),
             created_at: Some("2025-03-01T09-00-00".into()),
             updated_at: Some("2025-03-01T09-00-00".into()),
+            total_tokens: None,
         }],
         next_cursor: None,
         num_scanned_files: 5, // scanned 05, 04 (anchor), 03, 02 (anchor), 01
@@ -536,6 +549,7 @@ async fn test_get_conversation_contents() {
             tail: Vec::new(),
             created_at: Some(ts.into()),
             updated_at: Some(ts.into()),
+            total_tokens: None,
         }],
         next_cursor: None,
         num_scanned_files: 1,
@@ -569,6 +583,46 @@ async fn test_get_conversation_contents() {
     assert_eq!(content, expected_content);
 }
 
+#[tokio::test]
+async fn test_compressed_rollouts_are_listed_and_found() -> Result<()> {
+    let temp = TempDir::new().unwrap();
+    let home = temp.path();
+    let compressed = Uuid::new_v4();
+    let archived = Uuid::new_v4();
+    write_session_file(home, "2025-05-02T10-00-00", compressed, 1, None)?;
+    write_session_file(home, "2025-05-01T10-00-00", archived, 1, None)?;
+
+    // A rollout compressed in place is still listed.
+    let path = find_conversation_path_by_id_str(home, &compressed.to_string())
+        .await?
+        .expect("rollout should be found");
+    let mut gz_path = path.clone().into_os_string();
+    gz_path.push(".gz");
+    let mut encoder =
+        flate2::write::GzEncoder::new(File::create(&gz_path)?, flate2::Compression::default());
+    encoder.write_all(&fs::read(&path)?)?;
+    encoder.finish()?;
+    fs::remove_file(&path)?;
+
+    let page = get_conversations(home, 10, None, NO_SOURCE_FILTER, None, TEST_PROVIDER).await?;
+    let paths: Vec<_> = page.items.iter().map(|item| item.path.clone()).collect();
+    assert_eq!(paths.len(), 2);
+    assert_eq!(paths[0].as_os_str(), gz_path.as_os_str());
+    assert_eq!(page.items[0].head.len(), 1);
+    assert_eq!(page.items[0].tail.len(), 1);
+
+    // An archived rollout is no longer listed but can still be found.
+    let archive = archive_rollout(home, &paths[1])?;
+    let page = get_conversations(home, 10, None, NO_SOURCE_FILTER, None, TEST_PROVIDER).await?;
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(
+        find_conversation_path_by_id_str(home, &archived.to_string()).await?,
+        Some(archive)
+    );
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_tail_includes_last_response_items() -> Result<()> {
     let temp = TempDir::new().unwrap();
@@ -819,6 +873,22 @@ async fn test_tail_skips_trailing_non_responses() -> Result<()> {
     };
     writeln!(file, "{}", serde_json::to_string(&compacted_line)?)?;
 
+    let token_count_line = RolloutLine {
+        timestamp: format!("{ts}-tokens"),
+        item: RolloutItem::EventMsg(EventMsg::TokenCount(TokenCountEvent {
+            info: Some(TokenUsageInfo {
+                total_token_usage: TokenUsage {
+                    total_tokens: 1234,
+                    ..Default::default()
+                },
+                last_token_usage: TokenUsage::default(),
+                model_context_window: None,
+            }),
+            rate_limits: None,
+        })),
+    };
+    writeln!(file, "{}", serde_json::to_string(&token_count_line)?)?;
+
     let shutdown_event = RolloutLine {
         timestamp: format!("{ts}-shutdown"),
         item: RolloutItem::EventMsg(EventMsg::ShutdownComplete),
@@ -859,6 +929,7 @@ async fn test_tail_skips_trailing_non_responses() -> Result<()> {
         page.items[0].updated_at.as_deref(),
         Some(expected_updated.as_str())
     );
+    assert_eq!(page.items[0].total_tokens, Some(1234));
 
     Ok(())
 }
@@ -922,6 +993,7 @@ async fn test_stable_ordering_same_second_pagination() {
                 tail: Vec::new(),
                 created_at: Some(ts.to_string()),
                 updated_at: Some(ts.to_string()),
+                total_tokens: None,
             },
             ConversationItem {
                 path: p2,
@@ -929,6 +1001,7 @@ async fn test_stable_ordering_same_second_pagination() {
                 tail: Vec::new(),
                 created_at: Some(ts.to_string()),
                 updated_at: Some(ts.to_string()),
+                total_tokens: None,
             },
         ],
         next_cursor: Some(expected_cursor1.clone()),
@@ -960,6 +1033,7 @@ async fn test_stable_ordering_same_second_pagination() {
             tail: Vec::new(),
             created_at: Some(ts.to_string()),
             updated_at: Some(ts.to_string()),
+            total_tokens: None,
         }],
         next_cursor: None,
         num_scanned_files: 3, // scanned u3, u2 (anchor), u1
@@ -214,6 +214,9 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec![],
         };
 
         assert!(is_write_patch_constrained_to_writable_paths(
@@ -235,6 +238,9 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec![],
         };
         assert!(is_write_patch_constrained_to_writable_paths(
             &add_outside,
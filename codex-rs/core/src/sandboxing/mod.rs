@@ -7,6 +7,7 @@ ready‑to‑spawn environment.
 */
 
 pub mod assessment;
+pub mod network_proxy;
 
 use crate::exec::ExecExpiration;
 use crate::exec::ExecToolCallOutput;
@@ -15,6 +16,7 @@ use crate::exec::StdoutStream;
 use crate::exec::execute_exec_env;
 use crate::landlock::create_linux_sandbox_command_args;
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimits;
 #[cfg(target_os = "macos")]
 use crate::seatbelt::MACOS_PATH_TO_SEATBELT_EXECUTABLE;
 #[cfg(target_os = "macos")]
@@ -70,6 +72,7 @@ pub struct ExecEnv {
     pub with_escalated_permissions: Option<bool>,
     pub justification: Option<String>,
     pub arg0: Option<String>,
+    pub resource_limits: ResourceLimits,
 }
 
 pub enum SandboxPreference {
@@ -184,6 +187,7 @@ impl SandboxManager {
             with_escalated_permissions: spec.with_escalated_permissions,
             justification: spec.justification,
             arg0: arg0_override,
+            resource_limits: ResourceLimits::default(),
         })
     }
 
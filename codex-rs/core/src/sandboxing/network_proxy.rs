@@ -0,0 +1,340 @@
+//! Local HTTP proxy behind `network_allowlist`.
+//!
+//! A sandboxed command whose policy lists allowed hosts gets its own proxy on
+//! `127.0.0.1`, advertised through the usual `*_PROXY` variables. The platform
+//! sandbox lets the command reach localhost only, so every outbound
+//! connection goes through the proxy, which tunnels `CONNECT` requests and
+//! forwards plain HTTP requests to allowlisted hosts and refuses the rest.
+//! Refused destinations are collected so an approval request can name them.
+//!
+//! Seatbelt cannot restrict the localhost rule to the proxy's ephemeral port,
+//! so a sandboxed command can also reach any other service listening on the
+//! loopback interface. Such a service that forwards traffic is a way around
+//! the allowlist.
+
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::task::JoinHandle;
+use tracing::debug;
+
+use crate::exec::SandboxType;
+use crate::protocol::SandboxPolicy;
+
+/// Upper bound on the request line and headers read before deciding.
+const MAX_REQUEST_HEAD_BYTES: usize = 16 * 1024;
+
+const PROXY_ENV_VARS: [&str; 6] = [
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "ALL_PROXY",
+    "http_proxy",
+    "https_proxy",
+    "all_proxy",
+];
+const NO_PROXY_ENV_VARS: [&str; 2] = ["NO_PROXY", "no_proxy"];
+
+/// Whether `host:port` matches an allowlist entry. Entries are `host`
+/// (any port), `host:port`, or `*.domain` (any subdomain of `domain`, with
+/// an optional `:port`). Hosts compare case-insensitively.
+pub fn network_destination_allowed(allowlist: &[String], host: &str, port: u16) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    allowlist.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        let (pattern, entry_port) = split_host_port(&entry);
+        if entry_port.is_some_and(|entry_port| entry_port != port) {
+            return false;
+        }
+        match pattern.strip_prefix("*.") {
+            Some(domain) => host
+                .strip_suffix(domain)
+                .is_some_and(|prefix| prefix.ends_with('.') && prefix.len() > 1),
+            None => host == pattern,
+        }
+    })
+}
+
+/// Splits `host[:port]`, keeping the brackets off IPv6 literals.
+fn split_host_port(target: &str) -> (&str, Option<u16>) {
+    if let Some(rest) = target.strip_prefix('[') {
+        if let Some((host, after)) = rest.split_once(']') {
+            let port = after.strip_prefix(':').and_then(|port| port.parse().ok());
+            return (host, port);
+        }
+        return (target, None);
+    }
+    match target.rsplit_once(':') {
+        // A second colon means a bare IPv6 address without a port.
+        Some((host, port)) if !host.contains(':') => match port.parse() {
+            Ok(port) => (host, Some(port)),
+            Err(_) => (target, None),
+        },
+        _ => (target, None),
+    }
+}
+
+/// A running proxy for one command. Dropping it stops the proxy.
+#[derive(Debug)]
+pub(crate) struct NetworkProxy {
+    addr: SocketAddr,
+    blocked: Arc<Mutex<Vec<String>>>,
+    task: JoinHandle<()>,
+}
+
+impl NetworkProxy {
+    /// Starts a proxy when `sandbox` confines the command to localhost under
+    /// a policy with a network allowlist. Only Seatbelt can do that; the
+    /// Linux and Windows sandboxes keep network fully disabled.
+    pub(crate) async fn start_for(
+        policy: &SandboxPolicy,
+        sandbox: SandboxType,
+    ) -> io::Result<Option<Self>> {
+        match sandbox {
+            SandboxType::MacosSeatbelt if !policy.network_allowlist().is_empty() => {
+                Self::start(policy.network_allowlist().to_vec())
+                    .await
+                    .map(Some)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    async fn start(allowlist: Vec<String>) -> io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let blocked = Arc::new(Mutex::new(Vec::new()));
+        let allowlist = Arc::new(allowlist);
+        let task = {
+            let blocked = Arc::clone(&blocked);
+            tokio::spawn(async move {
+                loop {
+                    let Ok((stream, _)) = listener.accept().await else {
+                        break;
+                    };
+                    let allowlist = Arc::clone(&allowlist);
+                    let blocked = Arc::clone(&blocked);
+                    tokio::spawn(async move {
+                        if let Err(err) = handle_connection(stream, &allowlist, &blocked).await {
+                            debug!("sandbox network proxy connection failed: {err}");
+                        }
+                    });
+                }
+            })
+        };
+        Ok(Self {
+            addr,
+            blocked,
+            task,
+        })
+    }
+
+    /// Points the command's proxy variables at this proxy and drops any
+    /// `NO_PROXY` exemptions, which would only lead to refused connections.
+    pub(crate) fn apply_env(&self, env: &mut HashMap<String, String>) {
+        let url = format!("http://{}", self.addr);
+        for key in NO_PROXY_ENV_VARS {
+            env.remove(key);
+        }
+        for key in PROXY_ENV_VARS {
+            env.insert(key.to_string(), url.clone());
+        }
+    }
+
+    /// The destinations refused so far, sorted and without duplicates.
+    pub(crate) fn blocked_destinations(&self) -> Vec<String> {
+        let mut blocked = self
+            .blocked
+            .lock()
+            .map(|blocked| blocked.clone())
+            .unwrap_or_default();
+        blocked.sort();
+        blocked.dedup();
+        blocked
+    }
+
+    /// Stops the proxy and returns the destinations it refused.
+    pub(crate) fn finish(self) -> Vec<String> {
+        self.blocked_destinations()
+    }
+}
+
+impl Drop for NetworkProxy {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+async fn handle_connection(
+    mut client: TcpStream,
+    allowlist: &[String],
+    blocked: &Mutex<Vec<String>>,
+) -> io::Result<()> {
+    let head = read_request_head(&mut client).await?;
+    let Some((method, host, port)) = parse_request_target(&head) else {
+        client
+            .write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n")
+            .await?;
+        return Ok(());
+    };
+    let destination = if host.contains(':') {
+        format!("[{host}]:{port}")
+    } else {
+        format!("{host}:{port}")
+    };
+
+    if !network_destination_allowed(allowlist, &host, port) {
+        if let Ok(mut blocked) = blocked.lock() {
+            blocked.push(destination.clone());
+        }
+        let body = format!("{destination} is not in the sandbox network allowlist\n");
+        let response = format!(
+            "HTTP/1.1 403 Forbidden\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        client.write_all(response.as_bytes()).await?;
+        return Ok(());
+    }
+
+    let mut upstream = match TcpStream::connect((host.as_str(), port)).await {
+        Ok(upstream) => upstream,
+        Err(err) => {
+            client
+                .write_all(b"HTTP/1.1 502 Bad Gateway\r\nConnection: close\r\n\r\n")
+                .await?;
+            return Err(err);
+        }
+    };
+    if method.eq_ignore_ascii_case("CONNECT") {
+        client
+            .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+            .await?;
+        // Tunnel bytes the client sent without waiting for the reply.
+        if let Some(end) = head.windows(4).position(|window| window == b"\r\n\r\n") {
+            upstream.write_all(&head[end + 4..]).await?;
+        }
+    } else {
+        // Later requests on a kept-alive connection also reach this host,
+        // whatever their request line says.
+        upstream.write_all(&head).await?;
+    }
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await?;
+    Ok(())
+}
+
+/// Reads up to the end of the request headers. Bytes the client sent past
+/// them (a request body) are returned too and forwarded with the head.
+async fn read_request_head(client: &mut TcpStream) -> io::Result<Vec<u8>> {
+    let mut head = Vec::new();
+    let mut chunk = [0u8; 4096];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        if head.len() >= MAX_REQUEST_HEAD_BYTES {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "proxy request head too large",
+            ));
+        }
+        let read = client.read(&mut chunk).await?;
+        if read == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        head.extend_from_slice(&chunk[..read]);
+    }
+    Ok(head)
+}
+
+/// The method and destination of a `CONNECT host:port` or
+/// `GET http://host[:port]/...` request.
+fn parse_request_target(head: &[u8]) -> Option<(String, String, u16)> {
+    let line_end = head.windows(2).position(|window| window == b"\r\n")?;
+    let line = std::str::from_utf8(&head[..line_end]).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let target = parts.next()?;
+
+    let (authority, default_port) = if method.eq_ignore_ascii_case("CONNECT") {
+        (target, None)
+    } else {
+        let rest = target.strip_prefix("http://")?;
+        let authority = rest.split(['/', '?', '#']).next()?;
+        (authority, Some(80))
+    };
+    // Credentials in the URL are not part of the destination.
+    let authority = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let (host, port) = split_host_port(authority);
+    let port = port.or(default_port)?;
+    if host.is_empty() {
+        return None;
+    }
+    Some((method, host.to_ascii_lowercase(), port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn allowlist_matches_hosts_ports_and_subdomains() {
+        let allowlist = vec![
+            "crates.io".to_string(),
+            "*.github.com:443".to_string(),
+            "[::1]:8080".to_string(),
+        ];
+
+        let allowed = |host: &str, port| network_destination_allowed(&allowlist, host, port);
+
+        assert!(allowed("crates.io", 443));
+        assert!(allowed("Crates.IO.", 80));
+        assert!(allowed("api.github.com", 443));
+        assert!(!allowed("api.github.com", 22));
+        assert!(!allowed("github.com", 443));
+        assert!(!allowed("evilgithub.com", 443));
+        assert!(!allowed("static.crates.io", 443));
+        assert!(allowed("::1", 8080));
+    }
+
+    #[test]
+    fn parses_connect_and_absolute_form_targets() {
+        assert_eq!(
+            parse_request_target(b"CONNECT api.github.com:443 HTTP/1.1\r\n\r\n"),
+            Some(("CONNECT".to_string(), "api.github.com".to_string(), 443))
+        );
+        assert_eq!(
+            parse_request_target(b"GET http://user@Example.com/path?q=1 HTTP/1.1\r\n\r\n"),
+            Some(("GET".to_string(), "example.com".to_string(), 80))
+        );
+        assert_eq!(
+            parse_request_target(b"GET /relative HTTP/1.1\r\n\r\n"),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn refuses_and_records_unlisted_destinations() -> anyhow::Result<()> {
+        let proxy = NetworkProxy::start(vec!["localhost".to_string()]).await?;
+        let mut env = HashMap::from([("NO_PROXY".to_string(), "*".to_string())]);
+        proxy.apply_env(&mut env);
+        assert_eq!(env.get("NO_PROXY"), None);
+        assert_eq!(env["HTTPS_PROXY"], format!("http://{}", proxy.addr));
+
+        let mut client = TcpStream::connect(proxy.addr).await?;
+        client
+            .write_all(b"CONNECT example.com:443 HTTP/1.1\r\nHost: example.com:443\r\n\r\n")
+            .await?;
+        let mut response = String::new();
+        client.read_to_string(&mut response).await?;
+        assert!(response.starts_with("HTTP/1.1 403"), "{response}");
+
+        assert_eq!(proxy.finish(), vec!["example.com:443".to_string()]);
+        Ok(())
+    }
+}
@@ -7,12 +7,15 @@ use std::path::PathBuf;
 use tokio::process::Child;
 
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimits;
 use crate::spawn::CODEX_SANDBOX_ENV_VAR;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 
 const MACOS_SEATBELT_BASE_POLICY: &str = include_str!("seatbelt_base_policy.sbpl");
 const MACOS_SEATBELT_NETWORK_POLICY: &str = include_str!("seatbelt_network_policy.sbpl");
+const MACOS_SEATBELT_PROXY_NETWORK_POLICY: &str =
+    include_str!("seatbelt_proxy_network_policy.sbpl");
 
 /// When working with `sandbox-exec`, only consider `sandbox-exec` in `/usr/bin`
 /// to defend against an attacker trying to inject a malicious version on the
@@ -39,6 +42,7 @@ pub async fn spawn_command_under_seatbelt(
         sandbox_policy,
         stdio_policy,
         env,
+        ResourceLimits::default(),
     )
     .await
 }
@@ -107,6 +111,8 @@ pub(crate) fn create_seatbelt_command_args(
     // TODO(mbolin): apply_patch calls must also honor the SandboxPolicy.
     let network_policy = if sandbox_policy.has_full_network_access() {
         MACOS_SEATBELT_NETWORK_POLICY
+    } else if !sandbox_policy.network_allowlist().is_empty() {
+        MACOS_SEATBELT_PROXY_NETWORK_POLICY
     } else {
         ""
     };
@@ -156,6 +162,7 @@ fn macos_dir_params() -> Vec<(String, PathBuf)> {
 #[cfg(test)]
 mod tests {
     use super::MACOS_SEATBELT_BASE_POLICY;
+    use super::MACOS_SEATBELT_PROXY_NETWORK_POLICY;
     use super::create_seatbelt_command_args;
     use super::macos_dir_params;
     use crate::protocol::SandboxPolicy;
@@ -186,6 +193,9 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: true,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec![],
         };
 
         let args = create_seatbelt_command_args(
@@ -262,6 +272,9 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec![],
         };
 
         let args = create_seatbelt_command_args(
@@ -336,6 +349,26 @@ mod tests {
         assert_eq!(expected_args, args);
     }
 
+    #[test]
+    fn network_allowlist_only_opens_localhost() {
+        let tmp = TempDir::new().expect("tempdir");
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec!["crates.io".to_string()],
+        };
+
+        let args =
+            create_seatbelt_command_args(vec!["/usr/bin/true".to_string()], &policy, tmp.path());
+
+        assert!(args[1].ends_with(MACOS_SEATBELT_PROXY_NETWORK_POLICY));
+        assert!(!args[1].contains("(allow network-outbound)"));
+    }
+
     struct PopulatedTmp {
         root_with_git: PathBuf,
         root_without_git: PathBuf,
@@ -0,0 +1,466 @@
+//! Structured session summaries and session notes.
+//!
+//! The conversation so far is condensed into a transcript and sent to a small
+//! model in a side request that does not touch the session history. The
+//! resulting notes are written to `.kaioken/session-notes/` on `/summary save`
+//! and to `.kaioken/notes/` when a session ends with the `session_notes`
+//! feature enabled. New sessions in the same directory recall the most recent
+//! notes from both places as part of their initial context. Merging another
+//! session into the current one records that session's summary as
+//! `<merged_context>`.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Local;
+use codex_otel::otel_event_manager::OtelEventManager;
+use codex_protocol::ConversationId;
+use codex_protocol::config_types::ReasoningEffort;
+use codex_protocol::items::AgentMessageContent;
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::SessionSource;
+use codex_utils_string::take_bytes_at_char_boundary;
+use codex_utils_string::take_last_bytes_at_char_boundary;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::time::timeout;
+
+use crate::AuthManager;
+use crate::ModelProviderInfo;
+use crate::client::ModelClient;
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::config::Config;
+use crate::event_mapping::parse_turn_item;
+use crate::protocol::SessionSummaryEvent;
+
+/// Model used for the summary side request.
+const SESSION_SUMMARY_MODEL: &str = "gpt-5.1-codex-mini";
+
+const SESSION_SUMMARY_REASONING: ReasoningEffort = ReasoningEffort::Low;
+
+const SESSION_SUMMARY_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Shorter budget for the notes written at shutdown so exiting stays quick.
+pub(crate) const AUTOMATIC_NOTES_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Upper bound on the transcript sent to the model; older content is dropped
+/// first.
+const MAX_TRANSCRIPT_BYTES: usize = 120_000;
+
+/// Upper bound on a single transcript entry such as tool call arguments.
+const MAX_ENTRY_BYTES: usize = 2_000;
+
+/// Number of previous notes recalled into a new session.
+const MAX_RECALLED_NOTES: usize = 2;
+
+/// Upper bound on each recalled note.
+const MAX_RECALLED_NOTE_BYTES: usize = 4_000;
+
+pub(crate) const SESSION_NOTES_OPEN_TAG: &str = "<session_notes>";
+const SESSION_NOTES_CLOSE_TAG: &str = "</session_notes>";
+
+pub(crate) const MERGED_CONTEXT_OPEN_TAG: &str = "<merged_context>";
+const MERGED_CONTEXT_CLOSE_TAG: &str = "</merged_context>";
+
+/// Where a set of session notes is stored, relative to the working directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NotesKind {
+    /// Requested explicitly with `/summary save`.
+    Manual,
+    /// Written automatically at shutdown.
+    Automatic,
+}
+
+impl NotesKind {
+    const ALL: [NotesKind; 2] = [NotesKind::Manual, NotesKind::Automatic];
+
+    fn dir(self) -> &'static str {
+        match self {
+            NotesKind::Manual => ".kaioken/session-notes",
+            NotesKind::Automatic => ".kaioken/notes",
+        }
+    }
+}
+
+const SUMMARY_INSTRUCTIONS: &str = r#"You summarize a coding session between a user and a coding agent so that work can resume later.
+
+Return a JSON object with:
+- goals: what the user set out to accomplish
+- decisions: choices made along the way and why, when stated
+- files_changed: paths of files created, edited, or deleted
+- open_questions: unresolved issues, pending follow-ups, or known problems
+
+Keep every entry to one short sentence or a file path. Use empty arrays when nothing applies."#;
+
+#[derive(Debug, Default, Deserialize)]
+struct SummaryResponse {
+    #[serde(default)]
+    goals: Vec<String>,
+    #[serde(default)]
+    decisions: Vec<String>,
+    #[serde(default)]
+    files_changed: Vec<String>,
+    #[serde(default)]
+    open_questions: Vec<String>,
+}
+
+/// Summarize `history` with a side request to [`SESSION_SUMMARY_MODEL`].
+pub(crate) async fn summarize_session(
+    config: Arc<Config>,
+    provider: ModelProviderInfo,
+    auth_manager: Arc<AuthManager>,
+    otel: &OtelEventManager,
+    conversation_id: ConversationId,
+    session_source: SessionSource,
+    history: &[ResponseItem],
+) -> anyhow::Result<SessionSummaryEvent> {
+    let transcript = build_transcript(history);
+    if transcript.is_empty() {
+        anyhow::bail!("nothing to summarize yet");
+    }
+
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!("Summarize this session:\n\n{transcript}"),
+            }],
+        }],
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions_override: Some(SUMMARY_INSTRUCTIONS.to_string()),
+        output_schema: Some(summary_schema()),
+    };
+
+    let mut summary_config = (*config).clone();
+    summary_config.model = SESSION_SUMMARY_MODEL.to_string();
+    let child_otel = otel.with_model(SESSION_SUMMARY_MODEL, &summary_config.model_family.slug);
+    let client = ModelClient::new(
+        Arc::new(summary_config),
+        Some(auth_manager),
+        child_otel,
+        provider,
+        Some(SESSION_SUMMARY_REASONING),
+        config.model_reasoning_summary,
+        conversation_id,
+        session_source,
+    );
+
+    let output = timeout(SESSION_SUMMARY_TIMEOUT, async move {
+        let mut stream = client.stream(&prompt).await?;
+        let mut last_text = None;
+        while let Some(event) = stream.next().await {
+            match event? {
+                ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. }) => {
+                    last_text = content.into_iter().find_map(|item| match item {
+                        ContentItem::OutputText { text } => Some(text),
+                        _ => None,
+                    });
+                }
+                ResponseEvent::Completed { .. } => break,
+                _ => {}
+            }
+        }
+        Ok::<_, crate::error::CodexErr>(last_text)
+    })
+    .await
+    .map_err(|_| anyhow::anyhow!("summary request timed out"))??;
+
+    let Some(output) = output else {
+        anyhow::bail!("the model returned no summary");
+    };
+    let response: SummaryResponse = serde_json::from_str(output.trim())?;
+    Ok(SessionSummaryEvent {
+        goals: response.goals,
+        decisions: response.decisions,
+        files_changed: response.files_changed,
+        open_questions: response.open_questions,
+        saved_path: None,
+    })
+}
+
+/// Write `summary` as Markdown under the directory for `kind` in `cwd` and
+/// return the file path.
+pub(crate) async fn write_session_notes(
+    cwd: &Path,
+    kind: NotesKind,
+    conversation_id: ConversationId,
+    summary: &SessionSummaryEvent,
+) -> std::io::Result<PathBuf> {
+    let dir = cwd.join(kind.dir());
+    tokio::fs::create_dir_all(&dir).await?;
+    let now = Local::now();
+    let path = dir.join(format!("{}.md", now.format("%Y-%m-%dT%H-%M-%S")));
+    let contents = render_markdown(summary, &now.to_rfc3339(), conversation_id);
+    tokio::fs::write(&path, contents).await?;
+    Ok(path)
+}
+
+/// Load the most recent session notes saved in `cwd`, wrapped for inclusion
+/// in the initial context. Returns `None` when there are no notes.
+pub(crate) async fn load_recent_notes(cwd: &Path) -> Option<String> {
+    let mut paths = Vec::new();
+    for kind in NotesKind::ALL {
+        let Ok(mut entries) = tokio::fs::read_dir(cwd.join(kind.dir())).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "md") {
+                paths.push(path);
+            }
+        }
+    }
+    // File names are timestamps, so they sort chronologically.
+    paths.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+
+    let mut notes = Vec::new();
+    for path in paths.into_iter().take(MAX_RECALLED_NOTES) {
+        if let Ok(contents) = tokio::fs::read_to_string(&path).await {
+            let contents = contents.trim();
+            if !contents.is_empty() {
+                notes.push(
+                    take_bytes_at_char_boundary(contents, MAX_RECALLED_NOTE_BYTES).to_string(),
+                );
+            }
+        }
+    }
+    if notes.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "{SESSION_NOTES_OPEN_TAG}\nNotes from earlier sessions in this directory, most recent first. Use them for continuity, but verify against the current code before relying on them.\n\n{}\n{SESSION_NOTES_CLOSE_TAG}",
+        notes.join("\n\n")
+    ))
+}
+
+/// The history item carrying the summary of a session merged into this one.
+pub(crate) fn merged_context_item(
+    source_conversation_id: ConversationId,
+    summary: &SessionSummaryEvent,
+) -> ResponseItem {
+    let notes = render_markdown(summary, &Local::now().to_rfc3339(), source_conversation_id);
+    ResponseItem::Message {
+        id: None,
+        role: "user".to_string(),
+        content: vec![ContentItem::InputText {
+            text: format!(
+                "{MERGED_CONTEXT_OPEN_TAG}\nThe user merged another session into this one. Use its notes as background for this conversation, but verify against the current code before relying on them.\n\n{}\n{MERGED_CONTEXT_CLOSE_TAG}",
+                notes.trim_end()
+            ),
+        }],
+    }
+}
+
+fn render_markdown(
+    summary: &SessionSummaryEvent,
+    timestamp: &str,
+    conversation_id: ConversationId,
+) -> String {
+    let mut out = format!("# Session notes\n\n_{timestamp} · session {conversation_id}_\n");
+    for (title, entries) in [
+        ("Goals", &summary.goals),
+        ("Decisions", &summary.decisions),
+        ("Files changed", &summary.files_changed),
+        ("Open questions", &summary.open_questions),
+    ] {
+        out.push_str(&format!("\n## {title}\n\n"));
+        if entries.is_empty() {
+            out.push_str("- (none)\n");
+        }
+        for entry in entries {
+            out.push_str(&format!("- {entry}\n"));
+        }
+    }
+    out
+}
+
+/// Whether `history` contains any user input worth summarizing.
+pub(crate) fn has_user_input(history: &[ResponseItem]) -> bool {
+    history
+        .iter()
+        .any(|item| matches!(parse_turn_item(item), Some(TurnItem::UserMessage(_))))
+}
+
+/// Flatten user and agent messages plus tool calls into a plain-text
+/// transcript, keeping the most recent content when it is too long.
+pub(crate) fn build_transcript(history: &[ResponseItem]) -> String {
+    let mut transcript = String::new();
+    for item in history {
+        let entry = match item {
+            ResponseItem::FunctionCall {
+                name, arguments, ..
+            } => format!("[tool {name}] {arguments}"),
+            ResponseItem::CustomToolCall { name, input, .. } => {
+                format!("[tool {name}] {input}")
+            }
+            ResponseItem::LocalShellCall { action, .. } => format!("[shell] {action:?}"),
+            _ => match parse_turn_item(item) {
+                Some(TurnItem::UserMessage(message)) => format!("User: {}", message.message()),
+                Some(TurnItem::AgentMessage(message)) => {
+                    let text = message
+                        .content
+                        .iter()
+                        .map(|content| match content {
+                            AgentMessageContent::Text { text } => text.as_str(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join("");
+                    format!("Agent: {text}")
+                }
+                _ => continue,
+            },
+        };
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+        transcript.push_str(take_bytes_at_char_boundary(entry, MAX_ENTRY_BYTES));
+        transcript.push_str("\n\n");
+    }
+    take_last_bytes_at_char_boundary(transcript.trim_end(), MAX_TRANSCRIPT_BYTES).to_string()
+}
+
+fn summary_schema() -> serde_json::Value {
+    let list = json!({ "type": "array", "items": { "type": "string" } });
+    json!({
+        "name": "session_summary",
+        "strict": true,
+        "schema": {
+            "type": "object",
+            "properties": {
+                "goals": list,
+                "decisions": list,
+                "files_changed": list,
+                "open_questions": list,
+            },
+            "required": ["goals", "decisions", "files_changed", "open_questions"],
+            "additionalProperties": false,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn message(role: &str, text: &str) -> ResponseItem {
+        let content = if role == "user" {
+            ContentItem::InputText {
+                text: text.to_string(),
+            }
+        } else {
+            ContentItem::OutputText {
+                text: text.to_string(),
+            }
+        };
+        ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![content],
+        }
+    }
+
+    #[test]
+    fn transcript_skips_context_and_includes_tool_calls() {
+        let history = vec![
+            message(
+                "user",
+                "<environment_context>\n  <cwd>/repo</cwd>\n</environment_context>",
+            ),
+            message("user", "rename the config flag"),
+            ResponseItem::FunctionCall {
+                id: None,
+                name: "shell".to_string(),
+                arguments: "{\"command\":[\"rg\",\"flag\"]}".to_string(),
+                call_id: "call-1".to_string(),
+            },
+            message("assistant", "Renamed it in config.rs."),
+        ];
+
+        assert_eq!(
+            build_transcript(&history),
+            "User: rename the config flag\n\n\
+             [tool shell] {\"command\":[\"rg\",\"flag\"]}\n\n\
+             Agent: Renamed it in config.rs."
+        );
+    }
+
+    #[tokio::test]
+    async fn recalls_most_recent_notes_across_both_directories() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let cwd = temp.path();
+        assert_eq!(load_recent_notes(cwd).await, None);
+
+        for (kind, name, body) in [
+            (NotesKind::Automatic, "2025-01-01T10-00-00.md", "oldest"),
+            (NotesKind::Manual, "2025-01-02T10-00-00.md", "manual"),
+            (NotesKind::Automatic, "2025-01-03T10-00-00.md", "newest"),
+        ] {
+            let dir = cwd.join(kind.dir());
+            std::fs::create_dir_all(&dir).expect("create notes dir");
+            std::fs::write(dir.join(name), body).expect("write note");
+        }
+
+        let notes = load_recent_notes(cwd).await.expect("notes");
+        assert!(notes.starts_with(SESSION_NOTES_OPEN_TAG));
+        assert!(notes.ends_with(SESSION_NOTES_CLOSE_TAG));
+        assert!(notes.contains("newest\n\nmanual\n"));
+        assert!(!notes.contains("oldest"));
+    }
+
+    #[test]
+    fn merged_context_is_not_a_user_message() {
+        let summary = SessionSummaryEvent {
+            goals: vec!["Rename the config flag".to_string()],
+            decisions: Vec::new(),
+            files_changed: Vec::new(),
+            open_questions: Vec::new(),
+            saved_path: None,
+        };
+        let source = ConversationId::new();
+        let item = merged_context_item(source, &summary);
+
+        assert!(parse_turn_item(&item).is_none());
+        assert!(!has_user_input(std::slice::from_ref(&item)));
+        let ResponseItem::Message { content, .. } = item else {
+            panic!("expected a message");
+        };
+        let [ContentItem::InputText { text }] = content.as_slice() else {
+            panic!("expected one text item");
+        };
+        assert!(text.contains(&format!("session {source}")));
+        assert!(text.contains("- Rename the config flag"));
+    }
+
+    #[test]
+    fn markdown_lists_every_section() {
+        let summary = SessionSummaryEvent {
+            goals: vec!["Rename the config flag".to_string()],
+            decisions: Vec::new(),
+            files_changed: vec!["src/config.rs".to_string()],
+            open_questions: Vec::new(),
+            saved_path: None,
+        };
+        let conversation_id = ConversationId::new();
+        let markdown = render_markdown(&summary, "2025-01-01T00:00:00Z", conversation_id);
+        assert_eq!(
+            markdown,
+            format!(
+                "# Session notes\n\n_2025-01-01T00:00:00Z · session {conversation_id}_\n\
+                 \n## Goals\n\n- Rename the config flag\n\
+                 \n## Decisions\n\n- (none)\n\
+                 \n## Files changed\n\n- src/config.rs\n\
+                 \n## Open questions\n\n- (none)\n"
+            )
+        );
+    }
+}
@@ -6,6 +6,7 @@ use tokio::process::Command;
 use tracing::trace;
 
 use crate::protocol::SandboxPolicy;
+use crate::resource_limits::ResourceLimits;
 
 /// Experimental environment variable that will be set to some non-empty value
 /// if both of the following are true:
@@ -35,6 +36,10 @@ pub enum StdioPolicy {
 /// For now, we take `SandboxPolicy` as a parameter to spawn_child() because
 /// we need to determine whether to set the
 /// `CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR` environment variable.
+///
+/// `resource_limits` are installed in the child before it execs, so they also
+/// bind everything it spawns.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn spawn_child_async(
     program: PathBuf,
     args: Vec<String>,
@@ -43,9 +48,10 @@ pub(crate) async fn spawn_child_async(
     sandbox_policy: &SandboxPolicy,
     stdio_policy: StdioPolicy,
     env: HashMap<String, String>,
+    resource_limits: ResourceLimits,
 ) -> std::io::Result<Child> {
     trace!(
-        "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?}"
+        "spawn_child_async: {program:?} {args:?} {arg0:?} {cwd:?} {sandbox_policy:?} {stdio_policy:?} {env:?} {resource_limits:?}"
     );
 
     let mut cmd = Command::new(&program);
@@ -91,7 +97,8 @@ pub(crate) async fn spawn_child_async(
                     libc::raise(libc::SIGTERM);
                 }
             }
-            Ok(())
+
+            resource_limits.apply()
         });
     }
 
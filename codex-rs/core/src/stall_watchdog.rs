@@ -0,0 +1,195 @@
+//! Detects turns that stop making progress.
+//!
+//! Every event a session sends and every response event from the model
+//! provider counts as activity. When a turn goes `stall_watchdog.timeout_secs`
+//! without any, the watchdog emits [`EventMsg::StallDetected`] once per stall
+//! and, with `stall_watchdog.auto_interrupt`, fails the turn with a retriable
+//! [`CodexErrorInfo::TurnStalled`] error instead of leaving it hanging. Time
+//! spent waiting on the user for an approval does not count.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::StallWatchdog;
+use crate::protocol::CodexErrorInfo;
+use crate::protocol::ErrorEvent;
+use crate::protocol::EventMsg;
+use crate::protocol::StallDetectedEvent;
+use crate::protocol::TurnAbortReason;
+
+/// Upper bound on how long a stall goes unnoticed past the timeout.
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Last activity seen by a session, shared by the event path, the model
+/// stream, and the watchdog.
+pub(crate) struct ActivityTracker {
+    inner: Mutex<Activity>,
+}
+
+struct Activity {
+    last_activity: Instant,
+    last_event: String,
+    running_tool_calls: i64,
+}
+
+impl Default for ActivityTracker {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(Activity {
+                last_activity: Instant::now(),
+                last_event: String::new(),
+                running_tool_calls: 0,
+            }),
+        }
+    }
+}
+
+impl ActivityTracker {
+    /// Records an event sent to clients. The watchdog's own reports are not
+    /// progress and are ignored.
+    pub(crate) fn record_event(&self, msg: &EventMsg) {
+        if matches!(msg, EventMsg::StallDetected(_)) {
+            return;
+        }
+        let Ok(mut activity) = self.inner.lock() else {
+            return;
+        };
+        activity.last_activity = Instant::now();
+        activity.last_event = msg.to_string();
+        match msg {
+            EventMsg::TaskStarted(_) => activity.running_tool_calls = 0,
+            EventMsg::ExecCommandBegin(_)
+            | EventMsg::McpToolCallBegin(_)
+            | EventMsg::PatchApplyBegin(_) => activity.running_tool_calls += 1,
+            EventMsg::ExecCommandEnd(_)
+            | EventMsg::McpToolCallEnd(_)
+            | EventMsg::PatchApplyEnd(_) => {
+                activity.running_tool_calls = (activity.running_tool_calls - 1).max(0);
+            }
+            _ => {}
+        }
+    }
+
+    /// Records traffic from the model provider that is not surfaced as an
+    /// event, such as keep-alives and items that are still being assembled.
+    pub(crate) fn record_provider_traffic(&self) {
+        self.touch();
+    }
+
+    fn touch(&self) {
+        if let Ok(mut activity) = self.inner.lock() {
+            activity.last_activity = Instant::now();
+        }
+    }
+
+    /// Returns a stall report when nothing happened for at least `timeout`
+    /// before `now`.
+    fn check(&self, now: Instant, timeout: Duration) -> Option<StallDetectedEvent> {
+        let activity = self.inner.lock().ok()?;
+        let idle = now.saturating_duration_since(activity.last_activity);
+        (idle >= timeout).then(|| StallDetectedEvent {
+            idle_seconds: i64::try_from(idle.as_secs()).unwrap_or(i64::MAX),
+            last_event: activity.last_event.clone(),
+            running_tool_calls: activity.running_tool_calls,
+            interrupted: false,
+        })
+    }
+}
+
+/// Watches the turn run by `turn_context` until `cancellation_token` fires or
+/// the future is dropped. Returns immediately when the watchdog is disabled.
+pub(crate) async fn watch_turn(
+    sess: Arc<Session>,
+    turn_context: Arc<TurnContext>,
+    settings: StallWatchdog,
+    cancellation_token: CancellationToken,
+) {
+    let Ok(timeout_secs) = u64::try_from(settings.timeout_secs) else {
+        return;
+    };
+    if timeout_secs == 0 {
+        return;
+    }
+    let timeout = Duration::from_secs(timeout_secs);
+    let check_interval = timeout.min(MAX_CHECK_INTERVAL);
+    let tracker = &sess.services.activity_tracker;
+    let mut reported = false;
+
+    loop {
+        tokio::select! {
+            _ = cancellation_token.cancelled() => return,
+            _ = tokio::time::sleep(check_interval) => {}
+        }
+        if sess.has_pending_approvals().await {
+            tracker.touch();
+        }
+        let Some(mut stall) = tracker.check(Instant::now(), timeout) else {
+            reported = false;
+            continue;
+        };
+        if reported {
+            continue;
+        }
+        reported = true;
+        stall.interrupted = settings.auto_interrupt;
+        let idle_seconds = stall.idle_seconds;
+        sess.send_event(&turn_context, EventMsg::StallDetected(stall))
+            .await;
+        if settings.auto_interrupt {
+            let message = format!(
+                "Turn interrupted after {idle_seconds}s without activity; retry to continue."
+            );
+            sess.send_event(
+                &turn_context,
+                EventMsg::Error(ErrorEvent {
+                    message,
+                    codex_error_info: Some(CodexErrorInfo::TurnStalled),
+                }),
+            )
+            .await;
+            // Aborting the turn also drops this watchdog, so do it from a
+            // separate task to make sure the abort runs to completion.
+            let sess = Arc::clone(&sess);
+            tokio::spawn(async move {
+                sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
+            });
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::WarningEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn reports_stall_with_last_event_after_timeout() {
+        let tracker = ActivityTracker::default();
+        tracker.record_event(&EventMsg::Warning(WarningEvent {
+            message: "slow".to_string(),
+        }));
+        let timeout = Duration::from_secs(60);
+
+        assert_eq!(tracker.check(Instant::now(), timeout), None);
+        assert_eq!(
+            tracker.check(Instant::now() + Duration::from_secs(61), timeout),
+            Some(StallDetectedEvent {
+                idle_seconds: 61,
+                last_event: "warning".to_string(),
+                running_tool_calls: 0,
+                interrupted: false,
+            })
+        );
+
+        tracker.record_provider_traffic();
+        assert_eq!(tracker.check(Instant::now(), timeout), None);
+    }
+}
@@ -1,7 +1,10 @@
+mod read_cache;
 mod service;
 mod session;
 mod turn;
 
+pub(crate) use read_cache::FileStamp;
+pub(crate) use read_cache::ReadCacheKey;
 pub(crate) use service::SessionServices;
 pub(crate) use session::SessionState;
 pub(crate) use turn::ActiveTurn;
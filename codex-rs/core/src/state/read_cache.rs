@@ -0,0 +1,116 @@
+//! Turn-scoped cache of `read_file` results.
+//!
+//! Entries are keyed by path and the requested range, and remember the file's
+//! modification time and length when it was read. A lookup only hits while
+//! both still match, and applying a patch drops every entry for the files it
+//! touched, so edits made within the same second are not missed either.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// The file and the part of it a `read_file` call asked for.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ReadCacheKey {
+    pub(crate) path: PathBuf,
+    /// Canonical description of the requested range, e.g. `slice:1:2000`.
+    pub(crate) range: String,
+}
+
+/// Identifies one version of a file on disk.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    /// Stamp of the file as it is now; `None` when it cannot be inspected.
+    pub(crate) async fn of(path: &Path) -> Option<Self> {
+        let metadata = tokio::fs::metadata(path).await.ok()?;
+        Some(Self {
+            modified: metadata.modified().ok()?,
+            len: metadata.len(),
+        })
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct FileReadCache {
+    entries: HashMap<ReadCacheKey, (FileStamp, String)>,
+}
+
+impl FileReadCache {
+    pub(crate) fn get(&self, key: &ReadCacheKey, stamp: FileStamp) -> Option<String> {
+        self.entries
+            .get(key)
+            .filter(|(cached_stamp, _)| *cached_stamp == stamp)
+            .map(|(_, content)| content.clone())
+    }
+
+    pub(crate) fn insert(&mut self, key: ReadCacheKey, stamp: FileStamp, content: String) {
+        self.entries.insert(key, (stamp, content));
+    }
+
+    /// Forgets every cached range of `path`.
+    pub(crate) fn invalidate(&mut self, path: &Path) {
+        self.entries.retain(|key, _| key.path != path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    fn key(path: &str, range: &str) -> ReadCacheKey {
+        ReadCacheKey {
+            path: PathBuf::from(path),
+            range: range.to_string(),
+        }
+    }
+
+    #[test]
+    fn hits_only_while_the_file_is_unchanged() {
+        let stamp = FileStamp {
+            modified: SystemTime::UNIX_EPOCH,
+            len: 10,
+        };
+        let touched = FileStamp {
+            modified: SystemTime::UNIX_EPOCH + Duration::from_secs(1),
+            len: 10,
+        };
+        let mut cache = FileReadCache::default();
+        cache.insert(key("/repo/a.rs", "slice:1:10"), stamp, "L1: a".to_string());
+
+        assert_eq!(
+            cache.get(&key("/repo/a.rs", "slice:1:10"), stamp),
+            Some("L1: a".to_string())
+        );
+        assert_eq!(cache.get(&key("/repo/a.rs", "slice:2:10"), stamp), None);
+        assert_eq!(cache.get(&key("/repo/a.rs", "slice:1:10"), touched), None);
+    }
+
+    #[test]
+    fn invalidate_drops_every_range_of_the_path() {
+        let stamp = FileStamp {
+            modified: SystemTime::UNIX_EPOCH,
+            len: 10,
+        };
+        let mut cache = FileReadCache::default();
+        cache.insert(key("/repo/a.rs", "slice:1:10"), stamp, "a".to_string());
+        cache.insert(key("/repo/a.rs", "slice:5:10"), stamp, "a".to_string());
+        cache.insert(key("/repo/b.rs", "slice:1:10"), stamp, "b".to_string());
+
+        cache.invalidate(Path::new("/repo/a.rs"));
+
+        assert_eq!(cache.get(&key("/repo/a.rs", "slice:1:10"), stamp), None);
+        assert_eq!(cache.get(&key("/repo/a.rs", "slice:5:10"), stamp), None);
+        assert_eq!(
+            cache.get(&key("/repo/b.rs", "slice:1:10"), stamp),
+            Some("b".to_string())
+        );
+    }
+}
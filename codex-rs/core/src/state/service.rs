@@ -1,12 +1,16 @@
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::OnceLock;
 
 use crate::AuthManager;
 use crate::RolloutRecorder;
+use crate::config::reload::ConfigReloadState;
+use crate::config::reload::ReloadableSettings;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::memory::MemoryManager;
+use crate::stall_watchdog::ActivityTracker;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecSessionManager;
-use crate::user_notification::UserNotifier;
 use codex_otel::otel_event_manager::OtelEventManager;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
@@ -16,13 +20,28 @@ pub(crate) struct SessionServices {
     pub(crate) mcp_connection_manager: Arc<RwLock<McpConnectionManager>>,
     pub(crate) mcp_startup_cancellation_token: CancellationToken,
     pub(crate) unified_exec_manager: UnifiedExecSessionManager,
-    pub(crate) notifier: UserNotifier,
     pub(crate) rollout: Mutex<Option<RolloutRecorder>>,
     pub(crate) user_shell: crate::shell::Shell,
-    pub(crate) show_raw_agent_reasoning: bool,
+    /// Notifier command, raw reasoning display, and the session budget;
+    /// replaced when `config.toml` changes.
+    pub(crate) reloadable: std::sync::RwLock<ReloadableSettings>,
+    /// Set once the `config.toml` watcher has loaded its baseline; `None`
+    /// for sessions that do not follow the file.
+    pub(crate) config_reload: Mutex<Option<ConfigReloadState>>,
     pub(crate) auth_manager: Arc<AuthManager>,
     pub(crate) otel_event_manager: OtelEventManager,
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
-    /// Optional memory manager for persistent learning.
-    pub(crate) memory_manager: Option<Arc<MemoryManager>>,
+    /// Memory manager for persistent learning. Unset while it starts in the
+    /// background; `None` once startup failed or memory is off.
+    pub(crate) memory_manager: OnceLock<Option<Arc<MemoryManager>>>,
+    /// Notes from earlier sessions in the same directory, recalled into the
+    /// initial context.
+    pub(crate) recalled_session_notes: Option<String>,
+    /// Cancellation tokens for running subagent tasks, keyed by the
+    /// launching tool call id and the task's index within that call.
+    pub(crate) subagent_cancellations: Mutex<HashMap<(String, i64), CancellationToken>>,
+    /// Cancellation tokens for running tool calls, keyed by call id.
+    pub(crate) tool_call_cancellations: Mutex<HashMap<String, CancellationToken>>,
+    /// Last sign of progress, watched by the stall watchdog.
+    pub(crate) activity_tracker: ActivityTracker,
 }
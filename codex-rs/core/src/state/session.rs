@@ -1,9 +1,17 @@
 //! Session-wide mutable state.
 
+use std::collections::BTreeMap;
+
+use codex_git::GhostCommit;
 use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::MessageVariant;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
+use crate::cost::CostTracker;
+use crate::env_drift::WorkspaceFingerprint;
+use crate::pinned_files::PinnedFiles;
+use crate::plan_mode::PlanWorkflow;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
@@ -14,6 +22,24 @@ pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    pub(crate) plan_workflow: Option<PlanWorkflow>,
+    pub(crate) cost: CostTracker,
+    /// Ghost snapshots captured at the start of each turn, oldest first.
+    turn_snapshots: Vec<(String, GhostCommit)>,
+    /// Discarded answers per regenerated user message, keyed by the
+    /// message's 0-based position among the user messages in history.
+    previous_attempts: BTreeMap<usize, Vec<MessageVariant>>,
+    /// Assistant messages pinned with `Op::PinHistoryItem`, keyed by their
+    /// 0-based position among the conversation's assistant messages.
+    pinned_items: BTreeMap<usize, ResponseItem>,
+    /// Files pinned with `Op::PinFile`, recorded ahead of user turns.
+    pub(crate) pinned_files: PinnedFiles,
+    /// Suggestions from the last `EventMsg::FollowUpSuggestions`; cleared
+    /// when the next turn starts.
+    follow_up_suggestions: Vec<String>,
+    /// Workspace state when the last turn ended, compared at the start of
+    /// the next user turn.
+    workspace_fingerprint: Option<WorkspaceFingerprint>,
 }
 
 impl SessionState {
@@ -24,6 +50,14 @@ impl SessionState {
             session_configuration,
             history,
             latest_rate_limits: None,
+            plan_workflow: None,
+            cost: CostTracker::default(),
+            turn_snapshots: Vec::new(),
+            previous_attempts: BTreeMap::new(),
+            pinned_items: BTreeMap::new(),
+            pinned_files: PinnedFiles::default(),
+            follow_up_suggestions: Vec::new(),
+            workspace_fingerprint: None,
         }
     }
 
@@ -42,6 +76,7 @@ impl SessionState {
 
     pub(crate) fn replace_history(&mut self, items: Vec<ResponseItem>) {
         self.history.replace(items);
+        self.pinned_files.forget_recorded();
     }
 
     pub(crate) fn set_token_info(&mut self, info: Option<TokenUsageInfo>) {
@@ -78,4 +113,110 @@ impl SessionState {
     pub(crate) fn get_total_token_usage(&self) -> i64 {
         self.history.get_total_token_usage()
     }
+
+    // Turn snapshot helpers
+    pub(crate) fn record_turn_snapshot(&mut self, turn_id: String, ghost_commit: GhostCommit) {
+        self.turn_snapshots.push((turn_id, ghost_commit));
+    }
+
+    /// Snapshot taken when `turn_id` started, plus the one taken when the
+    /// following turn started (if any).
+    pub(crate) fn turn_snapshot(
+        &self,
+        turn_id: &str,
+    ) -> Option<(GhostCommit, Option<GhostCommit>)> {
+        let idx = self
+            .turn_snapshots
+            .iter()
+            .position(|(id, _)| id == turn_id)?;
+        let next = self
+            .turn_snapshots
+            .get(idx + 1)
+            .map(|(_, commit)| commit.clone());
+        Some((self.turn_snapshots[idx].1.clone(), next))
+    }
+
+    pub(crate) fn latest_turn_snapshot(&self) -> Option<GhostCommit> {
+        self.turn_snapshots.last().map(|(_, commit)| commit.clone())
+    }
+
+    // Regenerated answer helpers
+    pub(crate) fn push_previous_attempt(&mut self, user_message: usize, attempt: MessageVariant) {
+        self.previous_attempts
+            .entry(user_message)
+            .or_default()
+            .push(attempt);
+    }
+
+    /// Drops the attempts recorded for the `user_message`-th user message
+    /// and later ones, once those messages are removed from history.
+    pub(crate) fn discard_previous_attempts_from(&mut self, user_message: usize) {
+        self.previous_attempts
+            .retain(|message, _| *message < user_message);
+    }
+
+    pub(crate) fn previous_attempts(&self, user_message: usize) -> Vec<MessageVariant> {
+        self.previous_attempts
+            .get(&user_message)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// The user message that a discarded attempt with `message_id` answered.
+    pub(crate) fn user_message_for_attempt(&self, message_id: &str) -> Option<usize> {
+        self.previous_attempts
+            .iter()
+            .find(|(_, attempts)| {
+                attempts
+                    .iter()
+                    .any(|attempt| attempt.message_id.as_deref() == Some(message_id))
+            })
+            .map(|(user_message, _)| *user_message)
+    }
+
+    // Pinned message helpers
+    pub(crate) fn set_pinned_item(&mut self, at_item: usize, item: Option<ResponseItem>) {
+        match item {
+            Some(item) => {
+                self.pinned_items.insert(at_item, item);
+            }
+            None => {
+                self.pinned_items.remove(&at_item);
+            }
+        }
+    }
+
+    /// Unpins the `at_item`-th assistant message and later ones, once those
+    /// messages are removed from history.
+    pub(crate) fn unpin_items_from(&mut self, at_item: usize) {
+        self.pinned_items.retain(|pinned, _| *pinned < at_item);
+    }
+
+    /// Pinned messages in conversation order.
+    pub(crate) fn pinned_items(&self) -> Vec<ResponseItem> {
+        self.pinned_items.values().cloned().collect()
+    }
+
+    // Follow-up suggestion helpers
+    pub(crate) fn set_follow_up_suggestions(&mut self, suggestions: Vec<String>) {
+        self.follow_up_suggestions = suggestions;
+    }
+
+    /// The `index`-th current suggestion, if there is one; taking it
+    /// discards the others.
+    pub(crate) fn take_follow_up_suggestion(&mut self, index: usize) -> Option<String> {
+        if index >= self.follow_up_suggestions.len() {
+            return None;
+        }
+        Some(std::mem::take(&mut self.follow_up_suggestions).swap_remove(index))
+    }
+
+    // Environment drift helpers
+    pub(crate) fn set_workspace_fingerprint(&mut self, fingerprint: Option<WorkspaceFingerprint>) {
+        self.workspace_fingerprint = fingerprint;
+    }
+
+    pub(crate) fn take_workspace_fingerprint(&mut self) -> Option<WorkspaceFingerprint> {
+        self.workspace_fingerprint.take()
+    }
 }
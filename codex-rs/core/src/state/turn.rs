@@ -13,6 +13,7 @@ use tokio::sync::oneshot;
 
 use crate::codex::TurnContext;
 use crate::protocol::ReviewDecision;
+use crate::state::read_cache::FileReadCache;
 use crate::tasks::SessionTask;
 
 /// Metadata about the currently running turn.
@@ -68,6 +69,7 @@ impl ActiveTurn {
 pub(crate) struct TurnState {
     pending_approvals: HashMap<String, oneshot::Sender<ReviewDecision>>,
     pending_input: Vec<ResponseInputItem>,
+    read_cache: FileReadCache,
 }
 
 impl TurnState {
@@ -86,6 +88,10 @@ impl TurnState {
         self.pending_approvals.remove(key)
     }
 
+    pub(crate) fn has_pending_approvals(&self) -> bool {
+        !self.pending_approvals.is_empty()
+    }
+
     pub(crate) fn clear_pending(&mut self) {
         self.pending_approvals.clear();
         self.pending_input.clear();
@@ -95,6 +101,10 @@ impl TurnState {
         self.pending_input.push(input);
     }
 
+    pub(crate) fn read_cache(&mut self) -> &mut FileReadCache {
+        &mut self.read_cache
+    }
+
     pub(crate) fn take_pending_input(&mut self) -> Vec<ResponseInputItem> {
         if self.pending_input.is_empty() {
             Vec::with_capacity(0)
@@ -0,0 +1,639 @@
+//! Disk usage report and cleanup for the data Codex keeps on disk.
+//!
+//! Rollouts, archived rollouts and MCP image attachments live under
+//! `~/.codex`. The memory database and checkpoints belong to the session's
+//! project: the former under `.kaioken/memory/`, the latter as unreferenced
+//! commits in the repository's git object store. Only checkpoint commits
+//! recorded in a rollout are counted or removed; other unreachable objects in
+//! the repository are left to git. Cleanup steps only remove what they are
+//! asked to and, on a dry run, only measure it.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use codex_protocol::ConversationId;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::RepoStorageUsage;
+use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::StorageCategory;
+use codex_protocol::protocol::StorageCleanupOptions;
+use codex_protocol::protocol::StorageReportEvent;
+use codex_protocol::protocol::StorageUsageEntry;
+use flate2::read::GzDecoder;
+
+use crate::mcp::attachments::ATTACHMENTS_SUBDIR;
+use crate::memory::MemoryManager;
+use crate::memory::store::memory_dir;
+use crate::rollout::lock::is_session_locked;
+use crate::rollout::sessions::RolloutFile;
+use crate::rollout::sessions::cutoff;
+use crate::rollout::sessions::delete_rollout;
+use crate::rollout::sessions::list_rollouts;
+
+/// Length of the conversation id at the end of a rollout file name.
+const CONVERSATION_ID_LEN: usize = 36;
+
+/// A file system cleanup step selected by [`StorageCleanupOptions`].
+pub(crate) struct CleanupStep {
+    pub category: StorageCategory,
+    /// What the step does, for progress events and errors.
+    pub description: String,
+    run: Box<dyn FnOnce() -> io::Result<StorageUsageEntry> + Send>,
+}
+
+impl CleanupStep {
+    fn new(
+        category: StorageCategory,
+        description: String,
+        run: impl FnOnce() -> io::Result<StorageUsageEntry> + Send + 'static,
+    ) -> Self {
+        Self {
+            category,
+            description,
+            run: Box::new(run),
+        }
+    }
+
+    /// Runs the step; blocks on file system and git work.
+    pub fn run(self) -> io::Result<StorageUsageEntry> {
+        (self.run)()
+    }
+}
+
+/// The file system steps `options` selects, in the order they should run.
+/// Compacting the memory database is async and handled by [`vacuum_memory`].
+pub(crate) fn cleanup_steps(
+    codex_home: &Path,
+    cwd: &Path,
+    current: ConversationId,
+    options: &StorageCleanupOptions,
+) -> Vec<CleanupStep> {
+    let dry_run = options.dry_run;
+    let mut steps = Vec::new();
+    if let Some(days) = options.delete_rollouts_older_than_days {
+        let codex_home = codex_home.to_path_buf();
+        steps.push(CleanupStep::new(
+            StorageCategory::Rollouts,
+            format!("Deleting rollouts older than {days} days"),
+            move || delete_rollouts_older_than(&codex_home, days, SystemTime::now(), dry_run),
+        ));
+    }
+    if options.gc_attachments {
+        let codex_home = codex_home.to_path_buf();
+        steps.push(CleanupStep::new(
+            StorageCategory::Attachments,
+            "Deleting attachments of deleted sessions".to_string(),
+            move || gc_attachments(&codex_home, current, dry_run),
+        ));
+    }
+    if let Some(days) = options.prune_checkpoints_older_than_days {
+        let codex_home = codex_home.to_path_buf();
+        let cwd = cwd.to_path_buf();
+        steps.push(CleanupStep::new(
+            StorageCategory::Checkpoints,
+            format!("Pruning checkpoints older than {days} days"),
+            move || prune_checkpoints(&codex_home, &cwd, days, SystemTime::now(), dry_run),
+        ));
+    }
+    steps
+}
+
+/// Compacts the project's memory database and reports the bytes reclaimed,
+/// or on a dry run the bytes held by free pages.
+pub(crate) async fn vacuum_memory(
+    memory: Option<&MemoryManager>,
+    dry_run: bool,
+) -> anyhow::Result<StorageUsageEntry> {
+    let Some(memory) = memory else {
+        anyhow::bail!("memory system not available");
+    };
+    let store = memory.store();
+    let reclaimed = if dry_run {
+        store.reclaimable_bytes().await?
+    } else {
+        let before = store.db_size_bytes().await?;
+        store.compact().await?;
+        (before - store.db_size_bytes().await?).max(0)
+    };
+    let mut entry = empty_entry(StorageCategory::Memory);
+    entry.bytes = reclaimed;
+    Ok(entry)
+}
+
+/// Measures every category for `codex_home` and the project at
+/// `project_root`, and groups rollouts by the directory they were recorded in.
+pub fn storage_report(codex_home: &Path, project_root: &Path) -> io::Result<StorageReportEvent> {
+    let rollouts = list_rollouts(codex_home)?;
+    let mut active = empty_entry(StorageCategory::Rollouts);
+    let mut archived = empty_entry(StorageCategory::ArchivedRollouts);
+    for file in &rollouts {
+        let entry = if file.archived {
+            &mut archived
+        } else {
+            &mut active
+        };
+        add_file(entry, file.size_bytes);
+    }
+
+    let mut attachments = empty_entry(StorageCategory::Attachments);
+    dir_usage(&codex_home.join(ATTACHMENTS_SUBDIR), &mut attachments)?;
+    let mut memory = empty_entry(StorageCategory::Memory);
+    dir_usage(&memory_dir(project_root), &mut memory)?;
+    // Not every project is a git repository; report nothing for those.
+    let mut checkpoints = empty_entry(StorageCategory::Checkpoints);
+    for object in checkpoint_objects(project_root, &rollouts).unwrap_or_default() {
+        add_file(&mut checkpoints, object.size_bytes);
+    }
+
+    Ok(StorageReportEvent {
+        usage: vec![active, archived, attachments, memory, checkpoints],
+        repos: repo_usage(&rollouts),
+    })
+}
+
+/// Deletes active and archived rollouts not modified within `days`, except
+/// those a running session is writing.
+pub fn delete_rollouts_older_than(
+    codex_home: &Path,
+    days: i64,
+    now: SystemTime,
+    dry_run: bool,
+) -> io::Result<StorageUsageEntry> {
+    let mut freed = empty_entry(StorageCategory::Rollouts);
+    let Some(cutoff) = cutoff(now, days) else {
+        return Ok(freed);
+    };
+    for file in list_rollouts(codex_home)? {
+        let expired = file.modified.is_some_and(|modified| modified < cutoff);
+        if !expired || is_session_locked(&file.path) {
+            continue;
+        }
+        if !dry_run {
+            delete_rollout(codex_home, &file.path)?;
+        }
+        add_file(&mut freed, file.size_bytes);
+    }
+    Ok(freed)
+}
+
+/// Deletes the attachment directories of conversations that no longer have a
+/// rollout, except the one of `current`.
+pub fn gc_attachments(
+    codex_home: &Path,
+    current: ConversationId,
+    dry_run: bool,
+) -> io::Result<StorageUsageEntry> {
+    let mut freed = empty_entry(StorageCategory::Attachments);
+    let root = codex_home.join(ATTACHMENTS_SUBDIR);
+    let entries = match std::fs::read_dir(&root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(freed),
+        Err(err) => return Err(err),
+    };
+
+    let mut live: HashSet<String> = list_rollouts(codex_home)?
+        .iter()
+        .filter_map(|file| rollout_conversation_id(&file.path))
+        .collect();
+    live.insert(current.to_string());
+
+    for entry in entries {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() || live.contains(&*entry.file_name().to_string_lossy()) {
+            continue;
+        }
+        let path = entry.path();
+        dir_usage(&path, &mut freed)?;
+        if !dry_run {
+            std::fs::remove_dir_all(&path)?;
+        }
+    }
+    Ok(freed)
+}
+
+/// Deletes checkpoint commits older than `days` from the repository
+/// containing `cwd`. Only ghost commits recorded in a rollout under
+/// `codex_home` that are stored as loose objects and unreachable from every
+/// ref are removed; their trees and blobs are left for `git gc`.
+pub fn prune_checkpoints(
+    codex_home: &Path,
+    cwd: &Path,
+    days: i64,
+    now: SystemTime,
+    dry_run: bool,
+) -> io::Result<StorageUsageEntry> {
+    let mut freed = empty_entry(StorageCategory::Checkpoints);
+    let Some(cutoff) = cutoff(now, days) else {
+        return Ok(freed);
+    };
+    let rollouts = list_rollouts(codex_home)?;
+    for object in checkpoint_objects(cwd, &rollouts)? {
+        if object.committed >= cutoff || is_referenced(cwd, &object.id)? {
+            continue;
+        }
+        if !dry_run {
+            std::fs::remove_file(&object.path)?;
+        }
+        add_file(&mut freed, object.size_bytes);
+    }
+    Ok(freed)
+}
+
+/// A checkpoint commit stored as a loose object.
+struct CheckpointObject {
+    id: String,
+    path: PathBuf,
+    size_bytes: i64,
+    committed: SystemTime,
+}
+
+/// Number of commit ids passed to a single `git log` invocation.
+const GIT_LOG_BATCH: usize = 256;
+
+/// The checkpoint commits recorded in `rollouts` that exist as loose objects
+/// in the repository containing `cwd`.
+fn checkpoint_objects(cwd: &Path, rollouts: &[RolloutFile]) -> io::Result<Vec<CheckpointObject>> {
+    let objects_dir = PathBuf::from(git(cwd, &["rev-parse", "--git-path", "objects"])?.trim());
+    let objects_dir = if objects_dir.is_absolute() {
+        objects_dir
+    } else {
+        cwd.join(objects_dir)
+    };
+    let ids: Vec<String> = recorded_ghost_commits(rollouts).into_iter().collect();
+    let mut objects = Vec::new();
+    for batch in ids.chunks(GIT_LOG_BATCH) {
+        let mut args = vec!["log", "--no-walk", "--ignore-missing", "--format=%H %ct"];
+        args.extend(batch.iter().map(String::as_str));
+        for line in git(cwd, &args)?.lines() {
+            let Some((id, committed)) = line.split_once(' ') else {
+                continue;
+            };
+            let (Some(prefix), Some(rest), Ok(committed)) =
+                (id.get(..2), id.get(2..), committed.parse::<u64>())
+            else {
+                continue;
+            };
+            let path = objects_dir.join(prefix).join(rest);
+            // Packed checkpoints cannot be removed one by one.
+            let Ok(metadata) = std::fs::metadata(&path) else {
+                continue;
+            };
+            objects.push(CheckpointObject {
+                id: id.to_string(),
+                path,
+                size_bytes: i64::try_from(metadata.len()).unwrap_or(i64::MAX),
+                committed: UNIX_EPOCH + Duration::from_secs(committed),
+            });
+        }
+    }
+    Ok(objects)
+}
+
+/// Ghost commit ids of the checkpoints recorded in `rollouts`, compressed or
+/// not. Unreadable rollouts are skipped.
+fn recorded_ghost_commits(rollouts: &[RolloutFile]) -> HashSet<String> {
+    let mut ids = HashSet::new();
+    for file in rollouts {
+        let Ok(reader) = open_rollout(&file.path) else {
+            continue;
+        };
+        for line in BufReader::new(reader).lines().map_while(Result::ok) {
+            if !line.contains("\"ghost_snapshot\"") {
+                continue;
+            }
+            if let Ok(RolloutLine {
+                item: RolloutItem::ResponseItem(ResponseItem::GhostSnapshot { ghost_commit, .. }),
+                ..
+            }) = serde_json::from_str::<RolloutLine>(&line)
+            {
+                ids.insert(ghost_commit.id().to_string());
+            }
+        }
+    }
+    ids
+}
+
+/// Whether any branch or tag contains `id`, e.g. because a checkpoint was
+/// merged into real history.
+fn is_referenced(cwd: &Path, id: &str) -> io::Result<bool> {
+    let refs = git(cwd, &["for-each-ref", "--count=1", "--contains", id])?;
+    Ok(!refs.trim().is_empty())
+}
+
+fn git(cwd: &Path, args: &[&str]) -> io::Result<String> {
+    let output = Command::new("git").args(args).current_dir(cwd).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Rollout usage per recorded working directory, largest first. Rollouts
+/// without readable session metadata are left out.
+fn repo_usage(rollouts: &[RolloutFile]) -> Vec<RepoStorageUsage> {
+    let mut by_cwd: HashMap<PathBuf, RepoStorageUsage> = HashMap::new();
+    for file in rollouts {
+        let Some(cwd) = rollout_cwd(&file.path) else {
+            continue;
+        };
+        let usage = by_cwd
+            .entry(cwd.clone())
+            .or_insert_with(|| RepoStorageUsage {
+                cwd,
+                sessions: 0,
+                bytes: 0,
+            });
+        usage.sessions += 1;
+        usage.bytes += file.size_bytes;
+    }
+    let mut repos: Vec<RepoStorageUsage> = by_cwd.into_values().collect();
+    repos.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.cwd.cmp(&b.cwd)));
+    repos
+}
+
+/// The working directory recorded in the session metadata on the first line
+/// of a rollout, compressed or not.
+fn rollout_cwd(path: &Path) -> Option<PathBuf> {
+    let reader = open_rollout(path).ok()?;
+    let mut first_line = String::new();
+    BufReader::new(reader).read_line(&mut first_line).ok()?;
+    match serde_json::from_str::<RolloutLine>(&first_line).ok()?.item {
+        RolloutItem::SessionMeta(meta) => Some(meta.meta.cwd),
+        _ => None,
+    }
+}
+
+fn open_rollout(path: &Path) -> io::Result<Box<dyn Read>> {
+    let file = File::open(path)?;
+    Ok(if path.extension().is_some_and(|ext| ext == "gz") {
+        Box::new(GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    })
+}
+
+/// The conversation id at the end of a rollout file name.
+fn rollout_conversation_id(path: &Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let stem = name
+        .strip_suffix(".jsonl.gz")
+        .or_else(|| name.strip_suffix(".jsonl"))?;
+    let start = stem.len().checked_sub(CONVERSATION_ID_LEN)?;
+    stem.get(start..).map(str::to_string)
+}
+
+fn dir_usage(dir: &Path, entry: &mut StorageUsageEntry) -> io::Result<()> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+    for item in entries {
+        let item = item?;
+        let file_type = item.file_type()?;
+        if file_type.is_dir() {
+            dir_usage(&item.path(), entry)?;
+        } else if file_type.is_file() {
+            let size = item.metadata()?.len();
+            add_file(entry, i64::try_from(size).unwrap_or(i64::MAX));
+        }
+    }
+    Ok(())
+}
+
+fn empty_entry(category: StorageCategory) -> StorageUsageEntry {
+    StorageUsageEntry {
+        category,
+        files: 0,
+        bytes: 0,
+    }
+}
+
+fn add_file(entry: &mut StorageUsageEntry, size_bytes: i64) {
+    entry.files += 1;
+    entry.bytes = entry.bytes.saturating_add(size_bytes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rollout::ARCHIVED_SESSIONS_SUBDIR;
+    use crate::rollout::SESSIONS_SUBDIR;
+    use crate::rollout::sessions::SECONDS_PER_DAY;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    const CONVERSATION_ID: &str = "00000000-0000-0000-0000-000000000001";
+
+    fn write_rollout(home: &Path, subdir: &str, cwd: &str) -> PathBuf {
+        let dir = home.join(subdir).join("2025").join("01").join("01");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!(
+            "rollout-2025-01-01T12-00-00-{CONVERSATION_ID}.jsonl"
+        ));
+        let meta = serde_json::json!({
+            "timestamp": "2025-01-01T12:00:00Z",
+            "type": "session_meta",
+            "payload": {
+                "id": CONVERSATION_ID,
+                "timestamp": "2025-01-01T12:00:00Z",
+                "cwd": cwd,
+                "originator": "test",
+                "cli_version": "0.0.0",
+                "instructions": null,
+            },
+        });
+        std::fs::write(&path, format!("{meta}\n")).unwrap();
+        path
+    }
+
+    #[test]
+    fn report_groups_rollouts_by_working_directory() {
+        let home = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        write_rollout(home.path(), SESSIONS_SUBDIR, "/work/app");
+        let archived = write_rollout(home.path(), ARCHIVED_SESSIONS_SUBDIR, "/work/app");
+        let archived_bytes = i64::try_from(std::fs::metadata(&archived).unwrap().len()).unwrap();
+        std::fs::create_dir_all(home.path().join(ATTACHMENTS_SUBDIR).join("orphan")).unwrap();
+        std::fs::write(
+            home.path()
+                .join(ATTACHMENTS_SUBDIR)
+                .join("orphan")
+                .join("call-0.png"),
+            b"png",
+        )
+        .unwrap();
+
+        let report = storage_report(home.path(), project.path()).unwrap();
+        assert_eq!(
+            report.usage[1],
+            StorageUsageEntry {
+                category: StorageCategory::ArchivedRollouts,
+                files: 1,
+                bytes: archived_bytes,
+            }
+        );
+        assert_eq!(
+            report.usage[2],
+            StorageUsageEntry {
+                category: StorageCategory::Attachments,
+                files: 1,
+                bytes: 3,
+            }
+        );
+        assert_eq!(
+            report.repos,
+            vec![RepoStorageUsage {
+                cwd: PathBuf::from("/work/app"),
+                sessions: 2,
+                bytes: archived_bytes * 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn only_selected_steps_run() {
+        let home = TempDir::new().unwrap();
+        let steps = cleanup_steps(
+            home.path(),
+            home.path(),
+            ConversationId::new(),
+            &StorageCleanupOptions {
+                delete_rollouts_older_than_days: Some(30),
+                gc_attachments: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            steps
+                .iter()
+                .map(|step| (step.category, step.description.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    StorageCategory::Rollouts,
+                    "Deleting rollouts older than 30 days"
+                ),
+                (
+                    StorageCategory::Attachments,
+                    "Deleting attachments of deleted sessions"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn dry_runs_measure_without_deleting() {
+        let home = TempDir::new().unwrap();
+        let rollout = write_rollout(home.path(), SESSIONS_SUBDIR, "/work/app");
+        let orphan = home.path().join(ATTACHMENTS_SUBDIR).join("orphan");
+        let live = home.path().join(ATTACHMENTS_SUBDIR).join(CONVERSATION_ID);
+        for dir in [&orphan, &live] {
+            std::fs::create_dir_all(dir).unwrap();
+            std::fs::write(dir.join("call-0.png"), b"png").unwrap();
+        }
+        let later = SystemTime::now() + Duration::from_secs(2 * SECONDS_PER_DAY);
+
+        let freed = delete_rollouts_older_than(home.path(), 1, later, true).unwrap();
+        assert_eq!(freed.files, 1);
+        assert!(rollout.exists());
+        let freed = gc_attachments(home.path(), ConversationId::new(), true).unwrap();
+        assert_eq!((freed.files, freed.bytes), (1, 3));
+        assert!(orphan.exists());
+
+        delete_rollouts_older_than(home.path(), 1, later, false).unwrap();
+        assert!(!rollout.exists());
+        let freed = gc_attachments(home.path(), ConversationId::new(), false).unwrap();
+        assert_eq!(freed.files, 2);
+        assert!(!orphan.exists() && !live.exists());
+    }
+
+    fn git_in(repo: &Path, args: &[&str], env: &[(&str, &str)]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .envs(env.iter().copied())
+            .current_dir(repo)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    fn prune_checkpoints_only_removes_recorded_ghost_commits() {
+        let home = TempDir::new().unwrap();
+        let repo = TempDir::new().unwrap();
+        git_in(repo.path(), &["init", "-q"], &[]);
+        let identity = [
+            ("GIT_AUTHOR_NAME", "Codex"),
+            ("GIT_AUTHOR_EMAIL", "codex@example.com"),
+            ("GIT_COMMITTER_NAME", "Codex"),
+            ("GIT_COMMITTER_EMAIL", "codex@example.com"),
+            ("GIT_COMMITTER_DATE", "2020-01-01T00:00:00Z"),
+        ];
+        let tree = git_in(repo.path(), &["write-tree"], &[]);
+        let ghost = git_in(
+            repo.path(),
+            &["commit-tree", &tree, "-m", "ghost"],
+            &identity,
+        );
+        let other = git_in(
+            repo.path(),
+            &["commit-tree", &tree, "-m", "other"],
+            &identity,
+        );
+
+        let rollout = write_rollout(home.path(), SESSIONS_SUBDIR, "/work/app");
+        let line = RolloutLine {
+            timestamp: "2025-01-01T12:00:00Z".to_string(),
+            item: RolloutItem::ResponseItem(ResponseItem::GhostSnapshot {
+                ghost_commit: codex_git::GhostCommit::new(
+                    ghost.clone(),
+                    None,
+                    Vec::new(),
+                    Vec::new(),
+                ),
+                checkpoint: None,
+            }),
+        };
+        let mut contents = std::fs::read_to_string(&rollout).unwrap();
+        contents.push_str(&format!("{}\n", serde_json::to_string(&line).unwrap()));
+        std::fs::write(&rollout, contents).unwrap();
+
+        let exists = |id: &str| {
+            Command::new("git")
+                .args(["cat-file", "-e", id])
+                .current_dir(repo.path())
+                .status()
+                .unwrap()
+                .success()
+        };
+        let now = SystemTime::now();
+        let freed = prune_checkpoints(home.path(), repo.path(), 1, now, true).unwrap();
+        assert_eq!(freed.files, 1);
+        assert!(exists(&ghost));
+
+        let freed = prune_checkpoints(home.path(), repo.path(), 1, now, false).unwrap();
+        assert_eq!(freed.files, 1);
+        assert!(!exists(&ghost));
+        // Unreachable objects the rollouts do not record are left to git.
+        assert!(exists(&other));
+        assert!(exists(&tree));
+    }
+}
@@ -0,0 +1,113 @@
+//! Scratchpad shared by the subagents of one `subagent_run` call.
+//!
+//! Subagents running in parallel cannot see each other's turns, so they
+//! tend to repeat the same searches. While a fan-out runs, each subagent can
+//! append notes to the scratchpad of the parent's call id and read what the
+//! others wrote. Notes are never edited or removed; the parent collects them
+//! when the last task finishes.
+
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use codex_protocol::protocol::SubagentScratchpadNote;
+
+/// Notes kept per call; later appends are refused.
+pub const MAX_SCRATCHPAD_NOTES: usize = 200;
+/// Characters kept per note.
+pub const MAX_SCRATCHPAD_NOTE_CHARS: usize = 2_000;
+
+static SCRATCHPADS: LazyLock<Mutex<HashMap<String, Vec<SubagentScratchpadNote>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// The subagent a child session runs as, set on its config by the parent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScratchpadWriter {
+    /// Tool call id of the parent's `subagent_run` call.
+    pub call_id: String,
+    pub task: String,
+    pub agent_index: i64,
+}
+
+/// Start an empty scratchpad for `call_id`.
+pub(crate) fn open(call_id: &str) {
+    lock().insert(call_id.to_string(), Vec::new());
+}
+
+/// Append a note and return how many the scratchpad now holds.
+pub(crate) fn append(writer: &ScratchpadWriter, text: &str) -> Result<usize, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("note must not be empty".to_string());
+    }
+    let mut scratchpads = lock();
+    let Some(notes) = scratchpads.get_mut(&writer.call_id) else {
+        return Err("the scratchpad is closed because the subagent run finished".to_string());
+    };
+    if notes.len() >= MAX_SCRATCHPAD_NOTES {
+        return Err(format!(
+            "the scratchpad is full ({MAX_SCRATCHPAD_NOTES} notes)"
+        ));
+    }
+    notes.push(SubagentScratchpadNote {
+        task: writer.task.clone(),
+        agent_index: writer.agent_index,
+        text: text.chars().take(MAX_SCRATCHPAD_NOTE_CHARS).collect(),
+    });
+    Ok(notes.len())
+}
+
+/// Notes written so far, or `None` once the scratchpad is closed.
+pub(crate) fn read(call_id: &str) -> Option<Vec<SubagentScratchpadNote>> {
+    lock().get(call_id).cloned()
+}
+
+/// Remove the scratchpad and return its final contents.
+pub(crate) fn close(call_id: &str) -> Vec<SubagentScratchpadNote> {
+    lock().remove(call_id).unwrap_or_default()
+}
+
+fn lock() -> std::sync::MutexGuard<'static, HashMap<String, Vec<SubagentScratchpadNote>>> {
+    SCRATCHPADS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn writer(call_id: &str, task: &str, agent_index: i64) -> ScratchpadWriter {
+        ScratchpadWriter {
+            call_id: call_id.to_string(),
+            task: task.to_string(),
+            agent_index,
+        }
+    }
+
+    #[test]
+    fn notes_are_shared_until_the_scratchpad_closes() {
+        let api = writer("call-scratchpad", "api", 0);
+        let ui = writer("call-scratchpad", "ui", 1);
+        open("call-scratchpad");
+
+        assert_eq!(append(&api, "  auth lives in src/auth.rs \n"), Ok(1));
+        assert_eq!(append(&ui, "  "), Err("note must not be empty".to_string()));
+        assert_eq!(append(&ui, "tests use the fake clock"), Ok(2));
+        assert_eq!(
+            read("call-scratchpad")
+                .unwrap_or_default()
+                .iter()
+                .map(|note| (note.agent_index, note.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0, "auth lives in src/auth.rs"),
+                (1, "tests use the fake clock")
+            ]
+        );
+
+        assert_eq!(close("call-scratchpad").len(), 2);
+        assert_eq!(read("call-scratchpad"), None);
+        assert!(append(&api, "too late").is_err());
+    }
+}
@@ -0,0 +1,197 @@
+//! Brings memory and semantic search online after the session is usable.
+//!
+//! Opening the memory store, applying decay and the daily consolidation of
+//! near-duplicate memories can take seconds on large projects, so
+//! `Session::new` no longer waits for it. Until memory is ready
+//! [`Session::memory_manager`] returns `None` and memory queries fail soft.
+//! Each subsystem reports the outcome with [`EventMsg::SubsystemStartup`].
+
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Weak;
+
+use tracing::info;
+use tracing::warn;
+
+use crate::codex::INITIAL_SUBMIT_ID;
+use crate::codex::Session;
+use crate::memory::MemoryConfig;
+use crate::memory::MemoryManager;
+use crate::onboarding::find_sgrep_binary;
+use crate::protocol::Event;
+use crate::protocol::EventMsg;
+use crate::protocol::SessionSubsystem;
+use crate::protocol::SubsystemStartupEvent;
+use crate::protocol::SubsystemStartupStatus;
+
+pub(crate) fn spawn_subsystem_startup(
+    session: Weak<Session>,
+    cwd: PathBuf,
+    memory_config: MemoryConfig,
+) {
+    tokio::spawn(async move {
+        let status = match find_sgrep_binary() {
+            Some(_) => SubsystemStartupStatus::Ready,
+            None => SubsystemStartupStatus::Unavailable {
+                reason: "sgrep is not installed".to_string(),
+            },
+        };
+        let Some(sess) = session.upgrade() else {
+            return;
+        };
+        report(&sess, SessionSubsystem::SemanticSearch, status).await;
+        drop(sess);
+
+        let (memory, status) = match MemoryManager::init(&cwd, memory_config).await {
+            Ok(memory) => {
+                info!("Memory system initialized for session");
+                (Some(Arc::new(memory)), SubsystemStartupStatus::Ready)
+            }
+            Err(err) => {
+                warn!("Failed to initialize memory system: {err}");
+                let reason = err.to_string();
+                (None, SubsystemStartupStatus::Unavailable { reason })
+            }
+        };
+        let Some(sess) = session.upgrade() else {
+            return;
+        };
+        let _ = sess.services.memory_manager.set(memory.clone());
+        report(&sess, SessionSubsystem::Memory, status).await;
+        drop(sess);
+
+        let Some(memory) = memory.filter(|memory| memory.is_enabled()) else {
+            return;
+        };
+        if let Err(err) = memory.apply_decay().await {
+            warn!("Failed to apply memory decay on startup: {err}");
+        }
+        if memory.needs_consolidation().await
+            && let Some(sess) = session.upgrade()
+            && let Err(err) = sess.consolidate_memories(&memory).await
+        {
+            warn!("Failed to consolidate memories on startup: {err}");
+        }
+    });
+}
+
+async fn report(sess: &Session, subsystem: SessionSubsystem, status: SubsystemStartupStatus) {
+    sess.send_event_raw(Event {
+        id: INITIAL_SUBMIT_ID.to_owned(),
+        msg: EventMsg::SubsystemStartup(SubsystemStartupEvent { subsystem, status }),
+    })
+    .await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::codex::make_session_and_context_with_rx;
+    use pretty_assertions::assert_eq;
+    use std::sync::OnceLock;
+    use std::time::Duration;
+
+    /// A session whose memory has not started yet, with its event receiver.
+    fn starting_session() -> (Arc<Session>, async_channel::Receiver<Event>) {
+        let (session, _turn_context, rx) = make_session_and_context_with_rx();
+        let Ok(mut session) = Arc::try_unwrap(session) else {
+            panic!("session is not shared yet");
+        };
+        session.services.memory_manager = OnceLock::new();
+        (Arc::new(session), rx)
+    }
+
+    async fn next_startup_event(rx: &async_channel::Receiver<Event>) -> SubsystemStartupEvent {
+        let event = tokio::time::timeout(Duration::from_secs(30), rx.recv())
+            .await
+            .expect("timed out waiting for startup event")
+            .expect("event");
+        assert_eq!(event.id, INITIAL_SUBMIT_ID);
+        match event.msg {
+            EventMsg::SubsystemStartup(event) => event,
+            other => panic!("unexpected event: {other:?}"),
+        }
+    }
+
+    fn expected_semantic_search() -> SubsystemStartupEvent {
+        let status = match find_sgrep_binary() {
+            Some(_) => SubsystemStartupStatus::Ready,
+            None => SubsystemStartupStatus::Unavailable {
+                reason: "sgrep is not installed".to_string(),
+            },
+        };
+        SubsystemStartupEvent {
+            subsystem: SessionSubsystem::SemanticSearch,
+            status,
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_memory_ready_once_initialised() {
+        let project = tempfile::tempdir().expect("tempdir");
+        let (session, rx) = starting_session();
+        assert!(session.memory_starting());
+
+        spawn_subsystem_startup(
+            Arc::downgrade(&session),
+            project.path().to_path_buf(),
+            MemoryConfig::default(),
+        );
+
+        assert_eq!(next_startup_event(&rx).await, expected_semantic_search());
+        assert_eq!(
+            next_startup_event(&rx).await,
+            SubsystemStartupEvent {
+                subsystem: SessionSubsystem::Memory,
+                status: SubsystemStartupStatus::Ready,
+            }
+        );
+        assert!(!session.memory_starting());
+        assert!(session.memory_manager().is_some());
+    }
+
+    #[tokio::test]
+    async fn reports_memory_unavailable_when_the_store_cannot_open() {
+        let project = tempfile::tempdir().expect("tempdir");
+        // The memory store lives under `.kaioken/`; a file there blocks it.
+        std::fs::write(project.path().join(".kaioken"), "").expect("write");
+        let (session, rx) = starting_session();
+
+        spawn_subsystem_startup(
+            Arc::downgrade(&session),
+            project.path().to_path_buf(),
+            MemoryConfig::default(),
+        );
+
+        assert_eq!(next_startup_event(&rx).await, expected_semantic_search());
+        let event = next_startup_event(&rx).await;
+        assert_eq!(event.subsystem, SessionSubsystem::Memory);
+        assert!(
+            matches!(
+                &event.status,
+                SubsystemStartupStatus::Unavailable { reason } if !reason.is_empty()
+            ),
+            "unexpected status: {:?}",
+            event.status
+        );
+        // Startup is over; memory stays off instead of pending.
+        assert!(!session.memory_starting());
+        assert!(session.memory_manager().is_none());
+    }
+
+    #[tokio::test]
+    async fn reports_nothing_once_the_session_is_gone() {
+        let project = tempfile::tempdir().expect("tempdir");
+        let (session, rx) = starting_session();
+        let weak = Arc::downgrade(&session);
+        drop(session);
+
+        spawn_subsystem_startup(weak, project.path().to_path_buf(), MemoryConfig::default());
+
+        // The session owned the only sender, so the channel closes unused.
+        let event = tokio::time::timeout(Duration::from_secs(5), rx.recv())
+            .await
+            .expect("channel closes");
+        assert!(event.is_err(), "unexpected event: {event:?}");
+    }
+}
@@ -80,6 +80,10 @@ impl SessionTask for GhostSnapshotTask {
                                     checkpoint: None,
                                 }])
                                 .await;
+                            session
+                                .session
+                                .record_turn_snapshot(ctx.sub_id.clone(), ghost_commit.clone())
+                                .await;
                             info!("ghost commit captured: {}", ghost_commit.id());
                         }
                         Ok(Err(err)) => match err {
@@ -23,6 +23,7 @@ use crate::protocol::EventMsg;
 use crate::protocol::TaskCompleteEvent;
 use crate::protocol::TurnAbortReason;
 use crate::protocol::TurnAbortedEvent;
+use crate::stall_watchdog;
 use crate::state::ActiveTurn;
 use crate::state::RunningTask;
 use crate::state::TaskKind;
@@ -105,6 +106,10 @@ impl Session {
         task: T,
     ) {
         self.abort_all_tasks(TurnAbortReason::Replaced).await;
+        self.state
+            .lock()
+            .await
+            .set_follow_up_suggestions(Vec::new());
 
         let task: Arc<dyn SessionTask> = Arc::new(task);
         let task_kind = task.kind();
@@ -120,6 +125,12 @@ impl Session {
             let task_cancellation_token = cancellation_token.child_token();
             tokio::spawn(async move {
                 let ctx_for_finish = Arc::clone(&ctx);
+                let watchdog = AbortOnDropHandle::new(tokio::spawn(stall_watchdog::watch_turn(
+                    session_ctx.clone_session(),
+                    Arc::clone(&ctx),
+                    ctx.client.config().stall_watchdog,
+                    task_cancellation_token.child_token(),
+                )));
                 let last_agent_message = task_for_run
                     .run(
                         Arc::clone(&session_ctx),
@@ -128,12 +139,18 @@ impl Session {
                         task_cancellation_token.child_token(),
                     )
                     .await;
+                drop(watchdog);
                 session_ctx.clone_session().flush_rollout().await;
                 if !task_cancellation_token.is_cancelled() {
                     // Emit completion uniformly from spawn site so all tasks share the same lifecycle.
                     let sess = session_ctx.clone_session();
-                    sess.on_task_finished(ctx_for_finish, last_agent_message)
+                    let answered = last_agent_message.is_some();
+                    sess.on_task_finished(Arc::clone(&ctx_for_finish), last_agent_message)
                         .await;
+                    sess.record_workspace_fingerprint(&ctx_for_finish).await;
+                    if task_kind == TaskKind::Regular && answered {
+                        sess.suggest_follow_ups(ctx_for_finish).await;
+                    }
                 }
                 done_clone.notify_waiters();
             })
@@ -168,6 +185,10 @@ impl Session {
             *active = None;
         }
         drop(active);
+        if let Some(plan_ready) = self.finish_plan_turn(last_agent_message.clone()).await {
+            self.send_event(turn_context.as_ref(), EventMsg::PlanReady(plan_ready))
+                .await;
+        }
         let event = EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message });
         self.send_event(turn_context.as_ref(), event).await;
     }
@@ -218,6 +239,7 @@ impl Session {
 
         let event = EventMsg::TurnAborted(TurnAbortedEvent { reason });
         self.send_event(task.turn_context.as_ref(), event).await;
+        self.record_workspace_fingerprint(&task.turn_context).await;
     }
 }
 
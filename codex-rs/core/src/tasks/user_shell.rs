@@ -22,11 +22,11 @@ use crate::protocol::ExecCommandBeginEvent;
 use crate::protocol::ExecCommandEndEvent;
 use crate::protocol::ExecCommandSource;
 use crate::protocol::SandboxPolicy;
-use crate::protocol::TaskStartedEvent;
+use crate::resource_limits::ResourceLimits;
 use crate::sandboxing::ExecEnv;
 use crate::state::TaskKind;
 use crate::tools::format_exec_output_str;
-use crate::user_shell_command::user_shell_command_record_item;
+use crate::user_shell_command::user_shell_command_context_item;
 
 use super::SessionTask;
 use super::SessionTaskContext;
@@ -57,9 +57,7 @@ impl SessionTask for UserShellCommandTask {
         _input: Vec<UserInput>,
         cancellation_token: CancellationToken,
     ) -> Option<String> {
-        let event = EventMsg::TaskStarted(TaskStartedEvent {
-            model_context_window: turn_context.client.get_model_context_window(),
-        });
+        let event = EventMsg::TaskStarted(turn_context.task_started_event());
         let session = session.clone_session();
         session.send_event(turn_context.as_ref(), event).await;
 
@@ -88,6 +86,7 @@ impl SessionTask for UserShellCommandTask {
                     parsed_cmd: parsed_cmd.clone(),
                     source: ExecCommandSource::UserShell,
                     interaction_input: None,
+                    approval_rule: None,
                 }),
             )
             .await;
@@ -103,6 +102,7 @@ impl SessionTask for UserShellCommandTask {
             with_escalated_permissions: None,
             justification: None,
             arg0: None,
+            resource_limits: ResourceLimits::default(),
         };
 
         let stdout_stream = Some(StdoutStream {
@@ -127,14 +127,13 @@ impl SessionTask for UserShellCommandTask {
                     duration: Duration::ZERO,
                     timed_out: false,
                 };
-                let output_items = [user_shell_command_record_item(
-                    &raw_command,
-                    &exec_output,
-                    &turn_context,
-                )];
-                session
-                    .record_conversation_items(turn_context.as_ref(), &output_items)
-                    .await;
+                if let Some(item) =
+                    user_shell_command_context_item(&raw_command, &exec_output, &turn_context)
+                {
+                    session
+                        .record_conversation_items(turn_context.as_ref(), &[item])
+                        .await;
+                }
                 session
                     .send_event(
                         turn_context.as_ref(),
@@ -183,14 +182,13 @@ impl SessionTask for UserShellCommandTask {
                     )
                     .await;
 
-                let output_items = [user_shell_command_record_item(
-                    &raw_command,
-                    &output,
-                    &turn_context,
-                )];
-                session
-                    .record_conversation_items(turn_context.as_ref(), &output_items)
-                    .await;
+                if let Some(item) =
+                    user_shell_command_context_item(&raw_command, &output, &turn_context)
+                {
+                    session
+                        .record_conversation_items(turn_context.as_ref(), &[item])
+                        .await;
+                }
             }
             Ok(Err(err)) => {
                 error!("user shell command failed: {err:?}");
@@ -227,14 +225,13 @@ impl SessionTask for UserShellCommandTask {
                         }),
                     )
                     .await;
-                let output_items = [user_shell_command_record_item(
-                    &raw_command,
-                    &exec_output,
-                    &turn_context,
-                )];
-                session
-                    .record_conversation_items(turn_context.as_ref(), &output_items)
-                    .await;
+                if let Some(item) =
+                    user_shell_command_context_item(&raw_command, &exec_output, &turn_context)
+                {
+                    session
+                        .record_conversation_items(turn_context.as_ref(), &[item])
+                        .await;
+                }
             }
         }
         None
@@ -1,8 +1,10 @@
+use crate::approval_rules::ApprovalRuleMatch;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::error::CodexErr;
 use crate::error::SandboxErr;
 use crate::exec::ExecToolCallOutput;
+use crate::exec_output::with_summary;
 use crate::function_tool::FunctionCallError;
 use crate::parse_command::parse_command;
 use crate::protocol::EventMsg;
@@ -16,10 +18,12 @@ use crate::protocol::TurnDiffEvent;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
 use codex_protocol::parse_command::ParsedCommand;
+use codex_protocol::protocol::AskForApproval;
 use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
+use tracing::warn;
 
 use super::format_exec_output_str;
 
@@ -67,6 +71,12 @@ pub(crate) async fn emit_exec_command_begin(
     interaction_input: Option<String>,
     process_id: Option<&str>,
 ) {
+    let approval_rule = match source {
+        ExecCommandSource::Agent | ExecCommandSource::UnifiedExecStartup => {
+            auto_approval_rule(ctx.turn, command)
+        }
+        ExecCommandSource::UserShell | ExecCommandSource::UnifiedExecInteraction => None,
+    };
     ctx.session
         .send_event(
             ctx.turn,
@@ -79,10 +89,26 @@ pub(crate) async fn emit_exec_command_begin(
                 parsed_cmd: parsed_cmd.to_vec(),
                 source,
                 interaction_input,
+                approval_rule,
             }),
         )
         .await;
 }
+/// Returns the `approvals.auto_approve` rule that spares `command` an approval
+/// prompt under the turn's approval policy.
+fn auto_approval_rule(turn: &TurnContext, command: &[String]) -> Option<String> {
+    if !matches!(
+        turn.approval_policy,
+        AskForApproval::OnRequest | AskForApproval::UnlessTrusted
+    ) {
+        return None;
+    }
+    match turn.client.config().approval_rules.evaluate(command)? {
+        ApprovalRuleMatch::AutoApprove(rule) => Some(rule),
+        ApprovalRuleMatch::Deny(_) => None,
+    }
+}
+
 // Concrete, allocation-free emitter: avoid trait objects and boxed futures.
 pub(crate) enum ToolEmitter {
     Shell {
@@ -259,17 +285,57 @@ impl ToolEmitter {
         self.emit(ctx, ToolEventStage::Begin).await;
     }
 
-    fn format_exec_output_for_model(
+    async fn format_exec_output_for_model(
         &self,
         output: &ExecToolCallOutput,
         ctx: ToolEventCtx<'_>,
     ) -> String {
-        match self {
+        let processed = self.post_process_output(output, ctx).await;
+        let output = processed.as_ref().unwrap_or(output);
+        let formatted = match self {
             Self::Shell { freeform: true, .. } => {
                 super::format_exec_output_for_model_freeform(output, ctx.turn.truncation_policy)
             }
             _ => super::format_exec_output_for_model_structured(output, ctx.turn.truncation_policy),
+        };
+        ctx.turn.client.config().redactor.redact(&formatted)
+    }
+
+    /// Applies `[exec_output]` to shell output. Returns `None` when the model
+    /// should see the output unchanged.
+    async fn post_process_output(
+        &self,
+        output: &ExecToolCallOutput,
+        ctx: ToolEventCtx<'_>,
+    ) -> Option<ExecToolCallOutput> {
+        let Self::Shell { command, .. } = self else {
+            return None;
+        };
+        let config = ctx.turn.client.config();
+        let processed = config
+            .exec_output
+            .process(command, &output.aggregated_output.text);
+        let text = if processed.summarize {
+            match ctx
+                .session
+                .summarize_exec_output(ctx.turn, &command.join(" "), &processed.full)
+                .await
+            {
+                Ok(summary) => with_summary(&summary, &processed.excerpt),
+                Err(err) => {
+                    warn!("failed to summarize command output: {err:#}");
+                    processed.excerpt
+                }
+            }
+        } else {
+            processed.excerpt
+        };
+        if text == output.aggregated_output.text {
+            return None;
         }
+        let mut output = output.clone();
+        output.aggregated_output.text = text;
+        Some(output)
     }
 
     pub async fn finish(
@@ -279,19 +345,35 @@ impl ToolEmitter {
     ) -> Result<String, FunctionCallError> {
         let (event, result) = match out {
             Ok(output) => {
-                let content = self.format_exec_output_for_model(&output, ctx);
+                let content = self.format_exec_output_for_model(&output, ctx).await;
                 let exit_code = output.exit_code;
                 let event = ToolEventStage::Success(output);
                 let result = if exit_code == 0 {
                     Ok(content)
                 } else {
-                    Err(FunctionCallError::RespondToModel(content))
+                    Err(FunctionCallError::RespondToModel(
+                        append_mistake_notes(ctx, &output, content).await,
+                    ))
                 };
                 (event, result)
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
-            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
-                let response = self.format_exec_output_for_model(&output, ctx);
+            | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output, .. }))) => {
+                let response = self.format_exec_output_for_model(&output, ctx).await;
+                let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
+                let result = Err(FunctionCallError::RespondToModel(response));
+                (event, result)
+            }
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::ResourceLimit {
+                violation,
+                mut output,
+            }))) => {
+                // Lead with the limit so it is not lost if the output is truncated.
+                output.aggregated_output.text = format!(
+                    "command exceeded its {violation} and was stopped\n{}",
+                    output.aggregated_output.text
+                );
+                let response = self.format_exec_output_for_model(&output, ctx).await;
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
@@ -320,6 +402,27 @@ impl ToolEmitter {
     }
 }
 
+/// Appends stored mistakes matching a failed command's error so the model
+/// sees how the same failure was fixed before.
+async fn append_mistake_notes(
+    ctx: ToolEventCtx<'_>,
+    output: &ExecToolCallOutput,
+    content: String,
+) -> String {
+    let Some(mm) = ctx.session.memory_manager() else {
+        return content;
+    };
+    let error_output = if output.stderr.text.trim().is_empty() {
+        &output.aggregated_output.text
+    } else {
+        &output.stderr.text
+    };
+    match mm.mistake_notes(error_output).await {
+        Some(notes) => format!("{content}\n\n{notes}"),
+        None => content,
+    }
+}
+
 struct ExecCommandInput<'a> {
     command: &'a [String],
     cwd: &'a Path,
@@ -453,6 +556,16 @@ async fn emit_patch_end(
     stderr: String,
     success: bool,
 ) {
+    // Even a failed patch may have written some files, so drop cached reads of all of them.
+    let touched = changes.iter().flat_map(|(path, change)| {
+        let moved_to = match change {
+            FileChange::Update { move_path, .. } => move_path.as_deref(),
+            FileChange::Add { .. } | FileChange::Delete { .. } => None,
+        };
+        std::iter::once(path.as_path()).chain(moved_to)
+    });
+    ctx.session.invalidate_file_reads(touched).await;
+
     // Extract memories from file edits (non-blocking)
     if success {
         if let Some(mm) = ctx.session.memory_manager() {
@@ -128,7 +128,7 @@ impl ToolHandler for ApplyPatchHandler {
                             &call_id,
                             Some(&tracker),
                         );
-                        let content = emitter.finish(event_ctx, out).await?;
+                        let content = apply.annotate_output(emitter.finish(event_ctx, out).await?);
                         Ok(ToolOutput::Function {
                             content,
                             content_items: None,
@@ -8,6 +8,8 @@ mod plan;
 mod read_file;
 mod request_user_input;
 mod shell;
+mod subagent;
+mod subagent_scratchpad;
 mod test_sync;
 mod unified_exec;
 mod view_image;
@@ -27,6 +29,8 @@ pub use read_file::ReadFileHandler;
 pub use request_user_input::RequestUserInputHandler;
 pub use shell::ShellCommandHandler;
 pub use shell::ShellHandler;
+pub use subagent::SubagentHandler;
+pub use subagent_scratchpad::SubagentScratchpadHandler;
 pub use test_sync::TestSyncHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
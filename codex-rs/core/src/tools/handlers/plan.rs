@@ -104,6 +104,7 @@ pub(crate) async fn handle_update_plan(
     _call_id: String,
 ) -> Result<String, FunctionCallError> {
     let args = parse_update_plan_arguments(&arguments)?;
+    session.record_plan_update(&args).await;
     session
         .send_event(turn_context, EventMsg::PlanUpdate(args))
         .await;
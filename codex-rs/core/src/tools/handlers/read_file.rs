@@ -8,6 +8,10 @@ use serde::Deserialize;
 
 use crate::codex::Session;
 use crate::function_tool::FunctionCallError;
+use crate::protocol::EventMsg;
+use crate::protocol::FileReadEvent;
+use crate::state::FileStamp;
+use crate::state::ReadCacheKey;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
@@ -98,7 +102,13 @@ impl ToolHandler for ReadFileHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { session, payload, .. } = invocation;
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -142,18 +152,51 @@ impl ToolHandler for ReadFileHandler {
             ));
         }
 
-        let collected = match mode {
-            ReadMode::Slice => slice::read(&path, offset, limit).await?,
-            ReadMode::Indentation => {
-                let indentation = indentation.unwrap_or_default();
-                indentation::read_block(&path, offset, limit, indentation).await?
+        let indentation = match mode {
+            ReadMode::Slice => None,
+            ReadMode::Indentation => Some(indentation.unwrap_or_default()),
+        };
+        let key = ReadCacheKey {
+            path: path.clone(),
+            range: cache_range(offset, limit, indentation.as_ref()),
+        };
+        // Stamp before reading so a write racing with the read is caught next time.
+        let stamp = FileStamp::of(&path).await;
+        let cached = match stamp {
+            Some(stamp) => session.cached_file_read(&key, stamp).await,
+            None => None,
+        };
+        let was_cached = cached.is_some();
+
+        let content = match cached {
+            Some(content) => content,
+            None => {
+                let collected = match indentation {
+                    None => slice::read(&path, offset, limit).await?,
+                    Some(indentation) => {
+                        indentation::read_block(&path, offset, limit, indentation).await?
+                    }
+                };
+                let content = collected.join("\n");
+                if let Some(stamp) = stamp {
+                    session.cache_file_read(key, stamp, content.clone()).await;
+                }
+                // Extract memories from file read (non-blocking)
+                extract_file_read_memories(Arc::clone(&session), &path, &content);
+                content
             }
         };
 
-        let content = collected.join("\n");
-
-        // Extract memories from file read (non-blocking)
-        extract_file_read_memories(session, &path, &content);
+        session
+            .send_event(
+                turn.as_ref(),
+                EventMsg::FileRead(FileReadEvent {
+                    call_id,
+                    path,
+                    cached: was_cached,
+                }),
+            )
+            .await;
 
         Ok(ToolOutput::Function {
             content,
@@ -163,6 +206,21 @@ impl ToolHandler for ReadFileHandler {
     }
 }
 
+/// Cache key component for everything besides the path that shapes the output.
+fn cache_range(offset: usize, limit: usize, indentation: Option<&IndentationArgs>) -> String {
+    match indentation {
+        None => format!("slice:{offset}:{limit}"),
+        Some(args) => format!(
+            "indentation:{offset}:{limit}:{:?}:{}:{}:{}:{:?}",
+            args.anchor_line,
+            args.max_levels,
+            args.include_siblings,
+            args.include_header,
+            args.max_lines
+        ),
+    }
+}
+
 /// Spawn background task to extract memories from file read.
 fn extract_file_read_memories(session: Arc<Session>, path: &PathBuf, content: &str) {
     if let Some(mm) = session.memory_manager() {
@@ -264,7 +264,7 @@ impl ShellHandler {
                             &call_id,
                             Some(&tracker),
                         );
-                        let content = emitter.finish(event_ctx, out).await?;
+                        let content = apply.annotate_output(emitter.finish(event_ctx, out).await?);
                         return Ok(ToolOutput::Function {
                             content,
                             content_items: None,
@@ -306,6 +306,7 @@ impl ShellHandler {
             justification: exec_params.justification.clone(),
             approval_requirement: create_approval_requirement_for_command(
                 &turn.exec_policy,
+                &turn.client.config().approval_rules,
                 &exec_params.command,
                 turn.approval_policy,
                 &turn.sandbox_policy,
@@ -1,9 +1,14 @@
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use codex_protocol::config_types::SandboxMode;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::Op;
 use codex_protocol::protocol::SessionSource;
@@ -18,11 +23,22 @@ use crate::codex::CodexSpawnOk;
 use crate::config::Config;
 use crate::config::types::SUBAGENT_LIMIT_HARD_CAP;
 use crate::config::types::SUBAGENT_LIMIT_MIN;
+use crate::config::types::SubagentRole;
 use crate::function_tool::FunctionCallError;
+use crate::model_family::derive_default_model_family;
+use crate::model_family::find_family_for_model;
 use crate::protocol::AskForApproval;
 use crate::protocol::EventMsg;
+use crate::protocol::FileChange;
+use crate::protocol::SandboxPolicy;
+use crate::protocol::SubagentAgentReport;
+use crate::protocol::SubagentConflict;
+use crate::protocol::SubagentReportEvent;
+use crate::protocol::SubagentScratchpadEvent;
 use crate::protocol::SubagentTaskStatus;
 use crate::protocol::SubagentTaskUpdateEvent;
+use crate::subagent_scratchpad;
+use crate::subagent_scratchpad::ScratchpadWriter;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
 use crate::tools::context::ToolPayload;
@@ -44,6 +60,9 @@ struct SubagentTask {
     cwd: Option<String>,
     #[serde(default)]
     timeout_ms: Option<i64>,
+    /// Name of a role preset from `[subagents.roles]`.
+    #[serde(default)]
+    role: Option<String>,
 }
 
 #[derive(Debug)]
@@ -52,6 +71,35 @@ struct SubagentResult {
     status: String,
     output: Option<String>,
     error: Option<String>,
+    changes: SubagentChanges,
+}
+
+/// Files a subagent edited and the diff of its turn.
+#[derive(Debug, Default)]
+struct SubagentChanges {
+    files: BTreeSet<PathBuf>,
+    unified_diff: Option<String>,
+}
+
+impl SubagentChanges {
+    fn record(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::PatchApplyEnd(ev) if ev.success => {
+                for (path, change) in &ev.changes {
+                    self.files.insert(path.clone());
+                    if let FileChange::Update {
+                        move_path: Some(dest),
+                        ..
+                    } = change
+                    {
+                        self.files.insert(dest.clone());
+                    }
+                }
+            }
+            EventMsg::TurnDiff(ev) => self.unified_diff = Some(ev.unified_diff.clone()),
+            _ => {}
+        }
+    }
 }
 
 const DEFAULT_CHILD_TIMEOUT: Option<Duration> = None;
@@ -103,11 +151,24 @@ impl ToolHandler for SubagentHandler {
             )));
         }
 
+        for task in &args.tasks {
+            if let Some(role) = &task.role
+                && !parent_config.subagent_roles.contains_key(role)
+            {
+                return Err(FunctionCallError::RespondToModel(unknown_role_message(
+                    role,
+                    &parent_config,
+                )));
+            }
+        }
+
+        let task_names: Vec<String> = args.tasks.iter().map(|task| task.name.clone()).collect();
         let session_source = turn.client.get_session_source();
         let parent_cwd = turn.cwd.clone();
         let call_id = invocation.call_id.clone();
         let session = session.clone();
         let turn = turn.clone();
+        subagent_scratchpad::open(&call_id);
 
         let futures = args.tasks.into_iter().enumerate().map(|(idx, task)| {
             let auth_manager = auth_manager.clone();
@@ -117,7 +178,7 @@ impl ToolHandler for SubagentHandler {
             let session = session.clone();
             let turn = turn.clone();
             let call_id = call_id.clone();
-            let agent_index = idx;
+            let agent_index = i64::try_from(idx).unwrap_or(i64::MAX);
             async move {
                 send_subagent_update(
                     &session,
@@ -125,6 +186,8 @@ impl ToolHandler for SubagentHandler {
                     SubagentTaskStatus::Running,
                     &call_id,
                     &task.name,
+                    agent_index,
+                    task.role.as_deref(),
                     None,
                 )
                 .await;
@@ -144,6 +207,8 @@ impl ToolHandler for SubagentHandler {
         });
 
         let results = join_all(futures).await;
+        let notes = subagent_scratchpad::close(&call_id);
+        let report = build_report(&call_id, &task_names, &results);
 
         let mut lines: Vec<String> = Vec::new();
         for result in results {
@@ -156,12 +221,56 @@ impl ToolHandler for SubagentHandler {
                     if let Some(err) = res.error {
                         lines.push(format!("error: {err}"));
                     }
+                    if !res.changes.files.is_empty() {
+                        let files = res
+                            .changes
+                            .files
+                            .iter()
+                            .map(|path| path.display().to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        lines.push(format!("edited: {files}"));
+                    }
                 }
                 Err(err) => {
                     lines.push(format!("task failed: {err}"));
                 }
             }
         }
+        for conflict in &report.conflicts {
+            let tasks = conflict
+                .agent_indices
+                .iter()
+                .filter_map(|idx| usize::try_from(*idx).ok())
+                .filter_map(|idx| task_names.get(idx))
+                .map(String::as_str)
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!(
+                "conflict: {} was edited by {tasks}",
+                conflict.path.display()
+            ));
+        }
+        if !notes.is_empty() {
+            lines.push("scratchpad:".to_string());
+            for note in &notes {
+                lines.push(format!("[{}] {}", note.task, note.text));
+            }
+            session
+                .send_event(
+                    turn.as_ref(),
+                    EventMsg::SubagentScratchpad(SubagentScratchpadEvent {
+                        call_id: call_id.clone(),
+                        notes,
+                    }),
+                )
+                .await;
+        }
+        if report.agents.iter().any(|agent| !agent.files.is_empty()) {
+            session
+                .send_event(turn.as_ref(), EventMsg::SubagentReport(report))
+                .await;
+        }
 
         Ok(ToolOutput::Function {
             content: lines.join("\n"),
@@ -193,7 +302,7 @@ async fn run_subagent_task(
     session: Arc<crate::codex::Session>,
     turn: Arc<crate::codex::TurnContext>,
     call_id: String,
-    agent_index: usize,
+    agent_index: i64,
 ) -> Result<SubagentResult, String> {
     let timeout_duration = task
         .timeout_ms
@@ -203,14 +312,29 @@ async fn run_subagent_task(
 
     let task_name = task.name.clone();
     let task_name_for_timeout = task_name.clone();
+    let role_name = task.role.clone();
     let resolved_cwd = resolve_child_cwd(&parent_cwd, task.cwd);
-
-    let child_config = make_child_config(parent_config, resolved_cwd);
+    let role = role_name
+        .as_ref()
+        .and_then(|name| parent_config.subagent_roles.get(name))
+        .cloned();
+
+    let writer = ScratchpadWriter {
+        call_id: call_id.clone(),
+        task: task_name.clone(),
+        agent_index,
+    };
+    let child_config = make_child_config(parent_config, resolved_cwd, role.as_ref(), writer);
 
     let fut_task_name = task_name.clone();
     let session_for_result = session.clone();
     let turn_for_result = turn.clone();
     let call_id_for_result = call_id.clone();
+    let role_for_updates = role_name.clone();
+    let cancel_token = session.register_subagent_task(&call_id, agent_index).await;
+    // Shared so edits are still reported when the task times out.
+    let changes = Arc::new(Mutex::new(SubagentChanges::default()));
+    let task_changes = Arc::clone(&changes);
 
     let fut = async move {
         let CodexSpawnOk { codex, .. } = Codex::spawn(
@@ -233,8 +357,29 @@ async fn run_subagent_task(
             .map_err(|err| format!("failed to submit subagent task: {err}"))?;
 
         let mut last_message: Option<String> = None;
-        while let Ok(event) = codex.rx_event.recv().await {
+        loop {
+            let event = tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    let _ = codex.submit(Op::Interrupt).await;
+                    let _ = codex.submit(Op::Shutdown).await;
+                    return Ok(SubagentResult {
+                        name: fut_task_name,
+                        status: "cancelled".to_string(),
+                        output: last_message,
+                        error: Some("cancelled by user".to_string()),
+                        changes: SubagentChanges::default(),
+                    });
+                }
+                event = codex.rx_event.recv() => match event {
+                    Ok(event) => event,
+                    Err(_) => break,
+                },
+            };
             let child_msg = event.msg.clone();
+            task_changes
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .record(&child_msg);
             match &child_msg {
                 crate::protocol::EventMsg::AgentMessage(ev) => {
                     last_message = Some(ev.message.clone());
@@ -244,6 +389,8 @@ async fn run_subagent_task(
                         SubagentTaskStatus::Running,
                         &call_id,
                         &fut_task_name,
+                        agent_index,
+                        role_for_updates.as_deref(),
                         Some(ev.message.clone()),
                     )
                     .await;
@@ -257,7 +404,7 @@ async fn run_subagent_task(
                         &turn,
                         &call_id,
                         &fut_task_name,
-                        Some(agent_index),
+                        agent_index,
                         child_msg.clone(),
                     )
                     .await;
@@ -277,7 +424,7 @@ async fn run_subagent_task(
                         &turn,
                         &call_id,
                         &fut_task_name,
-                        Some(agent_index),
+                        agent_index,
                         child_msg.clone(),
                     )
                     .await;
@@ -295,10 +442,11 @@ async fn run_subagent_task(
             status: "done".to_string(),
             output: last_message,
             error: None,
+            changes: SubagentChanges::default(),
         })
     };
 
-    let result: Result<SubagentResult, String> = if let Some(duration) = timeout_duration {
+    let mut result: Result<SubagentResult, String> = if let Some(duration) = timeout_duration {
         match timeout(duration, fut).await {
             Ok(inner) => inner,
             Err(_) => Ok(SubagentResult {
@@ -306,28 +454,27 @@ async fn run_subagent_task(
                 status: "timeout".to_string(),
                 output: None,
                 error: Some(format!("timed out after {}s", duration.as_secs())),
+                changes: SubagentChanges::default(),
             }),
         }
     } else {
         fut.await
     };
 
+    if let Ok(res) = &mut result {
+        res.changes = std::mem::take(&mut *changes.lock().unwrap_or_else(PoisonError::into_inner));
+    }
+
     let (status, summary, name) = match &result {
-        Ok(res) if res.status == "done" => (
-            SubagentTaskStatus::Done,
-            res.output.clone(),
-            res.name.clone(),
-        ),
-        Ok(res) if res.status == "timeout" => (
-            SubagentTaskStatus::Timeout,
-            res.error.clone().or_else(|| res.output.clone()),
-            res.name.clone(),
-        ),
-        Ok(res) => (
-            SubagentTaskStatus::Failed,
-            res.error.clone().or_else(|| res.output.clone()),
-            res.name.clone(),
-        ),
+        Ok(res) => {
+            let status = task_status(&res.status);
+            let summary = if status == SubagentTaskStatus::Done {
+                res.output.clone()
+            } else {
+                res.error.clone().or_else(|| res.output.clone())
+            };
+            (status, summary, res.name.clone())
+        }
         Err(err) => (
             SubagentTaskStatus::Failed,
             Some(err.clone()),
@@ -335,12 +482,17 @@ async fn run_subagent_task(
         ),
     };
 
+    session_for_result
+        .unregister_subagent_task(&call_id_for_result, agent_index)
+        .await;
     send_subagent_update(
         &session_for_result,
         &turn_for_result,
         status,
         &call_id_for_result,
         &name,
+        agent_index,
+        role_name.as_deref(),
         summary,
     )
     .await;
@@ -348,6 +500,61 @@ async fn run_subagent_task(
     result
 }
 
+fn task_status(status: &str) -> SubagentTaskStatus {
+    match status {
+        "done" => SubagentTaskStatus::Done,
+        "cancelled" => SubagentTaskStatus::Cancelled,
+        "timeout" => SubagentTaskStatus::Timeout,
+        _ => SubagentTaskStatus::Failed,
+    }
+}
+
+/// Collects each task's edits and the files more than one task touched.
+fn build_report(
+    call_id: &str,
+    task_names: &[String],
+    results: &[Result<SubagentResult, String>],
+) -> SubagentReportEvent {
+    let mut agents = Vec::with_capacity(results.len());
+    let mut editors: BTreeMap<&Path, Vec<i64>> = BTreeMap::new();
+    for (idx, result) in results.iter().enumerate() {
+        let agent_index = i64::try_from(idx).unwrap_or(i64::MAX);
+        let (status, files, unified_diff) = match result {
+            Ok(res) => {
+                for path in &res.changes.files {
+                    editors.entry(path).or_default().push(agent_index);
+                }
+                (
+                    task_status(&res.status),
+                    res.changes.files.iter().cloned().collect(),
+                    res.changes.unified_diff.clone(),
+                )
+            }
+            Err(_) => (SubagentTaskStatus::Failed, Vec::new(), None),
+        };
+        agents.push(SubagentAgentReport {
+            task: task_names.get(idx).cloned().unwrap_or_default(),
+            agent_index,
+            status,
+            files,
+            unified_diff,
+        });
+    }
+    let conflicts = editors
+        .into_iter()
+        .filter(|(_, agent_indices)| agent_indices.len() > 1)
+        .map(|(path, agent_indices)| SubagentConflict {
+            path: path.to_path_buf(),
+            agent_indices,
+        })
+        .collect();
+    SubagentReportEvent {
+        call_id: call_id.to_string(),
+        agents,
+        conflicts,
+    }
+}
+
 fn resolve_child_cwd(parent_cwd: &Path, maybe_cwd: Option<String>) -> PathBuf {
     if let Some(cwd) = maybe_cwd {
         let trimmed = cwd.trim();
@@ -363,24 +570,75 @@ fn resolve_child_cwd(parent_cwd: &Path, maybe_cwd: Option<String>) -> PathBuf {
     parent_cwd.to_path_buf()
 }
 
-fn make_child_config(parent: Arc<Config>, cwd: PathBuf) -> Arc<Config> {
+fn make_child_config(
+    parent: Arc<Config>,
+    cwd: PathBuf,
+    role: Option<&SubagentRole>,
+    scratchpad: ScratchpadWriter,
+) -> Arc<Config> {
     let mut config = (*parent).clone();
     config.cwd = cwd;
+    config.subagent_scratchpad = Some(scratchpad);
     config.approval_policy = AskForApproval::Never;
+    // The parent nests the child's turns under its own spans from the
+    // forwarded history items.
+    config.otel.trace_spans = false;
+    // The parent already notifies webhooks about its own turns, approvals
+    // and errors.
+    config.webhooks = Vec::new();
+    if let Some(role) = role {
+        if let Some(model) = &role.model {
+            config.model = model.clone();
+            config.model_family =
+                find_family_for_model(model).unwrap_or_else(|| derive_default_model_family(model));
+        }
+        if let Some(mode) = role.sandbox_mode {
+            config.sandbox_policy = restrict_sandbox_policy(&parent.sandbox_policy, mode);
+        }
+    }
     Arc::new(config)
 }
 
+/// Apply a role's sandbox mode without granting more access than the parent has.
+fn restrict_sandbox_policy(parent: &SandboxPolicy, mode: SandboxMode) -> SandboxPolicy {
+    match (mode, parent) {
+        (SandboxMode::ReadOnly, _) => SandboxPolicy::new_read_only_policy(),
+        (SandboxMode::WorkspaceWrite, SandboxPolicy::DangerFullAccess) => {
+            SandboxPolicy::new_workspace_write_policy()
+        }
+        _ => parent.clone(),
+    }
+}
+
+fn unknown_role_message(role: &str, config: &Config) -> String {
+    if config.subagent_roles.is_empty() {
+        return format!("unknown subagent role `{role}`: no roles are configured");
+    }
+    let available = config
+        .subagent_roles
+        .keys()
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("unknown subagent role `{role}` (available: {available})")
+}
+
+#[allow(clippy::too_many_arguments)]
 async fn send_subagent_update(
     session: &Arc<crate::codex::Session>,
     turn: &Arc<crate::codex::TurnContext>,
     status: SubagentTaskStatus,
     call_id: &str,
     task_name: &str,
+    agent_index: i64,
+    role: Option<&str>,
     summary: Option<String>,
 ) {
     let event = EventMsg::SubagentTaskUpdate(SubagentTaskUpdateEvent {
         call_id: call_id.to_string(),
         task: task_name.to_string(),
+        role: role.map(str::to_string),
+        agent_index: Some(agent_index),
         status,
         summary,
     });
@@ -392,14 +650,74 @@ async fn send_subagent_history_item(
     turn: &Arc<crate::codex::TurnContext>,
     call_id: &str,
     task_name: &str,
-    agent_index: Option<usize>,
+    agent_index: i64,
     event: crate::protocol::EventMsg,
 ) {
     let event = EventMsg::SubagentHistoryItem(crate::protocol::SubagentHistoryItemEvent {
         call_id: call_id.to_string(),
         task: task_name.to_string(),
-        agent_index: agent_index.and_then(|idx| i64::try_from(idx).ok()),
+        agent_index: Some(agent_index),
         event: Box::new(event),
     });
     session.send_event(turn.as_ref(), event).await;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn finished(name: &str, files: &[&str]) -> Result<SubagentResult, String> {
+        Ok(SubagentResult {
+            name: name.to_string(),
+            status: "done".to_string(),
+            output: None,
+            error: None,
+            changes: SubagentChanges {
+                files: files.iter().map(PathBuf::from).collect(),
+                unified_diff: Some(format!("diff from {name}")),
+            },
+        })
+    }
+
+    #[test]
+    fn report_lists_files_edited_by_several_tasks_as_conflicts() {
+        let names = vec!["api".to_string(), "ui".to_string(), "docs".to_string()];
+        let results = vec![
+            finished("api", &["/repo/src/lib.rs", "/repo/src/api.rs"]),
+            finished("ui", &["/repo/src/lib.rs"]),
+            Err("failed to spawn subagent".to_string()),
+        ];
+
+        let report = build_report("call-1", &names, &results);
+
+        assert_eq!(
+            report.agents[0].files,
+            vec![
+                PathBuf::from("/repo/src/api.rs"),
+                PathBuf::from("/repo/src/lib.rs")
+            ]
+        );
+        assert_eq!(
+            report.agents[1].unified_diff.as_deref(),
+            Some("diff from ui")
+        );
+        assert_eq!(
+            report.agents[2],
+            SubagentAgentReport {
+                task: "docs".to_string(),
+                agent_index: 2,
+                status: SubagentTaskStatus::Failed,
+                files: Vec::new(),
+                unified_diff: None,
+            }
+        );
+        assert_eq!(
+            report.conflicts,
+            vec![SubagentConflict {
+                path: PathBuf::from("/repo/src/lib.rs"),
+                agent_indices: vec![0, 1],
+            }]
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use codex_protocol::protocol::SubagentScratchpadNote;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::subagent_scratchpad;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::handlers::parse_arguments;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+/// Handles `scratchpad_append` and `scratchpad_read` in subagent sessions.
+pub struct SubagentScratchpadHandler;
+
+#[derive(Debug, Deserialize)]
+struct AppendArgs {
+    note: String,
+}
+
+#[async_trait]
+impl ToolHandler for SubagentScratchpadHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            tool_name,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "scratchpad handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let config = session
+            .clone_original_config()
+            .await
+            .map_err(|err| FunctionCallError::RespondToModel(err.to_string()))?;
+        let Some(writer) = &config.subagent_scratchpad else {
+            return Err(FunctionCallError::RespondToModel(
+                "the scratchpad is only available to subagents".to_string(),
+            ));
+        };
+
+        let content = match tool_name.as_str() {
+            "scratchpad_append" => {
+                let args: AppendArgs = parse_arguments(&arguments)?;
+                let count = subagent_scratchpad::append(writer, &args.note)
+                    .map_err(FunctionCallError::RespondToModel)?;
+                format!("Noted. The scratchpad holds {count} note(s).")
+            }
+            "scratchpad_read" => match subagent_scratchpad::read(&writer.call_id) {
+                Some(notes) => format_notes(&notes, writer.agent_index),
+                None => {
+                    return Err(FunctionCallError::RespondToModel(
+                        "the scratchpad is closed because the subagent run finished".to_string(),
+                    ));
+                }
+            },
+            other => {
+                return Err(FunctionCallError::RespondToModel(format!(
+                    "unsupported scratchpad tool: {other}"
+                )));
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+fn format_notes(notes: &[SubagentScratchpadNote], own_index: i64) -> String {
+    if notes.is_empty() {
+        return "The scratchpad is empty.".to_string();
+    }
+    notes
+        .iter()
+        .map(|note| {
+            let author = if note.agent_index == own_index {
+                "you".to_string()
+            } else {
+                note.task.clone()
+            };
+            format!("[{author}] {}", note.text)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -1,5 +1,6 @@
 use std::path::PathBuf;
 
+use crate::features::Feature;
 use crate::function_tool::FunctionCallError;
 use crate::is_safe_command::is_known_safe_command;
 use crate::protocol::EventMsg;
@@ -16,6 +17,7 @@ use crate::tools::events::ToolEventStage;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
 use crate::unified_exec::ExecCommandRequest;
+use crate::unified_exec::STREAMING_RUNNING_HINT;
 use crate::unified_exec::UnifiedExecContext;
 use crate::unified_exec::UnifiedExecResponse;
 use crate::unified_exec::UnifiedExecSessionManager;
@@ -121,8 +123,17 @@ impl ToolHandler for UnifiedExecHandler {
 
         let manager: &UnifiedExecSessionManager = &session.services.unified_exec_manager;
         let context = UnifiedExecContext::new(session.clone(), turn.clone(), call_id.clone());
+        let config = turn.client.config();
+        let streaming = config.features.enabled(Feature::StreamingExec);
+        let stream_yield_time = |yield_time_ms: u64| {
+            if streaming {
+                yield_time_ms.min(config.exec_output.stream_interval_ms())
+            } else {
+                yield_time_ms
+            }
+        };
 
-        let response = match tool_name.as_str() {
+        let (mut response, process_id) = match tool_name.as_str() {
             "exec_command" => {
                 let args: ExecCommandArgs = serde_json::from_str(&arguments).map_err(|err| {
                     FunctionCallError::RespondToModel(format!(
@@ -174,12 +185,12 @@ impl ToolHandler for UnifiedExecHandler {
                 );
                 emitter.emit(event_ctx, ToolEventStage::Begin).await;
 
-                manager
+                let response = manager
                     .exec_command(
                         ExecCommandRequest {
                             command,
-                            process_id,
-                            yield_time_ms,
+                            process_id: process_id.clone(),
+                            yield_time_ms: stream_yield_time(yield_time_ms),
                             max_output_tokens,
                             workdir,
                             with_escalated_permissions,
@@ -190,7 +201,8 @@ impl ToolHandler for UnifiedExecHandler {
                     .await
                     .map_err(|err| {
                         FunctionCallError::RespondToModel(format!("exec_command failed: {err:?}"))
-                    })?
+                    })?;
+                (response, process_id)
             }
             "write_stdin" => {
                 let args: WriteStdinArgs = serde_json::from_str(&arguments).map_err(|err| {
@@ -198,18 +210,20 @@ impl ToolHandler for UnifiedExecHandler {
                         "failed to parse write_stdin arguments: {err:?}"
                     ))
                 })?;
-                manager
+                let process_id = args.session_id.to_string();
+                let response = manager
                     .write_stdin(WriteStdinRequest {
                         call_id: &call_id,
-                        process_id: &args.session_id.to_string(),
+                        process_id: &process_id,
                         input: &args.chars,
-                        yield_time_ms: args.yield_time_ms,
+                        yield_time_ms: stream_yield_time(args.yield_time_ms),
                         max_output_tokens: args.max_output_tokens,
                     })
                     .await
                     .map_err(|err| {
                         FunctionCallError::RespondToModel(format!("write_stdin failed: {err:?}"))
-                    })?
+                    })?;
+                (response, process_id)
             }
             other => {
                 return Err(FunctionCallError::RespondToModel(format!(
@@ -230,7 +244,21 @@ impl ToolHandler for UnifiedExecHandler {
                 .await;
         }
 
-        let content = format_response(&response);
+        response.output = config.exec_output.clean(&response.output);
+        if streaming {
+            response.output = manager.stream_budgets.take(
+                &process_id,
+                &response.output,
+                config.exec_output.stream_budget_tokens(),
+                response.process_id.is_none(),
+            );
+            if response.process_id.is_none() {
+                manager.stream_budgets.release(&process_id);
+            }
+        }
+        let content = config
+            .redactor
+            .redact(&format_response(&response, streaming));
 
         Ok(ToolOutput::Function {
             content,
@@ -245,7 +273,7 @@ fn get_command(args: &ExecCommandArgs) -> Vec<String> {
     shell.derive_exec_args(&args.cmd, args.login)
 }
 
-fn format_response(response: &UnifiedExecResponse) -> String {
+fn format_response(response: &UnifiedExecResponse, streaming: bool) -> String {
     let mut sections = Vec::new();
 
     if !response.chunk_id.is_empty() {
@@ -262,6 +290,9 @@ fn format_response(response: &UnifiedExecResponse) -> String {
     if let Some(process_id) = &response.process_id {
         // Training still uses "session ID".
         sections.push(format!("Process running with session ID {process_id}"));
+        if streaming {
+            sections.push(STREAMING_RUNNING_HINT.to_string());
+        }
     }
 
     if let Some(original_token_count) = response.original_token_count {
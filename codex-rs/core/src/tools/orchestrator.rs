@@ -85,16 +85,19 @@ impl ToolOrchestrator {
                     call_id: &tool_ctx.call_id,
                     retry_reason: reason,
                     risk,
+                    network_destinations: Vec::new(),
                 };
                 let decision = tool.start_approval_async(req, approval_ctx).await;
 
-                otel.tool_decision(otel_tn, otel_ci, decision, otel_user.clone());
+                otel.tool_decision(otel_tn, otel_ci, decision.clone(), otel_user.clone());
 
                 match decision {
                     ReviewDecision::Denied | ReviewDecision::Abort => {
                         return Err(ToolError::Rejected("rejected by user".to_string()));
                     }
-                    ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {}
+                    ReviewDecision::Approved
+                    | ReviewDecision::ApprovedPartially { .. }
+                    | ReviewDecision::ApprovedForSession => {}
                 }
                 already_approved = true;
             }
@@ -123,10 +126,14 @@ impl ToolOrchestrator {
                 // We have a successful initial result
                 Ok(out)
             }
-            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
+            Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
+                output,
+                network_destinations,
+            }))) => {
                 if !tool.escalate_on_failure() {
                     return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
                         output,
+                        network_destinations,
                     })));
                 }
                 // Under `Never` or `OnRequest`, do not retry without sandbox; surface a concise
@@ -134,6 +141,7 @@ impl ToolOrchestrator {
                 if !tool.wants_no_sandbox_approval(approval_policy) {
                     return Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
                         output,
+                        network_destinations,
                     })));
                 }
 
@@ -144,6 +152,7 @@ impl ToolOrchestrator {
                     if let Some(metadata) = req.sandbox_retry_data() {
                         let err = SandboxErr::Denied {
                             output: output.clone(),
+                            network_destinations: network_destinations.clone(),
                         };
                         let friendly = get_error_message_ui(&CodexErr::Sandbox(err));
                         let failure_summary = format!("failed in sandbox: {friendly}");
@@ -159,23 +168,27 @@ impl ToolOrchestrator {
                             .await;
                     }
 
-                    let reason_msg = build_denial_reason_from_output(output.as_ref());
+                    let reason_msg =
+                        build_denial_reason_from_output(output.as_ref(), &network_destinations);
                     let approval_ctx = ApprovalCtx {
                         session: tool_ctx.session,
                         turn: turn_ctx,
                         call_id: &tool_ctx.call_id,
                         retry_reason: Some(reason_msg),
                         risk,
+                        network_destinations,
                     };
 
                     let decision = tool.start_approval_async(req, approval_ctx).await;
-                    otel.tool_decision(otel_tn, otel_ci, decision, otel_user);
+                    otel.tool_decision(otel_tn, otel_ci, decision.clone(), otel_user);
 
                     match decision {
                         ReviewDecision::Denied | ReviewDecision::Abort => {
                             return Err(ToolError::Rejected("rejected by user".to_string()));
                         }
-                        ReviewDecision::Approved | ReviewDecision::ApprovedForSession => {}
+                        ReviewDecision::Approved
+                        | ReviewDecision::ApprovedPartially { .. }
+                        | ReviewDecision::ApprovedForSession => {}
                     }
                 }
 
@@ -195,8 +208,15 @@ impl ToolOrchestrator {
     }
 }
 
-fn build_denial_reason_from_output(_output: &ExecToolCallOutput) -> String {
+fn build_denial_reason_from_output(
+    _output: &ExecToolCallOutput,
+    network_destinations: &[String],
+) -> String {
     // Keep approval reason terse and stable for UX/tests, but accept the
     // output so we can evolve heuristics later without touching call sites.
-    "command failed; retry without sandbox?".to_string()
+    if network_destinations.is_empty() {
+        "command failed; retry without sandbox?".to_string()
+    } else {
+        "network destination not in the sandbox allowlist; retry without sandbox?".to_string()
+    }
 }
@@ -10,6 +10,8 @@ use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::error::CodexErr;
 use crate::function_tool::FunctionCallError;
+use crate::protocol::EventMsg;
+use crate::protocol::ToolCallCancelledEvent;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolPayload;
 use crate::tools::router::ToolCall;
@@ -57,9 +59,25 @@ impl ToolCallRuntime {
 
         let handle: AbortOnDropHandle<Result<ResponseInputItem, FunctionCallError>> =
             AbortOnDropHandle::new(tokio::spawn(async move {
-                tokio::select! {
-                    _ = cancellation_token.cancelled() => {
-                        let secs = started.elapsed().as_secs_f32().max(0.1);
+                let call_token = session
+                    .register_tool_call(&call.call_id, &cancellation_token)
+                    .await;
+                let result = tokio::select! {
+                    _ = call_token.cancelled() => {
+                        let elapsed = started.elapsed();
+                        // Only this call was cancelled; the turn keeps going.
+                        if !cancellation_token.is_cancelled() {
+                            session
+                                .send_event(
+                                    turn.as_ref(),
+                                    EventMsg::ToolCallCancelled(ToolCallCancelledEvent {
+                                        call_id: call.call_id.clone(),
+                                        duration: elapsed,
+                                    }),
+                                )
+                                .await;
+                        }
+                        let secs = elapsed.as_secs_f32().max(0.1);
                         Ok(Self::aborted_response(&call, secs))
                     },
                     res = async {
@@ -70,10 +88,17 @@ impl ToolCallRuntime {
                         };
 
                         router
-                            .dispatch_tool_call(session, turn, tracker, call.clone())
+                            .dispatch_tool_call(
+                                Arc::clone(&session),
+                                Arc::clone(&turn),
+                                tracker,
+                                call.clone(),
+                            )
                             .await
                     } => res,
-                }
+                };
+                session.unregister_tool_call(&call.call_id).await;
+                result
             }));
 
         async move {
@@ -101,13 +101,22 @@ impl Approvable<ShellRequest> for ShellRuntime {
             .clone()
             .or_else(|| req.justification.clone());
         let risk = ctx.risk.clone();
+        let network_destinations = ctx.network_destinations.clone();
         let session = ctx.session;
         let turn = ctx.turn;
         let call_id = ctx.call_id.to_string();
         Box::pin(async move {
             with_cached_approval(&session.services, key, move || async move {
                 session
-                    .request_command_approval(turn, call_id, command, cwd, reason, risk)
+                    .request_command_approval(
+                        turn,
+                        call_id,
+                        command,
+                        cwd,
+                        reason,
+                        risk,
+                        network_destinations,
+                    )
                     .await
             })
             .await
@@ -149,9 +158,15 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
             req.with_escalated_permissions,
             req.justification.clone(),
         )?;
-        let env = attempt
+        let mut env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
+        env.resource_limits = ctx
+            .turn
+            .client
+            .config()
+            .resource_limits
+            .for_policy(attempt.policy);
         let out = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
             .await
             .map_err(ToolError::Codex)?;
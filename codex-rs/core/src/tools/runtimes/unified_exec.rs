@@ -122,10 +122,19 @@ impl Approvable<UnifiedExecRequest> for UnifiedExecRuntime<'_> {
             .clone()
             .or_else(|| req.justification.clone());
         let risk = ctx.risk.clone();
+        let network_destinations = ctx.network_destinations.clone();
         Box::pin(async move {
             with_cached_approval(&session.services, key, || async move {
                 session
-                    .request_command_approval(turn, call_id, command, cwd, reason, risk)
+                    .request_command_approval(
+                        turn,
+                        call_id,
+                        command,
+                        cwd,
+                        reason,
+                        risk,
+                        network_destinations,
+                    )
                     .await
             })
             .await
@@ -157,10 +166,23 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecSession> for UnifiedExecRunt
         &mut self,
         req: &UnifiedExecRequest,
         attempt: &SandboxAttempt<'_>,
-        _ctx: &ToolCtx<'_>,
+        ctx: &ToolCtx<'_>,
     ) -> Result<UnifiedExecSession, ToolError> {
+        let resource_limits = ctx
+            .turn
+            .client
+            .config()
+            .resource_limits
+            .for_policy(attempt.policy);
+        // PTYs offer no `pre_exec` hook, so the limits are installed by a `sh`
+        // wrapper. It runs inside the sandbox, keeping the sandbox's own argv0
+        // dispatch intact.
+        #[cfg(unix)]
+        let command = resource_limits.wrap_command(req.command.clone());
+        #[cfg(not(unix))]
+        let command = req.command.clone();
         let spec = build_command_spec(
-            &req.command,
+            &command,
             &req.cwd,
             &req.env,
             ExecExpiration::DefaultTimeout,
@@ -168,18 +190,22 @@ impl<'a> ToolRuntime<UnifiedExecRequest, UnifiedExecSession> for UnifiedExecRunt
             req.justification.clone(),
         )
         .map_err(|_| ToolError::Rejected("missing command line for PTY".to_string()))?;
-        let exec_env = attempt
+        let mut exec_env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
+        exec_env.resource_limits = resource_limits;
         self.manager
-            .open_session_with_exec_env(&exec_env)
+            .open_session_with_exec_env(&exec_env, attempt.policy)
             .await
             .map_err(|err| match err {
-                UnifiedExecError::SandboxDenied { output, .. } => {
-                    ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
-                        output: Box::new(output),
-                    }))
-                }
+                UnifiedExecError::SandboxDenied {
+                    output,
+                    network_destinations,
+                    ..
+                } => ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied {
+                    output: Box::new(output),
+                    network_destinations,
+                })),
                 other => ToolError::Rejected(other.to_string()),
             })
     }
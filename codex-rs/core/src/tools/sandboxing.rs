@@ -84,6 +84,8 @@ pub(crate) struct ApprovalCtx<'a> {
     pub call_id: &'a str,
     pub retry_reason: Option<String>,
     pub risk: Option<SandboxCommandAssessment>,
+    /// Destinations the sandbox's network allowlist refused.
+    pub network_destinations: Vec<String>,
 }
 
 // Specifies what tool orchestrator should do with a given tool call.
@@ -1,6 +1,7 @@
 use crate::agent::AgentRole;
 use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
+use crate::config::types::SubagentRole;
 use crate::features::Feature;
 use crate::features::Features;
 use crate::tools::handlers::PLAN_TOOL;
@@ -28,6 +29,11 @@ pub(crate) struct ToolsConfig {
     pub web_search_mode: Option<WebSearchMode>,
     pub collab_tools: bool,
     pub collaboration_modes_tools: bool,
+    /// Whether the session is a subagent with a scratchpad to share.
+    pub subagent_scratchpad: bool,
+    /// The `[subagents.roles]` presets when the session may spawn subagents
+    /// with `subagent_run`; `None` for subagents themselves.
+    pub subagent_roles: Option<BTreeMap<String, SubagentRole>>,
     pub experimental_supported_tools: Vec<String>,
 }
 
@@ -50,7 +56,8 @@ impl ToolsConfig {
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
-        } else if features.enabled(Feature::UnifiedExec) {
+        } else if features.enabled(Feature::UnifiedExec) || features.enabled(Feature::StreamingExec)
+        {
             // If ConPTY not supported (for old Windows versions), fallback on ShellCommand.
             if codex_utils_pty::conpty_supported() {
                 ConfigShellToolType::UnifiedExec
@@ -79,6 +86,8 @@ impl ToolsConfig {
             web_search_mode: *web_search_mode,
             collab_tools: include_collab_tools,
             collaboration_modes_tools: include_collaboration_modes_tools,
+            subagent_scratchpad: false,
+            subagent_roles: None,
             experimental_supported_tools: model_info.experimental_supported_tools.clone(),
         }
     }
@@ -96,6 +105,14 @@ pub(crate) enum JsonSchema {
         #[serde(skip_serializing_if = "Option::is_none")]
         description: Option<String>,
     },
+    /// A string limited to `values`.
+    #[serde(rename = "string", skip_deserializing)]
+    StringEnum {
+        #[serde(rename = "enum")]
+        values: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+    },
     /// MCP schema allows "number" | "integer" for Number
     #[serde(alias = "integer")]
     Number {
@@ -638,6 +655,120 @@ fn create_close_agent_tool() -> ToolSpec {
     })
 }
 
+fn create_subagent_tool(roles: &BTreeMap<String, SubagentRole>) -> ToolSpec {
+    let mut task_properties = BTreeMap::from([
+        (
+            "name".to_string(),
+            JsonSchema::String {
+                description: Some("Short unique name for the task.".to_string()),
+            },
+        ),
+        (
+            "prompt".to_string(),
+            JsonSchema::String {
+                description: Some("Complete instructions for the subagent.".to_string()),
+            },
+        ),
+        (
+            "cwd".to_string(),
+            JsonSchema::String {
+                description: Some(
+                    "Working directory, relative to the current one. Defaults to the current one."
+                        .to_string(),
+                ),
+            },
+        ),
+        (
+            "timeout_ms".to_string(),
+            JsonSchema::Number {
+                description: Some("How long the task may run before it is stopped.".to_string()),
+            },
+        ),
+    ]);
+    let mut description = "Run tasks in parallel, each in a subagent with its own conversation, and return every subagent's result once all of them finish.".to_string();
+    if !roles.is_empty() {
+        task_properties.insert(
+            "role".to_string(),
+            JsonSchema::StringEnum {
+                values: roles.keys().cloned().collect(),
+                description: Some(
+                    "Role preset for the task. Defaults to this session's model and sandbox."
+                        .to_string(),
+                ),
+            },
+        );
+        description.push_str("\n\nAvailable roles:");
+        for (name, role) in roles {
+            match &role.description {
+                Some(role_description) => {
+                    description.push_str(&format!("\n- {name}: {role_description}"));
+                }
+                None => description.push_str(&format!("\n- {name}")),
+            }
+        }
+    }
+
+    let task = JsonSchema::Object {
+        properties: task_properties,
+        required: Some(vec!["name".to_string(), "prompt".to_string()]),
+        additional_properties: Some(false.into()),
+    };
+    ToolSpec::Function(ResponsesApiTool {
+        name: "subagent_run".to_string(),
+        description,
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties: BTreeMap::from([(
+                "tasks".to_string(),
+                JsonSchema::Array {
+                    items: Box::new(task),
+                    description: Some("Tasks to run, one subagent each.".to_string()),
+                },
+            )]),
+            required: Some(vec!["tasks".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_scratchpad_append_tool() -> ToolSpec {
+    let properties = BTreeMap::from([(
+        "note".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "A finding the other subagents should know, such as where something lives or what has been ruled out."
+                    .to_string(),
+            ),
+        },
+    )]);
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "scratchpad_append".to_string(),
+        description: "Share a note with the other subagents working on the same request. Notes cannot be changed once written."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["note".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_scratchpad_read_tool() -> ToolSpec {
+    ToolSpec::Function(ResponsesApiTool {
+        name: "scratchpad_read".to_string(),
+        description: "Read the notes the subagents working on the same request have shared so far. Check it before searching so work is not repeated."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
 fn create_test_sync_tool() -> ToolSpec {
     let barrier_properties = BTreeMap::from([
         (
@@ -1228,6 +1359,8 @@ pub(crate) fn build_specs(
     use crate::tools::handlers::RequestUserInputHandler;
     use crate::tools::handlers::ShellCommandHandler;
     use crate::tools::handlers::ShellHandler;
+    use crate::tools::handlers::SubagentHandler;
+    use crate::tools::handlers::SubagentScratchpadHandler;
     use crate::tools::handlers::TestSyncHandler;
     use crate::tools::handlers::UnifiedExecHandler;
     use crate::tools::handlers::ViewImageHandler;
@@ -1367,6 +1500,19 @@ pub(crate) fn build_specs(
         builder.register_handler("close_agent", collab_handler);
     }
 
+    if let Some(roles) = &config.subagent_roles {
+        builder.push_spec(create_subagent_tool(roles));
+        builder.register_handler("subagent_run", Arc::new(SubagentHandler));
+    }
+
+    if config.subagent_scratchpad {
+        let scratchpad_handler = Arc::new(SubagentScratchpadHandler);
+        builder.push_spec_with_parallel_support(create_scratchpad_append_tool(), true);
+        builder.push_spec_with_parallel_support(create_scratchpad_read_tool(), true);
+        builder.register_handler("scratchpad_append", scratchpad_handler.clone());
+        builder.register_handler("scratchpad_read", scratchpad_handler);
+    }
+
     if let Some(mcp_tools) = mcp_tools {
         let mut entries: Vec<(String, mcp_types::Tool)> = mcp_tools.into_iter().collect();
         entries.sort_by(|a, b| a.0.cmp(&b.0));
@@ -1393,6 +1539,7 @@ mod tests {
     use crate::config::test_config;
     use crate::models_manager::manager::ModelsManager;
     use crate::tools::registry::ConfiguredToolSpec;
+    use codex_protocol::config_types::SandboxMode;
     use mcp_types::ToolInputSchema;
     use pretty_assertions::assert_eq;
 
@@ -1453,6 +1600,7 @@ mod tests {
         match schema {
             JsonSchema::Boolean { description }
             | JsonSchema::String { description }
+            | JsonSchema::StringEnum { description, .. }
             | JsonSchema::Number { description } => {
                 *description = None;
             }
@@ -1567,6 +1715,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn scratchpad_tools_are_only_given_to_subagents() {
+        let config = test_config();
+        let model_info = ModelsManager::construct_model_info_offline("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_info: &model_info,
+            features: &features,
+            web_search_mode: Some(WebSearchMode::Cached),
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(
+            !tools.iter().any(|t| t.spec.name() == "scratchpad_read"),
+            "scratchpad tools should only be available to subagents"
+        );
+
+        tools_config.subagent_scratchpad = true;
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert_contains_tool_names(&tools, &["scratchpad_append", "scratchpad_read"]);
+    }
+
+    #[test]
+    fn subagent_tool_offers_configured_roles() {
+        let roles = BTreeMap::from([
+            (
+                "builder".to_string(),
+                SubagentRole {
+                    description: None,
+                    model: None,
+                    sandbox_mode: Some(SandboxMode::WorkspaceWrite),
+                },
+            ),
+            (
+                "researcher".to_string(),
+                SubagentRole {
+                    description: Some("Read-only investigation".to_string()),
+                    model: None,
+                    sandbox_mode: Some(SandboxMode::ReadOnly),
+                },
+            ),
+        ]);
+
+        let ToolSpec::Function(tool) = create_subagent_tool(&roles) else {
+            panic!("subagent_run should be a function tool");
+        };
+        assert!(
+            tool.description
+                .ends_with("Available roles:\n- builder\n- researcher: Read-only investigation"),
+            "unexpected description: {}",
+            tool.description
+        );
+        let parameters = serde_json::to_value(&tool.parameters).expect("serialize schema");
+        assert_eq!(
+            parameters["properties"]["tasks"]["items"]["properties"]["role"],
+            json!({
+                "type": "string",
+                "enum": ["builder", "researcher"],
+                "description": "Role preset for the task. Defaults to this session's model and sandbox.",
+            })
+        );
+
+        let ToolSpec::Function(tool) = create_subagent_tool(&BTreeMap::new()) else {
+            panic!("subagent_run should be a function tool");
+        };
+        let parameters = serde_json::to_value(&tool.parameters).expect("serialize schema");
+        assert_eq!(
+            parameters["properties"]["tasks"]["items"]["properties"]["role"],
+            JsonValue::Null
+        );
+    }
+
     #[test]
     fn request_user_input_requires_collaboration_modes_feature() {
         let config = test_config();
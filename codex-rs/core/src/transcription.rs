@@ -0,0 +1,297 @@
+//! Speech-to-text for voice input.
+//!
+//! Front ends record audio themselves and hand [`transcribe`] a WAV file; the
+//! text comes back for the user to review in the composer before sending.
+//! Recordings are transcribed locally with whisper.cpp when a model is
+//! configured in `[transcription]`, and otherwise (or when whisper.cpp fails
+//! and `engine = "auto"`) with the OpenAI transcription API.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::auth::AuthMode;
+use crate::auth::CodexAuth;
+use crate::auth::read_openai_api_key_from_env;
+use crate::config::Config;
+use crate::config::types::TranscriptionEngine;
+use crate::config::types::TranscriptionToml;
+use crate::default_client::build_reqwest_client;
+
+const DEFAULT_WHISPER_CPP_BINARY: &str = "whisper-cli";
+const DEFAULT_API_BASE_URL: &str = "https://api.openai.com/v1";
+const DEFAULT_API_MODEL: &str = "whisper-1";
+const TRANSCRIPTION_TIMEOUT: Duration = Duration::from_secs(120);
+const MULTIPART_BOUNDARY: &str = "codex-transcription-boundary";
+
+/// Engine that produced a transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptionBackend {
+    WhisperCpp,
+    Api,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    pub text: String,
+    pub backend: TranscriptionBackend,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TranscriptionError {
+    #[error("audio must be a WAV file")]
+    NotWav,
+    #[error(
+        "no transcription engine is available: set transcription.whisper_cpp_model or an OpenAI API key"
+    )]
+    NoEngine,
+    #[error("whisper.cpp failed: {0}")]
+    WhisperCpp(String),
+    #[error("transcription API failed: {0}")]
+    Api(String),
+}
+
+/// Transcribe a WAV recording with the engine selected in `[transcription]`.
+pub async fn transcribe(
+    config: &Config,
+    auth: Option<CodexAuth>,
+    wav: &[u8],
+) -> Result<Transcript, TranscriptionError> {
+    if !is_wav(wav) {
+        return Err(TranscriptionError::NotWav);
+    }
+    let settings = &config.transcription;
+    let api_key = api_key(auth);
+    match select_backends(settings, api_key.is_some()).as_slice() {
+        [] => Err(TranscriptionError::NoEngine),
+        [TranscriptionBackend::WhisperCpp, TranscriptionBackend::Api] => {
+            match transcribe_whisper_cpp(settings, wav).await {
+                Ok(text) => Ok(text),
+                Err(err) => {
+                    tracing::warn!("{err}; falling back to the transcription API");
+                    transcribe_api(settings, api_key.as_deref().unwrap_or_default(), wav).await
+                }
+            }
+        }
+        [TranscriptionBackend::WhisperCpp, ..] => transcribe_whisper_cpp(settings, wav).await,
+        [TranscriptionBackend::Api, ..] => {
+            transcribe_api(settings, api_key.as_deref().unwrap_or_default(), wav).await
+        }
+    }
+}
+
+/// Engines to try, in order.
+fn select_backends(settings: &TranscriptionToml, has_api_key: bool) -> Vec<TranscriptionBackend> {
+    let local = settings.whisper_cpp_model.is_some();
+    match settings.engine {
+        TranscriptionEngine::WhisperCpp if local => vec![TranscriptionBackend::WhisperCpp],
+        TranscriptionEngine::Api if has_api_key => vec![TranscriptionBackend::Api],
+        TranscriptionEngine::Auto => [
+            local.then_some(TranscriptionBackend::WhisperCpp),
+            has_api_key.then_some(TranscriptionBackend::Api),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        TranscriptionEngine::WhisperCpp | TranscriptionEngine::Api => Vec::new(),
+    }
+}
+
+/// The transcription API needs an API key; ChatGPT sign-in tokens are not
+/// accepted there.
+fn api_key(auth: Option<CodexAuth>) -> Option<String> {
+    read_openai_api_key_from_env().or_else(|| {
+        auth.filter(|auth| auth.mode == AuthMode::ApiKey)
+            .and_then(|auth| auth.api_key)
+    })
+}
+
+fn is_wav(bytes: &[u8]) -> bool {
+    bytes.len() > 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WAVE"
+}
+
+async fn transcribe_whisper_cpp(
+    settings: &TranscriptionToml,
+    wav: &[u8],
+) -> Result<Transcript, TranscriptionError> {
+    let model = settings
+        .whisper_cpp_model
+        .as_deref()
+        .ok_or(TranscriptionError::NoEngine)?;
+    let binary = settings
+        .whisper_cpp_binary
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_WHISPER_CPP_BINARY));
+    let input = tempfile::Builder::new()
+        .suffix(".wav")
+        .tempfile()
+        .map_err(|err| TranscriptionError::WhisperCpp(err.to_string()))?;
+    tokio::fs::write(input.path(), wav)
+        .await
+        .map_err(|err| TranscriptionError::WhisperCpp(err.to_string()))?;
+
+    let output = timeout(
+        TRANSCRIPTION_TIMEOUT,
+        whisper_cpp_command(&binary, model, input.path(), settings.language.as_deref()).output(),
+    )
+    .await
+    .map_err(|_| TranscriptionError::WhisperCpp("timed out".to_string()))?
+    .map_err(|err| TranscriptionError::WhisperCpp(format!("{}: {err}", binary.display())))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(TranscriptionError::WhisperCpp(
+            stderr
+                .lines()
+                .last()
+                .unwrap_or("exited with an error")
+                .to_string(),
+        ));
+    }
+    Ok(Transcript {
+        text: clean_transcript(&String::from_utf8_lossy(&output.stdout)),
+        backend: TranscriptionBackend::WhisperCpp,
+    })
+}
+
+fn whisper_cpp_command(
+    binary: &Path,
+    model: &Path,
+    input: &Path,
+    language: Option<&str>,
+) -> Command {
+    let mut command = Command::new(binary);
+    command
+        .arg("--model")
+        .arg(model)
+        .arg("--file")
+        .arg(input)
+        .arg("--no-timestamps")
+        .arg("--no-prints")
+        .arg("--language")
+        .arg(language.unwrap_or("auto"))
+        .kill_on_drop(true);
+    command
+}
+
+/// whisper.cpp prints one segment per line with leading spaces.
+fn clean_transcript(raw: &str) -> String {
+    raw.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[derive(Deserialize)]
+struct ApiTranscription {
+    text: String,
+}
+
+async fn transcribe_api(
+    settings: &TranscriptionToml,
+    api_key: &str,
+    wav: &[u8],
+) -> Result<Transcript, TranscriptionError> {
+    let base_url = settings
+        .api_base_url
+        .as_deref()
+        .unwrap_or(DEFAULT_API_BASE_URL)
+        .trim_end_matches('/');
+    let model = settings.api_model.as_deref().unwrap_or(DEFAULT_API_MODEL);
+    let response = build_reqwest_client()
+        .post(format!("{base_url}/audio/transcriptions"))
+        .bearer_auth(api_key)
+        .header(
+            reqwest::header::CONTENT_TYPE,
+            format!("multipart/form-data; boundary={MULTIPART_BOUNDARY}"),
+        )
+        .body(multipart_body(model, settings.language.as_deref(), wav))
+        .timeout(TRANSCRIPTION_TIMEOUT)
+        .send()
+        .await
+        .map_err(|err| TranscriptionError::Api(err.to_string()))?;
+    let status = response.status();
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(TranscriptionError::Api(format!("{status}: {body}")));
+    }
+    let transcription: ApiTranscription = response
+        .json()
+        .await
+        .map_err(|err| TranscriptionError::Api(err.to_string()))?;
+    Ok(Transcript {
+        text: transcription.text.trim().to_string(),
+        backend: TranscriptionBackend::Api,
+    })
+}
+
+fn multipart_body(model: &str, language: Option<&str>, wav: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(wav.len() + 512);
+    let mut field = |name: &str, value: &str| {
+        body.extend_from_slice(
+            format!(
+                "--{MULTIPART_BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            )
+            .as_bytes(),
+        );
+    };
+    field("model", model);
+    if let Some(language) = language {
+        field("language", language);
+    }
+    body.extend_from_slice(
+        format!(
+            "--{MULTIPART_BOUNDARY}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"audio.wav\"\r\nContent-Type: audio/wav\r\n\r\n"
+        )
+        .as_bytes(),
+    );
+    body.extend_from_slice(wav);
+    body.extend_from_slice(format!("\r\n--{MULTIPART_BOUNDARY}--\r\n").as_bytes());
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn auto_prefers_whisper_cpp_and_falls_back_to_the_api() {
+        let local = TranscriptionToml {
+            whisper_cpp_model: Some(PathBuf::from("ggml-base.en.bin")),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            select_backends(&local, true),
+            vec![TranscriptionBackend::WhisperCpp, TranscriptionBackend::Api]
+        );
+        assert_eq!(
+            select_backends(&TranscriptionToml::default(), true),
+            vec![TranscriptionBackend::Api]
+        );
+        assert_eq!(
+            select_backends(&TranscriptionToml::default(), false),
+            Vec::new()
+        );
+        let api_only = TranscriptionToml {
+            engine: TranscriptionEngine::Api,
+            ..local
+        };
+        assert_eq!(select_backends(&api_only, false), Vec::new());
+    }
+
+    #[test]
+    fn whisper_cpp_segments_are_joined() {
+        assert_eq!(
+            clean_transcript("\n Fix the failing test\n and rerun it.\n"),
+            "Fix the failing test and rerun it."
+        );
+        assert!(is_wav(b"RIFF\x24\x00\x00\x00WAVEfmt "));
+        assert!(!is_wav(b"ID3\x04\x00\x00\x00\x00\x00\x00\x00\x00"));
+    }
+}
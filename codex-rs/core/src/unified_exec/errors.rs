@@ -16,6 +16,8 @@ pub(crate) enum UnifiedExecError {
     SandboxDenied {
         message: String,
         output: ExecToolCallOutput,
+        /// Destinations the network allowlist proxy refused.
+        network_destinations: Vec<String>,
     },
 }
 
@@ -24,7 +26,15 @@ impl UnifiedExecError {
         Self::CreateSession { message }
     }
 
-    pub(crate) fn sandbox_denied(message: String, output: ExecToolCallOutput) -> Self {
-        Self::SandboxDenied { message, output }
+    pub(crate) fn sandbox_denied(
+        message: String,
+        output: ExecToolCallOutput,
+        network_destinations: Vec<String>,
+    ) -> Self {
+        Self::SandboxDenied {
+            message,
+            output,
+            network_destinations,
+        }
     }
 }
@@ -20,6 +20,7 @@
 //! concerns remain isolated here. The implementation is split between:
 //! - `session.rs`: PTY session lifecycle + output buffering.
 //! - `session_manager.rs`: orchestration (approvals, sandboxing, reuse) and request handling.
+//! - `streaming.rs`: per-session output budgets for `streaming_exec`.
 
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -37,9 +38,12 @@ use crate::codex::TurnContext;
 mod errors;
 mod session;
 mod session_manager;
+mod streaming;
 
 pub(crate) use errors::UnifiedExecError;
 pub(crate) use session::UnifiedExecSession;
+pub(crate) use streaming::STREAMING_RUNNING_HINT;
+use streaming::StreamBudgets;
 
 pub(crate) const MIN_YIELD_TIME_MS: u64 = 50;
 pub(crate) const MAX_YIELD_TIME_MS: u64 = 30_000;
@@ -100,6 +104,7 @@ pub(crate) struct UnifiedExecResponse {
 pub(crate) struct UnifiedExecSessionManager {
     sessions: Mutex<HashMap<String, SessionEntry>>,
     used_session_ids: Mutex<HashSet<String>>,
+    pub(crate) stream_budgets: StreamBudgets,
 }
 
 struct SessionEntry {
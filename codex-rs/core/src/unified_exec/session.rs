@@ -14,6 +14,7 @@ use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StreamOutput;
 use crate::exec::is_likely_sandbox_denied;
+use crate::sandboxing::network_proxy::NetworkProxy;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use codex_utils_pty::ExecCommandSession;
@@ -81,6 +82,7 @@ pub(crate) struct UnifiedExecSession {
     cancellation_token: CancellationToken,
     output_task: JoinHandle<()>,
     sandbox_type: SandboxType,
+    network_proxy: Option<NetworkProxy>,
 }
 
 impl UnifiedExecSession {
@@ -121,9 +123,16 @@ impl UnifiedExecSession {
             cancellation_token,
             output_task,
             sandbox_type,
+            network_proxy: None,
         }
     }
 
+    /// Keeps the proxy enforcing the network allowlist running until the
+    /// session ends.
+    fn set_network_proxy(&mut self, network_proxy: Option<NetworkProxy>) {
+        self.network_proxy = network_proxy;
+    }
+
     pub(super) fn writer_sender(&self) -> mpsc::Sender<Vec<u8>> {
         self.session.writer_sender()
     }
@@ -178,7 +187,13 @@ impl UnifiedExecSession {
             timed_out: false,
         };
 
-        if is_likely_sandbox_denied(self.sandbox_type(), &exec_output) {
+        let network_destinations = self
+            .network_proxy
+            .as_ref()
+            .map(NetworkProxy::blocked_destinations)
+            .unwrap_or_default();
+        let network_blocked = !network_destinations.is_empty() && exit_code != 0;
+        if network_blocked || is_likely_sandbox_denied(self.sandbox_type(), &exec_output) {
             let snippet = formatted_truncate_text(
                 &aggregated_text,
                 TruncationPolicy::Tokens(UNIFIED_EXEC_OUTPUT_MAX_TOKENS),
@@ -188,7 +203,11 @@ impl UnifiedExecSession {
             } else {
                 snippet
             };
-            return Err(UnifiedExecError::sandbox_denied(message, exec_output));
+            return Err(UnifiedExecError::sandbox_denied(
+                message,
+                exec_output,
+                network_destinations,
+            ));
         }
 
         Ok(())
@@ -197,13 +216,15 @@ impl UnifiedExecSession {
     pub(super) async fn from_spawned(
         spawned: SpawnedPty,
         sandbox_type: SandboxType,
+        network_proxy: Option<NetworkProxy>,
     ) -> Result<Self, UnifiedExecError> {
         let SpawnedPty {
             session,
             output_rx,
             mut exit_rx,
         } = spawned;
-        let managed = Self::new(session, output_rx, sandbox_type);
+        let mut managed = Self::new(session, output_rx, sandbox_type);
+        managed.set_network_proxy(network_proxy);
 
         let exit_ready = match exit_rx.try_recv() {
             Ok(_) | Err(TryRecvError::Closed) => true,
@@ -19,8 +19,10 @@ use crate::exec_policy::create_approval_requirement_for_command;
 use crate::protocol::BackgroundEventEvent;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandSource;
+use crate::protocol::SandboxPolicy;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxPermissions;
+use crate::sandboxing::network_proxy::NetworkProxy;
 use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
 use crate::tools::events::ToolEventFailure;
@@ -540,22 +542,31 @@ impl UnifiedExecSessionManager {
     pub(crate) async fn open_session_with_exec_env(
         &self,
         env: &ExecEnv,
+        sandbox_policy: &SandboxPolicy,
     ) -> Result<UnifiedExecSession, UnifiedExecError> {
         let (program, args) = env
             .command
             .split_first()
             .ok_or(UnifiedExecError::MissingCommandLine)?;
 
+        let network_proxy = NetworkProxy::start_for(sandbox_policy, env.sandbox)
+            .await
+            .map_err(|err| UnifiedExecError::create_session(err.to_string()))?;
+        let mut process_env = env.env.clone();
+        if let Some(proxy) = &network_proxy {
+            proxy.apply_env(&mut process_env);
+        }
+
         let spawned = codex_utils_pty::spawn_pty_process(
             program,
             args,
             env.cwd.as_path(),
-            &env.env,
+            &process_env,
             &env.arg0,
         )
         .await
         .map_err(|err| UnifiedExecError::create_session(err.to_string()))?;
-        UnifiedExecSession::from_spawned(spawned, env.sandbox).await
+        UnifiedExecSession::from_spawned(spawned, env.sandbox, network_proxy).await
     }
 
     pub(super) async fn open_session_with_sandbox(
@@ -577,6 +588,7 @@ impl UnifiedExecSessionManager {
             justification,
             create_approval_requirement_for_command(
                 &context.turn.exec_policy,
+                &context.turn.client.config().approval_rules,
                 command,
                 context.turn.approval_policy,
                 &context.turn.sandbox_policy,
@@ -0,0 +1,148 @@
+//! Output budgets for `streaming_exec`.
+//!
+//! With the feature enabled, `exec_command` and `write_stdin` yield after at
+//! most `exec_output.stream_interval_ms`, so a long command reaches the model
+//! in chunks it can react to: keep polling, send Ctrl-C, or move on. Every
+//! session may send up to `exec_output.stream_budget_tokens` tokens over all
+//! of its chunks; later chunks only say how much output was withheld, except
+//! the last one, whose tail always gets through so the model sees how the
+//! command ended. The transcript still shows everything.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use crate::truncate::TruncationPolicy;
+use crate::truncate::approx_bytes_for_tokens;
+use crate::truncate::approx_token_count;
+use crate::truncate::truncate_text;
+
+/// Appended to chunks of a command that is still running.
+pub(crate) const STREAMING_RUNNING_HINT: &str = "The command is still running. Call write_stdin with empty chars to read more output, or with chars \"\\u0003\" to interrupt it.";
+
+/// Tokens from the end of a command's final chunk sent even when the budget
+/// is used up; that is where test runners and compilers print their summary.
+const FINAL_CHUNK_TAIL_TOKENS: usize = 500;
+
+#[derive(Default)]
+pub(crate) struct StreamBudgets {
+    spent: Mutex<HashMap<String, usize>>,
+}
+
+impl StreamBudgets {
+    /// Trims `output` to what is left of `budget` for `process_id` and
+    /// records the tokens it uses. `exited` marks the command's final chunk,
+    /// which keeps its tail past the budget.
+    pub(crate) fn take(
+        &self,
+        process_id: &str,
+        output: &str,
+        budget: usize,
+        exited: bool,
+    ) -> String {
+        let mut spent = self
+            .spent
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let used = spent.entry(process_id.to_string()).or_default();
+        let remaining = budget.saturating_sub(*used);
+        let tokens = approx_token_count(output);
+        if tokens <= remaining {
+            *used += tokens;
+            return output.to_string();
+        }
+
+        *used = budget;
+        if exited && remaining < FINAL_CHUNK_TAIL_TOKENS {
+            let tail = tail(output, FINAL_CHUNK_TAIL_TOKENS);
+            if tail.len() == output.len() {
+                return output.to_string();
+            }
+            let withheld = tokens.saturating_sub(approx_token_count(tail));
+            return format!(
+                "[{withheld} tokens withheld: the streaming output budget of {budget} tokens is used up]\n{tail}"
+            );
+        }
+        if remaining == 0 {
+            return format!(
+                "[{tokens} tokens withheld: the streaming output budget of {budget} tokens is used up]"
+            );
+        }
+        let kept = truncate_text(output, TruncationPolicy::Tokens(remaining));
+        format!(
+            "{kept}\n[streaming output budget of {budget} tokens used up; later output is withheld]"
+        )
+    }
+
+    /// Forgets `process_id` once its command has exited.
+    pub(crate) fn release(&self, process_id: &str) {
+        self.spent
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(process_id);
+    }
+}
+
+/// The end of `text` within about `tokens` tokens, starting on a line
+/// boundary when there is one.
+fn tail(text: &str, tokens: usize) -> &str {
+    let max_bytes = approx_bytes_for_tokens(tokens);
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut start = text.len() - max_bytes;
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    match text[start..].find('\n') {
+        Some(newline) if start + newline + 1 < text.len() => &text[start + newline + 1..],
+        _ => &text[start..],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn output_past_the_budget_is_withheld() {
+        let budgets = StreamBudgets::default();
+
+        assert_eq!(
+            budgets.take("1000", "abcdefghijkl", 3, false),
+            "abcdefghijkl"
+        );
+        assert_eq!(
+            budgets.take("1000", "mnopqrst", 3, false),
+            "[2 tokens withheld: the streaming output budget of 3 tokens is used up]"
+        );
+        assert_eq!(budgets.take("1001", "abcd", 3, false), "abcd");
+
+        budgets.release("1000");
+        assert_eq!(budgets.take("1000", "abcd", 3, false), "abcd");
+    }
+
+    #[test]
+    fn final_chunk_keeps_its_tail_past_the_budget() {
+        let budgets = StreamBudgets::default();
+        assert_eq!(
+            budgets.take("1000", "abcdefghijkl", 3, false),
+            "abcdefghijkl"
+        );
+
+        let summary = "FAILED tests::parser (exit 101)";
+        assert_eq!(budgets.take("1000", summary, 3, true), summary);
+
+        let noisy = format!("{}\n{summary}", "progress line\n".repeat(1_000));
+        let last = budgets.take("1000", &noisy, 3, true);
+        assert!(
+            last.starts_with('[') && last.contains("tokens withheld"),
+            "{last}"
+        );
+        assert!(last.ends_with(summary), "{last}");
+        assert!(
+            approx_token_count(&last) <= FINAL_CHUNK_TAIL_TOKENS + 20,
+            "{last}"
+        );
+    }
+}
@@ -4,6 +4,7 @@ use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseItem;
 
 use crate::codex::TurnContext;
+use crate::config::types::UserShellContext;
 use crate::exec::ExecToolCallOutput;
 use crate::tools::format_exec_output_str;
 
@@ -25,6 +26,7 @@ fn format_user_shell_command_body(
     command: &str,
     exec_output: &ExecToolCallOutput,
     turn_context: &TurnContext,
+    include_output: bool,
 ) -> String {
     let mut sections = Vec::new();
     sections.push("<command>".to_string());
@@ -33,11 +35,13 @@ fn format_user_shell_command_body(
     sections.push("<result>".to_string());
     sections.push(format!("Exit code: {}", exec_output.exit_code));
     sections.push(format_duration_line(exec_output.duration));
-    sections.push("Output:".to_string());
-    sections.push(format_exec_output_str(
-        exec_output,
-        turn_context.truncation_policy,
-    ));
+    if include_output {
+        sections.push("Output:".to_string());
+        sections.push(format_exec_output_str(
+            exec_output,
+            turn_context.truncation_policy,
+        ));
+    }
     sections.push("</result>".to_string());
     sections.join("\n")
 }
@@ -47,7 +51,17 @@ pub fn format_user_shell_command_record(
     exec_output: &ExecToolCallOutput,
     turn_context: &TurnContext,
 ) -> String {
-    let body = format_user_shell_command_body(command, exec_output, turn_context);
+    let body = format_user_shell_command_body(command, exec_output, turn_context, true);
+    format!("{USER_SHELL_COMMAND_OPEN}\n{body}\n{USER_SHELL_COMMAND_CLOSE}")
+}
+
+/// Like [`format_user_shell_command_record`], without the command's output.
+pub fn format_user_shell_command_summary(
+    command: &str,
+    exec_output: &ExecToolCallOutput,
+    turn_context: &TurnContext,
+) -> String {
+    let body = format_user_shell_command_body(command, exec_output, turn_context, false);
     format!("{USER_SHELL_COMMAND_OPEN}\n{body}\n{USER_SHELL_COMMAND_CLOSE}")
 }
 
@@ -65,6 +79,30 @@ pub fn user_shell_command_record_item(
     }
 }
 
+/// The history item for a user shell command as configured by
+/// `user_shell_context`, or `None` when the model should not see it.
+pub fn user_shell_command_context_item(
+    command: &str,
+    exec_output: &ExecToolCallOutput,
+    turn_context: &TurnContext,
+) -> Option<ResponseItem> {
+    match turn_context.client.config().user_shell_context {
+        UserShellContext::Output => Some(user_shell_command_record_item(
+            command,
+            exec_output,
+            turn_context,
+        )),
+        UserShellContext::Summary => Some(ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format_user_shell_command_summary(command, exec_output, turn_context),
+            }],
+        }),
+        UserShellContext::None => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,6 +142,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn summary_omits_output() {
+        let exec_output = ExecToolCallOutput {
+            exit_code: 2,
+            stdout: StreamOutput::new("secret build log".to_string()),
+            stderr: StreamOutput::new(String::new()),
+            aggregated_output: StreamOutput::new("secret build log".to_string()),
+            duration: Duration::from_secs(3),
+            timed_out: false,
+        };
+        let (_, turn_context) = make_session_and_context();
+        assert_eq!(
+            format_user_shell_command_summary("make", &exec_output, &turn_context),
+            "<user_shell_command>\n<command>\nmake\n</command>\n<result>\nExit code: 2\nDuration: 3.0000 seconds\n</result>\n</user_shell_command>"
+        );
+    }
+
     #[test]
     fn uses_aggregated_output_over_streams() {
         let exec_output = ExecToolCallOutput {
@@ -0,0 +1,180 @@
+//! Webhook notifications for session events, configured with `[[webhooks]]`.
+//!
+//! Finished turns, approval requests and errors are posted as JSON to every
+//! webhook subscribed to the event. The payload carries a one-line `text`
+//! (also sent as `content`) so Slack and Discord incoming webhooks can take
+//! it as is, after secret masking with the session's `Redactor`. With a
+//! secret, the body is signed with HMAC-SHA256 in the
+//! `X-Codex-Signature-256: sha256=<hex>` header. Delivery is fire-and-forget.
+
+use std::path::Path;
+use std::time::Duration;
+
+use hmac::Hmac;
+use hmac::Mac;
+use serde::Serialize;
+use sha2::Sha256;
+use tracing::warn;
+
+use crate::config::types::WebhookConfig;
+use crate::config::types::WebhookEvent;
+use crate::default_client::build_reqwest_client;
+use crate::parse_command::shlex_join;
+use crate::protocol::EventMsg;
+use crate::redaction::Redactor;
+
+const SIGNATURE_HEADER: &str = "X-Codex-Signature-256";
+const EVENT_HEADER: &str = "X-Codex-Event";
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_SUMMARY_CHARS: usize = 300;
+
+#[derive(Debug, Serialize)]
+struct WebhookPayload<'a> {
+    event: WebhookEvent,
+    thread_id: String,
+    turn_id: &'a str,
+    cwd: &'a Path,
+    text: &'a str,
+    content: &'a str,
+}
+
+/// The webhook event `msg` triggers, with a one-line summary of it.
+pub(crate) fn classify(msg: &EventMsg) -> Option<(WebhookEvent, String)> {
+    match msg {
+        EventMsg::TaskComplete(event) => {
+            let summary = match event.last_agent_message.as_deref().map(str::trim) {
+                Some(message) if !message.is_empty() => format!("Turn complete: {message}"),
+                _ => "Turn complete.".to_string(),
+            };
+            Some((WebhookEvent::TurnComplete, summary))
+        }
+        EventMsg::ExecApprovalRequest(event) => Some((
+            WebhookEvent::ApprovalNeeded,
+            format!("Approval needed to run `{}`", shlex_join(&event.command)),
+        )),
+        EventMsg::ApplyPatchApprovalRequest(event) => Some((
+            WebhookEvent::ApprovalNeeded,
+            format!("Approval needed to edit {} file(s)", event.changes.len()),
+        )),
+        EventMsg::Error(event) => Some((WebhookEvent::Error, format!("Error: {}", event.message))),
+        _ => None,
+    }
+}
+
+/// Posts `event` to each of `webhooks` subscribed to it, in the background.
+pub(crate) fn deliver(
+    webhooks: &[WebhookConfig],
+    redactor: &Redactor,
+    event: WebhookEvent,
+    thread_id: String,
+    turn_id: &str,
+    cwd: &Path,
+    summary: &str,
+) {
+    let text = redactor.redact(&format!(
+        "[{}] {}",
+        cwd.file_name()
+            .map(|name| name.to_string_lossy())
+            .unwrap_or_else(|| cwd.to_string_lossy()),
+        truncate_summary(summary)
+    ));
+    let payload = WebhookPayload {
+        event,
+        thread_id,
+        turn_id,
+        cwd,
+        text: &text,
+        content: &text,
+    };
+    let body = match serde_json::to_vec(&payload) {
+        Ok(body) => body,
+        Err(err) => {
+            warn!("failed to serialize webhook payload: {err}");
+            return;
+        }
+    };
+    let event_name = event.to_string();
+
+    for webhook in webhooks
+        .iter()
+        .filter(|webhook| webhook.events.contains(&event))
+    {
+        let mut request = build_reqwest_client()
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, &event_name)
+            .timeout(DELIVERY_TIMEOUT);
+        if let Some(secret) = &webhook.secret {
+            request = request.header(SIGNATURE_HEADER, signature(secret, &body));
+        } else if let Some(var) = &webhook.secret_env_var {
+            match std::env::var(var) {
+                Ok(secret) => {
+                    request = request.header(SIGNATURE_HEADER, signature(&secret, &body));
+                }
+                Err(_) => warn!("webhook secret variable {var} is not set; sending unsigned"),
+            }
+        }
+        let request = request.body(body.clone());
+        let url = webhook.url.clone();
+        tokio::spawn(async move {
+            match request.send().await {
+                Ok(response) if !response.status().is_success() => {
+                    warn!("webhook {url} responded with {}", response.status());
+                }
+                Ok(_) => {}
+                Err(err) => warn!("failed to deliver webhook to {url}: {err}"),
+            }
+        });
+    }
+}
+
+fn truncate_summary(summary: &str) -> String {
+    let line = summary.lines().next().unwrap_or_default();
+    if line.chars().count() > MAX_SUMMARY_CHARS || line.len() < summary.trim_end().len() {
+        let truncated: String = line.chars().take(MAX_SUMMARY_CHARS).collect();
+        format!("{truncated}…")
+    } else {
+        line.to_string()
+    }
+}
+
+/// `sha256=<hex>` HMAC-SHA256 of `body` keyed with `secret`.
+fn signature(secret: &str, body: &[u8]) -> String {
+    #[expect(clippy::expect_used)]
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    let hex: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("sha256={hex}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::TaskCompleteEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn signature_matches_rfc_4231() {
+        assert_eq!(
+            signature("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn turn_complete_summary_uses_the_first_line_of_the_last_message() {
+        let (event, summary) = classify(&EventMsg::TaskComplete(TaskCompleteEvent {
+            last_agent_message: Some("Tests pass.\n\nDetails follow.".to_string()),
+        }))
+        .expect("turn complete is delivered");
+
+        assert_eq!(event, WebhookEvent::TurnComplete);
+        assert_eq!(truncate_summary(&summary), "Turn complete: Tests pass.…");
+    }
+}
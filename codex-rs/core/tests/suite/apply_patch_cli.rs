@@ -555,6 +555,9 @@ async fn apply_patch_cli_rejects_path_traversal_outside_workspace(
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        allow_write: vec![],
+        deny_write: vec![],
+        network_allowlist: vec![],
     };
     harness
         .submit_with_policy(
@@ -610,6 +613,9 @@ async fn apply_patch_cli_rejects_move_path_traversal_outside_workspace(
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        allow_write: vec![],
+        deny_write: vec![],
+        network_allowlist: vec![],
     };
     harness
         .submit_with_policy("attempt move traversal via apply_patch", sandbox_policy)
@@ -59,7 +59,6 @@ async fn model_selects_expected_tools() {
             "read_mcp_resource".to_string(),
             "update_plan".to_string(),
             "semantic_search".to_string(),
-            "subagent_run".to_string(),
             "view_image".to_string()
         ],
         "codex-mini-latest should expose the local shell tool",
@@ -76,7 +75,6 @@ async fn model_selects_expected_tools() {
             "update_plan".to_string(),
             "apply_patch".to_string(),
             "semantic_search".to_string(),
-            "subagent_run".to_string(),
             "view_image".to_string()
         ],
         "gpt-5-codex should expose the apply_patch tool",
@@ -93,7 +91,6 @@ async fn model_selects_expected_tools() {
             "update_plan".to_string(),
             "apply_patch".to_string(),
             "semantic_search".to_string(),
-            "subagent_run".to_string(),
             "view_image".to_string()
         ],
         "gpt-5.1-codex should expose the apply_patch tool",
@@ -109,7 +106,6 @@ async fn model_selects_expected_tools() {
             "read_mcp_resource".to_string(),
             "update_plan".to_string(),
             "semantic_search".to_string(),
-            "subagent_run".to_string(),
             "view_image".to_string()
         ],
         "gpt-5 should expose the apply_patch tool",
@@ -126,7 +122,6 @@ async fn model_selects_expected_tools() {
             "update_plan".to_string(),
             "apply_patch".to_string(),
             "semantic_search".to_string(),
-            "subagent_run".to_string(),
             "view_image".to_string()
         ],
         "gpt-5.1 should expose the apply_patch tool",
@@ -143,7 +138,6 @@ async fn model_selects_expected_tools() {
             "update_plan".to_string(),
             "apply_patch".to_string(),
             "semantic_search".to_string(),
-            "subagent_run".to_string(),
             "view_image".to_string()
         ],
         "exp-5.1 should expose the apply_patch tool",
@@ -160,7 +160,6 @@ async fn prompt_tools_are_consistent_across_requests() -> anyhow::Result<()> {
         "update_plan",
         "apply_patch",
         "semantic_search",
-        "subagent_run",
         "view_image",
     ];
     let body0 = req1.single_request().body_json();
@@ -309,6 +308,9 @@ async fn overrides_turn_context_but_keeps_cached_prefix_and_key_constant() -> an
                 network_access: true,
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
+                allow_write: vec![],
+                deny_write: vec![],
+                network_allowlist: vec![],
             }),
             model: Some("o3".to_string()),
             effort: Some(Some(ReasoningEffort::High)),
@@ -499,6 +501,9 @@ async fn per_turn_overrides_keep_cached_prefix_and_key_constant() -> anyhow::Res
                 network_access: true,
                 exclude_tmpdir_env_var: true,
                 exclude_slash_tmp: true,
+                allow_write: vec![],
+                deny_write: vec![],
+                network_allowlist: vec![],
             },
             model: "o3".to_string(),
             effort: Some(ReasoningEffort::High),
@@ -541,3 +541,51 @@ async fn checkpoint_create_restore_and_list() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn checkpoint_diff_reports_changes_between_checkpoints() -> Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let harness = undo_harness().await?;
+    init_git_repo(harness.cwd())?;
+
+    let tracked = harness.path("tracked.txt");
+    fs::write(&tracked, "original\n")?;
+    git(harness.cwd(), &["add", "tracked.txt"])?;
+    git(harness.cwd(), &["commit", "-m", "seed tracked file"])?;
+
+    let codex = Arc::clone(&harness.test().codex);
+    for (name, contents) in [("before", "original\n"), ("after", "modified\n")] {
+        fs::write(&tracked, contents)?;
+        codex
+            .submit(Op::CreateCheckpoint {
+                name: name.to_string(),
+            })
+            .await?;
+        wait_for_event_match(&codex, |msg| match msg {
+            EventMsg::CheckpointCreated(ev) if ev.checkpoint.name == name => Some(()),
+            _ => None,
+        })
+        .await;
+    }
+
+    codex
+        .submit(Op::DiffCheckpoints {
+            from: "before".to_string(),
+            to: "after".to_string(),
+        })
+        .await?;
+    let diff = wait_for_event_match(&codex, |msg| match msg {
+        EventMsg::CheckpointDiff(ev) => Some(ev.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        (diff.from.name.as_str(), diff.to.name.as_str()),
+        ("before", "after")
+    );
+    assert!(diff.unified_diff.contains("-original\n+modified\n"));
+    assert_eq!(fs::read_to_string(&tracked)?, "modified\n");
+
+    Ok(())
+}
@@ -5,10 +5,13 @@ use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::BackgroundEventEvent;
 use codex_core::protocol::CheckpointCreatedEvent;
+use codex_core::protocol::CheckpointDiffEvent;
 use codex_core::protocol::CheckpointEntry;
 use codex_core::protocol::CheckpointErrorEvent;
 use codex_core::protocol::CheckpointListEvent;
 use codex_core::protocol::CheckpointRestoredEvent;
+use codex_core::protocol::ConfigChangedEvent;
+use codex_core::protocol::CostLimitApprovalRequestEvent;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
@@ -17,16 +20,27 @@ use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpInvocation;
+use codex_core::protocol::McpServerDownEvent;
+use codex_core::protocol::McpServerRestartedEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::PlanReadyEvent;
+use codex_core::protocol::ProjectDocsLoadedEvent;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::SessionLockLostEvent;
+use codex_core::protocol::SessionSubsystem;
+use codex_core::protocol::StallDetectedEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::SubagentTaskLogEvent;
+use codex_core::protocol::SubsystemStartupEvent;
+use codex_core::protocol::SubsystemStartupStatus;
 use codex_core::protocol::TaskCompleteEvent;
+use codex_core::protocol::ToolCallCancelledEvent;
 use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnDiffEvent;
+use codex_core::protocol::TurnRetryScheduledEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchEndEvent;
 use codex_protocol::num_format::format_with_separators;
@@ -190,6 +204,59 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     "warning:".style(self.yellow).style(self.bold)
                 );
             }
+            EventMsg::ConfigChanged(ConfigChangedEvent {
+                applied,
+                requires_new_session,
+            }) => {
+                if !applied.is_empty() {
+                    ts_msg!(
+                        self,
+                        "{} reloaded {}",
+                        "config:".style(self.cyan),
+                        applied.join(", ")
+                    );
+                }
+                if !requires_new_session.is_empty() {
+                    ts_msg!(
+                        self,
+                        "{} {} changed; restart to apply",
+                        "config:".style(self.cyan),
+                        requires_new_session.join(", ")
+                    );
+                }
+            }
+            EventMsg::CostLimitApprovalRequest(CostLimitApprovalRequestEvent {
+                max_session_cost_usd,
+                ..
+            }) => {
+                // exec cannot confirm more spend, so the turn stops here.
+                ts_msg!(
+                    self,
+                    "{} reached the ${max_session_cost_usd:.2} budget; rerun with `-c max_session_cost_usd=<usd>` to allow more",
+                    "cost:".style(self.yellow).style(self.bold)
+                );
+            }
+            EventMsg::SessionLockLost(SessionLockLostEvent { rollout_path }) => {
+                ts_msg!(
+                    self,
+                    "{} another process took over {}; this session is no longer recorded",
+                    "warning:".style(self.yellow).style(self.bold),
+                    rollout_path.display()
+                );
+            }
+            EventMsg::StallDetected(StallDetectedEvent {
+                idle_seconds,
+                last_event,
+                running_tool_calls,
+                interrupted,
+            }) => {
+                let action = if interrupted { "; interrupted" } else { "" };
+                ts_msg!(
+                    self,
+                    "{} no activity for {idle_seconds}s (last event: {last_event}, {running_tool_calls} running tool calls){action}",
+                    "stalled:".style(self.yellow).style(self.bold)
+                );
+            }
             EventMsg::DeprecationNotice(DeprecationNoticeEvent { summary, details }) => {
                 ts_msg!(
                     self,
@@ -206,11 +273,16 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     codex_core::protocol::SubagentTaskStatus::Done => "done",
                     codex_core::protocol::SubagentTaskStatus::Timeout => "timeout",
                     codex_core::protocol::SubagentTaskStatus::Failed => "failed",
+                    codex_core::protocol::SubagentTaskStatus::Cancelled => "cancelled",
+                };
+                let task = match &update.role {
+                    Some(role) => format!("{} ({role})", update.task),
+                    None => update.task.clone(),
                 };
                 if let Some(summary) = update.summary {
-                    ts_msg!(self, "subagent {} [{}] {summary}", update.task, status);
+                    ts_msg!(self, "subagent {task} [{status}] {summary}");
                 } else {
-                    ts_msg!(self, "subagent {} [{}]", update.task, status);
+                    ts_msg!(self, "subagent {task} [{status}]");
                 }
             }
             EventMsg::SubagentTaskLog(SubagentTaskLogEvent {
@@ -234,6 +306,42 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::SubagentHistoryItem(_) => {
                 // History items are rendered in the TUI; skip in CLI streamer for now.
             }
+            EventMsg::SubagentReport(report) => {
+                for agent in &report.agents {
+                    if !agent.files.is_empty() {
+                        ts_msg!(
+                            self,
+                            "subagent {} edited {} file(s)",
+                            agent.task,
+                            agent.files.len()
+                        );
+                    }
+                }
+                for conflict in &report.conflicts {
+                    let agents = conflict
+                        .agent_indices
+                        .iter()
+                        .map(|idx| (idx + 1).to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    ts_msg!(
+                        self,
+                        "{} {} edited by subagents {agents}",
+                        "conflict:".style(self.red),
+                        conflict.path.display()
+                    );
+                }
+            }
+            EventMsg::SubagentScratchpad(scratchpad) => {
+                for note in &scratchpad.notes {
+                    ts_msg!(
+                        self,
+                        "{} {}",
+                        format!("scratchpad — {}:", note.task).style(self.magenta),
+                        note.text
+                    );
+                }
+            }
             EventMsg::McpStartupUpdate(update) => {
                 let status_text = match update.status {
                     codex_core::protocol::McpStartupStatus::Starting => "starting".to_string(),
@@ -270,9 +378,48 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 };
                 ts_msg!(self, "{} {}", "mcp startup:".style(self.cyan), joined);
             }
+            EventMsg::McpServerDown(McpServerDownEvent {
+                server,
+                reason,
+                retrying,
+            }) => {
+                let suffix = if retrying { " (restarting)" } else { "" };
+                ts_msg!(
+                    self,
+                    "{} {server} down: {reason}{suffix}",
+                    "mcp:".style(self.cyan)
+                );
+            }
+            EventMsg::McpServerRestarted(McpServerRestartedEvent { server }) => {
+                ts_msg!(self, "{} {server} restarted", "mcp:".style(self.cyan));
+            }
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
+            EventMsg::SubsystemStartup(SubsystemStartupEvent {
+                subsystem: SessionSubsystem::Memory,
+                status: SubsystemStartupStatus::Unavailable { reason },
+            }) => {
+                ts_msg!(
+                    self,
+                    "{}",
+                    format!("memory unavailable: {reason}").style(self.dimmed)
+                );
+            }
+            EventMsg::ProjectDocsLoaded(ProjectDocsLoadedEvent { docs }) => {
+                let listed = docs
+                    .iter()
+                    .map(|doc| {
+                        if doc.truncated {
+                            format!("{} (truncated)", doc.path.display())
+                        } else {
+                            doc.path.display().to_string()
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                ts_msg!(self, "{} {listed}", "project docs:".style(self.dimmed));
+            }
             EventMsg::CheckpointCreated(CheckpointCreatedEvent { checkpoint }) => {
                 ts_msg!(
                     self,
@@ -307,6 +454,24 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     }
                 }
             }
+            EventMsg::CheckpointDiff(CheckpointDiffEvent {
+                from,
+                to,
+                unified_diff,
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} diff {} -> {}",
+                    "checkpoint:".style(self.cyan),
+                    self.format_checkpoint_entry(&from),
+                    self.format_checkpoint_entry(&to)
+                );
+                if unified_diff.is_empty() {
+                    ts_msg!(self, "{}", "no changes".style(self.dimmed));
+                } else {
+                    eprintln!("{unified_diff}");
+                }
+            }
             EventMsg::CheckpointError(CheckpointErrorEvent {
                 action,
                 name,
@@ -326,6 +491,22 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::StreamError(StreamErrorEvent { message, .. }) => {
                 ts_msg!(self, "{}", message.style(self.dimmed));
             }
+            EventMsg::TurnRetryScheduled(TurnRetryScheduledEvent {
+                attempt,
+                max_attempts,
+                delay_ms,
+                reason,
+            }) => {
+                let delay_secs = delay_ms as f64 / 1000.0;
+                ts_msg!(
+                    self,
+                    "{}",
+                    format!(
+                        "retrying in {delay_secs:.1}s (attempt {attempt}/{max_attempts}): {reason}"
+                    )
+                    .style(self.dimmed)
+                );
+            }
             EventMsg::TaskStarted(_) => {
                 // Ignore.
             }
@@ -413,6 +594,10 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                 }
                 eprintln!("{}", truncated_output.style(self.dimmed));
             }
+            EventMsg::ToolCallCancelled(ToolCallCancelledEvent { duration, .. }) => {
+                let title = format!(" cancelled in {}", format_duration(duration));
+                ts_msg!(self, "{}", title.style(self.red));
+            }
             EventMsg::McpToolCallBegin(McpToolCallBeginEvent {
                 call_id: _,
                 invocation,
@@ -431,6 +616,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     result,
                     invocation,
                     duration,
+                    images: _,
                 } = tool_call_end_event;
 
                 let duration = format!(" in {}", format_duration(duration));
@@ -642,6 +828,18 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     }
                 }
             }
+            EventMsg::PlanReady(PlanReadyEvent { feedback, .. }) => {
+                let revisions = feedback.len();
+                if revisions == 0 {
+                    ts_msg!(self, "{}", "Plan ready for review".style(self.magenta));
+                } else {
+                    ts_msg!(
+                        self,
+                        "{} (revision {revisions})",
+                        "Plan ready for review".style(self.magenta)
+                    );
+                }
+            }
             EventMsg::ViewImageToolCall(view) => {
                 ts_msg!(
                     self,
@@ -650,6 +848,22 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     view.path.display()
                 );
             }
+            EventMsg::FileRead(read) => {
+                if read.cached {
+                    ts_msg!(
+                        self,
+                        "{}",
+                        format!("read {} (cached)", read.path.display()).style(self.dimmed)
+                    );
+                } else {
+                    ts_msg!(
+                        self,
+                        "{} {}",
+                        "read".style(self.magenta),
+                        read.path.display()
+                    );
+                }
+            }
             EventMsg::TurnAborted(abort_reason) => match abort_reason.reason {
                 TurnAbortReason::Interrupted => {
                     ts_msg!(self, "task interrupted");
@@ -671,6 +885,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
             | EventMsg::McpListToolsResponse(_)
+            | EventMsg::McpUnresolvedSecretsResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
             | EventMsg::RawResponseItem(_)
             | EventMsg::UserMessage(_)
@@ -687,7 +902,23 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::UndoCompleted(_)
             | EventMsg::UndoStarted(_)
             | EventMsg::MemoryRememberResponse(_)
-            | EventMsg::MemoryListResponse(_) => {}
+            | EventMsg::MemoryListResponse(_)
+            | EventMsg::MemoriesConsolidated(_)
+            | EventMsg::MemoryContextInjected(_)
+            | EventMsg::SessionSummary(_)
+            | EventMsg::ResponseRegenerated(_)
+            | EventMsg::UserMessageEdited(_)
+            | EventMsg::MessageVariants(_)
+            | EventMsg::SessionEnvUpdated(_)
+            | EventMsg::StorageReport(_)
+            | EventMsg::StorageCleanupProgress(_)
+            | EventMsg::StorageCleanupCompleted(_)
+            | EventMsg::FollowUpSuggestions(_)
+            | EventMsg::ConversationForked(_)
+            | EventMsg::ConversationMergeProgress(_)
+            | EventMsg::ConversationMerged(_)
+            | EventMsg::CostUpdate(_)
+            | EventMsg::SubsystemStartup(_) => {}
         }
         CodexStatus::Running
     }
@@ -102,6 +102,9 @@ fn task_started_produces_turn_started_event() {
         "t1",
         EventMsg::TaskStarted(codex_core::protocol::TaskStartedEvent {
             model_context_window: Some(32_000),
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: None,
+            sandbox_policy: None,
         }),
     ));
 
@@ -293,6 +296,7 @@ fn mcp_tool_call_begin_and_end_emit_item_events() {
                 is_error: None,
                 structured_content: None,
             }),
+            images: Vec::new(),
         }),
     );
     let end_events = ep.collect_thread_events(&end);
@@ -342,6 +346,7 @@ fn mcp_tool_call_failure_sets_failed_status() {
             invocation,
             duration: Duration::from_millis(5),
             result: Err("tool exploded".to_string()),
+            images: Vec::new(),
         }),
     );
     let events = ep.collect_thread_events(&end);
@@ -414,6 +419,7 @@ fn mcp_tool_call_defaults_arguments_and_preserves_structured_content() {
                 is_error: None,
                 structured_content: Some(json!({ "status": "ok" })),
             }),
+            images: Vec::new(),
         }),
     );
     let events = ep.collect_thread_events(&end);
@@ -644,6 +650,7 @@ fn exec_command_end_success_produces_completed_command_item() {
             parsed_cmd: parsed_cmd.clone(),
             source: ExecCommandSource::Agent,
             interaction_input: None,
+            approval_rule: None,
         }),
     );
     let out_begin = ep.collect_thread_events(&begin);
@@ -718,6 +725,7 @@ fn exec_command_end_failure_produces_failed_command_item() {
             parsed_cmd: parsed_cmd.clone(),
             source: ExecCommandSource::Agent,
             interaction_input: None,
+            approval_rule: None,
         }),
     );
     assert_eq!(
@@ -72,6 +72,9 @@ async fn python_multiprocessing_lock_works_under_sandbox() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        allow_write: vec![],
+        deny_write: vec![],
+        network_allowlist: vec![],
     };
 
     let python_code = r#"import multiprocessing
@@ -132,6 +135,9 @@ async fn sandbox_distinguishes_command_and_policy_cwds() {
         network_access: false,
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        allow_write: vec![],
+        deny_write: vec![],
+        network_allowlist: vec![],
     };
 
     // Attempt to write inside the command cwd, which is outside of the sandbox policy cwd.
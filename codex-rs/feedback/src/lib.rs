@@ -58,6 +58,22 @@ impl CodexFeedback {
                 .unwrap_or("no-active-thread-".to_string() + &ConversationId::new().to_string()),
         }
     }
+
+    /// The last `max_lines` lines of the log buffer. Returns nothing rather
+    /// than waiting when another thread holds the buffer, so it is safe to
+    /// call from a panic hook.
+    pub fn recent_lines(&self, max_lines: usize) -> Vec<String> {
+        let bytes = match self.inner.ring.try_lock() {
+            Ok(guard) => guard.snapshot_bytes(),
+            Err(_) => return Vec::new(),
+        };
+        let text = String::from_utf8_lossy(&bytes);
+        let lines: Vec<&str> = text.lines().collect();
+        lines[lines.len().saturating_sub(max_lines)..]
+            .iter()
+            .map(|line| (*line).to_string())
+            .collect()
+    }
 }
 
 struct FeedbackInner {
@@ -296,4 +312,14 @@ mod tests {
         // Capacity 8: after writing 10 bytes, we should keep the last 8.
         pretty_assertions::assert_eq!(std::str::from_utf8(snap.as_bytes()).unwrap(), "cdefghij");
     }
+
+    #[test]
+    fn recent_lines_keeps_the_tail() {
+        let fb = CodexFeedback::with_capacity(64);
+        {
+            let mut w = fb.make_writer().make_writer();
+            w.write_all(b"one\ntwo\nthree\n").unwrap();
+        }
+        pretty_assertions::assert_eq!(fb.recent_lines(2), vec!["two", "three"]);
+    }
 }
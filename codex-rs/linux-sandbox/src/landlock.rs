@@ -31,6 +31,8 @@ pub(crate) fn apply_sandbox_policy_to_current_thread(
     sandbox_policy: &SandboxPolicy,
     cwd: &Path,
 ) -> Result<()> {
+    // A `network_allowlist` does not open the network here: seccomp cannot
+    // tell connections to the local proxy from remote ones.
     if !sandbox_policy.has_full_network_access() {
         install_network_seccomp_filter_on_current_thread()?;
     }
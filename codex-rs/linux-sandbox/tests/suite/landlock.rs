@@ -54,6 +54,9 @@ async fn run_cmd(cmd: &[&str], writable_roots: &[PathBuf], timeout_ms: u64) {
         // writing to in the sandbox.
         exclude_tmpdir_env_var: true,
         exclude_slash_tmp: true,
+        allow_write: vec![],
+        deny_write: vec![],
+        network_allowlist: vec![],
     };
     let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
     let codex_linux_sandbox_exe = Some(PathBuf::from(sandbox_program));
@@ -132,8 +135,12 @@ async fn test_timeout() {
 /// does NOT succeed (i.e. returns a non‑zero exit code) **unless** the binary
 /// is missing in which case we silently treat it as an accepted skip so the
 /// suite remains green on leaner CI images.
-#[expect(clippy::expect_used)]
 async fn assert_network_blocked(cmd: &[&str]) {
+    assert_network_blocked_under(cmd, SandboxPolicy::new_read_only_policy()).await;
+}
+
+#[expect(clippy::expect_used)]
+async fn assert_network_blocked_under(cmd: &[&str], sandbox_policy: SandboxPolicy) {
     let cwd = std::env::current_dir().expect("cwd should exist");
     let sandbox_cwd = cwd.clone();
     let params = ExecParams {
@@ -148,7 +155,6 @@ async fn assert_network_blocked(cmd: &[&str]) {
         arg0: None,
     };
 
-    let sandbox_policy = SandboxPolicy::new_read_only_policy();
     let sandbox_program = env!("CARGO_BIN_EXE_codex-linux-sandbox");
     let codex_linux_sandbox_exe: Option<PathBuf> = Some(PathBuf::from(sandbox_program));
     let result = process_exec_tool_call(
@@ -162,7 +168,7 @@ async fn assert_network_blocked(cmd: &[&str]) {
 
     let output = match result {
         Ok(output) => output,
-        Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => *output,
+        Err(CodexErr::Sandbox(SandboxErr::Denied { output, .. })) => *output,
         _ => {
             panic!("expected sandbox denied error, got: {result:?}");
         }
@@ -189,6 +195,20 @@ async fn sandbox_blocks_curl() {
     assert_network_blocked(&["curl", "-I", "http://openai.com"]).await;
 }
 
+#[tokio::test]
+async fn sandbox_blocks_allowlisted_hosts_without_proxy() {
+    // seccomp cannot confine a command to the local proxy, so an allowlist
+    // must not open the network.
+    let mut policy = SandboxPolicy::new_workspace_write_policy();
+    if let SandboxPolicy::WorkspaceWrite {
+        network_allowlist, ..
+    } = &mut policy
+    {
+        *network_allowlist = vec!["openai.com".to_string()];
+    }
+    assert_network_blocked_under(&["curl", "-I", "http://openai.com"], policy).await;
+}
+
 #[tokio::test]
 async fn sandbox_blocks_wget() {
     assert_network_blocked(&["wget", "-qO-", "http://openai.com"]).await;
@@ -19,6 +19,7 @@ use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::Submission;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_protocol::ConversationId;
@@ -180,6 +181,7 @@ async fn run_codex_tool_session_inner(
                         call_id,
                         reason: _,
                         risk,
+                        network_destinations: _,
                         parsed_cmd,
                     }) => {
                         handle_exec_approval_request(
@@ -205,7 +207,11 @@ async fn run_codex_tool_session_inner(
                         outgoing.send_response(request_id.clone(), result).await;
                         break;
                     }
-                    EventMsg::Warning(_) => {
+                    EventMsg::Warning(_)
+                    | EventMsg::ConfigChanged(_)
+                    | EventMsg::SessionLockLost(_)
+                    | EventMsg::StallDetected(_)
+                    | EventMsg::TurnRetryScheduled(_) => {
                         continue;
                     }
                     EventMsg::SubagentTaskUpdate(_) => {
@@ -217,6 +223,12 @@ async fn run_codex_tool_session_inner(
                     EventMsg::SubagentHistoryItem(_) => {
                         continue;
                     }
+                    EventMsg::SubagentReport(_) => {
+                        continue;
+                    }
+                    EventMsg::SubagentScratchpad(_) => {
+                        continue;
+                    }
                     EventMsg::ElicitationRequest(_) => {
                         // TODO: forward elicitation requests to the client?
                         continue;
@@ -242,6 +254,19 @@ async fn run_codex_tool_session_inner(
                         .await;
                         continue;
                     }
+                    EventMsg::CostLimitApprovalRequest(_) => {
+                        // MCP clients cannot extend the budget; stop the turn.
+                        if let Err(e) = codex
+                            .submit(Op::CostLimitApproval {
+                                id: event.id.clone(),
+                                decision: ReviewDecision::Denied,
+                            })
+                            .await
+                        {
+                            tracing::error!("Failed to answer cost limit request: {e}");
+                        }
+                        continue;
+                    }
                     EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message }) => {
                         let text = match last_agent_message {
                             Some(msg) => msg,
@@ -273,7 +298,12 @@ async fn run_codex_tool_session_inner(
                     EventMsg::AgentReasoningDelta(_) => {
                         // TODO: think how we want to support this in the MCP
                     }
-                    EventMsg::McpStartupUpdate(_) | EventMsg::McpStartupComplete(_) => {
+                    EventMsg::McpStartupUpdate(_)
+                    | EventMsg::McpStartupComplete(_)
+                    | EventMsg::McpServerDown(_)
+                    | EventMsg::McpServerRestarted(_)
+                    | EventMsg::SubsystemStartup(_)
+                    | EventMsg::ProjectDocsLoaded(_) => {
                         // Ignored in MCP tool runner.
                     }
                     EventMsg::AgentMessage(AgentMessageEvent { .. }) => {
@@ -283,15 +313,18 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::AgentReasoningRawContentDelta(_)
                     | EventMsg::TaskStarted(_)
                     | EventMsg::TokenCount(_)
+                    | EventMsg::CostUpdate(_)
                     | EventMsg::AgentReasoning(_)
                     | EventMsg::AgentReasoningSectionBreak(_)
                     | EventMsg::McpToolCallBegin(_)
                     | EventMsg::McpToolCallEnd(_)
                     | EventMsg::McpListToolsResponse(_)
+                    | EventMsg::McpUnresolvedSecretsResponse(_)
                     | EventMsg::ListCustomPromptsResponse(_)
                     | EventMsg::ExecCommandBegin(_)
                     | EventMsg::ExecCommandOutputDelta(_)
                     | EventMsg::ExecCommandEnd(_)
+                    | EventMsg::ToolCallCancelled(_)
                     | EventMsg::BackgroundEvent(_)
                     | EventMsg::StreamError(_)
                     | EventMsg::PatchApplyBegin(_)
@@ -301,10 +334,12 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
                     | EventMsg::PlanUpdate(_)
+                    | EventMsg::PlanReady(_)
                     | EventMsg::TurnAborted(_)
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
                     | EventMsg::ViewImageToolCall(_)
+                    | EventMsg::FileRead(_)
                     | EventMsg::RawResponseItem(_)
                     | EventMsg::EnteredReviewMode(_)
                     | EventMsg::ItemStarted(_)
@@ -315,6 +350,7 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::CheckpointCreated(_)
                     | EventMsg::CheckpointRestored(_)
                     | EventMsg::CheckpointList(_)
+                    | EventMsg::CheckpointDiff(_)
                     | EventMsg::CheckpointError(_)
                     | EventMsg::UndoStarted(_)
                     | EventMsg::UndoCompleted(_)
@@ -322,7 +358,21 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::ContextCompacted(_)
                     | EventMsg::DeprecationNotice(_)
                     | EventMsg::MemoryRememberResponse(_)
-                    | EventMsg::MemoryListResponse(_) => {
+                    | EventMsg::MemoryListResponse(_)
+                    | EventMsg::MemoriesConsolidated(_)
+                    | EventMsg::MemoryContextInjected(_)
+                    | EventMsg::SessionSummary(_)
+                    | EventMsg::ResponseRegenerated(_)
+                    | EventMsg::UserMessageEdited(_)
+                    | EventMsg::MessageVariants(_)
+                    | EventMsg::SessionEnvUpdated(_)
+                    | EventMsg::StorageReport(_)
+                    | EventMsg::StorageCleanupProgress(_)
+                    | EventMsg::StorageCleanupCompleted(_)
+                    | EventMsg::FollowUpSuggestions(_)
+                    | EventMsg::ConversationForked(_)
+                    | EventMsg::ConversationMergeProgress(_)
+                    | EventMsg::ConversationMerged(_) => {
                         // For now, we do not do anything extra for these
                         // events. Note that
                         // send(codex_event_to_notification(&event)) above has
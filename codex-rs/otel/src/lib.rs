@@ -3,9 +3,13 @@ pub mod config;
 pub mod otel_event_manager;
 #[cfg(feature = "otel")]
 pub mod otel_provider;
+#[cfg(feature = "otel")]
+pub mod turn_spans;
 
 #[cfg(not(feature = "otel"))]
 mod imp {
+    use codex_protocol::ConversationId;
+    use codex_protocol::protocol::EventMsg;
     use reqwest::header::HeaderMap;
     use tracing::Span;
 
@@ -20,7 +24,22 @@ mod imp {
             HeaderMap::new()
         }
     }
+
+    #[derive(Clone, Debug)]
+    pub struct TurnSpans;
+
+    impl TurnSpans {
+        pub fn new(_conversation_id: ConversationId) -> Self {
+            Self
+        }
+
+        pub fn record(&self, _turn_id: &str, _msg: &EventMsg) {}
+    }
 }
 
 #[cfg(not(feature = "otel"))]
 pub use imp::OtelProvider;
+#[cfg(not(feature = "otel"))]
+pub use imp::TurnSpans;
+#[cfg(feature = "otel")]
+pub use turn_spans::TurnSpans;
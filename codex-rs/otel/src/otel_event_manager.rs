@@ -6,6 +6,7 @@ use codex_protocol::config_types::ReasoningEffort;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ReviewDecision;
 use codex_protocol::protocol::SandboxPolicy;
 use codex_protocol::protocol::SandboxRiskLevel;
@@ -22,6 +23,8 @@ use std::time::Instant;
 use strum_macros::Display;
 use tokio::time::error::Elapsed;
 
+use crate::TurnSpans;
+
 #[derive(Debug, Clone, Serialize, Display)]
 #[serde(rename_all = "snake_case")]
 pub enum ToolDecisionSource {
@@ -45,6 +48,7 @@ pub struct OtelEventMetadata {
 #[derive(Debug, Clone)]
 pub struct OtelEventManager {
     metadata: OtelEventMetadata,
+    turn_spans: Option<TurnSpans>,
 }
 
 impl OtelEventManager {
@@ -71,6 +75,20 @@ impl OtelEventManager {
                 app_version: env!("CARGO_PKG_VERSION"),
                 terminal_type,
             },
+            turn_spans: None,
+        }
+    }
+
+    /// Export each turn as a span tree (see [`TurnSpans`]) fed by
+    /// [`Self::record_turn_event`].
+    pub fn with_turn_spans(mut self, enabled: bool) -> Self {
+        self.turn_spans = enabled.then(|| TurnSpans::new(self.metadata.conversation_id));
+        self
+    }
+
+    pub fn record_turn_event(&self, turn_id: &str, msg: &EventMsg) {
+        if let Some(turn_spans) = &self.turn_spans {
+            turn_spans.record(turn_id, msg);
         }
     }
 
@@ -4,16 +4,19 @@ use crate::config::OtelSettings;
 use crate::config::OtelTlsConfig;
 use http::Uri;
 use opentelemetry::KeyValue;
+use opentelemetry::global;
 use opentelemetry_otlp::LogExporter;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_LOGS_TIMEOUT;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_TIMEOUT;
 use opentelemetry_otlp::OTEL_EXPORTER_OTLP_TIMEOUT_DEFAULT;
 use opentelemetry_otlp::Protocol;
+use opentelemetry_otlp::SpanExporter;
 use opentelemetry_otlp::WithExportConfig;
 use opentelemetry_otlp::WithHttpConfig;
 use opentelemetry_otlp::WithTonicConfig;
 use opentelemetry_sdk::Resource;
 use opentelemetry_sdk::logs::SdkLoggerProvider;
+use opentelemetry_sdk::trace::SdkTracerProvider;
 use opentelemetry_semantic_conventions as semconv;
 use reqwest::Certificate as ReqwestCertificate;
 use reqwest::Identity as ReqwestIdentity;
@@ -35,14 +38,20 @@ use tonic::transport::Identity as TonicIdentity;
 use tracing::debug;
 
 const ENV_ATTRIBUTE: &str = "env";
+const OTLP_HTTP_LOGS_PATH: &str = "/v1/logs";
+const OTLP_HTTP_TRACES_PATH: &str = "/v1/traces";
 
 pub struct OtelProvider {
     pub logger: SdkLoggerProvider,
+    /// Exports the turn span trees built by `TurnSpans`; installed as the
+    /// global tracer provider.
+    pub tracer: SdkTracerProvider,
 }
 
 impl OtelProvider {
     pub fn shutdown(&self) {
         let _ = self.logger.shutdown();
+        let _ = self.tracer.shutdown();
     }
 
     pub fn from(settings: &OtelSettings) -> Result<Option<Self>, Box<dyn Error>> {
@@ -57,7 +66,8 @@ impl OtelProvider {
             ])
             .build();
 
-        let mut builder = SdkLoggerProvider::builder().with_resource(resource);
+        let mut builder = SdkLoggerProvider::builder().with_resource(resource.clone());
+        let mut tracer_builder = SdkTracerProvider::builder().with_resource(resource);
 
         match &settings.exporter {
             OtelExporter::None => {
@@ -94,6 +104,12 @@ impl OtelProvider {
                     None => base_tls_config,
                 };
 
+                let span_exporter = SpanExporter::builder()
+                    .with_tonic()
+                    .with_endpoint(endpoint)
+                    .with_metadata(MetadataMap::from_headers(header_map.clone()))
+                    .with_tls_config(tls_config.clone())
+                    .build()?;
                 let exporter = LogExporter::builder()
                     .with_tonic()
                     .with_endpoint(endpoint)
@@ -102,6 +118,7 @@ impl OtelProvider {
                     .build()?;
 
                 builder = builder.with_batch_exporter(exporter);
+                tracer_builder = tracer_builder.with_batch_exporter(span_exporter);
             }
             OtelExporter::OtlpHttp {
                 endpoint,
@@ -121,20 +138,32 @@ impl OtelProvider {
                     .with_endpoint(endpoint)
                     .with_protocol(protocol)
                     .with_headers(headers.clone());
+                let mut span_exporter_builder = SpanExporter::builder()
+                    .with_http()
+                    .with_endpoint(traces_endpoint(endpoint))
+                    .with_protocol(protocol)
+                    .with_headers(headers.clone());
 
                 if let Some(tls) = tls.as_ref() {
                     let client = build_http_client(tls, settings.codex_home.as_path())?;
-                    exporter_builder = exporter_builder.with_http_client(client);
+                    exporter_builder = exporter_builder.with_http_client(client.clone());
+                    span_exporter_builder = span_exporter_builder.with_http_client(client);
                 }
 
                 let exporter = exporter_builder.build()?;
+                let span_exporter = span_exporter_builder.build()?;
 
                 builder = builder.with_batch_exporter(exporter);
+                tracer_builder = tracer_builder.with_batch_exporter(span_exporter);
             }
         }
 
+        let tracer = tracer_builder.build();
+        global::set_tracer_provider(tracer.clone());
+
         Ok(Some(Self {
             logger: builder.build(),
+            tracer,
         }))
     }
 }
@@ -142,6 +171,16 @@ impl OtelProvider {
 impl Drop for OtelProvider {
     fn drop(&mut self) {
         let _ = self.logger.shutdown();
+        let _ = self.tracer.shutdown();
+    }
+}
+
+/// OTLP/HTTP endpoints are per signal; the configured endpoint is the logs
+/// one, so spans go to its `/v1/traces` sibling.
+fn traces_endpoint(logs_endpoint: &str) -> String {
+    match logs_endpoint.strip_suffix(OTLP_HTTP_LOGS_PATH) {
+        Some(base) => format!("{base}{OTLP_HTTP_TRACES_PATH}"),
+        None => logs_endpoint.to_string(),
     }
 }
 
@@ -0,0 +1,412 @@
+//! OpenTelemetry spans for turns and the tool calls they make.
+//!
+//! Tool work is reported as protocol events. [`TurnSpans`] turns those events
+//! into nested spans, so a collector shows one trace per turn:
+//!
+//! ```text
+//! codex.turn
+//! ├── execute_tool exec_command
+//! └── execute_tool subagent_run
+//!     └── codex.subagent_turn
+//!         └── execute_tool apply_patch
+//! ```
+//!
+//! Subagents run as separate sessions. Their tool calls arrive here wrapped in
+//! `SubagentHistoryItem` events and are nested under the subagent's turn.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use codex_protocol::ConversationId;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::SubagentTaskStatus;
+use opentelemetry::Context;
+use opentelemetry::KeyValue;
+use opentelemetry::global;
+use opentelemetry::trace::SpanKind;
+use opentelemetry::trace::Status;
+use opentelemetry::trace::TraceContextExt;
+use opentelemetry::trace::Tracer;
+
+const TRACER_NAME: &str = "codex_otel";
+
+/// Builds the span tree for one conversation from its event stream.
+#[derive(Clone)]
+pub struct TurnSpans {
+    conversation_id: ConversationId,
+    state: Arc<Mutex<SpanState>>,
+}
+
+#[derive(Default)]
+struct SpanState {
+    /// Open turn spans by turn id.
+    turns: HashMap<String, Context>,
+    /// Open tool-call and subagent-turn spans by key (see `call_key`).
+    open: HashMap<String, OpenSpan>,
+}
+
+struct OpenSpan {
+    turn_id: String,
+    cx: Context,
+}
+
+impl fmt::Debug for TurnSpans {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TurnSpans")
+            .field("conversation_id", &self.conversation_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TurnSpans {
+    pub fn new(conversation_id: ConversationId) -> Self {
+        Self {
+            conversation_id,
+            state: Arc::new(Mutex::new(SpanState::default())),
+        }
+    }
+
+    /// Start, annotate or end the spans `msg` affects. `turn_id` is the id
+    /// of the event, i.e. the submission the turn runs for.
+    pub fn record(&self, turn_id: &str, msg: &EventMsg) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.record(&self.conversation_id, turn_id, None, msg);
+    }
+}
+
+impl SpanState {
+    /// `subagent` is the key of the subagent turn that emitted `msg`, or
+    /// `None` for events of the session itself.
+    fn record(
+        &mut self,
+        conversation_id: &ConversationId,
+        turn_id: &str,
+        subagent: Option<&str>,
+        msg: &EventMsg,
+    ) {
+        match msg {
+            EventMsg::TaskStarted(ev) if subagent.is_none() => {
+                let tracer = global::tracer(TRACER_NAME);
+                let span = tracer
+                    .span_builder("codex.turn")
+                    .with_kind(SpanKind::Internal)
+                    .with_attributes(vec![
+                        KeyValue::new("conversation.id", conversation_id.to_string()),
+                        KeyValue::new("turn.id", turn_id.to_string()),
+                        KeyValue::new("model", ev.model.clone()),
+                    ])
+                    .start_with_context(&tracer, &Context::new());
+                self.turns
+                    .insert(turn_id.to_string(), Context::new().with_span(span));
+            }
+            EventMsg::TaskComplete(_) if subagent.is_none() => self.end_turn(turn_id),
+            EventMsg::TurnAborted(ev) if subagent.is_none() => {
+                if let Some(cx) = self.turns.get(turn_id) {
+                    cx.span().set_attribute(KeyValue::new(
+                        "turn.aborted",
+                        format!("{:?}", ev.reason).to_lowercase(),
+                    ));
+                }
+                self.end_turn(turn_id);
+            }
+            EventMsg::Error(ev) if subagent.is_none() => {
+                if let Some(cx) = self.turns.get(turn_id) {
+                    cx.span().set_status(Status::error(ev.message.clone()));
+                }
+            }
+            EventMsg::ExecCommandBegin(ev) => {
+                self.start_call(
+                    turn_id,
+                    subagent,
+                    &ev.call_id,
+                    "exec_command",
+                    vec![KeyValue::new("exec.command", ev.command.join(" "))],
+                );
+            }
+            EventMsg::ExecCommandEnd(ev) => {
+                self.end_call(
+                    subagent,
+                    &ev.call_id,
+                    (ev.exit_code != 0).then(|| format!("exited with code {}", ev.exit_code)),
+                    vec![KeyValue::new("exec.exit_code", i64::from(ev.exit_code))],
+                );
+            }
+            EventMsg::McpToolCallBegin(ev) => {
+                self.start_call(
+                    turn_id,
+                    subagent,
+                    &ev.call_id,
+                    &format!("{}/{}", ev.invocation.server, ev.invocation.tool),
+                    vec![
+                        KeyValue::new("mcp.server", ev.invocation.server.clone()),
+                        KeyValue::new("mcp.tool", ev.invocation.tool.clone()),
+                    ],
+                );
+            }
+            EventMsg::McpToolCallEnd(ev) => {
+                let error = match &ev.result {
+                    Err(err) => Some(err.clone()),
+                    Ok(_) if !ev.is_success() => Some("tool reported an error".to_string()),
+                    Ok(_) => None,
+                };
+                self.end_call(subagent, &ev.call_id, error, Vec::new());
+            }
+            EventMsg::PatchApplyBegin(ev) => {
+                self.start_call(
+                    turn_id,
+                    subagent,
+                    &ev.call_id,
+                    "apply_patch",
+                    vec![KeyValue::new("patch.files", ev.changes.len() as i64)],
+                );
+            }
+            EventMsg::PatchApplyEnd(ev) => {
+                let error = (!ev.success).then(|| "patch was not applied".to_string());
+                self.end_call(subagent, &ev.call_id, error, Vec::new());
+            }
+            EventMsg::SubagentTaskUpdate(ev) if subagent.is_none() => {
+                let key =
+                    self.open_subagent(turn_id, &ev.call_id, ev.agent_index.unwrap_or(0), &ev.task);
+                let error = match ev.status {
+                    SubagentTaskStatus::Running => return,
+                    SubagentTaskStatus::Done | SubagentTaskStatus::Cancelled => None,
+                    SubagentTaskStatus::Timeout => Some("subagent timed out".to_string()),
+                    SubagentTaskStatus::Failed => Some(
+                        ev.summary
+                            .clone()
+                            .unwrap_or_else(|| "subagent failed".to_string()),
+                    ),
+                };
+                let status = format!("{:?}", ev.status).to_lowercase();
+                self.end_span(&key, error, vec![KeyValue::new("subagent.status", status)]);
+            }
+            EventMsg::SubagentHistoryItem(ev) if subagent.is_none() => {
+                let key =
+                    self.open_subagent(turn_id, &ev.call_id, ev.agent_index.unwrap_or(0), &ev.task);
+                self.record(conversation_id, turn_id, Some(&key), &ev.event);
+            }
+            EventMsg::SubagentReport(ev) if subagent.is_none() => {
+                let prefix = format!("{}#", ev.call_id);
+                let unfinished: Vec<String> = self
+                    .open
+                    .keys()
+                    .filter(|key| key.starts_with(&prefix))
+                    .cloned()
+                    .collect();
+                for key in unfinished {
+                    self.end_span(&key, None, Vec::new());
+                }
+                self.end_call(None, &ev.call_id, None, Vec::new());
+            }
+            _ => {}
+        }
+    }
+
+    fn parent_context(&self, turn_id: &str, subagent: Option<&str>) -> Context {
+        subagent
+            .and_then(|key| self.open.get(key))
+            .map(|open| open.cx.clone())
+            .or_else(|| self.turns.get(turn_id).cloned())
+            .unwrap_or_else(Context::new)
+    }
+
+    fn start_call(
+        &mut self,
+        turn_id: &str,
+        subagent: Option<&str>,
+        call_id: &str,
+        tool_name: &str,
+        mut attributes: Vec<KeyValue>,
+    ) {
+        attributes.push(KeyValue::new("gen_ai.tool.name", tool_name.to_string()));
+        attributes.push(KeyValue::new("gen_ai.tool.call.id", call_id.to_string()));
+        let parent = self.parent_context(turn_id, subagent);
+        self.start_span(
+            turn_id,
+            call_key(subagent, call_id),
+            format!("execute_tool {tool_name}"),
+            attributes,
+            &parent,
+        );
+    }
+
+    fn end_call(
+        &mut self,
+        subagent: Option<&str>,
+        call_id: &str,
+        error: Option<String>,
+        attributes: Vec<KeyValue>,
+    ) {
+        self.end_span(&call_key(subagent, call_id), error, attributes);
+    }
+
+    /// Open the `subagent_run` call and the subagent's turn span if they are
+    /// not open yet; returns the subagent turn's key.
+    fn open_subagent(
+        &mut self,
+        turn_id: &str,
+        call_id: &str,
+        agent_index: i64,
+        task: &str,
+    ) -> String {
+        if !self.open.contains_key(call_id) {
+            self.start_call(turn_id, None, call_id, "subagent_run", Vec::new());
+        }
+        let key = format!("{call_id}#{agent_index}");
+        if !self.open.contains_key(&key) {
+            let parent = self.parent_context(turn_id, Some(call_id));
+            self.start_span(
+                turn_id,
+                key.clone(),
+                "codex.subagent_turn".to_string(),
+                vec![
+                    KeyValue::new("subagent.task", task.to_string()),
+                    KeyValue::new("subagent.index", agent_index),
+                ],
+                &parent,
+            );
+        }
+        key
+    }
+
+    fn start_span(
+        &mut self,
+        turn_id: &str,
+        key: String,
+        name: String,
+        attributes: Vec<KeyValue>,
+        parent: &Context,
+    ) {
+        let tracer = global::tracer(TRACER_NAME);
+        let span = tracer
+            .span_builder(name)
+            .with_kind(SpanKind::Internal)
+            .with_attributes(attributes)
+            .start_with_context(&tracer, parent);
+        self.open.insert(
+            key,
+            OpenSpan {
+                turn_id: turn_id.to_string(),
+                cx: parent.with_span(span),
+            },
+        );
+    }
+
+    fn end_span(&mut self, key: &str, error: Option<String>, attributes: Vec<KeyValue>) {
+        let Some(open) = self.open.remove(key) else {
+            return;
+        };
+        let span = open.cx.span();
+        for attribute in attributes {
+            span.set_attribute(attribute);
+        }
+        if let Some(error) = error {
+            span.set_status(Status::error(error));
+        }
+        span.end();
+    }
+
+    /// End the turn and anything it left open, e.g. calls cut short by an
+    /// interrupt.
+    fn end_turn(&mut self, turn_id: &str) {
+        let unfinished: Vec<String> = self
+            .open
+            .iter()
+            .filter(|(_, open)| open.turn_id == turn_id)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in unfinished {
+            self.end_span(
+                &key,
+                Some("turn ended before the call finished".to_string()),
+                Vec::new(),
+            );
+        }
+        if let Some(cx) = self.turns.remove(turn_id) {
+            cx.span().end();
+        }
+    }
+}
+
+/// Calls made by a subagent are keyed under its turn, since their ids come
+/// from a different session and may repeat the parent's.
+fn call_key(subagent: Option<&str>, call_id: &str) -> String {
+    match subagent {
+        Some(subagent) => format!("{subagent}/{call_id}"),
+        None => call_id.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::PatchApplyBeginEvent;
+    use codex_protocol::protocol::SubagentHistoryItemEvent;
+    use codex_protocol::protocol::SubagentTaskUpdateEvent;
+    use codex_protocol::protocol::TaskCompleteEvent;
+    use codex_protocol::protocol::TaskStartedEvent;
+
+    fn open_keys(spans: &TurnSpans) -> Vec<String> {
+        let state = spans.state.lock().unwrap();
+        let mut keys: Vec<String> = state.open.keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn nests_subagent_calls_and_closes_them_with_the_turn() {
+        let spans = TurnSpans::new(ConversationId::new());
+        spans.record(
+            "1",
+            &EventMsg::TaskStarted(TaskStartedEvent {
+                model_context_window: None,
+                model: "gpt-5".to_string(),
+                reasoning_effort: None,
+                sandbox_policy: None,
+            }),
+        );
+        spans.record(
+            "1",
+            &EventMsg::SubagentHistoryItem(SubagentHistoryItemEvent {
+                call_id: "call-1".to_string(),
+                task: "docs".to_string(),
+                agent_index: Some(0),
+                event: Box::new(EventMsg::PatchApplyBegin(PatchApplyBeginEvent {
+                    call_id: "call-1".to_string(),
+                    turn_id: "1".to_string(),
+                    auto_approved: true,
+                    changes: HashMap::new(),
+                })),
+            }),
+        );
+        assert_eq!(
+            open_keys(&spans),
+            vec!["call-1", "call-1#0", "call-1#0/call-1"]
+        );
+
+        spans.record(
+            "1",
+            &EventMsg::SubagentTaskUpdate(SubagentTaskUpdateEvent {
+                call_id: "call-1".to_string(),
+                task: "docs".to_string(),
+                role: None,
+                agent_index: Some(0),
+                status: SubagentTaskStatus::Done,
+                summary: None,
+            }),
+        );
+        assert_eq!(open_keys(&spans), vec!["call-1", "call-1#0/call-1"]);
+
+        spans.record(
+            "1",
+            &EventMsg::TaskComplete(TaskCompleteEvent {
+                last_agent_message: None,
+            }),
+        );
+        assert!(open_keys(&spans).is_empty());
+        assert!(spans.state.lock().unwrap().turns.is_empty());
+    }
+}
@@ -51,6 +51,10 @@ pub struct ExecApprovalRequestEvent {
     /// Optional model-provided risk assessment describing the blocked command.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub risk: Option<SandboxCommandAssessment>,
+    /// `host:port` destinations the sandbox's network allowlist refused
+    /// while the command ran, when that is why approval is requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub network_destinations: Vec<String>,
     pub parsed_cmd: Vec<ParsedCommand>,
 }
 
@@ -92,6 +92,19 @@ pub enum SandboxMode {
     DangerFullAccess,
 }
 
+/// How detailed plans drafted by the plan-first workflow should be.
+#[derive(
+    Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Display, JsonSchema, TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PlanDetailPreference {
+    #[default]
+    Auto,
+    Coarse,
+    Detailed,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Display, JsonSchema, TS)]
 #[serde(rename_all = "lowercase")]
 #[strum(serialize_all = "lowercase")]
@@ -109,3 +122,16 @@ pub enum TrustLevel {
     Trusted,
     Untrusted,
 }
+
+/// Session event delivered to webhooks.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Display, JsonSchema, TS)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum WebhookEvent {
+    /// A turn finished.
+    TurnComplete,
+    /// A command or patch is waiting for approval.
+    ApprovalNeeded,
+    /// A turn failed.
+    Error,
+}
@@ -11,6 +11,7 @@ use serde::ser::Serializer;
 use ts_rs::TS;
 
 use crate::user_input::UserInput;
+use crate::user_input::quote_context_text;
 use codex_git::GhostCommit;
 use codex_utils_image::error::ImageProcessingError;
 use schemars::JsonSchema;
@@ -332,6 +333,9 @@ impl From<Vec<UserInput>> for ResponseInputItem {
                     },
                     // Skill bodies are injected later in core
                     UserInput::Skill { .. } => None,
+                    UserInput::Quote { item } => Some(ContentItem::InputText {
+                        text: quote_context_text(item),
+                    }),
                 })
                 .collect::<Vec<ContentItem>>(),
         }
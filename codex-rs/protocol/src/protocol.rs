@@ -12,8 +12,10 @@ use std::time::Duration;
 
 use crate::ConversationId;
 use crate::approvals::ElicitationRequestEvent;
+use crate::config_types::PlanDetailPreference;
 use crate::config_types::ReasoningEffort as ReasoningEffortConfig;
 use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use crate::config_types::WebhookEvent;
 use crate::custom_prompts::CustomPrompt;
 use crate::items::TurnItem;
 use crate::message_history::HistoryEntry;
@@ -23,6 +25,7 @@ use crate::num_format::format_with_separators;
 use crate::parse_command::ParsedCommand;
 use crate::plan_tool::UpdatePlanArgs;
 use crate::user_input::UserInput;
+use globset::GlobBuilder;
 use mcp_types::CallToolResult;
 use mcp_types::RequestId;
 use mcp_types::Resource as McpResource;
@@ -35,6 +38,7 @@ use serde_json::Value;
 use serde_with::serde_as;
 use strum_macros::Display;
 use ts_rs::TS;
+use walkdir::WalkDir;
 
 pub use crate::approvals::ApplyPatchApprovalRequestEvent;
 pub use crate::approvals::ElicitationAction;
@@ -182,6 +186,30 @@ pub enum Op {
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
 
+    /// Request the enabled MCP servers that reference `secret://` values no
+    /// secret provider has. Reply is delivered via
+    /// `EventMsg::McpUnresolvedSecretsResponse`.
+    ListMcpUnresolvedSecrets,
+
+    /// Restart a configured MCP server, replacing its connection. Emits
+    /// `EventMsg::McpServerRestarted` on success or `EventMsg::McpServerDown`
+    /// if the server fails to come back.
+    RestartMcpServer {
+        /// Server name as configured under `mcp_servers`.
+        name: String,
+    },
+
+    /// Re-read `config.toml` now instead of waiting for the file watcher.
+    /// Replies with `EventMsg::ConfigChanged`, empty when nothing changed, or
+    /// `EventMsg::Error` if the file cannot be loaded.
+    ReloadConfig,
+
+    /// Take this session's rollout back after another process took it over
+    /// (see `EventMsg::SessionLockLost`). Recording resumes with the next
+    /// item; items produced while the lock was lost are not written. Replies
+    /// with `EventMsg::BackgroundEvent`, or `EventMsg::Error` on failure.
+    ReclaimSessionLock,
+
     /// Request the list of available custom prompts.
     ListCustomPrompts,
 
@@ -202,6 +230,78 @@ pub enum Op {
     /// List all checkpoints captured in this session.
     ListCheckpoints,
 
+    /// Compute a unified diff between two previously created checkpoints
+    /// without restoring either of them. Reply is delivered via
+    /// `EventMsg::CheckpointDiff`.
+    DiffCheckpoints { from: String, to: String },
+
+    /// Restore `path` to its state before turn `turn_id` (the most recent turn
+    /// when omitted) without touching other files. The revert is reported as
+    /// an `EventMsg::PatchApplyBegin`/`EventMsg::PatchApplyEnd` pair.
+    RevertFileChange {
+        path: PathBuf,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        turn_id: Option<String>,
+    },
+
+    /// Restore every file changed during `turn_id` to its state before that
+    /// turn, reported the same way as `Op::RevertFileChange`.
+    RevertTurn { turn_id: String },
+
+    /// Start the plan-first workflow for `request`. The agent drafts a plan
+    /// without changing the workspace and replies with `EventMsg::PlanReady`
+    /// once the plan is ready for review.
+    StartPlan {
+        request: String,
+        /// Plan detail for this workflow; defaults to the configured
+        /// `plan_detail`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        detail: Option<PlanDetailPreference>,
+    },
+
+    /// Ask the agent to revise the plan awaiting review using `feedback`.
+    /// Replies with another `EventMsg::PlanReady`.
+    RevisePlan { feedback: String },
+
+    /// Approve the plan awaiting review and start implementing it.
+    ApprovePlan,
+
+    /// Abandon the current plan-first workflow.
+    CancelPlan,
+
+    /// Abort a single running subagent task without interrupting the turn
+    /// that spawned it. The task reports `SubagentTaskStatus::Cancelled`.
+    CancelSubagentTask {
+        /// Tool call id that launched the subagents.
+        call_id: String,
+        /// Index (0-based) of the task within that tool call.
+        agent_index: i64,
+    },
+
+    /// Answer an `EventMsg::CostLimitApprovalRequest`. `Approved` continues
+    /// until another `max_session_cost_usd` has been spent,
+    /// `ApprovedForSession` stops asking, and `Denied` ends the turn.
+    CostLimitApproval {
+        /// The id of the submission we are approving
+        id: String,
+        /// The user's decision in response to the request.
+        decision: ReviewDecision,
+    },
+
+    /// Terminate a single running tool call (command, MCP call, ...) without
+    /// interrupting the rest of the turn. The model receives an aborted
+    /// result for that call and `EventMsg::ToolCallCancelled` is emitted.
+    CancelToolCall {
+        /// Id of the tool call to cancel.
+        call_id: String,
+    },
+
+    /// Append items produced by another conversation, such as the winning
+    /// branch of a model duel, to this conversation's history and rollout.
+    /// Each item that maps to a turn item is reported as
+    /// `EventMsg::ItemCompleted`.
+    AppendHistory { items: Vec<ResponseItem> },
+
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
@@ -228,6 +328,114 @@ pub enum Op {
     /// Request memory statistics and recent memories.
     /// Reply is delivered via `EventMsg::MemoryListResponse`.
     ListMemories,
+
+    /// Merge near-duplicate memories into one canonical memory each.
+    /// Reply is delivered via `EventMsg::MemoriesConsolidated`.
+    ConsolidateMemories,
+
+    /// Ask a lightweight model for a structured summary of the session so
+    /// far. Reply is delivered via `EventMsg::SessionSummary`.
+    SummarizeSession {
+        /// Also write the summary to `.kaioken/session-notes/` in the
+        /// session's working directory.
+        save: bool,
+    },
+
+    /// Copy this conversation's rollout, up to and including its
+    /// `at_item`-th (0-based) assistant message, into a new conversation.
+    /// The original is left untouched. Reply is delivered via
+    /// `EventMsg::ConversationForked`; resume the returned rollout path to
+    /// continue the branch.
+    ForkConversation { at_item: usize },
+
+    /// Fold another conversation into this one: the source conversation is
+    /// summarized and the summary is recorded in this conversation's history
+    /// as merged context. Steps are reported via
+    /// `EventMsg::ConversationMergeProgress` and the result via
+    /// `EventMsg::ConversationMerged`.
+    MergeConversation {
+        source_conversation_id: ConversationId,
+    },
+
+    /// Discard the answer to the last user message and run that message
+    /// again, optionally with a different model or reasoning effort. The
+    /// overrides apply to the regenerated turn only. The discarded answer is
+    /// kept as a previous attempt and reported via
+    /// `EventMsg::ResponseRegenerated` before the new turn starts.
+    RegenerateLast {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        effort: Option<ReasoningEffortConfig>,
+    },
+
+    /// Rewind the conversation to just before its `at_item`-th (0-based)
+    /// user message and send `items` in its place. The original message and
+    /// everything after it are discarded, and the edit is reported via
+    /// `EventMsg::UserMessageEdited` before the new turn starts.
+    EditAndResend {
+        at_item: usize,
+        items: Vec<UserInput>,
+    },
+
+    /// List the earlier attempts at the answer that `message_id` (the
+    /// current answer or one of its previous attempts) belongs to.
+    /// Reply is delivered via `EventMsg::MessageVariants`.
+    GetMessageVariants { message_id: String },
+
+    /// Pin (or, with `pinned: false`, unpin) this conversation's
+    /// `at_item`-th (0-based) assistant message, counted as for
+    /// `Op::ForkConversation`. With the `keep-pinned-messages` compaction
+    /// policy, pinned messages are carried over every compaction verbatim.
+    /// Pins last for the session; failures are reported via
+    /// `EventMsg::Warning`.
+    PinHistoryItem { at_item: usize, pinned: bool },
+
+    /// Pin (or, with `pinned: false`, unpin) a file whose latest contents
+    /// are recorded ahead of every later user turn. Relative paths resolve
+    /// against the session's working directory. Contents are sent again
+    /// only when they change, within a per-turn token budget. Pins last for
+    /// the session; failures are reported via `EventMsg::Warning`.
+    PinFile { path: PathBuf, pinned: bool },
+
+    /// Replace the variables set on top of the shell environment for this
+    /// session's commands; an empty map clears them. With `persist`, they
+    /// also become the env profile of the session's working directory
+    /// (`[projects."<cwd>".env]` in `config.toml`), applied to every later
+    /// session started there. Reply is delivered via
+    /// `EventMsg::SessionEnvUpdated`.
+    SetSessionEnv {
+        vars: HashMap<String, String>,
+        #[serde(default)]
+        persist: bool,
+    },
+
+    /// Deliver `events` of this session to `url`, in addition to the
+    /// `[[webhooks]]` of `config.toml`, replacing an earlier registration of
+    /// the same URL. Empty `events` removes it. With `secret`, payloads are
+    /// signed with HMAC-SHA256. Registrations last for the session and are
+    /// never written to `config.toml`.
+    SetWebhook {
+        url: String,
+        events: Vec<WebhookEvent>,
+        #[serde(default)]
+        secret: Option<String>,
+    },
+
+    /// Report how much disk space Codex data uses, per category and per
+    /// repository. Reply is delivered via `EventMsg::StorageReport`.
+    GetStorageReport,
+
+    /// Delete or compact stored data as selected by `options`. Progress is
+    /// reported via `EventMsg::StorageCleanupProgress` and the outcome via
+    /// `EventMsg::StorageCleanupCompleted`.
+    CleanupStorage { options: StorageCleanupOptions },
+
+    /// Send the `index`-th (0-based) suggestion from the last
+    /// `EventMsg::FollowUpSuggestions` as a user message. Suggestions are
+    /// dropped once the next turn starts; failures are reported via
+    /// `EventMsg::Warning`.
+    AcceptSuggestion { index: usize },
 }
 
 /// Determines the conditions under which the user is consulted to approve
@@ -308,6 +516,22 @@ pub enum SandboxPolicy {
         /// writable roots on UNIX. Defaults to `false`.
         #[serde(default)]
         exclude_slash_tmp: bool,
+
+        /// Glob patterns (relative to cwd unless absolute) whose matching
+        /// paths are writable in addition to the writable roots.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        allow_write: Vec<String>,
+
+        /// Glob patterns (relative to cwd unless absolute) whose matching
+        /// paths stay read-only even under a writable root.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        deny_write: Vec<String>,
+
+        /// Hosts that stay reachable while `network_access` is `false`, as
+        /// `host`, `host:port` or `*.domain`. Traffic goes through a local
+        /// proxy that refuses every other destination.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        network_allowlist: Vec<String>,
     },
 }
 
@@ -342,6 +566,75 @@ impl WritableRoot {
     }
 }
 
+/// Upper bound on filesystem entries visited while expanding one write glob.
+const MAX_WRITE_GLOB_ENTRIES: usize = 10_000;
+
+/// Expands `allow_write`/`deny_write` patterns into the existing paths they
+/// match. Each walk starts at the pattern's literal prefix, so `docs/**` never
+/// scans outside `docs/`. A matched directory covers everything beneath it.
+pub fn expand_write_globs(patterns: &[String], cwd: &Path) -> Vec<PathBuf> {
+    let mut matches = Vec::new();
+    for pattern in patterns {
+        let pattern = cwd.join(pattern);
+        let mut base = PathBuf::new();
+        let mut remaining = Vec::new();
+        for component in pattern.components() {
+            let text = component.as_os_str().to_string_lossy();
+            if remaining.is_empty() && !text.contains(['*', '?', '[', '{']) {
+                base.push(component);
+            } else {
+                remaining.push(text.into_owned());
+            }
+        }
+        if remaining.is_empty() {
+            if pattern.exists() {
+                matches.push(pattern);
+            }
+            continue;
+        }
+
+        let matcher = match GlobBuilder::new(&pattern.to_string_lossy())
+            .literal_separator(true)
+            .build()
+        {
+            Ok(glob) => glob.compile_matcher(),
+            Err(err) => {
+                tracing::warn!(
+                    "ignoring invalid sandbox write glob {}: {err}",
+                    pattern.display()
+                );
+                continue;
+            }
+        };
+        let mut walker = WalkDir::new(&base).min_depth(1);
+        if !remaining.iter().any(|component| component == "**") {
+            walker = walker.max_depth(remaining.len());
+        }
+        let mut entries = walker.into_iter();
+        let mut visited = 0;
+        while let Some(entry) = entries.next() {
+            visited += 1;
+            if visited > MAX_WRITE_GLOB_ENTRIES {
+                tracing::warn!(
+                    "sandbox write glob {} matched too many entries",
+                    pattern.display()
+                );
+                break;
+            }
+            let Ok(entry) = entry else {
+                continue;
+            };
+            if matcher.is_match(entry.path()) {
+                if entry.file_type().is_dir() {
+                    entries.skip_current_dir();
+                }
+                matches.push(entry.into_path());
+            }
+        }
+    }
+    matches
+}
+
 impl FromStr for SandboxPolicy {
     type Err = serde_json::Error;
 
@@ -365,6 +658,9 @@ impl SandboxPolicy {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec![],
         }
     }
 
@@ -389,6 +685,19 @@ impl SandboxPolicy {
         }
     }
 
+    /// Destinations reachable through the sandbox's network proxy. Empty when
+    /// network access is either full or fully blocked.
+    pub fn network_allowlist(&self) -> &[String] {
+        match self {
+            SandboxPolicy::WorkspaceWrite {
+                network_access: false,
+                network_allowlist,
+                ..
+            } => network_allowlist,
+            _ => &[],
+        }
+    }
+
     /// Returns the list of writable roots (tailored to the current working
     /// directory) together with subpaths that should remain read‑only under
     /// each writable root.
@@ -401,6 +710,9 @@ impl SandboxPolicy {
                 exclude_tmpdir_env_var,
                 exclude_slash_tmp,
                 network_access: _,
+                allow_write,
+                deny_write,
+                network_allowlist: _,
             } => {
                 // Start from explicitly configured writable roots.
                 let mut roots: Vec<PathBuf> = writable_roots.clone();
@@ -432,6 +744,10 @@ impl SandboxPolicy {
                     roots.push(PathBuf::from(tmpdir));
                 }
 
+                // Paths matched by `allow_write` become roots of their own.
+                roots.extend(expand_write_globs(allow_write, cwd));
+                let denied = expand_write_globs(deny_write, cwd);
+
                 // For each root, compute subpaths that should remain read-only.
                 roots
                     .into_iter()
@@ -441,6 +757,12 @@ impl SandboxPolicy {
                         if top_level_git.is_dir() {
                             subpaths.push(top_level_git);
                         }
+                        subpaths.extend(
+                            denied
+                                .iter()
+                                .filter(|path| path.starts_with(&writable_root))
+                                .cloned(),
+                        );
                         WritableRoot {
                             root: writable_root,
                             read_only_subpaths: subpaths,
@@ -475,6 +797,21 @@ pub enum EventMsg {
     /// indicates the task continued but the user should still be notified.
     Warning(WarningEvent),
 
+    /// `config.toml` changed while the session was running.
+    ConfigChanged(ConfigChangedEvent),
+
+    /// Another process took this session's rollout over, so nothing more is
+    /// recorded until `Op::ReclaimSessionLock`.
+    SessionLockLost(SessionLockLostEvent),
+
+    /// A model request failed with a transient error and will be retried
+    /// after `delay_ms`. Interrupting the turn cancels the retry.
+    TurnRetryScheduled(TurnRetryScheduledEvent),
+
+    /// The running turn produced no output, tool activity, or provider
+    /// traffic for longer than `stall_watchdog.timeout_secs`.
+    StallDetected(StallDetectedEvent),
+
     /// Conversation history was compacted (either automatically or manually).
     ContextCompacted(ContextCompactedEvent),
 
@@ -520,6 +857,22 @@ pub enum EventMsg {
     /// Aggregate MCP startup completion summary.
     McpStartupComplete(McpStartupCompleteEvent),
 
+    /// A running MCP server lost its connection (for example, its process
+    /// exited). Codex retries in the background with backoff.
+    McpServerDown(McpServerDownEvent),
+
+    /// An MCP server was reconnected after going down or on request.
+    McpServerRestarted(McpServerRestartedEvent),
+
+    /// A subsystem that starts in the background after `SessionConfigured`
+    /// (memory, semantic search) finished starting.
+    SubsystemStartup(SubsystemStartupEvent),
+
+    /// Project docs (`AGENTS.md` and configured fallbacks) included in the
+    /// session's instructions. Sent once after `SessionConfigured` when any
+    /// doc was found.
+    ProjectDocsLoaded(ProjectDocsLoadedEvent),
+
     McpToolCallBegin(McpToolCallBeginEvent),
 
     McpToolCallEnd(McpToolCallEndEvent),
@@ -536,9 +889,16 @@ pub enum EventMsg {
 
     ExecCommandEnd(ExecCommandEndEvent),
 
+    /// A single tool call was cancelled via `Op::CancelToolCall`; no end
+    /// event will follow for it.
+    ToolCallCancelled(ToolCallCancelledEvent),
+
     /// Notification that the agent attached a local image via the view_image tool.
     ViewImageToolCall(ViewImageToolCallEvent),
 
+    /// Notification that the agent read a file with the read_file tool.
+    FileRead(FileReadEvent),
+
     ExecApprovalRequest(ExecApprovalRequestEvent),
 
     ElicitationRequest(ElicitationRequestEvent),
@@ -561,6 +921,9 @@ pub enum EventMsg {
 
     CheckpointList(CheckpointListEvent),
 
+    /// Unified diff between two checkpoints, in response to `Op::DiffCheckpoints`.
+    CheckpointDiff(CheckpointDiffEvent),
+
     CheckpointError(CheckpointErrorEvent),
 
     /// Notification that a model stream experienced an error or disconnect
@@ -576,17 +939,32 @@ pub enum EventMsg {
 
     TurnDiff(TurnDiffEvent),
 
+    /// Accumulated dollar cost of the turn and session, emitted after each
+    /// model response for models with known pricing.
+    CostUpdate(CostUpdateEvent),
+
+    /// The session exceeded `max_session_cost_usd`; the turn is paused until
+    /// the client replies with `Op::CostLimitApproval`.
+    CostLimitApprovalRequest(CostLimitApprovalRequestEvent),
+
     /// Response to GetHistoryEntryRequest.
     GetHistoryEntryResponse(GetHistoryEntryResponseEvent),
 
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
+    /// MCP servers that rely on secrets that could not be found.
+    McpUnresolvedSecretsResponse(McpUnresolvedSecretsResponseEvent),
+
     /// List of custom prompts available to the agent.
     ListCustomPromptsResponse(ListCustomPromptsResponseEvent),
 
     PlanUpdate(UpdatePlanArgs),
 
+    /// A plan drafted via `Op::StartPlan` or `Op::RevisePlan` is ready for
+    /// review.
+    PlanReady(PlanReadyEvent),
+
     TurnAborted(TurnAbortedEvent),
 
     /// Notification that the agent is shutting down.
@@ -613,12 +991,94 @@ pub enum EventMsg {
     SubagentTaskLog(SubagentTaskLogEvent),
     /// Tool/history events streamed from subagent tasks.
     SubagentHistoryItem(SubagentHistoryItemEvent),
+    /// Per-task diffs and conflicting edits once all tasks of a
+    /// `subagent_run` call have finished.
+    SubagentReport(SubagentReportEvent),
+    /// Notes the subagents of one call shared while they ran.
+    SubagentScratchpad(SubagentScratchpadEvent),
 
     /// Response to a Remember operation.
     MemoryRememberResponse(MemoryRememberResponseEvent),
 
     /// Response to a ListMemories operation.
     MemoryListResponse(MemoryListResponseEvent),
+
+    /// Response to a ConsolidateMemories operation.
+    MemoriesConsolidated(MemoriesConsolidatedEvent),
+
+    /// Memories recalled into the model's context for this turn.
+    MemoryContextInjected(MemoryContextInjectedEvent),
+
+    /// Response to a SummarizeSession operation.
+    SessionSummary(SessionSummaryEvent),
+
+    /// Response to a ForkConversation operation.
+    ConversationForked(ConversationForkedEvent),
+
+    /// A step of a MergeConversation operation started.
+    ConversationMergeProgress(ConversationMergeProgressEvent),
+
+    /// Response to a MergeConversation operation. Persisted so resumed
+    /// transcripts can mark where the context was merged in.
+    ConversationMerged(ConversationMergedEvent),
+
+    /// The last answer was discarded by a RegenerateLast operation; the new
+    /// turn follows.
+    ResponseRegenerated(ResponseRegeneratedEvent),
+
+    /// The conversation was rewound by an EditAndResend operation; the turn
+    /// for the edited message follows.
+    UserMessageEdited(UserMessageEditedEvent),
+
+    /// Response to a GetMessageVariants operation.
+    MessageVariants(MessageVariantsEvent),
+
+    /// Response to a SetSessionEnv operation.
+    SessionEnvUpdated(SessionEnvUpdatedEvent),
+
+    /// Response to a GetStorageReport operation.
+    StorageReport(StorageReportEvent),
+
+    /// A step of a CleanupStorage operation started.
+    StorageCleanupProgress(StorageCleanupProgressEvent),
+
+    /// Outcome of a CleanupStorage operation.
+    StorageCleanupCompleted(StorageCleanupCompletedEvent),
+
+    /// Prompts the user might send next, proposed after a turn completes
+    /// when the `follow_up_suggestions` feature is enabled.
+    FollowUpSuggestions(FollowUpSuggestionsEvent),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct CostUpdateEvent {
+    /// Cost of the current turn in USD.
+    pub turn_cost_usd: f64,
+    /// Cost of the whole session in USD.
+    pub session_cost_usd: f64,
+    /// Configured session budget, if any.
+    pub max_session_cost_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct CostLimitApprovalRequestEvent {
+    /// Cost of the session so far in USD.
+    pub session_cost_usd: f64,
+    /// Configured session budget in USD.
+    pub max_session_cost_usd: f64,
+}
+
+/// Plan drafted by the plan-first workflow and awaiting approval.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PlanReadyEvent {
+    /// The user's original request.
+    pub request: String,
+    /// Latest plan published via `update_plan`, if any.
+    pub plan: Option<UpdatePlanArgs>,
+    /// Final agent message from the planning turn.
+    pub summary: Option<String>,
+    /// Feedback rounds applied so far.
+    pub feedback: Vec<String>,
 }
 
 /// Status of an individual subagent task.
@@ -631,6 +1091,7 @@ pub enum SubagentTaskStatus {
     Done,
     Timeout,
     Failed,
+    Cancelled,
 }
 
 /// Update describing the state of a subagent task.
@@ -642,6 +1103,14 @@ pub struct SubagentTaskUpdateEvent {
     pub call_id: String,
     /// Task label provided by the tool arguments.
     pub task: String,
+    /// Role preset the task runs with, if one was assigned.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub role: Option<String>,
+    /// Index (0-based) of the task within the tool call; used to cancel it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub agent_index: Option<i64>,
     /// Current status of the task.
     pub status: SubagentTaskStatus,
     /// Optional short summary or last output line.
@@ -678,6 +1147,70 @@ pub struct SubagentHistoryItemEvent {
     pub event: Box<EventMsg>,
 }
 
+/// Edits made by the subagent tasks of one `subagent_run` call.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub struct SubagentReportEvent {
+    /// Tool call id that launched the subagents.
+    pub call_id: String,
+    /// One entry per task, in the order the tasks were given.
+    pub agents: Vec<SubagentAgentReport>,
+    /// Files edited by more than one task.
+    pub conflicts: Vec<SubagentConflict>,
+}
+
+/// Outcome and edits of a single subagent task.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub struct SubagentAgentReport {
+    /// Task label provided by the tool arguments.
+    pub task: String,
+    /// Index (0-based) of the task within the tool call.
+    pub agent_index: i64,
+    /// Final status of the task.
+    pub status: SubagentTaskStatus,
+    /// Files the task edited, sorted.
+    pub files: Vec<PathBuf>,
+    /// Unified diff of the task's edits, if it made any.
+    pub unified_diff: Option<String>,
+}
+
+/// A file edited by several subagent tasks of the same call.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub struct SubagentConflict {
+    pub path: PathBuf,
+    /// Indices (0-based) of the tasks that edited the file.
+    pub agent_indices: Vec<i64>,
+}
+
+/// Final contents of the scratchpad shared by the subagent tasks of one
+/// `subagent_run` call.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub struct SubagentScratchpadEvent {
+    /// Tool call id that launched the subagents.
+    pub call_id: String,
+    /// Notes in the order they were written.
+    pub notes: Vec<SubagentScratchpadNote>,
+}
+
+/// A note one subagent task left for the others.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub struct SubagentScratchpadNote {
+    /// Task label of the subagent that wrote the note.
+    pub task: String,
+    /// Index (0-based) of that task within the tool call.
+    pub agent_index: i64,
+    pub text: String,
+}
+
 /// Codex errors that we expose to clients.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
@@ -704,6 +1237,8 @@ pub enum CodexErrorInfo {
     ResponseTooManyFailedAttempts {
         http_status_code: Option<u16>,
     },
+    /// The stall watchdog interrupted a turn that stopped making progress.
+    TurnStalled,
     Other,
 }
 
@@ -840,6 +1375,46 @@ pub struct WarningEvent {
     pub message: String,
 }
 
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ConfigChangedEvent {
+    /// Config keys whose new values are already in effect, e.g.
+    /// `tui.notifications`.
+    pub applied: Vec<String>,
+    /// Config keys that changed but only apply to new sessions, e.g.
+    /// `model_provider`.
+    pub requires_new_session: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SessionLockLostEvent {
+    /// Rollout that is no longer being written by this session.
+    pub rollout_path: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnRetryScheduledEvent {
+    /// 1-based number of the upcoming retry.
+    pub attempt: i64,
+    /// Retries allowed before the turn fails.
+    pub max_attempts: i64,
+    /// Milliseconds until the retry starts.
+    pub delay_ms: i64,
+    /// The error that triggered the retry.
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct StallDetectedEvent {
+    /// Seconds since the turn last showed any activity.
+    pub idle_seconds: i64,
+    /// Type of the last event the turn emitted, e.g. `exec_command_begin`.
+    pub last_event: String,
+    /// Tool calls that had started but not finished when the stall was detected.
+    pub running_tool_calls: i64,
+    /// Whether the watchdog interrupted the turn (`stall_watchdog.auto_interrupt`).
+    pub interrupted: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
@@ -851,6 +1426,15 @@ pub struct TaskCompleteEvent {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TaskStartedEvent {
     pub model_context_window: Option<i64>,
+    /// Model used for this turn.
+    #[serde(default)]
+    pub model: String,
+    /// Reasoning effort used for this turn, if the model supports it.
+    #[serde(default)]
+    pub reasoning_effort: Option<ReasoningEffortConfig>,
+    /// Sandbox policy in effect for this turn.
+    #[serde(default)]
+    pub sandbox_policy: Option<SandboxPolicy>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default, JsonSchema, TS)]
@@ -1133,6 +1717,26 @@ pub struct McpToolCallEndEvent {
     pub duration: Duration,
     /// Result of the tool call. Note this could be an error.
     pub result: Result<CallToolResult, String>,
+    /// Image content from `result`, saved to disk so clients can display it
+    /// by path.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub images: Vec<McpImageAttachment>,
+}
+
+/// An image returned by an MCP tool and saved under `~/.codex/attachments/`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct McpImageAttachment {
+    pub path: PathBuf,
+    pub mime_type: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct ToolCallCancelledEvent {
+    /// Identifier of the cancelled tool call.
+    pub call_id: String,
+    /// How long the call ran before it was cancelled.
+    #[ts(type = "string")]
+    pub duration: Duration,
 }
 
 impl McpToolCallEndEvent {
@@ -1444,6 +2048,11 @@ pub struct ExecCommandBeginEvent {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[ts(optional)]
     pub interaction_input: Option<String>,
+    /// The `approvals.auto_approve` rule that let this command run without
+    /// an approval prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub approval_rule: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1493,6 +2102,16 @@ pub struct ViewImageToolCallEvent {
     pub path: PathBuf,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct FileReadEvent {
+    /// Identifier for the originating tool call.
+    pub call_id: String,
+    pub path: PathBuf,
+    /// True when the content was served from an earlier read of the unchanged
+    /// file in the same turn, so UIs can de-emphasize it.
+    pub cached: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecOutputStream {
@@ -1565,6 +2184,14 @@ pub struct CheckpointListEvent {
     pub checkpoints: Vec<CheckpointEntry>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CheckpointDiffEvent {
+    pub from: CheckpointEntry,
+    pub to: CheckpointEntry,
+    /// Unified diff from `from` to `to`; empty when the checkpoints match.
+    pub unified_diff: String,
+}
+
 #[derive(
     Debug, Clone, Copy, Deserialize, Serialize, JsonSchema, TS, PartialEq, Eq, Display, Hash,
 )]
@@ -1574,6 +2201,7 @@ pub enum CheckpointAction {
     Create,
     Restore,
     List,
+    Diff,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1655,6 +2283,20 @@ pub struct McpListToolsResponseEvent {
     pub auth_statuses: std::collections::HashMap<String, McpAuthStatus>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpUnresolvedSecretsResponseEvent {
+    /// Servers with unresolved secrets, sorted by name.
+    pub servers: Vec<McpUnresolvedSecrets>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpUnresolvedSecrets {
+    /// Server name as configured under `mcp_servers`.
+    pub server: String,
+    /// Names of the `secret://` references that could not be found.
+    pub secrets: Vec<String>,
+}
+
 /// Response to a Remember operation.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct MemoryRememberResponseEvent {
@@ -1679,6 +2321,258 @@ pub struct MemoryListResponseEvent {
     pub storage_path: Option<String>,
 }
 
+/// Response to a ConsolidateMemories operation.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct MemoriesConsolidatedEvent {
+    /// One entry per cluster of near-duplicates that was merged.
+    pub consolidated: Vec<ConsolidatedMemoryEntry>,
+    /// Error message if consolidation failed.
+    pub error: Option<String>,
+}
+
+/// Memories recalled into the model's context ahead of a user turn, in
+/// ranking order.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct MemoryContextInjectedEvent {
+    pub memories: Vec<InjectedMemoryEntry>,
+}
+
+/// One recalled memory and why it was chosen.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct InjectedMemoryEntry {
+    /// Memory ID.
+    pub id: String,
+    /// Type of memory (fact, pattern, lesson, etc.).
+    pub memory_type: String,
+    /// Content of the memory.
+    pub content: String,
+    /// Unix timestamp when the memory was learned.
+    pub created_at: i64,
+    /// Combined retrieval score.
+    pub score: f64,
+    /// Human-readable reasons, e.g. "matches files you're editing".
+    pub reasons: Vec<String>,
+    /// Memory pack the entry came from (`name@version`), if any.
+    pub source_pack: Option<String>,
+}
+
+/// A canonical memory written from a cluster of near-duplicates.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct ConsolidatedMemoryEntry {
+    /// ID of the new canonical memory.
+    pub id: String,
+    /// Type of memory (fact, pattern, lesson, etc.).
+    pub memory_type: String,
+    /// Content of the canonical memory.
+    pub content: String,
+    /// IDs of the memories it replaces. They are kept for audit but no
+    /// longer retrieved.
+    pub merged_from: Vec<String>,
+}
+
+/// Response to a SummarizeSession operation.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct SessionSummaryEvent {
+    /// What the user set out to do.
+    pub goals: Vec<String>,
+    /// Decisions made along the way.
+    pub decisions: Vec<String>,
+    /// Files created or modified during the session.
+    pub files_changed: Vec<String>,
+    /// Questions or follow-ups left unresolved.
+    pub open_questions: Vec<String>,
+    /// Where the notes were written, when saving was requested.
+    pub saved_path: Option<PathBuf>,
+}
+
+/// Response to a ForkConversation operation.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct ConversationForkedEvent {
+    /// Id of the new conversation.
+    pub conversation_id: ConversationId,
+    /// Rollout file holding the copied history.
+    pub rollout_path: PathBuf,
+    /// The assistant message the fork was cut after.
+    pub at_item: usize,
+}
+
+/// Steps of a MergeConversation operation.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema, TS, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConversationMergeStage {
+    /// Reading the source conversation's rollout.
+    LoadingSource,
+    /// Asking the model for a summary of the source conversation.
+    Summarizing,
+    /// Recording the summary in this conversation's history.
+    Recording,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct ConversationMergeProgressEvent {
+    pub source_conversation_id: ConversationId,
+    pub stage: ConversationMergeStage,
+}
+
+/// Response to a MergeConversation operation.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct ConversationMergedEvent {
+    /// The conversation whose context was merged in; it is left unchanged.
+    pub source_conversation_id: ConversationId,
+    /// The summary recorded as merged context.
+    pub summary: SessionSummaryEvent,
+}
+
+/// One discarded answer to a user message.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct MessageVariant {
+    /// Id of the final assistant message of the attempt, when the model
+    /// provided one.
+    pub message_id: Option<String>,
+    /// The attempt's assistant messages, joined by blank lines.
+    pub text: String,
+    pub model: String,
+    pub effort: Option<ReasoningEffortConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct ResponseRegeneratedEvent {
+    /// The answer that was discarded.
+    pub previous_attempt: MessageVariant,
+    /// Number of assistant messages removed from the conversation.
+    pub removed_messages: usize,
+    /// Model and effort used for the new attempt.
+    pub model: String,
+    pub effort: Option<ReasoningEffortConfig>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct UserMessageEditedEvent {
+    /// 0-based position of the edited message among the user messages.
+    pub at_item: usize,
+    /// Number of user messages removed, the edited one included.
+    pub removed_user_messages: usize,
+    /// Number of assistant messages removed from the conversation.
+    pub removed_messages: usize,
+    /// Files pinned after the edited message was sent, now unpinned.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unpinned_files: Vec<PathBuf>,
+}
+
+/// Response to a GetMessageVariants operation.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct MessageVariantsEvent {
+    pub message_id: String,
+    /// Previous attempts, oldest first; empty when the answer was never
+    /// regenerated.
+    pub variants: Vec<MessageVariant>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct FollowUpSuggestionsEvent {
+    /// At most three short prompts, in the order the model proposed them.
+    /// Accept one by position with `Op::AcceptSuggestion`.
+    pub suggestions: Vec<String>,
+}
+
+/// Response to a SetSessionEnv operation.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct SessionEnvUpdatedEvent {
+    /// Names of the variables now set for the session, sorted. Values are
+    /// not echoed since they often hold credentials.
+    pub names: Vec<String>,
+    /// Project directory whose env profile was saved, when persisting was
+    /// requested and succeeded.
+    pub persisted_project: Option<PathBuf>,
+    /// Error message if saving the env profile failed. The variables apply
+    /// to the session either way.
+    pub error: Option<String>,
+}
+
+/// A kind of data Codex keeps on disk.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, JsonSchema, TS, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageCategory {
+    /// Rollout files of sessions under `~/.codex/sessions`.
+    Rollouts,
+    /// Compressed rollouts under `~/.codex/archived_sessions`.
+    ArchivedRollouts,
+    /// Images saved from MCP tool results under `~/.codex/attachments`.
+    Attachments,
+    /// The memory database of the session's project.
+    Memory,
+    /// Unreferenced git objects, such as checkpoint snapshots, in the
+    /// session's repository.
+    Checkpoints,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct StorageUsageEntry {
+    pub category: StorageCategory,
+    #[ts(type = "number")]
+    pub files: i64,
+    #[ts(type = "number")]
+    pub bytes: i64,
+}
+
+/// Rollouts recorded in one working directory.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct RepoStorageUsage {
+    pub cwd: PathBuf,
+    #[ts(type = "number")]
+    pub sessions: i64,
+    #[ts(type = "number")]
+    pub bytes: i64,
+}
+
+/// Response to a GetStorageReport operation.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct StorageReportEvent {
+    pub usage: Vec<StorageUsageEntry>,
+    /// Rollout usage per working directory, largest first.
+    pub repos: Vec<RepoStorageUsage>,
+}
+
+/// What a CleanupStorage operation removes. Nothing is removed unless asked.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct StorageCleanupOptions {
+    /// Delete active and archived rollouts not modified for this many days.
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub delete_rollouts_older_than_days: Option<i64>,
+    /// Prune unreferenced git objects older than this many days from the
+    /// session's repository. This removes checkpoints created that long ago.
+    #[serde(default)]
+    #[ts(type = "number | null")]
+    pub prune_checkpoints_older_than_days: Option<i64>,
+    /// Rebuild the project's memory database to reclaim free pages.
+    #[serde(default)]
+    pub vacuum_memory: bool,
+    /// Delete attachments of conversations whose rollout no longer exists.
+    #[serde(default)]
+    pub gc_attachments: bool,
+    /// Report what would be removed without changing anything.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct StorageCleanupProgressEvent {
+    pub category: StorageCategory,
+    pub message: String,
+}
+
+/// Outcome of a CleanupStorage operation.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, PartialEq)]
+pub struct StorageCleanupCompletedEvent {
+    pub dry_run: bool,
+    /// Files and bytes removed, or that would be removed on a dry run, per
+    /// category that was cleaned.
+    pub freed: Vec<StorageUsageEntry>,
+    /// Steps that failed; the other steps still ran.
+    pub errors: Vec<String>,
+}
+
 /// A single memory entry for display.
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct MemoryEntry {
@@ -1712,6 +2606,50 @@ pub enum McpStartupStatus {
     Cancelled,
 }
 
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SubsystemStartupEvent {
+    pub subsystem: SessionSubsystem,
+    pub status: SubsystemStartupStatus,
+}
+
+/// Session subsystems that come online after the session is usable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum SessionSubsystem {
+    Memory,
+    SemanticSearch,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case", tag = "state")]
+#[ts(rename_all = "snake_case", tag = "state")]
+pub enum SubsystemStartupStatus {
+    Ready,
+    /// The subsystem stays off for the rest of the session.
+    Unavailable {
+        reason: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ProjectDocsLoadedEvent {
+    /// Ordered from the repository root to the working directory; later docs
+    /// take precedence over earlier ones.
+    pub docs: Vec<ProjectDocEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ProjectDocEntry {
+    pub path: PathBuf,
+    /// Bytes of the file included in the instructions.
+    #[ts(type = "number")]
+    pub bytes: i64,
+    /// The file did not fit in `project_doc_max_bytes` and was cut short, or
+    /// left out entirely when `bytes` is 0.
+    pub truncated: bool,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS, Default)]
 pub struct McpStartupCompleteEvent {
     pub ready: Vec<String>,
@@ -1725,6 +2663,22 @@ pub struct McpStartupFailure {
     pub error: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpServerDownEvent {
+    /// Server whose connection was lost.
+    pub server: String,
+    /// Why the server is unavailable.
+    pub reason: String,
+    /// Whether Codex will keep trying to restart the server on its own.
+    pub retrying: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct McpServerRestartedEvent {
+    /// Server that is available again.
+    pub server: String,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 #[ts(rename_all = "snake_case")]
@@ -1792,14 +2746,18 @@ pub struct SessionConfiguredEvent {
 }
 
 /// User's decision in response to an ExecApprovalRequest.
-#[derive(
-    Debug, Default, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, Display, JsonSchema, TS,
-)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq, Display, JsonSchema, TS)]
 #[serde(rename_all = "snake_case")]
 pub enum ReviewDecision {
     /// User has approved this command and the agent should execute it.
     Approved,
 
+    /// Only valid for patch approvals: apply `changes`, the proposed changes
+    /// with some files or `@@` hunks of their unified diffs left out.
+    ApprovedPartially {
+        changes: HashMap<PathBuf, FileChange>,
+    },
+
     /// User has approved this command and wants to automatically approve any
     /// future identical instances (`command` and `cwd` match exactly) for the
     /// remainder of the session.
@@ -1815,7 +2773,7 @@ pub enum ReviewDecision {
     Abort,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema, TS)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[ts(tag = "type")]
 pub enum FileChange {
@@ -1932,6 +2890,88 @@ mod tests {
         assert!(event.as_legacy_events(false).is_empty());
     }
 
+    #[test]
+    fn task_started_event_serializes_turn_settings() -> Result<()> {
+        let event = TaskStartedEvent {
+            model_context_window: Some(32_000),
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: Some(ReasoningEffortConfig::High),
+            sandbox_policy: Some(SandboxPolicy::ReadOnly),
+        };
+
+        assert_eq!(
+            serde_json::to_value(&event)?,
+            json!({
+                "model_context_window": 32_000,
+                "model": "gpt-5.1-codex",
+                "reasoning_effort": "high",
+                "sandbox_policy": { "type": "read-only" },
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn task_started_event_without_turn_settings_deserializes() -> Result<()> {
+        // Events recorded before the turn settings were reported.
+        let event: TaskStartedEvent =
+            serde_json::from_value(json!({ "model_context_window": 32_000 }))?;
+
+        assert_eq!(event.model_context_window, Some(32_000));
+        assert_eq!(event.model, "");
+        assert_eq!(event.reasoning_effort, None);
+        assert_eq!(event.sandbox_policy, None);
+        Ok(())
+    }
+
+    #[test]
+    fn write_globs_refine_workspace_write_roots() -> Result<()> {
+        let workspace = tempfile::tempdir()?;
+        let outside = tempfile::tempdir()?;
+        let cwd = workspace.path();
+        std::fs::create_dir_all(cwd.join("migrations"))?;
+        std::fs::create_dir_all(cwd.join("app/config"))?;
+        std::fs::write(cwd.join(".env"), "")?;
+        std::fs::write(cwd.join("app/config/.env"), "")?;
+        std::fs::write(cwd.join("app/main.rs"), "")?;
+        std::fs::create_dir_all(outside.path().join("cache"))?;
+
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+            allow_write: vec![format!("{}/*", outside.path().display())],
+            deny_write: vec!["**/.env".to_string(), "migrations".to_string()],
+            network_allowlist: vec![],
+        };
+        let mut roots = policy.get_writable_roots_with_cwd(cwd);
+        for root in &mut roots {
+            root.read_only_subpaths.sort();
+        }
+
+        assert_eq!(
+            roots,
+            vec![
+                WritableRoot {
+                    root: cwd.to_path_buf(),
+                    read_only_subpaths: vec![
+                        cwd.join(".env"),
+                        cwd.join("app/config/.env"),
+                        cwd.join("migrations"),
+                    ],
+                },
+                WritableRoot {
+                    root: outside.path().join("cache"),
+                    read_only_subpaths: vec![],
+                },
+            ]
+        );
+        assert!(roots[0].is_path_writable(&cwd.join("app/main.rs")));
+        assert!(!roots[0].is_path_writable(&cwd.join("migrations/0001.sql")));
+        Ok(())
+    }
+
     /// Serialize Event to verify that its JSON representation has the expected
     /// amount of nesting.
     #[test]
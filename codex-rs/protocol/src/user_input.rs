@@ -27,4 +27,49 @@ pub enum UserInput {
         name: String,
         path: std::path::PathBuf,
     },
+
+    /// Anchor for a `> ` quote of an earlier assistant message in the
+    /// accompanying text. Sent to the model as a short note naming the
+    /// quoted message.
+    Quote {
+        /// Position of the quoted message among the conversation's assistant
+        /// messages, counting from 0.
+        item: usize,
+    },
+}
+
+const QUOTE_CONTEXT_OPEN_TAG: &str = "<quote_context item=\"";
+const QUOTE_CONTEXT_CLOSE_TAG: &str = "</quote_context>";
+
+/// Model-facing text for [`UserInput::Quote`].
+pub fn quote_context_text(item: usize) -> String {
+    let number = item + 1;
+    format!(
+        "{QUOTE_CONTEXT_OPEN_TAG}{item}\">The lines starting with \"> \" in this message quote your assistant message #{number} in this conversation.{QUOTE_CONTEXT_CLOSE_TAG}"
+    )
+}
+
+/// Inverse of [`quote_context_text`], for rebuilding user messages from
+/// history.
+pub fn parse_quote_context(text: &str) -> Option<usize> {
+    let rest = text.trim().strip_prefix(QUOTE_CONTEXT_OPEN_TAG)?;
+    if !rest.ends_with(QUOTE_CONTEXT_CLOSE_TAG) {
+        return None;
+    }
+    let (item, _) = rest.split_once('"')?;
+    item.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn quote_context_round_trips() {
+        let text = quote_context_text(2);
+        assert!(text.contains("assistant message #3"));
+        assert_eq!(parse_quote_context(&text), Some(2));
+        assert_eq!(parse_quote_context("> quoted by the user"), None);
+    }
 }
@@ -383,6 +383,16 @@ impl RmcpClient {
         Ok(())
     }
 
+    /// Returns true once an initialized connection has gone away, for example
+    /// because a stdio server process exited.
+    pub async fn is_closed(&self) -> bool {
+        let guard = self.state.lock().await;
+        match &*guard {
+            ClientState::Ready { service, .. } => service.is_transport_closed(),
+            ClientState::Connecting { .. } => false,
+        }
+    }
+
     async fn service(&self) -> Result<Arc<RunningService<RoleClient, LoggingClientHandler>>> {
         let guard = self.state.lock().await;
         match &*guard {
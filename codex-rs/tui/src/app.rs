@@ -8,13 +8,19 @@ use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::file_search::FileSearchManager;
 use crate::history_cell::HistoryCell;
+use crate::history_cell::ImageHistoryCell;
+use crate::keymap::KeyAction;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_config;
 use crate::model_migration::run_model_migration_prompt;
 use crate::pager_overlay::Overlay;
+use crate::render::centered_columns;
 use crate::render::highlight::highlight_bash_to_lines;
+use crate::render::line_utils::prefix_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::ResumeSelection;
+use crate::theme;
+use crate::theme::ThemeStylize;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
@@ -28,6 +34,7 @@ use codex_core::AuthManager;
 use codex_core::ConversationManager;
 use codex_core::config::Config;
 use codex_core::config::edit::ConfigEditsBuilder;
+use codex_core::config::reload;
 use codex_core::config::types::SUBAGENT_LIMIT_HARD_CAP;
 use codex_core::config::types::SUBAGENT_LIMIT_MIN;
 #[cfg(target_os = "windows")]
@@ -39,6 +46,7 @@ use codex_core::protocol::Op;
 use codex_core::protocol::SessionSource;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol_config_types::ReasoningEffort as ReasoningEffortConfig;
+use codex_core::replay::RolloutReplay;
 use codex_protocol::ConversationId;
 use color_eyre::eyre::Result;
 use color_eyre::eyre::WrapErr;
@@ -212,6 +220,9 @@ pub(crate) struct App {
     // Pager overlay state (Transcript or Static like Diff)
     pub(crate) overlay: Option<Overlay>,
     pub(crate) deferred_history_lines: Vec<Line<'static>>,
+    /// History lines held back while the user has unfollowed the live tail;
+    /// `None` while following.
+    pub(crate) unfollowed_history_lines: Option<Vec<Line<'static>>>,
     has_emitted_history_lines: bool,
 
     pub(crate) enhanced_keys_supported: bool,
@@ -242,6 +253,79 @@ impl App {
         }
     }
 
+    /// Swap the current conversation for one resumed from `path`, taking it
+    /// over from another process when `take_over` is set. The current
+    /// session is kept if the rollout cannot be resumed.
+    async fn resume_session_from_rollout(
+        &mut self,
+        tui: &mut tui::Tui,
+        path: PathBuf,
+        take_over: bool,
+    ) {
+        let config = self.config.clone();
+        let auth_manager = self.auth_manager.clone();
+        let resumed = if take_over {
+            self.server
+                .take_over_conversation_from_rollout(config, path.clone(), auth_manager)
+                .await
+        } else {
+            self.server
+                .resume_conversation_from_rollout(config, path.clone(), auth_manager)
+                .await
+        };
+        let resumed = match resumed {
+            Ok(resumed) => resumed,
+            Err(err) => {
+                self.chat_widget.add_error_message(format!(
+                    "Failed to resume session from {}: {err}",
+                    path.display()
+                ));
+                return;
+            }
+        };
+        self.shutdown_current_conversation().await;
+        self.follow_live_tail(tui);
+        let init = crate::chatwidget::ChatWidgetInit {
+            config: self.config.clone(),
+            frame_requester: tui.frame_requester(),
+            app_event_tx: self.app_event_tx.clone(),
+            initial_prompt: None,
+            initial_images: Vec::new(),
+            enhanced_keys_supported: self.enhanced_keys_supported,
+            auth_manager: self.auth_manager.clone(),
+            feedback: self.feedback.clone(),
+        };
+        self.chat_widget =
+            ChatWidget::new_from_existing(init, resumed.conversation, resumed.session_configured);
+    }
+
+    /// Show a rollout read-only, e.g. one another process is still writing.
+    async fn replay_rollout(&mut self, tui: &mut tui::Tui, path: PathBuf, speed: f64) {
+        let replay = match RolloutReplay::load(&path).await {
+            Ok(replay) => replay,
+            Err(err) => {
+                self.chat_widget.add_error_message(format!(
+                    "Failed to load rollout for replay from {}: {err}",
+                    path.display()
+                ));
+                return;
+            }
+        };
+        self.shutdown_current_conversation().await;
+        self.follow_live_tail(tui);
+        let init = crate::chatwidget::ChatWidgetInit {
+            config: self.config.clone(),
+            frame_requester: tui.frame_requester(),
+            app_event_tx: self.app_event_tx.clone(),
+            initial_prompt: None,
+            initial_images: Vec::new(),
+            enhanced_keys_supported: self.enhanced_keys_supported,
+            auth_manager: self.auth_manager.clone(),
+            feedback: self.feedback.clone(),
+        };
+        self.chat_widget = ChatWidget::new_replay(init, replay, speed);
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn run(
         tui: &mut tui::Tui,
@@ -272,6 +356,7 @@ impl App {
 
         let enhanced_keys_supported = tui.enhanced_keys_supported();
 
+        let take_over = matches!(resume_selection, ResumeSelection::TakeOver(_));
         let mut chat_widget = match resume_selection {
             ResumeSelection::StartFresh | ResumeSelection::Exit => {
                 let init = crate::chatwidget::ChatWidgetInit {
@@ -286,17 +371,27 @@ impl App {
                 };
                 ChatWidget::new(init, conversation_manager.clone())
             }
-            ResumeSelection::Resume(path) => {
-                let resumed = conversation_manager
-                    .resume_conversation_from_rollout(
-                        config.clone(),
-                        path.clone(),
-                        auth_manager.clone(),
-                    )
-                    .await
-                    .wrap_err_with(|| {
-                        format!("Failed to resume session from {}", path.display())
-                    })?;
+            ResumeSelection::Resume(path) | ResumeSelection::TakeOver(path) => {
+                let resumed = if take_over {
+                    conversation_manager
+                        .take_over_conversation_from_rollout(
+                            config.clone(),
+                            path.clone(),
+                            auth_manager.clone(),
+                        )
+                        .await
+                } else {
+                    conversation_manager
+                        .resume_conversation_from_rollout(
+                            config.clone(),
+                            path.clone(),
+                            auth_manager.clone(),
+                        )
+                        .await
+                };
+                let resumed = resumed.wrap_err_with(|| {
+                    format!("Failed to resume session from {}", path.display())
+                })?;
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: config.clone(),
                     frame_requester: tui.frame_requester(),
@@ -313,9 +408,26 @@ impl App {
                     resumed.session_configured,
                 )
             }
+            ResumeSelection::Replay { path, speed } => {
+                let replay = RolloutReplay::load(&path).await.wrap_err_with(|| {
+                    format!("Failed to load rollout for replay from {}", path.display())
+                })?;
+                let init = crate::chatwidget::ChatWidgetInit {
+                    config: config.clone(),
+                    frame_requester: tui.frame_requester(),
+                    app_event_tx: app_event_tx.clone(),
+                    initial_prompt: None,
+                    initial_images: Vec::new(),
+                    enhanced_keys_supported,
+                    auth_manager: auth_manager.clone(),
+                    feedback: feedback.clone(),
+                };
+                ChatWidget::new_replay(init, replay, speed)
+            }
         };
 
         chat_widget.maybe_prompt_windows_sandbox_enable();
+        chat_widget.show_keymap_warnings();
 
         let file_search = FileSearchManager::new(config.cwd.clone(), app_event_tx.clone());
         #[cfg(not(debug_assertions))]
@@ -333,6 +445,7 @@ impl App {
             transcript_cells: Vec::new(),
             overlay: None,
             deferred_history_lines: Vec::new(),
+            unfollowed_history_lines: None,
             has_emitted_history_lines: false,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
             backtrack: BacktrackState::default(),
@@ -366,6 +479,23 @@ impl App {
             }
         }
 
+        if let Some(name) = app.config.tui_theme.clone() {
+            app.apply_theme(&name);
+        }
+
+        // Run the startup self-check in the background so it never delays the
+        // first frame; problems show up as a banner above the composer.
+        {
+            let config = app.config.clone();
+            let tx = app.app_event_tx.clone();
+            tokio::spawn(async move {
+                let findings = codex_core::doctor::run_checks(&config).await;
+                if !findings.is_empty() {
+                    tx.send(AppEvent::DoctorFindings(findings));
+                }
+            });
+        }
+
         #[cfg(not(debug_assertions))]
         if let Some(latest_version) = upgrade_version {
             app.handle_event(
@@ -451,6 +581,7 @@ impl App {
                     self.chat_widget.conversation_id(),
                 );
                 self.shutdown_current_conversation().await;
+                self.follow_live_tail(tui);
                 let init = crate::chatwidget::ChatWidgetInit {
                     config: self.config.clone(),
                     frame_requester: tui.frame_requester(),
@@ -465,13 +596,45 @@ impl App {
                 if let Some(summary) = summary {
                     let mut lines: Vec<Line<'static>> = vec![summary.usage_line.clone().into()];
                     if let Some(command) = summary.resume_command {
-                        let spans = vec!["To continue this session, run ".into(), command.cyan()];
+                        let spans = vec!["To continue this session, run ".into(), command.accent()];
                         lines.push(spans.into());
                     }
                     self.chat_widget.add_plain_history_lines(lines);
                 }
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::OpenResumePicker => {
+                let selection = crate::resume_picker::run_resume_picker(
+                    tui,
+                    &self.config.codex_home,
+                    &self.config.model_provider_id,
+                    false,
+                )
+                .await?;
+                match selection {
+                    ResumeSelection::Resume(path) => {
+                        self.resume_session_from_rollout(tui, path, false).await;
+                    }
+                    ResumeSelection::TakeOver(path) => {
+                        self.resume_session_from_rollout(tui, path, true).await;
+                    }
+                    ResumeSelection::Replay { path, speed } => {
+                        self.replay_rollout(tui, path, speed).await;
+                    }
+                    ResumeSelection::StartFresh | ResumeSelection::Exit => {}
+                }
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::ResumeSession(path) => {
+                self.resume_session_from_rollout(tui, path, false).await;
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::QuoteAgentMessage(at_item) => {
+                self.chat_widget.quote_agent_message(at_item);
+            }
+            AppEvent::PinAgentMessage { at_item, pinned } => {
+                self.chat_widget.pin_agent_message(at_item, pinned);
+            }
             AppEvent::InsertHistoryCell(cell) => {
                 let cell: Arc<dyn HistoryCell> = cell.into();
                 if let Some(Overlay::Transcript(t)) = &mut self.overlay {
@@ -479,7 +642,10 @@ impl App {
                     tui.frame_requester().schedule_frame();
                 }
                 self.transcript_cells.push(cell.clone());
-                let mut display = cell.display_lines(tui.terminal.last_known_screen_size.width);
+                let mut display = self.history_display_lines(
+                    cell.as_ref(),
+                    tui.terminal.last_known_screen_size.width,
+                );
                 if !display.is_empty() {
                     // Only insert a separating blank line for new cells that are not
                     // part of an ongoing stream. Streaming continuations should not
@@ -491,10 +657,17 @@ impl App {
                             self.has_emitted_history_lines = true;
                         }
                     }
-                    if self.overlay.is_some() {
-                        self.deferred_history_lines.extend(display);
-                    } else {
-                        tui.insert_history_lines(display);
+                    self.insert_history_lines(tui, display);
+                    // Held lines are replayed as text only, so the image is drawn
+                    // only when the cell goes straight to the scrollback.
+                    if self.overlay.is_none()
+                        && self.unfollowed_history_lines.is_none()
+                        && let Some(image) = cell
+                            .as_any()
+                            .downcast_ref::<ImageHistoryCell>()
+                            .and_then(ImageHistoryCell::inline_image)
+                    {
+                        tui.insert_history_image(image.clone());
                     }
                 }
             }
@@ -527,6 +700,19 @@ impl App {
                     self.suppress_shutdown_complete = false;
                     return Ok(true);
                 }
+                if let Some(Overlay::ExecOutput(o)) = &mut self.overlay {
+                    match &event.msg {
+                        EventMsg::ExecCommandOutputDelta(ev) if ev.call_id == o.call_id() => {
+                            o.push_output(&String::from_utf8_lossy(&ev.chunk));
+                            tui.frame_requester().schedule_frame();
+                        }
+                        EventMsg::ExecCommandEnd(ev) if ev.call_id == o.call_id() => {
+                            o.finish(ev.exit_code);
+                            tui.frame_requester().schedule_frame();
+                        }
+                        _ => {}
+                    }
+                }
                 self.chat_widget.handle_codex_event(event);
             }
             AppEvent::ConversationHistory(ev) => {
@@ -552,6 +738,15 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::OpenExecOutput {
+                call_id,
+                command,
+                output,
+            } => {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_exec_output(call_id, command, output));
+                tui.frame_requester().schedule_frame();
+            }
             AppEvent::StartFileSearch(query) => {
                 if !query.is_empty() {
                     self.file_search.on_user_query(query);
@@ -560,6 +755,9 @@ impl App {
             AppEvent::FileSearchResult { query, matches } => {
                 self.chat_widget.apply_file_search_result(query, matches);
             }
+            AppEvent::DoctorFindings(findings) => {
+                self.chat_widget.set_doctor_findings(findings);
+            }
             AppEvent::SemanticStatusUpdate(status, message) => {
                 self.chat_widget.set_semantic_status(status, message);
             }
@@ -569,6 +767,55 @@ impl App {
             AppEvent::UpdateReasoningEffort(effort) => {
                 self.on_update_reasoning_effort(effort);
             }
+            AppEvent::UpdateMaxTranscriptWidth(width) => {
+                self.config.max_transcript_width = width;
+            }
+            AppEvent::SetTheme(name) => {
+                if self.apply_theme(&name) {
+                    self.config.tui_theme = Some(name.clone());
+                    self.chat_widget.set_tui_theme(name.clone());
+                    if let Err(err) = ConfigEditsBuilder::new(&self.config.codex_home)
+                        .set_tui_theme(&name)
+                        .apply()
+                        .await
+                    {
+                        tracing::error!(error = %err, "failed to persist theme");
+                        self.chat_widget
+                            .add_error_message(format!("Failed to save theme preference: {err}"));
+                    }
+                    self.chat_widget.add_info_message(
+                        format!("Theme set to {name}"),
+                        Some("Output already in the scrollback keeps its colors.".to_string()),
+                    );
+                    tui.frame_requester().schedule_frame();
+                }
+            }
+            AppEvent::SetCompactionPolicies(policies) => {
+                self.config.compaction.policies = policies.clone();
+                // The running session picks the change up from config.toml.
+                if let Err(err) = ConfigEditsBuilder::new(&self.config.codex_home)
+                    .set_compaction_policies(&policies)
+                    .apply()
+                    .await
+                {
+                    tracing::error!(error = %err, "failed to persist compaction policies");
+                    self.chat_widget
+                        .add_error_message(format!("Failed to save compaction policies: {err}"));
+                }
+                self.chat_widget.set_compaction_policies(policies);
+            }
+            AppEvent::OpenCompactionPopup => {
+                self.chat_widget.open_compaction_popup();
+            }
+            AppEvent::FileWatchTriggered {
+                id,
+                first_change_at,
+                file_count,
+                message,
+            } => {
+                self.chat_widget
+                    .on_file_watch_triggered(id, first_change_at, file_count, message);
+            }
             AppEvent::UpdateModel(model) => {
                 self.chat_widget.set_model(&model);
                 self.config.model = model.clone();
@@ -674,10 +921,17 @@ impl App {
                 match ConfigEditsBuilder::new(&self.config.codex_home)
                     .with_profile(profile)
                     .set_model(Some(model.as_str()), effort)
+                    .set_model_preference(&model, effort)
                     .apply()
                     .await
                 {
                     Ok(()) => {
+                        self.config
+                            .model_preferences
+                            .entry(model.clone())
+                            .or_default()
+                            .reasoning_effort = effort;
+                        self.chat_widget.remember_model_effort(&model, effort);
                         let reasoning_label = Self::reasoning_label(effort);
                         if let Some(profile) = profile {
                             self.chat_widget.add_info_message(
@@ -779,6 +1033,29 @@ impl App {
                 self.config.plan_detail = detail;
                 self.chat_widget.set_plan_detail(detail);
             }
+            AppEvent::ReloadConfig(keys) => {
+                match reload::load_from_disk(
+                    &self.config.codex_home,
+                    &self.config.cwd,
+                    self.active_profile.clone(),
+                )
+                .await
+                {
+                    Ok(reloaded) => {
+                        self.config.apply_reloaded(&keys, &reloaded);
+                        self.chat_widget.apply_reloaded_config(&keys, &reloaded);
+                        if keys.iter().any(|key| key == "tui.theme") {
+                            let name = self
+                                .config
+                                .tui_theme
+                                .clone()
+                                .unwrap_or_else(|| theme::DEFAULT_THEME.to_string());
+                            self.apply_theme(&name);
+                        }
+                    }
+                    Err(err) => tracing::warn!("failed to reload config.toml: {err}"),
+                }
+            }
             AppEvent::UpdateSubagentTaskLimit(limit) => {
                 let normalized = limit.clamp(SUBAGENT_LIMIT_MIN, SUBAGENT_LIMIT_HARD_CAP);
                 self.config.subagent_max_tasks = normalized;
@@ -795,6 +1072,18 @@ impl App {
                     self.chat_widget.cancel_plan_workflow();
                 }
             },
+            AppEvent::EditQueuedMessage(edit) => {
+                self.chat_widget.apply_queued_message_edit(edit);
+            }
+            AppEvent::ReviewFindingAction { index, action } => {
+                self.chat_widget.apply_review_finding_action(index, action);
+            }
+            AppEvent::CommandPaletteAction(action) => {
+                self.chat_widget.on_command_palette_action(action);
+            }
+            AppEvent::PromptTemplateFilled { prompt, values } => {
+                self.chat_widget.submit_prompt_template(prompt, values);
+            }
             AppEvent::PersistFullAccessWarningAcknowledged => {
                 if let Err(err) = ConfigEditsBuilder::new(&self.config.codex_home)
                     .set_hide_full_access_warning(true)
@@ -963,6 +1252,9 @@ impl App {
                     ));
                 }
             },
+            AppEvent::PendingPatchStale { id, paths } => {
+                self.chat_widget.on_pending_patch_stale(&id, paths);
+            }
         }
         Ok(true)
     }
@@ -987,19 +1279,110 @@ impl App {
         self.config.model_reasoning_effort = effort;
     }
 
+    /// Make `name` the active theme. A palette that fails to load is reported
+    /// and leaves the current theme in place.
+    fn apply_theme(&mut self, name: &str) -> bool {
+        match theme::load(&self.config.codex_home, name) {
+            Ok(loaded) => {
+                theme::set_current(loaded);
+                true
+            }
+            Err(err) => {
+                self.chat_widget
+                    .add_error_message(format!("Failed to load theme `{name}`: {err}"));
+                false
+            }
+        }
+    }
+
+    /// Inserts lines into scrollback, or holds them while the transcript
+    /// overlay is open or the live tail is unfollowed.
+    /// Lines for `cell` in the scrollback, limited to `max_transcript_width`
+    /// and centered on wider terminals.
+    pub(crate) fn history_display_lines(
+        &self,
+        cell: &dyn HistoryCell,
+        screen_width: u16,
+    ) -> Vec<Line<'static>> {
+        let (margin, width) = centered_columns(
+            screen_width,
+            self.chat_widget.config_ref().max_transcript_width,
+        );
+        let lines = cell.display_lines(width);
+        if margin == 0 {
+            return lines;
+        }
+        let indent = " ".repeat(usize::from(margin));
+        prefix_lines(lines, indent.clone().into(), indent.into())
+    }
+
+    pub(crate) fn insert_history_lines(&mut self, tui: &mut tui::Tui, lines: Vec<Line<'static>>) {
+        if let Some(lines) = self.hold_history_lines(lines) {
+            tui.insert_history_lines(lines);
+        }
+    }
+
+    /// Holds `lines` when they cannot be shown yet; returns them otherwise.
+    fn hold_history_lines(&mut self, lines: Vec<Line<'static>>) -> Option<Vec<Line<'static>>> {
+        if self.overlay.is_some() {
+            self.deferred_history_lines.extend(lines);
+            None
+        } else if let Some(held) = self.unfollowed_history_lines.as_mut() {
+            held.extend(lines);
+            self.chat_widget
+                .set_unfollowed_history_lines(Some(held.len()));
+            None
+        } else {
+            Some(lines)
+        }
+    }
+
+    /// Toggles whether scrollback follows the live tail. While unfollowed the
+    /// scrollback stays put and streaming keeps running in the background;
+    /// following again flushes the held lines and jumps back to the tail.
+    fn toggle_follow(&mut self, tui: &mut tui::Tui) {
+        if self.unfollowed_history_lines.is_some() {
+            self.follow_live_tail(tui);
+        } else {
+            self.unfollowed_history_lines = Some(Vec::new());
+            self.chat_widget.set_unfollowed_history_lines(Some(0));
+        }
+        tui.frame_requester().schedule_frame();
+    }
+
+    fn follow_live_tail(&mut self, tui: &mut tui::Tui) {
+        let Some(held) = self.unfollowed_history_lines.take() else {
+            return;
+        };
+        self.chat_widget.set_unfollowed_history_lines(None);
+        if !held.is_empty() {
+            self.insert_history_lines(tui, held);
+        }
+    }
+
     async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
+        let keymap = self.chat_widget.keymap();
         match key_event {
-            KeyEvent {
-                code: KeyCode::Char('t'),
-                modifiers: crossterm::event::KeyModifiers::CONTROL,
-                kind: KeyEventKind::Press,
-                ..
-            } => {
+            _ if keymap.matches(KeyAction::Transcript, key_event) => {
                 // Enter alternate screen and set viewport to full size.
                 let _ = tui.enter_alt_screen();
                 self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
                 tui.frame_requester().schedule_frame();
             }
+            _ if keymap.matches(KeyAction::PauseFollow, key_event) => {
+                self.toggle_follow(tui);
+            }
+            // Ctrl+F opens the transcript with its search prompt focused. With
+            // text in the composer it keeps moving the cursor right instead.
+            _ if keymap.matches(KeyAction::TranscriptSearch, key_event)
+                && self.chat_widget.composer_is_empty() =>
+            {
+                let _ = tui.enter_alt_screen();
+                self.overlay = Some(Overlay::new_transcript_search(
+                    self.transcript_cells.clone(),
+                ));
+                tui.frame_requester().schedule_frame();
+            }
             // Esc primes/advances backtracking only in normal (not working) mode
             // with the composer focused and empty. In any other state, forward
             // Esc so the active UI (e.g. status indicator, modals, popups)
@@ -1012,7 +1395,9 @@ impl App {
                 if self.chat_widget.is_normal_backtrack_mode()
                     && self.chat_widget.composer_is_empty()
                 {
-                    self.handle_backtrack_esc_key(tui);
+                    if !self.chat_widget.dismiss_doctor_banner() {
+                        self.handle_backtrack_esc_key(tui);
+                    }
                 } else {
                     self.chat_widget.handle_key_event(key_event);
                 }
@@ -1120,6 +1505,7 @@ mod tests {
     use codex_core::protocol::SandboxPolicy;
     use codex_core::protocol::SessionConfiguredEvent;
     use codex_protocol::ConversationId;
+    use pretty_assertions::assert_eq;
     use ratatui::prelude::Line;
     use std::path::PathBuf;
     use std::sync::Arc;
@@ -1146,6 +1532,7 @@ mod tests {
             transcript_cells: Vec::new(),
             overlay: None,
             deferred_history_lines: Vec::new(),
+            unfollowed_history_lines: None,
             has_emitted_history_lines: false,
             enhanced_keys_supported: false,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
@@ -1183,6 +1570,7 @@ mod tests {
                 transcript_cells: Vec::new(),
                 overlay: None,
                 deferred_history_lines: Vec::new(),
+                unfollowed_history_lines: None,
                 has_emitted_history_lines: false,
                 enhanced_keys_supported: false,
                 commit_anim_running: Arc::new(AtomicBool::new(false)),
@@ -1362,6 +1750,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn unfollowed_history_lines_are_held_until_following_resumes() {
+        let mut app = make_test_app();
+        let line = || vec![Line::from("streamed")];
+
+        assert_eq!(app.hold_history_lines(line()), Some(line()));
+
+        app.unfollowed_history_lines = Some(Vec::new());
+        assert_eq!(app.hold_history_lines(line()), None);
+        assert_eq!(app.hold_history_lines(line()), None);
+        assert_eq!(
+            app.unfollowed_history_lines.take(),
+            Some(vec![Line::from("streamed"), Line::from("streamed")])
+        );
+
+        assert_eq!(app.hold_history_lines(line()), Some(line()));
+    }
+
     #[test]
     fn session_summary_skip_zero_usage() {
         assert!(session_summary(TokenUsage::default(), None).is_none());
@@ -1,16 +1,23 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Instant;
 
 use codex_common::approval_presets::ApprovalPreset;
 use codex_common::model_presets::ModelPreset;
+use codex_core::doctor::DoctorFinding;
 use codex_core::protocol::ConversationPathResponseEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
 use codex_file_search::FileMatch;
+use codex_protocol::custom_prompts::CustomPrompt;
 
 use crate::bottom_pane::ApprovalRequest;
+use crate::exec_cell::LiveOutput;
 use crate::history_cell::HistoryCell;
 use crate::semantic::SemanticStatus;
+use crate::slash_command::SlashCommand;
 
+use codex_core::config::types::CompactionPolicy;
 use codex_core::config::types::PlanDetailPreference;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
@@ -50,6 +57,16 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Open the full-screen follow view of a running command's output.
+    OpenExecOutput {
+        call_id: String,
+        command: String,
+        output: LiveOutput,
+    },
+
+    /// Problems found by the startup self-check.
+    DoctorFindings(Vec<DoctorFinding>),
+
     /// Update semantic search readiness (sgrep warmup/indexing state).
     SemanticStatusUpdate(SemanticStatus, Option<String>),
 
@@ -65,6 +82,30 @@ pub(crate) enum AppEvent {
     /// Update the current model slug in the running app and widget.
     UpdateModel(String),
 
+    /// Record the maximum transcript width chosen with `/width` so new
+    /// sessions keep it. `None` uses the full terminal width.
+    UpdateMaxTranscriptWidth(Option<u16>),
+
+    /// Switch to the named color theme chosen with `/theme` and save it as
+    /// `tui.theme`.
+    SetTheme(String),
+
+    /// Save the compaction policies chosen in `/settings` as
+    /// `compaction.policies`.
+    SetCompactionPolicies(Vec<CompactionPolicy>),
+
+    /// Open the compaction policies picker from `/settings`.
+    OpenCompactionPopup,
+
+    /// A `/watch` saw matching files change; `message` is its prompt with
+    /// the changed files and their diff.
+    FileWatchTriggered {
+        id: u64,
+        first_change_at: Instant,
+        file_count: usize,
+        message: String,
+    },
+
     /// Persist the selected model and reasoning effort to the appropriate config.
     PersistModelSelection {
         model: String,
@@ -127,10 +168,31 @@ pub(crate) enum AppEvent {
     /// Handle plan review commands triggered from the UI.
     PlanReviewAction(PlanReviewAction),
 
+    /// Apply a change made in the `/queue` editor to the queued user messages.
+    EditQueuedMessage(QueuedMessageEdit),
+
+    /// Triage finding `index` of the last review.
+    ReviewFindingAction {
+        index: usize,
+        action: ReviewFindingAction,
+    },
+
+    /// Run the entry chosen in the Ctrl+P command palette.
+    CommandPaletteAction(PaletteAction),
+
+    /// Submit a custom prompt whose template fields were filled in the form.
+    PromptTemplateFilled {
+        prompt: CustomPrompt,
+        values: HashMap<String, String>,
+    },
+
     /// Update whether rate limits should be shown in the footer.
     UpdateShowRateLimitsInFooter(bool),
     /// Update the preferred plan detail level.
     UpdatePlanDetailPreference(PlanDetailPreference),
+    /// Re-read `config.toml` and apply the named keys after a
+    /// `ConfigChanged` event.
+    ReloadConfig(Vec<String>),
     /// Update the maximum concurrent subagent tasks.
     UpdateSubagentTaskLimit(i64),
 
@@ -178,6 +240,28 @@ pub(crate) enum AppEvent {
     /// Open the approval popup.
     FullScreenApprovalRequest(ApprovalRequest),
 
+    /// Pick a recorded session and resume it in place of the current one.
+    OpenResumePicker,
+
+    /// Switch to the session recorded in this rollout file, such as a branch
+    /// created by `/fork`.
+    ResumeSession(PathBuf),
+
+    /// Quote assistant message `n` of this conversation into the composer.
+    QuoteAgentMessage(usize),
+
+    /// Pin (or unpin) assistant message `at_item` so compaction keeps it.
+    PinAgentMessage {
+        at_item: usize,
+        pinned: bool,
+    },
+
+    /// Files targeted by the pending patch `id` changed on disk.
+    PendingPatchStale {
+        id: String,
+        paths: Vec<PathBuf>,
+    },
+
     /// Open the feedback note entry overlay after the user selects a category.
     OpenFeedbackNote {
         category: FeedbackCategory,
@@ -208,6 +292,60 @@ pub(crate) enum PlanReviewAction {
     Cancel,
 }
 
+/// What choosing an entry in the command palette does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PaletteAction {
+    RunCommand(SlashCommand),
+    /// Start a `/prompts:<name>` invocation in the composer.
+    InsertPrompt(String),
+    /// Mention a workspace file in the composer.
+    InsertFile(String),
+    RestoreCheckpoint(String),
+    /// Run a recent `!` command again.
+    RunShellCommand(String),
+}
+
+/// A change to one queued user message, addressed by its position in the
+/// queue when the change was made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct QueuedMessageEdit {
+    pub index: usize,
+    /// Text of the message at `index`, used to find it again if the queue
+    /// advanced while the editor was open.
+    pub text: String,
+    pub action: QueuedMessageAction,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum QueuedMessageAction {
+    MoveUp,
+    MoveDown,
+    Delete,
+    /// Open the message in a text editor.
+    Edit,
+    /// Replace the message text.
+    Replace(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReviewFindingAction {
+    /// Queue a prompt asking the agent to fix the finding.
+    FixNow,
+    Ignore,
+    /// Undo an earlier ignore.
+    Restore,
+    /// Open the finding's location with the configured file opener.
+    OpenFile,
+}
+
+/// Where a review finding stands in this session's triage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ReviewFindingStatus {
+    Open,
+    FixQueued,
+    Ignored,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum FeedbackCategory {
     BadResult,
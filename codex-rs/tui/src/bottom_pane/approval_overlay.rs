@@ -8,6 +8,10 @@ use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::list_selection_view::ListSelectionView;
 use crate::bottom_pane::list_selection_view::SelectionItem;
 use crate::bottom_pane::list_selection_view::SelectionViewParams;
+use crate::bottom_pane::patch_hunk_review::HunkReviewOutcome;
+use crate::bottom_pane::patch_hunk_review::PatchHunkReview;
+use crate::bottom_pane::patch_staleness;
+use crate::bottom_pane::patch_staleness::PatchTargets;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
@@ -16,12 +20,14 @@ use crate::key_hint::KeyBinding;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
 use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::SandboxCommandAssessment;
 use codex_core::protocol::SandboxRiskLevel;
+use codex_protocol::user_input::UserInput;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
@@ -34,6 +40,7 @@ use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::widgets::Paragraph;
 use ratatui::widgets::Wrap;
+use tokio::task::JoinHandle;
 
 /// Request coming from the agent that needs user approval.
 #[derive(Clone, Debug)]
@@ -43,6 +50,7 @@ pub(crate) enum ApprovalRequest {
         command: Vec<String>,
         reason: Option<String>,
         risk: Option<SandboxCommandAssessment>,
+        network_destinations: Vec<String>,
     },
     ApplyPatch {
         id: String,
@@ -67,6 +75,15 @@ pub(crate) struct ApprovalOverlay {
     options: Vec<ApprovalOption>,
     current_complete: bool,
     done: bool,
+    /// On-disk snapshots of patch targets, keyed by approval id, taken when
+    /// each patch request arrived.
+    patch_targets: HashMap<String, PatchTargets>,
+    /// Poller watching the current patch's targets for concurrent edits.
+    patch_watch: Option<JoinHandle<()>>,
+    /// Targets of the current patch that changed since it was proposed.
+    stale_paths: Vec<PathBuf>,
+    /// Hunk selection for the current patch, while the user is reviewing it.
+    hunk_review: Option<PatchHunkReview>,
 }
 
 impl ApprovalOverlay {
@@ -80,34 +97,80 @@ impl ApprovalOverlay {
             options: Vec::new(),
             current_complete: false,
             done: false,
+            patch_targets: HashMap::new(),
+            patch_watch: None,
+            stale_paths: Vec::new(),
+            hunk_review: None,
         };
+        view.capture_patch_targets(&request);
         view.set_current(request);
         view
     }
 
     pub fn enqueue_request(&mut self, req: ApprovalRequest) {
+        self.capture_patch_targets(&req);
         self.queue.push(req);
     }
 
+    fn capture_patch_targets(&mut self, request: &ApprovalRequest) {
+        if let ApprovalRequest::ApplyPatch {
+            id, cwd, changes, ..
+        } = request
+        {
+            self.patch_targets
+                .insert(id.clone(), PatchTargets::capture(cwd, changes));
+        }
+    }
+
     fn set_current(&mut self, request: ApprovalRequest) {
-        self.current_request = Some(request.clone());
+        self.stop_patch_watch();
+        self.stale_paths.clear();
+        if let ApprovalRequest::ApplyPatch { id, .. } = &request
+            && let Some(targets) = self.patch_targets.remove(id)
+        {
+            self.stale_paths = targets.changed_paths();
+            if self.stale_paths.is_empty() {
+                self.patch_watch =
+                    patch_staleness::spawn_watch(id.clone(), targets, self.app_event_tx.clone());
+            }
+        }
+        self.current_request = Some(request);
+        self.current_complete = false;
+        self.rebuild_current();
+    }
+
+    fn rebuild_current(&mut self) {
+        let Some(request) = self.current_request.clone() else {
+            return;
+        };
+        self.hunk_review = None;
         let ApprovalRequestState { variant, header } = ApprovalRequestState::from(request);
         self.current_variant = Some(variant.clone());
-        self.current_complete = false;
-        let (options, params) = Self::build_options(variant, header);
+        let (options, params) = Self::build_options(variant, header, &self.stale_paths);
         self.options = options;
         self.list = ListSelectionView::new(params, self.app_event_tx.clone());
     }
 
+    fn stop_patch_watch(&mut self) {
+        if let Some(handle) = self.patch_watch.take() {
+            handle.abort();
+        }
+    }
+
     fn build_options(
         variant: ApprovalVariant,
         header: Box<dyn Renderable>,
+        stale_paths: &[PathBuf],
     ) -> (Vec<ApprovalOption>, SelectionViewParams) {
         let (options, title) = match &variant {
             ApprovalVariant::Exec { .. } => (
                 exec_options(),
                 "Would you like to run the following command?".to_string(),
             ),
+            ApprovalVariant::ApplyPatch { .. } if !stale_paths.is_empty() => (
+                stale_patch_options(),
+                "These files changed after the edits were proposed. Apply anyway?".to_string(),
+            ),
             ApprovalVariant::ApplyPatch { .. } => (
                 patch_options(),
                 "Would you like to make the following edits?".to_string(),
@@ -118,11 +181,14 @@ impl ApprovalOverlay {
             ),
         };
 
-        let header = Box::new(ColumnRenderable::with([
-            Line::from(title.bold()).into(),
-            Line::from("").into(),
-            header,
-        ]));
+        let mut header_rows: Vec<Box<dyn Renderable>> = Vec::new();
+        if !stale_paths.is_empty() {
+            header_rows.push(Box::new(render_stale_badge(stale_paths)));
+        }
+        header_rows.push(Line::from(title.bold()).into());
+        header_rows.push(Line::from("").into());
+        header_rows.push(header);
+        let header = Box::new(ColumnRenderable::with(header_rows));
 
         let items = options
             .iter()
@@ -162,10 +228,21 @@ impl ApprovalOverlay {
         if let Some(variant) = self.current_variant.as_ref() {
             match (&variant, &option.decision) {
                 (ApprovalVariant::Exec { id, command }, ApprovalDecision::Review(decision)) => {
-                    self.handle_exec_decision(id, command, *decision);
+                    self.handle_exec_decision(id, command, decision.clone());
                 }
                 (ApprovalVariant::ApplyPatch { id, .. }, ApprovalDecision::Review(decision)) => {
-                    self.handle_patch_decision(id, *decision);
+                    self.handle_patch_decision(id, decision.clone());
+                }
+                (ApprovalVariant::ApplyPatch { id, .. }, ApprovalDecision::RegeneratePatch) => {
+                    self.handle_patch_regeneration(id);
+                }
+                (ApprovalVariant::ApplyPatch { .. }, ApprovalDecision::ReviewHunks) => {
+                    if let Some(ApprovalRequest::ApplyPatch { cwd, changes, .. }) =
+                        self.current_request.as_ref()
+                    {
+                        self.hunk_review = Some(PatchHunkReview::new(cwd.clone(), changes));
+                    }
+                    return;
                 }
                 (
                     ApprovalVariant::McpElicitation {
@@ -185,7 +262,7 @@ impl ApprovalOverlay {
     }
 
     fn handle_exec_decision(&self, id: &str, command: &[String], decision: ReviewDecision) {
-        let cell = history_cell::new_approval_decision_cell(command.to_vec(), decision);
+        let cell = history_cell::new_approval_decision_cell(command.to_vec(), decision.clone());
         self.app_event_tx.send(AppEvent::InsertHistoryCell(cell));
         self.app_event_tx.send(AppEvent::CodexOp(Op::ExecApproval {
             id: id.to_string(),
@@ -200,6 +277,25 @@ impl ApprovalOverlay {
         }));
     }
 
+    /// Decline the stale patch and ask the agent to rebuild it against the
+    /// current file contents.
+    fn handle_patch_regeneration(&self, id: &str) {
+        self.handle_patch_decision(id, ReviewDecision::Denied);
+        let paths = self
+            .stale_paths
+            .iter()
+            .map(|path| format!("- {}", path.display()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let text = format!(
+            "These files changed on disk while your patch was waiting for approval:\n{paths}\n\
+             Re-read them and regenerate the patch against their current contents."
+        );
+        self.app_event_tx.send(AppEvent::CodexOp(Op::UserInput {
+            items: vec![UserInput::Text { text }],
+        }));
+    }
+
     fn handle_elicitation_decision(
         &self,
         server_name: &str,
@@ -218,10 +314,29 @@ impl ApprovalOverlay {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
         } else {
+            self.stop_patch_watch();
             self.done = true;
         }
     }
 
+    fn handle_hunk_review_key(&mut self, key_event: KeyEvent) {
+        let Some(review) = self.hunk_review.as_mut() else {
+            return;
+        };
+        match review.handle_key_event(key_event) {
+            Some(HunkReviewOutcome::Decide(decision)) => {
+                self.hunk_review = None;
+                if let Some(ApprovalVariant::ApplyPatch { id }) = self.current_variant.as_ref() {
+                    self.handle_patch_decision(id, decision);
+                }
+                self.current_complete = true;
+                self.advance_queue();
+            }
+            Some(HunkReviewOutcome::Back) => self.hunk_review = None,
+            None => {}
+        }
+    }
+
     fn try_handle_shortcut(&mut self, key_event: &KeyEvent) -> bool {
         match key_event {
             KeyEvent {
@@ -256,6 +371,10 @@ impl ApprovalOverlay {
 
 impl BottomPaneView for ApprovalOverlay {
     fn handle_key_event(&mut self, key_event: KeyEvent) {
+        if self.hunk_review.is_some() {
+            self.handle_hunk_review_key(key_event);
+            return;
+        }
         if self.try_handle_shortcut(&key_event) {
             return;
         }
@@ -292,6 +411,8 @@ impl BottomPaneView for ApprovalOverlay {
             }
         }
         self.queue.clear();
+        self.hunk_review = None;
+        self.stop_patch_watch();
         self.done = true;
         CancellationEvent::Handled
     }
@@ -307,19 +428,47 @@ impl BottomPaneView for ApprovalOverlay {
         self.enqueue_request(request);
         None
     }
+
+    fn mark_patch_stale(&mut self, id: &str, paths: &[PathBuf]) -> bool {
+        let is_current = matches!(
+            self.current_variant.as_ref(),
+            Some(ApprovalVariant::ApplyPatch { id: current }) if current == id
+        );
+        if !is_current || self.current_complete {
+            return false;
+        }
+        self.stale_paths = paths.to_vec();
+        self.rebuild_current();
+        true
+    }
+}
+
+impl Drop for ApprovalOverlay {
+    fn drop(&mut self) {
+        self.stop_patch_watch();
+    }
 }
 
 impl Renderable for ApprovalOverlay {
     fn desired_height(&self, width: u16) -> u16 {
-        self.list.desired_height(width)
+        match &self.hunk_review {
+            Some(review) => review.desired_height(width),
+            None => self.list.desired_height(width),
+        }
     }
 
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        self.list.render(area, buf);
+        match &self.hunk_review {
+            Some(review) => review.render(area, buf),
+            None => self.list.render(area, buf),
+        }
     }
 
     fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
-        self.list.cursor_pos(area)
+        match &self.hunk_review {
+            Some(_) => None,
+            None => self.list.cursor_pos(area),
+        }
     }
 }
 
@@ -336,13 +485,20 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                 command,
                 reason,
                 risk,
+                network_destinations,
             } => {
                 let reason = reason.filter(|item| !item.is_empty());
-                let has_reason = reason.is_some();
+                let has_reason = reason.is_some() || !network_destinations.is_empty();
                 let mut header: Vec<Line<'static>> = Vec::new();
                 if let Some(reason) = reason {
                     header.push(Line::from(vec!["Reason: ".into(), reason.italic()]));
                 }
+                if !network_destinations.is_empty() {
+                    header.push(Line::from(vec![
+                        "Blocked network: ".into(),
+                        network_destinations.join(", ").italic(),
+                    ]));
+                }
                 if let Some(risk) = risk.as_ref() {
                     header.extend(render_risk_lines(risk));
                 } else if has_reason {
@@ -404,11 +560,25 @@ impl From<ApprovalRequest> for ApprovalRequestState {
     }
 }
 
+fn render_stale_badge(paths: &[PathBuf]) -> Paragraph<'static> {
+    let mut lines = vec![Line::from(vec![
+        " STALE ".error().bold().reversed(),
+        " changed on disk since this patch was proposed:".error(),
+    ])];
+    lines.extend(
+        paths
+            .iter()
+            .map(|path| Line::from(format!("  {}", path.display())).secondary()),
+    );
+    lines.push(Line::from(""));
+    Paragraph::new(lines).wrap(Wrap { trim: false })
+}
+
 fn render_risk_lines(risk: &SandboxCommandAssessment) -> Vec<Line<'static>> {
     let level_span = match risk.risk_level {
-        SandboxRiskLevel::Low => "LOW".green().bold(),
-        SandboxRiskLevel::Medium => "MEDIUM".cyan().bold(),
-        SandboxRiskLevel::High => "HIGH".red().bold(),
+        SandboxRiskLevel::Low => "LOW".success().bold(),
+        SandboxRiskLevel::Medium => "MEDIUM".accent().bold(),
+        SandboxRiskLevel::High => "HIGH".error().bold(),
     };
 
     let mut lines = Vec::new();
@@ -445,6 +615,10 @@ enum ApprovalVariant {
 enum ApprovalDecision {
     Review(ReviewDecision),
     McpElicitation(ElicitationAction),
+    /// Decline a stale patch and ask the agent to produce a fresh one.
+    RegeneratePatch,
+    /// Pick which files and hunks of the patch to apply.
+    ReviewHunks,
 }
 
 #[derive(Clone)]
@@ -494,6 +668,35 @@ fn patch_options() -> Vec<ApprovalOption> {
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
         },
+        ApprovalOption {
+            label: "Choose which hunks to apply".to_string(),
+            decision: ApprovalDecision::ReviewHunks,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('h'))],
+        },
+        ApprovalOption {
+            label: "No, and tell Codex Kaioken what to do differently".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Abort),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
+        },
+    ]
+}
+
+fn stale_patch_options() -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Ask Codex Kaioken to regenerate the patch".to_string(),
+            decision: ApprovalDecision::RegeneratePatch,
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
+        },
+        ApprovalOption {
+            label: "Apply anyway".to_string(),
+            decision: ApprovalDecision::Review(ReviewDecision::Approved),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
+        },
         ApprovalOption {
             label: "No, and tell Codex Kaioken what to do differently".to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Abort),
@@ -539,6 +742,7 @@ mod tests {
             command: vec!["echo".to_string(), "hi".to_string()],
             reason: Some("reason".to_string()),
             risk: None,
+            network_destinations: Vec::new(),
         }
     }
 
@@ -581,6 +785,7 @@ mod tests {
             command,
             reason: None,
             risk: None,
+            network_destinations: Vec::new(),
         };
 
         let view = ApprovalOverlay::new(exec_request, tx);
@@ -602,6 +807,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn header_lists_blocked_network_destinations() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let exec_request = ApprovalRequest::Exec {
+            id: "test".into(),
+            command: vec!["curl".into(), "https://example.com".into()],
+            reason: None,
+            risk: None,
+            network_destinations: vec!["example.com:443".into()],
+        };
+
+        let view = ApprovalOverlay::new(exec_request, tx);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 80, view.desired_height(80)));
+        view.render(Rect::new(0, 0, 80, view.desired_height(80)), &mut buf);
+
+        let rendered: Vec<String> = (0..buf.area.height)
+            .map(|row| {
+                (0..buf.area.width)
+                    .map(|col| buf[(col, row)].symbol().to_string())
+                    .collect()
+            })
+            .collect();
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains("Blocked network: example.com:443")),
+            "expected header to list blocked destinations, got {rendered:?}"
+        );
+    }
+
     #[test]
     fn exec_history_cell_wraps_with_two_space_indent() {
         let command = vec![
@@ -651,4 +887,107 @@ mod tests {
         }
         assert_eq!(decision, Some(ReviewDecision::ApprovedForSession));
     }
+
+    #[test]
+    fn stale_patch_offers_regeneration() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let request = ApprovalRequest::ApplyPatch {
+            id: "patch-1".to_string(),
+            reason: None,
+            cwd: PathBuf::from("/tmp"),
+            changes: HashMap::from([(
+                PathBuf::from("foo.rs"),
+                FileChange::Add {
+                    content: "fn main() {}\n".to_string(),
+                },
+            )]),
+        };
+        let mut view = ApprovalOverlay::new(request, tx);
+
+        assert!(!view.mark_patch_stale("other", &[PathBuf::from("/tmp/foo.rs")]));
+        assert!(view.mark_patch_stale("patch-1", &[PathBuf::from("/tmp/foo.rs")]));
+
+        let width = 80;
+        let mut buf = Buffer::empty(Rect::new(0, 0, width, view.desired_height(width)));
+        view.render(Rect::new(0, 0, width, view.desired_height(width)), &mut buf);
+        let rendered: String = buf
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect();
+        assert!(
+            rendered.contains("STALE"),
+            "missing stale badge: {rendered}"
+        );
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE));
+        let mut ops = Vec::new();
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(op) = ev {
+                ops.push(op);
+            }
+        }
+        assert!(matches!(
+            ops.as_slice(),
+            [
+                Op::PatchApproval {
+                    decision: ReviewDecision::Denied,
+                    ..
+                },
+                Op::UserInput { .. }
+            ]
+        ));
+        assert!(view.is_complete());
+    }
+
+    #[test]
+    fn deselected_hunk_is_left_out_of_the_approval() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let path = PathBuf::from("/tmp/lib.rs");
+        let request = ApprovalRequest::ApplyPatch {
+            id: "patch-1".to_string(),
+            reason: None,
+            cwd: PathBuf::from("/tmp"),
+            changes: HashMap::from([(
+                path.clone(),
+                FileChange::Update {
+                    unified_diff: "@@ -1,2 +1,2 @@\n-a\n+A\n b\n@@ -9,2 +9,2 @@\n-i\n+I\n j\n"
+                        .to_string(),
+                    move_path: None,
+                },
+            )]),
+        };
+        let mut view = ApprovalOverlay::new(request, tx);
+
+        for code in [
+            KeyCode::Char('h'),
+            KeyCode::Down,
+            KeyCode::Char(' '),
+            KeyCode::Enter,
+        ] {
+            view.handle_key_event(KeyEvent::new(code, KeyModifiers::NONE));
+        }
+
+        let mut decision = None;
+        while let Ok(ev) = rx.try_recv() {
+            if let AppEvent::CodexOp(Op::PatchApproval { decision: d, .. }) = ev {
+                decision = Some(d);
+            }
+        }
+        assert_eq!(
+            decision,
+            Some(ReviewDecision::ApprovedPartially {
+                changes: HashMap::from([(
+                    path,
+                    FileChange::Update {
+                        unified_diff: "@@ -9,2 +9,2 @@\n-i\n+I\n j\n".to_string(),
+                        move_path: None,
+                    },
+                )]),
+            })
+        );
+        assert!(view.is_complete());
+    }
 }
@@ -1,5 +1,8 @@
+use std::path::PathBuf;
+
 use crate::bottom_pane::ApprovalRequest;
 use crate::render::renderable::Renderable;
+use codex_file_search::FileMatch;
 use crossterm::event::KeyEvent;
 
 use super::CancellationEvent;
@@ -34,4 +37,16 @@ pub(crate) trait BottomPaneView: Renderable {
     ) -> Option<ApprovalRequest> {
         Some(request)
     }
+
+    /// Mark the pending patch approval `id` as stale because `paths` changed
+    /// on disk. Return true if this view owns that approval.
+    fn mark_patch_stale(&mut self, _id: &str, _paths: &[PathBuf]) -> bool {
+        false
+    }
+
+    /// Receive the results of a file search. Return true if this view started
+    /// the search, so the composer's `@` popup does not see them.
+    fn on_file_search_result(&mut self, _query: &str, _matches: &[FileMatch]) -> bool {
+        false
+    }
 }
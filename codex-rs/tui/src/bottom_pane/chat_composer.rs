@@ -116,6 +116,7 @@ use crate::bottom_pane::prompt_args::parse_slash_name;
 use crate::bottom_pane::prompt_args::prompt_argument_names;
 use crate::bottom_pane::prompt_args::prompt_command_with_arg_placeholders;
 use crate::bottom_pane::prompt_args::prompt_has_numeric_placeholders;
+use crate::bottom_pane::prompt_args::prompt_has_template_fields;
 use crate::render::Insets;
 use crate::render::RectExt;
 use crate::render::renderable::Renderable;
@@ -137,6 +138,7 @@ use crate::bottom_pane::textarea::TextAreaState;
 use crate::clipboard_paste::normalize_pasted_path;
 use crate::clipboard_paste::pasted_image_format;
 use crate::history_cell;
+use crate::theme::ThemeStylize;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
@@ -172,6 +174,8 @@ pub enum InputResult {
     },
     Command(SlashCommand),
     CommandWithArgs(SlashCommand, String),
+    /// Open the form for a custom prompt with `{{name:description}}` fields.
+    PromptTemplate(CustomPrompt),
     None,
 }
 
@@ -195,6 +199,7 @@ enum PromptSelectionAction {
         text: String,
         text_elements: Vec<TextElement>,
     },
+    FillTemplate,
 }
 
 pub(crate) struct ChatComposer {
@@ -379,7 +384,8 @@ impl ChatComposer {
         entry: Option<String>,
     ) -> bool {
         let Some(text) = self.history.on_entry_response(log_id, offset, entry) else {
-            return false;
+            // Late-arriving entries may change the reverse-search preview.
+            return self.history.is_searching();
         };
         // Composer history (↑/↓) stores plain text only; no UI element ranges/attachments to restore here.
         self.set_text_content(text, Vec::new(), Vec::new());
@@ -784,6 +790,10 @@ impl ChatComposer {
             return (InputResult::None, false);
         }
 
+        if self.history.is_searching() {
+            return self.handle_key_event_with_history_search(key_event);
+        }
+
         let result = match &mut self.active_popup {
             ActivePopup::Command(_) => self.handle_key_event_with_slash_popup(key_event),
             ActivePopup::File(_) => self.handle_key_event_with_file_popup(key_event),
@@ -797,6 +807,99 @@ impl ChatComposer {
         result
     }
 
+    /// Handle key events while a reverse history search (Ctrl+R) is active.
+    ///
+    /// Printable characters refine the query, Ctrl+R cycles to the next match,
+    /// Enter/Tab insert the previewed entry, and Esc/Ctrl+G cancel. Any other
+    /// key accepts the current match and is then processed normally, mirroring
+    /// readline.
+    fn handle_key_event_with_history_search(&mut self, key_event: KeyEvent) -> (InputResult, bool) {
+        if key_event.kind == KeyEventKind::Release {
+            return (InputResult::None, false);
+        }
+        let query = self.history.search_query().unwrap_or_default().to_string();
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                self.history.search_next_match();
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('g') | KeyCode::Char('c'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => {
+                self.history.cancel_search();
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            } => {
+                let mut query = query;
+                query.pop();
+                self.history.set_search_query(query);
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Char(ch),
+                modifiers,
+                ..
+            } if !modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) => {
+                let mut query = query;
+                query.push(ch);
+                self.history.set_search_query(query);
+                (InputResult::None, true)
+            }
+            KeyEvent {
+                code: KeyCode::Enter | KeyCode::Tab,
+                ..
+            } => {
+                self.accept_history_search();
+                (InputResult::None, true)
+            }
+            other => {
+                self.accept_history_search();
+                let (result, _) = self.handle_key_event(other);
+                (result, true)
+            }
+        }
+    }
+
+    fn accept_history_search(&mut self) {
+        if let Some(text) = self.history.accept_search() {
+            // Composer history stores plain text only; no element ranges/attachments to restore.
+            self.set_text_content(text, Vec::new(), Vec::new());
+            let end = self.textarea.text().len();
+            self.textarea.set_cursor(end);
+        }
+    }
+
+    /// Footer line previewing the current reverse-search state.
+    fn history_search_line(&self) -> Option<Line<'static>> {
+        let query = self.history.search_query()?;
+        let mut spans = vec![
+            "reverse-i-search ".secondary(),
+            format!("`{query}`").accent(),
+            ": ".secondary(),
+        ];
+        match self.history.search_preview() {
+            Some(preview) => {
+                let first_line = preview.lines().next().unwrap_or_default().to_string();
+                spans.push(first_line.into());
+            }
+            None if query.is_empty() => spans.push("type to search history".secondary()),
+            None => spans.push("no match".secondary()),
+        }
+        Some(spans.into())
+    }
+
     /// Return true if either the slash-command popup or the file-search popup is active.
     pub(crate) fn popup_active(&self) -> bool {
         !matches!(self.active_popup, ActivePopup::None)
@@ -892,7 +995,8 @@ impl ChatComposer {
                                         self.textarea.set_text_clearing_elements(&text);
                                         cursor_target = Some(target);
                                     }
-                                    PromptSelectionAction::Submit { .. } => {}
+                                    PromptSelectionAction::Submit { .. }
+                                    | PromptSelectionAction::FillTemplate => {}
                                 }
                             }
                         }
@@ -979,6 +1083,11 @@ impl ChatComposer {
                                         self.textarea.set_cursor(target);
                                         return (InputResult::None, true);
                                     }
+                                    PromptSelectionAction::FillTemplate => {
+                                        let prompt = prompt.clone();
+                                        self.textarea.set_text_clearing_elements("");
+                                        return (InputResult::PromptTemplate(prompt), true);
+                                    }
                                 }
                             }
                             return (InputResult::None, true);
@@ -1823,7 +1932,15 @@ impl ChatComposer {
                 && let Some((_n, cmd)) =
                     Self::built_in_slash_commands_for_input(self.collaboration_modes_enabled)
                         .find(|(command_name, _)| *command_name == name)
-                && cmd == SlashCommand::Review
+                && matches!(
+                    cmd,
+                    SlashCommand::Review
+                        | SlashCommand::Width
+                        | SlashCommand::Theme
+                        | SlashCommand::Watch
+                        | SlashCommand::Pin
+                        | SlashCommand::Unpin
+                )
             {
                 self.textarea.set_text_clearing_elements("");
                 return Some(InputResult::CommandWithArgs(cmd, rest.to_string()));
@@ -1887,6 +2004,15 @@ impl ChatComposer {
                 }
                 self.handle_input_basic(key_event)
             }
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.history.start_search(&self.app_event_tx);
+                (InputResult::None, true)
+            }
             KeyEvent {
                 code: KeyCode::Tab,
                 modifiers: KeyModifiers::NONE,
@@ -2199,7 +2325,7 @@ impl ChatComposer {
     }
 
     fn custom_footer_height(&self) -> Option<u16> {
-        if self.footer_flash_visible() {
+        if self.history.is_searching() || self.footer_flash_visible() {
             return Some(1);
         }
         self.footer_hint_override
@@ -2363,6 +2489,10 @@ impl ChatComposer {
             .filter(move |(_, cmd)| collaboration_modes_enabled || *cmd != SlashCommand::Collab)
     }
 
+    pub(crate) fn custom_prompts(&self) -> &[CustomPrompt] {
+        &self.custom_prompts
+    }
+
     pub(crate) fn set_custom_prompts(&mut self, prompts: Vec<CustomPrompt>) {
         self.custom_prompts = prompts.clone();
         if let ActivePopup::Command(popup) = &mut self.active_popup {
@@ -2529,7 +2659,10 @@ impl Renderable for ChatComposer {
                     popup_rect
                 };
                 let mut left_content_width = None;
-                if self.footer_flash_visible() {
+                if let Some(line) = self.history_search_line() {
+                    left_content_width = Some(line.width() as u16);
+                    line.render(inset_footer_hint_area(hint_rect), buf);
+                } else if self.footer_flash_visible() {
                     if let Some(flash) = self.footer_flash.as_ref() {
                         flash.line.render(inset_footer_hint_area(hint_rect), buf);
                         left_content_width = Some(flash.line.width() as u16);
@@ -2556,7 +2689,7 @@ impl Renderable for ChatComposer {
             let prompt = if self.input_enabled {
                 "›".bold()
             } else {
-                "›".dim()
+                "›".secondary()
             };
             buf.set_span(
                 textarea_rect.x - LIVE_PREFIX_COLS,
@@ -2577,7 +2710,7 @@ impl Renderable for ChatComposer {
                     .unwrap_or("Input disabled.")
                     .to_string()
             };
-            let placeholder = Span::from(text).dim();
+            let placeholder = Span::from(text).secondary();
             Line::from(vec![placeholder]).render_ref(textarea_rect.inner(Margin::new(0, 0)), buf);
         }
     }
@@ -2591,8 +2724,14 @@ fn prompt_selection_action(
 ) -> PromptSelectionAction {
     let named_args = prompt_argument_names(&prompt.content);
     let has_numeric = prompt_has_numeric_placeholders(&prompt.content);
+    let has_template_fields = prompt_has_template_fields(prompt);
 
     match mode {
+        PromptSelectionMode::Completion if has_template_fields => {
+            let text = format!("/{PROMPTS_CMD_PREFIX}:{}", prompt.name);
+            PromptSelectionAction::Insert { text, cursor: None }
+        }
+        PromptSelectionMode::Submit if has_template_fields => PromptSelectionAction::FillTemplate,
         PromptSelectionMode::Completion => {
             if !named_args.is_empty() {
                 let (text, cursor) =
@@ -2928,6 +3067,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ctrl_r_searches_history_and_inserts_match() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.history.record_local_submission("run the tests");
+        composer.history.record_local_submission("fix the lint");
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        for ch in "tst".chars() {
+            let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char(ch), KeyModifiers::NONE));
+        }
+        assert!(
+            composer.is_empty(),
+            "search keystrokes must not edit the draft"
+        );
+        assert_eq!(
+            composer.history_search_line().map(|line| line.to_string()),
+            Some("reverse-i-search `tst`: run the tests".to_string())
+        );
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert_eq!(composer.current_text(), "run the tests");
+        assert_eq!(composer.textarea.cursor(), "run the tests".len());
+        assert!(composer.history_search_line().is_none());
+
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char('r'), KeyModifiers::CONTROL));
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Char('l'), KeyModifiers::NONE));
+        let _ = composer.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+        assert_eq!(composer.current_text(), "run the tests");
+    }
+
     /// Behavior: `?` toggles the shortcut overlay only when the composer is otherwise empty. After
     /// any typing has occurred, `?` should be inserted as a literal character.
     #[test]
@@ -3904,6 +4085,9 @@ mod tests {
             InputResult::Queued { .. } => {
                 panic!("expected command dispatch, but composer queued literal text")
             }
+            InputResult::PromptTemplate(_) => {
+                panic!("expected command dispatch, but composer opened a prompt template")
+            }
             InputResult::None => panic!("expected Command result for '/init'"),
         }
         assert!(composer.textarea.is_empty(), "composer should be cleared");
@@ -4005,6 +4189,9 @@ mod tests {
             InputResult::Queued { .. } => {
                 panic!("expected command dispatch after Tab completion, got literal queue")
             }
+            InputResult::PromptTemplate(_) => {
+                panic!("expected command dispatch, but composer opened a prompt template")
+            }
             InputResult::None => panic!("expected Command result for '/diff'"),
         }
         assert!(composer.textarea.is_empty());
@@ -4044,6 +4231,9 @@ mod tests {
             InputResult::Queued { .. } => {
                 panic!("expected command dispatch, but composer queued literal text")
             }
+            InputResult::PromptTemplate(_) => {
+                panic!("expected command dispatch, but composer opened a prompt template")
+            }
             InputResult::None => panic!("expected Command result for '/mention'"),
         }
         assert!(composer.textarea.is_empty(), "composer should be cleared");
@@ -4931,6 +5121,39 @@ mod tests {
         assert!(composer.textarea.is_empty());
     }
 
+    #[test]
+    fn selecting_template_prompt_opens_the_form() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let sender = AppEventSender::new(tx);
+        let mut composer = ChatComposer::new(
+            true,
+            sender,
+            false,
+            "Ask Codex to do anything".to_string(),
+            false,
+        );
+        composer.set_custom_prompts(vec![CustomPrompt {
+            name: "fix".to_string(),
+            path: "/tmp/fix.md".to_string().into(),
+            content: "Fix {{issue:Issue number}}".to_string(),
+            description: None,
+            argument_hint: None,
+        }]);
+
+        type_chars_humanlike(
+            &mut composer,
+            &['/', 'p', 'r', 'o', 'm', 'p', 't', 's', ':', 'f'],
+        );
+        let (result, _needs_redraw) =
+            composer.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(matches!(
+            result,
+            InputResult::PromptTemplate(prompt) if prompt.name == "fix"
+        ));
+        assert!(composer.textarea.is_empty());
+    }
+
     #[test]
     fn custom_prompt_submission_expands_arguments() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
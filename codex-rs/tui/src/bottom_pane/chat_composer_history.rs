@@ -2,8 +2,13 @@ use std::collections::HashMap;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use codex_common::fuzzy_match::fuzzy_match;
 use codex_core::protocol::Op;
 
+/// Upper bound on how many persistent entries a reverse search fetches up
+/// front. Older entries stay reachable through Up/Down navigation.
+const HISTORY_SEARCH_FETCH_LIMIT: usize = 200;
+
 /// State machine that manages shell-style history navigation (Up/Down) inside
 /// the chat composer. This struct is intentionally decoupled from the
 /// rendering widget so the logic remains isolated and easier to test.
@@ -28,6 +33,18 @@ pub(crate) struct ChatComposerHistory {
     /// history navigation. Used to decide if further Up/Down presses should be
     /// treated as navigation versus normal cursor movement.
     last_history_text: Option<String>,
+
+    /// Active reverse-incremental (Ctrl+R) search, if any.
+    search: Option<HistorySearch>,
+}
+
+/// State for a readline-style reverse history search.
+struct HistorySearch {
+    query: String,
+    /// Global history offsets matching `query`, best match first.
+    matches: Vec<usize>,
+    /// Position within `matches` of the entry currently previewed.
+    selected: usize,
 }
 
 impl ChatComposerHistory {
@@ -39,6 +56,7 @@ impl ChatComposerHistory {
             fetched_history: HashMap::new(),
             history_cursor: None,
             last_history_text: None,
+            search: None,
         }
     }
 
@@ -50,6 +68,7 @@ impl ChatComposerHistory {
         self.local_history.clear();
         self.history_cursor = None;
         self.last_history_text = None;
+        self.search = None;
     }
 
     /// Record a message submitted by the user in the current session so it can
@@ -154,6 +173,10 @@ impl ChatComposerHistory {
         }
         let text = entry?;
         self.fetched_history.insert(offset, text.clone());
+        if self.search.is_some() {
+            self.refresh_search_matches();
+            return None;
+        }
 
         if self.history_cursor == Some(offset as isize) {
             self.last_history_text = Some(text.clone());
@@ -162,10 +185,142 @@ impl ChatComposerHistory {
         None
     }
 
+    /// Whether a reverse history search (Ctrl+R) is in progress.
+    pub fn is_searching(&self) -> bool {
+        self.search.is_some()
+    }
+
+    /// Begin a reverse history search. Persistent entries that have not been
+    /// fetched yet are requested so they can participate in matching as their
+    /// responses arrive.
+    pub fn start_search(&mut self, app_event_tx: &AppEventSender) {
+        self.history_cursor = None;
+        self.last_history_text = None;
+        self.search = Some(HistorySearch {
+            query: String::new(),
+            matches: Vec::new(),
+            selected: 0,
+        });
+
+        if let Some(log_id) = self.history_log_id {
+            let oldest = self
+                .history_entry_count
+                .saturating_sub(HISTORY_SEARCH_FETCH_LIMIT);
+            for offset in (oldest..self.history_entry_count).rev() {
+                if !self.fetched_history.contains_key(&offset) {
+                    let op = Op::GetHistoryEntryRequest { offset, log_id };
+                    app_event_tx.send(AppEvent::CodexOp(op));
+                }
+            }
+        }
+    }
+
+    /// Current search query, when searching.
+    pub fn search_query(&self) -> Option<&str> {
+        self.search.as_ref().map(|search| search.query.as_str())
+    }
+
+    /// Text of the entry currently selected by the search, if any.
+    pub fn search_preview(&self) -> Option<&str> {
+        let search = self.search.as_ref()?;
+        let offset = *search.matches.get(search.selected)?;
+        self.entry_text(offset)
+    }
+
+    /// Replace the search query and jump back to the best match.
+    pub fn set_search_query(&mut self, query: String) {
+        if let Some(search) = self.search.as_mut() {
+            search.query = query;
+            search.selected = 0;
+        }
+        self.refresh_search_matches();
+    }
+
+    /// Advance to the next (lower ranked) match, as a repeated Ctrl+R does.
+    /// Returns false when there is nothing further to cycle to.
+    pub fn search_next_match(&mut self) -> bool {
+        match self.search.as_mut() {
+            Some(search) if search.selected + 1 < search.matches.len() => {
+                search.selected += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Finish the search, returning the selected match (if any) so the caller
+    /// can place it in the composer.
+    pub fn accept_search(&mut self) -> Option<String> {
+        let preview = self.search_preview().map(str::to_string);
+        self.search = None;
+        preview
+    }
+
+    /// Abort the search, leaving the composer draft untouched.
+    pub fn cancel_search(&mut self) {
+        self.search = None;
+    }
+
     // ---------------------------------------------------------------------
     // Internal helpers
     // ---------------------------------------------------------------------
 
+    fn entry_text(&self, global_idx: usize) -> Option<&str> {
+        if global_idx >= self.history_entry_count {
+            self.local_history
+                .get(global_idx - self.history_entry_count)
+                .map(String::as_str)
+        } else {
+            self.fetched_history.get(&global_idx).map(String::as_str)
+        }
+    }
+
+    /// Recompute search matches, keeping the previewed entry selected when it
+    /// still matches so late-arriving history does not yank the selection.
+    fn refresh_search_matches(&mut self) {
+        let Some(search) = self.search.as_ref() else {
+            return;
+        };
+        let previous = search.matches.get(search.selected).copied();
+        let query = search.query.clone();
+
+        let total_entries = self.history_entry_count + self.local_history.len();
+        let mut scored: Vec<(i32, usize)> = Vec::new();
+        if !query.is_empty() {
+            for offset in (0..total_entries).rev() {
+                let Some(text) = self.entry_text(offset) else {
+                    continue;
+                };
+                if let Some((_, score)) = fuzzy_match(text, &query) {
+                    scored.push((score, offset));
+                }
+            }
+        }
+        // Best score first; on ties prefer the most recent entry.
+        scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| b.1.cmp(&a.1)));
+        let mut matches: Vec<usize> = Vec::with_capacity(scored.len());
+        for (_, offset) in scored {
+            let Some(text) = self.entry_text(offset) else {
+                continue;
+            };
+            // Collapse repeated submissions of the same text into one match.
+            if matches
+                .iter()
+                .any(|existing| self.entry_text(*existing) == Some(text))
+            {
+                continue;
+            }
+            matches.push(offset);
+        }
+
+        if let Some(search) = self.search.as_mut() {
+            search.selected = previous
+                .and_then(|offset| matches.iter().position(|m| *m == offset))
+                .unwrap_or(0);
+            search.matches = matches;
+        }
+    }
+
     fn populate_history_at_index(
         &mut self,
         global_idx: usize,
@@ -278,6 +433,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reverse_search_prefers_best_match_and_cycles() {
+        let (tx, _rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+
+        let mut history = ChatComposerHistory::new();
+        history.record_local_submission("cargo test -p codex-tui");
+        history.record_local_submission("git status");
+        history.record_local_submission("cargo build");
+
+        history.start_search(&tx);
+        assert!(history.is_searching());
+        assert_eq!(None, history.search_preview());
+
+        history.set_search_query("cargo".into());
+        assert_eq!(Some("cargo build"), history.search_preview());
+
+        assert!(history.search_next_match());
+        assert_eq!(Some("cargo test -p codex-tui"), history.search_preview());
+        assert!(!history.search_next_match());
+
+        history.set_search_query("zzz".into());
+        assert_eq!(None, history.search_preview());
+        assert_eq!(None, history.accept_search());
+        assert!(!history.is_searching());
+
+        history.start_search(&tx);
+        history.set_search_query("status".into());
+        assert_eq!(Some("git status".into()), history.accept_search());
+
+        history.start_search(&tx);
+        history.set_search_query("status".into());
+        history.cancel_search();
+        assert!(!history.is_searching());
+    }
+
+    #[test]
+    fn reverse_search_fetches_persistent_entries() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+
+        let mut history = ChatComposerHistory::new();
+        history.set_metadata(1, 2);
+        history.fetched_history.insert(1, "cached entry".into());
+
+        history.start_search(&tx);
+        let event = rx.try_recv().expect("expected history fetch");
+        let AppEvent::CodexOp(request) = event else {
+            panic!("unexpected event variant");
+        };
+        assert_eq!(
+            Op::GetHistoryEntryRequest {
+                log_id: 1,
+                offset: 0
+            },
+            request
+        );
+        assert!(rx.try_recv().is_err(), "cached entries are not refetched");
+
+        history.set_search_query("older".into());
+        assert_eq!(None, history.search_preview());
+        assert_eq!(
+            None,
+            history.on_entry_response(1, 0, Some("older entry".into()))
+        );
+        assert_eq!(Some("older entry"), history.search_preview());
+    }
+
     #[test]
     fn reset_navigation_resets_cursor() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
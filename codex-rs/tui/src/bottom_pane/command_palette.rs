@@ -0,0 +1,502 @@
+use codex_common::fuzzy_match::fuzzy_match;
+use codex_file_search::FileMatch;
+use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Constraint;
+use ratatui::layout::Layout;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event::PaletteAction;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::Insets;
+use crate::render::RectExt as _;
+use crate::render::renderable::Renderable;
+use crate::slash_command::SlashCommand;
+use crate::slash_command::built_in_slash_commands;
+use crate::style::user_message_style;
+use crate::theme::ThemeStylize;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+use super::selection_popup_common::GenericDisplayRow;
+use super::selection_popup_common::measure_rows_height;
+use super::selection_popup_common::render_rows;
+
+/// One row in the palette. Indices point into the palette's own lists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum PaletteEntry {
+    Command(SlashCommand),
+    Prompt(usize),
+    Checkpoint(usize),
+    ShellCommand(usize),
+    File(usize),
+}
+
+/// Ctrl+P overlay that fuzzy-searches slash commands, custom prompts,
+/// checkpoints, recent `!` commands, and workspace files in one list.
+pub(crate) struct CommandPalette {
+    query: String,
+    commands: Vec<SlashCommand>,
+    prompts: Vec<String>,
+    checkpoints: Vec<String>,
+    shell_commands: Vec<String>,
+    /// Query the current `files` were found for.
+    file_query: String,
+    files: Vec<FileMatch>,
+    state: ScrollState,
+    complete: bool,
+    app_event_tx: AppEventSender,
+}
+
+impl CommandPalette {
+    pub(crate) fn new(
+        prompts: Vec<String>,
+        checkpoints: Vec<String>,
+        shell_commands: Vec<String>,
+        app_event_tx: AppEventSender,
+    ) -> Self {
+        let commands = built_in_slash_commands()
+            .into_iter()
+            .map(|(_, cmd)| cmd)
+            .collect();
+        let mut palette = Self {
+            query: String::new(),
+            commands,
+            prompts,
+            checkpoints,
+            shell_commands,
+            file_query: String::new(),
+            files: Vec::new(),
+            state: ScrollState::new(),
+            complete: false,
+            app_event_tx,
+        };
+        palette.refresh_selection();
+        palette
+    }
+
+    /// Matches for the current query with highlight indices into the
+    /// displayed name. Commands, prompts, checkpoints, and shell commands
+    /// are ranked by fuzzy score; files follow in file-search order.
+    fn filtered(&self) -> Vec<(PaletteEntry, Option<Vec<usize>>)> {
+        let query = self.query.trim();
+        if query.is_empty() {
+            return self
+                .commands
+                .iter()
+                .map(|cmd| PaletteEntry::Command(*cmd))
+                .chain((0..self.prompts.len()).map(PaletteEntry::Prompt))
+                .chain((0..self.checkpoints.len()).map(PaletteEntry::Checkpoint))
+                .chain((0..self.shell_commands.len()).map(PaletteEntry::ShellCommand))
+                .map(|entry| (entry, None))
+                .collect();
+        }
+
+        let mut ranked: Vec<(PaletteEntry, Vec<usize>, i32)> = Vec::new();
+        for cmd in &self.commands {
+            if let Some((indices, score)) = fuzzy_match(cmd.command(), query) {
+                // Shift past the leading '/' of the displayed name.
+                let indices = indices.into_iter().map(|i| i + 1).collect();
+                ranked.push((PaletteEntry::Command(*cmd), indices, score));
+            }
+        }
+        for (idx, name) in self.prompts.iter().enumerate() {
+            let display = format!("{PROMPTS_CMD_PREFIX}:{name}");
+            if let Some((indices, score)) = fuzzy_match(&display, query) {
+                let indices = indices.into_iter().map(|i| i + 1).collect();
+                ranked.push((PaletteEntry::Prompt(idx), indices, score));
+            }
+        }
+        for (idx, name) in self.checkpoints.iter().enumerate() {
+            if let Some((indices, score)) = fuzzy_match(name, query) {
+                ranked.push((PaletteEntry::Checkpoint(idx), indices, score));
+            }
+        }
+        for (idx, command) in self.shell_commands.iter().enumerate() {
+            let display = format!("!{command}");
+            if let Some((indices, score)) = fuzzy_match(&display, query) {
+                ranked.push((PaletteEntry::ShellCommand(idx), indices, score));
+            }
+        }
+        ranked.sort_by_key(|(_, _, score)| *score);
+
+        let files = (0..self.files.len()).map(|idx| {
+            let indices = self.files[idx]
+                .indices
+                .as_ref()
+                .map(|v| v.iter().map(|&i| i as usize).collect());
+            (PaletteEntry::File(idx), indices)
+        });
+        ranked
+            .into_iter()
+            .map(|(entry, indices, _)| (entry, Some(indices)))
+            .chain(files)
+            .collect()
+    }
+
+    fn rows(&self) -> Vec<GenericDisplayRow> {
+        self.filtered()
+            .into_iter()
+            .map(|(entry, match_indices)| {
+                let (name, description) = match entry {
+                    PaletteEntry::Command(cmd) => {
+                        (format!("/{}", cmd.command()), cmd.description().to_string())
+                    }
+                    PaletteEntry::Prompt(idx) => (
+                        format!("/{PROMPTS_CMD_PREFIX}:{}", self.prompts[idx]),
+                        "custom prompt".to_string(),
+                    ),
+                    PaletteEntry::Checkpoint(idx) => (
+                        self.checkpoints[idx].clone(),
+                        "restore checkpoint".to_string(),
+                    ),
+                    PaletteEntry::ShellCommand(idx) => (
+                        format!("!{}", self.shell_commands[idx]),
+                        "run shell command".to_string(),
+                    ),
+                    PaletteEntry::File(idx) => {
+                        (self.files[idx].path.clone(), "mention file".to_string())
+                    }
+                };
+                GenericDisplayRow {
+                    name,
+                    match_indices,
+                    is_current: false,
+                    display_shortcut: None,
+                    description: Some(description),
+                }
+            })
+            .collect()
+    }
+
+    fn on_query_changed(&mut self) {
+        let query = self.query.trim();
+        if query.is_empty() {
+            self.file_query.clear();
+            self.files.clear();
+        } else {
+            self.app_event_tx
+                .send(AppEvent::StartFileSearch(query.to_string()));
+        }
+        self.state.reset();
+        self.refresh_selection();
+    }
+
+    fn refresh_selection(&mut self) {
+        let len = self.filtered().len();
+        self.state.clamp_selection(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn move_up(&mut self) {
+        let len = self.filtered().len();
+        self.state.move_up_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn move_down(&mut self) {
+        let len = self.filtered().len();
+        self.state.move_down_wrap(len);
+        self.state.ensure_visible(len, MAX_POPUP_ROWS.min(len));
+    }
+
+    fn accept(&mut self) {
+        let selected = self
+            .state
+            .selected_idx
+            .and_then(|idx| self.filtered().into_iter().nth(idx))
+            .map(|(entry, _)| entry);
+        let Some(entry) = selected else {
+            return;
+        };
+        let action = match entry {
+            PaletteEntry::Command(cmd) => PaletteAction::RunCommand(cmd),
+            PaletteEntry::Prompt(idx) => PaletteAction::InsertPrompt(self.prompts[idx].clone()),
+            PaletteEntry::Checkpoint(idx) => {
+                PaletteAction::RestoreCheckpoint(self.checkpoints[idx].clone())
+            }
+            PaletteEntry::ShellCommand(idx) => {
+                PaletteAction::RunShellCommand(self.shell_commands[idx].clone())
+            }
+            PaletteEntry::File(idx) => PaletteAction::InsertFile(self.files[idx].path.clone()),
+        };
+        self.app_event_tx
+            .send(AppEvent::CommandPaletteAction(action));
+        self.complete = true;
+    }
+
+    fn footer_hint() -> Line<'static> {
+        Line::from(vec![
+            key_hint::plain(KeyCode::Enter).into(),
+            " to run · ".into(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " to close".into(),
+        ])
+    }
+}
+
+impl BottomPaneView for CommandPalette {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match key_event {
+            KeyEvent {
+                code: KeyCode::Up, ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('p'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => self.move_up(),
+            KeyEvent {
+                code: KeyCode::Down,
+                ..
+            }
+            | KeyEvent {
+                code: KeyCode::Char('n'),
+                modifiers: KeyModifiers::CONTROL,
+                ..
+            } => self.move_down(),
+            KeyEvent {
+                code: KeyCode::Backspace,
+                ..
+            } => {
+                if self.query.pop().is_some() {
+                    self.on_query_changed();
+                }
+            }
+            KeyEvent {
+                code: KeyCode::Esc, ..
+            } => {
+                self.on_ctrl_c();
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers,
+                ..
+            } if !modifiers.contains(KeyModifiers::CONTROL)
+                && !modifiers.contains(KeyModifiers::ALT) =>
+            {
+                self.query.push(c);
+                self.on_query_changed();
+            }
+            KeyEvent {
+                code: KeyCode::Enter,
+                modifiers: KeyModifiers::NONE,
+                ..
+            } => self.accept(),
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+
+    fn handle_paste(&mut self, pasted: String) -> bool {
+        let pasted = pasted.replace(['\r', '\n'], " ");
+        if pasted.is_empty() {
+            return false;
+        }
+        self.query.push_str(&pasted);
+        self.on_query_changed();
+        true
+    }
+
+    fn on_file_search_result(&mut self, query: &str, matches: &[FileMatch]) -> bool {
+        if query != self.query.trim() {
+            // Stale, but still ours: a newer search is in flight.
+            return true;
+        }
+        self.file_query = query.to_string();
+        self.files = matches.to_vec();
+        self.refresh_selection();
+        true
+    }
+}
+
+impl Renderable for CommandPalette {
+    fn desired_height(&self, width: u16) -> u16 {
+        let rows_height = measure_rows_height(
+            &self.rows(),
+            &self.state,
+            MAX_POPUP_ROWS,
+            width.saturating_sub(1),
+        );
+        // Title, query line, blank line, rows, blank line, footer hint.
+        rows_height.saturating_add(5)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        let [content_area, footer_area] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+
+        Block::default()
+            .style(user_message_style())
+            .render(content_area, buf);
+
+        let [title_area, query_area, _, list_area] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Length(1),
+            Constraint::Fill(1),
+        ])
+        .areas(content_area.inset(Insets::vh(0, 2)));
+
+        Line::from("Command palette".bold()).render(title_area, buf);
+        if self.query.is_empty() {
+            Line::from("Search commands, prompts, checkpoints, and files".secondary())
+                .render(query_area, buf);
+        } else {
+            Line::from(self.query.clone()).render(query_area, buf);
+        }
+
+        if list_area.height > 0 {
+            let render_area = Rect {
+                x: list_area.x.saturating_sub(2),
+                width: list_area.width.saturating_add(2),
+                ..list_area
+            };
+            let empty_message = if self.query.trim() != self.file_query {
+                "searching..."
+            } else {
+                "no matches"
+            };
+            render_rows(
+                render_area,
+                buf,
+                &self.rows(),
+                &self.state,
+                MAX_POPUP_ROWS,
+                empty_message,
+            );
+        }
+
+        let hint_area = Rect {
+            x: footer_area.x + 2,
+            width: footer_area.width.saturating_sub(2),
+            ..footer_area
+        };
+        Self::footer_hint().secondary().render(hint_area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::UnboundedReceiver;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn palette(checkpoints: &[&str]) -> (CommandPalette, UnboundedReceiver<AppEvent>) {
+        let (tx, rx) = unbounded_channel::<AppEvent>();
+        let palette = CommandPalette::new(
+            vec!["deploy".to_string()],
+            checkpoints.iter().map(ToString::to_string).collect(),
+            vec!["cargo test -p codex-tui".to_string()],
+            AppEventSender::new(tx),
+        );
+        (palette, rx)
+    }
+
+    fn type_query(palette: &mut CommandPalette, query: &str) {
+        for c in query.chars() {
+            palette.handle_key_event(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+    }
+
+    fn take_action(rx: &mut UnboundedReceiver<AppEvent>) -> Option<PaletteAction> {
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::CommandPaletteAction(action) = event {
+                return Some(action);
+            }
+        }
+        None
+    }
+
+    #[test]
+    fn enter_runs_the_best_matching_command() {
+        let (mut palette, mut rx) = palette(&[]);
+        type_query(&mut palette, "mdl");
+        palette.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            take_action(&mut rx),
+            Some(PaletteAction::RunCommand(SlashCommand::Model))
+        );
+        assert!(palette.is_complete());
+    }
+
+    #[test]
+    fn checkpoints_are_listed_and_restorable() {
+        let (mut palette, mut rx) = palette(&["before-refactor"]);
+        type_query(&mut palette, "before-ref");
+        palette.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            take_action(&mut rx),
+            Some(PaletteAction::RestoreCheckpoint(
+                "before-refactor".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn recent_shell_commands_can_be_rerun() {
+        let (mut palette, mut rx) = palette(&[]);
+        type_query(&mut palette, "!cargo t");
+        palette.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            take_action(&mut rx),
+            Some(PaletteAction::RunShellCommand(
+                "cargo test -p codex-tui".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn typing_starts_a_file_search_and_shows_results() {
+        let (mut palette, mut rx) = palette(&[]);
+        type_query(&mut palette, "zz");
+
+        let mut searches = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let AppEvent::StartFileSearch(query) = event {
+                searches.push(query);
+            }
+        }
+        assert_eq!(searches, vec!["z".to_string(), "zz".to_string()]);
+
+        let matches = vec![FileMatch {
+            score: 1,
+            path: "src/zz.rs".to_string(),
+            indices: None,
+        }];
+        assert!(palette.on_file_search_result("zz", &matches));
+        palette.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert_eq!(
+            take_action(&mut rx),
+            Some(PaletteAction::InsertFile("src/zz.rs".to_string()))
+        );
+    }
+}
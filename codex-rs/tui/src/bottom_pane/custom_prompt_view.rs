@@ -13,6 +13,7 @@ use ratatui::widgets::Widget;
 use std::cell::RefCell;
 
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
 
 use super::popup_consts::standard_popup_hint_line;
 
@@ -54,6 +55,13 @@ impl CustomPromptView {
             complete: false,
         }
     }
+
+    /// Prefill the input, placing the cursor at the end.
+    pub(crate) fn with_initial_text(mut self, text: &str) -> Self {
+        self.textarea.set_text(text);
+        self.textarea.set_cursor(text.len());
+        self
+    }
 }
 
 impl BottomPaneView for CustomPromptView {
@@ -137,7 +145,7 @@ impl Renderable for CustomPromptView {
                 width: area.width,
                 height: 1,
             };
-            let spans: Vec<Span<'static>> = vec![gutter(), context_label.clone().cyan()];
+            let spans: Vec<Span<'static>> = vec![gutter(), context_label.clone().accent()];
             Paragraph::new(Line::from(spans)).render(context_area, buf);
             input_y = input_y.saturating_add(1);
         }
@@ -182,7 +190,7 @@ impl Renderable for CustomPromptView {
                 let mut state = self.textarea_state.borrow_mut();
                 StatefulWidgetRef::render_ref(&(&self.textarea), textarea_rect, buf, &mut state);
                 if self.textarea.text().is_empty() {
-                    Paragraph::new(Line::from(self.placeholder.clone().dim()))
+                    Paragraph::new(Line::from(self.placeholder.clone().secondary()))
                         .render(textarea_rect, buf);
                 }
             }
@@ -243,5 +251,5 @@ impl CustomPromptView {
 }
 
 fn gutter() -> Span<'static> {
-    "▌ ".cyan()
+    "▌ ".accent()
 }
@@ -19,6 +19,7 @@ use crate::app_event::FeedbackCategory;
 use crate::app_event_sender::AppEventSender;
 use crate::history_cell;
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
 use codex_core::protocol::SessionSource;
 
 use super::CancellationEvent;
@@ -104,7 +105,7 @@ impl FeedbackNoteView {
                 if let Some(url) = issue_url {
                     lines.extend([
                         "".into(),
-                        Line::from(vec!["  ".into(), url.cyan().underlined()]),
+                        Line::from(vec!["  ".into(), url.accent().underlined()]),
                         "".into(),
                         Line::from(vec![
                             "  Or mention your thread ID ".into(),
@@ -262,7 +263,7 @@ impl Renderable for FeedbackNoteView {
                 let mut state = self.textarea_state.borrow_mut();
                 StatefulWidgetRef::render_ref(&(&self.textarea), textarea_rect, buf, &mut state);
                 if self.textarea.text().is_empty() {
-                    Paragraph::new(Line::from(placeholder.dim())).render(textarea_rect, buf);
+                    Paragraph::new(Line::from(placeholder.secondary())).render(textarea_rect, buf);
                 }
             }
         }
@@ -302,7 +303,7 @@ impl FeedbackNoteView {
 }
 
 fn gutter() -> Span<'static> {
-    "▌ ".cyan()
+    "▌ ".accent()
 }
 
 fn feedback_title_and_placeholder(category: FeedbackCategory) -> (String, String) {
@@ -431,7 +432,7 @@ pub(crate) fn feedback_upload_consent_params(
     let mut header_lines: Vec<Box<dyn crate::render::renderable::Renderable>> = vec![
         Line::from("Upload logs?".bold()).into(),
         Line::from("").into(),
-        Line::from("The following files will be sent:".dim()).into(),
+        Line::from("The following files will be sent:".secondary()).into(),
         Line::from(vec!["  • ".into(), "codex-logs.log".into()]).into(),
     ];
     if let Some(path) = rollout_path.as_deref()
@@ -2,11 +2,11 @@ use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::render::line_utils::prefix_lines;
 use crate::semantic::SemanticStatus;
+use crate::theme::ThemeStylize;
 use crate::ui_consts::FOOTER_INDENT_COLS;
 use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
 use ratatui::widgets::Paragraph;
@@ -108,10 +108,10 @@ fn footer_lines(props: &FooterProps) -> Vec<Line<'static>> {
                 props.input_tokens,
                 props.output_tokens,
             );
-            line.push_span(" · ".dim());
+            line.push_span(" · ".secondary());
             line.extend(vec![
                 key_hint::plain(KeyCode::Char('?')).into(),
-                " for shortcuts".dim(),
+                " for shortcuts".secondary(),
             ]);
             vec![line]
         }
@@ -154,13 +154,13 @@ fn ctrl_c_reminder_line(state: CtrlCReminderState) -> Line<'static> {
         key_hint::ctrl(KeyCode::Char('c')).into(),
         format!(" again to {action}").into(),
     ])
-    .dim()
+    .secondary()
 }
 
 fn esc_hint_line(esc_backtrack_hint: bool) -> Line<'static> {
     let esc = key_hint::plain(KeyCode::Esc);
     if esc_backtrack_hint {
-        Line::from(vec![esc.into(), " again to edit previous message".into()]).dim()
+        Line::from(vec![esc.into(), " again to edit previous message".into()]).secondary()
     } else {
         Line::from(vec![
             esc.into(),
@@ -168,7 +168,7 @@ fn esc_hint_line(esc_backtrack_hint: bool) -> Line<'static> {
             esc.into(),
             " to edit previous message".into(),
         ])
-        .dim()
+        .secondary()
     }
 }
 
@@ -251,7 +251,7 @@ fn build_columns(entries: Vec<Line<'static>>) -> Vec<Line<'static>> {
                     line.push_span(Span::from(" ".repeat(padding)));
                 }
             }
-            line.dim()
+            line.secondary()
         })
         .collect()
 }
@@ -267,25 +267,25 @@ fn context_window_line(
     output_tokens: Option<i64>,
 ) -> Line<'static> {
     let percent = percent.unwrap_or(100).clamp(0, 100);
-    let mut spans = vec![Span::from(format!("{percent}% context left")).dim()];
+    let mut spans = vec![Span::from(format!("{percent}% context left")).secondary()];
 
     // Show token usage if available
     if let (Some(input), Some(output)) = (input_tokens, output_tokens) {
-        spans.push(" · ".dim());
-        spans.push(Span::from(format!("↑{} ↓{}", format_token_count(input), format_token_count(output))).dim());
+        spans.push(" · ".secondary());
+        spans.push(Span::from(format!("↑{} ↓{}", format_token_count(input), format_token_count(output))).secondary());
     }
 
     if let Some(semantic_span) = semantic_search_status_span(status, spinner, message) {
-        spans.push(" · ".dim());
+        spans.push(" · ".secondary());
         spans.push(semantic_span);
     }
     if let Some(summary) = rate_limit_summary {
-        spans.push(" · ".dim());
-        spans.push(Span::from(summary.clone()).dim());
+        spans.push(" · ".secondary());
+        spans.push(Span::from(summary.clone()).secondary());
     }
     // Show terminal indicator only for running tasks
     if let Some(terminal_span) = terminal_status_span(terminal_count, spinner) {
-        spans.push(" · ".dim());
+        spans.push(" · ".secondary());
         spans.push(terminal_span);
     }
     Line::from(spans)
@@ -303,13 +303,12 @@ fn format_token_count(count: i64) -> String {
 }
 
 fn terminal_status_span(count: &TerminalCount, spinner: char) -> Option<Span<'static>> {
-    use ratatui::style::Color;
     // Only show running tasks, not finished ones
     if count.running > 0 {
         let running_label = if count.running == 1 { "task" } else { "tasks" };
         Some(Span::styled(
             format!("{} {} {}", spinner, count.running, running_label),
-            ratatui::style::Style::default().fg(Color::Cyan),
+            ratatui::style::Style::default().accent(),
         ))
     } else {
         None
@@ -328,9 +327,9 @@ fn semantic_search_status_span(
                 .as_ref()
                 .map(|m| format!(" ({m})"))
                 .unwrap_or_default();
-            Some(format!("semantic indexing {spinner}{detail}").dim())
+            Some(format!("semantic indexing {spinner}{detail}").secondary())
         }
-        SemanticStatus::Ready => Some("indexed".dim()),
+        SemanticStatus::Ready => Some("indexed".secondary()),
     }
 }
 
@@ -20,6 +20,7 @@ use crate::render::RectExt as _;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
+use crate::theme::ThemeStylize;
 
 use super::CancellationEvent;
 use super::bottom_pane_view::BottomPaneView;
@@ -90,7 +91,9 @@ impl ListSelectionView {
         let mut header = params.header;
         if params.title.is_some() || params.subtitle.is_some() {
             let title = params.title.map(|title| Line::from(title.bold()));
-            let subtitle = params.subtitle.map(|subtitle| Line::from(subtitle.dim()));
+            let subtitle = params
+                .subtitle
+                .map(|subtitle| Line::from(subtitle.secondary()));
             header = Box::new(ColumnRenderable::with([
                 header,
                 Box::new(title),
@@ -396,7 +399,7 @@ impl Renderable for ListSelectionView {
                 Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(header_area);
             self.header.render(header_area, buf);
             Paragraph::new(vec![
-                Line::from(format!("[… {header_height} lines] ctrl + a view all")).dim(),
+                Line::from(format!("[… {header_height} lines] ctrl + a view all")).secondary(),
             ])
             .render(elision_area, buf);
         } else {
@@ -408,7 +411,7 @@ impl Renderable for ListSelectionView {
             let query_span: Span<'static> = if self.search_query.is_empty() {
                 self.search_placeholder
                     .as_ref()
-                    .map(|placeholder| placeholder.clone().dim())
+                    .map(|placeholder| placeholder.clone().secondary())
                     .unwrap_or_else(|| "".into())
             } else {
                 self.search_query.clone().into()
@@ -440,7 +443,7 @@ impl Renderable for ListSelectionView {
                 width: footer_area.width.saturating_sub(2),
                 height: footer_area.height,
             };
-            hint.clone().dim().render(hint_area, buf);
+            hint.clone().secondary().render(hint_area, buf);
         }
     }
 }
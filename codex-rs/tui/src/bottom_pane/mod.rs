@@ -23,8 +23,11 @@ use crate::key_hint::KeyBinding;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableItem;
+use crate::theme::ThemeStylize;
 use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
+use codex_core::doctor::DoctorFinding;
+use codex_core::doctor::DoctorSeverity;
 use codex_core::features::Features;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
@@ -34,6 +37,10 @@ use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Wrap;
 use std::time::Duration;
 
 mod approval_overlay;
@@ -50,6 +57,7 @@ pub(crate) struct LocalImageAttachment {
 }
 mod chat_composer;
 mod chat_composer_history;
+mod command_palette;
 mod command_popup;
 pub mod custom_prompt_view;
 mod experimental_features_view;
@@ -57,6 +65,10 @@ mod file_search_popup;
 mod footer;
 mod list_selection_view;
 mod prompt_args;
+pub(crate) use prompt_args::fill_prompt_template;
+pub(crate) use prompt_args::prompt_has_template_fields;
+mod prompt_template_view;
+pub(crate) use prompt_template_view::PromptTemplateView;
 mod skill_popup;
 mod skills_toggle_view;
 pub(crate) use footer::CollaborationModeIndicator;
@@ -68,8 +80,17 @@ pub(crate) use feedback_view::feedback_upload_consent_params;
 pub(crate) use skills_toggle_view::SkillsToggleItem;
 pub(crate) use skills_toggle_view::SkillsToggleView;
 mod paste_burst;
+mod patch_hunk_review;
+mod patch_staleness;
+mod plan_review_view;
+pub(crate) use plan_review_view::PlanReviewView;
 pub mod popup_consts;
+mod queue_editor_view;
+pub(crate) use queue_editor_view::QueueEditorView;
 mod queued_user_messages;
+mod review_findings_view;
+pub(crate) use review_findings_view::ReviewFindingsView;
+pub(crate) use review_findings_view::review_fix_prompt;
 mod scroll_state;
 mod selection_popup_common;
 mod textarea;
@@ -105,6 +126,7 @@ pub(crate) enum CancellationEvent {
 
 pub(crate) use chat_composer::ChatComposer;
 pub(crate) use chat_composer::InputResult;
+pub(crate) use command_palette::CommandPalette;
 use codex_protocol::custom_prompts::CustomPrompt;
 
 use crate::status_indicator_widget::StatusIndicatorWidget;
@@ -140,6 +162,16 @@ pub(crate) struct BottomPane {
     unified_exec_footer: UnifiedExecFooter,
     /// Queued user messages to show above the composer while a turn is running.
     queued_user_messages: QueuedUserMessages,
+    /// Count of history lines held back while the live tail is unfollowed.
+    unfollowed_history_lines: Option<usize>,
+    /// Globs of the active `/watch` commands.
+    active_watches: Vec<String>,
+    /// Files pinned with `/pin <file>`.
+    pinned_files: Vec<String>,
+    /// Whether exploration mode (read-only, approvals on request) is on.
+    exploration_mode: bool,
+    /// Problems found by the startup self-check, until dismissed with Esc.
+    doctor_findings: Vec<DoctorFinding>,
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
 }
@@ -186,6 +218,11 @@ impl BottomPane {
             status: None,
             unified_exec_footer: UnifiedExecFooter::new(),
             queued_user_messages: QueuedUserMessages::new(),
+            unfollowed_history_lines: None,
+            active_watches: Vec::new(),
+            pinned_files: Vec::new(),
+            exploration_mode: false,
+            doctor_findings: Vec::new(),
             esc_backtrack_hint: false,
             animations_enabled,
             context_window_percent: None,
@@ -544,12 +581,61 @@ impl BottomPane {
         self.request_redraw();
     }
 
+    pub(crate) fn set_unfollowed_history_lines(&mut self, count: Option<usize>) {
+        if self.unfollowed_history_lines != count {
+            self.unfollowed_history_lines = count;
+            self.request_redraw();
+        }
+    }
+
+    pub(crate) fn set_active_watches(&mut self, patterns: Vec<String>) {
+        if self.active_watches != patterns {
+            self.active_watches = patterns;
+            self.request_redraw();
+        }
+    }
+
+    pub(crate) fn set_pinned_files(&mut self, paths: Vec<String>) {
+        if self.pinned_files != paths {
+            self.pinned_files = paths;
+            self.request_redraw();
+        }
+    }
+
+    pub(crate) fn set_exploration_mode(&mut self, enabled: bool) {
+        if self.exploration_mode != enabled {
+            self.exploration_mode = enabled;
+            self.request_redraw();
+        }
+    }
+
+    pub(crate) fn set_doctor_findings(&mut self, findings: Vec<DoctorFinding>) {
+        if self.doctor_findings != findings {
+            self.doctor_findings = findings;
+            self.request_redraw();
+        }
+    }
+
+    /// Hide the startup self-check banner. Returns false when none was shown.
+    pub(crate) fn dismiss_doctor_banner(&mut self) -> bool {
+        if self.doctor_findings.is_empty() {
+            return false;
+        }
+        self.doctor_findings.clear();
+        self.request_redraw();
+        true
+    }
+
     pub(crate) fn set_unified_exec_processes(&mut self, processes: Vec<String>) {
         if self.unified_exec_footer.set_processes(processes) {
             self.request_redraw();
         }
     }
 
+    pub(crate) fn custom_prompts(&self) -> &[CustomPrompt] {
+        self.composer.custom_prompts()
+    }
+
     /// Update custom prompts available for the slash popup.
     pub(crate) fn set_custom_prompts(&mut self, prompts: Vec<CustomPrompt>) {
         self.composer.set_custom_prompts(prompts);
@@ -609,6 +695,19 @@ impl BottomPane {
         self.push_view(Box::new(modal));
     }
 
+    /// Forward a stale-patch notice to the view holding that approval.
+    pub(crate) fn mark_patch_stale(&mut self, id: &str, paths: &[PathBuf]) -> bool {
+        let marked = self
+            .view_stack
+            .iter_mut()
+            .rev()
+            .any(|view| view.mark_patch_stale(id, paths));
+        if marked {
+            self.request_redraw();
+        }
+        marked
+    }
+
     /// Called when the agent requests user input.
     pub fn push_user_input_request(&mut self, request: RequestUserInputEvent) {
         let request = if let Some(view) = self.view_stack.last_mut() {
@@ -688,7 +787,13 @@ impl BottomPane {
     }
 
     pub(crate) fn on_file_search_result(&mut self, query: String, matches: Vec<FileMatch>) {
-        self.composer.on_file_search_result(query, matches);
+        let consumed = self
+            .view_stack
+            .last_mut()
+            .is_some_and(|view| view.on_file_search_result(&query, &matches));
+        if !consumed {
+            self.composer.on_file_search_result(query, matches);
+        }
         self.request_redraw();
     }
 
@@ -716,15 +821,38 @@ impl BottomPane {
             RenderableItem::Borrowed(view)
         } else {
             let mut flex = FlexRenderable::new();
+            if !self.doctor_findings.is_empty() {
+                flex.push(
+                    0,
+                    RenderableItem::Owned(doctor_banner(&self.doctor_findings).into()),
+                );
+            }
             if let Some(status) = &self.status {
                 flex.push(0, RenderableItem::Borrowed(status));
             }
             if !self.unified_exec_footer.is_empty() {
                 flex.push(0, RenderableItem::Borrowed(&self.unified_exec_footer));
             }
+            if let Some(count) = self.unfollowed_history_lines {
+                flex.push(0, RenderableItem::Owned(unfollowed_badge(count).into()));
+            }
+            if !self.active_watches.is_empty() {
+                flex.push(0, RenderableItem::Owned(watch_badge(&self.active_watches).into()));
+            }
+            if !self.pinned_files.is_empty() {
+                flex.push(0, RenderableItem::Owned(pinned_badge(&self.pinned_files).into()));
+            }
+            if self.exploration_mode {
+                flex.push(0, RenderableItem::Owned(exploration_badge().into()));
+            }
             let has_queued_messages = !self.queued_user_messages.messages.is_empty();
-            let has_status_or_footer =
-                self.status.is_some() || !self.unified_exec_footer.is_empty();
+            let has_status_or_footer = self.status.is_some()
+                || !self.doctor_findings.is_empty()
+                || !self.unified_exec_footer.is_empty()
+                || self.unfollowed_history_lines.is_some()
+                || !self.active_watches.is_empty()
+                || !self.pinned_files.is_empty()
+                || self.exploration_mode;
             if has_queued_messages && has_status_or_footer {
                 flex.push(0, RenderableItem::Owned("".into()));
             }
@@ -740,6 +868,75 @@ impl BottomPane {
     }
 }
 
+/// Badge shown while the live tail is unfollowed, counting held-back lines.
+fn unfollowed_badge(count: usize) -> Line<'static> {
+    let noun = if count == 1 { "line" } else { "lines" };
+    Line::from(vec![
+        "  Paused · ".secondary(),
+        format!("{count} new {noun}").accent(),
+        " · ".secondary(),
+        key_hint::ctrl(KeyCode::Char('s')).into(),
+        " to follow".secondary(),
+    ])
+}
+
+/// Badge listing the globs watched by `/watch`.
+fn watch_badge(patterns: &[String]) -> Line<'static> {
+    Line::from(vec![
+        "  Watching · ".secondary(),
+        patterns.join(", ").accent(),
+        " · /watch clear to stop".secondary(),
+    ])
+}
+
+/// Badge listing the files pinned by `/pin <file>`.
+fn pinned_badge(paths: &[String]) -> Line<'static> {
+    Line::from(vec![
+        "  Pinned · ".secondary(),
+        paths.join(", ").accent(),
+        " · /unpin <file> to remove".secondary(),
+    ])
+}
+
+/// Badge shown while exploration mode holds the session read-only.
+fn exploration_badge() -> Line<'static> {
+    Line::from(vec![
+        "  Exploring".success().bold(),
+        " · read-only, approvals on request · ".secondary(),
+        key_hint::ctrl(KeyCode::Char('x')).into(),
+        " to restore write access".secondary(),
+    ])
+}
+
+/// Banner summarizing startup self-check problems and how to fix them.
+fn doctor_banner(findings: &[DoctorFinding]) -> Paragraph<'static> {
+    let noun = if findings.len() == 1 {
+        "problem"
+    } else {
+        "problems"
+    };
+    let mut lines = vec![Line::from(vec![
+        "  Startup check · ".secondary(),
+        format!("{} {noun}", findings.len()).bold(),
+        " · ".secondary(),
+        key_hint::plain(KeyCode::Esc).into(),
+        " to dismiss".secondary(),
+    ])];
+    for finding in findings {
+        let summary = match finding.severity {
+            DoctorSeverity::Error => finding.summary.clone().error(),
+            DoctorSeverity::Warning => finding.summary.clone().into(),
+        };
+        let mut spans = vec!["  • ".secondary(), summary];
+        if let Some(fix) = &finding.fix {
+            spans.push(" · run ".secondary());
+            spans.push(fix.clone().accent());
+        }
+        lines.push(Line::from(spans));
+    }
+    Paragraph::new(lines).wrap(Wrap { trim: false })
+}
+
 impl Renderable for BottomPane {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         self.as_renderable().render(area, buf);
@@ -789,6 +986,7 @@ mod tests {
             command: vec!["echo".into(), "ok".into()],
             reason: None,
             proposed_execpolicy_amendment: None,
+            network_destinations: Vec::new(),
         }
     }
 
@@ -0,0 +1,269 @@
+//! Hunk-by-hunk review of a proposed patch.
+//!
+//! Opened from the patch approval overlay. Every file and every `@@` hunk of
+//! an updated file can be deselected; confirming answers the approval with
+//! only the selected changes.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use codex_core::patch_hunks::DiffHunk;
+use codex_core::patch_hunks::retain_hunks;
+use codex_core::patch_hunks::split_unified_diff;
+use codex_core::protocol::FileChange;
+use codex_core::protocol::ReviewDecision;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::diff_render::display_path_for;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
+
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+
+/// Diff lines of the selected hunk shown under the list.
+const PREVIEW_LINES: usize = 8;
+
+pub(crate) enum HunkReviewOutcome {
+    /// Answer the approval with this decision.
+    Decide(ReviewDecision),
+    /// Return to the approval options.
+    Back,
+}
+
+struct FileEntry {
+    path: PathBuf,
+    change: FileChange,
+    hunks: Vec<DiffHunk>,
+    /// One flag per hunk, or a single flag for files reviewed as a whole
+    /// (added, deleted, or renamed without edits).
+    kept: Vec<bool>,
+}
+
+impl FileEntry {
+    fn accepted(&self) -> Option<FileChange> {
+        if self.hunks.is_empty() {
+            return self.kept[0].then(|| self.change.clone());
+        }
+        let keep: Vec<usize> = (0..self.kept.len()).filter(|i| self.kept[*i]).collect();
+        if keep.is_empty() {
+            return None;
+        }
+        match &self.change {
+            FileChange::Update {
+                unified_diff,
+                move_path,
+            } => Some(FileChange::Update {
+                unified_diff: retain_hunks(unified_diff, &keep),
+                move_path: move_path.clone(),
+            }),
+            other => Some(other.clone()),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Row {
+    File(usize),
+    Hunk(usize, usize),
+}
+
+pub(crate) struct PatchHunkReview {
+    cwd: PathBuf,
+    files: Vec<FileEntry>,
+    rows: Vec<Row>,
+    state: ScrollState,
+}
+
+impl PatchHunkReview {
+    pub(crate) fn new(cwd: PathBuf, changes: &HashMap<PathBuf, FileChange>) -> Self {
+        let mut entries: Vec<_> = changes.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        let files: Vec<FileEntry> = entries
+            .into_iter()
+            .map(|(path, change)| {
+                let hunks = match change {
+                    FileChange::Update { unified_diff, .. } => split_unified_diff(unified_diff),
+                    FileChange::Add { .. } | FileChange::Delete { .. } => Vec::new(),
+                };
+                FileEntry {
+                    path: path.clone(),
+                    change: change.clone(),
+                    kept: vec![true; hunks.len().max(1)],
+                    hunks,
+                }
+            })
+            .collect();
+
+        let mut rows = Vec::new();
+        for (file_idx, file) in files.iter().enumerate() {
+            rows.push(Row::File(file_idx));
+            rows.extend((0..file.hunks.len()).map(|hunk_idx| Row::Hunk(file_idx, hunk_idx)));
+        }
+        let mut state = ScrollState::new();
+        state.clamp_selection(rows.len());
+        Self {
+            cwd,
+            files,
+            rows,
+            state,
+        }
+    }
+
+    pub(crate) fn handle_key_event(&mut self, key_event: KeyEvent) -> Option<HunkReviewOutcome> {
+        let len = self.rows.len();
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.move_up_wrap(len);
+                self.state.ensure_visible(len, MAX_POPUP_ROWS);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.move_down_wrap(len);
+                self.state.ensure_visible(len, MAX_POPUP_ROWS);
+            }
+            KeyCode::Char(' ') => self.toggle_selected(),
+            KeyCode::Enter => return Some(HunkReviewOutcome::Decide(self.decision())),
+            KeyCode::Esc => return Some(HunkReviewOutcome::Back),
+            _ => {}
+        }
+        None
+    }
+
+    fn toggle_selected(&mut self) {
+        let Some(row) = self.state.selected_idx.and_then(|idx| self.rows.get(idx)) else {
+            return;
+        };
+        match *row {
+            Row::File(file_idx) => {
+                let file = &mut self.files[file_idx];
+                let keep = !file.kept.iter().all(|kept| *kept);
+                file.kept.iter_mut().for_each(|kept| *kept = keep);
+            }
+            Row::Hunk(file_idx, hunk_idx) => {
+                let kept = &mut self.files[file_idx].kept[hunk_idx];
+                *kept = !*kept;
+            }
+        }
+    }
+
+    /// Everything kept approves the patch as proposed and nothing kept
+    /// rejects it; anything in between approves the kept part.
+    fn decision(&self) -> ReviewDecision {
+        if self.files.iter().all(|file| file.kept.iter().all(|k| *k)) {
+            return ReviewDecision::Approved;
+        }
+        let changes: HashMap<PathBuf, FileChange> = self
+            .files
+            .iter()
+            .filter_map(|file| file.accepted().map(|change| (file.path.clone(), change)))
+            .collect();
+        if changes.is_empty() {
+            ReviewDecision::Abort
+        } else {
+            ReviewDecision::ApprovedPartially { changes }
+        }
+    }
+
+    fn render_lines(&self) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line<'static>> =
+            vec!["Select the changes to apply".bold().into(), Line::from("")];
+        for (idx, row) in self
+            .rows
+            .iter()
+            .enumerate()
+            .skip(self.state.scroll_top)
+            .take(MAX_POPUP_ROWS)
+        {
+            let selected = self.state.selected_idx == Some(idx);
+            let (indent, checked, label) = match *row {
+                Row::File(file_idx) => {
+                    let file = &self.files[file_idx];
+                    let kept = file.kept.iter().filter(|k| **k).count();
+                    let mut label = display_path_for(&file.path, &self.cwd);
+                    if !file.hunks.is_empty() {
+                        label.push_str(&format!(" ({kept}/{} hunks)", file.hunks.len()));
+                    }
+                    ("", kept > 0, label)
+                }
+                Row::Hunk(file_idx, hunk_idx) => {
+                    let file = &self.files[file_idx];
+                    (
+                        "    ",
+                        file.kept[hunk_idx],
+                        file.hunks[hunk_idx].header.clone(),
+                    )
+                }
+            };
+            let marker = if checked { "[x] " } else { "[ ] " };
+            if selected {
+                lines.push(
+                    vec![
+                        "› ".accent(),
+                        indent.into(),
+                        marker.accent(),
+                        label.accent().bold(),
+                    ]
+                    .into(),
+                );
+            } else {
+                lines.push(vec!["  ".into(), indent.into(), marker.into(), label.into()].into());
+            }
+        }
+
+        if let Some(Row::Hunk(file_idx, hunk_idx)) = self
+            .state
+            .selected_idx
+            .and_then(|idx| self.rows.get(idx))
+            .copied()
+        {
+            lines.push(Line::from(""));
+            let hunk = &self.files[file_idx].hunks[hunk_idx];
+            for line in hunk.lines.iter().take(PREVIEW_LINES) {
+                let line = format!("  {line}");
+                lines.push(match line.as_bytes().get(2) {
+                    Some(b'+') => line.success().into(),
+                    Some(b'-') => line.error().into(),
+                    _ => line.secondary().into(),
+                });
+            }
+            if hunk.lines.len() > PREVIEW_LINES {
+                lines.push("  …".secondary().into());
+            }
+        }
+
+        lines.push(Line::from(""));
+        lines.push(
+            Line::from(vec![
+                key_hint::plain(KeyCode::Char(' ')).into(),
+                " toggle   ".into(),
+                key_hint::plain(KeyCode::Enter).into(),
+                " apply selected   ".into(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " back".into(),
+            ])
+            .secondary(),
+        );
+        lines
+    }
+}
+
+impl Renderable for PatchHunkReview {
+    fn desired_height(&self, _width: u16) -> u16 {
+        self.render_lines().len() as u16
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        Paragraph::new(self.render_lines()).render(area, buf);
+    }
+}
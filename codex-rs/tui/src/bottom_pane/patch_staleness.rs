@@ -0,0 +1,133 @@
+//! Detects on-disk edits to files targeted by a patch that is waiting for
+//! approval, so the overlay can warn before applying a stale patch.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+
+use codex_core::protocol::FileChange;
+use tokio::task::JoinHandle;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn read(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// Snapshot of the files a pending patch reads or writes.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct PatchTargets {
+    stamps: Vec<(PathBuf, Option<FileStamp>)>,
+}
+
+impl PatchTargets {
+    /// Record the current state of every file touched by `changes`.
+    pub(crate) fn capture(cwd: &Path, changes: &HashMap<PathBuf, FileChange>) -> Self {
+        let mut paths: Vec<PathBuf> = Vec::new();
+        for (path, change) in changes {
+            paths.push(cwd.join(path));
+            if let FileChange::Update {
+                move_path: Some(dest),
+                ..
+            } = change
+            {
+                paths.push(cwd.join(dest));
+            }
+        }
+        paths.sort();
+        paths.dedup();
+        let stamps = paths
+            .into_iter()
+            .map(|path| {
+                let stamp = FileStamp::read(&path);
+                (path, stamp)
+            })
+            .collect();
+        Self { stamps }
+    }
+
+    /// Files that were created, removed or modified since the snapshot.
+    pub(crate) fn changed_paths(&self) -> Vec<PathBuf> {
+        self.stamps
+            .iter()
+            .filter(|(path, stamp)| FileStamp::read(path) != *stamp)
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+/// Poll `targets` until one of them changes, then report it once via
+/// [`AppEvent::PendingPatchStale`]. Returns `None` outside a Tokio runtime.
+pub(crate) fn spawn_watch(
+    id: String,
+    targets: PatchTargets,
+    app_event_tx: AppEventSender,
+) -> Option<JoinHandle<()>> {
+    let runtime = tokio::runtime::Handle::try_current().ok()?;
+    Some(runtime.spawn(async move {
+        loop {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let paths = targets.changed_paths();
+            if !paths.is_empty() {
+                app_event_tx.send(AppEvent::PendingPatchStale { id, paths });
+                break;
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn detects_edits_and_new_files() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("foo.rs"), "fn main() {}\n").expect("write foo");
+        let changes = HashMap::from([
+            (
+                PathBuf::from("foo.rs"),
+                FileChange::Update {
+                    unified_diff: String::new(),
+                    move_path: None,
+                },
+            ),
+            (
+                PathBuf::from("bar.rs"),
+                FileChange::Add {
+                    content: "// new\n".to_string(),
+                },
+            ),
+        ]);
+
+        let targets = PatchTargets::capture(dir.path(), &changes);
+        assert_eq!(targets.changed_paths(), Vec::<PathBuf>::new());
+
+        std::fs::write(dir.path().join("foo.rs"), "fn main() { run(); }\n").expect("edit foo");
+        std::fs::write(dir.path().join("bar.rs"), "// someone else\n").expect("create bar");
+
+        assert_eq!(
+            targets.changed_paths(),
+            vec![dir.path().join("bar.rs"), dir.path().join("foo.rs")]
+        );
+    }
+}
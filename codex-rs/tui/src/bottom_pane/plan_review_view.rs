@@ -6,6 +6,7 @@ use crate::bottom_pane::bottom_pane_view::BottomPaneView;
 use crate::history_cell;
 use crate::history_cell::HistoryCell;
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
 use codex_protocol::plan_tool::UpdatePlanArgs;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -60,7 +61,7 @@ impl PlanReviewView {
             .into(),
         );
         if let Some(goal) = &self.goal_summary {
-            lines.push(vec!["Goal: ".dim(), goal.clone().into()].into());
+            lines.push(vec!["Goal: ".secondary(), goal.clone().into()].into());
         }
         lines.push(Line::from(""));
 
@@ -9,6 +9,17 @@ use std::collections::HashSet;
 lazy_static! {
     static ref PROMPT_ARG_REGEX: Regex =
         Regex::new(r"\$[A-Z][A-Z0-9_]*").unwrap_or_else(|_| std::process::abort());
+    static ref TEMPLATE_FIELD_REGEX: Regex =
+        Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_-]*)\s*(?::([^}]*))?\}\}")
+            .unwrap_or_else(|_| std::process::abort());
+}
+
+/// A `{{name:description}}` placeholder in a custom prompt, filled in a form
+/// before the prompt is submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct TemplateField {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
 }
 
 #[derive(Debug)]
@@ -296,6 +307,46 @@ pub fn expand_numeric_placeholders(content: &str, args: &[String]) -> String {
     out
 }
 
+/// Extracts the form fields of a prompt template in order of first
+/// appearance. A field used more than once keeps its first description.
+pub(crate) fn prompt_template_fields(content: &str) -> Vec<TemplateField> {
+    let mut fields: Vec<TemplateField> = Vec::new();
+    for caps in TEMPLATE_FIELD_REGEX.captures_iter(content) {
+        let name = caps[1].to_string();
+        let description = caps
+            .get(2)
+            .map(|m| m.as_str().trim().to_string())
+            .filter(|description| !description.is_empty());
+        match fields.iter_mut().find(|field| field.name == name) {
+            Some(field) => {
+                if field.description.is_none() {
+                    field.description = description;
+                }
+            }
+            None => fields.push(TemplateField { name, description }),
+        }
+    }
+    fields
+}
+
+/// Whether selecting `prompt` should open the template form.
+pub(crate) fn prompt_has_template_fields(prompt: &CustomPrompt) -> bool {
+    TEMPLATE_FIELD_REGEX.is_match(&prompt.content)
+}
+
+/// Replaces every `{{name…}}` placeholder in `content` with its value.
+/// Placeholders without a value are left as written.
+pub(crate) fn fill_prompt_template(content: &str, values: &HashMap<String, String>) -> String {
+    TEMPLATE_FIELD_REGEX
+        .replace_all(content, |caps: &regex_lite::Captures<'_>| {
+            values
+                .get(&caps[1])
+                .cloned()
+                .unwrap_or_else(|| caps[0].to_string())
+        })
+        .into_owned()
+}
+
 /// Constructs a command text for a custom prompt with arguments.
 /// Returns the text and the cursor position (inside the first double quote).
 pub fn prompt_command_with_arg_placeholders(name: &str, args: &[String]) -> (String, usize) {
@@ -403,4 +454,28 @@ mod tests {
         let out = expand_custom_prompt("/prompts:my-prompt", &prompts).unwrap();
         assert_eq!(out, Some("literal $$USER".to_string()));
     }
+
+    #[test]
+    fn template_fields_are_collected_and_filled() {
+        let content = "Fix {{issue: Issue number}} in {{ file }}.\n\nSee {{issue}} for details.";
+        assert_eq!(
+            prompt_template_fields(content),
+            vec![
+                TemplateField {
+                    name: "issue".to_string(),
+                    description: Some("Issue number".to_string()),
+                },
+                TemplateField {
+                    name: "file".to_string(),
+                    description: None,
+                },
+            ]
+        );
+
+        let values = HashMap::from([("issue".to_string(), "#42".to_string())]);
+        assert_eq!(
+            fill_prompt_template(content, &values),
+            "Fix #42 in {{ file }}.\n\nSee #42 for details."
+        );
+    }
 }
@@ -0,0 +1,377 @@
+//! Form for filling the `{{name:description}}` fields of a custom prompt.
+//!
+//! Each field starts with the value last used for it in this template; ↑/↓
+//! step through older values. Submitting sends the values to the chat
+//! widget, which remembers them and submits the filled prompt.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Block;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::StatefulWidgetRef;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::prompt_args::TemplateField;
+use super::prompt_args::prompt_template_fields;
+use super::textarea::TextArea;
+use super::textarea::TextAreaState;
+
+/// Rows the active field may grow to before it scrolls.
+const MAX_FIELD_ROWS: u16 = 6;
+
+pub(crate) struct PromptTemplateView {
+    app_event_tx: AppEventSender,
+    prompt: CustomPrompt,
+    fields: Vec<TemplateField>,
+    /// Values of the fields; the active one is only current in `textarea`.
+    values: Vec<String>,
+    /// Recently used values per field name, most recent first.
+    recent: HashMap<String, Vec<String>>,
+    active: usize,
+    textarea: TextArea,
+    textarea_state: RefCell<TextAreaState>,
+    complete: bool,
+}
+
+impl PromptTemplateView {
+    pub(crate) fn new(
+        app_event_tx: AppEventSender,
+        prompt: CustomPrompt,
+        recent: HashMap<String, Vec<String>>,
+    ) -> Self {
+        let fields = prompt_template_fields(&prompt.content);
+        let values = fields
+            .iter()
+            .map(|field| {
+                recent
+                    .get(&field.name)
+                    .and_then(|values| values.first())
+                    .cloned()
+                    .unwrap_or_default()
+            })
+            .collect();
+        let mut view = Self {
+            app_event_tx,
+            prompt,
+            fields,
+            values,
+            recent,
+            active: 0,
+            textarea: TextArea::new(),
+            textarea_state: RefCell::new(TextAreaState::default()),
+            complete: false,
+        };
+        view.load_active();
+        view
+    }
+
+    fn load_active(&mut self) {
+        let value = self.values.get(self.active).cloned().unwrap_or_default();
+        self.set_active_text(&value);
+    }
+
+    fn set_active_text(&mut self, text: &str) {
+        self.textarea.set_text(text);
+        self.textarea.set_cursor(text.len());
+    }
+
+    fn focus(&mut self, index: usize) {
+        if index >= self.fields.len() || index == self.active {
+            return;
+        }
+        self.values[self.active] = self.textarea.text().to_string();
+        self.active = index;
+        self.load_active();
+    }
+
+    /// Replace the active value with an older (`older == true`) or newer
+    /// recent value. Returns false when the key should edit the text instead.
+    fn recall(&mut self, older: bool) -> bool {
+        let Some(recent) = self
+            .fields
+            .get(self.active)
+            .and_then(|field| self.recent.get(&field.name))
+            .cloned()
+        else {
+            return false;
+        };
+        let text = self.textarea.text();
+        let position = recent.iter().position(|value| value == text);
+        let next = match (position, older) {
+            (None, true) if self.textarea.is_empty() => recent.first(),
+            (Some(index), true) => recent.get(index + 1),
+            (Some(0), false) => {
+                self.set_active_text("");
+                return true;
+            }
+            (Some(index), false) => recent.get(index - 1),
+            (None, _) => return false,
+        };
+        match next.cloned() {
+            Some(value) => {
+                self.set_active_text(&value);
+                true
+            }
+            // Stay on the oldest value rather than moving the cursor.
+            None => position.is_some(),
+        }
+    }
+
+    /// Move to the next field, or submit from the last one. Fields left
+    /// empty are focused instead of submitting.
+    fn advance(&mut self) {
+        if self.active + 1 < self.fields.len() {
+            self.focus(self.active + 1);
+            return;
+        }
+        self.values[self.active] = self.textarea.text().to_string();
+        if let Some(empty) = self.values.iter().position(|value| value.trim().is_empty()) {
+            self.focus(empty);
+            return;
+        }
+        let values = self
+            .fields
+            .iter()
+            .map(|field| field.name.clone())
+            .zip(self.values.iter().cloned())
+            .collect();
+        self.app_event_tx.send(AppEvent::PromptTemplateFilled {
+            prompt: self.prompt.clone(),
+            values,
+        });
+        self.complete = true;
+    }
+
+    fn active_field_height(&self, width: u16) -> u16 {
+        self.textarea
+            .desired_height(width.saturating_sub(2))
+            .clamp(1, MAX_FIELD_ROWS)
+    }
+
+    /// Where the active field's text area goes inside `inner`.
+    fn textarea_rect(&self, inner: Rect) -> Rect {
+        // Each field above the active one takes a label and a preview row.
+        let y = inner.y + (self.active as u16) * 2 + 1;
+        Rect {
+            x: inner.x.saturating_add(2),
+            y,
+            width: inner.width.saturating_sub(2),
+            height: self.active_field_height(inner.width),
+        }
+    }
+
+    fn field_label(&self, index: usize) -> Line<'static> {
+        let field = &self.fields[index];
+        let name = if index == self.active {
+            field.name.clone().accent().bold()
+        } else {
+            field.name.clone().bold()
+        };
+        let mut spans: Vec<Span<'static>> = vec![name];
+        if let Some(description) = &field.description {
+            spans.push(format!(" — {description}").secondary());
+        }
+        spans.into()
+    }
+
+    fn field_preview(&self, index: usize) -> Line<'static> {
+        let value = &self.values[index];
+        if value.is_empty() {
+            return vec!["  ".into(), "(empty)".secondary().italic()].into();
+        }
+        let first_line = value.lines().next().unwrap_or_default().to_string();
+        let more = if value.lines().nth(1).is_some() {
+            " …"
+        } else {
+            ""
+        };
+        vec!["  ".into(), first_line.into(), more.secondary()].into()
+    }
+
+    fn hint_line() -> Line<'static> {
+        Line::from(vec![
+            key_hint::plain(KeyCode::Tab).into(),
+            " next field   ".into(),
+            key_hint::plain(KeyCode::Up).into(),
+            "/".into(),
+            key_hint::plain(KeyCode::Down).into(),
+            " recent values   ".into(),
+            key_hint::shift(KeyCode::Enter).into(),
+            " newline   ".into(),
+            key_hint::plain(KeyCode::Enter).into(),
+            " continue   ".into(),
+            key_hint::plain(KeyCode::Esc).into(),
+            " cancel".into(),
+        ])
+        .secondary()
+    }
+}
+
+impl Renderable for PromptTemplateView {
+    fn desired_height(&self, width: u16) -> u16 {
+        let fields = self.fields.len() as u16;
+        let inner_width = width.saturating_sub(2);
+        // Borders, a label and value row per field, the extra rows of the
+        // active field, and a blank line above the hints.
+        2 + fields * 2 + self.active_field_height(inner_width).saturating_sub(1) + 2
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("/{PROMPTS_CMD_PREFIX}:{}", self.prompt.name));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.is_empty() {
+            return;
+        }
+
+        let textarea_rect = self.textarea_rect(inner);
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        for index in 0..self.fields.len() {
+            lines.push(self.field_label(index));
+            if index == self.active {
+                // Leave room for the text area, drawn over these rows below.
+                for row in 0..textarea_rect.height {
+                    let gutter = if row == 0 { "› " } else { "  " };
+                    lines.push(gutter.accent().into());
+                }
+            } else {
+                lines.push(self.field_preview(index));
+            }
+        }
+        lines.push(Line::from(""));
+        lines.push(Self::hint_line());
+        Paragraph::new(lines).render(inner, buf);
+
+        let textarea_rect = textarea_rect.intersection(inner);
+        if !textarea_rect.is_empty() {
+            let mut state = self.textarea_state.borrow_mut();
+            StatefulWidgetRef::render_ref(&(&self.textarea), textarea_rect, buf, &mut state);
+        }
+    }
+
+    fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
+        let inner = Block::default().borders(Borders::ALL).inner(area);
+        let textarea_rect = self.textarea_rect(inner).intersection(inner);
+        if textarea_rect.is_empty() {
+            return None;
+        }
+        let state = *self.textarea_state.borrow();
+        self.textarea.cursor_pos_with_state(textarea_rect, state)
+    }
+}
+
+impl BottomPaneView for PromptTemplateView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        match (key_event.code, key_event.modifiers) {
+            (KeyCode::Esc, _) => self.complete = true,
+            (KeyCode::Tab, _) => self.focus(self.active + 1),
+            (KeyCode::BackTab, _) => self.focus(self.active.saturating_sub(1)),
+            (KeyCode::Enter, KeyModifiers::NONE) => self.advance(),
+            (KeyCode::Up, KeyModifiers::NONE) if self.recall(true) => {}
+            (KeyCode::Down, KeyModifiers::NONE) if self.recall(false) => {}
+            _ => self.textarea.input(key_event),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+
+    fn handle_paste(&mut self, pasted: String) -> bool {
+        if pasted.is_empty() {
+            return false;
+        }
+        self.textarea.insert_str(&pasted);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn type_text(view: &mut PromptTemplateView, text: &str) {
+        for ch in text.chars() {
+            view.handle_key_event(key(KeyCode::Char(ch)));
+        }
+    }
+
+    #[test]
+    fn fields_start_from_recent_values_and_submit_in_order() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let prompt = CustomPrompt {
+            name: "triage".to_string(),
+            path: "/tmp/triage.md".into(),
+            content: "Triage {{issue:Issue number}} for {{team}}".to_string(),
+            description: None,
+            argument_hint: None,
+        };
+        let recent = HashMap::from([(
+            "issue".to_string(),
+            vec!["#7".to_string(), "#3".to_string()],
+        )]);
+        let mut view = PromptTemplateView::new(AppEventSender::new(tx_raw), prompt, recent);
+        assert_eq!(view.textarea.text(), "#7");
+
+        view.handle_key_event(key(KeyCode::Up));
+        assert_eq!(view.textarea.text(), "#3");
+
+        // `team` is empty, so submitting from it stays in the form.
+        view.handle_key_event(key(KeyCode::Enter));
+        view.handle_key_event(key(KeyCode::Enter));
+        assert!(!view.is_complete());
+
+        type_text(&mut view, "infra");
+        view.handle_key_event(key(KeyCode::Enter));
+        assert!(view.is_complete());
+
+        let Ok(AppEvent::PromptTemplateFilled { prompt, values }) = rx.try_recv() else {
+            panic!("expected PromptTemplateFilled");
+        };
+        assert_eq!(prompt.name, "triage");
+        assert_eq!(
+            values,
+            HashMap::from([
+                ("issue".to_string(), "#3".to_string()),
+                ("team".to_string(), "infra".to_string()),
+            ])
+        );
+    }
+}
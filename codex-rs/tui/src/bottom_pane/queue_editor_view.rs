@@ -0,0 +1,261 @@
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Block;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event::QueuedMessageAction;
+use crate::app_event::QueuedMessageEdit;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+
+/// Overlay listing the queued user messages with reorder, edit, and delete
+/// actions. Every change is sent to the chat widget, which owns the queue.
+pub(crate) struct QueueEditorView {
+    app_event_tx: AppEventSender,
+    messages: Vec<String>,
+    state: ScrollState,
+    complete: bool,
+}
+
+impl QueueEditorView {
+    pub(crate) fn new(app_event_tx: AppEventSender, messages: Vec<String>) -> Self {
+        let mut state = ScrollState::new();
+        state.clamp_selection(messages.len());
+        Self {
+            app_event_tx,
+            messages,
+            state,
+            complete: false,
+        }
+    }
+
+    fn send(&self, index: usize, action: QueuedMessageAction) {
+        let Some(text) = self.messages.get(index) else {
+            return;
+        };
+        self.app_event_tx
+            .send(AppEvent::EditQueuedMessage(QueuedMessageEdit {
+                index,
+                text: text.clone(),
+                action,
+            }));
+    }
+
+    fn select(&mut self, index: usize) {
+        self.state.selected_idx = Some(index);
+        self.state
+            .ensure_visible(self.messages.len(), MAX_POPUP_ROWS);
+    }
+
+    fn move_selected(&mut self, up: bool) {
+        let Some(index) = self.state.selected_idx else {
+            return;
+        };
+        let target = if up {
+            index.checked_sub(1)
+        } else {
+            Some(index + 1).filter(|target| *target < self.messages.len())
+        };
+        let Some(target) = target else {
+            return;
+        };
+        let action = if up {
+            QueuedMessageAction::MoveUp
+        } else {
+            QueuedMessageAction::MoveDown
+        };
+        self.send(index, action);
+        self.messages.swap(index, target);
+        self.select(target);
+    }
+
+    fn delete_selected(&mut self) {
+        let Some(index) = self.state.selected_idx else {
+            return;
+        };
+        self.send(index, QueuedMessageAction::Delete);
+        self.messages.remove(index);
+        self.state.clamp_selection(self.messages.len());
+        self.state
+            .ensure_visible(self.messages.len(), MAX_POPUP_ROWS);
+        if self.messages.is_empty() {
+            self.complete = true;
+        }
+    }
+
+    fn edit_selected(&mut self) {
+        if let Some(index) = self.state.selected_idx {
+            self.send(index, QueuedMessageAction::Edit);
+            self.complete = true;
+        }
+    }
+
+    fn render_lines(&self) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        if self.messages.is_empty() {
+            lines.push("No queued messages".secondary().italic().into());
+        }
+        for (index, message) in self
+            .messages
+            .iter()
+            .enumerate()
+            .skip(self.state.scroll_top)
+            .take(MAX_POPUP_ROWS)
+        {
+            let first_line = message.lines().next().unwrap_or_default().to_string();
+            let first_line = if message.lines().nth(1).is_some() {
+                format!("{first_line} …")
+            } else {
+                first_line
+            };
+            let number = format!("{}. ", index + 1);
+            if self.state.selected_idx == Some(index) {
+                lines.push(vec!["› ".accent(), number.accent(), first_line.accent().bold()].into());
+            } else {
+                lines.push(vec!["  ".into(), number.secondary(), first_line.into()].into());
+            }
+        }
+        lines.push(Line::from(""));
+        lines.push(
+            Line::from(vec![
+                key_hint::shift(KeyCode::Up).into(),
+                "/".into(),
+                key_hint::shift(KeyCode::Down).into(),
+                " reorder   ".into(),
+                key_hint::plain(KeyCode::Char('e')).into(),
+                " edit   ".into(),
+                key_hint::plain(KeyCode::Char('d')).into(),
+                " delete   ".into(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " close".into(),
+            ])
+            .secondary(),
+        );
+        lines
+    }
+}
+
+impl Renderable for QueueEditorView {
+    fn desired_height(&self, _width: u16) -> u16 {
+        let content_height = self.render_lines().len() as u16;
+        // Add top/bottom borders.
+        content_height.saturating_add(2)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Queued messages");
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.is_empty() {
+            return;
+        }
+
+        Paragraph::new(self.render_lines()).render(inner, buf);
+    }
+}
+
+impl BottomPaneView for QueueEditorView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        let len = self.messages.len();
+        match (key_event.code, key_event.modifiers) {
+            (KeyCode::Up, KeyModifiers::SHIFT) | (KeyCode::Char('K'), _) => {
+                self.move_selected(true);
+            }
+            (KeyCode::Down, KeyModifiers::SHIFT) | (KeyCode::Char('J'), _) => {
+                self.move_selected(false);
+            }
+            (KeyCode::Up, _) | (KeyCode::Char('k'), _) => {
+                self.state.move_up_wrap(len);
+                self.state.ensure_visible(len, MAX_POPUP_ROWS);
+            }
+            (KeyCode::Down, _) | (KeyCode::Char('j'), _) => {
+                self.state.move_down_wrap(len);
+                self.state.ensure_visible(len, MAX_POPUP_ROWS);
+            }
+            (KeyCode::Delete, _) | (KeyCode::Backspace, _) | (KeyCode::Char('d'), _) => {
+                self.delete_selected();
+            }
+            (KeyCode::Enter, _) | (KeyCode::Char('e'), _) => self.edit_selected(),
+            (KeyCode::Esc, _) | (KeyCode::Char('q'), _) => self.complete = true,
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn reorder_and_delete_send_edits_for_the_selected_message() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = QueueEditorView::new(
+            AppEventSender::new(tx_raw),
+            vec!["first".to_string(), "second".to_string()],
+        );
+
+        view.handle_key_event(key(KeyCode::Down, KeyModifiers::SHIFT));
+        view.handle_key_event(key(KeyCode::Char('d'), KeyModifiers::NONE));
+
+        let mut edits = Vec::new();
+        while let Ok(AppEvent::EditQueuedMessage(edit)) = rx.try_recv() {
+            edits.push(edit);
+        }
+        assert_eq!(
+            edits,
+            vec![
+                QueuedMessageEdit {
+                    index: 0,
+                    text: "first".to_string(),
+                    action: QueuedMessageAction::MoveDown,
+                },
+                QueuedMessageEdit {
+                    index: 1,
+                    text: "first".to_string(),
+                    action: QueuedMessageAction::Delete,
+                },
+            ]
+        );
+        assert_eq!(view.messages, vec!["second".to_string()]);
+        assert!(!view.is_complete());
+
+        view.handle_key_event(key(KeyCode::Char('e'), KeyModifiers::NONE));
+        assert!(view.is_complete());
+    }
+}
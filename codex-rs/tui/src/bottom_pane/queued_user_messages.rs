@@ -7,6 +7,7 @@ use ratatui::widgets::Paragraph;
 
 use crate::key_hint;
 use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_lines;
 
@@ -31,9 +32,9 @@ impl QueuedUserMessages {
 
         for message in &self.messages {
             let wrapped = word_wrap_lines(
-                message.lines().map(|line| line.dim().italic()),
+                message.lines().map(|line| line.secondary().italic()),
                 RtOptions::new(width as usize)
-                    .initial_indent(Line::from("  ↳ ".dim()))
+                    .initial_indent(Line::from("  ↳ ".secondary()))
                     .subsequent_indent(Line::from("    ")),
             );
             let len = wrapped.len();
@@ -41,7 +42,7 @@ impl QueuedUserMessages {
                 lines.push(line);
             }
             if len > 3 {
-                lines.push(Line::from("    …".dim().italic()));
+                lines.push(Line::from("    …".secondary().italic()));
             }
         }
 
@@ -51,7 +52,7 @@ impl QueuedUserMessages {
                 key_hint::alt(KeyCode::Up).into(),
                 " edit".into(),
             ])
-            .dim(),
+            .secondary(),
         );
 
         Paragraph::new(lines).into()
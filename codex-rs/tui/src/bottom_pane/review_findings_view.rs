@@ -0,0 +1,284 @@
+use codex_core::protocol::ReviewFinding;
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Block;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::app_event::AppEvent;
+use crate::app_event::ReviewFindingAction;
+use crate::app_event::ReviewFindingStatus;
+use crate::app_event_sender::AppEventSender;
+use crate::key_hint;
+use crate::render::renderable::Renderable;
+use crate::theme::ThemeStylize;
+use crate::wrapping::RtOptions;
+use crate::wrapping::word_wrap_lines;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::popup_consts::MAX_POPUP_ROWS;
+use super::scroll_state::ScrollState;
+
+/// Body lines shown for the selected finding before it is cut off.
+const MAX_BODY_ROWS: usize = 6;
+
+/// Findings from the last `/review`, each of which can be queued as a fix,
+/// ignored, or opened in the editor. The chat widget owns the triage state
+/// so it survives closing and reopening the list with `/findings`.
+pub(crate) struct ReviewFindingsView {
+    app_event_tx: AppEventSender,
+    findings: Vec<(ReviewFinding, ReviewFindingStatus)>,
+    state: ScrollState,
+    complete: bool,
+}
+
+impl ReviewFindingsView {
+    pub(crate) fn new(
+        app_event_tx: AppEventSender,
+        findings: Vec<(ReviewFinding, ReviewFindingStatus)>,
+    ) -> Self {
+        let mut state = ScrollState::new();
+        // Start on the first finding that still needs a decision.
+        state.selected_idx = findings
+            .iter()
+            .position(|(_, status)| *status == ReviewFindingStatus::Open)
+            .or((!findings.is_empty()).then_some(0));
+        state.ensure_visible(findings.len(), MAX_POPUP_ROWS);
+        Self {
+            app_event_tx,
+            findings,
+            state,
+            complete: false,
+        }
+    }
+
+    fn act(&mut self, action: ReviewFindingAction) {
+        let Some(index) = self.state.selected_idx else {
+            return;
+        };
+        let Some((_, status)) = self.findings.get_mut(index) else {
+            return;
+        };
+        let action = match action {
+            ReviewFindingAction::Ignore if *status == ReviewFindingStatus::Ignored => {
+                ReviewFindingAction::Restore
+            }
+            action => action,
+        };
+        match action {
+            ReviewFindingAction::FixNow => *status = ReviewFindingStatus::FixQueued,
+            ReviewFindingAction::Ignore => *status = ReviewFindingStatus::Ignored,
+            ReviewFindingAction::Restore => *status = ReviewFindingStatus::Open,
+            ReviewFindingAction::OpenFile => {}
+        }
+        self.app_event_tx
+            .send(AppEvent::ReviewFindingAction { index, action });
+    }
+
+    fn finding_row(&self, index: usize) -> Line<'static> {
+        let (finding, status) = &self.findings[index];
+        let selected = self.state.selected_idx == Some(index);
+        let mut spans: Vec<Span<'static>> = match (selected, status) {
+            (true, _) => vec!["› ".accent(), finding.title.clone().accent().bold()],
+            (false, ReviewFindingStatus::Ignored) => {
+                vec!["  ".into(), finding.title.clone().secondary()]
+            }
+            (false, _) => vec!["  ".into(), finding.title.clone().into()],
+        };
+        match status {
+            ReviewFindingStatus::Open => {}
+            ReviewFindingStatus::FixQueued => spans.push("  fix queued".success()),
+            ReviewFindingStatus::Ignored => spans.push("  ignored".secondary().italic()),
+        }
+        spans.into()
+    }
+
+    fn detail_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let Some((finding, _)) = self.state.selected_idx.and_then(|i| self.findings.get(i)) else {
+            return Vec::new();
+        };
+        let location = &finding.code_location;
+        let mut lines: Vec<Line<'static>> = vec![
+            format!(
+                "{}:{}-{}",
+                location.absolute_file_path.display(),
+                location.line_range.start,
+                location.line_range.end
+            )
+            .secondary()
+            .into(),
+        ];
+        let body = word_wrap_lines(finding.body.lines(), RtOptions::new(width as usize));
+        let len = body.len();
+        lines.extend(body.into_iter().take(MAX_BODY_ROWS));
+        if len > MAX_BODY_ROWS {
+            lines.push("…".secondary().italic().into());
+        }
+        lines
+    }
+
+    fn render_lines(&self, width: u16) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line<'static>> = (0..self.findings.len())
+            .skip(self.state.scroll_top)
+            .take(MAX_POPUP_ROWS)
+            .map(|index| self.finding_row(index))
+            .collect();
+        lines.push(Line::from(""));
+        lines.extend(self.detail_lines(width));
+        lines.push(Line::from(""));
+        lines.push(
+            Line::from(vec![
+                key_hint::plain(KeyCode::Char('f')).into(),
+                " fix now   ".into(),
+                key_hint::plain(KeyCode::Char('i')).into(),
+                " ignore   ".into(),
+                key_hint::plain(KeyCode::Char('o')).into(),
+                " open file   ".into(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " close".into(),
+            ])
+            .secondary(),
+        );
+        lines
+    }
+}
+
+impl Renderable for ReviewFindingsView {
+    fn desired_height(&self, width: u16) -> u16 {
+        let content_height = self.render_lines(width.saturating_sub(2)).len() as u16;
+        // Add top/bottom borders.
+        content_height.saturating_add(2)
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        let open = self
+            .findings
+            .iter()
+            .filter(|(_, status)| *status == ReviewFindingStatus::Open)
+            .count();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Review findings ({open} open)"));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.is_empty() {
+            return;
+        }
+
+        Paragraph::new(self.render_lines(inner.width)).render(inner, buf);
+    }
+}
+
+impl BottomPaneView for ReviewFindingsView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        let len = self.findings.len();
+        match key_event.code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.state.move_up_wrap(len);
+                self.state.ensure_visible(len, MAX_POPUP_ROWS);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.state.move_down_wrap(len);
+                self.state.ensure_visible(len, MAX_POPUP_ROWS);
+            }
+            KeyCode::Char('f') | KeyCode::Enter => self.act(ReviewFindingAction::FixNow),
+            KeyCode::Char('i') => self.act(ReviewFindingAction::Ignore),
+            KeyCode::Char('o') => self.act(ReviewFindingAction::OpenFile),
+            KeyCode::Esc | KeyCode::Char('q') => self.complete = true,
+            _ => {}
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        self.complete = true;
+        CancellationEvent::Handled
+    }
+}
+
+/// Prompt queued by "fix now", pointing the agent at one finding.
+pub(crate) fn review_fix_prompt(finding: &ReviewFinding) -> String {
+    let location = &finding.code_location;
+    format!(
+        "Fix this issue from the code review:\n\n{}\n{}:{}-{}\n\n{}",
+        finding.title,
+        location.absolute_file_path.display(),
+        location.line_range.start,
+        location.line_range.end,
+        finding.body.trim()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ReviewCodeLocation;
+    use codex_core::protocol::ReviewLineRange;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn finding(title: &str) -> ReviewFinding {
+        ReviewFinding {
+            title: title.to_string(),
+            body: "Details".to_string(),
+            confidence_score: 0.8,
+            priority: 1,
+            code_location: ReviewCodeLocation {
+                absolute_file_path: "/repo/src/lib.rs".into(),
+                line_range: ReviewLineRange { start: 3, end: 5 },
+            },
+        }
+    }
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn actions_apply_to_the_selected_finding() {
+        let (tx_raw, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = ReviewFindingsView::new(
+            AppEventSender::new(tx_raw),
+            vec![
+                (finding("[P1] Done"), ReviewFindingStatus::FixQueued),
+                (finding("[P2] Leak"), ReviewFindingStatus::Open),
+            ],
+        );
+        assert_eq!(view.state.selected_idx, Some(1));
+
+        view.handle_key_event(key(KeyCode::Char('i')));
+        view.handle_key_event(key(KeyCode::Char('i')));
+        view.handle_key_event(key(KeyCode::Up));
+        view.handle_key_event(key(KeyCode::Char('o')));
+
+        let mut actions = Vec::new();
+        while let Ok(AppEvent::ReviewFindingAction { index, action }) = rx.try_recv() {
+            actions.push((index, action));
+        }
+        assert_eq!(
+            actions,
+            vec![
+                (1, ReviewFindingAction::Ignore),
+                (1, ReviewFindingAction::Restore),
+                (0, ReviewFindingAction::OpenFile),
+            ]
+        );
+        assert_eq!(view.findings[1].1, ReviewFindingStatus::Open);
+        assert!(!view.is_complete());
+    }
+}
@@ -2,7 +2,6 @@ use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
 // Note: Table-based layout previously used Constraint; the manual renderer
 // below no longer requires it.
-use ratatui::style::Color;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
 use ratatui::text::Line;
@@ -11,6 +10,7 @@ use ratatui::widgets::Widget;
 use unicode_width::UnicodeWidthChar;
 
 use crate::key_hint::KeyBinding;
+use crate::theme::ThemeStylize;
 
 use super::scroll_state::ScrollState;
 
@@ -106,7 +106,7 @@ fn build_full_line(row: &GenericDisplayRow, desc_col: usize) -> Line<'static> {
         if gap > 0 {
             full_spans.push(" ".repeat(gap).into());
         }
-        full_spans.push(desc.clone().dim());
+        full_spans.push(desc.clone().secondary());
     }
     Line::from(full_spans)
 }
@@ -123,7 +123,7 @@ pub(crate) fn render_rows(
 ) {
     if rows_all.is_empty() {
         if area.height > 0 {
-            Line::from(empty_message.dim().italic()).render(area, buf);
+            Line::from(empty_message.secondary().italic()).render(area, buf);
         }
         return;
     }
@@ -183,7 +183,7 @@ pub(crate) fn render_rows(
             // Match previous behavior: cyan + bold for the selected row.
             // Reset the style first to avoid inheriting dim from keyboard shortcuts.
             full_line.spans.iter_mut().for_each(|span| {
-                span.style = Style::default().fg(Color::Cyan).bold();
+                span.style = Style::default().accent().bold();
             });
         }
 
@@ -33,12 +33,16 @@ use codex_app_server_protocol::AuthMode;
 use codex_backend_client::Client as BackendClient;
 use codex_core::config::Config;
 use codex_core::config::ConstraintResult;
+use codex_core::config::types::CompactionPolicy;
 use codex_core::config::types::Notifications;
+use codex_core::config::types::PlanDetailPreference;
+use codex_core::doctor::DoctorFinding;
 use codex_core::features::FEATURES;
 use codex_core::features::Feature;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
 use codex_core::models_manager::manager::ModelsManager;
+use codex_core::onboarding::AGENTS_MD_INIT_PROMPT;
 use codex_core::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use codex_core::protocol::AgentMessageDeltaEvent;
 use codex_core::protocol::AgentMessageEvent;
@@ -48,6 +52,12 @@ use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CheckpointCreatedEvent;
+use codex_core::protocol::CheckpointDiffEvent;
+use codex_core::protocol::CheckpointListEvent;
+use codex_core::protocol::ConversationForkedEvent;
+use codex_core::protocol::ConversationMergedEvent;
+use codex_core::protocol::CostLimitApprovalRequestEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
@@ -61,37 +71,66 @@ use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
+use codex_core::protocol::ConfigChangedEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
+use codex_core::protocol::McpServerDownEvent;
 use codex_core::protocol::McpStartupCompleteEvent;
 use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::McpUnresolvedSecretsResponseEvent;
+use codex_core::protocol::MemoryContextInjectedEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
+use codex_core::protocol::PlanReadyEvent;
+use codex_core::protocol::ProjectDocsLoadedEvent;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::ResponseRegeneratedEvent;
+use codex_core::protocol::ReviewCodeLocation;
+use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::ReviewFinding;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
+use codex_core::protocol::SessionLockLostEvent;
+use codex_core::protocol::SessionSubsystem;
+use codex_core::protocol::SessionSummaryEvent;
 use codex_core::protocol::SkillMetadata as ProtocolSkillMetadata;
+use codex_core::protocol::StallDetectedEvent;
+use codex_core::protocol::StorageCleanupCompletedEvent;
+use codex_core::protocol::StorageCleanupOptions;
+use codex_core::protocol::StorageCleanupProgressEvent;
+use codex_core::protocol::StorageReportEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::SubagentHistoryItemEvent;
+use codex_core::protocol::SubagentReportEvent;
+use codex_core::protocol::SubagentScratchpadEvent;
 use codex_core::protocol::SubagentTaskLogEvent;
 use codex_core::protocol::SubagentTaskUpdateEvent;
+use codex_core::protocol::SubsystemStartupEvent;
+use codex_core::protocol::SubsystemStartupStatus;
+use codex_core::protocol::TaskStartedEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
+use codex_core::protocol::ToolCallCancelledEvent;
 use codex_core::protocol::TurnAbortReason;
 use codex_core::protocol::TurnCompleteEvent;
 use codex_core::protocol::TurnDiffEvent;
+use codex_core::protocol::TurnRetryScheduledEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
+use codex_core::protocol::UserMessageEditedEvent;
 use codex_core::protocol::UserMessageEvent;
 use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::replay::RolloutReplay;
 use codex_core::skills::model::SkillMetadata;
 use codex_otel::OtelManager;
+use codex_protocol::custom_prompts::CustomPrompt;
+use codex_protocol::custom_prompts::PROMPTS_CMD_PREFIX;
 use codex_protocol::ThreadId;
 use codex_protocol::account::PlanType;
 use codex_protocol::approvals::ElicitationRequestEvent;
@@ -110,7 +149,6 @@ use crossterm::event::KeyModifiers;
 use rand::Rng;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Color;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -129,6 +167,11 @@ const PLAN_IMPLEMENTATION_CODING_MESSAGE: &str = "Implement the plan.";
 
 use crate::app_event::AppEvent;
 use crate::app_event::ExitMode;
+use crate::app_event::PaletteAction;
+use crate::app_event::QueuedMessageAction;
+use crate::app_event::QueuedMessageEdit;
+use crate::app_event::ReviewFindingAction;
+use crate::app_event::ReviewFindingStatus;
 #[cfg(target_os = "windows")]
 use crate::app_event::WindowsSandboxEnableMode;
 use crate::app_event::WindowsSandboxFallbackReason;
@@ -139,16 +182,24 @@ use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::CollaborationModeIndicator;
+use crate::bottom_pane::CommandPalette;
 use crate::bottom_pane::DOUBLE_PRESS_QUIT_SHORTCUT_ENABLED;
 use crate::bottom_pane::ExperimentalFeaturesView;
 use crate::bottom_pane::InputResult;
 use crate::bottom_pane::LocalImageAttachment;
+use crate::bottom_pane::PlanReviewView;
+use crate::bottom_pane::PromptTemplateView;
 use crate::bottom_pane::QUIT_SHORTCUT_TIMEOUT;
+use crate::bottom_pane::QueueEditorView;
+use crate::bottom_pane::ReviewFindingsView;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
 use crate::bottom_pane::custom_prompt_view::CustomPromptView;
+use crate::bottom_pane::fill_prompt_template;
 use crate::bottom_pane::popup_consts::standard_popup_hint_line;
+use crate::bottom_pane::prompt_has_template_fields;
+use crate::bottom_pane::review_fix_prompt;
 use crate::clipboard_paste::paste_image_to_temp_png;
 use crate::collab;
 use crate::collaboration_modes;
@@ -157,6 +208,7 @@ use crate::exec_cell::CommandOutput;
 use crate::exec_cell::ExecCell;
 use crate::exec_cell::new_active_exec_command;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::file_watch::FileWatch;
 use crate::get_git_diff::get_git_diff;
 use crate::history_cell;
 use crate::history_cell::AgentMessageCell;
@@ -166,22 +218,33 @@ use crate::history_cell::PlainHistoryCell;
 use crate::history_cell::SubagentTasksCell;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
+use crate::keymap::KeyAction;
+use crate::keymap::Keymap;
+use crate::keymap::label as key_label;
 use crate::markdown::append_markdown;
+use crate::prompt_template_history::PromptTemplateHistory;
 use crate::render::Insets;
+use crate::render::centered_columns;
 use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::FlexRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
+use crate::semantic::SemanticStatus;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
 use crate::text_formatting::truncate_text;
+use crate::theme;
+use crate::theme::ThemeStylize;
 use crate::tui::FrameRequester;
+use crate::turn_diff_pane::TurnDiffPane;
+use crate::turn_summary::TurnStats;
 mod interrupts;
 use self::interrupts::InterruptManager;
 mod agent;
 use self::agent::spawn_agent;
 use self::agent::spawn_agent_from_existing;
+use self::agent::spawn_replay;
 pub(crate) use self::agent::spawn_op_forwarder;
 mod session_header;
 use self::session_header::SessionHeader;
@@ -193,6 +256,8 @@ use std::path::Path;
 use chrono::Local;
 use codex_common::approval_presets::ApprovalPreset;
 use codex_common::approval_presets::builtin_approval_presets;
+use codex_common::rate_limits::RATE_LIMIT_WARNING_THRESHOLDS;
+use codex_common::rate_limits::get_limits_duration;
 use codex_core::AuthManager;
 use codex_core::CodexAuth;
 use codex_core::ThreadManager;
@@ -207,6 +272,8 @@ use strum::IntoEnumIterator;
 const USER_SHELL_COMMAND_HELP_TITLE: &str = "Prefix a command with ! to run it locally";
 const USER_SHELL_COMMAND_HELP_HINT: &str = "Example: !ls";
 const DEFAULT_OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+/// Narrowest transcript width accepted by `/width`.
+const MIN_TRANSCRIPT_WIDTH: u16 = 40;
 // Track information about an in-flight exec command.
 struct RunningCommand {
     command: Vec<String>,
@@ -269,8 +336,11 @@ fn is_standard_tool_call(parsed_cmd: &[ParsedCommand]) -> bool {
             .all(|parsed| !matches!(parsed, ParsedCommand::Unknown { .. }))
 }
 
-const RATE_LIMIT_WARNING_THRESHOLDS: [f64; 3] = [75.0, 90.0, 95.0];
 const NUDGE_MODEL_SLUG: &str = "gpt-5.1-codex-mini";
+/// Checkpoints offered by the command palette.
+const MAX_RECENT_CHECKPOINTS: usize = 20;
+/// `!` commands offered by the command palette.
+const MAX_RECENT_SHELL_COMMANDS: usize = 20;
 const RATE_LIMIT_SWITCH_PROMPT_THRESHOLD: f64 = 90.0;
 
 #[derive(Default)]
@@ -338,28 +408,6 @@ impl RateLimitWarningState {
     }
 }
 
-pub(crate) fn get_limits_duration(windows_minutes: i64) -> String {
-    const MINUTES_PER_HOUR: i64 = 60;
-    const MINUTES_PER_DAY: i64 = 24 * MINUTES_PER_HOUR;
-    const MINUTES_PER_WEEK: i64 = 7 * MINUTES_PER_DAY;
-    const MINUTES_PER_MONTH: i64 = 30 * MINUTES_PER_DAY;
-    const ROUNDING_BIAS_MINUTES: i64 = 3;
-
-    let windows_minutes = windows_minutes.max(0);
-
-    if windows_minutes <= MINUTES_PER_DAY.saturating_add(ROUNDING_BIAS_MINUTES) {
-        let adjusted = windows_minutes.saturating_add(ROUNDING_BIAS_MINUTES);
-        let hours = std::cmp::max(1, adjusted / MINUTES_PER_HOUR);
-        format!("{hours}h")
-    } else if windows_minutes <= MINUTES_PER_WEEK.saturating_add(ROUNDING_BIAS_MINUTES) {
-        "weekly".to_string()
-    } else if windows_minutes <= MINUTES_PER_MONTH.saturating_add(ROUNDING_BIAS_MINUTES) {
-        "monthly".to_string()
-    } else {
-        "annual".to_string()
-    }
-}
-
 /// Common initialization parameters shared by all `ChatWidget` constructors.
 pub(crate) struct ChatWidgetInit {
     pub(crate) config: Config,
@@ -479,6 +527,8 @@ pub(crate) struct ChatWidget {
     queued_user_messages: VecDeque<UserMessage>,
     // Pending notification to show when unfocused on next Draw
     pending_notification: Option<Notification>,
+    // Most recent MCP image saved to disk; Ctrl+O opens it.
+    last_mcp_image: Option<PathBuf>,
     /// When `Some`, the user has pressed a quit shortcut and the second press
     /// must occur before `quit_shortcut_expires_at`.
     quit_shortcut_expires_at: Option<Instant>,
@@ -504,17 +554,60 @@ pub(crate) struct ChatWidget {
     had_work_activity: bool,
     // Whether the current turn emitted a plan update.
     saw_plan_update_this_turn: bool,
+    // Side pane with the accumulated diff of the current turn, toggled with Ctrl+D.
+    turn_diff_pane: TurnDiffPane,
+    // Activity of the running turn, summarized in history when it completes.
+    turn_stats: Option<TurnStats>,
+    // Active `/watch` commands and the id for the next one.
+    file_watches: Vec<FileWatch>,
+    next_file_watch_id: u64,
+    // When the last turn ended; watch triggers for changes made before then
+    // came from the agent or arrived while it was busy.
+    last_turn_finished_at: Option<Instant>,
+    // Whether new requests should go through the plan-first workflow.
+    plan_mode_enabled: bool,
+    // Approval and sandbox policies to restore when exploration mode ends;
+    // `Some` while exploration mode is on.
+    exploration_restore: Option<(AskForApproval, SandboxPolicy)>,
+    // Effective key bindings from `[tui.keys]`.
+    keymap: Keymap,
+    // The plan-first workflow core is running on our behalf, if any.
+    plan_workflow: Option<PlanWorkflowStatus>,
+    // Whether the next submission is feedback on the plan under review.
+    plan_feedback_pending: bool,
     // Status-indicator elapsed seconds captured at the last emitted final-message separator.
     //
     // This lets the separator show per-chunk work time (since the previous separator) rather than
     // the total task-running time reported by the status indicator.
     last_separator_elapsed_secs: Option<u64>,
 
+    // Width of the transcript column at the last render, after applying
+    // `max_transcript_width`; used to wrap streamed output.
     last_rendered_width: std::cell::Cell<Option<usize>>,
     // Feedback sink for /feedback
     feedback: codex_feedback::CodexFeedback,
     // Current session rollout path (if known)
     current_rollout_path: Option<PathBuf>,
+    /// Checkpoint names seen this session, most recent first, for the
+    /// command palette.
+    recent_checkpoints: VecDeque<String>,
+    /// `!` commands run this session, most recent first, for the command
+    /// palette.
+    recent_shell_commands: VecDeque<String>,
+    /// Each assistant message in this conversation, in order, for `/fork`,
+    /// `/regenerate` and `/quote`.
+    agent_messages: Vec<String>,
+    /// Positions in `agent_messages` pinned with `/pin`.
+    pinned_messages: HashSet<usize>,
+    /// Files pinned with `/pin <file>`, as typed, in the order pinned.
+    pinned_files: Vec<String>,
+    /// Latest subagent report with edits, for `/subagent-review`.
+    last_subagent_report: Option<SubagentReportEvent>,
+    /// Assistant message quoted into the composer with `/quote`; its anchor is
+    /// sent with the next message that still contains the quote.
+    pending_quote: Option<usize>,
+    /// Findings of the last `/review` and how each was triaged.
+    review_findings: Vec<(ReviewFinding, ReviewFindingStatus)>,
     external_editor_state: ExternalEditorState,
 }
 
@@ -542,6 +635,14 @@ pub(crate) struct ActiveCellTranscriptKey {
     pub(crate) animation_tick: Option<u64>,
 }
 
+/// Client-side view of a plan-first workflow. Core owns the state machine and
+/// the prompts; the widget only remembers what to render.
+#[derive(Debug)]
+struct PlanWorkflowStatus {
+    /// The drafted plan awaiting review; `None` while core is drafting.
+    ready: Option<PlanReadyEvent>,
+}
+
 pub(crate) struct UserMessage {
     text: String,
     local_images: Vec<LocalImageAttachment>,
@@ -808,6 +909,7 @@ impl ChatWidget {
     }
 
     fn on_agent_message(&mut self, message: String) {
+        self.agent_messages.push(message.clone());
         // If we have a stream_controller, then the final agent message is redundant and will be a
         // duplicate of what has already been streamed.
         if self.stream_controller.is_none() {
@@ -865,7 +967,9 @@ impl ChatWidget {
 
     // Raw reasoning uses the same flow as summarized reasoning
 
-    fn on_task_started(&mut self) {
+    fn on_task_started(&mut self, event: TaskStartedEvent) {
+        self.session_header
+            .set_turn_settings(&event.model, event.reasoning_effort);
         self.agent_turn_running = true;
         self.saw_plan_update_this_turn = false;
         self.bottom_pane.clear_quit_shortcut_hint();
@@ -877,6 +981,11 @@ impl ChatWidget {
         self.set_status_header(String::from("Working"));
         self.full_reasoning_buffer.clear();
         self.reasoning_buffer.clear();
+        let usage = self
+            .token_info
+            .as_ref()
+            .map(|info| info.total_token_usage.clone());
+        self.turn_stats = Some(TurnStats::start(usage));
         self.request_redraw();
     }
 
@@ -886,12 +995,20 @@ impl ChatWidget {
         self.flush_unified_exec_wait_streak();
         // Mark task stopped and request redraw now that all content is in history.
         self.agent_turn_running = false;
+        self.last_turn_finished_at = Some(Instant::now());
         self.update_task_running_state();
         self.running_commands.clear();
         self.suppressed_exec_calls.clear();
         self.last_unified_wait = None;
         self.unified_exec_wait_streak = None;
         self.clear_unified_exec_processes();
+        if let Some(stats) = self.turn_stats.take()
+            && !from_replay
+            && stats.had_tool_calls()
+        {
+            let usage = self.token_info.as_ref().map(|info| &info.total_token_usage);
+            self.add_to_history(stats.finish(usage, &self.config.cwd));
+        }
         self.request_redraw();
 
         if !from_replay && self.queued_user_messages.is_empty() {
@@ -1102,6 +1219,7 @@ impl ChatWidget {
         self.finalize_active_cell_as_failed();
         // Reset running state and clear streaming buffers.
         self.agent_turn_running = false;
+        self.last_turn_finished_at = Some(Instant::now());
         self.update_task_running_state();
         self.running_commands.clear();
         self.suppressed_exec_calls.clear();
@@ -1114,6 +1232,9 @@ impl ChatWidget {
 
     fn on_error(&mut self, message: String) {
         self.finalize_turn();
+        self.notify(Notification::Error {
+            message: message.clone(),
+        });
         self.add_to_history(history_cell::new_error_event(message));
         self.request_redraw();
 
@@ -1194,6 +1315,138 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    fn on_session_lock_lost(&mut self, ev: SessionLockLostEvent) {
+        self.on_warning(format!(
+            "Another process took over this session ({}); new messages are no longer saved. Run `/reclaim` to take it back.",
+            ev.rollout_path.display()
+        ));
+    }
+
+    fn on_stall_detected(&mut self, ev: StallDetectedEvent) {
+        let StallDetectedEvent {
+            idle_seconds,
+            last_event,
+            running_tool_calls,
+            interrupted,
+        } = ev;
+        let mut message = format!(
+            "No activity for {idle_seconds}s (last event: {last_event}, {running_tool_calls} running tool calls)."
+        );
+        if interrupted {
+            message.push_str(" Interrupting the turn.");
+        }
+        self.on_warning(message);
+        if !interrupted {
+            self.open_stall_recovery_prompt(idle_seconds);
+        }
+    }
+
+    fn open_stall_recovery_prompt(&mut self, idle_seconds: i64) {
+        let stop_actions: Vec<SelectionAction> = vec![Box::new(|tx| {
+            tx.send(AppEvent::CodexOp(Op::Interrupt));
+        })];
+        let items = vec![
+            SelectionItem {
+                name: "Stop".to_string(),
+                description: Some("Interrupt the turn; send a message to retry.".to_string()),
+                actions: stop_actions,
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Keep waiting".to_string(),
+                description: Some("Let the turn continue.".to_string()),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("The agent seems stuck".to_string()),
+            subtitle: Some(format!("No activity for {idle_seconds}s.")),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    fn on_config_changed(&mut self, ev: ConfigChangedEvent) {
+        let ConfigChangedEvent {
+            applied,
+            requires_new_session,
+        } = ev;
+        if !applied.is_empty() {
+            self.add_info_message(
+                format!("Reloaded config.toml: {}", applied.join(", ")),
+                None,
+            );
+            self.app_event_tx.send(AppEvent::ReloadConfig(applied));
+        }
+        if !requires_new_session.is_empty() {
+            self.on_warning(format!(
+                "config.toml changed {}; start a new session with /new to apply.",
+                requires_new_session.join(", ")
+            ));
+        }
+    }
+
+    fn on_mcp_server_down(&mut self, ev: McpServerDownEvent) {
+        let McpServerDownEvent {
+            server,
+            reason,
+            retrying,
+        } = ev;
+        if retrying {
+            self.on_warning(format!(
+                "MCP server `{server}` disconnected ({reason}); restarting…"
+            ));
+        } else {
+            self.on_warning(format!(
+                "MCP server `{server}` is unavailable: {reason}. Run `/mcp restart {server}` to try again."
+            ));
+        }
+    }
+
+    fn on_subsystem_startup(&mut self, ev: SubsystemStartupEvent) {
+        match (ev.subsystem, ev.status) {
+            (SessionSubsystem::SemanticSearch, status) => {
+                let status = match status {
+                    SubsystemStartupStatus::Ready => SemanticStatus::Ready,
+                    SubsystemStartupStatus::Unavailable { .. } => SemanticStatus::Missing,
+                };
+                self.app_event_tx.send(AppEvent::SemanticStatusUpdate(status, None));
+            }
+            (SessionSubsystem::Memory, SubsystemStartupStatus::Unavailable { reason }) => {
+                self.on_warning(format!("Memory is unavailable for this session: {reason}"));
+            }
+            (SessionSubsystem::Memory, SubsystemStartupStatus::Ready) => {}
+        }
+    }
+
+    /// `/status` lists every project doc; only warn when one did not fit in
+    /// `project_doc_max_bytes`, since the model then sees less than listed.
+    fn on_project_docs_loaded(&mut self, ev: ProjectDocsLoadedEvent) {
+        let truncated: Vec<String> = ev
+            .docs
+            .iter()
+            .filter(|doc| doc.truncated)
+            .map(|doc| {
+                let path = display_path_for(&doc.path, &self.config.cwd);
+                if doc.bytes == 0 {
+                    format!("{path} (left out)")
+                } else {
+                    format!("{path} (first {} bytes)", doc.bytes)
+                }
+            })
+            .collect();
+        if !truncated.is_empty() {
+            self.on_warning(format!(
+                "Project docs exceed project_doc_max_bytes; docs closer to the working directory were kept: {}",
+                truncated.join(", ")
+            ));
+        }
+    }
+
     /// Handle a turn aborted due to user interrupt (Esc).
     /// When there are queued user messages, restore them into the composer
     /// separated by newlines rather than auto‑submitting the next one.
@@ -1282,6 +1535,22 @@ impl ChatWidget {
         self.add_to_history(history_cell::new_plan_update(update));
     }
 
+    fn on_plan_ready(&mut self, event: PlanReadyEvent) {
+        let plan = event.plan.clone().unwrap_or_else(|| UpdatePlanArgs {
+            explanation: event.summary.clone(),
+            plan: Vec::new(),
+        });
+        let view = PlanReviewView::new(
+            self.app_event_tx.clone(),
+            Some(event.request.clone()),
+            event.feedback.clone(),
+            plan,
+        );
+        self.plan_workflow = Some(PlanWorkflowStatus { ready: Some(event) });
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
     fn on_exec_approval_request(&mut self, id: String, ev: ExecApprovalRequestEvent) {
         let id2 = id.clone();
         let ev2 = ev.clone();
@@ -1324,6 +1593,9 @@ impl ChatWidget {
                 return;
             }
         }
+        if let Some(stats) = self.turn_stats.as_mut() {
+            stats.record_exec();
+        }
         let ev2 = ev.clone();
         self.defer_or_handle(|q| q.push_exec_begin(ev), |s| s.handle_exec_begin_now(ev2));
     }
@@ -1337,7 +1609,7 @@ impl ChatWidget {
             return;
         };
 
-        if cell.append_output(&ev.call_id, std::str::from_utf8(&ev.chunk).unwrap_or("")) {
+        if cell.append_output(&ev.call_id, &String::from_utf8_lossy(&ev.chunk)) {
             self.bump_active_cell_revision();
             self.request_redraw();
         }
@@ -1395,6 +1667,9 @@ impl ChatWidget {
     }
 
     fn on_patch_apply_begin(&mut self, event: PatchApplyBeginEvent) {
+        if let Some(stats) = self.turn_stats.as_mut() {
+            stats.record_patch(event.changes.keys());
+        }
         self.add_to_history(history_cell::new_patch_event(
             event.changes,
             &self.config.cwd,
@@ -1485,7 +1760,89 @@ impl ChatWidget {
         self.sync_unified_exec_footer();
     }
 
+    fn on_cost_limit_approval_request(&mut self, id: String, ev: CostLimitApprovalRequestEvent) {
+        self.flush_answer_stream_with_separator();
+        let decision_item = |name: &str, description: &str, decision: ReviewDecision| {
+            let id = id.clone();
+            SelectionItem {
+                name: name.to_string(),
+                description: Some(description.to_string()),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::CodexOp(Op::CostLimitApproval {
+                        id: id.clone(),
+                        decision: decision.clone(),
+                    }));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        };
+        let items = vec![
+            decision_item(
+                "Continue",
+                "Ask again after spending another budget",
+                ReviewDecision::Approved,
+            ),
+            decision_item(
+                "Continue without limit",
+                "Stop asking for the rest of this session",
+                ReviewDecision::ApprovedForSession,
+            ),
+            decision_item("Stop turn", "End the current turn", ReviewDecision::Denied),
+        ];
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Session budget exceeded".to_string()),
+            subtitle: Some(format!(
+                "This session has cost ${:.2}, over the ${:.2} budget.",
+                ev.session_cost_usd, ev.max_session_cost_usd
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    /// Close out the cell of a tool call cancelled via `Op::CancelToolCall`,
+    /// which will not receive a regular end event.
+    fn on_tool_call_cancelled(&mut self, ev: ToolCallCancelledEvent) {
+        const CANCELLED: &str = "cancelled by user";
+        if let Some(running) = self.running_commands.get(&ev.call_id) {
+            let end = ExecCommandEndEvent {
+                call_id: ev.call_id,
+                process_id: None,
+                turn_id: String::new(),
+                command: running.command.clone(),
+                cwd: self.config.cwd.clone(),
+                parsed_cmd: running.parsed_cmd.clone(),
+                source: running.source,
+                interaction_input: None,
+                stdout: String::new(),
+                stderr: String::new(),
+                aggregated_output: CANCELLED.to_string(),
+                exit_code: -1,
+                duration: ev.duration,
+                formatted_output: CANCELLED.to_string(),
+            };
+            self.handle_exec_end_now(end);
+            return;
+        }
+        if let Some(cell) = self
+            .active_cell
+            .as_mut()
+            .and_then(|cell| cell.as_any_mut().downcast_mut::<McpToolCallCell>())
+            && cell.call_id() == ev.call_id
+        {
+            cell.complete(ev.duration, Err(CANCELLED.to_string()));
+            self.flush_active_cell();
+        }
+        self.request_redraw();
+    }
+
     fn on_mcp_tool_call_begin(&mut self, ev: McpToolCallBeginEvent) {
+        if let Some(stats) = self.turn_stats.as_mut() {
+            stats.record_mcp_call();
+        }
         let ev2 = ev.clone();
         self.defer_or_handle(|q| q.push_mcp_begin(ev), |s| s.handle_mcp_begin_now(ev2));
     }
@@ -1525,7 +1882,7 @@ impl ChatWidget {
             .as_mut()
             .and_then(|c| c.as_any_mut().downcast_mut::<SubagentTasksCell>())
         {
-            cell.update_task(ev.task, ev.status, ev.summary, None);
+            cell.update_task(ev.task, ev.status, ev.summary, None, ev.role, ev.agent_index);
             if !cell.has_running() {
                 self.flush_active_cell();
             } else {
@@ -1534,6 +1891,31 @@ impl ChatWidget {
         }
     }
 
+    fn active_subagent_tasks_running(&self) -> bool {
+        self.active_cell
+            .as_ref()
+            .and_then(|cell| cell.as_any().downcast_ref::<SubagentTasksCell>())
+            .is_some_and(SubagentTasksCell::has_running)
+    }
+
+    /// Cancel the subagent task shown at `position` (1-based) in the active
+    /// subagent cell, leaving the rest of the turn running.
+    fn cancel_subagent_task_at(&mut self, position: usize) {
+        let Some(target) = self
+            .active_cell
+            .as_ref()
+            .and_then(|cell| cell.as_any().downcast_ref::<SubagentTasksCell>())
+            .and_then(|cell| cell.cancel_target(position))
+        else {
+            return;
+        };
+        let (call_id, agent_index) = target;
+        self.submit_op(Op::CancelSubagentTask {
+            call_id,
+            agent_index,
+        });
+    }
+
     fn on_subagent_task_log(&mut self, ev: SubagentTaskLogEvent) {
         let needs_new_cell = self
             .active_cell
@@ -1597,6 +1979,31 @@ impl ChatWidget {
         }
     }
 
+    fn on_subagent_report(&mut self, ev: SubagentReportEvent) {
+        self.flush_active_cell();
+        self.add_to_history(history_cell::new_subagent_report(&ev, &self.config.cwd));
+        self.last_subagent_report = Some(ev);
+        self.request_redraw();
+    }
+
+    fn on_subagent_scratchpad(&mut self, ev: SubagentScratchpadEvent) {
+        self.flush_active_cell();
+        self.add_to_history(history_cell::new_subagent_scratchpad(&ev));
+        self.request_redraw();
+    }
+
+    fn open_subagent_review(&mut self) {
+        let Some(report) = &self.last_subagent_report else {
+            self.add_info_message(
+                "No subagent changes to review yet.".to_string(),
+                Some("Reports appear after subagents that edited files finish.".to_string()),
+            );
+            return;
+        };
+        self.app_event_tx
+            .send(AppEvent::DiffResult(subagent_review_text(report)));
+    }
+
     fn on_collab_event(&mut self, cell: PlainHistoryCell) {
         self.flush_answer_stream_with_separator();
         self.add_to_history(cell);
@@ -1622,6 +2029,153 @@ impl ChatWidget {
 
     fn on_turn_diff(&mut self, unified_diff: String) {
         debug!("TurnDiffEvent: {unified_diff}");
+        self.turn_diff_pane.set_diff(&unified_diff);
+        if self.turn_diff_pane.is_visible() {
+            self.request_redraw();
+        }
+    }
+
+    pub(crate) fn on_checkpoint_created(&mut self, event: CheckpointCreatedEvent) {
+        let name = event.checkpoint.name;
+        self.bottom_pane.hide_status_indicator();
+        self.add_info_message(format!("Saved checkpoint `{name}`."), None);
+        self.remember_checkpoint(name);
+    }
+
+    fn on_checkpoint_list(&mut self, event: CheckpointListEvent) {
+        self.recent_checkpoints = event
+            .checkpoints
+            .into_iter()
+            .rev()
+            .map(|checkpoint| checkpoint.name)
+            .take(MAX_RECENT_CHECKPOINTS)
+            .collect();
+    }
+
+    fn remember_checkpoint(&mut self, name: String) {
+        self.recent_checkpoints.retain(|existing| existing != &name);
+        self.recent_checkpoints.push_front(name);
+        self.recent_checkpoints.truncate(MAX_RECENT_CHECKPOINTS);
+    }
+
+    fn on_checkpoint_diff(&mut self, event: CheckpointDiffEvent) {
+        let CheckpointDiffEvent {
+            from,
+            to,
+            unified_diff,
+        } = event;
+        self.add_info_message(
+            format!("Diff from checkpoint `{}` to `{}`", from.name, to.name),
+            None,
+        );
+        self.app_event_tx.send(AppEvent::DiffResult(unified_diff));
+    }
+
+    fn on_conversation_forked(&mut self, event: ConversationForkedEvent) {
+        self.add_info_message(
+            format!(
+                "Forked after answer #{} into a new session; switching to it.",
+                event.at_item + 1
+            ),
+            Some("The original session is unchanged; use /resume to go back.".to_string()),
+        );
+        self.app_event_tx
+            .send(AppEvent::ResumeSession(event.rollout_path));
+    }
+
+    fn on_conversation_merged(&mut self, event: ConversationMergedEvent) {
+        let summary = event.summary;
+        self.add_info_message(
+            format!(
+                "Merged context from session {}",
+                event.source_conversation_id
+            ),
+            Some(format!(
+                "{} goals, {} decisions, {} files changed, {} open questions",
+                summary.goals.len(),
+                summary.decisions.len(),
+                summary.files_changed.len(),
+                summary.open_questions.len()
+            )),
+        );
+    }
+
+    fn on_response_regenerated(&mut self, event: ResponseRegeneratedEvent) {
+        let kept = self
+            .agent_messages
+            .len()
+            .saturating_sub(event.removed_messages);
+        self.agent_messages.truncate(kept);
+        self.pinned_messages.retain(|pinned| *pinned < kept);
+        let effort = event
+            .effort
+            .map(|effort| format!(" ({} effort)", Self::reasoning_effort_label(effort)))
+            .unwrap_or_default();
+        self.add_info_message(
+            format!("Regenerating the last answer with {}{effort}.", event.model),
+            Some("The previous answer above is no longer part of the conversation.".to_string()),
+        );
+    }
+
+    fn on_user_message_edited(&mut self, event: UserMessageEditedEvent) {
+        let kept = self
+            .agent_messages
+            .len()
+            .saturating_sub(event.removed_messages);
+        self.agent_messages.truncate(kept);
+        self.pinned_messages.retain(|pinned| *pinned < kept);
+        if !event.unpinned_files.is_empty() {
+            let cwd = self.config.cwd.clone();
+            self.pinned_files
+                .retain(|pinned| !event.unpinned_files.contains(&cwd.join(pinned)));
+            self.bottom_pane.set_pinned_files(self.pinned_files.clone());
+        }
+        self.add_info_message(
+            format!("Resending edited message #{}.", event.at_item + 1),
+            Some("The conversation above from that message on is no longer part of it.".to_string()),
+        );
+    }
+
+    fn on_memory_context_injected(&mut self, event: MemoryContextInjectedEvent) {
+        let count = event.memories.len();
+        let noun = if count == 1 { "memory" } else { "memories" };
+        let mut lines: Vec<Line<'static>> =
+            vec![format!("• {count} {noun} used this turn").secondary().into()];
+        for memory in event.memories {
+            let mut detail = format!("{} · score {:.2}", memory.memory_type, memory.score);
+            if !memory.reasons.is_empty() {
+                detail.push_str(&format!(" · {}", memory.reasons.join(", ")));
+            }
+            lines.push(Line::from(vec![
+                format!("  {} ", truncate_text(&memory.content, 80)).into(),
+                detail.secondary(),
+            ]));
+        }
+        self.add_plain_history_lines(lines);
+    }
+
+    fn on_session_summary(&mut self, event: SessionSummaryEvent) {
+        self.add_to_history(history_cell::new_session_summary(&event, &self.config.cwd));
+        self.request_redraw();
+    }
+
+    fn on_storage_report(&mut self, event: StorageReportEvent) {
+        self.add_to_history(history_cell::new_storage_report(&event, &self.config.cwd));
+        self.request_redraw();
+    }
+
+    fn on_storage_cleanup_progress(&mut self, event: StorageCleanupProgressEvent) {
+        self.bottom_pane.ensure_status_indicator();
+        self.bottom_pane.set_interrupt_hint_visible(false);
+        self.set_status_header(event.message);
+    }
+
+    fn on_storage_cleanup_completed(&mut self, event: StorageCleanupCompletedEvent) {
+        if !self.bottom_pane.is_task_running() {
+            self.bottom_pane.hide_status_indicator();
+        }
+        self.add_to_history(history_cell::new_storage_cleanup(&event));
+        self.request_redraw();
     }
 
     fn on_deprecation_notice(&mut self, event: DeprecationNoticeEvent) {
@@ -1670,6 +2224,20 @@ impl ChatWidget {
         self.set_status(message, additional_details);
     }
 
+    fn on_turn_retry_scheduled(&mut self, ev: TurnRetryScheduledEvent) {
+        let TurnRetryScheduledEvent {
+            attempt,
+            max_attempts,
+            delay_ms,
+            reason,
+        } = ev;
+        let delay_secs = (delay_ms + 999) / 1000;
+        self.on_stream_error(
+            format!("Retrying in {delay_secs}s (attempt {attempt}/{max_attempts})"),
+            Some(format!("{reason} · Esc to cancel")),
+        );
+    }
+
     /// Periodic tick to commit at most one queued line to history with a small delay,
     /// animating the output.
     pub(crate) fn on_commit_tick(&mut self) {
@@ -1844,6 +2412,7 @@ impl ChatWidget {
             command: ev.command,
             reason: ev.reason,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
+            network_destinations: ev.network_destinations,
         };
         self.bottom_pane
             .push_approval_request(request, &self.config.features);
@@ -1872,6 +2441,13 @@ impl ChatWidget {
         });
     }
 
+    /// Flag a pending patch approval whose target files changed on disk.
+    pub(crate) fn on_pending_patch_stale(&mut self, id: &str, paths: Vec<PathBuf>) {
+        if self.bottom_pane.mark_patch_stale(id, &paths) {
+            self.request_redraw();
+        }
+    }
+
     pub(crate) fn handle_elicitation_request_now(&mut self, ev: ElicitationRequestEvent) {
         self.flush_answer_stream_with_separator();
 
@@ -1977,9 +2553,10 @@ impl ChatWidget {
             invocation,
             duration,
             result,
+            images,
         } = ev;
 
-        let extra_cell = match self
+        match self
             .active_cell
             .as_mut()
             .and_then(|cell| cell.as_any_mut().downcast_mut::<McpToolCallCell>())
@@ -1992,15 +2569,20 @@ impl ChatWidget {
                     invocation,
                     self.config.animations,
                 );
-                let extra_cell = cell.complete(duration, result);
+                cell.complete(duration, result);
                 self.active_cell = Some(Box::new(cell));
-                extra_cell
             }
-        };
+        }
 
         self.flush_active_cell();
-        if let Some(extra) = extra_cell {
-            self.add_boxed_history(extra);
+        for image in &images {
+            self.add_to_history(history_cell::new_mcp_image_output(
+                &image.path,
+                &self.config.cwd,
+            ));
+        }
+        if let Some(image) = images.into_iter().last() {
+            self.last_mcp_image = Some(image.path);
         }
         // Mark that actual work was done (MCP tool call)
         self.had_work_activity = true;
@@ -2047,6 +2629,7 @@ impl ChatWidget {
         };
 
         let active_cell = Some(Self::placeholder_session_header_cell(&config));
+        let keymap = Keymap::new(&config.tui_keys);
 
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
@@ -2099,6 +2682,7 @@ impl ChatWidget {
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
+            last_mcp_image: None,
             quit_shortcut_expires_at: None,
             quit_shortcut_key: None,
             is_review_mode: false,
@@ -2106,10 +2690,28 @@ impl ChatWidget {
             needs_final_message_separator: false,
             had_work_activity: false,
             saw_plan_update_this_turn: false,
+            turn_diff_pane: TurnDiffPane::default(),
+            turn_stats: None,
+            file_watches: Vec::new(),
+            next_file_watch_id: 0,
+            last_turn_finished_at: None,
+            plan_mode_enabled: false,
+            exploration_restore: None,
+            keymap,
+            plan_workflow: None,
+            plan_feedback_pending: false,
             last_separator_elapsed_secs: None,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            recent_checkpoints: VecDeque::new(),
+            recent_shell_commands: VecDeque::new(),
+            agent_messages: Vec::new(),
+            pinned_messages: HashSet::new(),
+            pinned_files: Vec::new(),
+            last_subagent_report: None,
+            pending_quote: None,
+            review_findings: Vec::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2168,6 +2770,7 @@ impl ChatWidget {
         };
 
         let active_cell = Some(Self::placeholder_session_header_cell(&config));
+        let keymap = Keymap::new(&config.tui_keys);
 
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
@@ -2217,10 +2820,21 @@ impl ChatWidget {
             thread_id: None,
             forked_from: None,
             saw_plan_update_this_turn: false,
+            turn_diff_pane: TurnDiffPane::default(),
+            turn_stats: None,
+            file_watches: Vec::new(),
+            next_file_watch_id: 0,
+            last_turn_finished_at: None,
+            plan_mode_enabled: false,
+            exploration_restore: None,
+            keymap,
+            plan_workflow: None,
+            plan_feedback_pending: false,
             queued_user_messages: VecDeque::new(),
             show_welcome_banner: is_first_run,
             suppress_session_configured_redraw: false,
             pending_notification: None,
+            last_mcp_image: None,
             quit_shortcut_expires_at: None,
             quit_shortcut_key: None,
             is_review_mode: false,
@@ -2231,6 +2845,14 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            recent_checkpoints: VecDeque::new(),
+            recent_shell_commands: VecDeque::new(),
+            agent_messages: Vec::new(),
+            pinned_messages: HashSet::new(),
+            pinned_files: Vec::new(),
+            last_subagent_report: None,
+            pending_quote: None,
+            review_findings: Vec::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2245,6 +2867,13 @@ impl ChatWidget {
         widget
     }
 
+    /// Create a ChatWidget that plays back a recorded rollout instead of
+    /// talking to a model.
+    pub(crate) fn new_replay(common: ChatWidgetInit, replay: RolloutReplay, speed: f64) -> Self {
+        let codex_op_tx = spawn_replay(replay, speed, common.app_event_tx.clone());
+        Self::new_with_op_sender(common, codex_op_tx)
+    }
+
     /// Create a ChatWidget attached to an existing conversation (e.g., a fork).
     pub(crate) fn new_from_existing(
         common: ChatWidgetInit,
@@ -2290,6 +2919,7 @@ impl ChatWidget {
                 settings: fallback_custom,
             }
         };
+        let keymap = Keymap::new(&config.tui_keys);
 
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
@@ -2342,6 +2972,7 @@ impl ChatWidget {
             show_welcome_banner: false,
             suppress_session_configured_redraw: true,
             pending_notification: None,
+            last_mcp_image: None,
             quit_shortcut_expires_at: None,
             quit_shortcut_key: None,
             is_review_mode: false,
@@ -2349,10 +2980,28 @@ impl ChatWidget {
             needs_final_message_separator: false,
             had_work_activity: false,
             saw_plan_update_this_turn: false,
+            turn_diff_pane: TurnDiffPane::default(),
+            turn_stats: None,
+            file_watches: Vec::new(),
+            next_file_watch_id: 0,
+            last_turn_finished_at: None,
+            plan_mode_enabled: false,
+            exploration_restore: None,
+            keymap,
+            plan_workflow: None,
+            plan_feedback_pending: false,
             last_separator_elapsed_secs: None,
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            recent_checkpoints: VecDeque::new(),
+            recent_shell_commands: VecDeque::new(),
+            agent_messages: Vec::new(),
+            pinned_messages: HashSet::new(),
+            pinned_files: Vec::new(),
+            last_subagent_report: None,
+            pending_quote: None,
+            review_findings: Vec::new(),
             external_editor_state: ExternalEditorState::Closed,
         };
 
@@ -2385,7 +3034,7 @@ impl ChatWidget {
                 kind: KeyEventKind::Press,
                 ..
             } if modifiers.contains(KeyModifiers::CONTROL) && c.eq_ignore_ascii_case(&'d') => {
-                if self.on_ctrl_d() {
+                if self.toggle_turn_diff_pane() || self.on_ctrl_d() {
                     return;
                 }
                 self.bottom_pane.clear_quit_shortcut_hint();
@@ -2428,22 +3077,61 @@ impl ChatWidget {
         }
 
         match key_event {
-            KeyEvent {
-                code: KeyCode::BackTab,
-                kind: KeyEventKind::Press,
-                ..
-            } if self.collaboration_modes_enabled()
+            _ if self.keymap.matches(KeyAction::CycleMode, key_event)
+                && self.collaboration_modes_enabled()
                 && !self.bottom_pane.is_task_running()
                 && self.bottom_pane.no_modal_or_popup_active() =>
             {
                 self.cycle_collaboration_mode();
             }
             KeyEvent {
-                code: KeyCode::Up,
+                code: KeyCode::Char(c @ '1'..='9'),
                 modifiers: KeyModifiers::ALT,
                 kind: KeyEventKind::Press,
                 ..
-            } if !self.queued_user_messages.is_empty() => {
+            } if self.active_subagent_tasks_running() => {
+                let position = c.to_digit(10).unwrap_or_default() as usize;
+                self.cancel_subagent_task_at(position);
+            }
+            KeyEvent {
+                code: KeyCode::Up | KeyCode::Down,
+                modifiers: KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } if self.turn_diff_pane.is_visible() => {
+                if key_event.code == KeyCode::Up {
+                    self.turn_diff_pane.select_prev();
+                } else {
+                    self.turn_diff_pane.select_next();
+                }
+                self.request_redraw();
+            }
+            _ if self.keymap.matches(KeyAction::CommandPalette, key_event)
+                && self.bottom_pane.no_modal_or_popup_active() =>
+            {
+                self.open_command_palette();
+            }
+            _ if self.keymap.matches(KeyAction::ExplorationMode, key_event)
+                && self.bottom_pane.no_modal_or_popup_active() =>
+            {
+                self.toggle_exploration_mode();
+            }
+            _ if self.keymap.matches(KeyAction::OpenOutput, key_event)
+                && self.turn_diff_pane.is_visible() =>
+            {
+                self.turn_diff_pane.toggle_selected_collapsed();
+                self.request_redraw();
+            }
+            _ if self.keymap.matches(KeyAction::OpenOutput, key_event)
+                && self.open_running_exec_output() => {}
+            _ if self.keymap.matches(KeyAction::OpenOutput, key_event)
+                && self.last_mcp_image.is_some() =>
+            {
+                self.open_last_mcp_image();
+            }
+            _ if self.keymap.matches(KeyAction::EditQueuedMessage, key_event)
+                && !self.queued_user_messages.is_empty() =>
+            {
                 // Prefer the most recently queued item.
                 if let Some(user_message) = self.queued_user_messages.pop_back() {
                     let local_image_paths = user_message
@@ -2502,6 +3190,9 @@ impl ChatWidget {
                 InputResult::CommandWithArgs(cmd, args) => {
                     self.dispatch_command_with_args(cmd, args);
                 }
+                InputResult::PromptTemplate(prompt) => {
+                    self.open_prompt_template(prompt);
+                }
                 InputResult::None => {}
             },
         }
@@ -2574,7 +3265,22 @@ impl ChatWidget {
                 self.app_event_tx.send(AppEvent::OpenResumePicker);
             }
             SlashCommand::Fork => {
-                self.app_event_tx.send(AppEvent::ForkCurrentSession);
+                self.open_fork_popup();
+            }
+            SlashCommand::Regenerate => {
+                self.open_regenerate_popup();
+            }
+            SlashCommand::Quote => {
+                self.open_quote_popup();
+            }
+            SlashCommand::Pin => {
+                self.open_pin_popup();
+            }
+            SlashCommand::Unpin => {
+                self.add_pinned_files_output();
+            }
+            SlashCommand::Settings => {
+                self.open_settings_popup();
             }
             SlashCommand::Init => {
                 let init_target = self.config.cwd.join(DEFAULT_PROJECT_DOC_FILENAME);
@@ -2585,8 +3291,7 @@ impl ChatWidget {
                     self.add_info_message(message, None);
                     return;
                 }
-                const INIT_PROMPT: &str = include_str!("../prompt_for_init_command.md");
-                self.submit_user_message(INIT_PROMPT.to_string().into());
+                self.submit_user_message(AGENTS_MD_INIT_PROMPT.to_string().into());
             }
             SlashCommand::Compact => {
                 self.clear_token_usage();
@@ -2692,6 +3397,15 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::SubagentReview => {
+                self.open_subagent_review();
+            }
+            SlashCommand::CheckpointDiff => {
+                self.add_info_message(
+                    "Usage: /checkpoint-diff <from> <to>".to_string(),
+                    Some("Use /checkpoints to see saved checkpoint names.".to_string()),
+                );
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
@@ -2701,12 +3415,39 @@ impl ChatWidget {
             SlashCommand::Status => {
                 self.add_status_output();
             }
+            SlashCommand::Width => {
+                self.add_transcript_width_output();
+            }
+            SlashCommand::Theme => {
+                self.open_theme_popup();
+            }
+            SlashCommand::Watch => {
+                self.add_watch_output();
+            }
+            SlashCommand::Keys => {
+                self.add_keys_output();
+            }
             SlashCommand::Ps => {
                 self.add_ps_output();
             }
+            SlashCommand::Summary => {
+                self.request_session_summary(false);
+            }
+            SlashCommand::Queue => {
+                self.open_queue_editor();
+            }
+            SlashCommand::Findings => {
+                self.open_review_findings();
+            }
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
+            SlashCommand::Storage => {
+                self.submit_op(Op::GetStorageReport);
+            }
+            SlashCommand::Reclaim => {
+                self.submit_op(Op::ReclaimSessionLock);
+            }
             SlashCommand::Rollout => {
                 if let Some(path) = self.rollout_path() {
                     self.add_info_message(
@@ -2777,15 +3518,56 @@ impl ChatWidget {
                     self.open_collaboration_modes_popup();
                 }
             }
-            SlashCommand::Review if !trimmed.is_empty() => {
-                self.submit_op(Op::Review {
-                    review_request: ReviewRequest {
-                        target: ReviewTarget::Custom {
-                            instructions: trimmed.to_string(),
-                        },
-                        user_facing_hint: None,
-                    },
-                });
+            SlashCommand::CheckpointDiff if !trimmed.is_empty() => {
+                let names: Vec<&str> = trimmed.split_whitespace().collect();
+                if let [from, to] = names.as_slice() {
+                    self.submit_op(Op::DiffCheckpoints {
+                        from: (*from).to_string(),
+                        to: (*to).to_string(),
+                    });
+                } else {
+                    self.dispatch_command(cmd);
+                }
+            }
+            SlashCommand::Mcp if !trimmed.is_empty() => {
+                match trimmed.split_whitespace().collect::<Vec<_>>().as_slice() {
+                    ["restart", name] => self.submit_op(Op::RestartMcpServer {
+                        name: (*name).to_string(),
+                    }),
+                    _ => self.add_error_message("Usage: /mcp restart <server>".to_string()),
+                }
+            }
+            SlashCommand::Width if !trimmed.is_empty() => {
+                self.set_transcript_width_from_args(trimmed);
+            }
+            SlashCommand::Theme if !trimmed.is_empty() => {
+                self.app_event_tx
+                    .send(AppEvent::SetTheme(trimmed.to_string()));
+            }
+            SlashCommand::Watch if !trimmed.is_empty() => {
+                self.handle_watch_command(trimmed);
+            }
+            SlashCommand::Pin if !trimmed.is_empty() => {
+                self.pin_file(trimmed);
+            }
+            SlashCommand::Unpin if !trimmed.is_empty() => {
+                self.unpin_file(trimmed);
+            }
+            SlashCommand::Summary if trimmed == "save" => {
+                self.request_session_summary(true);
+            }
+            SlashCommand::Storage if !trimmed.is_empty() => {
+                self.handle_storage_command(trimmed);
+            }
+            SlashCommand::Review if !trimmed.is_empty() => {
+                self.submit_op(Op::Review {
+                    review_request: ReviewRequest {
+                        target: ReviewTarget::Custom {
+                            instructions: trimmed.to_string(),
+                        },
+                        user_facing_hint: None,
+                    },
+                });
             }
             _ => self.dispatch_command(cmd),
         }
@@ -2884,9 +3666,7 @@ impl ChatWidget {
                 )));
                 return;
             }
-            self.submit_op(Op::RunUserShellCommand {
-                command: cmd.to_string(),
-            });
+            self.run_user_shell_command(cmd.to_string());
             return;
         }
 
@@ -2903,6 +3683,12 @@ impl ChatWidget {
             });
         }
 
+        if let Some(item) = self.pending_quote.take()
+            && text.contains(&quote_anchor(item))
+        {
+            items.push(UserInput::Quote { item });
+        }
+
         if let Some(skills) = self.bottom_pane.skills() {
             let skill_mentions = find_skill_mentions(&text, skills);
             for skill in skill_mentions {
@@ -2913,19 +3699,24 @@ impl ChatWidget {
             }
         }
 
-        let op = Op::UserTurn {
-            items,
-            cwd: self.config.cwd.clone(),
-            approval_policy: self.config.approval_policy.value(),
-            sandbox_policy: self.config.sandbox_policy.get().clone(),
-            model: self.stored_collaboration_mode.model().to_string(),
-            effort: self.stored_collaboration_mode.reasoning_effort(),
-            summary: self.config.model_reasoning_summary,
-            final_output_json_schema: None,
-            collaboration_mode: self
-                .collaboration_modes_enabled()
-                .then(|| self.stored_collaboration_mode.clone()),
-            personality: None,
+        // In plan mode core wraps the request with planning instructions, so
+        // only the raw text is sent.
+        let op = match self.plan_op_for_submission(&text, &local_images) {
+            Some(op) => op,
+            None => Op::UserTurn {
+                items,
+                cwd: self.config.cwd.clone(),
+                approval_policy: self.config.approval_policy.value(),
+                sandbox_policy: self.config.sandbox_policy.get().clone(),
+                model: self.stored_collaboration_mode.model().to_string(),
+                effort: self.stored_collaboration_mode.reasoning_effort(),
+                summary: self.config.model_reasoning_summary,
+                final_output_json_schema: None,
+                collaboration_mode: self
+                    .collaboration_modes_enabled()
+                    .then(|| self.stored_collaboration_mode.clone()),
+                personality: None,
+            },
         };
 
         self.codex_op_tx.send(op).unwrap_or_else(|e| {
@@ -2943,17 +3734,42 @@ impl ChatWidget {
 
         // Only show the text portion in conversation history.
         if !text.is_empty() {
-            let local_image_paths = local_images.into_iter().map(|img| img.path).collect();
+            let local_image_paths = local_images.iter().map(|img| img.path.clone()).collect();
             self.add_to_history(history_cell::new_user_prompt(
                 text,
                 text_elements,
                 local_image_paths,
             ));
         }
+        for image in &local_images {
+            if let Some(cell) = history_cell::new_attached_image(&image.path, &self.config.cwd) {
+                self.add_to_history(cell);
+            }
+        }
 
         self.needs_final_message_separator = false;
     }
 
+    /// Runs a `!` command and remembers it in the cross-session history and
+    /// the command palette.
+    fn run_user_shell_command(&mut self, command: String) {
+        self.submit_op(Op::RunUserShellCommand {
+            command: command.clone(),
+        });
+        self.codex_op_tx
+            .send(Op::AddToHistory {
+                text: format!("!{command}"),
+            })
+            .unwrap_or_else(|e| {
+                tracing::error!("failed to send AddHistory op: {e}");
+            });
+        self.recent_shell_commands
+            .retain(|existing| existing != &command);
+        self.recent_shell_commands.push_front(command);
+        self.recent_shell_commands
+            .truncate(MAX_RECENT_SHELL_COMMANDS);
+    }
+
     /// Replay a subset of initial events into the UI to seed the transcript when
     /// resuming an existing session. This approximates the live event flow and
     /// is intentionally conservative: only safe-to-replay items are rendered to
@@ -2988,7 +3804,10 @@ impl ChatWidget {
     /// `replay_initial_messages()`. Callers should treat `None` as a "fake" id
     /// that must not be used to correlate follow-up actions.
     fn dispatch_event_msg(&mut self, id: Option<String>, msg: EventMsg, from_replay: bool) {
-        let is_stream_error = matches!(&msg, EventMsg::StreamError(_));
+        let is_stream_error = matches!(
+            &msg,
+            EventMsg::StreamError(_) | EventMsg::TurnRetryScheduled(_)
+        );
         if !is_stream_error {
             self.restore_retry_status_header_if_present();
         }
@@ -3019,7 +3838,7 @@ impl ChatWidget {
                 self.on_agent_reasoning_final();
             }
             EventMsg::AgentReasoningSectionBreak(_) => self.on_reasoning_section_break(),
-            EventMsg::TurnStarted(_) => self.on_task_started(),
+            EventMsg::TaskStarted(event) => self.on_task_started(event),
             EventMsg::TurnComplete(TurnCompleteEvent { last_agent_message }) => {
                 self.on_task_complete(last_agent_message, from_replay)
             }
@@ -3028,9 +3847,18 @@ impl ChatWidget {
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
+            EventMsg::ConfigChanged(ev) => self.on_config_changed(ev),
+            EventMsg::SessionLockLost(ev) => self.on_session_lock_lost(ev),
+            EventMsg::StallDetected(ev) => self.on_stall_detected(ev),
             EventMsg::Error(ErrorEvent { message, .. }) => self.on_error(message),
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
+            EventMsg::McpServerDown(ev) => self.on_mcp_server_down(ev),
+            EventMsg::SubsystemStartup(ev) => self.on_subsystem_startup(ev),
+            EventMsg::ProjectDocsLoaded(ev) => self.on_project_docs_loaded(ev),
+            EventMsg::McpServerRestarted(ev) => {
+                self.add_info_message(format!("MCP server `{}` restarted", ev.server), None);
+            }
             EventMsg::TurnAborted(ev) => match ev.reason {
                 TurnAbortReason::Interrupted => {
                     self.on_interrupted_turn(ev.reason);
@@ -3043,10 +3871,19 @@ impl ChatWidget {
                 }
             },
             EventMsg::PlanUpdate(update) => self.on_plan_update(update),
+            EventMsg::PlanReady(ev) => self.on_plan_ready(ev),
             EventMsg::ExecApprovalRequest(ev) => {
                 // For replayed events, synthesize an empty id (these should not occur).
                 self.on_exec_approval_request(id.unwrap_or_default(), ev)
             }
+            EventMsg::CostLimitApprovalRequest(ev) => {
+                self.on_cost_limit_approval_request(id.unwrap_or_default(), ev)
+            }
+            EventMsg::CostUpdate(ev) => {
+                if let Some(stats) = self.turn_stats.as_mut() {
+                    stats.record_cost(ev.turn_cost_usd);
+                }
+            }
             EventMsg::ApplyPatchApprovalRequest(ev) => {
                 self.on_apply_patch_approval_request(id.unwrap_or_default(), ev)
             }
@@ -3062,6 +3899,7 @@ impl ChatWidget {
             EventMsg::PatchApplyBegin(ev) => self.on_patch_apply_begin(ev),
             EventMsg::PatchApplyEnd(ev) => self.on_patch_apply_end(ev),
             EventMsg::ExecCommandEnd(ev) => self.on_exec_command_end(ev),
+            EventMsg::ToolCallCancelled(ev) => self.on_tool_call_cancelled(ev),
             EventMsg::ViewImageToolCall(ev) => self.on_view_image_tool_call(ev),
             EventMsg::McpToolCallBegin(ev) => self.on_mcp_tool_call_begin(ev),
             EventMsg::McpToolCallEnd(ev) => self.on_mcp_tool_call_end(ev),
@@ -3069,6 +3907,7 @@ impl ChatWidget {
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
+            EventMsg::McpUnresolvedSecretsResponse(ev) => self.on_mcp_unresolved_secrets(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
             EventMsg::SkillsUpdateAvailable => {
@@ -3079,6 +3918,22 @@ impl ChatWidget {
             }
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
             EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
+            EventMsg::CheckpointCreated(ev) => self.on_checkpoint_created(ev),
+            EventMsg::CheckpointList(ev) => self.on_checkpoint_list(ev),
+            EventMsg::CheckpointDiff(ev) => self.on_checkpoint_diff(ev),
+            EventMsg::MemoryContextInjected(ev) => self.on_memory_context_injected(ev),
+            EventMsg::SessionSummary(ev) => self.on_session_summary(ev),
+            EventMsg::StorageReport(ev) => self.on_storage_report(ev),
+            EventMsg::StorageCleanupProgress(ev) => self.on_storage_cleanup_progress(ev),
+            EventMsg::StorageCleanupCompleted(ev) => self.on_storage_cleanup_completed(ev),
+            EventMsg::ConversationForked(ev) => self.on_conversation_forked(ev),
+            EventMsg::ConversationMerged(ev) => self.on_conversation_merged(ev),
+            EventMsg::ResponseRegenerated(ev) => self.on_response_regenerated(ev),
+            EventMsg::UserMessageEdited(ev) => self.on_user_message_edited(ev),
+            EventMsg::MessageVariants(_)
+            | EventMsg::ConversationMergeProgress(_)
+            | EventMsg::SessionEnvUpdated(_)
+            | EventMsg::FollowUpSuggestions(_) => {}
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
@@ -3090,6 +3945,7 @@ impl ChatWidget {
                 additional_details,
                 ..
             }) => self.on_stream_error(message, additional_details),
+            EventMsg::TurnRetryScheduled(ev) => self.on_turn_retry_scheduled(ev),
             EventMsg::UserMessage(ev) => {
                 if from_replay {
                     self.on_user_message_event(ev);
@@ -3098,7 +3954,7 @@ impl ChatWidget {
             EventMsg::EnteredReviewMode(review_request) => {
                 self.on_entered_review_mode(review_request, from_replay)
             }
-            EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
+            EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review, from_replay),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
             EventMsg::CollabAgentSpawnBegin(_) => {}
             EventMsg::CollabAgentSpawnEnd(ev) => self.on_collab_event(collab::spawn_end(ev)),
@@ -3114,7 +3970,10 @@ impl ChatWidget {
             EventMsg::SubagentTaskUpdate(ev) => self.on_subagent_task_update(ev),
             EventMsg::SubagentTaskLog(ev) => self.on_subagent_task_log(ev),
             EventMsg::SubagentHistoryItem(ev) => self.on_subagent_history_item(ev),
+            EventMsg::SubagentReport(ev) => self.on_subagent_report(ev),
+            EventMsg::SubagentScratchpad(ev) => self.on_subagent_scratchpad(ev),
             EventMsg::RawResponseItem(_)
+            | EventMsg::FileRead(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
             | EventMsg::AgentMessageContentDelta(_)
@@ -3141,7 +4000,7 @@ impl ChatWidget {
         self.request_redraw();
     }
 
-    fn on_exited_review_mode(&mut self, review: ExitedReviewModeEvent) {
+    fn on_exited_review_mode(&mut self, review: ExitedReviewModeEvent, from_replay: bool) {
         // Leave review mode; if output is present, flush pending stream + show results.
         if let Some(output) = review.review_output {
             self.flush_answer_stream_with_separator();
@@ -3163,8 +4022,22 @@ impl ChatWidget {
                     self.app_event_tx
                         .send(AppEvent::InsertHistoryCell(Box::new(body_cell)));
                 }
+            } else {
+                let count = output.findings.len();
+                self.review_findings = output
+                    .findings
+                    .into_iter()
+                    .map(|finding| (finding, ReviewFindingStatus::Open))
+                    .collect();
+                let noun = if count == 1 { "finding" } else { "findings" };
+                self.add_info_message(
+                    format!("Review reported {count} {noun}"),
+                    Some("use /findings to fix, ignore, or open them".to_string()),
+                );
+                if !from_replay {
+                    self.open_review_findings();
+                }
             }
-            // Final message is rendered as part of the AgentMessage.
         }
 
         self.is_review_mode = false;
@@ -3216,6 +4089,37 @@ impl ChatWidget {
         self.active_cell_revision = self.active_cell_revision.wrapping_add(1);
     }
 
+    /// Opens the output of the running command in the active cell in a
+    /// full-screen follow view. Returns whether there was one to open.
+    fn open_running_exec_output(&self) -> bool {
+        let Some((call, output)) = self
+            .active_cell
+            .as_ref()
+            .and_then(|cell| cell.as_any().downcast_ref::<ExecCell>())
+            .and_then(ExecCell::running_live_output)
+        else {
+            return false;
+        };
+        self.app_event_tx.send(AppEvent::OpenExecOutput {
+            call_id: call.call_id.clone(),
+            command: strip_bash_lc_and_escape(&call.command),
+            output: output.clone(),
+        });
+        true
+    }
+
+    fn open_last_mcp_image(&mut self) {
+        let Some(path) = self.last_mcp_image.clone() else {
+            return;
+        };
+        let opened = url::Url::from_file_path(&path)
+            .map_err(|()| "not an absolute path".to_string())
+            .and_then(|url| webbrowser::open(url.as_str()).map_err(|err| err.to_string()));
+        if let Err(err) = opened {
+            self.add_error_message(format!("Failed to open {}: {err}", path.display()));
+        }
+    }
+
     fn notify(&mut self, notification: Notification) {
         if !notification.allowed_for(&self.config.tui_notifications) {
             return;
@@ -3226,7 +4130,13 @@ impl ChatWidget {
 
     pub(crate) fn maybe_post_pending_notification(&mut self, tui: &mut crate::tui::Tui) {
         if let Some(notif) = self.pending_notification.take() {
-            tui.notify(notif.display());
+            let method = self
+                .config
+                .tui_notification_methods
+                .get(notif.type_name())
+                .copied()
+                .unwrap_or(self.config.tui_notification_method);
+            tui.notify(notif.display(), method);
         }
     }
 
@@ -3255,6 +4165,225 @@ impl ChatWidget {
         self.refresh_queued_user_messages();
     }
 
+    fn request_session_summary(&mut self, save: bool) {
+        self.add_info_message(
+            "Summarizing the session…".to_string(),
+            (!save).then(|| "use `/summary save` to also write session notes".to_string()),
+        );
+        self.submit_op(Op::SummarizeSession { save });
+    }
+
+    fn open_queue_editor(&mut self) {
+        if self.queued_user_messages.is_empty() {
+            self.add_info_message("No queued messages.".to_string(), None);
+            return;
+        }
+        let messages = self
+            .queued_user_messages
+            .iter()
+            .map(|m| m.text.clone())
+            .collect();
+        let view = QueueEditorView::new(self.app_event_tx.clone(), messages);
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
+    fn open_review_findings(&mut self) {
+        if self.review_findings.is_empty() {
+            self.add_info_message(
+                "No review findings yet; run /review first.".to_string(),
+                None,
+            );
+            return;
+        }
+        let view = ReviewFindingsView::new(self.app_event_tx.clone(), self.review_findings.clone());
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
+    pub(crate) fn apply_review_finding_action(
+        &mut self,
+        index: usize,
+        action: ReviewFindingAction,
+    ) {
+        let Some((finding, status)) = self.review_findings.get_mut(index) else {
+            return;
+        };
+        match action {
+            ReviewFindingAction::FixNow => {
+                *status = ReviewFindingStatus::FixQueued;
+                let prompt = review_fix_prompt(finding);
+                self.queue_user_message(prompt.into());
+            }
+            ReviewFindingAction::Ignore => *status = ReviewFindingStatus::Ignored,
+            ReviewFindingAction::Restore => *status = ReviewFindingStatus::Open,
+            ReviewFindingAction::OpenFile => {
+                let location = finding.code_location.clone();
+                self.open_review_location(&location);
+            }
+        }
+    }
+
+    /// Open a finding's file at its first line with the configured
+    /// `file_opener`, or with the system handler when that is `none`.
+    fn open_review_location(&mut self, location: &ReviewCodeLocation) {
+        let path = &location.absolute_file_path;
+        let opened = match self.config.file_opener.get_scheme() {
+            Some(scheme) => Ok(format!(
+                "{scheme}://file{}:{}",
+                path.display(),
+                location.line_range.start
+            )),
+            None => url::Url::from_file_path(path)
+                .map(String::from)
+                .map_err(|()| "not an absolute path".to_string()),
+        }
+        .and_then(|url| webbrowser::open(&url).map_err(|err| err.to_string()));
+        if let Err(err) = opened {
+            self.add_error_message(format!("Failed to open {}: {err}", path.display()));
+        }
+    }
+
+    fn open_command_palette(&mut self) {
+        let prompts = self
+            .bottom_pane
+            .custom_prompts()
+            .iter()
+            .map(|prompt| prompt.name.clone())
+            .collect();
+        let checkpoints = self.recent_checkpoints.iter().cloned().collect();
+        let shell_commands = self.recent_shell_commands.iter().cloned().collect();
+        let view = CommandPalette::new(
+            prompts,
+            checkpoints,
+            shell_commands,
+            self.app_event_tx.clone(),
+        );
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
+    pub(crate) fn on_command_palette_action(&mut self, action: PaletteAction) {
+        match action {
+            PaletteAction::RunCommand(cmd) => self.dispatch_command(cmd),
+            PaletteAction::InsertPrompt(name) => {
+                let template = self
+                    .bottom_pane
+                    .custom_prompts()
+                    .iter()
+                    .find(|prompt| prompt.name == name)
+                    .filter(|prompt| prompt_has_template_fields(prompt))
+                    .cloned();
+                match template {
+                    Some(prompt) => self.open_prompt_template(prompt),
+                    None => self.insert_str(&format!("/{PROMPTS_CMD_PREFIX}:{name} ")),
+                }
+            }
+            PaletteAction::InsertFile(path) => {
+                if path.chars().any(char::is_whitespace) && !path.contains('"') {
+                    self.insert_str(&format!("\"{path}\" "));
+                } else {
+                    self.insert_str(&format!("{path} "));
+                }
+            }
+            PaletteAction::RestoreCheckpoint(name) => {
+                self.submit_op(Op::RestoreCheckpoint { name });
+            }
+            PaletteAction::RunShellCommand(command) => self.run_user_shell_command(command),
+        }
+    }
+
+    /// Open the form for a custom prompt's `{{name:description}}` fields.
+    pub(crate) fn open_prompt_template(&mut self, prompt: CustomPrompt) {
+        let recent = PromptTemplateHistory::load(&self.config.codex_home).recent(&prompt.name);
+        let view = PromptTemplateView::new(self.app_event_tx.clone(), prompt, recent);
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
+    /// Remember the values filled into a prompt template and submit the
+    /// filled prompt as a user message.
+    pub(crate) fn submit_prompt_template(
+        &mut self,
+        prompt: CustomPrompt,
+        values: HashMap<String, String>,
+    ) {
+        let mut history = PromptTemplateHistory::load(&self.config.codex_home);
+        history.record(&prompt.name, &values);
+        if let Err(err) = history.save(&self.config.codex_home) {
+            tracing::warn!("failed to save prompt template values: {err}");
+        }
+
+        let user_message = UserMessage::from(fill_prompt_template(&prompt.content, &values));
+        if self.is_session_configured() {
+            self.set_status_header(String::from("Working"));
+            self.submit_user_message(user_message);
+        } else {
+            self.queue_user_message(user_message);
+        }
+    }
+
+    /// Apply a change from the `/queue` editor. The target is located by
+    /// index, falling back to its text when the queue advanced meanwhile.
+    pub(crate) fn apply_queued_message_edit(&mut self, edit: QueuedMessageEdit) {
+        let QueuedMessageEdit {
+            index,
+            text,
+            action,
+        } = edit;
+        let index = match self.queued_user_messages.get(index) {
+            Some(message) if message.text == text => index,
+            _ => match self.queued_user_messages.iter().position(|m| m.text == text) {
+                Some(index) => index,
+                None => return,
+            },
+        };
+        match action {
+            QueuedMessageAction::MoveUp => {
+                if index > 0 {
+                    self.queued_user_messages.swap(index, index - 1);
+                }
+            }
+            QueuedMessageAction::MoveDown => {
+                if index + 1 < self.queued_user_messages.len() {
+                    self.queued_user_messages.swap(index, index + 1);
+                }
+            }
+            QueuedMessageAction::Delete => {
+                self.queued_user_messages.remove(index);
+            }
+            QueuedMessageAction::Edit => {
+                let tx = self.app_event_tx.clone();
+                let original = text.clone();
+                let view = CustomPromptView::new(
+                    format!("Edit queued message {}", index + 1),
+                    "Type the message and press Enter".to_string(),
+                    None,
+                    Box::new(move |updated: String| {
+                        tx.send(AppEvent::EditQueuedMessage(QueuedMessageEdit {
+                            index,
+                            text: original.clone(),
+                            action: QueuedMessageAction::Replace(updated),
+                        }));
+                    }),
+                )
+                .with_initial_text(&text);
+                self.bottom_pane.show_view(Box::new(view));
+            }
+            QueuedMessageAction::Replace(updated) => {
+                if let Some(message) = self.queued_user_messages.get_mut(index)
+                    && message.text != updated
+                {
+                    message.text = updated;
+                    // Element ranges refer to the old text.
+                    message.text_elements.clear();
+                }
+            }
+        }
+        self.refresh_queued_user_messages();
+        self.request_redraw();
+    }
+
     /// Rebuild and update the queued user messages from the current queue.
     fn refresh_queued_user_messages(&mut self) {
         let messages: Vec<String> = self
@@ -3265,6 +4394,12 @@ impl ChatWidget {
         self.bottom_pane.set_queued_user_messages(messages);
     }
 
+    /// Shows how many history lines are held back while the live tail is
+    /// unfollowed; `None` hides the badge.
+    pub(crate) fn set_unfollowed_history_lines(&mut self, count: Option<usize>) {
+        self.bottom_pane.set_unfollowed_history_lines(count);
+    }
+
     pub(crate) fn add_diff_in_progress(&mut self) {
         self.request_redraw();
     }
@@ -3377,7 +4512,15 @@ impl ChatWidget {
     fn open_rate_limit_switch_prompt(&mut self, preset: ModelPreset) {
         let switch_model = preset.model.to_string();
         let display_name = preset.display_name.to_string();
-        let default_effort: ReasoningEffortConfig = preset.default_reasoning_effort;
+        let switch_effort: ReasoningEffortConfig = self
+            .remembered_effort(&preset.model)
+            .filter(|effort| {
+                preset
+                    .supported_reasoning_efforts
+                    .iter()
+                    .any(|option| option.effort == *effort)
+            })
+            .unwrap_or(preset.default_reasoning_effort);
 
         let switch_actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
             tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
@@ -3385,13 +4528,13 @@ impl ChatWidget {
                 approval_policy: None,
                 sandbox_policy: None,
                 model: Some(switch_model.clone()),
-                effort: Some(Some(default_effort)),
+                effort: Some(Some(switch_effort)),
                 summary: None,
                 collaboration_mode: None,
                 personality: None,
             }));
             tx.send(AppEvent::UpdateModel(switch_model.clone()));
-            tx.send(AppEvent::UpdateReasoningEffort(Some(default_effort)));
+            tx.send(AppEvent::UpdateReasoningEffort(Some(switch_effort)));
         })];
 
         let keep_actions: Vec<SelectionAction> = Vec::new();
@@ -3475,7 +4618,7 @@ impl ChatWidget {
         let subtitle = subtitle.to_string();
         let mut header = ColumnRenderable::new();
         header.push(Line::from(title.bold()));
-        header.push(Line::from(subtitle.dim()));
+        header.push(Line::from(subtitle.secondary()));
         if let Some(warning) = self.model_menu_warning_line() {
             header.push(warning);
         }
@@ -3487,7 +4630,7 @@ impl ChatWidget {
         let warning = format!(
             "Warning: OPENAI_BASE_URL is set to {base_url}. Selecting models may not be supported or work properly."
         );
-        Some(Line::from(warning.red()))
+        Some(Line::from(warning.error()))
     }
 
     fn custom_openai_base_url(&self) -> Option<String> {
@@ -3692,6 +4835,42 @@ impl ChatWidget {
         });
     }
 
+    pub(crate) fn open_theme_popup(&mut self) {
+        let current = self
+            .config
+            .tui_theme
+            .as_deref()
+            .unwrap_or(theme::DEFAULT_THEME);
+        let items: Vec<SelectionItem> = theme::available(&self.config.codex_home)
+            .into_iter()
+            .map(|name| {
+                let is_current = name == current;
+                let theme_name = name.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::SetTheme(theme_name.clone()));
+                })];
+                SelectionItem {
+                    name,
+                    is_current,
+                    actions,
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Select Theme".to_string()),
+            subtitle: Some(format!(
+                "Add your own palettes in {}.",
+                theme::themes_dir(&self.config.codex_home).display()
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
     fn model_selection_actions(
         model_for_action: String,
         effort_for_action: Option<ReasoningEffortConfig>,
@@ -3786,13 +4965,17 @@ impl ChatWidget {
             .flatten()
             .or_else(|| choices.iter().find_map(|choice| choice.stored))
             .or(Some(default_effort));
+        // Switching models starts on the effort last picked for the new model.
+        let remembered_choice = self
+            .remembered_effort(&preset.model)
+            .filter(|effort| choices.iter().any(|choice| choice.stored == Some(*effort)));
 
         let model_slug = preset.model.to_string();
         let is_current_model = self.current_model() == preset.model.as_str();
         let highlight_choice = if is_current_model {
             self.stored_collaboration_mode.reasoning_effort()
         } else {
-            default_choice
+            remembered_choice.or(default_choice)
         };
         let selection_choice = highlight_choice.or(default_choice);
         let initial_selected_idx = choices
@@ -3860,6 +5043,26 @@ impl ChatWidget {
         });
     }
 
+    /// Reasoning effort last picked for `model`, from `[model_preferences]`.
+    fn remembered_effort(&self, model: &str) -> Option<ReasoningEffortConfig> {
+        self.config
+            .model_preferences
+            .get(model)
+            .and_then(|preference| preference.reasoning_effort)
+    }
+
+    pub(crate) fn remember_model_effort(
+        &mut self,
+        model: &str,
+        effort: Option<ReasoningEffortConfig>,
+    ) {
+        self.config
+            .model_preferences
+            .entry(model.to_string())
+            .or_default()
+            .reasoning_effort = effort;
+    }
+
     fn reasoning_effort_label(effort: ReasoningEffortConfig) -> &'static str {
         match effort {
             ReasoningEffortConfig::None => "None",
@@ -4015,9 +5218,9 @@ impl ChatWidget {
 
         let footer_note = show_elevate_sandbox_hint.then(|| {
             vec![
-                "The non-elevated sandbox protects your files and prevents network access under most circumstances. However, it carries greater risk if prompt injected. To upgrade to the elevated sandbox, run ".dim(),
-                "/setup-elevated-sandbox".cyan(),
-                ".".dim(),
+                "The non-elevated sandbox protects your files and prevents network access under most circumstances. However, it carries greater risk if prompt injected. To upgrade to the elevated sandbox, run ".secondary(),
+                "/setup-elevated-sandbox".accent(),
+                ".".secondary(),
             ]
             .into()
         });
@@ -4056,22 +5259,74 @@ impl ChatWidget {
         sandbox: SandboxPolicy,
     ) -> Vec<SelectionAction> {
         vec![Box::new(move |tx| {
-            let sandbox_clone = sandbox.clone();
-            tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
-                cwd: None,
-                approval_policy: Some(approval),
-                sandbox_policy: Some(sandbox_clone.clone()),
-                model: None,
-                effort: None,
-                summary: None,
-                collaboration_mode: None,
-                personality: None,
-            }));
-            tx.send(AppEvent::UpdateAskForApprovalPolicy(approval));
-            tx.send(AppEvent::UpdateSandboxPolicy(sandbox_clone));
+            Self::send_approval_preset(tx, approval, sandbox.clone());
         })]
     }
 
+    fn send_approval_preset(tx: &AppEventSender, approval: AskForApproval, sandbox: SandboxPolicy) {
+        tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
+            cwd: None,
+            approval_policy: Some(approval),
+            sandbox_policy: Some(sandbox.clone()),
+            model: None,
+            effort: None,
+            summary: None,
+            collaboration_mode: None,
+            personality: None,
+        }));
+        tx.send(AppEvent::UpdateAskForApprovalPolicy(approval));
+        tx.send(AppEvent::UpdateSandboxPolicy(sandbox));
+    }
+
+    /// Toggle exploration mode: a read-only sandbox with on-request approvals,
+    /// entered and left with one keystroke. Leaving restores the policies that
+    /// were active before.
+    fn toggle_exploration_mode(&mut self) {
+        if let Some((approval, sandbox)) = self.exploration_restore.take() {
+            Self::send_approval_preset(&self.app_event_tx, approval, sandbox);
+            self.bottom_pane.set_exploration_mode(false);
+            self.add_info_message(
+                "Exploration mode off; previous permissions restored.".to_string(),
+                None,
+            );
+            return;
+        }
+        let previous = (
+            self.config.approval_policy.value(),
+            self.config.sandbox_policy.get().clone(),
+        );
+        // Update the local copy right away so the policy updates echoed back
+        // through the app do not look like an outside change.
+        if let Err(err) = self.set_sandbox_policy(SandboxPolicy::new_read_only_policy()) {
+            self.add_error_message(format!("Cannot enter exploration mode: {err}"));
+            return;
+        }
+        self.set_approval_policy(AskForApproval::OnRequest);
+        Self::send_approval_preset(
+            &self.app_event_tx,
+            AskForApproval::OnRequest,
+            SandboxPolicy::new_read_only_policy(),
+        );
+        self.exploration_restore = Some(previous);
+        self.bottom_pane.set_exploration_mode(true);
+        self.add_info_message(
+            "Exploration mode on: read-only sandbox, approvals on request.".to_string(),
+            Some("Press Ctrl+X to restore write access.".to_string()),
+        );
+    }
+
+    /// Exploration mode ends without restoring anything once permissions are
+    /// changed some other way (e.g. `/approvals`).
+    fn sync_exploration_mode(&mut self) {
+        if self.exploration_restore.is_some()
+            && (self.config.approval_policy.value() != AskForApproval::OnRequest
+                || !matches!(self.config.sandbox_policy.get(), SandboxPolicy::ReadOnly))
+        {
+            self.exploration_restore = None;
+            self.bottom_pane.set_exploration_mode(false);
+        }
+    }
+
     fn preset_matches_current(
         current_approval: AskForApproval,
         current_sandbox: &SandboxPolicy,
@@ -4137,7 +5392,7 @@ impl ChatWidget {
             "When Codex runs with full access, it can edit any file on your computer and run commands with network, without your approval. "
                 .into(),
             "Exercise caution when enabling full access. This significantly increases the risk of data loss, leaks, or unexpected behavior."
-                .fg(Color::Red),
+                .error(),
         ]);
         header_children.push(Box::new(title_line));
         header_children.push(Box::new(
@@ -4222,8 +5477,7 @@ impl ChatWidget {
             Line::from(vec![
                 "We couldn't complete the world-writable scan, so protections cannot be verified. "
                     .into(),
-                format!("The Windows sandbox cannot guarantee protection in {mode_label}.")
-                    .fg(Color::Red),
+                format!("The Windows sandbox cannot guarantee protection in {mode_label}.").error(),
             ])
         } else {
             Line::from(vec![
@@ -4618,6 +5872,7 @@ impl ChatWidget {
         if let Err(err) = self.config.approval_policy.set(policy) {
             tracing::warn!(%err, "failed to set approval_policy on chat config");
         }
+        self.sync_exploration_mode();
     }
 
     /// Set the sandbox policy in the widget's config copy.
@@ -4627,6 +5882,7 @@ impl ChatWidget {
             || codex_core::get_platform_sandbox().is_some();
 
         self.config.sandbox_policy.set(policy)?;
+        self.sync_exploration_mode();
 
         #[cfg(target_os = "windows")]
         if should_clear_downgrade {
@@ -4704,34 +5960,277 @@ impl ChatWidget {
                 .with_updates(Some(model.to_string()), None, None);
     }
 
-    pub(crate) fn current_model(&self) -> &str {
-        self.stored_collaboration_mode.model()
+    fn add_transcript_width_output(&mut self) {
+        let current = match self.config.max_transcript_width {
+            Some(width) => format!("Transcript width is limited to {width} columns."),
+            None => "Transcript uses the full terminal width.".to_string(),
+        };
+        self.add_info_message(
+            current,
+            Some(format!(
+                "Use /width <columns> (at least {MIN_TRANSCRIPT_WIDTH}) or /width off to change it."
+            )),
+        );
     }
 
-    #[allow(dead_code)] // Used in tests
-    pub(crate) fn stored_collaboration_mode(&self) -> &CollaborationMode {
-        &self.stored_collaboration_mode
+    fn set_transcript_width_from_args(&mut self, args: &str) {
+        let width = match args {
+            "off" | "0" => None,
+            _ => match args.parse::<u16>() {
+                Ok(width) if width >= MIN_TRANSCRIPT_WIDTH => Some(width),
+                _ => {
+                    self.add_error_message(format!(
+                        "Usage: /width <columns> (at least {MIN_TRANSCRIPT_WIDTH}) or /width off"
+                    ));
+                    return;
+                }
+            },
+        };
+        self.config.max_transcript_width = width;
+        self.app_event_tx.send(AppEvent::UpdateMaxTranscriptWidth(width));
+        self.add_transcript_width_output();
     }
 
-    #[cfg(test)]
-    pub(crate) fn current_reasoning_effort(&self) -> Option<ReasoningEffortConfig> {
-        self.stored_collaboration_mode.reasoning_effort()
+    fn add_watch_output(&mut self) {
+        if self.file_watches.is_empty() {
+            self.add_info_message(
+                "No active watches.".to_string(),
+                Some(
+                    "Use /watch <glob> <prompt> to send a prompt whenever matching files change."
+                        .to_string(),
+                ),
+            );
+            return;
+        }
+        let patterns = self
+            .file_watches
+            .iter()
+            .map(|watch| format!("`{}`", watch.pattern))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.add_info_message(
+            format!("Watching {patterns}."),
+            Some("Use /watch clear to stop.".to_string()),
+        );
     }
 
-    fn is_session_configured(&self) -> bool {
-        self.thread_id.is_some()
+    fn add_keys_output(&mut self) {
+        let mut lines: Vec<Line<'static>> = vec!["/keys".brand().into(), "".into()];
+        for (action, binding) in self.keymap.bindings() {
+            let chord = binding.map_or_else(|| "unbound".to_string(), |b| key_label(&b));
+            lines.push(Line::from(vec![
+                format!("  {:<22}", action.as_ref()).into(),
+                format!("{chord:<16}").accent(),
+                action.description().secondary(),
+            ]));
+        }
+        for warning in self.keymap.warnings() {
+            lines.push(Line::from(format!("  ⚠ {warning}").error()));
+        }
+        lines.push("".into());
+        lines.push("  Remap actions under [tui.keys] in config.toml.".secondary().into());
+        self.add_plain_history_lines(lines);
     }
 
-    fn collaboration_modes_enabled(&self) -> bool {
-        self.config.features.enabled(Feature::CollaborationModes)
+    fn handle_storage_command(&mut self, args: &str) {
+        let mut words: Vec<&str> = args.split_whitespace().collect();
+        let dry_run = words.iter().any(|word| *word == "--dry-run");
+        words.retain(|word| *word != "--dry-run");
+        let days = match words.as_slice() {
+            ["clean", days] => days.parse::<i64>().ok().filter(|days| *days >= 0),
+            _ => None,
+        };
+        let Some(days) = days else {
+            self.add_error_message("Usage: /storage clean <days> [--dry-run]".to_string());
+            return;
+        };
+        self.submit_op(Op::CleanupStorage {
+            options: StorageCleanupOptions {
+                delete_rollouts_older_than_days: Some(days),
+                prune_checkpoints_older_than_days: Some(days),
+                vacuum_memory: true,
+                gc_attachments: true,
+                dry_run,
+            },
+        });
     }
 
-    fn model_display_name(&self) -> &str {
-        let model = self.current_model();
-        if model.is_empty() {
-            DEFAULT_MODEL_DISPLAY_NAME
-        } else {
-            model
+    fn handle_watch_command(&mut self, args: &str) {
+        if matches!(args, "clear" | "off") {
+            self.file_watches.clear();
+            self.refresh_active_watches();
+            self.add_info_message("Stopped all watches.".to_string(), None);
+            return;
+        }
+        let Some((pattern, prompt)) = args
+            .split_once(char::is_whitespace)
+            .map(|(pattern, prompt)| (pattern, prompt.trim()))
+            .filter(|(_, prompt)| !prompt.is_empty())
+        else {
+            self.add_error_message("Usage: /watch <glob> <prompt> or /watch clear".to_string());
+            return;
+        };
+        match FileWatch::start(
+            self.next_file_watch_id,
+            pattern.to_string(),
+            prompt.to_string(),
+            &self.config.cwd,
+            self.app_event_tx.clone(),
+        ) {
+            Ok(watch) => {
+                self.next_file_watch_id += 1;
+                self.file_watches.push(watch);
+                self.refresh_active_watches();
+                self.add_info_message(
+                    format!("Watching `{pattern}`."),
+                    Some(
+                        "The prompt is sent with the diff when matching files change while the agent is idle."
+                            .to_string(),
+                    ),
+                );
+            }
+            Err(err) => self.add_error_message(err),
+        }
+    }
+
+    /// Pins `path` so core sends its latest contents at the start of every
+    /// turn in which it changed.
+    fn pin_file(&mut self, path: &str) {
+        let resolved = self.config.cwd.join(path);
+        if self
+            .pinned_files
+            .iter()
+            .any(|pinned| self.config.cwd.join(pinned) == resolved)
+        {
+            self.add_info_message(format!("`{path}` is already pinned."), None);
+            return;
+        }
+        if !resolved.is_file() {
+            self.add_error_message(format!("Cannot pin `{path}`: not a file."));
+            return;
+        }
+        self.pinned_files.push(path.to_string());
+        self.submit_op(Op::PinFile {
+            path: resolved,
+            pinned: true,
+        });
+        self.bottom_pane.set_pinned_files(self.pinned_files.clone());
+        self.add_info_message(
+            format!("Pinned `{path}`."),
+            Some(format!(
+                "Its latest contents are sent at the start of each turn after it changes. Use /unpin {path} to stop."
+            )),
+        );
+    }
+
+    fn unpin_file(&mut self, path: &str) {
+        let resolved = self.config.cwd.join(path);
+        let Some(index) = self
+            .pinned_files
+            .iter()
+            .position(|pinned| self.config.cwd.join(pinned) == resolved)
+        else {
+            self.add_error_message(format!("`{path}` is not pinned."));
+            return;
+        };
+        let path = self.pinned_files.remove(index);
+        self.submit_op(Op::PinFile {
+            path: resolved,
+            pinned: false,
+        });
+        self.bottom_pane.set_pinned_files(self.pinned_files.clone());
+        self.add_info_message(format!("Unpinned `{path}`."), None);
+    }
+
+    fn add_pinned_files_output(&mut self) {
+        if self.pinned_files.is_empty() {
+            self.add_info_message(
+                "No pinned files.".to_string(),
+                Some(
+                    "Use /pin <file> to send a file's latest contents at the start of every turn."
+                        .to_string(),
+                ),
+            );
+            return;
+        }
+        let listed = self
+            .pinned_files
+            .iter()
+            .map(|path| format!("`{path}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.add_info_message(
+            format!("Pinned files: {listed}"),
+            Some("Use /unpin <file> to stop sending one.".to_string()),
+        );
+    }
+
+    fn refresh_active_watches(&mut self) {
+        let patterns = self
+            .file_watches
+            .iter()
+            .map(|watch| watch.pattern.clone())
+            .collect();
+        self.bottom_pane.set_active_watches(patterns);
+    }
+
+    /// Queues a triggered watch's prompt unless the change happened while a
+    /// turn was running or repeats the last prompt from the same watch.
+    pub(crate) fn on_file_watch_triggered(
+        &mut self,
+        id: u64,
+        first_change_at: Instant,
+        file_count: usize,
+        message: String,
+    ) {
+        let during_turn = self.bottom_pane.is_task_running()
+            || self
+                .last_turn_finished_at
+                .is_some_and(|finished| first_change_at <= finished);
+        let Some(watch) = self.file_watches.iter_mut().find(|watch| watch.id == id) else {
+            return;
+        };
+        if during_turn || watch.last_message.as_ref() == Some(&message) {
+            return;
+        }
+        watch.last_message = Some(message.clone());
+        let pattern = watch.pattern.clone();
+        let noun = if file_count == 1 { "file" } else { "files" };
+        self.add_info_message(
+            format!("Watch `{pattern}` triggered by {file_count} changed {noun}."),
+            None,
+        );
+        self.queue_user_message(UserMessage::from(message));
+    }
+
+    pub(crate) fn current_model(&self) -> &str {
+        self.stored_collaboration_mode.model()
+    }
+
+    #[allow(dead_code)] // Used in tests
+    pub(crate) fn stored_collaboration_mode(&self) -> &CollaborationMode {
+        &self.stored_collaboration_mode
+    }
+
+    #[cfg(test)]
+    pub(crate) fn current_reasoning_effort(&self) -> Option<ReasoningEffortConfig> {
+        self.stored_collaboration_mode.reasoning_effort()
+    }
+
+    fn is_session_configured(&self) -> bool {
+        self.thread_id.is_some()
+    }
+
+    fn collaboration_modes_enabled(&self) -> bool {
+        self.config.features.enabled(Feature::CollaborationModes)
+    }
+
+    fn model_display_name(&self) -> &str {
+        let model = self.current_model();
+        if model.is_empty() {
+            DEFAULT_MODEL_DISPLAY_NAME
+        } else {
+            model
         }
     }
 
@@ -4836,6 +6335,197 @@ impl ChatWidget {
         }
     }
 
+    pub(crate) fn set_plan_mode(&mut self, enabled: bool) {
+        self.plan_mode_enabled = enabled;
+        if !enabled && self.plan_workflow.is_some() {
+            self.cancel_plan_workflow();
+        }
+    }
+
+    /// Applies settings reloaded from `config.toml`.
+    pub(crate) fn apply_reloaded_config(&mut self, keys: &[String], reloaded: &Config) {
+        self.config.apply_reloaded(keys, reloaded);
+        if keys.iter().any(|key| key == "tui.keys") {
+            self.keymap = Keymap::new(&self.config.tui_keys);
+            self.show_keymap_warnings();
+        }
+        self.request_redraw();
+    }
+
+    pub(crate) fn keymap(&self) -> &Keymap {
+        &self.keymap
+    }
+
+    /// Reports `[tui.keys]` entries that were ignored.
+    pub(crate) fn show_keymap_warnings(&mut self) {
+        for warning in self.keymap.warnings().to_vec() {
+            self.add_to_history(history_cell::new_warning_event(warning));
+        }
+        self.request_redraw();
+    }
+
+    pub(crate) fn set_tui_theme(&mut self, name: String) {
+        self.config.tui_theme = Some(name);
+    }
+
+    /// Settings that can be changed without editing `config.toml`.
+    pub(crate) fn open_settings_popup(&mut self) {
+        let show_rate_limits = self.config.show_rate_limits_in_footer;
+        let items = vec![
+            SelectionItem {
+                name: "Compaction policies".to_string(),
+                description: Some(compaction_policies_label(&self.config.compaction.policies)),
+                actions: vec![Box::new(|tx: &AppEventSender| {
+                    tx.send(AppEvent::OpenCompactionPopup);
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Rate limits in footer".to_string(),
+                description: Some(if show_rate_limits { "shown" } else { "hidden" }.to_string()),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::UpdateShowRateLimitsInFooter(!show_rate_limits));
+                    tx.send(AppEvent::PersistShowRateLimitsInFooter(!show_rate_limits));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Settings".to_string()),
+            subtitle: Some("Changes are saved to config.toml.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Turns the `compaction.policies` on or off one at a time.
+    pub(crate) fn open_compaction_popup(&mut self) {
+        let current = self.config.compaction.policies.clone();
+        let items = [
+            (
+                CompactionPolicy::KeepPinnedMessages,
+                "carry messages pinned with /pin over every compaction",
+            ),
+            (
+                CompactionPolicy::DropOldestToolsFirst,
+                "drop old tool calls before summarizing",
+            ),
+            (
+                CompactionPolicy::SummarizeReasoning,
+                "replace old reasoning with its summary before summarizing",
+            ),
+        ]
+        .into_iter()
+        .map(|(policy, description)| {
+            let enabled = current.contains(&policy);
+            let mut policies = current.clone();
+            if enabled {
+                policies.retain(|other| *other != policy);
+            } else {
+                policies.push(policy);
+            }
+            SelectionItem {
+                name: format!("[{}] {}", if enabled { "x" } else { " " }, policy.as_str()),
+                description: Some(description.to_string()),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::SetCompactionPolicies(policies.clone()));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            }
+        })
+        .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Compaction policies".to_string()),
+            subtitle: Some(
+                "Automatic compaction skips the summary when the drop and condense policies free enough room."
+                    .to_string(),
+            ),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn set_compaction_policies(&mut self, policies: Vec<CompactionPolicy>) {
+        let label = compaction_policies_label(&policies);
+        self.config.compaction.policies = policies;
+        self.add_info_message(format!("Compaction policies: {label}"), None);
+    }
+
+    pub(crate) fn set_plan_detail(&mut self, detail: PlanDetailPreference) {
+        self.config.plan_detail = detail;
+    }
+
+    pub(crate) fn plan_review_pending(&self) -> bool {
+        self.plan_workflow
+            .as_ref()
+            .is_some_and(|workflow| workflow.ready.is_some())
+    }
+
+    /// Approve the plan under review; core replays the request with the plan.
+    pub(crate) fn execute_plan_request(&mut self) {
+        if !self.plan_review_pending() {
+            return;
+        }
+        self.plan_workflow = None;
+        self.plan_feedback_pending = false;
+        self.submit_op(Op::ApprovePlan);
+    }
+
+    /// Route the next submission to core as feedback on the current plan.
+    pub(crate) fn prepare_plan_feedback(&mut self) {
+        if !self.plan_review_pending() {
+            return;
+        }
+        self.plan_feedback_pending = true;
+        self.add_info_message(
+            "Describe what should change in the plan.".to_string(),
+            Some("Your next message is sent as plan feedback.".to_string()),
+        );
+    }
+
+    pub(crate) fn cancel_plan_workflow(&mut self) {
+        if self.plan_workflow.take().is_none() {
+            return;
+        }
+        self.plan_feedback_pending = false;
+        self.submit_op(Op::CancelPlan);
+    }
+
+    /// Pick the plan op for a submission, if plan mode should handle it.
+    fn plan_op_for_submission(
+        &mut self,
+        text: &str,
+        local_images: &[LocalImageAttachment],
+    ) -> Option<Op> {
+        if !self.plan_mode_enabled || text.is_empty() || !local_images.is_empty() {
+            return None;
+        }
+        if self.plan_feedback_pending {
+            self.plan_feedback_pending = false;
+            if let Some(workflow) = self.plan_workflow.as_mut() {
+                workflow.ready = None;
+            }
+            return Some(Op::RevisePlan {
+                feedback: text.to_string(),
+            });
+        }
+        if self.plan_workflow.is_some() {
+            return None;
+        }
+        self.plan_workflow = Some(PlanWorkflowStatus { ready: None });
+        Some(Op::StartPlan {
+            request: text.to_string(),
+            detail: Some(self.config.plan_detail),
+        })
+    }
+
     pub(crate) fn add_info_message(&mut self, message: String, hint: Option<String>) {
         self.add_to_history(history_cell::new_info_event(message, hint));
         self.request_redraw();
@@ -4856,6 +6546,7 @@ impl ChatWidget {
             self.add_to_history(history_cell::empty_mcp_output());
         } else {
             self.submit_op(Op::ListMcpTools);
+            self.submit_op(Op::ListMcpUnresolvedSecrets);
         }
     }
 
@@ -4911,6 +6602,22 @@ impl ChatWidget {
         }
     }
 
+    /// Shows or hides the turn diff pane on Ctrl+D.
+    ///
+    /// Only applies when the composer is empty, no modal/popup is active, and the pane is
+    /// already open or the current turn has changes; otherwise Ctrl+D keeps its quit behavior.
+    fn toggle_turn_diff_pane(&mut self) -> bool {
+        if !self.bottom_pane.composer_is_empty()
+            || !self.bottom_pane.no_modal_or_popup_active()
+            || !(self.turn_diff_pane.is_visible() || self.turn_diff_pane.has_changes())
+        {
+            return false;
+        }
+        self.turn_diff_pane.toggle_visible();
+        self.request_redraw();
+        true
+    }
+
     /// Handles a Ctrl+D press at the chat-widget layer.
     ///
     /// Ctrl-D only participates in quit when the composer is empty and no modal/popup is active.
@@ -4972,6 +6679,15 @@ impl ChatWidget {
         self.bottom_pane.composer_is_empty()
     }
 
+    pub(crate) fn set_doctor_findings(&mut self, findings: Vec<DoctorFinding>) {
+        self.bottom_pane.set_doctor_findings(findings);
+    }
+
+    /// Hide the startup self-check banner. Returns false when none was shown.
+    pub(crate) fn dismiss_doctor_banner(&mut self) -> bool {
+        self.bottom_pane.dismiss_doctor_banner()
+    }
+
     pub(crate) fn submit_user_message_with_mode(
         &mut self,
         text: String,
@@ -5034,6 +6750,21 @@ impl ChatWidget {
         ));
     }
 
+    fn on_mcp_unresolved_secrets(&mut self, ev: McpUnresolvedSecretsResponseEvent) {
+        for entry in ev.servers {
+            let secrets = entry
+                .secrets
+                .iter()
+                .map(|name| format!("secret://{name}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            self.on_warning(format!(
+                "MCP server `{}` is missing secrets: {secrets}",
+                entry.server
+            ));
+        }
+    }
+
     fn on_list_custom_prompts(&mut self, ev: ListCustomPromptsResponseEvent) {
         let len = ev.custom_prompts.len();
         debug!("received {len} custom prompts");
@@ -5045,6 +6776,213 @@ impl ChatWidget {
         self.set_skills_from_response(&ev);
     }
 
+    /// Lists this conversation's assistant messages, most recent first, so the
+    /// chat can be branched into a new session right after one of them.
+    pub(crate) fn open_fork_popup(&mut self) {
+        if self.agent_messages.is_empty() {
+            self.add_info_message(
+                "Nothing to fork yet: there are no assistant messages in this session.".to_string(),
+                None,
+            );
+            return;
+        }
+
+        let items = self
+            .agent_messages
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(at_item, message)| SelectionItem {
+                name: format!("#{} {}", at_item + 1, agent_message_preview(message)),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::CodexOp(Op::ForkConversation { at_item }));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Fork from an assistant message".into()),
+            subtitle: Some("The new session keeps the conversation up to that answer.".into()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Lists this conversation's assistant messages, most recent first, so one
+    /// can be quoted into the composer for a follow-up.
+    pub(crate) fn open_quote_popup(&mut self) {
+        if self.agent_messages.is_empty() {
+            self.add_info_message(
+                "Nothing to quote yet: there are no assistant messages in this session."
+                    .to_string(),
+                None,
+            );
+            return;
+        }
+
+        let items = self
+            .agent_messages
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(at_item, message)| SelectionItem {
+                name: format!("#{} {}", at_item + 1, agent_message_preview(message)),
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::QuoteAgentMessage(at_item));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Quote an assistant message".into()),
+            subtitle: Some("The excerpt is added to your next message.".into()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    /// Lists this conversation's assistant messages, most recent first, to pin
+    /// or unpin. Pinned messages are kept verbatim through compaction.
+    pub(crate) fn open_pin_popup(&mut self) {
+        if self.agent_messages.is_empty() {
+            self.add_info_message(
+                "Nothing to pin yet: there are no assistant messages in this session.".to_string(),
+                None,
+            );
+            return;
+        }
+
+        let items = self
+            .agent_messages
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(at_item, message)| {
+                let pinned = self.pinned_messages.contains(&at_item);
+                SelectionItem {
+                    name: format!("#{} {}", at_item + 1, agent_message_preview(message)),
+                    description: pinned.then(|| "pinned".to_string()),
+                    actions: vec![Box::new(move |tx: &AppEventSender| {
+                        tx.send(AppEvent::PinAgentMessage {
+                            at_item,
+                            pinned: !pinned,
+                        });
+                    })],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Pin an assistant message".into()),
+            subtitle: Some(
+                "Compaction keeps pinned messages; select a pinned one to unpin it.".into(),
+            ),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn pin_agent_message(&mut self, at_item: usize, pinned: bool) {
+        if pinned {
+            self.pinned_messages.insert(at_item);
+        } else {
+            self.pinned_messages.remove(&at_item);
+        }
+        self.submit_op(Op::PinHistoryItem { at_item, pinned });
+        let action = if pinned { "Pinned" } else { "Unpinned" };
+        self.add_info_message(
+            format!("{action} assistant message #{}.", at_item + 1),
+            None,
+        );
+    }
+
+    /// Inserts an excerpt of assistant message `at_item` into the composer as
+    /// `> ` lines, tagged with its `[#n]` anchor.
+    pub(crate) fn quote_agent_message(&mut self, at_item: usize) {
+        let Some(message) = self.agent_messages.get(at_item) else {
+            return;
+        };
+        let mut lines: Vec<&str> = message
+            .lines()
+            .map(str::trim_end)
+            .skip_while(|line| line.is_empty())
+            .collect();
+        let truncated = lines.len() > QUOTE_MAX_LINES;
+        lines.truncate(QUOTE_MAX_LINES);
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+
+        let mut quote = format!("> {}", quote_anchor(at_item));
+        for (idx, line) in lines.iter().enumerate() {
+            if idx > 0 {
+                quote.push_str("\n>");
+            }
+            if !line.is_empty() {
+                quote.push(' ');
+                quote.push_str(line);
+            }
+        }
+        if truncated {
+            quote.push_str("\n> …");
+        }
+        quote.push_str("\n\n");
+
+        self.pending_quote = Some(at_item);
+        self.bottom_pane.insert_str(&quote);
+        self.request_redraw();
+    }
+
+    /// Offers to rerun the last message as-is or with another reasoning effort
+    /// supported by the current model.
+    pub(crate) fn open_regenerate_popup(&mut self) {
+        if self.agent_messages.is_empty() {
+            self.add_info_message("Nothing to regenerate yet.".to_string(), None);
+            return;
+        }
+
+        let regenerate = |name: String, effort: Option<ReasoningEffortConfig>| SelectionItem {
+            name,
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::CodexOp(Op::RegenerateLast {
+                    model: None,
+                    effort,
+                }));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        };
+        let mut items = vec![regenerate("Same model and effort".to_string(), None)];
+        let current_model = self.current_model().to_string();
+        if let Ok(models) = self.models_manager.try_list_models(&self.config)
+            && let Some(preset) = models.iter().find(|preset| preset.model == current_model)
+        {
+            items.extend(preset.supported_reasoning_efforts.iter().map(|option| {
+                regenerate(
+                    format!("{} effort", Self::reasoning_effort_label(option.effort)),
+                    Some(option.effort),
+                )
+            }));
+        }
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Regenerate the last answer".into()),
+            subtitle: Some("The current answer is kept as a previous attempt.".into()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
     pub(crate) fn open_review_popup(&mut self) {
         let mut items: Vec<SelectionItem> = Vec::new();
 
@@ -5271,7 +7209,7 @@ impl ChatWidget {
             0,
             RenderableItem::Borrowed(&self.bottom_pane).inset(Insets::tlbr(1, 0, 0, 0)),
         );
-        RenderableItem::Owned(Box::new(flex))
+        self.turn_diff_pane.beside(flex.max_width(self.config.max_transcript_width))
     }
 }
 
@@ -5284,7 +7222,8 @@ impl Drop for ChatWidget {
 impl Renderable for ChatWidget {
     fn render(&self, area: Rect, buf: &mut Buffer) {
         self.as_renderable().render(area, buf);
-        self.last_rendered_width.set(Some(area.width as usize));
+        let (_, width) = centered_columns(area.width, self.config.max_transcript_width);
+        self.last_rendered_width.set(Some(usize::from(width)));
     }
 
     fn desired_height(&self, width: u16) -> u16 {
@@ -5301,6 +7240,7 @@ enum Notification {
     ExecApprovalRequested { command: String },
     EditApprovalRequested { cwd: PathBuf, changes: Vec<PathBuf> },
     ElicitationRequested { server_name: String },
+    Error { message: String },
 }
 
 impl Notification {
@@ -5327,6 +7267,9 @@ impl Notification {
             Notification::ElicitationRequested { server_name } => {
                 format!("Approval requested by {server_name}")
             }
+            Notification::Error { message } => {
+                format!("Error: {}", truncate_text(message, 60))
+            }
         }
     }
 
@@ -5336,14 +7279,12 @@ impl Notification {
             Notification::ExecApprovalRequested { .. }
             | Notification::EditApprovalRequested { .. }
             | Notification::ElicitationRequested { .. } => "approval-requested",
+            Notification::Error { .. } => "error",
         }
     }
 
     fn allowed_for(&self, settings: &Notifications) -> bool {
-        match settings {
-            Notifications::Enabled(enabled) => *enabled,
-            Notifications::Custom(allowed) => allowed.iter().any(|a| a == self.type_name()),
-        }
+        settings.allows(self.type_name())
     }
 
     fn agent_turn_preview(response: &str) -> Option<String> {
@@ -5364,6 +7305,72 @@ impl Notification {
 }
 
 const AGENT_NOTIFICATION_PREVIEW_GRAPHEMES: usize = 200;
+const FORK_PREVIEW_GRAPHEMES: usize = 60;
+const QUOTE_MAX_LINES: usize = 6;
+
+fn compaction_policies_label(policies: &[CompactionPolicy]) -> String {
+    if policies.is_empty() {
+        return "summarize only".to_string();
+    }
+    policies
+        .iter()
+        .copied()
+        .map(CompactionPolicy::as_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Pager text for `/subagent-review`: conflicting files first, then each
+/// agent's diff.
+fn subagent_review_text(report: &SubagentReportEvent) -> String {
+    let mut text = String::new();
+    for conflict in &report.conflicts {
+        let agents = conflict
+            .agent_indices
+            .iter()
+            .map(|idx| (idx + 1).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        text.push_str(&format!(
+            "CONFLICT {} edited by agents {agents}\n",
+            conflict.path.display()
+        ));
+    }
+    for agent in &report.agents {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(&format!(
+            "=== Agent {} — {} ({}) ===\n",
+            agent.agent_index + 1,
+            agent.task,
+            agent.status
+        ));
+        match agent.unified_diff.as_deref().map(str::trim_end) {
+            Some(diff) if !diff.is_empty() => {
+                text.push_str(diff);
+                text.push('\n');
+            }
+            _ => text.push_str("(no changes)\n"),
+        }
+    }
+    text
+}
+
+/// First non-empty line of an assistant message, shortened for the `/fork` list.
+fn agent_message_preview(message: &str) -> String {
+    let line = message
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or("(empty message)");
+    truncate_text(line, FORK_PREVIEW_GRAPHEMES)
+}
+
+/// Marker naming the quoted assistant message in the composer, e.g. `[#2]`.
+fn quote_anchor(at_item: usize) -> String {
+    format!("[#{}]", at_item + 1)
+}
 
 const PLACEHOLDERS: [&str; 8] = [
     "Explain this codebase",
@@ -7,6 +7,7 @@ use codex_core::config::Config;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::replay::RolloutReplay;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::mpsc::unbounded_channel;
 
@@ -106,3 +107,35 @@ pub(crate) fn spawn_agent_from_existing(
 
     codex_op_tx
 }
+
+/// Spawn playback of a recorded rollout. Recorded events are forwarded at
+/// `speed` times their original pacing; nothing reaches a model, so submitted
+/// Ops are dropped.
+pub(crate) fn spawn_replay(
+    replay: RolloutReplay,
+    speed: f64,
+    app_event_tx: AppEventSender,
+) -> UnboundedSender<Op> {
+    let (codex_op_tx, mut codex_op_rx) = unbounded_channel::<Op>();
+
+    tokio::spawn(async move {
+        while let Some(op) = codex_op_rx.recv().await {
+            tracing::debug!("ignoring op during replay: {op:?}");
+        }
+    });
+
+    tokio::spawn(async move {
+        app_event_tx.send(AppEvent::CodexEvent(Event {
+            id: "".to_string(),
+            msg: EventMsg::SessionConfigured(replay.session_configured.clone()),
+        }));
+        replay
+            .play(speed, |event| {
+                app_event_tx.send(AppEvent::CodexEvent(event));
+                !app_event_tx.app_event_tx.is_closed()
+            })
+            .await;
+    });
+
+    codex_op_tx
+}
@@ -1,10 +1,17 @@
+use codex_core::protocol_config_types::ReasoningEffort as ReasoningEffortConfig;
+
 pub(crate) struct SessionHeader {
     model: String,
+    #[allow(dead_code)] // Reserved for rendering alongside the model name
+    reasoning_effort: Option<ReasoningEffortConfig>,
 }
 
 impl SessionHeader {
     pub(crate) fn new(model: String) -> Self {
-        Self { model }
+        Self {
+            model,
+            reasoning_effort: None,
+        }
     }
 
     /// Updates the header's model text.
@@ -13,4 +20,17 @@ impl SessionHeader {
             self.model = model.to_string();
         }
     }
+
+    /// Updates the header with the model and reasoning effort reported when a
+    /// turn starts. An empty model (older servers) keeps the current text.
+    pub(crate) fn set_turn_settings(
+        &mut self,
+        model: &str,
+        reasoning_effort: Option<ReasoningEffortConfig>,
+    ) {
+        if !model.is_empty() {
+            self.set_model(model);
+        }
+        self.reasoning_effort = reasoning_effort;
+    }
 }
@@ -1,5 +1,8 @@
 use super::*;
 use crate::app_event::AppEvent;
+use crate::app_event::PaletteAction;
+use crate::app_event::ReviewFindingAction;
+use crate::app_event::ReviewFindingStatus;
 use crate::app_event_sender::AppEventSender;
 use crate::test_backend::VT100Backend;
 use crate::tui::FrameRequester;
@@ -26,12 +29,17 @@ use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
+use codex_core::protocol::ExecCommandOutputDeltaEvent;
 use codex_core::protocol::ExecCommandSource;
+use codex_core::protocol::ExecOutputStream;
 use codex_core::protocol::ExitedReviewModeEvent;
 use codex_core::protocol::FileChange;
+use codex_core::protocol::MessageVariant;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::PlanReadyEvent;
+use codex_core::protocol::ProjectDocEntry;
 use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::ReviewCodeLocation;
 use codex_core::protocol::ReviewFinding;
@@ -39,6 +47,8 @@ use codex_core::protocol::ReviewLineRange;
 use codex_core::protocol::ReviewOutputEvent;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::StreamErrorEvent;
+use codex_core::protocol::SubagentTaskStatus;
+use codex_core::protocol::SubagentTaskUpdateEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TaskStartedEvent;
 use codex_core::protocol::TokenCountEvent;
@@ -223,6 +233,47 @@ fn exited_review_mode_emits_results_and_finishes() {
     assert!(!chat.is_review_mode);
 }
 
+/// Fixing a review finding queues a prompt about it and the triage state
+/// stays with the widget.
+#[test]
+fn review_finding_fix_now_queues_a_targeted_prompt() {
+    let (mut chat, _rx, _ops) = make_chatwidget_manual();
+    let finding = |title: &str| ReviewFinding {
+        title: title.to_string(),
+        body: "Details".to_string(),
+        confidence_score: 0.9,
+        priority: 1,
+        code_location: ReviewCodeLocation {
+            absolute_file_path: PathBuf::from("/repo/src/lib.rs"),
+            line_range: ReviewLineRange { start: 10, end: 12 },
+        },
+    };
+    chat.handle_codex_event(Event {
+        id: "review-end".into(),
+        msg: EventMsg::ExitedReviewMode(ExitedReviewModeEvent {
+            review_output: Some(ReviewOutputEvent {
+                findings: vec![finding("[P1] Fix bug"), finding("[P3] Nit")],
+                ..Default::default()
+            }),
+        }),
+    });
+
+    chat.apply_review_finding_action(0, ReviewFindingAction::FixNow);
+    chat.apply_review_finding_action(1, ReviewFindingAction::Ignore);
+
+    let statuses: Vec<_> = chat
+        .review_findings
+        .iter()
+        .map(|(_, status)| *status)
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![ReviewFindingStatus::FixQueued, ReviewFindingStatus::Ignored]
+    );
+    let queued = &chat.queued_user_messages.back().expect("queued fix").text;
+    assert!(queued.contains("[P1] Fix bug\n/repo/src/lib.rs:10-12"));
+}
+
 /// Exiting review restores the pre-review context window indicator.
 #[test]
 fn review_restores_context_window_indicator() {
@@ -379,6 +430,7 @@ fn make_chatwidget_manual() -> (
         queued_user_messages: VecDeque::new(),
         suppress_session_configured_redraw: false,
         pending_notification: None,
+        last_mcp_image: None,
         is_review_mode: false,
         pre_review_token_info: None,
         needs_final_message_separator: false,
@@ -386,9 +438,22 @@ fn make_chatwidget_manual() -> (
         feedback: codex_feedback::CodexFeedback::new(),
         current_rollout_path: None,
         recent_checkpoints: VecDeque::new(),
+        recent_shell_commands: VecDeque::new(),
+        agent_messages: Vec::new(),
+        pinned_messages: HashSet::new(),
+        last_subagent_report: None,
+        pending_quote: None,
+        review_findings: Vec::new(),
         plan_mode_enabled: false,
+        exploration_restore: None,
+        keymap: Keymap::default(),
         plan_workflow: None,
         plan_feedback_pending: false,
+        turn_diff_pane: crate::turn_diff_pane::TurnDiffPane::default(),
+        turn_stats: None,
+        file_watches: Vec::new(),
+        next_file_watch_id: 0,
+        last_turn_finished_at: None,
         default_placeholder: "Ask Kaioken anything".to_string(),
     };
     (widget, rx, op_rx)
@@ -602,6 +667,7 @@ fn exec_approval_emits_proposed_command_and_decision_history() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         risk: None,
+        network_destinations: vec![],
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -646,6 +712,7 @@ fn exec_approval_decision_truncates_multiline_and_long_commands() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         risk: None,
+        network_destinations: vec![],
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -696,6 +763,7 @@ fn exec_approval_decision_truncates_multiline_and_long_commands() {
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
         reason: None,
         risk: None,
+        network_destinations: vec![],
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -739,6 +807,7 @@ fn begin_exec_with_source(
         parsed_cmd,
         source,
         interaction_input,
+        approval_rule: None,
     };
     chat.handle_codex_event(Event {
         id: call_id.to_string(),
@@ -772,6 +841,7 @@ fn end_exec(
         source,
         interaction_input,
         process_id,
+        ..
     } = begin_event;
     chat.handle_codex_event(Event {
         id: call_id.clone(),
@@ -803,6 +873,48 @@ fn active_blob(chat: &ChatWidget) -> String {
     lines_to_single_string(&lines)
 }
 
+#[test]
+fn agent_exec_streams_output_and_opens_follow_view() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+    let begin = begin_exec(&mut chat, "call-stream", "cargo test");
+    let chunk: String = (0..20).map(|i| format!("test {i} ... ok\n")).collect();
+    chat.handle_codex_event(Event {
+        id: "call-stream".to_string(),
+        msg: EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
+            call_id: "call-stream".to_string(),
+            stream: ExecOutputStream::Stdout,
+            chunk: chunk.into_bytes(),
+        }),
+    });
+
+    let blob = active_blob(&chat);
+    assert!(blob.contains("test 19 ... ok"), "expected tail: {blob}");
+    assert!(
+        !blob.contains("test 0 ... ok"),
+        "expected head hidden: {blob}"
+    );
+    assert!(
+        blob.contains("+12 lines"),
+        "expected truncation marker: {blob}"
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+    let mut opened = None;
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::OpenExecOutput {
+            call_id, output, ..
+        } = ev
+        {
+            opened = Some((call_id, output));
+        }
+    }
+    let (call_id, output) = opened.expect("follow view requested");
+    assert_eq!(call_id, "call-stream");
+    assert!(output.text().starts_with("test 0 ... ok"));
+
+    end_exec(&mut chat, begin, "", "", 0);
+}
+
 #[test]
 fn empty_enter_during_task_does_not_queue() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
@@ -1059,6 +1171,98 @@ fn review_popup_custom_prompt_action_sends_event() {
     assert!(found, "expected OpenReviewCustomPrompt event to be sent");
 }
 
+#[test]
+fn width_command_updates_transcript_width() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+
+    chat.dispatch_command_with_args(SlashCommand::Width, "100".to_string());
+    assert_eq!(chat.config.max_transcript_width, Some(100));
+    let mut widths = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::UpdateMaxTranscriptWidth(width) = ev {
+            widths.push(width);
+        }
+    }
+    assert_eq!(widths, vec![Some(100)]);
+
+    // Too narrow widths are rejected and leave the setting alone.
+    chat.dispatch_command_with_args(SlashCommand::Width, "10".to_string());
+    assert_eq!(chat.config.max_transcript_width, Some(100));
+
+    chat.dispatch_command_with_args(SlashCommand::Width, "off".to_string());
+    assert_eq!(chat.config.max_transcript_width, None);
+}
+
+#[test]
+fn theme_command_with_name_requests_switch() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+
+    chat.dispatch_command_with_args(SlashCommand::Theme, " light ".to_string());
+    let mut themes = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::SetTheme(name) = ev {
+            themes.push(name);
+        }
+    }
+    assert_eq!(themes, vec!["light".to_string()]);
+}
+
+#[tokio::test]
+async fn watch_trigger_queues_prompt_only_when_idle() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
+    let tempdir = tempdir().unwrap();
+    chat.config.cwd = tempdir.path().to_path_buf();
+
+    chat.dispatch_command_with_args(SlashCommand::Watch, "*.rs run the tests".to_string());
+    assert_eq!(chat.file_watches.len(), 1);
+
+    // Changes seen while a turn runs come from the agent and are ignored.
+    chat.bottom_pane.set_task_running(true);
+    chat.on_file_watch_triggered(0, Instant::now(), 1, "run the tests".to_string());
+    chat.bottom_pane.set_task_running(false);
+    let changed_during_turn = Instant::now();
+    chat.last_turn_finished_at = Some(Instant::now());
+    chat.on_file_watch_triggered(0, changed_during_turn, 1, "run the tests".to_string());
+    assert!(chat.queued_user_messages.is_empty());
+
+    chat.on_file_watch_triggered(0, Instant::now(), 1, "run the tests".to_string());
+    assert_eq!(chat.queued_user_messages.len(), 1);
+
+    // The same prompt and diff are not sent twice.
+    chat.on_file_watch_triggered(0, Instant::now(), 1, "run the tests".to_string());
+    assert_eq!(chat.queued_user_messages.len(), 1);
+
+    chat.dispatch_command_with_args(SlashCommand::Watch, "clear".to_string());
+    assert!(chat.file_watches.is_empty());
+}
+
+#[test]
+fn slash_pin_with_a_path_pins_the_file_until_unpinned() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
+    let tempdir = tempdir().unwrap();
+    let spec = tempdir.path().join("spec.md");
+    std::fs::write(&spec, "# Spec").unwrap();
+    chat.config.cwd = tempdir.path().to_path_buf();
+
+    chat.dispatch_command_with_args(SlashCommand::Pin, "missing.md".to_string());
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+
+    chat.dispatch_command_with_args(SlashCommand::Pin, "spec.md".to_string());
+    assert_matches!(
+        op_rx.try_recv(),
+        Ok(Op::PinFile { path, pinned: true }) if path == spec
+    );
+    assert_eq!(chat.pinned_files, vec!["spec.md".to_string()]);
+
+    // Pinning the same file again does not send it twice.
+    chat.dispatch_command_with_args(SlashCommand::Pin, "./spec.md".to_string());
+    assert_matches!(op_rx.try_recv(), Err(TryRecvError::Empty));
+
+    chat.dispatch_command_with_args(SlashCommand::Unpin, "spec.md".to_string());
+    assert_matches!(op_rx.try_recv(), Ok(Op::PinFile { pinned: false, .. }));
+    assert!(chat.pinned_files.is_empty());
+}
+
 #[test]
 fn slash_init_skips_when_project_doc_exists() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual();
@@ -1109,6 +1313,15 @@ fn slash_exit_requests_exit() {
     assert_matches!(rx.try_recv(), Ok(AppEvent::ExitRequest));
 }
 
+#[test]
+fn slash_resume_opens_resume_picker() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+
+    chat.dispatch_command(SlashCommand::Resume);
+
+    assert_matches!(rx.try_recv(), Ok(AppEvent::OpenResumePicker));
+}
+
 #[test]
 fn slash_undo_sends_op() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
@@ -1121,6 +1334,219 @@ fn slash_undo_sends_op() {
     }
 }
 
+#[test]
+fn slash_fork_branches_from_the_selected_assistant_message() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+
+    for message in ["First answer", "Second answer\nwith details"] {
+        chat.handle_codex_event(Event {
+            id: "s1".into(),
+            msg: EventMsg::AgentMessage(AgentMessageEvent {
+                message: message.into(),
+            }),
+        });
+    }
+    assert_eq!(
+        chat.agent_messages,
+        vec![
+            "First answer".to_string(),
+            "Second answer\nwith details".to_string()
+        ]
+    );
+    while rx.try_recv().is_ok() {}
+
+    // Most recent answer is listed first; move down to the first answer.
+    chat.dispatch_command(SlashCommand::Fork);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_matches!(
+        rx.try_recv(),
+        Ok(AppEvent::CodexOp(Op::ForkConversation { at_item: 0 }))
+    );
+
+    let rollout_path = PathBuf::from("/tmp/codex-fork-rollout.jsonl");
+    chat.handle_codex_event(Event {
+        id: "fork".into(),
+        msg: EventMsg::ConversationForked(ConversationForkedEvent {
+            conversation_id: ConversationId::new(),
+            rollout_path: rollout_path.clone(),
+            at_item: 0,
+        }),
+    });
+    let mut resumed = None;
+    while let Ok(event) = rx.try_recv() {
+        if let AppEvent::ResumeSession(path) = event {
+            resumed = Some(path);
+        }
+    }
+    assert_eq!(resumed, Some(rollout_path));
+}
+
+#[test]
+fn slash_pin_toggles_the_selected_assistant_message() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual();
+
+    for message in ["First answer", "Second answer"] {
+        chat.handle_codex_event(Event {
+            id: "s1".into(),
+            msg: EventMsg::AgentMessage(AgentMessageEvent {
+                message: message.into(),
+            }),
+        });
+    }
+    while rx.try_recv().is_ok() {}
+
+    // Most recent answer is listed first; move down to the first answer.
+    chat.dispatch_command(SlashCommand::Pin);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let event = rx.try_recv().expect("pin event");
+    assert_matches!(
+        event,
+        AppEvent::PinAgentMessage {
+            at_item: 0,
+            pinned: true
+        }
+    );
+    chat.pin_agent_message(0, true);
+    assert_matches!(
+        op_rx.try_recv(),
+        Ok(Op::PinHistoryItem {
+            at_item: 0,
+            pinned: true
+        })
+    );
+
+    // Selecting a pinned message unpins it.
+    chat.dispatch_command(SlashCommand::Pin);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let events: Vec<AppEvent> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+    assert!(
+        events.iter().any(|event| matches!(
+            event,
+            AppEvent::PinAgentMessage {
+                at_item: 0,
+                pinned: false
+            }
+        )),
+        "expected an unpin event, got {events:?}"
+    );
+}
+
+#[test]
+fn slash_regenerate_reruns_the_last_message_and_drops_its_answer() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+
+    chat.dispatch_command(SlashCommand::Regenerate);
+    assert!(chat.bottom_pane.no_modal_or_popup_active());
+
+    for message in ["First answer", "Second answer"] {
+        chat.handle_codex_event(Event {
+            id: "s1".into(),
+            msg: EventMsg::AgentMessage(AgentMessageEvent {
+                message: message.into(),
+            }),
+        });
+    }
+    while rx.try_recv().is_ok() {}
+
+    chat.dispatch_command(SlashCommand::Regenerate);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_matches!(
+        rx.try_recv(),
+        Ok(AppEvent::CodexOp(Op::RegenerateLast {
+            model: None,
+            effort: None
+        }))
+    );
+
+    chat.handle_codex_event(Event {
+        id: "regen".into(),
+        msg: EventMsg::ResponseRegenerated(ResponseRegeneratedEvent {
+            previous_attempt: MessageVariant {
+                message_id: Some("msg_2".to_string()),
+                text: "Second answer".to_string(),
+                model: "gpt-5".to_string(),
+                effort: None,
+            },
+            removed_messages: 1,
+            model: "gpt-5".to_string(),
+            effort: None,
+        }),
+    });
+    assert_eq!(chat.agent_messages, vec!["First answer".to_string()]);
+}
+
+#[test]
+fn edited_user_message_drops_later_answers_and_pins() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
+    chat.pinned_files = vec!["spec.md".to_string(), "notes.md".to_string()];
+
+    for message in ["First answer", "Second answer", "Third answer"] {
+        chat.handle_codex_event(Event {
+            id: "s1".into(),
+            msg: EventMsg::AgentMessage(AgentMessageEvent {
+                message: message.into(),
+            }),
+        });
+    }
+
+    chat.handle_codex_event(Event {
+        id: "edit".into(),
+        msg: EventMsg::UserMessageEdited(UserMessageEditedEvent {
+            at_item: 1,
+            removed_user_messages: 2,
+            removed_messages: 2,
+            unpinned_files: vec![chat.config.cwd.join("notes.md")],
+        }),
+    });
+    assert_eq!(chat.agent_messages, vec!["First answer".to_string()]);
+    assert_eq!(chat.pinned_files, vec!["spec.md".to_string()]);
+}
+
+#[test]
+fn slash_quote_inserts_excerpt_and_sends_anchor() {
+    let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual();
+
+    for message in ["First answer\n\nwith details", "Second answer"] {
+        chat.handle_codex_event(Event {
+            id: "s1".into(),
+            msg: EventMsg::AgentMessage(AgentMessageEvent {
+                message: message.into(),
+            }),
+        });
+    }
+    while rx.try_recv().is_ok() {}
+
+    // Most recent answer is listed first; move down to the first answer.
+    chat.dispatch_command(SlashCommand::Quote);
+    chat.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let at_item = match rx.try_recv() {
+        Ok(AppEvent::QuoteAgentMessage(at_item)) => at_item,
+        other => panic!("expected QuoteAgentMessage, got {other:?}"),
+    };
+    assert_eq!(at_item, 0);
+
+    chat.quote_agent_message(at_item);
+    assert_eq!(
+        chat.bottom_pane.composer_text(),
+        "> [#1] First answer\n>\n> with details\n\n"
+    );
+
+    chat.conversation_id = Some(ConversationId::new());
+    chat.bottom_pane.insert_str("Why?");
+    chat.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    match op_rx.try_recv() {
+        Ok(Op::UserTurn { items, .. }) => {
+            assert_eq!(items.last(), Some(&UserInput::Quote { item: 0 }));
+        }
+        other => panic!("expected Op::UserTurn, got {other:?}"),
+    }
+    assert_eq!(chat.pending_quote, None);
+}
+
 #[test]
 fn checkpoint_command_submits_op() {
     let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
@@ -1256,6 +1682,130 @@ fn checkpoint_list_subcommand_submits_op() {
     }
 }
 
+#[test]
+fn checkpoint_diff_command_submits_op() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
+
+    chat.submit_user_message(UserMessage {
+        text: "/checkpoint-diff base feature".into(),
+        display_text: None,
+        image_paths: Vec::new(),
+    });
+
+    match op_rx.try_recv() {
+        Ok(Op::DiffCheckpoints { from, to }) => {
+            assert_eq!((from.as_str(), to.as_str()), ("base", "feature"));
+        }
+        other => panic!("expected DiffCheckpoints op, got {other:?}"),
+    }
+}
+
+#[test]
+fn ctrl_x_toggles_read_only_exploration_mode() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+    chat.set_approval_policy(AskForApproval::Never);
+    chat.set_sandbox_policy(SandboxPolicy::new_workspace_write_policy())
+        .expect("set sandbox policy");
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL));
+    assert_eq!(
+        chat.config.approval_policy.value(),
+        AskForApproval::OnRequest
+    );
+    assert_eq!(chat.config.sandbox_policy.get(), &SandboxPolicy::ReadOnly);
+    let mut saw_override = false;
+    while let Ok(ev) = rx.try_recv() {
+        if let AppEvent::CodexOp(Op::OverrideTurnContext {
+            approval_policy,
+            sandbox_policy,
+            ..
+        }) = ev
+        {
+            assert_eq!(approval_policy, Some(AskForApproval::OnRequest));
+            assert_eq!(sandbox_policy, Some(SandboxPolicy::ReadOnly));
+            saw_override = true;
+        }
+    }
+    assert!(
+        saw_override,
+        "expected OverrideTurnContext for exploration mode"
+    );
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL));
+    assert!(chat.exploration_restore.is_none());
+    let restored = std::iter::from_fn(|| rx.try_recv().ok()).find_map(|ev| match ev {
+        AppEvent::CodexOp(Op::OverrideTurnContext {
+            approval_policy,
+            sandbox_policy,
+            ..
+        }) => Some((approval_policy, sandbox_policy)),
+        _ => None,
+    });
+    assert_eq!(
+        restored,
+        Some((
+            Some(AskForApproval::Never),
+            Some(SandboxPolicy::new_workspace_write_policy())
+        ))
+    );
+}
+
+#[test]
+fn remapped_keys_replace_default_chords() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
+    let mut reloaded = chat.config.clone();
+    reloaded.tui_keys = [("exploration_mode".to_string(), "alt+x".to_string())]
+        .into_iter()
+        .collect();
+    chat.apply_reloaded_config(&["tui.keys".to_string()], &reloaded);
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL));
+    assert!(chat.exploration_restore.is_none());
+
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('x'), KeyModifiers::ALT));
+    assert!(chat.exploration_restore.is_some());
+}
+
+#[test]
+fn command_palette_restores_checkpoints_and_inserts_prompts() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
+
+    chat.on_command_palette_action(PaletteAction::RestoreCheckpoint("base".to_string()));
+    match op_rx.try_recv() {
+        Ok(Op::RestoreCheckpoint { name }) => assert_eq!(name, "base"),
+        other => panic!("expected RestoreCheckpoint op, got {other:?}"),
+    }
+
+    chat.on_command_palette_action(PaletteAction::InsertPrompt("deploy".to_string()));
+    assert_eq!(chat.bottom_pane.composer_text(), "/prompts:deploy ");
+}
+
+#[test]
+fn shell_commands_are_added_to_history_and_palette() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
+
+    chat.on_command_palette_action(PaletteAction::RunShellCommand("ls".to_string()));
+    chat.on_command_palette_action(PaletteAction::RunShellCommand("git status".to_string()));
+    chat.on_command_palette_action(PaletteAction::RunShellCommand("ls".to_string()));
+
+    let mut ops = Vec::new();
+    while let Ok(op) = op_rx.try_recv() {
+        ops.push(op);
+    }
+    assert!(matches!(
+        ops.first(),
+        Some(Op::RunUserShellCommand { command }) if command == "ls"
+    ));
+    assert!(matches!(
+        ops.get(1),
+        Some(Op::AddToHistory { text }) if text == "!ls"
+    ));
+    assert_eq!(
+        chat.recent_shell_commands,
+        VecDeque::from(["ls".to_string(), "git status".to_string()])
+    );
+}
+
 #[test]
 fn checkpoint_completion_clears_spinner() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
@@ -1562,6 +2112,9 @@ fn interrupted_turn_error_message_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: None,
+            sandbox_policy: None,
         }),
     });
 
@@ -1753,6 +2306,9 @@ fn preset_matching_ignores_extra_writable_roots() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        allow_write: vec![],
+        deny_write: vec![],
+        network_allowlist: vec![],
     };
 
     assert!(
@@ -1854,6 +2410,30 @@ fn model_reasoning_selection_popup_extra_high_warning_snapshot() {
     assert_snapshot!("model_reasoning_selection_popup_extra_high_warning", popup);
 }
 
+#[test]
+fn reasoning_popup_preselects_the_effort_remembered_for_the_model() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
+
+    chat.config.model = "gpt-5.1-codex".to_string();
+    chat.remember_model_effort("gpt-5.1-codex-max", Some(ReasoningEffortConfig::Low));
+
+    let preset = builtin_model_presets(None)
+        .into_iter()
+        .find(|preset| preset.model == "gpt-5.1-codex-max")
+        .expect("gpt-5.1-codex-max preset");
+    chat.open_reasoning_popup(preset);
+
+    let popup = render_bottom_popup(&chat, 80);
+    let selected = popup
+        .lines()
+        .find(|line| line.starts_with('›'))
+        .expect("a selected row");
+    assert!(
+        selected.contains("Low"),
+        "expected the remembered effort to be selected; popup: {popup}"
+    );
+}
+
 #[test]
 fn reasoning_popup_shows_extra_high_with_space() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
@@ -2055,6 +2635,7 @@ fn approval_modal_exec_snapshot() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         risk: None,
+        network_destinations: vec![],
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2101,6 +2682,7 @@ fn approval_modal_exec_without_reason_snapshot() {
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
         reason: None,
         risk: None,
+        network_destinations: vec![],
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2263,6 +2845,9 @@ fn ui_snapshots_small_heights_task_running() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: None,
+            sandbox_policy: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -2294,6 +2879,9 @@ fn status_widget_and_approval_modal_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: None,
+            sandbox_policy: None,
         }),
     });
     // Provide a deterministic header for the status line.
@@ -2314,6 +2902,7 @@ fn status_widget_and_approval_modal_snapshot() {
             "this is a test reason such as one that would be produced by the model".into(),
         ),
         risk: None,
+        network_destinations: vec![],
         parsed_cmd: vec![],
     };
     chat.handle_codex_event(Event {
@@ -2341,6 +2930,9 @@ fn status_widget_active_snapshot() {
         id: "task-1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: None,
+            sandbox_policy: None,
         }),
     });
     // Provide a deterministic header via a bold reasoning chunk.
@@ -2840,6 +3432,64 @@ fn plan_update_renders_history_cell() {
     assert!(blob.contains("Write tests"));
 }
 
+fn subagent_update(task: &str, agent_index: i64, status: SubagentTaskStatus) -> Event {
+    Event {
+        id: "sub-1".into(),
+        msg: EventMsg::SubagentTaskUpdate(SubagentTaskUpdateEvent {
+            call_id: "call-1".to_string(),
+            task: task.to_string(),
+            role: None,
+            agent_index: Some(agent_index),
+            status,
+            summary: None,
+        }),
+    }
+}
+
+#[test]
+fn alt_digit_cancels_the_numbered_subagent_task() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
+    chat.handle_codex_event(subagent_update("lint", 0, SubagentTaskStatus::Running));
+    chat.handle_codex_event(subagent_update("test", 1, SubagentTaskStatus::Running));
+    chat.handle_codex_event(subagent_update("lint", 0, SubagentTaskStatus::Done));
+
+    // Task 1 already finished, so only task 2 can be cancelled.
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('1'), KeyModifiers::ALT));
+    assert!(op_rx.try_recv().is_err());
+    chat.handle_key_event(KeyEvent::new(KeyCode::Char('2'), KeyModifiers::ALT));
+    assert_eq!(
+        op_rx.try_recv(),
+        Ok(Op::CancelSubagentTask {
+            call_id: "call-1".to_string(),
+            agent_index: 1,
+        })
+    );
+}
+
+fn plan_ready_event(request: &str) -> Event {
+    Event {
+        id: "sub-1".into(),
+        msg: EventMsg::PlanReady(PlanReadyEvent {
+            request: request.to_string(),
+            plan: Some(UpdatePlanArgs {
+                explanation: Some("Draft plan".into()),
+                plan: vec![],
+            }),
+            summary: None,
+            feedback: Vec::new(),
+        }),
+    }
+}
+
+fn next_plan_op(op_rx: &mut tokio::sync::mpsc::UnboundedReceiver<Op>) -> Op {
+    loop {
+        match op_rx.try_recv().expect("plan op") {
+            Op::AddToHistory { .. } | Op::Interrupt => continue,
+            op => break op,
+        }
+    }
+}
+
 #[test]
 fn plan_mode_captures_initial_request() {
     let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
@@ -2853,17 +3503,14 @@ fn plan_mode_captures_initial_request() {
         chat.plan_workflow.is_some(),
         "expected plan workflow to start"
     );
-    let op = op_rx.try_recv().expect("queued user input");
-    match op {
-        Op::UserInput { items } => {
-            assert_eq!(items.len(), 1);
-        }
+    match next_plan_op(&mut op_rx) {
+        Op::StartPlan { request, .. } => assert_eq!(request, "map the codebase"),
         other => panic!("unexpected op: {other:?}"),
     }
 }
 
 #[test]
-fn plan_request_wraps_prompt_but_history_shows_original() {
+fn plan_request_is_sent_raw_and_history_shows_original() {
     let (mut chat, mut rx, mut op_rx) = make_chatwidget_manual();
     chat.config.plan_detail = PlanDetailPreference::Detailed;
     chat.set_plan_mode(true);
@@ -2873,27 +3520,13 @@ fn plan_request_wraps_prompt_but_history_shows_original() {
         image_paths: Vec::new(),
     });
 
-    let submission_text = loop {
-        match op_rx.try_recv().expect("plan submission") {
-            Op::UserInput { items } => {
-                let text = match &items[0] {
-                    UserInput::Text { text } => text.clone(),
-                    _ => continue,
-                };
-                break text;
-            }
-            Op::AddToHistory { .. } | Op::Interrupt => continue,
-            other => panic!("unexpected op while waiting for submission: {other:?}"),
-        }
-    };
-    assert!(
-        submission_text.contains("plan-first workflow"),
-        "expected injected instructions, got {submission_text:?}"
-    );
-    assert!(
-        submission_text.contains("map the codebase"),
-        "expected original request in submission"
-    );
+    match next_plan_op(&mut op_rx) {
+        Op::StartPlan { request, .. } => assert!(
+            !request.contains("plan-first workflow"),
+            "core owns the plan instructions, got {request:?}"
+        ),
+        other => panic!("unexpected op while waiting for submission: {other:?}"),
+    }
 
     let cells = drain_insert_history(&mut rx);
     let prompt_entry = cells
@@ -2908,7 +3541,7 @@ fn plan_request_wraps_prompt_but_history_shows_original() {
 }
 
 #[test]
-fn plan_update_moves_workflow_to_review() {
+fn plan_ready_moves_workflow_to_review() {
     let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
     chat.set_plan_mode(true);
     chat.queue_user_message(UserMessage {
@@ -2916,62 +3549,68 @@ fn plan_update_moves_workflow_to_review() {
         display_text: None,
         image_paths: Vec::new(),
     });
-    let _ = op_rx.try_recv().expect("initial input");
-    let _ = op_rx.try_recv().expect("initial history entry");
-    let update = UpdatePlanArgs {
-        explanation: Some("Draft plan".into()),
-        plan: vec![],
-    };
+    let _ = next_plan_op(&mut op_rx);
     chat.handle_codex_event(Event {
         id: "sub-1".into(),
-        msg: EventMsg::PlanUpdate(update),
+        msg: EventMsg::PlanUpdate(UpdatePlanArgs {
+            explanation: Some("Draft plan".into()),
+            plan: vec![],
+        }),
     });
+    assert!(
+        !chat.plan_review_pending(),
+        "plan updates alone should not end drafting"
+    );
+
+    chat.handle_codex_event(plan_ready_event("add telemetry"));
     assert!(
         chat.plan_review_pending(),
-        "plan workflow should await approval after plan update"
+        "plan workflow should await approval after PlanReady"
     );
 }
 
 #[test]
-fn plan_execute_replays_request() {
+fn plan_feedback_is_sent_as_revision() {
     let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
     chat.set_plan_mode(true);
     chat.queue_user_message(UserMessage {
-        text: "ship it".to_string(),
+        text: "add telemetry".to_string(),
         display_text: None,
         image_paths: Vec::new(),
     });
-    let _ = op_rx.try_recv().expect("initial input sent");
-    let _ = op_rx.try_recv().expect("initial history entry");
-    chat.handle_codex_event(Event {
-        id: "sub-2".into(),
-        msg: EventMsg::PlanUpdate(UpdatePlanArgs {
-            explanation: None,
-            plan: vec![],
-        }),
+    let _ = next_plan_op(&mut op_rx);
+    chat.handle_codex_event(plan_ready_event("add telemetry"));
+
+    chat.prepare_plan_feedback();
+    chat.queue_user_message(UserMessage {
+        text: "split the first step".to_string(),
+        display_text: None,
+        image_paths: Vec::new(),
     });
-    chat.execute_plan_request();
-    let execution_op = loop {
-        let op = op_rx.try_recv().expect("execution op");
-        match op {
-            Op::UserInput { .. } => break op,
-            Op::AddToHistory { .. } | Op::Interrupt => continue,
-            other => panic!("unexpected op while waiting for execution: {other:?}"),
-        }
-    };
-    match execution_op {
-        Op::UserInput { items } => {
-            let text = match &items[0] {
-                UserInput::Text { text } => text,
-                _ => panic!("unexpected input {items:?}"),
-            };
-            assert!(
-                text.contains("Plan approved"),
-                "execution message should confirm approval"
-            );
-        }
+    match next_plan_op(&mut op_rx) {
+        Op::RevisePlan { feedback } => assert_eq!(feedback, "split the first step"),
         other => panic!("unexpected op: {other:?}"),
     }
+    assert!(!chat.plan_review_pending());
+}
+
+#[test]
+fn plan_execute_approves_plan() {
+    let (mut chat, _rx, mut op_rx) = make_chatwidget_manual();
+    chat.set_plan_mode(true);
+    chat.queue_user_message(UserMessage {
+        text: "ship it".to_string(),
+        display_text: None,
+        image_paths: Vec::new(),
+    });
+    let _ = next_plan_op(&mut op_rx);
+    chat.handle_codex_event(plan_ready_event("ship it"));
+
+    chat.execute_plan_request();
+    match next_plan_op(&mut op_rx) {
+        Op::ApprovePlan => {}
+        other => panic!("unexpected op while waiting for approval: {other:?}"),
+    }
     assert!(
         chat.plan_workflow.is_none(),
         "plan workflow should clear after execution"
@@ -3022,6 +3661,51 @@ fn warning_event_adds_warning_history_cell() {
     );
 }
 
+#[test]
+fn project_docs_loaded_warns_only_about_truncated_docs() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
+    let cwd = chat.config.cwd.clone();
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::ProjectDocsLoaded(ProjectDocsLoadedEvent {
+            docs: vec![ProjectDocEntry {
+                path: cwd.join("AGENTS.md"),
+                bytes: 11,
+                truncated: false,
+            }],
+        }),
+    });
+    assert!(drain_insert_history(&mut rx).is_empty());
+
+    chat.handle_codex_event(Event {
+        id: "sub-1".into(),
+        msg: EventMsg::ProjectDocsLoaded(ProjectDocsLoadedEvent {
+            docs: vec![
+                ProjectDocEntry {
+                    path: cwd.join("AGENTS.md"),
+                    bytes: 0,
+                    truncated: true,
+                },
+                ProjectDocEntry {
+                    path: cwd.join("packages/web/AGENTS.md"),
+                    bytes: 9,
+                    truncated: true,
+                },
+            ],
+        }),
+    });
+    let cells = drain_insert_history(&mut rx);
+    assert_eq!(cells.len(), 1, "expected one warning history cell");
+    let rendered = lines_to_single_string(&cells[0])
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert!(
+        rendered.contains("AGENTS.md (left out), packages/web/AGENTS.md (first 9 bytes)"),
+        "warning cell missing docs: {rendered}"
+    );
+}
+
 #[test]
 fn multiple_agent_messages_in_single_turn_emit_multiple_headers() {
     let (mut chat, mut rx, _op_rx) = make_chatwidget_manual();
@@ -3031,6 +3715,9 @@ fn multiple_agent_messages_in_single_turn_emit_multiple_headers() {
         id: "s1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: None,
+            sandbox_policy: None,
         }),
     });
 
@@ -3200,6 +3887,7 @@ fn chatwidget_exec_and_status_layout_vt100_snapshot() {
             parsed_cmd: parsed_cmd.clone(),
             source: ExecCommandSource::Agent,
             interaction_input: None,
+            approval_rule: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -3225,6 +3913,9 @@ fn chatwidget_exec_and_status_layout_vt100_snapshot() {
         id: "t1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: None,
+            sandbox_policy: None,
         }),
     });
     chat.handle_codex_event(Event {
@@ -3269,6 +3960,9 @@ fn chatwidget_markdown_code_blocks_vt100_snapshot() {
         id: "t1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: None,
+            sandbox_policy: None,
         }),
     });
     // Build a vt100 visual from the history insertions only (no UI overlay)
@@ -3357,6 +4051,9 @@ fn chatwidget_tall() {
         id: "t1".into(),
         msg: EventMsg::TaskStarted(TaskStartedEvent {
             model_context_window: None,
+            model: "gpt-5.1-codex".to_string(),
+            reasoning_effort: None,
+            sandbox_policy: None,
         }),
     });
     for i in 0..30 {
@@ -3374,3 +4071,18 @@ fn chatwidget_tall() {
     .unwrap();
     assert_snapshot!(term.backend().vt100().screen().contents());
 }
+
+#[test]
+fn error_notification_respects_filter() {
+    let (mut chat, _rx, _op_rx) = make_chatwidget_manual();
+
+    chat.on_error("stream disconnected".to_string());
+    assert!(matches!(
+        chat.pending_notification.take(),
+        Some(Notification::Error { ref message }) if message == "stream disconnected"
+    ));
+
+    chat.config.tui_notifications = Notifications::Custom(vec!["agent-turn-complete".to_string()]);
+    chat.on_error("stream disconnected".to_string());
+    assert!(chat.pending_notification.is_none());
+}
@@ -32,6 +32,15 @@ pub struct Cli {
     #[clap(skip)]
     pub resume_show_all: bool,
 
+    /// Internal: play back this rollout file instead of starting a session.
+    /// Set by the top-level `codex replay <ROLLOUT>` wrapper.
+    #[clap(skip)]
+    pub replay_rollout: Option<PathBuf>,
+
+    /// Internal: playback speed for `replay_rollout` relative to the recording.
+    #[clap(skip)]
+    pub replay_speed: f64,
+
     /// Model the agent should use.
     #[arg(long, short = 'm')]
     pub model: Option<String>,
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::time::Duration;
 use std::time::Instant;
 
@@ -25,10 +26,51 @@ pub(crate) struct ExecCall {
     pub(crate) interaction_input: Option<String>,
 }
 
+/// Most streamed output kept per running command; older lines are dropped.
+const LIVE_OUTPUT_MAX_BYTES: usize = 64 * 1024;
+
+/// Output streamed so far by a running command, capped at
+/// [`LIVE_OUTPUT_MAX_BYTES`] by dropping whole lines from the front.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct LiveOutput {
+    text: String,
+    omitted_lines: usize,
+}
+
+impl LiveOutput {
+    pub(crate) fn push(&mut self, chunk: &str) {
+        self.text.push_str(chunk);
+        if self.text.len() <= LIVE_OUTPUT_MAX_BYTES {
+            return;
+        }
+        let mut start = self.text.len() - LIVE_OUTPUT_MAX_BYTES;
+        while !self.text.is_char_boundary(start) {
+            start += 1;
+        }
+        // Keep whole lines so the first one shown is not cut mid-way.
+        if let Some(newline) = self.text[start..].find('\n') {
+            start += newline + 1;
+        }
+        self.omitted_lines += self.text[..start].matches('\n').count();
+        self.text.drain(..start);
+    }
+
+    pub(crate) fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Lines dropped from the front to stay under the cap.
+    pub(crate) fn omitted_lines(&self) -> usize {
+        self.omitted_lines
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct ExecCell {
     pub(crate) calls: Vec<ExecCall>,
     animations_enabled: bool,
+    /// Output streamed so far by running commands, by call id.
+    live_output: HashMap<String, LiveOutput>,
 }
 
 impl ExecCell {
@@ -36,6 +78,7 @@ impl ExecCell {
         Self {
             calls: vec![call],
             animations_enabled,
+            live_output: HashMap::new(),
         }
     }
 
@@ -61,6 +104,7 @@ impl ExecCell {
             Some(Self {
                 calls: [self.calls.clone(), vec![call]].concat(),
                 animations_enabled: self.animations_enabled,
+                live_output: self.live_output.clone(),
             })
         } else {
             None
@@ -78,6 +122,36 @@ impl ExecCell {
             call.duration = Some(duration);
             call.start_time = None;
         }
+        self.live_output.remove(call_id);
+    }
+
+    /// Appends streamed output for a running command. Returns whether the
+    /// cell changed; exploring calls (reads, searches) never show output.
+    pub(crate) fn append_output(&mut self, call_id: &str, chunk: &str) -> bool {
+        let running = self.calls.iter().any(|call| {
+            call.call_id == call_id && call.output.is_none() && !Self::is_exploring_call(call)
+        });
+        if !running || chunk.is_empty() {
+            return false;
+        }
+        self.live_output
+            .entry(call_id.to_string())
+            .or_default()
+            .push(chunk);
+        true
+    }
+
+    pub(crate) fn live_output(&self, call_id: &str) -> Option<&LiveOutput> {
+        self.live_output.get(call_id)
+    }
+
+    /// The running call with streamed output, if any, and its output.
+    pub(crate) fn running_live_output(&self) -> Option<(&ExecCall, &LiveOutput)> {
+        self.calls
+            .iter()
+            .rev()
+            .filter(|call| call.output.is_none())
+            .find_map(|call| Some((call, self.live_output.get(&call.call_id)?)))
     }
 
     pub(crate) fn should_flush(&self) -> bool {
@@ -5,10 +5,12 @@ use super::model::ExecCall;
 use super::model::ExecCell;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell::HistoryCell;
+use crate::key_hint;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::line_utils::push_owned_lines;
 use crate::shimmer::shimmer_spans;
+use crate::theme::ThemeStylize;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use crate::wrapping::word_wrap_lines;
@@ -16,6 +18,7 @@ use codex_ansi_escape::ansi_escape_line;
 use codex_common::elapsed::format_duration;
 use codex_core::protocol::ExecCommandSource;
 use codex_protocol::parse_command::ParsedCommand;
+use crossterm::event::KeyCode;
 use itertools::Itertools;
 use ratatui::prelude::*;
 use ratatui::style::Modifier;
@@ -25,7 +28,12 @@ use unicode_width::UnicodeWidthStr;
 
 pub(crate) const TOOL_CALL_MAX_LINES: usize = 5;
 const USER_SHELL_TOOL_CALL_MAX_LINES: usize = 50;
+/// Trailing lines of streamed output shown while a command runs.
+const LIVE_OUTPUT_LINES: usize = 8;
 const MAX_INTERACTION_PREVIEW_CHARS: usize = 80;
+/// Finished exploration runs with at least this many detail lines collapse
+/// into a one-line summary; the transcript overlay still shows every entry.
+const EXPLORE_SUMMARY_MIN_ENTRIES: usize = 5;
 
 pub(crate) struct OutputLinesParams {
     pub(crate) line_limit: usize,
@@ -174,7 +182,7 @@ pub(crate) fn output_lines(
 
 pub(crate) fn spinner(start_time: Option<Instant>, animations_enabled: bool) -> Span<'static> {
     if !animations_enabled {
-        return "•".dim();
+        return "•".secondary();
     }
     let elapsed = start_time.map(|st| st.elapsed()).unwrap_or_default();
     if supports_color::on_cached(supports_color::Stream::Stdout)
@@ -184,14 +192,18 @@ pub(crate) fn spinner(start_time: Option<Instant>, animations_enabled: bool) ->
         shimmer_spans("•")[0].clone()
     } else {
         let blink_on = (elapsed.as_millis() / 600).is_multiple_of(2);
-        if blink_on { "•".into() } else { "◦".dim() }
+        if blink_on {
+            "•".into()
+        } else {
+            "◦".secondary()
+        }
     }
 }
 
 impl HistoryCell for ExecCell {
     fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
         if self.is_exploring_cell() {
-            self.exploring_display_lines(width)
+            self.exploring_display_lines(width, true)
         } else {
             self.command_display_lines(width)
         }
@@ -203,6 +215,11 @@ impl HistoryCell for ExecCell {
 
     fn transcript_lines(&self, width: u16) -> Vec<Line<'static>> {
         let mut lines: Vec<Line<'static>> = vec![];
+        if self.is_collapsed_exploration() {
+            // Collapsed exploration runs expand to the full list in the transcript.
+            lines.extend(self.exploring_display_lines(width, false));
+            lines.push("".into());
+        }
         for (i, call) in self.iter_calls().enumerate() {
             if i > 0 {
                 lines.push("".into());
@@ -212,7 +229,7 @@ impl HistoryCell for ExecCell {
             let cmd_display = word_wrap_lines(
                 &highlighted_script,
                 RtOptions::new(width as usize)
-                    .initial_indent("$ ".magenta().into())
+                    .initial_indent("$ ".brand().into())
                     .subsequent_indent("    ".into()),
             );
             lines.extend(cmd_display);
@@ -226,14 +243,14 @@ impl HistoryCell for ExecCell {
                     .map(format_duration)
                     .unwrap_or_else(|| "unknown".to_string());
                 let mut result: Line = if output.exit_code == 0 {
-                    Line::from("✓".green().bold())
+                    Line::from("✓".success().bold())
                 } else {
                     Line::from(vec![
-                        "✗".red().bold(),
+                        "✗".error().bold(),
                         format!(" ({})", output.exit_code).into(),
                     ])
                 };
-                result.push_span(format!(" • {duration}").dim());
+                result.push_span(format!(" • {duration}").secondary());
                 lines.push(result);
             }
         }
@@ -242,13 +259,16 @@ impl HistoryCell for ExecCell {
 }
 
 impl ExecCell {
-    fn exploring_display_lines(&self, width: u16) -> Vec<Line<'static>> {
+    fn exploring_display_lines(&self, width: u16, collapse: bool) -> Vec<Line<'static>> {
+        let entries = self.exploring_entries();
+        let collapsed =
+            collapse && !self.is_active() && entries.len() >= EXPLORE_SUMMARY_MIN_ENTRIES;
         let mut out: Vec<Line<'static>> = Vec::new();
-        out.push(Line::from(vec![
+        let mut header = Line::from(vec![
             if self.is_active() {
                 spinner(self.active_start_time(), self.animations_enabled())
             } else {
-                "•".dim()
+                "•".secondary()
             },
             " ".into(),
             if self.is_active() {
@@ -256,10 +276,47 @@ impl ExecCell {
             } else {
                 "Explored".bold()
             },
-        ]));
+        ]);
+        if collapsed {
+            header.push_span(" ");
+            header.push_span(self.exploration_summary());
+            out.push(header);
+            return out;
+        }
+        out.push(header);
 
-        let mut calls = self.calls.clone();
         let mut out_indented = Vec::new();
+        for (title, line) in entries {
+            let line = Line::from(line);
+            let initial_indent = Line::from(vec![title.accent(), " ".into()]);
+            let subsequent_indent = " ".repeat(initial_indent.width()).into();
+            let wrapped = word_wrap_line(
+                &line,
+                RtOptions::new(width as usize)
+                    .initial_indent(initial_indent)
+                    .subsequent_indent(subsequent_indent),
+            );
+            push_owned_lines(&wrapped, &mut out_indented);
+        }
+
+        out.extend(prefix_lines(
+            out_indented,
+            "  └ ".secondary(),
+            "    ".into(),
+        ));
+        out
+    }
+
+    fn is_collapsed_exploration(&self) -> bool {
+        self.is_exploring_cell()
+            && !self.is_active()
+            && self.exploring_entries().len() >= EXPLORE_SUMMARY_MIN_ENTRIES
+    }
+
+    /// One `(title, spans)` entry per detail line, coalescing consecutive reads.
+    fn exploring_entries(&self) -> Vec<(&'static str, Vec<Span<'static>>)> {
+        let mut entries = Vec::new();
+        let mut calls = self.calls.clone();
         while !calls.is_empty() {
             let mut call = calls.remove(0);
             if call
@@ -286,7 +343,7 @@ impl ExecCell {
                 .iter()
                 .all(|parsed| matches!(parsed, ParsedCommand::Read { .. }));
 
-            let call_lines: Vec<(&str, Vec<Span<'static>>)> = if reads_only {
+            if reads_only {
                 let names = call
                     .parsed
                     .iter()
@@ -295,54 +352,68 @@ impl ExecCell {
                         _ => unreachable!(),
                     })
                     .unique();
-                vec![(
+                entries.push((
                     "Read",
-                    Itertools::intersperse(names.into_iter().map(Into::into), ", ".dim()).collect(),
-                )]
+                    Itertools::intersperse(names.into_iter().map(Into::into), ", ".secondary())
+                        .collect(),
+                ));
             } else {
-                let mut lines = Vec::new();
                 for parsed in &call.parsed {
                     match parsed {
                         ParsedCommand::Read { name, .. } => {
-                            lines.push(("Read", vec![name.clone().into()]));
+                            entries.push(("Read", vec![name.clone().into()]));
                         }
                         ParsedCommand::ListFiles { cmd, path } => {
-                            lines.push(("List", vec![path.clone().unwrap_or(cmd.clone()).into()]));
+                            entries
+                                .push(("List", vec![path.clone().unwrap_or(cmd.clone()).into()]));
                         }
                         ParsedCommand::Search { cmd, query, path } => {
                             let spans = match (query, path) {
                                 (Some(q), Some(p)) => {
-                                    vec![q.clone().into(), " in ".dim(), p.clone().into()]
+                                    vec![q.clone().into(), " in ".secondary(), p.clone().into()]
                                 }
                                 (Some(q), None) => vec![q.clone().into()],
                                 _ => vec![cmd.clone().into()],
                             };
-                            lines.push(("Search", spans));
+                            entries.push(("Search", spans));
                         }
                         ParsedCommand::Unknown { cmd } => {
-                            lines.push(("Run", vec![cmd.clone().into()]));
+                            entries.push(("Run", vec![cmd.clone().into()]));
                         }
                     }
                 }
-                lines
-            };
-
-            for (title, line) in call_lines {
-                let line = Line::from(line);
-                let initial_indent = Line::from(vec![title.cyan(), " ".into()]);
-                let subsequent_indent = " ".repeat(initial_indent.width()).into();
-                let wrapped = word_wrap_line(
-                    &line,
-                    RtOptions::new(width as usize)
-                        .initial_indent(initial_indent)
-                        .subsequent_indent(subsequent_indent),
-                );
-                push_owned_lines(&wrapped, &mut out_indented);
             }
         }
+        entries
+    }
 
-        out.extend(prefix_lines(out_indented, "  └ ".dim(), "    ".into()));
-        out
+    /// Summary such as "14 files, 6 searches" for a collapsed exploration run.
+    fn exploration_summary(&self) -> String {
+        let parsed = self.calls.iter().flat_map(|call| call.parsed.iter());
+        let mut files = Vec::new();
+        let mut searches = 0;
+        let mut listings = 0;
+        for parsed in parsed {
+            match parsed {
+                ParsedCommand::Read { name, .. } => {
+                    if !files.contains(&name) {
+                        files.push(name);
+                    }
+                }
+                ParsedCommand::Search { .. } => searches += 1,
+                ParsedCommand::ListFiles { .. } => listings += 1,
+                ParsedCommand::Unknown { .. } => {}
+            }
+        }
+        [
+            (files.len(), "file", "files"),
+            (listings, "listing", "listings"),
+            (searches, "search", "searches"),
+        ]
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, one, many)| format!("{count} {}", if count == 1 { one } else { many }))
+        .join(", ")
     }
 
     fn command_display_lines(&self, width: u16) -> Vec<Line<'static>> {
@@ -352,8 +423,8 @@ impl ExecCell {
         let layout = EXEC_DISPLAY_LAYOUT;
         let success = call.output.as_ref().map(|o| o.exit_code == 0);
         let bullet = match success {
-            Some(true) => "•".green().bold(),
-            Some(false) => "•".red().bold(),
+            Some(true) => "•".success().bold(),
+            Some(false) => "•".error().bold(),
             None => spinner(call.start_time, self.animations_enabled()),
         };
         let is_interaction = call.is_unified_exec_interaction();
@@ -416,8 +487,8 @@ impl ExecCell {
         if !continuation_lines.is_empty() {
             lines.extend(prefix_lines(
                 continuation_lines,
-                Span::from(layout.command_continuation.initial_prefix).dim(),
-                Span::from(layout.command_continuation.subsequent_prefix).dim(),
+                Span::from(layout.command_continuation.initial_prefix).secondary(),
+                Span::from(layout.command_continuation.subsequent_prefix).secondary(),
             ));
         }
 
@@ -445,8 +516,8 @@ impl ExecCell {
             if raw_output.lines.is_empty() {
                 if !call.is_unified_exec_interaction() {
                     lines.extend(prefix_lines(
-                        vec![Line::from("(no output)".dim())],
-                        Span::from(layout.output_block.initial_prefix).dim(),
+                        vec![Line::from("(no output)".secondary())],
+                        Span::from(layout.output_block.initial_prefix).secondary(),
                         Span::from(layout.output_block.subsequent_prefix),
                     ));
                 }
@@ -471,11 +542,43 @@ impl ExecCell {
                 if !wrapped_output.is_empty() {
                     lines.extend(prefix_lines(
                         wrapped_output,
-                        Span::from(layout.output_block.initial_prefix).dim(),
+                        Span::from(layout.output_block.initial_prefix).secondary(),
                         Span::from(layout.output_block.subsequent_prefix),
                     ));
                 }
             }
+        } else if let Some(live_output) = self.live_output(&call.call_id) {
+            let live_lines: Vec<&str> = live_output.text().lines().collect();
+            let skip = live_lines.len().saturating_sub(LIVE_OUTPUT_LINES);
+            let output_wrap_width = layout.output_block.wrap_width(width);
+            let output_opts =
+                RtOptions::new(output_wrap_width).word_splitter(WordSplitter::NoHyphenation);
+            let mut wrapped_output: Vec<Line<'static>> = Vec::new();
+            let omitted = live_output.omitted_lines() + skip;
+            if omitted > 0 {
+                wrapped_output.push(Line::from(vec![
+                    format!("… +{omitted} lines, ").secondary(),
+                    key_hint::ctrl(KeyCode::Char('o')).into(),
+                    " to view all".secondary(),
+                ]));
+            }
+            for raw in &live_lines[skip..] {
+                let mut line = ansi_escape_line(raw);
+                line.spans.iter_mut().for_each(|span| {
+                    span.style = span.style.add_modifier(Modifier::DIM);
+                });
+                push_owned_lines(
+                    &word_wrap_line(&line, output_opts.clone()),
+                    &mut wrapped_output,
+                );
+            }
+            if !wrapped_output.is_empty() {
+                lines.extend(prefix_lines(
+                    wrapped_output,
+                    Span::from(layout.output_block.initial_prefix).secondary(),
+                    Span::from(layout.output_block.subsequent_prefix),
+                ));
+            }
         }
 
         lines
@@ -542,7 +645,7 @@ impl ExecCell {
     }
 
     fn ellipsis_line(omitted: usize) -> Line<'static> {
-        Line::from(vec![format!("… +{omitted} lines").dim()])
+        Line::from(vec![format!("… +{omitted} lines").secondary()])
     }
 }
 
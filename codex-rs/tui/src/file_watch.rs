@@ -0,0 +1,231 @@
+//! `/watch` file watchers that queue a prompt when matching files change.
+//!
+//! Each watch observes the session's working directory recursively and keeps
+//! the changed paths that match its glob. A burst of changes (saving several
+//! files, or an editor's write-and-rename) is coalesced for [`DEBOUNCE`] and
+//! reported once as [`AppEvent::FileWatchTriggered`], carrying the prompt with
+//! the `git diff` of the changed files appended.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+use globset::Glob;
+use globset::GlobMatcher;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+/// Quiet period after the last matching change before the prompt is queued.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Longest diff appended to a watch prompt.
+const MAX_DIFF_BYTES: usize = 20_000;
+
+/// An active `/watch`. Dropping it stops the watcher and its debounce task.
+pub(crate) struct FileWatch {
+    pub(crate) id: u64,
+    pub(crate) pattern: String,
+    /// Last prompt queued by this watch, so identical changes are not sent twice.
+    pub(crate) last_message: Option<String>,
+    _watcher: RecommendedWatcher,
+}
+
+impl FileWatch {
+    /// Starts watching `cwd` for changes to files matching `pattern`. A
+    /// pattern without `/` matches file names anywhere, like `.gitignore`.
+    pub(crate) fn start(
+        id: u64,
+        pattern: String,
+        prompt: String,
+        cwd: &Path,
+        app_event_tx: AppEventSender,
+    ) -> Result<Self, String> {
+        let matcher = Glob::new(&pattern)
+            .map_err(|err| format!("invalid glob `{pattern}`: {err}"))?
+            .compile_matcher();
+        let root = cwd
+            .canonicalize()
+            .map_err(|err| format!("failed to watch {}: {err}", cwd.display()))?;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        let filter_root = root.clone();
+        let filter_pattern = pattern.clone();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            let Ok(event) = res else {
+                return;
+            };
+            if matches!(event.kind, EventKind::Access(_) | EventKind::Other) {
+                return;
+            }
+            for path in event.paths {
+                if let Some(relative) = watched_path(&filter_root, &filter_pattern, &matcher, &path)
+                {
+                    let _ = tx.send(relative);
+                }
+            }
+        })
+        .map_err(|err| format!("failed to start file watcher: {err}"))?;
+        watcher
+            .watch(&root, RecursiveMode::Recursive)
+            .map_err(|err| format!("failed to watch {}: {err}", root.display()))?;
+
+        tokio::spawn(debounce_changes(id, prompt, root, rx, app_event_tx));
+
+        Ok(Self {
+            id,
+            pattern,
+            last_message: None,
+            _watcher: watcher,
+        })
+    }
+}
+
+/// The path relative to `root` when it is outside `.git` and matches the glob.
+fn watched_path(root: &Path, pattern: &str, matcher: &GlobMatcher, path: &Path) -> Option<PathBuf> {
+    let relative = path.strip_prefix(root).ok()?;
+    if relative
+        .components()
+        .any(|component| component.as_os_str() == ".git")
+    {
+        return None;
+    }
+    let matched = if pattern.contains('/') {
+        matcher.is_match(relative)
+    } else {
+        relative
+            .file_name()
+            .is_some_and(|name| matcher.is_match(name))
+    };
+    matched.then(|| relative.to_path_buf())
+}
+
+// The watcher callback owns the sender, so this ends once the watch is dropped.
+async fn debounce_changes(
+    id: u64,
+    prompt: String,
+    root: PathBuf,
+    mut rx: mpsc::UnboundedReceiver<PathBuf>,
+    app_event_tx: AppEventSender,
+) {
+    while let Some(first) = rx.recv().await {
+        let first_change_at = Instant::now();
+        let mut paths = BTreeSet::from([first]);
+        loop {
+            match tokio::time::timeout(DEBOUNCE, rx.recv()).await {
+                Ok(Some(path)) => {
+                    paths.insert(path);
+                }
+                Ok(None) => return,
+                Err(_) => break,
+            }
+        }
+        let paths: Vec<PathBuf> = paths.into_iter().collect();
+        let diff = changed_files_diff(&root, &paths).await;
+        app_event_tx.send(AppEvent::FileWatchTriggered {
+            id,
+            first_change_at,
+            file_count: paths.len(),
+            message: watch_message(&prompt, &paths, &diff),
+        });
+    }
+}
+
+/// `git diff` of `paths`, empty outside a repository or for untracked files.
+async fn changed_files_diff(root: &Path, paths: &[PathBuf]) -> String {
+    let output = Command::new("git")
+        .args(["diff", "--no-color", "--"])
+        .args(paths)
+        .current_dir(root)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .await;
+    match output {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).into_owned()
+        }
+        _ => String::new(),
+    }
+}
+
+/// The user message queued for a triggered watch.
+fn watch_message(prompt: &str, paths: &[PathBuf], diff: &str) -> String {
+    let files = paths
+        .iter()
+        .map(|path| format!("- {}", path.display()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut message = format!("{prompt}\n\nChanged files:\n{files}");
+    let diff = diff.trim_end();
+    if !diff.is_empty() {
+        let mut end = diff.len().min(MAX_DIFF_BYTES);
+        while !diff.is_char_boundary(end) {
+            end -= 1;
+        }
+        let truncated = if end < diff.len() {
+            "\n[diff truncated]"
+        } else {
+            ""
+        };
+        message.push_str(&format!("\n\n```diff\n{}{truncated}\n```", &diff[..end]));
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn patterns_without_a_slash_match_file_names() {
+        let root = Path::new("/repo");
+        let matcher = |pattern: &str| Glob::new(pattern).expect("glob").compile_matcher();
+
+        assert_eq!(
+            watched_path(
+                root,
+                "*.rs",
+                &matcher("*.rs"),
+                Path::new("/repo/src/lib.rs")
+            ),
+            Some(PathBuf::from("src/lib.rs"))
+        );
+        assert_eq!(
+            watched_path(
+                root,
+                "tests/**",
+                &matcher("tests/**"),
+                Path::new("/repo/src/lib.rs")
+            ),
+            None
+        );
+        assert_eq!(
+            watched_path(root, "*", &matcher("*"), Path::new("/repo/.git/index")),
+            None
+        );
+    }
+
+    #[test]
+    fn watch_message_appends_files_and_diff() {
+        let paths = vec![PathBuf::from("src/lib.rs")];
+        assert_eq!(
+            watch_message("run the tests", &paths, "-old\n+new\n"),
+            "run the tests\n\nChanged files:\n- src/lib.rs\n\n```diff\n-old\n+new\n```"
+        );
+        assert_eq!(
+            watch_message("run the tests", &paths, ""),
+            "run the tests\n\nChanged files:\n- src/lib.rs"
+        );
+    }
+}
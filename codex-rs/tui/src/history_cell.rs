@@ -7,6 +7,9 @@ use crate::exec_cell::output_lines;
 use crate::exec_cell::spinner;
 use crate::exec_command::relativize_to_home;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::image_render::IMAGE_INDENT;
+use crate::image_render::InlineImage;
+use crate::key_hint;
 use crate::markdown::append_markdown;
 use crate::markdown_render::render_markdown_text_with_width;
 use crate::render::line_utils::line_to_static;
@@ -17,6 +20,7 @@ use crate::semantic::SemanticStatus;
 use crate::style::user_message_style;
 use crate::text_formatting::format_and_truncate_tool_result;
 use crate::text_formatting::truncate_text;
+use crate::theme::ThemeStylize;
 use crate::ui_consts::LIVE_PREFIX_COLS;
 use crate::update_action::UpdateAction;
 use crate::version::CODEX_CLI_VERSION;
@@ -24,7 +28,6 @@ use crate::version::display_version;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use crate::wrapping::word_wrap_lines;
-use base64::Engine;
 use codex_common::format_env_display::format_env_display;
 use codex_core::config::Config;
 use codex_core::config::types::McpServerTransportConfig;
@@ -35,12 +38,17 @@ use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::SessionSummaryEvent;
+use codex_core::protocol::StorageCategory;
+use codex_core::protocol::StorageCleanupCompletedEvent;
+use codex_core::protocol::StorageReportEvent;
+use codex_core::protocol::SubagentReportEvent;
+use codex_core::protocol::SubagentScratchpadEvent;
 use codex_core::protocol::SubagentTaskStatus;
 use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::plan_tool::StepStatus;
 use codex_protocol::plan_tool::UpdatePlanArgs;
-use image::DynamicImage;
-use image::ImageReader;
+use crossterm::event::KeyCode;
 use mcp_types::EmbeddedResourceResource;
 use mcp_types::Resource;
 use mcp_types::ResourceLink;
@@ -54,13 +62,11 @@ use ratatui::widgets::Paragraph;
 use ratatui::widgets::Wrap;
 use std::any::Any;
 use std::collections::HashMap;
-use std::io::Cursor;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Command;
 use std::time::Duration;
 use std::time::Instant;
-use tracing::error;
 use unicode_width::UnicodeWidthStr;
 
 /// Represents an event to display in the conversation history. Returns its
@@ -158,7 +164,7 @@ impl HistoryCell for UserHistoryCell {
         );
 
         lines.push(Line::from("").style(style));
-        lines.extend(prefix_lines(wrapped, "› ".bold().dim(), "  ".into()));
+        lines.extend(prefix_lines(wrapped, "› ".bold().secondary(), "  ".into()));
         lines.push(Line::from("").style(style));
         lines
     }
@@ -187,7 +193,7 @@ impl ReasoningSummaryCell {
             Some((width as usize).saturating_sub(2)),
             &mut lines,
         );
-        let summary_style = Style::default().dim().italic();
+        let summary_style = Style::default().secondary().italic();
         let summary_lines = lines
             .into_iter()
             .map(|mut line| {
@@ -203,7 +209,7 @@ impl ReasoningSummaryCell {
         word_wrap_lines(
             &summary_lines,
             RtOptions::new(width as usize)
-                .initial_indent("• ".dim().into())
+                .initial_indent("• ".secondary().into())
                 .subsequent_indent("  ".into()),
         )
     }
@@ -256,7 +262,7 @@ impl HistoryCell for AgentMessageCell {
             &self.lines,
             RtOptions::new(width as usize)
                 .initial_indent(if self.is_first_line {
-                    "• ".dim().into()
+                    "• ".secondary().into()
                 } else {
                     "  ".into()
                 })
@@ -308,19 +314,19 @@ impl HistoryCell for UpdateAvailableHistoryCell {
         use ratatui_macros::line;
         use ratatui_macros::text;
         let update_instruction = if let Some(update_action) = self.update_action {
-            line!["Run ", update_action.command_str().cyan(), " to update."]
+            line!["Run ", update_action.command_str().accent(), " to update."]
         } else {
             line![
                 "See ",
-                "https://github.com/openai/codex".cyan().underlined(),
+                "https://github.com/openai/codex".accent().underlined(),
                 " for installation options."
             ]
         };
 
         let content = text![
             line![
-                padded_emoji("✨").bold().cyan(),
-                "Update available!".bold().cyan(),
+                padded_emoji("✨").bold().accent(),
+                "Update available!".bold().accent(),
                 " ",
                 format!("{CODEX_CLI_VERSION} -> {}", self.latest_version).bold(),
             ],
@@ -328,7 +334,7 @@ impl HistoryCell for UpdateAvailableHistoryCell {
             "",
             "See full release notes:",
             "https://github.com/openai/codex/releases/latest"
-                .cyan()
+                .accent()
                 .underlined(),
         ];
 
@@ -401,10 +407,10 @@ pub fn new_approval_decision_cell(
     use codex_core::protocol::ReviewDecision::*;
 
     let (symbol, summary): (Span<'static>, Vec<Span<'static>>) = match decision {
-        Approved => {
-            let snippet = Span::from(exec_snippet(&command)).dim();
+        Approved | ApprovedPartially { .. } => {
+            let snippet = Span::from(exec_snippet(&command)).secondary();
             (
-                "✔ ".green(),
+                "✔ ".success(),
                 vec![
                     "You ".into(),
                     "approved".bold(),
@@ -415,9 +421,9 @@ pub fn new_approval_decision_cell(
             )
         }
         ApprovedForSession => {
-            let snippet = Span::from(exec_snippet(&command)).dim();
+            let snippet = Span::from(exec_snippet(&command)).secondary();
             (
-                "✔ ".green(),
+                "✔ ".success(),
                 vec![
                     "You ".into(),
                     "approved".bold(),
@@ -428,9 +434,9 @@ pub fn new_approval_decision_cell(
             )
         }
         Denied => {
-            let snippet = Span::from(exec_snippet(&command)).dim();
+            let snippet = Span::from(exec_snippet(&command)).secondary();
             (
-                "✗ ".red(),
+                "✗ ".error(),
                 vec![
                     "You ".into(),
                     "did not approve".bold(),
@@ -440,9 +446,9 @@ pub fn new_approval_decision_cell(
             )
         }
         Abort => {
-            let snippet = Span::from(exec_snippet(&command)).dim();
+            let snippet = Span::from(exec_snippet(&command)).secondary();
             (
-                "✗ ".red(),
+                "✗ ".error(),
                 vec![
                     "You ".into(),
                     "canceled".bold(),
@@ -463,7 +469,7 @@ pub fn new_approval_decision_cell(
 /// Cyan history cell line showing the current review status.
 pub(crate) fn new_review_status_line(message: String) -> PlainHistoryCell {
     PlainHistoryCell {
-        lines: vec![Line::from(message.cyan())],
+        lines: vec![Line::from(message.accent())],
     }
 }
 
@@ -479,16 +485,6 @@ impl HistoryCell for PatchHistoryCell {
     }
 }
 
-#[derive(Debug)]
-struct CompletedMcpToolCallWithImageOutput {
-    _image: DynamicImage,
-}
-impl HistoryCell for CompletedMcpToolCallWithImageOutput {
-    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
-        vec!["tool result (image output)".into()]
-    }
-}
-
 /// Render `lines` inside a border whose inner width is at least `inner_width`.
 ///
 /// This is useful when callers have already clamped their content to a
@@ -520,7 +516,7 @@ fn with_border_internal(
 
     let mut out = Vec::with_capacity(lines.len() + 2);
     let border_inner_width = content_width + 2;
-    out.push(vec![format!("╭{}╮", "─".repeat(border_inner_width)).dim()].into());
+    out.push(vec![format!("╭{}╮", "─".repeat(border_inner_width)).secondary()].into());
 
     for line in lines.into_iter() {
         let used_width: usize = line
@@ -529,16 +525,16 @@ fn with_border_internal(
             .sum();
         let span_count = line.spans.len();
         let mut spans: Vec<Span<'static>> = Vec::with_capacity(span_count + 4);
-        spans.push(Span::from("│ ").dim());
+        spans.push(Span::from("│ ").secondary());
         spans.extend(line.into_iter());
         if used_width < content_width {
-            spans.push(Span::from(" ".repeat(content_width - used_width)).dim());
+            spans.push(Span::from(" ".repeat(content_width - used_width)).secondary());
         }
-        spans.push(Span::from(" │").dim());
+        spans.push(Span::from(" │").secondary());
         out.push(Line::from(spans));
     }
 
-    out.push(vec![format!("╰{}╯", "─".repeat(border_inner_width)).dim()].into());
+    out.push(vec![format!("╰{}╯", "─".repeat(border_inner_width)).secondary()].into());
 
     out
 }
@@ -605,7 +601,7 @@ pub(crate) fn new_session_info(
         CompositeHistoryCell { parts: vec![] }
     } else {
         let lines = vec![
-            "model changed:".magenta().bold().into(),
+            "model changed:".brand().bold().into(),
             format!("requested: {}", config.model).into(),
             format!("used: {model}").into(),
         ];
@@ -749,10 +745,10 @@ impl HistoryCell for SessionHeaderHistoryCell {
         let bottom_bar = format!("└{}┘", "─".repeat(inner_width + 2));
 
         vec![
-            Line::from(title_bar.dim()),
+            Line::from(title_bar.secondary()),
             Line::from(pad_line(&line1)),
             Line::from(pad_line(&line2)),
-            Line::from(bottom_bar.dim()),
+            Line::from(bottom_bar.secondary()),
         ]
     }
 }
@@ -866,12 +862,9 @@ impl McpToolCallCell {
         &mut self,
         duration: Duration,
         result: Result<mcp_types::CallToolResult, String>,
-    ) -> Option<Box<dyn HistoryCell>> {
-        let image_cell = try_new_completed_mcp_tool_call_with_image_output(&result)
-            .map(|cell| Box::new(cell) as Box<dyn HistoryCell>);
+    ) {
         self.duration = Some(duration);
         self.result = Some(result);
-        image_cell
     }
 
     fn success(&self) -> Option<bool> {
@@ -914,8 +907,8 @@ impl HistoryCell for McpToolCallCell {
         let mut lines: Vec<Line<'static>> = Vec::new();
         let status = self.success();
         let bullet = match status {
-            Some(true) => "•".green().bold(),
-            Some(false) => "•".red().bold(),
+            Some(true) => "•".success().bold(),
+            Some(false) => "•".error().bold(),
             None => spinner(Some(self.start_time), self.animations_enabled),
         };
         let header_text = if status.is_some() {
@@ -944,7 +937,7 @@ impl HistoryCell for McpToolCallCell {
                 .subsequent_indent("    ".into());
             let wrapped = word_wrap_line(&invocation_line, opts);
             let body_lines: Vec<Line<'static>> = wrapped.iter().map(line_to_static).collect();
-            lines.extend(prefix_lines(body_lines, "  └ ".dim(), "    ".into()));
+            lines.extend(prefix_lines(body_lines, "  └ ".secondary(), "    ".into()));
         }
 
         let mut detail_lines: Vec<Line<'static>> = Vec::new();
@@ -958,7 +951,7 @@ impl HistoryCell for McpToolCallCell {
                         for block in content {
                             let text = Self::render_content_block(block, detail_wrap_width);
                             for segment in text.split('\n') {
-                                let line = Line::from(segment.to_string().dim());
+                                let line = Line::from(segment.to_string().secondary());
                                 let wrapped = word_wrap_line(
                                     &line,
                                     RtOptions::new(detail_wrap_width)
@@ -976,7 +969,7 @@ impl HistoryCell for McpToolCallCell {
                         TOOL_CALL_MAX_LINES,
                         width as usize,
                     );
-                    let err_line = Line::from(err_text.dim());
+                    let err_line = Line::from(err_text.secondary());
                     let wrapped = word_wrap_line(
                         &err_line,
                         RtOptions::new(detail_wrap_width)
@@ -990,7 +983,7 @@ impl HistoryCell for McpToolCallCell {
 
         if !detail_lines.is_empty() {
             let initial_prefix: Span<'static> = if inline_invocation {
-                "  └ ".dim()
+                "  └ ".secondary()
             } else {
                 "    ".into()
             };
@@ -1014,46 +1007,6 @@ pub(crate) fn new_web_search_call(query: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
-/// If the first content is an image, return a new cell with the image.
-/// TODO(rgwood-dd): Handle images properly even if they're not the first result.
-fn try_new_completed_mcp_tool_call_with_image_output(
-    result: &Result<mcp_types::CallToolResult, String>,
-) -> Option<CompletedMcpToolCallWithImageOutput> {
-    match result {
-        Ok(mcp_types::CallToolResult { content, .. }) => {
-            if let Some(mcp_types::ContentBlock::ImageContent(image)) = content.first() {
-                let raw_data = match base64::engine::general_purpose::STANDARD.decode(&image.data) {
-                    Ok(data) => data,
-                    Err(e) => {
-                        error!("Failed to decode image data: {e}");
-                        return None;
-                    }
-                };
-                let reader = match ImageReader::new(Cursor::new(raw_data)).with_guessed_format() {
-                    Ok(reader) => reader,
-                    Err(e) => {
-                        error!("Failed to guess image format: {e}");
-                        return None;
-                    }
-                };
-
-                let image = match reader.decode() {
-                    Ok(image) => image,
-                    Err(e) => {
-                        error!("Image decoding failed: {e}");
-                        return None;
-                    }
-                };
-
-                Some(CompletedMcpToolCallWithImageOutput { _image: image })
-            } else {
-                None
-            }
-        }
-        _ => None,
-    }
-}
-
 #[allow(clippy::disallowed_methods)]
 pub(crate) fn new_warning_event(message: String) -> PrefixedWrappedHistoryCell {
     PrefixedWrappedHistoryCell::new(message.yellow(), "⚠ ".yellow(), "  ")
@@ -1075,14 +1028,14 @@ pub(crate) fn new_deprecation_notice(
 impl HistoryCell for DeprecationNoticeCell {
     fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
         let mut lines: Vec<Line<'static>> = Vec::new();
-        lines.push(vec!["⚠ ".red().bold(), self.summary.clone().red()].into());
+        lines.push(vec!["⚠ ".error().bold(), self.summary.clone().error()].into());
 
         let wrap_width = width.saturating_sub(4).max(1) as usize;
 
         if let Some(details) = &self.details {
             let line = textwrap::wrap(details, wrap_width)
                 .into_iter()
-                .map(|s| s.to_string().dim().into())
+                .map(|s| s.to_string().secondary().into())
                 .collect::<Vec<_>>();
             lines.extend(line);
         }
@@ -1094,7 +1047,7 @@ impl HistoryCell for DeprecationNoticeCell {
 /// Render a summary of configured MCP servers from the current `Config`.
 pub(crate) fn empty_mcp_output() -> PlainHistoryCell {
     let lines: Vec<Line<'static>> = vec![
-        "/mcp".magenta().into(),
+        "/mcp".brand().into(),
         "".into(),
         vec!["🔌  ".into(), "MCP Tools".bold()].into(),
         "".into(),
@@ -1119,7 +1072,7 @@ pub(crate) fn new_mcp_tools_output(
     auth_statuses: &HashMap<String, McpAuthStatus>,
 ) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = vec![
-        "/mcp".magenta().into(),
+        "/mcp".brand().into(),
         "".into(),
         vec!["🔌  ".into(), "MCP Tools".bold()].into(),
         "".into(),
@@ -1150,13 +1103,13 @@ pub(crate) fn new_mcp_tools_output(
         let mut header: Vec<Span<'static>> = vec!["  • ".into(), server.clone().into()];
         if !cfg.enabled {
             header.push(" ".into());
-            header.push("(disabled)".red());
+            header.push("(disabled)".error());
             lines.push(header.into());
             lines.push(Line::from(""));
             continue;
         }
         lines.push(header.into());
-        lines.push(vec!["    • Status: ".into(), "enabled".green()].into());
+        lines.push(vec!["    • Status: ".into(), "enabled".success()].into());
         lines.push(vec!["    • Auth: ".into(), auth_status.to_string().into()].into());
 
         match &cfg.transport {
@@ -1239,7 +1192,7 @@ pub(crate) fn new_mcp_tools_output(
                 let label = resource.title.as_ref().unwrap_or(&resource.name);
                 spans.push(label.clone().into());
                 spans.push(" ".into());
-                spans.push(format!("({})", resource.uri).dim());
+                spans.push(format!("({})", resource.uri).secondary());
             }
 
             lines.push(spans.into());
@@ -1262,7 +1215,7 @@ pub(crate) fn new_mcp_tools_output(
                 let label = template.title.as_ref().unwrap_or(&template.name);
                 spans.push(label.clone().into());
                 spans.push(" ".into());
-                spans.push(format!("({})", template.uri_template).dim());
+                spans.push(format!("({})", template.uri_template).secondary());
             }
 
             lines.push(spans.into());
@@ -1274,7 +1227,7 @@ pub(crate) fn new_mcp_tools_output(
     PlainHistoryCell { lines }
 }
 pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHistoryCell {
-    let mut line = vec!["• ".dim(), message.into()];
+    let mut line = vec!["• ".secondary(), message.into()];
     if let Some(hint) = hint {
         line.push(" ".into());
         line.push(hint.dark_gray());
@@ -1285,9 +1238,9 @@ pub(crate) fn new_info_event(message: String, hint: Option<String>) -> PlainHist
 
 pub(crate) fn new_checkpoint_list(entries: &[CheckpointEntry]) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = Vec::new();
-    lines.push(vec!["• ".dim(), "Checkpoints".into()].into());
+    lines.push(vec!["• ".secondary(), "Checkpoints".into()].into());
     if entries.is_empty() {
-        lines.push(vec!["  └ (none yet)".dim()].into());
+        lines.push(vec!["  └ (none yet)".secondary()].into());
     } else {
         for (idx, entry) in entries.iter().enumerate() {
             let connector = if idx + 1 == entries.len() {
@@ -1306,20 +1259,216 @@ pub(crate) fn new_checkpoint_list(entries: &[CheckpointEntry]) -> PlainHistoryCe
                     connector.into(),
                     format!("`{}`", entry.name).into(),
                     " ".into(),
-                    detail.dim(),
+                    detail.secondary(),
+                ]
+                .into(),
+            );
+        }
+    }
+    PlainHistoryCell { lines }
+}
+
+pub(crate) fn new_session_summary(summary: &SessionSummaryEvent, cwd: &Path) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    lines.push(vec![padded_emoji("📌").into(), "Session summary".bold()].into());
+    for (title, entries) in [
+        ("Goals", &summary.goals),
+        ("Decisions", &summary.decisions),
+        ("Files changed", &summary.files_changed),
+        ("Open questions", &summary.open_questions),
+    ] {
+        lines.push(vec!["  ".into(), title.accent()].into());
+        if entries.is_empty() {
+            lines.push(vec!["    (none)".secondary()].into());
+        }
+        for entry in entries {
+            lines.push(vec!["    • ".secondary(), entry.clone().into()].into());
+        }
+    }
+    if let Some(path) = &summary.saved_path {
+        lines.push(
+            vec![
+                "  Saved to ".secondary(),
+                display_path_for(path, cwd).into(),
+            ]
+            .into(),
+        );
+    }
+    PlainHistoryCell { lines }
+}
+
+const MAX_STORAGE_REPOS: usize = 5;
+
+pub(crate) fn new_storage_report(report: &StorageReportEvent, cwd: &Path) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    lines.push(vec![padded_emoji("💾").into(), "Storage".bold()].into());
+    for entry in &report.usage {
+        lines.push(
+            vec![
+                format!("  {:<18}", storage_category_label(entry.category)).accent(),
+                format_bytes(entry.bytes).into(),
+                format!("  ({} files)", entry.files).secondary(),
+            ]
+            .into(),
+        );
+    }
+    if !report.repos.is_empty() {
+        lines.push(vec!["  Sessions by directory".accent()].into());
+        for repo in report.repos.iter().take(MAX_STORAGE_REPOS) {
+            lines.push(
+                vec![
+                    "    • ".secondary(),
+                    display_path_for(&repo.cwd, cwd).into(),
+                    format!(
+                        "  {} in {} sessions",
+                        format_bytes(repo.bytes),
+                        repo.sessions
+                    )
+                    .secondary(),
                 ]
                 .into(),
             );
         }
+        if report.repos.len() > MAX_STORAGE_REPOS {
+            let more = report.repos.len() - MAX_STORAGE_REPOS;
+            lines.push(vec![format!("    … {more} more").secondary()].into());
+        }
+    }
+    lines.push(vec!["  Run /storage clean <days> to free space".secondary()].into());
+    PlainHistoryCell { lines }
+}
+
+pub(crate) fn new_storage_cleanup(result: &StorageCleanupCompletedEvent) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    let header = if result.dry_run {
+        "Storage cleanup (dry run)"
+    } else {
+        "Storage cleanup"
+    };
+    lines.push(vec![padded_emoji("🧹").into(), header.bold()].into());
+    let verb = if result.dry_run {
+        "would free"
+    } else {
+        "freed"
+    };
+    for entry in &result.freed {
+        lines.push(
+            vec![
+                format!("  {:<18}", storage_category_label(entry.category)).accent(),
+                format!("{verb} {}", format_bytes(entry.bytes)).into(),
+                format!("  ({} files)", entry.files).secondary(),
+            ]
+            .into(),
+        );
+    }
+    if result.freed.is_empty() && result.errors.is_empty() {
+        lines.push(vec!["    (nothing to clean)".secondary()].into());
+    }
+    for error in &result.errors {
+        lines.push(vec!["  ".into(), error.clone().error()].into());
     }
     PlainHistoryCell { lines }
 }
 
+pub(crate) fn new_subagent_report(report: &SubagentReportEvent, cwd: &Path) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    lines.push(vec![padded_emoji("🧩").into(), "Subagent changes".bold()].into());
+    for agent in report.agents.iter().filter(|agent| !agent.files.is_empty()) {
+        let (added, removed) = agent
+            .unified_diff
+            .as_deref()
+            .map(count_diff_lines)
+            .unwrap_or_default();
+        lines.push(
+            vec![
+                format!("  Agent {} — {}", agent.agent_index + 1, agent.task).accent(),
+                format!("  {} file(s)", agent.files.len()).into(),
+                format!(" +{added}").success(),
+                format!(" -{removed}").error(),
+            ]
+            .into(),
+        );
+    }
+    for conflict in &report.conflicts {
+        let agents = conflict
+            .agent_indices
+            .iter()
+            .map(|idx| (idx + 1).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        lines.push(
+            vec![
+                "  ⚠ ".error(),
+                display_path_for(&conflict.path, cwd).into(),
+                format!(" edited by agents {agents}").error(),
+            ]
+            .into(),
+        );
+    }
+    lines.push(vec!["  Run /subagent-review to compare the diffs".secondary()].into());
+    PlainHistoryCell { lines }
+}
+
+/// Notes the subagents of one call shared, shown under their task boxes.
+pub(crate) fn new_subagent_scratchpad(scratchpad: &SubagentScratchpadEvent) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    lines.push(vec![padded_emoji("📝").into(), "Subagent scratchpad".bold()].into());
+    for note in &scratchpad.notes {
+        let mut text = note.text.lines();
+        lines.push(
+            vec![
+                format!("  Agent {} — {}: ", note.agent_index + 1, note.task).accent(),
+                text.next().unwrap_or_default().to_string().into(),
+            ]
+            .into(),
+        );
+        lines.extend(text.map(|line| format!("    {line}").into()));
+    }
+    PlainHistoryCell { lines }
+}
+
+/// Added and removed lines in a unified diff, excluding file headers.
+fn count_diff_lines(diff: &str) -> (usize, usize) {
+    diff.lines().fold((0, 0), |(added, removed), line| {
+        if line.starts_with('+') && !line.starts_with("+++") {
+            (added + 1, removed)
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            (added, removed + 1)
+        } else {
+            (added, removed)
+        }
+    })
+}
+
+fn storage_category_label(category: StorageCategory) -> &'static str {
+    match category {
+        StorageCategory::Rollouts => "Sessions",
+        StorageCategory::ArchivedRollouts => "Archived sessions",
+        StorageCategory::Attachments => "Attachments",
+        StorageCategory::Memory => "Memory",
+        StorageCategory::Checkpoints => "Checkpoints",
+    }
+}
+
+fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
 pub(crate) fn new_error_event(message: String) -> PlainHistoryCell {
     // Use a hair space (U+200A) to create a subtle, near-invisible separation
     // before the text. VS16 is intentionally omitted to keep spacing tighter
     // in terminals like Ghostty.
-    let lines: Vec<Line<'static>> = vec![vec![format!("■ {message}").red()].into()];
+    let lines: Vec<Line<'static>> = vec![vec![format!("■ {message}").error()].into()];
     PlainHistoryCell { lines }
 }
 
@@ -1341,15 +1490,15 @@ impl HistoryCell for PlanUpdateCell {
             let wrap_width = width.saturating_sub(4).max(1) as usize;
             textwrap::wrap(text, wrap_width)
                 .into_iter()
-                .map(|s| s.to_string().dim().italic().into())
+                .map(|s| s.to_string().secondary().italic().into())
                 .collect()
         };
 
         let render_step = |status: &StepStatus, text: &str| -> Vec<Line<'static>> {
             let (box_str, step_style) = match status {
-                StepStatus::Completed => ("✔ ", Style::default().crossed_out().dim()),
-                StepStatus::InProgress => ("□ ", Style::default().cyan().bold()),
-                StepStatus::Pending => ("□ ", Style::default().dim()),
+                StepStatus::Completed => ("✔ ", Style::default().crossed_out().secondary()),
+                StepStatus::InProgress => ("□ ", Style::default().accent().bold()),
+                StepStatus::Pending => ("□ ", Style::default().secondary()),
             };
             let wrap_width = (width as usize)
                 .saturating_sub(4)
@@ -1364,7 +1513,7 @@ impl HistoryCell for PlanUpdateCell {
         };
 
         let mut lines: Vec<Line<'static>> = vec![];
-        lines.push(vec!["• ".dim(), "Updated Plan".bold()].into());
+        lines.push(vec!["• ".secondary(), "Updated Plan".bold()].into());
 
         let mut indented_lines = vec![];
         let note = self
@@ -1377,13 +1526,17 @@ impl HistoryCell for PlanUpdateCell {
         };
 
         if self.plan.is_empty() {
-            indented_lines.push(Line::from("(no steps provided)".dim().italic()));
+            indented_lines.push(Line::from("(no steps provided)".secondary().italic()));
         } else {
             for PlanItemArg { step, status } in self.plan.iter() {
                 indented_lines.extend(render_step(status, step));
             }
         }
-        lines.extend(prefix_lines(indented_lines, "  └ ".dim(), "    ".into()));
+        lines.extend(prefix_lines(
+            indented_lines,
+            "  └ ".secondary(),
+            "    ".into(),
+        ));
 
         lines
     }
@@ -1406,7 +1559,7 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     let mut lines: Vec<Line<'static>> = Vec::new();
 
     // Failure title
-    lines.push(Line::from("✘ Failed to apply patch".magenta().bold()));
+    lines.push(Line::from("✘ Failed to apply patch".brand().bold()));
 
     if !stderr.trim().is_empty() {
         let output = output_lines(
@@ -1428,21 +1581,73 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
-pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistoryCell {
-    let display_path = display_path_for(&path, cwd);
+/// An image listed by path that also carries an inline rendering when the
+/// terminal supports a graphics protocol. The rendering is drawn below the
+/// cell's lines as it is inserted into scrollback.
+#[derive(Debug)]
+pub(crate) struct ImageHistoryCell {
+    lines: Vec<Line<'static>>,
+    image: Option<InlineImage>,
+}
 
-    let lines: Vec<Line<'static>> = vec![
-        vec!["• ".dim(), "Viewed Image".bold()].into(),
-        vec!["  └ ".dim(), display_path.dim()].into(),
-    ];
+impl ImageHistoryCell {
+    fn new(title: &'static str, path: &Path, cwd: &Path) -> Self {
+        let display_path = display_path_for(path, cwd);
+        let lines: Vec<Line<'static>> = vec![
+            vec!["• ".secondary(), title.bold()].into(),
+            vec!["  └ ".secondary(), display_path.secondary()].into(),
+        ];
+        let max_cols = crossterm::terminal::size()
+            .map(|(cols, _)| cols.saturating_sub(IMAGE_INDENT))
+            .unwrap_or(u16::MAX);
+        Self {
+            lines,
+            image: InlineImage::load(path, max_cols),
+        }
+    }
 
-    PlainHistoryCell { lines }
+    pub(crate) fn inline_image(&self) -> Option<&InlineImage> {
+        self.image.as_ref()
+    }
+}
+
+impl HistoryCell for ImageHistoryCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        self.lines.clone()
+    }
+}
+
+pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> ImageHistoryCell {
+    ImageHistoryCell::new("Viewed Image", &path, cwd)
+}
+
+/// An image returned by an MCP tool and saved to the attachment store. When
+/// the terminal cannot draw it inline, the cell points at the saved file.
+pub(crate) fn new_mcp_image_output(path: &Path, cwd: &Path) -> ImageHistoryCell {
+    let mut cell = ImageHistoryCell::new("Image Output", path, cwd);
+    if cell.image.is_none()
+        && let Some(line) = cell.lines.last_mut()
+    {
+        line.spans.push(" — press ".secondary());
+        line.spans.push(key_hint::ctrl(KeyCode::Char('o')).into());
+        line.spans.push(" to open".secondary());
+    }
+    cell
+}
+
+/// Attached images are already listed with the prompt, so this cell only
+/// exists when the image can be drawn inline.
+pub(crate) fn new_attached_image(path: &Path, cwd: &Path) -> Option<ImageHistoryCell> {
+    let cell = ImageHistoryCell::new("Attached Image", path, cwd);
+    cell.image.is_some().then_some(cell)
 }
 
 #[derive(Debug, Clone)]
 struct SubagentTaskState {
     name: String,
     label: Option<String>,
+    role: Option<String>,
+    agent_index: Option<i64>,
     status: SubagentTaskStatus,
     summary: Option<String>,
     started_at: Instant,
@@ -1472,11 +1677,19 @@ impl SubagentTasksCell {
         status: SubagentTaskStatus,
         summary: Option<String>,
         label: Option<String>,
+        role: Option<String>,
+        agent_index: Option<i64>,
     ) {
         let now = Instant::now();
         if let Some(task) = self.tasks.iter_mut().find(|task| task.name == name) {
             task.status = status;
             task.summary = summary;
+            if role.is_some() {
+                task.role = role;
+            }
+            if agent_index.is_some() {
+                task.agent_index = agent_index;
+            }
             if task.label.is_none()
                 && let Some(label) = label
             {
@@ -1491,6 +1704,8 @@ impl SubagentTasksCell {
         self.tasks.push(SubagentTaskState {
             name,
             label,
+            role,
+            agent_index,
             status,
             summary,
             started_at: now,
@@ -1524,6 +1739,8 @@ impl SubagentTasksCell {
         self.tasks.push(SubagentTaskState {
             name,
             label,
+            role: None,
+            agent_index: None,
             status: SubagentTaskStatus::Running,
             summary: None,
             started_at: Instant::now(),
@@ -1538,6 +1755,16 @@ impl SubagentTasksCell {
             .any(|task| matches!(task.status, SubagentTaskStatus::Running))
     }
 
+    /// Call id and agent index for the running task shown at `position`
+    /// (1-based), if any.
+    pub(crate) fn cancel_target(&self, position: usize) -> Option<(String, i64)> {
+        let task = self.tasks.get(position.checked_sub(1)?)?;
+        if !matches!(task.status, SubagentTaskStatus::Running) {
+            return None;
+        }
+        Some((self.call_id.clone(), task.agent_index?))
+    }
+
     fn pretty_elapsed(elapsed: Duration) -> String {
         if elapsed < Duration::from_secs(60) {
             return format!("{:.1}s", elapsed.as_secs_f32());
@@ -1546,10 +1773,12 @@ impl SubagentTasksCell {
     }
 
     fn status_color(&self, task: &SubagentTaskState) -> ratatui::style::Color {
+        let theme = crate::theme::current();
         match task.status {
-            SubagentTaskStatus::Running => ratatui::style::Color::Cyan,
-            SubagentTaskStatus::Done => ratatui::style::Color::Green,
-            SubagentTaskStatus::Timeout | SubagentTaskStatus::Failed => ratatui::style::Color::Red,
+            SubagentTaskStatus::Running => theme.accent,
+            SubagentTaskStatus::Done => theme.success,
+            SubagentTaskStatus::Timeout | SubagentTaskStatus::Failed => theme.error,
+            SubagentTaskStatus::Cancelled => ratatui::style::Color::DarkGray,
         }
     }
 }
@@ -1579,7 +1808,11 @@ impl HistoryCell for SubagentTasksCell {
 
             // Build top border: ┌─ name ───────────── status time ─┐
             let status_part = format!(" {} {} ", self.status_text(task), elapsed_display);
-            let name_part = format!(" {} ", label);
+            let position = task_idx + 1;
+            let name_part = match &task.role {
+                Some(role) => format!(" {position}. {label} ({role}) "),
+                None => format!(" {position}. {label} "),
+            };
             let fixed_len = 2 + name_part.len() + status_part.len() + 2; // ┌─ + name + status + ─┐
             let fill_len = box_width.saturating_sub(fixed_len);
             let fill = "─".repeat(fill_len);
@@ -1601,7 +1834,7 @@ impl HistoryCell for SubagentTasksCell {
                     let padding = box_width.saturating_sub(msg.len() + 4);
                     lines.push(Line::from(vec![
                         "│ ".fg(border_color),
-                        msg.dim().italic(),
+                        msg.secondary().italic(),
                         " ".repeat(padding).into(),
                         " │".fg(border_color),
                     ]));
@@ -1628,6 +1861,10 @@ impl HistoryCell for SubagentTasksCell {
             ]));
         }
 
+        if self.has_running() {
+            lines.push(Line::from("  alt+<n> cancels subagent n".secondary()));
+        }
+
         lines
     }
 }
@@ -1639,6 +1876,7 @@ impl SubagentTasksCell {
             SubagentTaskStatus::Done => "✓",
             SubagentTaskStatus::Timeout => "⏱",
             SubagentTaskStatus::Failed => "✗",
+            SubagentTaskStatus::Cancelled => "⊘",
         }
     }
 }
@@ -1662,7 +1900,7 @@ fn task_box_content(task: &SubagentTaskState, max_width: usize, _animations_enab
 
     // Add "more" indicator if we truncated
     if entries.len() > MAX_SUBAGENT_DETAIL_LINES {
-        content_lines.push(vec![format!("… {} more", entries.len() - MAX_SUBAGENT_DETAIL_LINES).dim()]);
+        content_lines.push(vec![format!("… {} more", entries.len() - MAX_SUBAGENT_DETAIL_LINES).secondary()]);
     }
 
     for entry in overflow {
@@ -1767,10 +2005,10 @@ impl HistoryCell for FinalMessageSeparator {
                     worked_for,
                     "─".repeat((width as usize).saturating_sub(worked_for_width)),
                 ])
-                .dim(),
+                .secondary(),
             ]
         } else {
-            vec![Line::from_iter(["─".repeat(width as usize).dim()])]
+            vec![Line::from_iter(["─".repeat(width as usize).secondary()])]
         }
     }
 }
@@ -1786,11 +2024,11 @@ fn format_mcp_invocation<'a>(invocation: McpInvocation) -> Line<'a> {
         .unwrap_or_default();
 
     let invocation_spans = vec![
-        invocation.server.clone().cyan(),
+        invocation.server.clone().accent(),
         ".".into(),
-        invocation.tool.cyan(),
+        invocation.tool.accent(),
         "(".into(),
-        args_str.dim(),
+        args_str.secondary(),
         ")".into(),
     ];
     invocation_spans.into()
@@ -1846,6 +2084,102 @@ mod tests {
         render_lines(&cell.transcript_lines(u16::MAX))
     }
 
+    #[test]
+    fn storage_cleanup_reports_freed_space_per_category() {
+        let cell = new_storage_cleanup(&StorageCleanupCompletedEvent {
+            dry_run: true,
+            freed: vec![codex_core::protocol::StorageUsageEntry {
+                category: StorageCategory::Rollouts,
+                files: 3,
+                bytes: 5 * 1024 * 1024 + 512 * 1024,
+            }],
+            errors: vec![
+                "Pruning checkpoints older than 7 days failed: not a git repository".to_string(),
+            ],
+        });
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "🧹\u{200A}Storage cleanup (dry run)".to_string(),
+                "  Sessions          would free 5.5 MB  (3 files)".to_string(),
+                "  Pruning checkpoints older than 7 days failed: not a git repository".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn subagent_report_summarizes_edits_and_conflicts() {
+        let agent = |agent_index: i64, task: &str, files: &[&str], diff: &str| {
+            codex_core::protocol::SubagentAgentReport {
+                task: task.to_string(),
+                agent_index,
+                status: SubagentTaskStatus::Done,
+                files: files.iter().map(PathBuf::from).collect(),
+                unified_diff: Some(diff.to_string()),
+            }
+        };
+        let cell = new_subagent_report(
+            &SubagentReportEvent {
+                call_id: "call-1".to_string(),
+                agents: vec![
+                    agent(
+                        0,
+                        "api",
+                        &["/repo/src/lib.rs"],
+                        "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1,2 @@\n-old\n+new\n+more\n",
+                    ),
+                    agent(1, "ui", &["/repo/src/lib.rs"], "+++ b/src/lib.rs\n+x\n"),
+                    agent(2, "docs", &[], ""),
+                ],
+                conflicts: vec![codex_core::protocol::SubagentConflict {
+                    path: PathBuf::from("/repo/src/lib.rs"),
+                    agent_indices: vec![0, 1],
+                }],
+            },
+            Path::new("/repo"),
+        );
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "🧩\u{200A}Subagent changes".to_string(),
+                "  Agent 1 — api  1 file(s) +2 -1".to_string(),
+                "  Agent 2 — ui  1 file(s) +1 -0".to_string(),
+                "  ⚠ /repo/src/lib.rs edited by agents 1, 2".to_string(),
+                "  Run /subagent-review to compare the diffs".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn subagent_scratchpad_lists_notes_by_agent() {
+        let note = |agent_index: i64, task: &str, text: &str| {
+            codex_core::protocol::SubagentScratchpadNote {
+                task: task.to_string(),
+                agent_index,
+                text: text.to_string(),
+            }
+        };
+        let cell = new_subagent_scratchpad(&SubagentScratchpadEvent {
+            call_id: "call-1".to_string(),
+            notes: vec![
+                note(1, "ui", "styles are in theme.rs"),
+                note(0, "api", "auth is in src/auth.rs\nsessions are in memory"),
+            ],
+        });
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec![
+                "📝\u{200A}Subagent scratchpad".to_string(),
+                "  Agent 2 — ui: styles are in theme.rs".to_string(),
+                "  Agent 1 — api: auth is in src/auth.rs".to_string(),
+                "    sessions are in memory".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn mcp_tools_output_masks_sensitive_values() {
         let mut config = test_config();
@@ -2000,16 +2334,29 @@ mod tests {
         };
 
         let mut cell = new_active_mcp_tool_call("call-2".into(), invocation, true);
-        assert!(
-            cell.complete(Duration::from_millis(1420), Ok(result))
-                .is_none()
-        );
+        cell.complete(Duration::from_millis(1420), Ok(result));
 
         let rendered = render_lines(&cell.display_lines(80)).join("\n");
 
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn mcp_image_output_without_inline_rendering_offers_open_hint() {
+        let cwd = PathBuf::from("/workspace");
+        let cell = new_mcp_image_output(&cwd.join("missing-chart.png"), &cwd);
+        assert!(cell.inline_image().is_none());
+
+        let rendered = render_lines(&cell.display_lines(80));
+        assert_eq!(
+            rendered,
+            vec![
+                "• Image Output".to_string(),
+                "  └ missing-chart.png — press ctrl + o to open".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn completed_mcp_tool_call_error_snapshot() {
         let invocation = McpInvocation {
@@ -2022,10 +2369,7 @@ mod tests {
         };
 
         let mut cell = new_active_mcp_tool_call("call-3".into(), invocation, true);
-        assert!(
-            cell.complete(Duration::from_secs(2), Err("network timeout".into()))
-                .is_none()
-        );
+        cell.complete(Duration::from_secs(2), Err("network timeout".into()));
 
         let rendered = render_lines(&cell.display_lines(80)).join("\n");
 
@@ -2066,10 +2410,7 @@ mod tests {
         };
 
         let mut cell = new_active_mcp_tool_call("call-4".into(), invocation, true);
-        assert!(
-            cell.complete(Duration::from_millis(640), Ok(result))
-                .is_none()
-        );
+        cell.complete(Duration::from_millis(640), Ok(result));
 
         let rendered = render_lines(&cell.display_lines(48)).join("\n");
 
@@ -2098,10 +2439,7 @@ mod tests {
         };
 
         let mut cell = new_active_mcp_tool_call("call-5".into(), invocation, true);
-        assert!(
-            cell.complete(Duration::from_millis(1280), Ok(result))
-                .is_none()
-        );
+        cell.complete(Duration::from_millis(1280), Ok(result));
 
         let rendered = render_lines(&cell.display_lines(40)).join("\n");
 
@@ -2137,10 +2475,7 @@ mod tests {
         };
 
         let mut cell = new_active_mcp_tool_call("call-6".into(), invocation, true);
-        assert!(
-            cell.complete(Duration::from_millis(320), Ok(result))
-                .is_none()
-        );
+        cell.complete(Duration::from_millis(320), Ok(result));
 
         let rendered = render_lines(&cell.display_lines(120)).join("\n");
 
@@ -2239,6 +2574,43 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn user_shell_command_shows_tail_of_live_output() {
+        let call_id = "c1".to_string();
+        let mut cell = ExecCell::new(
+            ExecCall {
+                call_id: call_id.clone(),
+                command: vec!["bash".into(), "-lc".into(), "make test".into()],
+                parsed: Vec::new(),
+                output: None,
+                source: ExecCommandSource::UserShell,
+                start_time: Some(Instant::now()),
+                duration: None,
+                interaction_input: None,
+            },
+            false,
+        );
+        let output: String = (1..=12).map(|i| format!("line {i}\n")).collect();
+        assert!(cell.append_output(&call_id, &output));
+        assert!(!cell.append_output("other", "ignored"));
+
+        let rendered = render_lines(&cell.display_lines(80));
+        assert!(rendered.iter().any(|line| line.contains("line 12")));
+        assert!(rendered.iter().any(|line| line.contains("line 5")));
+        assert!(!rendered.iter().any(|line| line.contains("line 4")));
+
+        cell.complete_call(
+            &call_id,
+            CommandOutput {
+                exit_code: 0,
+                aggregated_output: "done".into(),
+                formatted_output: "done".into(),
+            },
+            Duration::from_millis(1),
+        );
+        assert_eq!(cell.live_output(&call_id), None);
+    }
+
     #[test]
     fn coalesces_reads_across_multiple_calls() {
         let mut cell = ExecCell::new(
@@ -2333,6 +2705,78 @@ mod tests {
         insta::assert_snapshot!(rendered);
     }
 
+    #[test]
+    fn long_exploration_run_collapses_to_summary() {
+        let search = |query: &str| ParsedCommand::Search {
+            query: Some(query.into()),
+            path: None,
+            cmd: format!("rg {query}"),
+        };
+        let read = |name: &str| ParsedCommand::Read {
+            name: name.into(),
+            cmd: format!("cat {name}"),
+            path: name.into(),
+        };
+        let calls = vec![
+            vec![search("App")],
+            vec![read("app.rs"), read("lib.rs")],
+            vec![search("ChatWidget")],
+            vec![read("chatwidget.rs")],
+            vec![search("HistoryCell")],
+            vec![read("history_cell.rs"), read("app.rs")],
+        ];
+        let mut cell: Option<ExecCell> = None;
+        for (idx, parsed) in calls.into_iter().enumerate() {
+            let call_id = format!("c{idx}");
+            let command = vec!["bash".into(), "-lc".into(), "echo".into()];
+            let mut next = match cell {
+                None => ExecCell::new(
+                    ExecCall {
+                        call_id: call_id.clone(),
+                        command,
+                        parsed,
+                        output: None,
+                        source: ExecCommandSource::Agent,
+                        start_time: Some(Instant::now()),
+                        duration: None,
+                        interaction_input: None,
+                    },
+                    true,
+                ),
+                Some(cell) => cell
+                    .with_added_call(
+                        call_id.clone(),
+                        command,
+                        parsed,
+                        ExecCommandSource::Agent,
+                        None,
+                    )
+                    .expect("exploring call should coalesce"),
+            };
+            next.complete_call(&call_id, CommandOutput::default(), Duration::from_millis(1));
+            cell = Some(next);
+        }
+        let cell = cell.expect("cell");
+
+        assert_eq!(
+            render_lines(&cell.display_lines(80)),
+            vec!["• Explored 4 files, 3 searches".to_string()]
+        );
+        let transcript = render_lines(&cell.transcript_lines(80));
+        assert_eq!(
+            transcript[..7].to_vec(),
+            vec![
+                "• Explored".to_string(),
+                "  └ Search App".to_string(),
+                "    Read app.rs, lib.rs".to_string(),
+                "    Search ChatWidget".to_string(),
+                "    Read chatwidget.rs".to_string(),
+                "    Search HistoryCell".to_string(),
+                "    Read history_cell.rs, app.rs".to_string(),
+            ]
+        );
+    }
+
     #[test]
     fn multiline_command_wraps_with_extra_indent_on_subsequent_lines() {
         // Create a completed exec cell with a multiline command
@@ -2714,4 +3158,37 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn subagent_cancel_targets_follow_box_numbers() {
+        let mut cell = SubagentTasksCell::new("call-1".to_string(), false);
+        for (idx, name) in ["lint", "test", "docs"].into_iter().enumerate() {
+            cell.update_task(
+                name.to_string(),
+                SubagentTaskStatus::Running,
+                None,
+                None,
+                None,
+                Some(idx as i64),
+            );
+        }
+        cell.update_task(
+            "lint".to_string(),
+            SubagentTaskStatus::Done,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert_eq!(cell.cancel_target(1), None);
+        assert_eq!(cell.cancel_target(3), Some(("call-1".to_string(), 2)));
+        assert_eq!(cell.cancel_target(4), None);
+        let rendered = render_lines(&cell.display_lines(80));
+        assert!(rendered[0].contains("1. lint"), "{rendered:?}");
+        assert_eq!(
+            rendered.last().map(String::as_str),
+            Some("  alt+<n> cancels subagent n")
+        );
+    }
 }
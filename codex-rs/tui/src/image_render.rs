@@ -0,0 +1,365 @@
+//! Inline image rendering for terminals that speak a graphics protocol.
+//!
+//! History reaches the scrollback as styled text lines, so an image is
+//! inserted as a block of blank rows that the protocol payload is then drawn
+//! over (see `insert_history::insert_history_image`). When no protocol is
+//! detected, callers keep their path-only rendering.
+
+use std::fmt;
+use std::fmt::Write as _;
+use std::io::Cursor;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use base64::Engine;
+use image::DynamicImage;
+use image::GenericImageView;
+use image::ImageFormat;
+use image::ImageReader;
+use image::imageops::FilterType;
+
+/// Column at which inline images start, matching the `  └ ` tool-call prefix.
+pub(crate) const IMAGE_INDENT: u16 = 4;
+/// Widest an inline image may be, in terminal columns.
+const MAX_IMAGE_COLS: u16 = 60;
+/// Tallest an inline image may be, in terminal rows.
+const MAX_IMAGE_ROWS: u16 = 20;
+/// Cell size assumed when the terminal does not report its pixel size.
+const FALLBACK_CELL_PX: (u32, u32) = (8, 16);
+/// Kitty requires base64 payloads to be split into chunks of at most 4096 bytes.
+const KITTY_CHUNK_LEN: usize = 4096;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ImageProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+impl ImageProtocol {
+    /// The protocol supported by the current terminal, detected once per process.
+    pub(crate) fn detect() -> Option<Self> {
+        static PROTOCOL: OnceLock<Option<ImageProtocol>> = OnceLock::new();
+        *PROTOCOL.get_or_init(|| Self::from_env(|key| std::env::var(key).ok()))
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        // Multiplexers swallow graphics escapes unless passthrough is configured.
+        if var("TMUX").is_some() || var("STY").is_some() {
+            return None;
+        }
+
+        let term = var("TERM").unwrap_or_default();
+        let term_program = var("TERM_PROGRAM").unwrap_or_default();
+        if var("KITTY_WINDOW_ID").is_some()
+            || term == "xterm-kitty"
+            || term == "xterm-ghostty"
+            || term_program == "ghostty"
+        {
+            return Some(Self::Kitty);
+        }
+        if matches!(term_program.as_str(), "iTerm.app" | "WezTerm")
+            || var("ITERM_SESSION_ID").is_some()
+        {
+            return Some(Self::Iterm2);
+        }
+        if term.starts_with("foot")
+            || term == "mlterm"
+            || term.contains("sixel")
+            || var("KONSOLE_VERSION").is_some()
+        {
+            return Some(Self::Sixel);
+        }
+        None
+    }
+}
+
+/// An image encoded for the current terminal, sized in character cells.
+#[derive(Clone)]
+pub(crate) struct InlineImage {
+    protocol: ImageProtocol,
+    cols: u16,
+    rows: u16,
+    payload: String,
+}
+
+impl fmt::Debug for InlineImage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InlineImage")
+            .field("protocol", &self.protocol)
+            .field("cols", &self.cols)
+            .field("rows", &self.rows)
+            .finish_non_exhaustive()
+    }
+}
+
+impl InlineImage {
+    /// Loads `path` and encodes it for the detected protocol, fitting it into
+    /// at most `max_cols` columns. Returns `None` when the terminal cannot show
+    /// images or the file cannot be decoded.
+    pub(crate) fn load(path: &Path, max_cols: u16) -> Option<Self> {
+        let protocol = ImageProtocol::detect()?;
+        let image = ImageReader::open(path)
+            .and_then(ImageReader::with_guessed_format)
+            .ok()?
+            .decode()
+            .map_err(|err| tracing::debug!("failed to decode {}: {err}", path.display()))
+            .ok()?;
+        Self::encode(
+            &image,
+            protocol,
+            max_cols.min(MAX_IMAGE_COLS),
+            cell_size_px(),
+        )
+    }
+
+    fn encode(
+        image: &DynamicImage,
+        protocol: ImageProtocol,
+        max_cols: u16,
+        cell_px: (u32, u32),
+    ) -> Option<Self> {
+        let (cols, rows, width_px, height_px) =
+            fit_to_cells(image.dimensions(), cell_px, max_cols, MAX_IMAGE_ROWS)?;
+        let resized = image.resize_exact(width_px, height_px, FilterType::Triangle);
+        let payload = match protocol {
+            ImageProtocol::Kitty => kitty_payload(&png_bytes(&resized)?, cols, rows),
+            ImageProtocol::Iterm2 => iterm2_payload(&png_bytes(&resized)?, cols, rows),
+            ImageProtocol::Sixel => sixel_payload(&resized),
+        };
+        Some(Self {
+            protocol,
+            cols,
+            rows,
+            payload,
+        })
+    }
+
+    pub(crate) fn rows(&self) -> u16 {
+        self.rows
+    }
+
+    /// Escape sequence that draws the image with its top-left at the cursor.
+    pub(crate) fn payload(&self) -> &str {
+        &self.payload
+    }
+}
+
+fn cell_size_px() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0 => (
+            u32::from(size.width) / u32::from(size.columns),
+            u32::from(size.height) / u32::from(size.rows),
+        ),
+        _ => FALLBACK_CELL_PX,
+    }
+}
+
+/// Scales `(width, height)` pixels down to fit the cell budget, keeping the
+/// aspect ratio. Returns `(cols, rows, width_px, height_px)`.
+fn fit_to_cells(
+    (width, height): (u32, u32),
+    (cell_width, cell_height): (u32, u32),
+    max_cols: u16,
+    max_rows: u16,
+) -> Option<(u16, u16, u32, u32)> {
+    if width == 0 || height == 0 || cell_width == 0 || cell_height == 0 || max_cols == 0 {
+        return None;
+    }
+    let max_width = f64::from(u32::from(max_cols) * cell_width);
+    let max_height = f64::from(u32::from(max_rows) * cell_height);
+    let scale = (max_width / f64::from(width))
+        .min(max_height / f64::from(height))
+        .min(1.0);
+    let width_px = ((f64::from(width) * scale).round() as u32).max(1);
+    let height_px = ((f64::from(height) * scale).round() as u32).max(1);
+    let cols = width_px.div_ceil(cell_width).min(u32::from(max_cols)) as u16;
+    let rows = height_px.div_ceil(cell_height).min(u32::from(max_rows)) as u16;
+    Some((cols, rows, width_px, height_px))
+}
+
+fn png_bytes(image: &DynamicImage) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+        .map_err(|err| tracing::debug!("failed to encode image as png: {err}"))
+        .ok()?;
+    Some(bytes)
+}
+
+fn kitty_payload(png: &[u8], cols: u16, rows: u16) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png);
+    let chunks: Vec<&[u8]> = encoded.as_bytes().chunks(KITTY_CHUNK_LEN).collect();
+    let mut payload = String::new();
+    for (idx, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(idx + 1 < chunks.len());
+        let chunk = String::from_utf8_lossy(chunk);
+        if idx == 0 {
+            // C=1 keeps the cursor in place; q=2 silences the terminal's replies.
+            let _ = write!(
+                payload,
+                "\x1b_Ga=T,f=100,c={cols},r={rows},C=1,q=2,m={more};{chunk}\x1b\\"
+            );
+        } else {
+            let _ = write!(payload, "\x1b_Gm={more};{chunk}\x1b\\");
+        }
+    }
+    payload
+}
+
+fn iterm2_payload(png: &[u8], cols: u16, rows: u16) -> String {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(png);
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={cols};height={rows};preserveAspectRatio=1:{encoded}\x07",
+        png.len()
+    )
+}
+
+/// Encodes `image` as sixels using the 6x6x6 color cube.
+fn sixel_payload(image: &DynamicImage) -> String {
+    let rgb = image.to_rgb8();
+    let (width, height) = rgb.dimensions();
+    let palette_index = |x: u32, y: u32| -> usize {
+        let [r, g, b] = rgb.get_pixel(x, y).0;
+        let level = |c: u8| usize::from(c) * 5 / 255;
+        level(r) * 36 + level(g) * 6 + level(b)
+    };
+
+    let mut payload = format!("\x1bPq\"1;1;{width};{height}");
+    for idx in 0..216 {
+        let percent = |level: usize| level * 100 / 5;
+        let _ = write!(
+            payload,
+            "#{idx};2;{};{};{}",
+            percent(idx / 36),
+            percent(idx / 6 % 6),
+            percent(idx % 6)
+        );
+    }
+
+    for band_top in (0..height).step_by(6) {
+        let band_rows = band_top..(band_top + 6).min(height);
+        let mut columns: Vec<[u8; 216]> = vec![[0; 216]; width as usize];
+        let mut used = [false; 216];
+        for y in band_rows {
+            for x in 0..width {
+                let idx = palette_index(x, y);
+                columns[x as usize][idx] |= 1 << (y - band_top);
+                used[idx] = true;
+            }
+        }
+        for (idx, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+            let _ = write!(payload, "#{idx}");
+            let bits = columns.iter().map(|column| column[idx]);
+            push_sixel_runs(&mut payload, bits);
+            payload.push('$');
+        }
+        payload.push('-');
+    }
+    payload.push_str("\x1b\\");
+    payload
+}
+
+/// Appends one color's sixel row, run-length encoding repeated columns.
+fn push_sixel_runs(payload: &mut String, bits: impl Iterator<Item = u8>) {
+    let mut run: Option<(u8, usize)> = None;
+    for bits in bits {
+        run = match run {
+            Some((current, count)) if current == bits => Some((current, count + 1)),
+            Some((current, count)) => {
+                push_sixel_run(payload, current, count);
+                Some((bits, 1))
+            }
+            None => Some((bits, 1)),
+        };
+    }
+    if let Some((current, count)) = run {
+        push_sixel_run(payload, current, count);
+    }
+}
+
+fn push_sixel_run(payload: &mut String, bits: u8, count: usize) {
+    let ch = char::from(0x3f + bits);
+    if count > 3 {
+        let _ = write!(payload, "!{count}{ch}");
+    } else {
+        payload.extend(std::iter::repeat_n(ch, count));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgb;
+    use image::RgbImage;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn detect(vars: &[(&str, &str)]) -> Option<ImageProtocol> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+            .collect();
+        ImageProtocol::from_env(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn detects_protocol_from_environment() {
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty")]),
+            Some(ImageProtocol::Kitty)
+        );
+        assert_eq!(
+            detect(&[("TERM_PROGRAM", "iTerm.app")]),
+            Some(ImageProtocol::Iterm2)
+        );
+        assert_eq!(detect(&[("TERM", "foot")]), Some(ImageProtocol::Sixel));
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), None);
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux-1000/default")]),
+            None
+        );
+    }
+
+    #[test]
+    fn fit_to_cells_preserves_aspect_ratio_within_budget() {
+        // 800x400 px into 8x16 px cells, at most 60 columns and 20 rows.
+        assert_eq!(
+            fit_to_cells((800, 400), (8, 16), 60, 20),
+            Some((60, 15, 480, 240))
+        );
+        // Small images are not upscaled.
+        assert_eq!(
+            fit_to_cells((16, 16), (8, 16), 60, 20),
+            Some((2, 1, 16, 16))
+        );
+        assert_eq!(fit_to_cells((0, 16), (8, 16), 60, 20), None);
+    }
+
+    #[test]
+    fn kitty_payload_is_chunked() {
+        let png = vec![0_u8; KITTY_CHUNK_LEN];
+        let payload = kitty_payload(&png, 4, 2);
+
+        assert!(payload.starts_with("\x1b_Ga=T,f=100,c=4,r=2,C=1,q=2,m=1;"));
+        assert!(payload.ends_with("\x1b\\"));
+        assert_eq!(payload.matches("\x1b_G").count(), 2);
+        assert!(payload.contains("\x1b_Gm=0;"));
+    }
+
+    #[test]
+    fn sixel_payload_encodes_runs_per_color() {
+        let image = RgbImage::from_fn(5, 1, |x, _| {
+            if x == 0 {
+                Rgb([255, 0, 0])
+            } else {
+                Rgb([0, 0, 0])
+            }
+        });
+        let payload = sixel_payload(&DynamicImage::ImageRgb8(image));
+
+        assert!(payload.starts_with("\x1bPq\"1;1;5;1#0;2;0;0;0"));
+        // Black covers the last four columns of the band; red only the first.
+        assert!(payload.ends_with("#0?!4@$#180@!4?$-\x1b\\"));
+    }
+}
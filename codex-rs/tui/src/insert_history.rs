@@ -2,6 +2,8 @@ use std::fmt;
 use std::io;
 use std::io::Write;
 
+use crate::image_render::IMAGE_INDENT;
+use crate::image_render::InlineImage;
 use crate::wrapping::word_wrap_lines_borrowed;
 use crossterm::Command;
 use crossterm::cursor::MoveTo;
@@ -134,6 +136,32 @@ where
     Ok(())
 }
 
+/// Insert `image` above the viewport: its rows are reserved as blank history
+/// lines and the protocol payload is then drawn over them, indented to line up
+/// with tool-call output.
+pub(crate) fn insert_history_image<B>(
+    terminal: &mut crate::custom_terminal::Terminal<B>,
+    image: &InlineImage,
+) -> io::Result<()>
+where
+    B: Backend + Write,
+{
+    let rows = image.rows();
+    insert_history_lines(terminal, vec![Line::from(""); usize::from(rows)])?;
+
+    // Inserting may have scrolled the viewport down; draw only if the reserved
+    // rows are all still on screen above it.
+    let Some(image_top) = terminal.viewport_area.top().checked_sub(rows) else {
+        return Ok(());
+    };
+    let last_cursor_pos = terminal.last_known_cursor_pos;
+    let writer = terminal.backend_mut();
+    queue!(writer, MoveTo(IMAGE_INDENT, image_top))?;
+    queue!(writer, Print(image.payload()))?;
+    queue!(writer, MoveTo(last_cursor_pos.x, last_cursor_pos.y))?;
+    Ok(())
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SetScrollRegion(pub std::ops::Range<u16>);
 
@@ -1,9 +1,9 @@
+use crate::theme::ThemeStylize;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
 use crossterm::event::KeyModifiers;
 use ratatui::style::Style;
-use ratatui::style::Stylize;
 use ratatui::text::Span;
 
 #[cfg(test)]
@@ -80,6 +80,7 @@ impl From<&KeyBinding> for Span<'static> {
             KeyCode::Right => "→".to_string(),
             KeyCode::PageUp => "pgup".to_string(),
             KeyCode::PageDown => "pgdn".to_string(),
+            KeyCode::BackTab => "shift + tab".to_string(),
             _ => format!("{key}").to_ascii_lowercase(),
         };
         Span::styled(format!("{modifiers}{key}"), key_hint_style())
@@ -87,7 +88,7 @@ impl From<&KeyBinding> for Span<'static> {
 }
 
 fn key_hint_style() -> Style {
-    Style::default().dim()
+    Style::default().secondary()
 }
 
 pub(crate) fn has_ctrl_or_alt(mods: KeyModifiers) -> bool {
@@ -0,0 +1,362 @@
+//! Configurable key bindings for TUI actions.
+//!
+//! `[tui.keys]` in `config.toml` maps action names to key chords, e.g.
+//! `command_palette = "ctrl+k"`. Entries that do not parse, name an unknown
+//! action, use a chord the composer depends on, or collide with another
+//! action fall back to the default binding and produce a warning shown at
+//! startup and in `/keys`.
+
+use std::collections::BTreeMap;
+
+use crossterm::event::KeyCode;
+use crossterm::event::KeyEvent;
+use crossterm::event::KeyModifiers;
+use ratatui::text::Span;
+use strum::IntoEnumIterator;
+use strum_macros::AsRefStr;
+use strum_macros::EnumIter;
+use strum_macros::EnumString;
+
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+
+/// Actions whose keys can be remapped. The serialized names are the keys
+/// accepted in `[tui.keys]`; enum order is the order `/keys` lists them in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter, EnumString, AsRefStr)]
+#[strum(serialize_all = "snake_case")]
+pub(crate) enum KeyAction {
+    CycleMode,
+    EditQueuedMessage,
+    CommandPalette,
+    ExplorationMode,
+    OpenOutput,
+    Transcript,
+    TranscriptSearch,
+    PauseFollow,
+}
+
+impl KeyAction {
+    fn default_binding(self) -> KeyBinding {
+        match self {
+            KeyAction::CycleMode => key_hint::plain(KeyCode::BackTab),
+            KeyAction::EditQueuedMessage => key_hint::alt(KeyCode::Up),
+            KeyAction::CommandPalette => key_hint::ctrl(KeyCode::Char('p')),
+            KeyAction::ExplorationMode => key_hint::ctrl(KeyCode::Char('x')),
+            KeyAction::OpenOutput => key_hint::ctrl(KeyCode::Char('o')),
+            KeyAction::Transcript => key_hint::ctrl(KeyCode::Char('t')),
+            KeyAction::TranscriptSearch => key_hint::ctrl(KeyCode::Char('f')),
+            KeyAction::PauseFollow => key_hint::ctrl(KeyCode::Char('s')),
+        }
+    }
+
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            KeyAction::CycleMode => "cycle collaboration modes",
+            KeyAction::EditQueuedMessage => "edit the last queued message",
+            KeyAction::CommandPalette => "open the command palette",
+            KeyAction::ExplorationMode => "toggle read-only exploration mode",
+            KeyAction::OpenOutput => "open running command output or the last image",
+            KeyAction::Transcript => "open the transcript",
+            KeyAction::TranscriptSearch => "search the transcript",
+            KeyAction::PauseFollow => "pause or resume following output",
+        }
+    }
+}
+
+/// Chords the composer and the quit/paste handling rely on; binding an
+/// action to one of these would make the original behavior unreachable.
+const RESERVED: &[KeyBinding] = &[
+    key_hint::ctrl(KeyCode::Char('c')),
+    key_hint::ctrl(KeyCode::Char('d')),
+    key_hint::ctrl(KeyCode::Char('v')),
+    key_hint::ctrl(KeyCode::Char('z')),
+    key_hint::ctrl(KeyCode::Char('j')),
+    key_hint::plain(KeyCode::Enter),
+    key_hint::plain(KeyCode::Esc),
+    key_hint::plain(KeyCode::Tab),
+    key_hint::plain(KeyCode::Backspace),
+];
+
+/// Effective bindings after applying `[tui.keys]` over the defaults.
+#[derive(Debug, Clone)]
+pub(crate) struct Keymap {
+    /// `None` when the action was unbound with `"none"`.
+    bindings: BTreeMap<KeyAction, Option<KeyBinding>>,
+    warnings: Vec<String>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::new(&BTreeMap::new())
+    }
+}
+
+impl Keymap {
+    pub(crate) fn new(overrides: &BTreeMap<String, String>) -> Self {
+        let mut bindings: BTreeMap<KeyAction, Option<KeyBinding>> = KeyAction::iter()
+            .map(|action| (action, Some(action.default_binding())))
+            .collect();
+        let mut overridden = Vec::new();
+        let mut warnings = Vec::new();
+
+        for (name, chord) in overrides {
+            let Ok(action) = name.parse::<KeyAction>() else {
+                warnings.push(format!("Unknown action `{name}` in [tui.keys]; ignored."));
+                continue;
+            };
+            let binding = if chord.trim().eq_ignore_ascii_case("none") {
+                None
+            } else {
+                match parse_chord(chord) {
+                    Ok(binding) => Some(binding),
+                    Err(err) => {
+                        warnings.push(format!("[tui.keys] {name} = \"{chord}\": {err}."));
+                        continue;
+                    }
+                }
+            };
+            if let Some(binding) = binding
+                && RESERVED.contains(&binding)
+            {
+                warnings.push(format!(
+                    "[tui.keys] {name} = \"{chord}\": {} is reserved; keeping {}.",
+                    label(&binding),
+                    label(&action.default_binding())
+                ));
+                continue;
+            }
+            bindings.insert(action, binding);
+            overridden.push(action);
+        }
+
+        // Resolve collisions by reverting the overrides involved. Reverting
+        // can expose a collision with another override, so repeat until
+        // every chord belongs to at most one action.
+        while let Some((first, second)) = find_conflict(&bindings) {
+            let involved: Vec<KeyAction> = [first, second]
+                .into_iter()
+                .filter(|action| overridden.contains(action))
+                .collect();
+            if involved.is_empty() {
+                break;
+            }
+            for action in involved {
+                overridden.retain(|other| *other != action);
+                bindings.insert(action, Some(action.default_binding()));
+                let other = if action == first { second } else { first };
+                warnings.push(format!(
+                    "[tui.keys] {} conflicts with {}; keeping {}.",
+                    action.as_ref(),
+                    other.as_ref(),
+                    label(&action.default_binding())
+                ));
+            }
+        }
+
+        Self { bindings, warnings }
+    }
+
+    /// Whether `event` is a press of the chord bound to `action`.
+    pub(crate) fn matches(&self, action: KeyAction, event: KeyEvent) -> bool {
+        let Some(Some(binding)) = self.bindings.get(&action) else {
+            return false;
+        };
+        binding.is_press(normalize(event))
+    }
+
+    /// Every action with its effective binding, in presentation order.
+    pub(crate) fn bindings(&self) -> impl Iterator<Item = (KeyAction, Option<KeyBinding>)> + '_ {
+        self.bindings
+            .iter()
+            .map(|(action, binding)| (*action, *binding))
+    }
+
+    pub(crate) fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+}
+
+fn find_conflict(
+    bindings: &BTreeMap<KeyAction, Option<KeyBinding>>,
+) -> Option<(KeyAction, KeyAction)> {
+    let bound: Vec<(KeyAction, KeyBinding)> = bindings
+        .iter()
+        .filter_map(|(action, binding)| binding.map(|binding| (*action, binding)))
+        .collect();
+    bound.iter().enumerate().find_map(|(i, (first, binding))| {
+        bound[i + 1..]
+            .iter()
+            .find(|(_, other)| other == binding)
+            .map(|(second, _)| (*first, *second))
+    })
+}
+
+/// Terminals disagree on whether Shift+Tab carries the SHIFT modifier, and
+/// report Shift+letter as an uppercase character with or without it.
+fn normalize(mut event: KeyEvent) -> KeyEvent {
+    match event.code {
+        KeyCode::BackTab => event.modifiers.remove(KeyModifiers::SHIFT),
+        KeyCode::Char(c)
+            if c.is_ascii_uppercase()
+                || (c.is_ascii_lowercase() && event.modifiers.contains(KeyModifiers::SHIFT)) =>
+        {
+            event.code = KeyCode::Char(c.to_ascii_uppercase());
+            event.modifiers.insert(KeyModifiers::SHIFT);
+        }
+        _ => {}
+    }
+    event
+}
+
+/// Parse a chord such as `ctrl+k`, `alt+up`, or `shift+tab`.
+fn parse_chord(chord: &str) -> Result<KeyBinding, String> {
+    let parts: Vec<String> = chord
+        .split('+')
+        .map(|part| part.trim().to_ascii_lowercase())
+        .collect();
+    let Some((key, modifier_names)) = parts.split_last() else {
+        return Err("empty key chord".to_string());
+    };
+
+    let mut modifiers = KeyModifiers::NONE;
+    for name in modifier_names {
+        modifiers |= match name.as_str() {
+            "ctrl" | "control" => KeyModifiers::CONTROL,
+            "alt" | "option" | "meta" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            other => return Err(format!("unknown modifier `{other}`")),
+        };
+    }
+
+    let code = match key.as_str() {
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "enter" | "return" => KeyCode::Enter,
+        "esc" | "escape" => KeyCode::Esc,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "space" => KeyCode::Char(' '),
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pgup" | "pageup" => KeyCode::PageUp,
+        "pgdn" | "pagedown" => KeyCode::PageDown,
+        "delete" | "del" => KeyCode::Delete,
+        "backspace" => KeyCode::Backspace,
+        other => {
+            let mut chars = other.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => KeyCode::Char(c),
+                (Some('f'), Some(_)) => other[1..]
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|n| (1..=12).contains(n))
+                    .map(KeyCode::F)
+                    .ok_or_else(|| format!("unknown key `{other}`"))?,
+                _ => return Err(format!("unknown key `{other}`")),
+            }
+        }
+    };
+
+    Ok(match code {
+        // Shift+Tab arrives as BackTab; store it the way it is matched.
+        KeyCode::Tab | KeyCode::BackTab if modifiers.contains(KeyModifiers::SHIFT) => {
+            KeyBinding::new(KeyCode::BackTab, modifiers - KeyModifiers::SHIFT)
+        }
+        KeyCode::BackTab => KeyBinding::new(KeyCode::BackTab, modifiers),
+        KeyCode::Char(c) if modifiers.contains(KeyModifiers::SHIFT) => {
+            KeyBinding::new(KeyCode::Char(c.to_ascii_uppercase()), modifiers)
+        }
+        code => KeyBinding::new(code, modifiers),
+    })
+}
+
+/// Human-readable chord, matching the footer's key hints.
+pub(crate) fn label(binding: &KeyBinding) -> String {
+    Span::from(binding).content.into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyEventKind;
+    use pretty_assertions::assert_eq;
+
+    fn keys(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(action, chord)| (action.to_string(), chord.to_string()))
+            .collect()
+    }
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new_with_kind(code, modifiers, KeyEventKind::Press)
+    }
+
+    #[test]
+    fn parses_chords() {
+        assert_eq!(
+            parse_chord("Ctrl+K"),
+            Ok(key_hint::ctrl(KeyCode::Char('k')))
+        );
+        assert_eq!(parse_chord("alt + up"), Ok(key_hint::alt(KeyCode::Up)));
+        assert_eq!(
+            parse_chord("shift+tab"),
+            Ok(key_hint::plain(KeyCode::BackTab))
+        );
+        assert_eq!(parse_chord("f5"), Ok(key_hint::plain(KeyCode::F(5))));
+        assert!(parse_chord("hyper+k").is_err());
+        assert!(parse_chord("ctrl+nope").is_err());
+    }
+
+    #[test]
+    fn overrides_defaults_and_matches_events() {
+        let keymap = Keymap::new(&keys(&[
+            ("command_palette", "ctrl+k"),
+            ("exploration_mode", "none"),
+        ]));
+        assert!(keymap.warnings().is_empty());
+        assert!(keymap.matches(
+            KeyAction::CommandPalette,
+            press(KeyCode::Char('k'), KeyModifiers::CONTROL)
+        ));
+        assert!(!keymap.matches(
+            KeyAction::CommandPalette,
+            press(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        ));
+        assert!(!keymap.matches(
+            KeyAction::ExplorationMode,
+            press(KeyCode::Char('x'), KeyModifiers::CONTROL)
+        ));
+        assert!(keymap.matches(
+            KeyAction::CycleMode,
+            press(KeyCode::BackTab, KeyModifiers::SHIFT)
+        ));
+    }
+
+    #[test]
+    fn conflicting_and_invalid_entries_keep_defaults() {
+        let keymap = Keymap::new(&keys(&[
+            ("command_palette", "ctrl+t"),
+            ("exploration_mode", "ctrl+c"),
+            ("launch_rockets", "ctrl+r"),
+        ]));
+        assert!(keymap.matches(
+            KeyAction::CommandPalette,
+            press(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        ));
+        assert!(keymap.matches(
+            KeyAction::ExplorationMode,
+            press(KeyCode::Char('x'), KeyModifiers::CONTROL)
+        ));
+        assert_eq!(
+            keymap.warnings(),
+            [
+                "[tui.keys] exploration_mode = \"ctrl+c\": ctrl + c is reserved; keeping ctrl + x.",
+                "Unknown action `launch_rockets` in [tui.keys]; ignored.",
+                "[tui.keys] command_palette conflicts with transcript; keeping ctrl + p.",
+            ]
+        );
+    }
+}
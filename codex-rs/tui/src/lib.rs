@@ -22,6 +22,7 @@ use codex_core::config::resolve_oss_provider;
 use codex_core::find_conversation_path_by_id_str;
 use codex_core::get_platform_sandbox;
 use codex_core::protocol::AskForApproval;
+use codex_core::sessions;
 use codex_protocol::config_types::SandboxMode;
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use std::fs::OpenOptions;
@@ -48,11 +49,14 @@ mod diff_render;
 mod exec_cell;
 mod exec_command;
 mod file_search;
+mod file_watch;
 mod frames;
 mod get_git_diff;
 mod history_cell;
+mod image_render;
 pub mod insert_history;
 mod key_hint;
+mod keymap;
 pub mod live_wrap;
 mod markdown;
 mod markdown_render;
@@ -61,6 +65,7 @@ mod model_migration;
 pub mod onboarding;
 mod oss_selection;
 mod pager_overlay;
+mod prompt_template_history;
 pub mod public_widgets;
 mod render;
 mod resume_picker;
@@ -73,9 +78,14 @@ mod status;
 mod status_indicator_widget;
 mod streaming;
 mod style;
+mod terminal_notification;
 mod terminal_palette;
+mod theme;
 mod text_formatting;
+mod transcript_search;
 mod tui;
+mod turn_diff_pane;
+mod turn_summary;
 mod ui_consts;
 pub mod update_action;
 mod update_prompt;
@@ -429,8 +439,33 @@ async fn run_ratatui_app(
         initial_config
     };
 
-    // Determine resume behavior: explicit id, then resume last, then picker.
-    let resume_selection = if let Some(id_str) = cli.resume_session_id.as_deref() {
+    // Archive or delete old rollouts before listing sessions to resume.
+    let codex_home = config.codex_home.clone();
+    let retention = config.session_retention;
+    match tokio::task::spawn_blocking(move || {
+        sessions::apply_retention(&codex_home, &retention, std::time::SystemTime::now())
+    })
+    .await
+    {
+        Ok(Ok(report)) if !report.archived.is_empty() || !report.deleted.is_empty() => {
+            tracing::info!(
+                "session retention archived {} and deleted {} rollouts",
+                report.archived.len(),
+                report.deleted.len()
+            );
+        }
+        Ok(Ok(_)) => {}
+        Ok(Err(err)) => tracing::warn!("failed to apply session retention: {err}"),
+        Err(err) => tracing::warn!("session retention task failed: {err}"),
+    }
+
+    // Determine resume behavior: replay, explicit id, then resume last, then picker.
+    let resume_selection = if let Some(path) = cli.replay_rollout.clone() {
+        resume_picker::ResumeSelection::Replay {
+            path,
+            speed: cli.replay_speed,
+        }
+    } else if let Some(id_str) = cli.resume_session_id.as_deref() {
         match find_conversation_path_by_id_str(&config.codex_home, id_str).await? {
             Some(path) => resume_picker::ResumeSelection::Resume(path),
             None => {
@@ -622,7 +657,7 @@ mod tests {
             temp_dir.path().to_path_buf(),
         )?;
         config.did_user_set_custom_approval_policy_or_sandbox_mode = false;
-        config.active_project = ProjectConfig { trust_level: None };
+        config.active_project = ProjectConfig::default();
         config.set_windows_sandbox_globally(false);
 
         let should_show = should_show_trust_screen(&config);
@@ -649,7 +684,7 @@ mod tests {
             temp_dir.path().to_path_buf(),
         )?;
         config.did_user_set_custom_approval_policy_or_sandbox_mode = false;
-        config.active_project = ProjectConfig { trust_level: None };
+        config.active_project = ProjectConfig::default();
         config.set_windows_sandbox_globally(true);
 
         let should_show = should_show_trust_screen(&config);
@@ -678,6 +713,7 @@ mod tests {
         config.did_user_set_custom_approval_policy_or_sandbox_mode = false;
         config.active_project = ProjectConfig {
             trust_level: Some(TrustLevel::Untrusted),
+            ..Default::default()
         };
 
         let should_show = should_show_trust_screen(&config);
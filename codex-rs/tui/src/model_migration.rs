@@ -4,6 +4,7 @@ use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt as _;
 use crate::selection_list::selection_option_row;
+use crate::theme::ThemeStylize;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
 use crate::tui::TuiEvent;
@@ -267,13 +268,13 @@ impl WidgetRef for &ModelMigrationScreen {
             column.push(Line::from(""));
             column.push(
                 Line::from(vec![
-                    "Use ".dim(),
+                    "Use ".secondary(),
                     key_hint::plain(KeyCode::Up).into(),
-                    "/".dim(),
+                    "/".secondary(),
                     key_hint::plain(KeyCode::Down).into(),
-                    " to move, press ".dim(),
+                    " to move, press ".secondary(),
                     key_hint::plain(KeyCode::Enter).into(),
-                    " to confirm".dim(),
+                    " to confirm".secondary(),
                 ])
                 .inset(Insets::tlbr(0, 2, 0, 0)),
             );
@@ -308,7 +309,7 @@ fn gpt5_migration_copy() -> ModelMigrationCopy {
             Line::from(
                 "You can continue using legacy models by specifying them directly with the -m option or in your config.toml.",
             ),
-            Line::from(vec!["Press enter to continue".dim()]),
+            Line::from(vec!["Press enter to continue".secondary()]),
         ],
         can_opt_out: false,
     }
@@ -17,7 +17,6 @@ use ratatui::layout::Constraint;
 use ratatui::layout::Layout;
 use ratatui::layout::Rect;
 use ratatui::prelude::Widget;
-use ratatui::style::Color;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -37,6 +36,7 @@ use crate::LoginStatus;
 use crate::onboarding::onboarding_screen::KeyboardHandler;
 use crate::onboarding::onboarding_screen::StepStateProvider;
 use crate::shimmer::shimmer_spans;
+use crate::theme::ThemeStylize;
 use crate::tui::FrameRequester;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -197,8 +197,8 @@ impl AuthModeWidget {
 
             let line1 = if is_selected {
                 Line::from(vec![
-                    format!("{} {}. ", caret, idx + 1).cyan().dim(),
-                    text.to_string().cyan(),
+                    format!("{} {}. ", caret, idx + 1).accent().secondary(),
+                    text.to_string().accent(),
                 ])
             } else {
                 format!("  {}. {text}", idx + 1).into()
@@ -206,7 +206,7 @@ impl AuthModeWidget {
 
             let line2 = if is_selected {
                 Line::from(format!("     {description}"))
-                    .fg(Color::Cyan)
+                    .accent()
                     .add_modifier(Modifier::DIM)
             } else {
                 Line::from(format!("     {description}"))
@@ -239,7 +239,7 @@ impl AuthModeWidget {
         } else {
             lines.push(
                 "  API key login is disabled by this workspace. Sign in with ChatGPT to continue."
-                    .dim()
+                    .secondary()
                     .into(),
             );
             lines.push("".into());
@@ -247,11 +247,11 @@ impl AuthModeWidget {
         lines.push(
             // AE: Following styles.md, this should probably be Cyan because it's a user input tip.
             //     But leaving this for a future cleanup.
-            "  Press Enter to continue".dim().into(),
+            "  Press Enter to continue".secondary().into(),
         );
         if let Some(err) = &self.error {
             lines.push("".into());
-            lines.push(err.as_str().red().into());
+            lines.push(err.as_str().error().into());
         }
 
         Paragraph::new(lines)
@@ -277,11 +277,11 @@ impl AuthModeWidget {
         {
             lines.push("  If the link doesn't open automatically, open the following link to authenticate:".into());
             lines.push("".into());
-            lines.push(Line::from(state.auth_url.as_str().cyan().underlined()));
+            lines.push(Line::from(state.auth_url.as_str().accent().underlined()));
             lines.push("".into());
         }
 
-        lines.push("  Press Esc to cancel".dim().into());
+        lines.push("  Press Esc to cancel".secondary().into());
         Paragraph::new(lines)
             .wrap(Wrap { trim: false })
             .render(area, buf);
@@ -289,7 +289,7 @@ impl AuthModeWidget {
 
     fn render_chatgpt_success_message(&self, area: Rect, buf: &mut Buffer) {
         let lines = vec![
-            "✓ Signed in with your ChatGPT account".fg(Color::Green).into(),
+            "✓ Signed in with your ChatGPT account".success().into(),
             "".into(),
             "  Before you start:".into(),
             "".into(),
@@ -298,19 +298,19 @@ impl AuthModeWidget {
                 "  For more details see the ".into(),
                 "\u{1b}]8;;https://github.com/jayasuryajsk/codex-kaioken\u{7}Codex Kaioken docs\u{1b}]8;;\u{7}".underlined(),
             ])
-            .dim(),
+            .secondary(),
             "".into(),
             "  Codex Kaioken can make mistakes".into(),
-            "  Review the code it writes and commands it runs".dim().into(),
+            "  Review the code it writes and commands it runs".secondary().into(),
             "".into(),
             "  Powered by your ChatGPT account".into(),
             Line::from(vec![
                 "  Uses your plan's rate limits and ".into(),
                 "\u{1b}]8;;https://chatgpt.com/#settings\u{7}training data preferences\u{1b}]8;;\u{7}".underlined(),
             ])
-            .dim(),
+            .secondary(),
             "".into(),
-            "  Press Enter to continue".fg(Color::Cyan).into(),
+            "  Press Enter to continue".accent().into(),
         ];
 
         Paragraph::new(lines)
@@ -319,11 +319,7 @@ impl AuthModeWidget {
     }
 
     fn render_chatgpt_success(&self, area: Rect, buf: &mut Buffer) {
-        let lines = vec![
-            "✓ Signed in with your ChatGPT account"
-                .fg(Color::Green)
-                .into(),
-        ];
+        let lines = vec!["✓ Signed in with your ChatGPT account".success().into()];
 
         Paragraph::new(lines)
             .wrap(Wrap { trim: false })
@@ -332,7 +328,7 @@ impl AuthModeWidget {
 
     fn render_api_key_configured(&self, area: Rect, buf: &mut Buffer) {
         let lines = vec![
-            "✓ API key configured".fg(Color::Green).into(),
+            "✓ API key configured".success().into(),
             "".into(),
             "  Codex Kaioken will use usage-based billing with your API key.".into(),
         ];
@@ -363,7 +359,7 @@ impl AuthModeWidget {
             intro_lines.push("  Detected OPENAI_API_KEY environment variable.".into());
             intro_lines.push(
                 "  Paste a different key if you prefer to use another account."
-                    .dim()
+                    .secondary()
                     .into(),
             );
             intro_lines.push("".into());
@@ -373,7 +369,7 @@ impl AuthModeWidget {
             .render(intro_area, buf);
 
         let content_line: Line = if state.value.is_empty() {
-            vec!["Paste or type your API key".dim()].into()
+            vec!["Paste or type your API key".secondary()].into()
         } else {
             Line::from(state.value.clone())
         };
@@ -384,17 +380,17 @@ impl AuthModeWidget {
                     .title("API key")
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(Color::Cyan)),
+                    .border_style(Style::default().accent()),
             )
             .render(input_area, buf);
 
         let mut footer_lines: Vec<Line> = vec![
-            "  Press Enter to save".dim().into(),
-            "  Press Esc to go back".dim().into(),
+            "  Press Enter to save".secondary().into(),
+            "  Press Esc to go back".secondary().into(),
         ];
         if let Some(error) = &self.error {
             footer_lines.push("".into());
-            footer_lines.push(error.as_str().red().into());
+            footer_lines.push(error.as_str().error().into());
         }
         Paragraph::new(footer_lines)
             .wrap(Wrap { trim: false })
@@ -37,6 +37,8 @@ use ratatui::widgets::WidgetRef;
 use ratatui::widgets::Wrap;
 use std::time::Duration;
 
+use crate::theme;
+
 #[derive(Clone)]
 struct ProviderOption {
     name: String,
@@ -277,8 +279,8 @@ impl WidgetRef for &OssSelectionWidget<'_> {
 
 fn get_status_symbol_and_color(status: &ProviderStatus) -> (&'static str, Color) {
     match status {
-        ProviderStatus::Running => ("●", Color::Green),
-        ProviderStatus::NotRunning => ("○", Color::Red),
+        ProviderStatus::Running => ("●", theme::current().success),
+        ProviderStatus::NotRunning => ("○", theme::current().error),
         ProviderStatus::Unknown => ("?", Color::Yellow),
     }
 }
@@ -2,6 +2,7 @@ use std::io::Result;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::exec_cell::LiveOutput;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::UserHistoryCell;
 use crate::key_hint;
@@ -10,8 +11,15 @@ use crate::render::Insets;
 use crate::render::renderable::InsetRenderable;
 use crate::render::renderable::Renderable;
 use crate::style::user_message_style;
+use crate::theme::ThemeStylize;
+use crate::transcript_search::SearchIndex;
+use crate::transcript_search::SearchMatch;
+use crate::transcript_search::SearchMode;
+use crate::transcript_search::SearchQuery;
+use crate::transcript_search::highlight_line;
 use crate::tui;
 use crate::tui::TuiEvent;
+use codex_ansi_escape::ansi_escape_line;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use ratatui::buffer::Buffer;
@@ -31,6 +39,7 @@ use ratatui::widgets::Wrap;
 pub(crate) enum Overlay {
     Transcript(TranscriptOverlay),
     Static(StaticOverlay),
+    ExecOutput(ExecOutputOverlay),
 }
 
 impl Overlay {
@@ -38,6 +47,13 @@ impl Overlay {
         Self::Transcript(TranscriptOverlay::new(cells))
     }
 
+    /// Transcript overlay that opens with the search prompt focused.
+    pub(crate) fn new_transcript_search(cells: Vec<Arc<dyn HistoryCell>>) -> Self {
+        let mut overlay = TranscriptOverlay::new(cells);
+        overlay.start_search();
+        Self::Transcript(overlay)
+    }
+
     pub(crate) fn new_static_with_lines(lines: Vec<Line<'static>>, title: String) -> Self {
         Self::Static(StaticOverlay::with_title(lines, title))
     }
@@ -49,10 +65,15 @@ impl Overlay {
         Self::Static(StaticOverlay::with_renderables(renderables, title))
     }
 
+    pub(crate) fn new_exec_output(call_id: String, command: String, output: LiveOutput) -> Self {
+        Self::ExecOutput(ExecOutputOverlay::new(call_id, command, output))
+    }
+
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match self {
             Overlay::Transcript(o) => o.handle_event(tui, event),
             Overlay::Static(o) => o.handle_event(tui, event),
+            Overlay::ExecOutput(o) => o.handle_event(tui, event),
         }
     }
 
@@ -60,6 +81,7 @@ impl Overlay {
         match self {
             Overlay::Transcript(o) => o.is_done(),
             Overlay::Static(o) => o.is_done(),
+            Overlay::ExecOutput(o) => o.is_done(),
         }
     }
 }
@@ -76,6 +98,14 @@ const KEY_ESC: KeyBinding = key_hint::plain(KeyCode::Esc);
 const KEY_ENTER: KeyBinding = key_hint::plain(KeyCode::Enter);
 const KEY_CTRL_T: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
 const KEY_CTRL_C: KeyBinding = key_hint::ctrl(KeyCode::Char('c'));
+const KEY_SLASH: KeyBinding = key_hint::plain(KeyCode::Char('/'));
+const KEY_CTRL_F: KeyBinding = key_hint::ctrl(KeyCode::Char('f'));
+const KEY_TAB: KeyBinding = key_hint::plain(KeyCode::Tab);
+const KEY_BACKSPACE: KeyBinding = key_hint::plain(KeyCode::Backspace);
+const KEY_N: KeyBinding = key_hint::plain(KeyCode::Char('n'));
+const KEY_UPPER_N: KeyBinding = key_hint::plain(KeyCode::Char('N'));
+const KEY_SHIFT_N: KeyBinding = key_hint::shift(KeyCode::Char('N'));
+const KEY_F: KeyBinding = key_hint::plain(KeyCode::Char('f'));
 
 // Common pager navigation hints rendered on the first line
 const PAGER_KEY_HINTS: &[(&[KeyBinding], &str)] = &[
@@ -102,7 +132,7 @@ fn render_key_hints(area: Rect, buf: &mut Buffer, pairs: &[(&[KeyBinding], &str)
         spans.push(Span::from(desc.to_string()));
         first = false;
     }
-    Paragraph::new(vec![Line::from(spans).dim()]).render_ref(area, buf);
+    Paragraph::new(vec![Line::from(spans).secondary()]).render_ref(area, buf);
 }
 
 /// Generic widget for rendering a pager view.
@@ -114,6 +144,8 @@ struct PagerView {
     last_rendered_height: Option<usize>,
     /// If set, on next render ensure this chunk is visible.
     pending_scroll_chunk: Option<usize>,
+    /// If set, on next render scroll to this row of this chunk.
+    pending_scroll_row: Option<(usize, usize)>,
 }
 
 impl PagerView {
@@ -125,6 +157,7 @@ impl PagerView {
             last_content_height: None,
             last_rendered_height: None,
             pending_scroll_chunk: None,
+            pending_scroll_row: None,
         }
     }
 
@@ -147,6 +180,9 @@ impl PagerView {
         if let Some(idx) = self.pending_scroll_chunk.take() {
             self.ensure_chunk_visible(idx, content_area);
         }
+        if let Some((idx, row)) = self.pending_scroll_row.take() {
+            self.ensure_row_visible(idx, row, content_area);
+        }
         self.scroll_offset = self
             .scroll_offset
             .min(content_height.saturating_sub(content_area.height as usize));
@@ -158,10 +194,10 @@ impl PagerView {
 
     fn render_header(&self, area: Rect, buf: &mut Buffer) {
         Span::from("/ ".repeat(area.width as usize / 2))
-            .dim()
+            .secondary()
             .render_ref(area, buf);
         let header = format!("/ {}", self.title);
-        header.dim().render_ref(area, buf);
+        header.secondary().render_ref(area, buf);
     }
 
     fn render_content(&self, area: Rect, buf: &mut Buffer) {
@@ -211,7 +247,7 @@ impl PagerView {
         let sep_rect = Rect::new(full_area.x, sep_y, full_area.width, 1);
 
         Span::from("─".repeat(sep_rect.width as usize))
-            .dim()
+            .secondary()
             .render_ref(sep_rect, buf);
         let percent = if total_len == 0 {
             100
@@ -228,7 +264,7 @@ impl PagerView {
         let pct_w = pct_text.chars().count() as u16;
         let pct_x = sep_rect.x + sep_rect.width - pct_w - 1;
         Span::from(pct_text)
-            .dim()
+            .secondary()
             .render_ref(Rect::new(pct_x, sep_rect.y, pct_w, 1), buf);
     }
 
@@ -320,6 +356,31 @@ impl PagerView {
             self.scroll_offset = last.saturating_sub(area.height.saturating_sub(1) as usize);
         }
     }
+
+    /// Request that a row of the given chunk be scrolled into view on next render.
+    fn scroll_row_into_view(&mut self, chunk_index: usize, row: usize) {
+        self.pending_scroll_row = Some((chunk_index, row));
+    }
+
+    /// Scrolls so that `row` of chunk `idx` sits a third of the way down the
+    /// viewport, unless it is already visible.
+    fn ensure_row_visible(&mut self, idx: usize, row: usize, area: Rect) {
+        if area.height == 0 || idx >= self.renderables.len() {
+            return;
+        }
+        let target = self
+            .renderables
+            .iter()
+            .take(idx)
+            .map(|r| r.desired_height(area.width) as usize)
+            .sum::<usize>()
+            + row;
+        let current_top = self.scroll_offset;
+        let current_bottom = current_top.saturating_add(area.height.saturating_sub(1) as usize);
+        if target < current_top || target > current_bottom {
+            self.scroll_offset = target.saturating_sub(area.height as usize / 3);
+        }
+    }
 }
 
 /// A renderable that caches its desired height.
@@ -356,12 +417,19 @@ impl Renderable for CachedRenderable {
 struct CellRenderable {
     cell: Arc<dyn HistoryCell>,
     style: Style,
+    search: Option<SearchQuery>,
 }
 
 impl Renderable for CellRenderable {
     fn render(&self, area: Rect, buf: &mut Buffer) {
-        let p =
-            Paragraph::new(Text::from(self.cell.transcript_lines(area.width))).style(self.style);
+        let mut lines = self.cell.transcript_lines(area.width);
+        if let Some(query) = &self.search {
+            lines = lines
+                .into_iter()
+                .map(|line| highlight_line(line, query))
+                .collect();
+        }
+        let p = Paragraph::new(Text::from(lines)).style(self.style);
         p.render(area, buf);
     }
 
@@ -374,19 +442,51 @@ pub(crate) struct TranscriptOverlay {
     view: PagerView,
     cells: Vec<Arc<dyn HistoryCell>>,
     highlight_cell: Option<usize>,
+    search: Option<TranscriptSearch>,
+    search_index: SearchIndex,
     is_done: bool,
 }
 
+/// Search state of the transcript overlay, from `/` (or Ctrl+F) until it is
+/// cleared with Esc.
+struct TranscriptSearch {
+    input: String,
+    mode: SearchMode,
+    /// Whether keys edit the query rather than move between matches.
+    editing: bool,
+    query: Option<SearchQuery>,
+    error: Option<String>,
+    matches: Vec<SearchMatch>,
+    /// Index into `matches` of the match last jumped to.
+    current: Option<usize>,
+}
+
+impl TranscriptSearch {
+    fn new(mode: SearchMode) -> Self {
+        Self {
+            input: String::new(),
+            mode,
+            editing: true,
+            query: None,
+            error: None,
+            matches: Vec::new(),
+            current: None,
+        }
+    }
+}
+
 impl TranscriptOverlay {
     pub(crate) fn new(transcript_cells: Vec<Arc<dyn HistoryCell>>) -> Self {
         Self {
             view: PagerView::new(
-                Self::render_cells(&transcript_cells, None),
+                Self::render_cells(&transcript_cells, None, None),
                 "T R A N S C R I P T".to_string(),
                 usize::MAX,
             ),
             cells: transcript_cells,
             highlight_cell: None,
+            search: None,
+            search_index: SearchIndex::default(),
             is_done: false,
         }
     }
@@ -394,6 +494,7 @@ impl TranscriptOverlay {
     fn render_cells(
         cells: &[Arc<dyn HistoryCell>],
         highlight_cell: Option<usize>,
+        search: Option<&SearchQuery>,
     ) -> Vec<Box<dyn Renderable>> {
         cells
             .iter()
@@ -408,14 +509,16 @@ impl TranscriptOverlay {
                         } else {
                             user_message_style()
                         },
+                        search: search.cloned(),
                     })) as Box<dyn Renderable>
                 } else {
                     Box::new(CachedRenderable::new(CellRenderable {
                         cell: c.clone(),
                         style: Style::default(),
+                        search: search.cloned(),
                     })) as Box<dyn Renderable>
                 };
-                if !c.is_stream_continuation() && i > 0 {
+                if Self::has_top_inset(c.as_ref(), i) {
                     cell_renderable = Box::new(InsetRenderable::new(
                         cell_renderable,
                         Insets::tlbr(1, 0, 0, 0),
@@ -427,10 +530,26 @@ impl TranscriptOverlay {
             .collect()
     }
 
+    /// Cells after the first are separated by a blank row unless they
+    /// continue a stream.
+    fn has_top_inset(cell: &dyn HistoryCell, index: usize) -> bool {
+        !cell.is_stream_continuation() && index > 0
+    }
+
+    fn rebuild_renderables(&mut self) {
+        let query = self
+            .search
+            .as_ref()
+            .and_then(|search| search.query.as_ref());
+        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell, query);
+    }
+
     pub(crate) fn insert_cell(&mut self, cell: Arc<dyn HistoryCell>) {
         let follow_bottom = self.view.is_scrolled_to_bottom();
+        self.search_index.push(cell.as_ref());
         self.cells.push(cell);
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.refresh_search_matches();
+        self.rebuild_renderables();
         if follow_bottom {
             self.view.scroll_offset = usize::MAX;
         }
@@ -438,22 +557,181 @@ impl TranscriptOverlay {
 
     pub(crate) fn set_highlight_cell(&mut self, cell: Option<usize>) {
         self.highlight_cell = cell;
-        self.view.renderables = Self::render_cells(&self.cells, self.highlight_cell);
+        self.rebuild_renderables();
         if let Some(idx) = self.highlight_cell {
             self.view.scroll_chunk_into_view(idx);
         }
     }
 
+    /// True while a search is shown, so Esc and Enter belong to the search
+    /// rather than to backtracking.
+    pub(crate) fn is_searching(&self) -> bool {
+        self.search.is_some()
+    }
+
+    /// Focuses the search prompt with an empty query.
+    pub(crate) fn start_search(&mut self) {
+        let mode = self
+            .search
+            .as_ref()
+            .map_or(SearchMode::Plain, |search| search.mode);
+        self.search = Some(TranscriptSearch::new(mode));
+        self.rebuild_renderables();
+    }
+
+    fn clear_search(&mut self) {
+        self.search = None;
+        self.rebuild_renderables();
+    }
+
+    /// Re-parses the query after an edit and highlights its matches.
+    fn update_search_query(&mut self) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        match SearchQuery::new(&search.input, search.mode) {
+            Ok(query) => {
+                search.query = query;
+                search.error = None;
+            }
+            Err(err) => {
+                search.query = None;
+                search.error = Some(err);
+            }
+        }
+        search.current = None;
+        self.refresh_search_matches();
+        self.rebuild_renderables();
+    }
+
+    fn refresh_search_matches(&mut self) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        search.matches = search
+            .query
+            .as_ref()
+            .map(|query| self.search_index.matches(query))
+            .unwrap_or_default();
+        search.current = search
+            .current
+            .map(|current| current.min(search.matches.len().saturating_sub(1)))
+            .filter(|_| !search.matches.is_empty());
+    }
+
+    /// Moves to the previous (older) match when `backward`, otherwise to the
+    /// next one, wrapping around. The first jump lands on the most recent match.
+    fn jump_to_match(&mut self, backward: bool) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+        let count = search.matches.len();
+        if count == 0 {
+            return;
+        }
+        let current = match search.current {
+            None => count - 1,
+            Some(current) if backward => (current + count - 1) % count,
+            Some(current) => (current + 1) % count,
+        };
+        search.current = Some(current);
+        let SearchMatch { cell, line } = search.matches[current];
+        let inset = usize::from(Self::has_top_inset(self.cells[cell].as_ref(), cell));
+        self.view.scroll_row_into_view(cell, inset + line);
+    }
+
+    fn handle_search_edit_key(&mut self, key_event: KeyEvent) {
+        match key_event {
+            e if KEY_ESC.is_press(e) => self.clear_search(),
+            e if KEY_ENTER.is_press(e) => {
+                if let Some(search) = self.search.as_mut() {
+                    search.editing = false;
+                }
+                self.jump_to_match(true);
+            }
+            e if KEY_TAB.is_press(e) => {
+                if let Some(search) = self.search.as_mut() {
+                    search.mode = search.mode.toggled();
+                }
+                self.update_search_query();
+            }
+            e if KEY_BACKSPACE.is_press(e) => {
+                if let Some(search) = self.search.as_mut() {
+                    search.input.pop();
+                }
+                self.update_search_query();
+            }
+            KeyEvent {
+                code: KeyCode::Char(c),
+                modifiers,
+                ..
+            } if !key_hint::has_ctrl_or_alt(modifiers) => {
+                if let Some(search) = self.search.as_mut() {
+                    search.input.push(c);
+                }
+                self.update_search_query();
+            }
+            _ => {}
+        }
+    }
+
+    fn render_search_status(&self, search: &TranscriptSearch, area: Rect, buf: &mut Buffer) {
+        let mut spans: Vec<Span<'static>> = vec![" ".into()];
+        spans.push(match search.mode {
+            SearchMode::Plain => "/".accent(),
+            SearchMode::Regex => "regex /".accent(),
+        });
+        spans.push(search.input.clone().into());
+        if search.editing {
+            spans.push("▏".secondary());
+        }
+        spans.push("   ".into());
+        if let Some(err) = &search.error {
+            spans.push(format!("invalid regex: {err}").error());
+        } else if search.query.is_some() {
+            let status = match (search.current, search.matches.len()) {
+                (_, 0) => "no matches".to_string(),
+                (Some(current), count) => format!("{}/{count}", current + 1),
+                (None, 1) => "1 match".to_string(),
+                (None, count) => format!("{count} matches"),
+            };
+            spans.push(status.secondary());
+        }
+        Paragraph::new(Line::from(spans)).render_ref(area, buf);
+    }
+
     fn render_hints(&self, area: Rect, buf: &mut Buffer) {
         let line1 = Rect::new(area.x, area.y, area.width, 1);
         let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
-        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        let Some(search) = &self.search else {
+            render_key_hints(line1, buf, PAGER_KEY_HINTS);
+            let mut pairs: Vec<(&[KeyBinding], &str)> = vec![
+                (&[KEY_Q], "to quit"),
+                (&[KEY_ESC], "to edit prev"),
+                (&[KEY_SLASH], "to search"),
+            ];
+            if self.highlight_cell.is_some() {
+                pairs.push((&[KEY_ENTER], "to edit message"));
+            }
+            render_key_hints(line2, buf, &pairs);
+            return;
+        };
 
-        let mut pairs: Vec<(&[KeyBinding], &str)> =
-            vec![(&[KEY_Q], "to quit"), (&[KEY_ESC], "to edit prev")];
-        if self.highlight_cell.is_some() {
-            pairs.push((&[KEY_ENTER], "to edit message"));
-        }
+        self.render_search_status(search, line1, buf);
+        let pairs: Vec<(&[KeyBinding], &str)> = if search.editing {
+            vec![
+                (&[KEY_ENTER], "to search"),
+                (&[KEY_TAB], "to toggle regex"),
+                (&[KEY_ESC], "to cancel"),
+            ]
+        } else {
+            vec![
+                (&[KEY_N, KEY_UPPER_N], "for previous/next match"),
+                (&[KEY_SLASH], "to search again"),
+                (&[KEY_ESC], "to clear"),
+                (&[KEY_Q], "to quit"),
+            ]
+        };
         render_key_hints(line2, buf, &pairs);
     }
 
@@ -461,6 +739,9 @@ impl TranscriptOverlay {
         let top_h = area.height.saturating_sub(3);
         let top = Rect::new(area.x, area.y, area.width, top_h);
         let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        if self.search.is_some() && self.search_index.ensure_width(&self.cells, top.width) {
+            self.refresh_search_matches();
+        }
         self.view.render(top, buf);
         self.render_hints(bottom, buf);
     }
@@ -469,13 +750,29 @@ impl TranscriptOverlay {
 impl TranscriptOverlay {
     pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
         match event {
-            TuiEvent::Key(key_event) => match key_event {
-                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_CTRL_T.is_press(e) => {
+            TuiEvent::Key(key_event) => {
+                if KEY_CTRL_C.is_press(key_event) || KEY_CTRL_T.is_press(key_event) {
                     self.is_done = true;
-                    Ok(())
+                    return Ok(());
                 }
-                other => self.view.handle_key_event(tui, other),
-            },
+                let editing = self.search.as_ref().is_some_and(|search| search.editing);
+                match key_event {
+                    e if editing => self.handle_search_edit_key(e),
+                    e if KEY_Q.is_press(e) => self.is_done = true,
+                    e if KEY_SLASH.is_press(e) || KEY_CTRL_F.is_press(e) => self.start_search(),
+                    e if self.search.is_some() && KEY_ESC.is_press(e) => self.clear_search(),
+                    e if self.search.is_some() && KEY_N.is_press(e) => self.jump_to_match(true),
+                    e if self.search.is_some()
+                        && (KEY_UPPER_N.is_press(e) || KEY_SHIFT_N.is_press(e)) =>
+                    {
+                        self.jump_to_match(false)
+                    }
+                    other => return self.view.handle_key_event(tui, other),
+                }
+                tui.frame_requester()
+                    .schedule_frame_in(Duration::from_millis(16));
+                Ok(())
+            }
             TuiEvent::Draw => {
                 tui.draw(u16::MAX, |frame| {
                     self.render(frame.area(), frame.buffer);
@@ -549,6 +846,132 @@ impl StaticOverlay {
     }
 }
 
+/// Full-screen view of a command's streamed output. While following, new
+/// output keeps the view pinned to the bottom; scrolling up stops following
+/// and End or `f` resumes it.
+pub(crate) struct ExecOutputOverlay {
+    view: PagerView,
+    call_id: String,
+    command: String,
+    output: LiveOutput,
+    exit_code: Option<i32>,
+    follow: bool,
+    is_done: bool,
+}
+
+impl ExecOutputOverlay {
+    pub(crate) fn new(call_id: String, command: String, output: LiveOutput) -> Self {
+        let mut overlay = Self {
+            view: PagerView::new(Vec::new(), String::new(), usize::MAX),
+            call_id,
+            command,
+            output,
+            exit_code: None,
+            follow: true,
+            is_done: false,
+        };
+        overlay.refresh();
+        overlay
+    }
+
+    pub(crate) fn call_id(&self) -> &str {
+        &self.call_id
+    }
+
+    pub(crate) fn push_output(&mut self, chunk: &str) {
+        self.output.push(chunk);
+        self.refresh();
+    }
+
+    pub(crate) fn finish(&mut self, exit_code: i32) {
+        self.exit_code = Some(exit_code);
+        self.refresh();
+    }
+
+    fn refresh(&mut self) {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        let omitted = self.output.omitted_lines();
+        if omitted > 0 {
+            lines.push(
+                format!("… {omitted} earlier lines dropped")
+                    .secondary()
+                    .into(),
+            );
+        }
+        lines.extend(self.output.text().lines().map(ansi_escape_line));
+        let paragraph = Paragraph::new(Text::from(lines)).wrap(Wrap { trim: false });
+        self.view.renderables = vec![Box::new(CachedRenderable::new(paragraph))];
+        self.view.title = match self.exit_code {
+            Some(code) => format!("O U T P U T · {} · exited {code}", self.command),
+            None => format!("O U T P U T · {} · running", self.command),
+        };
+        if self.follow {
+            self.view.scroll_offset = usize::MAX;
+        }
+    }
+
+    fn render_hints(&self, area: Rect, buf: &mut Buffer) {
+        let line1 = Rect::new(area.x, area.y, area.width, 1);
+        let line2 = Rect::new(area.x, area.y.saturating_add(1), area.width, 1);
+        render_key_hints(line1, buf, PAGER_KEY_HINTS);
+        let follow_hint = if self.follow {
+            "to stop following"
+        } else {
+            "to follow"
+        };
+        let pairs: Vec<(&[KeyBinding], &str)> =
+            vec![(&[KEY_F], follow_hint), (&[KEY_Q], "to quit")];
+        render_key_hints(line2, buf, &pairs);
+    }
+
+    pub(crate) fn render(&mut self, area: Rect, buf: &mut Buffer) {
+        let top_h = area.height.saturating_sub(3);
+        let top = Rect::new(area.x, area.y, area.width, top_h);
+        let bottom = Rect::new(area.x, area.y + top_h, area.width, 3);
+        self.view.render(top, buf);
+        self.render_hints(bottom, buf);
+    }
+
+    pub(crate) fn handle_event(&mut self, tui: &mut tui::Tui, event: TuiEvent) -> Result<()> {
+        match event {
+            TuiEvent::Key(key_event) => match key_event {
+                e if KEY_Q.is_press(e) || KEY_CTRL_C.is_press(e) || KEY_ESC.is_press(e) => {
+                    self.is_done = true;
+                    Ok(())
+                }
+                e if KEY_F.is_press(e) => {
+                    self.follow = !self.follow;
+                    if self.follow {
+                        self.view.scroll_offset = usize::MAX;
+                    }
+                    tui.frame_requester().schedule_frame();
+                    Ok(())
+                }
+                e => {
+                    if KEY_END.is_press(e) {
+                        self.follow = true;
+                    } else if KEY_UP.is_press(e) || KEY_PAGE_UP.is_press(e) || KEY_HOME.is_press(e)
+                    {
+                        self.follow = false;
+                    }
+                    self.view.handle_key_event(tui, e)
+                }
+            },
+            TuiEvent::Draw => {
+                tui.draw(u16::MAX, |frame| {
+                    self.render(frame.area(), frame.buffer);
+                })?;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.is_done
+    }
+}
+
 fn render_offset_content(
     area: Rect,
     buf: &mut Buffer,
@@ -799,6 +1222,86 @@ mod tests {
         assert_eq!(overlay.view.scroll_offset, 0);
     }
 
+    #[test]
+    fn transcript_search_jumps_between_matches() {
+        let mut overlay = TranscriptOverlay::new(
+            (0..20)
+                .map(|i| {
+                    let text = if i == 2 || i == 15 {
+                        format!("needle{i}")
+                    } else {
+                        format!("line{i}")
+                    };
+                    Arc::new(TestCell {
+                        lines: vec![Line::from(text)],
+                    }) as Arc<dyn HistoryCell>
+                })
+                .collect(),
+        );
+        let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
+        overlay.start_search();
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        for c in "NEEDLE".chars() {
+            overlay.handle_search_edit_key(KeyEvent::from(KeyCode::Char(c)));
+        }
+        overlay.handle_search_edit_key(KeyEvent::from(KeyCode::Enter));
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        let text = buffer_to_text(term.backend().buffer(), Rect::new(0, 0, 40, 12));
+        assert!(
+            text.contains("needle15"),
+            "expected latest match in view: {text}"
+        );
+        assert!(
+            text.contains("/NEEDLE   2/2"),
+            "expected match status: {text}"
+        );
+
+        overlay.jump_to_match(true);
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        let text = buffer_to_text(term.backend().buffer(), Rect::new(0, 0, 40, 12));
+        assert!(
+            text.contains("needle2"),
+            "expected older match in view: {text}"
+        );
+        assert!(
+            !text.contains("needle15"),
+            "expected newer match scrolled away: {text}"
+        );
+        assert!(text.contains("1/2"), "expected match status: {text}");
+
+        overlay.handle_search_edit_key(KeyEvent::from(KeyCode::Esc));
+        assert!(!overlay.is_searching());
+    }
+
+    #[test]
+    fn exec_output_overlay_follows_until_scrolled_up() {
+        let mut output = LiveOutput::default();
+        output.push(&(0..30).map(|i| format!("line{i}\n")).collect::<String>());
+        let mut overlay =
+            ExecOutputOverlay::new("c1".to_string(), "cargo test".to_string(), output);
+        let mut term = Terminal::new(TestBackend::new(40, 12)).expect("term");
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        let text = buffer_to_text(term.backend().buffer(), Rect::new(0, 0, 40, 12));
+        assert!(text.contains("line29"), "expected tail in view: {text}");
+
+        overlay.push_output("line30\n");
+        assert_eq!(overlay.view.scroll_offset, usize::MAX);
+
+        overlay.follow = false;
+        overlay.view.scroll_offset = 0;
+        overlay.push_output("line31\n");
+        overlay.finish(1);
+        term.draw(|f| overlay.render(f.area(), f.buffer_mut()))
+            .expect("draw");
+        let text = buffer_to_text(term.backend().buffer(), Rect::new(0, 0, 40, 12));
+        assert!(text.contains("line0"), "expected scroll kept: {text}");
+        assert!(text.contains("exited 1"), "expected exit status: {text}");
+    }
+
     #[test]
     fn static_overlay_snapshot_basic() {
         // Prepare a static overlay with a few lines and a title
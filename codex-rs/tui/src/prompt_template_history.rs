@@ -0,0 +1,88 @@
+//! Values recently entered into custom prompt templates, remembered per
+//! template and field in `$CODEX_HOME/prompt_template_values.json` so the
+//! fill-in form can offer them again.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+const HISTORY_FILENAME: &str = "prompt_template_values.json";
+const MAX_VALUES_PER_FIELD: usize = 10;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct PromptTemplateHistory {
+    /// Template name → field name → values, most recent first.
+    templates: HashMap<String, HashMap<String, Vec<String>>>,
+}
+
+impl PromptTemplateHistory {
+    /// Read the saved history. A missing or unreadable file is an empty
+    /// history.
+    pub(crate) fn load(codex_home: &Path) -> Self {
+        std::fs::read(history_path(codex_home))
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn save(&self, codex_home: &Path) -> io::Result<()> {
+        std::fs::create_dir_all(codex_home)?;
+        std::fs::write(history_path(codex_home), serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Recent values for each field of `template`, most recent first.
+    pub(crate) fn recent(&self, template: &str) -> HashMap<String, Vec<String>> {
+        self.templates.get(template).cloned().unwrap_or_default()
+    }
+
+    pub(crate) fn record(&mut self, template: &str, values: &HashMap<String, String>) {
+        let fields = self.templates.entry(template.to_string()).or_default();
+        for (field, value) in values {
+            if value.trim().is_empty() {
+                continue;
+            }
+            let recent = fields.entry(field.clone()).or_default();
+            recent.retain(|existing| existing != value);
+            recent.insert(0, value.clone());
+            recent.truncate(MAX_VALUES_PER_FIELD);
+        }
+    }
+}
+
+fn history_path(codex_home: &Path) -> PathBuf {
+    codex_home.join(HISTORY_FILENAME)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn recent_values_are_deduplicated_and_persisted() {
+        let home = TempDir::new().expect("tempdir");
+        let mut history = PromptTemplateHistory::default();
+        for issue in ["#1", "#2", "#1"] {
+            history.record(
+                "triage",
+                &HashMap::from([("issue".to_string(), issue.to_string())]),
+            );
+        }
+        history.save(home.path()).expect("save history");
+
+        let reloaded = PromptTemplateHistory::load(home.path());
+        assert_eq!(
+            reloaded.recent("triage"),
+            HashMap::from([(
+                "issue".to_string(),
+                vec!["#1".to_string(), "#2".to_string()]
+            )])
+        );
+        assert_eq!(reloaded.recent("other"), HashMap::new());
+    }
+}
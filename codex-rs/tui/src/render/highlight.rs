@@ -1,5 +1,5 @@
+use crate::theme::ThemeStylize;
 use ratatui::style::Style;
-use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::text::Span;
 use std::sync::OnceLock;
@@ -51,7 +51,7 @@ impl BashHighlight {
 
     fn style(self) -> Style {
         match self {
-            Self::Comment | Self::Operator | Self::String => Style::default().dim(),
+            Self::Comment | Self::Operator | Self::String => Style::default().secondary(),
             _ => Style::default(),
         }
     }
@@ -32,6 +32,15 @@ impl Insets {
     }
 }
 
+/// Splits `width` into a left margin and a content width of at most
+/// `max_width`, centering the content. `None` uses the full width.
+pub fn centered_columns(width: u16, max_width: Option<u16>) -> (u16, u16) {
+    match max_width {
+        Some(max_width) if width > max_width => ((width - max_width) / 2, max_width),
+        _ => (0, width),
+    }
+}
+
 pub trait RectExt {
     fn inset(&self, insets: Insets) -> Rect;
 }
@@ -48,3 +57,17 @@ impl RectExt for Rect {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn centers_content_wider_than_max_width() {
+        assert_eq!(centered_columns(300, Some(120)), (90, 120));
+        assert_eq!(centered_columns(121, Some(120)), (0, 120));
+        assert_eq!(centered_columns(100, Some(120)), (0, 100));
+        assert_eq!(centered_columns(300, None), (0, 300));
+    }
+}
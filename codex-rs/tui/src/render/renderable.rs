@@ -9,6 +9,7 @@ use ratatui::widgets::WidgetRef;
 
 use crate::render::Insets;
 use crate::render::RectExt as _;
+use crate::render::centered_columns;
 
 pub trait Renderable {
     fn render(&self, area: Rect, buf: &mut Buffer);
@@ -415,8 +416,39 @@ impl<'a> InsetRenderable<'a> {
     }
 }
 
+/// Limits a child to `max_width` columns, centered in the available area.
+pub struct CenteredRenderable<'a> {
+    child: RenderableItem<'a>,
+    max_width: Option<u16>,
+}
+
+impl CenteredRenderable<'_> {
+    fn child_area(&self, area: Rect) -> Rect {
+        let (margin, width) = centered_columns(area.width, self.max_width);
+        Rect {
+            x: area.x + margin,
+            width,
+            ..area
+        }
+    }
+}
+
+impl<'a> Renderable for CenteredRenderable<'a> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        self.child.render(self.child_area(area), buf);
+    }
+    fn desired_height(&self, width: u16) -> u16 {
+        self.child
+            .desired_height(centered_columns(width, self.max_width).1)
+    }
+    fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
+        self.child.cursor_pos(self.child_area(area))
+    }
+}
+
 pub trait RenderableExt<'a> {
     fn inset(self, insets: Insets) -> RenderableItem<'a>;
+    fn max_width(self, max_width: Option<u16>) -> RenderableItem<'a>;
 }
 
 impl<'a, R> RenderableExt<'a> for R
@@ -428,4 +460,10 @@ where
             RenderableItem::Owned(Box::new(self) as Box<dyn Renderable + 'a>);
         RenderableItem::Owned(Box::new(InsetRenderable { child, insets }))
     }
+
+    fn max_width(self, max_width: Option<u16>) -> RenderableItem<'a> {
+        let child: RenderableItem<'a> =
+            RenderableItem::Owned(Box::new(self) as Box<dyn Renderable + 'a>);
+        RenderableItem::Owned(Box::new(CenteredRenderable { child, max_width }))
+    }
 }
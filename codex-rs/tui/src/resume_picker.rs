@@ -10,6 +10,8 @@ use codex_core::ConversationsPage;
 use codex_core::Cursor;
 use codex_core::INTERACTIVE_SESSION_SOURCES;
 use codex_core::RolloutRecorder;
+use codex_core::lock::SessionLockOwner;
+use codex_core::lock::session_lock_owner;
 use codex_protocol::items::TurnItem;
 use color_eyre::eyre::Result;
 use crossterm::event::KeyCode;
@@ -28,7 +30,9 @@ use unicode_width::UnicodeWidthStr;
 
 use crate::diff_render::display_path_for;
 use crate::key_hint;
+use crate::status::format_tokens_compact;
 use crate::text_formatting::truncate_text;
+use crate::theme::ThemeStylize;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
 use crate::tui::TuiEvent;
@@ -37,11 +41,20 @@ use codex_protocol::protocol::SessionMetaLine;
 
 const PAGE_SIZE: usize = 25;
 const LOAD_NEAR_THRESHOLD: usize = 5;
+const IN_USE_LABEL: &str = "in use · ";
 
 #[derive(Debug, Clone)]
 pub enum ResumeSelection {
     StartFresh,
     Resume(PathBuf),
+    /// Resume a session another process is writing; that process stops
+    /// recording it.
+    TakeOver(PathBuf),
+    /// Play back a recorded rollout without starting a live session.
+    Replay {
+        path: PathBuf,
+        speed: f64,
+    },
     Exit,
 }
 
@@ -189,6 +202,9 @@ struct PickerState {
     default_provider: String,
     show_all: bool,
     filter_cwd: Option<PathBuf>,
+    /// Set after Enter on a session another process is using, until the
+    /// user picks read-only, take over, or goes back.
+    locked_choice: Option<Row>,
 }
 
 struct PaginationState {
@@ -246,8 +262,11 @@ struct Row {
     preview: String,
     created_at: Option<DateTime<Utc>>,
     updated_at: Option<DateTime<Utc>>,
+    total_tokens: Option<i64>,
     cwd: Option<PathBuf>,
     git_branch: Option<String>,
+    /// The process currently writing this session, if not this one.
+    lock_owner: Option<SessionLockOwner>,
 }
 
 impl PickerState {
@@ -282,6 +301,7 @@ impl PickerState {
             default_provider,
             show_all,
             filter_cwd,
+            locked_choice: None,
         }
     }
 
@@ -290,6 +310,9 @@ impl PickerState {
     }
 
     async fn handle_key(&mut self, key: KeyEvent) -> Result<Option<ResumeSelection>> {
+        if let Some(row) = self.locked_choice.clone() {
+            return Ok(self.handle_locked_choice_key(&row, key));
+        }
         match key.code {
             KeyCode::Esc => return Ok(Some(ResumeSelection::StartFresh)),
             KeyCode::Char('c')
@@ -301,7 +324,12 @@ impl PickerState {
             }
             KeyCode::Enter => {
                 if let Some(row) = self.filtered_rows.get(self.selected) {
-                    return Ok(Some(ResumeSelection::Resume(row.path.clone())));
+                    if row.lock_owner.is_some() {
+                        self.locked_choice = Some(row.clone());
+                        self.request_frame();
+                    } else {
+                        return Ok(Some(ResumeSelection::Resume(row.path.clone())));
+                    }
                 }
             }
             KeyCode::Up => {
@@ -359,6 +387,29 @@ impl PickerState {
         Ok(None)
     }
 
+    fn handle_locked_choice_key(&mut self, row: &Row, key: KeyEvent) -> Option<ResumeSelection> {
+        match key.code {
+            KeyCode::Char('r') => Some(ResumeSelection::Replay {
+                path: row.path.clone(),
+                speed: 0.0,
+            }),
+            KeyCode::Char('t') => Some(ResumeSelection::TakeOver(row.path.clone())),
+            KeyCode::Char('c')
+                if key
+                    .modifiers
+                    .contains(crossterm::event::KeyModifiers::CONTROL) =>
+            {
+                Some(ResumeSelection::Exit)
+            }
+            KeyCode::Esc => {
+                self.locked_choice = None;
+                self.request_frame();
+                None
+            }
+            _ => None,
+        }
+    }
+
     async fn load_initial_page(&mut self) -> Result<()> {
         let provider_filter = vec![self.default_provider.clone()];
         let page = RolloutRecorder::list_conversations(
@@ -650,8 +701,10 @@ fn head_to_row(item: &ConversationItem) -> Row {
         preview,
         created_at,
         updated_at,
+        total_tokens: item.total_tokens,
         cwd,
         git_branch,
+        lock_owner: session_lock_owner(&item.path),
     }
 }
 
@@ -711,14 +764,22 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
         .areas(area);
 
         // Header
-        frame.render_widget_ref(
-            Line::from(vec!["Resume a previous session".bold().cyan()]),
-            header,
-        );
+        let header_line = match state
+            .locked_choice
+            .as_ref()
+            .and_then(|row| row.lock_owner.as_ref())
+        {
+            Some(owner) => Line::from(vec![
+                "Session in use ".bold().error(),
+                format!("by {}", lock_owner_label(owner)).secondary(),
+            ]),
+            None => Line::from(vec!["Resume a previous session".bold().accent()]),
+        };
+        frame.render_widget_ref(header_line, header);
 
         // Search line
         let q = if state.query.is_empty() {
-            "Type to search".dim().to_string()
+            "Type to search".secondary().to_string()
         } else {
             format!("Search: {}", state.query)
         };
@@ -731,22 +792,36 @@ fn draw_picker(tui: &mut Tui, state: &PickerState) -> std::io::Result<()> {
         render_list(frame, list, state, &metrics);
 
         // Hint line
-        let hint_line: Line = vec![
-            key_hint::plain(KeyCode::Enter).into(),
-            " to resume ".dim(),
-            "    ".dim(),
-            key_hint::plain(KeyCode::Esc).into(),
-            " to start new ".dim(),
-            "    ".dim(),
-            key_hint::ctrl(KeyCode::Char('c')).into(),
-            " to quit ".dim(),
-            "    ".dim(),
-            key_hint::plain(KeyCode::Up).into(),
-            "/".dim(),
-            key_hint::plain(KeyCode::Down).into(),
-            " to browse".dim(),
-        ]
-        .into();
+        let hint_line: Line = if state.locked_choice.is_some() {
+            vec![
+                key_hint::plain(KeyCode::Char('r')).into(),
+                " to open read-only ".secondary(),
+                "    ".secondary(),
+                key_hint::plain(KeyCode::Char('t')).into(),
+                " to take over (the other process stops recording) ".secondary(),
+                "    ".secondary(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " to go back".secondary(),
+            ]
+            .into()
+        } else {
+            vec![
+                key_hint::plain(KeyCode::Enter).into(),
+                " to resume ".secondary(),
+                "    ".secondary(),
+                key_hint::plain(KeyCode::Esc).into(),
+                " to start new ".secondary(),
+                "    ".secondary(),
+                key_hint::ctrl(KeyCode::Char('c')).into(),
+                " to quit ".secondary(),
+                "    ".secondary(),
+                key_hint::plain(KeyCode::Up).into(),
+                "/".secondary(),
+                key_hint::plain(KeyCode::Down).into(),
+                " to browse".secondary(),
+            ]
+            .into()
+        };
         frame.render_widget_ref(hint_line, hint);
     })
 }
@@ -775,10 +850,11 @@ fn render_list(
     let mut y = area.y;
 
     let max_updated_width = metrics.max_updated_width;
+    let max_tokens_width = metrics.max_tokens_width;
     let max_branch_width = metrics.max_branch_width;
     let max_cwd_width = metrics.max_cwd_width;
 
-    for (idx, (row, (updated_label, branch_label, cwd_label))) in rows[start..end]
+    for (idx, (row, (updated_label, tokens_label, branch_label, cwd_label))) in rows[start..end]
         .iter()
         .zip(labels[start..end].iter())
         .enumerate()
@@ -789,7 +865,12 @@ fn render_list(
         let updated_span = if max_updated_width == 0 {
             None
         } else {
-            Some(Span::from(format!("{updated_label:<max_updated_width$}")).dim())
+            Some(Span::from(format!("{updated_label:<max_updated_width$}")).secondary())
+        };
+        let tokens_span = if max_tokens_width == 0 {
+            None
+        } else {
+            Some(Span::from(format!("{tokens_label:>max_tokens_width$}")).secondary())
         };
         let branch_span = if max_branch_width == 0 {
             None
@@ -800,10 +881,10 @@ fn render_list(
                     empty = "-",
                     width = max_branch_width
                 ))
-                .dim(),
+                .secondary(),
             )
         } else {
-            Some(Span::from(format!("{branch_label:<max_branch_width$}")).cyan())
+            Some(Span::from(format!("{branch_label:<max_branch_width$}")).accent())
         };
         let cwd_span = if max_cwd_width == 0 {
             None
@@ -814,10 +895,10 @@ fn render_list(
                     empty = "-",
                     width = max_cwd_width
                 ))
-                .dim(),
+                .secondary(),
             )
         } else {
-            Some(Span::from(format!("{cwd_label:<max_cwd_width$}")).dim())
+            Some(Span::from(format!("{cwd_label:<max_cwd_width$}")).secondary())
         };
 
         let mut preview_width = area.width as usize;
@@ -825,6 +906,9 @@ fn render_list(
         if max_updated_width > 0 {
             preview_width = preview_width.saturating_sub(max_updated_width + 2);
         }
+        if max_tokens_width > 0 {
+            preview_width = preview_width.saturating_sub(max_tokens_width + 2);
+        }
         if max_branch_width > 0 {
             preview_width = preview_width.saturating_sub(max_branch_width + 2);
         }
@@ -835,12 +919,19 @@ fn render_list(
         if add_leading_gap {
             preview_width = preview_width.saturating_sub(2);
         }
+        if row.lock_owner.is_some() {
+            preview_width = preview_width.saturating_sub(IN_USE_LABEL.width());
+        }
         let preview = truncate_text(&row.preview, preview_width);
         let mut spans: Vec<Span> = vec![marker];
         if let Some(updated) = updated_span {
             spans.push(updated);
             spans.push("  ".into());
         }
+        if let Some(tokens) = tokens_span {
+            spans.push(tokens);
+            spans.push("  ".into());
+        }
         if let Some(branch) = branch_span {
             spans.push(branch);
             spans.push("  ".into());
@@ -852,6 +943,9 @@ fn render_list(
         if add_leading_gap {
             spans.push("  ".into());
         }
+        if row.lock_owner.is_some() {
+            spans.push(IN_USE_LABEL.error());
+        }
         spans.push(preview.into());
 
         let line: Line = spans.into();
@@ -861,7 +955,8 @@ fn render_list(
     }
 
     if state.pagination.loading.is_pending() && y < area.y.saturating_add(area.height) {
-        let loading_line: Line = vec!["  ".into(), "Loading older sessions…".italic().dim()].into();
+        let loading_line: Line =
+            vec!["  ".into(), "Loading older sessions…".italic().secondary()].into();
         let rect = Rect::new(area.x, y, area.width, 1);
         frame.render_widget_ref(loading_line, rect);
     }
@@ -872,27 +967,27 @@ fn render_empty_state_line(state: &PickerState) -> Line<'static> {
         if state.search_state.is_active()
             || (state.pagination.loading.is_pending() && state.pagination.next_cursor.is_some())
         {
-            return vec!["Searching…".italic().dim()].into();
+            return vec!["Searching…".italic().secondary()].into();
         }
         if state.pagination.reached_scan_cap {
             let msg = format!(
                 "Search scanned first {} sessions; more may exist",
                 state.pagination.num_scanned_files
             );
-            return vec![Span::from(msg).italic().dim()].into();
+            return vec![Span::from(msg).italic().secondary()].into();
         }
-        return vec!["No results for your search".italic().dim()].into();
+        return vec!["No results for your search".italic().secondary()].into();
     }
 
     if state.all_rows.is_empty() && state.pagination.num_scanned_files == 0 {
-        return vec!["No sessions yet".italic().dim()].into();
+        return vec!["No sessions yet".italic().secondary()].into();
     }
 
     if state.pagination.loading.is_pending() {
-        return vec!["Loading older sessions…".italic().dim()].into();
+        return vec!["Loading older sessions…".italic().secondary()].into();
     }
 
-    vec!["No sessions yet".italic().dim()].into()
+    vec!["No sessions yet".italic().secondary()].into()
 }
 
 fn human_time_ago(ts: DateTime<Utc>) -> String {
@@ -930,6 +1025,14 @@ fn human_time_ago(ts: DateTime<Utc>) -> String {
     }
 }
 
+/// "pid 4242 on devbox, started 5 minutes ago".
+fn lock_owner_label(owner: &SessionLockOwner) -> String {
+    let started = parse_timestamp_str(&owner.started_at)
+        .map(|at| format!(", started {}", human_time_ago(at)))
+        .unwrap_or_default();
+    format!("pid {} on {}{started}", owner.pid, owner.hostname)
+}
+
 fn format_updated_label(row: &Row) -> String {
     match (row.updated_at, row.created_at) {
         (Some(updated), _) => human_time_ago(updated),
@@ -957,6 +1060,15 @@ fn render_column_headers(
         spans.push(Span::from(label).bold());
         spans.push("  ".into());
     }
+    if metrics.max_tokens_width > 0 {
+        let label = format!(
+            "{text:>width$}",
+            text = "Tokens",
+            width = metrics.max_tokens_width
+        );
+        spans.push(Span::from(label).bold());
+        spans.push("  ".into());
+    }
     if metrics.max_branch_width > 0 {
         let label = format!(
             "{text:<width$}",
@@ -981,9 +1093,11 @@ fn render_column_headers(
 
 struct ColumnMetrics {
     max_updated_width: usize,
+    /// Zero when no listed session recorded token usage.
+    max_tokens_width: usize,
     max_branch_width: usize,
     max_cwd_width: usize,
-    labels: Vec<(String, String, String)>,
+    labels: Vec<(String, String, String, String)>,
 }
 
 fn calculate_column_metrics(rows: &[Row], include_cwd: bool) -> ColumnMetrics {
@@ -1006,8 +1120,13 @@ fn calculate_column_metrics(rows: &[Row], include_cwd: bool) -> ColumnMetrics {
         format!("…{tail}")
     }
 
-    let mut labels: Vec<(String, String, String)> = Vec::with_capacity(rows.len());
+    let mut labels: Vec<(String, String, String, String)> = Vec::with_capacity(rows.len());
     let mut max_updated_width = UnicodeWidthStr::width("Updated");
+    let mut max_tokens_width = if rows.iter().any(|row| row.total_tokens.is_some()) {
+        UnicodeWidthStr::width("Tokens")
+    } else {
+        0
+    };
     let mut max_branch_width = UnicodeWidthStr::width("Branch");
     let mut max_cwd_width = if include_cwd {
         UnicodeWidthStr::width("CWD")
@@ -1017,6 +1136,13 @@ fn calculate_column_metrics(rows: &[Row], include_cwd: bool) -> ColumnMetrics {
 
     for row in rows {
         let updated = format_updated_label(row);
+        let tokens = if max_tokens_width > 0 {
+            row.total_tokens
+                .map(format_tokens_compact)
+                .unwrap_or_else(|| "-".to_string())
+        } else {
+            String::new()
+        };
         let branch_raw = row.git_branch.clone().unwrap_or_default();
         let branch = right_elide(&branch_raw, 24);
         let cwd = if include_cwd {
@@ -1030,13 +1156,15 @@ fn calculate_column_metrics(rows: &[Row], include_cwd: bool) -> ColumnMetrics {
             String::new()
         };
         max_updated_width = max_updated_width.max(UnicodeWidthStr::width(updated.as_str()));
+        max_tokens_width = max_tokens_width.max(UnicodeWidthStr::width(tokens.as_str()));
         max_branch_width = max_branch_width.max(UnicodeWidthStr::width(branch.as_str()));
         max_cwd_width = max_cwd_width.max(UnicodeWidthStr::width(cwd.as_str()));
-        labels.push((updated, branch, cwd));
+        labels.push((updated, tokens, branch, cwd));
     }
 
     ColumnMetrics {
         max_updated_width,
+        max_tokens_width,
         max_branch_width,
         max_cwd_width,
         labels,
@@ -1078,6 +1206,7 @@ mod tests {
             tail: Vec::new(),
             created_at: Some(ts.to_string()),
             updated_at: Some(ts.to_string()),
+            total_tokens: None,
         }
     }
 
@@ -1153,6 +1282,7 @@ mod tests {
             tail: Vec::new(),
             created_at: Some("2025-01-01T00:00:00Z".into()),
             updated_at: Some("2025-01-01T00:00:00Z".into()),
+            total_tokens: None,
         };
         let b = ConversationItem {
             path: PathBuf::from("/tmp/b.jsonl"),
@@ -1160,6 +1290,7 @@ mod tests {
             tail: Vec::new(),
             created_at: Some("2025-01-02T00:00:00Z".into()),
             updated_at: Some("2025-01-02T00:00:00Z".into()),
+            total_tokens: None,
         };
         let rows = rows_from_items(vec![a, b]);
         assert_eq!(rows.len(), 2);
@@ -1188,6 +1319,7 @@ mod tests {
             tail,
             created_at: Some("2025-01-01T00:00:00Z".into()),
             updated_at: Some("2025-01-01T01:00:00Z".into()),
+            total_tokens: None,
         };
 
         let row = head_to_row(&item);
@@ -1202,6 +1334,43 @@ mod tests {
         assert_eq!(row.updated_at, Some(expected_updated));
     }
 
+    #[test]
+    fn row_keeps_total_tokens_from_listing() {
+        let mut item = make_item("/tmp/a.jsonl", "2025-01-01T00:00:00Z", "Hello");
+        item.total_tokens = Some(12_345);
+
+        let row = head_to_row(&item);
+
+        assert_eq!(row.total_tokens, Some(12_345));
+    }
+
+    #[test]
+    fn tokens_column_only_shown_when_usage_recorded() {
+        let row = |total_tokens| Row {
+            path: PathBuf::from("/tmp/a.jsonl"),
+            preview: String::from("Hello"),
+            created_at: None,
+            updated_at: None,
+            total_tokens,
+            cwd: None,
+            git_branch: None,
+            lock_owner: None,
+        };
+
+        let metrics = calculate_column_metrics(&[row(None), row(None)], false);
+        assert_eq!(metrics.max_tokens_width, 0);
+        assert!(metrics.labels.iter().all(|labels| labels.1.is_empty()));
+
+        let metrics = calculate_column_metrics(&[row(Some(12_345)), row(None)], false);
+        assert_eq!(metrics.max_tokens_width, "Tokens".len());
+        let tokens: Vec<&str> = metrics
+            .labels
+            .iter()
+            .map(|labels| labels.1.as_str())
+            .collect();
+        assert_eq!(tokens, vec!["12.3K", "-"]);
+    }
+
     #[test]
     fn resume_table_snapshot() {
         use crate::custom_terminal::Terminal;
@@ -1226,24 +1395,30 @@ mod tests {
                 preview: String::from("Fix resume picker timestamps"),
                 created_at: Some(now - Duration::minutes(16)),
                 updated_at: Some(now - Duration::seconds(42)),
+                total_tokens: None,
                 cwd: None,
                 git_branch: None,
+                lock_owner: None,
             },
             Row {
                 path: PathBuf::from("/tmp/b.jsonl"),
                 preview: String::from("Investigate lazy pagination cap"),
                 created_at: Some(now - Duration::hours(1)),
                 updated_at: Some(now - Duration::minutes(35)),
+                total_tokens: None,
                 cwd: None,
                 git_branch: None,
+                lock_owner: None,
             },
             Row {
                 path: PathBuf::from("/tmp/c.jsonl"),
                 preview: String::from("Explain the codebase"),
                 created_at: Some(now - Duration::hours(2)),
                 updated_at: Some(now - Duration::hours(2)),
+                total_tokens: None,
                 cwd: None,
                 git_branch: None,
+                lock_owner: None,
             },
         ];
         state.all_rows = rows.clone();
@@ -1424,6 +1599,54 @@ mod tests {
         assert_eq!(state.selected, 5);
     }
 
+    #[test]
+    fn enter_on_session_in_use_offers_read_only_or_takeover() {
+        let loader: PageLoader = Arc::new(|_| {});
+        let mut state = PickerState::new(
+            PathBuf::from("/tmp"),
+            FrameRequester::test_dummy(),
+            loader,
+            String::from("openai"),
+            true,
+            None,
+        );
+        let row = Row {
+            path: PathBuf::from("/tmp/in-use.jsonl"),
+            preview: String::from("Refactor the parser"),
+            created_at: None,
+            updated_at: None,
+            total_tokens: None,
+            cwd: None,
+            git_branch: None,
+            lock_owner: Some(SessionLockOwner {
+                hostname: String::from("devbox"),
+                pid: 4242,
+                started_at: String::from("2025-01-01T00:00:00Z"),
+            }),
+        };
+        state.all_rows = vec![row.clone()];
+        state.filtered_rows = vec![row];
+
+        let press = |state: &mut PickerState, code: KeyCode| {
+            block_on_future(state.handle_key(KeyEvent::new(code, KeyModifiers::NONE))).unwrap()
+        };
+
+        assert!(press(&mut state, KeyCode::Enter).is_none());
+        assert!(state.locked_choice.is_some());
+        assert!(press(&mut state, KeyCode::Esc).is_none());
+        assert!(state.locked_choice.is_none());
+
+        press(&mut state, KeyCode::Enter);
+        assert!(matches!(
+            press(&mut state, KeyCode::Char('r')),
+            Some(ResumeSelection::Replay { speed, .. }) if speed == 0.0
+        ));
+        assert!(matches!(
+            press(&mut state, KeyCode::Char('t')),
+            Some(ResumeSelection::TakeOver(path)) if path == PathBuf::from("/tmp/in-use.jsonl")
+        ));
+    }
+
     #[test]
     fn up_at_bottom_does_not_scroll_when_visible() {
         let loader: PageLoader = Arc::new(|_| {});
@@ -19,21 +19,38 @@ pub enum SlashCommand {
     Skills,
     Plan,
     Review,
+    Findings,
     New,
+    Resume,
+    Reclaim,
+    Fork,
+    Regenerate,
+    Quote,
     Init,
     Compact,
+    Pin,
+    Unpin,
     Undo,
     Checkpoint,
     RestoreCheckpoint,
     ListCheckpoints,
+    CheckpointDiff,
     Diff,
+    SubagentReview,
     Mention,
     Status,
     Ps,
     Kill,
+    Queue,
+    Summary,
     Mcp,
     Remember,
     Memories,
+    Storage,
+    Width,
+    Theme,
+    Watch,
+    Keys,
     Logout,
     Quit,
     Exit,
@@ -48,17 +65,31 @@ impl SlashCommand {
         match self {
             SlashCommand::Feedback => "send logs to maintainers",
             SlashCommand::New => "start a new chat during a conversation",
+            SlashCommand::Resume => "resume a previous session",
+            SlashCommand::Reclaim => "take this session back after another process took it over",
+            SlashCommand::Fork => "branch the chat into a new session from an earlier answer",
+            SlashCommand::Regenerate => "redo the last answer, optionally with another effort",
+            SlashCommand::Quote => "quote an earlier answer in your next message",
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex Kaioken",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
+            SlashCommand::Pin => {
+                "pin a file to every turn (`/pin <file>`) or an earlier answer through compaction"
+            }
+            SlashCommand::Unpin => "stop sending a pinned file (`/unpin <file>`)",
             SlashCommand::Review => "review my current changes and find issues",
+            SlashCommand::Findings => "fix, ignore, or open findings from the last review",
             SlashCommand::Undo => "ask Codex Kaioken to undo a turn",
             SlashCommand::Checkpoint => "save a named checkpoint (use `/checkpoint <name>`)",
             SlashCommand::RestoreCheckpoint => {
                 "restore a saved checkpoint (`/restore-checkpoint <name>`)"
             }
             SlashCommand::ListCheckpoints => "list saved checkpoints",
+            SlashCommand::CheckpointDiff => {
+                "diff two saved checkpoints (`/checkpoint-diff <from> <to>`)"
+            }
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex Kaioken",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::SubagentReview => "review each subagent's diff and conflicting edits",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Status => "show current session configuration and token usage",
             SlashCommand::Ps => "list background terminals",
@@ -69,9 +100,22 @@ impl SlashCommand {
             SlashCommand::Settings => "customize footer and other Kaioken UI defaults",
             SlashCommand::Skills => "list and toggle available skills",
             SlashCommand::Plan => "toggle plan mode or review pending plans",
+            SlashCommand::Queue => "reorder, edit, or delete queued messages",
+            SlashCommand::Summary => {
+                "summarize this session (`/summary save` to write session notes)"
+            }
             SlashCommand::Mcp => "list configured MCP tools",
             SlashCommand::Remember => "save something to memory (`/remember <text>`)",
             SlashCommand::Memories => "show stored memories and stats",
+            SlashCommand::Storage => {
+                "show disk usage (`/storage clean <days>` to free space, `--dry-run` to preview)"
+            }
+            SlashCommand::Width => "set the maximum transcript width (`/width <columns>` or `off`)",
+            SlashCommand::Theme => "switch the color theme (`/theme <name>`)",
+            SlashCommand::Watch => {
+                "send a prompt when matching files change (`/watch <glob> <prompt>`, `/watch clear`)"
+            }
+            SlashCommand::Keys => "show the effective key bindings",
             SlashCommand::Logout => "log out of Codex Kaioken",
             SlashCommand::Rollout => "print the rollout file path",
             SlashCommand::TestApproval => "test approval request",
@@ -90,6 +134,9 @@ impl SlashCommand {
             // Commands that either start a new turn, mutate in-flight work,
             // or would conflict with backend state stay disabled.
             SlashCommand::New
+            | SlashCommand::Resume
+            | SlashCommand::Fork
+            | SlashCommand::Regenerate
             | SlashCommand::Init
             | SlashCommand::Compact
             | SlashCommand::Undo
@@ -105,16 +152,30 @@ impl SlashCommand {
             | SlashCommand::Settings
             | SlashCommand::Skills
             | SlashCommand::Plan
+            | SlashCommand::Quote
+            | SlashCommand::Pin
+            | SlashCommand::Unpin
+            | SlashCommand::Findings
             // All of the commands below already operated during tasks.
             | SlashCommand::Diff
+            | SlashCommand::SubagentReview
             | SlashCommand::ListCheckpoints
+            | SlashCommand::CheckpointDiff
             | SlashCommand::Mention
             | SlashCommand::Status
             | SlashCommand::Ps
             | SlashCommand::Kill
+            | SlashCommand::Queue
+            | SlashCommand::Summary
             | SlashCommand::Mcp
             | SlashCommand::Remember
             | SlashCommand::Memories
+            | SlashCommand::Reclaim
+            | SlashCommand::Storage
+            | SlashCommand::Width
+            | SlashCommand::Theme
+            | SlashCommand::Watch
+            | SlashCommand::Keys
             | SlashCommand::Feedback
             | SlashCommand::Quit
             | SlashCommand::Exit
@@ -2,6 +2,7 @@ use crate::history_cell::CompositeHistoryCell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell::PlainHistoryCell;
 use crate::history_cell::with_border_with_inner_width;
+use crate::theme::ThemeStylize;
 use crate::version::display_version;
 use chrono::DateTime;
 use chrono::Local;
@@ -23,6 +24,7 @@ use super::format::truncate_line_to_width;
 use super::helpers::compose_account_display;
 use super::helpers::compose_agents_summary;
 use super::helpers::compose_model_display;
+use super::helpers::compose_workspace_write_display;
 use super::helpers::format_directory_display;
 use super::helpers::format_tokens_compact;
 use super::rate_limits::RateLimitSnapshotDisplay;
@@ -76,7 +78,7 @@ pub(crate) fn new_status_output(
     rate_limits: Option<&RateLimitSnapshotDisplay>,
     now: DateTime<Local>,
 ) -> CompositeHistoryCell {
-    let command = PlainHistoryCell::new(vec!["/status".magenta().into()]);
+    let command = PlainHistoryCell::new(vec!["/status".brand().into()]);
     let card = StatusHistoryCell::new(
         config,
         auth_manager,
@@ -110,7 +112,15 @@ impl StatusHistoryCell {
         let sandbox = match &config.sandbox_policy {
             SandboxPolicy::DangerFullAccess => "danger-full-access".to_string(),
             SandboxPolicy::ReadOnly => "read-only".to_string(),
-            SandboxPolicy::WorkspaceWrite { .. } => "workspace-write".to_string(),
+            SandboxPolicy::WorkspaceWrite {
+                allow_write,
+                deny_write,
+                ..
+            } => compose_workspace_write_display(
+                allow_write,
+                deny_write,
+                config.sandbox_policy.network_allowlist(),
+            ),
         };
         let agents_summary = compose_agents_summary(config);
         let account = compose_account_display(auth_manager);
@@ -153,13 +163,13 @@ impl StatusHistoryCell {
         vec![
             Span::from(total_fmt),
             Span::from(" total "),
-            Span::from(" (").dim(),
-            Span::from(input_fmt).dim(),
-            Span::from(" input").dim(),
-            Span::from(" + ").dim(),
-            Span::from(output_fmt).dim(),
-            Span::from(" output").dim(),
-            Span::from(")").dim(),
+            Span::from(" (").secondary(),
+            Span::from(input_fmt).secondary(),
+            Span::from(" input").secondary(),
+            Span::from(" + ").secondary(),
+            Span::from(output_fmt).secondary(),
+            Span::from(" output").secondary(),
+            Span::from(")").secondary(),
         ]
     }
 
@@ -171,11 +181,11 @@ impl StatusHistoryCell {
 
         Some(vec![
             Span::from(format!("{percent}% left")),
-            Span::from(" (").dim(),
-            Span::from(used_fmt).dim(),
-            Span::from(" used / ").dim(),
-            Span::from(window_fmt).dim(),
-            Span::from(")").dim(),
+            Span::from(" (").secondary(),
+            Span::from(used_fmt).secondary(),
+            Span::from(" used / ").secondary(),
+            Span::from(window_fmt).secondary(),
+            Span::from(")").secondary(),
         ])
     }
 
@@ -187,9 +197,10 @@ impl StatusHistoryCell {
         match &self.rate_limits {
             StatusRateLimitData::Available(rows_data) => {
                 if rows_data.is_empty() {
-                    return vec![
-                        formatter.line("Limits", vec![Span::from("data not available yet").dim()]),
-                    ];
+                    return vec![formatter.line(
+                        "Limits",
+                        vec![Span::from("data not available yet").secondary()],
+                    )];
                 }
 
                 self.rate_limit_row_lines(rows_data, available_inner_width, formatter)
@@ -199,12 +210,17 @@ impl StatusHistoryCell {
                     self.rate_limit_row_lines(rows_data, available_inner_width, formatter);
                 lines.push(formatter.line(
                     "Warning",
-                    vec![Span::from("limits may be stale - start new turn to refresh.").dim()],
+                    vec![
+                        Span::from("limits may be stale - start new turn to refresh.").secondary(),
+                    ],
                 ));
                 lines
             }
             StatusRateLimitData::Missing => {
-                vec![formatter.line("Limits", vec![Span::from("data not available yet").dim()])]
+                vec![formatter.line(
+                    "Limits",
+                    vec![Span::from("data not available yet").secondary()],
+                )]
             }
         }
     }
@@ -233,9 +249,9 @@ impl StatusHistoryCell {
                     let base_line = Line::from(base_spans.clone());
 
                     if let Some(resets_at) = resets_at.as_ref() {
-                        let resets_span = Span::from(format!("(resets {resets_at})")).dim();
+                        let resets_span = Span::from(format!("(resets {resets_at})")).secondary();
                         let mut inline_spans = base_spans.clone();
-                        inline_spans.push(Span::from(" ").dim());
+                        inline_spans.push(Span::from(" ").secondary());
                         inline_spans.push(resets_span.clone());
 
                         if line_display_width(&Line::from(inline_spans.clone()))
@@ -294,16 +310,16 @@ impl HistoryCell for StatusHistoryCell {
             .unwrap_or_else(|| (version_string.clone(), None));
 
         lines.push(Line::from(vec![
-            Span::from(format!("{}>_ ", FieldFormatter::INDENT)).dim(),
+            Span::from(format!("{}>_ ", FieldFormatter::INDENT)).secondary(),
             Span::from("Codex Kaioken").bold(),
         ]));
         let mut version_line = vec![
-            Span::from(format!("{}version  ", FieldFormatter::INDENT)).dim(),
+            Span::from(format!("{}version  ", FieldFormatter::INDENT)).secondary(),
             Span::from(format!("v{release_version}")),
         ];
         if let Some(commit) = commit_suffix {
-            version_line.push(Span::from("    ").dim());
-            version_line.push(Span::from("commit  ").dim());
+            version_line.push(Span::from("    ").secondary());
+            version_line.push(Span::from("commit  ").secondary());
             version_line.push(Span::from(commit));
         }
         lines.push(Line::from(version_line));
@@ -350,14 +366,14 @@ impl HistoryCell for StatusHistoryCell {
         let value_width = formatter.value_width(card_inner_width);
 
         let note_first_line = Line::from(vec![
-            Span::from("Visit ").cyan(),
+            Span::from("Visit ").accent(),
             "https://chatgpt.com/codex/settings/usage"
-                .cyan()
+                .accent()
                 .underlined(),
-            Span::from(" for up-to-date").cyan(),
+            Span::from(" for up-to-date").accent(),
         ]);
         let note_second_line = Line::from(vec![
-            Span::from("information on rate limits and credits").cyan(),
+            Span::from("information on rate limits and credits").accent(),
         ]);
         let note_lines = word_wrap_lines(
             [note_first_line, note_second_line],
@@ -368,9 +384,9 @@ impl HistoryCell for StatusHistoryCell {
 
         let mut model_spans = vec![Span::from(self.model_name.clone())];
         if !self.model_details.is_empty() {
-            model_spans.push(Span::from(" (").dim());
-            model_spans.push(Span::from(self.model_details.join(", ")).dim());
-            model_spans.push(Span::from(")").dim());
+            model_spans.push(Span::from(" (").secondary());
+            model_spans.push(Span::from(self.model_details.join(", ")).secondary());
+            model_spans.push(Span::from(")").secondary());
         }
 
         let directory_value = format_directory_display(&self.directory, Some(value_width));
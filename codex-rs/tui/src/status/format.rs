@@ -1,5 +1,5 @@
+use crate::theme::ThemeStylize;
 use ratatui::prelude::*;
-use ratatui::style::Stylize;
 use std::collections::BTreeSet;
 use unicode_width::UnicodeWidthChar;
 use unicode_width::UnicodeWidthStr;
@@ -45,7 +45,7 @@ impl FieldFormatter {
 
     pub(crate) fn continuation(&self, mut spans: Vec<Span<'static>>) -> Line<'static> {
         let mut all_spans = Vec::with_capacity(spans.len() + 1);
-        all_spans.push(Span::from(self.value_indent.clone()).dim());
+        all_spans.push(Span::from(self.value_indent.clone()).secondary());
         all_spans.append(&mut spans);
         Line::from(all_spans)
     }
@@ -78,7 +78,7 @@ impl FieldFormatter {
             buf.push(' ');
         }
 
-        Span::from(buf).dim()
+        Span::from(buf).secondary()
     }
 }
 
@@ -35,6 +35,30 @@ pub(crate) fn compose_model_display(
     (config.model.clone(), details)
 }
 
+/// `workspace-write`, followed by any `allow_write`/`deny_write` globs and
+/// allowlisted network destinations.
+pub(crate) fn compose_workspace_write_display(
+    allow_write: &[String],
+    deny_write: &[String],
+    network_allowlist: &[String],
+) -> String {
+    let mut rules = Vec::new();
+    if !allow_write.is_empty() {
+        rules.push(format!("allow write {}", allow_write.join(", ")));
+    }
+    if !deny_write.is_empty() {
+        rules.push(format!("deny write {}", deny_write.join(", ")));
+    }
+    if !network_allowlist.is_empty() {
+        rules.push(format!("network {}", network_allowlist.join(", ")));
+    }
+    if rules.is_empty() {
+        "workspace-write".to_string()
+    } else {
+        format!("workspace-write ({})", rules.join("; "))
+    }
+}
+
 pub(crate) fn compose_agents_summary(config: &Config) -> String {
     match discover_project_doc_paths(config) {
         Ok(paths) => {
@@ -100,6 +100,9 @@ fn status_snapshot_includes_reasoning_details() {
         network_access: false,
         exclude_tmpdir_env_var: false,
         exclude_slash_tmp: false,
+        allow_write: vec![],
+        deny_write: vec![],
+        network_allowlist: vec![],
     };
 
     config.cwd = PathBuf::from("/workspace/tests");
@@ -8,7 +8,6 @@ use codex_core::protocol::Op;
 use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::WidgetRef;
 
@@ -18,6 +17,7 @@ use crate::exec_cell::spinner;
 use crate::key_hint;
 use crate::render::renderable::Renderable;
 use crate::shimmer::shimmer_spans;
+use crate::theme::ThemeStylize;
 use crate::tui::FrameRequester;
 
 pub(crate) struct StatusIndicatorWidget {
@@ -185,18 +185,18 @@ impl Renderable for StatusIndicatorWidget {
         spans.push(" ".into());
         if self.show_interrupt_hint {
             spans.extend(vec![
-                format!("({pretty_elapsed} • ").dim(),
+                format!("({pretty_elapsed} • ").secondary(),
                 key_hint::plain(KeyCode::Esc).into(),
-                " to interrupt)".dim(),
+                " to interrupt)".secondary(),
             ]);
         } else {
-            spans.push(format!("({pretty_elapsed})").dim());
+            spans.push(format!("({pretty_elapsed})").secondary());
         }
 
         // Show token counts if available
         if let (Some(input), Some(output)) = (self.input_tokens, self.output_tokens) {
-            spans.push(" · ".dim());
-            spans.push(format!("↑{} ↓{}", format_token_count(input), format_token_count(output)).dim());
+            spans.push(" · ".secondary());
+            spans.push(format!("↑{} ↓{}", format_token_count(input), format_token_count(output)).secondary());
         }
 
         Line::from(spans).render_ref(area, buf);
@@ -0,0 +1,190 @@
+//! Delivers notifications through whatever the terminal can show.
+//!
+//! OSC 9 and OSC 777 escapes reach the local terminal even over SSH, where
+//! native desktop notifications cannot. The bell works everywhere, and inside
+//! tmux `display-message` shows the text in the status line.
+
+use std::fmt;
+use std::io::stdout;
+use std::process::Stdio;
+use std::sync::OnceLock;
+
+use codex_core::config::types::NotificationMethod;
+use crossterm::Command;
+use crossterm::execute;
+
+/// Title shown by transports that have one.
+const NOTIFICATION_TITLE: &str = "Codex";
+
+/// Resolves `Auto` to the transport detected for this terminal, once per
+/// process.
+pub(crate) fn resolve(method: NotificationMethod) -> NotificationMethod {
+    static DETECTED: OnceLock<NotificationMethod> = OnceLock::new();
+    match method {
+        NotificationMethod::Auto => *DETECTED.get_or_init(|| detect(|key| std::env::var(key).ok())),
+        other => other,
+    }
+}
+
+fn detect(var: impl Fn(&str) -> Option<String>) -> NotificationMethod {
+    // tmux does not forward OSC notifications unless passthrough is set up.
+    if var("TMUX").is_some() {
+        return NotificationMethod::Tmux;
+    }
+
+    let term = var("TERM").unwrap_or_default();
+    let term_program = var("TERM_PROGRAM").unwrap_or_default();
+    if matches!(term_program.as_str(), "iTerm.app" | "WezTerm" | "ghostty")
+        || var("ITERM_SESSION_ID").is_some()
+        || var("KITTY_WINDOW_ID").is_some()
+        || term == "xterm-kitty"
+        || term == "xterm-ghostty"
+    {
+        return NotificationMethod::Osc9;
+    }
+    if var("VTE_VERSION").is_some() || term.starts_with("foot") || term.starts_with("rxvt") {
+        return NotificationMethod::Osc777;
+    }
+    NotificationMethod::Bell
+}
+
+/// Posts `message` using `method`, resolving `Auto` first.
+pub(crate) fn post(method: NotificationMethod, message: &str) -> std::io::Result<()> {
+    // Control characters would end the escape sequence early.
+    let message: String = message
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect();
+    match resolve(method) {
+        NotificationMethod::Auto | NotificationMethod::Osc9 => {
+            execute!(stdout(), PostNotification(message))
+        }
+        NotificationMethod::Osc777 => execute!(stdout(), PostOsc777Notification(message)),
+        NotificationMethod::Bell => execute!(stdout(), RingBell),
+        NotificationMethod::Tmux => {
+            std::process::Command::new("tmux")
+                .arg("display-message")
+                .arg(&message)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()?;
+            Ok(())
+        }
+    }
+}
+
+/// Command that emits an OSC 9 desktop notification with a message.
+#[derive(Debug, Clone)]
+pub struct PostNotification(pub String);
+
+impl Command for PostNotification {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        write!(f, "\x1b]9;{}\x07", self.0)
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute PostNotification using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Command that emits an OSC 777 `notify` desktop notification.
+#[derive(Debug, Clone)]
+struct PostOsc777Notification(String);
+
+impl Command for PostOsc777Notification {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        // Semicolons separate the title from the body.
+        write!(
+            f,
+            "\x1b]777;notify;{NOTIFICATION_TITLE};{}\x07",
+            self.0.replace(';', ",")
+        )
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute PostOsc777Notification using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+/// Command that rings the terminal bell.
+#[derive(Debug, Clone, Copy)]
+struct RingBell;
+
+impl Command for RingBell {
+    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
+        f.write_char('\x07')
+    }
+
+    #[cfg(windows)]
+    fn execute_winapi(&self) -> std::io::Result<()> {
+        Err(std::io::Error::other(
+            "tried to execute RingBell using WinAPI; use ANSI instead",
+        ))
+    }
+
+    #[cfg(windows)]
+    fn is_ansi_code_supported(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+
+    fn detect_with(vars: &[(&str, &str)]) -> NotificationMethod {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+            .collect();
+        detect(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn detects_transport_from_environment() {
+        assert_eq!(
+            detect_with(&[("TERM_PROGRAM", "iTerm.app")]),
+            NotificationMethod::Osc9
+        );
+        assert_eq!(
+            detect_with(&[("TERM", "xterm-256color"), ("VTE_VERSION", "7600")]),
+            NotificationMethod::Osc777
+        );
+        assert_eq!(
+            detect_with(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux-1000/default")]),
+            NotificationMethod::Tmux
+        );
+        assert_eq!(
+            detect_with(&[("TERM", "xterm-256color")]),
+            NotificationMethod::Bell
+        );
+    }
+
+    #[test]
+    fn osc777_separates_title_from_body() {
+        let mut out = String::new();
+        PostOsc777Notification("done; 2 files".to_string())
+            .write_ansi(&mut out)
+            .expect("write");
+        assert_eq!(out, "\x1b]777;notify;Codex;done, 2 files\x07");
+    }
+}
@@ -0,0 +1,238 @@
+//! Color themes for the TUI.
+//!
+//! A [`Theme`] maps the roles from `tui/styles.md` (accent, success, error,
+//! Codex, secondary text) to colors. Widgets style text through
+//! [`ThemeStylize`] instead of hard-coding `.cyan()`, `.red()` or `.dim()`, so
+//! switching the active theme with `/theme` or `tui.theme` recolors everything
+//! drawn afterwards. Besides the built-in `dark`, `light` and `high-contrast`
+//! themes, users can define palettes in `~/.codex/themes/<name>.toml`:
+//!
+//! ```toml
+//! base = "dark"          # built-in theme for tokens left unset
+//! accent = "#5fafd7"     # ANSI name, `#rrggbb`, or 0-255 palette index
+//! success = "light-green"
+//! error = "red"
+//! brand = "magenta"
+//! secondary = "dim"      # a color, or `dim` to dim the default color
+//! ```
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+use ratatui::style::Color;
+use ratatui::style::Stylize;
+use serde::Deserialize;
+
+pub(crate) const DEFAULT_THEME: &str = "dark";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Theme {
+    /// User input tips, selection and status indicators.
+    pub(crate) accent: Color,
+    /// Success and additions.
+    pub(crate) success: Color,
+    /// Errors, failures and deletions.
+    pub(crate) error: Color,
+    /// Codex itself.
+    pub(crate) brand: Color,
+    /// Secondary text; `None` dims the default color instead.
+    pub(crate) secondary: Option<Color>,
+}
+
+const DARK: Theme = Theme {
+    accent: Color::Cyan,
+    success: Color::Green,
+    error: Color::Red,
+    brand: Color::Magenta,
+    secondary: None,
+};
+
+const LIGHT: Theme = Theme {
+    accent: Color::Blue,
+    success: Color::Green,
+    error: Color::Red,
+    brand: Color::Magenta,
+    secondary: Some(Color::DarkGray),
+};
+
+const HIGH_CONTRAST: Theme = Theme {
+    accent: Color::LightCyan,
+    success: Color::LightGreen,
+    error: Color::LightRed,
+    brand: Color::LightMagenta,
+    secondary: Some(Color::Reset),
+};
+
+const BUILTIN_THEMES: [(&str, Theme); 3] = [
+    ("dark", DARK),
+    ("light", LIGHT),
+    ("high-contrast", HIGH_CONTRAST),
+];
+
+static CURRENT: RwLock<Theme> = RwLock::new(DARK);
+
+/// The theme widgets should draw with right now.
+pub(crate) fn current() -> Theme {
+    *CURRENT.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+pub(crate) fn set_current(theme: Theme) {
+    *CURRENT.write().unwrap_or_else(PoisonError::into_inner) = theme;
+}
+
+/// Resolve `name` to a built-in theme or a palette in `<codex_home>/themes`.
+pub(crate) fn load(codex_home: &Path, name: &str) -> Result<Theme, String> {
+    if let Some(theme) = builtin(name) {
+        return Ok(theme);
+    }
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(format!("invalid theme name `{name}`"));
+    }
+    let path = themes_dir(codex_home).join(format!("{name}.toml"));
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+    parse(&contents).map_err(|err| format!("{}: {err}", path.display()))
+}
+
+/// Names of the built-in themes followed by the user's palettes, sorted.
+pub(crate) fn available(codex_home: &Path) -> Vec<String> {
+    let mut custom: Vec<String> = std::fs::read_dir(themes_dir(codex_home))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+        .filter(|name| builtin(name).is_none())
+        .collect();
+    custom.sort();
+    BUILTIN_THEMES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .chain(custom)
+        .collect()
+}
+
+pub(crate) fn themes_dir(codex_home: &Path) -> PathBuf {
+    codex_home.join("themes")
+}
+
+fn builtin(name: &str) -> Option<Theme> {
+    BUILTIN_THEMES
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(_, theme)| *theme)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ThemeToml {
+    base: Option<String>,
+    accent: Option<String>,
+    success: Option<String>,
+    error: Option<String>,
+    brand: Option<String>,
+    secondary: Option<String>,
+}
+
+fn parse(contents: &str) -> Result<Theme, String> {
+    let toml: ThemeToml = toml::from_str(contents).map_err(|err| err.to_string())?;
+    let base_name = toml.base.as_deref().unwrap_or(DEFAULT_THEME);
+    let base = builtin(base_name).ok_or_else(|| format!("unknown base theme `{base_name}`"))?;
+    let color = |value: Option<String>, token: &str, fallback: Color| match value {
+        Some(value) => parse_color(&value, token),
+        None => Ok(fallback),
+    };
+    Ok(Theme {
+        accent: color(toml.accent, "accent", base.accent)?,
+        success: color(toml.success, "success", base.success)?,
+        error: color(toml.error, "error", base.error)?,
+        brand: color(toml.brand, "brand", base.brand)?,
+        secondary: match toml.secondary.as_deref() {
+            None => base.secondary,
+            Some("dim") => None,
+            Some(value) => Some(parse_color(value, "secondary")?),
+        },
+    })
+}
+
+fn parse_color(value: &str, token: &str) -> Result<Color, String> {
+    Color::from_str(value).map_err(|_| format!("invalid color `{value}` for `{token}`"))
+}
+
+/// Theme-aware counterparts of the `Stylize` color helpers: use `.accent()`,
+/// `.success()`, `.error()`, `.brand()` and `.secondary()` in place of
+/// `.cyan()`, `.green()`, `.red()`, `.magenta()` and `.dim()`.
+pub(crate) trait ThemeStylize<'a, T>: Stylize<'a, T> + Sized {
+    fn accent(self) -> T {
+        self.fg(current().accent)
+    }
+
+    fn success(self) -> T {
+        self.fg(current().success)
+    }
+
+    fn error(self) -> T {
+        self.fg(current().error)
+    }
+
+    fn brand(self) -> T {
+        self.fg(current().brand)
+    }
+
+    fn secondary(self) -> T {
+        match current().secondary {
+            Some(color) => self.fg(color),
+            None => self.dim(),
+        }
+    }
+}
+
+impl<'a, T, S: Stylize<'a, T>> ThemeStylize<'a, T> for S {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    #[test]
+    fn custom_palettes_fill_unset_tokens_from_their_base() {
+        let codex_home = TempDir::new().unwrap();
+        std::fs::create_dir(themes_dir(codex_home.path())).unwrap();
+        std::fs::write(
+            themes_dir(codex_home.path()).join("ocean.toml"),
+            "base = \"high-contrast\"\naccent = \"#5fafd7\"\nsecondary = \"dim\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            load(codex_home.path(), "ocean"),
+            Ok(Theme {
+                accent: Color::from_str("#5fafd7").unwrap(),
+                secondary: None,
+                ..HIGH_CONTRAST
+            })
+        );
+        assert_eq!(
+            available(codex_home.path()),
+            vec!["dark", "light", "high-contrast", "ocean"]
+        );
+    }
+
+    #[test]
+    fn invalid_palettes_are_rejected() {
+        assert_eq!(
+            parse("accent = \"not-a-color\"\n"),
+            Err("invalid color `not-a-color` for `accent`".to_string())
+        );
+        assert_eq!(
+            parse("base = \"solarized\"\n"),
+            Err("unknown base theme `solarized`".to_string())
+        );
+        assert!(parse("accnet = \"red\"\n").is_err());
+    }
+}
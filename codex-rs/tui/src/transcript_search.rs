@@ -0,0 +1,253 @@
+//! Search over the transcript overlay's history cells.
+//!
+//! The index holds the plain text of every cell's transcript lines, wrapped
+//! at the overlay's width so that a match can be mapped to the row it is
+//! drawn on. It is rebuilt when the width changes and extended as cells are
+//! appended. Plain queries match case-insensitively; regex queries use
+//! `regex_lite` syntax as typed.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use regex_lite::Regex;
+
+use crate::history_cell::HistoryCell;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SearchMode {
+    Plain,
+    Regex,
+}
+
+impl SearchMode {
+    pub(crate) fn toggled(self) -> Self {
+        match self {
+            SearchMode::Plain => SearchMode::Regex,
+            SearchMode::Regex => SearchMode::Plain,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct SearchQuery {
+    regex: Regex,
+}
+
+impl SearchQuery {
+    /// `None` for an empty query; an error message for an invalid regex.
+    pub(crate) fn new(text: &str, mode: SearchMode) -> Result<Option<Self>, String> {
+        if text.is_empty() {
+            return Ok(None);
+        }
+        let pattern = match mode {
+            SearchMode::Plain => format!("(?i){}", regex_lite::escape(text)),
+            SearchMode::Regex => text.to_string(),
+        };
+        Regex::new(&pattern)
+            .map(|regex| Some(Self { regex }))
+            .map_err(|err| err.to_string())
+    }
+
+    /// Byte ranges of the non-empty matches in `haystack`.
+    pub(crate) fn find_ranges(&self, haystack: &str) -> Vec<Range<usize>> {
+        self.regex
+            .find_iter(haystack)
+            .map(|m| m.range())
+            .filter(|range| !range.is_empty())
+            .collect()
+    }
+}
+
+/// A line of the transcript that contains at least one match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct SearchMatch {
+    pub(crate) cell: usize,
+    /// Row within the cell's transcript lines at the indexed width.
+    pub(crate) line: usize,
+}
+
+#[derive(Default)]
+pub(crate) struct SearchIndex {
+    width: Option<u16>,
+    cells: Vec<Vec<String>>,
+}
+
+impl SearchIndex {
+    /// Re-indexes every cell when `width` differs from the indexed width.
+    /// Returns whether the index was rebuilt.
+    pub(crate) fn ensure_width(&mut self, cells: &[Arc<dyn HistoryCell>], width: u16) -> bool {
+        if self.width == Some(width) {
+            return false;
+        }
+        self.width = Some(width);
+        self.cells = cells
+            .iter()
+            .map(|cell| cell_text(cell.as_ref(), width))
+            .collect();
+        true
+    }
+
+    /// Indexes a cell appended to the transcript.
+    pub(crate) fn push(&mut self, cell: &dyn HistoryCell) {
+        if let Some(width) = self.width {
+            self.cells.push(cell_text(cell, width));
+        }
+    }
+
+    pub(crate) fn matches(&self, query: &SearchQuery) -> Vec<SearchMatch> {
+        self.cells
+            .iter()
+            .enumerate()
+            .flat_map(|(cell, lines)| {
+                lines.iter().enumerate().filter_map(move |(line, text)| {
+                    query
+                        .regex
+                        .is_match(text)
+                        .then_some(SearchMatch { cell, line })
+                })
+            })
+            .collect()
+    }
+}
+
+fn cell_text(cell: &dyn HistoryCell, width: u16) -> Vec<String> {
+    cell.transcript_lines(width).iter().map(line_text).collect()
+}
+
+fn line_text(line: &Line<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+/// Returns `line` with the text of every match reversed.
+pub(crate) fn highlight_line(line: Line<'static>, query: &SearchQuery) -> Line<'static> {
+    let ranges = query.find_ranges(&line_text(&line));
+    if ranges.is_empty() {
+        return line;
+    }
+
+    let mut spans: Vec<Span<'static>> = Vec::with_capacity(line.spans.len() + ranges.len() * 2);
+    let mut offset = 0;
+    for span in line.spans {
+        let content = span.content.as_ref();
+        let span_range = offset..offset + content.len();
+        offset = span_range.end;
+        // Split points inside this span where highlighting starts or stops.
+        let mut cuts: Vec<usize> = ranges
+            .iter()
+            .flat_map(|range| [range.start, range.end])
+            .filter(|cut| span_range.start < *cut && *cut < span_range.end)
+            .map(|cut| cut - span_range.start)
+            .collect();
+        cuts.push(content.len());
+        let mut start = 0;
+        for end in cuts {
+            if end <= start {
+                continue;
+            }
+            let absolute = span_range.start + start;
+            let piece = Span::styled(content[start..end].to_string(), span.style);
+            let in_match = ranges.iter().any(|range| range.contains(&absolute));
+            spans.push(if in_match { piece.reversed() } else { piece });
+            start = end;
+        }
+    }
+    Line { spans, ..line }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history_cell::PlainHistoryCell;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::Modifier;
+
+    fn cell(lines: &[&str]) -> Arc<dyn HistoryCell> {
+        Arc::new(PlainHistoryCell::new(
+            lines
+                .iter()
+                .map(|line| Line::from(line.to_string()))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn plain_search_is_case_insensitive_and_regex_is_not_escaped() {
+        let cells = vec![cell(&["cargo test", "all good"]), cell(&["Cargo build"])];
+        let mut index = SearchIndex::default();
+        index.ensure_width(&cells, 80);
+
+        let plain = SearchQuery::new("cargo", SearchMode::Plain)
+            .expect("valid")
+            .expect("non-empty");
+        assert_eq!(
+            index.matches(&plain),
+            vec![
+                SearchMatch { cell: 0, line: 0 },
+                SearchMatch { cell: 1, line: 0 }
+            ]
+        );
+
+        let regex = SearchQuery::new("^a.. good$", SearchMode::Regex)
+            .expect("valid")
+            .expect("non-empty");
+        assert_eq!(
+            index.matches(&regex),
+            vec![SearchMatch { cell: 0, line: 1 }]
+        );
+
+        assert!(SearchQuery::new("(", SearchMode::Regex).is_err());
+        assert!(
+            SearchQuery::new("", SearchMode::Plain)
+                .expect("valid")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn pushed_cells_are_indexed() {
+        let mut cells = vec![cell(&["first"])];
+        let mut index = SearchIndex::default();
+        index.ensure_width(&cells, 80);
+        cells.push(cell(&["second first"]));
+        index.push(cells[1].as_ref());
+
+        let query = SearchQuery::new("first", SearchMode::Plain)
+            .expect("valid")
+            .expect("non-empty");
+        assert_eq!(index.matches(&query).len(), 2);
+    }
+
+    #[test]
+    fn highlight_splits_spans_at_match_boundaries() {
+        let query = SearchQuery::new("lo wo", SearchMode::Plain)
+            .expect("valid")
+            .expect("non-empty");
+        let line = highlight_line(Line::from(vec!["hello".into(), " world".into()]), &query);
+
+        let pieces: Vec<(String, bool)> = line
+            .spans
+            .iter()
+            .map(|span| {
+                (
+                    span.content.to_string(),
+                    span.style.add_modifier.contains(Modifier::REVERSED),
+                )
+            })
+            .collect();
+        assert_eq!(
+            pieces,
+            vec![
+                ("hel".to_string(), false),
+                ("lo".to_string(), true),
+                (" wo".to_string(), true),
+                ("rld".to_string(), false),
+            ]
+        );
+    }
+}
@@ -38,10 +38,13 @@ use tokio_stream::Stream;
 
 use crate::custom_terminal;
 use crate::custom_terminal::Terminal as CustomTerminal;
+use crate::image_render::InlineImage;
+use crate::terminal_notification;
 #[cfg(unix)]
 use crate::tui::job_control::SUSPEND_KEY;
 #[cfg(unix)]
 use crate::tui::job_control::SuspendContext;
+use codex_core::config::types::NotificationMethod;
 
 #[cfg(unix)]
 mod job_control;
@@ -163,6 +166,8 @@ pub struct Tui {
     draw_tx: tokio::sync::broadcast::Sender<()>,
     pub(crate) terminal: Terminal,
     pending_history_lines: Vec<Line<'static>>,
+    /// Images to draw after the pending history line at each index.
+    pending_history_images: Vec<(usize, InlineImage)>,
     alt_saved_viewport: Option<ratatui::layout::Rect>,
     #[cfg(unix)]
     suspend_context: SuspendContext,
@@ -216,6 +221,7 @@ impl Tui {
             draw_tx,
             terminal,
             pending_history_lines: vec![],
+            pending_history_images: vec![],
             alt_saved_viewport: None,
             #[cfg(unix)]
             suspend_context: SuspendContext::new(),
@@ -235,15 +241,16 @@ impl Tui {
         self.enhanced_keys_supported
     }
 
-    /// Emit a desktop notification now if the terminal is unfocused.
+    /// Emit a notification now via `method` if the terminal is unfocused.
     /// Returns true if a notification was posted.
-    pub fn notify(&mut self, message: impl AsRef<str>) -> bool {
-        if !self.terminal_focused.load(Ordering::Relaxed) {
-            let _ = execute!(stdout(), PostNotification(message.as_ref().to_string()));
-            true
-        } else {
-            false
+    pub fn notify(&mut self, message: impl AsRef<str>, method: NotificationMethod) -> bool {
+        if self.terminal_focused.load(Ordering::Relaxed) {
+            return false;
         }
+        if let Err(err) = terminal_notification::post(method, message.as_ref()) {
+            tracing::warn!("failed to post notification: {err}");
+        }
+        true
     }
 
     pub fn event_stream(&self) -> Pin<Box<dyn Stream<Item = TuiEvent> + Send + 'static>> {
@@ -348,6 +355,13 @@ impl Tui {
         self.frame_requester().schedule_frame();
     }
 
+    /// Queues `image` to be drawn below the history lines inserted so far.
+    pub(crate) fn insert_history_image(&mut self, image: InlineImage) {
+        self.pending_history_images
+            .push((self.pending_history_lines.len(), image));
+        self.frame_requester().schedule_frame();
+    }
+
     pub fn draw(
         &mut self,
         height: u16,
@@ -414,12 +428,21 @@ impl Tui {
                 terminal.set_viewport_area(area);
             }
 
-            if !self.pending_history_lines.is_empty() {
-                crate::insert_history::insert_history_lines(
-                    terminal,
-                    self.pending_history_lines.clone(),
-                )?;
-                self.pending_history_lines.clear();
+            if !self.pending_history_lines.is_empty() || !self.pending_history_images.is_empty() {
+                let mut lines = std::mem::take(&mut self.pending_history_lines);
+                let mut inserted = 0;
+                for (at, image) in std::mem::take(&mut self.pending_history_images) {
+                    let rest = lines.split_off(at - inserted);
+                    if !lines.is_empty() {
+                        crate::insert_history::insert_history_lines(terminal, lines)?;
+                    }
+                    crate::insert_history::insert_history_image(terminal, &image)?;
+                    lines = rest;
+                    inserted = at;
+                }
+                if !lines.is_empty() {
+                    crate::insert_history::insert_history_lines(terminal, lines)?;
+                }
             }
 
             // Update the y position for suspending so Ctrl-Z can place the cursor correctly.
@@ -486,25 +509,3 @@ fn spawn_frame_scheduler(
         }
     });
 }
-
-/// Command that emits an OSC 9 desktop notification with a message.
-#[derive(Debug, Clone)]
-pub struct PostNotification(pub String);
-
-impl Command for PostNotification {
-    fn write_ansi(&self, f: &mut impl fmt::Write) -> fmt::Result {
-        write!(f, "\x1b]9;{}\x07", self.0)
-    }
-
-    #[cfg(windows)]
-    fn execute_winapi(&self) -> Result<()> {
-        Err(std::io::Error::other(
-            "tried to execute PostNotification using WinAPI; use ANSI instead",
-        ))
-    }
-
-    #[cfg(windows)]
-    fn is_ansi_code_supported(&self) -> bool {
-        true
-    }
-}
@@ -0,0 +1,349 @@
+//! Side pane showing the accumulated diff of the current turn.
+//!
+//! `TurnDiff` events carry the full unified diff of every change made so far
+//! in the turn. The pane splits it per file, renders each file with the same
+//! styling as patch approvals, and lets the user collapse individual files.
+//! It is toggled with Ctrl+D on an empty composer and drawn to the right of
+//! the active cell and composer when the terminal is wide enough.
+
+use std::collections::HashSet;
+
+use codex_core::protocol::FileChange;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Block;
+use ratatui::widgets::Borders;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+
+use crate::diff_render::render_change;
+use crate::render::renderable::Renderable;
+use crate::render::renderable::RenderableItem;
+use crate::theme::ThemeStylize;
+
+/// Narrower terminals keep the single-column layout.
+const MIN_SPLIT_WIDTH: u16 = 100;
+/// Cap so a large diff does not push the composer off screen.
+const MAX_PANE_HEIGHT: u16 = 24;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TurnDiffFile {
+    path: String,
+    /// Unified diff for this file starting at the `---` header.
+    diff: String,
+    added: usize,
+    removed: usize,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct TurnDiffPane {
+    files: Vec<TurnDiffFile>,
+    /// Collapsed files by path, kept across diff updates.
+    collapsed: HashSet<String>,
+    selected: usize,
+    visible: bool,
+}
+
+impl TurnDiffPane {
+    pub(crate) fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    pub(crate) fn has_changes(&self) -> bool {
+        !self.files.is_empty()
+    }
+
+    pub(crate) fn toggle_visible(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Replace the contents with the latest aggregated turn diff.
+    pub(crate) fn set_diff(&mut self, unified_diff: &str) {
+        self.files = split_files(unified_diff);
+        self.selected = self.selected.min(self.files.len().saturating_sub(1));
+    }
+
+    /// Forget the previous turn's diff. Visibility and collapse state persist.
+    pub(crate) fn clear(&mut self) {
+        self.files.clear();
+        self.selected = 0;
+    }
+
+    pub(crate) fn select_next(&mut self) {
+        if self.selected + 1 < self.files.len() {
+            self.selected += 1;
+        }
+    }
+
+    pub(crate) fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub(crate) fn toggle_selected_collapsed(&mut self) {
+        let Some(file) = self.files.get(self.selected) else {
+            return;
+        };
+        if !self.collapsed.remove(&file.path) {
+            self.collapsed.insert(file.path.clone());
+        }
+    }
+
+    /// Lay `main` out next to the pane when it is visible and there is room.
+    pub(crate) fn beside<'a>(&'a self, main: RenderableItem<'a>) -> RenderableItem<'a> {
+        if !self.visible {
+            return main;
+        }
+        RenderableItem::Owned(Box::new(SplitWithPane { main, pane: self }))
+    }
+
+    /// Body lines plus the line index where the selected file starts.
+    fn lines(&self, width: u16) -> (Vec<Line<'static>>, usize) {
+        let mut lines: Vec<Line<'static>> = Vec::new();
+        if self.files.is_empty() {
+            lines.push("No changes in this turn yet".secondary().into());
+            return (lines, 0);
+        }
+
+        let mut selected_start = 0;
+        let diff_width = usize::from(width.saturating_sub(2)).max(1);
+        for (idx, file) in self.files.iter().enumerate() {
+            let collapsed = self.collapsed.contains(&file.path);
+            let marker = if collapsed { "▸ " } else { "▾ " };
+            let path: Span<'static> = if idx == self.selected {
+                selected_start = lines.len();
+                file.path.clone().accent().bold()
+            } else {
+                file.path.clone().bold()
+            };
+            lines.push(Line::from(vec![
+                marker.secondary(),
+                path,
+                " ".into(),
+                format!("+{}", file.added).success(),
+                " ".into(),
+                format!("-{}", file.removed).error(),
+            ]));
+            if !collapsed {
+                let change = FileChange::Update {
+                    unified_diff: file.diff.clone(),
+                    move_path: None,
+                };
+                let mut rendered = Vec::new();
+                render_change(&change, &mut rendered, diff_width);
+                lines.extend(rendered.into_iter().map(|mut line| {
+                    line.spans.insert(0, "  ".into());
+                    line
+                }));
+            }
+        }
+        (lines, selected_start)
+    }
+
+    fn title(&self) -> Line<'static> {
+        let added: usize = self.files.iter().map(|file| file.added).sum();
+        let removed: usize = self.files.iter().map(|file| file.removed).sum();
+        let count = self.files.len();
+        let noun = if count == 1 { "file" } else { "files" };
+        Line::from(vec![
+            "Turn diff".bold(),
+            format!(" · {count} {noun} ").secondary(),
+            format!("+{added}").success(),
+            " ".into(),
+            format!("-{removed}").error(),
+        ])
+    }
+
+    fn hint() -> Line<'static> {
+        "ctrl+↑/↓ select · ctrl+o collapse · ctrl+d close"
+            .secondary()
+            .into()
+    }
+}
+
+impl Renderable for TurnDiffPane {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let block = Block::default().borders(Borders::LEFT).secondary();
+        let inner = block.inner(area);
+        block.render(area, buf);
+        if inner.height == 0 || inner.width == 0 {
+            return;
+        }
+
+        let (body, selected_start) = self.lines(inner.width);
+        let body_height = usize::from(inner.height.saturating_sub(2));
+        // Keep the selected file's header in view.
+        let max_scroll = body.len().saturating_sub(body_height);
+        let scroll = selected_start.min(max_scroll);
+        let mut lines = vec![self.title()];
+        lines.extend(body.into_iter().skip(scroll).take(body_height));
+        lines.resize(usize::from(inner.height).saturating_sub(1), Line::default());
+        lines.push(Self::hint());
+        Paragraph::new(lines).render(inner, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        let (body, _) = self.lines(width.saturating_sub(1));
+        let height = u16::try_from(body.len() + 2).unwrap_or(u16::MAX);
+        height.min(MAX_PANE_HEIGHT)
+    }
+}
+
+struct SplitWithPane<'a> {
+    main: RenderableItem<'a>,
+    pane: &'a TurnDiffPane,
+}
+
+impl SplitWithPane<'_> {
+    fn split(area: Rect) -> (Rect, Option<Rect>) {
+        if area.width < MIN_SPLIT_WIDTH {
+            return (area, None);
+        }
+        let pane_width = area.width * 2 / 5;
+        let main = Rect::new(area.x, area.y, area.width - pane_width, area.height);
+        let pane = Rect::new(main.right(), area.y, pane_width, area.height);
+        (main, Some(pane))
+    }
+}
+
+impl Renderable for SplitWithPane<'_> {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        let (main, pane) = Self::split(area);
+        self.main.render(main, buf);
+        if let Some(pane) = pane {
+            self.pane.render(pane, buf);
+        }
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        let (main, pane) = Self::split(Rect::new(0, 0, width, 0));
+        let main_height = self.main.desired_height(main.width);
+        match pane {
+            Some(pane) => main_height.max(self.pane.desired_height(pane.width)),
+            None => main_height,
+        }
+    }
+
+    fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
+        let (main, _) = Self::split(area);
+        self.main.cursor_pos(main)
+    }
+}
+
+/// Split an aggregated `diff --git` stream into per-file sections.
+fn split_files(unified_diff: &str) -> Vec<TurnDiffFile> {
+    let mut files = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+    for line in unified_diff.lines() {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            if let Some((path, body)) = current.take() {
+                files.push(file_from_section(path, &body));
+            }
+            let path = rest
+                .split_once(" b/")
+                .map(|(_, right)| right)
+                .unwrap_or(rest)
+                .to_string();
+            current = Some((path, Vec::new()));
+        } else if let Some((_, body)) = current.as_mut() {
+            body.push(line);
+        }
+    }
+    if let Some((path, body)) = current {
+        files.push(file_from_section(path, &body));
+    }
+    files
+}
+
+fn file_from_section(path: String, body: &[&str]) -> TurnDiffFile {
+    let diff_lines: Vec<&str> = body
+        .iter()
+        .skip_while(|line| !line.starts_with("--- "))
+        .copied()
+        .collect();
+    let mut added = 0;
+    let mut removed = 0;
+    for line in &diff_lines {
+        if line.starts_with('+') && !line.starts_with("+++ ") {
+            added += 1;
+        } else if line.starts_with('-') && !line.starts_with("--- ") {
+            removed += 1;
+        }
+    }
+    let mut diff = diff_lines.join("\n");
+    if !diff.is_empty() {
+        diff.push('\n');
+    }
+    TurnDiffFile {
+        path,
+        diff,
+        added,
+        removed,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const DIFF: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,2 +1,2 @@
+ fn main() {
+-    old();
++    new();
+diff --git a/README.md b/README.md
+new file mode 100644
+index 0000000..3333333
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1,2 @@
++# Demo
++Hello
+";
+
+    fn render_text(pane: &TurnDiffPane, width: u16) -> Vec<String> {
+        let (lines, _) = pane.lines(width);
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn splits_turn_diff_per_file() {
+        let files = split_files(DIFF);
+        let summary: Vec<(&str, usize, usize)> = files
+            .iter()
+            .map(|file| (file.path.as_str(), file.added, file.removed))
+            .collect();
+        assert_eq!(summary, vec![("src/lib.rs", 1, 1), ("README.md", 2, 0)]);
+        assert!(files[0].diff.starts_with("--- a/src/lib.rs\n"));
+    }
+
+    #[test]
+    fn collapsed_files_only_show_header() {
+        let mut pane = TurnDiffPane::default();
+        pane.set_diff(DIFF);
+        pane.toggle_selected_collapsed();
+
+        let text = render_text(&pane, 60);
+        assert_eq!(text[0], "▸ src/lib.rs +1 -1");
+        assert_eq!(text[1], "▾ README.md +2 -0");
+        assert!(text[2..].iter().any(|line| line.contains("# Demo")));
+
+        // Collapse state follows the file across updates.
+        pane.set_diff(DIFF);
+        assert_eq!(render_text(&pane, 60)[0], "▸ src/lib.rs +1 -1");
+    }
+}
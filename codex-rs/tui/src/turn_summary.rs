@@ -0,0 +1,247 @@
+//! Per-turn statistics shown after `TaskComplete`.
+//!
+//! `ChatWidget` feeds [`TurnStats`] from the events it already handles while
+//! a turn runs. When a turn that ran tools completes, the stats become a
+//! [`TurnSummaryCell`]: a single dim line in the scrollback, expanded into a
+//! per-category breakdown with the touched files in the transcript overlay
+//! (Ctrl+T).
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::Instant;
+
+use codex_core::protocol::TokenUsage;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+
+use crate::diff_render::display_path_for;
+use crate::history_cell::HistoryCell;
+use crate::status_indicator_widget::fmt_elapsed_compact;
+use crate::status_indicator_widget::format_token_count;
+use crate::theme::ThemeStylize;
+
+/// Activity recorded for the running turn.
+#[derive(Debug)]
+pub(crate) struct TurnStats {
+    started_at: Instant,
+    exec_calls: i64,
+    mcp_calls: i64,
+    patches: i64,
+    files_touched: BTreeSet<PathBuf>,
+    /// Session token totals when the turn started.
+    usage_at_start: Option<TokenUsage>,
+    cost_usd: Option<f64>,
+}
+
+impl TurnStats {
+    pub(crate) fn start(usage_at_start: Option<TokenUsage>) -> Self {
+        Self {
+            started_at: Instant::now(),
+            exec_calls: 0,
+            mcp_calls: 0,
+            patches: 0,
+            files_touched: BTreeSet::new(),
+            usage_at_start,
+            cost_usd: None,
+        }
+    }
+
+    pub(crate) fn record_exec(&mut self) {
+        self.exec_calls += 1;
+    }
+
+    pub(crate) fn record_mcp_call(&mut self) {
+        self.mcp_calls += 1;
+    }
+
+    pub(crate) fn record_patch<'a>(&mut self, paths: impl IntoIterator<Item = &'a PathBuf>) {
+        self.patches += 1;
+        self.files_touched.extend(paths.into_iter().cloned());
+    }
+
+    /// Whether the turn ran any commands, MCP tools, or patches. Purely
+    /// conversational turns get no summary.
+    pub(crate) fn had_tool_calls(&self) -> bool {
+        self.exec_calls + self.mcp_calls + self.patches > 0
+    }
+
+    pub(crate) fn record_cost(&mut self, turn_cost_usd: f64) {
+        self.cost_usd = Some(turn_cost_usd);
+    }
+
+    /// Builds the summary cell. `usage_now` is the session token total after
+    /// the turn; tokens are left out when either total is unknown.
+    pub(crate) fn finish(self, usage_now: Option<&TokenUsage>, cwd: &Path) -> TurnSummaryCell {
+        let tokens = match (&self.usage_at_start, usage_now) {
+            (Some(start), Some(now)) => Some((
+                now.input_tokens - start.input_tokens,
+                now.output_tokens - start.output_tokens,
+            )),
+            (None, Some(now)) => Some((now.input_tokens, now.output_tokens)),
+            _ => None,
+        };
+        TurnSummaryCell {
+            elapsed: self.started_at.elapsed(),
+            exec_calls: self.exec_calls,
+            mcp_calls: self.mcp_calls,
+            patches: self.patches,
+            files_touched: self
+                .files_touched
+                .iter()
+                .map(|path| display_path_for(path, cwd))
+                .collect(),
+            tokens,
+            cost_usd: self.cost_usd,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct TurnSummaryCell {
+    elapsed: Duration,
+    exec_calls: i64,
+    mcp_calls: i64,
+    patches: i64,
+    files_touched: Vec<String>,
+    /// Input and output tokens used by the turn.
+    tokens: Option<(i64, i64)>,
+    cost_usd: Option<f64>,
+}
+
+impl TurnSummaryCell {
+    fn parts(&self) -> Vec<String> {
+        let mut parts = vec![format!(
+            "Turn took {}",
+            fmt_elapsed_compact(self.elapsed.as_secs())
+        )];
+        for (count, singular, plural) in [
+            (self.exec_calls, "command", "commands"),
+            (self.mcp_calls, "tool call", "tool calls"),
+            (self.patches, "patch", "patches"),
+        ] {
+            if count > 0 {
+                let noun = if count == 1 { singular } else { plural };
+                parts.push(format!("{count} {noun}"));
+            }
+        }
+        match self.files_touched.len() {
+            0 => {}
+            1 => parts.push("1 file".to_string()),
+            files => parts.push(format!("{files} files")),
+        }
+        if let Some((input, output)) = self.tokens {
+            parts.push(format!(
+                "↑{} ↓{}",
+                format_token_count(input),
+                format_token_count(output)
+            ));
+        }
+        if let Some(cost) = self.cost_usd {
+            parts.push(format!("${cost:.2}"));
+        }
+        parts
+    }
+}
+
+impl HistoryCell for TurnSummaryCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        vec![vec!["• ".secondary(), self.parts().join(" · ").secondary()].into()]
+    }
+
+    fn transcript_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        let mut lines: Vec<Line<'static>> =
+            vec![vec!["• ".secondary(), "Turn summary".bold()].into()];
+        let mut row = |label: &str, value: String| {
+            lines.push(vec![format!("  {label:<11}").secondary(), value.into()].into());
+        };
+        row("Duration", fmt_elapsed_compact(self.elapsed.as_secs()));
+        row("Commands", self.exec_calls.to_string());
+        row("MCP calls", self.mcp_calls.to_string());
+        row("Patches", self.patches.to_string());
+        if let Some((input, output)) = self.tokens {
+            row(
+                "Tokens",
+                format!(
+                    "{} in, {} out",
+                    format_token_count(input),
+                    format_token_count(output)
+                ),
+            );
+        }
+        if let Some(cost) = self.cost_usd {
+            row("Cost", format!("${cost:.4}"));
+        }
+        if !self.files_touched.is_empty() {
+            lines.push(vec!["  Files".secondary()].into());
+            for file in &self.files_touched {
+                lines.push(vec!["    ".into(), file.clone().into()].into());
+            }
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn text(lines: Vec<Line<'static>>) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    fn usage(input_tokens: i64, output_tokens: i64) -> TokenUsage {
+        TokenUsage {
+            input_tokens,
+            output_tokens,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn summarizes_turn_activity() {
+        let cwd = PathBuf::from("/repo");
+        let mut stats = TurnStats::start(Some(usage(1_000, 100)));
+        stats.record_exec();
+        stats.record_exec();
+        stats.record_mcp_call();
+        stats.record_patch(&[cwd.join("src/lib.rs"), cwd.join("README.md")]);
+        stats.record_cost(0.0421);
+        assert!(stats.had_tool_calls());
+
+        let mut cell = stats.finish(Some(&usage(13_500, 1_300)), &cwd);
+        cell.elapsed = Duration::from_secs(65);
+
+        assert_eq!(
+            text(cell.display_lines(80)),
+            vec![
+                "• Turn took 1m 05s · 2 commands · 1 tool call · 1 patch · 2 files · ↑12.5K ↓1.2K · $0.04"
+            ]
+        );
+        assert_eq!(
+            text(cell.transcript_lines(80)),
+            vec![
+                "• Turn summary",
+                "  Duration   1m 05s",
+                "  Commands   2",
+                "  MCP calls  1",
+                "  Patches    1",
+                "  Tokens     12.5K in, 1.2K out",
+                "  Cost       $0.0421",
+                "  Files",
+                "    /repo/README.md",
+                "    /repo/src/lib.rs",
+            ]
+        );
+    }
+}
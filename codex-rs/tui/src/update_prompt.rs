@@ -7,6 +7,7 @@ use crate::render::renderable::ColumnRenderable;
 use crate::render::renderable::Renderable;
 use crate::render::renderable::RenderableExt as _;
 use crate::selection_list::selection_option_row;
+use crate::theme::ThemeStylize;
 use crate::tui::FrameRequester;
 use crate::tui::Tui;
 use crate::tui::TuiEvent;
@@ -190,7 +191,7 @@ impl WidgetRef for &UpdatePromptScreen {
 
         column.push("");
         column.push(Line::from(vec![
-            padded_emoji("  ✨").bold().cyan(),
+            padded_emoji("  ✨").bold().accent(),
             "Update available!".bold(),
             " ".into(),
             format!(
@@ -198,14 +199,14 @@ impl WidgetRef for &UpdatePromptScreen {
                 current = self.current_version,
                 latest = self.latest_version
             )
-            .dim(),
+            .secondary(),
         ]));
         column.push("");
         column.push(
             Line::from(vec![
-                "Release notes: ".dim(),
+                "Release notes: ".secondary(),
                 "https://github.com/openai/codex/releases/latest"
-                    .dim()
+                    .secondary()
                     .underlined(),
             ])
             .inset(Insets::tlbr(0, 2, 0, 0)),
@@ -229,9 +230,9 @@ impl WidgetRef for &UpdatePromptScreen {
         column.push("");
         column.push(
             Line::from(vec![
-                "Press ".dim(),
+                "Press ".secondary(),
                 key_hint::plain(KeyCode::Enter).into(),
-                " to continue".dim(),
+                " to continue".secondary(),
             ])
             .inset(Insets::tlbr(0, 2, 0, 0)),
         );
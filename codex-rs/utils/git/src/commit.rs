@@ -0,0 +1,258 @@
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::GitToolingError;
+use crate::operations::ensure_git_repository;
+use crate::operations::normalize_relative_path;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_status;
+use crate::operations::run_git_for_stdout;
+use crate::operations::run_git_for_stdout_all;
+
+/// A changed path reported by `git status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+pub struct StatusEntry {
+    /// Path relative to the repository root.
+    pub path: PathBuf,
+    /// Previous path for renames and copies.
+    pub original_path: Option<PathBuf>,
+    /// Porcelain status code for the index (e.g. `M`, `A`, `?`, or space).
+    pub index: String,
+    /// Porcelain status code for the working tree.
+    pub worktree: String,
+}
+
+impl StatusEntry {
+    /// Whether the entry has changes staged for the next commit.
+    pub fn is_staged(&self) -> bool {
+        !matches!(self.index.as_str(), " " | "?" | "!")
+    }
+
+    /// Whether the path is not tracked by git.
+    pub fn is_untracked(&self) -> bool {
+        self.index == "?"
+    }
+}
+
+/// Summary of the working tree: current branch and changed paths.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+pub struct WorkingTreeStatus {
+    /// Checked-out branch, or `None` for a detached `HEAD`.
+    pub branch: Option<String>,
+    pub entries: Vec<StatusEntry>,
+}
+
+/// Returns the branch and changed paths of the repository containing `repo_path`.
+///
+/// Mirrors `git status --porcelain=v1 --branch`, including untracked files.
+pub fn working_tree_status(repo_path: &Path) -> Result<WorkingTreeStatus, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let output = run_git_for_stdout_all(
+        repo_root.as_path(),
+        vec![
+            OsString::from("status"),
+            OsString::from("--porcelain=v1"),
+            OsString::from("--branch"),
+            OsString::from("--untracked-files=all"),
+            OsString::from("-z"),
+        ],
+        None,
+    )?;
+    Ok(parse_porcelain_status(&output))
+}
+
+/// Stages `paths` (relative to the repository root), including deletions.
+pub fn stage_files(repo_path: &Path, paths: &[PathBuf]) -> Result<(), GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    if paths.is_empty() {
+        return Ok(());
+    }
+
+    let mut args = vec![
+        OsString::from("add"),
+        OsString::from("--all"),
+        OsString::from("--"),
+    ];
+    for path in paths {
+        if path.is_absolute() {
+            return Err(GitToolingError::NonRelativePath { path: path.clone() });
+        }
+        args.push(normalize_relative_path(path)?.into_os_string());
+    }
+    run_git_for_status(repo_root.as_path(), args, None)
+}
+
+/// Commits the staged changes with `message` and returns the new commit id.
+pub fn commit_staged(repo_path: &Path, message: &str) -> Result<String, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    run_git_for_status(
+        repo_root.as_path(),
+        vec![
+            OsString::from("commit"),
+            OsString::from("--message"),
+            OsString::from(message),
+        ],
+        None,
+    )?;
+    run_git_for_stdout(
+        repo_root.as_path(),
+        vec![OsString::from("rev-parse"), OsString::from("HEAD")],
+        None,
+    )
+}
+
+/// Creates branch `name` at `HEAD` and checks it out, keeping local changes.
+pub fn create_branch(repo_path: &Path, name: &str) -> Result<(), GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    run_git_for_status(
+        repo_root.as_path(),
+        vec![
+            OsString::from("switch"),
+            OsString::from("--create"),
+            OsString::from(name),
+        ],
+        None,
+    )
+}
+
+fn parse_porcelain_status(output: &str) -> WorkingTreeStatus {
+    let mut branch = None;
+    let mut entries = Vec::new();
+    let mut records = output.split('\0').filter(|record| !record.is_empty());
+    while let Some(record) = records.next() {
+        if let Some(header) = record.strip_prefix("## ") {
+            branch = parse_branch_header(header);
+            continue;
+        }
+        let (Some(index), Some(worktree), Some(path)) =
+            (record.get(0..1), record.get(1..2), record.get(3..))
+        else {
+            continue;
+        };
+        // Renames and copies are followed by a record with the source path.
+        let original_path = if matches!(index, "R" | "C") {
+            records.next().map(PathBuf::from)
+        } else {
+            None
+        };
+        entries.push(StatusEntry {
+            path: PathBuf::from(path),
+            original_path,
+            index: index.to_string(),
+            worktree: worktree.to_string(),
+        });
+    }
+    WorkingTreeStatus { branch, entries }
+}
+
+fn parse_branch_header(header: &str) -> Option<String> {
+    if header.starts_with("HEAD (no branch)") {
+        return None;
+    }
+    let name = header
+        .strip_prefix("No commits yet on ")
+        .or_else(|| header.strip_prefix("Initial commit on "))
+        .unwrap_or(header);
+    let name = name.split("...").next().unwrap_or(name);
+    let name = name.split(' ').next().unwrap_or(name);
+    Some(name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn run_git_in(repo_path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .status()
+            .expect("git command");
+        assert!(status.success(), "git command failed: {args:?}");
+    }
+
+    fn init_test_repo(repo_path: &Path) {
+        run_git_in(repo_path, &["init", "--initial-branch=main"]);
+        run_git_in(repo_path, &["config", "core.autocrlf", "false"]);
+        run_git_in(repo_path, &["config", "user.name", "Tester"]);
+        run_git_in(repo_path, &["config", "user.email", "test@example.com"]);
+    }
+
+    #[test]
+    fn parses_porcelain_status_with_renames() {
+        let output =
+            "## feature...origin/feature [ahead 1]\0R  new.rs\0old.rs\0 M lib.rs\0?? notes.txt\0";
+        let status = parse_porcelain_status(output);
+        assert_eq!(status.branch.as_deref(), Some("feature"));
+        assert_eq!(
+            status.entries[0].original_path,
+            Some(PathBuf::from("old.rs"))
+        );
+        let summary: Vec<(&str, bool, bool)> = status
+            .entries
+            .iter()
+            .map(|entry| {
+                (
+                    entry.path.to_str().unwrap_or_default(),
+                    entry.is_staged(),
+                    entry.is_untracked(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("new.rs", true, false),
+                ("lib.rs", false, false),
+                ("notes.txt", false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn stage_commit_and_branch() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("a.txt"), "a\n")?;
+        std::fs::write(repo.join("b.txt"), "b\n")?;
+
+        let status = working_tree_status(repo)?;
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.entries.len(), 2);
+        assert!(status.entries.iter().all(StatusEntry::is_untracked));
+
+        stage_files(repo, &[PathBuf::from("a.txt")])?;
+        let commit = commit_staged(repo, "add a")?;
+        assert_eq!(commit.len(), 40);
+
+        let status = working_tree_status(repo)?;
+        let paths: Vec<PathBuf> = status.entries.into_iter().map(|entry| entry.path).collect();
+        assert_eq!(paths, vec![PathBuf::from("b.txt")]);
+
+        create_branch(repo, "agent/changes")?;
+        assert_eq!(
+            working_tree_status(repo)?.branch.as_deref(),
+            Some("agent/changes")
+        );
+
+        assert!(matches!(
+            stage_files(repo, &[PathBuf::from("../outside.txt")]),
+            Err(GitToolingError::PathEscapesRepository { .. })
+        ));
+        Ok(())
+    }
+}
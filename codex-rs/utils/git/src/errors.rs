@@ -26,6 +26,8 @@ pub enum GitToolingError {
     NonRelativePath { path: PathBuf },
     #[error("path {path:?} escapes the repository root")]
     PathEscapesRepository { path: PathBuf },
+    #[error("merging {branch} conflicts in {paths:?}")]
+    MergeConflict { branch: String, paths: Vec<PathBuf> },
     #[error("failed to process path inside worktree")]
     PathPrefix(#[from] std::path::StripPrefixError),
     #[error(transparent)]
@@ -296,6 +296,167 @@ pub fn restore_to_commit(repo_path: &Path, commit_id: &str) -> Result<(), GitToo
     restore_to_commit_inner(repo_root.as_path(), repo_prefix.as_deref(), commit_id)
 }
 
+/// Produce a unified diff describing how the tree changed between two ghost commits.
+/// The diff is scoped to `repo_path` when it points at a subdirectory of the repository.
+pub fn diff_ghost_commits(
+    repo_path: &Path,
+    from: &GhostCommit,
+    to: &GhostCommit,
+) -> Result<String, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+
+    let repo_root = resolve_repository_root(repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), repo_path);
+    let mut diff_args = vec![
+        OsString::from("diff"),
+        OsString::from("--no-color"),
+        OsString::from("--no-ext-diff"),
+        OsString::from("--binary"),
+        OsString::from(from.id()),
+        OsString::from(to.id()),
+        OsString::from("--"),
+    ];
+    if let Some(prefix) = repo_prefix {
+        diff_args.push(prefix.into_os_string());
+    }
+
+    run_git_for_stdout_all(repo_root.as_path(), diff_args, None)
+}
+
+/// List the files that differ between two ghost commits, relative to `repo_path`.
+pub fn changed_paths_between_ghost_commits(
+    repo_path: &Path,
+    from: &GhostCommit,
+    to: &GhostCommit,
+) -> Result<Vec<PathBuf>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+
+    let repo_root = resolve_repository_root(repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), repo_path);
+    let mut diff_args = vec![
+        OsString::from("diff"),
+        OsString::from("--name-only"),
+        OsString::from("--no-renames"),
+        OsString::from("-z"),
+        OsString::from(from.id()),
+        OsString::from(to.id()),
+        OsString::from("--"),
+    ];
+    if let Some(prefix) = repo_prefix.as_ref() {
+        diff_args.push(prefix.as_os_str().to_os_string());
+    }
+
+    let output = run_git_for_stdout_all(repo_root.as_path(), diff_args, None)?;
+    Ok(output
+        .split('\0')
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let path = PathBuf::from(entry);
+            match repo_prefix.as_deref() {
+                Some(prefix) => path
+                    .strip_prefix(prefix)
+                    .map(Path::to_path_buf)
+                    .unwrap_or(path),
+                None => path,
+            }
+        })
+        .collect())
+}
+
+/// Read `path` (relative to `repo_path`) as captured by `commit`.
+/// Returns `None` when the file did not exist in the snapshot.
+pub fn read_ghost_commit_file(
+    repo_path: &Path,
+    commit: &GhostCommit,
+    path: &Path,
+) -> Result<Option<String>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+
+    let repo_root = resolve_repository_root(repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), repo_path);
+    let relative = repo_relative_path(repo_prefix.as_deref(), path)?;
+    if !commit_contains_path(repo_root.as_path(), commit.id(), &relative) {
+        return Ok(None);
+    }
+
+    run_git_for_stdout_all(
+        repo_root.as_path(),
+        vec![
+            OsString::from("show"),
+            commit_object_spec(commit.id(), &relative),
+        ],
+        None,
+    )
+    .map(Some)
+}
+
+/// Restore only `paths` (relative to `repo_path`) to their state in `commit`.
+/// Files that did not exist when the snapshot was captured are removed.
+pub fn restore_ghost_commit_paths(
+    repo_path: &Path,
+    commit: &GhostCommit,
+    paths: &[PathBuf],
+) -> Result<(), GitToolingError> {
+    ensure_git_repository(repo_path)?;
+
+    let repo_root = resolve_repository_root(repo_path)?;
+    let repo_prefix = repo_subdir(repo_root.as_path(), repo_path);
+    for path in paths {
+        let relative = repo_relative_path(repo_prefix.as_deref(), path)?;
+        if commit_contains_path(repo_root.as_path(), commit.id(), &relative) {
+            run_git_for_status(
+                repo_root.as_path(),
+                vec![
+                    OsString::from("restore"),
+                    OsString::from("--source"),
+                    OsString::from(commit.id()),
+                    OsString::from("--worktree"),
+                    OsString::from("--staged"),
+                    OsString::from("--"),
+                    relative.into_os_string(),
+                ],
+                None,
+            )?;
+        } else {
+            remove_path(&repo_root.join(&relative))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Resolves `path` (relative to the session directory) against the repository root.
+fn repo_relative_path(repo_prefix: Option<&Path>, path: &Path) -> Result<PathBuf, GitToolingError> {
+    let normalized = normalize_relative_path(path)?;
+    Ok(match repo_prefix {
+        Some(prefix) => prefix.join(normalized),
+        None => normalized,
+    })
+}
+
+/// Returns true when `path` exists in the tree of `commit_id`.
+fn commit_contains_path(repo_root: &Path, commit_id: &str, path: &Path) -> bool {
+    run_git_for_status(
+        repo_root,
+        vec![
+            OsString::from("cat-file"),
+            OsString::from("-e"),
+            commit_object_spec(commit_id, path),
+        ],
+        None,
+    )
+    .is_ok()
+}
+
+/// Builds a `<commit>:<path>` object name using forward slashes on every platform.
+fn commit_object_spec(commit_id: &str, path: &Path) -> OsString {
+    let components: Vec<String> = path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+    OsString::from(format!("{commit_id}:{}", components.join("/")))
+}
+
 /// Restores the working tree and index to the given commit using `git restore`.
 /// The repository root and optional repository-relative prefix limit the restore scope.
 fn restore_to_commit_inner(
@@ -579,6 +740,103 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    /// Verifies the diff between two ghost commits reflects the intervening edits.
+    fn diff_between_ghost_commits() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("tracked.txt"), "one\n")?;
+        run_git_in(repo, &["add", "tracked.txt"]);
+        run_git_in(
+            repo,
+            &[
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "init",
+            ],
+        );
+
+        let before = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+        std::fs::write(repo.join("tracked.txt"), "two\n")?;
+        std::fs::write(repo.join("added.txt"), "new\n")?;
+        let after = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        let diff = diff_ghost_commits(repo, &before, &after)?;
+        assert!(diff.contains("diff --git a/tracked.txt b/tracked.txt"));
+        assert!(diff.contains("-one\n+two\n"));
+        assert!(diff.contains("diff --git a/added.txt b/added.txt"));
+
+        let unchanged = diff_ghost_commits(repo, &after, &after)?;
+        assert_eq!(unchanged, "");
+
+        Ok(())
+    }
+
+    #[test]
+    /// Verifies only the requested paths are restored from a ghost commit.
+    fn restore_selected_paths_from_ghost_commit() -> Result<(), GitToolingError> {
+        let temp = tempfile::tempdir()?;
+        let repo = temp.path();
+        init_test_repo(repo);
+        std::fs::write(repo.join("a.txt"), "a before\n")?;
+        std::fs::write(repo.join("b.txt"), "b before\n")?;
+        run_git_in(repo, &["add", "a.txt", "b.txt"]);
+        run_git_in(
+            repo,
+            &[
+                "-c",
+                "user.name=Tester",
+                "-c",
+                "user.email=test@example.com",
+                "commit",
+                "-m",
+                "init",
+            ],
+        );
+
+        let before = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+        std::fs::write(repo.join("a.txt"), "a after\n")?;
+        std::fs::write(repo.join("b.txt"), "b after\n")?;
+        std::fs::write(repo.join("created.txt"), "new\n")?;
+        let after = create_ghost_commit(&CreateGhostCommitOptions::new(repo))?;
+
+        let mut changed = changed_paths_between_ghost_commits(repo, &before, &after)?;
+        changed.sort();
+        assert_eq!(
+            changed,
+            vec![
+                PathBuf::from("a.txt"),
+                PathBuf::from("b.txt"),
+                PathBuf::from("created.txt"),
+            ]
+        );
+        assert_eq!(
+            read_ghost_commit_file(repo, &before, Path::new("a.txt"))?,
+            Some("a before\n".to_string())
+        );
+        assert_eq!(
+            read_ghost_commit_file(repo, &before, Path::new("created.txt"))?,
+            None
+        );
+
+        restore_ghost_commit_paths(
+            repo,
+            &before,
+            &[PathBuf::from("a.txt"), PathBuf::from("created.txt")],
+        )?;
+
+        assert_eq!(std::fs::read_to_string(repo.join("a.txt"))?, "a before\n");
+        assert_eq!(std::fs::read_to_string(repo.join("b.txt"))?, "b after\n");
+        assert!(!repo.join("created.txt").exists());
+
+        Ok(())
+    }
+
     #[test]
     fn create_snapshot_reports_large_untracked_dirs() -> Result<(), GitToolingError> {
         let temp = tempfile::tempdir()?;
@@ -3,10 +3,12 @@ use std::path::PathBuf;
 
 mod apply;
 mod branch;
+mod commit;
 mod errors;
 mod ghost_commits;
 mod operations;
 mod platform;
+mod worktree;
 
 pub use apply::ApplyGitRequest;
 pub use apply::ApplyGitResult;
@@ -15,20 +17,37 @@ pub use apply::extract_paths_from_patch;
 pub use apply::parse_git_apply_output;
 pub use apply::stage_paths;
 pub use branch::merge_base_with_head;
+pub use commit::StatusEntry;
+pub use commit::WorkingTreeStatus;
+pub use commit::commit_staged;
+pub use commit::create_branch;
+pub use commit::stage_files;
+pub use commit::working_tree_status;
 pub use errors::GitToolingError;
 pub use ghost_commits::CreateGhostCommitOptions;
 pub use ghost_commits::GhostSnapshotReport;
 pub use ghost_commits::LargeUntrackedDir;
 pub use ghost_commits::capture_ghost_snapshot_report;
+pub use ghost_commits::changed_paths_between_ghost_commits;
 pub use ghost_commits::create_ghost_commit;
 pub use ghost_commits::create_ghost_commit_with_report;
+pub use ghost_commits::diff_ghost_commits;
+pub use ghost_commits::read_ghost_commit_file;
 pub use ghost_commits::restore_ghost_commit;
+pub use ghost_commits::restore_ghost_commit_paths;
 pub use ghost_commits::restore_to_commit;
 pub use platform::create_symlink;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
 use ts_rs::TS;
+pub use worktree::WorktreeInfo;
+pub use worktree::create_worktree;
+pub use worktree::delete_branch;
+pub use worktree::find_stale_worktrees;
+pub use worktree::list_worktrees;
+pub use worktree::merge_worktree_into_base;
+pub use worktree::remove_worktree;
 
 type CommitID = String;
 
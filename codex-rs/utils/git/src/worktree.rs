@@ -0,0 +1,466 @@
+use std::ffi::OsString;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde::Serialize;
+use ts_rs::TS;
+
+use crate::GitToolingError;
+use crate::commit::working_tree_status;
+use crate::operations::ensure_git_repository;
+use crate::operations::resolve_repository_root;
+use crate::operations::run_git_for_status;
+use crate::operations::run_git_for_stdout;
+use crate::operations::run_git_for_stdout_all;
+
+/// A checkout reported by `git worktree list`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema, TS)]
+pub struct WorktreeInfo {
+    pub path: PathBuf,
+    /// Commit checked out in the worktree, or `None` before the first commit.
+    pub head: Option<String>,
+    /// Checked-out branch without the `refs/heads/` prefix, or `None` when detached.
+    pub branch: Option<String>,
+    /// Whether this is the main checkout that owns the repository.
+    pub is_main: bool,
+    /// Whether the worktree was locked with `git worktree lock`.
+    pub locked: bool,
+}
+
+/// Lists every worktree of the repository containing `repo_path`, main first.
+pub fn list_worktrees(repo_path: &Path) -> Result<Vec<WorktreeInfo>, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let output = run_git_for_stdout_all(
+        repo_root.as_path(),
+        vec![
+            OsString::from("worktree"),
+            OsString::from("list"),
+            OsString::from("--porcelain"),
+        ],
+        None,
+    )?;
+    Ok(parse_worktree_list(&output))
+}
+
+/// Creates a worktree at `path` on a new branch `branch` started from `HEAD`.
+pub fn create_worktree(
+    repo_path: &Path,
+    path: &Path,
+    branch: &str,
+) -> Result<WorktreeInfo, GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    run_git_for_status(
+        repo_root.as_path(),
+        vec![
+            OsString::from("worktree"),
+            OsString::from("add"),
+            OsString::from("-b"),
+            OsString::from(branch),
+            path.as_os_str().to_os_string(),
+        ],
+        None,
+    )?;
+    let head = run_git_for_stdout(
+        path,
+        vec![OsString::from("rev-parse"), OsString::from("HEAD")],
+        None,
+    )
+    .ok();
+    Ok(WorktreeInfo {
+        path: resolve_repository_root(path)?,
+        head,
+        branch: Some(branch.to_string()),
+        is_main: false,
+        locked: false,
+    })
+}
+
+/// Removes the worktree at `worktree_path` and prunes its administrative files.
+///
+/// Without `force`, git refuses to remove a worktree with uncommitted changes.
+/// The worktree's branch is kept; see [`delete_branch`].
+pub fn remove_worktree(
+    repo_path: &Path,
+    worktree_path: &Path,
+    force: bool,
+) -> Result<(), GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    let mut args = vec![OsString::from("worktree"), OsString::from("remove")];
+    if force {
+        args.push(OsString::from("--force"));
+    }
+    args.push(worktree_path.as_os_str().to_os_string());
+    run_git_for_status(repo_root.as_path(), args, None)?;
+    run_git_for_status(
+        repo_root.as_path(),
+        vec![OsString::from("worktree"), OsString::from("prune")],
+        None,
+    )
+}
+
+/// Deletes branch `name`. Without `force`, git refuses unless it is merged.
+pub fn delete_branch(repo_path: &Path, name: &str, force: bool) -> Result<(), GitToolingError> {
+    ensure_git_repository(repo_path)?;
+    let repo_root = resolve_repository_root(repo_path)?;
+    run_git_for_status(
+        repo_root.as_path(),
+        vec![
+            OsString::from("branch"),
+            OsString::from(if force { "-D" } else { "-d" }),
+            OsString::from(name),
+        ],
+        None,
+    )
+}
+
+/// Merges `branch` into the branch checked out in the main worktree and
+/// returns the resulting `HEAD`.
+///
+/// With `squash`, the branch's changes are recorded as a single commit. On
+/// conflicts the merge is rolled back and [`GitToolingError::MergeConflict`]
+/// lists the conflicting paths.
+pub fn merge_worktree_into_base(
+    repo_path: &Path,
+    branch: &str,
+    squash: bool,
+) -> Result<String, GitToolingError> {
+    let base = main_worktree_path(repo_path)?;
+    let merge_args = if squash {
+        vec![
+            OsString::from("merge"),
+            OsString::from("--squash"),
+            OsString::from(branch),
+        ]
+    } else {
+        vec![
+            OsString::from("merge"),
+            OsString::from("--no-ff"),
+            OsString::from("--no-edit"),
+            OsString::from(branch),
+        ]
+    };
+    if let Err(err) = run_git_for_status(base.as_path(), merge_args, None) {
+        let conflicts = conflicting_paths(base.as_path());
+        // `reset --merge` also undoes a conflicted `--squash`, which `merge --abort` cannot.
+        let _ = run_git_for_status(
+            base.as_path(),
+            vec![OsString::from("reset"), OsString::from("--merge")],
+            None,
+        );
+        return match conflicts {
+            Some(paths) if !paths.is_empty() => Err(GitToolingError::MergeConflict {
+                branch: branch.to_string(),
+                paths,
+            }),
+            _ => Err(err),
+        };
+    }
+
+    if squash && has_staged_changes(base.as_path())? {
+        run_git_for_status(
+            base.as_path(),
+            vec![
+                OsString::from("commit"),
+                OsString::from("--message"),
+                OsString::from(format!("Squash merge branch '{branch}'")),
+            ],
+            None,
+        )?;
+    }
+    run_git_for_stdout(
+        base.as_path(),
+        vec![OsString::from("rev-parse"), OsString::from("HEAD")],
+        None,
+    )
+}
+
+/// Returns linked worktrees that look abandoned: unlocked, not under any of
+/// `active_paths`, free of uncommitted changes, and untouched for `max_age`.
+///
+/// Activity is the newer of the last commit and the last index update.
+pub fn find_stale_worktrees(
+    repo_path: &Path,
+    max_age: Duration,
+    active_paths: &[PathBuf],
+) -> Result<Vec<WorktreeInfo>, GitToolingError> {
+    let now = SystemTime::now();
+    let mut stale = Vec::new();
+    for worktree in list_worktrees(repo_path)? {
+        if worktree.is_main || worktree.locked || !worktree.path.is_dir() {
+            continue;
+        }
+        if active_paths
+            .iter()
+            .any(|active| is_within(active, &worktree.path))
+        {
+            continue;
+        }
+        // Read the activity time before `git status` gets a chance to refresh the index.
+        let last_activity = last_activity(&worktree.path)?;
+        let age = now.duration_since(last_activity).unwrap_or_default();
+        if age < max_age {
+            continue;
+        }
+        if !working_tree_status(&worktree.path)?.entries.is_empty() {
+            continue;
+        }
+        stale.push(worktree);
+    }
+    Ok(stale)
+}
+
+fn main_worktree_path(repo_path: &Path) -> Result<PathBuf, GitToolingError> {
+    let worktrees = list_worktrees(repo_path)?;
+    match worktrees.into_iter().find(|worktree| worktree.is_main) {
+        Some(main) => Ok(main.path),
+        None => resolve_repository_root(repo_path),
+    }
+}
+
+fn conflicting_paths(dir: &Path) -> Option<Vec<PathBuf>> {
+    let output = run_git_for_stdout_all(
+        dir,
+        vec![
+            OsString::from("diff"),
+            OsString::from("--name-only"),
+            OsString::from("--diff-filter=U"),
+            OsString::from("-z"),
+        ],
+        None,
+    )
+    .ok()?;
+    Some(
+        output
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .collect(),
+    )
+}
+
+fn has_staged_changes(dir: &Path) -> Result<bool, GitToolingError> {
+    let output = run_git_for_stdout(
+        dir,
+        vec![
+            OsString::from("diff"),
+            OsString::from("--cached"),
+            OsString::from("--name-only"),
+        ],
+        None,
+    )?;
+    Ok(!output.is_empty())
+}
+
+fn last_activity(worktree_path: &Path) -> Result<SystemTime, GitToolingError> {
+    let committed = run_git_for_stdout(
+        worktree_path,
+        vec![
+            OsString::from("log"),
+            OsString::from("-1"),
+            OsString::from("--format=%ct"),
+        ],
+        None,
+    )
+    .ok()
+    .and_then(|seconds| seconds.parse::<u64>().ok())
+    .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds))
+    .unwrap_or(UNIX_EPOCH);
+
+    let index = run_git_for_stdout(
+        worktree_path,
+        vec![
+            OsString::from("rev-parse"),
+            OsString::from("--path-format=absolute"),
+            OsString::from("--git-path"),
+            OsString::from("index"),
+        ],
+        None,
+    )?;
+    let indexed = std::fs::metadata(index)
+        .and_then(|metadata| metadata.modified())
+        .unwrap_or(UNIX_EPOCH);
+    Ok(committed.max(indexed))
+}
+
+fn is_within(path: &Path, root: &Path) -> bool {
+    if path.starts_with(root) {
+        return true;
+    }
+    match (path.canonicalize(), root.canonicalize()) {
+        (Ok(path), Ok(root)) => path.starts_with(root),
+        _ => false,
+    }
+}
+
+fn parse_worktree_list(output: &str) -> Vec<WorktreeInfo> {
+    let mut worktrees: Vec<WorktreeInfo> = Vec::new();
+    for line in output.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            worktrees.push(WorktreeInfo {
+                path: PathBuf::from(path),
+                head: None,
+                branch: None,
+                // `git worktree list` always reports the main worktree first.
+                is_main: worktrees.is_empty(),
+                locked: false,
+            });
+            continue;
+        }
+        let Some(worktree) = worktrees.last_mut() else {
+            continue;
+        };
+        if let Some(head) = line.strip_prefix("HEAD ") {
+            // A repository without commits reports the all-zero id.
+            if head.chars().any(|c| c != '0') {
+                worktree.head = Some(head.to_string());
+            }
+        } else if let Some(branch) = line.strip_prefix("branch ") {
+            let branch = branch.strip_prefix("refs/heads/").unwrap_or(branch);
+            worktree.branch = Some(branch.to_string());
+        } else if line == "locked" || line.starts_with("locked ") {
+            worktree.locked = true;
+        }
+    }
+    worktrees
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use assert_matches::assert_matches;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    fn run_git_in(repo_path: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .current_dir(repo_path)
+            .args(args)
+            .status()
+            .expect("git command");
+        assert!(status.success(), "git command failed: {args:?}");
+    }
+
+    fn init_test_repo(repo_path: &Path) {
+        run_git_in(repo_path, &["init", "--initial-branch=main"]);
+        run_git_in(repo_path, &["config", "core.autocrlf", "false"]);
+        run_git_in(repo_path, &["config", "user.name", "Tester"]);
+        run_git_in(repo_path, &["config", "user.email", "test@example.com"]);
+        std::fs::write(repo_path.join("README.md"), "hello\n").expect("write readme");
+        run_git_in(repo_path, &["add", "README.md"]);
+        run_git_in(repo_path, &["commit", "-m", "init"]);
+    }
+
+    #[test]
+    fn parses_porcelain_worktree_list() {
+        let output = "worktree /repo\nHEAD 1111111111111111111111111111111111111111\nbranch refs/heads/main\n\nworktree /repo-feature\nHEAD 2222222222222222222222222222222222222222\nbranch refs/heads/feature\nlocked in use\n\nworktree /repo-detached\nHEAD 3333333333333333333333333333333333333333\ndetached\n\n";
+        assert_eq!(
+            parse_worktree_list(output),
+            vec![
+                WorktreeInfo {
+                    path: PathBuf::from("/repo"),
+                    head: Some("1".repeat(40)),
+                    branch: Some("main".to_string()),
+                    is_main: true,
+                    locked: false,
+                },
+                WorktreeInfo {
+                    path: PathBuf::from("/repo-feature"),
+                    head: Some("2".repeat(40)),
+                    branch: Some("feature".to_string()),
+                    is_main: false,
+                    locked: true,
+                },
+                WorktreeInfo {
+                    path: PathBuf::from("/repo-detached"),
+                    head: Some("3".repeat(40)),
+                    branch: None,
+                    is_main: false,
+                    locked: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn squash_merges_worktree_branch_and_removes_it() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path().join("repo");
+        std::fs::create_dir(&repo)?;
+        init_test_repo(&repo);
+
+        let worktree_path = temp.path().join("feature");
+        let worktree = create_worktree(&repo, &worktree_path, "feature")?;
+        std::fs::write(worktree.path.join("feature.txt"), "feature\n")?;
+        run_git_in(&worktree.path, &["add", "feature.txt"]);
+        run_git_in(&worktree.path, &["commit", "-m", "one"]);
+        std::fs::write(worktree.path.join("feature.txt"), "feature two\n")?;
+        run_git_in(&worktree.path, &["commit", "-am", "two"]);
+
+        let head = merge_worktree_into_base(&worktree.path, "feature", true)?;
+        assert_eq!(
+            std::fs::read_to_string(repo.join("feature.txt"))?,
+            "feature two\n"
+        );
+        let subject = run_git_for_stdout(&repo, ["log", "-1", "--format=%s", &head], None)?;
+        assert_eq!(subject, "Squash merge branch 'feature'");
+
+        remove_worktree(&repo, &worktree.path, false)?;
+        delete_branch(&repo, "feature", true)?;
+        assert_eq!(list_worktrees(&repo)?.len(), 1);
+        assert!(!worktree_path.exists());
+        Ok(())
+    }
+
+    #[test]
+    fn merge_conflict_rolls_back() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path().join("repo");
+        std::fs::create_dir(&repo)?;
+        init_test_repo(&repo);
+
+        let worktree = create_worktree(&repo, &temp.path().join("feature"), "feature")?;
+        std::fs::write(worktree.path.join("README.md"), "feature\n")?;
+        run_git_in(&worktree.path, &["commit", "-am", "feature"]);
+        std::fs::write(repo.join("README.md"), "main\n")?;
+        run_git_in(&repo, &["commit", "-am", "main"]);
+
+        let err = merge_worktree_into_base(&repo, "feature", false).unwrap_err();
+        assert_matches!(
+            err,
+            GitToolingError::MergeConflict { ref branch, ref paths }
+                if branch == "feature" && paths == &vec![PathBuf::from("README.md")]
+        );
+        assert_eq!(std::fs::read_to_string(repo.join("README.md"))?, "main\n");
+        assert!(working_tree_status(&repo)?.entries.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn stale_worktrees_skip_active_and_dirty_checkouts() -> Result<(), GitToolingError> {
+        let temp = tempdir()?;
+        let repo = temp.path().join("repo");
+        std::fs::create_dir(&repo)?;
+        init_test_repo(&repo);
+
+        let clean = create_worktree(&repo, &temp.path().join("clean"), "clean")?;
+        let dirty = create_worktree(&repo, &temp.path().join("dirty"), "dirty")?;
+        std::fs::write(dirty.path.join("scratch.txt"), "wip\n")?;
+        let active = create_worktree(&repo, &temp.path().join("active"), "active")?;
+
+        let stale = find_stale_worktrees(&repo, Duration::ZERO, &[active.path.join("src")])?;
+        assert_eq!(stale, vec![clean]);
+
+        let recent = find_stale_worktrees(&repo, Duration::from_secs(24 * 60 * 60), &[])?;
+        assert_eq!(recent, Vec::new());
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::policy::SandboxPolicy;
+use codex_protocol::protocol::expand_write_globs;
 use dunce::canonicalize;
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -65,14 +66,22 @@ pub fn compute_allow_paths(
             &mut add_deny_path,
         );
 
-        if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = policy {
-            for root in writable_roots {
-                add_writable_root(
-                    root.clone(),
-                    policy_cwd,
-                    &mut add_allow_path,
-                    &mut add_deny_path,
-                );
+        if let SandboxPolicy::WorkspaceWrite {
+            writable_roots,
+            allow_write,
+            deny_write,
+            ..
+        } = policy
+        {
+            for root in writable_roots
+                .iter()
+                .cloned()
+                .chain(expand_write_globs(allow_write, policy_cwd))
+            {
+                add_writable_root(root, policy_cwd, &mut add_allow_path, &mut add_deny_path);
+            }
+            for denied in expand_write_globs(deny_write, policy_cwd) {
+                add_deny_path(canonicalize(&denied).unwrap_or(denied));
             }
         }
     }
@@ -113,6 +122,9 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: false,
             exclude_slash_tmp: false,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec![],
         };
 
         let paths = compute_allow_paths(&policy, &command_cwd, &command_cwd, &HashMap::new());
@@ -139,6 +151,9 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: false,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec![],
         };
         let mut env_map = HashMap::new();
         env_map.insert("TEMP".into(), temp_dir.to_string_lossy().to_string());
@@ -166,6 +181,9 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: false,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec![],
         };
 
         let paths = compute_allow_paths(&policy, &command_cwd, &command_cwd, &HashMap::new());
@@ -191,6 +209,9 @@ mod tests {
             network_access: false,
             exclude_tmpdir_env_var: true,
             exclude_slash_tmp: false,
+            allow_write: vec![],
+            deny_write: vec![],
+            network_allowlist: vec![],
         };
 
         let paths = compute_allow_paths(&policy, &command_cwd, &command_cwd, &HashMap::new());
@@ -442,6 +442,9 @@ mod windows_impl {
                 network_access,
                 exclude_tmpdir_env_var: false,
                 exclude_slash_tmp: false,
+                allow_write: vec![],
+                deny_write: vec![],
+                network_allowlist: vec![],
             }
         }
 